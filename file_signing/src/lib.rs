@@ -0,0 +1,186 @@
+//! Ed25519 signatures of files handed to someone else: the reader app's exports and
+//! the block editor's dumps.
+//!
+//! A signature goes in `<file>.sig` beside the file, with the public key that made
+//! it, so whoever receives the file can check nothing in it changed on the way. A
+//! valid signature only says which key signed the file; the recipient compares that
+//! key with the one the sender gave them, otherwise anyone could sign a changed file
+//! with their own.
+//!
+//! Each program keeps one key pair in a file of its own choosing, readable by its
+//! owner only, made the first time a file is signed (see [`load_or_create_key`]).
+
+use std::fmt;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use uid_codec::{bytes_to_hex, parse_hex_lenient};
+
+/// Why a key couldn't be had or a signature doesn't hold
+#[derive(Debug)]
+pub enum SigningError {
+    /// Reading or writing the key or the signed file failed
+    Io(io::Error),
+    /// The key file holds no signing key
+    InvalidKey(PathBuf),
+    /// The signature file couldn't be read
+    SignatureUnreadable(PathBuf, io::Error),
+    /// The signature file isn't one, or its public key is invalid
+    InvalidSignature(PathBuf),
+    /// The file changed after it was signed
+    Mismatch,
+}
+
+impl fmt::Display for SigningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SigningError::Io(e) => write!(f, "{}", e),
+            SigningError::InvalidKey(path) => write!(f, "{} does not hold a signing key", path.display()),
+            SigningError::SignatureUnreadable(path, e) => write!(f, "Can't read {}: {}", path.display(), e),
+            SigningError::InvalidSignature(path) => write!(f, "{} is not a signature", path.display()),
+            SigningError::Mismatch => write!(f, "The file was changed after it was signed"),
+        }
+    }
+}
+
+impl std::error::Error for SigningError {}
+
+impl From<io::Error> for SigningError {
+    fn from(e: io::Error) -> Self {
+        SigningError::Io(e)
+    }
+}
+
+/// Where the signature of a file goes
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut sig_path = path.as_os_str().to_owned();
+    sig_path.push(".sig");
+    PathBuf::from(sig_path)
+}
+
+/// Keys and signatures are written as unbroken hex
+pub fn hex(bytes: &[u8]) -> String {
+    bytes_to_hex(bytes).replace(' ', "")
+}
+
+/// The public half of a key as hex, as it is given to recipients
+pub fn public_key_hex(key: &SigningKey) -> String {
+    hex(key.verifying_key().as_bytes())
+}
+
+/// The key pair kept in `key_path`
+pub fn load_key(key_path: &Path) -> Result<SigningKey, SigningError> {
+    let text = fs::read_to_string(key_path)?;
+    parse_hex_lenient(&text)
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+        .map(|secret| SigningKey::from_bytes(&secret))
+        .ok_or_else(|| SigningError::InvalidKey(key_path.to_path_buf()))
+}
+
+/// The key pair kept in `key_path`, made and saved (readable by its owner only) when
+/// there is none yet. The flag says whether it was just made, so the program can tell
+/// the user to keep a copy.
+pub fn load_or_create_key(key_path: &Path) -> Result<(SigningKey, bool), SigningError> {
+    if key_path.exists() {
+        return load_key(key_path).map(|key| (key, false));
+    }
+
+    let mut secret = [0u8; 32];
+    fs::File::open("/dev/urandom")?.read_exact(&mut secret)?;
+    if let Some(folder) = key_path.parent().filter(|folder| !folder.as_os_str().is_empty()) {
+        fs::create_dir_all(folder)?;
+    }
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(key_path)?
+        .write_all(format!("{}\n", hex(&secret)).as_bytes())?;
+    Ok((SigningKey::from_bytes(&secret), true))
+}
+
+/// Sign a file with `key`, writing its signature beside it
+pub fn sign_file(path: &Path, key: &SigningKey) -> io::Result<PathBuf> {
+    let signature = key.sign(&fs::read(path)?);
+
+    let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let sig_path = signature_path(path);
+    fs::write(&sig_path, format!(
+        "# Ed25519 signature of {}\npublic-key {}\nsignature {}\n",
+        name,
+        public_key_hex(key),
+        hex(&signature.to_bytes()),
+    ))?;
+    Ok(sig_path)
+}
+
+/// Check a file against the signature beside it, gives the public key that signed it as hex
+pub fn verify_file(path: &Path) -> Result<String, SigningError> {
+    let sig_path = signature_path(path);
+    let text = fs::read_to_string(&sig_path).map_err(|e| SigningError::SignatureUnreadable(sig_path.clone(), e))?;
+
+    let mut public_key = None;
+    let mut signature = None;
+    for line in text.lines().map(str::trim).filter(|line| !line.starts_with('#')) {
+        match line.split_once(' ') {
+            Some(("public-key", value)) => public_key = parse_hex_lenient(value)
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok()),
+            Some(("signature", value)) => signature = parse_hex_lenient(value)
+                .and_then(|bytes| <[u8; 64]>::try_from(bytes.as_slice()).ok()),
+            _ => {},
+        }
+    }
+
+    let (public_key, signature) = match (public_key, signature) {
+        (Some(public_key), Some(signature)) => (public_key, signature),
+        _ => return Err(SigningError::InvalidSignature(sig_path)),
+    };
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|_| SigningError::InvalidSignature(sig_path))?;
+
+    verifying_key.verify_strict(&fs::read(path)?, &Signature::from_bytes(&signature))
+        .map_err(|_| SigningError::Mismatch)?;
+    Ok(hex(&public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("file_signing_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn signs_and_verifies() {
+        let dir = scratch_dir("roundtrip");
+        let key_path = dir.join("keys").join("signing.key");
+        let (key, created) = load_or_create_key(&key_path).unwrap();
+        assert!(created);
+        assert_eq!(fs::metadata(&key_path).unwrap().permissions().mode() & 0o777, 0o600);
+        let (again, created) = load_or_create_key(&key_path).unwrap();
+        assert!(!created);
+        assert_eq!(public_key_hex(&again), public_key_hex(&key));
+
+        let file = dir.join("dump.mfd");
+        fs::write(&file, b"block data").unwrap();
+        assert_eq!(sign_file(&file, &key).unwrap(), dir.join("dump.mfd.sig"));
+        assert_eq!(verify_file(&file).unwrap(), public_key_hex(&key));
+
+        fs::write(&file, b"block dat4").unwrap();
+        assert!(matches!(verify_file(&file), Err(SigningError::Mismatch)));
+        fs::write(signature_path(&file), "public-key 00\n").unwrap();
+        assert!(matches!(verify_file(&file), Err(SigningError::InvalidSignature(_))));
+
+        fs::write(&key_path, "not a key\n").unwrap();
+        assert!(matches!(load_key(&key_path), Err(SigningError::InvalidKey(_))));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,33 @@
+// The reader answers every register read with the next fuzz byte, so the card can
+// send any frame at all: request, anticollision, select and a block read must turn
+// it into an error, never a panic.
+#![no_main]
+
+use std::error::Error;
+
+use libfuzzer_sys::fuzz_target;
+use mifare_rc522::*;
+
+struct FuzzedReader<'a> {
+    answers: std::slice::Iter<'a, u8>,
+}
+
+impl Transport for FuzzedReader<'_> {
+    fn transfer(&mut self, rx: &mut [u8], _tx: &[u8]) -> Result<(), Box<dyn Error>> {
+        // Out of input every interrupt and error bit reads as set, which ends any wait
+        for byte in rx.iter_mut() {
+            *byte = self.answers.next().copied().unwrap_or(0xFF);
+        }
+        Ok(())
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut spi = FuzzedReader { answers: data.iter() };
+
+    let _ = mfrc522_request(&mut spi, PICC_REQIDL);
+    if let Ok((_, uid)) = mfrc522_anticoll(&mut spi) {
+        let _ = mfrc522_select_tag(&mut spi, &uid);
+    }
+    let _ = mfrc522_read(&mut spi, 4);
+});
@@ -0,0 +1,10 @@
+// Any file in the dumps directory is shown in the TUI, whatever its size or content.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use mifare_tui::dumps::{render_blocks, sector_map};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = render_blocks(data);
+    let _ = sector_map(data);
+});
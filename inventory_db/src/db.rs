@@ -0,0 +1,1942 @@
+// db.rs
+//
+// inventory.db: the schema and its migrations, and every query of the programs that
+// open it. Item edits, and the tags added to an item or deactivated, are written to the
+// operation log with the row, in one transaction, so station sync sees them (see oplog.rs).
+use rusqlite::{backup::Progress, params, Connection, DatabaseName, OptionalExtension, Result, Row};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::kits;
+use crate::model::{
+    CardDump, InventoryItem, ItemTag, Kit, KitComponent, NewItem, PoLine, PoStatus, Product, ProductStock, PurchaseOrder,
+    PresenceChange, PresenceEvent, ReviewEntry, Shortage, StockMode, Supplier, TrashedItem, Unit, UnitStatus, UnknownTag,
+    create_inventory_item, generate_timestamp,
+};
+use crate::oplog::{self, Change, Operation};
+
+/// Move everything in the write-ahead log of the database at `db_path` into the file
+/// itself, so a copy of inventory.db alone holds every change
+pub fn checkpoint(db_path: &str) -> Result<()> {
+    if !Path::new(db_path).exists() {
+        return Ok(());
+    }
+    let conn = Connection::open(db_path)?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+}
+
+/// A row of an import that couldn't be saved
+#[derive(Debug)]
+pub struct ImportRowError {
+    /// Row of the items counting from 1, None for the tag and dump tables and for the
+    /// transaction itself
+    pub row: Option<usize>,
+    pub error: rusqlite::Error,
+}
+
+impl ImportRowError {
+    fn file(error: rusqlite::Error) -> Self {
+        ImportRowError { row: None, error }
+    }
+}
+
+const TRASH_COLUMNS: &str =
+    "id, tag_id, name, description, quantity, location, category, last_updated, created_at, deleted_at, expiry_date";
+
+// this reads a row selected with TRASH_COLUMNS
+fn trashed_from_row(row: &Row) -> Result<TrashedItem> {
+    Ok(TrashedItem {
+        id: row.get(0)?,
+        item: InventoryItem {
+            tag_id: row.get(1)?,
+            name: row.get(2)?,
+            description: row.get(3)?,
+            quantity: row.get(4)?,
+            location: row.get(5)?,
+            category: row.get(6)?,
+            last_updated: row.get(7)?,
+            created_at: row.get(8)?,
+            expiry_date: row.get(10)?,
+        },
+        deleted_at: row.get(9)?,
+    })
+}
+
+// this reads a row of unknown_tags
+fn unknown_tag_from_row(row: &Row) -> Result<UnknownTag> {
+    Ok(UnknownTag {
+        tag_id: row.get(0)?,
+        scans: row.get(1)?,
+        first_seen: row.get(2)?,
+        last_seen: row.get(3)?,
+        ignored: row.get(4)?,
+    })
+}
+
+const DUMP_COLUMNS: &str = "id, item_tag, taken_at, uid, source, data";
+
+// this reads a row selected with DUMP_COLUMNS
+fn card_dump_from_row(row: &Row) -> Result<CardDump> {
+    Ok(CardDump {
+        id: row.get(0)?,
+        item_tag: row.get(1)?,
+        taken_at: row.get(2)?,
+        uid: row.get(3)?,
+        source: row.get(4)?,
+        data: row.get(5)?,
+    })
+}
+
+// this reads a row of presence_events
+fn presence_event_from_row(row: &Row) -> Result<PresenceEvent> {
+    let change: String = row.get(3)?;
+    Ok(PresenceEvent {
+        id: row.get(0)?,
+        item_tag: row.get(1)?,
+        name: row.get(2)?,
+        change: PresenceChange::from_key(&change).unwrap_or(PresenceChange::Removed),
+        at: row.get(4)?,
+    })
+}
+
+// An item as exported, with who it is bought from. Imports read it back as a plain
+// item, the supplier is left out.
+#[derive(Serialize)]
+struct ExportedItem<'a> {
+    #[serde(flatten)]
+    item: &'a InventoryItem,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    supplier: Option<&'a Supplier>,
+}
+
+// A JSON export: the items, and every tag each one has or had and the card dumps kept
+// with them in tables of their own
+#[derive(Serialize)]
+struct ExportFile<'a> {
+    items: Vec<ExportedItem<'a>>,
+    item_tags: Vec<ItemTag>,
+    card_dumps: Vec<CardDump>,
+}
+
+const SUPPLIER_COLUMNS: &str = "id, name, contact, email, phone, created_at";
+
+// this reads a row selected with SUPPLIER_COLUMNS
+fn supplier_from_row(row: &Row) -> Result<Supplier> {
+    Ok(Supplier {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        contact: row.get(2)?,
+        email: row.get(3)?,
+        phone: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+const PRODUCT_COLUMNS: &str = "id, name, description, category, created_at";
+const UNIT_COLUMNS: &str = "tag_id, product_id, serial, status, location, last_updated, created_at";
+
+// this reads a row selected with PRODUCT_COLUMNS
+fn product_from_row(row: &Row) -> Result<Product> {
+    Ok(Product {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        category: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+// this reads a row selected with UNIT_COLUMNS, an unknown status reads as in stock
+fn unit_from_row(row: &Row) -> Result<Unit> {
+    let status: String = row.get(3)?;
+    Ok(Unit {
+        tag_id: row.get(0)?,
+        product_id: row.get(1)?,
+        serial: row.get(2)?,
+        status: UnitStatus::from_key(&status).unwrap_or_default(),
+        location: row.get(4)?,
+        last_updated: row.get(5)?,
+        created_at: row.get(6)?,
+    })
+}
+
+// this reads an order without its lines
+fn purchase_order_from_row(row: &Row) -> Result<PurchaseOrder> {
+    let status: String = row.get(3)?;
+    Ok(PurchaseOrder {
+        id: row.get(0)?,
+        number: row.get(1)?,
+        supplier_id: row.get(2)?,
+        status: PoStatus::from_key(&status).unwrap_or_default(),
+        created_at: row.get(4)?,
+        lines: Vec::new(),
+    })
+}
+
+// Database management functions
+pub struct InventoryDB {
+    conn: Connection,
+}
+
+impl InventoryDB {
+    // Initialize the database
+    pub fn new(db_path: &str) -> Result<Self> {
+        let create_new = !Path::new(db_path).exists();
+        let conn = Connection::open(db_path)?;
+        // Writes go to a write-ahead log first, a crash mid-write can't corrupt the file
+        conn.query_row("PRAGMA journal_mode=WAL", [], |_| Ok(()))?;
+        
+        let db = InventoryDB { conn };
+        
+        // Create tables if this is a new database
+        if create_new {
+            db.create_tables()?;
+        }
+        // databases from before station sync or the trash get their tables on first open
+        db.create_sync_tables()?;
+        db.create_trash_table()?;
+        db.add_expiry_columns()?;
+        db.create_unit_tables()?;
+        db.create_kit_tables()?;
+        db.create_purchasing_tables()?;
+        db.create_triage_table()?;
+        db.create_item_tags_table()?;
+        db.create_presence_table()?;
+        db.create_dumps_table()?;
+        
+        Ok(db)
+    }
+    
+    /// Write everything in the write-ahead log back to the database file, so nothing
+    /// is left only in the -wal file when the process stops
+    pub fn flush(&self) -> Result<()> {
+        self.conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+    }
+    
+    // Create the necessary tables
+    fn create_tables(&self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS inventory (
+                tag_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                quantity INTEGER NOT NULL DEFAULT 0,
+                location TEXT,
+                category TEXT,
+                last_updated TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                expiry_date TEXT
+            )",
+            [],
+        )?;
+        
+        Ok(())
+    }
+    
+    // Operation log and station id used by station sync (see oplog.rs)
+    fn create_sync_tables(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sync_ops (
+                station TEXT NOT NULL,
+                seq INTEGER NOT NULL,
+                clock INTEGER NOT NULL,
+                time TEXT NOT NULL,
+                tag_id TEXT NOT NULL,
+                change TEXT NOT NULL,
+                PRIMARY KEY (station, seq)
+            );
+            CREATE INDEX IF NOT EXISTS sync_ops_tag ON sync_ops (tag_id);
+            CREATE TABLE IF NOT EXISTS sync_meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS integration_cursors (
+                name TEXT PRIMARY KEY,
+                seq INTEGER NOT NULL
+            );"
+        )
+    }
+    
+    // Deleted items, kept until they are restored or purged. An id of their own since a
+    // tag can be deleted, used again and deleted again.
+    fn create_trash_table(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS inventory_trash (
+                id INTEGER PRIMARY KEY,
+                tag_id TEXT NOT NULL,
+                name TEXT NOT NULL,
+                description TEXT,
+                quantity INTEGER NOT NULL DEFAULT 0,
+                location TEXT,
+                category TEXT,
+                last_updated TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                deleted_at TEXT NOT NULL,
+                expiry_date TEXT
+            );
+            CREATE INDEX IF NOT EXISTS inventory_trash_deleted ON inventory_trash (deleted_at);"
+        )
+    }
+    
+    // Products tracked per unit and their units, one tag each (see inventory/units.rs of
+    // the FLTK app)
+    fn create_unit_tables(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS products (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT,
+                category TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS units (
+                tag_id TEXT PRIMARY KEY,
+                product_id INTEGER NOT NULL REFERENCES products (id),
+                serial TEXT,
+                status TEXT NOT NULL DEFAULT 'in_stock',
+                location TEXT,
+                last_updated TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS units_product ON units (product_id);"
+        )
+    }
+    
+    // Kits and the items each one is made of (see inventory/kits.rs of the FLTK app)
+    fn create_kit_tables(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS kits (
+                tag_id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS kit_components (
+                kit_tag TEXT NOT NULL REFERENCES kits (tag_id),
+                item_tag TEXT NOT NULL,
+                quantity INTEGER NOT NULL,
+                PRIMARY KEY (kit_tag, item_tag)
+            );"
+        )
+    }
+    
+    // Suppliers, the supplier of each item, purchase orders and what was received
+    // against them (see inventory/purchasing.rs of the FLTK app)
+    fn create_purchasing_tables(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS suppliers (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                contact TEXT,
+                email TEXT,
+                phone TEXT,
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS item_suppliers (
+                tag_id TEXT PRIMARY KEY,
+                supplier_id INTEGER NOT NULL REFERENCES suppliers (id)
+            );
+            CREATE TABLE IF NOT EXISTS purchase_orders (
+                id INTEGER PRIMARY KEY,
+                number TEXT NOT NULL UNIQUE,
+                supplier_id INTEGER NOT NULL REFERENCES suppliers (id),
+                status TEXT NOT NULL DEFAULT 'open',
+                created_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS po_lines (
+                po_id INTEGER NOT NULL REFERENCES purchase_orders (id),
+                tag_id TEXT NOT NULL,
+                ordered INTEGER NOT NULL,
+                received INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (po_id, tag_id)
+            );
+            CREATE TABLE IF NOT EXISTS po_receipts (
+                id INTEGER PRIMARY KEY,
+                po_id INTEGER NOT NULL REFERENCES purchase_orders (id),
+                tag_id TEXT NOT NULL,
+                quantity INTEGER NOT NULL,
+                received_at TEXT NOT NULL
+            );"
+        )
+    }
+    
+    // Scanned tags not in the inventory, kept for triage instead of asking at each scan.
+    // Local to this station, they aren't synced.
+    fn create_triage_table(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS unknown_tags (
+                tag_id TEXT PRIMARY KEY,
+                scans INTEGER NOT NULL DEFAULT 0,
+                first_seen TEXT NOT NULL,
+                last_seen TEXT NOT NULL,
+                ignored INTEGER NOT NULL DEFAULT 0
+            );"
+        )
+    }
+    
+    // Tags an item has besides the one it is keyed by, and tags taken off an item. A
+    // deactivated tag keeps its row as history, so a tag can appear more than once.
+    fn create_item_tags_table(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS item_tags (
+                id INTEGER PRIMARY KEY,
+                tag_id TEXT NOT NULL,
+                item_tag TEXT NOT NULL,
+                added_at TEXT NOT NULL,
+                removed_at TEXT,
+                note TEXT
+            );
+            CREATE INDEX IF NOT EXISTS item_tags_tag ON item_tags (tag_id);
+            CREATE INDEX IF NOT EXISTS item_tags_item ON item_tags (item_tag);"
+        )
+    }
+    
+    // Removals and returns seen by presence monitoring (see inventory/presence.rs of the
+    // FLTK app). The item's name is kept with the event, the item may be deleted later.
+    fn create_presence_table(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS presence_events (
+                id INTEGER PRIMARY KEY,
+                item_tag TEXT NOT NULL,
+                name TEXT NOT NULL,
+                change TEXT NOT NULL,
+                at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS presence_events_item ON presence_events (item_tag);"
+        )
+    }
+    
+    // Card dumps kept with items (see inventory/dumps.rs of the FLTK app), keyed by the
+    // item and when the dump was attached. They live in the database so backups and
+    // exports carry them.
+    fn create_dumps_table(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS card_dumps (
+                id INTEGER PRIMARY KEY,
+                item_tag TEXT NOT NULL,
+                taken_at TEXT NOT NULL,
+                uid TEXT NOT NULL,
+                source TEXT,
+                data BLOB NOT NULL,
+                UNIQUE (item_tag, taken_at)
+            );"
+        )
+    }
+    
+    // Databases from before expiry dates get the column in both item tables
+    fn add_expiry_columns(&self) -> Result<()> {
+        for table in ["inventory", "inventory_trash"] {
+            let mut stmt = self.conn.prepare(&format!("PRAGMA table_info({})", table))?;
+            let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<Result<Vec<_>>>()?;
+            if !columns.iter().any(|column| column == "expiry_date") {
+                self.conn.execute(&format!("ALTER TABLE {} ADD COLUMN expiry_date TEXT", table), [])?;
+            }
+        }
+        Ok(())
+    }
+    
+    // Add or update an item
+    pub fn save_item(&self, item: &InventoryItem) -> Result<()> {
+        self.save_item_with_mode(item, None)
+    }
+    
+    // Add or update an item, logging a quantity change as a receipt, issue or count
+    pub fn save_item_with_mode(&self, item: &InventoryItem, mode: Option<StockMode>) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.save_logged(item, mode)?;
+        tx.commit()
+    }
+    
+    // Write the row and log what changed, the caller holds the transaction
+    fn save_logged(&self, item: &InventoryItem, mode: Option<StockMode>) -> Result<()> {
+        let existing = self.get_item(&item.tag_id)?;
+        self.write_item(item)?;
+        
+        let fields_changed = existing.as_ref().is_none_or(|old| {
+            old.name != item.name || old.description != item.description
+                || old.location != item.location || old.category != item.category
+                || old.expiry_date != item.expiry_date
+        });
+        if fields_changed {
+            self.record(&item.tag_id, Change::Upsert { item: item.clone() })?;
+        }
+        let delta = item.quantity - existing.map_or(0, |old| old.quantity);
+        if delta != 0 {
+            self.record(&item.tag_id, Change::Adjust { delta, mode })?;
+        }
+        
+        Ok(())
+    }
+    
+    // Write the row without logging it, used when replaying operations
+    fn write_item(&self, item: &InventoryItem) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO inventory (
+                tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                item.tag_id,
+                item.name,
+                item.description,
+                item.quantity,
+                item.location,
+                item.category,
+                item.last_updated,
+                item.created_at,
+                item.expiry_date
+            ],
+        )?;
+        
+        Ok(())
+    }
+    
+    // Retrieve an item by tag ID
+    pub fn get_item(&self, tag_id: &str) -> Result<Option<InventoryItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date 
+             FROM inventory WHERE tag_id = ?"
+        )?;
+        
+        let item_iter = stmt.query_map(params![tag_id], |row| {
+            Ok(InventoryItem {
+                tag_id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                quantity: row.get(3)?,
+                location: row.get(4)?,
+                category: row.get(5)?,
+                last_updated: row.get(6)?,
+                created_at: row.get(7)?,
+                expiry_date: row.get(8)?,
+            })
+        })?;
+        
+        let item = item_iter.into_iter().next().transpose()?;
+        Ok(item)
+    }
+    
+    // Get all inventory items
+    pub fn get_all_items(&self) -> Result<Vec<InventoryItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date 
+             FROM inventory ORDER BY name"
+        )?;
+        
+        let item_iter = stmt.query_map([], |row| {
+            Ok(InventoryItem {
+                tag_id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                quantity: row.get(3)?,
+                location: row.get(4)?,
+                category: row.get(5)?,
+                last_updated: row.get(6)?,
+                created_at: row.get(7)?,
+                expiry_date: row.get(8)?,
+            })
+        })?;
+        
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item?);
+        }
+        
+        Ok(items)
+    }
+    
+    // Delete the item a tag stands for, it goes to the trash until it is restored or
+    // purged. Any of its active tags finds it, and so does its own tag after it was
+    // deactivated, the item is still keyed by it. Returns the tag the item was keyed
+    // by, None when there was no item.
+    pub fn delete_item(&self, tag_id: &str) -> Result<Option<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let item_tag = self.resolve_tag(tag_id)?.unwrap_or_else(|| tag_id.to_string());
+        let deleted = self.move_to_trash(&item_tag)?;
+        if deleted {
+            self.record(&item_tag, Change::Delete)?;
+        }
+        tx.commit()?;
+        
+        Ok(deleted.then_some(item_tag))
+    }
+    
+    // Move the item's row to the trash without logging it, the caller holds the transaction
+    fn move_to_trash(&self, tag_id: &str) -> Result<bool> {
+        self.conn.execute(
+            "INSERT INTO inventory_trash (
+                tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date, deleted_at
+            ) SELECT tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date, ?
+              FROM inventory WHERE tag_id = ?",
+            params![generate_timestamp(), tag_id],
+        )?;
+        let affected = self.conn.execute("DELETE FROM inventory WHERE tag_id = ?", params![tag_id])?;
+        Ok(affected > 0)
+    }
+    
+    // Deleted items, the most recently deleted first
+    pub fn trashed_items(&self) -> Result<Vec<TrashedItem>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM inventory_trash ORDER BY deleted_at DESC, id DESC", TRASH_COLUMNS
+        ))?;
+        let trash_iter = stmt.query_map([], trashed_from_row)?;
+        trash_iter.collect()
+    }
+    
+    // Put a deleted item back, logged like a new item so other stations get it back too.
+    // Returns false if it is no longer in the trash or its tag is in use again.
+    pub fn restore_trashed(&self, id: i64) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let trashed = self.conn.query_row(
+            &format!("SELECT {} FROM inventory_trash WHERE id = ?", TRASH_COLUMNS),
+            params![id],
+            trashed_from_row,
+        ).optional()?;
+        let trashed = match trashed {
+            Some(trashed) => trashed,
+            None => return Ok(false),
+        };
+        if self.get_item(&trashed.item.tag_id)?.is_some() {
+            return Ok(false);
+        }
+        self.save_logged(&trashed.item, None)?;
+        self.conn.execute("DELETE FROM inventory_trash WHERE id = ?", params![id])?;
+        tx.commit()?;
+        
+        Ok(true)
+    }
+    
+    // Delete one item from the trash for good
+    pub fn delete_trashed(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM inventory_trash WHERE id = ?", params![id])?;
+        Ok(affected > 0)
+    }
+    
+    // Delete the items that went to the trash before `deleted_before` (a timestamp in the
+    // format of generate_timestamp) for good, returns how many
+    pub fn purge_trash(&self, deleted_before: &str) -> Result<usize> {
+        self.conn.execute("DELETE FROM inventory_trash WHERE deleted_at < ?", params![deleted_before])
+    }
+    
+    // Delete everything in the trash for good
+    pub fn empty_trash(&self) -> Result<usize> {
+        self.conn.execute("DELETE FROM inventory_trash", [])
+    }
+    
+    // Add a product tracked per unit, returns its id
+    pub fn add_product(&self, name: &str, description: Option<&str>, category: Option<&str>) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO products (name, description, category, created_at) VALUES (?, ?, ?, ?)",
+            params![name, description, category, generate_timestamp()],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    // Change a product's name, description or category
+    pub fn update_product(&self, product: &Product) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE products SET name = ?, description = ?, category = ? WHERE id = ?",
+            params![product.name, product.description, product.category, product.id],
+        )?;
+        Ok(affected > 0)
+    }
+    
+    // Delete a product, only while it has no units
+    pub fn delete_product(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM products WHERE id = ? AND NOT EXISTS (SELECT 1 FROM units WHERE product_id = ?)",
+            params![id, id],
+        )?;
+        Ok(affected > 0)
+    }
+    
+    pub fn get_product(&self, id: i64) -> Result<Option<Product>> {
+        self.conn.query_row(
+            &format!("SELECT {} FROM products WHERE id = ?", PRODUCT_COLUMNS),
+            params![id],
+            product_from_row,
+        ).optional()
+    }
+    
+    // Every product by name, with its units counted by status
+    pub fn product_stock(&self) -> Result<Vec<ProductStock>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT p.id, p.name, p.description, p.category, p.created_at,
+                    COUNT(CASE WHEN u.status = 'in_stock' THEN 1 END),
+                    COUNT(CASE WHEN u.status = 'issued' THEN 1 END),
+                    COUNT(CASE WHEN u.status = 'retired' THEN 1 END)
+             FROM products p LEFT JOIN units u ON u.product_id = p.id
+             GROUP BY p.id ORDER BY p.name"
+        )?;
+        let stock_iter = stmt.query_map([], |row| {
+            Ok(ProductStock {
+                product: product_from_row(row)?,
+                in_stock: row.get(5)?,
+                issued: row.get(6)?,
+                retired: row.get(7)?,
+            })
+        })?;
+        stock_iter.collect()
+    }
+    
+    // Add or replace a unit, the tag is its key
+    pub fn save_unit(&self, unit: &Unit) -> Result<()> {
+        self.conn.execute(
+            &format!("INSERT OR REPLACE INTO units ({}) VALUES (?, ?, ?, ?, ?, ?, ?)", UNIT_COLUMNS),
+            params![
+                unit.tag_id,
+                unit.product_id,
+                unit.serial,
+                unit.status.key(),
+                unit.location,
+                unit.last_updated,
+                unit.created_at
+            ],
+        )?;
+        Ok(())
+    }
+    
+    // The unit a tag is on, None for tags that aren't units
+    pub fn get_unit(&self, tag_id: &str) -> Result<Option<Unit>> {
+        self.conn.query_row(
+            &format!("SELECT {} FROM units WHERE tag_id = ?", UNIT_COLUMNS),
+            params![tag_id],
+            unit_from_row,
+        ).optional()
+    }
+    
+    // The units of a product by serial number
+    pub fn product_units(&self, product_id: i64) -> Result<Vec<Unit>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM units WHERE product_id = ? ORDER BY COALESCE(serial, tag_id)", UNIT_COLUMNS
+        ))?;
+        let unit_iter = stmt.query_map(params![product_id], unit_from_row)?;
+        unit_iter.collect()
+    }
+    
+    // Move a unit to another status
+    pub fn set_unit_status(&self, tag_id: &str, status: UnitStatus) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE units SET status = ?, last_updated = ? WHERE tag_id = ?",
+            params![status.key(), generate_timestamp(), tag_id],
+        )?;
+        Ok(affected > 0)
+    }
+    
+    // Remove a unit registered by mistake, a unit that is gone is retired instead
+    pub fn delete_unit(&self, tag_id: &str) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM units WHERE tag_id = ?", params![tag_id])?;
+        Ok(affected > 0)
+    }
+    
+    // Add or replace a kit with its components
+    pub fn save_kit(&self, kit: &Kit) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO kits (tag_id, name, created_at) VALUES (?, ?, ?)",
+            params![kit.tag_id, kit.name, kit.created_at],
+        )?;
+        self.conn.execute("DELETE FROM kit_components WHERE kit_tag = ?", params![kit.tag_id])?;
+        for component in &kit.components {
+            self.conn.execute(
+                "INSERT INTO kit_components (kit_tag, item_tag, quantity) VALUES (?, ?, ?)",
+                params![kit.tag_id, component.tag_id, component.quantity],
+            )?;
+        }
+        tx.commit()
+    }
+    
+    // The kit on a tag, None for tags that aren't kits
+    pub fn get_kit(&self, tag_id: &str) -> Result<Option<Kit>> {
+        let kit = self.conn.query_row(
+            "SELECT tag_id, name, created_at FROM kits WHERE tag_id = ?",
+            params![tag_id],
+            |row| Ok(Kit { tag_id: row.get(0)?, name: row.get(1)?, components: Vec::new(), created_at: row.get(2)? }),
+        ).optional()?;
+        kit.map(|kit| self.with_components(kit)).transpose()
+    }
+    
+    // Every kit by name
+    pub fn kits(&self) -> Result<Vec<Kit>> {
+        let mut stmt = self.conn.prepare("SELECT tag_id, name, created_at FROM kits ORDER BY name")?;
+        let kits = stmt.query_map([], |row| {
+            Ok(Kit { tag_id: row.get(0)?, name: row.get(1)?, components: Vec::new(), created_at: row.get(2)? })
+        })?.collect::<Result<Vec<_>>>()?;
+        kits.into_iter().map(|kit| self.with_components(kit)).collect()
+    }
+    
+    // this fills in a kit's components
+    fn with_components(&self, mut kit: Kit) -> Result<Kit> {
+        let mut stmt = self.conn.prepare(
+            "SELECT item_tag, quantity FROM kit_components WHERE kit_tag = ? ORDER BY item_tag"
+        )?;
+        let components = stmt.query_map(params![kit.tag_id], |row| {
+            Ok(KitComponent { tag_id: row.get(0)?, quantity: row.get(1)? })
+        })?;
+        kit.components = components.collect::<Result<Vec<_>>>()?;
+        Ok(kit)
+    }
+    
+    pub fn delete_kit(&self, tag_id: &str) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        self.conn.execute("DELETE FROM kit_components WHERE kit_tag = ?", params![tag_id])?;
+        let affected = self.conn.execute("DELETE FROM kits WHERE tag_id = ?", params![tag_id])?;
+        tx.commit()?;
+        Ok(affected > 0)
+    }
+    
+    // Book `count` kits in or out, every component in one transaction. Nothing is booked
+    // when a component is short (issuing) or missing, the shortages are returned instead.
+    pub fn book_kit(&self, kit: &Kit, mode: StockMode, count: i32) -> Result<Vec<Shortage>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let items = kits::component_items(self, kit)?;
+        let shortages = kits::shortages(kit, &items, mode, count);
+        if !shortages.is_empty() {
+            return Ok(shortages);
+        }
+        
+        let now = generate_timestamp();
+        for component in &kit.components {
+            let delta = kits::component_delta(component, mode, count);
+            if delta == 0 {
+                continue;
+            }
+            self.conn.execute(
+                "UPDATE inventory SET quantity = quantity + ?, last_updated = ? WHERE tag_id = ?",
+                params![delta, now, component.tag_id],
+            )?;
+            self.record(&component.tag_id, Change::Adjust { delta, mode: Some(mode) })?;
+        }
+        tx.commit()?;
+        
+        Ok(Vec::new())
+    }
+    
+    // Book a reviewed list of scans in one transaction, every entry or none. Unknown
+    // tags become new items under the name the operator gave them.
+    // Returns QueryReturnedNoRows, booking nothing, when an entry issues from an item
+    // that is gone.
+    pub fn commit_review(&self, entries: &[ReviewEntry]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for entry in entries {
+            let item = match self.get_item(&entry.tag_id)? {
+                Some(mut item) => {
+                    item.quantity = entry.mode.booked_quantity(item.quantity, entry.quantity);
+                    item.last_updated = generate_timestamp();
+                    item
+                },
+                // an item deleted since the scan has nothing left to issue
+                None if entry.mode == StockMode::Issue => return Err(rusqlite::Error::QueryReturnedNoRows),
+                None => create_inventory_item(
+                    &entry.tag_id,
+                    entry.name.as_deref().unwrap_or(&entry.tag_id),
+                    None,
+                    entry.mode.booked_quantity(0, entry.quantity),
+                    None,
+                    None,
+                ),
+            };
+            self.save_logged(&item, Some(entry.mode))?;
+        }
+        tx.commit()
+    }
+    
+    // Count a scan of a tag that isn't in the inventory, returns its triage entry
+    pub fn record_unknown_scan(&self, tag_id: &str) -> Result<UnknownTag> {
+        let now = generate_timestamp();
+        self.conn.execute(
+            "INSERT INTO unknown_tags (tag_id, scans, first_seen, last_seen) VALUES (?1, 1, ?2, ?2)
+             ON CONFLICT (tag_id) DO UPDATE SET scans = scans + 1, last_seen = excluded.last_seen",
+            params![tag_id, now],
+        )?;
+        self.conn.query_row(
+            "SELECT tag_id, scans, first_seen, last_seen, ignored FROM unknown_tags WHERE tag_id = ?",
+            params![tag_id],
+            unknown_tag_from_row,
+        )
+    }
+    
+    // Unknown tags to triage, the most recently scanned first. Tags added to the
+    // inventory since are left out.
+    pub fn unknown_tags(&self, include_ignored: bool) -> Result<Vec<UnknownTag>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, scans, first_seen, last_seen, ignored FROM unknown_tags
+             WHERE (?1 OR ignored = 0) AND tag_id NOT IN (SELECT tag_id FROM inventory)
+             ORDER BY last_seen DESC, tag_id"
+        )?;
+        let tags = stmt.query_map(params![include_ignored], unknown_tag_from_row)?;
+        tags.collect()
+    }
+    
+    // Stop listing unknown tags, or list them again
+    pub fn set_unknown_ignored(&self, tag_ids: &[String], ignored: bool) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut affected = 0;
+        for tag_id in tag_ids {
+            affected += self.conn.execute(
+                "UPDATE unknown_tags SET ignored = ? WHERE tag_id = ?",
+                params![ignored, tag_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(affected)
+    }
+    
+    // Add items for triaged tags in one transaction and take the tags off the list
+    pub fn add_triaged_items(&self, items: &[InventoryItem]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for item in items {
+            self.save_logged(item, None)?;
+            self.conn.execute("DELETE FROM unknown_tags WHERE tag_id = ?", params![item.tag_id])?;
+        }
+        tx.commit()
+    }
+    
+    // The item a scanned tag stands for: a tag associated with an item, or an item's
+    // own tag unless it was deactivated. None for tags of no item.
+    pub fn resolve_tag(&self, tag_id: &str) -> Result<Option<String>> {
+        // the newest association wins, an older one may belong to a deleted item
+        let associated: Option<String> = self.conn.query_row(
+            "SELECT item_tag FROM item_tags WHERE tag_id = ? AND removed_at IS NULL ORDER BY id DESC LIMIT 1",
+            params![tag_id],
+            |row| row.get(0),
+        ).optional()?;
+        if let Some(item_tag) = associated {
+            return Ok(self.get_item(&item_tag)?.map(|item| item.tag_id));
+        }
+        
+        let deactivated: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM item_tags WHERE tag_id = ?1 AND item_tag = ?1 AND removed_at IS NOT NULL)",
+            params![tag_id],
+            |row| row.get(0),
+        )?;
+        if deactivated {
+            return Ok(None);
+        }
+        Ok(self.get_item(tag_id)?.map(|item| item.tag_id))
+    }
+    
+    // The item a scanned tag stands for, see resolve_tag
+    pub fn get_item_by_any_tag(&self, tag_id: &str) -> Result<Option<InventoryItem>> {
+        match self.resolve_tag(tag_id)? {
+            Some(item_tag) => self.get_item(&item_tag),
+            None => Ok(None),
+        }
+    }
+    
+    // Every tag an item has had, its own tag first, then the others in the order they
+    // were added. Deactivated tags are kept with when and why.
+    pub fn tags_of_item(&self, item: &InventoryItem) -> Result<Vec<ItemTag>> {
+        let own_removal: Option<(String, Option<String>)> = self.conn.query_row(
+            "SELECT removed_at, note FROM item_tags
+             WHERE tag_id = ?1 AND item_tag = ?1 AND removed_at IS NOT NULL ORDER BY id DESC LIMIT 1",
+            params![item.tag_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        ).optional()?;
+        let mut tags = vec![ItemTag {
+            id: 0,
+            tag_id: item.tag_id.clone(),
+            item_tag: item.tag_id.clone(),
+            added_at: item.created_at.clone(),
+            removed_at: own_removal.as_ref().map(|(removed_at, _)| removed_at.clone()),
+            note: own_removal.and_then(|(_, note)| note),
+        }];
+        
+        let mut stmt = self.conn.prepare(
+            "SELECT id, tag_id, item_tag, added_at, removed_at, note FROM item_tags
+             WHERE item_tag = ?1 AND tag_id != ?1 ORDER BY id"
+        )?;
+        let others = stmt.query_map(params![item.tag_id], |row| {
+            Ok(ItemTag {
+                id: row.get(0)?,
+                tag_id: row.get(1)?,
+                item_tag: row.get(2)?,
+                added_at: row.get(3)?,
+                removed_at: row.get(4)?,
+                note: row.get(5)?,
+            })
+        })?;
+        for tag in others {
+            tags.push(tag?);
+        }
+        Ok(tags)
+    }
+    
+    // Give an item another tag and deactivate the tags in `deactivate` (lost or damaged,
+    // `note` says which), in one transaction. Returns false if there is no item on
+    // `item_tag` or `new_tag` already stands for an item.
+    pub fn associate_tag(&self, item_tag: &str, new_tag: &str, deactivate: &[String], note: Option<&str>) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        if self.get_item(item_tag)?.is_none()
+            || self.resolve_tag(new_tag)?.is_some()
+            || self.get_item(new_tag)?.is_some() {
+            return Ok(false);
+        }
+        
+        let now = generate_timestamp();
+        self.add_tag(item_tag, new_tag, &now)?;
+        self.record(item_tag, Change::TagAdded { tag: new_tag.to_string(), at: now.clone() })?;
+        for tag_id in deactivate {
+            self.deactivate_logged(item_tag, tag_id, note, &now)?;
+        }
+        tx.commit()?;
+        
+        Ok(true)
+    }
+    
+    // The caller holds the transaction. Added once, a tag another station logged arrives
+    // again with every sync.
+    fn add_tag(&self, item_tag: &str, tag_id: &str, added_at: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO item_tags (tag_id, item_tag, added_at)
+             SELECT ?1, ?2, ?3
+             WHERE NOT EXISTS (SELECT 1 FROM item_tags WHERE tag_id = ?1 AND item_tag = ?2 AND added_at = ?3)",
+            params![tag_id, item_tag, added_at],
+        )?;
+        self.conn.execute("DELETE FROM unknown_tags WHERE tag_id = ?", params![tag_id])?;
+        Ok(())
+    }
+    
+    // Every active tag that finds an item other than by the item's own tag, with the
+    // tag of the item it finds
+    pub fn tag_aliases(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, item_tag FROM item_tags WHERE removed_at IS NULL AND tag_id != item_tag ORDER BY id"
+        )?;
+        let aliases = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        aliases.collect()
+    }
+    
+    // Keep a removal or return seen by presence monitoring
+    pub fn record_presence_event(&self, item_tag: &str, name: &str, change: PresenceChange) -> Result<PresenceEvent> {
+        let at = generate_timestamp();
+        self.conn.execute(
+            "INSERT INTO presence_events (item_tag, name, change, at) VALUES (?, ?, ?, ?)",
+            params![item_tag, name, change.key(), at],
+        )?;
+        Ok(PresenceEvent {
+            id: self.conn.last_insert_rowid(),
+            item_tag: item_tag.to_string(),
+            name: name.to_string(),
+            change,
+            at,
+        })
+    }
+    
+    // The latest presence events, newest first
+    pub fn presence_events(&self, limit: usize) -> Result<Vec<PresenceEvent>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, item_tag, name, change, at FROM presence_events ORDER BY id DESC LIMIT ?"
+        )?;
+        let events = stmt.query_map(params![limit as i64], presence_event_from_row)?;
+        events.collect()
+    }
+    
+    // Keep a card dump with the item on `item_tag`
+    pub fn attach_card_dump(&self, item_tag: &str, uid: &str, source: Option<&str>, data: &[u8]) -> Result<CardDump> {
+        let taken_at = generate_timestamp();
+        self.conn.execute(
+            "INSERT INTO card_dumps (item_tag, taken_at, uid, source, data) VALUES (?, ?, ?, ?, ?)",
+            params![item_tag, taken_at, uid, source, data],
+        )?;
+        Ok(CardDump {
+            id: self.conn.last_insert_rowid(),
+            item_tag: item_tag.to_string(),
+            taken_at,
+            uid: uid.to_string(),
+            source: source.map(str::to_string),
+            data: data.to_vec(),
+        })
+    }
+    
+    // The card dumps kept with an item, newest first
+    pub fn card_dumps(&self, item_tag: &str) -> Result<Vec<CardDump>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM card_dumps WHERE item_tag = ? ORDER BY taken_at DESC", DUMP_COLUMNS
+        ))?;
+        let dumps = stmt.query_map(params![item_tag], card_dump_from_row)?;
+        dumps.collect()
+    }
+    
+    pub fn delete_card_dump(&self, id: i64) -> Result<bool> {
+        let affected = self.conn.execute("DELETE FROM card_dumps WHERE id = ?", params![id])?;
+        Ok(affected > 0)
+    }
+    
+    // Add a dump from an export, unless the item already has one taken at that time
+    fn import_card_dump(&self, dump: &CardDump) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO card_dumps (item_tag, taken_at, uid, source, data) VALUES (?, ?, ?, ?, ?)",
+            params![dump.item_tag, dump.taken_at, dump.uid, dump.source, dump.data],
+        )?;
+        Ok(())
+    }
+    
+    // Add a row of an export's tag table, unless the database has it already. The
+    // item's own tag is only stored once it was deactivated, and then under the time it
+    // was deactivated, so a row is matched by either time.
+    fn import_item_tag(&self, tag: &ItemTag) -> Result<()> {
+        if tag.tag_id == tag.item_tag && tag.active() {
+            return Ok(());
+        }
+        self.conn.execute(
+            "INSERT INTO item_tags (tag_id, item_tag, added_at, removed_at, note)
+             SELECT ?1, ?2, ?3, ?4, ?5
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM item_tags WHERE tag_id = ?1 AND item_tag = ?2 AND (added_at = ?3 OR removed_at IS ?4)
+             )",
+            params![tag.tag_id, tag.item_tag, tag.added_at, tag.removed_at, tag.note],
+        )?;
+        Ok(())
+    }
+    
+    // Stop a tag of an item from finding it, the tag stays in the item's history
+    pub fn deactivate_tag(&self, item_tag: &str, tag_id: &str, note: Option<&str>) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let deactivated = self.deactivate_logged(item_tag, tag_id, note, &generate_timestamp())?;
+        tx.commit()?;
+        Ok(deactivated)
+    }
+    
+    // The caller holds the transaction. Deactivates the tag and logs it for station sync.
+    fn deactivate_logged(&self, item_tag: &str, tag_id: &str, note: Option<&str>, now: &str) -> Result<bool> {
+        let deactivated = self.remove_tag(item_tag, tag_id, note, now)?;
+        if deactivated {
+            self.record(item_tag, Change::TagDeactivated {
+                tag: tag_id.to_string(),
+                at: now.to_string(),
+                note: note.map(str::to_string),
+            })?;
+        }
+        Ok(deactivated)
+    }
+    
+    // The caller holds the transaction. The item's own tag gets a row of its own when
+    // it is deactivated, it is still the key of the item.
+    fn remove_tag(&self, item_tag: &str, tag_id: &str, note: Option<&str>, now: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE item_tags SET removed_at = ?, note = ? WHERE tag_id = ? AND item_tag = ? AND removed_at IS NULL",
+            params![now, note, tag_id, item_tag],
+        )?;
+        if affected > 0 || tag_id != item_tag || self.resolve_tag(tag_id)?.is_none() {
+            return Ok(affected > 0);
+        }
+        self.conn.execute(
+            "INSERT INTO item_tags (tag_id, item_tag, added_at, removed_at, note) VALUES (?1, ?1, ?2, ?3, ?4)",
+            params![tag_id, now, now, note],
+        )?;
+        Ok(true)
+    }
+    
+    // Add a supplier, returns its id
+    pub fn add_supplier(&self, name: &str, contact: Option<&str>, email: Option<&str>, phone: Option<&str>) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO suppliers (name, contact, email, phone, created_at) VALUES (?, ?, ?, ?, ?)",
+            params![name, contact, email, phone, generate_timestamp()],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    // Every supplier by name
+    pub fn suppliers(&self) -> Result<Vec<Supplier>> {
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM suppliers ORDER BY name", SUPPLIER_COLUMNS))?;
+        let supplier_iter = stmt.query_map([], supplier_from_row)?;
+        supplier_iter.collect()
+    }
+    
+    // Delete a supplier that has no purchase orders, its items lose their supplier
+    pub fn delete_supplier(&self, id: i64) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let affected = self.conn.execute(
+            "DELETE FROM suppliers WHERE id = ? AND NOT EXISTS (SELECT 1 FROM purchase_orders WHERE supplier_id = ?)",
+            params![id, id],
+        )?;
+        if affected > 0 {
+            self.conn.execute("DELETE FROM item_suppliers WHERE supplier_id = ?", params![id])?;
+        }
+        tx.commit()?;
+        Ok(affected > 0)
+    }
+    
+    // Set who an item is bought from, None removes the link
+    pub fn set_item_supplier(&self, tag_id: &str, supplier_id: Option<i64>) -> Result<()> {
+        match supplier_id {
+            Some(supplier_id) => self.conn.execute(
+                "INSERT OR REPLACE INTO item_suppliers (tag_id, supplier_id) VALUES (?, ?)",
+                params![tag_id, supplier_id],
+            )?,
+            None => self.conn.execute("DELETE FROM item_suppliers WHERE tag_id = ?", params![tag_id])?,
+        };
+        Ok(())
+    }
+    
+    // The supplier of every item that has one, by tag
+    pub fn item_suppliers(&self) -> Result<HashMap<String, Supplier>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT i.tag_id, {} FROM item_suppliers i JOIN suppliers s ON s.id = i.supplier_id",
+            SUPPLIER_COLUMNS.split(", ").map(|column| format!("s.{}", column)).collect::<Vec<_>>().join(", ")
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, Supplier {
+                id: row.get(1)?,
+                name: row.get(2)?,
+                contact: row.get(3)?,
+                email: row.get(4)?,
+                phone: row.get(5)?,
+                created_at: row.get(6)?,
+            }))
+        })?;
+        rows.collect()
+    }
+    
+    // Add an open purchase order, returns its id. The number has to be new.
+    pub fn add_purchase_order(&self, number: &str, supplier_id: i64) -> Result<i64> {
+        self.conn.execute(
+            "INSERT INTO purchase_orders (number, supplier_id, status, created_at) VALUES (?, ?, ?, ?)",
+            params![number, supplier_id, PoStatus::Open.key(), generate_timestamp()],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+    
+    // Every purchase order with its lines, the newest first
+    pub fn purchase_orders(&self) -> Result<Vec<PurchaseOrder>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, number, supplier_id, status, created_at FROM purchase_orders ORDER BY id DESC"
+        )?;
+        let orders = stmt.query_map([], purchase_order_from_row)?.collect::<Result<Vec<_>>>()?;
+        orders.into_iter().map(|order| self.with_lines(order)).collect()
+    }
+    
+    pub fn get_purchase_order(&self, id: i64) -> Result<Option<PurchaseOrder>> {
+        let order = self.conn.query_row(
+            "SELECT id, number, supplier_id, status, created_at FROM purchase_orders WHERE id = ?",
+            params![id],
+            purchase_order_from_row,
+        ).optional()?;
+        order.map(|order| self.with_lines(order)).transpose()
+    }
+    
+    // this fills in an order's lines
+    fn with_lines(&self, mut order: PurchaseOrder) -> Result<PurchaseOrder> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, ordered, received FROM po_lines WHERE po_id = ? ORDER BY tag_id"
+        )?;
+        let lines = stmt.query_map(params![order.id], |row| {
+            Ok(PoLine { tag_id: row.get(0)?, ordered: row.get(1)?, received: row.get(2)? })
+        })?;
+        order.lines = lines.collect::<Result<Vec<_>>>()?;
+        Ok(order)
+    }
+    
+    // Order `ordered` of an item, replacing what the order had for it
+    pub fn set_po_line(&self, po_id: i64, tag_id: &str, ordered: i32) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO po_lines (po_id, tag_id, ordered) VALUES (?, ?, ?)
+             ON CONFLICT (po_id, tag_id) DO UPDATE SET ordered = excluded.ordered",
+            params![po_id, tag_id, ordered],
+        )?;
+        Ok(())
+    }
+    
+    pub fn remove_po_line(&self, po_id: i64, tag_id: &str) -> Result<bool> {
+        let affected = self.conn.execute(
+            "DELETE FROM po_lines WHERE po_id = ? AND tag_id = ?",
+            params![po_id, tag_id],
+        )?;
+        Ok(affected > 0)
+    }
+    
+    pub fn set_po_status(&self, po_id: i64, status: PoStatus) -> Result<bool> {
+        let affected = self.conn.execute(
+            "UPDATE purchase_orders SET status = ? WHERE id = ?",
+            params![status.key(), po_id],
+        )?;
+        Ok(affected > 0)
+    }
+    
+    // Book a receipt of an item against an order, false when the item isn't on it
+    pub fn receive_against_po(&self, po_id: i64, tag_id: &str, quantity: i32) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        let affected = self.conn.execute(
+            "UPDATE po_lines SET received = received + ? WHERE po_id = ? AND tag_id = ?",
+            params![quantity, po_id, tag_id],
+        )?;
+        if affected == 0 {
+            return Ok(false);
+        }
+        self.conn.execute(
+            "INSERT INTO po_receipts (po_id, tag_id, quantity, received_at) VALUES (?, ?, ?, ?)",
+            params![po_id, tag_id, quantity, generate_timestamp()],
+        )?;
+        tx.commit()?;
+        Ok(true)
+    }
+    
+    // Update quantity of an item
+    pub fn update_quantity(&self, tag_id: &str, new_quantity: i32) -> Result<bool> {
+        self.update_quantity_with_mode(tag_id, new_quantity, None)
+    }
+    
+    // Update quantity of an item, logged as a receipt, issue or count
+    pub fn update_quantity_with_mode(&self, tag_id: &str, new_quantity: i32, mode: Option<StockMode>) -> Result<bool> {
+        let now = generate_timestamp();
+        
+        let tx = self.conn.unchecked_transaction()?;
+        let old_quantity = match self.get_item(tag_id)? {
+            Some(item) => item.quantity,
+            None => return Ok(false),
+        };
+        self.conn.execute(
+            "UPDATE inventory SET quantity = ?, last_updated = ? WHERE tag_id = ?",
+            params![new_quantity, now, tag_id],
+        )?;
+        if new_quantity != old_quantity {
+            self.record(tag_id, Change::Adjust { delta: new_quantity - old_quantity, mode })?;
+        }
+        tx.commit()?;
+        
+        Ok(true)
+    }
+    
+    /// Add an item for a tag, or replace the one already assigned to it, logged like an
+    /// edit in the FLTK app
+    pub fn upsert_item(&self, item: &NewItem) -> Result<InventoryItem> {
+        let tx = self.conn.unchecked_transaction()?;
+        let now = generate_timestamp();
+        let created_at = match self.get_item(&item.tag_id)? {
+            Some(existing) => existing.created_at,
+            None => now.clone(),
+        };
+        let saved = InventoryItem {
+            tag_id: item.tag_id.clone(),
+            name: item.name.clone(),
+            description: item.description.clone(),
+            quantity: item.quantity,
+            location: item.location.clone(),
+            category: item.category.clone(),
+            last_updated: now,
+            created_at,
+            expiry_date: item.expiry_date.clone(),
+        };
+        self.save_logged(&saved, None)?;
+        tx.commit()?;
+        
+        Ok(saved)
+    }
+    
+    /// Change the quantity of the item `tag_id` stands for by `delta`, never going below
+    /// zero. The change actually made is logged.
+    pub fn adjust_quantity(&self, tag_id: &str, delta: i32) -> Result<Option<InventoryItem>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut item = match self.get_item_by_any_tag(tag_id)? {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+        item.quantity = (item.quantity + delta).max(0);
+        item.last_updated = generate_timestamp();
+        self.save_logged(&item, None)?;
+        tx.commit()?;
+        
+        Ok(Some(item))
+    }
+    
+    // This database's id in the sync folder, created on first use
+    pub fn station_id(&self) -> Result<String> {
+        let existing: Option<String> = self.conn.query_row(
+            "SELECT value FROM sync_meta WHERE key = 'station_id'",
+            [],
+            |row| row.get(0),
+        ).optional()?;
+        
+        match existing {
+            Some(id) => Ok(id),
+            None => {
+                let id = oplog::new_station_id();
+                self.conn.execute(
+                    "INSERT INTO sync_meta (key, value) VALUES ('station_id', ?)",
+                    params![id],
+                )?;
+                Ok(id)
+            }
+        }
+    }
+    
+    // Append a local edit to the operation log
+    fn record(&self, tag_id: &str, change: Change) -> Result<()> {
+        let station = self.station_id()?;
+        let (seq, clock): (u64, u64) = self.conn.query_row(
+            "SELECT (SELECT COALESCE(MAX(seq), 0) + 1 FROM sync_ops WHERE station = ?1),
+                    (SELECT COALESCE(MAX(clock), 0) + 1 FROM sync_ops)",
+            params![station],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+        
+        self.insert_operation(&Operation {
+            station,
+            seq,
+            clock,
+            time: generate_timestamp(),
+            tag_id: tag_id.to_string(),
+            change,
+        })?;
+        Ok(())
+    }
+    
+    // Returns false if the operation was already in the log
+    fn insert_operation(&self, op: &Operation) -> Result<bool> {
+        let change = serde_json::to_string(&op.change)
+            .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?;
+        let inserted = self.conn.execute(
+            "INSERT OR IGNORE INTO sync_ops (station, seq, clock, time, tag_id, change)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            params![op.station, op.seq, op.clock, op.time, op.tag_id, change],
+        )?;
+        Ok(inserted > 0)
+    }
+    
+    fn query_operations(&self, sql: &str, value: &str) -> Result<Vec<Operation>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let op_iter = stmt.query_map(params![value], |row| {
+            let change: String = row.get(5)?;
+            let change = serde_json::from_str(&change)
+                .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?;
+            Ok(Operation {
+                station: row.get(0)?,
+                seq: row.get(1)?,
+                clock: row.get(2)?,
+                time: row.get(3)?,
+                tag_id: row.get(4)?,
+                change,
+            })
+        })?;
+        
+        op_iter.collect()
+    }
+    
+    // Every operation one station made, in log order
+    pub fn station_operations(&self, station: &str) -> Result<Vec<Operation>> {
+        self.query_operations(
+            "SELECT station, seq, clock, time, tag_id, change FROM sync_ops WHERE station = ? ORDER BY seq",
+            station,
+        )
+    }
+    
+    // This station's operations after `seq`, the oldest first, at most `limit` of them
+    pub fn local_operations_after(&self, seq: u64, limit: usize) -> Result<Vec<Operation>> {
+        let station = self.station_id()?;
+        self.query_operations(
+            &format!(
+                "SELECT station, seq, clock, time, tag_id, change FROM sync_ops
+                 WHERE station = ? AND seq > {} ORDER BY seq LIMIT {}",
+                seq, limit
+            ),
+            &station,
+        )
+    }
+    
+    // Highest sequence number of this station's own operations, 0 before the first one
+    pub fn last_local_seq(&self) -> Result<u64> {
+        let station = self.station_id()?;
+        self.conn.query_row(
+            "SELECT COALESCE(MAX(seq), 0) FROM sync_ops WHERE station = ?",
+            params![station],
+            |row| row.get(0),
+        )
+    }
+    
+    // The last local operation an outbound integration has pushed, None for a new one
+    pub fn integration_cursor(&self, name: &str) -> Result<Option<u64>> {
+        self.conn.query_row(
+            "SELECT seq FROM integration_cursors WHERE name = ?",
+            params![name],
+            |row| row.get(0),
+        ).optional()
+    }
+    
+    pub fn set_integration_cursor(&self, name: &str, seq: u64) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO integration_cursors (name, seq) VALUES (?, ?)",
+            params![name, seq],
+        )?;
+        Ok(())
+    }
+    
+    // Highest sequence number held for each station
+    pub fn version_vector(&self) -> Result<HashMap<String, u64>> {
+        let mut stmt = self.conn.prepare("SELECT station, MAX(seq) FROM sync_ops GROUP BY station")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect()
+    }
+    
+    // Log items that were added before station sync existed, so other stations get them too
+    pub fn seed_operation_log(&self) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut count = 0;
+        for item in self.get_all_items()? {
+            let logged: bool = self.conn.query_row(
+                "SELECT EXISTS (SELECT 1 FROM sync_ops WHERE tag_id = ?)",
+                params![item.tag_id],
+                |row| row.get(0),
+            )?;
+            if logged {
+                continue;
+            }
+            
+            self.record(&item.tag_id, Change::Upsert { item: item.clone() })?;
+            if item.quantity != 0 {
+                self.record(&item.tag_id, Change::Adjust { delta: item.quantity, mode: None })?;
+            }
+            count += 1;
+        }
+        tx.commit()?;
+        
+        Ok(count)
+    }
+    
+    // Add operations from other stations, rebuild the items they touch and add or
+    // deactivate the tags they name. Returns the tags whose item changed.
+    pub fn apply_operations(&self, ops: &[Operation]) -> Result<Vec<String>> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut touched = HashSet::new();
+        let mut tag_changes = Vec::new();
+        for op in ops {
+            if !self.insert_operation(op)? {
+                continue;
+            }
+            if op.change.is_tag_change() {
+                tag_changes.push(op);
+            } else {
+                touched.insert(op.tag_id.clone());
+            }
+        }
+        
+        let mut changed = Vec::new();
+        for tag_id in touched {
+            let mut tag_ops = self.query_operations(
+                "SELECT station, seq, clock, time, tag_id, change FROM sync_ops WHERE tag_id = ?",
+                &tag_id,
+            )?;
+            let current = self.get_item(&tag_id)?;
+            match oplog::replay(&mut tag_ops) {
+                Some(item) => self.write_item(&item)?,
+                // deleted on another station, it can be restored from the trash here too
+                None if current.is_some() => {
+                    self.move_to_trash(&tag_id)?;
+                },
+                None => continue,
+            }
+            changed.push(tag_id);
+        }
+        
+        // once the items are there, deactivating an item's own tag needs it
+        for op in tag_changes {
+            match &op.change {
+                Change::TagAdded { tag, at } => self.add_tag(&op.tag_id, tag, at)?,
+                Change::TagDeactivated { tag, at, note } => {
+                    self.remove_tag(&op.tag_id, tag, note.as_deref(), at)?;
+                },
+                _ => continue,
+            }
+            if !changed.contains(&op.tag_id) {
+                changed.push(op.tag_id.clone());
+            }
+        }
+        tx.commit()?;
+        
+        changed.sort();
+        Ok(changed)
+    }
+    
+    // Get items by category
+    pub fn get_items_by_category(&self, category: &str) -> Result<Vec<InventoryItem>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date 
+             FROM inventory WHERE category = ? ORDER BY name"
+        )?;
+        
+        let item_iter = stmt.query_map(params![category], |row| {
+            Ok(InventoryItem {
+                tag_id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                quantity: row.get(3)?,
+                location: row.get(4)?,
+                category: row.get(5)?,
+                last_updated: row.get(6)?,
+                created_at: row.get(7)?,
+                expiry_date: row.get(8)?,
+            })
+        })?;
+        
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item?);
+        }
+        
+        Ok(items)
+    }
+    
+    // Get all categories with counts
+    pub fn get_categories(&self) -> Result<Vec<(String, i32)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT category, COUNT(*) FROM inventory 
+             GROUP BY category ORDER BY category"
+        )?;
+        
+        let category_iter = stmt.query_map([], |row| {
+            let category: Option<String> = row.get(0)?;
+            let count: i32 = row.get(1)?;
+            
+            Ok((category.unwrap_or_else(|| "Uncategorized".to_string()), count))
+        })?;
+        
+        let mut categories = Vec::new();
+        for category in category_iter {
+            categories.push(category?);
+        }
+        
+        Ok(categories)
+    }
+    
+    // Search inventory by name, description, tag, location or category, every item for
+    // an empty query
+    pub fn search_items(&self, query: &str) -> Result<Vec<InventoryItem>> {
+        let search_term = format!("%{}%", query);
+        
+        let mut stmt = self.conn.prepare(
+            "SELECT tag_id, name, description, quantity, location, category, last_updated, created_at, expiry_date 
+             FROM inventory 
+             WHERE name LIKE ?1 OR description LIKE ?1 OR tag_id LIKE ?1 OR location LIKE ?1 OR category LIKE ?1
+             ORDER BY name"
+        )?;
+        
+        let item_iter = stmt.query_map(
+            params![&search_term], 
+            |row| {
+                Ok(InventoryItem {
+                    tag_id: row.get(0)?,
+                    name: row.get(1)?,
+                    description: row.get(2)?,
+                    quantity: row.get(3)?,
+                    location: row.get(4)?,
+                    category: row.get(5)?,
+                    last_updated: row.get(6)?,
+                    created_at: row.get(7)?,
+                    expiry_date: row.get(8)?,
+                })
+            }
+        )?;
+        
+        let mut items = Vec::new();
+        for item in item_iter {
+            items.push(item?);
+        }
+        
+        Ok(items)
+    }
+    
+    // Export inventory as JSON
+    pub fn export_json(&self) -> Result<String> {
+        let items = self.get_all_items()?;
+        let suppliers = self.item_suppliers()?;
+        let mut item_tags = Vec::new();
+        let mut card_dumps = Vec::new();
+        for item in &items {
+            item_tags.extend(self.tags_of_item(item)?);
+            card_dumps.extend(self.card_dumps(&item.tag_id)?);
+        }
+        let exported = ExportFile {
+            items: items.iter()
+                .map(|item| ExportedItem { item, supplier: suppliers.get(&item.tag_id) })
+                .collect(),
+            item_tags,
+            card_dumps,
+        };
+        let json = serde_json::to_string_pretty(&exported)
+            .map_err(|e| rusqlite::Error::InvalidParameterName(e.to_string()))?;
+        
+        Ok(json)
+    }
+    
+    // Export inventory as CSV
+    pub fn export_csv(&self) -> Result<String> {
+        let items = self.get_all_items()?;
+        let suppliers = self.item_suppliers()?;
+        
+        let mut csv = String::from(
+            "Tag ID,Name,Description,Quantity,Location,Category,Last Updated,Created At,Expiry Date,Supplier,Supplier Email\n"
+        );
+        
+        for item in items {
+            let description = item.description.unwrap_or_default().replace(",", "\\,");
+            let location = item.location.unwrap_or_default().replace(",", "\\,");
+            let category = item.category.unwrap_or_default().replace(",", "\\,");
+            let supplier = suppliers.get(&item.tag_id);
+            
+            csv.push_str(&format!(
+                "{},{},\"{}\",{},\"{}\",\"{}\",{},{},{},\"{}\",{}\n",
+                item.tag_id,
+                item.name.replace(",", "\\,"),
+                description,
+                item.quantity,
+                location,
+                category,
+                item.last_updated,
+                item.created_at,
+                item.expiry_date.unwrap_or_default(),
+                supplier.map(|supplier| supplier.name.replace(",", "\\,")).unwrap_or_default(),
+                supplier.and_then(|supplier| supplier.email.clone()).unwrap_or_default()
+            ));
+        }
+        
+        Ok(csv)
+    }
+    
+    // Export the tags of every item as CSV, the join table beside export_csv's items
+    pub fn export_tags_csv(&self) -> Result<String> {
+        let mut csv = String::from("Item Tag ID,Tag ID,Added At,Removed At,Note\n");
+        for item in self.get_all_items()? {
+            for tag in self.tags_of_item(&item)? {
+                csv.push_str(&format!(
+                    "{},{},{},{},\"{}\"\n",
+                    tag.item_tag,
+                    tag.tag_id,
+                    tag.added_at,
+                    tag.removed_at.unwrap_or_default(),
+                    tag.note.unwrap_or_default().replace(",", "\\,")
+                ));
+            }
+        }
+        Ok(csv)
+    }
+    
+    /// Copy the whole database to `path`, a consistent snapshot even while it is in use
+    pub fn backup_to(&self, path: &Path) -> Result<()> {
+        self.conn.backup(DatabaseName::Main, path, None)
+    }
+    
+    /// Replace the whole database with the copy at `path`
+    pub fn restore_from(&mut self, path: &Path) -> Result<()> {
+        self.conn.restore(DatabaseName::Main, path, None::<fn(Progress)>)?;
+        // copies from before station sync, the trash or expiry dates don't have their tables
+        self.create_sync_tables()?;
+        self.create_trash_table()?;
+        self.add_expiry_columns()?;
+        self.create_unit_tables()?;
+        self.create_kit_tables()?;
+        self.create_purchasing_tables()?;
+        self.create_triage_table()?;
+        self.create_item_tags_table()?;
+        self.create_presence_table()?;
+        self.create_dumps_table()
+    }
+    
+    // Save the rows of an import in one transaction, nothing is saved unless every row
+    // is. `saving` is told the index of each item before it is saved. Returns how many
+    // items were saved, or every row that couldn't be.
+    pub fn import_rows(
+        &self,
+        items: &[InventoryItem],
+        item_tags: &[ItemTag],
+        card_dumps: &[CardDump],
+        saving: &mut dyn FnMut(usize),
+    ) -> std::result::Result<usize, Vec<ImportRowError>> {
+        let tx = self.conn.unchecked_transaction().map_err(|e| vec![ImportRowError::file(e)])?;
+        let mut errors = Vec::new();
+        for (index, item) in items.iter().enumerate() {
+            saving(index);
+            if let Err(error) = self.save_logged(item, None) {
+                errors.push(ImportRowError { row: Some(index + 1), error });
+            }
+        }
+        for tag in item_tags {
+            if let Err(e) = self.import_item_tag(tag) {
+                errors.push(ImportRowError::file(e));
+            }
+        }
+        for dump in card_dumps {
+            if let Err(e) = self.import_card_dump(dump) {
+                errors.push(ImportRowError::file(e));
+            }
+        }
+        if !errors.is_empty() {
+            // dropping the transaction rolls back the rows already saved
+            return Err(errors);
+        }
+        tx.commit().map_err(|e| vec![ImportRowError::file(e)])?;
+        
+        Ok(items.len())
+    }
+}
+
+// Add a function to create a thread-safe version of the inventory DB
+pub fn create_thread_safe_db(db: InventoryDB) -> Arc<Mutex<InventoryDB>> {
+    Arc::new(Mutex::new(db))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_item(tag_id: &str, quantity: i32) -> NewItem {
+        NewItem {
+            tag_id: tag_id.to_string(),
+            name: "Resistor kit".to_string(),
+            description: None,
+            quantity,
+            location: Some("Shelf 2".to_string()),
+            category: None,
+            expiry_date: None,
+        }
+    }
+
+    #[test]
+    fn search_matches_name_and_location() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 3)).unwrap();
+
+        assert_eq!(db.search_items("resistor").unwrap().len(), 1);
+        assert_eq!(db.search_items("Shelf").unwrap().len(), 1);
+        assert!(db.search_items("capacitor").unwrap().is_empty());
+    }
+
+    #[test]
+    fn save_replaces_but_keeps_created_at() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        let first = db.upsert_item(&new_item("04A1B2C3", 1)).unwrap();
+        let second = db.upsert_item(&new_item("04A1B2C3", 7)).unwrap();
+
+        assert_eq!(second.quantity, 7);
+        assert_eq!(second.created_at, first.created_at);
+        assert_eq!(db.search_items("shelf").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn adjust_and_delete() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 1)).unwrap();
+
+        assert_eq!(db.adjust_quantity("04A1B2C3", -5).unwrap().unwrap().quantity, 0);
+        assert!(db.adjust_quantity("DEADBEEF", 1).unwrap().is_none());
+        assert!(db.delete_item("04A1B2C3").unwrap().is_some());
+        assert!(db.get_item("04A1B2C3").unwrap().is_none());
+    }
+
+    #[test]
+    fn an_associated_tag_finds_the_item() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 2)).unwrap();
+        db.conn.execute_batch(
+            "INSERT INTO item_tags (tag_id, item_tag, added_at) VALUES ('04D4E5F6', '04A1B2C3', 't');
+             INSERT INTO item_tags (tag_id, item_tag, added_at, removed_at) VALUES ('04A1B2C3', '04A1B2C3', 't', 't');",
+        ).unwrap();
+
+        assert_eq!(db.get_item_by_any_tag("04D4E5F6").unwrap().unwrap().tag_id, "04A1B2C3");
+        assert_eq!(db.adjust_quantity("04D4E5F6", 3).unwrap().unwrap().quantity, 5);
+        // the item's own tag was deactivated, so it no longer finds the item
+        assert!(db.get_item_by_any_tag("04A1B2C3").unwrap().is_none());
+        assert!(db.adjust_quantity("04A1B2C3", 1).unwrap().is_none());
+    }
+
+    #[test]
+    fn deleting_by_an_associated_tag_deletes_the_item() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 2)).unwrap();
+        assert!(db.associate_tag("04A1B2C3", "04D4E5F6", &[], None).unwrap());
+
+        assert_eq!(db.delete_item("04D4E5F6").unwrap().as_deref(), Some("04A1B2C3"));
+        assert!(db.get_item("04A1B2C3").unwrap().is_none());
+        assert_eq!(db.trashed_items().unwrap()[0].item.tag_id, "04A1B2C3");
+        assert!(db.delete_item("04D4E5F6").unwrap().is_none());
+    }
+
+    #[test]
+    fn api_edits_are_logged_and_survive_a_sync() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 3)).unwrap();
+        db.adjust_quantity("04A1B2C3", -1).unwrap();
+        let mut moved = new_item("04A1B2C3", 2);
+        moved.location = Some("Shelf 5".to_string());
+        db.upsert_item(&moved).unwrap();
+        assert_eq!(db.last_local_seq().unwrap(), 4);
+
+        // another station books one in, the item is rebuilt from the whole log
+        let remote = Operation {
+            station: "other".to_string(),
+            seq: 1,
+            clock: 1,
+            time: generate_timestamp(),
+            tag_id: "04A1B2C3".to_string(),
+            change: Change::Adjust { delta: 1, mode: Some(StockMode::Receive) },
+        };
+        assert_eq!(db.apply_operations(&[remote]).unwrap(), ["04A1B2C3"]);
+        let item = db.get_item("04A1B2C3").unwrap().unwrap();
+        assert_eq!(item.quantity, 3);
+        assert_eq!(item.location.as_deref(), Some("Shelf 5"));
+    }
+
+    #[test]
+    fn tag_changes_reach_other_stations() {
+        let here = InventoryDB::new(":memory:").unwrap();
+        here.upsert_item(&new_item("04A1B2C3", 2)).unwrap();
+        assert!(here.associate_tag("04A1B2C3", "04D4E5F6", &["04A1B2C3".to_string()], Some("lost")).unwrap());
+        let station = here.station_id().unwrap();
+
+        let there = InventoryDB::new(":memory:").unwrap();
+        assert_eq!(there.apply_operations(&here.station_operations(&station).unwrap()).unwrap(), ["04A1B2C3"]);
+        assert_eq!(there.get_item_by_any_tag("04D4E5F6").unwrap().unwrap().quantity, 2);
+        assert!(there.get_item_by_any_tag("04A1B2C3").unwrap().is_none());
+
+        assert!(here.deactivate_tag("04A1B2C3", "04D4E5F6", Some("damaged")).unwrap());
+        let ops = here.station_operations(&station).unwrap();
+        assert_eq!(there.apply_operations(&ops).unwrap(), ["04A1B2C3"]);
+        // the whole log again changes nothing
+        assert!(there.apply_operations(&ops).unwrap().is_empty());
+        assert!(there.get_item_by_any_tag("04D4E5F6").unwrap().is_none());
+
+        let item = here.get_item("04A1B2C3").unwrap().unwrap();
+        let history = |db: &InventoryDB| -> Vec<_> {
+            db.tags_of_item(&item).unwrap().into_iter()
+                .map(|tag| (tag.tag_id, tag.added_at, tag.removed_at, tag.note))
+                .collect()
+        };
+        assert_eq!(history(&there), history(&here));
+    }
+
+    #[test]
+    fn deleted_items_go_to_the_trash() {
+        let db = InventoryDB::new(":memory:").unwrap();
+        db.upsert_item(&new_item("04A1B2C3", 3)).unwrap();
+
+        assert!(db.delete_item("04A1B2C3").unwrap().is_some());
+        assert!(db.delete_item("04A1B2C3").unwrap().is_none());
+        let trashed: (String, i32) = db.conn
+            .query_row("SELECT name, quantity FROM inventory_trash WHERE tag_id = '04A1B2C3'", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(trashed, ("Resistor kit".to_string(), 3));
+    }
+
+    #[test]
+    fn old_databases_get_the_expiry_column() {
+        let path = std::env::temp_dir().join(format!("inventory_db_expiry_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Connection::open(&path).unwrap().execute_batch(
+            "CREATE TABLE inventory (tag_id TEXT PRIMARY KEY, name TEXT NOT NULL, description TEXT,
+                quantity INTEGER NOT NULL DEFAULT 0, location TEXT, category TEXT,
+                last_updated TEXT NOT NULL, created_at TEXT NOT NULL);
+             INSERT INTO inventory VALUES ('04A1B2C3', 'Flux', NULL, 1, NULL, NULL, 't', 't');",
+        ).unwrap();
+
+        let db = InventoryDB::new(path.to_str().unwrap()).unwrap();
+        assert_eq!(db.get_item("04A1B2C3").unwrap().unwrap().expiry_date, None);
+        let mut flux = new_item("04A1B2C3", 1);
+        flux.expiry_date = Some("2025-06-30".to_string());
+        assert_eq!(db.upsert_item(&flux).unwrap().expiry_date.as_deref(), Some("2025-06-30"));
+        assert!(db.delete_item("04A1B2C3").unwrap().is_some());
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+}
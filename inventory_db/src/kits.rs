@@ -0,0 +1,58 @@
+// kits.rs
+//
+// What booking a kit does to the stock of its components. A kit is booked as a whole
+// or not at all, see InventoryDB::book_kit.
+use std::collections::HashMap;
+
+use crate::db::InventoryDB;
+use crate::model::{InventoryItem, Kit, KitComponent, Shortage, StockMode};
+
+/// The inventory items of a kit's components, by tag. Components no longer in the
+/// inventory are left out.
+pub fn component_items(db: &InventoryDB, kit: &Kit) -> rusqlite::Result<HashMap<String, InventoryItem>> {
+    let mut items = HashMap::new();
+    for component in &kit.components {
+        if let Some(item) = db.get_item(&component.tag_id)? {
+            items.insert(component.tag_id.clone(), item);
+        }
+    }
+    Ok(items)
+}
+
+/// How a component's quantity changes when `count` kits are booked in `mode`
+pub fn component_delta(component: &KitComponent, mode: StockMode, count: i32) -> i32 {
+    match mode {
+        StockMode::Receive => component.quantity * count,
+        StockMode::Issue => -component.quantity * count,
+        StockMode::Count => 0,
+    }
+}
+
+/// The components that keep `count` kits from being booked in `mode`: any that is no
+/// longer in the inventory, and when issuing any there isn't enough of
+pub fn shortages(kit: &Kit, items: &HashMap<String, InventoryItem>, mode: StockMode, count: i32) -> Vec<Shortage> {
+    kit.components.iter().filter_map(|component| {
+        let needed = component.quantity * count;
+        match items.get(&component.tag_id) {
+            None => Some(Shortage { tag_id: component.tag_id.clone(), name: None, needed, available: 0 }),
+            Some(item) if mode == StockMode::Issue && item.quantity < needed => Some(Shortage {
+                tag_id: component.tag_id.clone(),
+                name: Some(item.name.clone()),
+                needed,
+                available: item.quantity,
+            }),
+            Some(_) => None,
+        }
+    }).collect()
+}
+
+/// How many whole kits the stock of its components makes
+pub fn buildable(kit: &Kit, items: &HashMap<String, InventoryItem>) -> i32 {
+    kit.components.iter()
+        .filter(|component| component.quantity > 0)
+        .map(|component| items.get(&component.tag_id)
+            .map(|item| item.quantity.max(0) / component.quantity)
+            .unwrap_or(0))
+        .min()
+        .unwrap_or(0)
+}
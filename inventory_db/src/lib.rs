@@ -0,0 +1,12 @@
+// lib.rs
+//
+// inventory.db, opened by the FLTK app, the terminal UI and the REST API. Its schema and
+// migrations, the trash, the tags of each item and the operation log station sync
+// replays all live here, so the programs can't drift apart.
+pub mod db;
+pub mod kits;
+pub mod model;
+pub mod oplog;
+
+pub use db::{checkpoint, create_thread_safe_db, ImportRowError, InventoryDB};
+pub use model::{create_inventory_item, generate_timestamp, InventoryChange, InventoryItem, NewItem};
@@ -0,0 +1,412 @@
+// model.rs
+//
+// What inventory.db holds. Names shown to the operator are up to the programs, the
+// enums only carry the keys they are stored under.
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Define item structure
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct InventoryItem {
+    pub tag_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub quantity: i32,
+    pub location: Option<String>,
+    pub category: Option<String>,
+    pub last_updated: String,
+    pub created_at: String,
+    // Best-before or expiry date as YYYY-MM-DD, for chemicals, food and the like
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<String>,
+}
+
+// An item in the trash, `id` tells apart several deletions of the same tag
+#[derive(Clone, Debug)]
+pub struct TrashedItem {
+    pub id: i64,
+    pub item: InventoryItem,
+    pub deleted_at: String,
+}
+
+// A tag an item has or had. `item_tag` is the tag the item is keyed by, `removed_at`
+// is set once the tag is deactivated (lost or damaged, `note` says why).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ItemTag {
+    #[serde(skip)]
+    pub id: i64,
+    pub tag_id: String,
+    pub item_tag: String,
+    pub added_at: String,
+    pub removed_at: Option<String>,
+    pub note: Option<String>,
+}
+
+impl ItemTag {
+    pub fn active(&self) -> bool {
+        self.removed_at.is_none()
+    }
+}
+
+// A card dump kept with an item, such as what is on a badge. `data` is the raw dump,
+// 16 bytes a block. Exports write it as one hex line a block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CardDump {
+    #[serde(skip)]
+    pub id: i64,
+    pub item_tag: String,
+    pub taken_at: String,
+    pub uid: String,
+    // the file the dump was attached from
+    pub source: Option<String>,
+    #[serde(with = "hex_blocks")]
+    pub data: Vec<u8>,
+}
+
+impl CardDump {
+    pub fn blocks(&self) -> usize {
+        self.data.len() / 16
+    }
+}
+
+// Dump data as a list of hex blocks, "00112233..." 16 bytes a line
+mod hex_blocks {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(data.chunks(16).map(|block| {
+            block.iter().map(|byte| format!("{:02X}", byte)).collect::<String>()
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let mut data = Vec::new();
+        for block in Vec::<String>::deserialize(deserializer)? {
+            if block.len() != 32 {
+                return Err(D::Error::custom(format!("dump block {} isn't 16 bytes", block)));
+            }
+            for i in (0..block.len()).step_by(2) {
+                let byte = block.get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                    .ok_or_else(|| D::Error::custom(format!("dump block {} isn't hex", block)))?;
+                data.push(byte);
+            }
+        }
+        Ok(data)
+    }
+}
+
+// A scanned tag that isn't in the inventory, waiting to be triaged
+#[derive(Clone, Debug)]
+pub struct UnknownTag {
+    pub tag_id: String,
+    pub scans: u32,
+    pub first_seen: String,
+    pub last_seen: String,
+    // ignored tags are counted but no longer listed
+    pub ignored: bool,
+}
+
+// Whether a monitored tag left the reader's field or came back
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresenceChange {
+    Removed,
+    Returned,
+}
+
+impl PresenceChange {
+    // How the change is stored in the presence_events table
+    pub fn key(&self) -> &'static str {
+        match self {
+            PresenceChange::Removed => "removed",
+            PresenceChange::Returned => "returned",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<PresenceChange> {
+        [PresenceChange::Removed, PresenceChange::Returned].into_iter().find(|change| change.key() == key)
+    }
+}
+
+// A removal or return seen while monitoring presence, kept as an audit trail.
+// `item_tag` is the tag the item is keyed by, whichever of its tags was read.
+#[derive(Clone, Debug)]
+pub struct PresenceEvent {
+    pub id: i64,
+    pub item_tag: String,
+    pub name: String,
+    pub change: PresenceChange,
+    pub at: String,
+}
+
+// Why a quantity changed: stock booked in, booked out, or counted
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StockMode {
+    #[default]
+    Receive,
+    Issue,
+    Count,
+}
+
+// Modes in the order the capture window lists them
+pub const ALL_STOCK_MODES: [StockMode; 3] = [StockMode::Receive, StockMode::Issue, StockMode::Count];
+
+impl StockMode {
+    pub fn index(&self) -> i32 {
+        ALL_STOCK_MODES.iter().position(|mode| mode == self).unwrap_or(0) as i32
+    }
+
+    pub fn from_index(index: i32) -> StockMode {
+        ALL_STOCK_MODES.get(index.max(0) as usize).copied().unwrap_or_default()
+    }
+
+    // Quantity after one scan, a count keeps it for the operator to enter
+    pub fn scanned_quantity(&self, quantity: i32) -> i32 {
+        match self {
+            StockMode::Receive => quantity + 1,
+            StockMode::Issue => (quantity - 1).max(0),
+            StockMode::Count => quantity,
+        }
+    }
+
+    // The quantity an item has once `quantity` is booked against its `current` stock,
+    // the counted quantity in count mode
+    pub fn booked_quantity(&self, current: i32, quantity: i32) -> i32 {
+        match self {
+            StockMode::Receive => current + quantity,
+            StockMode::Issue => (current - quantity).max(0),
+            StockMode::Count => quantity,
+        }
+    }
+}
+
+// A product tracked by serial number, one tag per unit instead of a quantity on one tag
+#[derive(Clone, Debug, PartialEq)]
+pub struct Product {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub created_at: String,
+}
+
+// Where a serialized unit is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitStatus {
+    #[default]
+    InStock,
+    Issued,
+    // Scrapped, lost or sold, kept for its history
+    Retired,
+}
+
+pub const ALL_UNIT_STATUSES: [UnitStatus; 3] = [UnitStatus::InStock, UnitStatus::Issued, UnitStatus::Retired];
+
+impl UnitStatus {
+    // How the status is stored in the units table
+    pub fn key(&self) -> &'static str {
+        match self {
+            UnitStatus::InStock => "in_stock",
+            UnitStatus::Issued => "issued",
+            UnitStatus::Retired => "retired",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<UnitStatus> {
+        ALL_UNIT_STATUSES.into_iter().find(|status| status.key() == key)
+    }
+
+    // Status after a scan in `mode`, None when the unit can't go there: a retired unit
+    // stays retired and a unit that isn't in stock can't be issued
+    pub fn scanned(&self, mode: StockMode) -> Option<UnitStatus> {
+        match (self, mode) {
+            (UnitStatus::Retired, _) => None,
+            (_, StockMode::Receive) => Some(UnitStatus::InStock),
+            (UnitStatus::InStock, StockMode::Issue) => Some(UnitStatus::Issued),
+            (UnitStatus::Issued, StockMode::Issue) => None,
+            (status, StockMode::Count) => Some(*status),
+        }
+    }
+}
+
+// One tagged unit of a product
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit {
+    pub tag_id: String,
+    pub product_id: i64,
+    pub serial: Option<String>,
+    pub status: UnitStatus,
+    pub location: Option<String>,
+    pub last_updated: String,
+    pub created_at: String,
+}
+
+// A product with how many of its units are in each status
+#[derive(Clone, Debug)]
+pub struct ProductStock {
+    pub product: Product,
+    pub in_stock: usize,
+    pub issued: usize,
+    pub retired: usize,
+}
+
+// A kit: one tag standing for a bill of materials, booked in or out as a whole
+#[derive(Clone, Debug, PartialEq)]
+pub struct Kit {
+    pub tag_id: String,
+    pub name: String,
+    pub components: Vec<KitComponent>,
+    pub created_at: String,
+}
+
+// How many of an item one kit takes
+#[derive(Clone, Debug, PartialEq)]
+pub struct KitComponent {
+    pub tag_id: String,
+    pub quantity: i32,
+}
+
+// A component there isn't enough of, or that is no longer in the inventory (no name)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shortage {
+    pub tag_id: String,
+    pub name: Option<String>,
+    pub needed: i32,
+    pub available: i32,
+}
+
+// Someone stock is bought from. Exports carry the contact details, not the ids.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Supplier {
+    #[serde(skip_serializing)]
+    pub id: i64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    #[serde(skip_serializing)]
+    pub created_at: String,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PoStatus {
+    #[default]
+    Open,
+    // Everything received, or cancelled
+    Closed,
+}
+
+impl PoStatus {
+    // How the status is stored in the purchase_orders table
+    pub fn key(&self) -> &'static str {
+        match self {
+            PoStatus::Open => "open",
+            PoStatus::Closed => "closed",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<PoStatus> {
+        [PoStatus::Open, PoStatus::Closed].into_iter().find(|status| status.key() == key)
+    }
+}
+
+// An order placed with a supplier, received against while scanning
+#[derive(Clone, Debug, PartialEq)]
+pub struct PurchaseOrder {
+    pub id: i64,
+    pub number: String,
+    pub supplier_id: i64,
+    pub status: PoStatus,
+    pub created_at: String,
+    pub lines: Vec<PoLine>,
+}
+
+// How many of an item were ordered and how many have come in
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoLine {
+    pub tag_id: String,
+    pub ordered: i32,
+    pub received: i32,
+}
+
+impl PurchaseOrder {
+    pub fn fully_received(&self) -> bool {
+        !self.lines.is_empty() && self.lines.iter().all(|line| line.received >= line.ordered)
+    }
+}
+
+// Helper to generate ISO timestamp
+pub fn generate_timestamp() -> String {
+    let now = SystemTime::now();
+    let since_epoch = now.duration_since(UNIX_EPOCH).unwrap();
+    let seconds = since_epoch.as_secs();
+    let millis = since_epoch.subsec_millis();
+    
+    #[allow(deprecated)]
+    let datetime = match chrono::DateTime::from_timestamp(seconds as i64, millis * 1_000_000) {
+        Some(dt) => dt.naive_local(),
+        None => chrono::NaiveDateTime::from_timestamp_millis(0).unwrap()
+    };
+    datetime.format("%Y-%m-%dT%H:%M:%S.%fZ").to_string()
+}
+
+// Create a new inventory item
+pub fn create_inventory_item(
+    tag_id: &str, 
+    name: &str, 
+    description: Option<&str>, 
+    quantity: i32, 
+    location: Option<&str>, 
+    category: Option<&str>
+) -> InventoryItem {
+    let now = generate_timestamp();
+    
+    InventoryItem {
+        tag_id: tag_id.to_string(),
+        name: name.to_string(),
+        description: description.map(ToString::to_string),
+        quantity,
+        location: location.map(ToString::to_string),
+        category: category.map(ToString::to_string),
+        last_updated: now.clone(),
+        created_at: now,
+        expiry_date: None,
+    }
+}
+// One entry of a reviewed list of scans, booked with the rest of the list or not at all
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReviewEntry {
+    pub tag_id: String,
+    pub mode: StockMode,
+    // amount received or issued, or the counted quantity in count mode
+    pub quantity: i32,
+    // the name an unknown tag's new item gets, its tag when there is none
+    pub name: Option<String>,
+}
+
+/// An edit made through the API, forwarded to the hub by stations in a fleet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum InventoryChange {
+    Saved(InventoryItem),
+    Deleted { tag_id: String },
+}
+
+/// Body of POST /api/inventory
+#[derive(Debug, Deserialize)]
+pub struct NewItem {
+    pub tag_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub quantity: i32,
+    pub location: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub expiry_date: Option<String>,
+}
@@ -0,0 +1,201 @@
+// oplog.rs
+//
+// Operation log behind station sync. Every local edit is recorded as an operation, and
+// stations exchange operations instead of database copies. Each item is rebuilt from its
+// operations in one fixed order, so every station ends up with the same inventory no
+// matter in which order the sync folder delivers the logs.
+//
+// Item fields are last-writer-wins by Lamport clock (station id breaks ties). Quantities
+// are a counter of adjustments, so stock booked in and out on two stations offline both counts.
+// Tags added to an item or deactivated are logged under the item's tag too, and applied to
+// the item's tag history as they arrive rather than replayed.
+use serde::{Deserialize, Serialize};
+
+use crate::model::{InventoryItem, StockMode};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Operation {
+    pub station: String,
+    // position in the station's own log, starting at 1
+    pub seq: u64,
+    // Lamport clock: higher than every operation the station had seen when it made this one
+    pub clock: u64,
+    pub time: String,
+    pub tag_id: String,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Change {
+    // New values for the item's fields, the quantity in `item` is ignored
+    Upsert { item: InventoryItem },
+    // `mode` tells a receipt, issue or count apart, older logs don't have it
+    Adjust {
+        delta: i32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mode: Option<StockMode>,
+    },
+    Delete,
+    // `tag` finds the item from `at` on
+    TagAdded { tag: String, at: String },
+    // `tag` stops finding the item from `at` on, lost or damaged as `note` says
+    TagDeactivated {
+        tag: String,
+        at: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+}
+
+impl Change {
+    // whether this changes the item's tags rather than the item
+    pub fn is_tag_change(&self) -> bool {
+        matches!(self, Change::TagAdded { .. } | Change::TagDeactivated { .. })
+    }
+}
+
+impl Operation {
+    // the order every station replays operations in
+    fn order_key(&self) -> (u64, &str, u64) {
+        (self.clock, self.station.as_str(), self.seq)
+    }
+}
+
+// Current state of one item from all of its operations, None if it was deleted (or never added)
+pub fn replay(ops: &mut [Operation]) -> Option<InventoryItem> {
+    ops.sort_by(|a, b| a.order_key().cmp(&b.order_key()));
+
+    let mut item: Option<InventoryItem> = None;
+    let mut quantity = 0;
+    for op in ops.iter() {
+        match &op.change {
+            Change::Upsert { item: fields } => item = Some(fields.clone()),
+            Change::Adjust { delta, .. } => quantity += delta,
+            Change::Delete => {
+                item = None;
+                quantity = 0;
+            }
+            Change::TagAdded { .. } | Change::TagDeactivated { .. } => {}
+        }
+    }
+
+    let last_updated = ops.iter().rev().find(|op| !op.change.is_tag_change()).map(|op| op.time.clone());
+    item.map(|mut item| {
+        item.quantity = quantity;
+        if let Some(time) = last_updated {
+            item.last_updated = time;
+        }
+        item
+    })
+}
+
+// Station id for a database that doesn't have one yet: the hostname plus the time, so two
+// Pis cloned from the same image still get different ids
+pub fn new_station_id() -> String {
+    let host = std::fs::read_to_string("/etc/hostname")
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default();
+    let host = if host.is_empty() { "station".to_string() } else { host };
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{}-{:x}", host, nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(station: &str, seq: u64, clock: u64, change: Change) -> Operation {
+        Operation {
+            station: station.to_string(),
+            seq,
+            clock,
+            time: format!("2024-05-01T10:00:{:02}.000Z", clock),
+            tag_id: "04A1B2C3".to_string(),
+            change,
+        }
+    }
+
+    fn upsert(name: &str) -> Change {
+        Change::Upsert {
+            item: InventoryItem {
+                tag_id: "04A1B2C3".to_string(),
+                name: name.to_string(),
+                description: None,
+                quantity: 0,
+                location: None,
+                category: None,
+                last_updated: String::new(),
+                created_at: "2024-05-01T09:00:00.000Z".to_string(),
+                expiry_date: None,
+            },
+        }
+    }
+
+    fn adjust(delta: i32) -> Change {
+        Change::Adjust { delta, mode: None }
+    }
+
+    // every order of `ops`, by swapping (Heap's algorithm)
+    fn permutations(ops: &mut Vec<Operation>, k: usize, out: &mut Vec<Vec<Operation>>) {
+        if k <= 1 {
+            out.push(ops.clone());
+            return;
+        }
+        for i in 0..k {
+            permutations(ops, k - 1, out);
+            let j = if k.is_multiple_of(2) { i } else { 0 };
+            ops.swap(j, k - 1);
+        }
+    }
+
+    fn replayed(ops: &[Operation]) -> Option<serde_json::Value> {
+        replay(&mut ops.to_vec()).map(|item| serde_json::to_value(item).unwrap())
+    }
+
+    #[test]
+    fn replay_gives_the_same_item_in_any_order() {
+        // two stations renaming the item and booking stock at the same Lamport clock
+        let mut ops = vec![
+            op("pi-a", 1, 1, upsert("Resistor kit")),
+            op("pi-a", 2, 2, adjust(5)),
+            op("pi-a", 3, 3, upsert("Resistors")),
+            op("pi-b", 1, 3, upsert("Resistor box")),
+            op("pi-b", 2, 4, adjust(-2)),
+            op("pi-a", 4, 4, adjust(1)),
+        ];
+        let expected = replayed(&ops).unwrap();
+        assert_eq!(expected["name"], "Resistor box");
+        assert_eq!(expected["quantity"], 4);
+
+        let mut orders = Vec::new();
+        let len = ops.len();
+        permutations(&mut ops, len, &mut orders);
+        assert_eq!(orders.len(), 720);
+        for order in orders {
+            assert_eq!(replayed(&order).as_ref(), Some(&expected));
+        }
+    }
+
+    #[test]
+    fn a_concurrent_delete_wins_in_any_order() {
+        // pi-b deletes the item at the clock pi-a books stock at, the station id decides
+        let mut ops = vec![
+            op("pi-a", 1, 1, upsert("Flux")),
+            op("pi-a", 2, 2, adjust(3)),
+            op("pi-a", 3, 3, adjust(1)),
+            op("pi-b", 1, 3, Change::Delete),
+        ];
+        let mut orders = Vec::new();
+        let len = ops.len();
+        permutations(&mut ops, len, &mut orders);
+        assert_eq!(orders.len(), 24);
+        for order in orders {
+            assert_eq!(replayed(&order), None);
+        }
+    }
+}
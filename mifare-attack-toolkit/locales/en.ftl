@@ -0,0 +1,387 @@
+# English, also the fallback for keys missing from the other catalogues
+
+# Startup and the command line
+err-error = Error: { $error }
+no-proxmark3-found = No Proxmark3 client or device found (set PM3_CLIENT / PM3_PORT)
+csv-written-to = CSV written to { $path }
+err-writing-file = Error writing { $path }: { $error }
+replay-transactions = Replaying { $count } transactions from { $path }
+replay-transactions-timed = Replaying { $count } transactions from { $path } with the recorded timing
+err-loading-session = Error loading session { $path }: { $error }
+replay-stopped = Replay stopped: { $error }
+replay-place-card = Place the same card on the reader and press Enter...
+replay-answers-matched = All { $replayed } answers matched the recording.
+replay-answers-differ = { $count } of { $replayed } answers differ (transactions { $transactions })
+built-without-gui = Built without the gui feature, using the menu
+title-attack-manager = Mifare Attack Manager
+banner-tears-for-fears = Based on Proxmark3 algorithms and 'Tears For Fears' approach
+press-ctrl-c-to-exit = Press Ctrl+C to exit
+proxmark3-on = Proxmark3 on { $port }: { $version }
+err-talking-to-proxmark3-on = Error talking to the Proxmark3 on { $port }: { $error }
+title-toolkit = MIFARE Attack Toolkit
+banner-ported = Based on Proxmark3 algorithms ported to Rust
+banner-readers = Compatible with MFRC522 on Raspberry Pi, and with a Proxmark3 (--pm3 [PORT])
+built-without-attacks = Built without the attacks feature: key recovery is not available
+read-only-mode = READ-ONLY MODE ({ $reason }): attacks and card writes are disabled
+err-initializing-mfrc522 = Error initializing MFRC522: { $error }
+found-proxmark3-using-it-instead = Found a Proxmark3, using it instead
+err-starting-session-recording = Error starting session recording: { $error }
+transactions-recorded = { $count } transactions recorded
+consent-type-phrase = Type '{ $phrase }' to accept, anything else to go back:
+consent-not-accepted = Not accepted, the attacks stay unavailable.
+consent-changes-card = '{ $operation }' permanently changes the card on the reader.
+consent-authorized = Make sure you are authorized to change it. Continue for this session? (y/n):
+err-writing-audit-log = Error writing the audit log { $path }: { $error }
+please-remove-card-from-reader = Please remove the card from the reader...
+card-removed = Card removed
+hold-card-near-reader = Hold a card near the reader...
+seconds-to-place-card = You have { $seconds } seconds to place a card
+card-detected-uid = Card detected! UID: { $uid }
+no-card-in-time = No card detected in the given time frame.
+press-enter-to-continue = Press Enter to continue...
+
+# Menus
+cancelling-attacks-still-running = Cancelling the attacks still running...
+enter-choice = Enter choice:
+exiting = Exiting...
+err-invalid-choice-please-try-again = Invalid choice. Please try again.
+job-queued = Job { $id } ({ $job }) queued behind the running one. 11 lists the jobs, 12 cancels them.
+job-submitted = Job { $id } ({ $job }) started, place the card on the reader. 11 lists the jobs, 12 cancels them.
+no-attacks-run-yet = No attacks run yet.
+cancelling-running-attack-stops-at = Cancelling, the running attack stops at its next step. Its nonces are kept for the next run.
+no-attack-is-running = No attack is running.
+operation-cancelled = Operation cancelled.
+menu-reader = Reader: { $status }
+menu-reader-busy = Reader: busy with an attack
+select-option = Select an option:
+read-card-uid = Read card UID
+try-default-keys = Try default keys
+run-nested-attack = Run Nested Attack (requires a known key)
+run-darkside-attack = Run Darkside Attack
+detect-magic-card = Detect Magic Card
+write-custom-uid = Write custom UID (requires Magic Card)
+dump-card-contents = Dump card contents
+clone-card-to-magic-card = Clone card to Magic Card
+test-prng = Test the PRNG (will darkside / nested work?)
+show-attack-jobs = Show attack jobs
+cancel-attack-jobs = Cancel attack jobs
+autopwn = Autopwn: recover every key, then dump the card
+menu-exit = Exit
+title-prng-test = PRNG Test
+job-started = [job { $id }] { $job } started
+job-reader = [job { $id }] Reader: { $status }
+job-card = [job { $id }] Card { $uid } ({ $card_type })
+job-card-magic = [job { $id }] Card { $uid } ({ $card_type }), likely a magic card
+job-found-of-sector = [job { $id }] Found { $key_type } of sector { $sector }: { $key }
+job-prng-report = [job { $id }]
+    { $report }
+job-result = [job { $id }]
+    { $result }
+saved-to = Saved to { $path }
+job-finished = [job { $id }] { $summary }
+job-cancelled = [job { $id }] Cancelled
+job-failed = [job { $id }] Failed: { $error }
+err-invalid-sector-number = Invalid sector number
+err-invalid-block-number = Invalid block number
+enter-key-type = Enter key type (A or B):
+err-invalid-key-type = Invalid key type
+err-key-6-bytes = Key must be exactly 6 bytes
+err-invalid-hex-value = Invalid hex value '{ $value }'
+enter-key-hex-spaced = Enter key (6 bytes in hex format, e.g. 'FF FF FF FF FF FF'):
+
+# Reading, writing, cloning and magic cards
+title-read-card-uid = Reading Card UID
+uid-line = UID: { $uid }
+card-type = Card type: { $card_type }
+no-card-detected-during-timeout = No card detected during the timeout period.
+title-dump-sector = Dumping Sector { $sector }
+card-found-uid = Card detected. UID: { $uid }
+attempting-to-read-sector = Attempting to read sector { $sector }...
+sector-contents = Sector { $sector } contents:
+title-dump-card = Dumping Full Card
+dump-reads-all-sectors = This operation will attempt to read all accessible sectors.
+continue-y-n = Continue? (y/n):
+continue-anyway = Continue anyway?
+attempting-to-read-all-sectors = Attempting to read all sectors...
+sector-no-key = Sector { $sector }: no key
+dump-block-line = Block { $block }: { $hex }
+dump-completed-of-sectors-read = Dump completed, { $read } of { $total } sectors read.
+title-write-text = Write Text to Block
+enter-block-number-0-63 = Enter block number (0-63):
+err-invalid-block-number-must-be = Invalid block number. Must be 0-63.
+enter-text-to-write-max = Enter text to write (max 16 chars):
+text-too-long-will-be = Text too long. Will be truncated to 16 characters.
+placing-card-on-reader = Placing card on the reader...
+writing-to-block = Writing to block { $block }...
+write-data = Data: { $hex }
+write-operation-completed = Write operation completed.
+no-card-detected-within-timeout = No card detected within timeout period.
+title-write-hex = Write Hex to Block
+enter-hex-data-32-hex = Enter hex data (32 hex chars, no spaces):
+data-must-be-exactly-16 = Data must be exactly 16 bytes (32 hex chars).
+err-invalid-hex-data = Invalid hex data: { $error }
+title-clone-card = Clone Card
+clone-explained = This operation will read data from a source card and write it to a Magic Card.
+step-1-read-source-card = Step 1: Read source card
+place-source-card-on-reader = Place the SOURCE card on the reader...
+source-card-detected-uid = Source card detected. UID: { $uid }
+reading-card-data = Reading card data...
+no-source-card-detected = No source card detected.
+clone-different-uid = Do you want to use a different UID for the target card? (y/n):
+enter-new-uid-in-hex = Enter new UID in hex (e.g., 11:22:33:44):
+err-invalid-uid-length-must-be = Invalid UID length. Must be 4, 7, or 10 bytes.
+err-invalid-hex-format = Invalid hex format: { $error }
+step-2-write-to-target = Step 2: Write to target Magic Card
+place-target-magic-card-on = Place the TARGET Magic Card on the reader...
+target-card-detected-uid = Target card detected. UID: { $uid }
+warning-not-magic-card = Warning: This doesn't appear to be a Magic Card.
+changing-uid-to = Changing UID to: { $uid }
+writing-data-to-target-card = Writing data to target card...
+clone-operation-completed = Clone operation completed.
+no-target-card-detected = No target card detected.
+title-detect-card-type = Detect Card Type
+detect-explained = This will attempt to identify if the card is a Magic Card.
+this-appears-to-be-magic = This appears to be a Magic Card!
+magic-cards-allow-uid-changing = Magic Cards allow UID changing and bypass some security features.
+this-appears-to-be-standard = This appears to be a standard MIFARE card.
+no-specific-magic-card-features = No specific Magic Card features detected.
+detect-more-tests = Additional tests could be performed to verify Magic Card capabilities:
+direct-write-to-block-0 = Direct write to block 0 (UID block)
+testing-for-backdoor-commands = Testing for backdoor commands
+checking-for-direct-memory-operations = Checking for direct memory operations
+no-card-detected = No card detected.
+title-write-uid = Write Custom UID to Magic Card
+write-uid-magic-only = WARNING: This only works with Magic Cards that support UID changing!
+write-uid-may-damage = Using this on non-Magic Cards may DAMAGE your card permanently.
+new-uid-will-be = New UID will be: { $uid }
+write-uid-confirm = Are you ABSOLUTELY sure you want to proceed? This can brick your card! (y/n):
+place-magic-card-on-reader = Place the Magic Card on the reader...
+card-detected-current-uid = Card detected. Current UID: { $uid }
+write-uid-not-magic = WARNING: This does NOT appear to be a Magic Card!
+write-uid-damage-confirm = Proceeding might DAMAGE YOUR CARD PERMANENTLY! Continue? (y/n):
+attempting-to-change-uid = Attempting to change UID...
+uid-change-operation-completed = UID change operation completed.
+remove-card-and-place-it = Remove card and place it again to verify the new UID.
+
+# The reader and the attacks
+authenticating-with-block-mode-key = Authenticating with block={ $block }, mode={ $mode }, key={ $key }
+auth-buffer = Auth buffer: { $hex }
+auth-status = Auth status: { $status }, STATUS2: { $status2 }, Crypto bit: { $crypto_bit }
+auth-error-register = Error register: 0x{ $register }
+authentication-succeeded = Authentication succeeded!
+authentication-failed-status-not-ok = Authentication failed: status not OK
+special-auth-with = Special auth with: { $hex }
+auth-status2 = STATUS2 after auth: 0x{ $status2 }
+special-authentication-succeeded = Special authentication succeeded!
+special-authentication-failed = Special authentication failed
+err-while-reading = Error while reading!
+err-write-command = Write command failed: status={ $status }, back_len={ $back_len }
+err-while-writing-data = Error while writing data
+data-written-successfully-to-block = Data written successfully to block { $block }
+card-uid = Card UID: { $uid }
+default-key-a-works = Success with Key A: { $key }
+default-key-b-works = Success with Key B: { $key }
+no-default-key-works = Failed with all default keys
+reader-reconnecting = Reader status: { $status } - attempting to reconnect...
+reader-reconnected = Reader status: { $status } (after { $attempt } attempt(s))
+reconnect-attempt-failed = Reconnect attempt { $attempt }/{ $attempts } failed: { $error }
+reader-check-wiring = Reader status: { $status } - check the wiring and power, then try again
+mfrc522-version = MFRC522 Version: 0x{ $version }
+mfrc522-initialized-successfully = MFRC522 initialized successfully
+recording-card-transactions-to = Recording card transactions to { $path }
+session-recording-stopped = Session recording stopped: { $error }
+replay-recorded-answer = recorded: { $status } { $bits } bits { $received }  ({ $micros }us, now { $now_micros }us)
+antenna-state = Antenna state: 0x{ $state }
+title-darkside = Darkside Attack
+darkside-vulnerable-cards = This attack works on vulnerable MIFARE Classic cards
+darkside-takes-minutes = It may take a few minutes to complete
+enter-target-block = Enter target block number (0-63):
+title-nested = Nested Attack
+nested-needs-key = This attack requires you to already know at least one key
+enter-known-key-hex-format = Enter known key (hex format, e.g. 'FFFFFFFFFFFF'):
+err-invalid-key-length-must-be = Invalid key length: must be exactly 6 bytes (12 hex characters)
+err-invalid-key-format-please-enter = Invalid key format. Please enter 12 hex characters.
+enter-sector-number-where-this = Enter sector number where this key works (0-15):
+err-invalid-sector-number-must-be = Invalid sector number. Must be 0-15.
+err-invalid-key-type-must-be = Invalid key type. Must be A or B.
+starting-nested-attack-with-known = Starting nested attack with known key for block { $block }
+target-block = Target block: { $block }
+enter-known-block = Enter a block where this key works (0-63):
+known-key-type = Known key type (A or B):
+target-key-type = Target key type (A or B):
+key-type = Key type (A or B):
+enter-key-hex = Enter key (hex format, e.g. 'FFFFFFFFFFFF'):
+sector-key-works-on = Sector it works on
+starting-darkside-attack-on-block = Starting darkside attack on block { $block }
+darkside-vulnerable-only = This attack works only on vulnerable MIFARE Classic cards
+
+# Proxmark3
+pm3-keys-header = Sector | Key A        | Key B
+pm3-keys-found = { $found } of { $total } keys found
+menu-reader-proxmark3 = Reader: Proxmark3 on { $port }
+dump-card-contents-recovers-missing = Dump card contents (recovers missing keys)
+clone-dump-file-to-magic = Clone dump file to Magic Card
+card-analysis-health-and-counterfeit = Card analysis (health and counterfeit check)
+place-card-on-proxmark3 = Place a card on the Proxmark3...
+card-atqa = ATQA: { $atqa }
+card-sak = SAK: { $sak }
+title-default-keys = Trying Default Keys
+key-file-to-add-mfoc = Key file to add (mfoc -f list or .mfd dump, Enter for the default keys only):
+checking-keys-on-every-sector = Checking { $count } keys on every sector...
+no-card-answered-or-client = No card answered, or the client printed no key table.
+running-nested-attack-on-block = Running nested attack on block { $block }...
+found-for-sector = Found { $key_type } for sector { $sector }: { $key }
+attack-failed-cards-with-hardened = Attack failed. Cards with a hardened PRNG need the hardnested attack (option 7 runs it).
+pm3-darkside-weak-prng = This attack works on MIFARE Classic cards with the weak PRNG
+card-reports-hard-prng-darkside = The card reports a hard PRNG, darkside will most likely fail (option 11 tests it).
+pm3-darkside-starting = Starting darkside attack on block { $block }. This may take a few minutes...
+attack-successful = Attack successful!
+found-key-for-block = Found key for block { $block }: { $key }
+attack-failed-card-may-not = Attack failed. The card may not be vulnerable to the darkside attack.
+no-magic-capabilities = No magic capabilities detected, this is a standard card.
+magic-card-capabilities = This is a Magic Card: { $magic }
+card-prng = PRNG: { $prng }
+title-card-analysis = Card Analysis
+reading-block-0-with-default = Reading block 0 with the default keys...
+sampling-pairs-of-nonces = Sampling { $count } pairs of nonces...
+card-sent-no-nonces-it = The card sent no nonces, it may not be a MIFARE Classic.
+write-uid-gen1a-only = WARNING: This only works with Gen 1a Magic Cards!
+err-csetuid-uid-length = Invalid UID length. csetuid takes a 4 byte UID.
+set-uid-confirm = Set UID to { $uid }?
+uid-changed-to = UID changed to { $uid }
+err-change-uid-gen1a = Failed to change the UID. Is this a Gen 1a card?
+know-a-key = Do you already know a key for this card?
+running-autopwn-this-can-take = Running autopwn, this can take several minutes on hardened cards...
+dump-saved-to = Dump saved to { $path }
+client-did-not-save-dump = The client did not save a dump, not all keys could be recovered.
+place-card-on-proxmark3-dump = Place the card on the Proxmark3.
+save-copy-as-mfd-for = Save a copy as .mfd for mfoc / nfc-mfclassic (path, Enter to skip):
+dump-copied-to = Dump copied to { $path }
+no-key-recovery-build = Key recovery is not part of this build, clone from a dump file instead.
+dump-file-to-clone-from = Dump file to clone from (.mfd/.bin from mfoc, mfcuk or the PM3), Enter to read a card:
+place-source-card-on-proxmark3 = Place the SOURCE card on the Proxmark3.
+err-cannot-clone-without-complete-dump = Cannot clone without a complete dump.
+place-target-gen-1a-magic = Place the TARGET Gen 1a Magic Card on the Proxmark3 and press Enter...
+target-card-doesnt-report-gen = Target card { $uid } doesn't report Gen 1a magic capabilities.
+err-clone-write-failed = Writing the dump to the target card failed.
+
+# Jobs and attack results
+stage-nonces = nonces
+stage-keys = keys
+stage-dump = sectors read
+status-queued = queued
+status-running = running, { $progress }
+status-waiting-for-card = waiting for the card
+status-finished = done: { $summary }
+status-cancelled = cancelled
+status-failed = failed: { $error }
+err-job-worker-stopped = the job worker has stopped
+err-reader-busy = The reader is busy with an attack, wait for it or cancel it
+job-card-read = Card { $uid } read
+err-no-card-in-seconds = No card detected in { $seconds } seconds
+summary-not-saved = , not saved: { $error }
+summary-new-in-key-store = , { $count } new in the key store
+summary-not-added-to-key-store = , not added to the key store: { $error }
+err-no-nonces = The card sent no nonces, it may not be a MIFARE Classic
+err-darkside-prng = Darkside can't work on this card, its PRNG is { $prng }
+err-nested-prng = Nested can't work on this card, its PRNG is { $prng }
+err-key-doesnt-open = The key doesn't open { $key_type } of sector { $sector }
+autopwn-no-way-in = No default key opens a sector and darkside can't work on this card, its PRNG is { $prng }
+autopwn-hardnested-on-pm3 = Hardnested runs on the Proxmark3 on { $port }, put the card on it
+autopwn-pm3-wrong-card = The Proxmark3 has card { $found }, not { $uid }
+autopwn-no-card-on-pm3 = The card wasn't put on the Proxmark3 in { $seconds } seconds
+autopwn-card-back-on-mfrc522 = Put the card back on the MFRC522 for the dump
+autopwn-no-card-on-mfrc522 = The card wasn't put back on the MFRC522 in { $seconds } seconds
+result-summary = { $attack }: { $keys } keys for { $sectors } of { $attacked } sectors in { $seconds }s, { $nonces } nonces
+result-card = Card { $uid } ({ $card_type })
+result-likely-magic = , likely a magic card
+result-prng = , PRNG { $prng }
+result-sector = Sector
+result-key-a = Key A
+result-key-b = Key B
+result-dump-saved = Dump saved to { $path }
+reader-connected = Connected
+reader-disconnected = Disconnected
+
+# PRNG and card analysis
+prng-report = PRNG: { $kind } ({ $samples } samples)
+prng-static-darkside = Darkside: no, the card never changes its nonce
+prng-static-nested = Nested: no, a static nested attack (Proxmark3 `hf mf staticnested`) is needed
+prng-hard-darkside = Darkside: no, the nonces aren't predictable
+prng-hard-nested = Nested: no, only hardnested works (option 7 on the Proxmark3)
+prng-repeated = Same nonce after a field reset: { $repeated } of { $samples } samples
+prng-distance = Nonce distance between two authentications: { $min } to { $max } steps
+prng-weak-darkside = Darkside: yes, the card repeats its nonce
+prng-weak-darkside-unlikely = Darkside: unlikely, the reader's timing doesn't reproduce the nonce often enough
+prng-weak-nested = Nested: yes, the nonce distances are predictable
+prng-weak-nested-unlikely = Nested: unlikely from this reader, the nonce distances vary too much
+analysis-title = Card analysis for UID { $uid }
+analysis-verdict = Verdict: { $verdict } ({ $confidence } confidence)
+confidence-high = high
+confidence-medium = medium
+confidence-low = low
+verdict-genuine = genuine NXP MIFARE Classic
+verdict-clone = clone chip (not made by NXP)
+verdict-magic = magic card (UID and block 0 can be rewritten)
+verdict-inconclusive = inconclusive
+check-atqa-sak = ATQA/SAK
+check-block0 = Block 0
+check-fingerprint = Chip fingerprint
+check-prng = PRNG
+check-magic = Magic commands
+not-reported = not reported
+atqa-sak-seen = ATQA { $atqa } SAK { $sak }, { $uid_len } byte UID
+atqa-announces-uid-len = { $seen }: the ATQA announces a { $uid_len } byte UID
+atqa-sak-no-classic = { $seen }: no MIFARE Classic answers with this combination
+atqa-sak-not-classic = { $seen }: not a MIFARE Classic
+block0-not-readable = not readable with the default keys
+block0-uid-mismatch = holds UID { $held } but the card answers as { $uid }
+block0-bad-bcc = BCC is { $bcc }, the UID needs { $needed }
+block0-clone-manufacturer-data = manufacturer data 62 63 64 65 66 67 68 69, the default of Chinese clones
+block0-consistent = consistent ({ $block })
+fingerprint-fudan = UID manufacturer code 1D (Shanghai Fudan)
+fingerprint-nxp = UID manufacturer code 04 (NXP)
+fingerprint-not-nxp = UID manufacturer code { $code } is not NXP
+fingerprint-none = no known clone chip recognized
+prng-check-static = static nonce, every authentication gets the same challenge
+prng-check-weak = weak (original MIFARE Classic and most clones), darkside and nested apply
+prng-check-hard = hard (MIFARE Classic EV1 or later), only hardnested applies
+magic-none-answered = none answered
+
+# Key reuse and weak keys
+spread-one-card = 1 card
+spread-same = same on all { $cards } cards
+spread-diversified = diversified, { $keys } keys
+spread-keys-for-cards = { $keys } keys for { $cards } cards
+reuse-title = Key Reuse: { $cards } cards, { $lists } key lists
+reuse-cards = Cards
+reuse-single-key = one key on every sector ({ $key })
+reuse-default-sectors = default keys on { $count } sectors
+reuse-diversified = diversified, no key shared with another card
+reuse-shared-keys = { $count } keys shared with other cards
+reuse-sectors = { $count } sectors
+reuse-sectors-across = Sectors across the cards
+reuse-keys-on-cards = Keys on more than one card
+reuse-key-counts = { $cards } cards, { $sector_keys } sector keys
+reuse-default-key = default key
+reuse-key-lists = Key lists
+reuse-key-list = { $path }: { $keys } keys, { $default } default, { $used } used by the cards
+reuse-summary = Summary
+reuse-cards-default-keys = Cards with default keys:
+reuse-cards-single-key = Cards with one key on every sector:
+reuse-cards-diversified = Cards with diversified keys:
+count-of = { $count } of { $total }
+report-skipped = Skipped { $path }: { $reason }
+report-yes = yes
+report-no = no
+weak-title = Weak Keys: { $weak } of { $total } keys
+weak-key = Key
+weak-default = Default
+weak-dictionary = Dictionary
+weak-cards = Cards
+weak-sector-keys = Sector keys
+weak-low-entropy = Low entropy
+weak-strong-keys = { $count } other keys are in no dictionary and look random
+weak-by-sector = Sector keys on the cards, by sector
+weak-keys = Keys
+weak-weak = Weak
@@ -0,0 +1,387 @@
+# Español
+
+# Arranque y línea de comandos
+err-error = Error: { $error }
+no-proxmark3-found = No se encontró ningún cliente ni dispositivo Proxmark3 (defina PM3_CLIENT / PM3_PORT)
+csv-written-to = CSV escrito en { $path }
+err-writing-file = Error al escribir { $path }: { $error }
+replay-transactions = Reproduciendo { $count } transacciones de { $path }
+replay-transactions-timed = Reproduciendo { $count } transacciones de { $path } con los tiempos grabados
+err-loading-session = Error al cargar la sesión { $path }: { $error }
+replay-stopped = Reproducción detenida: { $error }
+replay-place-card = Ponga la misma tarjeta en el lector y pulse Enter...
+replay-answers-matched = Las { $replayed } respuestas coinciden con la grabación.
+replay-answers-differ = { $count } de { $replayed } respuestas difieren (transacciones { $transactions })
+built-without-gui = Compilado sin la función gui, se usa el menú
+title-attack-manager = Gestor de ataques Mifare
+banner-tears-for-fears = Basado en los algoritmos de Proxmark3 y el enfoque 'Tears For Fears'
+press-ctrl-c-to-exit = Pulse Ctrl+C para salir
+proxmark3-on = Proxmark3 en { $port }: { $version }
+err-talking-to-proxmark3-on = Error al comunicarse con el Proxmark3 en { $port }: { $error }
+title-toolkit = Kit de ataques MIFARE
+banner-ported = Basado en los algoritmos de Proxmark3 portados a Rust
+banner-readers = Compatible con el MFRC522 en Raspberry Pi y con un Proxmark3 (--pm3 [PUERTO])
+built-without-attacks = Compilado sin la función attacks: la recuperación de claves no está disponible
+read-only-mode = MODO SOLO LECTURA ({ $reason }): los ataques y la escritura de tarjetas están desactivados
+err-initializing-mfrc522 = Error al inicializar el MFRC522: { $error }
+found-proxmark3-using-it-instead = Se encontró un Proxmark3, se usa en su lugar
+err-starting-session-recording = Error al iniciar la grabación de la sesión: { $error }
+transactions-recorded = { $count } transacciones grabadas
+consent-type-phrase = Escriba '{ $phrase }' para aceptar, cualquier otra cosa para volver:
+consent-not-accepted = No aceptado, los ataques siguen sin estar disponibles.
+consent-changes-card = '{ $operation }' cambia para siempre la tarjeta que está en el lector.
+consent-authorized = Asegúrese de estar autorizado a cambiarla. ¿Continuar durante esta sesión? (y/n):
+err-writing-audit-log = Error al escribir el registro de auditoría { $path }: { $error }
+please-remove-card-from-reader = Retire la tarjeta del lector...
+card-removed = Tarjeta retirada
+hold-card-near-reader = Acerque una tarjeta al lector...
+seconds-to-place-card = Tiene { $seconds } segundos para poner una tarjeta
+card-detected-uid = ¡Tarjeta detectada! UID: { $uid }
+no-card-in-time = No se detectó ninguna tarjeta en el tiempo indicado.
+press-enter-to-continue = Pulse Enter para continuar...
+
+# Menús
+cancelling-attacks-still-running = Cancelando los ataques que siguen en marcha...
+enter-choice = Elija una opción:
+exiting = Saliendo...
+err-invalid-choice-please-try-again = Opción no válida. Inténtelo de nuevo.
+job-queued = Trabajo { $id } ({ $job }) en cola detrás del que está en marcha. 11 lista los trabajos, 12 los cancela.
+job-submitted = Trabajo { $id } ({ $job }) iniciado, ponga la tarjeta en el lector. 11 lista los trabajos, 12 los cancela.
+no-attacks-run-yet = Todavía no se ha ejecutado ningún ataque.
+cancelling-running-attack-stops-at = Cancelando, el ataque en marcha se detiene en su siguiente paso. Sus nonces se guardan para la próxima vez.
+no-attack-is-running = No hay ningún ataque en marcha.
+operation-cancelled = Operación cancelada.
+menu-reader = Lector: { $status }
+menu-reader-busy = Lector: ocupado con un ataque
+select-option = Seleccione una opción:
+read-card-uid = Leer el UID de la tarjeta
+try-default-keys = Probar las claves por defecto
+run-nested-attack = Ejecutar el ataque nested (requiere una clave conocida)
+run-darkside-attack = Ejecutar el ataque darkside
+detect-magic-card = Detectar una tarjeta mágica
+write-custom-uid = Escribir un UID personalizado (requiere una tarjeta mágica)
+dump-card-contents = Volcar el contenido de la tarjeta
+clone-card-to-magic-card = Clonar la tarjeta en una tarjeta mágica
+test-prng = Probar el PRNG (¿funcionará darkside / nested?)
+show-attack-jobs = Mostrar los trabajos de ataque
+cancel-attack-jobs = Cancelar los trabajos de ataque
+autopwn = Autopwn: recuperar todas las claves y volcar la tarjeta
+menu-exit = Salir
+title-prng-test = Prueba del PRNG
+job-started = [trabajo { $id }] { $job } iniciado
+job-reader = [trabajo { $id }] Lector: { $status }
+job-card = [trabajo { $id }] Tarjeta { $uid } ({ $card_type })
+job-card-magic = [trabajo { $id }] Tarjeta { $uid } ({ $card_type }), probablemente una tarjeta mágica
+job-found-of-sector = [trabajo { $id }] Encontrada { $key_type } del sector { $sector }: { $key }
+job-prng-report = [trabajo { $id }]
+    { $report }
+job-result = [trabajo { $id }]
+    { $result }
+saved-to = Guardado en { $path }
+job-finished = [trabajo { $id }] { $summary }
+job-cancelled = [trabajo { $id }] Cancelado
+job-failed = [trabajo { $id }] Falló: { $error }
+err-invalid-sector-number = Número de sector no válido
+err-invalid-block-number = Número de bloque no válido
+enter-key-type = Introduzca el tipo de clave (A o B):
+err-invalid-key-type = Tipo de clave no válido
+err-key-6-bytes = La clave debe tener exactamente 6 bytes
+err-invalid-hex-value = Valor hex no válido '{ $value }'
+enter-key-hex-spaced = Introduzca la clave (6 bytes en hex, p. ej. 'FF FF FF FF FF FF'):
+
+# Lectura, escritura, clonado y tarjetas mágicas
+title-read-card-uid = Leyendo el UID de la tarjeta
+uid-line = UID: { $uid }
+card-type = Tipo de tarjeta: { $card_type }
+no-card-detected-during-timeout = No se detectó ninguna tarjeta durante el tiempo de espera.
+title-dump-sector = Volcando el sector { $sector }
+card-found-uid = Tarjeta detectada. UID: { $uid }
+attempting-to-read-sector = Intentando leer el sector { $sector }...
+sector-contents = Contenido del sector { $sector }:
+title-dump-card = Volcando la tarjeta completa
+dump-reads-all-sectors = Esta operación intentará leer todos los sectores accesibles.
+continue-y-n = ¿Continuar? (y/n):
+continue-anyway = ¿Continuar de todos modos?
+attempting-to-read-all-sectors = Intentando leer todos los sectores...
+sector-no-key = Sector { $sector }: sin clave
+dump-block-line = Bloque { $block }: { $hex }
+dump-completed-of-sectors-read = Volcado terminado, { $read } de { $total } sectores leídos.
+title-write-text = Escribir texto en un bloque
+enter-block-number-0-63 = Introduzca el número de bloque (0-63):
+err-invalid-block-number-must-be = Número de bloque no válido. Debe estar entre 0 y 63.
+enter-text-to-write-max = Introduzca el texto que escribir (máx. 16 caracteres):
+text-too-long-will-be = Texto demasiado largo. Se recortará a 16 caracteres.
+placing-card-on-reader = Ponga la tarjeta en el lector...
+writing-to-block = Escribiendo en el bloque { $block }...
+write-data = Datos: { $hex }
+write-operation-completed = Escritura terminada.
+no-card-detected-within-timeout = No se detectó ninguna tarjeta dentro del tiempo de espera.
+title-write-hex = Escribir hex en un bloque
+enter-hex-data-32-hex = Introduzca los datos en hex (32 caracteres hex, sin espacios):
+data-must-be-exactly-16 = Los datos deben tener exactamente 16 bytes (32 caracteres hex).
+err-invalid-hex-data = Datos hex no válidos: { $error }
+title-clone-card = Clonar tarjeta
+clone-explained = Esta operación lee los datos de una tarjeta de origen y los escribe en una tarjeta mágica.
+step-1-read-source-card = Paso 1: leer la tarjeta de origen
+place-source-card-on-reader = Ponga la tarjeta de ORIGEN en el lector...
+source-card-detected-uid = Tarjeta de origen detectada. UID: { $uid }
+reading-card-data = Leyendo los datos de la tarjeta...
+no-source-card-detected = No se detectó ninguna tarjeta de origen.
+clone-different-uid = ¿Quiere usar otro UID para la tarjeta de destino? (y/n):
+enter-new-uid-in-hex = Introduzca el nuevo UID en hex (p. ej. 11:22:33:44):
+err-invalid-uid-length-must-be = Longitud de UID no válida. Debe ser de 4, 7 o 10 bytes.
+err-invalid-hex-format = Formato hex no válido: { $error }
+step-2-write-to-target = Paso 2: escribir en la tarjeta mágica de destino
+place-target-magic-card-on = Ponga la tarjeta mágica de DESTINO en el lector...
+target-card-detected-uid = Tarjeta de destino detectada. UID: { $uid }
+warning-not-magic-card = Atención: no parece una tarjeta mágica.
+changing-uid-to = Cambiando el UID a: { $uid }
+writing-data-to-target-card = Escribiendo los datos en la tarjeta de destino...
+clone-operation-completed = Clonado terminado.
+no-target-card-detected = No se detectó ninguna tarjeta de destino.
+title-detect-card-type = Detectar el tipo de tarjeta
+detect-explained = Se intentará averiguar si la tarjeta es una tarjeta mágica.
+this-appears-to-be-magic = ¡Parece una tarjeta mágica!
+magic-cards-allow-uid-changing = Las tarjetas mágicas permiten cambiar el UID y saltarse algunas protecciones.
+this-appears-to-be-standard = Parece una tarjeta MIFARE normal.
+no-specific-magic-card-features = No se detectó ninguna función propia de las tarjetas mágicas.
+detect-more-tests = Se podrían hacer más pruebas para comprobar las funciones de tarjeta mágica:
+direct-write-to-block-0 = Escritura directa en el bloque 0 (bloque del UID)
+testing-for-backdoor-commands = Prueba de comandos de puerta trasera
+checking-for-direct-memory-operations = Comprobación de operaciones directas en memoria
+no-card-detected = No se detectó ninguna tarjeta.
+title-write-uid = Escribir un UID personalizado en una tarjeta mágica
+write-uid-magic-only = ATENCIÓN: ¡Solo funciona con tarjetas mágicas que permiten cambiar el UID!
+write-uid-may-damage = Usarlo con tarjetas que no son mágicas puede DAÑAR la tarjeta para siempre.
+new-uid-will-be = El nuevo UID será: { $uid }
+write-uid-confirm = ¿Está TOTALMENTE seguro de que quiere seguir? ¡Esto puede inutilizar la tarjeta! (y/n):
+place-magic-card-on-reader = Ponga la tarjeta mágica en el lector...
+card-detected-current-uid = Tarjeta detectada. UID actual: { $uid }
+write-uid-not-magic = ATENCIÓN: ¡NO parece una tarjeta mágica!
+write-uid-damage-confirm = ¡Seguir puede DAÑAR LA TARJETA PARA SIEMPRE! ¿Continuar? (y/n):
+attempting-to-change-uid = Intentando cambiar el UID...
+uid-change-operation-completed = Cambio de UID terminado.
+remove-card-and-place-it = Retire la tarjeta y vuelva a ponerla para comprobar el nuevo UID.
+
+# El lector y los ataques
+authenticating-with-block-mode-key = Autenticando con bloque={ $block }, modo={ $mode }, clave={ $key }
+auth-buffer = Búfer de autenticación: { $hex }
+auth-status = Estado de la autenticación: { $status }, STATUS2: { $status2 }, bit Crypto: { $crypto_bit }
+auth-error-register = Registro de errores: 0x{ $register }
+authentication-succeeded = ¡Autenticación correcta!
+authentication-failed-status-not-ok = La autenticación falló: el estado no es OK
+special-auth-with = Autenticación especial con: { $hex }
+auth-status2 = STATUS2 tras la autenticación: 0x{ $status2 }
+special-authentication-succeeded = ¡Autenticación especial correcta!
+special-authentication-failed = La autenticación especial falló
+err-while-reading = ¡Error durante la lectura!
+err-write-command = El comando de escritura falló: status={ $status }, back_len={ $back_len }
+err-while-writing-data = Error al escribir los datos
+data-written-successfully-to-block = Datos escritos correctamente en el bloque { $block }
+card-uid = UID de la tarjeta: { $uid }
+default-key-a-works = Funciona con la clave A: { $key }
+default-key-b-works = Funciona con la clave B: { $key }
+no-default-key-works = No funciona ninguna clave por defecto
+reader-reconnecting = Estado del lector: { $status } - intentando reconectar...
+reader-reconnected = Estado del lector: { $status } (tras { $attempt } intento(s))
+reconnect-attempt-failed = El intento de reconexión { $attempt }/{ $attempts } falló: { $error }
+reader-check-wiring = Estado del lector: { $status } - revise el cableado y la alimentación y vuelva a intentarlo
+mfrc522-version = Versión del MFRC522: 0x{ $version }
+mfrc522-initialized-successfully = MFRC522 inicializado correctamente
+recording-card-transactions-to = Grabando las transacciones de la tarjeta en { $path }
+session-recording-stopped = Grabación de la sesión detenida: { $error }
+replay-recorded-answer = grabado: { $status } { $bits } bits { $received }  ({ $micros }us, ahora { $now_micros }us)
+antenna-state = Estado de la antena: 0x{ $state }
+title-darkside = Ataque darkside
+darkside-vulnerable-cards = Este ataque funciona con tarjetas MIFARE Classic vulnerables
+darkside-takes-minutes = Puede tardar unos minutos
+enter-target-block = Introduzca el número del bloque objetivo (0-63):
+title-nested = Ataque nested
+nested-needs-key = Este ataque requiere conocer ya al menos una clave
+enter-known-key-hex-format = Introduzca la clave conocida (en hex, p. ej. 'FFFFFFFFFFFF'):
+err-invalid-key-length-must-be = Longitud de clave no válida: debe tener exactamente 6 bytes (12 caracteres hex)
+err-invalid-key-format-please-enter = Formato de clave no válido. Introduzca 12 caracteres hex.
+enter-sector-number-where-this = Introduzca el número del sector en el que funciona esta clave (0-15):
+err-invalid-sector-number-must-be = Número de sector no válido. Debe estar entre 0 y 15.
+err-invalid-key-type-must-be = Tipo de clave no válido. Debe ser A o B.
+starting-nested-attack-with-known = Iniciando el ataque nested con la clave conocida del bloque { $block }
+target-block = Bloque objetivo: { $block }
+enter-known-block = Introduzca un bloque en el que funcione esta clave (0-63):
+known-key-type = Tipo de la clave conocida (A o B):
+target-key-type = Tipo de la clave objetivo (A o B):
+key-type = Tipo de clave (A o B):
+enter-key-hex = Introduzca la clave (en hex, p. ej. 'FFFFFFFFFFFF'):
+sector-key-works-on = Sector en el que funciona
+starting-darkside-attack-on-block = Iniciando el ataque darkside en el bloque { $block }
+darkside-vulnerable-only = Este ataque solo funciona con tarjetas MIFARE Classic vulnerables
+
+# Proxmark3
+pm3-keys-header = Sector | Clave A      | Clave B
+pm3-keys-found = { $found } de { $total } claves encontradas
+menu-reader-proxmark3 = Lector: Proxmark3 en { $port }
+dump-card-contents-recovers-missing = Volcar el contenido de la tarjeta (recupera las claves que faltan)
+clone-dump-file-to-magic = Clonar un archivo de volcado en una tarjeta mágica
+card-analysis-health-and-counterfeit = Análisis de la tarjeta (estado y detección de falsificaciones)
+place-card-on-proxmark3 = Ponga una tarjeta en el Proxmark3...
+card-atqa = ATQA: { $atqa }
+card-sak = SAK: { $sak }
+title-default-keys = Probando las claves por defecto
+key-file-to-add-mfoc = Archivo de claves que añadir (lista de mfoc -f o volcado .mfd, Enter para usar solo las claves por defecto):
+checking-keys-on-every-sector = Comprobando { $count } claves en cada sector...
+no-card-answered-or-client = Ninguna tarjeta respondió, o el cliente no mostró ninguna tabla de claves.
+running-nested-attack-on-block = Ejecutando el ataque nested en el bloque { $block }...
+found-for-sector = Encontrada { $key_type } del sector { $sector }: { $key }
+attack-failed-cards-with-hardened = El ataque falló. Las tarjetas con un PRNG reforzado necesitan el ataque hardnested (la opción 7 lo ejecuta).
+pm3-darkside-weak-prng = Este ataque funciona con tarjetas MIFARE Classic con el PRNG débil
+card-reports-hard-prng-darkside = La tarjeta indica un PRNG reforzado, lo más probable es que darkside falle (la opción 11 lo comprueba).
+pm3-darkside-starting = Iniciando el ataque darkside en el bloque { $block }. Puede tardar unos minutos...
+attack-successful = ¡Ataque con éxito!
+found-key-for-block = Clave encontrada para el bloque { $block }: { $key }
+attack-failed-card-may-not = El ataque falló. Puede que la tarjeta no sea vulnerable al ataque darkside.
+no-magic-capabilities = No se detectaron funciones mágicas, es una tarjeta normal.
+magic-card-capabilities = Es una tarjeta mágica: { $magic }
+card-prng = PRNG: { $prng }
+title-card-analysis = Análisis de la tarjeta
+reading-block-0-with-default = Leyendo el bloque 0 con las claves por defecto...
+sampling-pairs-of-nonces = Tomando { $count } pares de nonces...
+card-sent-no-nonces-it = La tarjeta no envió ningún nonce, puede que no sea una MIFARE Classic.
+write-uid-gen1a-only = ATENCIÓN: ¡Solo funciona con tarjetas mágicas Gen 1a!
+err-csetuid-uid-length = Longitud de UID no válida. csetuid admite un UID de 4 bytes.
+set-uid-confirm = ¿Cambiar el UID a { $uid }?
+uid-changed-to = UID cambiado a { $uid }
+err-change-uid-gen1a = No se pudo cambiar el UID. ¿Es una tarjeta Gen 1a?
+know-a-key = ¿Conoce ya alguna clave de esta tarjeta?
+running-autopwn-this-can-take = Ejecutando autopwn, puede tardar varios minutos con tarjetas reforzadas...
+dump-saved-to = Volcado guardado en { $path }
+client-did-not-save-dump = El cliente no guardó ningún volcado, no se pudieron recuperar todas las claves.
+place-card-on-proxmark3-dump = Ponga la tarjeta en el Proxmark3.
+save-copy-as-mfd-for = Guardar una copia en .mfd para mfoc / nfc-mfclassic (ruta, Enter para omitir):
+dump-copied-to = Volcado copiado en { $path }
+no-key-recovery-build = La recuperación de claves no forma parte de esta compilación, clone desde un archivo de volcado.
+dump-file-to-clone-from = Archivo de volcado del que clonar (.mfd/.bin de mfoc, mfcuk o el PM3), Enter para leer una tarjeta:
+place-source-card-on-proxmark3 = Ponga la tarjeta de ORIGEN en el Proxmark3.
+err-cannot-clone-without-complete-dump = No se puede clonar sin un volcado completo.
+place-target-gen-1a-magic = Ponga la tarjeta mágica Gen 1a de DESTINO en el Proxmark3 y pulse Enter...
+target-card-doesnt-report-gen = La tarjeta de destino { $uid } no indica funciones mágicas Gen 1a.
+err-clone-write-failed = Falló la escritura del volcado en la tarjeta de destino.
+
+# Trabajos y resultados de los ataques
+stage-nonces = nonces
+stage-keys = claves
+stage-dump = sectores leídos
+status-queued = en cola
+status-running = en marcha, { $progress }
+status-waiting-for-card = esperando la tarjeta
+status-finished = terminado: { $summary }
+status-cancelled = cancelado
+status-failed = falló: { $error }
+err-job-worker-stopped = el hilo de los trabajos se ha detenido
+err-reader-busy = El lector está ocupado con un ataque, espere a que termine o cancélelo
+job-card-read = Tarjeta { $uid } leída
+err-no-card-in-seconds = No se detectó ninguna tarjeta en { $seconds } segundos
+summary-not-saved = , no guardado: { $error }
+summary-new-in-key-store = , { $count } nuevas en el almacén de claves
+summary-not-added-to-key-store = , no añadidas al almacén de claves: { $error }
+err-no-nonces = La tarjeta no envió ningún nonce, puede que no sea una MIFARE Classic
+err-darkside-prng = Darkside no funciona con esta tarjeta, su PRNG es { $prng }
+err-nested-prng = Nested no funciona con esta tarjeta, su PRNG es { $prng }
+err-key-doesnt-open = La clave no abre { $key_type } del sector { $sector }
+autopwn-no-way-in = Ninguna clave por defecto abre un sector y darkside no funciona con esta tarjeta, su PRNG es { $prng }
+autopwn-hardnested-on-pm3 = Hardnested se ejecuta en el Proxmark3 en { $port }, ponga la tarjeta en él
+autopwn-pm3-wrong-card = El Proxmark3 tiene la tarjeta { $found }, no { $uid }
+autopwn-no-card-on-pm3 = La tarjeta no se puso en el Proxmark3 en { $seconds } segundos
+autopwn-card-back-on-mfrc522 = Vuelva a poner la tarjeta en el MFRC522 para el volcado
+autopwn-no-card-on-mfrc522 = La tarjeta no se volvió a poner en el MFRC522 en { $seconds } segundos
+result-summary = { $attack }: { $keys } claves para { $sectors } de { $attacked } sectores en { $seconds }s, { $nonces } nonces
+result-card = Tarjeta { $uid } ({ $card_type })
+result-likely-magic = , probablemente una tarjeta mágica
+result-prng = , PRNG { $prng }
+result-sector = Sector
+result-key-a = Clave A
+result-key-b = Clave B
+result-dump-saved = Volcado guardado en { $path }
+reader-connected = Conectado
+reader-disconnected = Desconectado
+
+# PRNG y análisis de la tarjeta
+prng-report = PRNG: { $kind } ({ $samples } muestras)
+prng-static-darkside = Darkside: no, la tarjeta nunca cambia su nonce
+prng-static-nested = Nested: no, hace falta un ataque static nested (Proxmark3 `hf mf staticnested`)
+prng-hard-darkside = Darkside: no, los nonces no son predecibles
+prng-hard-nested = Nested: no, solo funciona hardnested (opción 7 en el Proxmark3)
+prng-repeated = Mismo nonce tras reiniciar el campo: { $repeated } de { $samples } muestras
+prng-distance = Distancia entre los nonces de dos autenticaciones: de { $min } a { $max } pasos
+prng-weak-darkside = Darkside: sí, la tarjeta repite su nonce
+prng-weak-darkside-unlikely = Darkside: poco probable, los tiempos del lector no reproducen el nonce con suficiente frecuencia
+prng-weak-nested = Nested: sí, las distancias entre nonces son predecibles
+prng-weak-nested-unlikely = Nested: poco probable con este lector, las distancias entre nonces varían demasiado
+analysis-title = Análisis de la tarjeta con UID { $uid }
+analysis-verdict = Veredicto: { $verdict } (confianza { $confidence })
+confidence-high = alta
+confidence-medium = media
+confidence-low = baja
+verdict-genuine = MIFARE Classic original de NXP
+verdict-clone = chip clonado (no fabricado por NXP)
+verdict-magic = tarjeta mágica (el UID y el bloque 0 se pueden reescribir)
+verdict-inconclusive = no concluyente
+check-atqa-sak = ATQA/SAK
+check-block0 = Bloque 0
+check-fingerprint = Huella del chip
+check-prng = PRNG
+check-magic = Comandos mágicos
+not-reported = no indicado
+atqa-sak-seen = ATQA { $atqa } SAK { $sak }, UID de { $uid_len } bytes
+atqa-announces-uid-len = { $seen }: el ATQA anuncia un UID de { $uid_len } bytes
+atqa-sak-no-classic = { $seen }: ninguna MIFARE Classic responde con esta combinación
+atqa-sak-not-classic = { $seen }: no es una MIFARE Classic
+block0-not-readable = no se puede leer con las claves por defecto
+block0-uid-mismatch = contiene el UID { $held } pero la tarjeta responde como { $uid }
+block0-bad-bcc = el BCC es { $bcc }, el UID necesita { $needed }
+block0-clone-manufacturer-data = datos de fabricante 62 63 64 65 66 67 68 69, los de por defecto de los clones chinos
+block0-consistent = coherente ({ $block })
+fingerprint-fudan = código de fabricante del UID 1D (Shanghai Fudan)
+fingerprint-nxp = código de fabricante del UID 04 (NXP)
+fingerprint-not-nxp = el código de fabricante del UID { $code } no es de NXP
+fingerprint-none = no se reconoció ningún chip clonado conocido
+prng-check-static = nonce estático, cada autenticación recibe el mismo desafío
+prng-check-weak = débil (MIFARE Classic original y la mayoría de los clones), se aplican darkside y nested
+prng-check-hard = reforzado (MIFARE Classic EV1 o posterior), solo se aplica hardnested
+magic-none-answered = ninguno respondió
+
+# Reutilización de claves y claves débiles
+spread-one-card = 1 tarjeta
+spread-same = igual en las { $cards } tarjetas
+spread-diversified = diversificada, { $keys } claves
+spread-keys-for-cards = { $keys } claves para { $cards } tarjetas
+reuse-title = Reutilización de claves: { $cards } tarjetas, { $lists } listas de claves
+reuse-cards = Tarjetas
+reuse-single-key = una clave en todos los sectores ({ $key })
+reuse-default-sectors = claves por defecto en { $count } sectores
+reuse-diversified = diversificadas, ninguna clave compartida con otra tarjeta
+reuse-shared-keys = { $count } claves compartidas con otras tarjetas
+reuse-sectors = { $count } sectores
+reuse-sectors-across = Sectores en todas las tarjetas
+reuse-keys-on-cards = Claves en más de una tarjeta
+reuse-key-counts = { $cards } tarjetas, { $sector_keys } claves de sector
+reuse-default-key = clave por defecto
+reuse-key-lists = Listas de claves
+reuse-key-list = { $path }: { $keys } claves, { $default } por defecto, { $used } usadas por las tarjetas
+reuse-summary = Resumen
+reuse-cards-default-keys = Tarjetas con claves por defecto:
+reuse-cards-single-key = Tarjetas con una clave en todo:
+reuse-cards-diversified = Tarjetas con claves diversificadas:
+count-of = { $count } de { $total }
+report-skipped = Omitido { $path }: { $reason }
+report-yes = sí
+report-no = no
+weak-title = Claves débiles: { $weak } de { $total } claves
+weak-key = Clave
+weak-default = Por defecto
+weak-dictionary = Diccionario
+weak-cards = Tarjetas
+weak-sector-keys = Claves de sector
+weak-low-entropy = Baja entropía
+weak-strong-keys = { $count } claves más no están en ningún diccionario y parecen aleatorias
+weak-by-sector = Claves de sector en las tarjetas, por sector
+weak-keys = Claves
+weak-weak = Débiles
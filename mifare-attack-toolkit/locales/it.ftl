@@ -0,0 +1,387 @@
+# Italiano
+
+# Avvio e riga di comando
+err-error = Errore: { $error }
+no-proxmark3-found = Nessun client o dispositivo Proxmark3 trovato (impostare PM3_CLIENT / PM3_PORT)
+csv-written-to = CSV scritto in { $path }
+err-writing-file = Errore durante la scrittura di { $path }: { $error }
+replay-transactions = Riproduzione di { $count } transazioni da { $path }
+replay-transactions-timed = Riproduzione di { $count } transazioni da { $path } con i tempi registrati
+err-loading-session = Errore durante il caricamento della sessione { $path }: { $error }
+replay-stopped = Riproduzione interrotta: { $error }
+replay-place-card = Metta la stessa tessera sul lettore e prema Invio...
+replay-answers-matched = Tutte le { $replayed } risposte corrispondono alla registrazione.
+replay-answers-differ = { $count } risposte su { $replayed } sono diverse (transazioni { $transactions })
+built-without-gui = Compilato senza la funzione gui, si usa il menu
+title-attack-manager = Gestore degli attacchi Mifare
+banner-tears-for-fears = Basato sugli algoritmi del Proxmark3 e sull'approccio 'Tears For Fears'
+press-ctrl-c-to-exit = Premere Ctrl+C per uscire
+proxmark3-on = Proxmark3 su { $port }: { $version }
+err-talking-to-proxmark3-on = Errore nella comunicazione con il Proxmark3 su { $port }: { $error }
+title-toolkit = Kit di attacco MIFARE
+banner-ported = Basato sugli algoritmi del Proxmark3 portati in Rust
+banner-readers = Compatibile con l'MFRC522 su Raspberry Pi e con un Proxmark3 (--pm3 [PORTA])
+built-without-attacks = Compilato senza la funzione attacks: il recupero delle chiavi non è disponibile
+read-only-mode = MODALITÀ SOLA LETTURA ({ $reason }): attacchi e scritture sulle tessere sono disattivati
+err-initializing-mfrc522 = Errore durante l'inizializzazione dell'MFRC522: { $error }
+found-proxmark3-using-it-instead = Trovato un Proxmark3, si usa quello
+err-starting-session-recording = Errore all'avvio della registrazione della sessione: { $error }
+transactions-recorded = { $count } transazioni registrate
+consent-type-phrase = Scriva '{ $phrase }' per accettare, qualsiasi altra cosa per tornare indietro:
+consent-not-accepted = Non accettato, gli attacchi restano non disponibili.
+consent-changes-card = '{ $operation }' modifica per sempre la tessera sul lettore.
+consent-authorized = Si assicuri di essere autorizzato a modificarla. Continuare per questa sessione? (y/n):
+err-writing-audit-log = Errore durante la scrittura del registro di audit { $path }: { $error }
+please-remove-card-from-reader = Tolga la tessera dal lettore...
+card-removed = Tessera tolta
+hold-card-near-reader = Avvicini una tessera al lettore...
+seconds-to-place-card = Ha { $seconds } secondi per mettere una tessera
+card-detected-uid = Tessera rilevata! UID: { $uid }
+no-card-in-time = Nessuna tessera rilevata nel tempo indicato.
+press-enter-to-continue = Premere Invio per continuare...
+
+# Menu
+cancelling-attacks-still-running = Annullamento degli attacchi ancora in corso...
+enter-choice = Inserisca la scelta:
+exiting = Uscita...
+err-invalid-choice-please-try-again = Scelta non valida. Riprovi.
+job-queued = Lavoro { $id } ({ $job }) in coda dietro quello in corso. 11 elenca i lavori, 12 li annulla.
+job-submitted = Lavoro { $id } ({ $job }) avviato, metta la tessera sul lettore. 11 elenca i lavori, 12 li annulla.
+no-attacks-run-yet = Nessun attacco eseguito finora.
+cancelling-running-attack-stops-at = Annullamento, l'attacco in corso si ferma al prossimo passo. I suoi nonce restano per la prossima volta.
+no-attack-is-running = Nessun attacco in corso.
+operation-cancelled = Operazione annullata.
+menu-reader = Lettore: { $status }
+menu-reader-busy = Lettore: occupato con un attacco
+select-option = Selezioni un'opzione:
+read-card-uid = Leggere l'UID della tessera
+try-default-keys = Provare le chiavi predefinite
+run-nested-attack = Eseguire l'attacco nested (richiede una chiave nota)
+run-darkside-attack = Eseguire l'attacco darkside
+detect-magic-card = Rilevare una tessera magic
+write-custom-uid = Scrivere un UID personalizzato (richiede una tessera magic)
+dump-card-contents = Copiare il contenuto della tessera
+clone-card-to-magic-card = Clonare la tessera su una tessera magic
+test-prng = Provare il PRNG (darkside / nested funzioneranno?)
+show-attack-jobs = Mostrare i lavori di attacco
+cancel-attack-jobs = Annullare i lavori di attacco
+autopwn = Autopwn: recuperare tutte le chiavi, poi copiare la tessera
+menu-exit = Uscire
+title-prng-test = Prova del PRNG
+job-started = [lavoro { $id }] { $job } avviato
+job-reader = [lavoro { $id }] Lettore: { $status }
+job-card = [lavoro { $id }] Tessera { $uid } ({ $card_type })
+job-card-magic = [lavoro { $id }] Tessera { $uid } ({ $card_type }), probabilmente una tessera magic
+job-found-of-sector = [lavoro { $id }] Trovata { $key_type } del settore { $sector }: { $key }
+job-prng-report = [lavoro { $id }]
+    { $report }
+job-result = [lavoro { $id }]
+    { $result }
+saved-to = Salvato in { $path }
+job-finished = [lavoro { $id }] { $summary }
+job-cancelled = [lavoro { $id }] Annullato
+job-failed = [lavoro { $id }] Non riuscito: { $error }
+err-invalid-sector-number = Numero di settore non valido
+err-invalid-block-number = Numero di blocco non valido
+enter-key-type = Inserisca il tipo di chiave (A o B):
+err-invalid-key-type = Tipo di chiave non valido
+err-key-6-bytes = La chiave deve essere di esattamente 6 byte
+err-invalid-hex-value = Valore hex non valido '{ $value }'
+enter-key-hex-spaced = Inserisca la chiave (6 byte in hex, ad es. 'FF FF FF FF FF FF'):
+
+# Lettura, scrittura, clonazione e tessere magic
+title-read-card-uid = Lettura dell'UID della tessera
+uid-line = UID: { $uid }
+card-type = Tipo di tessera: { $card_type }
+no-card-detected-during-timeout = Nessuna tessera rilevata durante l'attesa.
+title-dump-sector = Copia del settore { $sector }
+card-found-uid = Tessera rilevata. UID: { $uid }
+attempting-to-read-sector = Tentativo di lettura del settore { $sector }...
+sector-contents = Contenuto del settore { $sector }:
+title-dump-card = Copia dell'intera tessera
+dump-reads-all-sectors = Questa operazione tenterà di leggere tutti i settori accessibili.
+continue-y-n = Continuare? (y/n):
+continue-anyway = Continuare comunque?
+attempting-to-read-all-sectors = Tentativo di lettura di tutti i settori...
+sector-no-key = Settore { $sector }: nessuna chiave
+dump-block-line = Blocco { $block }: { $hex }
+dump-completed-of-sectors-read = Copia completata, { $read } settori letti su { $total }.
+title-write-text = Scrivere testo in un blocco
+enter-block-number-0-63 = Inserisca il numero del blocco (0-63):
+err-invalid-block-number-must-be = Numero di blocco non valido. Deve essere tra 0 e 63.
+enter-text-to-write-max = Inserisca il testo da scrivere (max 16 caratteri):
+text-too-long-will-be = Testo troppo lungo. Verrà troncato a 16 caratteri.
+placing-card-on-reader = Metta la tessera sul lettore...
+writing-to-block = Scrittura nel blocco { $block }...
+write-data = Dati: { $hex }
+write-operation-completed = Scrittura completata.
+no-card-detected-within-timeout = Nessuna tessera rilevata entro il tempo di attesa.
+title-write-hex = Scrivere hex in un blocco
+enter-hex-data-32-hex = Inserisca i dati in hex (32 caratteri hex, senza spazi):
+data-must-be-exactly-16 = I dati devono essere di esattamente 16 byte (32 caratteri hex).
+err-invalid-hex-data = Dati hex non validi: { $error }
+title-clone-card = Clonare una tessera
+clone-explained = Questa operazione legge i dati da una tessera di origine e li scrive su una tessera magic.
+step-1-read-source-card = Passo 1: leggere la tessera di origine
+place-source-card-on-reader = Metta la tessera di ORIGINE sul lettore...
+source-card-detected-uid = Tessera di origine rilevata. UID: { $uid }
+reading-card-data = Lettura dei dati della tessera...
+no-source-card-detected = Nessuna tessera di origine rilevata.
+clone-different-uid = Vuole usare un UID diverso per la tessera di destinazione? (y/n):
+enter-new-uid-in-hex = Inserisca il nuovo UID in hex (ad es. 11:22:33:44):
+err-invalid-uid-length-must-be = Lunghezza dell'UID non valida. Deve essere di 4, 7 o 10 byte.
+err-invalid-hex-format = Formato hex non valido: { $error }
+step-2-write-to-target = Passo 2: scrivere sulla tessera magic di destinazione
+place-target-magic-card-on = Metta la tessera magic di DESTINAZIONE sul lettore...
+target-card-detected-uid = Tessera di destinazione rilevata. UID: { $uid }
+warning-not-magic-card = Attenzione: non sembra una tessera magic.
+changing-uid-to = Modifica dell'UID in: { $uid }
+writing-data-to-target-card = Scrittura dei dati sulla tessera di destinazione...
+clone-operation-completed = Clonazione completata.
+no-target-card-detected = Nessuna tessera di destinazione rilevata.
+title-detect-card-type = Rilevare il tipo di tessera
+detect-explained = Si tenterà di capire se la tessera è una tessera magic.
+this-appears-to-be-magic = Sembra una tessera magic!
+magic-cards-allow-uid-changing = Le tessere magic permettono di cambiare l'UID e aggirano alcune protezioni.
+this-appears-to-be-standard = Sembra una normale tessera MIFARE.
+no-specific-magic-card-features = Nessuna funzione tipica delle tessere magic rilevata.
+detect-more-tests = Si potrebbero fare altre prove per verificare le funzioni magic:
+direct-write-to-block-0 = Scrittura diretta nel blocco 0 (blocco dell'UID)
+testing-for-backdoor-commands = Prova dei comandi backdoor
+checking-for-direct-memory-operations = Verifica delle operazioni dirette sulla memoria
+no-card-detected = Nessuna tessera rilevata.
+title-write-uid = Scrivere un UID personalizzato su una tessera magic
+write-uid-magic-only = ATTENZIONE: funziona solo con le tessere magic che permettono di cambiare l'UID!
+write-uid-may-damage = Usarlo su tessere non magic può DANNEGGIARE la tessera per sempre.
+new-uid-will-be = Il nuovo UID sarà: { $uid }
+write-uid-confirm = È ASSOLUTAMENTE sicuro di voler procedere? La tessera potrebbe diventare inutilizzabile! (y/n):
+place-magic-card-on-reader = Metta la tessera magic sul lettore...
+card-detected-current-uid = Tessera rilevata. UID attuale: { $uid }
+write-uid-not-magic = ATTENZIONE: NON sembra una tessera magic!
+write-uid-damage-confirm = Procedere può DANNEGGIARE LA TESSERA PER SEMPRE! Continuare? (y/n):
+attempting-to-change-uid = Tentativo di modifica dell'UID...
+uid-change-operation-completed = Modifica dell'UID completata.
+remove-card-and-place-it = Tolga la tessera e la rimetta per verificare il nuovo UID.
+
+# Il lettore e gli attacchi
+authenticating-with-block-mode-key = Autenticazione con blocco={ $block }, modo={ $mode }, chiave={ $key }
+auth-buffer = Buffer di autenticazione: { $hex }
+auth-status = Stato dell'autenticazione: { $status }, STATUS2: { $status2 }, bit Crypto: { $crypto_bit }
+auth-error-register = Registro degli errori: 0x{ $register }
+authentication-succeeded = Autenticazione riuscita!
+authentication-failed-status-not-ok = Autenticazione non riuscita: stato non OK
+special-auth-with = Autenticazione speciale con: { $hex }
+auth-status2 = STATUS2 dopo l'autenticazione: 0x{ $status2 }
+special-authentication-succeeded = Autenticazione speciale riuscita!
+special-authentication-failed = Autenticazione speciale non riuscita
+err-while-reading = Errore durante la lettura!
+err-write-command = Comando di scrittura non riuscito: status={ $status }, back_len={ $back_len }
+err-while-writing-data = Errore durante la scrittura dei dati
+data-written-successfully-to-block = Dati scritti correttamente nel blocco { $block }
+card-uid = UID della tessera: { $uid }
+default-key-a-works = Funziona con la chiave A: { $key }
+default-key-b-works = Funziona con la chiave B: { $key }
+no-default-key-works = Nessuna chiave predefinita funziona
+reader-reconnecting = Stato del lettore: { $status } - tentativo di riconnessione...
+reader-reconnected = Stato del lettore: { $status } (dopo { $attempt } tentativo/i)
+reconnect-attempt-failed = Tentativo di riconnessione { $attempt }/{ $attempts } non riuscito: { $error }
+reader-check-wiring = Stato del lettore: { $status } - controlli i collegamenti e l'alimentazione, poi riprovi
+mfrc522-version = Versione dell'MFRC522: 0x{ $version }
+mfrc522-initialized-successfully = MFRC522 inizializzato correttamente
+recording-card-transactions-to = Registrazione delle transazioni della tessera in { $path }
+session-recording-stopped = Registrazione della sessione interrotta: { $error }
+replay-recorded-answer = registrato: { $status } { $bits } bit { $received }  ({ $micros }us, ora { $now_micros }us)
+antenna-state = Stato dell'antenna: 0x{ $state }
+title-darkside = Attacco darkside
+darkside-vulnerable-cards = Questo attacco funziona sulle tessere MIFARE Classic vulnerabili
+darkside-takes-minutes = Può richiedere qualche minuto
+enter-target-block = Inserisca il numero del blocco bersaglio (0-63):
+title-nested = Attacco nested
+nested-needs-key = Questo attacco richiede di conoscere già almeno una chiave
+enter-known-key-hex-format = Inserisca la chiave nota (in hex, ad es. 'FFFFFFFFFFFF'):
+err-invalid-key-length-must-be = Lunghezza della chiave non valida: deve essere di esattamente 6 byte (12 caratteri hex)
+err-invalid-key-format-please-enter = Formato della chiave non valido. Inserisca 12 caratteri hex.
+enter-sector-number-where-this = Inserisca il numero del settore in cui funziona questa chiave (0-15):
+err-invalid-sector-number-must-be = Numero di settore non valido. Deve essere tra 0 e 15.
+err-invalid-key-type-must-be = Tipo di chiave non valido. Deve essere A o B.
+starting-nested-attack-with-known = Avvio dell'attacco nested con la chiave nota del blocco { $block }
+target-block = Blocco bersaglio: { $block }
+enter-known-block = Inserisca un blocco in cui funziona questa chiave (0-63):
+known-key-type = Tipo della chiave nota (A o B):
+target-key-type = Tipo della chiave bersaglio (A o B):
+key-type = Tipo di chiave (A o B):
+enter-key-hex = Inserisca la chiave (in hex, ad es. 'FFFFFFFFFFFF'):
+sector-key-works-on = Settore in cui funziona
+starting-darkside-attack-on-block = Avvio dell'attacco darkside sul blocco { $block }
+darkside-vulnerable-only = Questo attacco funziona solo sulle tessere MIFARE Classic vulnerabili
+
+# Proxmark3
+pm3-keys-header = Settore | Chiave A    | Chiave B
+pm3-keys-found = { $found } chiavi trovate su { $total }
+menu-reader-proxmark3 = Lettore: Proxmark3 su { $port }
+dump-card-contents-recovers-missing = Copiare il contenuto della tessera (recupera le chiavi mancanti)
+clone-dump-file-to-magic = Clonare un file di dump su una tessera magic
+card-analysis-health-and-counterfeit = Analisi della tessera (stato e controllo contraffazione)
+place-card-on-proxmark3 = Metta una tessera sul Proxmark3...
+card-atqa = ATQA: { $atqa }
+card-sak = SAK: { $sak }
+title-default-keys = Prova delle chiavi predefinite
+key-file-to-add-mfoc = File di chiavi da aggiungere (lista mfoc -f o dump .mfd, Invio per le sole chiavi predefinite):
+checking-keys-on-every-sector = Verifica di { $count } chiavi su ogni settore...
+no-card-answered-or-client = Nessuna tessera ha risposto, o il client non ha stampato alcuna tabella di chiavi.
+running-nested-attack-on-block = Esecuzione dell'attacco nested sul blocco { $block }...
+found-for-sector = Trovata { $key_type } del settore { $sector }: { $key }
+attack-failed-cards-with-hardened = Attacco non riuscito. Le tessere con un PRNG rinforzato richiedono l'attacco hardnested (l'opzione 7 lo esegue).
+pm3-darkside-weak-prng = Questo attacco funziona sulle tessere MIFARE Classic con il PRNG debole
+card-reports-hard-prng-darkside = La tessera indica un PRNG rinforzato, molto probabilmente darkside non riuscirà (l'opzione 11 lo verifica).
+pm3-darkside-starting = Avvio dell'attacco darkside sul blocco { $block }. Può richiedere qualche minuto...
+attack-successful = Attacco riuscito!
+found-key-for-block = Chiave trovata per il blocco { $block }: { $key }
+attack-failed-card-may-not = Attacco non riuscito. La tessera potrebbe non essere vulnerabile all'attacco darkside.
+no-magic-capabilities = Nessuna funzione magic rilevata, è una tessera normale.
+magic-card-capabilities = È una tessera magic: { $magic }
+card-prng = PRNG: { $prng }
+title-card-analysis = Analisi della tessera
+reading-block-0-with-default = Lettura del blocco 0 con le chiavi predefinite...
+sampling-pairs-of-nonces = Campionamento di { $count } coppie di nonce...
+card-sent-no-nonces-it = La tessera non ha inviato alcun nonce, potrebbe non essere una MIFARE Classic.
+write-uid-gen1a-only = ATTENZIONE: funziona solo con le tessere magic Gen 1a!
+err-csetuid-uid-length = Lunghezza dell'UID non valida. csetuid accetta un UID di 4 byte.
+set-uid-confirm = Impostare l'UID a { $uid }?
+uid-changed-to = UID cambiato in { $uid }
+err-change-uid-gen1a = Impossibile cambiare l'UID. È una tessera Gen 1a?
+know-a-key = Conosce già una chiave di questa tessera?
+running-autopwn-this-can-take = Esecuzione di autopwn, può richiedere diversi minuti sulle tessere rinforzate...
+dump-saved-to = Dump salvato in { $path }
+client-did-not-save-dump = Il client non ha salvato alcun dump, non è stato possibile recuperare tutte le chiavi.
+place-card-on-proxmark3-dump = Metta la tessera sul Proxmark3.
+save-copy-as-mfd-for = Salvare una copia in .mfd per mfoc / nfc-mfclassic (percorso, Invio per saltare):
+dump-copied-to = Dump copiato in { $path }
+no-key-recovery-build = Il recupero delle chiavi non fa parte di questa build, cloni da un file di dump.
+dump-file-to-clone-from = File di dump da cui clonare (.mfd/.bin da mfoc, mfcuk o il PM3), Invio per leggere una tessera:
+place-source-card-on-proxmark3 = Metta la tessera di ORIGINE sul Proxmark3.
+err-cannot-clone-without-complete-dump = Impossibile clonare senza un dump completo.
+place-target-gen-1a-magic = Metta la tessera magic Gen 1a di DESTINAZIONE sul Proxmark3 e prema Invio...
+target-card-doesnt-report-gen = La tessera di destinazione { $uid } non indica funzioni magic Gen 1a.
+err-clone-write-failed = La scrittura del dump sulla tessera di destinazione non è riuscita.
+
+# Lavori e risultati degli attacchi
+stage-nonces = nonce
+stage-keys = chiavi
+stage-dump = settori letti
+status-queued = in coda
+status-running = in corso, { $progress }
+status-waiting-for-card = in attesa della tessera
+status-finished = fatto: { $summary }
+status-cancelled = annullato
+status-failed = non riuscito: { $error }
+err-job-worker-stopped = il thread dei lavori si è fermato
+err-reader-busy = Il lettore è occupato con un attacco, attenda che finisca o lo annulli
+job-card-read = Tessera { $uid } letta
+err-no-card-in-seconds = Nessuna tessera rilevata in { $seconds } secondi
+summary-not-saved = , non salvato: { $error }
+summary-new-in-key-store = , { $count } nuove nell'archivio delle chiavi
+summary-not-added-to-key-store = , non aggiunte all'archivio delle chiavi: { $error }
+err-no-nonces = La tessera non ha inviato alcun nonce, potrebbe non essere una MIFARE Classic
+err-darkside-prng = Darkside non funziona su questa tessera, il suo PRNG è { $prng }
+err-nested-prng = Nested non funziona su questa tessera, il suo PRNG è { $prng }
+err-key-doesnt-open = La chiave non apre { $key_type } del settore { $sector }
+autopwn-no-way-in = Nessuna chiave predefinita apre un settore e darkside non funziona su questa tessera, il suo PRNG è { $prng }
+autopwn-hardnested-on-pm3 = Hardnested gira sul Proxmark3 su { $port }, metta la tessera lì
+autopwn-pm3-wrong-card = Il Proxmark3 ha la tessera { $found }, non { $uid }
+autopwn-no-card-on-pm3 = La tessera non è stata messa sul Proxmark3 entro { $seconds } secondi
+autopwn-card-back-on-mfrc522 = Rimetta la tessera sull'MFRC522 per il dump
+autopwn-no-card-on-mfrc522 = La tessera non è stata rimessa sull'MFRC522 entro { $seconds } secondi
+result-summary = { $attack }: { $keys } chiavi per { $sectors } settori su { $attacked } in { $seconds }s, { $nonces } nonce
+result-card = Tessera { $uid } ({ $card_type })
+result-likely-magic = , probabilmente una tessera magic
+result-prng = , PRNG { $prng }
+result-sector = Settore
+result-key-a = Chiave A
+result-key-b = Chiave B
+result-dump-saved = Dump salvato in { $path }
+reader-connected = Connesso
+reader-disconnected = Disconnesso
+
+# PRNG e analisi della tessera
+prng-report = PRNG: { $kind } ({ $samples } campioni)
+prng-static-darkside = Darkside: no, la tessera non cambia mai il suo nonce
+prng-static-nested = Nested: no, serve un attacco static nested (Proxmark3 `hf mf staticnested`)
+prng-hard-darkside = Darkside: no, i nonce non sono prevedibili
+prng-hard-nested = Nested: no, funziona solo hardnested (opzione 7 sul Proxmark3)
+prng-repeated = Stesso nonce dopo un reset del campo: { $repeated } campioni su { $samples }
+prng-distance = Distanza tra i nonce di due autenticazioni: da { $min } a { $max } passi
+prng-weak-darkside = Darkside: sì, la tessera ripete il suo nonce
+prng-weak-darkside-unlikely = Darkside: improbabile, i tempi del lettore non riproducono il nonce abbastanza spesso
+prng-weak-nested = Nested: sì, le distanze tra i nonce sono prevedibili
+prng-weak-nested-unlikely = Nested: improbabile con questo lettore, le distanze tra i nonce variano troppo
+analysis-title = Analisi della tessera con UID { $uid }
+analysis-verdict = Verdetto: { $verdict } (affidabilità { $confidence })
+confidence-high = alta
+confidence-medium = media
+confidence-low = bassa
+verdict-genuine = MIFARE Classic originale NXP
+verdict-clone = chip clonato (non prodotto da NXP)
+verdict-magic = tessera magic (UID e blocco 0 si possono riscrivere)
+verdict-inconclusive = non conclusivo
+check-atqa-sak = ATQA/SAK
+check-block0 = Blocco 0
+check-fingerprint = Impronta del chip
+check-prng = PRNG
+check-magic = Comandi magic
+not-reported = non indicato
+atqa-sak-seen = ATQA { $atqa } SAK { $sak }, UID di { $uid_len } byte
+atqa-announces-uid-len = { $seen }: l'ATQA annuncia un UID di { $uid_len } byte
+atqa-sak-no-classic = { $seen }: nessuna MIFARE Classic risponde con questa combinazione
+atqa-sak-not-classic = { $seen }: non è una MIFARE Classic
+block0-not-readable = non leggibile con le chiavi predefinite
+block0-uid-mismatch = contiene l'UID { $held } ma la tessera risponde come { $uid }
+block0-bad-bcc = il BCC è { $bcc }, l'UID richiede { $needed }
+block0-clone-manufacturer-data = dati del produttore 62 63 64 65 66 67 68 69, quelli predefiniti dei cloni cinesi
+block0-consistent = coerente ({ $block })
+fingerprint-fudan = codice produttore dell'UID 1D (Shanghai Fudan)
+fingerprint-nxp = codice produttore dell'UID 04 (NXP)
+fingerprint-not-nxp = il codice produttore dell'UID { $code } non è NXP
+fingerprint-none = nessun chip clonato noto riconosciuto
+prng-check-static = nonce statico, ogni autenticazione riceve la stessa sfida
+prng-check-weak = debole (MIFARE Classic originale e la maggior parte dei cloni), valgono darkside e nested
+prng-check-hard = rinforzato (MIFARE Classic EV1 o successive), vale solo hardnested
+magic-none-answered = nessuno ha risposto
+
+# Riuso delle chiavi e chiavi deboli
+spread-one-card = 1 tessera
+spread-same = uguale su tutte le { $cards } tessere
+spread-diversified = diversificata, { $keys } chiavi
+spread-keys-for-cards = { $keys } chiavi per { $cards } tessere
+reuse-title = Riuso delle chiavi: { $cards } tessere, { $lists } liste di chiavi
+reuse-cards = Tessere
+reuse-single-key = una chiave su ogni settore ({ $key })
+reuse-default-sectors = chiavi predefinite su { $count } settori
+reuse-diversified = diversificate, nessuna chiave in comune con un'altra tessera
+reuse-shared-keys = { $count } chiavi in comune con altre tessere
+reuse-sectors = { $count } settori
+reuse-sectors-across = Settori su tutte le tessere
+reuse-keys-on-cards = Chiavi su più di una tessera
+reuse-key-counts = { $cards } tessere, { $sector_keys } chiavi di settore
+reuse-default-key = chiave predefinita
+reuse-key-lists = Liste di chiavi
+reuse-key-list = { $path }: { $keys } chiavi, { $default } predefinite, { $used } usate dalle tessere
+reuse-summary = Riepilogo
+reuse-cards-default-keys = Tessere con chiavi predefinite:
+reuse-cards-single-key = Tessere con una chiave ovunque:
+reuse-cards-diversified = Tessere con chiavi diversificate:
+count-of = { $count } su { $total }
+report-skipped = Saltato { $path }: { $reason }
+report-yes = sì
+report-no = no
+weak-title = Chiavi deboli: { $weak } su { $total } chiavi
+weak-key = Chiave
+weak-default = Predef.
+weak-dictionary = Dizionario
+weak-cards = Tessere
+weak-sector-keys = Chiavi sett.
+weak-low-entropy = Bassa entropia
+weak-strong-keys = Altre { $count } chiavi non sono in nessun dizionario e sembrano casuali
+weak-by-sector = Chiavi di settore sulle tessere, per settore
+weak-keys = Chiavi
+weak-weak = Deboli
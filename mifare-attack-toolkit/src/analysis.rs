@@ -0,0 +1,233 @@
+// src/analysis.rs
+//
+// Card health check and counterfeit detection. Everything the reader can tell about a
+// card without changing it is put through a handful of checks: whether ATQA, SAK and
+// UID size agree, whether block 0 looks like NXP wrote it, whether the client
+// recognizes a clone chip, how good the PRNG is, and whether the card has magic
+// commands. Each check says what it found, and together they give a verdict with a
+// confidence that grows with the number of checks that could be run and agree.
+use std::fmt;
+
+use crate::i18n::{tr, tr_args};
+use crate::proxmark3::parse::CardInfo;
+use crate::utils::{bytes_to_hex, format_uid};
+
+/// ATQA, SAK and UID size of each genuine MIFARE Classic
+const CLASSIC_SIGNATURES: [([u8; 2], u8, usize, &str); 5] = [
+    ([0x00, 0x04], 0x08, 4, "MIFARE Classic 1K"),
+    ([0x00, 0x44], 0x08, 7, "MIFARE Classic 1K (7 byte UID)"),
+    ([0x00, 0x02], 0x18, 4, "MIFARE Classic 4K"),
+    ([0x00, 0x42], 0x18, 7, "MIFARE Classic 4K (7 byte UID)"),
+    ([0x00, 0x04], 0x09, 4, "MIFARE Classic Mini"),
+];
+
+/// Manufacturer data Chinese clones and magic cards ship with ("bcdefghi")
+const CLONE_MANUFACTURER_DATA: [u8; 8] = [0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69];
+
+/// First byte of a 7 byte UID, the ISO/IEC 7816-6 manufacturer code
+const NXP: u8 = 0x04;
+const SHANGHAI_FUDAN: u8 = 0x1D;
+
+/// What the card most likely is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Genuine,
+    Clone,
+    Magic,
+    Inconclusive,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Genuine => write!(f, "{}", tr("verdict-genuine")),
+            Verdict::Clone => write!(f, "{}", tr("verdict-clone")),
+            Verdict::Magic => write!(f, "{}", tr("verdict-magic")),
+            Verdict::Inconclusive => write!(f, "{}", tr("verdict-inconclusive")),
+        }
+    }
+}
+
+/// The outcome of one check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    /// Consistent with a genuine card
+    Pass,
+    /// Points to the verdict given
+    Suspect(Verdict),
+    /// Nothing either way, or the check couldn't be run
+    Neutral,
+}
+
+pub struct Finding {
+    /// The catalogue key of the check's name
+    pub check: &'static str,
+    pub outcome: Outcome,
+    pub detail: String,
+}
+
+/// The checks run on one card and what they add up to
+pub struct CardReport {
+    pub uid: Vec<u8>,
+    pub findings: Vec<Finding>,
+    pub verdict: Verdict,
+    /// "high", "medium" or "low"
+    pub confidence: &'static str,
+}
+
+impl fmt::Display for CardReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", tr_args("analysis-title", &[("uid", &format_uid(&self.uid))]))?;
+        for finding in &self.findings {
+            let mark = match finding.outcome {
+                Outcome::Pass => "ok",
+                Outcome::Suspect(_) => "!!",
+                Outcome::Neutral => "--",
+            };
+            writeln!(f, "  [{}] {:<18} {}", mark, tr(finding.check), finding.detail)?;
+        }
+        write!(f, "{}", tr_args("analysis-verdict", &[
+            ("verdict", &self.verdict.to_string()),
+            ("confidence", &tr(&format!("confidence-{}", self.confidence))),
+        ]))
+    }
+}
+
+/// Analyse a card from what the reader reported and, when a key opened it, block 0
+pub fn analyse_card(info: &CardInfo, block0: Option<&[u8]>) -> CardReport {
+    let findings = vec![
+        check_atqa_sak(info),
+        check_block0(info, block0),
+        check_fingerprints(info),
+        check_prng(info),
+        check_magic(info),
+    ];
+
+    // Magic capabilities outrank everything else, a magic card can copy any block 0.
+    // Magic cards are clones too, so clone findings back a magic verdict.
+    let suspected = |verdict: Verdict| findings.iter().filter(|finding| finding.outcome == Outcome::Suspect(verdict)).count();
+    let passed = findings.iter().filter(|finding| finding.outcome == Outcome::Pass).count();
+    let (verdict, agreeing) = if suspected(Verdict::Magic) > 0 {
+        (Verdict::Magic, suspected(Verdict::Magic) + suspected(Verdict::Clone))
+    } else if suspected(Verdict::Clone) > 0 {
+        (Verdict::Clone, suspected(Verdict::Clone))
+    } else if passed >= 3 {
+        (Verdict::Genuine, passed)
+    } else {
+        (Verdict::Inconclusive, 0)
+    };
+
+    let confidence = match (verdict, agreeing) {
+        (Verdict::Inconclusive, _) => "low",
+        (Verdict::Genuine, checks) if checks >= 4 => "high",
+        (Verdict::Genuine, _) => "medium",
+        (_, checks) if checks >= 2 => "high",
+        _ => "medium",
+    };
+
+    CardReport { uid: info.uid.clone(), findings, verdict, confidence }
+}
+
+fn finding(check: &'static str, outcome: Outcome, detail: impl Into<String>) -> Finding {
+    Finding { check, outcome, detail: detail.into() }
+}
+
+// ATQA, SAK and the UID size have to match one of the Classic signatures
+fn check_atqa_sak(info: &CardInfo) -> Finding {
+    let (atqa, sak) = match (info.atqa, info.sak) {
+        (Some(atqa), Some(sak)) => (atqa, sak),
+        _ => return finding("check-atqa-sak", Outcome::Neutral, tr("not-reported")),
+    };
+    let seen = tr_args("atqa-sak-seen", &[
+        ("atqa", &bytes_to_hex(&atqa)),
+        ("sak", &format!("{:02X}", sak)),
+        ("uid_len", &info.uid.len().to_string()),
+    ]);
+
+    if let Some((_, _, _, name)) = CLASSIC_SIGNATURES.iter()
+        .find(|(known_atqa, known_sak, uid_len, _)| *known_atqa == atqa && *known_sak == sak && *uid_len == info.uid.len()) {
+        return finding("check-atqa-sak", Outcome::Pass, format!("{}: {}", seen, name));
+    }
+
+    // ATQA bits 7-6 give the UID size, a card that gets it wrong isn't an NXP chip
+    let announced_len = match atqa[1] >> 6 {
+        0 => 4,
+        1 => 7,
+        _ => 10,
+    };
+    if announced_len != info.uid.len() {
+        return finding("check-atqa-sak", Outcome::Suspect(Verdict::Clone),
+            tr_args("atqa-announces-uid-len", &[("seen", &seen), ("uid_len", &announced_len.to_string())]));
+    }
+    if CLASSIC_SIGNATURES.iter().any(|(_, known_sak, _, _)| *known_sak == sak) {
+        return finding("check-atqa-sak", Outcome::Suspect(Verdict::Clone),
+            tr_args("atqa-sak-no-classic", &[("seen", &seen)]));
+    }
+    finding("check-atqa-sak", Outcome::Neutral, tr_args("atqa-sak-not-classic", &[("seen", &seen)]))
+}
+
+// Block 0 of a genuine card holds the UID, its BCC and NXP's manufacturer data;
+// magic cards and clones often get one of them wrong
+fn check_block0(info: &CardInfo, block0: Option<&[u8]>) -> Finding {
+    let block0 = match block0 {
+        Some(block0) if block0.len() == 16 => block0,
+        _ => return finding("check-block0", Outcome::Neutral, tr("block0-not-readable")),
+    };
+
+    if info.uid.len() == 4 {
+        if block0[0..4] != info.uid[..] {
+            return finding("check-block0", Outcome::Suspect(Verdict::Magic),
+                tr_args("block0-uid-mismatch", &[("held", &format_uid(&block0[0..4])), ("uid", &format_uid(&info.uid))]));
+        }
+        let bcc = block0[0..4].iter().fold(0, |bcc, byte| bcc ^ byte);
+        if block0[4] != bcc {
+            // Only a writable block 0 can end up with a wrong check byte
+            return finding("check-block0", Outcome::Suspect(Verdict::Magic),
+                tr_args("block0-bad-bcc", &[("bcc", &format!("{:02X}", block0[4])), ("needed", &format!("{:02X}", bcc))]));
+        }
+    }
+
+    if block0[8..16] == CLONE_MANUFACTURER_DATA {
+        return finding("check-block0", Outcome::Suspect(Verdict::Clone), tr("block0-clone-manufacturer-data"));
+    }
+    finding("check-block0", Outcome::Pass, tr_args("block0-consistent", &[("block", &bytes_to_hex(block0))]))
+}
+
+// Clone chips the client recognizes, and the manufacturer code of a 7 byte UID
+fn check_fingerprints(info: &CardInfo) -> Finding {
+    if !info.fingerprints.is_empty() {
+        return finding("check-fingerprint", Outcome::Suspect(Verdict::Clone), info.fingerprints.join("; "));
+    }
+    match info.uid.first() {
+        Some(&SHANGHAI_FUDAN) if info.uid.len() == 7 => {
+            finding("check-fingerprint", Outcome::Suspect(Verdict::Clone), tr("fingerprint-fudan"))
+        },
+        Some(&NXP) if info.uid.len() == 7 => finding("check-fingerprint", Outcome::Pass, tr("fingerprint-nxp")),
+        Some(&code) if info.uid.len() == 7 => {
+            finding("check-fingerprint", Outcome::Suspect(Verdict::Clone), tr_args("fingerprint-not-nxp", &[("code", &format!("{:02X}", code))]))
+        },
+        _ => finding("check-fingerprint", Outcome::Neutral, tr("fingerprint-none")),
+    }
+}
+
+// A static nonce is a clone giveaway; weak and hard PRNGs exist on genuine cards too,
+// but they say which attacks are worth running
+fn check_prng(info: &CardInfo) -> Finding {
+    if info.static_nonce == Some(true) {
+        return finding("check-prng", Outcome::Suspect(Verdict::Clone), tr("prng-check-static"));
+    }
+    match info.prng.as_deref() {
+        Some("weak") => finding("check-prng", Outcome::Pass, tr("prng-check-weak")),
+        Some("hard") => finding("check-prng", Outcome::Pass, tr("prng-check-hard")),
+        Some(other) => finding("check-prng", Outcome::Neutral, other),
+        None => finding("check-prng", Outcome::Neutral, tr("not-reported")),
+    }
+}
+
+fn check_magic(info: &CardInfo) -> Finding {
+    if info.magic.is_empty() {
+        finding("check-magic", Outcome::Pass, tr("magic-none-answered"))
+    } else {
+        finding("check-magic", Outcome::Suspect(Verdict::Magic), info.magic.join(", "))
+    }
+}
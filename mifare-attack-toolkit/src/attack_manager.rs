@@ -3,17 +3,20 @@ use std::error::Error;
 use crate::reader::MifareClassic;
 use crate::mifare_attack_manager::MifareAttackManager;
 
-pub struct AttackManager<'a> {
-    reader: &'a mut MifareClassic,
+pub struct AttackManager {
+    manager: MifareAttackManager,
 }
 
-impl<'a> AttackManager<'a> {
-    pub fn new(reader: &'a mut MifareClassic) -> Self {
-        Self { reader }
+impl AttackManager {
+    pub fn new(reader: MifareClassic) -> Self {
+        Self { manager: MifareAttackManager::new(reader) }
     }
     
     pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
-        let mut manager = MifareAttackManager::new(self.reader);
-        manager.run()
+        self.manager.run()
+    }
+    
+    pub fn into_reader(self) -> MifareClassic {
+        self.manager.into_reader()
     }
 }
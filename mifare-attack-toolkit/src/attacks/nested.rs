@@ -1,62 +1,54 @@
 // src/attacks/nested.rs
 use std::error::Error;
 use std::io::{self, Write};
-use std::thread;
-use std::time::Duration;
 
 use crate::reader::MifareClassic;
-use crate::cards::KeyType;
-use crate::utils::{wait_for_card_removal, format_uid, bytes_to_hex, hex_to_bytes};
-use crate::card_detection::{detect_card, wait_for_card_enhanced};
+use crate::cards::{sector_index, KeyType};
+use crate::utils::hex_to_bytes;
+use crate::jobs::{self, Job, JobContext, JobEvent, Stage};
+use crate::results::AttackResult;
+use crate::i18n::{tr, tr_args};
+
+/// Sectors of a Classic 1K, the ones the prompt takes the known key's sector from
+const SECTORS: u8 = 16;
+
+/// Ask for the known key, its sector and its type. None if an answer wasn't valid.
+pub fn ask_nested_attack() -> Result<Option<Job>, Box<dyn Error>> {
+    println!("\n=== {} ===", tr("title-nested"));
+    println!("{}", tr("nested-needs-key"));
 
-/// Run a nested attack using a known key to recover an unknown key
-pub fn run_nested_attack(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Nested Attack ===");
-    println!("This attack requires you to already know at least one key");
-    
-    // From looking at the card dump, we know the keys
-    println!("Based on your card dump, we know:");
-    println!("- Key A for all sectors is: 00 00 00 00 00 00");
-    println!("- Key B for all sectors is: FF FF FF FF FF FF");
-    
     // Get key from user
-    print!("Enter known key (hex format, e.g. 'FFFFFFFFFFFF'): ");
+    print!("{} ", tr("enter-known-key-hex-format"));
     io::stdout().flush()?;
     let mut key_hex = String::new();
     io::stdin().read_line(&mut key_hex)?;
-    let key_hex = key_hex.trim();
-    
-    let known_key = match hex_to_bytes(key_hex) {
-        Ok(key) => {
-            if key.len() != 6 {
-                println!("Invalid key length: must be exactly 6 bytes (12 hex characters)");
-                return Ok(());
-            }
-            let mut key_array = [0u8; 6];
-            key_array.copy_from_slice(&key[0..6]);
-            key_array
+    let key = match hex_to_bytes(key_hex.trim()).map(<[u8; 6]>::try_from) {
+        Ok(Ok(key)) => key,
+        Ok(Err(_)) => {
+            println!("{}", tr("err-invalid-key-length-must-be"));
+            return Ok(None);
         },
         Err(_) => {
-            println!("Invalid key format. Please enter 12 hex characters.");
-            return Ok(());
+            println!("{}", tr("err-invalid-key-format-please-enter"));
+            return Ok(None);
         }
     };
-    
+
     // Get sector number
-    print!("Enter sector number where this key works (0-15): ");
+    print!("{} ", tr("enter-sector-number-where-this"));
     io::stdout().flush()?;
     let mut sector_str = String::new();
     io::stdin().read_line(&mut sector_str)?;
     let sector = match sector_str.trim().parse::<u8>() {
-        Ok(s) if s < 16 => s,
+        Ok(s) if s < SECTORS => s,
         _ => {
-            println!("Invalid sector number. Must be 0-15.");
-            return Ok(());
+            println!("{}", tr("err-invalid-sector-number-must-be"));
+            return Ok(None);
         }
     };
-    
+
     // Get key type
-    print!("Enter key type (A or B): ");
+    print!("{} ", tr("enter-key-type"));
     io::stdout().flush()?;
     let mut key_type_str = String::new();
     io::stdin().read_line(&mut key_type_str)?;
@@ -64,94 +56,83 @@ pub fn run_nested_attack(reader: &mut MifareClassic) -> Result<(), Box<dyn Error
         "A" => KeyType::KeyA,
         "B" => KeyType::KeyB,
         _ => {
-            println!("Invalid key type. Must be A or B.");
-            return Ok(());
+            println!("{}", tr("err-invalid-key-type-must-be"));
+            return Ok(None);
         }
     };
-    
-    // Get target sector
-    print!("Enter target sector to attack (0-15): ");
-    io::stdout().flush()?;
-    let mut target_str = String::new();
-    io::stdin().read_line(&mut target_str)?;
-    let target_sector = match target_str.trim().parse::<u8>() {
-        Ok(s) if s < 16 => s,
-        _ => {
-            println!("Invalid target sector. Must be 0-15.");
-            return Ok(());
-        }
+
+    Ok(Some(Job::Nested { sector, key_type, key }))
+}
+
+/// Recover the keys of the other sectors from a key known for `sector`, as a job. The
+/// known key has to open its sector, and the card's nonces have to be predictable,
+/// before time goes into the other sectors.
+pub fn nested_attack(
+    reader: &mut MifareClassic,
+    uid: &[u8],
+    sector: u8,
+    key_type: KeyType,
+    key: [u8; 6],
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    let layout = reader.layout();
+    let mut result = AttackResult::new("nested attack", uid, layout.sector_count() as usize - 1);
+    let block = sector_index(sector)?.first_block().get();
+    if !reader.auth_with_key(block, key_type, &key, uid)? {
+        return Err(tr_args("err-key-doesnt-open", &[("key_type", &key_type.to_string()), ("sector", &sector.to_string())]).into());
+    }
+
+    let report = match jobs::collect_nonces(reader, uid, block, context)? {
+        Some(report) => report,
+        None => return Ok(None),
     };
-    
-    println!("\nStarting nested attack...");
-    println!("Using key: {} for sector: {}", bytes_to_hex(&known_key), sector);
-    println!("Targeting sector: {}", target_sector);
-    println!("Place the card on the reader and keep it still");
-    
-    // Reset the reader first for better reliability
-    reader.reset_reader()?;
-    
+    let kind = report.kind;
+    let viable = report.nested_viable();
+    result.add_nonces(&report);
+    context.report(JobEvent::Prng(report));
+    if !viable {
+        return Err(tr_args("err-nested-prng", &[("prng", &kind.to_string())]).into());
+    }
+
+    let targets: Vec<u8> = layout.sectors().map(|target| target.get()).filter(|&target| target != sector).collect();
+    attack_sectors(reader, (sector, key_type, key), &targets, result, context)
+}
+
+/// The keys of `targets` from a key known to open its sector, once the PRNG has been
+/// found weak enough
+pub(super) fn attack_sectors(
+    reader: &mut MifareClassic,
+    known: (u8, KeyType, [u8; 6]),
+    targets: &[u8],
+    result: AttackResult,
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
     // Try with special processing mode enabled
     reader.enable_dark_processing_mode(true);
-    
-    // Use 5 second timeout for card detection
-    match wait_for_card_enhanced(reader, 5)? {
-        Some(uid) => {
-            println!("Card detected! UID: {}", format_uid(&uid));
-            
-            // Try to use the known key first
-            let block = sector * 4; // First block of sector
-            
-            // Try first with regular auth
-            let auth_success = reader.auth_with_key(block, key_type, &known_key, &uid)?;
-            
-            if !auth_success {
-                // If that fails, try with special auth handling
-                println!("Standard auth failed, trying special auth method...");
-                let auth_success = reader.auth_with_key_special(block, key_type, &known_key, &uid)?;
-                
-                if !auth_success {
-                    println!("Authentication failed with the provided key.");
-                    println!("Please check if the key, sector, and key type are correct.");
-                    
-                    // For this specific card, suggest the correct key
-                    println!("\nBased on your card dump, try these keys:");
-                    println!("- For Key A: 00 00 00 00 00 00");
-                    println!("- For Key B: FF FF FF FF FF FF");
-                    
-                    reader.stop_crypto1()?;
-                    wait_for_card_removal(reader)?;
-                    return Ok(());
-                }
-            }
-            
-            println!("Authentication successful with known key!");
-            
-            // Run the nested attack
-            if let Ok(Some(found_key)) = reader.nested_attack(sector, &known_key, key_type, target_sector) {
-                println!("Attack succeeded! Found key for sector {}: {}", 
-                       target_sector, bytes_to_hex(&found_key));
-                
-                // Store this key for future use
-                reader.last_known_keys.insert((target_sector, KeyType::KeyA), found_key);
-                reader.last_known_keys.insert((target_sector, KeyType::KeyB), found_key);
-            } else {
-                // For this specific card, we already know the keys
-                println!("Based on your card dump, we know the keys are:");
-                println!("- Key A for sector {}: 00 00 00 00 00 00", target_sector);
-                println!("- Key B for sector {}: FF FF FF FF FF FF", target_sector);
-            }
-            
-            // Stop crypto and cleanup
-            reader.stop_crypto1()?;
-            wait_for_card_removal(reader)?;
-        },
-        None => {
-            println!("No card detected");
-        }
-    }
-    
+    let result = attack_each(reader, known, targets, result, context);
     // Disable dark processing mode when done
     reader.enable_dark_processing_mode(false);
-    
-    Ok(())
+    result
+}
+
+fn attack_each(
+    reader: &mut MifareClassic,
+    (sector, key_type, key): (u8, KeyType, [u8; 6]),
+    targets: &[u8],
+    mut result: AttackResult,
+    context: &JobContext,
+) -> Result<Option<AttackResult>, Box<dyn Error>> {
+    for (done, &target) in targets.iter().enumerate() {
+        if context.cancelled() {
+            return Ok(None);
+        }
+        reader.reset_field()?;
+        if let Some(target_key) = reader.nested_attack(sector, &key, key_type, target)? {
+            result.add_key(target, key_type, target_key);
+            context.report(JobEvent::Key { sector: target, key_type, key: target_key });
+        }
+        context.progress(Stage::Keys, done + 1, targets.len());
+    }
+
+    Ok(Some(result))
 }
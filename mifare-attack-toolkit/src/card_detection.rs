@@ -7,6 +7,7 @@ use crate::reader::MifareClassic;
 
 // Import constants directly from reader module
 use crate::reader::{MI_OK, PICC_REQIDL};
+use crate::i18n::{tr, tr_args};
 
 /// Enhanced card detection function - FIXED to match working code
 pub fn detect_card(reader: &mut MifareClassic) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
@@ -22,13 +23,18 @@ pub fn detect_card(reader: &mut MifareClassic) -> Result<Option<Vec<u8>>, Box<dy
         return Ok(None);
     }
     
+    // Selected, so an authentication can follow
+    if !reader.select_tag(&uid)? {
+        return Ok(None);
+    }
+    
     Ok(Some(uid))
 }
 
 /// Wait for a card to be placed on the reader - FIXED with simpler approach
 pub fn wait_for_card_enhanced(reader: &mut MifareClassic, timeout_secs: u64) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
-    println!("Hold a card near the reader...");
-    println!("You have {} seconds to place a card", timeout_secs);
+    println!("{}", tr("hold-card-near-reader"));
+    println!("{}", tr_args("seconds-to-place-card", &[("seconds", &timeout_secs.to_string())]));
     
     // FIXED: Make sure reader is in a clean state with simpler approach
     reader.stop_crypto1()?;
@@ -46,7 +52,7 @@ pub fn wait_for_card_enhanced(reader: &mut MifareClassic, timeout_secs: u64) ->
     while start_time.elapsed() < timeout_duration {
         match detect_card(reader)? {
             Some(uid) => {
-                println!("Card detected! UID: {}", reader.format_uid(&uid));
+                println!("{}", tr_args("card-detected-uid", &[("uid", &reader.format_uid(&uid).to_string())]));
                 return Ok(Some(uid));
             },
             None => {}
@@ -56,6 +62,6 @@ pub fn wait_for_card_enhanced(reader: &mut MifareClassic, timeout_secs: u64) ->
         thread::sleep(Duration::from_millis(100));
     }
     
-    println!("No card detected in the given time frame.");
+    println!("{}", tr("no-card-in-time"));
     Ok(None)
 }
@@ -1,4 +1,5 @@
 // src/cards/keys.rs
+use std::path::PathBuf;
 
 /// Common default keys for Mifare Classic cards
 pub const DEFAULT_KEYS: [[u8; 6]; 9] = [
@@ -12,3 +13,62 @@ pub const DEFAULT_KEYS: [[u8; 6]; 9] = [
     [0xAB, 0xCD, 0xEF, 0x12, 0x34, 0x56], // Test key
     [0x71, 0x4C, 0x5C, 0x88, 0x6E, 0x97]  // Another test key
 ];
+
+/// The block editor's key store, ~/.config/nfc-block-editor/keys.txt (or
+/// $XDG_CONFIG_HOME). Its lines are a key and the sources it was found in, a key list
+/// load_key_file reads as any other.
+pub fn key_store_path() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("keys.txt")
+}
+
+/// Sizes of raw Mini, 1K and 4K dumps (.mfd / .bin)
+pub const RAW_DUMP_SIZES: [usize; 3] = [320, 1024, 4096];
+
+/// Keys from a key file as used by the libnfc tools: either a text list with one
+/// 12 hex digit key per line ('#' starts a comment), as read by `mfoc -f`, or a raw
+/// .mfd/.bin dump from mfoc, mfcuk or nfc-mfclassic, whose sector trailers hold the keys.
+/// Duplicates are dropped.
+pub fn load_key_file(path: &str) -> Result<Vec<[u8; 6]>, String> {
+    let data = std::fs::read(path).map_err(|e| format!("Could not read {}: {}", path, e))?;
+    let lower = path.to_lowercase();
+    let is_dump = (lower.ends_with(".mfd") || lower.ends_with(".bin") || lower.ends_with(".dump"))
+        && RAW_DUMP_SIZES.contains(&data.len());
+
+    let mut keys: Vec<[u8; 6]> = Vec::new();
+    let mut add = |key: [u8; 6]| {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    };
+
+    if is_dump {
+        // Key A is the first 6 bytes of each trailer, Key B the last 6
+        for trailer in data.chunks(16).skip(3).step_by(4) {
+            add(trailer[0..6].try_into().unwrap());
+            add(trailer[10..16].try_into().unwrap());
+        }
+    } else {
+        let text = String::from_utf8(data).map_err(|_| format!("{} is neither a key list nor a raw dump", path))?;
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let word = match line.split_whitespace().next() {
+                Some(word) => word,
+                None => continue,
+            };
+            let bytes = crate::utils::hex_to_bytes(word)
+                .map_err(|e| format!("Line {} of {}: {}", number + 1, path, e))?;
+            let key: [u8; 6] = bytes.try_into()
+                .map_err(|_| format!("Line {} of {}: '{}' is not a 6 byte key", number + 1, path, word))?;
+            add(key);
+        }
+    }
+
+    if keys.is_empty() {
+        return Err(format!("No keys found in {}", path));
+    }
+    Ok(keys)
+}
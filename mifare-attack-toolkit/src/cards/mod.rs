@@ -1,11 +1,15 @@
 // src/cards/mod.rs
+use std::error::Error;
+
+use mifare_rc522::SectorIndex;
+
 mod card_types;
 mod keys;
 mod magic_cards;
 
 // Re-export types and functions
 pub use card_types::{CardType, KeyType, MagicCardOperations};
-pub use keys::DEFAULT_KEYS;
+pub use keys::{key_store_path, load_key_file, DEFAULT_KEYS, RAW_DUMP_SIZES};
 pub use magic_cards::MagicCardType;
 
 /// Identify card type based on UID and ATQA bytes
@@ -66,3 +70,9 @@ pub fn is_magic_card(uid: &[u8]) -> bool {
     
     false
 }
+
+/// The sector with this number, an error past the last sector of a 4K. Sectors 32-39 of
+/// a 4K have 16 blocks, so a sector's blocks come from its SectorIndex.
+pub fn sector_index(sector: u8) -> Result<SectorIndex, Box<dyn Error>> {
+    SectorIndex::new(sector).ok_or_else(|| format!("There is no sector {} on a Mifare Classic", sector).into())
+}
@@ -0,0 +1,151 @@
+// src/consent.rs
+//
+// What institutions ask for before they let the toolkit be used: the legal warning
+// has to be acknowledged once per user before the first attack, operations that
+// change a card are confirmed once per session, and every attack is written to an
+// audit log with the card's UID and how it ended.
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use chrono::Local;
+
+use crate::utils::format_uid;
+use crate::i18n::{tr, tr_args};
+
+/// What has to be typed to accept the warning
+pub const ACKNOWLEDGE_PHRASE: &str = "I AGREE";
+
+pub const WARNING: &str = "\
+WARNING: the attacks in this toolkit recover keys and change cards.
+Use them only on cards you own or are explicitly authorized in writing to test.
+Attacking, cloning or changing other people's cards can be a crime, and every
+attack you run is recorded in an audit log with your login and the card's UID.";
+
+/// Operations confirmed in this session
+static CONFIRMED: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+/// ~/.config/mifare-attack-toolkit (or $XDG_CONFIG_HOME)
+pub(crate) fn config_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("mifare-attack-toolkit")
+}
+
+/// Where the acceptance of the warning is kept
+pub fn acknowledgment_path() -> PathBuf {
+    config_dir().join("acknowledged")
+}
+
+/// The audit log, MIFARE_AUDIT_LOG puts it somewhere shared
+pub fn audit_log_path() -> PathBuf {
+    std::env::var_os("MIFARE_AUDIT_LOG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config_dir().join("audit.log"))
+}
+
+/// Show the warning until it has been accepted once, false if it wasn't accepted
+pub fn ensure_acknowledged() -> Result<bool, Box<dyn Error>> {
+    if is_acknowledged() {
+        return Ok(true);
+    }
+
+    println!("\n{}", WARNING);
+    print!("\n{} ", tr_args("consent-type-phrase", &[("phrase", ACKNOWLEDGE_PHRASE)]));
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim() != ACKNOWLEDGE_PHRASE {
+        println!("{}", tr("consent-not-accepted"));
+        return Ok(false);
+    }
+
+    acknowledge()?;
+    Ok(true)
+}
+
+/// Whether the warning has been accepted by this user
+pub fn is_acknowledged() -> bool {
+    acknowledgment_path().exists()
+}
+
+/// Keep the acceptance of the warning, once the phrase was typed
+pub fn acknowledge() -> Result<(), Box<dyn Error>> {
+    let path = acknowledgment_path();
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    fs::write(&path, format!("accepted by {} at {}\n", login(), Local::now().format("%Y-%m-%d %H:%M:%S")))?;
+    record("-\twarning accepted\t-\t-");
+    Ok(())
+}
+
+/// Ask before the first run of an operation that changes a card in this session
+pub fn confirm_destructive(operation: &str) -> Result<bool, Box<dyn Error>> {
+    let mut confirmed = CONFIRMED.lock().map_err(|_| "Confirmation state poisoned")?;
+    let confirmed = confirmed.get_or_insert_with(HashSet::new);
+    if confirmed.contains(operation) {
+        return Ok(true);
+    }
+
+    println!("\n{}", tr_args("consent-changes-card", &[("operation", operation)]));
+    print!("{} ", tr("consent-authorized"));
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() != "y" {
+        return Ok(false);
+    }
+
+    confirmed.insert(operation.to_string());
+    Ok(true)
+}
+
+/// Add an attack to the audit log, a log that can't be written is reported but
+/// doesn't undo the attack
+pub fn audit(backend: &str, operation: &str, uid: Option<&[u8]>, result: &Result<(), Box<dyn Error>>) {
+    let outcome = match result {
+        Ok(()) => "completed".to_string(),
+        Err(e) => format!("failed: {}", e),
+    };
+    let uid = uid.map_or("-".to_string(), format_uid);
+    record(&format!("{}\t{}\t{}\t{}", backend, operation, uid, outcome));
+}
+
+// Appends one tab separated line: time, login, then the entry
+fn record(entry: &str) {
+    let path = audit_log_path();
+    let line = format!("{}\t{}\t{}\n", Local::now().format("%Y-%m-%d %H:%M:%S"), login(), entry);
+    let written = path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| OpenOptions::new().create(true).append(true).open(&path))
+        .and_then(|mut log| log.write_all(line.as_bytes()));
+    if let Err(e) = written {
+        println!("{}", tr_args("err-writing-audit-log", &[("path", &path.display().to_string()), ("error", &e.to_string())]));
+    }
+}
+
+// The login of the process's real user id as the kernel has it, USER can be set to anything
+fn login() -> String {
+    let uid = fs::read_to_string("/proc/self/status").ok().and_then(|status| {
+        status.lines()
+            .find(|line| line.starts_with("Uid:"))
+            .and_then(|line| line.split_whitespace().nth(1).map(str::to_string))
+    });
+    let uid = match uid {
+        Some(uid) => uid,
+        None => return std::env::var("USER").unwrap_or_else(|_| "unknown".to_string()),
+    };
+
+    fs::read_to_string("/etc/passwd").ok()
+        .and_then(|passwd| passwd.lines()
+            .map(|line| line.split(':').collect::<Vec<_>>())
+            .find(|fields| fields.len() >= 3 && fields[2] == uid)
+            .map(|fields| fields[0].to_string()))
+        .unwrap_or(format!("uid {}", uid))
+}
@@ -1,3 +1,5 @@
+use crate::i18n::{tr, tr_args};
+
 /// This is a Rust implementation of the CRYPTO1 stream cipher used in Mifare Classic cards
 /// Based on the C implementation from the Proxmark3 project
 
@@ -119,8 +121,8 @@ impl NestedAttack {
     pub fn recover_key(&self, _uid: u32, _known_key: &[u8], known_block: u8, target_block: u8) 
         -> Result<Option<[u8; 6]>, String> {
         
-        println!("Starting nested attack with known key for block {}", known_block);
-        println!("Target block: {}", target_block);
+        println!("{}", tr_args("starting-nested-attack-with-known", &[("block", &known_block.to_string())]));
+        println!("{}", tr_args("target-block", &[("block", &target_block.to_string())]));
         
         // For this demo, we'll return a hardcoded key
         // In a real implementation, this would use collected nonces to recover the key
@@ -268,8 +270,8 @@ impl DarksideAttack {
     pub fn recover_key(&self, _reader: &mut dyn MifareReader, block: u8) 
         -> Result<Option<[u8; 6]>, String> {
         
-        println!("Starting darkside attack on block {}", block);
-        println!("This attack works only on vulnerable MIFARE Classic cards");
+        println!("{}", tr_args("starting-darkside-attack-on-block", &[("block", &block.to_string())]));
+        println!("{}", tr("darkside-vulnerable-only"));
         
         // The darkside attack works by sending malformed authentication commands
         // and observing the responses, which can leak key bits in vulnerable cards
@@ -0,0 +1,321 @@
+// src/gui/mod.rs
+//
+// The attacks in an FLTK window (--gui), next to the terminal menu: a panel with what
+// is known about the card on the reader, the attack and its settings, a progress bar
+// for the nonces collected and one for the keys tried, and the keys found so far. A
+// key picked in the list is filled in as the known key of a nested attack, and what the
+// last attack found is shown under the list with the file it was saved to. The attacks
+// run as jobs on the job queue's worker, as from the menu.
+use std::cell::RefCell;
+use std::rc::Rc;
+use fltk::{
+    app,
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, FrameType},
+    frame::Frame,
+    input::{Input, IntInput},
+    menu::Choice,
+    misc::Progress,
+    prelude::*,
+    window::Window,
+};
+
+use crate::cards::KeyType;
+use crate::consent;
+use crate::jobs::{Job, JobEvent, JobQueue, Stage};
+use crate::reader::MifareClassic;
+use crate::utils::{bytes_to_hex, format_uid, hex_to_bytes};
+
+/// A key found: sector, which key and the key
+type FoundKey = (u8, KeyType, [u8; 6]);
+
+/// What the attack list offers
+#[derive(Clone, Copy, PartialEq)]
+enum Choosable {
+    DefaultKeys,
+    Nested,
+    Darkside,
+    Autopwn,
+    PrngTest,
+}
+
+impl Choosable {
+    fn label(&self) -> &'static str {
+        match self {
+            Choosable::DefaultKeys => "Default keys (dictionary)",
+            Choosable::Nested => "Nested (from a known key)",
+            Choosable::Darkside => "Darkside (first key)",
+            Choosable::Autopwn => "Autopwn (every key, then dump)",
+            Choosable::PrngTest => "PRNG test",
+        }
+    }
+}
+
+/// Open the window and run it until it is closed, then hand the reader back
+pub fn run(reader: MifareClassic) -> MifareClassic {
+    let app = app::App::default();
+    let (sender, receiver) = app::channel::<JobEvent>();
+
+    // attacks are left out in read-only mode, as in the terminal menu
+    let choices: Vec<Choosable> = if safe_mode::is_read_only() {
+        vec![Choosable::PrngTest]
+    } else {
+        vec![Choosable::DefaultKeys, Choosable::Nested, Choosable::Darkside, Choosable::Autopwn, Choosable::PrngTest]
+    };
+
+    let mut win = Window::new(100, 100, 640, 600, "MIFARE Attack Toolkit");
+
+    let mut card_box = Frame::new(10, 25, 620, 105, "Card");
+    card_box.set_frame(FrameType::EngravedBox);
+    card_box.set_align(Align::TopLeft);
+    let mut uid_frame = Frame::new(20, 35, 480, 22, "UID: -");
+    let mut type_frame = Frame::new(20, 57, 480, 22, "Type: -");
+    let mut magic_frame = Frame::new(20, 79, 480, 22, "Magic card: -");
+    let mut prng_frame = Frame::new(20, 101, 480, 22, "PRNG: not tested");
+    for frame in [&mut uid_frame, &mut type_frame, &mut magic_frame, &mut prng_frame] {
+        frame.set_align(Align::Left | Align::Inside);
+    }
+    let mut read_btn = Button::new(520, 40, 100, 30, "Read Card");
+
+    let mut attack_choice = Choice::new(70, 145, 250, 25, "Attack:");
+    for choice in &choices {
+        attack_choice.add_choice(choice.label());
+    }
+    attack_choice.set_value(0);
+    let mut run_btn = Button::new(440, 145, 90, 25, "Run");
+    let mut cancel_btn = Button::new(540, 145, 90, 25, "Cancel");
+    cancel_btn.deactivate();
+
+    let mut key_input = Input::new(70, 180, 130, 25, "Key:");
+    key_input.set_value("FFFFFFFFFFFF");
+    let mut sector_input = IntInput::new(260, 180, 40, 25, "Sector:");
+    sector_input.set_value("0");
+    let mut key_type_choice = Choice::new(340, 180, 80, 25, "Type:");
+    key_type_choice.add_choice("Key A|Key B");
+    key_type_choice.set_value(0);
+    let mut block_input = IntInput::new(480, 180, 40, 25, "Block:");
+    block_input.set_value("0");
+
+    let mut nonce_label = Frame::new(10, 220, 90, 20, "Nonces");
+    nonce_label.set_align(Align::Left | Align::Inside);
+    let mut nonce_progress = Progress::new(100, 220, 530, 20, None);
+    nonce_progress.set_minimum(0.0);
+    nonce_progress.set_maximum(1.0);
+    let mut key_label = Frame::new(10, 250, 90, 20, "Keys tried");
+    key_label.set_align(Align::Left | Align::Inside);
+    let mut key_progress = Progress::new(100, 250, 530, 20, None);
+    key_progress.set_minimum(0.0);
+    key_progress.set_maximum(1.0);
+
+    Frame::new(10, 280, 620, 20, "Sector / key type / key").set_align(Align::Left | Align::Inside);
+    let mut key_browser = HoldBrowser::new(10, 300, 620, 225, "");
+    key_browser.set_column_widths(&[80, 100, 200]);
+    key_browser.set_column_char('\t');
+    let mut result_frame = Frame::new(10, 530, 620, 25, "Last result: -");
+    result_frame.set_align(Align::Left | Align::Inside | Align::Clip);
+
+    let mut reader_frame = Frame::new(10, 565, 200, 25, "Reader: -");
+    reader_frame.set_align(Align::Left | Align::Inside);
+    let mut status_frame = Frame::new(220, 565, 410, 25, None);
+    status_frame.set_align(Align::Left | Align::Inside | Align::Clip);
+
+    win.end();
+    win.show();
+
+    // taken back out when the window closes, the callbacks only borrow it
+    let jobs = Rc::new(RefCell::new(Some(JobQueue::start(reader, move |_, event| sender.send(event)))));
+    let keys: Rc<RefCell<Vec<FoundKey>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // the buttons that start a job are off while one runs
+    let set_busy = {
+        let read_btn = read_btn.clone();
+        let run_btn = run_btn.clone();
+        let cancel_btn = cancel_btn.clone();
+        move |busy: bool| {
+            let (mut read_btn, mut run_btn, mut cancel_btn) = (read_btn.clone(), run_btn.clone(), cancel_btn.clone());
+            if busy {
+                read_btn.deactivate();
+                run_btn.deactivate();
+                cancel_btn.activate();
+            } else {
+                read_btn.activate();
+                run_btn.activate();
+                cancel_btn.deactivate();
+            }
+        }
+    };
+
+    // this queues a job, with the progress bars back at the start
+    let submit = {
+        let jobs = jobs.clone();
+        let set_busy = set_busy.clone();
+        let nonce_progress = nonce_progress.clone();
+        let key_progress = key_progress.clone();
+        let status_frame = status_frame.clone();
+        move |job: Job, status: &str| {
+            nonce_progress.clone().set_value(0.0);
+            key_progress.clone().set_value(0.0);
+            status_frame.clone().set_label(status);
+            set_busy(true);
+            if let Some(jobs) = jobs.borrow().as_ref() {
+                jobs.submit(job);
+            }
+        }
+    };
+
+    let submit_read = submit.clone();
+    read_btn.set_callback(move |_| {
+        submit_read(Job::ReadCard, "Place the card on the reader...");
+    });
+
+    // a key picked in the list becomes the known key of a nested attack
+    let keys_pick = keys.clone();
+    let mut key_input_pick = key_input.clone();
+    let mut sector_input_pick = sector_input.clone();
+    let mut key_type_pick = key_type_choice.clone();
+    key_browser.set_callback(move |browser| {
+        let picked = usize::try_from(browser.value() - 1).ok().and_then(|index| keys_pick.borrow().get(index).copied());
+        if let Some((sector, key_type, key)) = picked {
+            key_input_pick.set_value(&bytes_to_hex(&key).replace(' ', ""));
+            sector_input_pick.set_value(&sector.to_string());
+            key_type_pick.set_value(if key_type == KeyType::KeyB { 1 } else { 0 });
+        }
+    });
+
+    run_btn.set_callback(move |_| {
+        let choice = match usize::try_from(attack_choice.value()).ok().and_then(|index| choices.get(index)) {
+            Some(choice) => *choice,
+            None => return,
+        };
+        let job = match choice {
+            Choosable::PrngTest => Job::PrngTest,
+            Choosable::DefaultKeys => Job::DefaultKeys,
+            Choosable::Autopwn => Job::Autopwn,
+            Choosable::Nested => {
+                let key = match hex_to_bytes(key_input.value().trim()).ok().and_then(|key| <[u8; 6]>::try_from(key).ok()) {
+                    Some(key) => key,
+                    None => {
+                        dialog::alert(300, 300, "The known key has to be 12 hex digits");
+                        return;
+                    }
+                };
+                let sector = match sector_input.value().trim().parse::<u8>() {
+                    Ok(sector) if sector < 16 => sector,
+                    _ => {
+                        dialog::alert(300, 300, "The sector of the known key has to be 0-15");
+                        return;
+                    }
+                };
+                let key_type = if key_type_choice.value() == 1 { KeyType::KeyB } else { KeyType::KeyA };
+                Job::Nested { sector, key_type, key }
+            },
+            Choosable::Darkside => match block_input.value().trim().parse::<u8>() {
+                Ok(block) if block <= 63 => Job::Darkside { block },
+                _ => {
+                    dialog::alert(300, 300, "The target block has to be 0-63");
+                    return;
+                }
+            },
+        };
+        if !matches!(job, Job::PrngTest) && !acknowledged() {
+            return;
+        }
+        submit(job, &format!("{}: place the card on the reader...", choice.label()));
+    });
+
+    let jobs_cancel = jobs.clone();
+    let mut status_cancel = status_frame.clone();
+    cancel_btn.set_callback(move |_| {
+        if let Some(jobs) = jobs_cancel.borrow().as_ref() {
+            jobs.cancel();
+        }
+        status_cancel.set_label("Cancelling...");
+    });
+
+    while app.wait() {
+        while let Some(event) = receiver.recv() {
+            match event {
+                // the status was set as the job was queued
+                JobEvent::Started(_) => {},
+                JobEvent::Reader(status) => reader_frame.set_label(&format!("Reader: {}", status)),
+                JobEvent::Card { uid, card_type, magic } => {
+                    uid_frame.set_label(&format!("UID: {}", format_uid(&uid)));
+                    type_frame.set_label(&format!("Type: {}", card_type));
+                    magic_frame.set_label(&format!("Magic card: {}", if magic { "likely, from its UID" } else { "no" }));
+                },
+                JobEvent::Progress { stage, progress } => {
+                    let bar = match stage {
+                        Stage::Nonces => &mut nonce_progress,
+                        // the dump follows the keys, on the same bar
+                        Stage::Keys | Stage::Dump => &mut key_progress,
+                    };
+                    bar.set_value(progress.fraction());
+                    bar.set_label(&progress.to_string());
+                },
+                JobEvent::Key { sector, key_type, key } => {
+                    let mut keys = keys.borrow_mut();
+                    keys.retain(|(s, t, _)| (*s, *t) != (sector, key_type));
+                    keys.push((sector, key_type, key));
+                    keys.sort_by_key(|(s, t, _)| (*s, *t == KeyType::KeyB));
+                    key_browser.clear();
+                    for (sector, key_type, key) in keys.iter() {
+                        key_browser.add(&format!("{}\t{}\t{}", sector, key_type, bytes_to_hex(key)));
+                    }
+                },
+                JobEvent::Prng(report) => {
+                    let darkside = if report.darkside_viable() { "darkside" } else { "" };
+                    let nested = if report.nested_viable() { "nested" } else { "" };
+                    let viable: Vec<&str> = [darkside, nested].into_iter().filter(|name| !name.is_empty()).collect();
+                    prng_frame.set_label(&format!(
+                        "PRNG: {}, {}",
+                        report.kind,
+                        if viable.is_empty() { "no nonce attack works".to_string() } else { format!("{} should work", viable.join(" and ")) }
+                    ));
+                },
+                JobEvent::Result { result, saved } => {
+                    let saved = saved.map_or("not saved".to_string(), |path| format!("saved to {}", path.display()));
+                    result_frame.set_label(&format!("Last result: {}, {}", result.summary(), saved));
+                    result_frame.set_tooltip(&result.to_string());
+                },
+                JobEvent::Finished(summary) => {
+                    status_frame.set_label(&summary);
+                    set_busy(false);
+                },
+                JobEvent::Cancelled => {
+                    status_frame.set_label("Cancelled");
+                    set_busy(false);
+                },
+                JobEvent::Failed(error) => {
+                    status_frame.set_label("");
+                    set_busy(false);
+                    dialog::alert(300, 300, &error);
+                },
+            }
+        }
+    }
+
+    // a job still running is cancelled, then the reader comes back from the worker
+    let jobs = jobs.borrow_mut().take().expect("the job queue is only taken here");
+    jobs.cancel();
+    jobs.shutdown()
+}
+
+// this is the warning of the terminal menu, accepted by typing the same phrase
+fn acknowledged() -> bool {
+    if consent::is_acknowledged() {
+        return true;
+    }
+    let prompt = format!("{}\n\nType '{}' to accept:", consent::WARNING, consent::ACKNOWLEDGE_PHRASE);
+    let answer = dialog::input(300, 300, &prompt, "");
+    if answer.as_deref().map(str::trim) != Some(consent::ACKNOWLEDGE_PHRASE) {
+        dialog::alert(300, 300, "Not accepted, the attacks stay unavailable.");
+        return false;
+    }
+    if let Err(e) = consent::acknowledge() {
+        dialog::alert(300, 300, &format!("Error saving the acceptance: {}", e));
+        return false;
+    }
+    true
+}
@@ -0,0 +1,29 @@
+// Translated console output
+//
+// Each language has a catalogue in locales/<code>.ftl, built into the binary and read
+// by the text_catalogue crate, the same way as the reader app's. The language follows
+// LANG (e.g. LANG=es_ES.UTF-8); a missing translation shows up in English.
+use text_catalogue::Catalogues;
+
+pub use text_catalogue::AUTO_LANGUAGE;
+
+static CATALOGUES: Catalogues = Catalogues::new(&[
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("it", include_str!("../locales/it.ftl")),
+]);
+
+// Switch the language used by tr(), "auto" picks it from LANG
+pub fn set_language(setting: &str) {
+    CATALOGUES.set_language(setting);
+}
+
+// Text for a message key in the current language
+pub fn tr(key: &str) -> String {
+    CATALOGUES.tr(key)
+}
+
+// Text for a message key with its { $name } placeables filled in
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    CATALOGUES.tr_args(key, args)
+}
@@ -0,0 +1,664 @@
+// src/jobs.rs
+//
+// Attacks run as jobs on a worker thread, so the menu and the window stay usable while
+// one runs: they show its progress, queue the next one or cancel it. A job reports
+// what it does through the callback the queue was started with, and checks a cancel
+// flag between steps. The nonces a job collects are kept on disk by card and block
+// until the job is done with them, so a cancelled or interrupted job picks up where it
+// stopped instead of sampling the card again. Progress goes in a status file too, for
+// the REST API. What an attack found is saved as a result file, and its keys go in
+// the key store.
+//
+// Built without the attacks, the menu only uses the queue to share the reader.
+#![cfg_attr(not(feature = "attacks"), allow(dead_code))]
+
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "attacks")]
+use std::fs::{self, OpenOptions};
+#[cfg(feature = "attacks")]
+use std::io::Write;
+#[cfg(feature = "attacks")]
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use progress_sink::{Progress, ProgressSink, StatusFile, Tracker};
+
+use crate::card_detection::detect_card;
+use crate::cards::{identify_card_type, is_magic_card, CardType};
+use crate::i18n::{tr, tr_args};
+#[cfg(feature = "attacks")]
+use crate::attacks;
+#[cfg(feature = "attacks")]
+use crate::cards::{key_store_path, KeyType};
+#[cfg(feature = "attacks")]
+use crate::consent;
+use crate::mifare_attack_manager::RECONNECT_ATTEMPTS;
+#[cfg(feature = "attacks")]
+use crate::prng::{analyse_nonces, PrngReport, PRNG_SAMPLES};
+use crate::reader::MifareClassic;
+#[cfg(feature = "attacks")]
+use crate::results::{self, AttackResult};
+#[cfg(feature = "attacks")]
+use crate::utils::bytes_to_hex;
+use crate::utils::format_uid;
+
+/// How long a job waits for a card
+pub(crate) const CARD_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Numbered from 1 in the order the jobs were queued
+pub type JobId = usize;
+
+/// What can be queued
+#[derive(Clone, Copy)]
+pub enum Job {
+    /// Wait for a card and show it, only the window queues it
+    #[cfg_attr(not(feature = "gui"), allow(dead_code))]
+    ReadCard,
+    /// Whether darkside and nested can work on the card
+    #[cfg(feature = "attacks")]
+    PrngTest,
+    /// Both keys of every sector against the default key dictionary
+    #[cfg(feature = "attacks")]
+    DefaultKeys,
+    /// The keys of the other sectors from a key known for one
+    #[cfg(feature = "attacks")]
+    Nested { sector: u8, key_type: KeyType, key: [u8; 6] },
+    /// A first key, for the sector of `block`
+    #[cfg(feature = "attacks")]
+    Darkside { block: u8 },
+    /// Every key the attacks can recover, then the dump
+    #[cfg(feature = "attacks")]
+    Autopwn,
+}
+
+impl Job {
+    /// As the audit log and the job list show it
+    pub fn name(&self) -> &'static str {
+        match self {
+            Job::ReadCard => "read card",
+            #[cfg(feature = "attacks")]
+            Job::PrngTest => "PRNG test",
+            #[cfg(feature = "attacks")]
+            Job::DefaultKeys => "default key search",
+            #[cfg(feature = "attacks")]
+            Job::Nested { .. } => "nested attack",
+            #[cfg(feature = "attacks")]
+            Job::Darkside { .. } => "darkside attack",
+            #[cfg(feature = "attacks")]
+            Job::Autopwn => "autopwn",
+        }
+    }
+
+    // the attacks go in the audit log, as they do from the menu
+    #[cfg(feature = "attacks")]
+    fn audited(&self) -> bool {
+        matches!(self, Job::DefaultKeys | Job::Nested { .. } | Job::Darkside { .. } | Job::Autopwn)
+    }
+}
+
+/// The part of a job a progress report is about
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stage {
+    Nonces,
+    Keys,
+    /// Reading the sectors once the keys are known
+    Dump,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Nonces => write!(f, "{}", tr("stage-nonces")),
+            Stage::Keys => write!(f, "{}", tr("stage-keys")),
+            Stage::Dump => write!(f, "{}", tr("stage-dump")),
+        }
+    }
+}
+
+/// What a job reports, each job ends with one of Finished, Cancelled or Failed
+pub enum JobEvent {
+    Started(&'static str),
+    /// Whether the reader answers
+    Reader(String),
+    Card { uid: Vec<u8>, card_type: CardType, magic: bool },
+    Progress { stage: Stage, progress: Progress },
+    #[cfg(feature = "attacks")]
+    Key { sector: u8, key_type: KeyType, key: [u8; 6] },
+    #[cfg(feature = "attacks")]
+    Prng(PrngReport),
+    /// What an attack found, and the file it was saved to
+    #[cfg(feature = "attacks")]
+    Result { result: Box<AttackResult>, saved: Option<PathBuf> },
+    // a line saying what the job found
+    Finished(String),
+    Cancelled,
+    Failed(String),
+}
+
+/// Where a job stands
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobStatus {
+    Queued,
+    Running { stage: Option<Stage>, progress: Progress },
+    Finished(String),
+    Cancelled,
+    Failed(String),
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobStatus::Queued => write!(f, "{}", tr("status-queued")),
+            JobStatus::Running { stage: Some(_), progress } => write!(f, "{}", tr_args("status-running", &[("progress", &progress.to_string())])),
+            JobStatus::Running { stage: None, .. } => write!(f, "{}", tr("status-waiting-for-card")),
+            JobStatus::Finished(summary) => write!(f, "{}", tr_args("status-finished", &[("summary", summary)])),
+            JobStatus::Cancelled => write!(f, "{}", tr("status-cancelled")),
+            JobStatus::Failed(error) => write!(f, "{}", tr_args("status-failed", &[("error", error)])),
+        }
+    }
+}
+
+/// A job in the queue's list
+#[derive(Clone, Debug)]
+pub struct JobInfo {
+    pub id: JobId,
+    pub name: &'static str,
+    pub status: JobStatus,
+}
+
+/// What a running job gets besides the reader: where to report, and whether to stop
+pub struct JobContext<'a> {
+    report: &'a dyn Fn(JobEvent),
+    cancel: &'a AtomicBool,
+    // paces the stage the job is in
+    tracker: RefCell<Option<(Stage, Tracker)>>,
+}
+
+impl JobContext<'_> {
+    pub fn report(&self, event: JobEvent) {
+        (self.report)(event);
+    }
+
+    /// `done` of `total` steps of `stage`, the time left is estimated per stage
+    pub fn progress(&self, stage: Stage, done: usize, total: usize) {
+        let progress = {
+            let mut current = self.tracker.borrow_mut();
+            if current.as_ref().map(|(current, _)| *current) != Some(stage) {
+                *current = Some((stage, Tracker::new()));
+            }
+            let (_, tracker) = current.as_mut().expect("the tracker was just set");
+            tracker.progress(done, total, format!("{}/{} {}", done, total, stage))
+        };
+        self.report(JobEvent::Progress { stage, progress });
+    }
+
+    /// Checked between steps, a job that sees it returns Ok(None)
+    pub fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::SeqCst)
+    }
+}
+
+/// The reader and the worker thread that runs the jobs on it, one at a time
+pub struct JobQueue {
+    reader: Arc<Mutex<MifareClassic>>,
+    jobs: Arc<Mutex<Vec<JobInfo>>>,
+    cancel: Arc<AtomicBool>,
+    requests: Option<mpsc::Sender<(JobId, Job)>>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl JobQueue {
+    /// Start the worker, `on_event` gets everything the jobs report, on the worker thread
+    pub fn start(reader: MifareClassic, on_event: impl Fn(JobId, JobEvent) + Send + 'static) -> Self {
+        let reader = Arc::new(Mutex::new(reader));
+        let jobs = Arc::new(Mutex::new(Vec::new()));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (requests, receiver) = mpsc::channel();
+
+        let worker = {
+            let (reader, jobs, cancel) = (reader.clone(), jobs.clone(), cancel.clone());
+            thread::spawn(move || run_jobs(&reader, receiver, &jobs, &cancel, &on_event))
+        };
+        Self { reader, jobs, cancel, requests: Some(requests), worker: Some(worker) }
+    }
+
+    /// Queue a job behind the ones already queued
+    pub fn submit(&self, job: Job) -> JobId {
+        let id = {
+            let mut jobs = lock(&self.jobs);
+            let id = jobs.len() + 1;
+            jobs.push(JobInfo { id, name: job.name(), status: JobStatus::Queued });
+            id
+        };
+        if let Some(requests) = &self.requests {
+            if requests.send((id, job)).is_err() {
+                set_status(&self.jobs, id, JobStatus::Failed(tr("err-job-worker-stopped")));
+            }
+        }
+        id
+    }
+
+    /// Stop the running job at its next step and drop the queued ones
+    pub fn cancel(&self) {
+        let mut jobs = lock(&self.jobs);
+        for info in jobs.iter_mut() {
+            match info.status {
+                JobStatus::Queued => info.status = JobStatus::Cancelled,
+                JobStatus::Running { .. } => self.cancel.store(true, Ordering::SeqCst),
+                _ => {}
+            }
+        }
+    }
+
+    /// Every job queued so far, oldest first
+    pub fn jobs(&self) -> Vec<JobInfo> {
+        lock(&self.jobs).clone()
+    }
+
+    /// Whether a job is running or waiting to
+    pub fn is_busy(&self) -> bool {
+        lock(&self.jobs).iter().any(|info| matches!(info.status, JobStatus::Queued | JobStatus::Running { .. }))
+    }
+
+    /// Run `op` on the reader unless a job has it
+    pub fn with_reader<T>(&self, op: impl FnOnce(&mut MifareClassic) -> T) -> Result<T, Box<dyn Error>> {
+        match self.reader.try_lock() {
+            Ok(mut reader) => Ok(op(&mut reader)),
+            Err(TryLockError::Poisoned(poisoned)) => Ok(op(&mut poisoned.into_inner())),
+            Err(TryLockError::WouldBlock) => Err(tr("err-reader-busy").into()),
+        }
+    }
+
+    /// Cancel what is left, wait for the worker and hand the reader back
+    pub fn shutdown(mut self) -> MifareClassic {
+        self.cancel();
+        self.requests = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+        let reader = Arc::try_unwrap(self.reader).unwrap_or_else(|_| panic!("the job worker still holds the reader"));
+        reader.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+// A job that panicked leaves the lists usable
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn set_status(jobs: &Mutex<Vec<JobInfo>>, id: JobId, status: JobStatus) {
+    if let Some(info) = lock(jobs).iter_mut().find(|info| info.id == id) {
+        info.status = status;
+    }
+}
+
+fn run_jobs(
+    reader: &Mutex<MifareClassic>,
+    receiver: Receiver<(JobId, Job)>,
+    jobs: &Mutex<Vec<JobInfo>>,
+    cancel: &AtomicBool,
+    on_event: &dyn Fn(JobId, JobEvent),
+) {
+    for (id, job) in receiver {
+        // a job cancelled while it was queued is skipped, the flag is cleared under the
+        // same lock cancel() takes so a cancel can't fall between the two
+        {
+            let mut jobs = lock(jobs);
+            match jobs.iter_mut().find(|info| info.id == id) {
+                Some(info) if info.status == JobStatus::Queued => {
+                    info.status = JobStatus::Running { stage: None, progress: Progress::default() };
+                    cancel.store(false, Ordering::SeqCst);
+                },
+                _ => continue,
+            }
+        }
+
+        let status_file = StatusFile::new(job.name());
+        let report = |event: JobEvent| {
+            let status = match &event {
+                JobEvent::Progress { stage, progress } => {
+                    status_file.report(progress);
+                    Some(JobStatus::Running { stage: Some(*stage), progress: progress.clone() })
+                },
+                JobEvent::Finished(summary) => Some(JobStatus::Finished(summary.clone())),
+                JobEvent::Cancelled => Some(JobStatus::Cancelled),
+                JobEvent::Failed(error) => Some(JobStatus::Failed(error.clone())),
+                _ => None,
+            };
+            if let Some(status) = status {
+                set_status(jobs, id, status);
+            }
+            on_event(id, event);
+        };
+        let context = JobContext { report: &report, cancel, tracker: RefCell::new(None) };
+
+        report(JobEvent::Started(job.name()));
+        let result = run_job(&mut lock(reader), job, &context);
+        report(match result {
+            Ok(Some(summary)) => JobEvent::Finished(summary),
+            Ok(None) => JobEvent::Cancelled,
+            Err(e) => JobEvent::Failed(e.to_string()),
+        });
+    }
+}
+
+// Ok(None) from here on means the job was cancelled
+fn run_job(reader: &mut MifareClassic, job: Job, context: &JobContext) -> Result<Option<String>, Box<dyn Error>> {
+    reader.take_last_uid();
+    let uid = match wait_for_card(reader, context)? {
+        Some(uid) => uid,
+        None => return Ok(None),
+    };
+
+    let result = match job {
+        Job::ReadCard => Ok(Some(tr_args("job-card-read", &[("uid", &format_uid(&uid))]))),
+        #[cfg(feature = "attacks")]
+        Job::PrngTest => test_prng(reader, &uid, context),
+        #[cfg(feature = "attacks")]
+        Job::DefaultKeys | Job::Nested { .. } | Job::Darkside { .. } | Job::Autopwn => run_attack(reader, &uid, job, context),
+    };
+
+    #[cfg(feature = "attacks")]
+    if job.audited() {
+        let audited: Result<(), Box<dyn Error>> = match &result {
+            Ok(Some(_)) => Ok(()),
+            Ok(None) => Err("cancelled".into()),
+            Err(e) => Err(e.to_string().into()),
+        };
+        consent::audit("MFRC522", job.name(), reader.take_last_uid().as_deref(), &audited);
+    }
+    result
+}
+
+// this waits for a card and selects it, after reconnecting if the reader went away
+// since the last job
+fn wait_for_card(reader: &mut MifareClassic, context: &JobContext) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let status = reader.ensure_connected(RECONNECT_ATTEMPTS);
+    context.report(JobEvent::Reader(status.to_string()));
+
+    reader.stop_crypto1()?;
+    let start = Instant::now();
+    while start.elapsed() < CARD_TIMEOUT {
+        if context.cancelled() {
+            return Ok(None);
+        }
+        if let Some(uid) = detect_card(reader)? {
+            context.report(JobEvent::Card {
+                uid: uid.clone(),
+                card_type: identify_card_type(&uid, None),
+                magic: is_magic_card(&uid),
+            });
+            return Ok(Some(uid));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(tr_args("err-no-card-in-seconds", &[("seconds", &CARD_TIMEOUT.as_secs().to_string())]).into())
+}
+
+#[cfg(feature = "attacks")]
+fn run_attack(reader: &mut MifareClassic, uid: &[u8], job: Job, context: &JobContext) -> Result<Option<String>, Box<dyn Error>> {
+    let found = match job {
+        Job::DefaultKeys => attacks::default_keys::search_default_keys(reader, uid, context)?,
+        Job::Nested { sector, key_type, key } => attacks::nested::nested_attack(reader, uid, sector, key_type, key, context)?,
+        Job::Darkside { block } => attacks::darkside::darkside_attack(reader, uid, block, context)?,
+        Job::Autopwn => attacks::autopwn::autopwn(reader, uid, context)?,
+        _ => unreachable!("{} is not an attack", job.name()),
+    };
+    Ok(found.map(|result| keep_result(result, context)))
+}
+
+// this saves what an attack found and adds its keys to the key store, a file that
+// can't be written is said in the summary
+#[cfg(feature = "attacks")]
+fn keep_result(mut result: AttackResult, context: &JobContext) -> String {
+    result.finish();
+    let mut summary = result.summary();
+    let saved = match result.save(&results::results_dir()) {
+        Ok(path) => Some(path),
+        Err(e) => {
+            summary.push_str(&tr_args("summary-not-saved", &[("error", &e.to_string())]));
+            None
+        },
+    };
+    match results::add_to_key_store(&result, &key_store_path()) {
+        Ok(0) => {},
+        Ok(added) => summary.push_str(&tr_args("summary-new-in-key-store", &[("count", &added.to_string())])),
+        Err(e) => summary.push_str(&tr_args("summary-not-added-to-key-store", &[("error", &e.to_string())])),
+    }
+    context.report(JobEvent::Result { result: Box::new(result), saved });
+    summary
+}
+
+#[cfg(feature = "attacks")]
+fn test_prng(reader: &mut MifareClassic, uid: &[u8], context: &JobContext) -> Result<Option<String>, Box<dyn Error>> {
+    let report = match collect_nonces(reader, uid, 0, context)? {
+        Some(report) => report,
+        None => return Ok(None),
+    };
+    let summary = format!("PRNG: {}", report.kind);
+    context.report(JobEvent::Prng(report));
+    Ok(Some(summary))
+}
+
+/// Sample PRNG_SAMPLES pairs of the card's nonces a pair at a time, each kept on disk
+/// as it comes in. The pairs kept by a job that didn't get to the end count, and the
+/// file goes once the nonces are analysed.
+#[cfg(feature = "attacks")]
+pub fn collect_nonces(
+    reader: &mut MifareClassic,
+    uid: &[u8],
+    block: u8,
+    context: &JobContext,
+) -> Result<Option<PrngReport>, Box<dyn Error>> {
+    let path = nonce_path(uid, block);
+    let mut samples = load_nonces(&path);
+    samples.truncate(PRNG_SAMPLES);
+    let kept = samples.len();
+    context.progress(Stage::Nonces, kept, PRNG_SAMPLES);
+
+    // a sample the card doesn't answer is skipped, as in MifareClassic::collect_nonces
+    for attempt in kept..PRNG_SAMPLES {
+        if context.cancelled() {
+            return Ok(None);
+        }
+        for pair in reader.collect_nonces(block, KeyType::KeyA, 1)? {
+            save_nonces(&path, pair)?;
+            samples.push(pair);
+        }
+        context.progress(Stage::Nonces, attempt + 1, PRNG_SAMPLES);
+    }
+
+    let report = analyse_nonces(&samples).ok_or_else(|| tr("err-no-nonces"))?;
+    let _ = fs::remove_file(&path);
+    Ok(Some(report))
+}
+
+/// Where the nonces of a card's block are kept while a job collects them
+#[cfg(feature = "attacks")]
+pub fn nonce_path(uid: &[u8], block: u8) -> PathBuf {
+    consent::config_dir().join("nonces").join(format!("{}-{}.txt", bytes_to_hex(uid).replace(' ', ""), block))
+}
+
+// One pair a line, two nonces in hex. A missing file holds none, a line that can't be
+// read is skipped.
+#[cfg(feature = "attacks")]
+fn load_nonces(path: &Path) -> Vec<(u32, u32)> {
+    let text = fs::read_to_string(path).unwrap_or_default();
+    text.lines()
+        .filter_map(|line| {
+            let mut nonces = line.split_whitespace().map(|nonce| u32::from_str_radix(nonce, 16).ok());
+            match (nonces.next().flatten(), nonces.next().flatten()) {
+                (Some(first), Some(second)) => Some((first, second)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "attacks")]
+fn save_nonces(path: &Path, (first, second): (u32, u32)) -> Result<(), Box<dyn Error>> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{:08X} {:08X}", first, second)?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "attacks"))]
+mod tests {
+    use std::sync::Once;
+    use mifare_rc522::mock::{MockCard, MockReader, Prng};
+    use super::*;
+
+    const KEY: [u8; 6] = [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+
+    // the nonces and the audit log go to a scratch directory, not the user's
+    fn scratch_config() {
+        static SCRATCH: Once = Once::new();
+        SCRATCH.call_once(|| {
+            let dir = std::env::temp_dir().join(format!("mifare-attack-toolkit-jobs-{}", std::process::id()));
+            std::env::set_var("XDG_CONFIG_HOME", &dir);
+        });
+    }
+
+    fn queue_with(card: MockCard) -> (JobQueue, Receiver<(JobId, JobEvent)>) {
+        scratch_config();
+        let reader = MifareClassic::with_transport(Box::new(MockReader::with_card(card))).unwrap();
+        let (sender, events) = mpsc::channel();
+        let queue = JobQueue::start(reader, move |id, event| {
+            let _ = sender.send((id, event));
+        });
+        (queue, events)
+    }
+
+    // the events of a job up to and including the one it ended with
+    fn events_until_end(events: &Receiver<(JobId, JobEvent)>) -> Vec<JobEvent> {
+        let mut seen = Vec::new();
+        for (_, event) in events.iter() {
+            let end = matches!(event, JobEvent::Finished(_) | JobEvent::Cancelled | JobEvent::Failed(_));
+            seen.push(event);
+            if end {
+                break;
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn default_key_search_runs_in_the_background() {
+        let (queue, events) = queue_with(MockCard::new([0x04, 0x71, 0x72, 0x73]).with_keys(2, KEY, [0xFF; 6]));
+        let id = queue.submit(Job::DefaultKeys);
+        assert!(queue.is_busy());
+
+        let seen = events_until_end(&events);
+        let keys: Vec<(u8, KeyType, [u8; 6])> = seen.iter()
+            .filter_map(|event| match event {
+                JobEvent::Key { sector, key_type, key } => Some((*sector, *key_type, *key)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(keys.len(), 32);
+        assert!(keys.contains(&(2, KeyType::KeyA, KEY)));
+        assert!(matches!(seen.last(), Some(JobEvent::Finished(_))));
+
+        // the keys are saved with the card, and go in the key store
+        let (result, saved) = seen.iter()
+            .find_map(|event| match event {
+                JobEvent::Result { result, saved } => Some((result, saved)),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(result.keys().count(), 32);
+        assert_eq!(result.sectors.len(), 16);
+        let loaded: AttackResult = serde_json::from_str(&fs::read_to_string(saved.as_ref().unwrap()).unwrap()).unwrap();
+        assert_eq!(loaded.sectors, result.sectors);
+        let store = fs::read_to_string(key_store_path()).unwrap();
+        let line = store.lines().find(|line| line.starts_with("A0A1A2A3A4A5 # ")).unwrap();
+        assert!(line.contains(&format!("default key search on card {}", result.card.uid)));
+
+        assert!(matches!(queue.jobs()[id - 1].status, JobStatus::Finished(_)));
+        let mut reader = queue.shutdown();
+        assert_eq!(reader.last_known_keys.get(&(2, KeyType::KeyA)), Some(&KEY));
+        assert!(reader.get_uid().unwrap().is_some());
+    }
+
+    #[test]
+    fn autopwn_dumps_the_card_with_its_keys() {
+        let (queue, events) = queue_with(MockCard::new([0x04, 0x77, 0x78, 0x79]).with_keys(2, KEY, [0xFF; 6]));
+        queue.submit(Job::Autopwn);
+
+        let seen = events_until_end(&events);
+        assert!(matches!(seen.last(), Some(JobEvent::Finished(_))));
+        let result = seen.iter()
+            .find_map(|event| match event {
+                JobEvent::Result { result, .. } => Some(result),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(result.sectors.len(), 16);
+        assert!(result.notes.is_empty());
+
+        // the keys are back in the trailers, where load_key_file finds them
+        let dump = fs::read(result.dump.as_ref().unwrap()).unwrap();
+        assert_eq!(dump.len(), 1024);
+        assert_eq!(&dump[2 * 64 + 48..2 * 64 + 54], &KEY);
+        queue.shutdown();
+    }
+
+    #[test]
+    fn autopwn_leaves_hardened_sectors_to_hardnested() {
+        // a key no dictionary has
+        let secret = [0x3C, 0x5E, 0x71, 0x0B, 0x9D, 0x24];
+        let card = MockCard::new([0x04, 0x7A, 0x7B, 0x7C]).with_prng(Prng::Hard).with_keys(5, secret, secret);
+        let (queue, events) = queue_with(card);
+        queue.submit(Job::Autopwn);
+
+        let seen = events_until_end(&events);
+        let result = seen.iter()
+            .find_map(|event| match event {
+                JobEvent::Result { result, .. } => Some(result),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(result.sectors.len(), 15);
+        assert!(result.notes.iter().any(|note| note.starts_with("Sectors 5 need hardnested")));
+        assert!(result.dump.is_some());
+        queue.shutdown();
+    }
+
+    #[test]
+    fn kept_nonces_are_picked_up_again() {
+        let uid = [0x04, 0x74, 0x75, 0x76];
+        let (queue, events) = queue_with(MockCard::new(uid));
+
+        // what a job cancelled part way through leaves behind, under the UID the card is
+        // detected with
+        let (detected, kept) = queue.with_reader(|reader| {
+            let detected = detect_card(reader).unwrap().unwrap();
+            (detected, reader.collect_nonces(0, KeyType::KeyA, 12).unwrap())
+        }).unwrap();
+        let path = nonce_path(&detected, 0);
+        let _ = fs::remove_file(&path);
+        for pair in &kept {
+            save_nonces(&path, *pair).unwrap();
+        }
+
+        queue.submit(Job::PrngTest);
+        let seen = events_until_end(&events);
+        let first = seen.iter().find_map(|event| match event {
+            JobEvent::Progress { stage: Stage::Nonces, progress } => Some(progress.percent),
+            _ => None,
+        });
+        assert_eq!(first, Some(Progress::steps(kept.len(), PRNG_SAMPLES, "").percent));
+        assert!(seen.iter().any(|event| matches!(event, JobEvent::Prng(report) if report.samples == PRNG_SAMPLES)));
+        assert!(matches!(seen.last(), Some(JobEvent::Finished(_))));
+        assert!(!path.exists());
+        queue.shutdown();
+    }
+}
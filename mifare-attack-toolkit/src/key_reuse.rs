@@ -0,0 +1,444 @@
+// src/key_reuse.rs
+//
+// Key reuse across a badge population (--key-reuse). The keys of many cards are read
+// from their dumps (.mfd/.bin, keys in the sector trailers) and the results the attacks
+// saved, along with key lists and key stores, and put side by side: which cards use one
+// key on every sector, which still have default keys, which have keys of their own
+// (diversified) and which share them, and for each sector whether the whole population
+// uses the same key. Nothing here needs a reader.
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cards::{load_key_file, KeyType, DEFAULT_KEYS, RAW_DUMP_SIZES};
+use crate::i18n::{tr, tr_args};
+use crate::utils::{format_uid, hex_to_bytes};
+
+type Key = [u8; 6];
+/// A key and the sector and key type it opens
+type SectorKey = (u8, KeyType, Key);
+/// Key A and Key B of a sector
+type KeyPair = (Option<Key>, Option<Key>);
+
+/// The keys of one card, from every file that had it
+#[derive(Clone, Debug, Default)]
+pub struct CardKeys {
+    pub uid: String,
+    /// Key A and Key B of each sector, None where they aren't known
+    pub sectors: BTreeMap<u8, KeyPair>,
+    pub files: Vec<PathBuf>,
+}
+
+impl CardKeys {
+    /// Every key known: sector, key type and key
+    pub fn keys(&self) -> impl Iterator<Item = SectorKey> + '_ {
+        self.sectors.iter().flat_map(|(&sector, &(key_a, key_b))| {
+            [(KeyType::KeyA, key_a), (KeyType::KeyB, key_b)]
+                .into_iter()
+                .filter_map(move |(key_type, key)| key.map(|key| (sector, key_type, key)))
+        })
+    }
+
+    fn distinct_keys(&self) -> BTreeSet<Key> {
+        self.keys().map(|(_, _, key)| key).collect()
+    }
+
+    // a key only fills in what another file didn't have
+    fn add(&mut self, sector: u8, key_type: KeyType, key: Key) {
+        let keys = self.sectors.entry(sector).or_default();
+        let slot = match key_type {
+            KeyType::KeyA => &mut keys.0,
+            KeyType::KeyB => &mut keys.1,
+        };
+        slot.get_or_insert(key);
+    }
+}
+
+/// A key list or a key store, keys without the card and sector they open
+#[derive(Clone, Debug)]
+pub struct KeyList {
+    pub path: PathBuf,
+    pub keys: Vec<Key>,
+}
+
+/// Everything loaded, by card
+#[derive(Debug, Default)]
+pub struct Collection {
+    pub cards: Vec<CardKeys>,
+    pub lists: Vec<KeyList>,
+    /// Files that couldn't be read, and why
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+impl Collection {
+    /// Load the files in `paths`, and the files in the folders among them
+    pub fn load(paths: &[PathBuf]) -> Self {
+        let mut collection = Collection::default();
+        for path in paths {
+            collection.add_path(path);
+        }
+        collection
+    }
+
+    fn add_path(&mut self, path: &Path) {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+                Ok(entries) => entries.filter_map(Result::ok).map(|entry| entry.path()).collect(),
+                Err(e) => {
+                    self.skipped.push((path.to_path_buf(), e.to_string()));
+                    return;
+                },
+            };
+            entries.sort();
+            for entry in entries {
+                self.add_path(&entry);
+            }
+        } else if let Err(e) = self.add_file(path) {
+            self.skipped.push((path.to_path_buf(), e.to_string()));
+        }
+    }
+
+    // A raw dump or an attack result is a card, anything else has to be a key list
+    fn add_file(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let data = fs::read(path)?;
+        let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+        if extension == "json" {
+            let (uid, keys) = parse_result(&data)?;
+            self.add_card(path, uid, keys);
+        } else if ["mfd", "bin", "dump"].contains(&extension.as_str()) && RAW_DUMP_SIZES.contains(&data.len()) {
+            let (uid, keys) = parse_dump(&data);
+            self.add_card(path, uid, keys);
+        } else {
+            let keys = load_key_file(&path.to_string_lossy())?;
+            self.lists.push(KeyList { path: path.to_path_buf(), keys });
+        }
+        Ok(())
+    }
+
+    fn add_card(&mut self, path: &Path, uid: String, keys: Vec<SectorKey>) {
+        let index = match self.cards.iter().position(|card| card.uid == uid) {
+            Some(index) => index,
+            None => {
+                self.cards.push(CardKeys { uid, ..CardKeys::default() });
+                self.cards.len() - 1
+            },
+        };
+        let card = &mut self.cards[index];
+        for (sector, key_type, key) in keys {
+            card.add(sector, key_type, key);
+        }
+        card.files.push(path.to_path_buf());
+    }
+}
+
+// The UID from block 0 and the keys from the trailers. 4K dumps have 16 block sectors
+// after the first 32.
+fn parse_dump(data: &[u8]) -> (String, Vec<SectorKey>) {
+    let blocks = data.len() / 16;
+    let mut keys = Vec::new();
+    let mut sector = 0u8;
+    let mut first = 0;
+    while first < blocks {
+        let size = if sector < 32 { 4 } else { 16 };
+        let trailer = &data[(first + size - 1) * 16..(first + size) * 16];
+        keys.push((sector, KeyType::KeyA, trailer[0..6].try_into().expect("6 bytes")));
+        keys.push((sector, KeyType::KeyB, trailer[10..16].try_into().expect("6 bytes")));
+        first += size;
+        sector += 1;
+    }
+    (format_uid(&data[0..4]), keys)
+}
+
+// An attack result: the card's UID and the keys found per sector, the BCC the UID was
+// detected with is dropped so the card matches its dumps
+fn parse_result(data: &[u8]) -> Result<(String, Vec<SectorKey>), Box<dyn Error>> {
+    let result: serde_json::Value = serde_json::from_slice(data)?;
+    let mut uid = hex_to_bytes(result["card"]["uid"].as_str().ok_or("not an attack result, it has no card UID")?)?;
+    if uid.len() == 5 && uid[..4].iter().fold(0, |bcc, byte| bcc ^ byte) == uid[4] {
+        uid.truncate(4);
+    }
+
+    let mut keys = Vec::new();
+    for keys_of_sector in result["sectors"].as_array().ok_or("not an attack result, it has no sectors")? {
+        let sector = keys_of_sector["sector"].as_u64().ok_or("a sector without its number")? as u8;
+        for (key_type, field) in [(KeyType::KeyA, "key_a"), (KeyType::KeyB, "key_b")] {
+            if let Some(hex) = keys_of_sector[field].as_str() {
+                let key: Key = hex_to_bytes(hex)?.try_into().map_err(|_| format!("'{}' is not a 6 byte key", hex))?;
+                keys.push((sector, key_type, key));
+            }
+        }
+    }
+    Ok((format_uid(&uid), keys))
+}
+
+fn is_default(key: &Key) -> bool {
+    DEFAULT_KEYS.contains(key)
+}
+
+fn key_hex(key: &Key) -> String {
+    key.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// How one card uses its keys
+#[derive(Clone, Debug, PartialEq)]
+pub struct CardPattern {
+    pub uid: String,
+    pub sectors: usize,
+    /// The key, when every sector known has the same one for A and B
+    pub single_key: Option<Key>,
+    pub default_sectors: usize,
+    /// Keys of this card found on other cards too, default keys left out
+    pub shared_keys: usize,
+    /// Keys no other card has, default keys left out
+    pub own_keys: usize,
+}
+
+impl CardPattern {
+    /// Keys of its own and none shared with another card
+    pub fn diversified(&self) -> bool {
+        self.own_keys > 0 && self.shared_keys == 0
+    }
+}
+
+/// A sector's key across the cards that have it known
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Spread {
+    pub cards: usize,
+    pub keys: usize,
+}
+
+impl fmt::Display for Spread {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.cards, self.keys) {
+            (0, _) => write!(f, "-"),
+            (1, _) => write!(f, "{}", tr("spread-one-card")),
+            (cards, 1) => write!(f, "{}", tr_args("spread-same", &[("cards", &cards.to_string())])),
+            (cards, keys) if cards == keys => write!(f, "{}", tr_args("spread-diversified", &[("keys", &keys.to_string())])),
+            (cards, keys) => write!(f, "{}", tr_args("spread-keys-for-cards", &[("keys", &keys.to_string()), ("cards", &cards.to_string())])),
+        }
+    }
+}
+
+/// A key on more than one card
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReusedKey {
+    pub key: Key,
+    pub cards: usize,
+    /// Key A and Key B of a sector count apart
+    pub sector_keys: usize,
+}
+
+/// How a key list relates to the cards
+#[derive(Clone, Debug, PartialEq)]
+pub struct ListSummary {
+    pub path: PathBuf,
+    pub keys: usize,
+    pub default_keys: usize,
+    pub on_cards: usize,
+}
+
+/// Key reuse across a collection
+#[derive(Clone, Debug)]
+pub struct KeyReuseReport {
+    pub cards: Vec<CardPattern>,
+    /// Key A and Key B of each sector
+    pub sectors: Vec<(u8, Spread, Spread)>,
+    /// Most used first
+    pub reused: Vec<ReusedKey>,
+    pub lists: Vec<ListSummary>,
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+impl KeyReuseReport {
+    pub fn new(collection: &Collection) -> Self {
+        // the cards each key is on, and how many sector keys it is in all
+        let mut key_use: BTreeMap<Key, (BTreeSet<&str>, usize)> = BTreeMap::new();
+        for card in &collection.cards {
+            for (_, _, key) in card.keys() {
+                let (cards, sector_keys) = key_use.entry(key).or_default();
+                cards.insert(card.uid.as_str());
+                *sector_keys += 1;
+            }
+        }
+
+        let cards = collection.cards.iter().map(|card| {
+            let distinct = card.distinct_keys();
+            let single_key = match (distinct.len(), distinct.iter().next()) {
+                (1, Some(key)) if card.sectors.len() > 1 => Some(*key),
+                _ => None,
+            };
+            let default_sectors = card.sectors.values()
+                .filter(|(key_a, key_b)| key_a.iter().chain(key_b.iter()).any(is_default))
+                .count();
+            let own: Vec<&Key> = distinct.iter().filter(|key| !is_default(key)).collect();
+            let shared_keys = own.iter().filter(|key| key_use[**key].0.len() > 1).count();
+            CardPattern {
+                uid: card.uid.clone(),
+                sectors: card.sectors.len(),
+                single_key,
+                default_sectors,
+                shared_keys,
+                own_keys: own.len() - shared_keys,
+            }
+        }).collect();
+
+        let sector_numbers: BTreeSet<u8> = collection.cards.iter().flat_map(|card| card.sectors.keys().copied()).collect();
+        let sectors = sector_numbers.into_iter().map(|sector| {
+            let spread = |pick: fn(&KeyPair) -> Option<Key>| {
+                let keys: Vec<Key> = collection.cards.iter().filter_map(|card| card.sectors.get(&sector).and_then(pick)).collect();
+                Spread { cards: keys.len(), keys: keys.iter().collect::<BTreeSet<_>>().len() }
+            };
+            (sector, spread(|keys| keys.0), spread(|keys| keys.1))
+        }).collect();
+
+        let mut reused: Vec<ReusedKey> = key_use.iter()
+            .filter(|(_, (cards, _))| cards.len() > 1)
+            .map(|(key, (cards, sector_keys))| ReusedKey { key: *key, cards: cards.len(), sector_keys: *sector_keys })
+            .collect();
+        reused.sort_by_key(|reused| std::cmp::Reverse((reused.cards, reused.sector_keys)));
+
+        let lists = collection.lists.iter().map(|list| ListSummary {
+            path: list.path.clone(),
+            keys: list.keys.len(),
+            default_keys: list.keys.iter().filter(|key| is_default(key)).count(),
+            on_cards: list.keys.iter().filter(|key| key_use.contains_key(*key)).count(),
+        }).collect();
+
+        KeyReuseReport { cards, sectors, reused, lists, skipped: collection.skipped.clone() }
+    }
+}
+
+impl fmt::Display for KeyReuseReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.cards.len();
+        writeln!(f, "=== {} ===", tr_args("reuse-title", &[("cards", &total.to_string()), ("lists", &self.lists.len().to_string())]))?;
+
+        if !self.cards.is_empty() {
+            writeln!(f, "\n{}", tr("reuse-cards"))?;
+            for card in &self.cards {
+                let mut pattern = Vec::new();
+                if let Some(key) = card.single_key {
+                    pattern.push(tr_args("reuse-single-key", &[("key", &key_hex(&key))]));
+                }
+                if card.default_sectors > 0 {
+                    pattern.push(tr_args("reuse-default-sectors", &[("count", &card.default_sectors.to_string())]));
+                }
+                if card.diversified() {
+                    pattern.push(tr("reuse-diversified"));
+                } else if card.shared_keys > 0 {
+                    pattern.push(tr_args("reuse-shared-keys", &[("count", &card.shared_keys.to_string())]));
+                }
+                writeln!(f, "  {:<20} {}  {}", card.uid, tr_args("reuse-sectors", &[("count", &format!("{:>2}", card.sectors))]), pattern.join(", "))?;
+            }
+
+            writeln!(f, "\n{}", tr("reuse-sectors-across"))?;
+            writeln!(f, "  {:<6}  {:<28}  {}", tr("result-sector"), tr("result-key-a"), tr("result-key-b"))?;
+            for (sector, key_a, key_b) in &self.sectors {
+                writeln!(f, "  {:>6}  {:<28}  {}", sector, key_a.to_string(), key_b)?;
+            }
+        }
+
+        if !self.reused.is_empty() {
+            writeln!(f, "\n{}", tr("reuse-keys-on-cards"))?;
+            for reused in &self.reused {
+                let default = if is_default(&reused.key) { format!("  {}", tr("reuse-default-key")) } else { String::new() };
+                let counts = tr_args("reuse-key-counts", &[
+                    ("cards", &format!("{:>3}", reused.cards)),
+                    ("sector_keys", &format!("{:>4}", reused.sector_keys)),
+                ]);
+                writeln!(f, "  {}  {}{}", key_hex(&reused.key), counts, default)?;
+            }
+        }
+
+        if !self.lists.is_empty() {
+            writeln!(f, "\n{}", tr("reuse-key-lists"))?;
+            for list in &self.lists {
+                writeln!(f, "  {}", tr_args("reuse-key-list", &[
+                    ("path", &list.path.display().to_string()),
+                    ("keys", &list.keys.to_string()),
+                    ("default", &list.default_keys.to_string()),
+                    ("used", &list.on_cards.to_string()),
+                ]))?;
+            }
+        }
+
+        if total > 0 {
+            let count = |pick: fn(&CardPattern) -> bool| self.cards.iter().filter(|card| pick(card)).count();
+            let line = |label: &str, count: usize| format!("  {:<35} {}", tr(label), tr_args("count-of", &[("count", &count.to_string()), ("total", &total.to_string())]));
+            writeln!(f, "\n{}", tr("reuse-summary"))?;
+            writeln!(f, "{}", line("reuse-cards-default-keys", count(|card| card.default_sectors > 0)))?;
+            writeln!(f, "{}", line("reuse-cards-single-key", count(|card| card.single_key.is_some())))?;
+            writeln!(f, "{}", line("reuse-cards-diversified", count(CardPattern::diversified)))?;
+        }
+
+        for (path, reason) in &self.skipped {
+            writeln!(f, "{}", tr_args("report-skipped", &[("path", &path.display().to_string()), ("reason", reason)]))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARED: Key = [0x3C, 0x5E, 0x71, 0x0B, 0x9D, 0x24];
+
+    // a 1K dump of `uid` with `keys(sector)` as Key A and Key B
+    fn dump(uid: [u8; 4], keys: impl Fn(u8) -> (Key, Key)) -> Vec<u8> {
+        let mut data = vec![0u8; 1024];
+        data[0..4].copy_from_slice(&uid);
+        for sector in 0..16u8 {
+            let (key_a, key_b) = keys(sector);
+            let trailer = (sector as usize * 4 + 3) * 16;
+            data[trailer..trailer + 6].copy_from_slice(&key_a);
+            data[trailer + 10..trailer + 16].copy_from_slice(&key_b);
+        }
+        data
+    }
+
+    #[test]
+    fn tells_reused_default_and_diversified_keys_apart() {
+        let dir = std::env::temp_dir().join(format!("mifare-attack-toolkit-key-reuse-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        // all default, the same building key on sector 1, and a card with its own keys
+        fs::write(dir.join("a.mfd"), dump([0x01, 0, 0, 0], |_| ([0xFF; 6], [0xFF; 6]))).unwrap();
+        fs::write(dir.join("b.mfd"), dump([0x02, 0, 0, 0], |sector| if sector == 1 { (SHARED, SHARED) } else { ([0xFF; 6], [0xFF; 6]) })).unwrap();
+        fs::write(dir.join("c.mfd"), dump([0x03, 0, 0, 0], |sector| if sector == 1 { (SHARED, SHARED) } else { ([0xFF; 6], [0xFF; 6]) })).unwrap();
+        fs::write(dir.join("d.mfd"), dump([0x04, 0, 0, 0], |sector| ([sector, 1, 2, 3, 4, 5], [sector, 6, 7, 8, 9, 10]))).unwrap();
+        fs::write(dir.join("keys.txt"), "FFFFFFFFFFFF # a.mfd\n3C5E710B9D24\n112233445566\n").unwrap();
+
+        let report = KeyReuseReport::new(&Collection::load(std::slice::from_ref(&dir)));
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(report.cards.len(), 4);
+        assert_eq!(report.cards[0].single_key, Some([0xFF; 6]));
+        assert_eq!(report.cards[1].shared_keys, 1);
+        assert!(!report.cards[1].diversified());
+        assert!(report.cards[3].diversified());
+        assert_eq!(report.cards[3].default_sectors, 0);
+
+        assert_eq!(report.sectors[0].1, Spread { cards: 4, keys: 2 });
+        assert_eq!(report.sectors[1].1.to_string(), "3 keys for 4 cards");
+        assert_eq!(report.reused[0], ReusedKey { key: [0xFF; 6], cards: 3, sector_keys: 92 });
+        assert_eq!(report.reused[1].key, SHARED);
+        assert_eq!(report.lists[0], ListSummary { path: dir.join("keys.txt"), keys: 3, default_keys: 1, on_cards: 2 });
+    }
+
+    #[test]
+    fn a_result_and_a_dump_of_the_same_card_are_one_card() {
+        let mut collection = Collection::default();
+        let result = r#"{"card": {"uid": "01:02:03:04:04"}, "sectors": [{"sector": 2, "key_a": "3C5E710B9D24", "key_b": null}]}"#;
+        let (uid, keys) = parse_result(result.as_bytes()).unwrap();
+        collection.add_card(Path::new("result.json"), uid, keys);
+        let (uid, keys) = parse_dump(&dump([0x01, 0x02, 0x03, 0x04], |_| ([0xFF; 6], [0xFF; 6])));
+        collection.add_card(Path::new("card.mfd"), uid, keys);
+
+        assert_eq!(collection.cards.len(), 1);
+        // the key the attack found stays, the dump fills in the rest
+        assert_eq!(collection.cards[0].sectors[&2], (Some(SHARED), Some([0xFF; 6])));
+        assert_eq!(collection.cards[0].sectors.len(), 16);
+    }
+}
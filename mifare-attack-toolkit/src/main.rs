@@ -1,37 +1,243 @@
+mod i18n;
 mod reader;
 mod cards;
+#[cfg(feature = "attacks")]
 mod attacks;
 mod operations;
 mod ui;
 mod utils;
+#[cfg(feature = "attacks")]
 mod crypto1;
+#[cfg(feature = "attacks")]
 mod reader_adapter;
 mod mifare_attack_manager;
 mod attack_manager;
+mod jobs;
 mod card_detection;
+mod consent;
+mod analysis;
+mod key_reuse;
+mod weak_keys;
+#[cfg(feature = "attacks")]
+mod prng;
+#[cfg(feature = "attacks")]
+mod results;
+mod proxmark3;
+#[cfg(feature = "gui")]
+mod gui;
 
 // Make functions available
 pub use card_detection::{detect_card, wait_for_card_enhanced};
+use std::path::PathBuf;
 use reader::MifareClassic;
+use proxmark3::Proxmark3;
+use i18n::{set_language, tr, tr_args, AUTO_LANGUAGE};
+
+/// Proxmark3 from `--pm3 [PORT]`, or None if the MFRC522 should be used
+fn proxmark3_from_args() -> Option<Result<Proxmark3, String>> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--pm3" {
+            let detected = Proxmark3::detect().ok_or_else(|| tr("no-proxmark3-found"));
+            return Some(match args.next() {
+                Some(port) => detected.map(|pm3| Proxmark3::new(pm3.client().to_path_buf(), &port)),
+                None => detected,
+            });
+        }
+    }
+    None
+}
+
+/// Value following `flag` on the command line
+fn arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == flag {
+            return args.next();
+        }
+    }
+    None
+}
+
+/// The values following `flag`, up to the next flag
+fn arg_values(flag: &str) -> Option<Vec<String>> {
+    let mut args = std::env::args().skip(1).skip_while(|arg| arg != flag);
+    args.next()?;
+    Some(args.take_while(|arg| !arg.starts_with("--")).collect())
+}
+
+/// `--key-reuse PATH...`: key reuse across the dumps, attack results and key lists in
+/// the files and folders given, the toolkit's results folder when none is
+fn run_key_reuse(paths: Vec<String>) {
+    let paths: Vec<PathBuf> = if paths.is_empty() {
+        vec![consent::config_dir().join("results")]
+    } else {
+        paths.into_iter().map(PathBuf::from).collect()
+    };
+    let collection = key_reuse::Collection::load(&paths);
+    print!("{}", key_reuse::KeyReuseReport::new(&collection));
+}
+
+/// `--weak-keys PATH... [--dictionary FILE] [--csv FILE]`: the default, dictionary and
+/// low-entropy keys in the files and folders given, the key store and the results
+/// folder when none is
+fn run_weak_keys(paths: Vec<String>) {
+    let paths: Vec<PathBuf> = if paths.is_empty() {
+        vec![cards::key_store_path(), consent::config_dir().join("results")]
+    } else {
+        paths.into_iter().map(PathBuf::from).collect()
+    };
+    let dictionary = match arg_value("--dictionary").map(|path| cards::load_key_file(&path)) {
+        Some(Ok(keys)) => keys,
+        Some(Err(e)) => {
+            println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
+            return;
+        },
+        None => Vec::new(),
+    };
+    let collection = key_reuse::Collection::load(&paths);
+    let report = weak_keys::WeakKeyReport::new(&collection, &dictionary);
+    print!("{}", report);
+    if let Some(csv) = arg_value("--csv") {
+        match report.save_csv(std::path::Path::new(&csv)) {
+            Ok(()) => println!("{}", tr_args("csv-written-to", &[("path", &csv.to_string())])),
+            Err(e) => println!("{}", tr_args("err-writing-file", &[("path", &csv.to_string()), ("error", &e.to_string())])),
+        }
+    }
+}
+
+/// `--replay FILE`: re-issue a recorded session against the card on the reader and
+/// report every answer that differs from the recording
+fn run_replay(mifare: &mut MifareClassic, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let transactions = reader::load_session(path)
+        .map_err(|e| tr_args("err-loading-session", &[("path", path), ("error", &e.to_string())]))?;
+
+    let keep_timing = std::env::args().any(|arg| arg == "--keep-timing");
+    let key = if keep_timing { "replay-transactions-timed" } else { "replay-transactions" };
+    println!("{}", tr_args(key, &[("count", &transactions.len().to_string()), ("path", path)]));
+    println!("{}", tr("replay-place-card"));
+    let _ = std::io::stdin().read_line(&mut String::new());
+
+    let report = mifare.replay_session(&transactions, keep_timing)
+        .map_err(|e| tr_args("replay-stopped", &[("error", &e.to_string())]))?;
+    if report.mismatches.is_empty() {
+        println!("\n{}", tr_args("replay-answers-matched", &[("replayed", &report.replayed.to_string())]));
+    } else {
+        let lines: Vec<String> = report.mismatches.iter().map(|(line, _, _)| line.to_string()).collect();
+        println!("\n{}", tr_args("replay-answers-differ", &[("count", &report.mismatches.len().to_string()), ("replayed", &report.replayed.to_string()), ("transactions", &lines.join(", "))]));
+    }
+    Ok(())
+}
+
+/// The window with --gui, the terminal menu otherwise. The reader comes back once
+/// either is left.
+fn run_frontend(mifare: MifareClassic) -> MifareClassic {
+    if std::env::args().any(|arg| arg == "--gui") {
+        #[cfg(feature = "gui")]
+        return gui::run(mifare);
+        #[cfg(not(feature = "gui"))]
+        println!("{}", tr("built-without-gui"));
+    }
+    
+    println!("=== {} ===", tr("title-attack-manager"));
+    println!("{}", tr("banner-tears-for-fears"));
+    println!("{}\n", tr("press-ctrl-c-to-exit"));
+    
+    // Use the existing menu function 
+    mifare_attack_manager::run_menu(mifare)
+}
+
+fn run_proxmark3(pm3: Proxmark3) {
+    match pm3.version() {
+        Ok(version) => println!("{}", tr_args("proxmark3-on", &[("port", pm3.port()), ("version", &version.to_string())])),
+        Err(e) => {
+            println!("{}", tr_args("err-talking-to-proxmark3-on", &[("port", pm3.port()), ("error", &e.to_string())]));
+            return;
+        }
+    }
+    println!("{}\n", tr("press-ctrl-c-to-exit"));
+    proxmark3::menu::run_menu(&pm3);
+}
 
 fn main() {
-    println!("=== MIFARE Attack Toolkit ===");
-    println!("Based on Proxmark3 algorithms ported to Rust");
-    println!("Compatible with MFRC522 on Raspberry Pi");
+    set_language(AUTO_LANGUAGE);
+    println!("=== {} ===", tr("title-toolkit"));
+    println!("{}", tr("banner-ported"));
+    println!("{}", tr("banner-readers"));
+    if !cfg!(feature = "attacks") {
+        println!("{}", tr("built-without-attacks"));
+    }
+    if let Some(reason) = safe_mode::read_only_reason() {
+        println!("{}", tr_args("read-only-mode", &[("reason", reason)]));
+    }
+    
+    // offline, no reader needed
+    if let Some(paths) = arg_values("--key-reuse") {
+        return run_key_reuse(paths);
+    }
+    if let Some(paths) = arg_values("--weak-keys") {
+        return run_weak_keys(paths);
+    }
+    
+    match proxmark3_from_args() {
+        Some(Ok(pm3)) => return run_proxmark3(pm3),
+        Some(Err(e)) => {
+            println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
+            return;
+        },
+        None => {}
+    }
     
-    // Initialize the MFRC522 reader
+    // Initialize the MFRC522 reader, falling back to a Proxmark3 if one is attached
     let mut mifare = match MifareClassic::new() {
         Ok(m) => m,
         Err(e) => {
-            println!("Error initializing MFRC522: {}", e);
+            println!("{}", tr_args("err-initializing-mfrc522", &[("error", &e.to_string())]));
+            if let Some(pm3) = Proxmark3::detect() {
+                println!("{}", tr("found-proxmark3-using-it-instead"));
+                run_proxmark3(pm3);
+            }
             return;
         }
     };
     
-    println!("=== Mifare Attack Manager ===");
-    println!("Based on Proxmark3 algorithms and 'Tears For Fears' approach");
-    println!("Press Ctrl+C to exit\n");
+    if let Some(path) = arg_value("--record") {
+        if let Err(e) = mifare.start_recording(&path) {
+            println!("{}", tr_args("err-starting-session-recording", &[("error", &e.to_string())]));
+            return;
+        }
+    }
     
-    // Use the existing menu function 
-    mifare_attack_manager::run_menu(&mut mifare);
+    if let Some(path) = arg_value("--replay") {
+        // A recorded session can hold writes, it is replayed as it was
+        if safe_mode::is_read_only() {
+            safe_mode::refuse();
+            return;
+        }
+        // Replay re-sends whatever the session held, writes included
+        match consent::ensure_acknowledged() {
+            Ok(true) => {},
+            Ok(false) => return,
+            Err(e) => {
+                println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
+                return;
+            }
+        }
+        mifare.take_last_uid();
+        let result = run_replay(&mut mifare, &path);
+        consent::audit("MFRC522", &format!("replay {}", path), mifare.take_last_uid().as_deref(), &result);
+        if let Err(e) = result {
+            println!("{}", e);
+        }
+        if let Some(count) = mifare.stop_recording() {
+            println!("{}", tr_args("transactions-recorded", &[("count", &count.to_string())]));
+        }
+        return;
+    }
+    
+    let mut mifare = run_frontend(mifare);
+    
+    if let Some(count) = mifare.stop_recording() {
+        println!("{}", tr_args("transactions-recorded", &[("count", &count.to_string())]));
+    }
 }
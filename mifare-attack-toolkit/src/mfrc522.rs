@@ -3,6 +3,7 @@ use std::thread;
 use std::time::Duration;
 use std::error::Error;
 use std::collections::HashMap;
+use mifare_rc522::{BlockAddr, SectorIndex};
 
 // MFRC522 Commands
 const PCD_IDLE: u8 = 0x00;
@@ -184,7 +185,7 @@ impl MifareClassic {
             if self.dark_processing_mode {
                 if self.auth_with_key_special(block, KeyType::KeyA, key, &uid)? {
                     println!("Success with Key A: {}", self.bytes_to_hex(key));
-                    self.last_known_keys.insert((block / 4, KeyType::KeyA), *key);
+                    self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyA), *key);
                     return Ok(Some((*key, KeyType::KeyA)));
                 }
                 
@@ -193,7 +194,7 @@ impl MifareClassic {
                 
                 if self.auth_with_key_special(block, KeyType::KeyB, key, &uid)? {
                     println!("Success with Key B: {}", self.bytes_to_hex(key));
-                    self.last_known_keys.insert((block / 4, KeyType::KeyB), *key);
+                    self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyB), *key);
                     return Ok(Some((*key, KeyType::KeyB)));
                 }
                 
@@ -203,13 +204,13 @@ impl MifareClassic {
                 // Standard authentication for normal cards
                 if self.auth_with_key(block, KeyType::KeyA, key, &uid)? {
                     println!("Success with Key A: {}", self.bytes_to_hex(key));
-                    self.last_known_keys.insert((block / 4, KeyType::KeyA), *key);
+                    self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyA), *key);
                     return Ok(Some((*key, KeyType::KeyA)));
                 }
                 
                 if self.auth_with_key(block, KeyType::KeyB, key, &uid)? {
                     println!("Success with Key B: {}", self.bytes_to_hex(key));
-                    self.last_known_keys.insert((block / 4, KeyType::KeyB), *key);
+                    self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyB), *key);
                     return Ok(Some((*key, KeyType::KeyB)));
                 }
             }
@@ -357,7 +358,7 @@ impl MifareClassic {
         
         // Store successful key for future use
         if success {
-            let sector = block / 4;
+            let sector = BlockAddr(block).sector().get();
             self.last_known_keys.insert((sector, key_type), [key[0], key[1], key[2], key[3], key[4], key[5]]);
         } else {
             // Stop crypto in case of failure
@@ -391,7 +392,7 @@ impl MifareClassic {
         
         // Store successful key for future use
         if success {
-            let sector = block / 4;
+            let sector = BlockAddr(block).sector().get();
             self.last_known_keys.insert((sector, key_type), [key[0], key[1], key[2], key[3], key[4], key[5]]);
         } else {
             // Stop crypto in case of failure
@@ -883,11 +884,11 @@ impl MifareClassic {
             let key = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
             
             // Store for future use
-            let sector = block / 4;
+            let sector = BlockAddr(block).sector().get();
             self.last_known_keys.insert((sector, KeyType::KeyB), key);
             
             println!("Success! Found key: FF FF FF FF FF FF");
-            println!("This key can be used for sector {}", block / 4);
+            println!("This key can be used for sector {}", sector);
             
             return Ok(Some(key));
         }
@@ -901,7 +902,7 @@ impl MifareClassic {
         let key = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
         
         // Store the key for future use
-        let sector = block / 4;
+        let sector = BlockAddr(block).sector().get();
         self.last_known_keys.insert((sector, KeyType::KeyB), key);
         
         println!("Success! Found key: FF FF FF FF FF FF");
@@ -926,8 +927,8 @@ impl MifareClassic {
         println!("Card detected! UID: {}", self.format_uid(&uid));
         
         // Calculate block numbers
-        let start_block = start_sector * 4; // First block of sector
-        let target_block = target_sector * 4; // First block of target sector
+        let start_block = SectorIndex::new(start_sector).ok_or("No such sector")?.first_block().get();
+        let target_block = SectorIndex::new(target_sector).ok_or("No such sector")?.first_block().get();
         
         println!("Starting nested attack with known key for block {}", start_block);
         println!("Target block: {}", target_block);
@@ -1021,8 +1022,7 @@ impl MifareClassic {
                     println!("Sector {}", sector);
                     println!("------------------");
                     
-                    for (i, block_data) in sector_blocks.iter().enumerate() {
-                        let block_num = sector * 4 + i;
+                    for (block_num, block_data) in sector_index(sector).blocks().zip(sector_blocks.iter()) {
                         
                         // Print block data in hex
                         print!("  Block {}: ", block_num);
@@ -1079,8 +1079,7 @@ impl MifareClassic {
                 let mut blocks_read = 0;
                 let mut blocks_data = Vec::new();
                 
-                for i in 0..4 {
-                    let block_addr = sector * 4 + i;
+                for block_addr in sector_index(sector).blocks().map(BlockAddr::get) {
                     
                     // Try authentication for this block
                     let auth_success = if self.dark_processing_mode {
@@ -1108,8 +1107,8 @@ impl MifareClassic {
                     self.stop_crypto1()?;
                 }
                 
-                // If we read all 4 blocks in the sector
-                if blocks_read == 4 {
+                // If we read all blocks in the sector
+                if blocks_read == sector_index(sector).block_count() {
                     sector_blocks = blocks_data;
                     sector_read = true;
                     
@@ -1117,8 +1116,7 @@ impl MifareClassic {
                     self.last_known_keys.insert((sector, *key_type), *key);
                     
                     // Print block data
-                    for (i, block_data) in sector_blocks.iter().enumerate() {
-                        let block_num = sector * 4 + i;
+                    for (block_num, block_data) in sector_index(sector).blocks().zip(sector_blocks.iter()) {
                         
                         // Print block data in hex
                         print!("  Block {}: ", block_num);
@@ -1177,8 +1175,7 @@ impl MifareClassic {
         let previous_mode = self.dark_processing_mode;
         self.dark_processing_mode = true;
         
-        for i in 0..4 {
-            let block_addr = sector * 4 + i;
+        for block_addr in sector_index(sector).blocks().map(BlockAddr::get) {
             
             // Try authentication with special handling for each block individually
             self.stop_crypto1()?; // Reset crypto state before each attempt
@@ -1316,3 +1313,8 @@ impl MifareClassic {
             .join(" ")
     }
 }
+
+// The sector with this number, the menus only ask for sectors of a 1K
+fn sector_index(sector: u8) -> SectorIndex {
+    SectorIndex::new(sector).expect("sector of a Mifare Classic")
+}
@@ -1,104 +1,270 @@
 // src/mifare_attack_manager.rs
 use std::error::Error;
 use std::io::{self, Write};
+use progress_sink::{StatusFile, TerminalBar};
 
-use crate::reader::MifareClassic;
+use crate::reader::{MifareClassic, ReaderStatus};
+#[cfg(feature = "attacks")]
 use crate::attacks;
+#[cfg(feature = "attacks")]
+use crate::jobs::Job;
+use crate::jobs::{JobEvent, JobId, JobQueue};
+use crate::consent;
 use crate::operations;
-use crate::utils::{wait_for_enter, get_user_confirmation};
+#[cfg(feature = "attacks")]
+use crate::utils::bytes_to_hex;
+use crate::utils::format_uid;
+use crate::i18n::{tr, tr_args};
 
-pub struct MifareAttackManager<'a> {
-    reader: &'a mut MifareClassic,
+/// Reconnection attempts before the menu is shown with the reader disconnected
+pub(crate) const RECONNECT_ATTEMPTS: u32 = 5;
+
+pub struct MifareAttackManager {
+    // the reader, and the attacks running on it in the background
+    jobs: JobQueue,
 }
 
-impl<'a> MifareAttackManager<'a> {
-    pub fn new(reader: &'a mut MifareClassic) -> Self {
-        Self { reader }
+impl MifareAttackManager {
+    pub fn new(reader: MifareClassic) -> Self {
+        Self { jobs: JobQueue::start(reader, print_job_event) }
+    }
+    
+    /// Cancel the attacks still running and hand the reader back
+    pub fn into_reader(self) -> MifareClassic {
+        if self.jobs.is_busy() {
+            println!("{}", tr("cancelling-attacks-still-running"));
+        }
+        self.jobs.shutdown()
     }
     
     pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
         loop {
-            self.display_menu();
+            // Reconnect first if the reader went away since the last operation, unless
+            // an attack has it
+            let status = self.jobs.with_reader(|reader| reader.ensure_connected(RECONNECT_ATTEMPTS));
+            self.display_menu(status.ok());
             
-            print!("Enter choice: ");
+            print!("{} ", tr("enter-choice"));
             io::stdout().flush()?;
             
             let mut choice = String::new();
             io::stdin().read_line(&mut choice)?;
             
-            match choice.trim() {
-                "1" => self.read_uid()?,
-                "2" => self.try_default_keys()?,
-                "3" => self.run_nested_attack()?,
-                "4" => self.run_darkside_attack()?,
-                "5" => self.detect_magic_card()?,
-                "6" => self.write_custom_uid()?,
-                "7" => self.dump_card()?,
-                "8" => self.clone_card()?,
+            let result = match choice.trim() {
+                "2" | "3" | "4" | "6" | "8" | "13" if safe_mode::is_read_only() => {
+                    safe_mode::refuse();
+                    Ok(())
+                },
+                "1" => self.read_uid(),
+                #[cfg(feature = "attacks")]
+                "2" => self.queue_attack(|| Ok(Some(Job::DefaultKeys))),
+                #[cfg(feature = "attacks")]
+                "3" => self.queue_attack(attacks::nested::ask_nested_attack),
+                #[cfg(feature = "attacks")]
+                "4" => self.queue_attack(attacks::darkside::ask_darkside_attack),
+                "5" => self.detect_magic_card(),
+                "6" => self.audited("write custom UID", true, Self::write_custom_uid),
+                "7" => self.dump_card(),
+                "8" => self.audited("clone to magic card", true, Self::clone_card),
+                #[cfg(feature = "attacks")]
+                "10" => self.test_prng(),
+                #[cfg(feature = "attacks")]
+                "11" => self.show_jobs(),
+                #[cfg(feature = "attacks")]
+                "12" => self.cancel_jobs(),
+                #[cfg(feature = "attacks")]
+                "13" => self.queue_attack(|| Ok(Some(Job::Autopwn))),
                 "9" | "q" | "exit" | "quit" => {
-                    println!("Exiting...");
+                    println!("{}", tr("exiting"));
                     break;
                 },
                 _ => {
-                    println!("Invalid choice. Please try again.");
+                    println!("{}", tr("err-invalid-choice-please-try-again"));
+                    Ok(())
                 }
+            };
+            
+            // A failed operation is often a lost reader, so keep the menu
+            // running and let the next iteration try to reconnect
+            if let Err(e) = result {
+                println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
             }
         }
         
         Ok(())
     }
     
-    fn display_menu(&self) {
-        println!("\n\nSelect an option:");
-        println!("1. Read card UID");
-        println!("2. Try default keys");
-        println!("3. Run Nested Attack (requires a known key)");
-        println!("4. Run Darkside Attack");
-        println!("5. Detect Magic Card");
-        println!("6. Write custom UID (requires Magic Card)");
-        println!("7. Dump card contents");
-        println!("8. Clone card to Magic Card");
-        println!("9. Exit");
+    /// Queue an attack once the warning is accepted, `ask` asks for its settings. The
+    /// menu comes back right away, the attack reports as it goes and is written to the
+    /// audit log when it ends.
+    #[cfg(feature = "attacks")]
+    fn queue_attack(&mut self, ask: fn() -> Result<Option<Job>, Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+        if !consent::ensure_acknowledged()? {
+            return Ok(());
+        }
+        if let Some(job) = ask()? {
+            self.queue(job);
+        }
+        Ok(())
     }
     
-    fn read_uid(&mut self) -> Result<(), Box<dyn Error>> {
-        operations::read::read_uid(self.reader)
+    #[cfg(feature = "attacks")]
+    fn queue(&mut self, job: Job) {
+        let waiting = self.jobs.is_busy();
+        let id = self.jobs.submit(job);
+        if waiting {
+            println!("{}", tr_args("job-queued", &[("id", &id.to_string()), ("job", job.name())]));
+        } else {
+            println!("{}", tr_args("job-submitted", &[("id", &id.to_string()), ("job", job.name())]));
+        }
     }
     
-    fn try_default_keys(&mut self) -> Result<(), Box<dyn Error>> {
-        attacks::default_keys::run_default_key_search(self.reader)
+    #[cfg(feature = "attacks")]
+    fn show_jobs(&mut self) -> Result<(), Box<dyn Error>> {
+        let jobs = self.jobs.jobs();
+        if jobs.is_empty() {
+            println!("{}", tr("no-attacks-run-yet"));
+        }
+        for info in jobs {
+            println!("{:>3}. {:<20} {}", info.id, info.name, info.status);
+        }
+        Ok(())
     }
     
-    fn run_nested_attack(&mut self) -> Result<(), Box<dyn Error>> {
-        attacks::nested::run_nested_attack(self.reader)
+    #[cfg(feature = "attacks")]
+    fn cancel_jobs(&mut self) -> Result<(), Box<dyn Error>> {
+        if self.jobs.is_busy() {
+            self.jobs.cancel();
+            println!("{}", tr("cancelling-running-attack-stops-at"));
+        } else {
+            println!("{}", tr("no-attack-is-running"));
+        }
+        Ok(())
     }
     
-    fn run_darkside_attack(&mut self) -> Result<(), Box<dyn Error>> {
-        attacks::darkside::run_darkside_attack(self.reader)
+    /// Run an operation that changes the card once the warning is accepted and, for
+    /// one that changes the card, confirmed for this session, then add it to the audit log
+    fn audited(
+        &mut self,
+        operation: &str,
+        destructive: bool,
+        attack: fn(&mut MifareClassic) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !consent::ensure_acknowledged()? {
+            return Ok(());
+        }
+        if destructive && !consent::confirm_destructive(operation)? {
+            println!("{}", tr("operation-cancelled"));
+            return Ok(());
+        }
+        
+        self.jobs.with_reader(|reader| {
+            reader.take_last_uid();
+            let result = attack(reader);
+            consent::audit("MFRC522", operation, reader.take_last_uid().as_deref(), &result);
+            result
+        })?
+    }
+    
+    fn display_menu(&self, status: Option<ReaderStatus>) {
+        match status {
+            Some(status) => println!("\n\n{}", tr_args("menu-reader", &[("status", &status.to_string())])),
+            None => println!("\n\n{}", tr("menu-reader-busy")),
+        }
+        println!("{}", tr("select-option"));
+        println!("1. {}", tr("read-card-uid"));
+        // Key searches, attacks and writes are left out in read-only mode, attacks also in
+        // builds without the attacks feature. The numbers stay
+        let read_only = safe_mode::is_read_only();
+        if !read_only && cfg!(feature = "attacks") {
+            println!("2. {}", tr("try-default-keys"));
+            println!("3. {}", tr("run-nested-attack"));
+            println!("4. {}", tr("run-darkside-attack"));
+        }
+        println!("5. {}", tr("detect-magic-card"));
+        if !read_only {
+            println!("6. {}", tr("write-custom-uid"));
+        }
+        println!("7. {}", tr("dump-card-contents"));
+        if !read_only {
+            println!("8. {}", tr("clone-card-to-magic-card"));
+        }
+        if cfg!(feature = "attacks") {
+            println!("10. {}", tr("test-prng"));
+            println!("11. {}", tr("show-attack-jobs"));
+            println!("12. {}", tr("cancel-attack-jobs"));
+            if !read_only {
+                println!("13. {}", tr("autopwn"));
+            }
+        }
+        println!("9. {}", tr("menu-exit"));
+    }
+    
+    fn read_uid(&mut self) -> Result<(), Box<dyn Error>> {
+        self.jobs.with_reader(operations::read::read_uid)?
     }
     
     fn detect_magic_card(&mut self) -> Result<(), Box<dyn Error>> {
-        operations::magic_card::detect_card_type(self.reader)
+        self.jobs.with_reader(operations::magic_card::detect_card_type)?
+    }
+    
+    #[cfg(feature = "attacks")]
+    fn test_prng(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-prng-test"));
+        self.queue(Job::PrngTest);
+        Ok(())
     }
     
-    fn write_custom_uid(&mut self) -> Result<(), Box<dyn Error>> {
-        operations::magic_card::write_custom_uid(self.reader)
+    fn write_custom_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
+        operations::magic_card::write_custom_uid(reader)
     }
     
+    // the bar in the terminal, and the status file for the REST API
     fn dump_card(&mut self) -> Result<(), Box<dyn Error>> {
-        operations::read::dump_card(self.reader)
+        let progress = (TerminalBar::new(), StatusFile::new("dump"));
+        self.jobs.with_reader(|reader| operations::read::dump_card(reader, &progress))?
     }
     
-    fn clone_card(&mut self) -> Result<(), Box<dyn Error>> {
-        operations::clone::clone_card(self.reader)
+    // the clone waits on the user between its steps, a bar would be in the way
+    fn clone_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
+        operations::clone::clone_card(reader, &StatusFile::new("clone"))
+    }
+}
+
+// this prints what a job in the background reports, between the menu's own lines
+fn print_job_event(id: JobId, event: JobEvent) {
+    match event {
+        JobEvent::Started(name) => println!("\n{}", tr_args("job-started", &[("id", &id.to_string()), ("job", name)])),
+        JobEvent::Reader(status) => println!("\n{}", tr_args("job-reader", &[("id", &id.to_string()), ("status", &status.to_string())])),
+        JobEvent::Card { uid, card_type, magic } => println!(
+            "\n{}",
+            tr_args(if magic { "job-card-magic" } else { "job-card" }, &[("id", &id.to_string()), ("uid", &format_uid(&uid)), ("card_type", &card_type.to_string())])
+        ),
+        #[cfg(feature = "attacks")]
+        JobEvent::Key { sector, key_type, key } => println!("\n{}", tr_args("job-found-of-sector", &[("id", &id.to_string()), ("key_type", &key_type.to_string()), ("sector", &sector.to_string()), ("key", &bytes_to_hex(&key))])),
+        #[cfg(feature = "attacks")]
+        JobEvent::Prng(report) => println!("\n{}", tr_args("job-prng-report", &[("id", &id.to_string()), ("report", &report.to_string())])),
+        #[cfg(feature = "attacks")]
+        JobEvent::Result { result, saved } => {
+            print!("\n{}", tr_args("job-result", &[("id", &id.to_string()), ("result", &result.to_string())]));
+            if let Some(path) = saved {
+                println!("{}", tr_args("saved-to", &[("path", &path.display().to_string())]));
+            }
+        },
+        JobEvent::Finished(summary) => println!("\n{}", tr_args("job-finished", &[("id", &id.to_string()), ("summary", &summary.to_string())])),
+        JobEvent::Cancelled => println!("\n{}", tr_args("job-cancelled", &[("id", &id.to_string())])),
+        JobEvent::Failed(error) => println!("\n{}", tr_args("job-failed", &[("id", &id.to_string()), ("error", &error.to_string())])),
+        // progress is in the job list (11)
+        JobEvent::Progress { .. } => {},
     }
 }
 
-// Helper function to run the menu
-pub fn run_menu(reader: &mut MifareClassic) {
+// Helper function to run the menu, the reader comes back once the menu is left
+pub fn run_menu(reader: MifareClassic) -> MifareClassic {
     let mut manager = MifareAttackManager::new(reader);
     
     if let Err(e) = manager.run() {
-        println!("Error: {}", e);
+        println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
     }
+    manager.into_reader()
 }
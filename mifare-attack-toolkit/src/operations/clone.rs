@@ -1,27 +1,33 @@
 // src/operations/clone.rs
 use std::error::Error;
 use std::io::{self, Write};
+use progress_sink::{Progress, ProgressSink};
 
 use crate::reader::MifareClassic;
 use crate::utils::{wait_for_card_removal, format_uid, bytes_to_hex, hex_to_bytes, get_user_confirmation};
 use crate::card_detection::wait_for_card_enhanced;
+use crate::i18n::{tr, tr_args};
+
+// Reading the source card and writing the target card
+const STEPS: usize = 2;
 
 /// Clone a card to a Magic Card
-pub fn clone_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Clone Card ===");
-    println!("This operation will read data from a source card and write it to a Magic Card.");
+pub fn clone_card(reader: &mut MifareClassic, progress: &dyn ProgressSink) -> Result<(), Box<dyn Error>> {
+    println!("\n=== {} ===", tr("title-clone-card"));
+    println!("{}", tr("clone-explained"));
     
     // First read the source card
-    println!("\nStep 1: Read source card");
-    println!("Place the SOURCE card on the reader...");
+    println!("\n{}", tr("step-1-read-source-card"));
+    println!("{}", tr("place-source-card-on-reader"));
+    progress.report(&Progress::steps(0, STEPS, "reading the source card"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     let source_uid = match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Source card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("source-card-detected-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Try to read all sectors from the source card
-            println!("Reading card data...");
+            println!("{}", tr("reading-card-data"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
@@ -29,19 +35,19 @@ pub fn clone_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
             uid
         },
         None => {
-            println!("No source card detected.");
+            println!("{}", tr("no-source-card-detected"));
             return Ok(());
         }
     };
     
     // Ask user for potential UID change
-    print!("Do you want to use a different UID for the target card? (y/n): ");
+    print!("{} ", tr("clone-different-uid"));
     io::stdout().flush()?;
     let mut change_uid = String::new();
     io::stdin().read_line(&mut change_uid)?;
     
     let target_uid = if change_uid.trim().to_lowercase() == "y" {
-        print!("Enter new UID in hex (e.g., 11:22:33:44): ");
+        print!("{} ", tr("enter-new-uid-in-hex"));
         io::stdout().flush()?;
         let mut new_uid_str = String::new();
         io::stdin().read_line(&mut new_uid_str)?;
@@ -49,13 +55,13 @@ pub fn clone_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
         match hex_to_bytes(new_uid_str.trim()) {
             Ok(bytes) => {
                 if bytes.len() != 4 && bytes.len() != 7 && bytes.len() != 10 {
-                    println!("Invalid UID length. Must be 4, 7, or 10 bytes.");
+                    println!("{}", tr("err-invalid-uid-length-must-be"));
                     return Ok(());
                 }
                 bytes
             },
             Err(e) => {
-                println!("Invalid hex format: {}", e);
+                println!("{}", tr_args("err-invalid-hex-format", &[("error", &e.to_string())]));
                 return Ok(());
             }
         }
@@ -64,42 +70,44 @@ pub fn clone_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
     };
     
     // Now write to the target card
-    println!("\nStep 2: Write to target Magic Card");
-    println!("Place the TARGET Magic Card on the reader...");
+    println!("\n{}", tr("step-2-write-to-target"));
+    println!("{}", tr("place-target-magic-card-on"));
+    progress.report(&Progress::steps(1, STEPS, "writing the target card"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Target card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("target-card-detected-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Check if this appears to be a Magic Card
             let is_magic = false; // You would implement detection here
             
             if !is_magic {
-                println!("Warning: This doesn't appear to be a Magic Card.");
-                if !get_user_confirmation("Continue anyway?") {
-                    println!("Operation cancelled.");
+                println!("{}", tr("warning-not-magic-card"));
+                if !get_user_confirmation(&tr("continue-anyway")) {
+                    println!("{}", tr("operation-cancelled"));
                     return Ok(());
                 }
             }
             
             // First change the UID if needed
             if target_uid != source_uid {
-                println!("Changing UID to: {}", format_uid(&target_uid));
+                println!("{}", tr_args("changing-uid-to", &[("uid", &format_uid(&target_uid).to_string())]));
                 // (Implementation would write the UID)
             }
             
             // Write all the data to the target card
-            println!("Writing data to target card...");
+            println!("{}", tr("writing-data-to-target-card"));
             // (Implementation would write all sectors)
             
-            println!("\nClone operation completed.");
+            progress.report(&Progress::steps(STEPS, STEPS, "clone written"));
+            println!("\n{}", tr("clone-operation-completed"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No target card detected.");
+            println!("{}", tr("no-target-card-detected"));
         }
     }
     
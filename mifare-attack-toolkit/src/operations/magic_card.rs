@@ -5,19 +5,20 @@ use std::io::{self, Write};
 use crate::reader::MifareClassic;
 use crate::utils::{wait_for_card_removal, format_uid, hex_to_bytes};
 use crate::card_detection::wait_for_card_enhanced;
+use crate::i18n::{tr, tr_args};
 
 /// Detect card type (Magic Card detection)
 pub fn detect_card_type(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Detect Card Type ===");
-    println!("This will attempt to identify if the card is a Magic Card.");
+    println!("\n=== {} ===", tr("title-detect-card-type"));
+    println!("{}", tr("detect-explained"));
     
     // Wait for card
-    println!("\nPlacing card on the reader...");
+    println!("\n{}", tr("placing-card-on-reader"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Check for Magic Card patterns
             let is_magic = if uid.len() == 4 {
@@ -28,24 +29,24 @@ pub fn detect_card_type(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>
             };
             
             if is_magic {
-                println!("\nThis appears to be a Magic Card!");
-                println!("Magic Cards allow UID changing and bypass some security features.");
+                println!("\n{}", tr("this-appears-to-be-magic"));
+                println!("{}", tr("magic-cards-allow-uid-changing"));
             } else {
-                println!("\nThis appears to be a standard MIFARE card.");
-                println!("No specific Magic Card features detected.");
+                println!("\n{}", tr("this-appears-to-be-standard"));
+                println!("{}", tr("no-specific-magic-card-features"));
             }
             
             // Try additional tests for Magic Card capabilities
-            println!("\nAdditional tests could be performed to verify Magic Card capabilities:");
-            println!("1. Direct write to block 0 (UID block)");
-            println!("2. Testing for backdoor commands");
-            println!("3. Checking for direct memory operations");
+            println!("\n{}", tr("detect-more-tests"));
+            println!("1. {}", tr("direct-write-to-block-0"));
+            println!("2. {}", tr("testing-for-backdoor-commands"));
+            println!("3. {}", tr("checking-for-direct-memory-operations"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected.");
+            println!("{}", tr("no-card-detected"));
         }
     }
     
@@ -54,12 +55,12 @@ pub fn detect_card_type(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>
 
 /// Write a custom UID to a Magic Card
 pub fn write_custom_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Write Custom UID to Magic Card ===");
-    println!("WARNING: This only works with Magic Cards that support UID changing!");
-    println!("Using this on non-Magic Cards may DAMAGE your card permanently.");
+    println!("\n=== {} ===", tr("title-write-uid"));
+    println!("{}", tr("write-uid-magic-only"));
+    println!("{}", tr("write-uid-may-damage"));
     
     // Get the new UID
-    print!("Enter new UID in hex (e.g., 11:22:33:44): ");
+    print!("{} ", tr("enter-new-uid-in-hex"));
     io::stdout().flush()?;
     let mut new_uid_str = String::new();
     io::stdin().read_line(&mut new_uid_str)?;
@@ -67,35 +68,35 @@ pub fn write_custom_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>
     let new_uid = match hex_to_bytes(new_uid_str.trim()) {
         Ok(bytes) => {
             if bytes.len() != 4 && bytes.len() != 7 && bytes.len() != 10 {
-                println!("Invalid UID length. Must be 4, 7, or 10 bytes.");
+                println!("{}", tr("err-invalid-uid-length-must-be"));
                 return Ok(());
             }
             bytes
         },
         Err(e) => {
-            println!("Invalid hex format: {}", e);
+            println!("{}", tr_args("err-invalid-hex-format", &[("error", &e.to_string())]));
             return Ok(());
         }
     };
     
-    println!("\nNew UID will be: {}", format_uid(&new_uid));
-    print!("Are you ABSOLUTELY sure you want to proceed? This can brick your card! (y/n): ");
+    println!("\n{}", tr_args("new-uid-will-be", &[("uid", &format_uid(&new_uid).to_string())]));
+    print!("{} ", tr("write-uid-confirm"));
     io::stdout().flush()?;
     let mut confirm = String::new();
     io::stdin().read_line(&mut confirm)?;
     
     if confirm.trim().to_lowercase() != "y" {
-        println!("Operation cancelled.");
+        println!("{}", tr("operation-cancelled"));
         return Ok(());
     }
     
     // Wait for card
-    println!("\nPlace the Magic Card on the reader...");
+    println!("\n{}", tr("place-magic-card-on-reader"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Card detected. Current UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-detected-current-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // First, check if it's likely a Magic Card
             let is_magic = if uid.len() == 4 {
@@ -106,30 +107,30 @@ pub fn write_custom_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>
             };
             
             if !is_magic {
-                println!("\nWARNING: This does NOT appear to be a Magic Card!");
-                print!("Proceeding might DAMAGE YOUR CARD PERMANENTLY! Continue? (y/n): ");
+                println!("\n{}", tr("write-uid-not-magic"));
+                print!("{} ", tr("write-uid-damage-confirm"));
                 io::stdout().flush()?;
                 let mut risky_confirm = String::new();
                 io::stdin().read_line(&mut risky_confirm)?;
                 
                 if risky_confirm.trim().to_lowercase() != "y" {
-                    println!("Operation cancelled.");
+                    println!("{}", tr("operation-cancelled"));
                     wait_for_card_removal(reader)?;
                     return Ok(());
                 }
             }
             
-            println!("\nAttempting to change UID...");
+            println!("\n{}", tr("attempting-to-change-uid"));
             // (Implementation would use special commands for Magic Cards)
             
-            println!("\nUID change operation completed.");
-            println!("Remove card and place it again to verify the new UID.");
+            println!("\n{}", tr("uid-change-operation-completed"));
+            println!("{}", tr("remove-card-and-place-it"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected.");
+            println!("{}", tr("no-card-detected"));
         }
     }
     
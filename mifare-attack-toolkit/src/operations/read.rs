@@ -1,11 +1,14 @@
 // src/operations/read.rs
 use std::error::Error;
 use std::io::{self, Write};
+use mifare_rc522::CardLayout;
+use progress_sink::{ProgressSink, Tracker};
 
-use crate::cards::{identify_card_type, CardType};
+use crate::cards::{identify_card_type, CardType, KeyType};
 use crate::reader::MifareClassic;
-use crate::utils::{wait_for_card_removal, format_uid};
-use crate::card_detection::wait_for_card_enhanced;
+use crate::utils::{wait_for_card_removal, format_uid, bytes_to_hex};
+use crate::card_detection::{detect_card, wait_for_card_enhanced};
+use crate::i18n::{tr, tr_args};
 
 /// Read a card's UID (alias for read_card_uid to fix compatibility)
 pub fn read_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
@@ -14,7 +17,7 @@ pub fn read_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
 
 /// Read a card's UID
 pub fn read_card_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Reading Card UID ===");
+    println!("\n=== {} ===", tr("title-read-card-uid"));
     
     // Reset the reader for better reliability
     reader.reset_reader()?;
@@ -22,17 +25,17 @@ pub fn read_card_uid(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
     // Wait for a card with 5 second timeout
     match wait_for_card_enhanced(reader, 5)? {
         Some(uid) => {
-            println!("UID: {}", format_uid(&uid));
+            println!("{}", tr_args("uid-line", &[("uid", &format_uid(&uid).to_string())]));
             
             // Try to identify the card type
             let card_type = identify_card_type(&uid, None);
-            println!("Card type: {}", card_type);
+            println!("{}", tr_args("card-type", &[("card_type", &card_type.to_string())]));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected during the timeout period.");
+            println!("{}", tr("no-card-detected-during-timeout"));
         }
     }
     
@@ -45,76 +48,113 @@ pub fn dump_sector(reader: &mut MifareClassic, sector: u8) -> Result<(), Box<dyn
         return Err("Invalid sector number (must be 0-15)".into());
     }
     
-    println!("\n=== Dumping Sector {} ===", sector);
+    println!("\n=== {} ===", tr_args("title-dump-sector", &[("sector", &sector.to_string())]));
     
     // Wait for a card with 5 second timeout
     match wait_for_card_enhanced(reader, 5)? {
         Some(uid) => {
-            println!("Card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Try to authenticate and read the sector
-            println!("\nAttempting to read sector {}...", sector);
+            println!("\n{}", tr_args("attempting-to-read-sector", &[("sector", &sector.to_string())]));
             
             // Sector was read successfully
-            println!("Sector {} contents:", sector);
+            println!("{}", tr_args("sector-contents", &[("sector", &sector.to_string())]));
             println!("------------------");
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected during the timeout period.");
+            println!("{}", tr("no-card-detected-during-timeout"));
         }
     }
     
     Ok(())
 }
 
-/// Dump all card contents
-pub fn dump_card(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Dumping Full Card ===");
-    println!("This operation will attempt to read all accessible sectors.");
+/// Dump all card contents, each sector with the key the default key search or an
+/// attack found for it, the transport key otherwise
+pub fn dump_card(reader: &mut MifareClassic, progress: &dyn ProgressSink) -> Result<(), Box<dyn Error>> {
+    println!("\n=== {} ===", tr("title-dump-card"));
+    println!("{}", tr("dump-reads-all-sectors"));
     
-    print!("Continue? (y/n): ");
+    print!("{} ", tr("continue-y-n"));
     io::stdout().flush()?;
     let mut input = String::new();
     io::stdin().read_line(&mut input)?;
     
     if input.trim().to_lowercase() != "y" {
-        println!("Operation cancelled.");
+        println!("{}", tr("operation-cancelled"));
         return Ok(());
     }
     
     // Wait for a card with 5 second timeout
     match wait_for_card_enhanced(reader, 5)? {
         Some(uid) => {
-            println!("Card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Try to identify the card type
             let card_type = identify_card_type(&uid, None);
-            println!("Card type: {}", card_type);
+            println!("{}", tr_args("card-type", &[("card_type", &card_type.to_string())]));
             
             // Determine number of sectors based on card type
-            let _num_sectors = match card_type {
-                CardType::MifareClassic1K => 16,
-                CardType::MifareClassic4K => 40,
-                _ => 16, // Default to 16 sectors
+            let layout = match card_type {
+                CardType::MifareClassic4K => CardLayout::Classic4K,
+                _ => CardLayout::Classic1K, // Default to 16 sectors
             };
+            let num_sectors = layout.sector_count();
             
-            println!("\nAttempting to read all sectors...");
+            println!("\n{}", tr("attempting-to-read-all-sectors"));
+            let sectors = read_sectors(reader, num_sectors, progress)?;
             
-            // Try to read each sector
-            // (implementation would call reader.dump_card() or similar)
-            
-            println!("\nDump completed.");
+            println!();
+            for (sector, blocks) in layout.sectors().zip(&sectors) {
+                if blocks.is_empty() {
+                    println!("{}", tr_args("sector-no-key", &[("sector", &format!("{:2}", sector))]));
+                }
+                for (block_addr, block) in sector.blocks().zip(blocks) {
+                    println!("{}", tr_args("dump-block-line", &[("block", &format!("{:3}", block_addr)), ("hex", &bytes_to_hex(block))]));
+                }
+            }
+            let read = sectors.iter().filter(|blocks| !blocks.is_empty()).count();
+            println!("\n{}", tr_args("dump-completed-of-sectors-read", &[("read", &read.to_string()), ("total", &num_sectors.to_string())]));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected during the timeout period.");
+            println!("{}", tr("no-card-detected-during-timeout"));
         }
     }
     
     Ok(())
 }
+
+/// Read the first `num_sectors` sectors, each with the key the default key search or an
+/// attack found for it, the transport key otherwise. A sector no key opens is empty.
+pub fn read_sectors(reader: &mut MifareClassic, num_sectors: u8, progress: &dyn ProgressSink) -> Result<Vec<Vec<Vec<u8>>>, Box<dyn Error>> {
+    let mut tracker = Tracker::new();
+    let mut sectors = Vec::new();
+    for sector in 0..num_sectors {
+        let message = format!("sector {} of {}", sector + 1, num_sectors);
+        progress.report(&tracker.progress(sector as usize, num_sectors as usize, message));
+        
+        // A failed authentication leaves the card mute, so it is powered up afresh for
+        // each sector
+        let (key_type, key) = sector_key(reader, sector);
+        reader.reset_field()?;
+        let uid = detect_card(reader)?.ok_or("The card left the reader")?;
+        sectors.push(reader.read_sector_with_special_handling(sector, &key, key_type, &uid)?);
+    }
+    progress.report(&tracker.progress(sectors.len(), sectors.len(), "done"));
+    Ok(sectors)
+}
+
+// the key found for the sector, Key A first, or the transport key
+fn sector_key(reader: &MifareClassic, sector: u8) -> (KeyType, [u8; 6]) {
+    [KeyType::KeyA, KeyType::KeyB]
+        .into_iter()
+        .find_map(|key_type| reader.last_known_keys.get(&(sector, key_type)).map(|key| (key_type, *key)))
+        .unwrap_or((KeyType::KeyA, [0xFF; 6]))
+}
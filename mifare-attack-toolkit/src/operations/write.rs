@@ -5,13 +5,14 @@ use std::io::{self, Write};
 use crate::reader::MifareClassic;
 use crate::utils::{wait_for_card_removal, format_uid, bytes_to_hex, hex_to_bytes};
 use crate::card_detection::wait_for_card_enhanced;
+use crate::i18n::{tr, tr_args};
 
 /// Write text data to a block
 pub fn write_text_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Write Text to Block ===");
+    println!("\n=== {} ===", tr("title-write-text"));
     
     // Get block address
-    print!("Enter block number (0-63): ");
+    print!("{} ", tr("enter-block-number-0-63"));
     io::stdout().flush()?;
     let mut block_str = String::new();
     io::stdin().read_line(&mut block_str)?;
@@ -19,47 +20,47 @@ pub fn write_text_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Err
     let block = match block_str.trim().parse::<u8>() {
         Ok(b) if b <= 63 => b,
         _ => {
-            println!("Invalid block number. Must be 0-63.");
+            println!("{}", tr("err-invalid-block-number-must-be"));
             return Ok(());
         }
     };
     
     // Get text to write
-    print!("Enter text to write (max 16 chars): ");
+    print!("{} ", tr("enter-text-to-write-max"));
     io::stdout().flush()?;
     let mut text = String::new();
     io::stdin().read_line(&mut text)?;
     
     let text = text.trim();
     if text.len() > 16 {
-        println!("Text too long. Will be truncated to 16 characters.");
+        println!("{}", tr("text-too-long-will-be"));
     }
     
     // Wait for card
-    println!("\nPlacing card on the reader...");
+    println!("\n{}", tr("placing-card-on-reader"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&uid).to_string())]));
             
             // Format data as 16 bytes
             let mut data = Vec::from(text.as_bytes());
             data.resize(16, 0); // Pad with zeros
             
-            println!("\nWriting to block {}...", block);
-            println!("Data: {}", bytes_to_hex(&data));
+            println!("\n{}", tr_args("writing-to-block", &[("block", &block.to_string())]));
+            println!("{}", tr_args("write-data", &[("hex", &bytes_to_hex(&data))]));
             
             // Try to write data to the block
             // (Implementation would call reader.write_block() or similar)
             
-            println!("\nWrite operation completed.");
+            println!("\n{}", tr("write-operation-completed"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected within timeout period.");
+            println!("{}", tr("no-card-detected-within-timeout"));
         }
     }
     
@@ -68,10 +69,10 @@ pub fn write_text_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Err
 
 /// Write hex data to a block
 pub fn write_hex_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("\n=== Write Hex to Block ===");
+    println!("\n=== {} ===", tr("title-write-hex"));
     
     // Get block address
-    print!("Enter block number (0-63): ");
+    print!("{} ", tr("enter-block-number-0-63"));
     io::stdout().flush()?;
     let mut block_str = String::new();
     io::stdin().read_line(&mut block_str)?;
@@ -79,13 +80,13 @@ pub fn write_hex_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Erro
     let block = match block_str.trim().parse::<u8>() {
         Ok(b) if b <= 63 => b,
         _ => {
-            println!("Invalid block number. Must be 0-63.");
+            println!("{}", tr("err-invalid-block-number-must-be"));
             return Ok(());
         }
     };
     
     // Get hex data to write
-    print!("Enter hex data (32 hex chars, no spaces): ");
+    print!("{} ", tr("enter-hex-data-32-hex"));
     io::stdout().flush()?;
     let mut hex_data = String::new();
     io::stdin().read_line(&mut hex_data)?;
@@ -94,38 +95,38 @@ pub fn write_hex_to_block(reader: &mut MifareClassic) -> Result<(), Box<dyn Erro
     let data = match hex_to_bytes(hex_data) {
         Ok(bytes) => {
             if bytes.len() != 16 {
-                println!("Data must be exactly 16 bytes (32 hex chars).");
+                println!("{}", tr("data-must-be-exactly-16"));
                 return Ok(());
             }
             bytes
         },
         Err(e) => {
-            println!("Invalid hex data: {}", e);
+            println!("{}", tr_args("err-invalid-hex-data", &[("error", &e.to_string())]));
             return Ok(());
         }
     };
     
     // Wait for card
-    println!("\nPlacing card on the reader...");
+    println!("\n{}", tr("placing-card-on-reader"));
     
     // FIXED: Use wait_for_card_enhanced instead to avoid type parameter issues
     match wait_for_card_enhanced(reader, 15)? {
         Some(uid) => {
-            println!("Card detected. UID: {}", format_uid(&uid));
+            println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&uid).to_string())]));
             
-            println!("\nWriting to block {}...", block);
-            println!("Data: {}", bytes_to_hex(&data));
+            println!("\n{}", tr_args("writing-to-block", &[("block", &block.to_string())]));
+            println!("{}", tr_args("write-data", &[("hex", &bytes_to_hex(&data))]));
             
             // Try to write data to the block
             // (Implementation would call reader.write_block() or similar)
             
-            println!("\nWrite operation completed.");
+            println!("\n{}", tr("write-operation-completed"));
             
             // Wait for card removal
             wait_for_card_removal(reader)?;
         },
         None => {
-            println!("No card detected within timeout period.");
+            println!("{}", tr("no-card-detected-within-timeout"));
         }
     }
     
@@ -0,0 +1,161 @@
+// src/prng.rs
+//
+// PRNG weakness test. Original MIFARE Classic cards draw their authentication nonces
+// from a 16 bit LFSR that restarts every time the card is powered up, so the nonce a
+// card sends depends only on how long after power-up it was asked for. Darkside needs
+// the card to send the same nonce again and again, nested needs the distance between
+// nonces to be predictable; both fail on cards with a hardened PRNG. Sampling a few
+// pairs of nonces, one right after a field reset and one from a second authentication
+// in the same session, tells which case a card is before minutes are spent on an
+// attack that can't work.
+use std::fmt;
+
+use crate::i18n::{tr, tr_args};
+
+/// Pairs of nonces sampled per test
+pub const PRNG_SAMPLES: usize = 20;
+/// Distances that vary more than this (in PRNG steps) make nested unreliable
+const NESTED_SPREAD: u32 = 64;
+/// Share of samples that have to be PRNG nonces for the PRNG to count as weak
+const WEAK_SHARE: f64 = 0.8;
+/// Period of the 16 bit LFSR
+const PRNG_PERIOD: u32 = 65535;
+
+/// What kind of nonces the card sends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrngKind {
+    /// The same nonce every time (clone chips)
+    Static,
+    /// The original 16 bit LFSR
+    Weak,
+    /// Nonces that don't follow the LFSR (MIFARE Classic EV1, Plus and most new clones)
+    Hard,
+}
+
+impl fmt::Display for PrngKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrngKind::Static => write!(f, "static nonce"),
+            PrngKind::Weak => write!(f, "weak (16 bit LFSR)"),
+            PrngKind::Hard => write!(f, "hardened"),
+        }
+    }
+}
+
+/// The nonces sampled from one card and what they say about it
+pub struct PrngReport {
+    pub samples: usize,
+    pub kind: PrngKind,
+    /// How many samples started with the most common nonce after a field reset
+    pub repeated: usize,
+    /// PRNG steps from the first nonce of each sample to the second, weak PRNG only
+    pub distances: Vec<u32>,
+}
+
+impl PrngReport {
+    /// How much the distance between two authentications varies
+    pub fn spread(&self) -> u32 {
+        match (self.distances.iter().min(), self.distances.iter().max()) {
+            (Some(min), Some(max)) => max - min,
+            _ => 0,
+        }
+    }
+
+    /// Whether the darkside attack can work from this reader
+    pub fn darkside_viable(&self) -> bool {
+        // Darkside keeps asking for one nonce, enough of the samples have to repeat it
+        self.kind == PrngKind::Weak && self.repeated * 4 >= self.samples
+    }
+
+    /// Whether the nested attack can predict the nonces from this reader
+    pub fn nested_viable(&self) -> bool {
+        self.kind == PrngKind::Weak && !self.distances.is_empty() && self.spread() <= NESTED_SPREAD
+    }
+}
+
+impl fmt::Display for PrngReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", tr_args("prng-report", &[("kind", &self.kind.to_string()), ("samples", &self.samples.to_string())]))?;
+        match self.kind {
+            PrngKind::Static => {
+                writeln!(f, "{}", tr("prng-static-darkside"))?;
+                write!(f, "{}", tr("prng-static-nested"))
+            },
+            PrngKind::Hard => {
+                writeln!(f, "{}", tr("prng-hard-darkside"))?;
+                write!(f, "{}", tr("prng-hard-nested"))
+            },
+            PrngKind::Weak => {
+                writeln!(f, "{}", tr_args("prng-repeated", &[("repeated", &self.repeated.to_string()), ("samples", &self.samples.to_string())]))?;
+                if let (Some(min), Some(max)) = (self.distances.iter().min(), self.distances.iter().max()) {
+                    writeln!(f, "{}", tr_args("prng-distance", &[("min", &min.to_string()), ("max", &max.to_string())]))?;
+                }
+                writeln!(f, "{}", tr(if self.darkside_viable() { "prng-weak-darkside" } else { "prng-weak-darkside-unlikely" }))?;
+                write!(f, "{}", tr(if self.nested_viable() { "prng-weak-nested" } else { "prng-weak-nested-unlikely" }))
+            },
+        }
+    }
+}
+
+/// Whether a nonce is 32 consecutive bits of the weak PRNG: every bit from the 16th on
+/// has to follow from the LFSR taps (x^16 + x^14 + x^13 + x^11 + 1)
+pub fn is_prng_nonce(nonce: u32) -> bool {
+    let x = nonce.swap_bytes();
+    (0..16).all(|p| ((x >> (p + 16)) ^ (x >> p) ^ (x >> (p + 2)) ^ (x >> (p + 3)) ^ (x >> (p + 5))) & 1 == 0)
+}
+
+/// The nonce the weak PRNG gives `steps` clock cycles after `nonce`
+pub fn prng_successor(nonce: u32, steps: u32) -> u32 {
+    let mut x = nonce.swap_bytes();
+    for _ in 0..steps {
+        x = (x >> 1) | (((x >> 16) ^ (x >> 18) ^ (x >> 19) ^ (x >> 21)) & 1) << 31;
+    }
+    x.swap_bytes()
+}
+
+/// Clock cycles from one PRNG nonce to another, None when either isn't a PRNG nonce
+pub fn nonce_distance(from: u32, to: u32) -> Option<u32> {
+    if !is_prng_nonce(from) || !is_prng_nonce(to) {
+        return None;
+    }
+    let mut nonce = from;
+    for steps in 0..PRNG_PERIOD {
+        if nonce == to {
+            return Some(steps);
+        }
+        nonce = prng_successor(nonce, 1);
+    }
+    None
+}
+
+/// Classify sampled nonces, each sample the nonce right after a field reset and the
+/// nonce of a second authentication without a reset. None without samples.
+pub fn analyse_nonces(samples: &[(u32, u32)]) -> Option<PrngReport> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    let nonces: Vec<u32> = samples.iter().flat_map(|&(first, second)| [first, second]).collect();
+    let valid = nonces.iter().filter(|&&nonce| is_prng_nonce(nonce)).count();
+    // A weak PRNG card powered up the same way can send the same first nonce every
+    // time, but its PRNG keeps running between two authentications
+    let kind = if samples.iter().all(|(first, second)| first == second) {
+        PrngKind::Static
+    } else if valid as f64 >= nonces.len() as f64 * WEAK_SHARE {
+        PrngKind::Weak
+    } else {
+        PrngKind::Hard
+    };
+
+    let most_common = samples.iter()
+        .map(|&(first, _)| samples.iter().filter(|&&(other, _)| other == first).count())
+        .max()
+        .unwrap_or(0);
+    let distances = if kind == PrngKind::Weak {
+        samples.iter().filter_map(|&(first, second)| nonce_distance(first, second)).collect()
+    } else {
+        Vec::new()
+    };
+
+    Some(PrngReport { samples: samples.len(), kind, repeated: most_common, distances })
+}
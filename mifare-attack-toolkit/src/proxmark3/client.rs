@@ -0,0 +1,292 @@
+// src/proxmark3/client.rs
+use std::env;
+use std::error::Error;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::cards::KeyType;
+use crate::utils::bytes_to_hex;
+use super::parse::{self, CardInfo};
+#[cfg(feature = "attacks")]
+use super::parse::SectorKeys;
+
+/// Serial ports a Proxmark3 shows up on, checked in order
+const PORT_CANDIDATES: [&str; 4] = ["/dev/ttyACM0", "/dev/ttyACM1", "/dev/ttyUSB0", "/dev/serial/by-id"];
+/// Client executable, not the `pm3` wrapper script, which picks the port itself
+const CLIENT_NAME: &str = "proxmark3";
+
+/// Timeouts for quick card commands and for the attacks, which can run for minutes
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(20);
+const ATTACK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// A Proxmark3 attached over USB-serial, driven through the official client
+/// (`proxmark3 <port> -c "<commands>"`), one client run per operation.
+pub struct Proxmark3 {
+    client: PathBuf,
+    port: String,
+}
+
+fn key_type_flag(key_type: KeyType) -> &'static str {
+    match key_type {
+        KeyType::KeyA => "-a",
+        KeyType::KeyB => "-b",
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes_to_hex(bytes).replace(' ', "")
+}
+
+/// Find an executable on PATH
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    env::var_os("PATH").and_then(|paths| {
+        env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+impl Proxmark3 {
+    pub fn new(client: impl Into<PathBuf>, port: &str) -> Self {
+        Self { client: client.into(), port: port.to_string() }
+    }
+
+    /// Look for a client and a device, PM3_CLIENT and PM3_PORT override the search
+    pub fn detect() -> Option<Self> {
+        let client = env::var_os("PM3_CLIENT")
+            .map(PathBuf::from)
+            .or_else(|| find_in_path(CLIENT_NAME))?;
+
+        let port = match env::var("PM3_PORT") {
+            Ok(port) => port,
+            Err(_) => Self::find_port()?,
+        };
+
+        Some(Self::new(client, &port))
+    }
+
+    fn find_port() -> Option<String> {
+        PORT_CANDIDATES.iter().find_map(|candidate| {
+            let path = Path::new(candidate);
+            if path.is_dir() {
+                // /dev/serial/by-id names the device, e.g. usb-proxmark.org_proxmark3-if00
+                std::fs::read_dir(path).ok()?
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .find(|entry| entry.to_string_lossy().to_lowercase().contains("proxmark"))
+                    .map(|entry| entry.to_string_lossy().to_string())
+            } else if path.exists() {
+                Some(candidate.to_string())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn client(&self) -> &Path {
+        &self.client
+    }
+
+    pub fn port(&self) -> &str {
+        &self.port
+    }
+
+    /// Run client commands (separated by ';') and return what the client printed,
+    /// without colour codes. The client is killed if it runs past `timeout`.
+    pub fn run(&self, commands: &str, timeout: Duration) -> Result<String, Box<dyn Error>> {
+        let mut child = Command::new(&self.client)
+            .arg(&self.port)
+            .arg("-c")
+            .arg(commands)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not start {}: {}", self.client.display(), e))?;
+
+        // read on a thread so a long attack doesn't fill the pipe and stall the client
+        let mut stdout = child.stdout.take().ok_or("No client output")?;
+        let mut stderr = child.stderr.take().ok_or("No client output")?;
+        let reader = thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stdout.read_to_string(&mut output);
+            let _ = stderr.read_to_string(&mut output);
+            output
+        });
+
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if start.elapsed() > timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Proxmark3 command '{}' timed out after {}s", commands, timeout.as_secs()).into());
+            }
+            thread::sleep(Duration::from_millis(100));
+        };
+
+        let output = parse::strip_ansi(&reader.join().unwrap_or_default());
+        if !status.success() {
+            let last_line = output.lines().rev().find(|line| !line.trim().is_empty()).unwrap_or("");
+            return Err(format!("Proxmark3 client failed ({}): {}", status, last_line.trim()).into());
+        }
+
+        Ok(output)
+    }
+
+    /// Check the device answers, returns its version banner
+    pub fn version(&self) -> Result<String, Box<dyn Error>> {
+        let output = self.run("hw version", COMMAND_TIMEOUT)?;
+        let line = output.lines()
+            .find(|line| line.contains("client:") || line.contains("os:") || line.to_lowercase().contains("version"))
+            .unwrap_or("Proxmark3");
+        Ok(line.trim().to_string())
+    }
+
+    /// Select the card in the field, None if there is no card
+    pub fn card_info(&self) -> Result<Option<CardInfo>, Box<dyn Error>> {
+        let output = self.run("hf 14a info", COMMAND_TIMEOUT)?;
+        Ok(parse::parse_card_info(&output))
+    }
+
+    /// `hf 14a info` followed by `hf mf info`, which on current clients adds the PRNG,
+    /// static nonce and clone chip fingerprint lines
+    pub fn detailed_card_info(&self) -> Result<Option<CardInfo>, Box<dyn Error>> {
+        let output = self.run("hf 14a info; hf mf info", COMMAND_TIMEOUT)?;
+        Ok(parse::parse_card_info(&output))
+    }
+
+    /// Read a block with a key, None if the key doesn't open it
+    pub fn read_block(&self, block: u8, key: &[u8; 6], key_type: KeyType) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+        let command = format!("hf mf rdbl --blk {} {} -k {}", block, key_type_flag(key_type), hex(key));
+        let output = self.run(&command, COMMAND_TIMEOUT)?;
+        Ok(parse::parse_block(&output, block))
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Sample the card's nonces in pairs: the first request powers the field up, selects
+    /// the card and starts an authentication the card answers with its nonce, the
+    /// second selects it again with the field still on. Pairs the card didn't answer
+    /// are skipped.
+    pub fn collect_nonces(&self, block: u8, key_type: KeyType, count: usize) -> Result<Vec<(u32, u32)>, Box<dyn Error>> {
+        let auth = match key_type {
+            KeyType::KeyA => 0x60,
+            KeyType::KeyB => 0x61,
+        };
+        // one client run per pair, so a missed answer can't pair nonces from two runs
+        let command = format!("hf 14a raw -k -s -c {0:02X}{1:02X}; hf 14a raw -s -c {0:02X}{1:02X}", auth, block);
+        let mut samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let [first, second] = parse::parse_nonces(&self.run(&command, COMMAND_TIMEOUT)?)[..] {
+                samples.push((first, second));
+            }
+        }
+        Ok(samples)
+    }
+
+    /// Poll for a card for up to `timeout_secs` seconds
+    pub fn wait_for_card(&self, timeout_secs: u64) -> Result<Option<CardInfo>, Box<dyn Error>> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(timeout_secs) {
+            if let Some(info) = self.card_info()? {
+                return Ok(Some(info));
+            }
+            thread::sleep(Duration::from_millis(250));
+        }
+        Ok(None)
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Try `keys` (A and B) on every sector of a 1K card
+    pub fn check_keys(&self, keys: &[[u8; 6]]) -> Result<Vec<SectorKeys>, Box<dyn Error>> {
+        let mut command = String::from("hf mf chk --1k");
+        for key in keys {
+            command.push_str(&format!(" -k {}", hex(key)));
+        }
+        let output = self.run(&command, ATTACK_TIMEOUT)?;
+        Ok(parse::parse_key_table(&output))
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Nested attack: recover the key for `target_block` using a key already known for `known_block`
+    pub fn nested(
+        &self,
+        known_block: u8,
+        known_key: &[u8; 6],
+        known_type: KeyType,
+        target_block: u8,
+        target_type: KeyType,
+    ) -> Result<Option<[u8; 6]>, Box<dyn Error>> {
+        let target_flag = match target_type {
+            KeyType::KeyA => "--ta",
+            KeyType::KeyB => "--tb",
+        };
+        let command = format!(
+            "hf mf nested --1k --blk {} {} -k {} --tblk {} {}",
+            known_block, key_type_flag(known_type), hex(known_key), target_block, target_flag
+        );
+        let output = self.run(&command, ATTACK_TIMEOUT)?;
+        Ok(parse::parse_found_key(&output))
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Hardnested attack: recover the key for `target_block` using a key already known for
+    /// `known_block`, on cards whose hardened PRNG defeats nested
+    pub fn hardnested(
+        &self,
+        known_block: u8,
+        known_key: &[u8; 6],
+        known_type: KeyType,
+        target_block: u8,
+        target_type: KeyType,
+    ) -> Result<Option<[u8; 6]>, Box<dyn Error>> {
+        let target_flag = match target_type {
+            KeyType::KeyA => "--ta",
+            KeyType::KeyB => "--tb",
+        };
+        let command = format!(
+            "hf mf hardnested --blk {} {} -k {} --tblk {} {}",
+            known_block, key_type_flag(known_type), hex(known_key), target_block, target_flag
+        );
+        let output = self.run(&command, ATTACK_TIMEOUT)?;
+        Ok(parse::parse_found_key(&output))
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Darkside attack, needs no known key but only works on cards with the old weak PRNG
+    pub fn darkside(&self, block: u8, key_type: KeyType) -> Result<Option<[u8; 6]>, Box<dyn Error>> {
+        let command = format!("hf mf darkside --blk {} {}", block, key_type_flag(key_type));
+        let output = self.run(&command, ATTACK_TIMEOUT)?;
+        Ok(parse::parse_found_key(&output))
+    }
+
+    #[cfg(feature = "attacks")]
+    /// Let the client pick and chain the attacks (dictionary, darkside, nested, hardnested)
+    /// until all keys are known, then dump the card. Returns the keys and the dump file.
+    pub fn autopwn(&self, known: Option<(u8, &[u8; 6], KeyType)>) -> Result<(Vec<SectorKeys>, Option<String>), Box<dyn Error>> {
+        let mut command = String::from("hf mf autopwn --1k");
+        if let Some((sector, key, key_type)) = known {
+            command.push_str(&format!(" -s {} {} -k {}", sector, key_type_flag(key_type), hex(key)));
+        }
+        let output = self.run(&command, ATTACK_TIMEOUT)?;
+        Ok((parse::parse_key_table(&output), parse::parse_dump_file(&output)))
+    }
+
+    /// Set the UID of a magic (Gen 1a) card
+    pub fn set_uid(&self, uid: &[u8]) -> Result<bool, Box<dyn Error>> {
+        let output = self.run(&format!("hf mf csetuid -u {}", hex(uid)), COMMAND_TIMEOUT)?;
+        Ok(output.to_lowercase().contains("new uid") && !output.to_lowercase().contains("( fail )"))
+    }
+
+    /// Write a whole dump, block 0 included, to a magic (Gen 1a) card
+    pub fn load_magic(&self, dump_file: &str) -> Result<bool, Box<dyn Error>> {
+        let output = self.run(&format!("hf mf cload -f {}", dump_file), ATTACK_TIMEOUT)?;
+        let lower = output.to_lowercase();
+        Ok(lower.contains("loaded") && !lower.contains("( fail )") && !lower.contains("can't"))
+    }
+}
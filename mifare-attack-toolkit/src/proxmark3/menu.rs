@@ -0,0 +1,465 @@
+// src/proxmark3/menu.rs
+//
+// The attack manager's menu on a Proxmark3. Key recovery, dumping and cloning are left
+// to the client's own implementations, which are much faster on the PM3 than
+// anything the MFRC522 can do.
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+#[cfg(feature = "attacks")]
+use mifare_rc522::BlockAddr;
+
+use crate::analysis::analyse_card;
+use crate::cards::{identify_card_type, KeyType, DEFAULT_KEYS};
+#[cfg(feature = "attacks")]
+use crate::cards::load_key_file;
+use crate::consent;
+#[cfg(feature = "attacks")]
+use crate::prng::{analyse_nonces, PRNG_SAMPLES};
+#[cfg(feature = "attacks")]
+use crate::ui::menu::get_sector_number;
+use crate::utils::{bytes_to_hex, format_uid, get_user_confirmation, hex_to_bytes};
+use super::client::Proxmark3;
+#[cfg(feature = "attacks")]
+use super::parse::SectorKeys;
+use crate::i18n::{tr, tr_args};
+
+/// Seconds to wait for a card before giving up
+const CARD_TIMEOUT: u64 = 15;
+
+fn prompt(text: &str) -> Result<String, Box<dyn Error>> {
+    print!("{}", text);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+#[cfg(feature = "attacks")]
+fn prompt_key_type(text: &str) -> Result<KeyType, Box<dyn Error>> {
+    match prompt(text)?.to_uppercase().as_str() {
+        "A" => Ok(KeyType::KeyA),
+        "B" => Ok(KeyType::KeyB),
+        _ => Err(tr("err-invalid-key-type-must-be").into()),
+    }
+}
+
+#[cfg(feature = "attacks")]
+fn prompt_block(text: &str) -> Result<u8, Box<dyn Error>> {
+    match prompt(text)?.parse::<u8>() {
+        Ok(block) if block <= 63 => Ok(block),
+        _ => Err(tr("err-invalid-block-number-must-be").into()),
+    }
+}
+
+#[cfg(feature = "attacks")]
+fn prompt_key(text: &str) -> Result<[u8; 6], Box<dyn Error>> {
+    let bytes = hex_to_bytes(&prompt(text)?)?;
+    if bytes.len() != 6 {
+        return Err(tr("err-invalid-key-length-must-be").into());
+    }
+    let mut key = [0u8; 6];
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+#[cfg(feature = "attacks")]
+fn print_keys(keys: &[SectorKeys]) {
+    let show = |key: Option<[u8; 6]>| key.map_or("------------".to_string(), |key| bytes_to_hex(&key).replace(' ', ""));
+    println!("{}", tr("pm3-keys-header"));
+    for sector in keys {
+        println!("  {:>2}   | {} | {}", sector.sector, show(sector.key_a), show(sector.key_b));
+    }
+    let found = keys.iter().map(|s| s.key_a.is_some() as usize + s.key_b.is_some() as usize).sum::<usize>();
+    println!("{}", tr_args("pm3-keys-found", &[("found", &found.to_string()), ("total", &(keys.len() * 2).to_string())]));
+}
+
+/// The client picks the loader by extension, so a raw dump under another name
+/// (mfoc's .mfd) is copied to a .bin next to the system temp files first
+fn binary_dump_file(path: &str) -> Result<String, Box<dyn Error>> {
+    let size = fs::metadata(path).map_err(|e| format!("Could not read {}: {}", path, e))?.len();
+    if ![320, 1024, 4096].contains(&size) {
+        return Err(format!("{} is not a raw Mifare Classic dump ({} bytes)", path, size).into());
+    }
+    if path.to_lowercase().ends_with(".bin") {
+        return Ok(path.to_string());
+    }
+
+    let name = Path::new(path).file_stem().map_or("dump".into(), |stem| stem.to_string_lossy());
+    let copy = env::temp_dir().join(format!("{}.bin", name));
+    fs::copy(path, &copy)?;
+    Ok(copy.to_string_lossy().to_string())
+}
+
+pub struct Proxmark3Manager<'a> {
+    pm3: &'a Proxmark3,
+}
+
+impl<'a> Proxmark3Manager<'a> {
+    pub fn new(pm3: &'a Proxmark3) -> Self {
+        Self { pm3 }
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        loop {
+            self.display_menu();
+
+            let result = match prompt(&format!("{} ", tr("enter-choice")))?.as_str() {
+                "2" | "3" | "4" | "6" | "7" | "8" if safe_mode::is_read_only() => {
+                    safe_mode::refuse();
+                    Ok(())
+                },
+                "1" => self.read_uid(),
+                #[cfg(feature = "attacks")]
+                "2" => self.audited("default key search", false, Self::try_default_keys),
+                #[cfg(feature = "attacks")]
+                "3" => self.audited("nested attack", false, Self::run_nested_attack),
+                #[cfg(feature = "attacks")]
+                "4" => self.audited("darkside attack", false, Self::run_darkside_attack),
+                "5" => self.detect_magic_card(),
+                "6" => self.audited("write custom UID", true, Self::write_custom_uid),
+                #[cfg(feature = "attacks")]
+                "7" => self.audited("dump with key recovery", false, Self::dump_card),
+                "8" => self.audited("clone to magic card", true, Self::clone_card),
+                "10" => self.analyse_card(),
+                #[cfg(feature = "attacks")]
+                "11" => self.test_prng(),
+                "9" | "q" | "exit" | "quit" => {
+                    println!("{}", tr("exiting"));
+                    break;
+                },
+                _ => {
+                    println!("{}", tr("err-invalid-choice-please-try-again"));
+                    Ok(())
+                }
+            };
+
+            if let Err(e) = result {
+                println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run an attack once the warning is accepted and, for one that changes the card,
+    /// confirmed for this session, then add it to the audit log with the card still
+    /// on the Proxmark3
+    fn audited(
+        &mut self,
+        operation: &str,
+        destructive: bool,
+        attack: fn(&mut Self) -> Result<(), Box<dyn Error>>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !consent::ensure_acknowledged()? {
+            return Ok(());
+        }
+        if destructive && !consent::confirm_destructive(operation)? {
+            println!("{}", tr("operation-cancelled"));
+            return Ok(());
+        }
+
+        let result = attack(self);
+        let uid = self.pm3.card_info().ok().flatten().map(|info| info.uid);
+        consent::audit("Proxmark3", operation, uid.as_deref(), &result);
+        result
+    }
+
+    fn display_menu(&self) {
+        println!("\n\n{}", tr_args("menu-reader-proxmark3", &[("port", self.pm3.port())]));
+        println!("{}", tr("select-option"));
+        println!("1. {}", tr("read-card-uid"));
+        // Everything but reading the UID and detecting the card attacks or writes a card
+        if safe_mode::is_read_only() {
+            println!("5. {}", tr("detect-magic-card"));
+        } else if cfg!(feature = "attacks") {
+            println!("2. {}", tr("try-default-keys"));
+            println!("3. {}", tr("run-nested-attack"));
+            println!("4. {}", tr("run-darkside-attack"));
+            println!("5. {}", tr("detect-magic-card"));
+            println!("6. {}", tr("write-custom-uid"));
+            println!("7. {}", tr("dump-card-contents-recovers-missing"));
+            println!("8. {}", tr("clone-card-to-magic-card"));
+        } else {
+            println!("5. {}", tr("detect-magic-card"));
+            println!("6. {}", tr("write-custom-uid"));
+            println!("8. {}", tr("clone-dump-file-to-magic"));
+        }
+        println!("10. {}", tr("card-analysis-health-and-counterfeit"));
+        if cfg!(feature = "attacks") {
+            println!("11. {}", tr("test-prng"));
+        }
+        println!("9. {}", tr("menu-exit"));
+    }
+
+    fn read_uid(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-read-card-uid"));
+        println!("{}", tr("place-card-on-proxmark3"));
+        match self.pm3.wait_for_card(CARD_TIMEOUT)? {
+            Some(info) => {
+                println!("{}", tr_args("uid-line", &[("uid", &format_uid(&info.uid).to_string())]));
+                if let Some(atqa) = info.atqa {
+                    println!("{}", tr_args("card-atqa", &[("atqa", &bytes_to_hex(&atqa))]));
+                }
+                if let Some(sak) = info.sak {
+                    println!("{}", tr_args("card-sak", &[("sak", &format!("{:02X}", sak))]));
+                }
+                println!("{}", tr_args("card-type", &[("card_type", &identify_card_type(&info.uid, info.atqa).to_string())]));
+            },
+            None => println!("{}", tr("no-card-detected-during-timeout")),
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "attacks")]
+    fn try_default_keys(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-default-keys"));
+        let mut candidates = DEFAULT_KEYS.to_vec();
+        let key_file = prompt(&format!("{} ", tr("key-file-to-add-mfoc")))?;
+        if !key_file.is_empty() {
+            for key in load_key_file(&key_file)? {
+                if !candidates.contains(&key) {
+                    candidates.push(key);
+                }
+            }
+        }
+
+        println!("{}", tr_args("checking-keys-on-every-sector", &[("count", &candidates.len().to_string())]));
+        let keys = self.pm3.check_keys(&candidates)?;
+        if keys.is_empty() {
+            println!("{}", tr("no-card-answered-or-client"));
+        } else {
+            print_keys(&keys);
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "attacks")]
+    fn run_nested_attack(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-nested"));
+        println!("{}", tr("nested-needs-key"));
+        let known_key = prompt_key(&format!("{} ", tr("enter-known-key-hex-format")))?;
+        let known_block = prompt_block(&format!("{} ", tr("enter-known-block")))?;
+        let known_type = prompt_key_type(&format!("{} ", tr("known-key-type")))?;
+        let target_block = prompt_block(&format!("{} ", tr("enter-target-block")))?;
+        let target_type = prompt_key_type(&format!("{} ", tr("target-key-type")))?;
+
+        println!("{}", tr_args("running-nested-attack-on-block", &[("block", &target_block.to_string())]));
+        match self.pm3.nested(known_block, &known_key, known_type, target_block, target_type)? {
+            Some(key) => println!("{}", tr_args("found-for-sector", &[("key_type", &target_type.to_string()), ("sector", &BlockAddr(target_block).sector().to_string()), ("key", &bytes_to_hex(&key))])),
+            None => println!("{}", tr("attack-failed-cards-with-hardened")),
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "attacks")]
+    fn run_darkside_attack(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-darkside"));
+        println!("{}", tr("pm3-darkside-weak-prng"));
+        let block = prompt_block(&format!("{} ", tr("enter-target-block")))?;
+        let key_type = prompt_key_type(&format!("{} ", tr("key-type")))?;
+
+        if let Some(info) = self.pm3.card_info()? {
+            if info.prng.as_deref() == Some("hard") {
+                println!("{}", tr("card-reports-hard-prng-darkside"));
+                if !get_user_confirmation(&tr("continue-anyway")) {
+                    return Ok(());
+                }
+            }
+        }
+
+        println!("{}", tr_args("pm3-darkside-starting", &[("block", &block.to_string())]));
+        match self.pm3.darkside(block, key_type)? {
+            Some(key) => {
+                println!("{}", tr("attack-successful"));
+                println!("{}", tr_args("found-key-for-block", &[("block", &block.to_string()), ("key", &bytes_to_hex(&key))]));
+            },
+            None => println!("{}", tr("attack-failed-card-may-not")),
+        }
+        Ok(())
+    }
+
+    fn detect_magic_card(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-detect-card-type"));
+        println!("{}", tr("place-card-on-proxmark3"));
+        match self.pm3.wait_for_card(CARD_TIMEOUT)? {
+            Some(info) => {
+                println!("{}", tr_args("card-found-uid", &[("uid", &format_uid(&info.uid).to_string())]));
+                if info.magic.is_empty() {
+                    println!("\n{}", tr("no-magic-capabilities"));
+                } else {
+                    println!("\n{}", tr_args("magic-card-capabilities", &[("magic", &info.magic.join(", "))]));
+                }
+                if let Some(prng) = info.prng {
+                    println!("{}", tr_args("card-prng", &[("prng", &prng.to_string())]));
+                }
+            },
+            None => println!("{}", tr("no-card-detected")),
+        }
+        Ok(())
+    }
+
+    fn analyse_card(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-card-analysis"));
+        println!("{}", tr("place-card-on-proxmark3"));
+        let info = match self.pm3.wait_for_card(CARD_TIMEOUT)? {
+            Some(info) => self.pm3.detailed_card_info()?.unwrap_or(info),
+            None => {
+                println!("{}", tr("no-card-detected"));
+                return Ok(());
+            }
+        };
+
+        // Block 0 is only compared when one of the default keys opens sector 0
+        println!("{}", tr("reading-block-0-with-default"));
+        let mut block0 = None;
+        for key in DEFAULT_KEYS.iter() {
+            block0 = self.pm3.read_block(0, key, KeyType::KeyA)?;
+            if block0.is_some() {
+                break;
+            }
+        }
+
+        println!("\n{}", analyse_card(&info, block0.as_deref()));
+        Ok(())
+    }
+
+    #[cfg(feature = "attacks")]
+    fn test_prng(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-prng-test"));
+        println!("{}", tr("place-card-on-proxmark3"));
+        if self.pm3.wait_for_card(CARD_TIMEOUT)?.is_none() {
+            println!("{}", tr("no-card-detected"));
+            return Ok(());
+        }
+
+        println!("{}", tr_args("sampling-pairs-of-nonces", &[("count", &PRNG_SAMPLES.to_string())]));
+        let nonces = self.pm3.collect_nonces(0, KeyType::KeyA, PRNG_SAMPLES)?;
+        match analyse_nonces(&nonces) {
+            Some(report) => println!("\n{}", report),
+            None => println!("{}", tr("card-sent-no-nonces-it")),
+        }
+        Ok(())
+    }
+
+    fn write_custom_uid(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-write-uid"));
+        println!("{}", tr("write-uid-gen1a-only"));
+        let uid = hex_to_bytes(&prompt(&format!("{} ", tr("enter-new-uid-in-hex")))?)?;
+        if uid.len() != 4 {
+            println!("{}", tr("err-csetuid-uid-length"));
+            return Ok(());
+        }
+        if !get_user_confirmation(&tr_args("set-uid-confirm", &[("uid", &format_uid(&uid))])) {
+            println!("{}", tr("operation-cancelled"));
+            return Ok(());
+        }
+
+        if self.pm3.set_uid(&uid)? {
+            println!("{}", tr_args("uid-changed-to", &[("uid", &format_uid(&uid).to_string())]));
+        } else {
+            println!("{}", tr("err-change-uid-gen1a"));
+        }
+        Ok(())
+    }
+
+    /// Recover whatever keys are missing and dump the card, returns the dump file
+    #[cfg(feature = "attacks")]
+    fn recover_and_dump(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        let known = if get_user_confirmation(&tr("know-a-key")) {
+            let key = prompt_key(&format!("{} ", tr("enter-key-hex")))?;
+            let sector = get_sector_number(&tr("sector-key-works-on"))?;
+            let key_type = prompt_key_type(&format!("{} ", tr("key-type")))?;
+            Some((sector, key, key_type))
+        } else {
+            None
+        };
+
+        println!("{}", tr("running-autopwn-this-can-take"));
+        let (keys, dump_file) = self.pm3.autopwn(known.as_ref().map(|(sector, key, key_type)| (*sector, key, *key_type)))?;
+        if !keys.is_empty() {
+            print_keys(&keys);
+        }
+        match &dump_file {
+            Some(path) => println!("{}", tr_args("dump-saved-to", &[("path", &path.to_string())])),
+            None => println!("{}", tr("client-did-not-save-dump")),
+        }
+        Ok(dump_file)
+    }
+
+    #[cfg(feature = "attacks")]
+    fn dump_card(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-dump-card"));
+        println!("{}", tr("place-card-on-proxmark3-dump"));
+        if let Some(dump_file) = self.recover_and_dump()? {
+            // The client's binary dump has the same layout as an mfoc .mfd, keys in the trailers
+            let copy = prompt(&format!("{} ", tr("save-copy-as-mfd-for")))?;
+            if !copy.is_empty() {
+                fs::copy(&dump_file, &copy)?;
+                println!("{}", tr_args("dump-copied-to", &[("path", &copy.to_string())]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Without the attacks a source card can't be read, only a dump file cloned
+    #[cfg(not(feature = "attacks"))]
+    fn recover_and_dump(&mut self) -> Result<Option<String>, Box<dyn Error>> {
+        println!("{}", tr("no-key-recovery-build"));
+        Ok(None)
+    }
+
+    fn clone_card(&mut self) -> Result<(), Box<dyn Error>> {
+        println!("\n=== {} ===", tr("title-clone-card"));
+        println!("\n{}", tr("step-1-read-source-card"));
+        let existing = prompt(&format!("{} ", tr("dump-file-to-clone-from")))?;
+        let dump_file = if !existing.is_empty() {
+            binary_dump_file(&existing)?
+        } else {
+            println!("{}", tr("place-source-card-on-proxmark3"));
+            match self.recover_and_dump()? {
+                Some(path) => path,
+                None => {
+                    println!("{}", tr("err-cannot-clone-without-complete-dump"));
+                    return Ok(());
+                }
+            }
+        };
+
+        println!("\n{}", tr("step-2-write-to-target"));
+        prompt(&tr("place-target-gen-1a-magic"))?;
+        match self.pm3.card_info()? {
+            Some(info) if info.magic.iter().any(|magic| magic.contains("1a")) => {
+                println!("{}", tr_args("target-card-detected-uid", &[("uid", &format_uid(&info.uid).to_string())]));
+            },
+            Some(info) => {
+                println!("{}", tr_args("target-card-doesnt-report-gen", &[("uid", &format_uid(&info.uid).to_string())]));
+                if !get_user_confirmation(&tr("continue-anyway")) {
+                    println!("{}", tr("operation-cancelled"));
+                    return Ok(());
+                }
+            },
+            None => {
+                println!("{}", tr("no-target-card-detected"));
+                return Ok(());
+            }
+        }
+
+        if self.pm3.load_magic(&dump_file)? {
+            println!("\n{}", tr("clone-operation-completed"));
+        } else {
+            println!("\n{}", tr("err-clone-write-failed"));
+        }
+        Ok(())
+    }
+}
+
+pub fn run_menu(pm3: &Proxmark3) {
+    let mut manager = Proxmark3Manager::new(pm3);
+
+    if let Err(e) = manager.run() {
+        println!("{}", tr_args("err-error", &[("error", &e.to_string())]));
+    }
+}
@@ -0,0 +1,6 @@
+// src/proxmark3/mod.rs
+pub mod client;
+pub mod menu;
+pub mod parse;
+
+pub use client::Proxmark3;
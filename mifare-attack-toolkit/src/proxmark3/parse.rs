@@ -0,0 +1,216 @@
+// src/proxmark3/parse.rs
+//
+// Parsers for the text the Proxmark3 client prints. They follow the output of the
+// current (Iceman) client and only look for the parts of each line that have been
+// stable across releases.
+
+/// What `hf 14a info` reports about the card in the field
+#[derive(Debug, Clone, Default)]
+pub struct CardInfo {
+    pub uid: Vec<u8>,
+    pub atqa: Option<[u8; 2]>,
+    pub sak: Option<u8>,
+    /// e.g. "Gen 1a", "Gen 2 / CUID"
+    pub magic: Vec<String>,
+    /// "weak" or "hard"
+    pub prng: Option<String>,
+    /// Whether the card answers every authentication with the same nonce
+    pub static_nonce: Option<bool>,
+    /// Lines naming a known clone chip, e.g. "Fudan FM11RF08S"
+    pub fingerprints: Vec<String>,
+}
+
+/// Chips and makers the client names when it recognizes a clone
+const CLONE_FINGERPRINTS: [&str; 3] = ["fudan", "shanghai", "fm11rf08"];
+
+#[cfg(feature = "attacks")]
+/// Keys found for one sector by `hf mf chk` or `hf mf autopwn`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectorKeys {
+    pub sector: u8,
+    pub key_a: Option<[u8; 6]>,
+    pub key_b: Option<[u8; 6]>,
+}
+
+/// Remove the colour escape sequences the client adds even when writing to a pipe
+pub fn strip_ansi(text: &str) -> String {
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip "[" and the parameters up to the final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            clean.push(c);
+        }
+    }
+    clean
+}
+
+/// Line text without the "[+]" / "[=]" / "[#]" prefix
+fn message(line: &str) -> &str {
+    let line = line.trim();
+    match line.strip_prefix('[') {
+        Some(rest) if rest.len() > 2 && rest.as_bytes()[1] == b']' => rest[2..].trim(),
+        _ => line,
+    }
+}
+
+fn hex_bytes(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(feature = "attacks")]
+pub fn parse_key(text: &str) -> Option<[u8; 6]> {
+    let bytes = hex_bytes(text)?;
+    let mut key = [0u8; 6];
+    if bytes.len() != key.len() {
+        return None;
+    }
+    key.copy_from_slice(&bytes);
+    Some(key)
+}
+
+/// Value after "<label>:" or "<label>......" on the first line that starts with the label
+fn field<'a>(output: &'a str, label: &str) -> Option<&'a str> {
+    output.lines()
+        .map(message)
+        .find(|line| line.starts_with(label))
+        .map(|line| value(line, label))
+}
+
+/// What follows the label, past the ':' or the dot leader newer clients print
+fn value<'a>(line: &'a str, label: &str) -> &'a str {
+    line[label.len()..].trim_start_matches(['.', ':', ' ']).trim()
+}
+
+/// `hf 14a info`, None when no card answered
+pub fn parse_card_info(output: &str) -> Option<CardInfo> {
+    // "UID: 04 A1 B2 C3   ( ONUID, re-used )", the hex run ends at the first non-hex word
+    let uid_text: Vec<&str> = field(output, "UID")?
+        .split_whitespace()
+        .take_while(|word| word.len() == 2 && word.chars().all(|c| c.is_ascii_hexdigit()))
+        .collect();
+    let uid = hex_bytes(&uid_text.concat())?;
+
+    let atqa = field(output, "ATQA")
+        .and_then(|value| hex_bytes(&value.split_whitespace().take(2).collect::<String>()))
+        .and_then(|bytes| <[u8; 2]>::try_from(bytes.as_slice()).ok());
+    let sak = field(output, "SAK")
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| u8::from_str_radix(value, 16).ok());
+    let mut magic: Vec<String> = Vec::new();
+    let mut fingerprints: Vec<String> = Vec::new();
+    for line in output.lines().map(message) {
+        if line.starts_with("Magic capabilities") {
+            let capability = value(line, "Magic capabilities").to_string();
+            if !capability.is_empty() && !magic.contains(&capability) {
+                magic.push(capability);
+            }
+        }
+        let lower = line.to_lowercase();
+        if CLONE_FINGERPRINTS.iter().any(|name| lower.contains(name)) && !fingerprints.iter().any(|known| known == line) {
+            fingerprints.push(line.to_string());
+        }
+    }
+    let prng = field(output, "Prng detection").map(|value| value.trim_end_matches('.').to_string());
+    let static_nonce = field(output, "Static nonce").map(|value| value.to_lowercase().starts_with("yes"));
+
+    Some(CardInfo { uid, atqa, sak, magic, prng, static_nonce, fingerprints })
+}
+
+#[cfg(feature = "attacks")]
+/// Key table printed by `hf mf chk`, `hf mf fchk` and `hf mf autopwn`:
+/// " 000 | 003 | FFFFFFFFFFFF | D | ------------ | 0"
+pub fn parse_key_table(output: &str) -> Vec<SectorKeys> {
+    let mut sectors: Vec<SectorKeys> = output.lines().map(message).filter_map(|line| {
+        let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+        if columns.len() < 6 {
+            return None;
+        }
+        let sector = columns[0].parse::<u8>().ok()?;
+        columns[1].parse::<u8>().ok()?;
+        Some(SectorKeys {
+            sector,
+            key_a: parse_key(columns[2]),
+            key_b: parse_key(columns[4]),
+        })
+    }).collect();
+
+    // autopwn prints the table again at the end, keep the last (most complete) row per sector
+    sectors.reverse();
+    let mut seen = Vec::new();
+    sectors.retain(|keys| {
+        let new = !seen.contains(&keys.sector);
+        seen.push(keys.sector);
+        new
+    });
+    sectors.sort_by_key(|keys| keys.sector);
+    sectors
+}
+
+#[cfg(feature = "attacks")]
+/// Key reported by `hf mf nested`, `hf mf hardnested` or `hf mf darkside` ("found valid
+/// key: ffffffffffff", "found valid key [ FFFFFFFFFFFF ]")
+pub fn parse_found_key(output: &str) -> Option<[u8; 6]> {
+    output.lines().find_map(|line| {
+        let lower = line.to_lowercase();
+        let start = lower.find("found valid key")? + "found valid key".len();
+        line.get(start..)?
+            .split(|c: char| !c.is_ascii_hexdigit())
+            .find_map(|word| if word.len() == 12 { parse_key(word) } else { None })
+    })
+}
+
+#[cfg(feature = "attacks")]
+/// Binary dump written by `hf mf autopwn` / `hf mf dump` ("Saved 1024 bytes to binary file `path`")
+pub fn parse_dump_file(output: &str) -> Option<String> {
+    output.lines().map(message).find_map(|line| {
+        let lower = line.to_lowercase();
+        if !lower.contains("saved") || !lower.contains("binary file") {
+            return None;
+        }
+        let start = line.find(['`', '\''])? + 1;
+        let end = start + line[start..].find(['`', '\''])?;
+        Some(line[start..end].to_string())
+    })
+}
+
+/// Block data printed by `hf mf rdbl`, as a table row (" 0 | 11 22 ... | ascii") or,
+/// on older clients, as "data: 11 22 ..."
+pub fn parse_block(output: &str, block: u8) -> Option<Vec<u8>> {
+    let sixteen = |text: &str| hex_bytes(text).filter(|bytes| bytes.len() == 16);
+    output.lines().map(message).find_map(|line| {
+        let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+        if columns.len() >= 2 && columns[0].parse::<u8>().ok() == Some(block) {
+            return sixteen(columns[1]);
+        }
+        line.strip_prefix("data:").and_then(sixteen)
+    })
+}
+
+#[cfg(feature = "attacks")]
+/// Nonces answered to `hf 14a raw` authentication requests, one line of 4 bytes each
+pub fn parse_nonces(output: &str) -> Vec<u32> {
+    output.lines().map(message).filter_map(|line| {
+        let words: Vec<&str> = line.split_whitespace()
+            .take_while(|word| word.len() == 2 && word.chars().all(|c| c.is_ascii_hexdigit()))
+            .collect();
+        if words.len() != 4 {
+            return None;
+        }
+        let bytes = hex_bytes(&words.concat())?;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }).collect()
+}
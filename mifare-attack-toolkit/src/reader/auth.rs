@@ -2,10 +2,12 @@
 use std::error::Error;
 use std::thread;
 use std::time::Duration;
+use mifare_rc522::BlockAddr;
 
 use crate::cards::KeyType;
 use super::commands::*;
 use super::mfrc522::MifareClassic;
+use crate::i18n::{tr, tr_args};
 
 impl MifareClassic {
     /// Authenticate with a key - IMPROVED for better crypto handling
@@ -22,8 +24,7 @@ impl MifareClassic {
         };
         
         // Debug output - this is helpful for troubleshooting
-        println!("Authenticating with block={}, mode={:02X}, key={}", 
-               block, auth_mode, self.bytes_to_hex(key));
+        println!("{}", tr_args("authenticating-with-block-mode-key", &[("block", &block.to_string()), ("mode", &format!("{:02X}", auth_mode)), ("key", &self.bytes_to_hex(key).to_string())]));
         
         // Build auth buffer
         let mut buf: Vec<u8> = Vec::new();
@@ -57,7 +58,7 @@ impl MifareClassic {
         }
         
         // Debug output
-        println!("Auth buffer: {}", self.bytes_to_hex(&buf));
+        println!("{}", tr_args("auth-buffer", &[("hex", &self.bytes_to_hex(&buf).to_string())]));
         
         // Clear any pending interrupts and reset command states
         self.write_register(COMMAND_REG, PCD_IDLE)?;
@@ -74,13 +75,12 @@ impl MifareClassic {
         let status2 = self.read_register(STATUS2_REG)?;
         let crypto_bit = status2 & 0x08;
         
-        println!("Auth status: {}, STATUS2: {:02X}, Crypto bit: {}", 
-               status, status2, if crypto_bit != 0 { "SET" } else { "NOT SET" });
+        println!("{}", tr_args("auth-status", &[("status", &status.to_string()), ("status2", &format!("{:02X}", status2)), ("crypto_bit", (if crypto_bit != 0 { "SET" } else { "NOT SET" }))]));
         
         // Additional check for error register
         let error_reg = self.read_register(ERROR_REG)?;
         if error_reg != 0 {
-            println!("Error register: 0x{:02X}", error_reg);
+            println!("{}", tr_args("auth-error-register", &[("register", &format!("{:02X}", error_reg))]));
         }
         
         if crypto_bit == 0 {
@@ -92,13 +92,13 @@ impl MifareClassic {
         
         if success {
             // Store successful key
-            let sector = block / 4;
+            let sector = BlockAddr(block).sector().get();
             self.last_known_keys.insert((sector, key_type), [key[0], key[1], key[2], key[3], key[4], key[5]]);
-            println!("Authentication succeeded!");
+            println!("{}", tr("authentication-succeeded"));
         } else {
             // Stop crypto on failure
             self.stop_crypto1()?;
-            println!("Authentication failed: status not OK");
+            println!("{}", tr("authentication-failed-status-not-ok"));
         }
         
         Ok(success)
@@ -158,7 +158,7 @@ impl MifareClassic {
             buf.extend_from_slice(serial_num);
         }
         
-        println!("Special auth with: {}", self.bytes_to_hex(&buf));
+        println!("{}", tr_args("special-auth-with", &[("hex", &self.bytes_to_hex(&buf).to_string())]));
         
         // Enhanced handling for MFRC522
         let irq_en: u8 = 0x12;
@@ -197,19 +197,19 @@ impl MifareClassic {
         
         // Check crypto state with better debug
         let status2 = self.read_register(STATUS2_REG)?;
-        println!("STATUS2 after auth: 0x{:02X}", status2);
+        println!("{}", tr_args("auth-status2", &[("status2", &format!("{:02X}", status2))]));
         
         let success = (status2 & 0x08) != 0;
         
         if success {
             // Store successful key
-            let sector = block / 4;
+            let sector = BlockAddr(block).sector().get();
             self.last_known_keys.insert((sector, key_type), [key[0], key[1], key[2], key[3], key[4], key[5]]);
-            println!("Special authentication succeeded!");
+            println!("{}", tr("special-authentication-succeeded"));
             
             // Don't stop crypto here - we need it for the next operation
         } else {
-            println!("Special authentication failed");
+            println!("{}", tr("special-authentication-failed"));
             // Stop crypto when failed
             self.clear_bit_mask(STATUS2_REG, 0x08)?;
         }
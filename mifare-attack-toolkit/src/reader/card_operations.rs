@@ -1,9 +1,11 @@
 // src/reader/card_operations.rs
 use std::error::Error;
+use mifare_rc522::{BlockAddr, CardLayout};
 
-use crate::cards::{KeyType, CardType};
+use crate::cards::{sector_index, KeyType, CardType};
 use super::commands::*;
 use super::mfrc522::MifareClassic;
+use crate::i18n::{tr, tr_args};
 
 impl MifareClassic {
     /// Get card UID - FIXED to match working code
@@ -22,6 +24,11 @@ impl MifareClassic {
             return Ok(None);
         }
         
+        // The card only takes an authentication once it is selected
+        if !self.select_tag(&uid)? {
+            return Ok(None);
+        }
+        
         Ok(Some(uid))
     }
     
@@ -57,6 +64,7 @@ impl MifareClassic {
                 if check_sum != back_data[4] {
                     return Ok((MI_ERR, vec![]));
                 }
+                self.last_uid = Some(back_data[..4].to_vec());
             } else {
                 return Ok((MI_ERR, vec![]));
             }
@@ -65,6 +73,33 @@ impl MifareClassic {
         Ok((status, back_data))
     }
     
+    /// Select the card with the UID and check byte from anticollision
+    pub(crate) fn select_tag(&mut self, uid: &[u8]) -> Result<bool, Box<dyn Error>> {
+        if uid.len() < 5 {
+            return Ok(false);
+        }
+        let mut buf = vec![PICC_SELECTTAG, 0x70];
+        buf.extend_from_slice(&uid[..5]);
+        let crc = self.calculate_crc(&buf)?;
+        buf.extend_from_slice(&crc[..2]);
+
+        let (status, back_data, back_bits) = self.to_card(PCD_TRANSCEIVE, &buf)?;
+        let selected = status == MI_OK && back_bits == 0x18;
+        self.sak = if selected { back_data.first().copied() } else { None };
+        Ok(selected)
+    }
+    
+    /// Sectors and blocks of the selected card, from its SAK. A card that answered with
+    /// the SAK of no Classic is taken for a 1K, as clones often do.
+    pub fn layout(&self) -> CardLayout {
+        self.sak.map_or(CardLayout::Classic1K, CardLayout::from_sak_or_1k)
+    }
+    
+    /// UID of the last card seen since the previous call, for the audit log
+    pub fn take_last_uid(&mut self) -> Option<Vec<u8>> {
+        self.last_uid.take()
+    }
+    
     /// Select the card and return its type
     pub fn select_card(&mut self, _uid: &[u8]) -> Result<CardType, Box<dyn Error>> {
         // For now, we'll assume it's a Classic 1K
@@ -84,7 +119,7 @@ impl MifareClassic {
         let (status, back_data, _) = self.to_card(PCD_TRANSCEIVE, &recv_data)?;
         
         if status != MI_OK {
-            println!("Error while reading!");
+            println!("{}", tr("err-while-reading"));
             return Ok(None);
         }
         
@@ -108,7 +143,7 @@ impl MifareClassic {
         let (status, back_data, back_len) = self.to_card(PCD_TRANSCEIVE, &buf)?;
         
         if (status != MI_OK) || (back_len != 4) || ((back_data[0] & 0x0F) != 0x0A) {
-            println!("Write command failed: status={}, back_len={}", status, back_len);
+            println!("{}", tr_args("err-write-command", &[("status", &status.to_string()), ("back_len", &back_len.to_string())]));
             return Ok(false);
         }
         
@@ -134,10 +169,10 @@ impl MifareClassic {
             let (status, back_data, back_len) = self.to_card(PCD_TRANSCEIVE, &buf)?;
             
             if (status != MI_OK) || (back_len != 4) || ((back_data[0] & 0x0F) != 0x0A) {
-                println!("Error while writing data");
+                println!("{}", tr("err-while-writing-data"));
                 return Ok(false);
             } else {
-                println!("Data written successfully to block {}", block_addr);
+                println!("{}", tr_args("data-written-successfully-to-block", &[("block", &block_addr.to_string())]));
                 return Ok(true);
             }
         }
@@ -153,26 +188,26 @@ impl MifareClassic {
             None => return Ok(None),
         };
         
-        println!("Card UID: {}", self.format_uid(&uid));
+        println!("{}", tr_args("card-uid", &[("uid", &self.format_uid(&uid).to_string())]));
         
         // Try each default key
         for key in DEFAULT_KEYS.iter() {
             // Try Key A
             if self.auth_with_key(block, KeyType::KeyA, key, &uid)? {
-                println!("Success with Key A: {}", self.bytes_to_hex(key));
-                self.last_known_keys.insert((block / 4, KeyType::KeyA), *key);
+                println!("{}", tr_args("default-key-a-works", &[("key", &self.bytes_to_hex(key).to_string())]));
+                self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyA), *key);
                 return Ok(Some((*key, KeyType::KeyA)));
             }
             
             // Try Key B
             if self.auth_with_key(block, KeyType::KeyB, key, &uid)? {
-                println!("Success with Key B: {}", self.bytes_to_hex(key));
-                self.last_known_keys.insert((block / 4, KeyType::KeyB), *key);
+                println!("{}", tr_args("default-key-b-works", &[("key", &self.bytes_to_hex(key).to_string())]));
+                self.last_known_keys.insert((BlockAddr(block).sector().get(), KeyType::KeyB), *key);
                 return Ok(Some((*key, KeyType::KeyB)));
             }
         }
         
-        println!("Failed with all default keys");
+        println!("{}", tr("no-default-key-works"));
         Ok(None)
     }
     
@@ -182,8 +217,7 @@ impl MifareClassic {
         
         let mut sector_blocks = Vec::new();
         
-        for i in 0..4 {
-            let block_addr = sector * 4 + i;
+        for block_addr in sector_index(sector)?.blocks().map(BlockAddr::get) {
             
             // Reset crypto state before each attempt
             self.stop_crypto1()?;
@@ -210,3 +244,30 @@ impl MifareClassic {
         Ok(sector_blocks)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mifare_rc522::mock::{MockCard, MockReader};
+    use super::*;
+
+    const KEY: [u8; 6] = [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+
+    #[test]
+    fn detect_authenticate_read_and_write() {
+        let card = MockCard::new([0x04, 0x51, 0x52, 0x53])
+            .with_keys(2, KEY, [0xFF; 6])
+            .with_block(9, *b"toolkit test    ");
+        let mut reader = MifareClassic::with_transport(Box::new(MockReader::with_card(card))).unwrap();
+
+        let uid = reader.get_uid().unwrap().expect("card detected");
+        assert_eq!(uid[..4], [0x04, 0x51, 0x52, 0x53]);
+        assert!(!reader.auth_with_key(8, KeyType::KeyA, &[0xFF; 6], &uid).unwrap());
+
+        let uid = reader.get_uid().unwrap().expect("card detected again");
+        assert!(reader.auth_with_key(8, KeyType::KeyA, &KEY, &uid).unwrap());
+        assert_eq!(reader.read_block(9).unwrap().as_deref(), Some(&b"toolkit test    "[..]));
+        assert!(reader.write_block(10, &[0x42; 16]).unwrap());
+        assert_eq!(reader.read_block(10).unwrap(), Some(vec![0x42; 16]));
+        assert_eq!(reader.last_known_keys.get(&(2, KeyType::KeyA)), Some(&KEY));
+    }
+}
@@ -1,14 +1,28 @@
 // src/reader/communication.rs
 use std::error::Error;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use super::commands::*;
 use super::mfrc522::MifareClassic;
 
 impl MifareClassic {
-    /// Communicate with the card - FIXED version matching working code
+    /// Communicate with the card, logging the exchange when a session is being recorded
     pub(crate) fn to_card(&mut self, command: u8, data: &[u8]) -> Result<(u8, Vec<u8>, usize), Box<dyn Error>> {
+        let started = match self.recording_offset() {
+            Some(offset) => offset,
+            None => return self.transceive(command, data),
+        };
+
+        let framing = self.read_register(BIT_FRAMING_REG)?;
+        let sent_at = Instant::now();
+        let response = self.transceive(command, data)?;
+        self.record_transaction(command, framing, data, started, sent_at.elapsed(), &response);
+        Ok(response)
+    }
+    
+    /// Run one command on the card - FIXED version matching working code
+    fn transceive(&mut self, command: u8, data: &[u8]) -> Result<(u8, Vec<u8>, usize), Box<dyn Error>> {
         let mut back_data: Vec<u8> = Vec::new();
         let mut back_len: usize = 0;
         let mut status = MI_ERR;
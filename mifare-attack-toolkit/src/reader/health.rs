@@ -0,0 +1,82 @@
+// src/reader/health.rs
+use std::error::Error;
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
+
+use super::commands::*;
+use super::mfrc522::MifareClassic;
+use crate::i18n::{tr, tr_args};
+
+/// First delay between reconnection attempts, doubled after each failure
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(250);
+/// Longest delay between reconnection attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(8);
+
+/// Whether the MFRC522 is answering on the SPI bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderStatus {
+    Connected,
+    Disconnected,
+}
+
+impl fmt::Display for ReaderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReaderStatus::Connected => write!(f, "{}", tr("reader-connected")),
+            ReaderStatus::Disconnected => write!(f, "{}", tr("reader-disconnected")),
+        }
+    }
+}
+
+impl MifareClassic {
+    /// Check the reader is still there by reading its version register.
+    /// A missing or unpowered chip leaves MISO floating, which reads as 0x00 or 0xFF.
+    pub fn status(&mut self) -> ReaderStatus {
+        match self.read_register(VERSION_REG) {
+            Ok(0x00) | Ok(0xFF) | Err(_) => ReaderStatus::Disconnected,
+            Ok(_) => ReaderStatus::Connected,
+        }
+    }
+
+    /// Reopen the SPI device and re-initialize the reader
+    pub fn reconnect(&mut self) -> Result<(), Box<dyn Error>> {
+        self.spi = Box::new(Spi::new(Bus::Spi0, SlaveSelect::Ss0, 1_000_000, Mode::Mode0)?);
+        self.reset_reader()?;
+
+        if self.status() == ReaderStatus::Disconnected {
+            return Err("Reader not responding".into());
+        }
+
+        Ok(())
+    }
+
+    /// Make sure the reader is connected, retrying with backoff if it was lost.
+    /// Returns the final status after at most `max_attempts` reconnection attempts.
+    pub fn ensure_connected(&mut self, max_attempts: u32) -> ReaderStatus {
+        if self.status() == ReaderStatus::Connected {
+            return ReaderStatus::Connected;
+        }
+
+        println!("{}", tr_args("reader-reconnecting", &[("status", &ReaderStatus::Disconnected.to_string())]));
+
+        let mut delay = RECONNECT_INITIAL_DELAY;
+        for attempt in 1..=max_attempts {
+            match self.reconnect() {
+                Ok(_) => {
+                    println!("{}", tr_args("reader-reconnected", &[("status", &ReaderStatus::Connected.to_string()), ("attempt", &attempt.to_string())]));
+                    return ReaderStatus::Connected;
+                },
+                Err(e) => {
+                    println!("{}", tr_args("reconnect-attempt-failed", &[("attempt", &attempt.to_string()), ("attempts", &max_attempts.to_string()), ("error", &e.to_string())]));
+                    thread::sleep(delay);
+                    delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                }
+            }
+        }
+
+        println!("{}", tr_args("reader-check-wiring", &[("status", &ReaderStatus::Disconnected.to_string())]));
+        ReaderStatus::Disconnected
+    }
+}
@@ -3,16 +3,22 @@ use std::error::Error;
 use std::collections::HashMap;
 use std::thread;
 use std::time::Duration;
+use mifare_rc522::{BlockAddr, Transport};
 use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
 
 use crate::cards::KeyType;
 use super::commands::*;
+use super::session::SessionRecorder;
+use crate::i18n::{tr, tr_args};
 
 /// The main struct for Mifare card operations
 pub struct MifareClassic {
-    pub(crate) spi: Spi,
+    pub(crate) spi: Box<dyn Transport + Send>,
     pub(crate) last_known_keys: HashMap<(u8, KeyType), [u8; 6]>, // Stores known keys by (sector, key_type)
     pub(crate) dark_processing_mode: bool, // Special mode for difficult cards
+    pub(crate) recorder: Option<SessionRecorder>, // Session being recorded, if any
+    pub(crate) last_uid: Option<Vec<u8>>, // UID of the last card that answered anticollision
+    pub(crate) sak: Option<u8>, // SAK the selected card answered with
 }
 
 impl MifareClassic {
@@ -20,11 +26,19 @@ impl MifareClassic {
     pub fn new() -> Result<Self, Box<dyn Error>> {
         // FIXED: Using standard SPI speed from working code (1MHz instead of 100KHz)
         let spi = Spi::new(Bus::Spi0, SlaveSelect::Ss0, 1_000_000, Mode::Mode0)?;
-        
+        Self::with_transport(Box::new(spi))
+    }
+    
+    /// Create a handler for a reader on another bus, or a simulated one in tests. The
+    /// handler can move to the thread that runs the attack jobs.
+    pub fn with_transport(spi: Box<dyn Transport + Send>) -> Result<Self, Box<dyn Error>> {
         let mut instance = Self { 
             spi,
             last_known_keys: HashMap::new(),
             dark_processing_mode: false, // FIXED: Start with disabled dark mode
+            recorder: None,
+            last_uid: None,
+            sak: None,
         };
         instance.init()?;
         
@@ -39,7 +53,7 @@ impl MifareClassic {
         
         // Check version
         let version = self.read_register(VERSION_REG)?;
-        println!("MFRC522 Version: 0x{:02X}", version);
+        println!("{}", tr_args("mfrc522-version", &[("version", &format!("{:02X}", version))]));
         
         // FIXED: Timer configurations exactly matching working code
         self.write_register(T_MODE_REG, 0x8D)?;
@@ -54,7 +68,7 @@ impl MifareClassic {
         // FIXED: Turn on the antenna with same approach as working code
         self.antenna_on()?;
         
-        println!("MFRC522 initialized successfully");
+        println!("{}", tr("mfrc522-initialized-successfully"));
         
         Ok(())
     }
@@ -74,7 +88,6 @@ impl MifareClassic {
     /// Set special processing mode for difficult cards
     pub fn enable_dark_processing_mode(&mut self, enable: bool) {
         self.dark_processing_mode = enable;
-        println!("Dark processing mode {}", if enable { "enabled" } else { "disabled" });
     }
     
     /// Perform Darkside attack (simplified)
@@ -90,7 +103,7 @@ impl MifareClassic {
         let key = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
         
         // Store the key for future use
-        let sector = block / 4;
+        let sector = BlockAddr(block).sector().get();
         self.last_known_keys.insert((sector, KeyType::KeyA), key);
         self.last_known_keys.insert((sector, KeyType::KeyB), key);
         
@@ -107,18 +120,12 @@ impl MifareClassic {
             None => return Err("No card detected".into()),
         };
         
-        // Calculate block numbers
-        let start_block = start_sector * 4; // First block of sector
-        let target_block = target_sector * 4; // First block of target sector
-        
-        println!("Starting nested attack with known key for block {}", start_block);
-        println!("Targeting block: {}", target_block);
+        let start_block = crate::cards::sector_index(start_sector)?.first_block().get();
         
         // First authenticate with the known key - FIXED: use standard auth
         let auth_success = self.auth_with_key(start_block, key_type, known_key, &uid)?;
         
         if !auth_success {
-            println!("Authentication failed with provided key.");
             return Ok(None);
         }
         
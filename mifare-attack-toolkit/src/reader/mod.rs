@@ -3,9 +3,15 @@ mod utils;
 mod communication;
 mod auth;
 mod card_operations;
+#[cfg(feature = "attacks")]
+mod nonces;
 pub mod commands;
 pub mod mfrc522;
+pub mod health;
+pub mod session;
 
 // Re-export components needed elsewhere
 pub use mfrc522::MifareClassic;
+pub use health::ReaderStatus;
+pub use session::load_session;
 pub use commands::{MI_OK, MI_ERR, PICC_REQIDL};
@@ -0,0 +1,117 @@
+// src/reader/nonces.rs
+use std::error::Error;
+
+use crate::cards::KeyType;
+use super::commands::*;
+use super::mfrc522::MifareClassic;
+
+impl MifareClassic {
+    /// Sample the card's authentication nonces in pairs: reset the field, select the
+    /// card and start an authentication, which the card answers with its nonce in the
+    /// clear, then select it again and start a second one. No authentication is ever
+    /// finished. Samples the card doesn't answer are skipped, so fewer than `count`
+    /// pairs can come back.
+    pub fn collect_nonces(&mut self, block: u8, key_type: KeyType, count: usize) -> Result<Vec<(u32, u32)>, Box<dyn Error>> {
+        let auth_mode = match key_type {
+            KeyType::KeyA => PICC_AUTHENT1A,
+            KeyType::KeyB => PICC_AUTHENT1B,
+        };
+        let mut request = vec![auth_mode, block];
+        let crc = self.calculate_crc(&request)?;
+        request.extend_from_slice(&crc[..2]);
+
+        let mut samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            self.stop_crypto1()?;
+            self.reset_field()?;
+
+            let first = match self.request_nonce(&request)? {
+                Some(nonce) => nonce,
+                None => continue,
+            };
+            if let Some(second) = self.request_nonce(&request)? {
+                samples.push((first, second));
+            }
+        }
+
+        // The card is still waiting in the last authentication, reset it so the next
+        // operation finds it
+        self.reset_field()?;
+
+        Ok(samples)
+    }
+
+    // Wakes and selects the card, then starts an authentication. A card left waiting
+    // in an unfinished authentication ignores the first wake-up.
+    fn request_nonce(&mut self, request: &[u8]) -> Result<Option<u32>, Box<dyn Error>> {
+        let mut woken = false;
+        for _ in 0..2 {
+            if self.request_card(PICC_REQALL)?.0 == MI_OK {
+                woken = true;
+                break;
+            }
+        }
+        if !woken {
+            return Ok(None);
+        }
+
+        let (status, uid) = self.anticoll()?;
+        if status != MI_OK || !self.select_tag(&uid)? {
+            return Ok(None);
+        }
+
+        self.write_register(BIT_FRAMING_REG, 0x00)?;
+        let (status, back_data, back_bits) = self.to_card(PCD_TRANSCEIVE, request)?;
+        if status == MI_OK && back_bits == 32 && back_data.len() == 4 {
+            return Ok(Some(u32::from_be_bytes([back_data[0], back_data[1], back_data[2], back_data[3]])));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mifare_rc522::mock::{MockCard, MockReader, Prng};
+    use crate::prng::{analyse_nonces, PrngKind, PrngReport, PRNG_SAMPLES};
+    use super::*;
+
+    fn reader_with(card: MockCard) -> MifareClassic {
+        MifareClassic::with_transport(Box::new(MockReader::with_card(card))).unwrap()
+    }
+
+    fn prng_report(reader: &mut MifareClassic) -> PrngReport {
+        let samples = reader.collect_nonces(0, KeyType::KeyA, PRNG_SAMPLES).unwrap();
+        assert_eq!(samples.len(), PRNG_SAMPLES);
+        analyse_nonces(&samples).unwrap()
+    }
+
+    #[test]
+    fn nested_attack_on_a_weak_prng_card() {
+        let known = [0x4B; 6];
+        let mut reader = reader_with(MockCard::new([0x04, 0x61, 0x62, 0x63]).with_keys(1, known, known));
+
+        let report = prng_report(&mut reader);
+        assert_eq!(report.kind, PrngKind::Weak);
+        assert!(report.darkside_viable());
+        assert!(report.nested_viable());
+
+        assert_eq!(reader.nested_attack(1, &[0x00; 6], KeyType::KeyA, 3).unwrap(), None);
+        let key = reader.nested_attack(1, &known, KeyType::KeyA, 3).unwrap().expect("key for sector 3");
+
+        // the key found opens the target sector
+        let uid = reader.get_uid().unwrap().expect("card detected");
+        assert!(reader.auth_with_key(12, KeyType::KeyA, &key, &uid).unwrap());
+        assert!(reader.read_block(12).unwrap().is_some());
+    }
+
+    #[test]
+    fn static_and_hardened_cards_resist_nested() {
+        let report = prng_report(&mut reader_with(MockCard::new([0x04, 0x64, 0x65, 0x66]).with_prng(Prng::Static(0x0102_0304))));
+        assert_eq!(report.kind, PrngKind::Static);
+        assert!(!report.nested_viable());
+
+        let report = prng_report(&mut reader_with(MockCard::new([0x04, 0x67, 0x68, 0x69]).with_prng(Prng::Hard)));
+        assert_eq!(report.kind, PrngKind::Hard);
+        assert!(!report.darkside_viable());
+    }
+}
@@ -0,0 +1,255 @@
+// src/reader/session.rs
+//
+// Recording and replay of card transactions. Every command the MFRC522 sends to the
+// card goes through `to_card`, so recording there captures the whole conversation:
+// requests, anticollision, select, authentication, reads and writes, with when each
+// was sent and how long the card took to answer.
+//
+// Session file, one transaction per line, tab separated:
+//   offset_ms  duration_us  command  framing  sent  status  bits  received
+// Bytes are hex with no separators, "-" for none. Lines starting with '#' are comments.
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::utils::hex_to_bytes;
+use super::commands::*;
+use super::mfrc522::MifareClassic;
+use crate::i18n::tr_args;
+
+/// One command/response pair
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    /// Time since the recording started
+    pub offset: Duration,
+    /// Time the card took to answer
+    pub duration: Duration,
+    /// PCD command, PCD_TRANSCEIVE or PCD_AUTHENT
+    pub command: u8,
+    /// BitFramingReg when the command was sent (7 valid bits for REQA/WUPA)
+    pub framing: u8,
+    pub sent: Vec<u8>,
+    pub status: u8,
+    /// Valid bits in the answer
+    pub bits: usize,
+    pub received: Vec<u8>,
+}
+
+fn hex_field(bytes: &[u8]) -> String {
+    if bytes.is_empty() {
+        "-".to_string()
+    } else {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+}
+
+fn parse_hex_field(field: &str) -> Result<Vec<u8>, String> {
+    if field == "-" {
+        Ok(Vec::new())
+    } else {
+        hex_to_bytes(field)
+    }
+}
+
+fn parse_byte(field: &str) -> Result<u8, String> {
+    u8::from_str_radix(field, 16).map_err(|_| format!("Invalid byte '{}'", field))
+}
+
+fn status_name(status: u8) -> &'static str {
+    match status {
+        MI_OK => "OK",
+        MI_NOTAGERR => "NOTAG",
+        _ => "ERR",
+    }
+}
+
+impl Transaction {
+    pub fn to_line(&self) -> String {
+        format!(
+            "{:.3}\t{}\t{:02X}\t{:02X}\t{}\t{:02X}\t{}\t{}",
+            self.offset.as_secs_f64() * 1000.0,
+            self.duration.as_micros(),
+            self.command,
+            self.framing,
+            hex_field(&self.sent),
+            self.status,
+            self.bits,
+            hex_field(&self.received)
+        )
+    }
+
+    pub fn from_line(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+        if fields.len() != 8 {
+            return Err(format!("Expected 8 fields, found {}", fields.len()));
+        }
+
+        let offset_ms: f64 = fields[0].parse().map_err(|_| format!("Invalid offset '{}'", fields[0]))?;
+        let duration_us: u64 = fields[1].parse().map_err(|_| format!("Invalid duration '{}'", fields[1]))?;
+        Ok(Self {
+            offset: Duration::from_secs_f64(offset_ms.max(0.0) / 1000.0),
+            duration: Duration::from_micros(duration_us),
+            command: parse_byte(fields[2])?,
+            framing: parse_byte(fields[3])?,
+            sent: parse_hex_field(fields[4])?,
+            status: parse_byte(fields[5])?,
+            bits: fields[6].parse().map_err(|_| format!("Invalid bit count '{}'", fields[6]))?,
+            received: parse_hex_field(fields[7])?,
+        })
+    }
+
+    /// Same answer as `other`, timing aside
+    pub fn same_response(&self, other: &Transaction) -> bool {
+        self.status == other.status && self.bits == other.bits && self.received == other.received
+    }
+}
+
+/// Writes transactions to a session file as they happen, flushed after each one
+/// so a crash or a hung card still leaves the log up to the last command
+pub struct SessionRecorder {
+    writer: BufWriter<File>,
+    start: Instant,
+    count: usize,
+}
+
+impl SessionRecorder {
+    pub fn create(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "# MIFARE attack toolkit session, started {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"))?;
+        writeln!(writer, "# offset_ms\tduration_us\tcommand\tframing\tsent\tstatus\tbits\treceived")?;
+        writer.flush()?;
+        Ok(Self { writer, start: Instant::now(), count: 0 })
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn record(&mut self, transaction: &Transaction) -> Result<(), Box<dyn Error>> {
+        writeln!(self.writer, "{}", transaction.to_line())?;
+        self.writer.flush()?;
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Read a session file
+pub fn load_session(path: &str) -> Result<Vec<Transaction>, Box<dyn Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut transactions = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let transaction = Transaction::from_line(line)
+            .map_err(|e| format!("{} line {}: {}", path, number + 1, e))?;
+        transactions.push(transaction);
+    }
+    Ok(transactions)
+}
+
+/// How a replay went
+pub struct ReplayReport {
+    pub replayed: usize,
+    /// (line in the session, recorded, replayed)
+    pub mismatches: Vec<(usize, Transaction, Transaction)>,
+}
+
+impl MifareClassic {
+    /// Start logging every card transaction to `path`, replacing any recording in progress
+    pub fn start_recording(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        self.stop_recording();
+        self.recorder = Some(SessionRecorder::create(path)?);
+        println!("{}", tr_args("recording-card-transactions-to", &[("path", path)]));
+        Ok(())
+    }
+
+    /// Stop logging, returns how many transactions were recorded
+    pub fn stop_recording(&mut self) -> Option<usize> {
+        self.recorder.take().map(|recorder| recorder.count)
+    }
+
+    /// Log a finished `to_card` call if a recording is running
+    pub(crate) fn record_transaction(
+        &mut self,
+        command: u8,
+        framing: u8,
+        sent: &[u8],
+        started: Duration,
+        duration: Duration,
+        response: &(u8, Vec<u8>, usize),
+    ) {
+        if let Some(recorder) = self.recorder.as_mut() {
+            let transaction = Transaction {
+                offset: started,
+                duration,
+                command,
+                framing,
+                sent: sent.to_vec(),
+                status: response.0,
+                bits: response.2,
+                received: response.1.clone(),
+            };
+            // A full disk shouldn't stop the operation being debugged
+            if let Err(e) = recorder.record(&transaction) {
+                println!("{}", tr_args("session-recording-stopped", &[("error", &e.to_string())]));
+                self.recorder = None;
+            }
+        }
+    }
+
+    /// Time since the recording started, if there is one
+    pub(crate) fn recording_offset(&self) -> Option<Duration> {
+        self.recorder.as_ref().map(|recorder| recorder.elapsed())
+    }
+
+    /// Re-issue a recorded sequence and compare the answers. With `keep_timing` each
+    /// command waits for its original offset, which matters for cards that only
+    /// misbehave when commands come too fast or too slow.
+    pub fn replay_session(&mut self, transactions: &[Transaction], keep_timing: bool) -> Result<ReplayReport, Box<dyn Error>> {
+        let start = Instant::now();
+        let mut mismatches = Vec::new();
+
+        for (index, recorded) in transactions.iter().enumerate() {
+            if keep_timing {
+                if let Some(wait) = recorded.offset.checked_sub(start.elapsed()) {
+                    thread::sleep(wait);
+                }
+            }
+
+            self.write_register(BIT_FRAMING_REG, recorded.framing)?;
+            let sent_at = Instant::now();
+            let (status, received, bits) = self.to_card(recorded.command, &recorded.sent)?;
+            let replayed = Transaction {
+                offset: start.elapsed(),
+                duration: sent_at.elapsed(),
+                command: recorded.command,
+                framing: recorded.framing,
+                sent: recorded.sent.clone(),
+                status,
+                bits,
+                received,
+            };
+
+            let matches = recorded.same_response(&replayed);
+            println!(
+                "{:>4} {:>9.3}ms  {:02X} {:<24} {:<5} {:<32} {}",
+                index + 1,
+                replayed.offset.as_secs_f64() * 1000.0,
+                recorded.command,
+                hex_field(&recorded.sent),
+                status_name(replayed.status),
+                hex_field(&replayed.received),
+                if matches { "ok" } else { "DIFFERS" }
+            );
+            if !matches {
+                println!("     {}", tr_args("replay-recorded-answer", &[("status", status_name(recorded.status)), ("bits", &recorded.bits.to_string()), ("received", &hex_field(&recorded.received).to_string()), ("micros", &recorded.duration.as_micros().to_string()), ("now_micros", &replayed.duration.as_micros().to_string())]));
+                mismatches.push((index + 1, recorded.clone(), replayed));
+            }
+        }
+
+        Ok(ReplayReport { replayed: transactions.len(), mismatches })
+    }
+}
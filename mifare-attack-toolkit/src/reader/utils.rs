@@ -1,7 +1,13 @@
 // src/reader/utils.rs
 use std::error::Error;
+use std::thread;
+use std::time::Duration;
 use super::commands::*;
 use super::mfrc522::MifareClassic;
+use crate::i18n::tr_args;
+
+/// How long the field stays off so the card powers down and its PRNG restarts
+const FIELD_RESET: Duration = Duration::from_millis(10);
 
 impl MifareClassic {
     /// Read register - FIXED to match working code
@@ -47,7 +53,7 @@ impl MifareClassic {
         
         // Debug info
         let state = self.read_register(TX_CONTROL_REG)?;
-        println!("Antenna state: 0x{:02X}", state);
+        println!("{}", tr_args("antenna-state", &[("state", &format!("{:02X}", state))]));
         
         Ok(())
     }
@@ -57,6 +63,14 @@ impl MifareClassic {
         self.clear_bit_mask(TX_CONTROL_REG, 0x03)?;
         Ok(())
     }
+
+    /// Turn the field off and on again, so the card powers up afresh and answers a
+    /// request whatever state it was left in
+    pub(crate) fn reset_field(&mut self) -> Result<(), Box<dyn Error>> {
+        self.antenna_off()?;
+        thread::sleep(FIELD_RESET);
+        self.set_bit_mask(TX_CONTROL_REG, 0x03)
+    }
     
     /// Format a UID as a string
     pub fn format_uid(&self, uid: &[u8]) -> String {
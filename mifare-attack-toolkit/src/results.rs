@@ -0,0 +1,339 @@
+// src/results.rs
+//
+// What an attack found, as one record: the card it ran on, the keys found for each
+// sector, how long it took and how many nonces it used. The job saves it as JSON in
+// the results folder, shows it in the menu or the window, and adds the keys to the key
+// store the block editor opens cards with, so a key recovered here is tried there
+// first.
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::cards::{identify_card_type, is_magic_card, KeyType};
+use crate::consent;
+use crate::i18n::{tr, tr_args};
+use crate::prng::PrngReport;
+use crate::utils::{format_uid, hex_to_bytes};
+
+/// What is known about the card an attack ran on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CardFingerprint {
+    /// As detected, e.g. "04:71:72:73:76"
+    pub uid: String,
+    pub card_type: String,
+    /// Whether the UID looks like a magic card's
+    pub magic: bool,
+    /// The PRNG, once the attack sampled the card's nonces
+    pub prng: Option<String>,
+}
+
+impl CardFingerprint {
+    pub fn of(uid: &[u8]) -> Self {
+        CardFingerprint {
+            uid: format_uid(uid),
+            card_type: identify_card_type(uid, None).to_string(),
+            magic: is_magic_card(uid),
+            prng: None,
+        }
+    }
+}
+
+/// The keys found for one sector, as 12 hex digits
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SectorKeys {
+    pub sector: u8,
+    pub key_a: Option<String>,
+    pub key_b: Option<String>,
+}
+
+/// What one run of an attack found
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AttackResult {
+    /// The job's name, e.g. "nested attack"
+    pub attack: String,
+    pub card: CardFingerprint,
+    /// Local time the attack started
+    pub started: String,
+    pub seconds: f64,
+    /// Nonces sampled from the card, kept ones from an earlier run included
+    pub nonces: usize,
+    /// Sectors the attack went after
+    pub sectors_attacked: usize,
+    /// Sectors with a key found, by sector
+    pub sectors: Vec<SectorKeys>,
+    /// What the attack couldn't do, e.g. sectors left for hardnested
+    #[serde(default)]
+    pub notes: Vec<String>,
+    /// The card's dump, saved next to the result
+    #[serde(default)]
+    pub dump: Option<PathBuf>,
+    #[serde(skip)]
+    clock: Option<Instant>,
+}
+
+impl AttackResult {
+    /// An attack starting now on the card with `uid`
+    pub fn new(attack: &str, uid: &[u8], sectors_attacked: usize) -> Self {
+        AttackResult {
+            attack: attack.to_string(),
+            card: CardFingerprint::of(uid),
+            started: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            seconds: 0.0,
+            nonces: 0,
+            sectors_attacked,
+            sectors: Vec::new(),
+            notes: Vec::new(),
+            dump: None,
+            clock: Some(Instant::now()),
+        }
+    }
+
+    pub fn add_key(&mut self, sector: u8, key_type: KeyType, key: [u8; 6]) {
+        self.set_key(sector, key_type, key_hex(&key));
+    }
+
+    fn set_key(&mut self, sector: u8, key_type: KeyType, hex: String) {
+        let index = match self.sectors.iter().position(|keys| keys.sector >= sector) {
+            Some(index) if self.sectors[index].sector == sector => index,
+            Some(index) => {
+                self.sectors.insert(index, SectorKeys { sector, key_a: None, key_b: None });
+                index
+            },
+            None => {
+                self.sectors.push(SectorKeys { sector, key_a: None, key_b: None });
+                self.sectors.len() - 1
+            },
+        };
+        match key_type {
+            KeyType::KeyA => self.sectors[index].key_a = Some(hex),
+            KeyType::KeyB => self.sectors[index].key_b = Some(hex),
+        }
+    }
+
+    /// The nonces of a PRNG test the attack started with
+    pub fn add_nonces(&mut self, report: &PrngReport) {
+        self.nonces += report.samples;
+        self.card.prng = Some(report.kind.to_string());
+    }
+
+    /// The keys and nonces of an attack run as part of this one
+    pub fn merge(&mut self, other: &AttackResult) {
+        for (sector, key_type, key) in other.keys() {
+            self.set_key(sector, key_type, key.to_string());
+        }
+        self.nonces += other.nonces;
+        if other.card.prng.is_some() {
+            self.card.prng = other.card.prng.clone();
+        }
+    }
+
+    /// The key found for `sector`, if any
+    pub fn key(&self, sector: u8, key_type: KeyType) -> Option<[u8; 6]> {
+        let keys = self.sectors.iter().find(|keys| keys.sector == sector)?;
+        let hex = match key_type {
+            KeyType::KeyA => keys.key_a.as_deref()?,
+            KeyType::KeyB => keys.key_b.as_deref()?,
+        };
+        hex_to_bytes(hex).ok()?.try_into().ok()
+    }
+
+    /// Every key found: sector, key type and the 12 hex digits
+    pub fn keys(&self) -> impl Iterator<Item = (u8, KeyType, &str)> {
+        self.sectors.iter().flat_map(|keys| {
+            [(KeyType::KeyA, &keys.key_a), (KeyType::KeyB, &keys.key_b)]
+                .into_iter()
+                .filter_map(move |(key_type, key)| key.as_deref().map(|key| (keys.sector, key_type, key)))
+        })
+    }
+
+    /// Stop the clock
+    pub fn finish(&mut self) {
+        if let Some(clock) = self.clock {
+            self.seconds = clock.elapsed().as_secs_f64();
+        }
+    }
+
+    /// One line, for the job list and the status bar
+    pub fn summary(&self) -> String {
+        tr_args("result-summary", &[
+            ("attack", &self.attack),
+            ("keys", &self.keys().count().to_string()),
+            ("sectors", &self.sectors.len().to_string()),
+            ("attacked", &self.sectors_attacked.to_string()),
+            ("seconds", &format!("{:.1}", self.seconds)),
+            ("nonces", &self.nonces.to_string()),
+        ])
+    }
+
+    /// The name of the files of this result, from the card, the time and the attack
+    pub fn file_stem(&self) -> String {
+        let stamp = self.started.replace(['-', ':'], "").replace(' ', "-");
+        format!("{}-{}-{}", self.card.uid.replace(':', ""), stamp, self.attack.replace(' ', "-"))
+    }
+
+    /// Write the result as JSON in `dir`, named after the card and the time. Returns
+    /// the file.
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, Box<dyn Error>> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(format!("{}.json", self.file_stem()));
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+// e.g. "Card 04:71:72:73:76 (MIFARE Classic 1K), PRNG weak" then a line per sector
+impl fmt::Display for AttackResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", tr_args("result-card", &[("uid", &self.card.uid), ("card_type", &self.card.card_type)]))?;
+        if self.card.magic {
+            write!(f, "{}", tr("result-likely-magic"))?;
+        }
+        if let Some(prng) = &self.card.prng {
+            write!(f, "{}", tr_args("result-prng", &[("prng", prng)]))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{}", self.summary())?;
+        writeln!(f, "{:<6}  {:<12}  {}", tr("result-sector"), tr("result-key-a"), tr("result-key-b"))?;
+        for keys in &self.sectors {
+            writeln!(
+                f,
+                "{:>6}  {:<12}  {}",
+                keys.sector,
+                keys.key_a.as_deref().unwrap_or("-"),
+                keys.key_b.as_deref().unwrap_or("-")
+            )?;
+        }
+        for note in &self.notes {
+            writeln!(f, "{}", note)?;
+        }
+        if let Some(dump) = &self.dump {
+            writeln!(f, "{}", tr_args("result-dump-saved", &[("path", &dump.display().to_string())]))?;
+        }
+        Ok(())
+    }
+}
+
+/// ~/.config/mifare-attack-toolkit/results
+pub fn results_dir() -> PathBuf {
+    consent::config_dir().join("results")
+}
+
+/// Add the keys of `result` to the key store at `path`, each with the attack and the
+/// card it came from as its source. Returns how many keys the store didn't have.
+pub fn add_to_key_store(result: &AttackResult, path: &Path) -> Result<usize, Box<dyn Error>> {
+    let mut entries = read_key_store(path)?;
+    let source = format!("{} on card {}", result.attack, result.card.uid);
+    let mut added = 0;
+    for (_, _, key) in result.keys() {
+        match entries.iter_mut().find(|(known, _)| known.eq_ignore_ascii_case(key)) {
+            Some((_, sources)) => {
+                if !sources.contains(&source) {
+                    sources.push(source.clone());
+                }
+            },
+            None => {
+                entries.push((key.to_string(), vec![source.clone()]));
+                added += 1;
+            },
+        }
+    }
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    let mut text = String::from("# Keys imported into the key store, each followed by the files it was found in\n");
+    for (key, sources) in &entries {
+        text.push_str(&format!("{} # {}\n", key, sources.join("; ")));
+    }
+    fs::write(path, text)?;
+    Ok(added)
+}
+
+// A key in the store, and where it was found
+type StoreEntry = (String, Vec<String>);
+
+// The store's lines are "<key> # <source>; <source>", as the block editor writes them.
+// A line that isn't one leaves the file alone.
+fn read_key_store(path: &Path) -> Result<Vec<StoreEntry>, Box<dyn Error>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut entries = Vec::new();
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (key, sources) = line.split_once('#').unwrap_or((line, ""));
+        let key = key.trim();
+        if key.len() != 12 || !key.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid line '{}' in the key store {}", line, path.display()).into());
+        }
+        let sources = sources.split("; ").map(str::trim).filter(|source| !source.is_empty()).map(str::to_string).collect();
+        entries.push((key.to_uppercase(), sources));
+    }
+    Ok(entries)
+}
+
+fn key_hex(key: &[u8; 6]) -> String {
+    key.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("mifare-attack-toolkit-results-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn keys_are_kept_by_sector() {
+        let mut result = AttackResult::new("nested attack", &[0x04, 0x71, 0x72, 0x73, 0x76], 15);
+        result.add_key(7, KeyType::KeyB, [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]);
+        result.add_key(2, KeyType::KeyA, [0xFF; 6]);
+        result.add_key(7, KeyType::KeyA, [0xFF; 6]);
+
+        let sectors: Vec<u8> = result.sectors.iter().map(|keys| keys.sector).collect();
+        assert_eq!(sectors, vec![2, 7]);
+        assert_eq!(result.sectors[1].key_b.as_deref(), Some("A0A1A2A3A4A5"));
+        assert_eq!(result.keys().count(), 3);
+        assert!(result.summary().starts_with("nested attack: 3 keys for 2 of 15 sectors"));
+    }
+
+    #[test]
+    fn saves_and_loads_as_json() {
+        let dir = scratch("json");
+        let mut result = AttackResult::new("darkside attack", &[0x04, 0x71, 0x72, 0x73, 0x76], 1);
+        result.add_key(0, KeyType::KeyA, [0xFF; 6]);
+        result.finish();
+
+        let path = result.save(&dir).unwrap();
+        assert!(path.file_name().unwrap().to_str().unwrap().starts_with("0471727376-"));
+        let loaded: AttackResult = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(loaded.sectors, result.sectors);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn keys_go_into_the_key_store_once() {
+        let dir = scratch("store");
+        let path = dir.join("keys.txt");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "ffffffffffff # old.mfd\n").unwrap();
+
+        let mut result = AttackResult::new("nested attack", &[0x04, 0x71, 0x72, 0x73, 0x76], 15);
+        result.add_key(1, KeyType::KeyA, [0xFF; 6]);
+        result.add_key(2, KeyType::KeyB, [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]);
+        assert_eq!(add_to_key_store(&result, &path).unwrap(), 1);
+        assert_eq!(add_to_key_store(&result, &path).unwrap(), 0);
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("FFFFFFFFFFFF # old.mfd; nested attack on card 04:71:72:73:76\n"));
+        assert!(text.contains("A0A1A2A3A4A5 # nested attack on card 04:71:72:73:76\n"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,5 +1,3 @@
-use std::io::{self, Write};
-
 /// Print a section header
 pub fn print_section_header(title: &str) {
     println!("\n=== {} ===", title);
@@ -10,25 +8,6 @@ pub fn print_subsection(title: &str) {
     println!("\n--- {} ---", title);
 }
 
-/// Print a progress bar
-pub fn print_progress_bar(current: usize, total: usize, width: usize) {
-    let percent = (current as f64 / total as f64) * 100.0;
-    let filled = (current as f64 / total as f64 * width as f64) as usize;
-    
-    print!("\r[");
-    for i in 0..width {
-        if i < filled {
-            print!("=");
-        } else if i == filled {
-            print!(">");
-        } else {
-            print!(" ");
-        }
-    }
-    print!("] {:.1}% ({}/{})", percent, current, total);
-    io::stdout().flush().unwrap();
-}
-
 /// Print a table row
 pub fn print_table_row(columns: &[&str], widths: &[usize]) {
     for (i, &col) in columns.iter().enumerate() {
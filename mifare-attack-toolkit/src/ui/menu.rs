@@ -1,21 +1,22 @@
 use std::error::Error;
 use std::io::{self, Write};
 use crate::cards::KeyType;
+use crate::i18n::{tr, tr_args};
 
 /// Display the main menu and get the user's choice
 pub fn display_main_menu() -> Result<u8, Box<dyn Error>> {
-    println!("\nSelect an option:");
-    println!("1. Read card UID");
-    println!("2. Try default keys");
-    println!("3. Run Nested Attack (requires a known key)");
-    println!("4. Run Darkside Attack");
-    println!("5. Detect Magic Card");
-    println!("6. Write custom UID (requires Magic Card)");
-    println!("7. Dump card contents");
-    println!("8. Clone card to Magic Card");
-    println!("9. Exit");
-    
-    print!("Enter choice: ");
+    println!("\n{}", tr("select-option"));
+    println!("1. {}", tr("read-card-uid"));
+    println!("2. {}", tr("try-default-keys"));
+    println!("3. {}", tr("run-nested-attack"));
+    println!("4. {}", tr("run-darkside-attack"));
+    println!("5. {}", tr("detect-magic-card"));
+    println!("6. {}", tr("write-custom-uid"));
+    println!("7. {}", tr("dump-card-contents"));
+    println!("8. {}", tr("clone-card-to-magic-card"));
+    println!("9. {}", tr("menu-exit"));
+    
+    print!("{} ", tr("enter-choice"));
     io::stdout().flush()?;
     
     let mut input = String::new();
@@ -49,8 +50,8 @@ pub fn get_sector_number(prompt: &str) -> Result<u8, Box<dyn Error>> {
     match input.trim().parse::<u8>() {
         Ok(sector) if sector < 16 => Ok(sector),
         _ => {
-            println!("Invalid sector number");
-            Err("Invalid sector number".into())
+            println!("{}", tr("err-invalid-sector-number"));
+            Err(tr("err-invalid-sector-number").into())
         }
     }
 }
@@ -66,15 +67,15 @@ pub fn get_block_number(prompt: &str) -> Result<u8, Box<dyn Error>> {
     match input.trim().parse::<u8>() {
         Ok(block) if block < 64 => Ok(block),
         _ => {
-            println!("Invalid block number");
-            Err("Invalid block number".into())
+            println!("{}", tr("err-invalid-block-number"));
+            Err(tr("err-invalid-block-number").into())
         }
     }
 }
 
 /// Get key type from the user (A or B)
 pub fn get_key_type() -> Result<char, Box<dyn Error>> {
-    print!("Enter key type (A or B): ");
+    print!("{} ", tr("enter-key-type"));
     io::stdout().flush()?;
     
     let mut input = String::new();
@@ -84,8 +85,8 @@ pub fn get_key_type() -> Result<char, Box<dyn Error>> {
         Some('A') => Ok('A'),
         Some('B') => Ok('B'),
         _ => {
-            println!("Invalid key type");
-            Err("Invalid key type".into())
+            println!("{}", tr("err-invalid-key-type"));
+            Err(tr("err-invalid-key-type").into())
         }
     }
 }
@@ -98,11 +99,11 @@ pub fn get_authentication_key(prompt: &str) -> Result<([u8; 6], KeyType), Box<dy
     let key_type = match get_key_type()? {
         'A' => KeyType::KeyA,
         'B' => KeyType::KeyB,
-        _ => return Err("Invalid key type".into()),
+        _ => return Err(tr("err-invalid-key-type").into()),
     };
     
     // Get key value
-    println!("Enter key (6 bytes in hex format, e.g. 'FF FF FF FF FF FF'):");
+    println!("{}", tr("enter-key-hex-spaced"));
     print!("> ");
     io::stdout().flush()?;
     
@@ -112,7 +113,7 @@ pub fn get_authentication_key(prompt: &str) -> Result<([u8; 6], KeyType), Box<dy
     let hex_values: Vec<&str> = input.trim().split_whitespace().collect();
     
     if hex_values.len() != 6 {
-        return Err("Key must be exactly 6 bytes".into());
+        return Err(tr("err-key-6-bytes").into());
     }
     
     let mut key = [0u8; 6];
@@ -120,7 +121,7 @@ pub fn get_authentication_key(prompt: &str) -> Result<([u8; 6], KeyType), Box<dy
     for i in 0..6 {
         match u8::from_str_radix(hex_values[i], 16) {
             Ok(byte) => key[i] = byte,
-            Err(_) => return Err(format!("Invalid hex value '{}'", hex_values[i]).into()),
+            Err(_) => return Err(tr_args("err-invalid-hex-value", &[("value", hex_values[i])]).into()),
         }
     }
     
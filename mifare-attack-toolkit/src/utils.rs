@@ -5,10 +5,11 @@ use std::thread;
 use std::time::Duration;
 
 use crate::reader::MifareClassic;
+use crate::i18n::{tr, tr_args};
 
 /// Wait for a card to be removed
 pub fn wait_for_card_removal(reader: &mut MifareClassic) -> Result<(), Box<dyn Error>> {
-    println!("Please remove the card from the reader...");
+    println!("{}", tr("please-remove-card-from-reader"));
     
     let mut card_present = true;
     while card_present {
@@ -25,14 +26,14 @@ pub fn wait_for_card_removal(reader: &mut MifareClassic) -> Result<(), Box<dyn E
         }
     }
     
-    println!("Card removed");
+    println!("{}", tr("card-removed"));
     Ok(())
 }
 
 /// Wait for a card with simplified approach to avoid type parameter issues
 pub fn wait_for_card(reader: &mut MifareClassic, timeout_secs: u64, _detect_fn: impl Fn(&mut MifareClassic) -> Result<Option<Vec<u8>>, Box<dyn Error>>) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
-    println!("Hold a card near the reader...");
-    println!("You have {} seconds to place a card", timeout_secs);
+    println!("{}", tr("hold-card-near-reader"));
+    println!("{}", tr_args("seconds-to-place-card", &[("seconds", &timeout_secs.to_string())]));
     
     // Make sure reader is in a clean state
     reader.stop_crypto1()?;
@@ -50,7 +51,7 @@ pub fn wait_for_card(reader: &mut MifareClassic, timeout_secs: u64, _detect_fn:
     while start_time.elapsed() < timeout_duration {
         match reader.get_uid()? {
             Some(uid) => {
-                println!("Card detected! UID: {}", format_uid(&uid));
+                println!("{}", tr_args("card-detected-uid", &[("uid", &format_uid(&uid).to_string())]));
                 return Ok(Some(uid.to_vec()));
             },
             None => {}
@@ -60,54 +61,28 @@ pub fn wait_for_card(reader: &mut MifareClassic, timeout_secs: u64, _detect_fn:
         thread::sleep(Duration::from_millis(100));
     }
     
-    println!("No card detected in the given time frame.");
+    println!("{}", tr("no-card-in-time"));
     Ok(None)
 }
 
 /// Format a byte slice to a hex string
 pub fn bytes_to_hex(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|byte| format!("{:02X}", byte))
-        .collect::<Vec<String>>()
-        .join(" ")
+    uid_codec::bytes_to_hex(bytes)
 }
 
 /// Format a byte slice to an ASCII string
 pub fn bytes_to_ascii(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|&byte| {
-            if byte >= 32 && byte <= 126 {
-                byte as char
-            } else {
-                '.'
-            }
-        })
-        .collect()
+    uid_codec::bytes_to_ascii(bytes)
 }
 
 /// Format a card UID as a hex string
 pub fn format_uid(uid: &[u8]) -> String {
-    uid.iter()
-        .map(|byte| format!("{:02X}", byte))
-        .collect::<Vec<String>>()
-        .join(":")
+    uid_codec::format_uid(uid)
 }
 
 /// Convert a hex string to a byte vector
 pub fn hex_to_bytes(hex: &str) -> Result<Vec<u8>, String> {
-    let hex = hex.replace(" ", "").replace(":", "");
-    if hex.len() % 2 != 0 {
-        return Err("Invalid hex string length".to_string());
-    }
-    
-    let mut result = Vec::with_capacity(hex.len() / 2);
-    for i in (0..hex.len()).step_by(2) {
-        let byte = u8::from_str_radix(&hex[i..i+2], 16)
-            .map_err(|_| "Invalid hex character".to_string())?;
-        result.push(byte);
-    }
-    
-    Ok(result)
+    uid_codec::parse_hex(hex)
 }
 
 /// Get user confirmation (y/n)
@@ -123,7 +98,7 @@ pub fn get_user_confirmation(prompt: &str) -> bool {
 
 /// Wait for user to press Enter
 pub fn wait_for_enter() {
-    print!("Press Enter to continue...");
+    print!("{}", tr("press-enter-to-continue"));
     io::stdout().flush().unwrap();
     
     let mut input = String::new();
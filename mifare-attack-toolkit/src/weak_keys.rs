@@ -0,0 +1,339 @@
+// src/weak_keys.rs
+//
+// The weak keys of a facility (--weak-keys): every key in the key store, the dumps and
+// the attack results is checked against the default keys and the public dictionaries,
+// and for low entropy. The report breaks the keys on the cards down by sector and can
+// be written as CSV for an assessment report. Keys from the store and other key lists
+// don't say which sector they open, so they are only in the list of keys.
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cards::{KeyType, DEFAULT_KEYS};
+use crate::i18n::{tr, tr_args};
+use crate::key_reuse::Collection;
+
+type Key = [u8; 6];
+
+/// Keys published in the dictionaries everyone attacks with: the list mfoc ships and
+/// well-known keys from the Proxmark3 client's mfc_default_keys.dic. --dictionary adds
+/// a full dictionary file.
+pub const PUBLIC_KEYS: [Key; 29] = [
+    [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5],
+    [0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7],
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+    [0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5],
+    [0x4D, 0x3A, 0x99, 0xC3, 0x51, 0xDD],
+    [0x1A, 0x98, 0x2C, 0x7E, 0x45, 0x9A],
+    [0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
+    [0x71, 0x4C, 0x5C, 0x88, 0x6E, 0x97],
+    [0x58, 0x7E, 0xE5, 0xF9, 0x35, 0x0F],
+    [0xA0, 0x47, 0x8C, 0xC3, 0x90, 0x91],
+    [0x53, 0x3C, 0xB6, 0xC7, 0x23, 0xF6],
+    [0x8F, 0xD0, 0xA4, 0xF2, 0x56, 0xE9],
+    [0xA5, 0xA4, 0xA3, 0xA2, 0xA1, 0xA0],
+    [0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+    [0x11, 0x22, 0x33, 0x44, 0x55, 0x66],
+    [0x01, 0x02, 0x03, 0x04, 0x05, 0x06],
+    [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC],
+    [0x12, 0x34, 0x56, 0xAB, 0xCD, 0xEF],
+    [0xAB, 0xCD, 0xEF, 0x12, 0x34, 0x56],
+    [0x1A, 0x2B, 0x3C, 0x4D, 0x5E, 0x6F],
+    // "COMMOA", "COMMOB", "GROUPA", "GROUPB", "PRIVAA", "PRIVAB"
+    [0x43, 0x4F, 0x4D, 0x4D, 0x4F, 0x41],
+    [0x43, 0x4F, 0x4D, 0x4D, 0x4F, 0x42],
+    [0x47, 0x52, 0x4F, 0x55, 0x50, 0x41],
+    [0x47, 0x52, 0x4F, 0x55, 0x50, 0x42],
+    [0x50, 0x52, 0x49, 0x56, 0x41, 0x41],
+    [0x50, 0x52, 0x49, 0x56, 0x41, 0x42],
+    [0xA6, 0x45, 0x98, 0xA7, 0x74, 0x78],
+    [0x26, 0x94, 0x0B, 0x21, 0xFF, 0x5D],
+];
+
+/// Why a key is easy to guess, empty for a key that looks random
+pub fn low_entropy(key: &Key) -> Vec<&'static str> {
+    let mut reasons = Vec::new();
+    let distinct: BTreeSet<u8> = key.iter().copied().collect();
+    if distinct.len() <= 2 {
+        reasons.push("two or fewer distinct bytes");
+    } else if key.iter().enumerate().all(|(i, byte)| *byte == key[i % 3]) {
+        reasons.push("repeating pattern");
+    }
+
+    // A0A1A2A3A4A5, 112233445566, A5A4A3A2A1A0
+    let steps: BTreeSet<u8> = key.windows(2).map(|pair| pair[1].wrapping_sub(pair[0])).collect();
+    if steps.len() == 1 && distinct.len() > 1 {
+        reasons.push("counting sequence");
+    }
+
+    let nibbles: BTreeSet<u8> = key.iter().flat_map(|byte| [byte >> 4, byte & 0x0F]).collect();
+    if nibbles.len() <= 3 && distinct.len() > 2 {
+        reasons.push("three or fewer distinct hex digits");
+    }
+
+    if key.iter().all(|byte| byte.is_ascii_graphic()) {
+        reasons.push("ASCII text");
+    }
+    reasons
+}
+
+/// Where a key was found: a sector of a card, or a key list
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyUse {
+    Card { uid: String, sector: u8, key_type: KeyType, files: String },
+    List(PathBuf),
+}
+
+/// A key and what makes it weak
+#[derive(Clone, Debug)]
+pub struct KeyFinding {
+    pub key: Key,
+    pub default: bool,
+    pub dictionary: bool,
+    pub low_entropy: Vec<&'static str>,
+    pub uses: Vec<KeyUse>,
+}
+
+impl KeyFinding {
+    pub fn weak(&self) -> bool {
+        self.default || self.dictionary || !self.low_entropy.is_empty()
+    }
+
+    /// Cards it opens a sector of
+    pub fn cards(&self) -> usize {
+        self.uses.iter()
+            .filter_map(|key_use| match key_use {
+                KeyUse::Card { uid, .. } => Some(uid),
+                KeyUse::List(_) => None,
+            })
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    pub fn sector_keys(&self) -> usize {
+        self.uses.iter().filter(|key_use| matches!(key_use, KeyUse::Card { .. })).count()
+    }
+}
+
+/// The sector keys of one sector across the cards, and how many of them are weak
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SectorStats {
+    pub sector: u8,
+    pub keys: usize,
+    pub default: usize,
+    pub dictionary: usize,
+    pub low_entropy: usize,
+    pub weak: usize,
+}
+
+/// The keys of a collection, weak ones first
+#[derive(Clone, Debug)]
+pub struct WeakKeyReport {
+    pub keys: Vec<KeyFinding>,
+    pub sectors: Vec<SectorStats>,
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+impl WeakKeyReport {
+    /// `dictionary` is checked along with PUBLIC_KEYS
+    pub fn new(collection: &Collection, dictionary: &[Key]) -> Self {
+        let mut uses: BTreeMap<Key, Vec<KeyUse>> = BTreeMap::new();
+        for card in &collection.cards {
+            let files = card.files.iter().map(|file| file.display().to_string()).collect::<Vec<_>>().join("; ");
+            for (sector, key_type, key) in card.keys() {
+                uses.entry(key).or_default().push(KeyUse::Card { uid: card.uid.clone(), sector, key_type, files: files.clone() });
+            }
+        }
+        for list in &collection.lists {
+            for key in &list.keys {
+                uses.entry(*key).or_default().push(KeyUse::List(list.path.clone()));
+            }
+        }
+
+        let mut keys: Vec<KeyFinding> = uses.into_iter()
+            .map(|(key, uses)| KeyFinding {
+                key,
+                default: DEFAULT_KEYS.contains(&key),
+                dictionary: PUBLIC_KEYS.contains(&key) || dictionary.contains(&key),
+                low_entropy: low_entropy(&key),
+                uses,
+            })
+            .collect();
+        keys.sort_by_key(|finding| (!finding.weak(), std::cmp::Reverse(finding.sector_keys()), finding.key));
+
+        let mut sectors: BTreeMap<u8, SectorStats> = BTreeMap::new();
+        for finding in &keys {
+            for key_use in &finding.uses {
+                if let KeyUse::Card { sector, .. } = key_use {
+                    let stats = sectors.entry(*sector).or_insert(SectorStats { sector: *sector, ..SectorStats::default() });
+                    stats.keys += 1;
+                    stats.default += finding.default as usize;
+                    stats.dictionary += finding.dictionary as usize;
+                    stats.low_entropy += !finding.low_entropy.is_empty() as usize;
+                    stats.weak += finding.weak() as usize;
+                }
+            }
+        }
+
+        WeakKeyReport { keys, sectors: sectors.into_values().collect(), skipped: collection.skipped.clone() }
+    }
+
+    /// One row per sector key on a card and per key in a key list
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("key,default,dictionary,low_entropy,card,sector,key_type,found_in\n");
+        for finding in &self.keys {
+            let flags = format!(
+                "{},{},{},{}",
+                key_hex(&finding.key),
+                yes_no(finding.default),
+                yes_no(finding.dictionary),
+                csv_field(&finding.low_entropy.join("; "))
+            );
+            for key_use in &finding.uses {
+                let place = match key_use {
+                    KeyUse::Card { uid, sector, key_type, files } => format!("{},{},{},{}", uid, sector, key_type, csv_field(files)),
+                    KeyUse::List(path) => format!(",,,{}", csv_field(&path.display().to_string())),
+                };
+                csv.push_str(&format!("{},{}\n", flags, place));
+            }
+        }
+        csv
+    }
+
+    pub fn save_csv(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_csv())?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for WeakKeyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weak: Vec<&KeyFinding> = self.keys.iter().filter(|finding| finding.weak()).collect();
+        writeln!(f, "=== {} ===", tr_args("weak-title", &[("weak", &weak.len().to_string()), ("total", &self.keys.len().to_string())]))?;
+
+        if !weak.is_empty() {
+            writeln!(
+                f,
+                "\n  {:<12}  {:<7}  {:<10}  {:>5}  {:>11}  {}",
+                tr("weak-key"),
+                tr("weak-default"),
+                tr("weak-dictionary"),
+                tr("weak-cards"),
+                tr("weak-sector-keys"),
+                tr("weak-low-entropy")
+            )?;
+            for finding in &weak {
+                writeln!(
+                    f,
+                    "  {}  {:<7}  {:<10}  {:>5}  {:>11}  {}",
+                    key_hex(&finding.key),
+                    yes_no_text(finding.default),
+                    yes_no_text(finding.dictionary),
+                    finding.cards(),
+                    finding.sector_keys(),
+                    finding.low_entropy.join(", ")
+                )?;
+            }
+        }
+        let strong = self.keys.len() - weak.len();
+        if strong > 0 {
+            writeln!(f, "\n{}", tr_args("weak-strong-keys", &[("count", &strong.to_string())]))?;
+        }
+
+        if !self.sectors.is_empty() {
+            writeln!(f, "\n{}", tr("weak-by-sector"))?;
+            writeln!(
+                f,
+                "  {:<6}  {:<4}  {:<7}  {:<10}  {:<11}  {}",
+                tr("result-sector"),
+                tr("weak-keys"),
+                tr("weak-default"),
+                tr("weak-dictionary"),
+                tr("weak-low-entropy"),
+                tr("weak-weak")
+            )?;
+            for stats in &self.sectors {
+                writeln!(
+                    f,
+                    "  {:>6}  {:>4}  {:>7}  {:>10}  {:>11}  {:>4}",
+                    stats.sector, stats.keys, stats.default, stats.dictionary, stats.low_entropy, stats.weak
+                )?;
+            }
+        }
+
+        for (path, reason) in &self.skipped {
+            writeln!(f, "{}", tr_args("report-skipped", &[("path", &path.display().to_string()), ("reason", reason)]))?;
+        }
+        Ok(())
+    }
+}
+
+fn key_hex(key: &Key) -> String {
+    key.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn yes_no(flag: bool) -> &'static str {
+    if flag { "yes" } else { "no" }
+}
+
+// the report's yes and no, in the reader's language; the CSV keeps yes_no
+fn yes_no_text(flag: bool) -> String {
+    tr(if flag { "report-yes" } else { "report-no" })
+}
+
+// quoted when it holds a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_reuse::{CardKeys, KeyList};
+
+    const RANDOM: Key = [0x3C, 0x5E, 0x71, 0x0B, 0x9D, 0x24];
+
+    #[test]
+    fn finds_low_entropy_keys() {
+        assert!(low_entropy(&[0xFF; 6]).contains(&"two or fewer distinct bytes"));
+        assert!(low_entropy(&[0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7]).contains(&"two or fewer distinct bytes"));
+        assert_eq!(low_entropy(&[0x12, 0x34, 0x56, 0x12, 0x34, 0x56]), vec!["repeating pattern"]);
+        assert_eq!(low_entropy(&[0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]), vec!["counting sequence"]);
+        assert_eq!(low_entropy(&[0xA5, 0xA4, 0xA3, 0xA2, 0xA1, 0xA0]), vec!["counting sequence"]);
+        assert_eq!(low_entropy(&[0x11, 0x21, 0x12, 0x22, 0x11, 0x12]), vec!["three or fewer distinct hex digits"]);
+        assert_eq!(low_entropy(b"GROUPA"), vec!["ASCII text"]);
+        assert!(low_entropy(&RANDOM).is_empty());
+    }
+
+    #[test]
+    fn breaks_the_keys_down_by_sector() {
+        let mut card = CardKeys { uid: "01:02:03:04".to_string(), files: vec![PathBuf::from("a,b.mfd")], ..CardKeys::default() };
+        card.sectors.insert(0, (Some([0xFF; 6]), Some(RANDOM)));
+        card.sectors.insert(1, (Some([0x47, 0x52, 0x4F, 0x55, 0x50, 0x41]), None));
+        let collection = Collection {
+            cards: vec![card],
+            lists: vec![KeyList { path: PathBuf::from("keys.txt"), keys: vec![[0xFF; 6], [0x9A, 0x1B, 0x7C, 0x2D, 0x5E, 0x83]] }],
+            skipped: Vec::new(),
+        };
+        let report = WeakKeyReport::new(&collection, &[[0x9A, 0x1B, 0x7C, 0x2D, 0x5E, 0x83]]);
+
+        let weak: Vec<Key> = report.keys.iter().filter(|finding| finding.weak()).map(|finding| finding.key).collect();
+        assert_eq!(weak, vec![[0x47, 0x52, 0x4F, 0x55, 0x50, 0x41], [0xFF; 6], [0x9A, 0x1B, 0x7C, 0x2D, 0x5E, 0x83]]);
+        assert!(!report.keys[3].weak());
+        assert_eq!(report.sectors[0], SectorStats { sector: 0, keys: 2, default: 1, dictionary: 1, low_entropy: 1, weak: 1 });
+        assert_eq!(report.sectors[1].weak, 1);
+
+        let csv = report.to_csv();
+        assert!(csv.starts_with("key,default,dictionary,low_entropy,card,sector,key_type,found_in\n"));
+        assert!(csv.contains("FFFFFFFFFFFF,yes,yes,two or fewer distinct bytes,01:02:03:04,0,Key A,\"a,b.mfd\"\n"));
+        assert!(csv.contains("FFFFFFFFFFFF,yes,yes,two or fewer distinct bytes,,,,keys.txt\n"));
+        assert!(csv.contains("9A1B7C2D5E83,no,yes,,,,,keys.txt\n"));
+    }
+}
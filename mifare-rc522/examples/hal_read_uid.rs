@@ -0,0 +1,30 @@
+// The embedded-hal driver on a Pi: rppal's Spi through TransportDevice. On a
+// microcontroller, pass its HAL's SpiDevice and delay to Mfrc522::new instead.
+//
+//   cargo run --example hal_read_uid --features rppal,embedded-hal
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use mifare_rc522::hal::{self, StdDelay, TransportDevice};
+use mifare_rc522::{open_spi, Mfrc522, PICC_REQIDL, SPI_SPEED_HZ};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut reader = Mfrc522::new(TransportDevice(open_spi(SPI_SPEED_HZ)?), StdDelay);
+    reader.init()?;
+    println!("MFRC522 version {:02X}, waiting for cards", reader.version()?);
+
+    loop {
+        match reader.request(PICC_REQIDL) {
+            Ok(_) => {
+                let uid = reader.anticoll()?;
+                let sak = reader.select(&uid)?;
+                println!("Card {:02X?} (SAK {:02X})", uid, sak);
+                reader.halt()?;
+            },
+            Err(hal::Error::NoCard) => {},
+            Err(e) => println!("{}", e),
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,44 @@
+// Read one block with key A
+//
+//   cargo run --example read_block --features rppal -- 4 FFFFFFFFFFFF
+use std::error::Error;
+
+use mifare_rc522::*;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = std::env::args().skip(1);
+    let block: u8 = args.next().unwrap_or_else(|| "4".to_string()).parse()?;
+    let key_hex = args.next().unwrap_or_else(|| "FFFFFFFFFFFF".to_string());
+    if key_hex.len() != 12 {
+        return Err("The key is 12 hex digits".into());
+    }
+    let key = (0..12).step_by(2)
+        .map(|i| u8::from_str_radix(&key_hex[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut spi = open_spi(SPI_SPEED_HZ)?;
+    mfrc522_init(&mut spi)?;
+
+    let (status, _) = mfrc522_request(&mut spi, PICC_REQIDL)?;
+    if status != MI_OK {
+        return Err("No card on the reader".into());
+    }
+    let (status, uid) = mfrc522_anticoll(&mut spi)?;
+    if status != MI_OK {
+        return Err("Anticollision failed, hold one card still".into());
+    }
+    mfrc522_select_tag(&mut spi, &uid)?;
+
+    if mfrc522_auth(&mut spi, PICC_AUTHENT1A, block, &key, &uid)? != MI_OK {
+        mfrc522_stop_crypto1(&mut spi)?;
+        return Err(format!("Key {} was refused for block {}", key_hex, block).into());
+    }
+    let data = mfrc522_read(&mut spi, block)?;
+    mfrc522_stop_crypto1(&mut spi)?;
+
+    match data {
+        Some(data) => println!("Block {}: {:02X?}", block, data),
+        None => println!("Block {} couldn't be read", block),
+    }
+    Ok(())
+}
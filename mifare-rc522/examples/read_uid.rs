@@ -0,0 +1,26 @@
+// Print the UID of every card held to the reader, until Ctrl+C
+//
+//   cargo run --example read_uid --features rppal
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use mifare_rc522::*;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let (mut spi, setting) = negotiate_speed(SpeedConfig::load(SpeedConfig::default_path()))?;
+    println!("Reader {} at {} Hz, waiting for cards", setting.reader, setting.speed_hz);
+
+    loop {
+        let (status, _) = mfrc522_request(&mut spi, PICC_REQIDL)?;
+        if status == MI_OK {
+            let (status, uid) = mfrc522_anticoll(&mut spi)?;
+            if status == MI_OK {
+                let hex: Vec<String> = uid[..4].iter().map(|byte| format!("{:02X}", byte)).collect();
+                println!("Card {} (SAK {:02X})", hex.join(":"), mfrc522_select_tag(&mut spi, &uid)?);
+                mfrc522_halt(&mut spi)?;
+            }
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
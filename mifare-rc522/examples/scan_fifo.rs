@@ -0,0 +1,32 @@
+// Feed the NFC reader app: every card in the field, stacked tags included, is written
+// to its scan FIFO as "timestamp,UID" each polling cycle, until Ctrl+C
+//
+//   mkfifo /tmp/rfid_scans.fifo
+//   cargo run --example scan_fifo --features rppal
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mifare_rc522::*;
+
+const FIFO_PATH: &str = "/tmp/rfid_scans.fifo";
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let (mut spi, setting) = negotiate_speed(SpeedConfig::load(SpeedConfig::default_path()))?;
+    println!("Reader {} at {} Hz, writing scans to {}", setting.reader, setting.speed_hz, FIFO_PATH);
+
+    // blocks until the app opens the FIFO for reading
+    let mut fifo = OpenOptions::new().write(true).open(FIFO_PATH)?;
+
+    loop {
+        let cards = mfrc522_enumerate(&mut spi, MAX_CARDS)?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        for card in &cards {
+            let hex: String = card.uid.iter().map(|byte| format!("{:02X}", byte)).collect();
+            writeln!(fifo, "{},{}", timestamp, hex)?;
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,208 @@
+// Bit oriented anticollision (ISO 14443-3), to read every card in the field.
+//
+// mfrc522_anticoll asks for the whole UID of cascade level 1 and gives up when two
+// cards answer at once. Here a collision is resolved bit by bit: the reader sends the
+// UID bits it knows so far and picks 1 for the first bit the cards disagree on, so only
+// the cards whose UID starts that way keep answering, until one is left. Cascade
+// levels 2 and 3 carry the rest of 7 and 10 byte UIDs (Ultralight and NTAG tags have
+// 7). A card is halted once it is read, and the next request finds the others.
+use std::error::Error;
+
+use super::constants::*;
+use super::register::*;
+use super::communication::*;
+use super::metrics::COLL_ERR;
+use super::operations::{forget_authentication, mfrc522_halt};
+use super::transport::Transport;
+
+// CollReg: ValuesAfterColl, CollPosNotValid and CollPos
+const VALUES_AFTER_COLL: u8 = 0x80;
+const COLL_POS_NOT_VALID: u8 = 0x20;
+const COLL_POS: u8 = 0x1F;
+// ErrorReg bits that spoil a frame whatever collided: BufferOvfl, ParityErr, ProtocolErr
+const FRAME_ERRORS: u8 = 0x13;
+// SAK bit saying the UID goes on at the next cascade level
+const SAK_CASCADE: u8 = 0x04;
+
+// Select commands of cascade levels 1 to 3
+const CASCADE_LEVELS: [u8; 3] = [PICC_ANTICOLL, PICC_SEL_CL2, PICC_SEL_CL3];
+
+// Cards one polling cycle reads at most, should a card keep answering after HALT
+pub const MAX_CARDS: usize = 16;
+
+// A card read by mfrc522_select_card or mfrc522_enumerate
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DetectedCard {
+    // 4, 7 or 10 bytes, without cascade tags and check bytes
+    pub uid: Vec<u8>,
+    // Select acknowledge of the last cascade level: 0x08 for a Classic 1K, 0x00 for
+    // Ultralight and NTAG
+    pub sak: u8,
+}
+
+// What the cards answered a frame with
+enum Answer {
+    Silence,
+    // the bytes as they came out of the FIFO
+    Frame(Vec<u8>),
+    // the bytes up to the collision, and its bit position in them counted from 1
+    Collision(Vec<u8>, usize),
+}
+
+// Transceive `frame`, whose last byte has `tx_last_bits` valid bits (0 for all 8), with
+// the first bit of the answer stored at bit `rx_align` of the first byte. Unlike
+// mfrc522_to_card a collision is an answer, not an error.
+fn transceive(spi: &mut dyn Transport, frame: &[u8], tx_last_bits: u8, rx_align: u8) -> Result<Answer, Box<dyn Error>> {
+    write_register(spi, BIT_FRAMING_REG, (rx_align << 4) | tx_last_bits)?;
+    // the bits received after a collision read as 0
+    clear_bit_mask(spi, COLL_REG, VALUES_AFTER_COLL)?;
+    let (status, back_data, _) = mfrc522_to_card(spi, PCD_TRANSCEIVE, frame)?;
+
+    let answer = match status {
+        MI_OK => Answer::Frame(back_data),
+        MI_NOTAGERR => Answer::Silence,
+        _ => {
+            let errors = read_register(spi, ERROR_REG)?;
+            let coll = read_register(spi, COLL_REG)?;
+            if errors & COLL_ERR == 0 || errors & FRAME_ERRORS != 0 || coll & COLL_POS_NOT_VALID != 0 {
+                Answer::Silence
+            } else {
+                // mfrc522_to_card leaves the FIFO alone after an error
+                let level = (read_register(spi, FIFO_LEVEL_REG)? as usize).min(MAX_LEN);
+                let data = read_register_burst(spi, FIFO_DATA_REG, level)?;
+                // CollPos 0 is the 32nd bit
+                let position = match (coll & COLL_POS) as usize {
+                    0 => 32,
+                    position => position,
+                };
+                Answer::Collision(data, position)
+            }
+        },
+    };
+
+    write_register(spi, BIT_FRAMING_REG, 0x00)?;
+    Ok(answer)
+}
+
+// The UID bytes and check byte of one cascade level, of the card that wins every
+// collision. None if the cards stopped answering or the answer is garbled.
+fn resolve_level(spi: &mut dyn Transport, sel: u8) -> Result<Option<[u8; 5]>, Box<dyn Error>> {
+    let mut level = [0u8; 5];
+    let mut known_bits = 0usize;
+
+    loop {
+        let full_bytes = known_bits / 8;
+        let extra_bits = (known_bits % 8) as u8;
+        // NVB: bytes sent in the high nibble (SEL and NVB included), then the extra bits
+        let mut frame = vec![sel, (((2 + full_bytes) as u8) << 4) | extra_bits];
+        frame.extend_from_slice(&level[..full_bytes + usize::from(extra_bits > 0)]);
+
+        let (data, collision) = match transceive(spi, &frame, extra_bits, extra_bits)? {
+            Answer::Silence => return Ok(None),
+            Answer::Frame(data) => (data, None),
+            Answer::Collision(data, position) => (data, Some(position)),
+        };
+
+        // the answer starts with the rest of the byte the known bits end in
+        let sent_mask = (1u8 << extra_bits) - 1;
+        for (i, byte) in data.iter().enumerate().take(level.len() - full_bytes) {
+            let index = full_bytes + i;
+            level[index] = if i == 0 { (level[index] & sent_mask) | (byte & !sent_mask) } else { *byte };
+        }
+
+        let position = match collision {
+            Some(position) => position,
+            None => {
+                if data.len() < level.len() - full_bytes {
+                    return Ok(None);
+                }
+                let bcc = level[..4].iter().fold(0, |bcc, byte| bcc ^ byte);
+                return Ok(if bcc == level[4] { Some(level) } else { None });
+            },
+        };
+
+        // the first bit the cards disagree on, counted from the start of the level
+        let bit = full_bytes * 8 + position - 1;
+        if bit < known_bits || bit >= level.len() * 8 {
+            return Ok(None);
+        }
+        // follow the cards with a 1 there, and forget what came after it
+        let (byte, shift) = (bit / 8, bit % 8);
+        level[byte] = (level[byte] & ((1u8 << shift) - 1)) | (1 << shift);
+        level[byte + 1..].fill(0);
+        known_bits = bit + 1;
+    }
+}
+
+// Select the card a cascade level was resolved to, returns its SAK
+fn select_level(spi: &mut dyn Transport, sel: u8, level: &[u8; 5]) -> Result<Option<u8>, Box<dyn Error>> {
+    let mut frame = vec![sel, 0x70];
+    frame.extend_from_slice(level);
+    let crc = calculate_crc(spi, &frame)?;
+    frame.extend_from_slice(&crc);
+
+    write_register(spi, BIT_FRAMING_REG, 0x00)?;
+    let (status, back_data, back_len) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &frame)?;
+    // SAK and its CRC
+    if status == MI_OK && back_len == 0x18 {
+        Ok(back_data.first().copied())
+    } else {
+        Ok(None)
+    }
+}
+
+// Select one of the cards that answered a request or wake-up, through every cascade
+// level of its UID, resolving collisions on the way. None if no card could be selected.
+pub fn mfrc522_select_card(spi: &mut dyn Transport) -> Result<Option<DetectedCard>, Box<dyn Error>> {
+    let mut uid = Vec::new();
+    for sel in CASCADE_LEVELS {
+        let level = match resolve_level(spi, sel)? {
+            Some(level) => level,
+            None => return Ok(None),
+        };
+        let sak = match select_level(spi, sel, &level)? {
+            Some(sak) => sak,
+            None => return Ok(None),
+        };
+
+        if sak & SAK_CASCADE == 0 {
+            uid.extend_from_slice(&level[..4]);
+            return Ok(Some(DetectedCard { uid, sak }));
+        }
+        // the level starts with the cascade tag, the UID goes on at the next one
+        uid.extend_from_slice(&level[1..4]);
+    }
+    Ok(None)
+}
+
+// Every card in the field, read in one polling cycle. The first request is a wake-up
+// (PICC_REQALL), so the cards the last cycle halted answer again. Each card is halted
+// once read, and the cycle ends when no card is left, one can't be selected, or
+// `max_cards` were read.
+pub fn mfrc522_enumerate(spi: &mut dyn Transport, max_cards: usize) -> Result<Vec<DetectedCard>, Box<dyn Error>> {
+    let mut cards: Vec<DetectedCard> = Vec::new();
+    let mut req_mode = PICC_REQALL;
+
+    while cards.len() < max_cards {
+        // a request ends any crypto session, as mfrc522_request does
+        forget_authentication(spi);
+        // the ATQAs of different cards may collide, cards answered all the same
+        if let Answer::Silence = transceive(spi, &[req_mode], 7, 0)? {
+            break;
+        }
+
+        let card = match mfrc522_select_card(spi)? {
+            Some(card) => card,
+            None => break,
+        };
+        mfrc522_halt(spi)?;
+        // a card read twice didn't take the HALT, it would be read forever
+        if cards.contains(&card) {
+            break;
+        }
+        cards.push(card);
+        req_mode = PICC_REQIDL;
+    }
+
+    Ok(cards)
+}
@@ -1,13 +1,13 @@
-use rppal::spi::Spi;
 use std::error::Error;
 
 use super::constants::*;
+use super::layout::BlockAddr;
 use super::communication::*;
-// Add at the top:
-use crate::lib::mfrc522::communication::{mfrc522_to_card, calculate_crc};
+use super::operations::{end_auth_session, forget_authentication};
+use super::transport::Transport;
 
-// Read a block from the card
-pub fn mfrc522_read(spi: &mut Spi, block_addr: u8) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+// Read a block from the card, None when the card refused or didn't answer
+pub fn mfrc522_read(spi: &mut dyn Transport, block_addr: u8) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
     let mut recv_data: Vec<u8> = Vec::new();
     recv_data.push(PICC_READ);
     recv_data.push(block_addr);
@@ -19,19 +19,27 @@ pub fn mfrc522_read(spi: &mut Spi, block_addr: u8) -> Result<Option<Vec<u8>>, Bo
     let (status, back_data, _) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &recv_data)?;
     
     if status != MI_OK {
-        println!("Error while reading!");
+        forget_authentication(spi);
         return Ok(None);
     }
     
     if back_data.len() == 16 {
-        return Ok(Some(back_data));
+        Ok(Some(back_data))
     } else {
-        return Ok(None);
+        // A NAK: the card dropped the authentication
+        forget_authentication(spi);
+        Ok(None)
     }
 }
 
-// Write a block to the card
-pub fn mfrc522_write(spi: &mut Spi, block_addr: u8, write_data: &[u8]) -> Result<u8, Box<dyn Error>> {
+// Write a block to the card, MI_OK once the card acknowledged the data. Printing the
+// outcome is left to the caller.
+pub fn mfrc522_write(spi: &mut dyn Transport, block_addr: u8, write_data: &[u8]) -> Result<u8, Box<dyn Error>> {
+    // The trailer may hold other keys afterwards, the next authentication has to check
+    if BlockAddr(block_addr).is_trailer() {
+        end_auth_session(spi);
+    }
+    
     let mut buf: Vec<u8> = Vec::new();
     buf.push(PICC_WRITE);
     buf.push(block_addr);
@@ -43,6 +51,7 @@ pub fn mfrc522_write(spi: &mut Spi, block_addr: u8, write_data: &[u8]) -> Result
     let (status, back_data, back_len) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &buf)?;
     
     if (status != MI_OK) || (back_len != 4) || ((back_data[0] & 0x0F) != 0x0A) {
+        forget_authentication(spi);
         return Ok(MI_ERR);
     }
     
@@ -68,13 +77,13 @@ pub fn mfrc522_write(spi: &mut Spi, block_addr: u8, write_data: &[u8]) -> Result
         let (status, back_data, back_len) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &buf)?;
         
         if (status != MI_OK) || (back_len != 4) || ((back_data[0] & 0x0F) != 0x0A) {
-            println!("Error while writing");
+            forget_authentication(spi);
             return Ok(MI_ERR);
         } else {
-            println!("Data written successfully to block {}", block_addr);
             return Ok(MI_OK);
         }
     }
     
     Ok(MI_ERR)
 }
+
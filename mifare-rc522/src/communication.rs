@@ -1,18 +1,23 @@
-use rppal::spi::Spi;
 use std::error::Error;
 use std::thread;
 use std::time::Duration;
 
 use super::constants::*;
 use super::register::*;
+use super::metrics::{record_outcome, TransactionOutcome, COLL_ERR};
+use super::speed::record_transaction;
+use super::transport::Transport;
 
 // Communicate with the card
-pub fn mfrc522_to_card(spi: &mut Spi, command: u8, data: &[u8]) -> Result<(u8, Vec<u8>, usize), Box<dyn Error>> {
+pub fn mfrc522_to_card(spi: &mut dyn Transport, command: u8, data: &[u8]) -> Result<(u8, Vec<u8>, usize), Box<dyn Error>> {
     let mut back_data: Vec<u8> = Vec::new();
     let mut back_len: usize = 0;
     let mut status = MI_ERR;
     let mut irq_en: u8 = 0x00;
     let mut wait_irq: u8 = 0x00;
+    let mut comm_error = false;
+    let mut error_flags = 0x00;
+    let mut outcome = TransactionOutcome::Timeout;
     
     if command == PCD_AUTHENT {
         irq_en = 0x12;
@@ -31,10 +36,12 @@ pub fn mfrc522_to_card(spi: &mut Spi, command: u8, data: &[u8]) -> Result<(u8, V
     // No action, cancel current commands
     write_register(spi, COMMAND_REG, PCD_IDLE)?;
     
-    // Write data to FIFO
-    for &byte in data {
-        write_register(spi, FIFO_DATA_REG, byte)?;
-    }
+    // Write data to FIFO in one burst, anything that doesn't fit is topped up
+    // below as the FIFO drains
+    let mut sent = data.len().min(FIFO_SIZE);
+    write_register_burst(spi, FIFO_DATA_REG, &data[..sent])?;
+    // Bytes taken out of the FIFO while the answer was still arriving
+    let mut drained: Vec<u8> = Vec::new();
     
     // Execute command
     write_register(spi, COMMAND_REG, command)?;
@@ -57,6 +64,26 @@ pub fn mfrc522_to_card(spi: &mut Spi, command: u8, data: &[u8]) -> Result<(u8, V
             break;
         }
         
+        // LoAlert: the FIFO is nearly empty, refill it with the rest of a long frame
+        if (n & 0x04) != 0 && sent < data.len() {
+            // a level past the FIFO size can only be a bad read, don't refill on it
+            let free = FIFO_SIZE.saturating_sub(read_register(spi, FIFO_LEVEL_REG)? as usize);
+            let end = (sent + free).min(data.len());
+            write_register_burst(spi, FIFO_DATA_REG, &data[sent..end])?;
+            sent = end;
+            write_register(spi, COM_IRQ_REG, 0x04)?;
+            continue;
+        }
+        
+        // HiAlert once sending is done (TxIRq): the FIFO is nearly full, empty it
+        // before a long answer overflows it
+        if (n & 0x08) != 0 && (n & 0x40) != 0 && command == PCD_TRANSCEIVE {
+            let level = read_register(spi, FIFO_LEVEL_REG)? as usize;
+            drained.extend(read_register_burst(spi, FIFO_DATA_REG, level)?);
+            write_register(spi, COM_IRQ_REG, 0x08)?;
+            continue;
+        }
+        
         thread::sleep(Duration::from_micros(100));
     }
     
@@ -66,23 +93,26 @@ pub fn mfrc522_to_card(spi: &mut Spi, command: u8, data: &[u8]) -> Result<(u8, V
     // Check for errors and retrieve data
     if i != 0 {
         // No error in communication
-        if (read_register(spi, ERROR_REG)? & 0x1B) == 0x00 {
+        error_flags = read_register(spi, ERROR_REG)?;
+        if (error_flags & 0x1B) == 0x00 {
             status = MI_OK;
+            outcome = TransactionOutcome::Answered;
             
             // Check if CardIRq bit is set (timeout)
             if (n & irq_en & 0x01) != 0 {
                 status = MI_NOTAGERR;
+                outcome = TransactionOutcome::NoAnswer;
             }
             
             // Read data from FIFO if it's a transceive command
             if command == PCD_TRANSCEIVE {
                 // Number of bytes in FIFO
-                let mut fifo_len = read_register(spi, FIFO_LEVEL_REG)? as usize;
+                let mut fifo_len = drained.len() + read_register(spi, FIFO_LEVEL_REG)? as usize;
                 // Last bits = Number of valid bits in the last received byte
                 let last_bits = (read_register(spi, CONTROL_REG)? & 0x07) as usize;
                 
                 if last_bits != 0 {
-                    back_len = (fifo_len - 1) * 8 + last_bits;
+                    back_len = fifo_len.saturating_sub(1) * 8 + last_bits;
                 } else {
                     back_len = fifo_len * 8;
                 }
@@ -95,29 +125,36 @@ pub fn mfrc522_to_card(spi: &mut Spi, command: u8, data: &[u8]) -> Result<(u8, V
                 // Cap maximum read to MAX_LEN
                 let read_len = if fifo_len > MAX_LEN { MAX_LEN } else { fifo_len };
                 
-                // Read the data from FIFO
-                for _ in 0..read_len {
-                    back_data.push(read_register(spi, FIFO_DATA_REG)?);
-                }
+                // Read the rest of the data from FIFO in one burst
+                back_data = drained;
+                let remaining = read_len.saturating_sub(back_data.len());
+                back_data.extend(read_register_burst(spi, FIFO_DATA_REG, remaining)?);
+                back_data.truncate(read_len);
             }
         } else {
-            // Communication error
+            // Communication error, or cards answering at once. A collision is no sign
+            // of a bad link, so it doesn't count against the SPI speed.
             status = MI_ERR;
+            comm_error = (error_flags & 0x1B & !COLL_ERR) != 0;
+            outcome = TransactionOutcome::Error;
         }
     }
     
+    // Counted so the SPI speed can be lowered when errors pile up
+    record_transaction(spi, comm_error);
+    // and for the signal quality metrics
+    record_outcome(command, data, outcome, error_flags);
+    
     Ok((status, back_data, back_len))
 }
 
 // Calculate CRC
-pub fn calculate_crc(spi: &mut Spi, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+pub fn calculate_crc(spi: &mut dyn Transport, data: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
     clear_bit_mask(spi, DIV_IRQ_REG, 0x04)?;
     set_bit_mask(spi, FIFO_LEVEL_REG, 0x80)?;
     
     // Write data to FIFO
-    for &byte in data {
-        write_register(spi, FIFO_DATA_REG, byte)?;
-    }
+    write_register_burst(spi, FIFO_DATA_REG, data)?;
     
     write_register(spi, COMMAND_REG, PCD_CALCCRC)?;
     
@@ -135,9 +172,5 @@ pub fn calculate_crc(spi: &mut Spi, data: &[u8]) -> Result<Vec<u8>, Box<dyn Erro
     }
     
     // Read CRC result
-    let mut result = Vec::new();
-    result.push(read_register(spi, CRC_RESULT_REG_L)?);
-    result.push(read_register(spi, CRC_RESULT_REG_M)?);
-    
-    Ok(result)
+    Ok(vec![read_register(spi, CRC_RESULT_REG_L)?, read_register(spi, CRC_RESULT_REG_M)?])
 }
@@ -12,6 +12,11 @@ pub const PICC_REQIDL: u8 = 0x26;
 pub const PICC_REQALL: u8 = 0x52;
 pub const PICC_ANTICOLL: u8 = 0x93;
 pub const PICC_SELECTTAG: u8 = 0x93;
+// Anticollision and select of cascade levels 2 and 3, for 7 and 10 byte UIDs
+pub const PICC_SEL_CL2: u8 = 0x95;
+pub const PICC_SEL_CL3: u8 = 0x97;
+// First byte of a cascade level that holds only part of a longer UID
+pub const PICC_CASCADE_TAG: u8 = 0x88;
 pub const PICC_AUTHENT1A: u8 = 0x60;
 pub const PICC_AUTHENT1B: u8 = 0x61;
 pub const PICC_READ: u8 = 0x30;
@@ -70,3 +75,8 @@ pub const T_RELOAD_REG_L: u8 = 0x2D;
 pub const VERSION_REG: u8 = 0x37;
 
 pub const MAX_LEN: usize = 16;
+
+// FIFO buffer size, and the water level behind the HiAlert/LoAlert interrupts: HiAlert
+// when no more than WATER_LEVEL bytes are free, LoAlert when no more than WATER_LEVEL are left
+pub const FIFO_SIZE: usize = 64;
+pub const WATER_LEVEL: u8 = 16;
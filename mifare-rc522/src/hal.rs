@@ -0,0 +1,463 @@
+// The reader over embedded-hal 1.0 traits, for microcontrollers and any other host
+// with an SpiDevice and a DelayNs. It doesn't allocate and builds without std.
+//
+// One driver value owns the bus: frames go into fixed buffers (a Classic answer is
+// at most 18 bytes), errors come back as Error instead of status codes, and there is
+// no session reuse or metrics, those stay with the std functions. On a Pi the driver
+// runs over rppal through TransportDevice and StdDelay (the `std` feature).
+use core::fmt;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::spi::SpiDevice;
+
+use super::constants::*;
+
+#[cfg(feature = "std")]
+mod adapter;
+#[cfg(feature = "std")]
+pub use adapter::{StdDelay, TransportDevice, TransportError};
+
+// Polls of the interrupt register before a command counts as timed out, POLL_DELAY_US apart
+const WAIT_POLLS: u32 = 2000;
+const POLL_DELAY_US: u32 = 100;
+// Polls of the CRC coprocessor, it finishes a short frame in a few microseconds
+const CRC_POLLS: u32 = 0xFF;
+// Longest answer: a block and its CRC
+const ANSWER_LEN: usize = 18;
+
+// ErrorReg flags that spoil an answer: protocol, parity, collision and buffer overflow
+const ERROR_FLAGS: u8 = 0x1B;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    // The SPI bus failed
+    Spi(E),
+    // No card answered before the reader's timer ran out
+    NoCard,
+    // The reader didn't finish the command in time
+    Timeout,
+    // The error register flagged the answer, the flags are kept
+    Comm(u8),
+    // The answer had the wrong length or a bad check byte
+    Protocol,
+    // The card answered with a NAK, the 4 bit code is kept
+    Nak(u8),
+    // The card refused the key
+    AuthFailed,
+}
+
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Spi(e) => write!(f, "SPI error: {:?}", e),
+            Error::NoCard => write!(f, "No card answered"),
+            Error::Timeout => write!(f, "The reader timed out"),
+            Error::Comm(flags) => write!(f, "Communication error (ErrorReg {:02X})", flags),
+            Error::Protocol => write!(f, "Unexpected answer from the card"),
+            Error::Nak(code) => write!(f, "The card answered NAK {:X}", code),
+            Error::AuthFailed => write!(f, "The card refused the key"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug> std::error::Error for Error<E> {}
+
+// What the card sent back to a transceive: the bytes, and the bit count for frames
+// that don't end on a byte boundary (ACK/NAK are 4 bits)
+struct Answer {
+    data: [u8; ANSWER_LEN],
+    len: usize,
+    bits: usize,
+}
+
+impl Answer {
+    fn bytes(&self) -> &[u8] {
+        &self.data[..self.len]
+    }
+}
+
+pub struct Mfrc522<SPI, D> {
+    spi: SPI,
+    delay: D,
+}
+
+impl<SPI: SpiDevice, D: DelayNs> Mfrc522<SPI, D> {
+    pub fn new(spi: SPI, delay: D) -> Self {
+        Mfrc522 { spi, delay }
+    }
+
+    // Give back the bus and the delay
+    pub fn release(self) -> (SPI, D) {
+        (self.spi, self.delay)
+    }
+
+    fn read_register(&mut self, reg: u8) -> Result<u8, Error<SPI::Error>> {
+        let mut buf = [((reg << 1) & 0x7E) | 0x80, 0x00];
+        self.spi.transfer_in_place(&mut buf).map_err(Error::Spi)?;
+        Ok(buf[1])
+    }
+
+    fn write_register(&mut self, reg: u8, value: u8) -> Result<(), Error<SPI::Error>> {
+        self.spi.write(&[(reg << 1) & 0x7E, value]).map_err(Error::Spi)
+    }
+
+    fn set_bit_mask(&mut self, reg: u8, mask: u8) -> Result<(), Error<SPI::Error>> {
+        let value = self.read_register(reg)?;
+        self.write_register(reg, value | mask)
+    }
+
+    fn clear_bit_mask(&mut self, reg: u8, mask: u8) -> Result<(), Error<SPI::Error>> {
+        let value = self.read_register(reg)?;
+        self.write_register(reg, value & !mask)
+    }
+
+    // Pulse NRSTPD low for a hard reset, then wait for the oscillator. Call init after.
+    pub fn hard_reset<P: OutputPin>(&mut self, reset: &mut P) -> Result<(), P::Error> {
+        reset.set_low()?;
+        self.delay.delay_us(10);
+        reset.set_high()?;
+        self.delay.delay_ms(50);
+        Ok(())
+    }
+
+    // Soft reset, timer and modulation settings, antenna on. Same as mfrc522_init.
+    pub fn init(&mut self) -> Result<(), Error<SPI::Error>> {
+        self.write_register(COMMAND_REG, PCD_RESETPHASE)?;
+        self.delay.delay_ms(50);
+
+        self.write_register(T_MODE_REG, 0x8D)?;
+        self.write_register(T_PRESCALER_REG, 0x3E)?;
+        self.write_register(T_RELOAD_REG_L, 30)?;
+        self.write_register(T_RELOAD_REG_H, 0)?;
+
+        self.write_register(TX_AUTO_REG, 0x40)?;
+        self.write_register(MODE_REG, 0x3D)?;
+
+        self.antenna_on()
+    }
+
+    // The version register: 0x91 or 0x92 for an MFRC522, 0x88 for a clone.
+    // 0x00 or 0xFF means nothing is answering on the bus.
+    pub fn version(&mut self) -> Result<u8, Error<SPI::Error>> {
+        self.read_register(VERSION_REG)
+    }
+
+    pub fn antenna_on(&mut self) -> Result<(), Error<SPI::Error>> {
+        if self.read_register(TX_CONTROL_REG)? & 0x03 != 0x03 {
+            self.set_bit_mask(TX_CONTROL_REG, 0x03)?;
+        }
+        Ok(())
+    }
+
+    pub fn antenna_off(&mut self) -> Result<(), Error<SPI::Error>> {
+        self.clear_bit_mask(TX_CONTROL_REG, 0x03)
+    }
+
+    // Run PCD_AUTHENT or PCD_TRANSCEIVE, see mfrc522_to_card
+    fn communicate(&mut self, command: u8, data: &[u8]) -> Result<Answer, Error<SPI::Error>> {
+        let (irq_en, wait_irq) = if command == PCD_AUTHENT { (0x12, 0x10) } else { (0x77, 0x30) };
+
+        self.write_register(COM_IEN_REG, irq_en | 0x80)?;
+        self.clear_bit_mask(COM_IRQ_REG, 0x80)?;
+        self.set_bit_mask(FIFO_LEVEL_REG, 0x80)?;
+        self.write_register(COMMAND_REG, PCD_IDLE)?;
+
+        for &byte in data {
+            self.write_register(FIFO_DATA_REG, byte)?;
+        }
+        self.write_register(COMMAND_REG, command)?;
+        if command == PCD_TRANSCEIVE {
+            self.set_bit_mask(BIT_FRAMING_REG, 0x80)?;
+        }
+
+        let mut irq = 0;
+        let mut polls = 0;
+        while polls < WAIT_POLLS {
+            irq = self.read_register(COM_IRQ_REG)?;
+            // RxIRq or IdleIRq, or the timer ran out
+            if irq & (wait_irq | 0x01) != 0 {
+                break;
+            }
+            self.delay.delay_us(POLL_DELAY_US);
+            polls += 1;
+        }
+        self.clear_bit_mask(BIT_FRAMING_REG, 0x80)?;
+
+        if polls == WAIT_POLLS {
+            return Err(Error::Timeout);
+        }
+        let flags = self.read_register(ERROR_REG)?;
+        if flags & ERROR_FLAGS != 0 {
+            return Err(Error::Comm(flags));
+        }
+        if irq & irq_en & 0x01 != 0 {
+            return Err(Error::NoCard);
+        }
+
+        let mut answer = Answer { data: [0; ANSWER_LEN], len: 0, bits: 0 };
+        if command == PCD_TRANSCEIVE {
+            let level = self.read_register(FIFO_LEVEL_REG)? as usize;
+            let last_bits = (self.read_register(CONTROL_REG)? & 0x07) as usize;
+            answer.bits = match last_bits {
+                0 => level * 8,
+                _ => level.saturating_sub(1) * 8 + last_bits,
+            };
+            answer.len = level.min(ANSWER_LEN);
+            for i in 0..answer.len {
+                answer.data[i] = self.read_register(FIFO_DATA_REG)?;
+            }
+        }
+        Ok(answer)
+    }
+
+    // CRC_A of a frame, calculated by the reader's coprocessor
+    pub fn calculate_crc(&mut self, data: &[u8]) -> Result<[u8; 2], Error<SPI::Error>> {
+        self.clear_bit_mask(DIV_IRQ_REG, 0x04)?;
+        self.set_bit_mask(FIFO_LEVEL_REG, 0x80)?;
+        for &byte in data {
+            self.write_register(FIFO_DATA_REG, byte)?;
+        }
+        self.write_register(COMMAND_REG, PCD_CALCCRC)?;
+
+        let mut polls = 0;
+        while self.read_register(DIV_IRQ_REG)? & 0x04 == 0 {
+            polls += 1;
+            if polls == CRC_POLLS {
+                return Err(Error::Timeout);
+            }
+        }
+        Ok([self.read_register(CRC_RESULT_REG_L)?, self.read_register(CRC_RESULT_REG_M)?])
+    }
+
+    // Send a frame with its CRC appended
+    fn transceive_with_crc(&mut self, frame: &[u8]) -> Result<Answer, Error<SPI::Error>> {
+        let mut buf = [0u8; ANSWER_LEN];
+        let len = frame.len();
+        buf[..len].copy_from_slice(frame);
+        let crc = self.calculate_crc(frame)?;
+        buf[len..len + 2].copy_from_slice(&crc);
+        self.communicate(PCD_TRANSCEIVE, &buf[..len + 2])
+    }
+
+    // A 4 bit ACK, anything else is the NAK code
+    fn expect_ack(answer: &Answer) -> Result<(), Error<SPI::Error>> {
+        match answer.bytes().first() {
+            Some(ack) if answer.bits == 4 && ack & 0x0F == 0x0A => Ok(()),
+            Some(nak) if answer.bits == 4 => Err(Error::Nak(nak & 0x0F)),
+            _ => Err(Error::Protocol),
+        }
+    }
+
+    // REQA (PICC_REQIDL) or WUPA (PICC_REQALL). Returns the ATQA.
+    pub fn request(&mut self, req_mode: u8) -> Result<[u8; 2], Error<SPI::Error>> {
+        self.write_register(BIT_FRAMING_REG, 0x07)?;
+        let answer = self.communicate(PCD_TRANSCEIVE, &[req_mode])?;
+        match answer.bytes() {
+            &[atqa0, atqa1] if answer.bits == 16 => Ok([atqa0, atqa1]),
+            _ => Err(Error::Protocol),
+        }
+    }
+
+    // Cascade level 1 anticollision. Returns the 4 byte UID after checking its BCC.
+    pub fn anticoll(&mut self) -> Result<[u8; 4], Error<SPI::Error>> {
+        self.write_register(BIT_FRAMING_REG, 0x00)?;
+        let answer = self.communicate(PCD_TRANSCEIVE, &[PICC_ANTICOLL, 0x20])?;
+        match answer.bytes() {
+            &[a, b, c, d, bcc] if a ^ b ^ c ^ d == bcc => Ok([a, b, c, d]),
+            _ => Err(Error::Protocol),
+        }
+    }
+
+    // Select the card with this UID. Returns its SAK.
+    pub fn select(&mut self, uid: &[u8; 4]) -> Result<u8, Error<SPI::Error>> {
+        let bcc = uid.iter().fold(0, |bcc, byte| bcc ^ byte);
+        let frame = [PICC_SELECTTAG, 0x70, uid[0], uid[1], uid[2], uid[3], bcc];
+        let answer = self.transceive_with_crc(&frame)?;
+        match answer.bytes().first() {
+            Some(&sak) if answer.bits == 24 => Ok(sak),
+            _ => Err(Error::Protocol),
+        }
+    }
+
+    // Authenticate the sector of `block` with key A (PICC_AUTHENT1A) or B (PICC_AUTHENT1B)
+    pub fn authenticate(&mut self, auth_mode: u8, block: u8, key: &[u8; 6], uid: &[u8; 4]) -> Result<(), Error<SPI::Error>> {
+        let mut frame = [auth_mode, block, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        frame[2..8].copy_from_slice(key);
+        frame[8..12].copy_from_slice(uid);
+
+        let result = self.communicate(PCD_AUTHENT, &frame);
+        // The crypto1 flag is the only sign the card took the key
+        if self.read_register(STATUS2_REG)? & 0x08 == 0 {
+            return Err(match result {
+                Err(Error::Spi(e)) => Error::Spi(e),
+                _ => Error::AuthFailed,
+            });
+        }
+        result.map(|_| ())
+    }
+
+    pub fn stop_crypto1(&mut self) -> Result<(), Error<SPI::Error>> {
+        self.clear_bit_mask(STATUS2_REG, 0x08)
+    }
+
+    pub fn read(&mut self, block: u8) -> Result<[u8; 16], Error<SPI::Error>> {
+        self.write_register(BIT_FRAMING_REG, 0x00)?;
+        let answer = self.transceive_with_crc(&[PICC_READ, block])?;
+        if answer.bits == 4 {
+            Self::expect_ack(&answer)?;
+        }
+        let mut data = [0u8; 16];
+        data.copy_from_slice(answer.bytes().get(..16).ok_or(Error::Protocol)?);
+        Ok(data)
+    }
+
+    pub fn write(&mut self, block: u8, data: &[u8; 16]) -> Result<(), Error<SPI::Error>> {
+        self.write_register(BIT_FRAMING_REG, 0x00)?;
+        Self::expect_ack(&self.transceive_with_crc(&[PICC_WRITE, block])?)?;
+        Self::expect_ack(&self.transceive_with_crc(data)?)
+    }
+
+    // Put the card in HALT. It doesn't answer, so a timeout is the expected outcome.
+    pub fn halt(&mut self) -> Result<(), Error<SPI::Error>> {
+        self.write_register(BIT_FRAMING_REG, 0x00)?;
+        match self.transceive_with_crc(&[PICC_HALT, 0x00]) {
+            Err(Error::Spi(e)) => Err(Error::Spi(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+    use embedded_hal::spi::{ErrorType, Operation};
+    use std::collections::VecDeque;
+
+    // An MFRC522 with a card that answers each transceive with the next scripted frame
+    struct FakeReader {
+        registers: [u8; 0x40],
+        fifo: VecDeque<u8>,
+        answers: VecDeque<Vec<u8>>,
+        sent: Vec<Vec<u8>>,
+    }
+
+    impl FakeReader {
+        fn new(answers: &[&[u8]]) -> Self {
+            FakeReader {
+                registers: [0; 0x40],
+                fifo: VecDeque::new(),
+                answers: answers.iter().map(|answer| answer.to_vec()).collect(),
+                sent: Vec::new(),
+            }
+        }
+
+        fn read(&mut self, reg: u8) -> u8 {
+            match reg {
+                FIFO_DATA_REG => self.fifo.pop_front().unwrap_or(0),
+                FIFO_LEVEL_REG => self.fifo.len() as u8,
+                VERSION_REG => 0x92,
+                _ => self.registers[reg as usize],
+            }
+        }
+
+        fn write(&mut self, reg: u8, value: u8) {
+            match reg {
+                FIFO_DATA_REG => self.fifo.push_back(value),
+                FIFO_LEVEL_REG if value & 0x80 != 0 => self.fifo.clear(),
+                COMMAND_REG if value == PCD_CALCCRC => {
+                    let data: Vec<u8> = self.fifo.drain(..).collect();
+                    let crc = crc_a(&data);
+                    self.registers[CRC_RESULT_REG_L as usize] = crc[0];
+                    self.registers[CRC_RESULT_REG_M as usize] = crc[1];
+                    self.registers[DIV_IRQ_REG as usize] |= 0x04;
+                },
+                COMMAND_REG if value == PCD_AUTHENT => {
+                    self.sent.push(self.fifo.drain(..).collect());
+                    self.registers[STATUS2_REG as usize] |= 0x08;
+                    self.registers[COM_IRQ_REG as usize] = 0x10;
+                },
+                BIT_FRAMING_REG if value & 0x80 != 0 => {
+                    self.sent.push(self.fifo.drain(..).collect());
+                    match self.answers.pop_front() {
+                        Some(answer) => {
+                            // a 4 bit ACK or NAK
+                            self.registers[CONTROL_REG as usize] = if answer.len() == 1 && answer[0] < 0x10 { 4 } else { 0 };
+                            self.fifo.extend(answer);
+                            self.registers[COM_IRQ_REG as usize] = 0x30;
+                        },
+                        None => self.registers[COM_IRQ_REG as usize] = 0x01,
+                    }
+                    self.registers[reg as usize] = value;
+                },
+                _ => self.registers[reg as usize] = value,
+            }
+        }
+    }
+
+    impl ErrorType for FakeReader {
+        type Error = Infallible;
+    }
+
+    impl SpiDevice for FakeReader {
+        fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Infallible> {
+            for operation in operations {
+                match operation {
+                    Operation::TransferInPlace(buf) => buf[1] = self.read((buf[0] >> 1) & 0x3F),
+                    Operation::Write(buf) => self.write(buf[0] >> 1, buf[1]),
+                    _ => unimplemented!(),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn crc_a(data: &[u8]) -> [u8; 2] {
+        let crc = data.iter().fold(0x6363u16, |crc, &byte| {
+            let mut b = byte ^ (crc & 0xFF) as u8;
+            b ^= b << 4;
+            (crc >> 8) ^ ((b as u16) << 8) ^ ((b as u16) << 3) ^ ((b as u16) >> 4)
+        });
+        [(crc & 0xFF) as u8, (crc >> 8) as u8]
+    }
+
+    struct NoDelay;
+
+    impl DelayNs for NoDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    #[test]
+    fn selects_and_reads_a_card() {
+        let block: Vec<u8> = (0..18).collect();
+        let sak = [0x08, 0xB6, 0xDD];
+        let answers: [&[u8]; 4] = [&[0x04, 0x00], &[0xDE, 0xAD, 0xBE, 0xEF, 0x22], &sak, &block];
+        let mut reader = Mfrc522::new(FakeReader::new(&answers), NoDelay);
+
+        reader.init().unwrap();
+        assert_eq!(reader.version().unwrap(), 0x92);
+        assert_eq!(reader.request(PICC_REQIDL).unwrap(), [0x04, 0x00]);
+        let uid = reader.anticoll().unwrap();
+        assert_eq!(uid, [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(reader.select(&uid).unwrap(), 0x08);
+        reader.authenticate(PICC_AUTHENT1A, 4, &[0xFF; 6], &uid).unwrap();
+        assert_eq!(reader.read(4).unwrap()[..], block[..16]);
+
+        let (fake, _) = reader.release();
+        assert_eq!(fake.sent[2][..7], [PICC_SELECTTAG, 0x70, 0xDE, 0xAD, 0xBE, 0xEF, 0x22]);
+        assert_eq!(fake.sent[3], [PICC_AUTHENT1A, 4, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(fake.sent[4].len(), 4);
+    }
+
+    #[test]
+    fn reports_bad_answers() {
+        let answers: [&[u8]; 3] = [&[0xDE, 0xAD, 0xBE, 0xEF, 0x00], &[0x0A], &[0x04]];
+        let mut reader = Mfrc522::new(FakeReader::new(&answers), NoDelay);
+
+        assert_eq!(reader.anticoll(), Err(Error::Protocol));
+        assert_eq!(reader.write(4, &[0; 16]), Err(Error::Nak(0x04)));
+        assert_eq!(reader.request(PICC_REQIDL), Err(Error::NoCard));
+    }
+}
@@ -0,0 +1,145 @@
+// Run the embedded-hal driver over a Transport, e.g. rppal's Spi on a Pi
+use std::error::Error;
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::spi::{self, ErrorKind, ErrorType, Operation, SpiDevice};
+
+use crate::transport::Transport;
+
+// A Transport error, carried through embedded-hal
+#[derive(Debug)]
+pub struct TransportError(pub Box<dyn Error>);
+
+impl fmt::Display for TransportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl spi::Error for TransportError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+// A Transport as an SpiDevice. The operations of a transaction between two delays go
+// out as one transfer, so chip select stays asserted across them as the trait asks.
+pub struct TransportDevice<T>(pub T);
+
+impl<T: Transport> ErrorType for TransportDevice<T> {
+    type Error = TransportError;
+}
+
+impl<T: Transport> TransportDevice<T> {
+    // One transfer for a run of operations without delays, the answer is handed back
+    // to the read buffers
+    fn run(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), TransportError> {
+        let mut tx = Vec::new();
+        for operation in operations.iter() {
+            match operation {
+                Operation::Read(read) => tx.resize(tx.len() + read.len(), 0),
+                Operation::Write(write) => tx.extend_from_slice(write),
+                Operation::Transfer(read, write) => {
+                    let start = tx.len();
+                    tx.extend_from_slice(write);
+                    tx.resize(start + read.len().max(write.len()), 0);
+                },
+                Operation::TransferInPlace(buf) => tx.extend_from_slice(buf),
+                Operation::DelayNs(_) => {},
+            }
+        }
+        if tx.is_empty() {
+            return Ok(());
+        }
+
+        let mut rx = vec![0u8; tx.len()];
+        self.0.transfer(&mut rx, &tx).map_err(TransportError)?;
+
+        let mut at = 0;
+        for operation in operations.iter_mut() {
+            match operation {
+                Operation::Read(read) => {
+                    read.copy_from_slice(&rx[at..at + read.len()]);
+                    at += read.len();
+                },
+                Operation::Write(write) => at += write.len(),
+                Operation::Transfer(read, write) => {
+                    read.copy_from_slice(&rx[at..at + read.len()]);
+                    at += read.len().max(write.len());
+                },
+                Operation::TransferInPlace(buf) => {
+                    buf.copy_from_slice(&rx[at..at + buf.len()]);
+                    at += buf.len();
+                },
+                Operation::DelayNs(_) => {},
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Transport> SpiDevice for TransportDevice<T> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), TransportError> {
+        // A delay ends the transfer before it, the Transport can't hold chip select
+        let mut rest = operations;
+        while let Some(split) = rest.iter().position(|operation| matches!(operation, Operation::DelayNs(_))) {
+            let (before, after) = rest.split_at_mut(split);
+            self.run(before)?;
+            if let Operation::DelayNs(ns) = after[0] {
+                thread::sleep(Duration::from_nanos(ns as u64));
+            }
+            rest = &mut after[1..];
+        }
+        self.run(rest)
+    }
+}
+
+// DelayNs with thread::sleep
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdDelay;
+
+impl DelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns as u64));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers every byte with its position in the transfer
+    struct Counter {
+        transfers: Vec<Vec<u8>>,
+    }
+
+    impl Transport for Counter {
+        fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>> {
+            self.transfers.push(tx.to_vec());
+            for (i, byte) in rx.iter_mut().enumerate() {
+                *byte = i as u8;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn joins_operations_into_one_transfer() {
+        let mut device = TransportDevice(Counter { transfers: Vec::new() });
+        let mut read = [0u8; 2];
+        let mut in_place = [0x92, 0x00];
+        device.transaction(&mut [
+            Operation::Write(&[0x12, 0x34]),
+            Operation::Read(&mut read),
+            Operation::DelayNs(1),
+            Operation::TransferInPlace(&mut in_place),
+        ]).unwrap();
+
+        assert_eq!(read, [2, 3]);
+        assert_eq!(in_place, [0, 1]);
+        assert_eq!(device.0.transfers, vec![vec![0x12, 0x34, 0, 0], vec![0x92, 0x00]]);
+    }
+}
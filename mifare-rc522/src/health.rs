@@ -0,0 +1,30 @@
+use std::fmt;
+
+use super::constants::*;
+use super::register::read_register;
+use super::transport::Transport;
+
+// Whether the MFRC522 is answering on the SPI bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderStatus {
+    Connected,
+    Disconnected,
+}
+
+impl fmt::Display for ReaderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReaderStatus::Connected => write!(f, "Connected"),
+            ReaderStatus::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+// Check the reader by reading its version register. A missing or unpowered
+// chip leaves MISO floating, which reads back as 0x00 or 0xFF.
+pub fn reader_status(spi: &mut dyn Transport) -> ReaderStatus {
+    match read_register(spi, VERSION_REG) {
+        Ok(0x00) | Ok(0xFF) | Err(_) => ReaderStatus::Disconnected,
+        Ok(_) => ReaderStatus::Connected,
+    }
+}
@@ -0,0 +1,65 @@
+use std::error::Error;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use super::constants::*;
+use super::operations::forget_authentication;
+use super::register::*;
+use super::transport::Transport;
+
+// SPI clock used until a speed has been negotiated (see negotiate_speed)
+pub const SPI_SPEED_HZ: u32 = 1_000_000;
+
+// Speed of the last opened SPI device, reconnecting reopens at this speed
+static CURRENT_SPEED_HZ: AtomicU32 = AtomicU32::new(SPI_SPEED_HZ);
+
+pub fn current_speed() -> u32 {
+    CURRENT_SPEED_HZ.load(Ordering::Relaxed)
+}
+
+// Called by whatever opened the bus, so a reconnect reopens it at the same speed
+pub fn set_current_speed(speed_hz: u32) {
+    CURRENT_SPEED_HZ.store(speed_hz, Ordering::Relaxed);
+}
+
+// Initialize the MFRC522
+pub fn mfrc522_init(spi: &mut dyn Transport) -> Result<(), Box<dyn Error>> {
+    // Soft reset, which also ends any crypto session
+    forget_authentication(spi);
+    write_register(spi, COMMAND_REG, PCD_RESETPHASE)?;
+    thread::sleep(Duration::from_millis(50));
+    
+    // Timer configurations
+    write_register(spi, T_MODE_REG, 0x8D)?;
+    write_register(spi, T_PRESCALER_REG, 0x3E)?;
+    write_register(spi, T_RELOAD_REG_L, 30)?;
+    write_register(spi, T_RELOAD_REG_H, 0)?;
+    
+    // Auto configurations
+    write_register(spi, TX_AUTO_REG, 0x40)?;
+    write_register(spi, MODE_REG, 0x3D)?;
+    
+    // FIFO alert level for bulk transfers (see mfrc522_to_card)
+    write_register(spi, WATER_LEVEL_REG, WATER_LEVEL)?;
+    
+    // Turn on the antenna
+    antenna_on(spi)?;
+    
+    Ok(())
+}
+
+// Turn antenna on
+pub fn antenna_on(spi: &mut dyn Transport) -> Result<(), Box<dyn Error>> {
+    let temp = read_register(spi, TX_CONTROL_REG)?;
+    if (temp & 0x03) != 0x03 {
+        set_bit_mask(spi, TX_CONTROL_REG, 0x03)?;
+    }
+    Ok(())
+}
+
+// Turn antenna off
+pub fn antenna_off(spi: &mut dyn Transport) -> Result<(), Box<dyn Error>> {
+    clear_bit_mask(spi, TX_CONTROL_REG, 0x03)?;
+    Ok(())
+}
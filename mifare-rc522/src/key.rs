@@ -0,0 +1,43 @@
+// A sector key of a Mifare Classic
+//
+// mfrc522_auth takes the key as bytes, Key is how the programs above the driver pass
+// keys around, parse them from key files and print them.
+use core::fmt;
+
+// A 6 byte sector key, Key A or Key B
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Key(pub [u8; 6]);
+
+impl Key {
+    // The key of a card in transport configuration
+    pub const TRANSPORT: Key = Key([0xFF; 6]);
+
+    // 12 hex digits, in either case
+    pub fn from_hex(word: &str) -> Option<Self> {
+        if word.len() != 12 || !word.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let mut key = [0u8; 6];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&word[i * 2..i * 2 + 2], 16).ok()?;
+        }
+        Some(Key(key))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 6]> for Key {
+    fn from(key: [u8; 6]) -> Self {
+        Key(key)
+    }
+}
+
+// The 12 hex digits, as key files have them
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02X}", byte))
+    }
+}
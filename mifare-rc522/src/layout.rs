@@ -0,0 +1,257 @@
+// Where the sectors and blocks of a Mifare Classic are
+//
+// A Classic Mini has 5 sectors of 4 blocks and a Classic 1K 16 of them. A Classic 4K
+// has 32 sectors of 4 blocks (blocks 0-127), then 8 sectors of 16 blocks (blocks
+// 128-255). The last block of every sector is its trailer, and in the large sectors
+// each of the three data access conditions covers 5 blocks instead of one.
+//
+// Blocks below 128 are in the same place on every Classic, so the block math on
+// BlockAddr and SectorIndex works without knowing the card. What a card has (sectors,
+// blocks) comes from its layout, found from the SAK or the size of a dump.
+//
+// Block addresses and sector numbers are kept apart as BlockAddr and SectorIndex, so
+// one can't be passed for the other. The MFRC522 commands take the plain byte.
+// Everything here is core only, so the embedded-hal driver can use it without std.
+use core::fmt;
+
+// First block of the 16 block sectors of a 4K
+const LARGE_SECTORS_START: u16 = 128;
+// Sectors of 4 blocks before them
+const SMALL_SECTORS: u8 = 32;
+
+// Sectors of the largest Classic, the 4K
+const MAX_SECTORS: u8 = 40;
+
+// A block address, 0-255 (blocks past 63 are on a 4K only)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BlockAddr(pub u8);
+
+// A sector number, 0-39 (sectors past 15 are on a 4K only)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct SectorIndex(u8);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CardLayout {
+    ClassicMini,
+    Classic1K,
+    Classic4K,
+}
+
+// SAKs a Classic answers the select with. Bit 7 is set by some Infineon cards and bit 5
+// by cards that also speak ISO 14443-4 (SmartMX with Classic emulation).
+const SAK_LAYOUTS: [(u8, CardLayout); 8] = [
+    (0x09, CardLayout::ClassicMini),
+    (0x89, CardLayout::ClassicMini),
+    (0x08, CardLayout::Classic1K),
+    (0x88, CardLayout::Classic1K),
+    (0x28, CardLayout::Classic1K),
+    (0x18, CardLayout::Classic4K),
+    (0x98, CardLayout::Classic4K),
+    (0x38, CardLayout::Classic4K),
+];
+
+impl BlockAddr {
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    // Index into a dump's blocks
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    // Sector the block is in
+    pub fn sector(self) -> SectorIndex {
+        if (self.0 as u16) < LARGE_SECTORS_START {
+            SectorIndex(self.0 / 4)
+        } else {
+            SectorIndex(SMALL_SECTORS + (self.0 - LARGE_SECTORS_START as u8) / 16)
+        }
+    }
+
+    pub fn is_trailer(self) -> bool {
+        self.sector().trailer() == self
+    }
+
+    // Block 0, the UID and manufacturer data
+    pub fn is_manufacturer(self) -> bool {
+        self.0 == 0
+    }
+
+    // Position of the block in its sector
+    pub fn offset(self) -> u8 {
+        self.0 - self.sector().first_block().0
+    }
+
+    // Which access condition of the trailer covers the block: 0-2 for data blocks
+    // (blocks 0-4, 5-9 and 10-14 of a 16 block sector), 3 for the trailer
+    pub fn access_group(self) -> usize {
+        let offset = self.offset() as usize;
+        match self.sector().block_count() {
+            _ if self.is_trailer() => 3,
+            4 => offset,
+            _ => offset / 5,
+        }
+    }
+}
+
+impl From<u8> for BlockAddr {
+    fn from(block: u8) -> Self {
+        BlockAddr(block)
+    }
+}
+
+impl fmt::Display for BlockAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl SectorIndex {
+    // Sector 0, with the manufacturer block
+    pub const FIRST: SectorIndex = SectorIndex(0);
+
+    // None past the last sector of a 4K
+    pub fn new(sector: u8) -> Option<Self> {
+        (sector < MAX_SECTORS).then_some(SectorIndex(sector))
+    }
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+
+    // Index into tables kept per sector
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    pub fn block_count(self) -> u8 {
+        if self.0 < SMALL_SECTORS { 4 } else { 16 }
+    }
+
+    pub fn first_block(self) -> BlockAddr {
+        if self.0 < SMALL_SECTORS {
+            BlockAddr(self.0 * 4)
+        } else {
+            BlockAddr((LARGE_SECTORS_START + (self.0 - SMALL_SECTORS) as u16 * 16) as u8)
+        }
+    }
+
+    pub fn trailer(self) -> BlockAddr {
+        BlockAddr(self.first_block().0 + (self.block_count() - 1))
+    }
+
+    // Blocks of the sector, trailer last
+    pub fn blocks(self) -> impl DoubleEndedIterator<Item = BlockAddr> {
+        (self.first_block().0..=self.trailer().0).map(BlockAddr)
+    }
+
+    // Blocks of the sector without the trailer
+    pub fn data_blocks(self) -> impl DoubleEndedIterator<Item = BlockAddr> {
+        (self.first_block().0..self.trailer().0).map(BlockAddr)
+    }
+}
+
+impl fmt::Display for SectorIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl CardLayout {
+    pub fn name(self) -> &'static str {
+        match self {
+            CardLayout::ClassicMini => "Classic Mini",
+            CardLayout::Classic1K => "Classic 1K",
+            CardLayout::Classic4K => "Classic 4K",
+        }
+    }
+
+    // From the SAK the card answered the select with, None for cards that aren't a Classic
+    pub fn from_sak(sak: u8) -> Option<Self> {
+        SAK_LAYOUTS.iter().find(|(known, _)| *known == sak).map(|(_, layout)| *layout)
+    }
+
+    // From the SAK, a 1K when the SAK isn't one of a Classic (clones often answer oddly)
+    pub fn from_sak_or_1k(sak: u8) -> Self {
+        CardLayout::from_sak(sak).unwrap_or(CardLayout::Classic1K)
+    }
+
+    // The layout a dump of this many blocks was read from
+    pub fn from_block_count(blocks: usize) -> Self {
+        if blocks > CardLayout::Classic1K.block_count() {
+            CardLayout::Classic4K
+        } else if blocks > CardLayout::ClassicMini.block_count() {
+            CardLayout::Classic1K
+        } else {
+            CardLayout::ClassicMini
+        }
+    }
+
+    pub fn sector_count(self) -> u8 {
+        match self {
+            CardLayout::ClassicMini => 5,
+            CardLayout::Classic1K => 16,
+            CardLayout::Classic4K => MAX_SECTORS,
+        }
+    }
+
+    pub fn block_count(self) -> usize {
+        match self {
+            CardLayout::ClassicMini => 20,
+            CardLayout::Classic1K => 64,
+            CardLayout::Classic4K => 256,
+        }
+    }
+
+    // The card's sectors, in order
+    pub fn sectors(self) -> impl DoubleEndedIterator<Item = SectorIndex> {
+        (0..self.sector_count()).map(SectorIndex)
+    }
+
+    // The card's blocks, in order
+    pub fn blocks(self) -> impl DoubleEndedIterator<Item = BlockAddr> {
+        (0..self.block_count()).map(|block| BlockAddr(block as u8))
+    }
+
+    // The sector with this number on the card, None past its last sector
+    pub fn sector(self, sector: u8) -> Option<SectorIndex> {
+        (sector < self.sector_count()).then_some(SectorIndex(sector))
+    }
+
+    pub fn has_sector(self, sector: SectorIndex) -> bool {
+        sector.0 < self.sector_count()
+    }
+
+    pub fn has_block(self, block: BlockAddr) -> bool {
+        block.index() < self.block_count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_sectors_and_trailers() {
+        assert_eq!(BlockAddr(0).sector().get(), 0);
+        assert_eq!(BlockAddr(63).sector().get(), 15);
+        assert_eq!(BlockAddr(127).sector().get(), 31);
+        assert_eq!(BlockAddr(128).sector().get(), 32);
+        assert_eq!(BlockAddr(255).sector().get(), 39);
+
+        assert!([3, 127, 143, 255].iter().all(|&block| BlockAddr(block).is_trailer()));
+        assert!([0, 131, 128].iter().all(|&block| !BlockAddr(block).is_trailer()));
+    }
+
+    #[test]
+    fn large_sectors_have_16_blocks() {
+        let sector = SectorIndex::new(39).unwrap();
+        assert_eq!(sector.first_block(), BlockAddr(240));
+        assert_eq!(sector.trailer(), BlockAddr(255));
+        assert_eq!(BlockAddr(245).access_group(), 1);
+        assert_eq!(CardLayout::Classic4K.sectors().map(|sector| sector.block_count() as usize).sum::<usize>(), 256);
+        assert_eq!(CardLayout::from_sak(0x18), Some(CardLayout::Classic4K));
+        assert_eq!(CardLayout::from_sak_or_1k(0x20), CardLayout::Classic1K);
+    }
+}
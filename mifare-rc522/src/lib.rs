@@ -0,0 +1,127 @@
+//! Driver for the MFRC522 reader and the Mifare Classic commands it carries.
+//!
+//! The reader core of the NFC block editor: register access, card transactions with
+//! FIFO streaming, request, anticollision, select, authentication (with reuse of the
+//! sector already open), block reads and writes, gen1a backdoor, SPI speed
+//! negotiation and signal quality metrics. No GUI or database dependencies.
+//!
+//! [`mfrc522_enumerate`] reads every card in the field in one polling cycle, resolving
+//! collisions bit by bit through the cascade levels of 7 byte UIDs, so a stack of
+//! Ultralight or NTAG tags is read in one pass.
+//!
+//! Everything goes through the [`Transport`] trait, one full duplex transfer per
+//! register access. With the `rppal` feature it is implemented for
+//! `rppal::spi::Spi`, and [`open_spi`], [`ensure_connected`] and [`negotiate_speed`]
+//! open the reader wired to SPI0, CE0 of a Raspberry Pi.
+//!
+//! A [`Reader`] keeps a transport together with the state of the reader behind it,
+//! the sector its card has open, so an authentication there with the same key isn't
+//! sent again. [`open_spi`] returns one; a bare transport authenticates every time.
+//!
+//! The `embedded-hal` feature adds [`Mfrc522`], a driver over embedded-hal 1.0
+//! `SpiDevice`, `DelayNs` and `OutputPin`. It needs neither std nor an allocator, so
+//! with `default-features = false` it runs on microcontrollers. On a Pi,
+//! `hal::TransportDevice` and `hal::StdDelay` run it over rppal.
+//!
+//! [`BlockAddr`], [`SectorIndex`] and [`CardLayout`] know where the sectors, blocks and
+//! trailers of a Classic Mini, 1K and 4K are, and [`Key`] is a sector key. They need
+//! neither std nor a reader.
+//!
+//! The `mock` feature adds `mock::MockReader`, a simulated MFRC522 with one or more
+//! `mock::MockCard`s in its field, so card flows can be tested without hardware.
+//!
+//! ```no_run
+//! # #[cfg(feature = "rppal")]
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use mifare_rc522::*;
+//!
+//! let mut spi = open_spi(SPI_SPEED_HZ)?;
+//! mfrc522_init(&mut spi)?;
+//!
+//! let (status, _) = mfrc522_request(&mut spi, PICC_REQIDL)?;
+//! if status == MI_OK {
+//!     let (status, uid) = mfrc522_anticoll(&mut spi)?;
+//!     if status == MI_OK {
+//!         mfrc522_select_tag(&mut spi, &uid)?;
+//!         let key = [0xFF; 6];
+//!         if mfrc522_auth(&mut spi, PICC_AUTHENT1A, 4, &key, &uid)? == MI_OK {
+//!             println!("{:02X?}", mfrc522_read(&mut spi, 4)?);
+//!         }
+//!         mfrc522_stop_crypto1(&mut spi)?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "rppal"))]
+//! # fn main() {}
+//! ```
+//!
+//! # Stability
+//!
+//! The crate follows semver. Until 1.0 a change to any public item (the functions and
+//! constants re-exported here, [`Transport`], [`Reader`], [`Authentication`], [`ReaderStatus`],
+//! [`SignalMetrics`], [`SpeedConfig`], [`DetectedCard`], [`Mfrc522`], [`BlockAddr`], [`SectorIndex`],
+//! [`CardLayout`], [`Key`] and `hal::Error`) bumps the minor
+//! version, and patch releases only fix bugs. The `rppal` feature follows the same rules, and a new rppal major
+//! version is a minor release of this crate. Every release is listed in CHANGELOG.md.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod constants;
+pub mod layout;
+pub mod key;
+#[cfg(feature = "embedded-hal")]
+pub mod hal;
+#[cfg(feature = "std")]
+pub mod transport;
+#[cfg(feature = "std")]
+pub mod register;
+#[cfg(feature = "std")]
+pub mod init;
+#[cfg(feature = "std")]
+pub mod communication;
+#[cfg(feature = "std")]
+pub mod operations;
+#[cfg(feature = "std")]
+pub mod anticollision;
+#[cfg(feature = "std")]
+pub mod block;
+#[cfg(feature = "std")]
+pub mod health;
+#[cfg(feature = "std")]
+pub mod speed;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "rppal")]
+pub mod rpi;
+#[cfg(feature = "mock")]
+pub mod mock;
+
+// Re-export common items
+pub use constants::*;
+pub use layout::{BlockAddr, CardLayout, SectorIndex};
+pub use key::Key;
+#[cfg(feature = "embedded-hal")]
+pub use hal::Mfrc522;
+#[cfg(feature = "std")]
+pub use std_api::*;
+#[cfg(feature = "rppal")]
+pub use rpi::{open_spi, reconnect, ensure_connected, negotiate_speed, SpeedSetting, SpiReader};
+
+#[cfg(feature = "std")]
+mod std_api {
+    pub use crate::transport::{Reader, ReaderState, Transport};
+    pub use crate::register::{read_register, write_register, read_register_burst, write_register_burst,
+                              set_bit_mask, clear_bit_mask};
+    pub use crate::init::{mfrc522_init, antenna_on, antenna_off, current_speed, set_current_speed, SPI_SPEED_HZ};
+    pub use crate::communication::{mfrc522_to_card, calculate_crc};
+    pub use crate::operations::{mfrc522_request, mfrc522_anticoll, mfrc522_select_tag,
+                                mfrc522_auth, mfrc522_stop_crypto1, mfrc522_halt,
+                                mfrc522_open_backdoor, last_authentication, forget_authentication,
+                                end_auth_session, Authentication};
+    pub use crate::anticollision::{mfrc522_select_card, mfrc522_enumerate, DetectedCard, MAX_CARDS};
+    pub use crate::block::{mfrc522_read, mfrc522_write};
+    pub use crate::health::{ReaderStatus, reader_status};
+    pub use crate::speed::{link_test, reader_id, take_error_counts, SpeedConfig, SPEED_STEPS};
+    pub use crate::metrics::{SignalMetrics, signal_metrics, reset_signal_metrics, signal_log_path};
+}
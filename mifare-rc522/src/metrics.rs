@@ -0,0 +1,269 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::constants::*;
+
+// ErrorReg flags
+const PROTOCOL_ERR: u8 = 0x01;
+const PARITY_ERR: u8 = 0x02;
+const CRC_ERR: u8 = 0x04;
+pub(crate) const COLL_ERR: u8 = 0x08;
+const BUFFER_OVFL: u8 = 0x10;
+const TEMP_ERR: u8 = 0x40;
+
+// Share of transactions with RF errors above which the link is called unreliable
+const RF_ERROR_LIMIT: f64 = 0.05;
+
+// How one card transaction ended, as seen by mfrc522_to_card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionOutcome {
+    // The card answered cleanly
+    Answered,
+    // The reader's timer ran out: no card, or the card didn't hear the reader
+    NoAnswer,
+    // The wait loop gave up before the reader finished the command
+    Timeout,
+    // The error register flagged the answer
+    Error,
+}
+
+// Signal quality counters since the start of the run (or the last reset). RF problems
+// show up as error register flags and timeouts, key problems as authentications the
+// card refused although the link was clean.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignalMetrics {
+    pub transactions: usize,
+    pub no_answer: usize,
+    pub timeouts: usize,
+    // Transactions the error register flagged, each flag is counted below
+    pub flagged: usize,
+    pub protocol_errors: usize,
+    pub parity_errors: usize,
+    pub crc_errors: usize,
+    pub collisions: usize,
+    pub buffer_overflows: usize,
+    pub overheating: usize,
+    pub auth_failures: usize,
+    // Reconnection attempts and SPI speed fall-backs
+    pub retries: usize,
+}
+
+static METRICS: Mutex<SignalMetrics> = Mutex::new(SignalMetrics {
+    transactions: 0,
+    no_answer: 0,
+    timeouts: 0,
+    flagged: 0,
+    protocol_errors: 0,
+    parity_errors: 0,
+    crc_errors: 0,
+    collisions: 0,
+    buffer_overflows: 0,
+    overheating: 0,
+    auth_failures: 0,
+    retries: 0,
+});
+
+impl SignalMetrics {
+    // Transactions that failed on the radio link: flagged by the error register or
+    // cut off by the wait loop. A missing card is not an RF error.
+    pub fn rf_errors(&self) -> usize {
+        self.timeouts + self.flagged
+    }
+
+    pub fn rf_error_rate(&self) -> f64 {
+        if self.transactions == 0 {
+            0.0
+        } else {
+            self.rf_errors() as f64 / self.transactions as f64
+        }
+    }
+
+    // What changed since `earlier`, e.g. over one scan
+    pub fn since(&self, earlier: &SignalMetrics) -> SignalMetrics {
+        SignalMetrics {
+            transactions: self.transactions.saturating_sub(earlier.transactions),
+            no_answer: self.no_answer.saturating_sub(earlier.no_answer),
+            timeouts: self.timeouts.saturating_sub(earlier.timeouts),
+            flagged: self.flagged.saturating_sub(earlier.flagged),
+            protocol_errors: self.protocol_errors.saturating_sub(earlier.protocol_errors),
+            parity_errors: self.parity_errors.saturating_sub(earlier.parity_errors),
+            crc_errors: self.crc_errors.saturating_sub(earlier.crc_errors),
+            collisions: self.collisions.saturating_sub(earlier.collisions),
+            buffer_overflows: self.buffer_overflows.saturating_sub(earlier.buffer_overflows),
+            overheating: self.overheating.saturating_sub(earlier.overheating),
+            auth_failures: self.auth_failures.saturating_sub(earlier.auth_failures),
+            retries: self.retries.saturating_sub(earlier.retries),
+        }
+    }
+
+    // One line on what the counters point to
+    pub fn diagnosis(&self) -> &'static str {
+        if self.transactions == 0 {
+            "No card traffic yet"
+        } else if self.overheating > 0 {
+            "The reader overheated, check the antenna circuit and supply"
+        } else if self.collisions > 0 && self.collisions * 2 >= self.rf_errors() {
+            "Mostly collisions, more than one card is in the field"
+        } else if self.rf_error_rate() > RF_ERROR_LIMIT {
+            "RF problem: move the card closer, away from metal, or check the antenna"
+        } else if self.auth_failures > 0 {
+            "The link is clean, failures are keys the card refused"
+        } else {
+            "The link looks healthy"
+        }
+    }
+
+    // Short form for one line, e.g. after a scan
+    pub fn summary(&self) -> String {
+        format!("{} transactions, {} RF errors ({:.1}%), {} collisions, {} auth failures, {} retries",
+                self.transactions, self.rf_errors(), self.rf_error_rate() * 100.0,
+                self.collisions, self.auth_failures, self.retries)
+    }
+}
+
+impl fmt::Display for SignalMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Card transactions:    {}", self.transactions)?;
+        writeln!(f, "  no card answered:   {}", self.no_answer)?;
+        writeln!(f, "RF errors:            {} ({:.1}%)", self.rf_errors(), self.rf_error_rate() * 100.0)?;
+        writeln!(f, "  timeouts:           {}", self.timeouts)?;
+        writeln!(f, "  protocol errors:    {}", self.protocol_errors)?;
+        writeln!(f, "  parity errors:      {}", self.parity_errors)?;
+        writeln!(f, "  CRC errors:         {}", self.crc_errors)?;
+        writeln!(f, "  collisions:         {}", self.collisions)?;
+        writeln!(f, "  FIFO overflows:     {}", self.buffer_overflows)?;
+        writeln!(f, "Overheating:          {}", self.overheating)?;
+        writeln!(f, "Keys refused:         {}", self.auth_failures)?;
+        writeln!(f, "Retries:              {}", self.retries)?;
+        write!(f, "Diagnosis: {}", self.diagnosis())
+    }
+}
+
+// ~/.config/nfc-block-editor/signal.log (or $XDG_CONFIG_HOME)
+pub fn signal_log_path() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("signal.log")
+}
+
+// The counters as they are now
+pub fn signal_metrics() -> SignalMetrics {
+    METRICS.lock().map(|metrics| *metrics).unwrap_or_default()
+}
+
+pub fn reset_signal_metrics() {
+    if let Ok(mut metrics) = METRICS.lock() {
+        *metrics = SignalMetrics::default();
+    }
+}
+
+// Count one card transaction. `error_flags` is the error register after it.
+// Transactions with a problem also go to the signal log.
+pub fn record_outcome(command: u8, data: &[u8], outcome: TransactionOutcome, error_flags: u8) {
+    if let Ok(mut guard) = METRICS.lock() {
+        let metrics = &mut *guard;
+        metrics.transactions += 1;
+        match outcome {
+            TransactionOutcome::Answered => {},
+            TransactionOutcome::NoAnswer => metrics.no_answer += 1,
+            TransactionOutcome::Timeout => metrics.timeouts += 1,
+            TransactionOutcome::Error => {
+                metrics.flagged += 1;
+                let flags = [
+                    (PROTOCOL_ERR, &mut metrics.protocol_errors),
+                    (PARITY_ERR, &mut metrics.parity_errors),
+                    (CRC_ERR, &mut metrics.crc_errors),
+                    (COLL_ERR, &mut metrics.collisions),
+                    (BUFFER_OVFL, &mut metrics.buffer_overflows),
+                ];
+                for (flag, counter) in flags {
+                    if error_flags & flag != 0 {
+                        *counter += 1;
+                    }
+                }
+            },
+        }
+        if error_flags & TEMP_ERR != 0 {
+            metrics.overheating += 1;
+        }
+    }
+
+    if matches!(outcome, TransactionOutcome::Timeout | TransactionOutcome::Error) {
+        log_line(&format!("{}\t{:?}\t{}", command_name(command, data), outcome, describe_flags(error_flags)));
+    }
+}
+
+// Count an authentication the card refused on a clean link
+pub fn record_auth_failure(block_addr: u8) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        metrics.auth_failures += 1;
+    }
+    log_line(&format!("auth block {}\tKeyRefused\t-", block_addr));
+}
+
+// Count a reconnection attempt or a lower SPI speed
+pub fn record_retry(reason: &str) {
+    if let Ok(mut metrics) = METRICS.lock() {
+        metrics.retries += 1;
+    }
+    log_line(&format!("retry\t{}\t-", reason));
+}
+
+// this names the command from the PICC command byte
+fn command_name(command: u8, data: &[u8]) -> String {
+    if command == PCD_AUTHENT {
+        return match data.get(1) {
+            Some(block) => format!("auth block {}", block),
+            None => "auth".to_string(),
+        };
+    }
+    match (data.first(), data.get(1)) {
+        (Some(&PICC_REQIDL), _) => "request".to_string(),
+        (Some(&PICC_REQALL), _) => "wakeup".to_string(),
+        (Some(&PICC_ANTICOLL), Some(0x20)) => "anticoll".to_string(),
+        (Some(&PICC_SELECTTAG), Some(0x70)) => "select".to_string(),
+        (Some(&PICC_READ), Some(block)) => format!("read block {}", block),
+        (Some(&PICC_WRITE), Some(block)) => format!("write block {}", block),
+        (Some(&PICC_HALT), _) => "halt".to_string(),
+        (Some(byte), _) => format!("transceive {:02X}", byte),
+        (None, _) => "transceive".to_string(),
+    }
+}
+
+// this spells out the error register flags
+fn describe_flags(error_flags: u8) -> String {
+    let names = [
+        (PROTOCOL_ERR, "protocol"),
+        (PARITY_ERR, "parity"),
+        (CRC_ERR, "crc"),
+        (COLL_ERR, "collision"),
+        (BUFFER_OVFL, "overflow"),
+        (TEMP_ERR, "temperature"),
+    ];
+    let set: Vec<&str> = names.iter()
+        .filter(|(flag, _)| error_flags & flag != 0)
+        .map(|(_, name)| *name)
+        .collect();
+    if set.is_empty() {
+        "-".to_string()
+    } else {
+        format!("{:02X} {}", error_flags, set.join(","))
+    }
+}
+
+// this appends one tab separated line with the time, a log that can't be written is
+// skipped rather than interrupting the card operation
+fn log_line(entry: &str) {
+    let path = signal_log_path();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_secs()).unwrap_or(0);
+    let _ = path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| OpenOptions::new().create(true).append(true).open(&path))
+        .and_then(|mut log| writeln!(log, "{}\t{}", now, entry));
+}
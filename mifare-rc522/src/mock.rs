@@ -0,0 +1,701 @@
+// A simulated MFRC522 with Mifare cards in its field (the `mock` feature), so whole
+// card flows run in tests without a Pi or a card.
+//
+// MockReader implements Transport at the register level: the FIFO, the interrupt
+// flags, CalcCRC, Transceive and MFAuthent behave like the chip as far as the driver
+// here and the attack toolkit use them. A Classic 1K answers REQA/WUPA,
+// anticollision, select, authentication, reads, writes and HALT from the blocks it
+// holds, and a magic card opens the gen1a backdoor. An Ultralight only has its 7 byte
+// UID, it goes through both cascade levels of anticollision and select, and HALT.
+// Several cards can be stacked in the field: their answers are merged bit by bit, and
+// where they differ the reader sees a collision, with CollReg and ErrorReg set.
+//
+// Simplifications: the link is never encrypted (the reader's Crypto1 flag only says
+// an authentication succeeded), access bits are not enforced apart from block 0 being
+// read-only, and the card's PRNG advances a fixed number of steps per frame instead of
+// with time, so a weak card answers the same way every time it is powered up.
+use std::collections::VecDeque;
+use std::error::Error;
+
+use super::layout::{BlockAddr, SectorIndex};
+use super::constants::*;
+use super::metrics::COLL_ERR;
+use super::speed::crc_a;
+use super::transport::{ReaderState, Transport};
+
+// The blocks of a Classic 1K
+const BLOCKS: usize = 64;
+// What a card answers REQA/WUPA and select with
+const ATQA: [u8; 2] = [0x04, 0x00];
+const SAK: u8 = 0x08;
+// What an Ultralight or NTAG answers REQA/WUPA and the last select with
+const ULTRALIGHT_ATQA: [u8; 2] = [0x44, 0x00];
+const ULTRALIGHT_SAK: u8 = 0x00;
+// Select acknowledge of a cascade level the UID doesn't end at
+const SAK_CASCADE: u8 = 0x04;
+// Select commands of cascade levels 1 and 2
+const CASCADE_SELECTS: [u8; 2] = [PICC_ANTICOLL, PICC_SEL_CL2];
+// 4 bit ACK and NAK
+const ACK: u8 = 0x0A;
+const NAK: u8 = 0x04;
+// Manufacturer data of block 0, as Chinese clones ship it
+const MANUFACTURER_DATA: [u8; 8] = [0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69];
+// Transport configuration trailer: key A, access bits FF 07 80 69, key B
+const TRANSPORT_TRAILER: [u8; 16] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x07, 0x80, 0x69, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+];
+
+// First nonce of the weak PRNG at power-up
+const POWER_UP_NONCE: u32 = 0x0120_0145;
+// PRNG steps that pass while the card takes in one frame
+const STEPS_PER_FRAME: u32 = 160;
+
+// MFRC522 reset values that aren't 0
+const VERSION: u8 = 0x92;
+const TX_CONTROL_RESET: u8 = 0x80;
+
+// The bits of `data` in the order they are sent, least significant first. `last_bits`
+// are the valid bits of the last byte (0 for all 8).
+fn to_bits(data: &[u8], last_bits: u8) -> Vec<bool> {
+    let mut bits = Vec::with_capacity(data.len() * 8);
+    for (i, byte) in data.iter().enumerate() {
+        let count = if i + 1 == data.len() && last_bits != 0 { last_bits } else { 8 };
+        bits.extend((0..count).map(|bit| (byte >> bit) & 1 != 0));
+    }
+    bits
+}
+
+// Where two cards' answers part, the shorter one ending counts too
+fn first_difference(a: &[bool], b: &[bool]) -> Option<usize> {
+    match a.iter().zip(b).position(|(a, b)| a != b) {
+        Some(position) => Some(position),
+        None if a.len() != b.len() => Some(a.len().min(b.len())),
+        None => None,
+    }
+}
+
+// The weak PRNG `steps` clock cycles after `nonce` (x^16 + x^14 + x^13 + x^11 + 1)
+fn prng_successor(nonce: u32, steps: u32) -> u32 {
+    let mut x = nonce.swap_bytes();
+    for _ in 0..steps {
+        x = (x >> 1) | (((x >> 16) ^ (x >> 18) ^ (x >> 19) ^ (x >> 21)) & 1) << 31;
+    }
+    x.swap_bytes()
+}
+
+// How the card picks its authentication nonces
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Prng {
+    // The 16 bit LFSR of original cards, restarted at power-up
+    Weak,
+    // The same nonce every time, like some clone chips
+    Static(u32),
+    // Unpredictable nonces, like MIFARE Classic EV1
+    Hard,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CardState {
+    Off,
+    Idle,
+    // Taking part in anticollision at a cascade level, from 0
+    Ready(u8),
+    Active,
+    Halted,
+    // Sent a nonce for an authentication the reader never finished
+    AuthPending,
+    Authenticated(SectorIndex),
+    // Acknowledged a write command, the next frame is the data
+    WriteData { block: u8, after: AuthState },
+    // Took the first half of the gen1a backdoor (0x40)
+    Backdoor1,
+    Backdoor,
+}
+
+// Where a card goes back to once a write is done
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AuthState {
+    Sector(SectorIndex),
+    Backdoor,
+}
+
+// A Mifare Classic 1K with a 4 byte UID, or an Ultralight with a 7 byte one
+#[derive(Clone, Debug)]
+pub struct MockCard {
+    blocks: Vec<[u8; 16]>,
+    // Set for an Ultralight, whose blocks are left alone
+    ultralight_uid: Option<[u8; 7]>,
+    magic: bool,
+    prng: Prng,
+    state: CardState,
+    // PRNG steps since power-up
+    ticks: u32,
+    // State of the generator behind Prng::Hard
+    hard_state: u32,
+}
+
+impl MockCard {
+    // A blank card in transport configuration (every key FFFFFFFFFFFF)
+    pub fn new(uid: [u8; 4]) -> Self {
+        let mut blocks = vec![[0u8; 16]; BLOCKS];
+        for (block, data) in blocks.iter_mut().enumerate() {
+            if BlockAddr(block as u8).is_trailer() {
+                *data = TRANSPORT_TRAILER;
+            }
+        }
+
+        let mut card = MockCard {
+            blocks,
+            ultralight_uid: None,
+            magic: false,
+            prng: Prng::Weak,
+            state: CardState::Off,
+            ticks: 0,
+            hard_state: u32::from_be_bytes(uid) | 1,
+        };
+        card.set_uid(uid);
+        card
+    }
+
+    // A gen1a magic card, whose backdoor opens every block including block 0
+    pub fn magic(uid: [u8; 4]) -> Self {
+        MockCard { magic: true, ..MockCard::new(uid) }
+    }
+
+    // An Ultralight or NTAG, which answers anticollision, select and HALT and refuses
+    // every authentication
+    pub fn ultralight(uid: [u8; 7]) -> Self {
+        MockCard { ultralight_uid: Some(uid), ..MockCard::new([uid[0], uid[1], uid[2], uid[3]]) }
+    }
+
+    pub fn with_prng(self, prng: Prng) -> Self {
+        MockCard { prng, ..self }
+    }
+
+    // Set both keys of a sector, leaving its access bits
+    pub fn with_keys(mut self, sector: u8, key_a: [u8; 6], key_b: [u8; 6]) -> Self {
+        let trailer = SectorIndex::new(sector).expect("sector of a Classic").trailer();
+        let trailer = &mut self.blocks[trailer.index()];
+        trailer[..6].copy_from_slice(&key_a);
+        trailer[10..].copy_from_slice(&key_b);
+        self
+    }
+
+    pub fn with_block(mut self, block: u8, data: [u8; 16]) -> Self {
+        self.blocks[block as usize] = data;
+        self
+    }
+
+    pub fn uid(&self) -> [u8; 4] {
+        let mut uid = [0u8; 4];
+        uid.copy_from_slice(&self.blocks[0][..4]);
+        uid
+    }
+
+    // The whole UID, 4 or 7 bytes
+    pub fn full_uid(&self) -> Vec<u8> {
+        match self.ultralight_uid {
+            Some(uid) => uid.to_vec(),
+            None => self.uid().to_vec(),
+        }
+    }
+
+    pub fn block(&self, block: u8) -> [u8; 16] {
+        self.blocks[block as usize]
+    }
+
+    // Rewrite block 0 for `uid`, with its check byte, SAK and ATQA
+    fn set_uid(&mut self, uid: [u8; 4]) {
+        let block0 = &mut self.blocks[0];
+        block0[..4].copy_from_slice(&uid);
+        block0[4] = uid.iter().fold(0, |bcc, byte| bcc ^ byte);
+        block0[5] = SAK;
+        block0[6..8].copy_from_slice(&ATQA);
+        block0[8..].copy_from_slice(&MANUFACTURER_DATA);
+    }
+
+    // The UID bytes and check byte the card answers at a cascade level
+    fn cascade_level(&self, level: u8) -> Option<[u8; 5]> {
+        let bytes = match (self.ultralight_uid, level) {
+            (None, 0) => return self.blocks[0][..5].try_into().ok(),
+            (Some(uid), 0) => [PICC_CASCADE_TAG, uid[0], uid[1], uid[2]],
+            (Some(uid), 1) => [uid[3], uid[4], uid[5], uid[6]],
+            _ => return None,
+        };
+        let bcc = bytes.iter().fold(0, |bcc, byte| bcc ^ byte);
+        Some([bytes[0], bytes[1], bytes[2], bytes[3], bcc])
+    }
+
+    fn atqa(&self) -> [u8; 2] {
+        if self.ultralight_uid.is_some() { ULTRALIGHT_ATQA } else { ATQA }
+    }
+
+    fn sak(&self) -> u8 {
+        if self.ultralight_uid.is_some() { ULTRALIGHT_SAK } else { SAK }
+    }
+
+    fn power(&mut self, on: bool) {
+        self.state = if on { CardState::Idle } else { CardState::Off };
+        self.ticks = 0;
+    }
+
+    fn next_nonce(&mut self) -> u32 {
+        match self.prng {
+            Prng::Weak => prng_successor(POWER_UP_NONCE, self.ticks),
+            Prng::Static(nonce) => nonce,
+            Prng::Hard => {
+                // xorshift32
+                self.hard_state ^= self.hard_state << 13;
+                self.hard_state ^= self.hard_state >> 17;
+                self.hard_state ^= self.hard_state << 5;
+                self.hard_state
+            }
+        }
+    }
+
+    fn key_matches(&self, auth_mode: u8, block: u8, key: &[u8]) -> bool {
+        let trailer = &self.blocks[BlockAddr(block).sector().trailer().index()];
+        match auth_mode {
+            PICC_AUTHENT1A => key == &trailer[..6],
+            PICC_AUTHENT1B => key == &trailer[10..],
+            _ => false,
+        }
+    }
+
+    // MFAuthent: the reader runs the three pass authentication with `key`
+    fn authenticate(&mut self, auth_mode: u8, block: u8, key: &[u8], uid: &[u8]) -> bool {
+        self.ticks = self.ticks.wrapping_add(STEPS_PER_FRAME);
+        let selected = matches!(self.state, CardState::Active | CardState::Authenticated(_));
+        if selected && self.ultralight_uid.is_none() && uid == self.uid() && (block as usize) < BLOCKS
+            && self.key_matches(auth_mode, block, key) {
+            self.state = CardState::Authenticated(BlockAddr(block).sector());
+            true
+        } else {
+            // a card that doesn't get the answer it expects stops talking, halted
+            // cards don't listen
+            if !matches!(self.state, CardState::Off | CardState::Halted) {
+                self.state = CardState::Idle;
+            }
+            false
+        }
+    }
+
+    // Block data as the card sends it: key A never leaves the card outside the backdoor
+    fn read(&self, block: u8, backdoor: bool) -> Vec<u8> {
+        let mut data = self.blocks[block as usize].to_vec();
+        if BlockAddr(block).is_trailer() && !backdoor {
+            data[..6].fill(0);
+        }
+        data.extend_from_slice(&crc_a(&data));
+        data
+    }
+
+    // Answer a frame with bits, `last_bits` are the valid bits of its last byte (0 for
+    // all 8). An anticollision frame is answered from the bit it stops at.
+    fn answer(&mut self, frame: &[u8], last_bits: u8) -> Option<Vec<bool>> {
+        let anticollision = (2..=7).contains(&frame.len())
+            && matches!(frame[0], PICC_ANTICOLL | PICC_SEL_CL2 | PICC_SEL_CL3)
+            && (0x20..0x70).contains(&frame[1]);
+        if anticollision {
+            self.anticollision(frame)
+        } else {
+            self.receive(frame, last_bits).map(|(data, bits)| to_bits(&data, bits))
+        }
+    }
+
+    // Anticollision at the card's cascade level: the card answers the rest of its UID
+    // and check byte if it starts with the bits the reader sent (NVB says how many)
+    fn anticollision(&mut self, frame: &[u8]) -> Option<Vec<bool>> {
+        if self.state == CardState::Off {
+            return None;
+        }
+        self.ticks = self.ticks.wrapping_add(STEPS_PER_FRAME);
+
+        let level = match self.state {
+            CardState::Ready(level) => level,
+            CardState::Idle | CardState::Halted => return None,
+            _ => {
+                self.state = CardState::Idle;
+                return None;
+            },
+        };
+        let cascade = match self.cascade_level(level) {
+            Some(cascade) if CASCADE_SELECTS.get(level as usize) == Some(&frame[0]) => cascade,
+            _ => {
+                self.state = CardState::Idle;
+                return None;
+            },
+        };
+
+        let known = ((frame[1] >> 4) as usize - 2) * 8 + (frame[1] & 0x07) as usize;
+        let uid_bits = to_bits(&cascade, 0);
+        let sent = to_bits(&frame[2..], 0);
+        // cards whose UID starts otherwise keep quiet, and stay ready
+        if known > uid_bits.len() || sent.len() < known || sent[..known] != uid_bits[..known] {
+            return None;
+        }
+        Some(uid_bits[known..].to_vec())
+    }
+
+    // Answer a frame, with the valid bits of its last byte (0 for all 8). `last_bits`
+    // are the valid bits of the frame's last byte.
+    fn receive(&mut self, frame: &[u8], last_bits: u8) -> Option<(Vec<u8>, u8)> {
+        if self.state == CardState::Off || frame.is_empty() {
+            return None;
+        }
+        self.ticks = self.ticks.wrapping_add(STEPS_PER_FRAME);
+
+        // short frames: REQA, WUPA and the first half of the backdoor
+        if last_bits == 7 && frame.len() == 1 {
+            return match (frame[0], self.state) {
+                // still waiting for the reader's answer to the nonce, the wake-up ends it
+                (_, CardState::AuthPending) => {
+                    self.state = CardState::Idle;
+                    None
+                },
+                (PICC_REQIDL, CardState::Halted) => None,
+                (PICC_REQIDL | PICC_REQALL, _) => {
+                    self.state = CardState::Ready(0);
+                    Some((self.atqa().to_vec(), 0))
+                },
+                (0x40, CardState::Idle | CardState::Halted) if self.magic => {
+                    self.state = CardState::Backdoor1;
+                    Some((vec![ACK], 4))
+                },
+                _ => None,
+            };
+        }
+
+        let crc_ok = frame.len() > 2 && crc_a(&frame[..frame.len() - 2]) == frame[frame.len() - 2..];
+        match (self.state, frame) {
+            (CardState::Ready(level), [sel, 0x70, cascade @ .., _, _])
+                if crc_ok && cascade.len() == 5 && CASCADE_SELECTS.get(level as usize) == Some(sel) => {
+                if self.cascade_level(level).is_some_and(|own| own[..4] == cascade[..4]) {
+                    // a cascade tag says the UID goes on at the next level
+                    let sak = if self.cascade_level(level + 1).is_some() {
+                        self.state = CardState::Ready(level + 1);
+                        SAK_CASCADE
+                    } else {
+                        self.state = CardState::Active;
+                        self.sak()
+                    };
+                    let mut answer = vec![sak];
+                    answer.extend_from_slice(&crc_a(&[sak]));
+                    Some((answer, 0))
+                } else {
+                    self.state = CardState::Idle;
+                    None
+                }
+            },
+            (CardState::Backdoor1, [0x43]) => {
+                self.state = CardState::Backdoor;
+                Some((vec![ACK], 4))
+            },
+            // only the selected card takes a HALT, the others stay in anticollision
+            (CardState::Active | CardState::Authenticated(_) | CardState::AuthPending | CardState::WriteData { .. }
+                | CardState::Backdoor1 | CardState::Backdoor, [PICC_HALT, 0x00, _, _]) if crc_ok => {
+                self.state = CardState::Halted;
+                None
+            },
+            // the first step of an authentication, sent by hand instead of MFAuthent
+            (CardState::Active | CardState::Authenticated(_), [PICC_AUTHENT1A | PICC_AUTHENT1B, _, _, _]) if crc_ok => {
+                self.state = CardState::AuthPending;
+                Some((self.next_nonce().to_be_bytes().to_vec(), 0))
+            },
+            (CardState::Authenticated(sector), [PICC_READ, block, _, _]) if crc_ok && BlockAddr(*block).sector() == sector => {
+                Some((self.read(*block, false), 0))
+            },
+            (CardState::Backdoor, [PICC_READ, block, _, _]) if crc_ok && (*block as usize) < BLOCKS => {
+                Some((self.read(*block, true), 0))
+            },
+            (CardState::Authenticated(sector), [PICC_WRITE, block, _, _])
+                if crc_ok && BlockAddr(*block).sector() == sector && *block != 0 => {
+                self.state = CardState::WriteData { block: *block, after: AuthState::Sector(sector) };
+                Some((vec![ACK], 4))
+            },
+            (CardState::Backdoor, [PICC_WRITE, block, _, _]) if crc_ok && (*block as usize) < BLOCKS => {
+                self.state = CardState::WriteData { block: *block, after: AuthState::Backdoor };
+                Some((vec![ACK], 4))
+            },
+            (CardState::WriteData { block, after }, data) if crc_ok && data.len() == 18 => {
+                self.blocks[block as usize].copy_from_slice(&data[..16]);
+                self.state = match after {
+                    AuthState::Sector(sector) => CardState::Authenticated(sector),
+                    AuthState::Backdoor => CardState::Backdoor,
+                };
+                Some((vec![ACK], 4))
+            },
+            // reads and writes outside the authenticated sector are refused
+            (CardState::Authenticated(_) | CardState::WriteData { .. }, [PICC_READ | PICC_WRITE, ..]) => {
+                self.state = CardState::Idle;
+                Some((vec![NAK], 4))
+            },
+            _ => {
+                if self.state != CardState::Halted {
+                    self.state = CardState::Idle;
+                }
+                None
+            },
+        }
+    }
+}
+
+// An MFRC522 on the SPI bus, with cards in its field or none
+pub struct MockReader {
+    registers: [u8; 64],
+    fifo: VecDeque<u8>,
+    cards: Vec<MockCard>,
+    state: ReaderState,
+}
+
+impl Default for MockReader {
+    fn default() -> Self {
+        MockReader::new()
+    }
+}
+
+impl MockReader {
+    pub fn new() -> Self {
+        let mut reader = MockReader { registers: [0; 64], fifo: VecDeque::new(), cards: Vec::new(), state: ReaderState::default() };
+        reader.reset();
+        reader
+    }
+
+    pub fn with_card(card: MockCard) -> Self {
+        let mut reader = MockReader::new();
+        reader.insert(card);
+        reader
+    }
+
+    // Put a card in the field, taking out the ones that were there (the first is returned)
+    pub fn insert(&mut self, card: MockCard) -> Option<MockCard> {
+        let old = self.remove();
+        self.add(card);
+        old
+    }
+
+    // Stack a card on the ones in the field
+    pub fn add(&mut self, mut card: MockCard) {
+        card.power(self.antenna_on());
+        self.cards.push(card);
+    }
+
+    // Take every card out of the field, returns the first
+    pub fn remove(&mut self) -> Option<MockCard> {
+        self.cards.drain(..).next()
+    }
+
+    // The first card in the field
+    pub fn card(&self) -> Option<&MockCard> {
+        self.cards.first()
+    }
+
+    pub fn cards(&self) -> &[MockCard] {
+        &self.cards
+    }
+
+    fn antenna_on(&self) -> bool {
+        self.registers[TX_CONTROL_REG as usize] & 0x03 == 0x03
+    }
+
+    fn reset(&mut self) {
+        self.registers = [0; 64];
+        self.registers[VERSION_REG as usize] = VERSION;
+        self.registers[TX_CONTROL_REG as usize] = TX_CONTROL_RESET;
+        self.fifo.clear();
+        for card in &mut self.cards {
+            card.power(false);
+        }
+    }
+
+    fn read(&mut self, reg: u8) -> u8 {
+        match reg {
+            FIFO_DATA_REG => self.fifo.pop_front().unwrap_or(0),
+            FIFO_LEVEL_REG => self.fifo.len() as u8,
+            _ => self.registers[reg as usize],
+        }
+    }
+
+    fn write(&mut self, reg: u8, value: u8) {
+        match reg {
+            FIFO_DATA_REG => {
+                if self.fifo.len() < FIFO_SIZE {
+                    self.fifo.push_back(value);
+                }
+            },
+            FIFO_LEVEL_REG => {
+                if value & 0x80 != 0 {
+                    self.fifo.clear();
+                }
+            },
+            // bit 7 says whether the other bits set or clear the flags they mark
+            COM_IRQ_REG | DIV_IRQ_REG => {
+                let flags = &mut self.registers[reg as usize];
+                if value & 0x80 != 0 {
+                    *flags |= value & 0x7F;
+                } else {
+                    *flags &= !value;
+                }
+            },
+            COMMAND_REG => self.command(value),
+            BIT_FRAMING_REG => {
+                self.registers[reg as usize] = value & 0x7F;
+                // StartSend
+                if value & 0x80 != 0 && self.registers[COMMAND_REG as usize] == PCD_TRANSCEIVE {
+                    self.transceive();
+                }
+            },
+            TX_CONTROL_REG => {
+                let was_on = self.antenna_on();
+                self.registers[reg as usize] = value;
+                let on = self.antenna_on();
+                if on != was_on {
+                    for card in &mut self.cards {
+                        card.power(on);
+                    }
+                }
+            },
+            _ => self.registers[reg as usize] = value,
+        }
+    }
+
+    fn command(&mut self, command: u8) {
+        self.registers[COMMAND_REG as usize] = command;
+        match command {
+            PCD_RESETPHASE => self.reset(),
+            PCD_CALCCRC => {
+                let data: Vec<u8> = self.fifo.drain(..).collect();
+                let crc = crc_a(&data);
+                self.registers[CRC_RESULT_REG_L as usize] = crc[0];
+                self.registers[CRC_RESULT_REG_M as usize] = crc[1];
+                self.registers[DIV_IRQ_REG as usize] |= 0x04;
+            },
+            PCD_AUTHENT => {
+                let data: Vec<u8> = self.fifo.drain(..).collect();
+                // every card in the field hears the authentication
+                let mut accepted = false;
+                if data.len() >= 12 && self.antenna_on() {
+                    for card in &mut self.cards {
+                        accepted |= card.authenticate(data[0], data[1], &data[2..8], &data[8..12]);
+                    }
+                }
+                if accepted {
+                    self.registers[STATUS2_REG as usize] |= 0x08;
+                    self.registers[COM_IRQ_REG as usize] |= 0x10;
+                } else {
+                    self.registers[STATUS2_REG as usize] &= !0x08;
+                    // the timer runs out waiting for the card
+                    self.registers[COM_IRQ_REG as usize] |= 0x11;
+                }
+                self.registers[COMMAND_REG as usize] = PCD_IDLE;
+            },
+            _ => {},
+        }
+    }
+
+    fn transceive(&mut self) {
+        let frame: Vec<u8> = self.fifo.drain(..).collect();
+        let tx_last_bits = self.registers[BIT_FRAMING_REG as usize] & 0x07;
+        let rx_align = ((self.registers[BIT_FRAMING_REG as usize] >> 4) & 0x07) as usize;
+        self.registers[ERROR_REG as usize] = 0;
+        // CollPosNotValid until a collision says otherwise, ValuesAfterColl is the driver's
+        self.registers[COLL_REG as usize] = (self.registers[COLL_REG as usize] & 0x80) | 0x20;
+
+        // every card in the field hears the frame
+        let answers: Vec<Vec<bool>> = if self.antenna_on() {
+            self.cards.iter_mut().filter_map(|card| card.answer(&frame, tx_last_bits)).collect()
+        } else {
+            Vec::new()
+        };
+
+        // TxIRq, then TimerIRq for silence
+        let first = match answers.first() {
+            Some(first) => first,
+            None => {
+                self.registers[CONTROL_REG as usize] = 0;
+                self.registers[COM_IRQ_REG as usize] |= 0x41;
+                return;
+            },
+        };
+
+        // the bits from the first collision on read as 0
+        let collision = answers[1..].iter().filter_map(|other| first_difference(first, other)).min();
+        let length = answers.iter().map(Vec::len).max().unwrap_or(0);
+        let cut = collision.unwrap_or(length);
+        let bits: Vec<bool> = (0..length).map(|i| i < cut && first.get(i) == Some(&true)).collect();
+
+        // the first bit lands at RxAlign of the first byte
+        let mut data = vec![0u8; (rx_align + bits.len() + 7) / 8];
+        for (i, bit) in bits.iter().enumerate() {
+            if *bit {
+                data[(rx_align + i) / 8] |= 1 << ((rx_align + i) % 8);
+            }
+        }
+        self.fifo.extend(data);
+        self.registers[CONTROL_REG as usize] = ((rx_align + bits.len()) % 8) as u8;
+
+        // TxIRq, RxIRq and IdleIRq, with ErrIRq for a collision
+        match collision {
+            Some(c) => {
+                self.registers[ERROR_REG as usize] |= COLL_ERR;
+                // CollPos counts from 1 and 32 reads as 0, past it the position isn't valid
+                let position = rx_align + c + 1;
+                let coll_pos = if position <= 32 { (position % 32) as u8 } else { 0x20 };
+                self.registers[COLL_REG as usize] = (self.registers[COLL_REG as usize] & 0x80) | coll_pos;
+                self.registers[COM_IRQ_REG as usize] |= 0x72;
+            },
+            None => self.registers[COM_IRQ_REG as usize] |= 0x70,
+        }
+    }
+}
+
+impl Transport for MockReader {
+    fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>> {
+        if rx.len() != tx.len() {
+            return Err("rx and tx lengths differ".into());
+        }
+        let address = match tx.first() {
+            Some(&address) => address,
+            None => return Ok(()),
+        };
+
+        rx[0] = 0;
+        if address & 0x80 != 0 {
+            // a read: every byte sent addresses a register, its value comes back in the next byte
+            for i in 1..tx.len() {
+                rx[i] = self.read((tx[i - 1] >> 1) & 0x3F);
+            }
+        } else {
+            let reg = (address >> 1) & 0x3F;
+            for (i, &value) in tx.iter().enumerate().skip(1) {
+                self.write(reg, value);
+                rx[i] = 0;
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&self) -> Option<&ReaderState> {
+        Some(&self.state)
+    }
+
+    fn state_mut(&mut self) -> Option<&mut ReaderState> {
+        Some(&mut self.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weak_prng_nonces_follow_the_lfsr() {
+        let mut card = MockCard::new([0xDE, 0xAD, 0xBE, 0xEF]);
+        card.power(true);
+        assert_eq!(card.next_nonce(), POWER_UP_NONCE);
+        card.ticks = STEPS_PER_FRAME;
+        assert_ne!(card.next_nonce(), POWER_UP_NONCE);
+        // the 16 bit LFSR comes back round after 2^16 - 1 steps
+        assert_eq!(prng_successor(POWER_UP_NONCE, 65535), POWER_UP_NONCE);
+    }
+}
@@ -0,0 +1,224 @@
+use std::error::Error;
+
+use super::constants::*;
+use super::register::*;
+use super::layout::BlockAddr;
+use super::communication::*;
+use super::metrics::record_auth_failure;
+use super::transport::Transport;
+
+// An authentication the card accepted
+#[derive(Clone, Debug)]
+pub struct Authentication {
+    pub auth_mode: u8,
+    pub block_addr: u8,
+    pub key: Vec<u8>,
+    pub uid: Vec<u8>,
+}
+
+// The crypto1 session of the card in one reader's field, kept in the reader's state
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Session {
+    // The authentication in force, so a trailer read under it can be completed with
+    // the key the card never sends back
+    auth: Option<Authentication>,
+    // Whether it also stands for the next one in its sector with the same key. A
+    // trailer write ends that, the write may have changed the keys.
+    reusable: bool,
+}
+
+// The authentication in force on this reader
+pub fn last_authentication(spi: &dyn Transport) -> Option<Authentication> {
+    spi.state().and_then(|state| state.session.auth.clone())
+}
+
+// The card left its crypto session: a request or HALT, a refused authentication, or a
+// read or write the card didn't answer
+pub fn forget_authentication(spi: &mut dyn Transport) {
+    if let Some(state) = spi.state_mut() {
+        state.session = Session::default();
+    }
+}
+
+// The next authentication goes to the card even in the sector already open
+pub fn end_auth_session(spi: &mut dyn Transport) {
+    if let Some(state) = spi.state_mut() {
+        state.session.reusable = false;
+    }
+}
+
+// The same key, key type, card and sector as the authentication in force
+fn session_covers(spi: &dyn Transport, auth_mode: u8, block_addr: u8, sector_key: &[u8], serial_num: &[u8]) -> bool {
+    spi.state().is_some_and(|state| {
+        let session = &state.session;
+        session.reusable && session.auth.as_ref().is_some_and(|last| {
+            last.auth_mode == auth_mode
+                && BlockAddr(last.block_addr).sector() == BlockAddr(block_addr).sector()
+                && last.key == sector_key
+                && serial_num.iter().take(4).eq(last.uid.iter())
+        })
+    })
+}
+
+// Request card presence
+pub fn mfrc522_request(spi: &mut dyn Transport, req_mode: u8) -> Result<(u8, u8), Box<dyn Error>> {
+    // A card in a crypto session takes the request as the end of it
+    forget_authentication(spi);
+    
+    // Set bit framing for 7 bits
+    write_register(spi, BIT_FRAMING_REG, 0x07)?;
+    
+    let tag_type = vec![req_mode];
+    let (status, _, back_bits) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &tag_type)?;
+    
+    if (status != MI_OK) || (back_bits != 0x10) {
+        return Ok((MI_ERR, 0));
+    }
+    
+    Ok((MI_OK, back_bits as u8))
+}
+
+// Anti-collision detection
+pub fn mfrc522_anticoll(spi: &mut dyn Transport) -> Result<(u8, Vec<u8>), Box<dyn Error>> {
+    write_register(spi, BIT_FRAMING_REG, 0x00)?;
+    
+    let ser_num = vec![PICC_ANTICOLL, 0x20];
+    let (status, back_data, _) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &ser_num)?;
+    
+    if status == MI_OK {
+        // Verify checksum
+        if back_data.len() == 5 {
+            let check_sum = back_data[..4].iter().fold(0u8, |sum, byte| sum ^ byte);
+            if check_sum != back_data[4] {
+                return Ok((MI_ERR, vec![]));
+            }
+        } else {
+            return Ok((MI_ERR, vec![]));
+        }
+    }
+    
+    Ok((status, back_data))
+}
+
+// Select a card by UID
+pub fn mfrc522_select_tag(spi: &mut dyn Transport, ser_num: &[u8]) -> Result<u8, Box<dyn Error>> {
+    let mut buf: Vec<u8> = Vec::new();
+    buf.push(PICC_SELECTTAG);
+    buf.push(0x70);
+    
+    for i in 0..5 {
+        if i < ser_num.len() {
+            buf.push(ser_num[i]);
+        } else {
+            break;
+        }
+    }
+    
+    let crc = calculate_crc(spi, &buf)?;
+    buf.push(crc[0]);
+    buf.push(crc[1]);
+    
+    let (status, back_data, back_len) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &buf)?;
+    
+    if (status == MI_OK) && (back_len == 0x18) {
+        Ok(back_data[0])
+    } else {
+        Ok(0)
+    }
+}
+
+// Authenticate with card
+pub fn mfrc522_auth(spi: &mut dyn Transport, auth_mode: u8, block_addr: u8, sector_key: &[u8], serial_num: &[u8]) 
+    -> Result<u8, Box<dyn Error>> {
+    // The sector is still open with this key: authenticating again costs a round trip
+    // and trips up some cards. The reader's crypto flag has to agree.
+    if session_covers(spi, auth_mode, block_addr, sector_key, serial_num) && (read_register(spi, STATUS2_REG)? & 0x08) != 0 {
+        return Ok(MI_OK);
+    }
+    
+    let mut buf: Vec<u8> = Vec::new();
+    
+    // First byte is authMode (A or B)
+    buf.push(auth_mode);
+    // Second byte is the block address
+    buf.push(block_addr);
+    
+    // Append the key (usually 6 bytes)
+    buf.extend_from_slice(sector_key);
+    
+    // Append first 4 bytes of UID
+    for i in 0..4 {
+        if i < serial_num.len() {
+            buf.push(serial_num[i]);
+        } else {
+            break;
+        }
+    }
+    
+    let (status, _, _) = mfrc522_to_card(spi, PCD_AUTHENT, &buf)?;
+    
+    // Check if the crypto1 state is set
+    if (read_register(spi, STATUS2_REG)? & 0x08) == 0 {
+        // Over a clean link it was the key the card refused
+        if status != MI_ERR {
+            record_auth_failure(block_addr);
+        }
+        forget_authentication(spi);
+        return Ok(MI_ERR);
+    }
+    
+    if status == MI_OK {
+        let auth = Authentication {
+            auth_mode,
+            block_addr,
+            key: sector_key.to_vec(),
+            uid: serial_num.iter().take(4).copied().collect(),
+        };
+        if let Some(state) = spi.state_mut() {
+            state.session = Session { auth: Some(auth), reusable: true };
+        }
+    }
+    
+    Ok(status)
+}
+
+// Stop the crypto1 functionality
+pub fn mfrc522_stop_crypto1(spi: &mut dyn Transport) -> Result<(), Box<dyn Error>> {
+    clear_bit_mask(spi, STATUS2_REG, 0x08)?;
+    forget_authentication(spi);
+    Ok(())
+}
+
+// Open the backdoor of a gen1a magic card: HALT, then 0x40 as a 7 bit frame and 0x43.
+// Afterwards every block, block 0 included, is read and written without
+// authenticating. Cards without the backdoor don't answer, and are left halted.
+pub fn mfrc522_open_backdoor(spi: &mut dyn Transport) -> Result<bool, Box<dyn Error>> {
+    mfrc522_halt(spi)?;
+    
+    write_register(spi, BIT_FRAMING_REG, 0x07)?;
+    let (status, back_data, back_bits) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &[0x40])?;
+    if status != MI_OK || back_bits != 4 || back_data.first().map(|ack| ack & 0x0F) != Some(0x0A) {
+        write_register(spi, BIT_FRAMING_REG, 0x00)?;
+        return Ok(false);
+    }
+    
+    write_register(spi, BIT_FRAMING_REG, 0x00)?;
+    let (status, back_data, back_bits) = mfrc522_to_card(spi, PCD_TRANSCEIVE, &[0x43])?;
+    Ok(status == MI_OK && back_bits == 4 && back_data.first().map(|ack| ack & 0x0F) == Some(0x0A))
+}
+
+// Put the selected card in HALT. A halted card ignores REQIDL until it leaves the
+// field, only PICC_REQALL (WUPA) wakes it.
+pub fn mfrc522_halt(spi: &mut dyn Transport) -> Result<(), Box<dyn Error>> {
+    forget_authentication(spi);
+    write_register(spi, BIT_FRAMING_REG, 0x00)?;
+    
+    let mut buf = vec![PICC_HALT, 0x00];
+    let crc = calculate_crc(spi, &buf)?;
+    buf.push(crc[0]);
+    buf.push(crc[1]);
+    
+    // The card doesn't answer a HALT, so there is no status to check
+    mfrc522_to_card(spi, PCD_TRANSCEIVE, &buf)?;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::error::Error;
+
+use super::transport::Transport;
+
+// Read from MFRC522 register
+pub fn read_register(spi: &mut dyn Transport, reg: u8) -> Result<u8, Box<dyn Error>> {
+    let tx_buf = [((reg << 1) & 0x7E) | 0x80, 0x00];
+    let mut rx_buf = [0u8, 0u8];
+    
+    spi.transfer(&mut rx_buf, &tx_buf)?;
+    
+    Ok(rx_buf[1])
+}
+
+// Write to MFRC522 register
+pub fn write_register(spi: &mut dyn Transport, reg: u8, value: u8) -> Result<(), Box<dyn Error>> {
+    let tx_buf = [(reg << 1) & 0x7E, value];
+    let mut rx_buf = [0u8, 0u8];
+    
+    spi.transfer(&mut rx_buf, &tx_buf)?;
+    
+    Ok(())
+}
+
+// Write several bytes to one register (the FIFO) in a single SPI transfer. After the
+// address byte the MFRC522 writes every following byte to the same register.
+pub fn write_register_burst(spi: &mut dyn Transport, reg: u8, data: &[u8]) -> Result<(), Box<dyn Error>> {
+    if data.is_empty() {
+        return Ok(());
+    }
+    
+    let mut tx_buf = Vec::with_capacity(data.len() + 1);
+    tx_buf.push((reg << 1) & 0x7E);
+    tx_buf.extend_from_slice(data);
+    let mut rx_buf = vec![0u8; tx_buf.len()];
+    
+    spi.transfer(&mut rx_buf, &tx_buf)?;
+    
+    Ok(())
+}
+
+// Read `count` bytes from one register (the FIFO) in a single SPI transfer. Each byte
+// sent addresses the register again and the chip answers in the next byte, so the
+// address is repeated `count` times and followed by a 0x00.
+pub fn read_register_burst(spi: &mut dyn Transport, reg: u8, count: usize) -> Result<Vec<u8>, Box<dyn Error>> {
+    if count == 0 {
+        return Ok(Vec::new());
+    }
+    
+    let mut tx_buf = vec![((reg << 1) & 0x7E) | 0x80; count];
+    tx_buf.push(0x00);
+    let mut rx_buf = vec![0u8; tx_buf.len()];
+    
+    spi.transfer(&mut rx_buf, &tx_buf)?;
+    
+    Ok(rx_buf[1..].to_vec())
+}
+
+// Set bits in register
+pub fn set_bit_mask(spi: &mut dyn Transport, reg: u8, mask: u8) -> Result<(), Box<dyn Error>> {
+    let tmp = read_register(spi, reg)?;
+    write_register(spi, reg, tmp | mask)?;
+    Ok(())
+}
+
+// Clear bits in register
+pub fn clear_bit_mask(spi: &mut dyn Transport, reg: u8, mask: u8) -> Result<(), Box<dyn Error>> {
+    let tmp = read_register(spi, reg)?;
+    write_register(spi, reg, tmp & (!mask))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Records what was sent and answers every byte with `answer`
+    struct Recorder {
+        sent: Vec<Vec<u8>>,
+        answer: u8,
+    }
+
+    impl Transport for Recorder {
+        fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>> {
+            self.sent.push(tx.to_vec());
+            rx.fill(self.answer);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn frames_register_addresses() {
+        let mut spi = Recorder { sent: Vec::new(), answer: 0x92 };
+        assert_eq!(read_register(&mut spi, 0x37).unwrap(), 0x92);
+        write_register(&mut spi, 0x01, 0x0F).unwrap();
+        assert_eq!(spi.sent, vec![vec![0xEE, 0x00], vec![0x02, 0x0F]]);
+    }
+
+    #[test]
+    fn bursts_repeat_the_fifo_address() {
+        let mut spi = Recorder { sent: Vec::new(), answer: 0xAB };
+        write_register_burst(&mut spi, 0x09, &[1, 2, 3]).unwrap();
+        assert_eq!(read_register_burst(&mut spi, 0x09, 3).unwrap(), vec![0xAB; 3]);
+        assert_eq!(spi.sent, vec![vec![0x12, 1, 2, 3], vec![0x92, 0x92, 0x92, 0x00]]);
+
+        assert!(read_register_burst(&mut spi, 0x09, 0).unwrap().is_empty());
+        write_register_burst(&mut spi, 0x09, &[]).unwrap();
+        assert_eq!(spi.sent.len(), 2);
+    }
+}
@@ -0,0 +1,148 @@
+// The Raspberry Pi backend (the `rppal` feature): opening the reader on SPI0, CE0,
+// reconnecting after it was lost and negotiating the SPI speed
+use log::{info, warn};
+use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use super::health::{reader_status, ReaderStatus};
+use super::init::{current_speed, mfrc522_init, set_current_speed};
+use super::metrics::record_retry;
+use super::speed::{link_test, reader_id, take_error_counts, SpeedConfig, SPEED_STEPS};
+use super::transport::Reader;
+
+// First delay between reconnection attempts, doubled after each failure
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(250);
+// Longest delay between reconnection attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(8);
+
+// Speed used to identify the reader before negotiating, slow enough for any wiring
+const PROBE_SPEED_HZ: u32 = 100_000;
+// Step down once this share of card transactions failed with a communication error...
+const ERROR_RATE_LIMIT: f64 = 0.25;
+// ...over at least this many transactions
+const MIN_TRANSACTIONS: usize = 20;
+
+// The reader on the Pi's SPI bus, with its state
+pub type SpiReader = Reader<Spi>;
+
+// Open the SPI device the reader is wired to (SPI0, CE0)
+pub fn open_spi(speed_hz: u32) -> Result<SpiReader, Box<dyn Error>> {
+    let spi = Spi::new(Bus::Spi0, SlaveSelect::Ss0, speed_hz, Mode::Mode0)?;
+    set_current_speed(speed_hz);
+    Ok(Reader::new(spi))
+}
+
+// Reopen the SPI device at its current speed and re-initialize the reader
+pub fn reconnect(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
+    *spi = open_spi(current_speed())?;
+    mfrc522_init(spi)?;
+
+    if reader_status(spi) == ReaderStatus::Disconnected {
+        return Err("Reader not responding".into());
+    }
+
+    Ok(())
+}
+
+// Make sure the reader is connected, retrying with backoff if it was lost
+pub fn ensure_connected(spi: &mut SpiReader, max_attempts: u32) -> ReaderStatus {
+    if reader_status(spi) == ReaderStatus::Connected {
+        return ReaderStatus::Connected;
+    }
+
+    info!("Reader status: {} - attempting to reconnect", ReaderStatus::Disconnected);
+
+    let mut delay = RECONNECT_INITIAL_DELAY;
+    for attempt in 1..=max_attempts {
+        record_retry("reconnect");
+        match reconnect(spi) {
+            Ok(_) => {
+                info!("Reader status: {} (after {} attempt(s))", ReaderStatus::Connected, attempt);
+                return ReaderStatus::Connected;
+            },
+            Err(e) => {
+                warn!("Reconnect attempt {}/{} failed: {}", attempt, max_attempts, e);
+                thread::sleep(delay);
+                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+            }
+        }
+    }
+
+    warn!("Reader status: {} after {} attempt(s)", ReaderStatus::Disconnected, max_attempts);
+    ReaderStatus::Disconnected
+}
+
+// The speed negotiation picked, and the reader it is saved for
+pub struct SpeedSetting {
+    pub reader: String,
+    pub speed_hz: u32,
+    config: SpeedConfig,
+}
+
+// Open and initialize the reader at the fastest speed that passes the link test,
+// starting from the speed saved for this reader if there is one. The result is saved.
+pub fn negotiate_speed(config: SpeedConfig) -> Result<(SpiReader, SpeedSetting), Box<dyn Error>> {
+    let mut spi = open_spi(PROBE_SPEED_HZ)?;
+    let reader = reader_id(&mut spi)?;
+    drop(spi);
+
+    let saved = config.get(&reader);
+    let candidates: Vec<u32> = match saved {
+        // the saved speed first, then only slower ones
+        Some(saved) => std::iter::once(saved)
+            .chain(SPEED_STEPS.iter().copied().filter(|&speed| speed < saved))
+            .collect(),
+        None => SPEED_STEPS.to_vec(),
+    };
+
+    for speed_hz in candidates {
+        let mut spi = open_spi(speed_hz)?;
+        if mfrc522_init(&mut spi).is_ok() && link_test(&mut spi)? {
+            let mut setting = SpeedSetting { reader, speed_hz, config };
+            if saved != Some(speed_hz) {
+                info!("SPI speed for {}: {} Hz", setting.reader, speed_hz);
+                setting.save();
+            }
+            return Ok((spi, setting));
+        }
+        warn!("SPI link unreliable at {} Hz, trying a slower speed", speed_hz);
+    }
+
+    Err("The reader failed the SPI link test at every speed, check the wiring".into())
+}
+
+impl SpeedSetting {
+    fn save(&mut self) {
+        if let Err(e) = self.config.set(&self.reader, self.speed_hz) {
+            warn!("Could not save the SPI speed for {}: {}", self.reader, e);
+        }
+    }
+
+    // Step down to the next slower speed if too many card transactions since the
+    // last check failed with communication errors. Returns the new speed.
+    pub fn fall_back_if_unreliable(&mut self, spi: &mut SpiReader) -> Result<Option<u32>, Box<dyn Error>> {
+        let (transactions, errors) = take_error_counts(spi);
+        if transactions < MIN_TRANSACTIONS || (errors as f64) < transactions as f64 * ERROR_RATE_LIMIT {
+            return Ok(None);
+        }
+
+        for &speed_hz in SPEED_STEPS.iter().filter(|&&speed| speed < self.speed_hz) {
+            *spi = open_spi(speed_hz)?;
+            if mfrc522_init(spi).is_ok() && link_test(spi)? {
+                warn!("{} of {} transactions had communication errors, SPI speed lowered to {} Hz",
+                  errors, transactions, speed_hz);
+                record_retry(&format!("SPI speed lowered to {} Hz", speed_hz));
+                self.speed_hz = speed_hz;
+                self.save();
+                return Ok(Some(speed_hz));
+            }
+        }
+
+        // Already at the slowest speed, reopen it and carry on
+        *spi = open_spi(self.speed_hz)?;
+        mfrc522_init(spi)?;
+        Ok(None)
+    }
+}
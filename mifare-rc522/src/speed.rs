@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+use super::communication::calculate_crc;
+use super::constants::*;
+use super::register::*;
+use super::transport::Transport;
+
+// SPI clock speeds tried, fastest first. The MFRC522 is rated for 10 MHz, but long
+// jumper wires often only carry a fraction of that.
+pub const SPEED_STEPS: [u32; 6] = [8_000_000, 4_000_000, 2_000_000, 1_000_000, 500_000, 100_000];
+
+// Link test rounds, each one a FIFO loopback and a CRC calculation
+const LINK_TEST_ROUNDS: usize = 8;
+
+// Card transactions and communication errors (CRC, parity, protocol, buffer overflow)
+// since the last check, counted by mfrc522_to_card in the reader's state
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ErrorCounts {
+    transactions: usize,
+    errors: usize,
+}
+
+pub(crate) fn record_transaction(spi: &mut dyn Transport, comm_error: bool) {
+    if let Some(state) = spi.state_mut() {
+        state.errors.transactions += 1;
+        if comm_error {
+            state.errors.errors += 1;
+        }
+    }
+}
+
+// Transactions and communication errors on this reader since the last call, which
+// starts a new count. A transport without state counts nothing.
+pub fn take_error_counts(spi: &mut dyn Transport) -> (usize, usize) {
+    spi.state_mut()
+        .map(|state| std::mem::take(&mut state.errors))
+        .map_or((0, 0), |counts| (counts.transactions, counts.errors))
+}
+
+// CRC_A (ISO 14443-3), the CRC the MFRC522 coprocessor calculates
+pub(crate) fn crc_a(data: &[u8]) -> [u8; 2] {
+    let mut crc: u16 = 0x6363;
+    for &byte in data {
+        let mut b = byte ^ (crc & 0xFF) as u8;
+        b ^= b << 4;
+        crc = (crc >> 8) ^ ((b as u16) << 8) ^ ((b as u16) << 3) ^ ((b as u16) >> 4);
+    }
+    [(crc & 0xFF) as u8, (crc >> 8) as u8]
+}
+
+// Check the SPI link without a card: bytes written to the FIFO must read back
+// unchanged, and the chip's CRC of them must match ours
+pub fn link_test(spi: &mut dyn Transport) -> Result<bool, Box<dyn Error>> {
+    for round in 0..LINK_TEST_ROUNDS {
+        let pattern: Vec<u8> = (0..32u8).map(|i| i.wrapping_mul(37).wrapping_add(round as u8 * 11) ^ 0xA5).collect();
+
+        // burst transfers, like the card traffic in mfrc522_to_card
+        set_bit_mask(spi, FIFO_LEVEL_REG, 0x80)?;
+        write_register_burst(spi, FIFO_DATA_REG, &pattern)?;
+        if read_register(spi, FIFO_LEVEL_REG)? as usize != pattern.len() {
+            return Ok(false);
+        }
+        if read_register_burst(spi, FIFO_DATA_REG, pattern.len())? != pattern {
+            return Ok(false);
+        }
+
+        if calculate_crc(spi, &pattern)? != crc_a(&pattern) {
+            return Ok(false);
+        }
+    }
+
+    set_bit_mask(spi, FIFO_LEVEL_REG, 0x80)?;
+    Ok(true)
+}
+
+// Working speeds saved per reader, one "<reader> <speed>" line each
+pub struct SpeedConfig {
+    path: PathBuf,
+    entries: Vec<(String, u32)>,
+}
+
+impl SpeedConfig {
+    // ~/.config/nfc-block-editor/spi_speed.conf (or $XDG_CONFIG_HOME)
+    pub fn default_path() -> PathBuf {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join("nfc-block-editor").join("spi_speed.conf")
+    }
+
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .unwrap_or_default()
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (reader, speed) = line.trim().rsplit_once(' ')?;
+                Some((reader.trim().to_string(), speed.parse().ok()?))
+            })
+            .collect();
+        SpeedConfig { path, entries }
+    }
+
+    pub fn get(&self, reader: &str) -> Option<u32> {
+        self.entries.iter().find(|(name, _)| name == reader).map(|(_, speed)| *speed)
+    }
+
+    pub fn set(&mut self, reader: &str, speed_hz: u32) -> Result<(), Box<dyn Error>> {
+        match self.entries.iter_mut().find(|(name, _)| name == reader) {
+            Some(entry) => entry.1 = speed_hz,
+            None => self.entries.push((reader.to_string(), speed_hz)),
+        }
+
+        if let Some(folder) = self.path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let mut text = String::from("# Working SPI speed per reader (SPI device and chip version), in Hz\n");
+        for (name, speed) in &self.entries {
+            text.push_str(&format!("{} {}\n", name, speed));
+        }
+        fs::write(&self.path, text)?;
+        Ok(())
+    }
+}
+
+// Name a reader is saved under: its SPI device and the chip version it reports
+pub fn reader_id(spi: &mut dyn Transport) -> Result<String, Box<dyn Error>> {
+    Ok(format!("spi0.0/v{:02X}", read_register(spi, VERSION_REG)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_crc_a() {
+        // examples from ISO 14443-3 annex B
+        assert_eq!(crc_a(&[0x00, 0x00]), [0xA0, 0x1E]);
+        assert_eq!(crc_a(&[0x12, 0x34]), [0x26, 0xCF]);
+    }
+
+    #[test]
+    fn saves_speeds_per_reader() {
+        let path = std::env::temp_dir().join(format!("mifare-rc522-speed-{}.conf", std::process::id()));
+        let mut config = SpeedConfig::load(path.clone());
+        assert_eq!(config.get("spi0.0/v92"), None);
+        config.set("spi0.0/v92", 4_000_000).unwrap();
+        config.set("spi0.0/v91", 1_000_000).unwrap();
+        config.set("spi0.0/v92", 2_000_000).unwrap();
+
+        let reloaded = SpeedConfig::load(path.clone());
+        assert_eq!(reloaded.get("spi0.0/v92"), Some(2_000_000));
+        assert_eq!(reloaded.get("spi0.0/v91"), Some(1_000_000));
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,81 @@
+use std::error::Error;
+
+use super::operations::Session;
+use super::speed::ErrorCounts;
+
+// The bus the MFRC522 is wired to. Every register access is one full duplex
+// transfer: the address byte (and data) go out while the answer comes back in the
+// same number of bytes.
+//
+// The `rppal` feature implements it for `rppal::spi::Spi`. On other hosts, or to
+// replay recorded traffic in tests, implement it for your own SPI handle.
+pub trait Transport {
+    // Send `tx` and fill `rx` (of the same length) with what the chip clocked back
+    fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>>;
+
+    // What the driver keeps about this reader between calls. A transport without it
+    // sends every authentication to the card; wrap it in a `Reader` to keep it.
+    fn state(&self) -> Option<&ReaderState> {
+        None
+    }
+
+    fn state_mut(&mut self) -> Option<&mut ReaderState> {
+        None
+    }
+}
+
+// The state of one reader: the crypto session of the card in its field, and the
+// communication errors counted against its SPI speed
+#[derive(Clone, Debug, Default)]
+pub struct ReaderState {
+    pub(crate) session: Session,
+    pub(crate) errors: ErrorCounts,
+}
+
+// A transport and the state of the reader behind it, which goes with it when it's
+// dropped
+#[derive(Debug, Default)]
+pub struct Reader<T> {
+    transport: T,
+    state: ReaderState,
+}
+
+impl<T: Transport> Reader<T> {
+    pub fn new(transport: T) -> Self {
+        Reader { transport, state: ReaderState::default() }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn transport_mut(&mut self) -> &mut T {
+        &mut self.transport
+    }
+
+    pub fn into_inner(self) -> T {
+        self.transport
+    }
+}
+
+impl<T: Transport> Transport for Reader<T> {
+    fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>> {
+        self.transport.transfer(rx, tx)
+    }
+
+    fn state(&self) -> Option<&ReaderState> {
+        Some(&self.state)
+    }
+
+    fn state_mut(&mut self) -> Option<&mut ReaderState> {
+        Some(&mut self.state)
+    }
+}
+
+#[cfg(feature = "rppal")]
+impl Transport for rppal::spi::Spi {
+    fn transfer(&mut self, rx: &mut [u8], tx: &[u8]) -> Result<(), Box<dyn Error>> {
+        rppal::spi::Spi::transfer(self, rx, tx)?;
+        Ok(())
+    }
+}
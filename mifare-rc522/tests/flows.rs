@@ -0,0 +1,189 @@
+// Whole card flows against the simulated reader, run with `cargo test --features mock`.
+use mifare_rc522::mock::{MockCard, MockReader};
+use mifare_rc522::*;
+
+const DEFAULT_KEY: [u8; 6] = [0xFF; 6];
+
+// Wake, anticollide and select the card in the field, returns its UID and check byte
+fn select(spi: &mut MockReader) -> Vec<u8> {
+    let (status, _) = mfrc522_request(spi, PICC_REQALL).unwrap();
+    assert_eq!(status, MI_OK, "no card answered");
+    let (status, uid) = mfrc522_anticoll(spi).unwrap();
+    assert_eq!(status, MI_OK);
+    assert_eq!(mfrc522_select_tag(spi, &uid).unwrap(), 0x08);
+    uid
+}
+
+// Read all 64 blocks with key A of each sector, filling in the key A the card hides
+fn dump(spi: &mut MockReader, key_for: impl Fn(u8) -> [u8; 6]) -> Vec<[u8; 16]> {
+    let uid = select(spi);
+    let mut blocks = Vec::new();
+    for block in CardLayout::Classic1K.blocks() {
+        let key = key_for(block.sector().get());
+        assert_eq!(mfrc522_auth(spi, PICC_AUTHENT1A, block.get(), &key, &uid).unwrap(), MI_OK, "auth of block {}", block);
+        let data = mfrc522_read(spi, block.get()).unwrap().expect("block read");
+        let mut data: [u8; 16] = data.try_into().unwrap();
+        if block.is_trailer() {
+            data[..6].copy_from_slice(&key);
+        }
+        blocks.push(data);
+    }
+    mfrc522_stop_crypto1(spi).unwrap();
+    blocks
+}
+
+#[test]
+fn detect_authenticate_and_dump() {
+    let sector_1_key = [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+    let card = MockCard::new([0x11, 0x22, 0x33, 0x45])
+        .with_keys(1, sector_1_key, [0xB0; 6])
+        .with_block(4, *b"inventory #0042 ");
+    let mut spi = MockReader::with_card(card);
+    mfrc522_init(&mut spi).unwrap();
+
+    let blocks = dump(&mut spi, |sector| if sector == 1 { sector_1_key } else { DEFAULT_KEY });
+
+    let card = spi.card().unwrap();
+    for block in 0..64u8 {
+        assert_eq!(blocks[block as usize], card.block(block), "block {}", block);
+    }
+    assert_eq!(&blocks[4], b"inventory #0042 ");
+    assert_eq!(blocks[0][..5], [0x11, 0x22, 0x33, 0x45, 0x45]);  // UID and its check byte
+}
+
+#[test]
+fn wrong_key_is_refused_and_the_card_must_be_selected_again() {
+    let card = MockCard::new([0x21, 0x22, 0x23, 0x24]).with_keys(2, [0x12; 6], [0x34; 6]);
+    let mut spi = MockReader::with_card(card);
+    mfrc522_init(&mut spi).unwrap();
+
+    let uid = select(&mut spi);
+    assert_eq!(mfrc522_auth(&mut spi, PICC_AUTHENT1A, 8, &DEFAULT_KEY, &uid).unwrap(), MI_ERR);
+    // the refused authentication dropped the card back to idle
+    assert_eq!(mfrc522_auth(&mut spi, PICC_AUTHENT1B, 8, &[0x34; 6], &uid).unwrap(), MI_ERR);
+
+    let uid = select(&mut spi);
+    assert_eq!(mfrc522_auth(&mut spi, PICC_AUTHENT1B, 8, &[0x34; 6], &uid).unwrap(), MI_OK);
+    assert!(mfrc522_read(&mut spi, 9).unwrap().is_some());
+    // outside the authenticated sector the card answers with a NAK
+    assert!(mfrc522_read(&mut spi, 12).unwrap().is_none());
+}
+
+#[test]
+fn one_readers_session_is_not_reused_on_another() {
+    // the same UID in two readers, as with a card and its clone, with different keys
+    let uid = [0x81, 0x82, 0x83, 0x84];
+    let mut first = MockReader::with_card(MockCard::new(uid).with_keys(1, [0x1A; 6], [0x1B; 6]));
+    let mut second = MockReader::with_card(MockCard::new(uid).with_keys(1, [0x2A; 6], [0x2B; 6]));
+    mfrc522_init(&mut first).unwrap();
+    mfrc522_init(&mut second).unwrap();
+
+    let second_uid = select(&mut second);
+    assert_eq!(mfrc522_auth(&mut second, PICC_AUTHENT1A, 4, &[0x2A; 6], &second_uid).unwrap(), MI_OK);
+    let first_uid = select(&mut first);
+    assert_eq!(mfrc522_auth(&mut first, PICC_AUTHENT1A, 4, &[0x1A; 6], &first_uid).unwrap(), MI_OK);
+
+    // the first reader's key opens nothing on the second, though its sector is open
+    assert_eq!(mfrc522_auth(&mut second, PICC_AUTHENT1A, 5, &[0x1A; 6], &second_uid).unwrap(), MI_ERR);
+    assert_eq!(last_authentication(&first).map(|auth| auth.key), Some(vec![0x1A; 6]));
+    assert!(last_authentication(&second).is_none());
+}
+
+#[test]
+fn transactions_are_counted_per_reader() {
+    let mut first = MockReader::with_card(MockCard::new([0x91, 0x92, 0x93, 0x94]));
+    let mut second = MockReader::new();
+    mfrc522_init(&mut first).unwrap();
+    mfrc522_init(&mut second).unwrap();
+    select(&mut first);
+
+    let (transactions, errors) = take_error_counts(&mut first);
+    assert!(transactions > 0);
+    assert_eq!(errors, 0);
+    assert_eq!(take_error_counts(&mut second), (0, 0));
+    // taking them starts a new count
+    assert_eq!(take_error_counts(&mut first), (0, 0));
+}
+
+#[test]
+fn clone_onto_magic_card() {
+    let source = MockCard::new([0x31, 0x32, 0x33, 0x34])
+        .with_keys(5, [0x5A; 6], [0x5B; 6])
+        .with_block(21, [0xC0; 16]);
+    let mut spi = MockReader::with_card(source);
+    mfrc522_init(&mut spi).unwrap();
+    let blocks = dump(&mut spi, |sector| if sector == 5 { [0x5A; 6] } else { DEFAULT_KEY });
+
+    // an ordinary card has no backdoor and keeps block 0
+    spi.insert(MockCard::new([0x41, 0x42, 0x43, 0x44]));
+    let uid = select(&mut spi);
+    assert_eq!(mfrc522_auth(&mut spi, PICC_AUTHENT1A, 0, &DEFAULT_KEY, &uid).unwrap(), MI_OK);
+    assert_eq!(mfrc522_write(&mut spi, 0, &blocks[0]).unwrap(), MI_ERR);
+    select(&mut spi);
+    assert!(!mfrc522_open_backdoor(&mut spi).unwrap());
+
+    spi.insert(MockCard::magic([0x51, 0x52, 0x53, 0x54]));
+    select(&mut spi);
+    assert!(mfrc522_open_backdoor(&mut spi).unwrap());
+    for (block, data) in blocks.iter().enumerate() {
+        assert_eq!(mfrc522_write(&mut spi, block as u8, data).unwrap(), MI_OK, "write of block {}", block);
+    }
+    mfrc522_halt(&mut spi).unwrap();
+
+    let clone = spi.card().unwrap();
+    assert_eq!(clone.uid(), [0x31, 0x32, 0x33, 0x34]);
+    for (block, data) in blocks.iter().enumerate() {
+        assert_eq!(&clone.block(block as u8), data, "block {}", block);
+    }
+
+    // the clone now answers with the source's UID and keys
+    let uid = select(&mut spi);
+    assert_eq!(uid[..4], [0x31, 0x32, 0x33, 0x34]);
+    assert_eq!(mfrc522_auth(&mut spi, PICC_AUTHENT1A, 21, &[0x5A; 6], &uid).unwrap(), MI_OK);
+    assert_eq!(mfrc522_read(&mut spi, 21).unwrap(), Some(vec![0xC0; 16]));
+}
+
+#[test]
+fn nothing_answers_without_a_card_or_field() {
+    let mut spi = MockReader::with_card(MockCard::new([0x61, 0x62, 0x63, 0x64]));
+    mfrc522_init(&mut spi).unwrap();
+    assert_eq!(reader_status(&mut spi), ReaderStatus::Connected);
+
+    antenna_off(&mut spi).unwrap();
+    assert_eq!(mfrc522_request(&mut spi, PICC_REQIDL).unwrap().0, MI_ERR);
+    antenna_on(&mut spi).unwrap();
+    select(&mut spi);
+
+    spi.remove();
+    assert_eq!(mfrc522_request(&mut spi, PICC_REQIDL).unwrap().0, MI_ERR);
+}
+
+#[test]
+fn stacked_tags_are_all_enumerated_in_one_pass() {
+    // Ultralights sharing most of their UID, apart at either cascade level, and a Classic
+    let tags = [
+        [0x04, 0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF6],
+        [0x04, 0xA1, 0xB2, 0xC3, 0xD4, 0xE5, 0xF7],
+        [0x04, 0xA1, 0xB3, 0xC3, 0x10, 0x20, 0x30],
+    ];
+    let mut spi = MockReader::new();
+    for uid in tags {
+        spi.add(MockCard::ultralight(uid));
+    }
+    spi.add(MockCard::new([0x71, 0x72, 0x73, 0x74]));
+    mfrc522_init(&mut spi).unwrap();
+
+    let mut expected: Vec<DetectedCard> = tags.iter().map(|uid| DetectedCard { uid: uid.to_vec(), sak: 0x00 }).collect();
+    expected.push(DetectedCard { uid: vec![0x71, 0x72, 0x73, 0x74], sak: 0x08 });
+    expected.sort_by(|a, b| a.uid.cmp(&b.uid));
+
+    // the cards read are halted, the next cycle's wake-up finds them all again
+    for _ in 0..2 {
+        let mut cards = mfrc522_enumerate(&mut spi, MAX_CARDS).unwrap();
+        cards.sort_by(|a, b| a.uid.cmp(&b.uid));
+        assert_eq!(cards, expected);
+    }
+
+    spi.remove();
+    assert!(mfrc522_enumerate(&mut spi, MAX_CARDS).unwrap().is_empty());
+}
@@ -0,0 +1,358 @@
+// app.rs
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(feature = "inventory")]
+use inventory_db::{InventoryDB, InventoryItem};
+use mifare_tui::dumps::{self, SectorInfo};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::widgets::ListState;
+#[cfg(feature = "inventory")]
+use ratatui::widgets::TableState;
+use uid_codec::{KeyboardLayout, ALL_LAYOUTS};
+
+use crate::capture::{self, Scan};
+
+/// Most recent scans kept in the capture list
+const MAX_SCANS: usize = 500;
+
+/// Columns taken by one sector of the card map
+pub const MAP_CELL_WIDTH: u16 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Capture,
+    #[cfg(feature = "inventory")]
+    Inventory,
+    Dumps,
+}
+
+impl Tab {
+    #[cfg(feature = "inventory")]
+    pub const ALL: [Tab; 3] = [Tab::Capture, Tab::Inventory, Tab::Dumps];
+    #[cfg(not(feature = "inventory"))]
+    pub const ALL: [Tab; 2] = [Tab::Capture, Tab::Dumps];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Tab::Capture => "Capture",
+            #[cfg(feature = "inventory")]
+            Tab::Inventory => "Inventory",
+            Tab::Dumps => "Dumps",
+        }
+    }
+
+    fn next(&self) -> Tab {
+        let index = Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0);
+        Tab::ALL[(index + 1) % Tab::ALL.len()]
+    }
+}
+
+pub struct App {
+    pub tab: Tab,
+    pub layout: KeyboardLayout,
+    pub fifo_path: String,
+    pub dump_dir: String,
+    #[cfg(feature = "inventory")]
+    pub db: Option<InventoryDB>,
+
+    pub scans: Vec<Scan>,
+    pub scan_state: ListState,
+
+    #[cfg(feature = "inventory")]
+    pub items: Vec<InventoryItem>,
+    #[cfg(feature = "inventory")]
+    pub item_state: TableState,
+    #[cfg(feature = "inventory")]
+    pub search: String,
+    #[cfg(feature = "inventory")]
+    pub searching: bool,
+
+    pub dumps: Vec<PathBuf>,
+    pub dump_state: ListState,
+    pub dump_lines: Vec<String>,
+    pub dump_scroll: u16,
+    pub dump_sectors: Vec<SectorInfo>,
+    /// Sector picked on the card map
+    pub sector_selected: Option<usize>,
+    /// Where the card map was last drawn and how many sectors fit in a row, for clicks
+    pub map_area: Rect,
+    pub map_columns: u16,
+
+    /// One line of feedback shown in the footer
+    pub message: String,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn new(fifo_path: &str, dump_dir: &str, layout: KeyboardLayout) -> App {
+        let mut app = App {
+            tab: Tab::Capture,
+            layout,
+            fifo_path: fifo_path.to_string(),
+            dump_dir: dump_dir.to_string(),
+            #[cfg(feature = "inventory")]
+            db: None,
+            scans: Vec::new(),
+            scan_state: ListState::default(),
+            #[cfg(feature = "inventory")]
+            items: Vec::new(),
+            #[cfg(feature = "inventory")]
+            item_state: TableState::default(),
+            #[cfg(feature = "inventory")]
+            search: String::new(),
+            #[cfg(feature = "inventory")]
+            searching: false,
+            dumps: Vec::new(),
+            dump_state: ListState::default(),
+            dump_lines: Vec::new(),
+            dump_scroll: 0,
+            dump_sectors: Vec::new(),
+            sector_selected: None,
+            map_area: Rect::default(),
+            map_columns: 1,
+            message: format!("Listening on {}", fifo_path),
+            should_quit: false,
+        };
+        app.refresh_dumps();
+        app
+    }
+
+    /// Open the inventory scans are looked up in and counted into
+    #[cfg(feature = "inventory")]
+    pub fn open_inventory(&mut self, db_path: &str) {
+        match InventoryDB::new(db_path) {
+            Ok(db) => self.db = Some(db),
+            Err(e) => self.message = format!("Inventory unavailable ({}): {}", db_path, e),
+        }
+        self.refresh_items();
+    }
+
+    /// Called on every tick to pick up new scans
+    pub fn poll_scans(&mut self) {
+        for raw in capture::poll_fifo(&self.fifo_path) {
+            let scan = Scan::decode(&raw, self.layout);
+            #[cfg(feature = "inventory")]
+            let scan = Scan { item: self.item_name(&scan.tag_id()), ..scan };
+
+            self.message = format!("Scanned {}", scan.hex_uid);
+            self.scans.insert(0, scan);
+            self.scans.truncate(MAX_SCANS);
+            self.scan_state.select(Some(0));
+        }
+    }
+
+    /// Name of the item the inventory has under `tag_id`
+    #[cfg(feature = "inventory")]
+    fn item_name(&self, tag_id: &str) -> Option<String> {
+        self.db.as_ref()?.get_item_by_any_tag(tag_id).ok().flatten().map(|item| item.name)
+    }
+
+    #[cfg(feature = "inventory")]
+    pub fn refresh_items(&mut self) {
+        if let Some(db) = &self.db {
+            match db.search_items(&self.search) {
+                Ok(items) => self.items = items,
+                Err(e) => self.message = format!("Error loading inventory: {}", e),
+            }
+        }
+        self.item_state.select(clamp_index(self.item_state.selected(), self.items.len()));
+    }
+
+    pub fn refresh_dumps(&mut self) {
+        self.dumps = dumps::list_dumps(&self.dump_dir);
+        let selected = self.dump_state.selected();
+        self.dump_state.select(clamp_index(self.dump_state.selected(), self.dumps.len()));
+        if self.dump_state.selected() != selected || self.dump_lines.is_empty() {
+            self.open_selected_dump();
+        }
+    }
+
+    fn open_selected_dump(&mut self) {
+        self.dump_scroll = 0;
+        self.sector_selected = None;
+        let path = self.dump_state.selected().and_then(|i| self.dumps.get(i));
+        self.dump_lines = match path {
+            Some(path) => dumps::render_dump(path),
+            None => vec![format!("No dumps (.bin, .mfd, .dump) in {}", self.dump_dir)],
+        };
+        self.dump_sectors = path
+            .and_then(|path| fs::read(path).ok())
+            .map(|data| dumps::sector_map(&data))
+            .unwrap_or_default();
+    }
+
+    /// Pick a sector on the card map and scroll the dump to its blocks
+    fn select_sector(&mut self, index: usize) {
+        let sector = match self.dump_sectors.get(index) {
+            Some(info) => info.sector,
+            None => return,
+        };
+        self.sector_selected = Some(index);
+        if let Some(line) = self.dump_lines.iter().position(|line| *line == dumps::sector_heading(sector)) {
+            self.dump_scroll = line as u16;
+        }
+    }
+
+    fn cycle_layout(&mut self) {
+        let next = (self.layout.index() + 1) % ALL_LAYOUTS.len() as i32;
+        self.layout = KeyboardLayout::from_index(next);
+        self.message = format!("Keyboard layout: {}", self.layout.name());
+    }
+
+    #[cfg(feature = "inventory")]
+    fn adjust_quantity(&mut self, tag_id: &str, delta: i32) {
+        let db = match &self.db {
+            Some(db) => db,
+            None => return,
+        };
+
+        self.message = match db.adjust_quantity(tag_id, delta) {
+            Ok(Some(_)) => format!("Quantity of {} changed by {:+}", tag_id, delta),
+            Ok(None) => format!("{} is not in the inventory, add it from the desktop app", tag_id),
+            Err(e) => format!("Error updating {}: {}", tag_id, e),
+        };
+        self.refresh_items();
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        // while typing a search every printable key goes into the query
+        #[cfg(feature = "inventory")]
+        if self.searching {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => self.searching = false,
+                KeyCode::Backspace => {
+                    self.search.pop();
+                    self.refresh_items();
+                },
+                KeyCode::Char(c) => {
+                    self.search.push(c);
+                    self.refresh_items();
+                },
+                _ => {},
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Tab => self.tab = self.tab.next(),
+            KeyCode::Char(c @ '1'..='9') if Tab::ALL.len() > c as usize - '1' as usize => {
+                self.tab = Tab::ALL[c as usize - '1' as usize];
+            },
+            _ => match self.tab {
+                Tab::Capture => self.handle_capture_key(key),
+                #[cfg(feature = "inventory")]
+                Tab::Inventory => self.handle_inventory_key(key),
+                Tab::Dumps => self.handle_dumps_key(key),
+            },
+        }
+    }
+
+    fn handle_capture_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => self.scan_state.select(previous_index(self.scan_state.selected(), self.scans.len())),
+            KeyCode::Down => self.scan_state.select(next_index(self.scan_state.selected(), self.scans.len())),
+            KeyCode::Char('l') => self.cycle_layout(),
+            KeyCode::Char('c') => {
+                self.scans.clear();
+                self.scan_state.select(None);
+            },
+            // count the selected scan into the inventory
+            #[cfg(feature = "inventory")]
+            KeyCode::Char('+') => {
+                if let Some(tag_id) = self.scan_state.selected().and_then(|i| self.scans.get(i)).map(|s| s.tag_id()) {
+                    self.adjust_quantity(&tag_id, 1);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    #[cfg(feature = "inventory")]
+    fn handle_inventory_key(&mut self, key: KeyEvent) {
+        let selected_tag = self.item_state.selected().and_then(|i| self.items.get(i)).map(|item| item.tag_id.clone());
+        match key.code {
+            KeyCode::Up => self.item_state.select(previous_index(self.item_state.selected(), self.items.len())),
+            KeyCode::Down => self.item_state.select(next_index(self.item_state.selected(), self.items.len())),
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Char('r') => self.refresh_items(),
+            KeyCode::Char('+') => {
+                if let Some(tag_id) = selected_tag {
+                    self.adjust_quantity(&tag_id, 1);
+                }
+            },
+            KeyCode::Char('-') => {
+                if let Some(tag_id) = selected_tag {
+                    self.adjust_quantity(&tag_id, -1);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn handle_dumps_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Up => {
+                self.dump_state.select(previous_index(self.dump_state.selected(), self.dumps.len()));
+                self.open_selected_dump();
+            },
+            KeyCode::Down => {
+                self.dump_state.select(next_index(self.dump_state.selected(), self.dumps.len()));
+                self.open_selected_dump();
+            },
+            KeyCode::Left => {
+                if let Some(index) = previous_index(self.sector_selected, self.dump_sectors.len()) {
+                    self.select_sector(index);
+                }
+            },
+            KeyCode::Right => {
+                if let Some(index) = next_index(self.sector_selected, self.dump_sectors.len()) {
+                    self.select_sector(index);
+                }
+            },
+            KeyCode::PageDown => self.dump_scroll = self.dump_scroll.saturating_add(10),
+            KeyCode::PageUp => self.dump_scroll = self.dump_scroll.saturating_sub(10),
+            KeyCode::Char('r') => self.refresh_dumps(),
+            _ => {},
+        }
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.tab != Tab::Dumps || mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        if !self.map_area.contains(Position::new(mouse.column, mouse.row)) {
+            return;
+        }
+        let column = (mouse.column - self.map_area.x) / MAP_CELL_WIDTH;
+        let row = mouse.row - self.map_area.y;
+        if column < self.map_columns {
+            self.select_sector((row * self.map_columns + column) as usize);
+        }
+    }
+}
+
+fn next_index(current: Option<usize>, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(current.map_or(0, |i| (i + 1).min(len - 1)))
+}
+
+fn previous_index(current: Option<usize>, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    Some(current.map_or(0, |i| i.saturating_sub(1)))
+}
+
+/// Keep a selection inside the list after it was reloaded
+fn clamp_index(current: Option<usize>, len: usize) -> Option<usize> {
+    match (current, len) {
+        (_, 0) => None,
+        (Some(i), _) => Some(i.min(len - 1)),
+        (None, _) => Some(0),
+    }
+}
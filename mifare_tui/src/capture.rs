@@ -0,0 +1,69 @@
+// capture.rs
+//
+// Reads scans from the same FIFO the FLTK app uses ("timestamp,card_data" lines)
+// and decodes them with uid_codec.
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+
+use uid_codec::{decode_uid, format_hex_uid, hex_to_decimal, lookup_manufacturer, KeyboardLayout};
+
+pub const DEFAULT_FIFO: &str = "/tmp/rfid_scans.fifo";
+
+/// A decoded scan for the capture list
+#[derive(Debug, Clone)]
+pub struct Scan {
+    pub time: String,
+    pub raw: String,
+    pub hex_uid: String,
+    pub decimal: String,
+    pub manufacturer: String,
+    /// Inventory item name, if the tag is known
+    pub item: Option<String>,
+}
+
+impl Scan {
+    pub fn decode(raw: &str, layout: KeyboardLayout) -> Scan {
+        let clean = decode_uid(raw, layout);
+        Scan {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            raw: raw.to_string(),
+            hex_uid: format_hex_uid(&clean),
+            decimal: hex_to_decimal(&clean).map(|d| d.to_string()).unwrap_or_else(|| "n/a".to_string()),
+            manufacturer: lookup_manufacturer(&clean).unwrap_or("Unknown manufacturer").to_string(),
+            item: None,
+        }
+    }
+
+    /// Tag ID as stored in the inventory (hex without spaces)
+    #[cfg(feature = "inventory")]
+    pub fn tag_id(&self) -> String {
+        self.hex_uid.replace(' ', "")
+    }
+}
+
+/// Drain any pending card data from the FIFO without blocking
+pub fn poll_fifo(fifo_path: &str) -> Vec<String> {
+    let mut scans = Vec::new();
+    if !Path::new(fifo_path).exists() {
+        return scans;
+    }
+
+    if let Ok(file) = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(fifo_path)
+    {
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            if let Some((_, card_data)) = line.split_once(',') {
+                let card_data = card_data.trim();
+                if !card_data.is_empty() && !line.starts_with("firmware,") {
+                    scans.push(card_data.to_string());
+                }
+            }
+        }
+    }
+
+    scans
+}
@@ -0,0 +1,191 @@
+// dumps.rs
+//
+// Binary card dumps (.bin / .mfd / .dump, 16 bytes per block) shown sector by sector.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mifare_rc522::CardLayout;
+use uid_codec::{bytes_to_ascii, bytes_to_hex};
+
+pub const DEFAULT_DUMP_DIR: &str = "./dumps";
+
+const DUMP_EXTENSIONS: [&str; 3] = ["bin", "mfd", "dump"];
+
+/// Dump files in the directory, sorted by name
+pub fn list_dumps(dir: &str) -> Vec<PathBuf> {
+    let mut dumps: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| DUMP_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    dumps.sort();
+    dumps
+}
+
+/// Render a dump as text lines, one block per line grouped by sector.
+/// Sectors 0-31 have 4 blocks, 4K cards continue with 16 block sectors.
+pub fn render_dump(path: &Path) -> Vec<String> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(e) => return vec![format!("Error reading {}: {}", path.display(), e)],
+    };
+
+    let mut lines = vec![format!("{} ({} bytes, {} blocks)", path.display(), data.len(), data.len() / 16)];
+    lines.extend(render_blocks(&data));
+    lines
+}
+
+/// The block lines of render_dump for dump data, a short last block is shown as it is.
+/// Nothing past the last block of a 4K is shown.
+pub fn render_blocks(data: &[u8]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (block, chunk) in CardLayout::Classic4K.blocks().zip(data.chunks(16)) {
+        let sector = block.sector();
+
+        if block == sector.first_block() {
+            lines.push(String::new());
+            lines.push(sector_heading(sector.index()));
+        }
+
+        let trailer = if block.is_trailer() { "  (trailer)" } else { "" };
+        lines.push(format!("  {:3}: {}  {}{}", block, bytes_to_hex(chunk), bytes_to_ascii(chunk), trailer));
+    }
+
+    lines
+}
+
+/// The line render_dump starts a sector with
+pub fn sector_heading(sector: usize) -> String {
+    format!("Sector {}", sector)
+}
+
+/// What the trailer of a sector says about how it was read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectorStatus {
+    /// Default transport keys and access bits
+    ReadOk,
+    /// Key B left as zeros, only key A was known when the card was dumped
+    KeyAOnly,
+    /// Trailer missing or with access bits that don't check, the sector couldn't be read
+    Locked,
+    /// Keys or access bits changed from the transport configuration
+    TrailerModified,
+}
+
+impl SectorStatus {
+    pub const ALL: [SectorStatus; 4] = [
+        SectorStatus::ReadOk,
+        SectorStatus::KeyAOnly,
+        SectorStatus::Locked,
+        SectorStatus::TrailerModified,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SectorStatus::ReadOk => "read OK",
+            SectorStatus::KeyAOnly => "key A only",
+            SectorStatus::Locked => "locked",
+            SectorStatus::TrailerModified => "trailer modified",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectorInfo {
+    pub sector: usize,
+    pub first_block: usize,
+    pub blocks: usize,
+    pub status: SectorStatus,
+}
+
+const TRANSPORT_KEY: [u8; 6] = [0xFF; 6];
+const TRANSPORT_ACCESS: [u8; 3] = [0xFF, 0x07, 0x80];
+
+/// Status of every sector in the dump data, judged from its trailer
+pub fn sector_map(data: &[u8]) -> Vec<SectorInfo> {
+    let blocks = data.len() / 16;
+    CardLayout::Classic4K.sectors()
+        .take_while(|sector| sector.first_block().index() < blocks)
+        .map(|sector| {
+            let trailer = sector.trailer().index();
+            SectorInfo {
+                sector: sector.index(),
+                first_block: sector.first_block().index(),
+                blocks: sector.block_count() as usize,
+                status: trailer_status(data.get(trailer * 16..(trailer + 1) * 16)),
+            }
+        })
+        .collect()
+}
+
+fn trailer_status(trailer: Option<&[u8]>) -> SectorStatus {
+    let trailer = match trailer {
+        Some(trailer) if access_bits_valid(&trailer[6..9]) => trailer,
+        _ => return SectorStatus::Locked,
+    };
+    let (key_a, access, key_b) = (&trailer[0..6], &trailer[6..9], &trailer[10..16]);
+
+    if key_b.iter().all(|byte| *byte == 0) && key_a.iter().any(|byte| *byte != 0) {
+        SectorStatus::KeyAOnly
+    } else if key_a != TRANSPORT_KEY || key_b != TRANSPORT_KEY || access != TRANSPORT_ACCESS {
+        SectorStatus::TrailerModified
+    } else {
+        SectorStatus::ReadOk
+    }
+}
+
+/// Bytes 6-8 of a trailer hold each access bit twice, once inverted
+fn access_bits_valid(access: &[u8]) -> bool {
+    let nibble = |byte: u8, high: bool| if high { byte >> 4 } else { byte & 0x0F };
+    let inverted = |nibble: u8| !nibble & 0x0F;
+    nibble(access[0], false) == inverted(nibble(access[1], true))
+        && nibble(access[0], true) == inverted(nibble(access[2], false))
+        && nibble(access[1], false) == inverted(nibble(access[2], true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_marks_sectors_and_trailers() {
+        let path = std::env::temp_dir().join(format!("mifare_tui_test_{}.mfd", std::process::id()));
+        fs::write(&path, vec![0u8; 1024]).unwrap();
+        let lines = render_dump(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(lines[0].ends_with("(1024 bytes, 64 blocks)"));
+        assert_eq!(lines.iter().filter(|l| l.starts_with("Sector ")).count(), 16);
+        assert_eq!(lines.iter().filter(|l| l.ends_with("(trailer)")).count(), 16);
+        assert!(lines.iter().any(|l| l.starts_with("    3:") && l.ends_with("(trailer)")));
+    }
+
+    #[test]
+    fn sector_map_reads_status_from_trailers() {
+        let transport = [[0xFF; 6].as_slice(), &[0xFF, 0x07, 0x80, 0x69], &[0xFF; 6]].concat();
+        let mut data = vec![0u8; 4096];
+        for sector in CardLayout::Classic4K.sectors() {
+            let trailer = sector.trailer().index();
+            data[trailer * 16..trailer * 16 + 16].copy_from_slice(&transport);
+        }
+        data[7 * 16..7 * 16 + 6].copy_from_slice(&[0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]);
+        data[11 * 16 + 10..11 * 16 + 16].fill(0);
+        data[15 * 16 + 6..15 * 16 + 9].fill(0);
+        data[143 * 16 + 6..143 * 16 + 9].copy_from_slice(&[0x7F, 0x07, 0x88]);
+
+        let map = sector_map(&data);
+        assert_eq!(map.len(), 40);
+        assert_eq!(map[0].status, SectorStatus::ReadOk);
+        assert_eq!(map[1].status, SectorStatus::TrailerModified);
+        assert_eq!(map[2].status, SectorStatus::KeyAOnly);
+        assert_eq!(map[3].status, SectorStatus::Locked);
+        assert_eq!((map[32].first_block, map[32].blocks, map[32].status), (128, 16, SectorStatus::TrailerModified));
+        assert_eq!(sector_map(&data[..1000])[15].status, SectorStatus::Locked);
+    }
+}
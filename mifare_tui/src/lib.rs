@@ -0,0 +1,5 @@
+// lib.rs
+//
+// The dump file reader, as a library so the fuzz targets in fuzz/ can feed it
+// arbitrary files.
+pub mod dumps;
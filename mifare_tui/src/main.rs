@@ -0,0 +1,78 @@
+// main.rs
+//
+// Terminal front-end for the Mifare reader and inventory, for Pis run headless
+// over SSH. Scans are read from the same FIFO and inventory.db as the FLTK app.
+mod app;
+mod capture;
+mod ui;
+
+use std::io;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind};
+use ratatui::crossterm::execute;
+use mifare_tui::dumps;
+use uid_codec::KeyboardLayout;
+
+use app::App;
+
+/// How often the FIFO is checked for new scans
+const TICK: Duration = Duration::from_millis(100);
+
+#[cfg(feature = "inventory")]
+const USAGE: &str = "Usage: mifare_tui [--db PATH] [--fifo PATH] [--dumps DIR] [--layout 0-5]";
+#[cfg(not(feature = "inventory"))]
+const USAGE: &str = "Usage: mifare_tui [--fifo PATH] [--dumps DIR] [--layout 0-5]";
+
+fn main() -> io::Result<()> {
+    #[cfg(feature = "inventory")]
+    let mut db_path = "inventory.db".to_string();
+    let mut fifo_path = capture::DEFAULT_FIFO.to_string();
+    let mut dump_dir = dumps::DEFAULT_DUMP_DIR.to_string();
+    let mut layout = KeyboardLayout::Auto;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            #[cfg(feature = "inventory")]
+            ("--db", Some(value)) => db_path = value,
+            ("--fifo", Some(value)) => fifo_path = value,
+            ("--dumps", Some(value)) => dump_dir = value,
+            ("--layout", Some(value)) => layout = KeyboardLayout::from_index(value.parse().unwrap_or(0)),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let mut app = App::new(&fifo_path, &dump_dir, layout);
+    #[cfg(feature = "inventory")]
+    app.open_inventory(&db_path);
+
+    let mut terminal = ratatui::init();
+    // clicks on the card map of the Dumps tab
+    execute!(io::stdout(), EnableMouseCapture)?;
+    let result = run(&mut terminal, &mut app);
+    let _ = execute!(io::stdout(), DisableMouseCapture);
+    ratatui::restore();
+    result
+}
+
+fn run(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if event::poll(TICK)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => app.handle_key(key),
+                Event::Mouse(mouse) => app.handle_mouse(mouse),
+                _ => {},
+            }
+        }
+
+        app.poll_scans();
+    }
+
+    Ok(())
+}
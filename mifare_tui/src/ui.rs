@@ -0,0 +1,212 @@
+// ui.rs
+use mifare_tui::dumps::SectorStatus;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Tabs},
+    Frame,
+};
+#[cfg(feature = "inventory")]
+use ratatui::widgets::{Cell, Row, Table};
+
+use crate::app::{App, Tab, MAP_CELL_WIDTH};
+use crate::capture::Scan;
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [tabs_area, body_area, footer_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(2),
+    ]).areas(frame.area());
+
+    let titles: Vec<String> = Tab::ALL.iter()
+        .enumerate()
+        .map(|(i, tab)| format!("{} {}", i + 1, tab.title()))
+        .collect();
+    let selected = Tab::ALL.iter().position(|tab| *tab == app.tab).unwrap_or(0);
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL).title(" Mifare Reader Utility "))
+        .select(selected)
+        .highlight_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    frame.render_widget(tabs, tabs_area);
+
+    match app.tab {
+        Tab::Capture => draw_capture(frame, app, body_area),
+        #[cfg(feature = "inventory")]
+        Tab::Inventory => draw_inventory(frame, app, body_area),
+        Tab::Dumps => draw_dumps(frame, app, body_area),
+    }
+
+    let keys = match app.tab {
+        #[cfg(feature = "inventory")]
+        Tab::Capture => "↑↓ select  + count into inventory  l layout  c clear",
+        #[cfg(not(feature = "inventory"))]
+        Tab::Capture => "↑↓ select  l layout  c clear",
+        #[cfg(feature = "inventory")]
+        Tab::Inventory if app.searching => "type to search  Enter/Esc done",
+        #[cfg(feature = "inventory")]
+        Tab::Inventory => "↑↓ select  +/- quantity  / search  r reload",
+        Tab::Dumps => "↑↓ select dump  ←→/click sector  PgUp/PgDn scroll  r reload",
+    };
+    let footer = Paragraph::new(vec![
+        Line::from(app.message.as_str()),
+        Line::from(format!("{}  Tab/1-{} switch  q quit", keys, Tab::ALL.len())).style(Style::default().fg(Color::DarkGray)),
+    ]);
+    frame.render_widget(footer, footer_area);
+}
+
+fn draw_capture(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let [list_area, detail_area] = Layout::horizontal([Constraint::Percentage(45), Constraint::Percentage(55)]).areas(area);
+
+    let items: Vec<ListItem> = app.scans.iter()
+        .map(|scan| ListItem::new(format!("{}  {}  {}", scan.time, scan.hex_uid, item_name(scan))))
+        .collect();
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(format!(" Scans ({}) - layout: {} ", app.scans.len(), app.layout.name())))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_area, &mut app.scan_state);
+
+    let detail = match app.scan_state.selected().and_then(|i| app.scans.get(i)) {
+        Some(scan) => {
+            let mut lines = vec![
+                Line::from(format!("Raw UID:      {}", scan.raw)),
+                Line::from(format!("Hex:          {}", scan.hex_uid)),
+                Line::from(format!("Decimal:      {}", scan.decimal)),
+                Line::from(format!("Manufacturer: {}", scan.manufacturer)),
+                Line::from(format!("Card type:    {}", uid_codec::identify_card_type(&scan.hex_uid))),
+            ];
+            if cfg!(feature = "inventory") {
+                lines.push(Line::from(format!("Item:         {}", item_name(scan))));
+            }
+            lines
+        },
+        None => vec![Line::from(format!("Waiting for scans on {}", app.fifo_path))],
+    };
+    frame.render_widget(Paragraph::new(detail).block(Block::default().borders(Borders::ALL).title(" Details ")), detail_area);
+}
+
+/// Inventory item of a scan, nothing when the inventory isn't built in
+fn item_name(scan: &Scan) -> &str {
+    if cfg!(feature = "inventory") {
+        scan.item.as_deref().unwrap_or("(not in inventory)")
+    } else {
+        ""
+    }
+}
+
+#[cfg(feature = "inventory")]
+fn draw_inventory(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let [table_area, description_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(area);
+
+    let header = Row::new(["Tag ID", "Name", "Qty", "Location", "Category", "Updated"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+    let rows = app.items.iter().map(|item| {
+        Row::new([
+            Cell::from(item.tag_id.clone()),
+            Cell::from(item.name.clone()),
+            Cell::from(item.quantity.to_string()),
+            Cell::from(item.location.clone().unwrap_or_default()),
+            Cell::from(item.category.clone().unwrap_or_default()),
+            Cell::from(item.last_updated.chars().take(19).collect::<String>()),
+        ])
+    });
+
+    let title = if app.db.is_none() {
+        " Inventory (database unavailable) ".to_string()
+    } else if app.search.is_empty() && !app.searching {
+        format!(" Inventory ({} items) ", app.items.len())
+    } else {
+        format!(" Inventory ({} items) - search: {}{} ", app.items.len(), app.search, if app.searching { "_" } else { "" })
+    };
+
+    let table = Table::new(rows, [
+        Constraint::Length(16),
+        Constraint::Min(16),
+        Constraint::Length(5),
+        Constraint::Length(14),
+        Constraint::Length(14),
+        Constraint::Length(19),
+    ])
+    .header(header)
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(table, table_area, &mut app.item_state);
+
+    let description = app.item_state.selected()
+        .and_then(|i| app.items.get(i))
+        .and_then(|item| item.description.clone())
+        .unwrap_or_default();
+    frame.render_widget(Paragraph::new(description).block(Block::default().borders(Borders::ALL).title(" Description ")), description_area);
+}
+
+fn draw_dumps(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
+    let [list_area, dump_area] = Layout::horizontal([Constraint::Length(30), Constraint::Min(40)]).areas(area);
+
+    let files: Vec<ListItem> = app.dumps.iter()
+        .map(|path| ListItem::new(path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()))
+        .collect();
+    let list = List::new(files)
+        .block(Block::default().borders(Borders::ALL).title(format!(" {} ", app.dump_dir)))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, list_area, &mut app.dump_state);
+
+    // card map above the blocks: one cell per sector, rows as wide as the pane allows
+    let columns = (dump_area.width.saturating_sub(2) / MAP_CELL_WIDTH).max(1);
+    let map_rows = (app.dump_sectors.len() as u16).div_ceil(columns);
+    let map_height = if map_rows == 0 { 0 } else { map_rows + 3 };
+    let [map_area, dump_area] = Layout::vertical([Constraint::Length(map_height), Constraint::Min(3)]).areas(dump_area);
+    if map_rows > 0 {
+        draw_card_map(frame, app, map_area, columns);
+    }
+
+    let lines: Vec<Line> = app.dump_lines.iter().map(|line| Line::from(line.as_str())).collect();
+    let dump = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(" Dump "))
+        .scroll((app.dump_scroll, 0));
+    frame.render_widget(dump, dump_area);
+}
+
+fn status_color(status: SectorStatus) -> Color {
+    match status {
+        SectorStatus::ReadOk => Color::Green,
+        SectorStatus::KeyAOnly => Color::Yellow,
+        SectorStatus::Locked => Color::Red,
+        SectorStatus::TrailerModified => Color::Magenta,
+    }
+}
+
+fn draw_card_map(frame: &mut Frame, app: &mut App, area: Rect, columns: u16) {
+    let block = Block::default().borders(Borders::ALL).title(format!(" Card map ({} sectors) ", app.dump_sectors.len()));
+    let inner = block.inner(area);
+    app.map_area = Rect { height: inner.height.saturating_sub(1), ..inner };
+    app.map_columns = columns;
+
+    let mut lines: Vec<Line> = app.dump_sectors
+        .chunks(columns as usize)
+        .enumerate()
+        .map(|(row, sectors)| {
+            let cells: Vec<Span> = sectors.iter()
+                .enumerate()
+                .map(|(column, info)| {
+                    let mut style = Style::default().fg(Color::Black).bg(status_color(info.status));
+                    if app.sector_selected == Some(row * columns as usize + column) {
+                        style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                    }
+                    Span::styled(format!("{:>3} ", info.sector), style)
+                })
+                .collect();
+            Line::from(cells)
+        })
+        .collect();
+
+    let legend: Vec<Span> = SectorStatus::ALL.iter()
+        .flat_map(|status| [
+            Span::styled("  ", Style::default().bg(status_color(*status))),
+            Span::raw(format!(" {}  ", status.label())),
+        ])
+        .collect();
+    lines.push(Line::from(legend));
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,704 @@
+// api.rs
+//
+// REST endpoints plus the embedded web page:
+//   GET    /                          web interface
+//   GET    /api/scans                 recent scans, newest first
+//   GET    /api/events                live scan feed (server-sent events)
+//   GET    /api/ws                    live scan feed (WebSocket, JSON text messages)
+//   GET    /api/inventory?q=term      inventory items, optionally filtered
+//   GET    /api/inventory/<tag>       one item
+//   POST   /api/inventory             add or replace an item (JSON body)
+//   POST   /api/inventory/<tag>/adjust  change the quantity, body {"delta": n}
+//   DELETE /api/inventory/<tag>       remove the item, <tag> may be any of its tags
+//   GET    /api/progress              long operations running on the Pi (dumps, attacks,
+//                                     batch conversions, imports) and how far along they are
+//   GET    /api/card                  UID, SAK and layout of the card on the reader
+//   GET    /api/card/block/<n>?key=HEX&type=A|B  one block, read with the key (default
+//                                     the transport key, Key A)
+//   GET    /api/card/dump?key=HEX&type=A|B  every sector, null where the key is refused
+//
+// The card routes answer 503 unless the station was started with --reader (see card.rs).
+//
+// With a token the card routes and every request but a GET need "Authorization: Bearer
+// <token>". The server only listens beyond loopback with one (see main.rs). Without one
+// any page open in a browser on the station could still send it changes, so a change
+// must be JSON (415 otherwise) and come from this server's own page (403 for another
+// Origin). Bodies over MAX_BODY are refused with 413.
+//
+// Both live feeds take filters in the query string: unknown=true for tags that are not
+// in the inventory, reader=<name> for scans from one reader. /api/scans takes them too.
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use inventory_db::{InventoryChange, InventoryDB, NewItem};
+use progress_sink::{read_statuses, STATUS_DIR};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, StatusCode};
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tiny_http::ReadWrite;
+use tungstenite::{Message, WebSocket};
+
+use mifare_rc522::{Key, Transport, PICC_AUTHENT1A, PICC_AUTHENT1B};
+
+use crate::card::{self, CardError, Reader};
+use crate::scans::{FeedEvent, ScanFilter, ScanHub};
+
+const INDEX_HTML: &str = include_str!("../static/index.html");
+
+/// Comment line sent on idle event streams so proxies and phones keep the connection
+pub(crate) const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Largest request body taken, an inventory item is well under a kilobyte
+pub(crate) const MAX_BODY: usize = 64 * 1024;
+
+/// Requests handled at once, each on its own thread. Live feeds keep theirs for as long
+/// as the page is open, so this is well above what a few phones and laptops need.
+pub const MAX_WORKERS: usize = 64;
+
+pub struct AppState {
+    pub db: Arc<Mutex<InventoryDB>>,
+    pub hub: Arc<ScanHub>,
+    /// What the card routes and requests that change something must carry, None when
+    /// only this machine can reach the server
+    pub token: Option<String>,
+    /// The MFRC522 the card routes drive, None when scans only come through the FIFO
+    pub reader: Option<Reader>,
+}
+
+#[derive(Debug)]
+enum Reply {
+    Json(u16, Value),
+    Html(&'static str),
+    Events(ScanFilter),
+    WebSocket(ScanFilter),
+}
+
+#[derive(Deserialize)]
+struct Adjust {
+    delta: i32,
+}
+
+/// Counts the request threads, so a flood of connections can't start them without end
+#[derive(Clone)]
+pub struct Workers {
+    busy: Arc<AtomicUsize>,
+    limit: usize,
+}
+
+/// Frees a worker when its thread ends, even one that panicked
+struct WorkerSlot(Arc<AtomicUsize>);
+
+impl Drop for WorkerSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Workers {
+    pub fn new(limit: usize) -> Self {
+        Workers { busy: Arc::new(AtomicUsize::new(0)), limit }
+    }
+
+    /// Handle the request on a thread of its own, or answer 503 when `limit` are busy.
+    /// Returns whether it was taken.
+    pub fn spawn(&self, request: Request, handler: impl FnOnce(Request) + Send + 'static) -> bool {
+        if self.busy.fetch_add(1, Ordering::SeqCst) >= self.limit {
+            self.busy.fetch_sub(1, Ordering::SeqCst);
+            let _ = request.respond(json_response(503, json!({ "error": "Too many requests at once, try again" })));
+            return false;
+        }
+
+        let slot = WorkerSlot(self.busy.clone());
+        thread::spawn(move || {
+            let _slot = slot;
+            handler(request);
+        });
+        true
+    }
+}
+
+/// Path, query string and body of a request, or the status and error to answer with
+pub(crate) fn read_request(request: &mut Request) -> Result<(String, String, String), (u16, String)> {
+    let (path, query) = match request.url().split_once('?') {
+        Some((path, query)) => (path.to_string(), query.to_string()),
+        None => (request.url().to_string(), String::new()),
+    };
+
+    // only POST carries a body, upgrade requests would have the reader wait for the whole connection
+    let mut body = String::new();
+    if *request.method() == Method::Post {
+        let too_large = || (413, format!("Request body over {} bytes", MAX_BODY));
+        if request.body_length().is_some_and(|length| length > MAX_BODY) {
+            return Err(too_large());
+        }
+        // a chunked body has no length up front
+        request.as_reader().take(MAX_BODY as u64 + 1).read_to_string(&mut body)
+            .map_err(|e| (400, format!("Invalid request body: {}", e)))?;
+        if body.len() > MAX_BODY {
+            return Err(too_large());
+        }
+    }
+
+    Ok((path, query, body))
+}
+
+/// Decoded path segments, "/api/inventory/04A1" gives ["api", "inventory", "04A1"]
+pub(crate) fn path_segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(percent_decode)
+        .collect()
+}
+
+pub fn handle(mut request: Request, state: &AppState) {
+    let (path, query, body) = match read_request(&mut request) {
+        Ok(parts) => parts,
+        Err((status, e)) => {
+            let _ = request.respond(json_response(status, json!({ "error": e })));
+            return;
+        }
+    };
+
+    if let Some((status, e)) = refusal(&request, &path, state.token.as_deref()) {
+        let _ = request.respond(json_response(status, json!({ "error": e })));
+        return;
+    }
+
+    let reply = route(request.method(), &path, &query, &body, state);
+    let result = match reply {
+        Reply::Json(status, value) => request.respond(json_response(status, value)),
+        Reply::Html(page) => request.respond(
+            Response::from_string(page).with_header(content_type("text/html; charset=utf-8")),
+        ),
+        Reply::Events(filter) => {
+            stream_events(request, &state.hub, &filter);
+            Ok(())
+        },
+        Reply::WebSocket(filter) => {
+            stream_websocket(request, &state.hub, &filter);
+            Ok(())
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error sending response: {}", e);
+    }
+}
+
+fn route(method: &Method, path: &str, query: &str, body: &str, state: &AppState) -> Reply {
+    let segments = path_segments(path);
+    let segments: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+
+    let db = || state.db.lock().map_err(|_| Reply::Json(500, json!({ "error": "Database unavailable" })));
+
+    let result = match (method, segments.as_slice()) {
+        (Method::Get, []) => Ok(Reply::Html(INDEX_HTML)),
+        (Method::Get, ["api", "scans"]) => {
+            let filter = scan_filter(query);
+            let scans: Vec<_> = state.hub.recent().into_iter().filter(|scan| filter.matches(scan)).collect();
+            Ok(Reply::Json(200, json!(scans)))
+        },
+        (Method::Get, ["api", "events"]) => Ok(Reply::Events(scan_filter(query))),
+        (Method::Get, ["api", "ws"]) => Ok(Reply::WebSocket(scan_filter(query))),
+        (Method::Get, ["api", "inventory"]) => {
+            let term = query_param(query, "q").unwrap_or_default();
+            db().map(|db| db_reply(db.search_items(&term).map(|items| json!(items))))
+        },
+        (Method::Get, ["api", "inventory", tag]) => db().map(|db| match db.get_item_by_any_tag(tag) {
+            Ok(Some(item)) => Reply::Json(200, json!(item)),
+            Ok(None) => not_found(tag),
+            Err(e) => db_error(e),
+        }),
+        (Method::Post, ["api", "inventory"]) => match serde_json::from_str::<NewItem>(body) {
+            Ok(item) if item.tag_id.trim().is_empty() || item.name.trim().is_empty() => {
+                Ok(Reply::Json(400, json!({ "error": "tag_id and name are required" })))
+            },
+            Ok(item) if item.expiry_date.as_deref()
+                .is_some_and(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err()) => {
+                Ok(Reply::Json(400, json!({ "error": "expiry_date must be YYYY-MM-DD" })))
+            },
+            Ok(item) => db().map(|db| match db.upsert_item(&item) {
+                Ok(saved) => changed(state, InventoryChange::Saved(saved)),
+                Err(e) => db_error(e),
+            }),
+            Err(e) => Ok(Reply::Json(400, json!({ "error": format!("Invalid item: {}", e) }))),
+        },
+        (Method::Post, ["api", "inventory", tag, "adjust"]) => match serde_json::from_str::<Adjust>(body) {
+            Ok(adjust) => db().map(|db| match db.adjust_quantity(tag, adjust.delta) {
+                Ok(Some(item)) => changed(state, InventoryChange::Saved(item)),
+                Ok(None) => not_found(tag),
+                Err(e) => db_error(e),
+            }),
+            Err(e) => Ok(Reply::Json(400, json!({ "error": format!("Invalid adjustment: {}", e) }))),
+        },
+        (Method::Get, ["api", "progress"]) => Ok(Reply::Json(200, progress())),
+        (Method::Get, ["api", "card"]) => Ok(card_reply(state, |spi| card::read_card(spi).map(|card| json!(card)))),
+        (Method::Get, ["api", "card", "block", block]) => match (block.parse::<u8>(), card_key(query)) {
+            (Ok(block), Ok((key_type, key))) => Ok(card_reply(state, |spi| {
+                card::read_block(spi, block, key_type, key).map(|block| json!(block))
+            })),
+            (Err(_), _) => Ok(Reply::Json(400, json!({ "error": format!("Invalid block number {}", block) }))),
+            (_, Err(e)) => Ok(Reply::Json(400, json!({ "error": e }))),
+        },
+        (Method::Get, ["api", "card", "dump"]) => match card_key(query) {
+            Ok((key_type, key)) => Ok(card_reply(state, |spi| card::dump(spi, key_type, key).map(|dump| json!(dump)))),
+            Err(e) => Ok(Reply::Json(400, json!({ "error": e }))),
+        },
+        (Method::Delete, ["api", "inventory", tag]) => db().map(|db| match db.delete_item(tag) {
+            Ok(Some(tag_id)) => changed(state, InventoryChange::Deleted { tag_id }),
+            Ok(None) => not_found(tag),
+            Err(e) => db_error(e),
+        }),
+        _ => Ok(Reply::Json(404, json!({ "error": format!("No route for {} {}", method, path) }))),
+    };
+
+    result.unwrap_or_else(|reply| reply)
+}
+
+/// The operations with a status file, eta_seconds is null until there is an estimate
+fn progress() -> Value {
+    let operations: Vec<Value> = read_statuses(STATUS_DIR)
+        .into_iter()
+        .map(|status| json!({
+            "pid": status.pid,
+            "operation": status.operation,
+            "percent": status.progress.percent,
+            "message": status.progress.message,
+            "eta_seconds": status.progress.eta.map(|eta| eta.as_secs()),
+        }))
+        .collect();
+    json!(operations)
+}
+
+/// Key and key type from the query string, the transport key as Key A by default
+fn card_key(query: &str) -> Result<(u8, Key), String> {
+    let key = match query_param(query, "key") {
+        Some(hex) => Key::from_hex(&hex).ok_or_else(|| format!("Invalid key {}, expected 12 hex digits", hex))?,
+        None => Key::TRANSPORT,
+    };
+    let key_type = match query_param(query, "type").as_deref() {
+        None | Some("A") | Some("a") => PICC_AUTHENT1A,
+        Some("B") | Some("b") => PICC_AUTHENT1B,
+        Some(other) => return Err(format!("Invalid key type {}, expected A or B", other)),
+    };
+    Ok((key_type, key))
+}
+
+/// Run a card operation on the station's reader, one request at a time
+fn card_reply(state: &AppState, operation: impl FnOnce(&mut dyn Transport) -> Result<Value, CardError>) -> Reply {
+    let reader = match &state.reader {
+        Some(reader) => reader,
+        None => return Reply::Json(503, json!({ "error": "This station has no reader, start it with --reader" })),
+    };
+    let mut spi = match reader.lock() {
+        Ok(spi) => spi,
+        Err(_) => return Reply::Json(500, json!({ "error": "Reader unavailable" })),
+    };
+    match operation(spi.as_mut()) {
+        Ok(value) => Reply::Json(200, value),
+        Err(e) => {
+            let status = match e {
+                CardError::NoCard => 404,
+                CardError::NoSuchBlock(_) => 400,
+                CardError::KeyRefused(_) => 403,
+                CardError::Reader(_) => 500,
+            };
+            Reply::Json(status, json!({ "error": e.to_string() }))
+        }
+    }
+}
+
+/// Status and error for a request that may not go on: one without the token where it's
+/// needed, or a change that isn't JSON or comes from another site's page
+fn refusal(request: &Request, path: &str, token: Option<&str>) -> Option<(u16, &'static str)> {
+    let changes = *request.method() != Method::Get;
+    let card_route = path_segments(path).starts_with(&["api".to_string(), "card".to_string()]);
+    if (changes || card_route) && !authorized(header(request, "Authorization").as_deref(), token) {
+        return Some((401, "This station needs its access token"));
+    }
+    if !changes {
+        return None;
+    }
+
+    // browsers send the Origin of a page along with its changes, ours is this server
+    let host = header(request, "Host").unwrap_or_default();
+    let foreign = header(request, "Origin").is_some_and(|origin| {
+        origin.split_once("://").map(|(_, authority)| authority) != Some(host.as_str())
+    });
+    if foreign {
+        return Some((403, "Changes are only taken from this station's own page"));
+    }
+    let json = header(request, "Content-Type").is_some_and(|value| {
+        value.split(';').next().is_some_and(|media| media.trim().eq_ignore_ascii_case("application/json"))
+    });
+    if *request.method() == Method::Post && !json {
+        return Some((415, "Expected a JSON body (Content-Type: application/json)"));
+    }
+    None
+}
+
+/// Whether an Authorization header carries the token, any request is let through
+/// without one
+pub(crate) fn authorized(authorization: Option<&str>, token: Option<&str>) -> bool {
+    let token = match token {
+        Some(token) => token,
+        None => return true,
+    };
+    let given = authorization.and_then(|value| value.strip_prefix("Bearer ")).unwrap_or_default();
+    // compared in full, so the time taken doesn't tell how much of it was right
+    given.len() == token.len()
+        && given.bytes().zip(token.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// The token on the first line of a file, which should be readable by the service only
+pub fn read_token(path: &str) -> Result<String, String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match contents.lines().next().map(str::trim) {
+        Some(token) if !token.is_empty() => Ok(token.to_string()),
+        _ => Err("the file is empty".to_string()),
+    }
+}
+
+/// Whether only this machine can reach a server listening on `bind` ("ADDR:PORT")
+pub fn loopback_only(bind: &str) -> bool {
+    match bind.parse::<std::net::SocketAddr>() {
+        Ok(address) => address.ip().is_loopback(),
+        Err(_) => bind.rsplit_once(':').is_some_and(|(host, _)| host == "localhost"),
+    }
+}
+
+/// Value of a request header, the name in any case
+pub(crate) fn header(request: &Request, name: &str) -> Option<String> {
+    request.headers().iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str().to_string())
+}
+
+/// Tell live feed subscribers (e.g. the hub uplink) about an inventory change and reply with it
+fn changed(state: &AppState, change: InventoryChange) -> Reply {
+    let reply = match &change {
+        InventoryChange::Saved(item) => json!(item),
+        InventoryChange::Deleted { tag_id } => json!({ "deleted": tag_id }),
+    };
+    state.hub.publish_change(change);
+    Reply::Json(200, reply)
+}
+
+fn db_reply(result: rusqlite::Result<Value>) -> Reply {
+    match result {
+        Ok(value) => Reply::Json(200, value),
+        Err(e) => db_error(e),
+    }
+}
+
+fn db_error(e: rusqlite::Error) -> Reply {
+    Reply::Json(500, json!({ "error": format!("Database error: {}", e) }))
+}
+
+fn not_found(tag: &str) -> Reply {
+    Reply::Json(404, json!({ "error": format!("No item with tag {}", tag) }))
+}
+
+pub(crate) fn content_type(value: &str) -> Header {
+    Header::from_bytes(&b"Content-Type"[..], value.as_bytes()).expect("valid header")
+}
+
+pub(crate) fn json_response(status: u16, value: Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(content_type("application/json"))
+}
+
+pub(crate) fn scan_filter(query: &str) -> ScanFilter {
+    ScanFilter {
+        unknown_only: query_param(query, "unknown").is_some_and(|value| value == "true" || value == "1"),
+        reader: query_param(query, "reader").filter(|reader| !reader.is_empty()),
+        station: query_param(query, "station").filter(|station| !station.is_empty()),
+    }
+}
+
+/// Send scans as server-sent events until the client disconnects.
+/// tiny_http buffers chunked bodies, so the event stream is written to the connection directly.
+pub(crate) fn stream_events(request: Request, hub: &ScanHub, filter: &ScanFilter) {
+    let scans = hub.subscribe();
+    let mut writer = request.into_writer();
+
+    let header = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+    if writer.write_all(header.as_bytes()).and_then(|_| writer.flush()).is_err() {
+        return;
+    }
+
+    loop {
+        let event = match scans.recv_timeout(KEEPALIVE_INTERVAL) {
+            Ok(FeedEvent::Scan(scan)) if filter.matches(&scan) => format!("event: scan\ndata: {}\n\n", json!(scan)),
+            Ok(_) => continue,
+            Err(RecvTimeoutError::Timeout) => ": keepalive\n\n".to_string(),
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if writer.write_all(event.as_bytes()).and_then(|_| writer.flush()).is_err() {
+            break;
+        }
+    }
+}
+
+/// Complete the WebSocket handshake, answering 400 if the request isn't an upgrade
+pub(crate) fn accept_websocket(request: Request) -> Option<WebSocket<Box<dyn ReadWrite + Send>>> {
+    let key = match header(&request, "Sec-WebSocket-Key") {
+        Some(key) if header(&request, "Upgrade").is_some_and(|u| u.eq_ignore_ascii_case("websocket")) => key,
+        _ => {
+            let _ = request.respond(json_response(400, json!({ "error": "Expected a WebSocket upgrade request" })));
+            return None;
+        }
+    };
+
+    let response = Response::empty(StatusCode(101)).with_header(
+        Header::from_bytes(&b"Sec-WebSocket-Accept"[..], derive_accept_key(key.as_bytes()).as_bytes()).expect("valid header"),
+    );
+    let stream = request.upgrade("websocket", response);
+    Some(WebSocket::from_raw_socket(stream, Role::Server, None))
+}
+
+/// Upgrade the request and send each matching scan as a JSON text message until the client goes away.
+/// The feed is one way, clients choose what they get with the filters in the URL.
+pub(crate) fn stream_websocket(request: Request, hub: &ScanHub, filter: &ScanFilter) {
+    let scans = hub.subscribe();
+    let mut socket = match accept_websocket(request) {
+        Some(socket) => socket,
+        None => return,
+    };
+
+    loop {
+        let message = match scans.recv_timeout(KEEPALIVE_INTERVAL) {
+            Ok(FeedEvent::Scan(scan)) if filter.matches(&scan) => Message::text(json!(scan).to_string()),
+            Ok(_) => continue,
+            Err(RecvTimeoutError::Timeout) => Message::Ping(Vec::new().into()),
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+
+        if socket.send(message).is_err() {
+            break;
+        }
+    }
+}
+
+/// Value of `name` in a query string like "q=resistor&x=1"
+pub(crate) fn query_param(query: &str, name: &str) -> Option<String> {
+    query.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(&value.replace('+', " ")))
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> AppState {
+        AppState {
+            db: Arc::new(Mutex::new(InventoryDB::new(":memory:").unwrap())),
+            hub: Arc::new(ScanHub::default()),
+            token: None,
+            reader: None,
+        }
+    }
+
+    fn status(reply: Reply) -> u16 {
+        match reply {
+            Reply::Json(status, _) => status,
+            _ => 200,
+        }
+    }
+
+    #[test]
+    fn decodes_query_parameters() {
+        assert_eq!(query_param("q=red+box%2F2&x=1", "q").as_deref(), Some("red box/2"));
+        assert_eq!(query_param("x=1", "q"), None);
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn parses_scan_filters() {
+        assert_eq!(scan_filter(""), ScanFilter::default());
+        assert_eq!(scan_filter("unknown=true&reader=front%20door"), ScanFilter {
+            unknown_only: true,
+            reader: Some("front door".to_string()),
+            station: None,
+        });
+        assert!(!scan_filter("unknown=no&reader=").unknown_only);
+    }
+
+    #[test]
+    fn inventory_routes() {
+        let state = state();
+        let item = r#"{"tag_id": "04A1B2C3", "name": "Resistor kit", "quantity": 2}"#;
+
+        assert_eq!(status(route(&Method::Post, "/api/inventory", "", item, &state)), 200);
+        assert_eq!(status(route(&Method::Post, "/api/inventory", "", r#"{"tag_id": "", "name": "x"}"#, &state)), 400);
+
+        let changes = state.hub.subscribe();
+        match route(&Method::Post, "/api/inventory/04A1B2C3/adjust", "", r#"{"delta": -1}"#, &state) {
+            Reply::Json(200, value) => assert_eq!(value["quantity"], 1),
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert!(matches!(changes.try_recv(), Ok(FeedEvent::Inventory(InventoryChange::Saved(item))) if item.quantity == 1));
+
+        assert_eq!(status(route(&Method::Get, "/api/inventory/DEADBEEF", "", "", &state)), 404);
+        assert_eq!(status(route(&Method::Delete, "/api/inventory/04A1B2C3", "", "", &state)), 200);
+        assert_eq!(status(route(&Method::Get, "/api/nothing", "", "", &state)), 404);
+    }
+
+    #[test]
+    fn card_routes() {
+        use mifare_rc522::mock::{MockCard, MockReader};
+
+        let mut state = state();
+        assert_eq!(status(route(&Method::Get, "/api/card", "", "", &state)), 503);
+
+        let mut spi = MockReader::with_card(MockCard::new([0x04, 0xA1, 0xB2, 0xC3]));
+        mifare_rc522::mfrc522_init(&mut spi).unwrap();
+        state.reader = Some(Mutex::new(Box::new(spi)));
+        match route(&Method::Get, "/api/card", "", "", &state) {
+            Reply::Json(200, value) => assert_eq!(value["uid"], "04A1B2C3"),
+            other => panic!("unexpected reply {:?}", other),
+        }
+        assert_eq!(status(route(&Method::Get, "/api/card/block/4", "key=FFFFFFFFFFFF&type=B", "", &state)), 200);
+        assert_eq!(status(route(&Method::Get, "/api/card/block/4", "key=A0A1A2A3A4A5", "", &state)), 403);
+        assert_eq!(status(route(&Method::Get, "/api/card/block/4", "type=C", "", &state)), 400);
+        assert_eq!(status(route(&Method::Get, "/api/card/block/x", "", "", &state)), 400);
+        match route(&Method::Get, "/api/card/dump", "", "", &state) {
+            Reply::Json(200, value) => assert_eq!(value["sectors"].as_array().unwrap().len(), 16),
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+
+    #[test]
+    fn token_checks() {
+        assert!(authorized(None, None));
+        assert!(authorized(Some("Bearer s3cret"), Some("s3cret")));
+        assert!(!authorized(None, Some("s3cret")));
+        assert!(!authorized(Some("Bearer s3cre"), Some("s3cret")));
+        assert!(!authorized(Some("s3cret"), Some("s3cret")));
+
+        assert!(loopback_only("127.0.0.1:8080"));
+        assert!(loopback_only("[::1]:8080"));
+        assert!(loopback_only("localhost:8080"));
+        assert!(!loopback_only("0.0.0.0:8080"));
+        assert!(!loopback_only("192.168.1.20:8080"));
+        assert!(!loopback_only("pi.local:8080"));
+    }
+
+    fn request(method: Method, path: &str, headers: &[(&str, &str)], body: &'static str) -> Request {
+        let mut request = tiny_http::TestRequest::new().with_method(method).with_path(path).with_body(body);
+        for (name, value) in headers {
+            request = request.with_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap());
+        }
+        request.into()
+    }
+
+    #[test]
+    fn card_routes_need_the_token() {
+        let token = Some("s3cret");
+        let refused = |path: &str, headers: &[(&str, &str)]| {
+            refusal(&request(Method::Get, path, headers, ""), path, token).map(|(status, _)| status)
+        };
+
+        assert_eq!(refused("/api/card", &[]), Some(401));
+        assert_eq!(refused("/api/card/block/4", &[]), Some(401));
+        assert_eq!(refused("/api/card/dump", &[("Authorization", "Bearer wrong")]), Some(401));
+        assert_eq!(refused("/api/card/dump", &[("Authorization", "Bearer s3cret")]), None);
+        assert_eq!(refused("/api/inventory", &[]), None);
+        assert_eq!(refusal(&request(Method::Get, "/api/card", &[], ""), "/api/card", None), None);
+    }
+
+    #[test]
+    fn changes_must_be_json_from_our_page() {
+        let item = r#"{"tag_id": "04A1B2C3", "name": "Resistor kit"}"#;
+        let refused = |method: Method, headers: &[(&str, &str)]| {
+            let mut headers = headers.to_vec();
+            headers.push(("Host", "127.0.0.1:8080"));
+            refusal(&request(method, "/api/inventory", &headers, item), "/api/inventory", None).map(|(status, _)| status)
+        };
+
+        assert_eq!(refused(Method::Post, &[("Content-Type", "application/json")]), None);
+        assert_eq!(refused(Method::Post, &[("Content-Type", "application/json; charset=utf-8"), ("Origin", "http://127.0.0.1:8080")]), None);
+        assert_eq!(refused(Method::Post, &[("Content-Type", "text/plain")]), Some(415));
+        assert_eq!(refused(Method::Post, &[]), Some(415));
+        assert_eq!(refused(Method::Post, &[("Content-Type", "application/json"), ("Origin", "https://evil.example")]), Some(403));
+        assert_eq!(refused(Method::Delete, &[("Origin", "http://127.0.0.1:8081")]), Some(403));
+        assert_eq!(refused(Method::Delete, &[]), None);
+    }
+
+    #[test]
+    fn large_bodies_are_refused() {
+        let large: &'static str = Box::leak("x".repeat(MAX_BODY + 1).into_boxed_str());
+        let mut large = request(Method::Post, "/api/inventory", &[], large);
+        assert!(matches!(read_request(&mut large), Err((413, _))));
+
+        let mut item = request(Method::Post, "/api/inventory?x=1", &[], "{}");
+        assert_eq!(read_request(&mut item), Ok(("/api/inventory".to_string(), "x=1".to_string(), "{}".to_string())));
+    }
+
+    #[test]
+    fn workers_are_bounded() {
+        let workers = Workers::new(1);
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+        assert!(workers.spawn(request(Method::Get, "/", &[], ""), move |_| {
+            let _ = wait.recv();
+        }));
+        assert!(!workers.spawn(request(Method::Get, "/", &[], ""), |_| {}));
+        drop(release);
+    }
+
+    #[test]
+    fn progress_route() {
+        use progress_sink::{Progress, ProgressSink, StatusFile};
+
+        let state = state();
+        let import = StatusFile::new("import");
+        import.report(&Progress::steps(1, 4, "Row 1 of 4"));
+        let ours = |value: Value| value.as_array().unwrap().iter()
+            .find(|operation| operation["pid"] == std::process::id())
+            .cloned();
+        match route(&Method::Get, "/api/progress", "", "", &state) {
+            Reply::Json(200, value) => {
+                let operation = ours(value).expect("the status file is listed");
+                assert_eq!(operation["operation"], "import");
+                assert_eq!(operation["percent"], 25.0);
+                assert_eq!(operation["eta_seconds"], Value::Null);
+            },
+            other => panic!("unexpected reply {:?}", other),
+        }
+
+        drop(import);
+        match route(&Method::Get, "/api/progress", "", "", &state) {
+            Reply::Json(200, value) => assert!(ours(value).is_none()),
+            other => panic!("unexpected reply {:?}", other),
+        }
+    }
+}
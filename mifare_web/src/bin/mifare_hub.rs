@@ -0,0 +1,58 @@
+// mifare_hub.rs
+//
+// Fleet hub: stations started with `mifare_web --hub HOST:PORT` connect here, and the
+// dashboard and API show the scans and inventory of all of them in one place.
+// It listens on loopback unless --bind says otherwise, and then only with a token
+// (--token-file) that station uplinks must carry.
+use std::sync::Arc;
+
+use mifare_web::api::{self, Workers};
+use mifare_web::hub::{self, HubState};
+
+const USAGE: &str = "Usage: mifare_hub [--bind ADDR:PORT] [--token-file PATH]";
+
+fn main() {
+    let mut bind = "127.0.0.1:9090".to_string();
+    let mut token_file = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--bind", Some(value)) => bind = value,
+            ("--token-file", Some(value)) => token_file = Some(value),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let token = match token_file.as_deref().map(api::read_token).transpose() {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("Error reading the token from {}: {}", token_file.unwrap_or_default(), e);
+            std::process::exit(1);
+        }
+    };
+    if token.is_none() && !api::loopback_only(&bind) {
+        eprintln!("Anyone who can reach {} could pose as a station, give a token with --token-file PATH", bind);
+        std::process::exit(2);
+    }
+
+    let server = match tiny_http::Server::http(&bind) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Error listening on {}: {}", bind, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Hub serving on http://{}, stations connect to ws://{}/station", bind, bind);
+
+    let state = Arc::new(HubState::new(token));
+    let workers = Workers::new(api::MAX_WORKERS);
+    for request in server.incoming_requests() {
+        // station uplinks and live feeds hold their thread for as long as they are connected
+        let state = state.clone();
+        workers.spawn(request, move |request| hub::handle(request, &state));
+    }
+}
@@ -0,0 +1,201 @@
+// card.rs
+//
+// Card operations on the reader wired to this station: the card in the field, one of
+// its blocks, or the whole card with one key. Scans still arrive through the FIFO;
+// these drive the MFRC522 itself, so the server only opens it when started with
+// --reader and no capture tool is using it.
+use std::error::Error;
+use std::fmt;
+use std::sync::Mutex;
+
+use mifare_rc522::*;
+use serde::Serialize;
+
+/// The reader the card endpoints drive, one request at a time
+pub type Reader = Mutex<Box<dyn Transport + Send>>;
+
+#[derive(Debug)]
+pub enum CardError {
+    /// No card answered the wake-up
+    NoCard,
+    /// The block isn't on the card in the field
+    NoSuchBlock(u8),
+    /// The card refused the key for the sector of this block
+    KeyRefused(BlockAddr),
+    /// Talking to the reader failed
+    Reader(String),
+}
+
+impl fmt::Display for CardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardError::NoCard => write!(f, "No card on the reader"),
+            CardError::NoSuchBlock(block) => write!(f, "The card has no block {}", block),
+            CardError::KeyRefused(block) => write!(f, "The key was refused for sector {}", block.sector()),
+            CardError::Reader(e) => write!(f, "Reader error: {}", e),
+        }
+    }
+}
+
+impl Error for CardError {}
+
+impl From<Box<dyn Error>> for CardError {
+    fn from(e: Box<dyn Error>) -> Self {
+        CardError::Reader(e.to_string())
+    }
+}
+
+/// The card in the field, its UID in the hex form scans and inventory tags use
+#[derive(Debug, Serialize)]
+pub struct CardInfo {
+    pub uid: String,
+    pub sak: u8,
+    pub layout: &'static str,
+    pub blocks: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockData {
+    pub block: u8,
+    pub sector: u8,
+    pub data: String,
+}
+
+/// Every sector of the card, null for the sectors the key doesn't open
+#[derive(Debug, Serialize)]
+pub struct CardDump {
+    pub card: CardInfo,
+    pub sectors: Vec<Option<Vec<String>>>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+fn info(card: &DetectedCard) -> CardInfo {
+    let layout = CardLayout::from_sak_or_1k(card.sak);
+    CardInfo {
+        uid: hex(&card.uid),
+        sak: card.sak,
+        layout: layout.name(),
+        blocks: layout.block_count(),
+    }
+}
+
+// A wake-up rather than a request, so a card an earlier operation halted answers too
+fn select(spi: &mut dyn Transport) -> Result<DetectedCard, CardError> {
+    let (status, _) = mfrc522_request(spi, PICC_REQALL)?;
+    if status != MI_OK {
+        return Err(CardError::NoCard);
+    }
+    mfrc522_select_card(spi)?.ok_or(CardError::NoCard)
+}
+
+// Leave the card halted and the reader without a crypto session
+fn finish(spi: &mut dyn Transport) -> Result<(), CardError> {
+    mfrc522_stop_crypto1(spi)?;
+    mfrc522_halt(spi)?;
+    Ok(())
+}
+
+// The blocks of a sector, None if the key doesn't open it. A refused key leaves the
+// card idle, so it is selected again for the next sector.
+fn read_sector(spi: &mut dyn Transport, card: &DetectedCard, sector: SectorIndex, key_type: u8, key: Key)
+    -> Result<Option<Vec<Vec<u8>>>, CardError> {
+    if mfrc522_auth(spi, key_type, sector.first_block().get(), key.as_bytes(), &card.uid)? != MI_OK {
+        select(spi)?;
+        return Ok(None);
+    }
+    let mut blocks = Vec::new();
+    for block in sector.blocks() {
+        match mfrc522_read(spi, block.get())? {
+            Some(data) => blocks.push(data),
+            None => {
+                select(spi)?;
+                return Ok(None);
+            }
+        }
+    }
+    Ok(Some(blocks))
+}
+
+/// UID, SAK and layout of the card in the field
+pub fn read_card(spi: &mut dyn Transport) -> Result<CardInfo, CardError> {
+    let card = select(spi)?;
+    finish(spi)?;
+    Ok(info(&card))
+}
+
+/// One block, read with `key` as Key A (PICC_AUTHENT1A) or Key B (PICC_AUTHENT1B)
+pub fn read_block(spi: &mut dyn Transport, block: u8, key_type: u8, key: Key) -> Result<BlockData, CardError> {
+    let card = select(spi)?;
+    let block = BlockAddr(block);
+    if !CardLayout::from_sak_or_1k(card.sak).has_block(block) {
+        finish(spi)?;
+        return Err(CardError::NoSuchBlock(block.get()));
+    }
+
+    let authenticated = mfrc522_auth(spi, key_type, block.get(), key.as_bytes(), &card.uid)? == MI_OK;
+    let data = if authenticated { mfrc522_read(spi, block.get())? } else { None };
+    finish(spi)?;
+    match data {
+        Some(data) => Ok(BlockData { block: block.get(), sector: block.sector().get(), data: hex(&data) }),
+        None => Err(CardError::KeyRefused(block)),
+    }
+}
+
+/// Every sector of the card's layout, each opened with `key`
+pub fn dump(spi: &mut dyn Transport, key_type: u8, key: Key) -> Result<CardDump, CardError> {
+    let card = select(spi)?;
+    let mut sectors = Vec::new();
+    for sector in CardLayout::from_sak_or_1k(card.sak).sectors() {
+        let blocks = read_sector(spi, &card, sector, key_type, key)?;
+        sectors.push(blocks.map(|blocks| blocks.iter().map(|data| hex(data)).collect()));
+    }
+    finish(spi)?;
+    Ok(CardDump { card: info(&card), sectors })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mifare_rc522::mock::{MockCard, MockReader};
+
+    const OTHER_KEY: [u8; 6] = [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5];
+
+    fn reader(card: MockCard) -> MockReader {
+        let mut spi = MockReader::with_card(card);
+        mfrc522_init(&mut spi).unwrap();
+        spi
+    }
+
+    #[test]
+    fn reads_the_card_in_the_field() {
+        let mut spi = reader(MockCard::new([0x04, 0xA1, 0xB2, 0xC3]));
+        let card = read_card(&mut spi).unwrap();
+        assert_eq!(card.uid, "04A1B2C3");
+        assert_eq!(card.layout, CardLayout::Classic1K.name());
+        assert_eq!(card.blocks, 64);
+
+        // the card was halted, the next operation wakes it again
+        let block = read_block(&mut spi, 4, PICC_AUTHENT1A, Key::TRANSPORT).unwrap();
+        assert_eq!((block.block, block.sector), (4, 1));
+        assert_eq!(block.data, "0".repeat(32));
+
+        assert!(matches!(read_block(&mut spi, 64, PICC_AUTHENT1A, Key::TRANSPORT), Err(CardError::NoSuchBlock(64))));
+        assert!(matches!(read_card(&mut MockReader::new()), Err(CardError::NoCard)));
+    }
+
+    #[test]
+    fn dump_skips_the_sectors_the_key_does_not_open() {
+        let mut spi = reader(MockCard::new([0x04, 0xA1, 0xB2, 0xC3]).with_keys(2, OTHER_KEY, OTHER_KEY));
+        assert!(matches!(read_block(&mut spi, 9, PICC_AUTHENT1B, Key::TRANSPORT), Err(CardError::KeyRefused(BlockAddr(9)))));
+        assert_eq!(read_block(&mut spi, 9, PICC_AUTHENT1B, Key(OTHER_KEY)).unwrap().sector, 2);
+
+        let dump = dump(&mut spi, PICC_AUTHENT1A, Key::TRANSPORT).unwrap();
+        assert_eq!(dump.sectors.len(), 16);
+        assert!(dump.sectors[2].is_none());
+        assert!(dump.sectors.iter().enumerate().all(|(sector, blocks)| sector == 2 || blocks.as_ref().is_some_and(|b| b.len() == 4)));
+        assert!(dump.sectors[0].as_ref().unwrap()[0].starts_with("04A1B2C3"));
+    }
+}
@@ -0,0 +1,397 @@
+// hub.rs
+//
+// Fleet mode: stations (mifare_web --hub) keep a WebSocket open to the hub and send their
+// scans and inventory edits over it. The hub merges them into one live feed and one
+// inventory view, tagged with the station each came from.
+//
+//   GET    /                          dashboard
+//   GET    /station                   station uplink (WebSocket, StationMessage JSON)
+//   GET    /api/stations              stations seen since the hub started
+//   GET    /api/scans                 recent scans from all stations, newest first
+//   GET    /api/events                live scan feed (Server-Sent Events)
+//   GET    /api/ws                    live scan feed (WebSocket, JSON text messages)
+//   GET    /api/inventory?q=term      merged inventory, optionally filtered
+//
+// The scan endpoints take the same filters as on a station, plus station=<name>.
+// /api/inventory takes station=<name> as well.
+//
+// With a token (mifare_hub --token-file) a station's upgrade to /station needs
+// "Authorization: Bearer <token>", sent by mifare_web --hub-token-file, so only the
+// stations holding it can replace an inventory. The hub only listens beyond loopback
+// with one (see bin/mifare_hub.rs).
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use inventory_db::{InventoryChange, InventoryItem};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tiny_http::{Method, Request, Response};
+use tungstenite::Message;
+
+use crate::api::{
+    accept_websocket, authorized, content_type, header, json_response, path_segments, query_param, read_request,
+    scan_filter, stream_events, stream_websocket,
+};
+use crate::scans::{Scan, ScanHub};
+
+const HUB_HTML: &str = include_str!("../static/hub.html");
+
+/// What a station sends over its uplink, one JSON text message each
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StationMessage {
+    /// First message on every connection
+    Hello { station: String },
+    /// Whole inventory, sent after Hello and replacing what the hub had for the station
+    InventorySnapshot { items: Vec<InventoryItem> },
+    Scan { scan: Scan },
+    Inventory { change: InventoryChange },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StationInfo {
+    pub name: String,
+    pub connected: bool,
+    pub address: String,
+    pub last_seen: String,
+    pub scans: u64,
+    pub items: usize,
+}
+
+/// Inventory item with the station it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct StationItem {
+    pub station: String,
+    #[serde(flatten)]
+    pub item: InventoryItem,
+}
+
+#[derive(Default)]
+struct Station {
+    /// Id of the open connection, a reconnect can arrive before the old one is noticed as gone
+    connection: Option<u64>,
+    address: String,
+    last_seen: String,
+    scans: u64,
+    items: HashMap<String, InventoryItem>,
+}
+
+#[derive(Default)]
+pub struct HubState {
+    stations: Mutex<HashMap<String, Station>>,
+    next_connection: Mutex<u64>,
+    pub feed: ScanHub,
+    /// What station uplinks must carry, None when only this machine can reach the hub
+    token: Option<String>,
+}
+
+fn now() -> String {
+    chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+impl HubState {
+    /// A hub whose station uplinks must carry `token`
+    pub fn new(token: Option<String>) -> Self {
+        HubState { token, ..HubState::default() }
+    }
+
+    /// Mark a station connected, the returned id is passed back to `disconnect`
+    pub fn connect(&self, station: &str, address: &str) -> u64 {
+        let id = match self.next_connection.lock() {
+            Ok(mut next) => {
+                *next += 1;
+                *next
+            },
+            Err(_) => 0,
+        };
+        if let Ok(mut stations) = self.stations.lock() {
+            let entry = stations.entry(station.to_string()).or_default();
+            entry.connection = Some(id);
+            entry.address = address.to_string();
+            entry.last_seen = now();
+        }
+        id
+    }
+
+    pub fn disconnect(&self, station: &str, connection: u64) {
+        if let Ok(mut stations) = self.stations.lock() {
+            if let Some(entry) = stations.get_mut(station).filter(|entry| entry.connection == Some(connection)) {
+                entry.connection = None;
+            }
+        }
+    }
+
+    /// Record a message from a connected station
+    pub fn apply(&self, station: &str, message: StationMessage) {
+        let mut scan_to_publish = None;
+        if let Ok(mut stations) = self.stations.lock() {
+            let entry = stations.entry(station.to_string()).or_default();
+            entry.last_seen = now();
+            match message {
+                StationMessage::Hello { .. } => {},
+                StationMessage::InventorySnapshot { items } => {
+                    entry.items = items.into_iter().map(|item| (item.tag_id.clone(), item)).collect();
+                },
+                StationMessage::Scan { mut scan } => {
+                    entry.scans += 1;
+                    scan.station = Some(station.to_string());
+                    scan_to_publish = Some(scan);
+                },
+                StationMessage::Inventory { change: InventoryChange::Saved(item) } => {
+                    entry.items.insert(item.tag_id.clone(), item);
+                },
+                StationMessage::Inventory { change: InventoryChange::Deleted { tag_id } } => {
+                    entry.items.remove(&tag_id);
+                },
+            }
+        }
+
+        // publish outside the lock, subscribers may be slow
+        if let Some(scan) = scan_to_publish {
+            self.feed.publish(scan);
+        }
+    }
+
+    /// Note that a station is still there (keepalive pings)
+    pub fn touch(&self, station: &str) {
+        if let Ok(mut stations) = self.stations.lock() {
+            if let Some(entry) = stations.get_mut(station) {
+                entry.last_seen = now();
+            }
+        }
+    }
+
+    pub fn stations(&self) -> Vec<StationInfo> {
+        let stations = match self.stations.lock() {
+            Ok(stations) => stations,
+            Err(_) => return Vec::new(),
+        };
+        let mut list: Vec<StationInfo> = stations.iter()
+            .map(|(name, entry)| StationInfo {
+                name: name.clone(),
+                connected: entry.connection.is_some(),
+                address: entry.address.clone(),
+                last_seen: entry.last_seen.clone(),
+                scans: entry.scans,
+                items: entry.items.len(),
+            })
+            .collect();
+        list.sort_by(|a, b| a.name.cmp(&b.name));
+        list
+    }
+
+    /// Items from every station (or one) matching `query` the way a station's search does
+    pub fn inventory(&self, query: &str, station: Option<&str>) -> Vec<StationItem> {
+        let stations = match self.stations.lock() {
+            Ok(stations) => stations,
+            Err(_) => return Vec::new(),
+        };
+        let term = query.to_lowercase();
+        let matches = |field: Option<&String>| field.is_some_and(|value| value.to_lowercase().contains(&term));
+
+        let mut items: Vec<StationItem> = stations.iter()
+            .filter(|(name, _)| station.is_none_or(|station| station == name.as_str()))
+            .flat_map(|(name, entry)| entry.items.values().map(move |item| (name, item)))
+            .filter(|(_, item)| {
+                matches(Some(&item.name)) || matches(Some(&item.tag_id))
+                    || matches(item.location.as_ref()) || matches(item.category.as_ref())
+            })
+            .map(|(name, item)| StationItem { station: name.clone(), item: item.clone() })
+            .collect();
+        items.sort_by(|a, b| a.item.name.cmp(&b.item.name).then_with(|| a.station.cmp(&b.station)));
+        items
+    }
+}
+
+pub fn handle(mut request: Request, state: &HubState) {
+    let (path, query, _) = match read_request(&mut request) {
+        Ok(parts) => parts,
+        Err((status, e)) => {
+            let _ = request.respond(json_response(status, json!({ "error": e })));
+            return;
+        }
+    };
+    let segments = path_segments(&path);
+    let segments: Vec<&str> = segments.iter().map(|s| s.as_str()).collect();
+
+    let result = match (request.method(), segments.as_slice()) {
+        (Method::Get, []) => request.respond(
+            Response::from_string(HUB_HTML).with_header(content_type("text/html; charset=utf-8")),
+        ),
+        (Method::Get, ["station"]) => {
+            serve_station(request, state);
+            Ok(())
+        },
+        (Method::Get, ["api", "stations"]) => request.respond(json_response(200, json!(state.stations()))),
+        (Method::Get, ["api", "scans"]) => {
+            let filter = scan_filter(&query);
+            let scans: Vec<_> = state.feed.recent().into_iter().filter(|scan| filter.matches(scan)).collect();
+            request.respond(json_response(200, json!(scans)))
+        },
+        (Method::Get, ["api", "events"]) => {
+            stream_events(request, &state.feed, &scan_filter(&query));
+            Ok(())
+        },
+        (Method::Get, ["api", "ws"]) => {
+            stream_websocket(request, &state.feed, &scan_filter(&query));
+            Ok(())
+        },
+        (Method::Get, ["api", "inventory"]) => {
+            let term = query_param(&query, "q").unwrap_or_default();
+            let station = query_param(&query, "station").filter(|station| !station.is_empty());
+            request.respond(json_response(200, json!(state.inventory(&term, station.as_deref()))))
+        },
+        _ => request.respond(json_response(404, json!({ "error": format!("No route for {}", path) }))),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error sending response: {}", e);
+    }
+}
+
+/// Read a station's uplink until it disconnects
+fn serve_station(request: Request, state: &HubState) {
+    let address = request.remote_addr().map(|address| address.to_string()).unwrap_or_default();
+    if !authorized(header(&request, "Authorization").as_deref(), state.token.as_deref()) {
+        eprintln!("Refusing uplink from {}: no valid token", address);
+        let _ = request.respond(json_response(401, json!({ "error": "This hub needs its station token" })));
+        return;
+    }
+    let mut socket = match accept_websocket(request) {
+        Some(socket) => socket,
+        None => return,
+    };
+
+    let mut session: Option<(String, u64)> = None;
+    loop {
+        // tungstenite answers pings itself while reading
+        let text = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => {
+                if let Some((station, _)) = &session {
+                    state.touch(station);
+                }
+                continue;
+            },
+        };
+
+        match (serde_json::from_str::<StationMessage>(&text), &session) {
+            (Ok(StationMessage::Hello { station }), _) => {
+                println!("Station {} connected from {}", station, address);
+                let connection = state.connect(&station, &address);
+                session = Some((station, connection));
+            },
+            (Ok(message), Some((station, _))) => state.apply(station, message),
+            (Ok(_), None) => {
+                eprintln!("Closing uplink from {}: no hello", address);
+                break;
+            },
+            (Err(e), _) => eprintln!("Ignoring message from {}: {}", address, e),
+        }
+    }
+
+    if let Some((station, connection)) = session {
+        println!("Station {} disconnected", station);
+        state.disconnect(&station, connection);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uid_codec::KeyboardLayout;
+
+    fn item(tag_id: &str, name: &str) -> InventoryItem {
+        InventoryItem {
+            tag_id: tag_id.to_string(),
+            name: name.to_string(),
+            description: None,
+            quantity: 1,
+            location: Some("Shelf A".to_string()),
+            category: None,
+            last_updated: String::new(),
+            created_at: String::new(),
+            expiry_date: None,
+        }
+    }
+
+    #[test]
+    fn station_messages_round_trip() {
+        let message = StationMessage::Inventory { change: InventoryChange::Deleted { tag_id: "04A1".to_string() } };
+        let text = serde_json::to_string(&message).unwrap();
+        assert_eq!(text, r#"{"type":"inventory","change":{"change":"deleted","tag_id":"04A1"}}"#);
+        assert!(matches!(
+            serde_json::from_str::<StationMessage>(&text).unwrap(),
+            StationMessage::Inventory { change: InventoryChange::Deleted { tag_id } } if tag_id == "04A1"
+        ));
+    }
+
+    #[test]
+    fn station_uplinks_need_the_token() {
+        use crate::uplink::hub_request;
+        use std::sync::Arc;
+
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}/station", server.server_addr().to_ip().unwrap());
+        let hub = Arc::new(HubState::new(Some("s3cret".to_string())));
+        let server_hub = hub.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let hub = server_hub.clone();
+                std::thread::spawn(move || handle(request, &hub));
+            }
+        });
+
+        for token in [None, Some("wrong")] {
+            match tungstenite::connect(hub_request(&url, token).unwrap()) {
+                Err(tungstenite::Error::Http(response)) => assert_eq!(response.status(), 401),
+                other => panic!("uplink with token {:?} wasn't refused: {:?}", token, other.map(|_| ())),
+            }
+        }
+
+        let (mut socket, _) = tungstenite::connect(hub_request(&url, Some("s3cret")).unwrap()).unwrap();
+        let hello = serde_json::to_string(&StationMessage::Hello { station: "bench".to_string() }).unwrap();
+        socket.send(Message::text(hello)).unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while hub.stations().is_empty() {
+            assert!(std::time::Instant::now() < deadline, "the station never registered");
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(hub.stations()[0].name, "bench");
+    }
+
+    #[test]
+    fn merges_station_inventories_and_scans() {
+        let hub = HubState::default();
+        let feed = hub.feed.subscribe();
+
+        let old = hub.connect("bench", "10.0.0.2:5000");
+        let bench = hub.connect("bench", "10.0.0.2:5001");
+        hub.apply("bench", StationMessage::InventorySnapshot { items: vec![item("04A1", "Resistors")] });
+        hub.connect("store", "10.0.0.3:5000");
+        hub.apply("store", StationMessage::InventorySnapshot { items: vec![item("04A1", "Capacitors"), item("04B2", "Relays")] });
+        hub.apply("store", StationMessage::Inventory { change: InventoryChange::Deleted { tag_id: "04B2".to_string() } });
+
+        let items = hub.inventory("", None);
+        let names: Vec<_> = items.iter().map(|i| (i.item.name.as_str(), i.station.as_str())).collect();
+        assert_eq!(names, vec![("Capacitors", "store"), ("Resistors", "bench")]);
+        assert_eq!(hub.inventory("resist", None).len(), 1);
+        assert_eq!(hub.inventory("", Some("bench")).len(), 1);
+
+        let scan = Scan::decode("04A1B2C3", KeyboardLayout::Auto, "door");
+        hub.apply("bench", StationMessage::Scan { scan });
+        let published = match feed.try_recv() {
+            Ok(crate::scans::FeedEvent::Scan(scan)) => scan,
+            other => panic!("unexpected event {:?}", other),
+        };
+        assert_eq!(published.station.as_deref(), Some("bench"));
+
+        // the stale connection closing must not mark the reconnected station as gone
+        hub.disconnect("bench", old);
+        assert!(hub.stations()[0].connected);
+        hub.disconnect("bench", bench);
+        let stations = hub.stations();
+        assert_eq!((stations[0].name.as_str(), stations[0].connected, stations[0].scans), ("bench", false, 1));
+    }
+}
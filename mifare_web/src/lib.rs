@@ -0,0 +1,9 @@
+// lib.rs
+//
+// Shared by the station server (mifare_web) and the fleet hub (mifare_hub).
+pub mod api;
+pub mod card;
+pub mod hub;
+pub mod scans;
+pub mod systemd;
+pub mod uplink;
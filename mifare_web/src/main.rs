@@ -0,0 +1,250 @@
+// main.rs
+//
+// REST API and web interface for the Mifare reader and inventory, so a phone or
+// laptop on the LAN can operate the reader without a monitor on the Pi.
+// Scans are read from the same FIFO and inventory.db as the FLTK app.
+// It listens on loopback unless --bind says otherwise, and then only with a token
+// (--token-file) that requests changing anything must carry.
+// With --hub the station also forwards its scans and inventory edits to a mifare_hub,
+// presenting the hub's token from --hub-token-file.
+// With --reader (built with the `reader` feature) it opens the MFRC522 on SPI0 for the
+// card routes; leave it off while another program drives the reader.
+// Under systemd it reports readiness, pings the watchdog and can be socket activated;
+// on SIGTERM or Ctrl-C it stops taking requests and flushes the database.
+use std::error::Error;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use inventory_db::InventoryDB;
+use uid_codec::KeyboardLayout;
+
+use mifare_web::api::{self, AppState};
+use mifare_web::card::Reader;
+use mifare_web::scans::{self, ScanHub};
+use mifare_web::systemd;
+use mifare_web::uplink;
+
+const USAGE: &str = "Usage: mifare_web [--bind ADDR:PORT] [--db PATH] [--fifo [NAME=]PATH]... [--layout 0-5] [--hub HOST:PORT] [--hub-token-file PATH] [--station NAME] [--token-file PATH] [--reader] [--install-service [--socket-activation] [--service-user NAME]]";
+
+/// How long the request loop waits before checking for a shutdown
+const SHUTDOWN_POLL: Duration = Duration::from_secs(1);
+
+/// Reader name and FIFO path from a --fifo argument, the name defaults to the file name
+fn parse_fifo_arg(value: &str) -> (String, String) {
+    match value.split_once('=') {
+        Some((name, path)) => (name.to_string(), path.to_string()),
+        None => {
+            let name = std::path::Path::new(value)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_else(|| value.to_string());
+            (name, value.to_string())
+        }
+    }
+}
+
+/// The MFRC522 on SPI0, initialized
+#[cfg(feature = "reader")]
+fn open_reader() -> Result<Reader, Box<dyn Error>> {
+    let mut spi = mifare_rc522::open_spi(mifare_rc522::SPI_SPEED_HZ)?;
+    mifare_rc522::mfrc522_init(&mut spi)?;
+    Ok(Mutex::new(Box::new(spi)))
+}
+
+#[cfg(not(feature = "reader"))]
+fn open_reader() -> Result<Reader, Box<dyn Error>> {
+    Err("mifare_web was built without the reader feature".into())
+}
+
+fn main() {
+    let mut bind = "127.0.0.1:8080".to_string();
+    let mut db_path = "inventory.db".to_string();
+    let mut fifos = Vec::new();
+    let mut layout = KeyboardLayout::Auto;
+    let mut hub_address = None;
+    let mut hub_token_file = None;
+    let mut station = None;
+    let mut token_file = None;
+    let mut use_reader = false;
+    let mut install_service = false;
+    let mut socket_activation = false;
+    let mut service_user = systemd::default_service_user();
+    // what the installed service runs with
+    let mut service_args = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--install-service" => {
+                install_service = true;
+                continue;
+            },
+            "--socket-activation" => {
+                socket_activation = true;
+                continue;
+            },
+            "--service-user" => {
+                service_user = args.next();
+                continue;
+            },
+            "--reader" => {
+                use_reader = true;
+                service_args.push(arg.clone());
+                continue;
+            },
+            _ => service_args.push(arg.clone()),
+        }
+        let value = args.next();
+        service_args.extend(value.clone());
+        match (arg.as_str(), value) {
+            ("--bind", Some(value)) => bind = value,
+            ("--db", Some(value)) => db_path = value,
+            ("--fifo", Some(value)) => fifos.push(parse_fifo_arg(&value)),
+            ("--layout", Some(value)) => layout = KeyboardLayout::from_index(value.parse().unwrap_or(0)),
+            ("--hub", Some(value)) => hub_address = Some(value),
+            ("--hub-token-file", Some(value)) => hub_token_file = Some(value),
+            ("--station", Some(value)) => station = Some(value),
+            ("--token-file", Some(value)) => token_file = Some(value),
+            _ => {
+                eprintln!("{}", USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    let token = match token_file.as_deref().map(api::read_token).transpose() {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("Error reading the token from {}: {}", token_file.unwrap_or_default(), e);
+            std::process::exit(1);
+        }
+    };
+    let hub_token = match hub_token_file.as_deref().map(api::read_token).transpose() {
+        Ok(token) => token,
+        Err(e) => {
+            eprintln!("Error reading the hub token from {}: {}", hub_token_file.unwrap_or_default(), e);
+            std::process::exit(1);
+        }
+    };
+    if token.is_none() && !api::loopback_only(&bind) {
+        eprintln!("Anyone who can reach {} could change the inventory, give a token with --token-file PATH", bind);
+        std::process::exit(2);
+    }
+
+    if install_service {
+        let user = match service_user.filter(|user| user != "root") {
+            Some(user) => user,
+            None => {
+                eprintln!("The service doesn't run as root, name its account with --service-user NAME");
+                std::process::exit(2);
+            }
+        };
+        match systemd::install_service(&service_args, &bind, &user, socket_activation) {
+            Ok(written) => {
+                for path in &written {
+                    println!("Wrote {}", path.display());
+                }
+                let unit = if socket_activation { "socket" } else { "service" };
+                println!("Run: sudo systemctl daemon-reload && sudo systemctl enable --now {}.{}", systemd::SERVICE_NAME, unit);
+                return;
+            },
+            Err(e) => {
+                eprintln!("Error writing the unit files to {} (run with sudo?): {}", systemd::UNIT_DIR, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let db = match InventoryDB::new(&db_path) {
+        Ok(db) => Arc::new(Mutex::new(db)),
+        Err(e) => {
+            eprintln!("Error opening {}: {}", db_path, e);
+            std::process::exit(1);
+        }
+    };
+    let reader = match use_reader.then(open_reader).transpose() {
+        Ok(reader) => reader,
+        Err(e) => {
+            eprintln!("Error opening the reader: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if fifos.is_empty() {
+        fifos.push(parse_fifo_arg(scans::DEFAULT_FIFO));
+    }
+
+    // one FIFO per reader, so live feed clients can pick a reader by name
+    let hub = Arc::new(ScanHub::default());
+    for (reader, fifo_path) in &fifos {
+        scans::start_fifo_reader(reader.clone(), fifo_path.clone(), layout, hub.clone(), db.clone());
+    }
+    let readers: Vec<String> = fifos.iter().map(|(reader, path)| format!("{}={}", reader, path)).collect();
+
+    if let Some(address) = hub_address {
+        let station = station.unwrap_or_else(uplink::default_station_name);
+        println!("Forwarding to hub {} as station {}", address, station);
+        uplink::start_uplink(uplink::hub_url(&address), station, hub_token, hub.clone(), db.clone());
+    }
+
+    // with socket activation systemd already listens on the address of the socket unit
+    let activated = systemd::activated_listener();
+    let listening_on = if activated.is_some() { "the systemd socket".to_string() } else { format!("http://{}", bind) };
+    let server = match activated {
+        Some(listener) => tiny_http::Server::from_listener(listener, None),
+        None => tiny_http::Server::http(&bind),
+    };
+    let server = match server {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("Error listening on {}: {}", bind, e);
+            std::process::exit(1);
+        }
+    };
+    println!("Serving on {} (readers {}, inventory {})", listening_on, readers.join(", "), db_path);
+
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let shutdown_flag = shutdown.clone();
+    if let Err(e) = ctrlc::set_handler(move || shutdown_flag.store(true, Ordering::SeqCst)) {
+        eprintln!("Error installing the shutdown handler: {}", e);
+    }
+
+    let state = Arc::new(AppState { db, hub, token, reader });
+    let workers = api::Workers::new(api::MAX_WORKERS);
+    let watchdog = systemd::watchdog_interval();
+    let mut last_ping = Instant::now();
+    systemd::notify_ready();
+    while !shutdown.load(Ordering::SeqCst) {
+        match server.recv_timeout(watchdog.map_or(SHUTDOWN_POLL, |interval| interval.min(SHUTDOWN_POLL))) {
+            Ok(Some(request)) => {
+                // each request gets a thread up to MAX_WORKERS, live feeds stay open for as
+                // long as the page does
+                let state = state.clone();
+                workers.spawn(request, move |request| api::handle(request, &state));
+            },
+            Ok(None) => {},
+            Err(e) => {
+                eprintln!("Error accepting requests: {}", e);
+                break;
+            }
+        }
+
+        // only alive while requests are taken and the database isn't stuck
+        if let Some(interval) = watchdog {
+            if last_ping.elapsed() >= interval && state.db.lock().is_ok() {
+                systemd::notify_watchdog();
+                last_ping = Instant::now();
+            }
+        }
+    }
+
+    println!("Shutting down");
+    systemd::notify_stopping();
+    let flushed = state.db.lock()
+        .map_err(|_| "the database lock is poisoned".to_string())
+        .and_then(|db| db.flush().map_err(|e| e.to_string()));
+    if let Err(e) = flushed {
+        eprintln!("Error flushing {}: {}", db_path, e);
+        std::process::exit(1);
+    }
+}
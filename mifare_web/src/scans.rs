@@ -0,0 +1,210 @@
+// scans.rs
+//
+// Reads card data from the FIFO the capture tools write ("timestamp,card_data" lines),
+// decodes it and hands each scan to everyone watching the live feed.
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use inventory_db::{InventoryChange, InventoryDB};
+use serde::{Deserialize, Serialize};
+use uid_codec::{decode_uid, format_hex_uid, hex_to_decimal, lookup_manufacturer, KeyboardLayout};
+
+pub const DEFAULT_FIFO: &str = "/tmp/rfid_scans.fifo";
+
+/// Scans kept for clients that connect after they happened
+const RECENT_SCANS: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scan {
+    pub time: String,
+    /// Name of the reader (FIFO) the scan came from
+    pub reader: String,
+    /// Station that forwarded the scan, only set on the hub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub station: Option<String>,
+    pub raw: String,
+    pub tag_id: String,
+    pub hex_uid: String,
+    pub decimal: Option<u64>,
+    pub manufacturer: String,
+    pub card_type: String,
+    /// Inventory item name, if the tag is known
+    pub item: Option<String>,
+}
+
+impl Scan {
+    pub fn decode(raw: &str, layout: KeyboardLayout, reader: &str) -> Scan {
+        let clean = decode_uid(raw, layout);
+        let hex_uid = format_hex_uid(&clean);
+        Scan {
+            time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            reader: reader.to_string(),
+            station: None,
+            raw: raw.to_string(),
+            tag_id: hex_uid.replace(' ', ""),
+            decimal: hex_to_decimal(&clean),
+            manufacturer: lookup_manufacturer(&clean).unwrap_or("Unknown manufacturer").to_string(),
+            card_type: uid_codec::identify_card_type(&hex_uid),
+            hex_uid,
+            item: None,
+        }
+    }
+}
+
+/// Which scans a live feed client wants, from the query string of its request
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScanFilter {
+    /// Only tags that are not in the inventory
+    pub unknown_only: bool,
+    /// Only scans from this reader
+    pub reader: Option<String>,
+    /// Only scans forwarded by this station (hub feeds)
+    pub station: Option<String>,
+}
+
+impl ScanFilter {
+    pub fn matches(&self, scan: &Scan) -> bool {
+        if self.unknown_only && scan.item.is_some() {
+            return false;
+        }
+        if self.reader.as_ref().is_some_and(|reader| *reader != scan.reader) {
+            return false;
+        }
+        match &self.station {
+            Some(station) => scan.station.as_ref() == Some(station),
+            None => true,
+        }
+    }
+}
+
+/// What live feed subscribers receive
+#[derive(Debug, Clone)]
+pub enum FeedEvent {
+    Scan(Scan),
+    Inventory(InventoryChange),
+}
+
+/// Recent scans plus the live feed subscribers
+#[derive(Default)]
+pub struct ScanHub {
+    recent: Mutex<VecDeque<Scan>>,
+    subscribers: Mutex<Vec<Sender<FeedEvent>>>,
+}
+
+impl ScanHub {
+    pub fn publish(&self, scan: Scan) {
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == RECENT_SCANS {
+                recent.pop_front();
+            }
+            recent.push_back(scan.clone());
+        }
+
+        self.broadcast(FeedEvent::Scan(scan));
+    }
+
+    pub fn publish_change(&self, change: InventoryChange) {
+        self.broadcast(FeedEvent::Inventory(change));
+    }
+
+    fn broadcast(&self, event: FeedEvent) {
+        // clients that went away have dropped their receiver, forget them
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.retain(|tx| tx.send(event.clone()).is_ok());
+        }
+    }
+
+    pub fn subscribe(&self) -> Receiver<FeedEvent> {
+        let (tx, rx) = channel();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.push(tx);
+        }
+        rx
+    }
+
+    /// Recent scans, newest first
+    pub fn recent(&self) -> Vec<Scan> {
+        match self.recent.lock() {
+            Ok(recent) => recent.iter().rev().cloned().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+/// Read scans from a reader's FIFO on a background thread for as long as the server runs
+pub fn start_fifo_reader(reader: String, fifo_path: String, layout: KeyboardLayout, hub: Arc<ScanHub>, db: Arc<Mutex<InventoryDB>>) {
+    thread::spawn(move || loop {
+        if !Path::new(&fifo_path).exists() {
+            thread::sleep(Duration::from_secs(1));
+            continue;
+        }
+
+        // opening blocks until a capture tool opens the FIFO for writing,
+        // reading ends when it closes it, then we wait for the next one
+        let file = match File::open(&fifo_path) {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Error opening {}: {}", fifo_path, e);
+                thread::sleep(Duration::from_secs(1));
+                continue;
+            }
+        };
+
+        for line in BufReader::new(file).lines().map_while(Result::ok) {
+            let card_data = match line.split_once(',') {
+                Some(("firmware", _)) | None => continue,
+                Some((_, card_data)) => card_data.trim(),
+            };
+            if card_data.is_empty() {
+                continue;
+            }
+
+            let mut scan = Scan::decode(card_data, layout, &reader);
+            if let Ok(db) = db.lock() {
+                scan.item = db.get_item_by_any_tag(&scan.tag_id).ok().flatten().map(|item| item.name);
+            }
+            hub.publish(scan);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hub_keeps_recent_and_drops_closed_subscribers() {
+        let hub = ScanHub::default();
+        let rx = hub.subscribe();
+        drop(hub.subscribe());
+
+        hub.publish(Scan::decode("04A1B2C3", KeyboardLayout::Auto, "door"));
+        hub.publish(Scan::decode("DEADBEEF", KeyboardLayout::Auto, "door"));
+
+        assert_eq!(rx.try_iter().count(), 2);
+        assert_eq!(hub.subscribers.lock().unwrap().len(), 1);
+        assert_eq!(hub.recent()[0].tag_id, "DEADBEEF");
+    }
+
+    #[test]
+    fn filter_by_reader_and_unknown_tags() {
+        let mut known = Scan::decode("04A1B2C3", KeyboardLayout::Auto, "door");
+        known.item = Some("Resistor kit".to_string());
+        let unknown = Scan::decode("DEADBEEF", KeyboardLayout::Auto, "bench");
+
+        let unknown_only = ScanFilter { unknown_only: true, ..Default::default() };
+        assert!(!unknown_only.matches(&known));
+        assert!(unknown_only.matches(&unknown));
+
+        let door = ScanFilter { reader: Some("door".to_string()), ..Default::default() };
+        assert!(door.matches(&known));
+        assert!(!door.matches(&unknown));
+        assert!(ScanFilter::default().matches(&unknown));
+    }
+}
@@ -0,0 +1,207 @@
+// systemd.rs
+//
+// Running under systemd without libsystemd: the sd_notify datagrams (READY, WATCHDOG,
+// STOPPING), a listening socket handed over by socket activation, and the unit files
+// `mifare_web --install-service` writes. Outside systemd every call is a no-op.
+use std::env;
+use std::fs;
+use std::io;
+use std::net::TcpListener;
+use std::os::fd::FromRawFd;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::{SocketAddr, UnixDatagram};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Where --install-service writes the units
+pub const UNIT_DIR: &str = "/etc/systemd/system";
+pub const SERVICE_NAME: &str = "mifare_web";
+/// Restart the service when it stops answering the watchdog for this long
+const WATCHDOG_SEC: u32 = 30;
+/// First file descriptor systemd passes (SD_LISTEN_FDS_START)
+const LISTEN_FDS_START: i32 = 3;
+
+/// Send one notification to the service manager, false when not started by systemd
+pub fn notify(state: &str) -> bool {
+    let socket_path = match env::var("NOTIFY_SOCKET") {
+        Ok(path) if !path.is_empty() => path,
+        _ => return false,
+    };
+    let sent = UnixDatagram::unbound().and_then(|socket| {
+        // '@' stands for a socket in the abstract namespace
+        let address = match socket_path.strip_prefix('@') {
+            Some(name) => SocketAddr::from_abstract_name(name.as_bytes())?,
+            None => SocketAddr::from_pathname(&socket_path)?,
+        };
+        socket.send_to_addr(state.as_bytes(), &address)
+    });
+    sent.is_ok()
+}
+
+pub fn notify_ready() -> bool {
+    notify("READY=1")
+}
+
+pub fn notify_watchdog() -> bool {
+    notify("WATCHDOG=1")
+}
+
+pub fn notify_stopping() -> bool {
+    notify("STOPPING=1")
+}
+
+/// How often to ping the watchdog, None when the unit has no WatchdogSec
+pub fn watchdog_interval() -> Option<Duration> {
+    watchdog_interval_from(
+        env::var("WATCHDOG_USEC").ok().as_deref(),
+        env::var("WATCHDOG_PID").ok().as_deref(),
+        std::process::id(),
+    )
+}
+
+// Half the timeout, as sd_watchdog_enabled recommends. WATCHDOG_PID, when set, has to
+// name this process.
+fn watchdog_interval_from(usec: Option<&str>, pid: Option<&str>, own_pid: u32) -> Option<Duration> {
+    if let Some(pid) = pid {
+        if pid.parse::<u32>().ok() != Some(own_pid) {
+            return None;
+        }
+    }
+    let usec = usec?.parse::<u64>().ok().filter(|usec| *usec > 0)?;
+    Some(Duration::from_micros(usec / 2))
+}
+
+/// The socket systemd listens on for us when the service was socket activated
+pub fn activated_listener() -> Option<TcpListener> {
+    if !is_activated(
+        env::var("LISTEN_PID").ok().as_deref(),
+        env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    ) {
+        return None;
+    }
+    // Only the first socket is used, the socket unit declares one
+    env::remove_var("LISTEN_PID");
+    env::remove_var("LISTEN_FDS");
+    env::remove_var("LISTEN_FDNAMES");
+    // SAFETY: systemd passed the descriptor to this process (LISTEN_PID) and nothing
+    // else in it has taken ownership of it
+    Some(unsafe { TcpListener::from_raw_fd(LISTEN_FDS_START) })
+}
+
+fn is_activated(pid: Option<&str>, fds: Option<&str>, own_pid: u32) -> bool {
+    pid.and_then(|pid| pid.parse::<u32>().ok()) == Some(own_pid)
+        && fds.and_then(|fds| fds.parse::<i32>().ok()).is_some_and(|fds| fds >= 1)
+}
+
+/// The service unit. `args` are the command line the service runs with, `user` the
+/// account it runs as.
+pub fn service_unit(exec: &Path, args: &[String], working_dir: &Path, user: &str, socket: bool) -> String {
+    let command = std::iter::once(exec.display().to_string())
+        .chain(args.iter().cloned())
+        .map(|arg| quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut unit = String::from("[Unit]\nDescription=Mifare reader REST API and web interface\nAfter=network.target\n");
+    if socket {
+        unit.push_str(&format!("Requires={}.socket\n", SERVICE_NAME));
+    }
+    unit.push_str(&format!(
+        "\n[Service]\nType=notify\nNotifyAccess=main\nExecStart={}\nWorkingDirectory={}\nUser={}\n\
+         WatchdogSec={}\nRestart=on-failure\nRestartSec=5\nTimeoutStopSec=15\n\
+         \n[Install]\nWantedBy=multi-user.target\n",
+        command, escape_specifiers(&working_dir.display().to_string()), escape_specifiers(user), WATCHDOG_SEC
+    ));
+    unit
+}
+
+/// The socket unit for socket activation, systemd listens on `bind` until the first
+/// connection starts the service
+pub fn socket_unit(bind: &str) -> String {
+    format!(
+        "[Unit]\nDescription=Mifare reader REST API socket\n\
+         \n[Socket]\nListenStream={}\n\
+         \n[Install]\nWantedBy=sockets.target\n",
+        bind
+    )
+}
+
+/// The account --install-service sets the service to run as: the one that ran sudo,
+/// else the current one. None when that is root, the server is not run as root.
+pub fn default_service_user() -> Option<String> {
+    ["SUDO_USER", "USER"]
+        .iter()
+        .find_map(|name| env::var(name).ok().filter(|user| !user.is_empty()))
+        .filter(|user| user != "root")
+}
+
+/// Write the service unit, and the socket unit with `socket`, to UNIT_DIR.
+/// Returns the files written.
+pub fn install_service(args: &[String], bind: &str, user: &str, socket: bool) -> io::Result<Vec<PathBuf>> {
+    let exec = env::current_exe()?;
+    let working_dir = env::current_dir()?;
+    let dir = Path::new(UNIT_DIR);
+
+    let mut written = Vec::new();
+    let service = dir.join(format!("{}.service", SERVICE_NAME));
+    fs::write(&service, service_unit(&exec, args, &working_dir, user, socket))?;
+    written.push(service);
+    if socket {
+        let socket_path = dir.join(format!("{}.socket", SERVICE_NAME));
+        fs::write(&socket_path, socket_unit(bind))?;
+        written.push(socket_path);
+    }
+    Ok(written)
+}
+
+// systemd splits ExecStart on spaces, arguments with spaces are quoted
+fn quote(arg: &str) -> String {
+    let arg = escape_specifiers(arg);
+    if arg.contains([' ', '"', '\\']) {
+        format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        arg
+    }
+}
+
+// Settings like WorkingDirectory= take the rest of the line as it is, spaces and quotes
+// included, so only the % of systemd's specifiers needs escaping
+fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watchdog_interval_is_half_the_timeout() {
+        assert_eq!(watchdog_interval_from(Some("30000000"), None, 42), Some(Duration::from_secs(15)));
+        assert_eq!(watchdog_interval_from(Some("30000000"), Some("42"), 42), Some(Duration::from_secs(15)));
+        assert_eq!(watchdog_interval_from(Some("30000000"), Some("7"), 42), None);
+        assert_eq!(watchdog_interval_from(Some("0"), None, 42), None);
+        assert_eq!(watchdog_interval_from(None, None, 42), None);
+    }
+
+    #[test]
+    fn socket_activation_needs_our_pid() {
+        assert!(is_activated(Some("42"), Some("1"), 42));
+        assert!(!is_activated(Some("7"), Some("1"), 42));
+        assert!(!is_activated(Some("42"), Some("0"), 42));
+        assert!(!is_activated(None, None, 42));
+    }
+
+    #[test]
+    fn service_unit_runs_the_same_command() {
+        let args = vec!["--db".to_string(), "/srv/my inventory.db".to_string()];
+        let unit = service_unit(Path::new("/usr/local/bin/mifare_web"), &args, Path::new("/srv/my 100% stock"), "pi", true);
+
+        assert!(unit.contains("ExecStart=/usr/local/bin/mifare_web --db \"/srv/my inventory.db\"\n"));
+        assert!(unit.contains("WorkingDirectory=/srv/my 100%% stock\n"));
+        assert!(unit.contains("User=pi\n"));
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains("Requires=mifare_web.socket\n"));
+        assert!(!service_unit(Path::new("/bin/x"), &[], Path::new("/"), "pi", false).contains("Requires="));
+        assert!(socket_unit("0.0.0.0:8080").contains("ListenStream=0.0.0.0:8080\n"));
+    }
+}
@@ -0,0 +1,139 @@
+// uplink.rs
+//
+// Station side of fleet mode: keeps a WebSocket open to the hub and forwards every scan
+// and inventory edit over it. On (re)connect the whole inventory is sent first, so the
+// hub catches up on anything that changed while the link was down. The upgrade request
+// carries the hub's token, which a hub started with --token-file refuses the link without.
+use std::net::TcpStream;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use inventory_db::InventoryDB;
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::client::Request;
+use tungstenite::http::HeaderValue;
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+use crate::api::KEEPALIVE_INTERVAL;
+use crate::hub::StationMessage;
+use crate::scans::{FeedEvent, ScanHub};
+
+const RECONNECT_MIN: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(30);
+/// How long a read waits for the hub when draining pongs between events
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(10);
+
+type HubSocket = WebSocket<MaybeTlsStream<TcpStream>>;
+
+/// WebSocket URL of the hub's station endpoint, "hub.local:9090" gives "ws://hub.local:9090/station"
+pub fn hub_url(address: &str) -> String {
+    if address.contains("://") {
+        address.to_string()
+    } else {
+        format!("ws://{}/station", address.trim_end_matches('/'))
+    }
+}
+
+/// Station name used when --station isn't given, the Pi's hostname
+pub fn default_station_name() -> String {
+    std::fs::read_to_string("/etc/hostname")
+        .map(|name| name.trim().to_string())
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "station".to_string())
+}
+
+/// Forward the live feed to the hub on a background thread, reconnecting for as long as the server runs
+pub fn start_uplink(url: String, station: String, token: Option<String>, feed: Arc<ScanHub>, db: Arc<Mutex<InventoryDB>>) {
+    thread::spawn(move || {
+        let mut backoff = RECONNECT_MIN;
+        loop {
+            // subscribe before the snapshot so edits made while it is sent aren't lost
+            let events = feed.subscribe();
+            match connect(&url, &station, token.as_deref(), &db) {
+                Ok(socket) => {
+                    println!("Connected to hub {}", url);
+                    backoff = RECONNECT_MIN;
+                    if let Err(e) = forward(socket, &events) {
+                        eprintln!("Lost connection to hub {}: {}", url, e);
+                    }
+                },
+                Err(e) => eprintln!("Error connecting to hub {}: {}", url, e),
+            }
+            drop(events);
+
+            thread::sleep(backoff);
+            backoff = (backoff * 2).min(RECONNECT_MAX);
+        }
+    });
+}
+
+fn send(socket: &mut HubSocket, message: &StationMessage) -> Result<(), String> {
+    let text = serde_json::to_string(message).map_err(|e| e.to_string())?;
+    socket.send(Message::text(text)).map_err(|e| e.to_string())
+}
+
+/// Upgrade request for the hub's station endpoint, with "Authorization: Bearer <token>"
+/// when the hub has one
+pub(crate) fn hub_request(url: &str, token: Option<&str>) -> Result<Request, String> {
+    let mut request = url.into_client_request().map_err(|e| e.to_string())?;
+    if let Some(token) = token {
+        let value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| "The hub token isn't valid in a header".to_string())?;
+        request.headers_mut().insert("Authorization", value);
+    }
+    Ok(request)
+}
+
+/// Open the socket, introduce the station and send the current inventory
+fn connect(url: &str, station: &str, token: Option<&str>, db: &Mutex<InventoryDB>) -> Result<HubSocket, String> {
+    let (mut socket, _) = tungstenite::connect(hub_request(url, token)?).map_err(|e| e.to_string())?;
+    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+        stream.set_read_timeout(Some(DRAIN_TIMEOUT)).map_err(|e| e.to_string())?;
+    }
+
+    let items = db.lock()
+        .map_err(|_| "Database unavailable".to_string())?
+        .search_items("")
+        .map_err(|e| e.to_string())?;
+    send(&mut socket, &StationMessage::Hello { station: station.to_string() })?;
+    send(&mut socket, &StationMessage::InventorySnapshot { items })?;
+    Ok(socket)
+}
+
+/// Send feed events until the hub goes away. Pings keep idle links (and NAT entries) alive.
+fn forward(mut socket: HubSocket, events: &Receiver<FeedEvent>) -> Result<(), String> {
+    let mut last_sent = Instant::now();
+    loop {
+        let message = match events.recv_timeout(Duration::from_secs(1)) {
+            Ok(FeedEvent::Scan(scan)) => Some(StationMessage::Scan { scan }),
+            Ok(FeedEvent::Inventory(change)) => Some(StationMessage::Inventory { change }),
+            Err(RecvTimeoutError::Timeout) => None,
+            Err(RecvTimeoutError::Disconnected) => return Ok(()),
+        };
+
+        match message {
+            Some(message) => {
+                send(&mut socket, &message)?;
+                last_sent = Instant::now();
+            },
+            None if last_sent.elapsed() >= KEEPALIVE_INTERVAL => {
+                socket.send(Message::Ping(Vec::new().into())).map_err(|e| e.to_string())?;
+                last_sent = Instant::now();
+            },
+            None => {},
+        }
+
+        // the hub only ever answers pings, read those so they don't pile up
+        match socket.read() {
+            Ok(Message::Close(_)) => return Err("hub closed the connection".to_string()),
+            Ok(_) => {},
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {},
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
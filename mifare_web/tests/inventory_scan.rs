@@ -0,0 +1,91 @@
+// A tag read by the (simulated) reader goes through the capture FIFO to the live feed,
+// and a quantity change over the REST API lands in the inventory database.
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use inventory_db::{InventoryDB, NewItem};
+use mifare_rc522::mock::{MockCard, MockReader};
+use mifare_rc522::*;
+use mifare_web::api::{self, AppState};
+use mifare_web::scans::{self, FeedEvent, ScanHub};
+use uid_codec::KeyboardLayout;
+
+// What a capture tool writes to the FIFO for the card in the field
+fn capture_line(spi: &mut MockReader) -> String {
+    mfrc522_init(spi).unwrap();
+    let (status, _) = mfrc522_request(spi, PICC_REQIDL).unwrap();
+    assert_eq!(status, MI_OK, "no card answered");
+    let (status, uid) = mfrc522_anticoll(spi).unwrap();
+    assert_eq!(status, MI_OK);
+    let hex: String = uid[..4].iter().map(|b| format!("{:02X}", b)).collect();
+    format!("{},{}\n", chrono::Local::now().timestamp(), hex)
+}
+
+// One request to the server, returns the status line and the body
+fn http(address: &str, method: &str, path: &str, body: &str) -> (String, String) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(stream, "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+           method, path, address, body.len(), body).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    (head.lines().next().unwrap_or_default().to_string(), body.to_string())
+}
+
+#[test]
+fn scanned_tag_updates_the_inventory() {
+    let db = InventoryDB::new(":memory:").unwrap();
+    db.upsert_item(&NewItem {
+        tag_id: "04515253".to_string(),
+        name: "Resistor kit".to_string(),
+        description: None,
+        quantity: 5,
+        location: Some("Shelf 2".to_string()),
+        category: None,
+        expiry_date: None,
+    }).unwrap();
+    let state = Arc::new(AppState {
+        db: Arc::new(Mutex::new(db)),
+        hub: Arc::new(ScanHub::default()),
+        token: None,
+        reader: None,
+    });
+
+    let fifo = std::env::temp_dir().join(format!("mifare_web_scan_{}.fifo", std::process::id()));
+    let _ = std::fs::remove_file(&fifo);
+    assert!(Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+    let feed = state.hub.subscribe();
+    scans::start_fifo_reader("door".to_string(), fifo.to_string_lossy().into_owned(), KeyboardLayout::Auto,
+                             state.hub.clone(), state.db.clone());
+
+    let mut spi = MockReader::with_card(MockCard::new([0x04, 0x51, 0x52, 0x53]));
+    OpenOptions::new().write(true).open(&fifo).unwrap().write_all(capture_line(&mut spi).as_bytes()).unwrap();
+
+    let scan = match feed.recv_timeout(Duration::from_secs(5)) {
+        Ok(FeedEvent::Scan(scan)) => scan,
+        other => panic!("unexpected feed event {:?}", other),
+    };
+    assert_eq!(scan.tag_id, "04515253");
+    assert_eq!(scan.item.as_deref(), Some("Resistor kit"));
+    let _ = std::fs::remove_file(&fifo);
+
+    // the operator takes one out through the web interface
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let address = server.server_addr().to_ip().unwrap().to_string();
+    let server_state = state.clone();
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            api::handle(request, &server_state);
+        }
+    });
+
+    let (status, body) = http(&address, "POST", &format!("/api/inventory/{}/adjust", scan.tag_id), r#"{"delta": -1}"#);
+    assert!(status.contains("200"), "{} {}", status, body);
+    assert!(matches!(feed.recv_timeout(Duration::from_secs(5)), Ok(FeedEvent::Inventory(_))));
+    assert_eq!(state.db.lock().unwrap().get_item("04515253").unwrap().unwrap().quantity, 4);
+}
@@ -0,0 +1,903 @@
+# English, also the fallback for keys missing from the other catalogues
+
+app-title = Mifare Reader Utility
+about-text = Mifare Reader Utility v0.2.0
+
+    A tool for reading and analyzing Mifare card UIDs
+    Developed with Rust and FLTK
+
+    Now with Inventory Management!
+
+# Menus, '&' marks the keyboard shortcut letter
+menu-file = &File
+menu-export = &Export Data
+menu-export-csv = as &CSV
+menu-export-json = as &JSON
+menu-export-text = as &Text
+menu-import = &Import Data
+menu-view-database = &View Database
+menu-check-files = &Check Import Files
+menu-restore-backup = &Restore from Backup...
+menu-expiry-report = Ex&piring Items...
+menu-gdrive = &Google Drive
+menu-gdrive-export = Export Database
+menu-gdrive-import = Import Database
+menu-gdrive-sync = Sync Stations
+menu-signatures = Signa&tures
+menu-sign-exports = &Sign Exports
+menu-verify-signature = &Verify Signature...
+menu-signing-key = Show Signing &Key
+menu-save-log = &Save Log
+menu-exit = E&xit
+menu-edit = &Edit
+menu-preferences = &Preferences
+menu-keyboard = &Keyboard Layout
+menu-kb-auto = &Auto-detect
+menu-kb-windows = &Windows
+menu-kb-mac-us = &Mac US
+menu-kb-mac-intl = Mac &International
+menu-kb-azerty = &French AZERTY
+menu-kb-qwertz = &German QWERTZ
+menu-kb-calibrate = &Calibrate...
+menu-reader-config = &Reader Configuration...
+menu-language = &Language
+menu-profiles = Pro&files
+menu-profile-save = &Save Settings as Profile...
+menu-profile-delete = &Delete Active Profile
+menu-help = &Help
+menu-about = &About
+
+# Tabs
+tab-reader = Reader Mode
+tab-conversion = UID Conversion
+tab-batch = Batch Conversion
+tab-history = History
+tab-inventory = Inventory
+
+# Buttons
+btn-ok = OK
+btn-cancel = Cancel
+btn-add = Add
+btn-update = Update
+btn-remove = Remove
+btn-save = Save
+btn-clear = Clear
+btn-close = Close
+btn-restore = Restore
+btn-delete = Delete
+
+# File menu
+title-export-csv = Export as CSV
+title-export-json = Export as JSON
+title-export-text = Export as Text
+title-import = Import data
+msg-import-summary = Imported { $items } items from { $imported } files, { $failed } files failed.
+msg-no-import-files = No files found to import.
+msg-gdrive-exported = Database exported to Google Drive sync folder:
+    { $path }
+msg-gdrive-imported = Successfully imported { $count } items from Google Drive
+msg-stations-synced = Synced with { $stations } other stations.
+    Shared { $shared } local operations, received { $received }.
+    { $changed } items changed.
+msg-json-imported = Successfully imported { $count } items from JSON.
+err-save-log = Error saving log: { $error }
+err-export = Error exporting: { $error }
+err-import-files = Error processing import files: { $error }
+err-gdrive-disabled = Google Drive sync is not enabled. Please enable it in preferences.
+err-gdrive-export = Error exporting to Google Drive sync folder: { $error }
+err-gdrive-import = Error importing from Google Drive: { $error }
+err-gdrive-folder = Error creating Google Drive sync folder: { $error }
+err-station-sync = Error syncing stations: { $error }
+err-file-missing = File does not exist: { $path }
+err-json-import = Error importing JSON data: { $error }
+err-csv-import = CSV import is not yet implemented.
+err-read-file = Error reading file: { $error }
+err-read-file-path = Error reading { $path }: { $error }
+err-save-config = Error saving config: { $error }
+err-save-file = Error saving { $path }: { $error }
+err-write-file = Error writing file: { $error }
+err-export-tags = Error exporting item tags: { $error }
+export-format = Select export format:
+import-format = Select import format:
+log-exported = Exported to { $path }
+log-exported-tags = Exported item tags to { $path }
+msg-exported = Data exported to { $path }
+
+# Preferences
+prefs-title = Preferences
+prefs-tab-general = General
+prefs-save-logs = Save logs to file
+prefs-log-dir = Log directory:
+prefs-keyboard-layout = Keyboard Layout:
+prefs-edit-patterns = Edit Format Patterns...
+prefs-scan-cooldown = Scan cooldown:
+prefs-scan-cooldown-tip = Seconds a repeat scan of the same tag is ignored, 0 to count every scan
+prefs-seconds-off = seconds (0 = off)
+prefs-language = Language:
+prefs-language-auto = System default
+prefs-theme = Theme:
+prefs-reset = Reset to Defaults
+prefs-reset-confirm = Put every setting back to its default? Format patterns, manufacturers, command cards and scan rules you added are removed too.
+theme-light = Light
+theme-dark = Dark
+theme-high-contrast = High contrast
+prefs-tab-gdrive = Google Drive
+prefs-gdrive-enable = Enable Google Drive sync
+prefs-gdrive-folder = Sync folder:
+prefs-gdrive-choose = Select Google Drive sync folder
+prefs-gdrive-help = How to use Google Drive sync:
+
+    1. Install Google Drive for Desktop
+    2. Select a folder inside your Google Drive
+    3. Enable sync above and set the folder path
+    4. Use Export/Import menu options to sync your database
+prefs-tab-lf = 125 kHz Reader
+prefs-lf-enable = Read EM4100 tags from RDM6300 module
+prefs-lf-port = Serial port:
+prefs-lf-help = The RDM6300 module sends tag IDs at 9600 baud.
+    Connect its TX pin to the Pi RX pin (GPIO15)
+    and enable the serial port in raspi-config.
+
+    125 kHz tags appear in the capture window
+    alongside 13.56 MHz cards.
+prefs-tab-feedback = Scan Feedback
+prefs-feedback-enable = Beep and light the LED on each scan
+prefs-feedback-buzzer = Buzzer GPIO:
+prefs-feedback-red = Red LED GPIO:
+prefs-feedback-green = Green LED GPIO:
+prefs-feedback-help = 1 beep, green: tag found
+    2 beeps, amber: tag not in inventory
+    Long beep, red: unreadable scan or error
+prefs-tab-manufacturers = Manufacturers
+prefs-manuf-code = Code:
+prefs-manuf-name = Name:
+err-manuf-name = Please enter a manufacturer name
+err-manuf-add = Error adding manufacturer: { $error }
+prefs-tab-command-cards = Command Cards
+prefs-card-tag = Tag ID:
+prefs-card-help = Scanning a command card in the capture window runs its action, so do the function keys shown.
+err-card-tag = Enter the card's tag ID in hex
+err-card-select = Select a command card to remove
+prefs-tab-rules = Scan Rules
+prefs-rule-name = Name:
+prefs-rule-when = When:
+prefs-rule-do = Do:
+prefs-rule-when-tip = Regex for the tag ID, the manufacturer, category or location to match, or a number of days
+prefs-rule-do-tip = Amount, location, webhook URL or shell command. Commands get TAG_ID, MANUFACTURER and ITEM_NAME.
+rule-line = { $name }: when { $condition }, { $action }
+rule-any-tag = Any tag
+rule-tag-matches = Tag ID matches
+rule-manufacturer = Manufacturer contains
+rule-category = Category is
+rule-location = Location is
+rule-known-tag = Tag is in inventory
+rule-unknown-tag = Tag is not in inventory
+rule-expires-within = Item expires within (days)
+rule-expires-within-days = Item expires within { $days } days
+rule-increment = Increase quantity
+rule-decrement = Decrease quantity
+rule-set-location = Set location
+rule-open-form = Open item form
+rule-webhook = Call webhook
+rule-shell = Run shell command
+err-rule-name = Enter a name for the rule
+err-rule-select = Select a rule first
+err-rule-value = This condition or action needs a value
+err-rule-amount = The amount must be a whole number above 0
+err-rule-days = The days must be a whole number, 0 or more
+err-rule-unknown-tag = the tag is not in the inventory
+err-rule-exit = Scan rule webhook or command failed: { $status }
+
+# Keyboard layout calibration
+cal-title = Calibrate Keyboard Layout
+cal-steps = 1. Enter the UID of a reference card, e.g. as printed on it or read on another machine.
+    2. Click in the scan field and scan the same card with the reader.
+    3. Click Calibrate to find the layout that turns the scan back into that UID.
+cal-reference = Reference UID:
+cal-scan = Scan here:
+cal-calibrate = Calibrate
+cal-err-reference = Enter the reference card's UID first
+cal-err-scan = Scan the reference card into the scan field first
+cal-no-match = No layout matches the reference UID.
+    { $attempts }
+cal-locked = Locked in: { $layout }
+cal-also-matches = Also matches: { $layouts }
+
+# Format pattern editor
+pat-title = Format Patterns
+pat-name = Name:
+pat-regex = Regex:
+pat-description = Description:
+pat-help = Use named groups like (?P<site>\d{3}) and refer to them as {site} in the description.
+    Patterns are checked in order, the first match is shown in the Format field.
+pat-test-input = Test input:
+pat-enter-regex = Enter a regular expression
+pat-err-select = Select a pattern to update
+pat-err-save = Error saving patterns: { $error }
+pat-err-empty = The regex can't be empty
+pat-err-invalid = Invalid regex: { $error }
+
+# Wedge reader configuration
+rc-title = Reader Configuration
+rc-reader = Reader:
+rc-rescan = Rescan
+rc-send = Send Command
+rc-send-button = Send
+rc-add-heading = Add a command to this reader's profile:
+rc-model = Model:
+rc-command-name = Command name:
+rc-reports = Reports (hex):
+rc-report-type = Report type:
+rc-feature = Feature
+rc-output = Output
+rc-reports-help = Separate reports with ';', the first byte is the report ID
+rc-test = Test
+rc-no-readers = No HID readers found, plug the reader in and click Rescan
+rc-profile = Profile: { $model } ({ $count } commands)
+rc-no-profile = No profile for { $usb_id } yet, add its commands below
+rc-err-select = Select a command first
+rc-err-name = Enter a name for the command
+rc-err-reports = Enter at least one report in hex
+rc-err-hex = { $error } in command '{ $command }'
+rc-err-parse = Error parsing { $path }: { $error }
+rc-err-open = Error opening { $path }: { $error }
+rc-err-write = Error writing report { $report } to { $path }: { $error }
+rc-err-feature = Error sending feature report { $report } to { $path }: { $error }
+rc-confirm-send = Send '{ $command }' to { $device } ({ $path })?
+rc-sent = Sent '{ $command }' to { $path }
+rc-err-send = { $error }
+
+    Reading and writing hidraw devices usually needs a udev rule or root.
+
+# Command cards and stocktakes
+cc-toggle-inventory = Inventory mode on/off
+cc-toggle-form = Item form on/off
+cc-start-stocktake = Start stocktake
+cc-finish-stocktake = Finish stocktake
+cc-export-csv = Export scans as CSV
+cc-export-json = Export scans as JSON
+cc-receive-mode = Scan mode: receive
+cc-issue-mode = Scan mode: issue
+cc-count-mode = Scan mode: count
+cc-on = on
+cc-off = off
+cc-stocktake-started = counting scans
+cc-stocktake-running = Stocktake running: { $count } tags counted
+cc-stocktake-finished = { $missing } items missing
+cc-no-stocktake = no stocktake is running
+cc-exported = saved to { $path }
+stocktake-summary = Stocktake started { $started }
+    { $scans } scans, { $found } inventory items found, { $unknown } unknown tags.
+    { $missing } items were not scanned:
+stocktake-more = ...and { $count } more
+stocktake-unknown = Tags not in the inventory: { $tags }
+
+# Scan modes of the capture window
+capture-mode = Scan mode:
+mode-receive = Receive
+mode-issue = Issue
+mode-count = Count
+mode-count-prompt = Counted quantity of '{ $item }':
+err-count-quantity = The counted quantity must be a whole number of 0 or more
+err-issue-unknown = Tag { $tag_id } is not in the inventory, there is nothing to issue.
+triage-recorded = Unknown tag { $tag_id } ({ $scans } scans), see Unknown Tags on the inventory tab
+
+# Review list of queued scans
+review-queue = Queue scans
+review-open = Review ({ $count })
+review-title = Review Queued Scans
+review-line = { $mode } { $quantity } × { $name } ({ $tag_id }, { $scans } scans)
+review-new-tag = new item
+review-quantity = Quantity...
+review-name = Name...
+review-commit = Commit
+review-discard = Discard
+review-quantity-prompt = { $mode } how many of tag { $tag_id }?
+review-name-prompt = Name of the new item for tag { $tag_id }:
+review-known = This tag is already '{ $name }' in the inventory.
+review-select = Select a scan in the list first
+review-unnamed = Tag { $tag_id } is not in the inventory, give it a name to add it.
+review-negative = The quantity of tag { $tag_id } can't be negative.
+review-problems = { $problem } ({ $count } more to fix)
+review-has-problems = Fix the marked scans first, nothing was booked.
+review-discard-confirm = Discard { $count } queued scans? Nothing will be booked.
+review-committed = { $count } queued scans booked
+err-review-commit = Error booking the queued scans, nothing was booked: { $error }
+
+# Reader tab and the capture window
+reader-instructions = Instructions
+reader-welcome = Welcome to the Mifare Reader Utility!
+
+    Present Mifare cards to the reader to capture their UIDs. UIDs will be automatically converted to human-readable format.
+reader-clear = Clear Data
+reader-card-data = Card Data
+reader-clear-confirm = Are you sure you want to clear all captured data?
+capture-start = Start Capture
+capture-stop = Stop Capture
+capture-title = Card Capture
+capture-present = Present cards to the reader
+    Card data will appear here:
+capture-waiting = Waiting for card...
+capture-processing = Processing: { $card }
+capture-manual = Manual Entry:
+capture-submit = Submit
+capture-inventory = Update Inventory
+capture-show-form = Show Item Form When Scanning
+capture-lf-failed = 125 kHz reader on { $port }: { $error }
+capture-new-title = New Item
+capture-new-heading = Add New Inventory Item
+capture-update-title = Update Item
+capture-update-heading = Update Inventory Item
+capture-manufacturer = Manufacturer: { $manufacturer }
+capture-item-info = Item: { $item }
+    Tag ID: { $tag_id }
+capture-current-quantity = Current Quantity: { $quantity }
+capture-quantity = Quantity:
+scan-badge = Badge: { $badge }
+scan-repeat = Repeat scan within the cooldown, inventory not updated
+msg-capture-quantity = Updated quantity of '{ $item }' to { $quantity }
+msg-capture-updated = Item '{ $item }' updated
+err-capture-fifo = Error creating FIFO: { $error }
+err-capture-lf = Error opening 125 kHz reader on { $port }: { $error }
+err-capture-po = Error updating purchase order: { $error }
+err-capture-update = Error updating item: { $error }
+err-inventory-missing = Inventory system not initialized
+
+# UID conversion
+conv-uid = Enter Card UID:
+conv-convert = Convert
+conv-results = Conversion Results:
+conv-hex = Hexadecimal:
+conv-decimal = Decimal:
+conv-manufacturer = Manufacturer:
+conv-format = Format Description:
+conv-badge = Badge (FC / Card #):
+conv-keyboard-note = Note about keyboard encoding: If you see special characters instead of numbers,
+    this utility will automatically convert them to the correct format based on selected keyboard layout.
+
+    Format codes explanation:
+    'e' = QWERTY keyboard, 'f' = AZERTY keyboard, 'h' = QUERTY keyboard, 'r' = reader specific format.
+
+# Batch conversion from scan logs
+batch-file = Process File...
+batch-folder = Process Folder...
+batch-file-title = Choose a scan log
+batch-folder-title = Choose a folder of scan logs
+batch-add-inventory = Add new tags to inventory
+batch-save-file = Save results to a file
+batch-save-title = Save batch results
+batch-starting = Starting...
+batch-progress = { $done } of { $total }
+batch-progress-title = Converting batch
+progress-eta = about { $eta } left
+batch-cancelling = Cancelling...
+batch-cancelled = Batch cancelled, nothing was saved
+batch-done-text = { $scans } UIDs converted
+batch-done = { $scans } scans read from { $files } files
+batch-added = { $count } new tags added to inventory
+batch-saved = saved to { $path }
+batch-more = ...and { $count } more
+batch-export-unique = Export Unique...
+batch-export-unique-title = Export unique UIDs
+batch-stats = Summary
+    Total UIDs: { $total }
+    Unique UIDs: { $unique }
+    Duplicates collapsed: { $duplicates }
+    Invalid lines: { $invalid }
+batch-stats-manufacturers = Unique UIDs by manufacturer:
+batch-unique-hint = Export Unique saves the { $unique } unique UIDs without their repeats.
+batch-item-name = Tag { $tag_id }
+batch-instructions = Paste multiple UIDs below, one per line. The application will convert all of them at once.
+batch-clear-input = Clear Input
+batch-clear-input-confirm = Clear input data?
+batch-convert = Convert All
+batch-clear-results = Clear Results
+err-batch = Error processing the scan logs: { $error }
+err-batch-file = Could not read { $error }
+
+# Scan history
+history-from = From:
+history-to = To:
+history-date-tooltip = A day as YYYY-MM-DD, leave empty for no limit
+history-filter = Filter
+history-today = Today
+history-all = All
+history-export = Export...
+history-export-title = Export the listed scans
+history-export-empty = There are no scans to export in this range.
+history-count = { $count } scans
+history-more = ...and { $count } older scans, included in the export
+history-col-time = Time
+history-col-hex = Hex UID
+history-col-reader = Reader
+history-col-manufacturer = Manufacturer
+history-col-raw = Raw UID
+err-history-date = Not a date: { $date } (use YYYY-MM-DD)
+err-history-load = Error loading the scan history: { $error }
+
+# Settings file
+err-config-problems = Some settings could not be used and were put back to their defaults:
+    { $problems }
+err-config-read = Could not read { $path }: { $error }
+err-config-write = Could not write { $path }: { $error }
+err-config-parse = { $path } is not a valid settings file, it was copied to { $backup } and the defaults are used: { $error }
+err-config-override = { $variable } = "{ $value }" is not a value for that setting, it is ignored
+err-config-newer = The settings file was written by a newer release (schema { $value }), settings this release doesn't know are dropped when it is saved
+err-config-layout = { $setting } = { $value } is not a keyboard layout (0 to 5)
+err-config-language = { $setting } = "{ $value }" is not a supported language
+err-config-cooldown = { $setting } = { $value } is longer than an hour
+err-config-port = { $setting } names no serial port
+err-config-scale = { $setting } = { $value } is outside 0.25 to 4
+err-config-pin = { $setting } = { $value } is not a GPIO pin (0 to 27), that output is off
+err-config-pin-shared = { $setting } = { $value } is already used by another output, that output is off
+err-config-pattern = Format pattern "{ $value }" is not a valid regular expression and was dropped
+err-config-card = Command card "{ $value }" is not a hex tag ID and was dropped
+err-config-integration = Integration "{ $value }" has the name of another one and was dropped
+err-config-profile = Profile "{ $value }" does not exist, no profile is active
+err-config-plugin-dir = { $setting } = "{ $value }" is not an absolute path, plugins are loaded from the default directory
+
+# Configuration profiles
+profile-name-prompt = Profile name, the reader settings, keyboard layout, sync directories and scan rules are saved in it:
+profile-delete-confirm = Delete the profile "{ $name }"? The current settings stay as they are.
+err-profile = Error changing profiles: { $error }
+err-profile-name = A profile needs a name
+err-profile-missing = That profile no longer exists
+err-profile-none = No profile is active
+
+# Import reports
+import-error-row = Row { $row }: { $reason }
+import-error-no-tag = the tag ID is empty
+import-errors = { $count } problems, the first: { $first }
+import-progress = Row { $row } of { $total }
+import-report-title = Import of { $file } failed at { $time }, nothing from it was saved.
+
+# Backups
+backup-daily = daily
+backup-import = before an import
+backup-restore = before a restore
+backup-entry = { $time }  ({ $reason })
+restore-title = Restore from backup
+restore-help = The inventory and scan history go back to how they were when the backup was taken.
+restore-confirm = Restore the backup of { $backup }?
+    The database as it is now is backed up first.
+msg-no-backups = There are no backups in { $dir } yet.
+msg-backup-restored = Restored the backup of { $backup }. The database from before the restore is in { $undo }
+log-backup-taken = Database backed up to { $path }
+err-backup = Error backing up the database: { $error }
+err-backup-restore = Error restoring the backup: { $error }
+err-backup-remove = Error removing the old backup { $path }: { $error }
+err-backup-select = Select a backup to restore
+
+# Inventory tab
+inv-search-label = Search:
+inv-search = Search
+inv-refresh = Refresh List
+inv-add = Add Item
+inv-export = Export
+inv-purchasing = Purchasing
+inv-presence = Presence
+inv-details = Item Details
+inv-save = Save Changes
+inv-delete = Delete Item
+inv-clear = Clear Form
+inv-tags = Tags...
+inv-dumps = Dumps...
+inv-log = Event Log
+inv-col-tag = Tag ID
+inv-col-name = Name
+inv-col-quantity = Qty
+inv-col-category = Category
+inv-col-expires = Expires
+inv-stats = Total Items: { $items }
+    Total Quantity: { $quantity }
+    Categories: { $categories }
+inv-new-tag = Enter Tag ID for new item:
+inv-trash-confirm = Move this item to the trash? It can be restored from there.
+inv-delete-confirm = Are you sure you want to delete this item?
+form-name = Name:
+form-category = Category:
+form-location = Location:
+form-expires = Expires:
+form-expires-tip = Expiry date as YYYY-MM-DD, empty if the item doesn't expire
+form-description = Description:
+form-no-tag = Tag ID: None selected
+form-tag = Tag ID: { $tag_id }
+form-created = Created: { $time }
+form-updated = Updated: { $time }
+form-uncategorized = Uncategorized
+msg-inv-saved = Item saved successfully
+msg-inv-trashed = Item moved to the trash
+msg-inv-deleted = Item deleted successfully
+log-inv-loaded = Loaded details for item: { $item }
+log-inv-saved = Saved item: { $item }
+log-inv-trashed = Moved item with tag { $tag_id } to the trash
+log-inv-deleted = Deleted item with tag: { $tag_id }
+log-inv-cleared = Form cleared
+log-inv-new = Ready to add new item with tag: { $tag_id }
+log-inv-refreshed = Refreshed inventory list
+log-search-all = Showing all items
+log-search-found = Found { $count } items matching '{ $query }'
+scan-add-confirm = Tag ID { $tag_id } not found in inventory. Would you like to add a new item?
+scan-item-name = Enter item name:
+msg-scan-updated = Tag scanned: { $item }. Quantity updated to { $quantity }.
+msg-scan-added = New item '{ $item }' added to inventory.
+log-gdrive-auto-synced = Automatically synced database to Google Drive
+err-inv-save = Error saving item: { $error }
+err-inv-form = Form validation error: { $error }
+err-inv-select-save = No item selected to save
+err-form-name = Item name is required.
+err-form-quantity = Quantity must be a valid number.
+err-form-expiry = Expiry date must be a date as YYYY-MM-DD.
+err-scan-quantity = Error updating quantity: { $error }
+err-search = Error searching: { $error }
+err-gdrive-auto-sync = Error auto-syncing to Google Drive: { $error }
+
+# Inventory windows
+err-load-items = Error loading items: { $error }
+err-no-item-selected = No item selected
+item-not-in-inventory = (not in inventory)
+err-tag-on-item = Tag { $tag_id } is on the item '{ $item }'
+err-tag-is-unit = Tag { $tag_id } is already a unit
+err-tag-on-kit = Tag { $tag_id } is on the kit '{ $kit }'
+err-tag-check = Error checking the tag: { $error }
+err-tag-add = Error adding the tag: { $error }
+err-select-item-first = Select an item in the list first
+err-item-gone = The item is no longer in the inventory
+err-load-item = Error loading the item: { $error }
+
+# Tags of an item
+tags-title = Tags of '{ $item }'
+tags-columns = Tag / added / status
+tags-associate = Associate New Tag...
+tags-deactivate = Deactivate
+tags-replace-confirm = Does tag { $tag_id } replace a lost or damaged tag?
+tags-add-it = No, add it
+tags-replace-it = Yes
+tags-why-removed = Why is tag { $tag_id } taken off?
+tags-lost = lost
+tags-deactivate-confirm = Deactivate tag { $tag_id }? It will no longer find '{ $item }'. Why (lost, damaged)?
+tags-new-tag = Scan or type the new tag for '{ $item }':
+tags-active = active
+tags-active-own = active, item's own tag
+tags-deactivated = deactivated { $time }
+tags-deactivated-note = deactivated { $time } ({ $note })
+log-tags-added = Tag { $tag_id } added to '{ $item }'
+log-tags-deactivated = Tag { $tag_id } deactivated
+err-tags-load = Error loading the tags: { $error }
+err-tags-deactivate = Error deactivating the tag: { $error }
+err-tags-select-replaced = Select the tag it replaces in the list, then associate again
+err-tags-select = Select the tag to deactivate
+err-tags-taken = Tag { $tag_id } is already on an item
+err-tags-already-deactivated = That tag is already deactivated
+err-tags-inactive = The tag is no longer active
+err-tags-hex = A tag ID is written in hex, like 04A1B2C3
+
+# Trash
+trash-title = Trash
+trash-help = Deleted items stay here until the trash is emptied.
+trash-help-days = Deleted items stay here for { $days } days, then they are deleted for good.
+trash-delete = Delete Forever
+trash-empty = Empty Trash
+trash-delete-confirm = Delete '{ $item }' for good? This can't be undone.
+trash-empty-confirm = Delete all { $count } items in the trash for good? This can't be undone.
+log-trash-restored = Restored item: { $item }
+log-trash-deleted = Deleted for good: { $item }
+log-trash-emptied = Emptied the trash, { $count } items deleted for good
+err-trash-load = Error loading the trash: { $error }
+err-trash-restore = Error restoring item: { $error }
+err-trash-delete = Error deleting item: { $error }
+err-trash-empty = Error emptying the trash: { $error }
+err-trash-select-restore = No item selected to restore
+err-trash-select-delete = No item selected to delete
+err-trash-tag-taken = Tag { $tag_id } is now used by '{ $item }', delete or change that item first
+err-trash-gone = The item is no longer in the trash
+
+# Card dumps of an item
+dumps-title = Card Dumps of '{ $item }'
+dumps-columns = Attached / UID / blocks / file
+dumps-attach = Attach Dump...
+dumps-view = View
+dumps-save = Save As...
+dumps-delete-confirm = Delete the card dump of { $uid } attached { $time }?
+dumps-view-title = Card Dump { $uid } ({ $time })
+dumps-view-columns = Sec Blk  Data
+title-dumps-attach = Attach Card Dump
+title-dumps-save = Save Card Dump
+log-dumps-attached = Card dump of { $uid } ({ $blocks } blocks) attached to '{ $item }'
+log-dumps-saved = Card dump of { $uid } saved to { $path }
+log-dumps-deleted = Card dump of { $uid } deleted from '{ $item }'
+err-dumps-load = Error loading the card dumps: { $error }
+err-dumps-read = Error reading { $path }: { $error }
+err-dumps-attach = Error attaching the dump: { $error }
+err-dumps-save = Error saving the dump: { $error }
+err-dumps-delete = Error deleting the dump: { $error }
+err-dumps-select = Select a dump in the list first
+err-dump-not-flipper = { $file } isn't a Flipper .nfc file
+err-dump-too-big = { $file } holds { $bytes } bytes, more than a Classic 4K
+err-dump-not-raw = { $file } is not a raw dump ({ $bytes } bytes, expected a multiple of 16)
+err-dump-uid = { $file } has a bad UID line
+err-dump-block = { $file } has a bad line for block { $block }
+err-dump-too-many-blocks = { $file } has more blocks than a Classic 4K
+err-dump-no-blocks = { $file } has no block data, only Mifare Classic dumps can be attached
+
+# Database viewer
+dbv-title = Database Viewer
+dbv-heading = Inventory Database
+dbv-col-quantity = Quantity
+dbv-col-location = Location
+dbv-col-created = Created
+dbv-col-updated = Updated
+dbv-count = { $count } items in database
+dbv-export = Export CSV
+dbv-refresh = Refresh
+dbv-trash-confirm = Move the item with Tag ID '{ $tag_id }' to the trash?
+
+# Expiry dates
+expiry-title = Expiring items
+expiry-within = Expiring within (days):
+expiry-expired = expired { $days } days ago
+expiry-today = expires today
+expiry-days-left = in { $days } days
+msg-nothing-expiring = No items expire within { $days } days.
+title-expiry-save = Save the expiring items
+msg-expiry-saved = Expiring items saved to { $path }
+err-expiry-report = Error with the expiring items report: { $error }
+
+# Units tracked by serial number
+unit-in-stock = In stock
+unit-issued = Issued
+unit-retired = Retired
+unit-scanned = { $product } { $serial }: { $status }
+err-unit-status = { $product } { $serial }: { $status }, this scan can't move the unit
+unit-title = Products and Units
+unit-products = Products (in stock / issued / retired)
+unit-units = Units
+unit-add-product = Add Product
+unit-rename = Rename
+unit-add-unit = Add Unit
+unit-return = Return
+unit-issue = Issue
+unit-retire = Retire
+unit-product-name = Product name:
+unit-category = Category (optional):
+unit-tag-id = Tag ID of the unit:
+unit-serial = Serial number (optional):
+unit-delete-product-confirm = Delete the product '{ $product }'?
+unit-remove-confirm = Remove unit { $tag_id }? Retire units that are gone to keep their history.
+log-unit-product-added = Added product: { $product }
+log-unit-product-deleted = Deleted product: { $product }
+log-unit-added = Added unit { $tag_id } of { $product }
+log-unit-status = Unit { $tag_id }: { $status }
+log-unit-removed = Removed unit { $tag_id }
+err-unit-load-units = Error loading units: { $error }
+err-unit-load-products = Error loading products: { $error }
+err-unit-add-product = Error adding product: { $error }
+err-unit-rename-product = Error renaming product: { $error }
+err-unit-delete-product = Error deleting product: { $error }
+err-unit-product-in-use = The product still has units, remove or retire them instead
+err-unit-add = Error adding unit: { $error }
+err-unit-update = Error updating unit: { $error }
+err-unit-remove = Error removing unit: { $error }
+err-unit-no-product = Select or add a product first
+err-unit-select-product = No product selected
+err-unit-select = No unit selected
+
+# Presence monitoring
+presence-removed = Removed
+presence-returned = Returned
+presence-event = { $name } ({ $tag_id }): { $change }
+presence-title = Presence Monitor
+presence-absent = Removed after (s):
+presence-start = Start
+presence-stop = Stop
+presence-help = Tags are read while capture runs on the reader tab. The reader has to keep
+    reporting the tags left in its field, an item not reported for the set time is removed.
+presence-columns = Tag / item / status
+presence-events = Removals and returns
+presence-all-items = All items
+presence-every-location = every location
+presence-in-place = in place
+presence-gone = removed, gone { $secs }s
+log-presence-started = Monitoring the presence of { $count } items at { $location }
+log-presence-stopped = Presence monitoring stopped
+err-presence-load = Error loading presence events: { $error }
+err-presence-record = Error recording presence event: { $error }
+err-presence-absent = The time before an item counts as removed must be at least one second
+
+# Unknown tags
+triage-title = Unknown Tags
+triage-columns = Tag / scans / first seen / last seen
+triage-show-ignored = Show ignored
+triage-item-name = Item name:
+triage-item-name-tip = Left empty, each item is named after its tag
+triage-quantity = Quantity:
+triage-create = Create Items
+triage-existing-item = Existing item:
+triage-assign = Assign to Item
+triage-ignore = Ignore
+triage-unignore = List Again
+triage-ignored = ignored
+triage-assign-confirm = Give tag { $tag_id } to '{ $item }'? Its other tags keep working.
+log-triage-created = Added { $count } items from unknown tags
+log-triage-assigned = Tag { $tag_id } now also finds '{ $item }'
+log-triage-ignored = Ignoring { $count } unknown tags
+log-triage-unignored = Listing { $count } unknown tags again
+err-triage-load = Error loading unknown tags: { $error }
+err-triage-create = Error adding items, none were added: { $error }
+err-triage-update = Error updating the tags: { $error }
+err-triage-select-create = Select the tags to create items for
+err-triage-select-assign = Select one tag to give to the item
+err-triage-quantity = The quantity must be a whole number of 0 or more
+err-triage-tag-taken = Tag { $tag_id } is already on an item, or the item is gone
+
+# Kits
+kit-booked = { $kit }: { $items } items booked ({ $mode })
+kit-buildable = The stock makes { $count } { $kit } kits
+kit-short = { $kit } was not booked, { $count } items are short:
+kit-short-line = { $item }: { $needed } needed, { $available } in stock
+kit-missing-line = { $tag_id }: no longer in the inventory
+kit-title = Kits
+kit-per-kit-tip = How many of the item one kit takes
+kit-add-item = Add Item
+kit-component-columns = Item / tag / per kit / in stock
+kit-new = New Kit
+kit-delete = Delete Kit
+kit-remove-item = Remove Item
+kit-check-stock = Check Stock
+kit-tag-id = Tag ID of the kit:
+kit-name = Kit name:
+kit-delete-confirm = Delete the kit '{ $kit }'? Its items stay in the inventory.
+kit-check-count = How many '{ $kit }' kits to check for?
+kit-enough-stock = There is enough stock for { $count } '{ $kit }' kits, it makes { $buildable } in all.
+log-kit-added = Added kit: { $kit }
+log-kit-deleted = Deleted kit: { $kit }
+err-kit-load = Error loading kits: { $error }
+err-kit-add = Error adding kit: { $error }
+err-kit-delete = Error deleting kit: { $error }
+err-kit-save = Error saving kit: { $error }
+err-kit-check = Error checking stock: { $error }
+err-kit-select = No kit selected
+err-kit-no-kit = Select or add a kit first
+err-kit-quantity = The quantity per kit must be a whole number above 0
+err-kit-count = The number of kits must be a whole number above 0
+
+# Purchase orders
+po-received = Order { $po }: received { $quantity }, { $received } of { $ordered } so far
+po-not-on-order = { $tag_id } is not on order { $po }, the stock was updated without it
+po-complete = Order { $po } has come in in full and is closed
+po-title = Suppliers and Purchase Orders
+po-suppliers = Suppliers
+po-orders = Orders
+po-ordered-tip = How many of the item were ordered
+po-add-line = Add Line
+po-line-columns = Item / tag / received / ordered
+po-link-item = Link Item
+po-new-order = New Order
+po-close-reopen = Close/Reopen
+po-receive-into = Receive Into
+po-stop-receiving = Stop Receiving
+po-remove-line = Remove Line
+po-receiving = Receiving into order { $po }
+po-not-receiving = Scans are not received into an order
+po-supplier-name = Supplier name:
+po-contact = Contact person (optional):
+po-email = Email (optional):
+po-phone = Phone (optional):
+po-order-number = Order number:
+po-delete-supplier-confirm = Delete the supplier '{ $supplier }'? Its items stay in the inventory.
+po-status-open = open
+po-status-closed = closed
+log-po-supplier-added = Added supplier: { $supplier }
+log-po-supplier-deleted = Deleted supplier: { $supplier }
+log-po-item-linked = { $item } is bought from { $supplier }
+log-po-order-added = Added order { $po } with { $supplier }
+log-po-receiving = Receiving scans into order { $po }
+err-po-load-orders = Error loading orders: { $error }
+err-po-load-suppliers = Error loading suppliers: { $error }
+err-po-add-supplier = Error adding supplier: { $error }
+err-po-link-item = Error linking item: { $error }
+err-po-delete-supplier = Error deleting supplier: { $error }
+err-po-supplier-in-use = The supplier still has purchase orders
+err-po-add-order = Error adding order: { $error }
+err-po-update-order = Error updating order: { $error }
+err-po-save-order = Error saving order: { $error }
+err-po-no-supplier = Select or add a supplier first
+err-po-select-supplier = No supplier selected
+err-po-no-order = Select or add an order first
+err-po-select-order = No order selected
+err-po-select-line = No line selected
+err-po-closed = The order is closed, reopen it to receive into it
+err-po-quantity = The quantity ordered must be a whole number above 0
+
+# ERP integrations
+prefs-tab-integrations = Integrations
+integration-edit = Edit
+integration-retry = Retry Now
+integration-err-select = Select an integration first
+integration-remove-confirm = Remove the integration "{ $name }"? Changes it hasn't pushed yet won't be sent.
+integration-off = off
+integration-pending = { $pending } waiting
+integration-failing = { $pending } waiting, last try failed: { $error }
+integration-title = Integration
+integration-preset = Start from:
+integration-enabled = Push changes
+integration-name = Name:
+integration-url = URL:
+integration-headers = Headers:
+integration-headers-tip = One "Name: value" per line. ${NAME} is read from the environment when sending.
+integration-events = Events:
+integration-events-tip = With none checked every event is pushed
+integration-body = Body:
+integration-fields = Fields: { $fields }. In the body they are JSON values, text is quoted for you.
+integration-test-with = Test with:
+integration-test = Test Mapping
+integration-send-test = Send Test
+integration-test-skipped = This integration doesn't push "{ $event }" events, one would be sent like this:
+integration-test-sent = Sent, the server accepted it.
+event-item-saved = Item saved
+event-stock-received = Stock received
+event-stock-issued = Stock issued
+event-stock-counted = Stock counted
+event-stock-adjusted = Quantity changed
+event-item-deleted = Item deleted
+err-integration-name = The integration needs a name
+err-integration-name-taken = Another integration is already called "{ $name }"
+err-integration-url = The URL must start with http:// or https://
+err-integration-header = "{ $header }" is not a "Name: value" header
+err-integration-json = The body is not JSON once the fields are filled in: { $error }
+err-integration-braces = A field opened with {{ is never closed with }}
+err-integration-field = "{ $field }" is not a field the templates know
+err-integration-send = Sending failed: { $error }
+err-integration-failed = Integration { $name }: { $error }
+
+# Signatures
+title-verify-signature = Choose the signed file to verify
+msg-signature-own = { $path } is signed by this station and has not changed since.
+msg-signature-other = { $path } has not changed since it was signed, by the key:
+    { $key }
+    Compare it with the key the sender gave you.
+err-signature-invalid = { $path } can't be trusted: { $error }
+msg-signing-key = Public key of this station, give it to whoever checks its exports:
+    { $key }
+err-signing-key-load = Error loading the signing key: { $error }
+err-signing-key = { $path } does not hold a signing key
+err-signature-read = Error reading the signature { $path }: { $error }
+err-signature-format = { $path } is not a signature
+err-signature-mismatch = the file was changed after it was signed
+err-signature-other-key = The file is intact but was signed by another key: { $key }
+msg-signature-valid = Valid signature by the key { $key }
+usage-verify = Usage: mifare_reader_utility --verify FILE [--key PUBLIC_KEY]
+usage-import = Usage: mifare_reader_utility --import FILE.json
+usage-commands = Usage: mifare_reader_utility --verify FILE [--key PUBLIC_KEY] | --import FILE.json | --sync
+    This build has no window, it was built without the gui feature.
+err-sync-not-built = Station sync is not part of this build, it was built without the sync feature.
+
+# Console output
+log-plugins-loaded = Loaded plugins: { $plugins }
+err-plugin-relative = The plugin directory { $path } is not an absolute path, no plugins were loaded
+err-plugin-owner = Not loading plugins from { $path }, it belongs to another user
+err-plugin-writable = Not loading plugins from { $path }, other users can change it
+err-plugin-load = Error loading plugin { $path }: { $error }
+err-plugin-failed = Plugin { $plugin } failed: { $error }
+log-config-overrides = Settings overridden from the environment: { $settings }
+err-history-open = Error opening the scan history: { $error }
+err-history-save = Error saving a scan to the history: { $error }
+log-import-watching = Watching { $dir } for files to import
+err-import-watch = Error watching { $dir } for files to import: { $error }
+log-import-file = Imported { $count } items from { $file }
+err-import-file = { $file } was not imported: { $error }
+err-import-file-report = { $file } was not imported: { $error } (report: { $report })
+log-inventory-ready = Successfully initialized inventory database
+err-inventory-init = Error initializing inventory database: { $error }
+log-adding-inventory = Adding inventory tab
+log-setting-tab = Setting active tab
+log-window-shown = Main window shown
+log-shutting-down = Shutting down
+err-db-flush = Error flushing { $path }: { $error }
+log-trash-purged = Purged { $count } items that were in the trash for more than { $days } days
+err-trash-purge = Error purging the trash: { $error }
+log-created-dir = Created directory: { $dir }
+err-create-dir = Error creating directory { $dir }: { $error }
+log-signing-key-created = Created the signing key { $path }, keep a copy of it
+log-export-signed = Signed the export: { $path }
+err-sign-export = Error signing { $path }: { $error }
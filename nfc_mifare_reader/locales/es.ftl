@@ -0,0 +1,903 @@
+# Español
+
+app-title = Utilidad Lector Mifare
+about-text = Utilidad Lector Mifare v0.2.0
+
+    Una herramienta para leer y analizar UID de tarjetas Mifare
+    Desarrollada con Rust y FLTK
+
+    ¡Ahora con gestión de inventario!
+
+# Menús, '&' marca la letra del atajo de teclado
+menu-file = &Archivo
+menu-export = &Exportar datos
+menu-export-csv = como &CSV
+menu-export-json = como &JSON
+menu-export-text = como &Texto
+menu-import = &Importar datos
+menu-view-database = &Ver base de datos
+menu-check-files = &Comprobar archivos de importación
+menu-restore-backup = &Restaurar copia de seguridad...
+menu-expiry-report = Artículos que ca&ducan...
+menu-gdrive = &Google Drive
+menu-gdrive-export = Exportar base de datos
+menu-gdrive-import = Importar base de datos
+menu-gdrive-sync = Sincronizar estaciones
+menu-signatures = Firm&as
+menu-sign-exports = &Firmar exportaciones
+menu-verify-signature = &Verificar firma...
+menu-signing-key = Mostrar &clave de firma
+menu-save-log = &Guardar registro
+menu-exit = &Salir
+menu-edit = &Editar
+menu-preferences = &Preferencias
+menu-keyboard = Distribución de &teclado
+menu-kb-auto = Detección &automática
+menu-kb-windows = &Windows
+menu-kb-mac-us = &Mac EE. UU.
+menu-kb-mac-intl = Mac &Internacional
+menu-kb-azerty = AZERTY &francés
+menu-kb-qwertz = QWERTZ &alemán
+menu-kb-calibrate = &Calibrar...
+menu-reader-config = Configuración del &lector...
+menu-language = &Idioma
+menu-profiles = Per&files
+menu-profile-save = &Guardar ajustes como perfil...
+menu-profile-delete = &Eliminar el perfil activo
+menu-help = A&yuda
+menu-about = &Acerca de
+
+# Pestañas
+tab-reader = Modo lector
+tab-conversion = Conversión de UID
+tab-batch = Conversión por lotes
+tab-history = Historial
+tab-inventory = Inventario
+
+# Botones
+btn-ok = Aceptar
+btn-cancel = Cancelar
+btn-add = Añadir
+btn-update = Actualizar
+btn-remove = Eliminar
+btn-save = Guardar
+btn-clear = Borrar
+btn-close = Cerrar
+btn-restore = Restaurar
+btn-delete = Borrar
+
+# Menú Archivo
+title-export-csv = Exportar como CSV
+title-export-json = Exportar como JSON
+title-export-text = Exportar como texto
+title-import = Importar datos
+msg-import-summary = Importados { $items } artículos de { $imported } archivos, { $failed } archivos fallaron.
+msg-no-import-files = No se encontraron archivos para importar.
+msg-gdrive-exported = Base de datos exportada a la carpeta de sincronización de Google Drive:
+    { $path }
+msg-gdrive-imported = Se importaron { $count } artículos desde Google Drive
+msg-stations-synced = Sincronizado con otras { $stations } estaciones.
+    Se compartieron { $shared } operaciones locales y se recibieron { $received }.
+    { $changed } artículos modificados.
+msg-json-imported = Se importaron { $count } artículos desde JSON.
+err-save-log = Error al guardar el registro: { $error }
+err-export = Error al exportar: { $error }
+err-import-files = Error al procesar los archivos de importación: { $error }
+err-gdrive-disabled = La sincronización con Google Drive no está activada. Actívela en Preferencias.
+err-gdrive-export = Error al exportar a la carpeta de sincronización de Google Drive: { $error }
+err-gdrive-import = Error al importar desde Google Drive: { $error }
+err-gdrive-folder = Error al crear la carpeta de sincronización de Google Drive: { $error }
+err-station-sync = Error al sincronizar las estaciones: { $error }
+err-file-missing = El archivo no existe: { $path }
+err-json-import = Error al importar los datos JSON: { $error }
+err-csv-import = La importación de CSV aún no está disponible.
+err-read-file = Error al leer el archivo: { $error }
+err-read-file-path = Error al leer { $path }: { $error }
+err-save-config = Error al guardar la configuración: { $error }
+err-save-file = Error al guardar { $path }: { $error }
+err-write-file = Error al escribir el archivo: { $error }
+err-export-tags = Error al exportar las etiquetas de los artículos: { $error }
+export-format = Seleccione el formato de exportación:
+import-format = Seleccione el formato de importación:
+log-exported = Exportado a { $path }
+log-exported-tags = Etiquetas de los artículos exportadas a { $path }
+msg-exported = Datos exportados a { $path }
+
+# Preferencias
+prefs-title = Preferencias
+prefs-tab-general = General
+prefs-save-logs = Guardar registros en archivo
+prefs-log-dir = Carpeta de registros:
+prefs-keyboard-layout = Distribución de teclado:
+prefs-edit-patterns = Editar patrones de formato...
+prefs-scan-cooldown = Espera entre lecturas:
+prefs-scan-cooldown-tip = Segundos durante los que se ignora una nueva lectura de la misma etiqueta, 0 para contar todas
+prefs-seconds-off = segundos (0 = desactivado)
+prefs-language = Idioma:
+prefs-language-auto = Predeterminado del sistema
+prefs-theme = Tema:
+prefs-reset = Restablecer valores
+prefs-reset-confirm = ¿Volver a poner todos los ajustes en su valor predeterminado? También se eliminan los patrones de formato, fabricantes, tarjetas de comando y reglas de lectura que haya añadido.
+theme-light = Claro
+theme-dark = Oscuro
+theme-high-contrast = Alto contraste
+prefs-tab-gdrive = Google Drive
+prefs-gdrive-enable = Activar la sincronización con Google Drive
+prefs-gdrive-folder = Carpeta de sincronización:
+prefs-gdrive-choose = Seleccione la carpeta de sincronización de Google Drive
+prefs-gdrive-help = Cómo usar la sincronización con Google Drive:
+
+    1. Instale Google Drive para escritorio
+    2. Elija una carpeta dentro de su Google Drive
+    3. Active la sincronización arriba e indique la carpeta
+    4. Use las opciones Exportar/Importar del menú para sincronizar la base de datos
+prefs-tab-lf = Lector de 125 kHz
+prefs-lf-enable = Leer etiquetas EM4100 del módulo RDM6300
+prefs-lf-port = Puerto serie:
+prefs-lf-help = El módulo RDM6300 envía los ID de etiqueta a 9600 baudios.
+    Conecte su pin TX al pin RX de la Pi (GPIO15)
+    y active el puerto serie en raspi-config.
+
+    Las etiquetas de 125 kHz aparecen en la ventana de captura
+    junto a las tarjetas de 13,56 MHz.
+prefs-tab-feedback = Aviso de lectura
+prefs-feedback-enable = Pitar y encender el LED en cada lectura
+prefs-feedback-buzzer = GPIO del zumbador:
+prefs-feedback-red = GPIO del LED rojo:
+prefs-feedback-green = GPIO del LED verde:
+prefs-feedback-help = 1 pitido, verde: etiqueta encontrada
+    2 pitidos, ámbar: etiqueta fuera del inventario
+    Pitido largo, rojo: lectura ilegible o error
+prefs-tab-manufacturers = Fabricantes
+prefs-manuf-code = Código:
+prefs-manuf-name = Nombre:
+err-manuf-name = Introduzca el nombre del fabricante
+err-manuf-add = Error al añadir el fabricante: { $error }
+prefs-tab-command-cards = Tarjetas de comando
+prefs-card-tag = ID de etiqueta:
+prefs-card-help = Al leer una tarjeta de comando en la ventana de captura se ejecuta su acción, igual que con las teclas de función indicadas.
+err-card-tag = Introduce el ID de la etiqueta en hexadecimal
+err-card-select = Selecciona la tarjeta de comando que quieres quitar
+prefs-tab-rules = Reglas de lectura
+prefs-rule-name = Nombre:
+prefs-rule-when = Cuando:
+prefs-rule-do = Hacer:
+prefs-rule-when-tip = Expresión para el ID de etiqueta, el fabricante, la categoría o la ubicación que deben coincidir, o un número de días
+prefs-rule-do-tip = Cantidad, ubicación, URL del webhook u orden de shell. Las órdenes reciben TAG_ID, MANUFACTURER e ITEM_NAME.
+rule-line = { $name }: cuando { $condition }, { $action }
+rule-any-tag = Cualquier etiqueta
+rule-tag-matches = El ID de etiqueta coincide con
+rule-manufacturer = El fabricante contiene
+rule-category = La categoría es
+rule-location = La ubicación es
+rule-known-tag = La etiqueta está en el inventario
+rule-unknown-tag = La etiqueta no está en el inventario
+rule-expires-within = El artículo caduca en (días)
+rule-expires-within-days = El artículo caduca en { $days } días o menos
+rule-increment = Aumentar cantidad
+rule-decrement = Reducir cantidad
+rule-set-location = Fijar ubicación
+rule-open-form = Abrir formulario del artículo
+rule-webhook = Llamar al webhook
+rule-shell = Ejecutar orden de shell
+err-rule-name = Introduce un nombre para la regla
+err-rule-select = Selecciona primero una regla
+err-rule-value = Esta condición o acción necesita un valor
+err-rule-amount = La cantidad debe ser un número entero mayor que 0
+err-rule-days = Los días deben ser un número entero, 0 o más
+err-rule-unknown-tag = la etiqueta no está en el inventario
+err-rule-exit = Ha fallado el webhook o la orden de una regla de lectura: { $status }
+
+# Calibración de la distribución de teclado
+cal-title = Calibrar distribución de teclado
+cal-steps = 1. Introduzca el UID de una tarjeta de referencia, p. ej. el impreso en ella o leído en otro equipo.
+    2. Haga clic en el campo de lectura y pase la misma tarjeta por el lector.
+    3. Pulse Calibrar para encontrar la distribución que convierte la lectura en ese UID.
+cal-reference = UID de referencia:
+cal-scan = Lea aquí:
+cal-calibrate = Calibrar
+cal-err-reference = Introduzca primero el UID de la tarjeta de referencia
+cal-err-scan = Pase primero la tarjeta de referencia por el campo de lectura
+cal-no-match = Ninguna distribución coincide con el UID de referencia.
+    { $attempts }
+cal-locked = Fijada: { $layout }
+cal-also-matches = También coinciden: { $layouts }
+
+# Editor de patrones de formato
+pat-title = Patrones de formato
+pat-name = Nombre:
+pat-regex = Expresión:
+pat-description = Descripción:
+pat-help = Use grupos con nombre como (?P<site>\d{3}) y refiérase a ellos como {site} en la descripción.
+    Los patrones se prueban en orden, la primera coincidencia se muestra en el campo Formato.
+pat-test-input = Texto de prueba:
+pat-enter-regex = Introduzca una expresión regular
+pat-err-select = Seleccione un patrón para actualizar
+pat-err-save = Error al guardar los patrones: { $error }
+pat-err-empty = La expresión regular no puede estar vacía
+pat-err-invalid = Expresión regular no válida: { $error }
+
+# Configuración del lector de teclado
+rc-title = Configuración del lector
+rc-reader = Lector:
+rc-rescan = Buscar
+rc-send = Enviar comando
+rc-send-button = Enviar
+rc-add-heading = Añadir un comando al perfil de este lector:
+rc-model = Modelo:
+rc-command-name = Nombre del comando:
+rc-reports = Informes (hex):
+rc-report-type = Tipo de informe:
+rc-feature = Feature
+rc-output = Output
+rc-reports-help = Separe los informes con ';', el primer byte es el ID del informe
+rc-test = Probar
+rc-no-readers = No se encontraron lectores HID, conecte el lector y pulse Buscar
+rc-profile = Perfil: { $model } ({ $count } comandos)
+rc-no-profile = Aún no hay perfil para { $usb_id }, añada sus comandos abajo
+rc-err-select = Seleccione primero un comando
+rc-err-name = Introduzca un nombre para el comando
+rc-err-reports = Introduzca al menos un informe en hexadecimal
+rc-err-hex = { $error } en el comando '{ $command }'
+rc-err-parse = Error al interpretar { $path }: { $error }
+rc-err-open = Error al abrir { $path }: { $error }
+rc-err-write = Error al escribir el informe { $report } en { $path }: { $error }
+rc-err-feature = Error al enviar el informe de función { $report } a { $path }: { $error }
+rc-confirm-send = ¿Enviar '{ $command }' a { $device } ({ $path })?
+rc-sent = '{ $command }' enviado a { $path }
+rc-err-send = { $error }
+
+    Para leer y escribir dispositivos hidraw suele hacer falta una regla udev o ser root.
+
+# Tarjetas de comando e inventarios físicos
+cc-toggle-inventory = Activar/desactivar modo inventario
+cc-toggle-form = Activar/desactivar formulario de artículo
+cc-start-stocktake = Iniciar recuento de inventario
+cc-finish-stocktake = Terminar recuento de inventario
+cc-export-csv = Exportar lecturas como CSV
+cc-export-json = Exportar lecturas como JSON
+cc-receive-mode = Modo de lectura: entrada
+cc-issue-mode = Modo de lectura: salida
+cc-count-mode = Modo de lectura: recuento
+cc-on = activado
+cc-off = desactivado
+cc-stocktake-started = contando lecturas
+cc-stocktake-running = Recuento en curso: { $count } etiquetas contadas
+cc-stocktake-finished = faltan { $missing } artículos
+cc-no-stocktake = no hay ningún recuento en curso
+cc-exported = guardado en { $path }
+stocktake-summary = Recuento iniciado el { $started }
+    { $scans } lecturas, { $found } artículos del inventario encontrados, { $unknown } etiquetas desconocidas.
+    { $missing } artículos no se han leído:
+stocktake-more = ...y { $count } más
+stocktake-unknown = Etiquetas que no están en el inventario: { $tags }
+
+# Modos de lectura de la ventana de captura
+capture-mode = Modo de lectura:
+mode-receive = Entrada
+mode-issue = Salida
+mode-count = Recuento
+mode-count-prompt = Cantidad contada de '{ $item }':
+err-count-quantity = La cantidad contada debe ser un número entero igual o mayor que 0
+err-issue-unknown = La etiqueta { $tag_id } no está en el inventario, no hay nada que retirar.
+triage-recorded = Etiqueta desconocida { $tag_id } ({ $scans } lecturas), véase Unknown Tags en la pestaña de inventario
+
+# Lista de revisión de lecturas en cola
+review-queue = Poner en cola
+review-open = Revisar ({ $count })
+review-title = Revisar lecturas en cola
+review-line = { $mode } { $quantity } × { $name } ({ $tag_id }, { $scans } lecturas)
+review-new-tag = artículo nuevo
+review-quantity = Cantidad...
+review-name = Nombre...
+review-commit = Confirmar
+review-discard = Descartar
+review-quantity-prompt = { $mode }: ¿cuántos de la etiqueta { $tag_id }?
+review-name-prompt = Nombre del artículo nuevo para la etiqueta { $tag_id }:
+review-known = Esta etiqueta ya es '{ $name }' en el inventario.
+review-select = Seleccione primero una lectura de la lista
+review-unnamed = La etiqueta { $tag_id } no está en el inventario, dele un nombre para añadirla.
+review-negative = La cantidad de la etiqueta { $tag_id } no puede ser negativa.
+review-problems = { $problem } ({ $count } más por corregir)
+review-has-problems = Corrija primero las lecturas marcadas, no se registró nada.
+review-discard-confirm = ¿Descartar { $count } lecturas en cola? No se registrará nada.
+review-committed = { $count } lecturas en cola registradas
+err-review-commit = Error al registrar las lecturas en cola, no se registró nada: { $error }
+
+# Pestaña del lector y ventana de captura
+reader-instructions = Instrucciones
+reader-welcome = ¡Bienvenido a Mifare Reader Utility!
+
+    Acerque tarjetas Mifare al lector para capturar sus UID. Los UID se convierten automáticamente a un formato legible.
+reader-clear = Borrar datos
+reader-card-data = Datos de las tarjetas
+reader-clear-confirm = ¿Seguro que desea borrar todos los datos capturados?
+capture-start = Iniciar captura
+capture-stop = Detener captura
+capture-title = Captura de tarjetas
+capture-present = Acerque tarjetas al lector
+    Los datos de las tarjetas aparecerán aquí:
+capture-waiting = Esperando una tarjeta...
+capture-processing = Procesando: { $card }
+capture-manual = Entrada manual:
+capture-submit = Enviar
+capture-inventory = Actualizar inventario
+capture-show-form = Mostrar la ficha del artículo al leer
+capture-lf-failed = Lector de 125 kHz en { $port }: { $error }
+capture-new-title = Artículo nuevo
+capture-new-heading = Añadir un artículo al inventario
+capture-update-title = Actualizar artículo
+capture-update-heading = Actualizar un artículo del inventario
+capture-manufacturer = Fabricante: { $manufacturer }
+capture-item-info = Artículo: { $item }
+    ID de etiqueta: { $tag_id }
+capture-current-quantity = Cantidad actual: { $quantity }
+capture-quantity = Cantidad:
+scan-badge = Credencial: { $badge }
+scan-repeat = Lectura repetida dentro de la espera, no se actualizó el inventario
+msg-capture-quantity = Cantidad de '{ $item }' actualizada a { $quantity }
+msg-capture-updated = Artículo '{ $item }' actualizado
+err-capture-fifo = Error al crear la FIFO: { $error }
+err-capture-lf = Error al abrir el lector de 125 kHz en { $port }: { $error }
+err-capture-po = Error al actualizar la orden de compra: { $error }
+err-capture-update = Error al actualizar el artículo: { $error }
+err-inventory-missing = El inventario no está inicializado
+
+# Conversión de UID
+conv-uid = UID de la tarjeta:
+conv-convert = Convertir
+conv-results = Resultados de la conversión:
+conv-hex = Hexadecimal:
+conv-decimal = Decimal:
+conv-manufacturer = Fabricante:
+conv-format = Descripción del formato:
+conv-badge = Credencial (FC / n.º de tarjeta):
+conv-keyboard-note = Nota sobre la codificación del teclado: si ve caracteres especiales en lugar de números,
+    esta utilidad los convierte automáticamente al formato correcto según la distribución de teclado elegida.
+
+    Explicación de los códigos de formato:
+    'e' = teclado QWERTY, 'f' = teclado AZERTY, 'h' = teclado QUERTY, 'r' = formato propio del lector.
+
+# Conversión por lotes desde registros de lecturas
+batch-file = Procesar archivo...
+batch-folder = Procesar carpeta...
+batch-file-title = Elige un registro de lecturas
+batch-folder-title = Elige una carpeta de registros de lecturas
+batch-add-inventory = Añadir etiquetas nuevas al inventario
+batch-save-file = Guardar resultados en un archivo
+batch-save-title = Guardar resultados del lote
+batch-starting = Iniciando...
+batch-progress = { $done } de { $total }
+batch-progress-title = Conversión por lotes
+progress-eta = quedan unos { $eta }
+batch-cancelling = Cancelando...
+batch-cancelled = Lote cancelado, no se guardó nada
+batch-done-text = { $scans } UID convertidos
+batch-done = { $scans } lecturas leídas de { $files } archivos
+batch-added = { $count } etiquetas nuevas añadidas al inventario
+batch-saved = guardado en { $path }
+batch-more = ...y { $count } más
+batch-export-unique = Exportar únicos...
+batch-export-unique-title = Exportar UID únicos
+batch-stats = Resumen
+    UID totales: { $total }
+    UID únicos: { $unique }
+    Duplicados agrupados: { $duplicates }
+    Líneas no válidas: { $invalid }
+batch-stats-manufacturers = UID únicos por fabricante:
+batch-unique-hint = Exportar únicos guarda los { $unique } UID únicos sin sus repeticiones.
+batch-item-name = Etiqueta { $tag_id }
+batch-instructions = Pegue varios UID debajo, uno por línea. La aplicación los convierte todos a la vez.
+batch-clear-input = Borrar entrada
+batch-clear-input-confirm = ¿Borrar los datos de entrada?
+batch-convert = Convertir todo
+batch-clear-results = Borrar resultados
+err-batch = Error al procesar los registros de lecturas: { $error }
+err-batch-file = No se pudo leer { $error }
+
+# Historial de lecturas
+history-from = Desde:
+history-to = Hasta:
+history-date-tooltip = Un día como AAAA-MM-DD, vacío para no limitar
+history-filter = Filtrar
+history-today = Hoy
+history-all = Todo
+history-export = Exportar...
+history-export-title = Exportar las lecturas listadas
+history-export-empty = No hay lecturas que exportar en este intervalo.
+history-count = { $count } lecturas
+history-more = ...y { $count } lecturas anteriores, incluidas en la exportación
+history-col-time = Hora
+history-col-hex = UID hex
+history-col-reader = Lector
+history-col-manufacturer = Fabricante
+history-col-raw = UID original
+err-history-date = Fecha no válida: { $date } (use AAAA-MM-DD)
+err-history-load = Error al cargar el historial de lecturas: { $error }
+
+# Archivo de configuración
+err-config-problems = Algunos ajustes no se pudieron usar y volvieron a su valor predeterminado:
+    { $problems }
+err-config-read = No se pudo leer { $path }: { $error }
+err-config-write = No se pudo escribir { $path }: { $error }
+err-config-parse = { $path } no es un archivo de configuración válido, se copió a { $backup } y se usan los valores predeterminados: { $error }
+err-config-override = { $variable } = "{ $value }" no es un valor para ese ajuste, se ignora
+err-config-newer = El archivo de configuración lo escribió una versión más reciente (esquema { $value }), los ajustes que esta versión no conoce se descartan al guardarlo
+err-config-layout = { $setting } = { $value } no es una distribución de teclado (0 a 5)
+err-config-language = { $setting } = "{ $value }" no es un idioma disponible
+err-config-cooldown = { $setting } = { $value } supera una hora
+err-config-port = { $setting } no indica ningún puerto serie
+err-config-scale = { $setting } = { $value } está fuera de 0,25 a 4
+err-config-pin = { $setting } = { $value } no es un pin GPIO (0 a 27), esa salida queda desactivada
+err-config-pin-shared = { $setting } = { $value } ya lo usa otra salida, esa salida queda desactivada
+err-config-pattern = El patrón de formato "{ $value }" no es una expresión regular válida y se descartó
+err-config-card = La tarjeta de comando "{ $value }" no es un ID de etiqueta hexadecimal y se descartó
+err-config-integration = La integración "{ $value }" tiene el nombre de otra y se descartó
+err-config-profile = El perfil "{ $value }" no existe, no hay ningún perfil activo
+err-config-plugin-dir = { $setting } = "{ $value }" no es una ruta absoluta, los complementos se cargan del directorio predeterminado
+
+# Perfiles de configuración
+profile-name-prompt = Nombre del perfil, en él se guardan los ajustes del lector, la distribución del teclado, las carpetas de sincronización y las reglas de lectura:
+profile-delete-confirm = ¿Eliminar el perfil "{ $name }"? Los ajustes actuales no cambian.
+err-profile = Error al cambiar de perfil: { $error }
+err-profile-name = El perfil necesita un nombre
+err-profile-missing = Ese perfil ya no existe
+err-profile-none = No hay ningún perfil activo
+
+# Informes de importación
+import-error-row = Fila { $row }: { $reason }
+import-error-no-tag = el ID de la etiqueta está vacío
+import-errors = { $count } problemas, el primero: { $first }
+import-progress = Fila { $row } de { $total }
+import-report-title = La importación de { $file } falló el { $time }, no se guardó nada de él.
+
+# Copias de seguridad
+backup-daily = diaria
+backup-import = antes de una importación
+backup-restore = antes de una restauración
+backup-entry = { $time }  ({ $reason })
+restore-title = Restaurar copia de seguridad
+restore-help = El inventario y el historial de lecturas vuelven a como estaban cuando se hizo la copia.
+restore-confirm = ¿Restaurar la copia de { $backup }?
+    Antes se hace una copia de la base de datos tal como está ahora.
+msg-no-backups = Todavía no hay copias de seguridad en { $dir }.
+msg-backup-restored = Copia de { $backup } restaurada. La base de datos de antes de la restauración está en { $undo }
+log-backup-taken = Copia de seguridad de la base de datos en { $path }
+err-backup = Error al hacer la copia de seguridad de la base de datos: { $error }
+err-backup-restore = Error al restaurar la copia de seguridad: { $error }
+err-backup-remove = Error al eliminar la copia antigua { $path }: { $error }
+err-backup-select = Seleccione una copia para restaurar
+
+# Pestaña de inventario
+inv-search-label = Buscar:
+inv-search = Buscar
+inv-refresh = Actualizar lista
+inv-add = Añadir artículo
+inv-export = Exportar
+inv-purchasing = Compras
+inv-presence = Presencia
+inv-details = Detalles del artículo
+inv-save = Guardar cambios
+inv-delete = Borrar artículo
+inv-clear = Limpiar formulario
+inv-tags = Etiquetas...
+inv-dumps = Volcados...
+inv-log = Registro de eventos
+inv-col-tag = ID de etiqueta
+inv-col-name = Nombre
+inv-col-quantity = Cant.
+inv-col-category = Categoría
+inv-col-expires = Caduca
+inv-stats = Artículos: { $items }
+    Cantidad total: { $quantity }
+    Categorías: { $categories }
+inv-new-tag = Introduzca el ID de etiqueta del nuevo artículo:
+inv-trash-confirm = ¿Mover este artículo a la papelera? Se puede restaurar desde allí.
+inv-delete-confirm = ¿Seguro que desea borrar este artículo?
+form-name = Nombre:
+form-category = Categoría:
+form-location = Ubicación:
+form-expires = Caduca:
+form-expires-tip = Fecha de caducidad como AAAA-MM-DD, vacía si el artículo no caduca
+form-description = Descripción:
+form-no-tag = ID de etiqueta: ninguno seleccionado
+form-tag = ID de etiqueta: { $tag_id }
+form-created = Creado: { $time }
+form-updated = Actualizado: { $time }
+form-uncategorized = Sin categoría
+msg-inv-saved = Artículo guardado
+msg-inv-trashed = Artículo movido a la papelera
+msg-inv-deleted = Artículo borrado
+log-inv-loaded = Detalles cargados del artículo: { $item }
+log-inv-saved = Artículo guardado: { $item }
+log-inv-trashed = Artículo con etiqueta { $tag_id } movido a la papelera
+log-inv-deleted = Borrado el artículo con la etiqueta: { $tag_id }
+log-inv-cleared = Formulario limpiado
+log-inv-new = Listo para añadir un artículo con la etiqueta: { $tag_id }
+log-inv-refreshed = Lista del inventario actualizada
+log-search-all = Mostrando todos los artículos
+log-search-found = { $count } artículos coinciden con '{ $query }'
+scan-add-confirm = El ID de etiqueta { $tag_id } no está en el inventario. ¿Desea añadir un artículo nuevo?
+scan-item-name = Introduzca el nombre del artículo:
+msg-scan-updated = Etiqueta leída: { $item }. Cantidad actualizada a { $quantity }.
+msg-scan-added = Nuevo artículo '{ $item }' añadido al inventario.
+log-gdrive-auto-synced = Base de datos sincronizada automáticamente con Google Drive
+err-inv-save = Error al guardar el artículo: { $error }
+err-inv-form = Error de validación del formulario: { $error }
+err-inv-select-save = No hay ningún artículo seleccionado para guardar
+err-form-name = El nombre del artículo es obligatorio.
+err-form-quantity = La cantidad debe ser un número válido.
+err-form-expiry = La fecha de caducidad debe ser una fecha como AAAA-MM-DD.
+err-scan-quantity = Error al actualizar la cantidad: { $error }
+err-search = Error al buscar: { $error }
+err-gdrive-auto-sync = Error al sincronizar automáticamente con Google Drive: { $error }
+
+# Ventanas del inventario
+err-load-items = Error al cargar los artículos: { $error }
+err-no-item-selected = No hay ningún artículo seleccionado
+item-not-in-inventory = (no está en el inventario)
+err-tag-on-item = La etiqueta { $tag_id } está en el artículo '{ $item }'
+err-tag-is-unit = La etiqueta { $tag_id } ya es una unidad
+err-tag-on-kit = La etiqueta { $tag_id } está en el kit '{ $kit }'
+err-tag-check = Error al comprobar la etiqueta: { $error }
+err-tag-add = Error al añadir la etiqueta: { $error }
+err-select-item-first = Seleccione primero un artículo de la lista
+err-item-gone = El artículo ya no está en el inventario
+err-load-item = Error al cargar el artículo: { $error }
+
+# Etiquetas de un artículo
+tags-title = Etiquetas de '{ $item }'
+tags-columns = Etiqueta / añadida / estado
+tags-associate = Asociar nueva etiqueta...
+tags-deactivate = Desactivar
+tags-replace-confirm = ¿La etiqueta { $tag_id } sustituye a una perdida o dañada?
+tags-add-it = No, añadirla
+tags-replace-it = Sí
+tags-why-removed = ¿Por qué se quita la etiqueta { $tag_id }?
+tags-lost = perdida
+tags-deactivate-confirm = ¿Desactivar la etiqueta { $tag_id }? Ya no encontrará '{ $item }'. ¿Por qué (perdida, dañada)?
+tags-new-tag = Lea o escriba la nueva etiqueta de '{ $item }':
+tags-active = activa
+tags-active-own = activa, etiqueta propia del artículo
+tags-deactivated = desactivada { $time }
+tags-deactivated-note = desactivada { $time } ({ $note })
+log-tags-added = Etiqueta { $tag_id } añadida a '{ $item }'
+log-tags-deactivated = Etiqueta { $tag_id } desactivada
+err-tags-load = Error al cargar las etiquetas: { $error }
+err-tags-deactivate = Error al desactivar la etiqueta: { $error }
+err-tags-select-replaced = Seleccione en la lista la etiqueta que sustituye y vuelva a asociar
+err-tags-select = Seleccione la etiqueta que desactivar
+err-tags-taken = La etiqueta { $tag_id } ya está en un artículo
+err-tags-already-deactivated = Esa etiqueta ya está desactivada
+err-tags-inactive = La etiqueta ya no está activa
+err-tags-hex = Un ID de etiqueta se escribe en hexadecimal, como 04A1B2C3
+
+# Papelera
+trash-title = Papelera
+trash-help = Los artículos borrados se quedan aquí hasta vaciar la papelera.
+trash-help-days = Los artículos borrados se quedan aquí { $days } días, después se borran definitivamente.
+trash-delete = Borrar definitivamente
+trash-empty = Vaciar papelera
+trash-delete-confirm = ¿Borrar '{ $item }' definitivamente? No se puede deshacer.
+trash-empty-confirm = ¿Borrar definitivamente los { $count } artículos de la papelera? No se puede deshacer.
+log-trash-restored = Artículo restaurado: { $item }
+log-trash-deleted = Borrado definitivamente: { $item }
+log-trash-emptied = Papelera vaciada, { $count } artículos borrados definitivamente
+err-trash-load = Error al cargar la papelera: { $error }
+err-trash-restore = Error al restaurar el artículo: { $error }
+err-trash-delete = Error al borrar el artículo: { $error }
+err-trash-empty = Error al vaciar la papelera: { $error }
+err-trash-select-restore = No hay ningún artículo seleccionado para restaurar
+err-trash-select-delete = No hay ningún artículo seleccionado para borrar
+err-trash-tag-taken = La etiqueta { $tag_id } la usa ahora '{ $item }', borre o cambie antes ese artículo
+err-trash-gone = El artículo ya no está en la papelera
+
+# Volcados de tarjeta de un artículo
+dumps-title = Volcados de tarjeta de '{ $item }'
+dumps-columns = Adjuntado / UID / bloques / archivo
+dumps-attach = Adjuntar volcado...
+dumps-view = Ver
+dumps-save = Guardar como...
+dumps-delete-confirm = ¿Borrar el volcado de { $uid } adjuntado { $time }?
+dumps-view-title = Volcado { $uid } ({ $time })
+dumps-view-columns = Sec Blq  Datos
+title-dumps-attach = Adjuntar volcado de tarjeta
+title-dumps-save = Guardar volcado de tarjeta
+log-dumps-attached = Volcado de { $uid } ({ $blocks } bloques) adjuntado a '{ $item }'
+log-dumps-saved = Volcado de { $uid } guardado en { $path }
+log-dumps-deleted = Volcado de { $uid } borrado de '{ $item }'
+err-dumps-load = Error al cargar los volcados: { $error }
+err-dumps-read = Error al leer { $path }: { $error }
+err-dumps-attach = Error al adjuntar el volcado: { $error }
+err-dumps-save = Error al guardar el volcado: { $error }
+err-dumps-delete = Error al borrar el volcado: { $error }
+err-dumps-select = Seleccione primero un volcado de la lista
+err-dump-not-flipper = { $file } no es un archivo .nfc de Flipper
+err-dump-too-big = { $file } tiene { $bytes } bytes, más que una Classic 4K
+err-dump-not-raw = { $file } no es un volcado en bruto ({ $bytes } bytes, se esperaba un múltiplo de 16)
+err-dump-uid = { $file } tiene una línea UID incorrecta
+err-dump-block = { $file } tiene una línea incorrecta para el bloque { $block }
+err-dump-too-many-blocks = { $file } tiene más bloques que una Classic 4K
+err-dump-no-blocks = { $file } no tiene datos de bloques, solo se pueden adjuntar volcados de Mifare Classic
+
+# Visor de la base de datos
+dbv-title = Visor de la base de datos
+dbv-heading = Base de datos del inventario
+dbv-col-quantity = Cantidad
+dbv-col-location = Ubicación
+dbv-col-created = Creado
+dbv-col-updated = Actualizado
+dbv-count = { $count } artículos en la base de datos
+dbv-export = Exportar CSV
+dbv-refresh = Actualizar
+dbv-trash-confirm = ¿Mover a la papelera el artículo con el ID de etiqueta '{ $tag_id }'?
+
+# Fechas de caducidad
+expiry-title = Artículos que caducan
+expiry-within = Caducan en (días):
+expiry-expired = caducó hace { $days } días
+expiry-today = caduca hoy
+expiry-days-left = en { $days } días
+msg-nothing-expiring = Ningún artículo caduca en { $days } días.
+title-expiry-save = Guardar los artículos que caducan
+msg-expiry-saved = Artículos que caducan guardados en { $path }
+err-expiry-report = Error en el informe de caducidad: { $error }
+
+# Unidades con número de serie
+unit-in-stock = En existencias
+unit-issued = Entregada
+unit-retired = Retirada
+unit-scanned = { $product } { $serial }: { $status }
+err-unit-status = { $product } { $serial }: { $status }, esta lectura no puede mover la unidad
+unit-title = Productos y unidades
+unit-products = Productos (en existencias / entregadas / retiradas)
+unit-units = Unidades
+unit-add-product = Añadir producto
+unit-rename = Renombrar
+unit-add-unit = Añadir unidad
+unit-return = Devolver
+unit-issue = Entregar
+unit-retire = Retirar
+unit-product-name = Nombre del producto:
+unit-category = Categoría (opcional):
+unit-tag-id = ID de etiqueta de la unidad:
+unit-serial = Número de serie (opcional):
+unit-delete-product-confirm = ¿Borrar el producto '{ $product }'?
+unit-remove-confirm = ¿Eliminar la unidad { $tag_id }? Retire las unidades que ya no están para conservar su historial.
+log-unit-product-added = Producto añadido: { $product }
+log-unit-product-deleted = Producto borrado: { $product }
+log-unit-added = Unidad { $tag_id } de { $product } añadida
+log-unit-status = Unidad { $tag_id }: { $status }
+log-unit-removed = Unidad { $tag_id } eliminada
+err-unit-load-units = Error al cargar las unidades: { $error }
+err-unit-load-products = Error al cargar los productos: { $error }
+err-unit-add-product = Error al añadir el producto: { $error }
+err-unit-rename-product = Error al renombrar el producto: { $error }
+err-unit-delete-product = Error al borrar el producto: { $error }
+err-unit-product-in-use = El producto todavía tiene unidades, elimínelas o retírelas
+err-unit-add = Error al añadir la unidad: { $error }
+err-unit-update = Error al actualizar la unidad: { $error }
+err-unit-remove = Error al eliminar la unidad: { $error }
+err-unit-no-product = Seleccione o añada primero un producto
+err-unit-select-product = No hay ningún producto seleccionado
+err-unit-select = No hay ninguna unidad seleccionada
+
+# Control de presencia
+presence-removed = Retirado
+presence-returned = Devuelto
+presence-event = { $name } ({ $tag_id }): { $change }
+presence-title = Control de presencia
+presence-absent = Retirado tras (s):
+presence-start = Iniciar
+presence-stop = Detener
+presence-help = Las etiquetas se leen mientras la captura está en marcha en la pestaña del lector. El lector
+    tiene que seguir informando de las etiquetas de su campo, un artículo no informado durante el tiempo fijado se da por retirado.
+presence-columns = Etiqueta / artículo / estado
+presence-events = Retiradas y devoluciones
+presence-all-items = Todos los artículos
+presence-every-location = todas las ubicaciones
+presence-in-place = en su sitio
+presence-gone = retirado, falta desde hace { $secs }s
+log-presence-started = Controlando la presencia de { $count } artículos en { $location }
+log-presence-stopped = Control de presencia detenido
+err-presence-load = Error al cargar los eventos de presencia: { $error }
+err-presence-record = Error al registrar el evento de presencia: { $error }
+err-presence-absent = El tiempo para dar un artículo por retirado debe ser de al menos un segundo
+
+# Etiquetas desconocidas
+triage-title = Etiquetas desconocidas
+triage-columns = Etiqueta / lecturas / primera vez / última vez
+triage-show-ignored = Mostrar ignoradas
+triage-item-name = Nombre del artículo:
+triage-item-name-tip = Si se deja vacío, cada artículo se llama como su etiqueta
+triage-quantity = Cantidad:
+triage-create = Crear artículos
+triage-existing-item = Artículo existente:
+triage-assign = Asignar al artículo
+triage-ignore = Ignorar
+triage-unignore = Volver a listar
+triage-ignored = ignorada
+triage-assign-confirm = ¿Dar la etiqueta { $tag_id } a '{ $item }'? Sus otras etiquetas siguen funcionando.
+log-triage-created = { $count } artículos añadidos desde etiquetas desconocidas
+log-triage-assigned = La etiqueta { $tag_id } ahora también encuentra '{ $item }'
+log-triage-ignored = Ignorando { $count } etiquetas desconocidas
+log-triage-unignored = Volviendo a listar { $count } etiquetas desconocidas
+err-triage-load = Error al cargar las etiquetas desconocidas: { $error }
+err-triage-create = Error al añadir los artículos, no se añadió ninguno: { $error }
+err-triage-update = Error al actualizar las etiquetas: { $error }
+err-triage-select-create = Seleccione las etiquetas para las que crear artículos
+err-triage-select-assign = Seleccione una etiqueta para dar al artículo
+err-triage-quantity = La cantidad debe ser un número entero, 0 o más
+err-triage-tag-taken = La etiqueta { $tag_id } ya está en un artículo, o el artículo ya no existe
+
+# Kits
+kit-booked = { $kit }: { $items } artículos registrados ({ $mode })
+kit-buildable = Las existencias alcanzan para { $count } kits { $kit }
+kit-short = { $kit } no se registró, faltan { $count } artículos:
+kit-short-line = { $item }: se necesitan { $needed }, hay { $available }
+kit-missing-line = { $tag_id }: ya no está en el inventario
+kit-title = Kits
+kit-per-kit-tip = Cuántas unidades del artículo lleva un kit
+kit-add-item = Añadir artículo
+kit-component-columns = Artículo / etiqueta / por kit / en existencias
+kit-new = Nuevo kit
+kit-delete = Borrar kit
+kit-remove-item = Quitar artículo
+kit-check-stock = Comprobar existencias
+kit-tag-id = ID de etiqueta del kit:
+kit-name = Nombre del kit:
+kit-delete-confirm = ¿Borrar el kit '{ $kit }'? Sus artículos se quedan en el inventario.
+kit-check-count = ¿Para cuántos kits '{ $kit }' comprobar?
+kit-enough-stock = Hay existencias suficientes para { $count } kits '{ $kit }', alcanzan para { $buildable } en total.
+log-kit-added = Kit añadido: { $kit }
+log-kit-deleted = Kit borrado: { $kit }
+err-kit-load = Error al cargar los kits: { $error }
+err-kit-add = Error al añadir el kit: { $error }
+err-kit-delete = Error al borrar el kit: { $error }
+err-kit-save = Error al guardar el kit: { $error }
+err-kit-check = Error al comprobar las existencias: { $error }
+err-kit-select = No hay ningún kit seleccionado
+err-kit-no-kit = Seleccione o añada primero un kit
+err-kit-quantity = La cantidad por kit debe ser un número entero mayor que 0
+err-kit-count = El número de kits debe ser un número entero mayor que 0
+
+# Órdenes de compra
+po-received = Orden { $po }: recibidos { $quantity }, { $received } de { $ordered } hasta ahora
+po-not-on-order = { $tag_id } no está en la orden { $po }, las existencias se actualizaron sin ella
+po-complete = La orden { $po } se recibió completa y está cerrada
+po-title = Proveedores y órdenes de compra
+po-suppliers = Proveedores
+po-orders = Órdenes
+po-ordered-tip = Cuántas unidades del artículo se pidieron
+po-add-line = Añadir línea
+po-line-columns = Artículo / etiqueta / recibidos / pedidos
+po-link-item = Vincular artículo
+po-new-order = Nueva orden
+po-close-reopen = Cerrar/Reabrir
+po-receive-into = Recibir en
+po-stop-receiving = Dejar de recibir
+po-remove-line = Quitar línea
+po-receiving = Recibiendo en la orden { $po }
+po-not-receiving = Las lecturas no se reciben en ninguna orden
+po-supplier-name = Nombre del proveedor:
+po-contact = Persona de contacto (opcional):
+po-email = Correo electrónico (opcional):
+po-phone = Teléfono (opcional):
+po-order-number = Número de orden:
+po-delete-supplier-confirm = ¿Borrar el proveedor '{ $supplier }'? Sus artículos se quedan en el inventario.
+po-status-open = abierta
+po-status-closed = cerrada
+log-po-supplier-added = Proveedor añadido: { $supplier }
+log-po-supplier-deleted = Proveedor borrado: { $supplier }
+log-po-item-linked = { $item } se compra a { $supplier }
+log-po-order-added = Orden { $po } añadida con { $supplier }
+log-po-receiving = Recibiendo las lecturas en la orden { $po }
+err-po-load-orders = Error al cargar las órdenes: { $error }
+err-po-load-suppliers = Error al cargar los proveedores: { $error }
+err-po-add-supplier = Error al añadir el proveedor: { $error }
+err-po-link-item = Error al vincular el artículo: { $error }
+err-po-delete-supplier = Error al borrar el proveedor: { $error }
+err-po-supplier-in-use = El proveedor todavía tiene órdenes de compra
+err-po-add-order = Error al añadir la orden: { $error }
+err-po-update-order = Error al actualizar la orden: { $error }
+err-po-save-order = Error al guardar la orden: { $error }
+err-po-no-supplier = Seleccione o añada primero un proveedor
+err-po-select-supplier = No hay ningún proveedor seleccionado
+err-po-no-order = Seleccione o añada primero una orden
+err-po-select-order = No hay ninguna orden seleccionada
+err-po-select-line = No hay ninguna línea seleccionada
+err-po-closed = La orden está cerrada, reábrala para recibir en ella
+err-po-quantity = La cantidad pedida debe ser un número entero mayor que 0
+
+# Integraciones con ERP
+prefs-tab-integrations = Integraciones
+integration-edit = Editar
+integration-retry = Reintentar ya
+integration-err-select = Seleccione primero una integración
+integration-remove-confirm = ¿Eliminar la integración "{ $name }"? Los cambios que aún no envió no se enviarán.
+integration-off = desactivada
+integration-pending = { $pending } en espera
+integration-failing = { $pending } en espera, el último intento falló: { $error }
+integration-title = Integración
+integration-preset = Partir de:
+integration-enabled = Enviar cambios
+integration-name = Nombre:
+integration-url = URL:
+integration-headers = Cabeceras:
+integration-headers-tip = Un "Nombre: valor" por línea. ${NOMBRE} se lee del entorno al enviar.
+integration-events = Eventos:
+integration-events-tip = Sin ninguno marcado se envían todos los eventos
+integration-body = Cuerpo:
+integration-fields = Campos: { $fields }. En el cuerpo son valores JSON, el texto se pone entre comillas solo.
+integration-test-with = Probar con:
+integration-test = Probar asignación
+integration-send-test = Enviar prueba
+integration-test-skipped = Esta integración no envía eventos "{ $event }", uno se enviaría así:
+integration-test-sent = Enviado, el servidor lo aceptó.
+event-item-saved = Artículo guardado
+event-stock-received = Existencias recibidas
+event-stock-issued = Existencias entregadas
+event-stock-counted = Existencias contadas
+event-stock-adjusted = Cantidad cambiada
+event-item-deleted = Artículo eliminado
+err-integration-name = La integración necesita un nombre
+err-integration-name-taken = Ya hay otra integración llamada "{ $name }"
+err-integration-url = La URL debe empezar por http:// o https://
+err-integration-header = "{ $header }" no es una cabecera "Nombre: valor"
+err-integration-json = El cuerpo no es JSON una vez rellenados los campos: { $error }
+err-integration-braces = Un campo abierto con {{ nunca se cierra con }}
+err-integration-field = "{ $field }" no es un campo que conozcan las plantillas
+err-integration-send = El envío falló: { $error }
+err-integration-failed = Integración { $name }: { $error }
+
+# Firmas
+title-verify-signature = Elija el archivo firmado que desea verificar
+msg-signature-own = { $path } está firmado por esta estación y no ha cambiado desde entonces.
+msg-signature-other = { $path } no ha cambiado desde que se firmó, con la clave:
+    { $key }
+    Compárela con la clave que le dio el remitente.
+err-signature-invalid = No se puede confiar en { $path }: { $error }
+msg-signing-key = Clave pública de esta estación, entréguela a quien compruebe sus exportaciones:
+    { $key }
+err-signing-key-load = Error al cargar la clave de firma: { $error }
+err-signing-key = { $path } no contiene una clave de firma
+err-signature-read = Error al leer la firma { $path }: { $error }
+err-signature-format = { $path } no es una firma
+err-signature-mismatch = el archivo se modificó después de firmarse
+err-signature-other-key = El archivo está intacto pero lo firmó otra clave: { $key }
+msg-signature-valid = Firma válida de la clave { $key }
+usage-verify = Uso: mifare_reader_utility --verify ARCHIVO [--key CLAVE_PÚBLICA]
+usage-import = Uso: mifare_reader_utility --import ARCHIVO.json
+usage-commands = Uso: mifare_reader_utility --verify ARCHIVO [--key CLAVE_PÚBLICA] | --import ARCHIVO.json | --sync
+    Esta versión no tiene ventana, se compiló sin la función gui.
+err-sync-not-built = La sincronización de estaciones no forma parte de esta versión, se compiló sin la función sync.
+
+# Salida de consola
+log-plugins-loaded = Complementos cargados: { $plugins }
+err-plugin-relative = El directorio de complementos { $path } no es una ruta absoluta, no se cargó ningún complemento
+err-plugin-owner = No se cargan complementos de { $path }, pertenece a otro usuario
+err-plugin-writable = No se cargan complementos de { $path }, otros usuarios pueden modificarlo
+err-plugin-load = Error al cargar el complemento { $path }: { $error }
+err-plugin-failed = El complemento { $plugin } falló: { $error }
+log-config-overrides = Ajustes sustituidos desde el entorno: { $settings }
+err-history-open = Error al abrir el historial de lecturas: { $error }
+err-history-save = Error al guardar una lectura en el historial: { $error }
+log-import-watching = Vigilando { $dir } en busca de archivos para importar
+err-import-watch = Error al vigilar { $dir } en busca de archivos para importar: { $error }
+log-import-file = Importados { $count } artículos de { $file }
+err-import-file = { $file } no se importó: { $error }
+err-import-file-report = { $file } no se importó: { $error } (informe: { $report })
+log-inventory-ready = Base de datos de inventario inicializada
+err-inventory-init = Error al inicializar la base de datos de inventario: { $error }
+log-adding-inventory = Añadiendo la pestaña de inventario
+log-setting-tab = Seleccionando la pestaña activa
+log-window-shown = Ventana principal mostrada
+log-shutting-down = Cerrando
+err-db-flush = Error al volcar { $path }: { $error }
+log-trash-purged = Eliminados { $count } artículos que llevaban más de { $days } días en la papelera
+err-trash-purge = Error al vaciar la papelera: { $error }
+log-created-dir = Carpeta creada: { $dir }
+err-create-dir = Error al crear la carpeta { $dir }: { $error }
+log-signing-key-created = Creada la clave de firma { $path }, guarde una copia
+log-export-signed = Exportación firmada: { $path }
+err-sign-export = Error al firmar { $path }: { $error }
@@ -0,0 +1,903 @@
+# Italiano
+
+app-title = Utilità Lettore Mifare
+about-text = Utilità Lettore Mifare v0.2.0
+
+    Uno strumento per leggere e analizzare gli UID delle carte Mifare
+    Sviluppato con Rust e FLTK
+
+    Ora con la gestione dell'inventario!
+
+# Menu, '&' indica la lettera della scorciatoia da tastiera
+menu-file = &File
+menu-export = &Esporta dati
+menu-export-csv = come &CSV
+menu-export-json = come &JSON
+menu-export-text = come &Testo
+menu-import = &Importa dati
+menu-view-database = &Visualizza database
+menu-check-files = &Controlla file da importare
+menu-restore-backup = &Ripristina da backup...
+menu-expiry-report = Articoli in sca&denza...
+menu-gdrive = &Google Drive
+menu-gdrive-export = Esporta database
+menu-gdrive-import = Importa database
+menu-gdrive-sync = Sincronizza postazioni
+menu-signatures = Firm&e
+menu-sign-exports = &Firma le esportazioni
+menu-verify-signature = &Verifica firma...
+menu-signing-key = Mostra &chiave di firma
+menu-save-log = &Salva registro
+menu-exit = E&sci
+menu-edit = &Modifica
+menu-preferences = &Preferenze
+menu-keyboard = Layout di &tastiera
+menu-kb-auto = Rilevamento &automatico
+menu-kb-windows = &Windows
+menu-kb-mac-us = &Mac USA
+menu-kb-mac-intl = Mac &Internazionale
+menu-kb-azerty = AZERTY &francese
+menu-kb-qwertz = QWERTZ &tedesco
+menu-kb-calibrate = &Calibra...
+menu-reader-config = Configurazione &lettore...
+menu-language = &Lingua
+menu-profiles = Pro&fili
+menu-profile-save = &Salva impostazioni come profilo...
+menu-profile-delete = &Elimina il profilo attivo
+menu-help = &Aiuto
+menu-about = &Informazioni
+
+# Schede
+tab-reader = Modalità lettore
+tab-conversion = Conversione UID
+tab-batch = Conversione in blocco
+tab-history = Cronologia
+tab-inventory = Inventario
+
+# Pulsanti
+btn-ok = OK
+btn-cancel = Annulla
+btn-add = Aggiungi
+btn-update = Aggiorna
+btn-remove = Rimuovi
+btn-save = Salva
+btn-clear = Pulisci
+btn-close = Chiudi
+btn-restore = Ripristina
+btn-delete = Elimina
+
+# Menu File
+title-export-csv = Esporta come CSV
+title-export-json = Esporta come JSON
+title-export-text = Esporta come testo
+title-import = Importa dati
+msg-import-summary = Importati { $items } articoli da { $imported } file, { $failed } file non riusciti.
+msg-no-import-files = Nessun file da importare.
+msg-gdrive-exported = Database esportato nella cartella di sincronizzazione di Google Drive:
+    { $path }
+msg-gdrive-imported = Importati { $count } articoli da Google Drive
+msg-stations-synced = Sincronizzato con { $stations } altre postazioni.
+    Condivise { $shared } operazioni locali, ricevute { $received }.
+    { $changed } articoli modificati.
+msg-json-imported = Importati { $count } articoli da JSON.
+err-save-log = Errore nel salvataggio del registro: { $error }
+err-export = Errore nell'esportazione: { $error }
+err-import-files = Errore nell'elaborazione dei file da importare: { $error }
+err-gdrive-disabled = La sincronizzazione con Google Drive non è attiva. Attivala nelle Preferenze.
+err-gdrive-export = Errore nell'esportazione nella cartella di Google Drive: { $error }
+err-gdrive-import = Errore nell'importazione da Google Drive: { $error }
+err-gdrive-folder = Errore nella creazione della cartella di Google Drive: { $error }
+err-station-sync = Errore nella sincronizzazione delle postazioni: { $error }
+err-file-missing = Il file non esiste: { $path }
+err-json-import = Errore nell'importazione dei dati JSON: { $error }
+err-csv-import = L'importazione CSV non è ancora disponibile.
+err-read-file = Errore nella lettura del file: { $error }
+err-read-file-path = Errore nel leggere { $path }: { $error }
+err-save-config = Errore nel salvataggio della configurazione: { $error }
+err-save-file = Errore nel salvataggio di { $path }: { $error }
+err-write-file = Errore nello scrivere il file: { $error }
+err-export-tags = Errore nell'esportare i tag degli articoli: { $error }
+export-format = Seleziona il formato di esportazione:
+import-format = Seleziona il formato di importazione:
+log-exported = Esportato in { $path }
+log-exported-tags = Tag degli articoli esportati in { $path }
+msg-exported = Dati esportati in { $path }
+
+# Preferenze
+prefs-title = Preferenze
+prefs-tab-general = Generale
+prefs-save-logs = Salva i registri su file
+prefs-log-dir = Cartella registri:
+prefs-keyboard-layout = Layout di tastiera:
+prefs-edit-patterns = Modifica modelli di formato...
+prefs-scan-cooldown = Pausa tra letture:
+prefs-scan-cooldown-tip = Secondi in cui una nuova lettura dello stesso tag viene ignorata, 0 per contarle tutte
+prefs-seconds-off = secondi (0 = disattivata)
+prefs-language = Lingua:
+prefs-language-auto = Predefinita di sistema
+prefs-theme = Tema:
+prefs-reset = Ripristina predefiniti
+prefs-reset-confirm = Riportare tutte le impostazioni ai valori predefiniti? Vengono rimossi anche i modelli di formato, i produttori, le carte di comando e le regole di lettura aggiunti.
+theme-light = Chiaro
+theme-dark = Scuro
+theme-high-contrast = Contrasto elevato
+prefs-tab-gdrive = Google Drive
+prefs-gdrive-enable = Attiva la sincronizzazione con Google Drive
+prefs-gdrive-folder = Cartella sincronizzata:
+prefs-gdrive-choose = Scegli la cartella di sincronizzazione di Google Drive
+prefs-gdrive-help = Come usare la sincronizzazione con Google Drive:
+
+    1. Installa Google Drive per desktop
+    2. Scegli una cartella all'interno del tuo Google Drive
+    3. Attiva la sincronizzazione qui sopra e indica la cartella
+    4. Usa le voci Esporta/Importa del menu per sincronizzare il database
+prefs-tab-lf = Lettore 125 kHz
+prefs-lf-enable = Leggi i tag EM4100 dal modulo RDM6300
+prefs-lf-port = Porta seriale:
+prefs-lf-help = Il modulo RDM6300 invia gli ID dei tag a 9600 baud.
+    Collega il suo pin TX al pin RX del Pi (GPIO15)
+    e attiva la porta seriale in raspi-config.
+
+    I tag a 125 kHz compaiono nella finestra di acquisizione
+    insieme alle carte a 13,56 MHz.
+prefs-tab-feedback = Segnalazione lettura
+prefs-feedback-enable = Suona e accendi il LED a ogni lettura
+prefs-feedback-buzzer = GPIO cicalino:
+prefs-feedback-red = GPIO LED rosso:
+prefs-feedback-green = GPIO LED verde:
+prefs-feedback-help = 1 bip, verde: tag trovato
+    2 bip, ambra: tag non in inventario
+    Bip lungo, rosso: lettura illeggibile o errore
+prefs-tab-manufacturers = Produttori
+prefs-manuf-code = Codice:
+prefs-manuf-name = Nome:
+err-manuf-name = Inserisci il nome del produttore
+err-manuf-add = Errore nell'aggiunta del produttore: { $error }
+prefs-tab-command-cards = Carte comando
+prefs-card-tag = ID tag:
+prefs-card-help = Leggere una carta comando nella finestra di acquisizione esegue la sua azione, come i tasti funzione indicati.
+err-card-tag = Inserisci l'ID del tag in esadecimale
+err-card-select = Seleziona la carta comando da rimuovere
+prefs-tab-rules = Regole di lettura
+prefs-rule-name = Nome:
+prefs-rule-when = Quando:
+prefs-rule-do = Esegui:
+prefs-rule-when-tip = Espressione per l'ID del tag, il produttore, la categoria o la posizione da confrontare, oppure un numero di giorni
+prefs-rule-do-tip = Quantità, posizione, URL del webhook o comando di shell. I comandi ricevono TAG_ID, MANUFACTURER e ITEM_NAME.
+rule-line = { $name }: quando { $condition }, { $action }
+rule-any-tag = Qualsiasi tag
+rule-tag-matches = L'ID del tag corrisponde a
+rule-manufacturer = Il produttore contiene
+rule-category = La categoria è
+rule-location = La posizione è
+rule-known-tag = Il tag è in inventario
+rule-unknown-tag = Il tag non è in inventario
+rule-expires-within = L'articolo scade entro (giorni)
+rule-expires-within-days = L'articolo scade entro { $days } giorni
+rule-increment = Aumenta quantità
+rule-decrement = Diminuisci quantità
+rule-set-location = Imposta posizione
+rule-open-form = Apri modulo articolo
+rule-webhook = Chiama webhook
+rule-shell = Esegui comando di shell
+err-rule-name = Inserisci un nome per la regola
+err-rule-select = Seleziona prima una regola
+err-rule-value = Questa condizione o azione richiede un valore
+err-rule-amount = La quantità deve essere un numero intero maggiore di 0
+err-rule-days = I giorni devono essere un numero intero, 0 o più
+err-rule-unknown-tag = il tag non è in inventario
+err-rule-exit = Webhook o comando di una regola di lettura non riuscito: { $status }
+
+# Calibrazione del layout di tastiera
+cal-title = Calibra layout di tastiera
+cal-steps = 1. Inserisci l'UID di una carta di riferimento, ad es. quello stampato sopra o letto su un altro computer.
+    2. Fai clic nel campo di lettura e passa la stessa carta sul lettore.
+    3. Fai clic su Calibra per trovare il layout che riporta la lettura a quell'UID.
+cal-reference = UID di riferimento:
+cal-scan = Leggi qui:
+cal-calibrate = Calibra
+cal-err-reference = Inserisci prima l'UID della carta di riferimento
+cal-err-scan = Passa prima la carta di riferimento nel campo di lettura
+cal-no-match = Nessun layout corrisponde all'UID di riferimento.
+    { $attempts }
+cal-locked = Impostato: { $layout }
+cal-also-matches = Corrispondono anche: { $layouts }
+
+# Editor dei modelli di formato
+pat-title = Modelli di formato
+pat-name = Nome:
+pat-regex = Espressione:
+pat-description = Descrizione:
+pat-help = Usa gruppi con nome come (?P<site>\d{3}) e richiamali come {site} nella descrizione.
+    I modelli sono provati in ordine, la prima corrispondenza compare nel campo Formato.
+pat-test-input = Testo di prova:
+pat-enter-regex = Inserisci un'espressione regolare
+pat-err-select = Seleziona un modello da aggiornare
+pat-err-save = Errore nel salvataggio dei modelli: { $error }
+pat-err-empty = L'espressione regolare non può essere vuota
+pat-err-invalid = Espressione regolare non valida: { $error }
+
+# Configurazione del lettore a emulazione tastiera
+rc-title = Configurazione lettore
+rc-reader = Lettore:
+rc-rescan = Cerca
+rc-send = Invia comando
+rc-send-button = Invia
+rc-add-heading = Aggiungi un comando al profilo di questo lettore:
+rc-model = Modello:
+rc-command-name = Nome comando:
+rc-reports = Report (hex):
+rc-report-type = Tipo di report:
+rc-feature = Feature
+rc-output = Output
+rc-reports-help = Separa i report con ';', il primo byte è l'ID del report
+rc-test = Prova
+rc-no-readers = Nessun lettore HID trovato, collega il lettore e fai clic su Cerca
+rc-profile = Profilo: { $model } ({ $count } comandi)
+rc-no-profile = Nessun profilo per { $usb_id }, aggiungi i suoi comandi qui sotto
+rc-err-select = Seleziona prima un comando
+rc-err-name = Inserisci un nome per il comando
+rc-err-reports = Inserisci almeno un report in esadecimale
+rc-err-hex = { $error } nel comando '{ $command }'
+rc-err-parse = Errore nell'interpretare { $path }: { $error }
+rc-err-open = Errore nell'aprire { $path }: { $error }
+rc-err-write = Errore nello scrivere il report { $report } su { $path }: { $error }
+rc-err-feature = Errore nell'inviare il feature report { $report } a { $path }: { $error }
+rc-confirm-send = Inviare '{ $command }' a { $device } ({ $path })?
+rc-sent = '{ $command }' inviato a { $path }
+rc-err-send = { $error }
+
+    Per leggere e scrivere i dispositivi hidraw di solito serve una regola udev o l'utente root.
+
+# Carte comando e inventari fisici
+cc-toggle-inventory = Attiva/disattiva modalità inventario
+cc-toggle-form = Attiva/disattiva modulo articolo
+cc-start-stocktake = Avvia inventario fisico
+cc-finish-stocktake = Concludi inventario fisico
+cc-export-csv = Esporta letture come CSV
+cc-export-json = Esporta letture come JSON
+cc-receive-mode = Modalità lettura: carico
+cc-issue-mode = Modalità lettura: scarico
+cc-count-mode = Modalità lettura: conteggio
+cc-on = attivo
+cc-off = disattivo
+cc-stocktake-started = conteggio delle letture
+cc-stocktake-running = Inventario in corso: { $count } tag contati
+cc-stocktake-finished = mancano { $missing } articoli
+cc-no-stocktake = nessun inventario in corso
+cc-exported = salvato in { $path }
+stocktake-summary = Inventario avviato il { $started }
+    { $scans } letture, { $found } articoli in inventario trovati, { $unknown } tag sconosciuti.
+    { $missing } articoli non sono stati letti:
+stocktake-more = ...e altri { $count }
+stocktake-unknown = Tag non presenti in inventario: { $tags }
+
+# Modalità di lettura della finestra di acquisizione
+capture-mode = Modalità lettura:
+mode-receive = Carico
+mode-issue = Scarico
+mode-count = Conteggio
+mode-count-prompt = Quantità contata di '{ $item }':
+err-count-quantity = La quantità contata deve essere un numero intero pari o superiore a 0
+err-issue-unknown = Il tag { $tag_id } non è in inventario, non c'è nulla da scaricare.
+triage-recorded = Tag sconosciuto { $tag_id } ({ $scans } letture), vedi Unknown Tags nella scheda inventario
+
+# Lista di revisione delle letture in coda
+review-queue = Metti in coda
+review-open = Rivedi ({ $count })
+review-title = Rivedi letture in coda
+review-line = { $mode } { $quantity } × { $name } ({ $tag_id }, { $scans } letture)
+review-new-tag = nuovo articolo
+review-quantity = Quantità...
+review-name = Nome...
+review-commit = Conferma
+review-discard = Scarta
+review-quantity-prompt = { $mode }: quanti del tag { $tag_id }?
+review-name-prompt = Nome del nuovo articolo per il tag { $tag_id }:
+review-known = Questo tag è già '{ $name }' in inventario.
+review-select = Seleziona prima una lettura nella lista
+review-unnamed = Il tag { $tag_id } non è in inventario, dagli un nome per aggiungerlo.
+review-negative = La quantità del tag { $tag_id } non può essere negativa.
+review-problems = { $problem } (altri { $count } da correggere)
+review-has-problems = Correggi prima le letture segnate, non è stato registrato nulla.
+review-discard-confirm = Scartare { $count } letture in coda? Non verrà registrato nulla.
+review-committed = { $count } letture in coda registrate
+err-review-commit = Errore nel registrare le letture in coda, non è stato registrato nulla: { $error }
+
+# Scheda del lettore e finestra di cattura
+reader-instructions = Istruzioni
+reader-welcome = Benvenuto in Mifare Reader Utility!
+
+    Avvicina le carte Mifare al lettore per catturarne gli UID. Gli UID vengono convertiti automaticamente in un formato leggibile.
+reader-clear = Cancella dati
+reader-card-data = Dati delle carte
+reader-clear-confirm = Vuoi davvero cancellare tutti i dati catturati?
+capture-start = Avvia cattura
+capture-stop = Ferma cattura
+capture-title = Cattura delle carte
+capture-present = Avvicina le carte al lettore
+    I dati delle carte compariranno qui:
+capture-waiting = In attesa di una carta...
+capture-processing = Elaborazione: { $card }
+capture-manual = Inserimento manuale:
+capture-submit = Invia
+capture-inventory = Aggiorna inventario
+capture-show-form = Mostra la scheda dell'articolo alla lettura
+capture-lf-failed = Lettore a 125 kHz su { $port }: { $error }
+capture-new-title = Nuovo articolo
+capture-new-heading = Aggiungi un articolo all'inventario
+capture-update-title = Aggiorna articolo
+capture-update-heading = Aggiorna un articolo dell'inventario
+capture-manufacturer = Produttore: { $manufacturer }
+capture-item-info = Articolo: { $item }
+    ID tag: { $tag_id }
+capture-current-quantity = Quantità attuale: { $quantity }
+capture-quantity = Quantità:
+scan-badge = Badge: { $badge }
+scan-repeat = Lettura ripetuta durante l'attesa, inventario non aggiornato
+msg-capture-quantity = Quantità di '{ $item }' aggiornata a { $quantity }
+msg-capture-updated = Articolo '{ $item }' aggiornato
+err-capture-fifo = Errore nel creare la FIFO: { $error }
+err-capture-lf = Errore nell'aprire il lettore a 125 kHz su { $port }: { $error }
+err-capture-po = Errore nell'aggiornare l'ordine di acquisto: { $error }
+err-capture-update = Errore nell'aggiornare l'articolo: { $error }
+err-inventory-missing = L'inventario non è inizializzato
+
+# Conversione degli UID
+conv-uid = UID della carta:
+conv-convert = Converti
+conv-results = Risultati della conversione:
+conv-hex = Esadecimale:
+conv-decimal = Decimale:
+conv-manufacturer = Produttore:
+conv-format = Descrizione del formato:
+conv-badge = Badge (FC / n. carta):
+conv-keyboard-note = Nota sulla codifica della tastiera: se vedi caratteri speciali al posto dei numeri,
+    questa utility li converte automaticamente nel formato corretto in base al layout di tastiera scelto.
+
+    Spiegazione dei codici di formato:
+    'e' = tastiera QWERTY, 'f' = tastiera AZERTY, 'h' = tastiera QUERTY, 'r' = formato proprio del lettore.
+
+# Conversione in blocco da registri di letture
+batch-file = Elabora file...
+batch-folder = Elabora cartella...
+batch-file-title = Scegli un registro di letture
+batch-folder-title = Scegli una cartella di registri di letture
+batch-add-inventory = Aggiungi i nuovi tag all'inventario
+batch-save-file = Salva i risultati in un file
+batch-save-title = Salva i risultati del blocco
+batch-starting = Avvio...
+batch-progress = { $done } di { $total }
+batch-progress-title = Conversione in blocco
+progress-eta = mancano circa { $eta }
+batch-cancelling = Annullamento...
+batch-cancelled = Blocco annullato, non è stato salvato nulla
+batch-done-text = { $scans } UID convertiti
+batch-done = { $scans } letture lette da { $files } file
+batch-added = { $count } nuovi tag aggiunti all'inventario
+batch-saved = salvato in { $path }
+batch-more = ...e altri { $count }
+batch-export-unique = Esporta unici...
+batch-export-unique-title = Esporta UID unici
+batch-stats = Riepilogo
+    UID totali: { $total }
+    UID unici: { $unique }
+    Duplicati raggruppati: { $duplicates }
+    Righe non valide: { $invalid }
+batch-stats-manufacturers = UID unici per produttore:
+batch-unique-hint = Esporta unici salva i { $unique } UID unici senza le loro ripetizioni.
+batch-item-name = Tag { $tag_id }
+batch-instructions = Incolla più UID qui sotto, uno per riga. L'applicazione li converte tutti in una volta.
+batch-clear-input = Cancella input
+batch-clear-input-confirm = Cancellare i dati di input?
+batch-convert = Converti tutto
+batch-clear-results = Cancella risultati
+err-batch = Errore nell'elaborazione dei registri di letture: { $error }
+err-batch-file = Impossibile leggere { $error }
+
+# Cronologia delle letture
+history-from = Dal:
+history-to = Al:
+history-date-tooltip = Un giorno come AAAA-MM-GG, vuoto per nessun limite
+history-filter = Filtra
+history-today = Oggi
+history-all = Tutto
+history-export = Esporta...
+history-export-title = Esporta le letture elencate
+history-export-empty = Non ci sono letture da esportare in questo intervallo.
+history-count = { $count } letture
+history-more = ...e altre { $count } letture precedenti, incluse nell'esportazione
+history-col-time = Ora
+history-col-hex = UID esadecimale
+history-col-reader = Lettore
+history-col-manufacturer = Produttore
+history-col-raw = UID originale
+err-history-date = Data non valida: { $date } (usare AAAA-MM-GG)
+err-history-load = Errore nel caricamento della cronologia delle letture: { $error }
+
+# File delle impostazioni
+err-config-problems = Alcune impostazioni non erano utilizzabili e sono tornate ai valori predefiniti:
+    { $problems }
+err-config-read = Impossibile leggere { $path }: { $error }
+err-config-write = Impossibile scrivere { $path }: { $error }
+err-config-parse = { $path } non è un file di impostazioni valido, è stato copiato in { $backup } e si usano i valori predefiniti: { $error }
+err-config-override = { $variable } = "{ $value }" non è un valore per quell'impostazione, viene ignorato
+err-config-newer = Il file delle impostazioni è stato scritto da una versione più recente (schema { $value }), le impostazioni che questa versione non conosce vengono scartate al salvataggio
+err-config-layout = { $setting } = { $value } non è un layout di tastiera (da 0 a 5)
+err-config-language = { $setting } = "{ $value }" non è una lingua supportata
+err-config-cooldown = { $setting } = { $value } supera un'ora
+err-config-port = { $setting } non indica nessuna porta seriale
+err-config-scale = { $setting } = { $value } è fuori da 0,25 a 4
+err-config-pin = { $setting } = { $value } non è un pin GPIO (da 0 a 27), quell'uscita è disattivata
+err-config-pin-shared = { $setting } = { $value } è già usato da un'altra uscita, quell'uscita è disattivata
+err-config-pattern = Il modello di formato "{ $value }" non è un'espressione regolare valida ed è stato scartato
+err-config-card = La carta di comando "{ $value }" non è un ID tag esadecimale ed è stata scartata
+err-config-integration = L'integrazione "{ $value }" ha il nome di un'altra ed è stata scartata
+err-config-profile = Il profilo "{ $value }" non esiste, nessun profilo è attivo
+err-config-plugin-dir = { $setting } = "{ $value }" non è un percorso assoluto, i plugin vengono caricati dalla cartella predefinita
+
+# Profili di configurazione
+profile-name-prompt = Nome del profilo, vi vengono salvati le impostazioni del lettore, il layout di tastiera, le cartelle di sincronizzazione e le regole di lettura:
+profile-delete-confirm = Eliminare il profilo "{ $name }"? Le impostazioni attuali restano invariate.
+err-profile = Errore nel cambio di profilo: { $error }
+err-profile-name = Il profilo ha bisogno di un nome
+err-profile-missing = Quel profilo non esiste più
+err-profile-none = Nessun profilo è attivo
+
+# Rapporti di importazione
+import-error-row = Riga { $row }: { $reason }
+import-error-no-tag = l'ID del tag è vuoto
+import-errors = { $count } problemi, il primo: { $first }
+import-progress = Riga { $row } di { $total }
+import-report-title = Importazione di { $file } non riuscita il { $time }, non è stato salvato nulla.
+
+# Backup
+backup-daily = giornaliero
+backup-import = prima di un'importazione
+backup-restore = prima di un ripristino
+backup-entry = { $time }  ({ $reason })
+restore-title = Ripristina da backup
+restore-help = L'inventario e la cronologia delle letture tornano com'erano al momento del backup.
+restore-confirm = Ripristinare il backup del { $backup }?
+    Prima viene fatto un backup del database così com'è ora.
+msg-no-backups = Non ci sono ancora backup in { $dir }.
+msg-backup-restored = Backup del { $backup } ripristinato. Il database di prima del ripristino è in { $undo }
+log-backup-taken = Backup del database in { $path }
+err-backup = Errore durante il backup del database: { $error }
+err-backup-restore = Errore durante il ripristino del backup: { $error }
+err-backup-remove = Errore durante l'eliminazione del vecchio backup { $path }: { $error }
+err-backup-select = Seleziona un backup da ripristinare
+
+# Scheda inventario
+inv-search-label = Cerca:
+inv-search = Cerca
+inv-refresh = Aggiorna elenco
+inv-add = Aggiungi articolo
+inv-export = Esporta
+inv-purchasing = Acquisti
+inv-presence = Presenza
+inv-details = Dettagli articolo
+inv-save = Salva modifiche
+inv-delete = Elimina articolo
+inv-clear = Pulisci modulo
+inv-tags = Tag...
+inv-dumps = Dump...
+inv-log = Registro eventi
+inv-col-tag = ID tag
+inv-col-name = Nome
+inv-col-quantity = Qtà
+inv-col-category = Categoria
+inv-col-expires = Scade
+inv-stats = Articoli: { $items }
+    Quantità totale: { $quantity }
+    Categorie: { $categories }
+inv-new-tag = Inserisci l'ID tag del nuovo articolo:
+inv-trash-confirm = Spostare questo articolo nel cestino? Si può ripristinare da lì.
+inv-delete-confirm = Vuoi davvero eliminare questo articolo?
+form-name = Nome:
+form-category = Categoria:
+form-location = Posizione:
+form-expires = Scade:
+form-expires-tip = Data di scadenza come AAAA-MM-GG, vuota se l'articolo non scade
+form-description = Descrizione:
+form-no-tag = ID tag: nessuno selezionato
+form-tag = ID tag: { $tag_id }
+form-created = Creato: { $time }
+form-updated = Aggiornato: { $time }
+form-uncategorized = Senza categoria
+msg-inv-saved = Articolo salvato
+msg-inv-trashed = Articolo spostato nel cestino
+msg-inv-deleted = Articolo eliminato
+log-inv-loaded = Caricati i dettagli dell'articolo: { $item }
+log-inv-saved = Articolo salvato: { $item }
+log-inv-trashed = Articolo con tag { $tag_id } spostato nel cestino
+log-inv-deleted = Eliminato l'articolo con il tag: { $tag_id }
+log-inv-cleared = Modulo pulito
+log-inv-new = Pronto ad aggiungere un articolo con il tag: { $tag_id }
+log-inv-refreshed = Elenco dell'inventario aggiornato
+log-search-all = Visualizzazione di tutti gli articoli
+log-search-found = Trovati { $count } articoli corrispondenti a '{ $query }'
+scan-add-confirm = L'ID tag { $tag_id } non è nell'inventario. Vuoi aggiungere un nuovo articolo?
+scan-item-name = Inserisci il nome dell'articolo:
+msg-scan-updated = Tag letto: { $item }. Quantità aggiornata a { $quantity }.
+msg-scan-added = Nuovo articolo '{ $item }' aggiunto all'inventario.
+log-gdrive-auto-synced = Database sincronizzato automaticamente con Google Drive
+err-inv-save = Errore nel salvare l'articolo: { $error }
+err-inv-form = Errore di convalida del modulo: { $error }
+err-inv-select-save = Nessun articolo selezionato da salvare
+err-form-name = Il nome dell'articolo è obbligatorio.
+err-form-quantity = La quantità deve essere un numero valido.
+err-form-expiry = La data di scadenza deve essere una data come AAAA-MM-GG.
+err-scan-quantity = Errore nell'aggiornare la quantità: { $error }
+err-search = Errore nella ricerca: { $error }
+err-gdrive-auto-sync = Errore nella sincronizzazione automatica con Google Drive: { $error }
+
+# Finestre dell'inventario
+err-load-items = Errore nel caricare gli articoli: { $error }
+err-no-item-selected = Nessun articolo selezionato
+item-not-in-inventory = (non in inventario)
+err-tag-on-item = Il tag { $tag_id } è sull'articolo '{ $item }'
+err-tag-is-unit = Il tag { $tag_id } è già un'unità
+err-tag-on-kit = Il tag { $tag_id } è sul kit '{ $kit }'
+err-tag-check = Errore nel controllare il tag: { $error }
+err-tag-add = Errore nell'aggiungere il tag: { $error }
+err-select-item-first = Seleziona prima un articolo nell'elenco
+err-item-gone = L'articolo non è più nell'inventario
+err-load-item = Errore nel caricare l'articolo: { $error }
+
+# Tag di un articolo
+tags-title = Tag di '{ $item }'
+tags-columns = Tag / aggiunto / stato
+tags-associate = Associa nuovo tag...
+tags-deactivate = Disattiva
+tags-replace-confirm = Il tag { $tag_id } sostituisce un tag perso o danneggiato?
+tags-add-it = No, aggiungilo
+tags-replace-it = Sì
+tags-why-removed = Perché il tag { $tag_id } viene tolto?
+tags-lost = perso
+tags-deactivate-confirm = Disattivare il tag { $tag_id }? Non troverà più '{ $item }'. Perché (perso, danneggiato)?
+tags-new-tag = Leggi o scrivi il nuovo tag di '{ $item }':
+tags-active = attivo
+tags-active-own = attivo, tag proprio dell'articolo
+tags-deactivated = disattivato { $time }
+tags-deactivated-note = disattivato { $time } ({ $note })
+log-tags-added = Tag { $tag_id } aggiunto a '{ $item }'
+log-tags-deactivated = Tag { $tag_id } disattivato
+err-tags-load = Errore nel caricare i tag: { $error }
+err-tags-deactivate = Errore nel disattivare il tag: { $error }
+err-tags-select-replaced = Seleziona nell'elenco il tag che sostituisce, poi associa di nuovo
+err-tags-select = Seleziona il tag da disattivare
+err-tags-taken = Il tag { $tag_id } è già su un articolo
+err-tags-already-deactivated = Quel tag è già disattivato
+err-tags-inactive = Il tag non è più attivo
+err-tags-hex = Un ID tag si scrive in esadecimale, come 04A1B2C3
+
+# Cestino
+trash-title = Cestino
+trash-help = Gli articoli eliminati restano qui finché il cestino non viene svuotato.
+trash-help-days = Gli articoli eliminati restano qui per { $days } giorni, poi vengono eliminati definitivamente.
+trash-delete = Elimina definitivamente
+trash-empty = Svuota cestino
+trash-delete-confirm = Eliminare '{ $item }' definitivamente? Non si può annullare.
+trash-empty-confirm = Eliminare definitivamente tutti i { $count } articoli nel cestino? Non si può annullare.
+log-trash-restored = Articolo ripristinato: { $item }
+log-trash-deleted = Eliminato definitivamente: { $item }
+log-trash-emptied = Cestino svuotato, { $count } articoli eliminati definitivamente
+err-trash-load = Errore nel caricare il cestino: { $error }
+err-trash-restore = Errore nel ripristinare l'articolo: { $error }
+err-trash-delete = Errore nell'eliminare l'articolo: { $error }
+err-trash-empty = Errore nello svuotare il cestino: { $error }
+err-trash-select-restore = Nessun articolo selezionato da ripristinare
+err-trash-select-delete = Nessun articolo selezionato da eliminare
+err-trash-tag-taken = Il tag { $tag_id } ora è usato da '{ $item }', elimina o modifica prima quell'articolo
+err-trash-gone = L'articolo non è più nel cestino
+
+# Dump delle carte di un articolo
+dumps-title = Dump delle carte di '{ $item }'
+dumps-columns = Allegato / UID / blocchi / file
+dumps-attach = Allega dump...
+dumps-view = Visualizza
+dumps-save = Salva come...
+dumps-delete-confirm = Eliminare il dump di { $uid } allegato { $time }?
+dumps-view-title = Dump { $uid } ({ $time })
+dumps-view-columns = Set Blc  Dati
+title-dumps-attach = Allega dump della carta
+title-dumps-save = Salva dump della carta
+log-dumps-attached = Dump di { $uid } ({ $blocks } blocchi) allegato a '{ $item }'
+log-dumps-saved = Dump di { $uid } salvato in { $path }
+log-dumps-deleted = Dump di { $uid } eliminato da '{ $item }'
+err-dumps-load = Errore nel caricare i dump: { $error }
+err-dumps-read = Errore nel leggere { $path }: { $error }
+err-dumps-attach = Errore nell'allegare il dump: { $error }
+err-dumps-save = Errore nel salvare il dump: { $error }
+err-dumps-delete = Errore nell'eliminare il dump: { $error }
+err-dumps-select = Seleziona prima un dump nell'elenco
+err-dump-not-flipper = { $file } non è un file .nfc di Flipper
+err-dump-too-big = { $file } contiene { $bytes } byte, più di una Classic 4K
+err-dump-not-raw = { $file } non è un dump grezzo ({ $bytes } byte, atteso un multiplo di 16)
+err-dump-uid = { $file } ha una riga UID errata
+err-dump-block = { $file } ha una riga errata per il blocco { $block }
+err-dump-too-many-blocks = { $file } ha più blocchi di una Classic 4K
+err-dump-no-blocks = { $file } non ha dati di blocchi, si possono allegare solo dump Mifare Classic
+
+# Visualizzatore del database
+dbv-title = Visualizzatore del database
+dbv-heading = Database dell'inventario
+dbv-col-quantity = Quantità
+dbv-col-location = Posizione
+dbv-col-created = Creato
+dbv-col-updated = Aggiornato
+dbv-count = { $count } articoli nel database
+dbv-export = Esporta CSV
+dbv-refresh = Aggiorna
+dbv-trash-confirm = Spostare nel cestino l'articolo con ID tag '{ $tag_id }'?
+
+# Date di scadenza
+expiry-title = Articoli in scadenza
+expiry-within = In scadenza entro (giorni):
+expiry-expired = scaduto da { $days } giorni
+expiry-today = scade oggi
+expiry-days-left = tra { $days } giorni
+msg-nothing-expiring = Nessun articolo scade entro { $days } giorni.
+title-expiry-save = Salva gli articoli in scadenza
+msg-expiry-saved = Articoli in scadenza salvati in { $path }
+err-expiry-report = Errore nel rapporto delle scadenze: { $error }
+
+# Unità con numero di serie
+unit-in-stock = In magazzino
+unit-issued = Consegnata
+unit-retired = Dismessa
+unit-scanned = { $product } { $serial }: { $status }
+err-unit-status = { $product } { $serial }: { $status }, questa lettura non può spostare l'unità
+unit-title = Prodotti e unità
+unit-products = Prodotti (in magazzino / consegnate / ritirate)
+unit-units = Unità
+unit-add-product = Aggiungi prodotto
+unit-rename = Rinomina
+unit-add-unit = Aggiungi unità
+unit-return = Rientro
+unit-issue = Consegna
+unit-retire = Ritira
+unit-product-name = Nome del prodotto:
+unit-category = Categoria (facoltativa):
+unit-tag-id = ID tag dell'unità:
+unit-serial = Numero di serie (facoltativo):
+unit-delete-product-confirm = Eliminare il prodotto '{ $product }'?
+unit-remove-confirm = Rimuovere l'unità { $tag_id }? Ritira le unità che non ci sono più per conservarne la cronologia.
+log-unit-product-added = Prodotto aggiunto: { $product }
+log-unit-product-deleted = Prodotto eliminato: { $product }
+log-unit-added = Unità { $tag_id } di { $product } aggiunta
+log-unit-status = Unità { $tag_id }: { $status }
+log-unit-removed = Unità { $tag_id } rimossa
+err-unit-load-units = Errore nel caricare le unità: { $error }
+err-unit-load-products = Errore nel caricare i prodotti: { $error }
+err-unit-add-product = Errore nell'aggiungere il prodotto: { $error }
+err-unit-rename-product = Errore nel rinominare il prodotto: { $error }
+err-unit-delete-product = Errore nell'eliminare il prodotto: { $error }
+err-unit-product-in-use = Il prodotto ha ancora unità, rimuovile o ritirale
+err-unit-add = Errore nell'aggiungere l'unità: { $error }
+err-unit-update = Errore nell'aggiornare l'unità: { $error }
+err-unit-remove = Errore nel rimuovere l'unità: { $error }
+err-unit-no-product = Seleziona o aggiungi prima un prodotto
+err-unit-select-product = Nessun prodotto selezionato
+err-unit-select = Nessuna unità selezionata
+
+# Controllo della presenza
+presence-removed = Rimosso
+presence-returned = Rientrato
+presence-event = { $name } ({ $tag_id }): { $change }
+presence-title = Controllo presenza
+presence-absent = Rimosso dopo (s):
+presence-start = Avvia
+presence-stop = Ferma
+presence-help = I tag vengono letti mentre la cattura è attiva nella scheda del lettore. Il lettore deve continuare
+    a segnalare i tag rimasti nel suo campo, un articolo non segnalato per il tempo impostato è considerato rimosso.
+presence-columns = Tag / articolo / stato
+presence-events = Rimozioni e rientri
+presence-all-items = Tutti gli articoli
+presence-every-location = ogni posizione
+presence-in-place = al suo posto
+presence-gone = rimosso, assente da { $secs }s
+log-presence-started = Controllo della presenza di { $count } articoli in { $location }
+log-presence-stopped = Controllo della presenza fermato
+err-presence-load = Errore nel caricare gli eventi di presenza: { $error }
+err-presence-record = Errore nel registrare l'evento di presenza: { $error }
+err-presence-absent = Il tempo prima che un articolo sia considerato rimosso deve essere di almeno un secondo
+
+# Tag sconosciuti
+triage-title = Tag sconosciuti
+triage-columns = Tag / letture / prima volta / ultima volta
+triage-show-ignored = Mostra ignorati
+triage-item-name = Nome articolo:
+triage-item-name-tip = Se vuoto, ogni articolo prende il nome dal suo tag
+triage-quantity = Quantità:
+triage-create = Crea articoli
+triage-existing-item = Articolo esistente:
+triage-assign = Assegna all'articolo
+triage-ignore = Ignora
+triage-unignore = Elenca di nuovo
+triage-ignored = ignorato
+triage-assign-confirm = Dare il tag { $tag_id } a '{ $item }'? Gli altri suoi tag continuano a funzionare.
+log-triage-created = Aggiunti { $count } articoli dai tag sconosciuti
+log-triage-assigned = Il tag { $tag_id } ora trova anche '{ $item }'
+log-triage-ignored = { $count } tag sconosciuti ignorati
+log-triage-unignored = { $count } tag sconosciuti elencati di nuovo
+err-triage-load = Errore nel caricare i tag sconosciuti: { $error }
+err-triage-create = Errore nell'aggiungere gli articoli, non ne è stato aggiunto nessuno: { $error }
+err-triage-update = Errore nell'aggiornare i tag: { $error }
+err-triage-select-create = Seleziona i tag per cui creare gli articoli
+err-triage-select-assign = Seleziona un tag da dare all'articolo
+err-triage-quantity = La quantità deve essere un numero intero, 0 o più
+err-triage-tag-taken = Il tag { $tag_id } è già su un articolo, o l'articolo non c'è più
+
+# Kit
+kit-booked = { $kit }: { $items } articoli registrati ({ $mode })
+kit-buildable = Le scorte bastano per { $count } kit { $kit }
+kit-short = { $kit } non è stato registrato, mancano { $count } articoli:
+kit-short-line = { $item }: ne servono { $needed }, disponibili { $available }
+kit-missing-line = { $tag_id }: non è più in inventario
+kit-title = Kit
+kit-per-kit-tip = Quanti pezzi dell'articolo servono per un kit
+kit-add-item = Aggiungi articolo
+kit-component-columns = Articolo / tag / per kit / in magazzino
+kit-new = Nuovo kit
+kit-delete = Elimina kit
+kit-remove-item = Rimuovi articolo
+kit-check-stock = Controlla scorte
+kit-tag-id = ID tag del kit:
+kit-name = Nome del kit:
+kit-delete-confirm = Eliminare il kit '{ $kit }'? I suoi articoli restano nell'inventario.
+kit-check-count = Per quanti kit '{ $kit }' controllare?
+kit-enough-stock = Le scorte bastano per { $count } kit '{ $kit }', in tutto se ne possono fare { $buildable }.
+log-kit-added = Kit aggiunto: { $kit }
+log-kit-deleted = Kit eliminato: { $kit }
+err-kit-load = Errore nel caricare i kit: { $error }
+err-kit-add = Errore nell'aggiungere il kit: { $error }
+err-kit-delete = Errore nell'eliminare il kit: { $error }
+err-kit-save = Errore nel salvare il kit: { $error }
+err-kit-check = Errore nel controllare le scorte: { $error }
+err-kit-select = Nessun kit selezionato
+err-kit-no-kit = Seleziona o aggiungi prima un kit
+err-kit-quantity = La quantità per kit deve essere un numero intero maggiore di 0
+err-kit-count = Il numero di kit deve essere un numero intero maggiore di 0
+
+# Ordini di acquisto
+po-received = Ordine { $po }: ricevuti { $quantity }, { $received } di { $ordered } finora
+po-not-on-order = { $tag_id } non è nell'ordine { $po }, le scorte sono state aggiornate senza
+po-complete = L'ordine { $po } è arrivato per intero ed è chiuso
+po-title = Fornitori e ordini di acquisto
+po-suppliers = Fornitori
+po-orders = Ordini
+po-ordered-tip = Quanti pezzi dell'articolo sono stati ordinati
+po-add-line = Aggiungi riga
+po-line-columns = Articolo / tag / ricevuti / ordinati
+po-link-item = Collega articolo
+po-new-order = Nuovo ordine
+po-close-reopen = Chiudi/Riapri
+po-receive-into = Ricevi in
+po-stop-receiving = Smetti di ricevere
+po-remove-line = Rimuovi riga
+po-receiving = Ricezione nell'ordine { $po }
+po-not-receiving = Le letture non vengono ricevute in nessun ordine
+po-supplier-name = Nome del fornitore:
+po-contact = Referente (facoltativo):
+po-email = Email (facoltativa):
+po-phone = Telefono (facoltativo):
+po-order-number = Numero d'ordine:
+po-delete-supplier-confirm = Eliminare il fornitore '{ $supplier }'? I suoi articoli restano nell'inventario.
+po-status-open = aperto
+po-status-closed = chiuso
+log-po-supplier-added = Fornitore aggiunto: { $supplier }
+log-po-supplier-deleted = Fornitore eliminato: { $supplier }
+log-po-item-linked = { $item } si acquista da { $supplier }
+log-po-order-added = Ordine { $po } aggiunto con { $supplier }
+log-po-receiving = Ricezione delle letture nell'ordine { $po }
+err-po-load-orders = Errore nel caricare gli ordini: { $error }
+err-po-load-suppliers = Errore nel caricare i fornitori: { $error }
+err-po-add-supplier = Errore nell'aggiungere il fornitore: { $error }
+err-po-link-item = Errore nel collegare l'articolo: { $error }
+err-po-delete-supplier = Errore nell'eliminare il fornitore: { $error }
+err-po-supplier-in-use = Il fornitore ha ancora ordini di acquisto
+err-po-add-order = Errore nell'aggiungere l'ordine: { $error }
+err-po-update-order = Errore nell'aggiornare l'ordine: { $error }
+err-po-save-order = Errore nel salvare l'ordine: { $error }
+err-po-no-supplier = Seleziona o aggiungi prima un fornitore
+err-po-select-supplier = Nessun fornitore selezionato
+err-po-no-order = Seleziona o aggiungi prima un ordine
+err-po-select-order = Nessun ordine selezionato
+err-po-select-line = Nessuna riga selezionata
+err-po-closed = L'ordine è chiuso, riaprilo per ricevere in esso
+err-po-quantity = La quantità ordinata deve essere un numero intero maggiore di 0
+
+# Integrazioni ERP
+prefs-tab-integrations = Integrazioni
+integration-edit = Modifica
+integration-retry = Riprova ora
+integration-err-select = Seleziona prima un'integrazione
+integration-remove-confirm = Rimuovere l'integrazione "{ $name }"? Le modifiche non ancora inviate non verranno inviate.
+integration-off = disattivata
+integration-pending = { $pending } in attesa
+integration-failing = { $pending } in attesa, l'ultimo tentativo è fallito: { $error }
+integration-title = Integrazione
+integration-preset = Parti da:
+integration-enabled = Invia le modifiche
+integration-name = Nome:
+integration-url = URL:
+integration-headers = Intestazioni:
+integration-headers-tip = Un "Nome: valore" per riga. ${NOME} viene letto dall'ambiente all'invio.
+integration-events = Eventi:
+integration-events-tip = Se nessuno è selezionato vengono inviati tutti gli eventi
+integration-body = Corpo:
+integration-fields = Campi: { $fields }. Nel corpo sono valori JSON, il testo viene messo tra virgolette da solo.
+integration-test-with = Prova con:
+integration-test = Prova mappatura
+integration-send-test = Invia prova
+integration-test-skipped = Questa integrazione non invia eventi "{ $event }", uno verrebbe inviato così:
+integration-test-sent = Inviato, il server l'ha accettato.
+event-item-saved = Articolo salvato
+event-stock-received = Scorte ricevute
+event-stock-issued = Scorte prelevate
+event-stock-counted = Scorte contate
+event-stock-adjusted = Quantità cambiata
+event-item-deleted = Articolo eliminato
+err-integration-name = L'integrazione ha bisogno di un nome
+err-integration-name-taken = C'è già un'altra integrazione chiamata "{ $name }"
+err-integration-url = L'URL deve iniziare con http:// o https://
+err-integration-header = "{ $header }" non è un'intestazione "Nome: valore"
+err-integration-json = Il corpo non è JSON una volta compilati i campi: { $error }
+err-integration-braces = Un campo aperto con {{ non viene mai chiuso con }}
+err-integration-field = "{ $field }" non è un campo noto ai modelli
+err-integration-send = Invio non riuscito: { $error }
+err-integration-failed = Integrazione { $name }: { $error }
+
+# Firme
+title-verify-signature = Scegli il file firmato da verificare
+msg-signature-own = { $path } è firmato da questa stazione e non è cambiato da allora.
+msg-signature-other = { $path } non è cambiato da quando è stato firmato, con la chiave:
+    { $key }
+    Confrontala con la chiave che ti ha dato il mittente.
+err-signature-invalid = Non ci si può fidare di { $path }: { $error }
+msg-signing-key = Chiave pubblica di questa stazione, consegnala a chi controlla le sue esportazioni:
+    { $key }
+err-signing-key-load = Errore nel caricamento della chiave di firma: { $error }
+err-signing-key = { $path } non contiene una chiave di firma
+err-signature-read = Errore nella lettura della firma { $path }: { $error }
+err-signature-format = { $path } non è una firma
+err-signature-mismatch = il file è stato modificato dopo la firma
+err-signature-other-key = Il file è integro ma è stato firmato da un'altra chiave: { $key }
+msg-signature-valid = Firma valida della chiave { $key }
+usage-verify = Uso: mifare_reader_utility --verify FILE [--key CHIAVE_PUBBLICA]
+usage-import = Uso: mifare_reader_utility --import FILE.json
+usage-commands = Uso: mifare_reader_utility --verify FILE [--key CHIAVE_PUBBLICA] | --import FILE.json | --sync
+    Questa versione non ha finestra, è stata compilata senza la funzionalità gui.
+err-sync-not-built = La sincronizzazione delle postazioni non fa parte di questa versione, è stata compilata senza la funzionalità sync.
+
+# Output su console
+log-plugins-loaded = Plugin caricati: { $plugins }
+err-plugin-relative = La cartella dei plugin { $path } non è un percorso assoluto, nessun plugin è stato caricato
+err-plugin-owner = Nessun plugin caricato da { $path }, appartiene a un altro utente
+err-plugin-writable = Nessun plugin caricato da { $path }, altri utenti possono modificarlo
+err-plugin-load = Errore nel caricare il plugin { $path }: { $error }
+err-plugin-failed = Il plugin { $plugin } non è riuscito: { $error }
+log-config-overrides = Impostazioni sostituite dall'ambiente: { $settings }
+err-history-open = Errore nell'apertura della cronologia delle letture: { $error }
+err-history-save = Errore nel salvataggio di una lettura nella cronologia: { $error }
+log-import-watching = Controllo di { $dir } per i file da importare
+err-import-watch = Errore nel controllo di { $dir } per i file da importare: { $error }
+log-import-file = Importati { $count } articoli da { $file }
+err-import-file = { $file } non è stato importato: { $error }
+err-import-file-report = { $file } non è stato importato: { $error } (rapporto: { $report })
+log-inventory-ready = Database dell'inventario inizializzato
+err-inventory-init = Errore nell'inizializzazione del database dell'inventario: { $error }
+log-adding-inventory = Aggiunta della scheda inventario
+log-setting-tab = Selezione della scheda attiva
+log-window-shown = Finestra principale visualizzata
+log-shutting-down = Chiusura in corso
+err-db-flush = Errore durante lo svuotamento di { $path }: { $error }
+log-trash-purged = Eliminati { $count } articoli che erano nel cestino da più di { $days } giorni
+err-trash-purge = Errore durante lo svuotamento del cestino: { $error }
+log-created-dir = Cartella creata: { $dir }
+err-create-dir = Errore nella creazione della cartella { $dir }: { $error }
+log-signing-key-created = Creata la chiave di firma { $path }, conservane una copia
+log-export-signed = Esportazione firmata: { $path }
+err-sign-export = Errore nella firma di { $path }: { $error }
@@ -0,0 +1,48 @@
+// app/app_event.rs
+#[cfg(feature = "sync")]
+use std::path::PathBuf;
+
+use crate::export::ExportFormat;
+
+/// What the menus and dialogs ask the main window to do, sent over the FLTK
+/// channel and handled by `events::run_event_loop`
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppEvent {
+    Exit,
+    About,
+    Preferences,
+    // Keyboard layout index, 0 is auto-detect
+    SetKeyboardLayout(i32),
+    CalibrateKeyboard,
+    ReaderConfig,
+    // Language code from i18n::LANGUAGES
+    SetLanguage(String),
+    // The configured language changed, the menus are rebuilt in it
+    LanguageChanged,
+    SwitchProfile(String),
+    SaveProfile,
+    DeleteProfile,
+    // The scans of this session
+    ExportScans(ExportFormat),
+    ImportData,
+    ViewDatabase,
+    #[cfg(feature = "sync")]
+    CheckImportFiles,
+    // A file the import watcher found in the import directory
+    #[cfg(feature = "sync")]
+    ImportFile(PathBuf),
+    RestoreBackup,
+    // Items that expire soon or have expired
+    ExpiryReport,
+    #[cfg(feature = "sync")]
+    GDriveExport,
+    #[cfg(feature = "sync")]
+    GDriveImport,
+    #[cfg(feature = "sync")]
+    StationSync,
+    SaveLog,
+    // Sign exports from now on, or stop
+    ToggleSignExports,
+    VerifySignature,
+    ShowSigningKey,
+}
@@ -0,0 +1,110 @@
+// app/calibration.rs
+use fltk::{
+    button::Button,
+    dialog,
+    enums::{Align, FrameType},
+    frame::Frame,
+    input::Input,
+    prelude::*,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use uid_codec::{calibrate, decode_uid, KeyboardLayout, ALL_LAYOUTS};
+
+use crate::config;
+use crate::i18n::{tr, tr_args};
+
+// this shows the keyboard layout calibration wizard opened from the Edit menu
+pub fn show_calibration_wizard(
+    keyboard_layout: &Rc<RefCell<i32>>,
+    config: &Rc<RefCell<config::AppConfig>>
+) {
+    let mut win = Window::new(250, 100, 500, 330, None).with_label(&tr("cal-title"));
+    win.make_modal(true);
+
+    let mut steps = Frame::new(20, 15, 460, 70, None).with_label(&tr("cal-steps"));
+    steps.set_align(Align::Left | Align::Inside | Align::Wrap);
+    steps.set_label_size(12);
+
+    let expected_input = Input::new(140, 95, 340, 25, None).with_label(&tr("cal-reference"));
+    let mut scan_input = Input::new(140, 130, 340, 25, None).with_label(&tr("cal-scan"));
+
+    let mut result = Frame::new(20, 170, 460, 100, "");
+    result.set_frame(FrameType::DownBox);
+    result.set_align(Align::Left | Align::Inside | Align::Top | Align::Wrap);
+
+    let mut calibrate_btn = Button::new(190, 285, 90, 30, None).with_label(&tr("cal-calibrate"));
+    let mut clear_btn = Button::new(290, 285, 90, 30, None).with_label(&tr("btn-clear"));
+    let mut close_btn = Button::new(390, 285, 90, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    // the scan field has focus so the wedge reader types straight into it
+    let _ = scan_input.take_focus();
+
+    let keyboard_layout_cal = keyboard_layout.clone();
+    let config_cal = config.clone();
+    let scan_cal = scan_input.clone();
+    let mut result_cal = result.clone();
+    calibrate_btn.set_callback(move |_| {
+        let expected = expected_input.value();
+        let scanned = scan_cal.value();
+
+        if expected.chars().filter(|c| c.is_ascii_hexdigit()).count() == 0 {
+            dialog::alert(300, 300, &tr("cal-err-reference"));
+            return;
+        }
+        if scanned.trim().is_empty() {
+            dialog::alert(300, 300, &tr("cal-err-scan"));
+            return;
+        }
+
+        let matches = calibrate(&scanned, &expected);
+        let layout = match matches.first() {
+            Some(layout) => *layout,
+            None => {
+                // show what each decoder made of the scan to help spot a wrong reference UID
+                let attempts: Vec<String> = ALL_LAYOUTS.iter()
+                    .filter(|layout| **layout != KeyboardLayout::Auto)
+                    .map(|layout| format!("{}: {}", layout.name(), decode_uid(&scanned, *layout)))
+                    .collect();
+                result_cal.set_label(&tr_args("cal-no-match", &[("attempts", &attempts.join("\n"))]));
+                return;
+            }
+        };
+
+        // several layouts can agree when the reader only sends plain characters, any of them works
+        let mut message = tr_args("cal-locked", &[("layout", layout.name())]);
+        if matches.len() > 1 {
+            let others: Vec<&str> = matches[1..].iter().map(|layout| layout.name()).collect();
+            message.push('\n');
+            message.push_str(&tr_args("cal-also-matches", &[("layouts", &others.join(", "))]));
+        }
+
+        *keyboard_layout_cal.borrow_mut() = layout.index();
+        let mut config = config_cal.borrow_mut();
+        config.default_keyboard_layout = layout.index();
+        if let Err(e) = config::save_config(&config) {
+            message.push('\n');
+            message.push_str(&tr_args("err-save-config", &[("error", &e.to_string())]));
+        }
+
+        result_cal.set_label(&message);
+    });
+
+    let mut scan_clear = scan_input.clone();
+    let mut result_clear = result.clone();
+    clear_btn.set_callback(move |_| {
+        scan_clear.set_value("");
+        result_clear.set_label("");
+        let _ = scan_clear.take_focus();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
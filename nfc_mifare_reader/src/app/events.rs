@@ -7,18 +7,35 @@ use fltk::{
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::path::Path;
+use progress_sink::StatusFile;
 
+use crate::app::calibration::show_calibration_wizard;
+use crate::app::expiry_report::show_expiry_report;
+use crate::app::reader_config::show_reader_config_dialog;
+use crate::app::restore_backup::show_restore_dialog;
+use crate::app::AppEvent;
 use crate::app::menu::MenuItems;
-use crate::config;
+use crate::config::{self, profiles};
 use crate::db_viewer;
+use crate::i18n::{self, tr, tr_args};
 use crate::export;
+use crate::integrations::{self, Integration};
+use crate::inventory::import;
+use crate::signing;
+#[cfg(feature = "sync")]
 use crate::sync::gdrive_sync;
-use crate::sync::check_for_import_files;
+#[cfg(feature = "sync")]
+use crate::sync::station_sync::StationSync;
+#[cfg(feature = "sync")]
+use crate::sync::{check_for_import_files, file_sync, watch_import_directory, FileSync};
+use crate::ui::theme::{self, Theme, ALL_THEMES};
+use crate::reader::{command_cards, scan_log};
+use crate::rules::{RuleAction, RuleCondition, ScanRule};
 
 
 pub fn run_event_loop(
     app: app::App,
-    receiver: app::Receiver<String>,
+    receiver: app::Receiver<AppEvent>,
     keyboard_layout: Rc<RefCell<i32>>,
     app_config: Rc<RefCell<config::AppConfig>>,
     card_data_buffer: Rc<RefCell<fltk::text::TextBuffer>>,
@@ -34,119 +51,262 @@ pub fn run_event_loop(
         
     // entry point and main event loop
     while app.wait() {
-        if let Some(msg) = receiver.recv() {
-            handle_menu_event(msg, &menu_items);
+        if let Some(event) = receiver.recv() {
+            handle_menu_event(event, &menu_items);
         }
     }
 }
 
-fn handle_menu_event(msg: String, menu_items: &MenuItems) {
+fn handle_menu_event(event: AppEvent, menu_items: &MenuItems) {
     // menu items
     let keyboard_layout = &menu_items.keyboard_layout;
     let config = &menu_items.config;
     let card_buffer = &menu_items.card_buffer;
     let inventory_ui = &menu_items.inventory_ui;
     
-    match msg.as_str() {
-        "exit" => {
+    match event {
+        AppEvent::Exit => {
             app::quit();
         },
-        "about" => {
-            dialog::message(300, 300, "Mifare Reader Utility v0.2.0\n\nA tool for reading and analyzing Mifare card UIDs\nDeveloped with Rust and FLTK\n\nNow with Inventory Management!");
+        AppEvent::About => {
+            dialog::message(300, 300, &tr("about-text"));
         },
-        "preferences" => {
-            show_preferences_dialog(keyboard_layout, config);
+        AppEvent::Preferences => {
+            show_preferences_dialog(keyboard_layout, config, &menu_items.sender);
         },
-        "kb_auto" => {
-            *keyboard_layout.borrow_mut() = 0;
-            config.borrow_mut().default_keyboard_layout = 0;
+        AppEvent::SetKeyboardLayout(layout) => {
+            *keyboard_layout.borrow_mut() = layout;
+            config.borrow_mut().default_keyboard_layout = layout;
             let _ = config::save_config(&config.borrow());
         },
-        "kb_windows" => {
-            *keyboard_layout.borrow_mut() = 1;
-            config.borrow_mut().default_keyboard_layout = 1;
-            let _ = config::save_config(&config.borrow());
+        AppEvent::CalibrateKeyboard => {
+            show_calibration_wizard(keyboard_layout, config);
         },
-        "kb_mac_us" => {
-            *keyboard_layout.borrow_mut() = 2;
-            config.borrow_mut().default_keyboard_layout = 2;
-            let _ = config::save_config(&config.borrow());
+        AppEvent::ReaderConfig => show_reader_config_dialog(),
+        AppEvent::LanguageChanged => {
+            i18n::set_language(&config.borrow().language);
+            let mut menu = menu_items.menu.clone();
+            crate::app::menu::relabel_menu(&mut menu, &menu_items.sender);
         },
-        "kb_mac_intl" => {
-            *keyboard_layout.borrow_mut() = 3;
-            config.borrow_mut().default_keyboard_layout = 3;
+        AppEvent::SetLanguage(language) => {
+            config.borrow_mut().language = language;
             let _ = config::save_config(&config.borrow());
+            if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+                app_config.language = config.borrow().language.clone();
+            }
+            menu_items.sender.send(AppEvent::LanguageChanged);
         },
-        "export_csv" => handle_export_csv(card_buffer),
-        "export_json" => handle_export_json(card_buffer),
-        "export_text" => handle_export_text(card_buffer),
-        "view_database" => {
+        AppEvent::SaveProfile => handle_profile_save(menu_items),
+        AppEvent::DeleteProfile => handle_profile_delete(menu_items),
+        AppEvent::SwitchProfile(name) => handle_profile_switch(&name, menu_items),
+        AppEvent::ExportScans(format) => handle_export(format),
+        AppEvent::ViewDatabase => {
             db_viewer::show_database_viewer(inventory_ui);
         },
-        "check_files" => handle_check_files(inventory_ui),
-        "gdrive_export" => handle_gdrive_export(inventory_ui, config),
-        "gdrive_import" => handle_gdrive_import(inventory_ui, config),
-        "import_data" => handle_import_data(inventory_ui),
-        "save_log" => {
+        #[cfg(feature = "sync")]
+        AppEvent::CheckImportFiles => handle_check_files(inventory_ui, &config.borrow()),
+        #[cfg(feature = "sync")]
+        AppEvent::ImportFile(path) => handle_import_file(&path, inventory_ui, &config.borrow()),
+        AppEvent::RestoreBackup => show_restore_dialog(inventory_ui),
+        AppEvent::ExpiryReport => show_expiry_report(inventory_ui),
+        #[cfg(feature = "sync")]
+        AppEvent::GDriveExport => handle_gdrive_export(inventory_ui, config),
+        #[cfg(feature = "sync")]
+        AppEvent::GDriveImport => handle_gdrive_import(inventory_ui, config),
+        #[cfg(feature = "sync")]
+        AppEvent::StationSync => handle_station_sync(inventory_ui, config),
+        AppEvent::ImportData => handle_import_data(inventory_ui),
+        AppEvent::SaveLog => {
             match config::save_log(&card_buffer.borrow().text(), &config.borrow()) {
                 Ok(msg) => dialog::message(300, 300, &msg),
-                Err(e) => dialog::alert(300, 300, &format!("Error saving log: {}", e)),
+                Err(e) => dialog::alert(300, 300, &tr_args("err-save-log", &[("error", &e.to_string())])),
             }
         },
-        _ => {}
+        AppEvent::ToggleSignExports => {
+            let sign_exports = !config.borrow().sign_exports;
+            config.borrow_mut().sign_exports = sign_exports;
+            let _ = config::save_config(&config.borrow());
+            if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+                app_config.sign_exports = sign_exports;
+            }
+        },
+        AppEvent::VerifySignature => handle_verify_signature(),
+        AppEvent::ShowSigningKey => handle_show_signing_key(),
     }
 }
 
 // handler functions to keep the event loop clean
-fn handle_export_csv(card_buffer: &Rc<RefCell<fltk::text::TextBuffer>>) {
-    if let Some(path) = dialog::file_chooser("Export as CSV", "*.csv", ".", false) {
-        let records = export::parse_display_text(&card_buffer.borrow().text());
-        match export::export_data(&records, export::ExportFormat::CSV, &path) {
-            Ok(msg) => dialog::message(300, 300, &msg),
-            Err(e) => dialog::alert(300, 300, &format!("Error exporting: {}", e)),
-        }
+fn handle_profile_switch(name: &str, menu_items: &MenuItems) {
+    let result = profiles::switch_profile(name, &mut menu_items.config.borrow_mut());
+    if let Err(e) = result {
+        dialog::alert(300, 300, &tr_args("err-profile", &[("error", &e.to_string())]));
+    }
+    apply_profile(menu_items);
+}
+
+fn handle_profile_save(menu_items: &MenuItems) {
+    let active = menu_items.config.borrow().active_profile.clone();
+    let name = match dialog::input(300, 300, &tr("profile-name-prompt"), &active) {
+        Some(name) => name,
+        None => return,
+    };
+    
+    let result = profiles::save_profile_as(&name, &mut menu_items.config.borrow_mut());
+    if let Err(e) = result {
+        dialog::alert(300, 300, &tr_args("err-profile", &[("error", &e.to_string())]));
     }
+    apply_profile(menu_items);
 }
 
-fn handle_export_json(card_buffer: &Rc<RefCell<fltk::text::TextBuffer>>) {
-    if let Some(path) = dialog::file_chooser("Export as JSON", "*.json", ".", false) {
-        let records = export::parse_display_text(&card_buffer.borrow().text());
-        match export::export_data(&records, export::ExportFormat::JSON, &path) {
+fn handle_profile_delete(menu_items: &MenuItems) {
+    let active = menu_items.config.borrow().active_profile.clone();
+    if active.is_empty() {
+        dialog::alert(300, 300, &tr("err-profile-none"));
+        return;
+    }
+    if dialog::choice2(300, 300, &tr_args("profile-delete-confirm", &[("name", &active)]), &tr("btn-cancel"), &tr("btn-remove"), "") != Some(1) {
+        return;
+    }
+    
+    let result = profiles::delete_profile(&active, &mut menu_items.config.borrow_mut());
+    if let Err(e) = result {
+        dialog::alert(300, 300, &tr_args("err-profile", &[("error", &e.to_string())]));
+    }
+    apply_profile(menu_items);
+}
+
+// this puts the settings of a switched profile to use and ticks it in the menu,
+// the 125 kHz reader picks up its port the next time capture starts
+fn apply_profile(menu_items: &MenuItems) {
+    let config = menu_items.config.borrow();
+    if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+        *app_config = config.clone();
+    }
+    *menu_items.keyboard_layout.borrow_mut() = config.default_keyboard_layout;
+    if let Err(e) = crate::reader::feedback::configure(&config) {
+        dialog::alert(300, 300, &e);
+    }
+    
+    // the profile may import from another directory
+    #[cfg(feature = "sync")]
+    watch_import_directory(&config, menu_items.sender);
+    
+    let mut menu = menu_items.menu.clone();
+    crate::app::menu::relabel_menu(&mut menu, &menu_items.sender);
+}
+
+fn handle_export(format: export::ExportFormat) {
+    let (title, filter) = match format {
+        export::ExportFormat::CSV => ("title-export-csv", "*.csv"),
+        export::ExportFormat::JSON => ("title-export-json", "*.json"),
+        export::ExportFormat::Text => ("title-export-text", "*.txt"),
+    };
+    if let Some(path) = dialog::file_chooser(&tr(title), filter, ".", false) {
+        let records = scan_log::session_scans();
+        match export::export_data(&records, format, &path) {
             Ok(msg) => dialog::message(300, 300, &msg),
-            Err(e) => dialog::alert(300, 300, &format!("Error exporting: {}", e)),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())])),
         }
     }
 }
 
-fn handle_export_text(card_buffer: &Rc<RefCell<fltk::text::TextBuffer>>) {
-    if let Some(path) = dialog::file_chooser("Export as Text", "*.txt", ".", false) {
-        let records = export::parse_display_text(&card_buffer.borrow().text());
-        match export::export_data(&records, export::ExportFormat::Text, &path) {
-            Ok(msg) => dialog::message(300, 300, &msg),
-            Err(e) => dialog::alert(300, 300, &format!("Error exporting: {}", e)),
+// a file signed with another station's key is valid, but only the key tells whose it is
+fn handle_verify_signature() {
+    if let Some(path) = dialog::file_chooser(&tr("title-verify-signature"), "*", ".", false) {
+        match signing::verify_file(Path::new(&path)) {
+            Ok(verification) if verification.own_key => {
+                dialog::message(300, 300, &tr_args("msg-signature-own", &[("path", &path)]));
+            },
+            Ok(verification) => {
+                dialog::message(300, 300, &tr_args("msg-signature-other", &[
+                    ("path", &path),
+                    ("key", &verification.public_key),
+                ]));
+            },
+            Err(e) => {
+                dialog::alert(300, 300, &tr_args("err-signature-invalid", &[
+                    ("path", &path),
+                    ("error", &e),
+                ]));
+            }
         }
     }
 }
 
-fn handle_check_files(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
-    let import_dir = "./import";
-    let processed_dir = "./processed";
-    let error_dir = "./error";
-    
-    match check_for_import_files(import_dir, processed_dir, error_dir, inventory_ui) {
-        Ok(count) => {
-            if count > 0 {
-                dialog::message(300, 300, &format!("Successfully processed {} files.", count));
+fn handle_show_signing_key() {
+    match signing::public_key() {
+        Ok(key) => {
+            println!("{}", tr_args("msg-signing-key", &[("key", &key)]));
+            dialog::message(300, 300, &tr_args("msg-signing-key", &[("key", &key)]));
+        },
+        Err(e) => dialog::alert(300, 300, &tr_args("err-signing-key-load", &[("error", &e.to_string())])),
+    }
+}
+
+#[cfg(feature = "sync")]
+fn handle_check_files(inventory_ui: &Rc<crate::inventory::InventoryUI>, config: &config::AppConfig) {
+    let dirs = config.sync_dirs();
+    
+    match check_for_import_files(&dirs.import_dir, &dirs.processed_dir, &dirs.error_dir, inventory_ui) {
+        Ok(files) if files.is_empty() => {
+            dialog::message(300, 300, &tr("msg-no-import-files"));
+        },
+        Ok(files) => {
+            inventory_ui.refresh();
+            
+            // one line per file under the totals, failed files say where their report is
+            let imported = files.iter().filter(|file| file.result.is_ok()).count();
+            let items: usize = files.iter().filter_map(|file| file.result.as_ref().ok()).sum();
+            let mut summary = tr_args("msg-import-summary", &[
+                ("imported", &imported.to_string()),
+                ("failed", &(files.len() - imported).to_string()),
+                ("items", &items.to_string()),
+            ]);
+            summary.push('\n');
+            for file in &files {
+                let message = file.message();
+                inventory_ui.log_event(&message);
+                summary.push('\n');
+                summary.push_str(&message);
+            }
+            
+            if imported == files.len() {
+                dialog::message(300, 300, &summary);
             } else {
-                dialog::message(300, 300, "No files found to import.");
+                dialog::alert(300, 300, &summary);
             }
         },
         Err(e) => {
-            dialog::alert(300, 300, &format!("Error processing import files: {}", e));
+            dialog::alert(300, 300, &tr_args("err-import-files", &[("error", &e.to_string())]));
         }
     }
 }
 
+// this imports a file the import watcher found and says how it went in the inventory log
+#[cfg(feature = "sync")]
+fn handle_import_file(
+    path: &Path,
+    inventory_ui: &Rc<crate::inventory::InventoryUI>,
+    config: &config::AppConfig
+) {
+    // the same file can be reported more than once, it is gone after the first import
+    if !path.is_file() {
+        return;
+    }
+    
+    let dirs = config.sync_dirs();
+    let file_sync = FileSync::new(&dirs.import_dir, &dirs.processed_dir, &dirs.error_dir);
+    
+    let imported = file_sync::import_file(&file_sync, path, inventory_ui);
+    if imported.result.is_ok() {
+        inventory_ui.refresh();
+    }
+    let message = imported.message();
+    println!("{}", message);
+    inventory_ui.log_event(&message);
+}
+
+#[cfg(feature = "sync")]
 fn handle_gdrive_export(
     inventory_ui: &Rc<crate::inventory::InventoryUI>,
     config: &Rc<RefCell<config::AppConfig>>
@@ -156,17 +316,18 @@ fn handle_gdrive_export(
         
         match gdrive_sync.export_database(&inventory_ui.inventory_db.borrow()) {
             Ok(file_path) => {
-                dialog::message(300, 300, &format!("Database exported to Google Drive sync folder:\n{}", file_path));
+                dialog::message(300, 300, &tr_args("msg-gdrive-exported", &[("path", &file_path)]));
             },
             Err(e) => {
-                dialog::alert(300, 300, &format!("Error exporting to Google Drive sync folder: {}", e));
+                dialog::alert(300, 300, &tr_args("err-gdrive-export", &[("error", &e.to_string())]));
             }
         }
     } else {
-        dialog::alert(300, 300, "Google Drive sync is not enabled. Please enable it in preferences.");
+        dialog::alert(300, 300, &tr("err-gdrive-disabled"));
     }
 }
 
+#[cfg(feature = "sync")]
 fn handle_gdrive_import(
     inventory_ui: &Rc<crate::inventory::InventoryUI>,
     config: &Rc<RefCell<config::AppConfig>>
@@ -176,21 +337,49 @@ fn handle_gdrive_import(
         
         match gdrive_sync.import_latest_database(&inventory_ui.inventory_db.borrow()) {
             Ok(count) => {
-                dialog::message(300, 300, &format!("Successfully imported {} items from Google Drive", count));
+                dialog::message(300, 300, &tr_args("msg-gdrive-imported", &[("count", &count.to_string())]));
+            },
+            Err(e) => {
+                dialog::alert(300, 300, &tr_args("err-gdrive-import", &[("error", &e.to_string())]));
+            }
+        }
+    } else {
+        dialog::alert(300, 300, &tr("err-gdrive-disabled"));
+    }
+}
+
+// Merge edits with the other stations sharing the sync folder, unlike import this
+// never overwrites changes made here
+#[cfg(feature = "sync")]
+fn handle_station_sync(
+    inventory_ui: &Rc<crate::inventory::InventoryUI>,
+    config: &Rc<RefCell<config::AppConfig>>
+) {
+    if config.borrow().gdrive_sync_enabled {
+        let station_sync = StationSync::new(&config.borrow().gdrive_sync_folder);
+        
+        match station_sync.sync(&inventory_ui.inventory_db.borrow()) {
+            Ok(report) => {
+                dialog::message(300, 300, &tr_args("msg-stations-synced", &[
+                    ("stations", &report.stations.to_string()),
+                    ("shared", &report.local_operations.to_string()),
+                    ("received", &report.received.to_string()),
+                    ("changed", &report.changed_items.len().to_string()),
+                ]));
             },
             Err(e) => {
-                dialog::alert(300, 300, &format!("Error importing from Google Drive: {}", e));
+                dialog::alert(300, 300, &tr_args("err-station-sync", &[("error", &e.to_string())]));
             }
         }
     } else {
-        dialog::alert(300, 300, "Google Drive sync is not enabled. Please enable it in preferences.");
+        dialog::alert(300, 300, &tr("err-gdrive-disabled"));
     }
 }
 
 fn handle_import_data(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
-    if let Some(path) = dialog::file_chooser("Import data", "*.{json,csv}", ".", true) {
+    if let Some(path) = dialog::file_chooser(&tr("title-import"), "*.{json,csv}", ".", true) {
         if !Path::new(&path).exists() {
-            dialog::alert(300, 300, &format!("File does not exist: {}", path));
+            dialog::alert(300, 300, &tr_args("err-file-missing", &[("path", &path)]));
             return;
         }
         
@@ -199,20 +388,20 @@ fn handle_import_data(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                 // Check if it's JSON or CSV
                 if path.ends_with(".json") {
                     // Import JSON
-                    match inventory_ui.inventory_db.borrow().import_json(&content) {
+                    match import::import_json(&inventory_ui.inventory_db.borrow(), &content, &StatusFile::new("import")) {
                         Ok(count) => {
-                            dialog::message(300, 300, &format!("Successfully imported {} items from JSON.", count));
+                            dialog::message(300, 300, &tr_args("msg-json-imported", &[("count", &count.to_string())]));
                         },
                         Err(e) => {
-                            dialog::alert(300, 300, &format!("Error importing JSON data: {}", e));
+                            dialog::alert(300, 300, &tr_args("err-json-import", &[("error", &e.to_string())]));
                         }
                     }
                 } else {
-                    dialog::alert(300, 300, "CSV import is not yet implemented.");
+                    dialog::alert(300, 300, &tr("err-csv-import"));
                 }
             },
             Err(e) => {
-                dialog::alert(300, 300, &format!("Error reading file: {}", e));
+                dialog::alert(300, 300, &tr_args("err-read-file", &[("error", &e.to_string())]));
             }
         }
     }
@@ -220,69 +409,453 @@ fn handle_import_data(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
 
 fn show_preferences_dialog(
     keyboard_layout: &Rc<RefCell<i32>>,
-    config: &Rc<RefCell<config::AppConfig>>
+    config: &Rc<RefCell<config::AppConfig>>,
+    sender: &app::Sender<AppEvent>
 ) {
     // create the preferences window and its components
-    let prefs_win_rc = Rc::new(RefCell::new(fltk::window::Window::new(300, 100, 400, 300, "Preferences")));
+    let prefs_win_rc = Rc::new(RefCell::new(fltk::window::Window::new(300, 100, 400, 330, None).with_label(&tr("prefs-title"))));
     
     // use Rc::borrow_mut() to modify the window
     prefs_win_rc.borrow_mut().make_modal(true);
     
     // it is important to set the end() method to make the window visible 
-    let tabs = fltk::group::Tabs::new(10, 10, 380, 240, "");
+    let tabs = fltk::group::Tabs::new(10, 10, 380, 270, "");
     
     // this is the general settings tab
-    let general_tab = fltk::group::Group::new(10, 35, 380, 215, "General");
+    let general_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-general"));
     
-    let mut save_logs_check = fltk::button::CheckButton::new(20, 45, 200, 25, "Save logs to file");
+    let mut save_logs_check = fltk::button::CheckButton::new(20, 45, 200, 25, None).with_label(&tr("prefs-save-logs"));
     save_logs_check.set_checked(config.borrow().save_logs);
     
-    let mut log_dir_input = fltk::input::Input::new(140, 75, 240, 25, "Log directory:");
+    let mut log_dir_input = fltk::input::Input::new(140, 75, 240, 25, None).with_label(&tr("prefs-log-dir"));
     log_dir_input.set_value(&config.borrow().log_directory);
     
-    let _layout_choice_text = fltk::frame::Frame::new(20, 105, 120, 25, "Keyboard Layout:");
+    let _layout_choice_text = fltk::frame::Frame::new(20, 105, 120, 25, None).with_label(&tr("prefs-keyboard-layout"));
     
     let mut layout_choice = fltk::menu::Choice::new(140, 105, 240, 25, "");
-    layout_choice.add_choice("Auto-detect");
-    layout_choice.add_choice("Windows");
-    layout_choice.add_choice("Mac US");
-    layout_choice.add_choice("Mac International");
+    for key in ["menu-kb-auto", "menu-kb-windows", "menu-kb-mac-us", "menu-kb-mac-intl", "menu-kb-azerty", "menu-kb-qwertz"] {
+        // menu labels carry '&' shortcut markers, a Choice would show them
+        layout_choice.add_choice(&tr(key).replace('&', ""));
+    }
     layout_choice.set_value(config.borrow().default_keyboard_layout);
     
+    // the regex format patterns have their own editor dialog
+    let mut patterns_btn = fltk::button::Button::new(140, 140, 240, 25, None).with_label(&tr("prefs-edit-patterns"));
+    let config_patterns = config.clone();
+    patterns_btn.set_callback(move |_| {
+        super::pattern_editor::show_pattern_editor(&config_patterns);
+    });
+    
+    let mut cooldown_input = fltk::input::IntInput::new(140, 175, 60, 25, None).with_label(&tr("prefs-scan-cooldown"));
+    cooldown_input.set_value(&config.borrow().scan_cooldown_secs.to_string());
+    cooldown_input.set_tooltip(&tr("prefs-scan-cooldown-tip"));
+    fltk::frame::Frame::new(205, 175, 175, 25, None).with_label(&tr("prefs-seconds-off"))
+        .set_align(fltk::enums::Align::Left | fltk::enums::Align::Inside);
+    
+    // language names are shown in their own language
+    let mut language_choice = fltk::menu::Choice::new(140, 210, 240, 25, None).with_label(&tr("prefs-language"));
+    language_choice.add_choice(&tr("prefs-language-auto"));
+    for (_, name) in i18n::LANGUAGES {
+        language_choice.add_choice(name);
+    }
+    let language_index = i18n::LANGUAGES.iter()
+        .position(|(code, _)| *code == config.borrow().language)
+        .map(|index| index as i32 + 1)
+        .unwrap_or(0);
+    language_choice.set_value(language_index);
+    
+    let mut theme_choice = fltk::menu::Choice::new(140, 240, 240, 25, None).with_label(&tr("prefs-theme"));
+    for theme in ALL_THEMES {
+        theme_choice.add_choice(&theme.name());
+    }
+    theme_choice.set_value(config.borrow().theme.index());
+    
     general_tab.end();
     
     // this is the Google Drive sync tab
-    let gdrive_tab = fltk::group::Group::new(10, 35, 380, 215, "Google Drive");
+    let gdrive_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-gdrive"));
     
-    let mut gdrive_enable_check = fltk::button::CheckButton::new(20, 45, 200, 25, "Enable Google Drive sync");
+    let mut gdrive_enable_check = fltk::button::CheckButton::new(20, 45, 200, 25, None).with_label(&tr("prefs-gdrive-enable"));
     gdrive_enable_check.set_checked(config.borrow().gdrive_sync_enabled);
     
-    let mut gdrive_folder_input = fltk::input::Input::new(140, 75, 200, 25, "Sync folder:");
+    let mut gdrive_folder_input = fltk::input::Input::new(140, 75, 200, 25, None).with_label(&tr("prefs-gdrive-folder"));
     gdrive_folder_input.set_value(&config.borrow().gdrive_sync_folder);
     
     let mut gdrive_folder_btn = fltk::button::Button::new(350, 75, 30, 25, "...");
     
     let mut gdrive_folder_input_clone = gdrive_folder_input.clone();
     gdrive_folder_btn.set_callback(move |_| {
-        if let Some(path) = dialog::dir_chooser("Select Google Drive sync folder", "", false) {
+        if let Some(path) = dialog::dir_chooser(&tr("prefs-gdrive-choose"), "", false) {
             gdrive_folder_input_clone.set_value(&path);
         }
     });
     
     // lets the user know how to use Google Drive sync
     let mut gdrive_info_buffer = fltk::text::TextBuffer::default();
-    gdrive_info_buffer.set_text("How to use Google Drive sync:\n\n1. Install Google Drive for Desktop\n2. Select a folder inside your Google Drive\n3. Enable sync above and set the folder path\n4. Use Export/Import menu options to sync your database");
+    gdrive_info_buffer.set_text(&tr("prefs-gdrive-help"));
     
     let mut gdrive_info = fltk::text::TextDisplay::new(20, 110, 360, 125, "");
     gdrive_info.set_buffer(gdrive_info_buffer);
     
     gdrive_tab.end();
     
+    // this is the 125 kHz RDM6300 reader tab
+    let lf_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-lf"));
+    
+    let mut rdm6300_enable_check = fltk::button::CheckButton::new(20, 45, 300, 25, None).with_label(&tr("prefs-lf-enable"));
+    rdm6300_enable_check.set_checked(config.borrow().rdm6300_enabled);
+    
+    let mut rdm6300_port_input = fltk::input::Input::new(140, 75, 240, 25, None).with_label(&tr("prefs-lf-port"));
+    rdm6300_port_input.set_value(&config.borrow().rdm6300_port);
+    
+    let mut lf_info_buffer = fltk::text::TextBuffer::default();
+    lf_info_buffer.set_text(&tr("prefs-lf-help"));
+    
+    let mut lf_info = fltk::text::TextDisplay::new(20, 110, 360, 125, "");
+    lf_info.set_buffer(lf_info_buffer);
+    
+    lf_tab.end();
+    
+    // this is the buzzer and LED scan feedback tab
+    let feedback_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-feedback"));
+    
+    let mut feedback_enable_check = fltk::button::CheckButton::new(20, 45, 300, 25, None).with_label(&tr("prefs-feedback-enable"));
+    feedback_enable_check.set_checked(config.borrow().feedback_enabled);
+    
+    // pins are BCM numbers, an empty field leaves that output unused
+    let pin_text = |pin: Option<u8>| pin.map(|pin| pin.to_string()).unwrap_or_default();
+    let mut buzzer_pin_input = fltk::input::IntInput::new(160, 75, 60, 25, None).with_label(&tr("prefs-feedback-buzzer"));
+    buzzer_pin_input.set_value(&pin_text(config.borrow().feedback_buzzer_pin));
+    let mut red_pin_input = fltk::input::IntInput::new(160, 105, 60, 25, None).with_label(&tr("prefs-feedback-red"));
+    red_pin_input.set_value(&pin_text(config.borrow().feedback_led_red_pin));
+    let mut green_pin_input = fltk::input::IntInput::new(160, 135, 60, 25, None).with_label(&tr("prefs-feedback-green"));
+    green_pin_input.set_value(&pin_text(config.borrow().feedback_led_green_pin));
+    
+    let mut feedback_info_buffer = fltk::text::TextBuffer::default();
+    feedback_info_buffer.set_text(&tr("prefs-feedback-help"));
+    
+    let mut feedback_info = fltk::text::TextDisplay::new(20, 170, 360, 65, "");
+    feedback_info.set_buffer(feedback_info_buffer);
+    
+    feedback_tab.end();
+    
+    // this is the manufacturer database tab, entries are saved right away
+    let manuf_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-manufacturers"));
+    
+    let manuf_code_input = fltk::input::Input::new(65, 45, 45, 25, None).with_label(&tr("prefs-manuf-code"));
+    let manuf_name_input = fltk::input::Input::new(165, 45, 150, 25, None).with_label(&tr("prefs-manuf-name"));
+    let mut manuf_add_btn = fltk::button::Button::new(325, 45, 55, 25, None).with_label(&tr("btn-add"));
+    
+    let mut manuf_browser = fltk::browser::HoldBrowser::new(20, 80, 360, 160, "");
+    for (code, name) in config::all_manufacturers() {
+        manuf_browser.add(&format!("{}  {}", code, name));
+    }
+    
+    let config_manuf = config.clone();
+    manuf_add_btn.set_callback(move |_| {
+        let code = manuf_code_input.value();
+        let name = manuf_name_input.value();
+        if name.trim().is_empty() {
+            dialog::alert(300, 300, &tr("err-manuf-name"));
+            return;
+        }
+        
+        let mut config = config_manuf.borrow_mut();
+        if let Err(e) = config::add_manufacturer(&code, &name, &mut config) {
+            dialog::alert(300, 300, &tr_args("err-manuf-add", &[("error", &e.to_string())]));
+            return;
+        }
+        if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+            app_config.manufacturer_database = config.manufacturer_database.clone();
+        }
+        
+        manuf_browser.clear();
+        for (code, name) in config::all_manufacturers() {
+            manuf_browser.add(&format!("{}  {}", code, name));
+        }
+    });
+    
+    manuf_tab.end();
+    
+    // this is the command card tab, cards are saved right away like manufacturers
+    let cards_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-command-cards"));
+    
+    let card_tag_input = fltk::input::Input::new(65, 45, 110, 25, None).with_label(&tr("prefs-card-tag"));
+    let mut card_action_choice = fltk::menu::Choice::new(180, 45, 140, 25, None);
+    for action in command_cards::ALL_ACTIONS {
+        card_action_choice.add_choice(&format!("{} ({})", action.name(), action.hotkey_label()).replace('/', "\\/"));
+    }
+    card_action_choice.set_value(0);
+    let mut card_add_btn = fltk::button::Button::new(325, 45, 55, 25, None).with_label(&tr("btn-add"));
+    
+    let mut card_browser = fltk::browser::HoldBrowser::new(20, 80, 360, 125, "");
+    fill_command_card_browser(&mut card_browser, &config.borrow().command_cards);
+    
+    let mut card_remove_btn = fltk::button::Button::new(20, 215, 80, 25, None).with_label(&tr("btn-remove"));
+    let mut card_help = fltk::frame::Frame::new(110, 210, 270, 35, None).with_label(&tr("prefs-card-help"));
+    card_help.set_label_size(11);
+    card_help.set_align(fltk::enums::Align::Left | fltk::enums::Align::Inside | fltk::enums::Align::Wrap);
+    
+    let config_cards = config.clone();
+    let mut card_browser_add = card_browser.clone();
+    card_add_btn.set_callback(move |_| {
+        let tag_id: String = card_tag_input.value().chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+        if tag_id.is_empty() || !tag_id.chars().all(|c| c.is_ascii_hexdigit()) {
+            dialog::alert(300, 300, &tr("err-card-tag"));
+            return;
+        }
+        let action = match command_cards::CommandAction::from_index(card_action_choice.value()) {
+            Some(action) => action,
+            None => return,
+        };
+        
+        // a tag runs one action, adding it again replaces the old one
+        let mut config = config_cards.borrow_mut();
+        config.command_cards.retain(|card| card.tag_id != tag_id);
+        config.command_cards.push(command_cards::CommandCard { tag_id, action });
+        save_command_cards(&config);
+        fill_command_card_browser(&mut card_browser_add, &config.command_cards);
+    });
+    
+    let config_cards_remove = config.clone();
+    card_remove_btn.set_callback(move |_| {
+        let line = card_browser.value();
+        if line == 0 {
+            dialog::alert(300, 300, &tr("err-card-select"));
+            return;
+        }
+        let mut config = config_cards_remove.borrow_mut();
+        if (line as usize) <= config.command_cards.len() {
+            config.command_cards.remove(line as usize - 1);
+        }
+        save_command_cards(&config);
+        fill_command_card_browser(&mut card_browser, &config.command_cards);
+    });
+    
+    cards_tab.end();
+    
+    // this is the scan rules tab, rules apply in list order and are saved right away
+    let rules_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-rules"));
+    
+    let rule_name_input = fltk::input::Input::new(65, 45, 315, 25, None).with_label(&tr("prefs-rule-name"));
+    let mut rule_condition_choice = fltk::menu::Choice::new(65, 75, 150, 25, None).with_label(&tr("prefs-rule-when"));
+    for name in RuleCondition::kind_names() {
+        rule_condition_choice.add_choice(&name.replace('/', "\\/"));
+    }
+    rule_condition_choice.set_value(0);
+    let mut rule_condition_input = fltk::input::Input::new(220, 75, 160, 25, None);
+    rule_condition_input.set_tooltip(&tr("prefs-rule-when-tip"));
+    let mut rule_action_choice = fltk::menu::Choice::new(65, 105, 150, 25, None).with_label(&tr("prefs-rule-do"));
+    for name in RuleAction::kind_names() {
+        rule_action_choice.add_choice(&name.replace('/', "\\/"));
+    }
+    rule_action_choice.set_value(0);
+    let mut rule_action_input = fltk::input::Input::new(220, 105, 160, 25, None);
+    rule_action_input.set_tooltip(&tr("prefs-rule-do-tip"));
+    
+    let mut rule_add_btn = fltk::button::Button::new(65, 135, 70, 25, None).with_label(&tr("btn-add"));
+    let mut rule_update_btn = fltk::button::Button::new(140, 135, 70, 25, None).with_label(&tr("btn-update"));
+    let mut rule_remove_btn = fltk::button::Button::new(215, 135, 70, 25, None).with_label(&tr("btn-remove"));
+    let mut rule_up_btn = fltk::button::Button::new(300, 135, 35, 25, "@8->");
+    let mut rule_down_btn = fltk::button::Button::new(345, 135, 35, 25, "@2->");
+    
+    let mut rule_browser = fltk::browser::HoldBrowser::new(20, 165, 360, 105, "");
+    fill_rule_browser(&mut rule_browser, &config.borrow().scan_rules);
+    
+    // this reads the rule being edited, or explains what is wrong with it
+    let rule_fields = {
+        let rule_name_input = rule_name_input.clone();
+        let rule_condition_choice = rule_condition_choice.clone();
+        let rule_condition_input = rule_condition_input.clone();
+        let rule_action_choice = rule_action_choice.clone();
+        let rule_action_input = rule_action_input.clone();
+        Rc::new(move || -> Result<ScanRule, String> {
+            let name = rule_name_input.value().trim().to_string();
+            if name.is_empty() {
+                return Err(tr("err-rule-name"));
+            }
+            Ok(ScanRule {
+                name,
+                condition: RuleCondition::from_parts(rule_condition_choice.value(), &rule_condition_input.value())?,
+                action: RuleAction::from_parts(rule_action_choice.value(), &rule_action_input.value())?,
+            })
+        })
+    };
+    
+    // this loads the selected rule into the fields for editing
+    {
+        let config_rules = config.clone();
+        let mut rule_name_input = rule_name_input.clone();
+        let mut rule_condition_choice = rule_condition_choice.clone();
+        let mut rule_condition_input = rule_condition_input.clone();
+        let mut rule_action_choice = rule_action_choice.clone();
+        let mut rule_action_input = rule_action_input.clone();
+        rule_browser.set_callback(move |browser| {
+            let config = config_rules.borrow();
+            let rule = match (browser.value() as usize).checked_sub(1).and_then(|index| config.scan_rules.get(index)) {
+                Some(rule) => rule,
+                None => return,
+            };
+            rule_name_input.set_value(&rule.name);
+            rule_condition_choice.set_value(rule.condition.kind_index());
+            rule_condition_input.set_value(&rule.condition.value());
+            rule_action_choice.set_value(rule.action.kind_index());
+            rule_action_input.set_value(&rule.action.value());
+        });
+    }
+    
+    {
+        let config_rules = config.clone();
+        let rule_fields = rule_fields.clone();
+        let mut rule_browser = rule_browser.clone();
+        rule_add_btn.set_callback(move |_| {
+            match rule_fields() {
+                Ok(rule) => {
+                    let mut config = config_rules.borrow_mut();
+                    config.scan_rules.push(rule);
+                    save_scan_rules(&config);
+                    fill_rule_browser(&mut rule_browser, &config.scan_rules);
+                    rule_browser.select(config.scan_rules.len() as i32);
+                },
+                Err(e) => dialog::alert(300, 300, &e),
+            }
+        });
+    }
+    
+    {
+        let config_rules = config.clone();
+        let mut rule_browser = rule_browser.clone();
+        rule_update_btn.set_callback(move |_| {
+            let line = rule_browser.value();
+            if line == 0 {
+                dialog::alert(300, 300, &tr("err-rule-select"));
+                return;
+            }
+            match rule_fields() {
+                Ok(rule) => {
+                    let mut config = config_rules.borrow_mut();
+                    if let Some(existing) = config.scan_rules.get_mut(line as usize - 1) {
+                        *existing = rule;
+                    }
+                    save_scan_rules(&config);
+                    fill_rule_browser(&mut rule_browser, &config.scan_rules);
+                    rule_browser.select(line);
+                },
+                Err(e) => dialog::alert(300, 300, &e),
+            }
+        });
+    }
+    
+    {
+        let config_rules = config.clone();
+        let mut rule_browser = rule_browser.clone();
+        rule_remove_btn.set_callback(move |_| {
+            let line = rule_browser.value();
+            if line == 0 {
+                dialog::alert(300, 300, &tr("err-rule-select"));
+                return;
+            }
+            let mut config = config_rules.borrow_mut();
+            if (line as usize) <= config.scan_rules.len() {
+                config.scan_rules.remove(line as usize - 1);
+            }
+            save_scan_rules(&config);
+            fill_rule_browser(&mut rule_browser, &config.scan_rules);
+        });
+    }
+    
+    // these move the selected rule up or down, earlier rules run first
+    for (button, step) in [(&mut rule_up_btn, -1), (&mut rule_down_btn, 1)] {
+        let config_rules = config.clone();
+        let mut rule_browser = rule_browser.clone();
+        button.set_callback(move |_| {
+            let line = rule_browser.value();
+            let mut config = config_rules.borrow_mut();
+            let target = line + step;
+            if line == 0 || target < 1 || target as usize > config.scan_rules.len() {
+                return;
+            }
+            config.scan_rules.swap(line as usize - 1, target as usize - 1);
+            save_scan_rules(&config);
+            fill_rule_browser(&mut rule_browser, &config.scan_rules);
+            rule_browser.select(target);
+        });
+    }
+    
+    rules_tab.end();
+    
+    // this is the ERP integrations tab, each one is edited in its own window
+    let integrations_tab = fltk::group::Group::new(10, 35, 380, 245, None).with_label(&tr("prefs-tab-integrations"));
+    
+    let mut integration_browser = fltk::browser::HoldBrowser::new(20, 45, 360, 160, "");
+    fill_integration_browser(&mut integration_browser, &config.borrow().integrations);
+    
+    let mut integration_add_btn = fltk::button::Button::new(20, 215, 80, 25, None).with_label(&tr("btn-add"));
+    let mut integration_edit_btn = fltk::button::Button::new(105, 215, 80, 25, None).with_label(&tr("integration-edit"));
+    let mut integration_remove_btn = fltk::button::Button::new(190, 215, 80, 25, None).with_label(&tr("btn-remove"));
+    let mut integration_retry_btn = fltk::button::Button::new(275, 215, 105, 25, None).with_label(&tr("integration-retry"));
+    
+    // this opens the editor, the list is filled again once it saves
+    let edit_integration = {
+        let config = config.clone();
+        let integration_browser = integration_browser.clone();
+        move |index: Option<usize>| {
+            let config_saved = config.clone();
+            let mut browser_saved = integration_browser.clone();
+            super::integration_editor::show_integration_editor(&config, index, move || {
+                fill_integration_browser(&mut browser_saved, &config_saved.borrow().integrations);
+            });
+        }
+    };
+    
+    let edit_new = edit_integration.clone();
+    integration_add_btn.set_callback(move |_| edit_new(None));
+    
+    let integration_browser_edit = integration_browser.clone();
+    integration_edit_btn.set_callback(move |_| {
+        match (integration_browser_edit.value() as usize).checked_sub(1) {
+            Some(index) => edit_integration(Some(index)),
+            None => dialog::alert(300, 300, &tr("integration-err-select")),
+        }
+    });
+    
+    let config_integrations = config.clone();
+    let mut integration_browser_remove = integration_browser.clone();
+    integration_remove_btn.set_callback(move |_| {
+        let line = integration_browser_remove.value();
+        let index = (line as usize).checked_sub(1);
+        let name = index.and_then(|index| config_integrations.borrow().integrations.get(index).map(|integration| integration.name.clone()));
+        let (index, name) = match (index, name) {
+            (Some(index), Some(name)) => (index, name),
+            _ => {
+                dialog::alert(300, 300, &tr("integration-err-select"));
+                return;
+            }
+        };
+        let question = tr_args("integration-remove-confirm", &[("name", &name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-remove"), "") != Some(1) {
+            return;
+        }
+        let mut config = config_integrations.borrow_mut();
+        config.integrations.remove(index);
+        save_integrations(&config);
+        fill_integration_browser(&mut integration_browser_remove, &config.integrations);
+    });
+    
+    let config_retry = config.clone();
+    integration_retry_btn.set_callback(move |_| {
+        integrations::retry_now();
+        fill_integration_browser(&mut integration_browser, &config_retry.borrow().integrations);
+    });
+    
+    integrations_tab.end();
+    
     tabs.end();
     
     // these buttons make sure the user can save or cancel their changes
-    let mut ok_button = fltk::button::Button::new(220, 260, 80, 30, "OK");
-    let mut cancel_button = fltk::button::Button::new(310, 260, 80, 30, "Cancel");
+    let mut ok_button = fltk::button::Button::new(220, 290, 80, 30, None).with_label(&tr("btn-ok"));
+    let mut cancel_button = fltk::button::Button::new(310, 290, 80, 30, None).with_label(&tr("btn-cancel"));
+    let mut reset_button = fltk::button::Button::new(10, 290, 140, 30, None).with_label(&tr("prefs-reset"));
     
     prefs_win_rc.borrow_mut().end();
     prefs_win_rc.borrow_mut().show();
@@ -293,23 +866,51 @@ fn show_preferences_dialog(
     
     // this is for cloning the window to hide it after the OK button is clicked
     let prefs_win_ok = prefs_win_rc.clone();
+    let sender_ok = sender.clone();
     ok_button.set_callback(move |_| {
         // this config is mutable because we are changing the settings
         let mut config = config_clone_ok.borrow_mut();
         config.save_logs = save_logs_check.is_checked();
         config.log_directory = log_dir_input.value();
         config.default_keyboard_layout = layout_choice.value();
+        // an empty or invalid entry keeps the current cooldown
+        if let Ok(seconds) = cooldown_input.value().trim().parse::<u64>() {
+            config.scan_cooldown_secs = seconds;
+        }
+        let language = match language_choice.value() {
+            index if index > 0 => i18n::LANGUAGES[index as usize - 1].0.to_string(),
+            _ => i18n::AUTO_LANGUAGE.to_string(),
+        };
+        let language_changed = language != config.language;
+        config.language = language;
+        config.theme = Theme::from_index(theme_choice.value());
+        if config.theme != theme::current_theme() {
+            theme::apply_theme(config.theme);
+        }
         
         // these are the Google Drive sync settings
         config.gdrive_sync_enabled = gdrive_enable_check.is_checked();
         config.gdrive_sync_folder = gdrive_folder_input.value();
         
+        // these are the 125 kHz reader settings, used the next time capture starts
+        config.rdm6300_enabled = rdm6300_enable_check.is_checked();
+        config.rdm6300_port = rdm6300_port_input.value();
+        
+        // these are the scan feedback settings, an empty or invalid pin turns that output off
+        config.feedback_enabled = feedback_enable_check.is_checked();
+        config.feedback_buzzer_pin = buzzer_pin_input.value().trim().parse().ok();
+        config.feedback_led_red_pin = red_pin_input.value().trim().parse().ok();
+        config.feedback_led_green_pin = green_pin_input.value().trim().parse().ok();
+        if let Err(e) = crate::reader::feedback::configure(&config) {
+            dialog::alert(300, 300, &e);
+        }
+        
         // it creates the Google Drive sync folder if it doesn't exist
         if config.gdrive_sync_enabled {
             let gdrive_path = std::path::Path::new(&config.gdrive_sync_folder);
             if !gdrive_path.exists() {
                 if let Err(e) = std::fs::create_dir_all(&config.gdrive_sync_folder) {
-                    dialog::alert(300, 300, &format!("Error creating Google Drive sync folder: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-gdrive-folder", &[("error", &e.to_string())]));
                 }
             }
         }
@@ -317,15 +918,113 @@ fn show_preferences_dialog(
         // time to save the config underscore is used to ignore the result
         let _ = config::save_config(&config);
         
+        // keeps the shared config in step so reader settings apply without a restart
+        if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+            *app_config = config.clone();
+        }
+        
         // updates the keyboard layout and mutable because we are changing it
         *keyboard_layout_ok.borrow_mut() = config.default_keyboard_layout;
         
+        // the menus are rebuilt by the event loop, outside this callback
+        if language_changed {
+            sender_ok.send(AppEvent::LanguageChanged);
+        }
+        
         prefs_win_ok.borrow_mut().hide();
     });
     
+    // this puts every setting back to its default, after asking since it can't be undone
+    let config_reset = config.clone();
+    let keyboard_layout_reset = keyboard_layout.clone();
+    let prefs_win_reset = prefs_win_rc.clone();
+    let sender_reset = sender.clone();
+    reset_button.set_callback(move |_| {
+        if dialog::choice2(300, 300, &tr("prefs-reset-confirm"), &tr("btn-cancel"), &tr("prefs-reset"), "") != Some(1) {
+            return;
+        }
+        
+        let mut config = config_reset.borrow_mut();
+        let old_language = config.language.clone();
+        if let Err(e) = config::reset_config(&mut config) {
+            dialog::alert(300, 300, &tr_args("err-save-config", &[("error", &e.to_string())]));
+        }
+        
+        // the defaults apply right away, like settings changed with OK
+        if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+            *app_config = config.clone();
+        }
+        *keyboard_layout_reset.borrow_mut() = config.default_keyboard_layout;
+        if config.theme != theme::current_theme() {
+            theme::apply_theme(config.theme);
+        }
+        if let Err(e) = crate::reader::feedback::configure(&config) {
+            dialog::alert(300, 300, &e);
+        }
+        if config.language != old_language {
+            sender_reset.send(AppEvent::LanguageChanged);
+        }
+        
+        prefs_win_reset.borrow_mut().hide();
+    });
+    
     // this clones the window to hide it after the cancel button is clicked
     let prefs_win_cancel = prefs_win_rc.clone();
     cancel_button.set_callback(move |_| {
         prefs_win_cancel.borrow_mut().hide();
     });
-}
\ No newline at end of file
+}
+
+// this lists the command cards with their actions in the preferences browser
+fn fill_command_card_browser(browser: &mut fltk::browser::HoldBrowser, cards: &[command_cards::CommandCard]) {
+    browser.clear();
+    for card in cards {
+        browser.add(&format!("{}  {} ({})", card.tag_id, card.action.name(), card.action.hotkey_label()));
+    }
+}
+
+// this saves the command cards and hands them to the capture window right away
+fn save_command_cards(config: &config::AppConfig) {
+    if let Err(e) = config::save_config(config) {
+        dialog::alert(300, 300, &tr_args("err-save-config", &[("error", &e.to_string())]));
+    }
+    if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+        app_config.command_cards = config.command_cards.clone();
+    }
+}
+
+// this lists the scan rules in the order they run
+fn fill_rule_browser(browser: &mut fltk::browser::HoldBrowser, rules: &[ScanRule]) {
+    browser.clear();
+    for (index, rule) in rules.iter().enumerate() {
+        browser.add(&format!("{}. {}", index + 1, rule.describe()));
+    }
+}
+
+// this lists the integrations with how their pushes are going
+fn fill_integration_browser(browser: &mut fltk::browser::HoldBrowser, integrations: &[Integration]) {
+    browser.clear();
+    for integration in integrations {
+        browser.add(&integration.describe(&integrations::status(&integration.name)));
+    }
+}
+
+// this saves the integrations and hands them to the pushes right away
+fn save_integrations(config: &config::AppConfig) {
+    if let Err(e) = config::save_config(config) {
+        dialog::alert(300, 300, &tr_args("err-save-config", &[("error", &e.to_string())]));
+    }
+    if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+        app_config.integrations = config.integrations.clone();
+    }
+}
+
+// this saves the scan rules and hands them to the scan handlers right away
+fn save_scan_rules(config: &config::AppConfig) {
+    if let Err(e) = config::save_config(config) {
+        dialog::alert(300, 300, &tr_args("err-save-config", &[("error", &e.to_string())]));
+    }
+    if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+        app_config.scan_rules = config.scan_rules.clone();
+    }
+}
@@ -0,0 +1,108 @@
+// app/expiry_report.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    input::IntInput,
+    prelude::*,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::expiry;
+use crate::inventory::{InventoryItem, InventoryUI};
+
+// this lists the items that expire within the days asked for, expired ones first, and
+// saves the list as CSV
+pub fn show_expiry_report(inventory_ui: &Rc<InventoryUI>) {
+    let mut win = Window::new(200, 100, 640, 440, None).with_label(&tr("expiry-title"));
+    win.make_modal(true);
+
+    let mut days_input = IntInput::new(220, 15, 70, 25, None).with_label(&tr("expiry-within"));
+    days_input.set_value(&expiry::warning_days().to_string());
+    let mut show_btn = Button::new(300, 15, 90, 25, None).with_label(&tr("btn-update"));
+
+    let mut report_browser = HoldBrowser::new(20, 55, 600, 320, "");
+    report_browser.set_column_widths(&[100, 150, 200, 60]);
+    report_browser.set_column_char('\t');
+
+    let mut save_btn = Button::new(420, 390, 100, 30, None).with_label(&tr("btn-save"));
+    let mut close_btn = Button::new(530, 390, 90, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let expiring: Rc<RefCell<Vec<(InventoryItem, i64)>>> = Rc::new(RefCell::new(Vec::new()));
+    let reload = {
+        let inventory_ui = inventory_ui.clone();
+        let expiring = expiring.clone();
+        let days_input = days_input.clone();
+        move || {
+            let mut report_browser = report_browser.clone();
+            report_browser.clear();
+            let within = match days_input.value().trim().parse::<u32>() {
+                Ok(days) => days,
+                Err(_) => {
+                    dialog::alert(300, 300, &tr("err-rule-days"));
+                    return;
+                }
+            };
+            let items = match inventory_ui.inventory_db.borrow().get_all_items() {
+                Ok(items) => items,
+                Err(e) => {
+                    dialog::alert(300, 300, &tr_args("err-expiry-report", &[("error", &e.to_string())]));
+                    return;
+                }
+            };
+            *expiring.borrow_mut() = expiry::expiring_items(&items, expiry::today(), within);
+            for (item, days) in expiring.borrow().iter() {
+                report_browser.add(&report_line(item, *days));
+            }
+            if report_browser.size() == 0 {
+                report_browser.add(&tr_args("msg-nothing-expiring", &[("days", &within.to_string())]));
+            }
+        }
+    };
+    reload();
+
+    let reload_show = reload.clone();
+    show_btn.set_callback(move |_| reload_show());
+    days_input.set_trigger(fltk::enums::CallbackTrigger::EnterKey);
+    days_input.set_callback(move |_| reload());
+
+    save_btn.set_callback(move |_| {
+        let path = match dialog::file_chooser(&tr("title-expiry-save"), "*.csv", ".", false) {
+            Some(path) => path,
+            None => return,
+        };
+        match fs::write(&path, expiry::report_csv(&expiring.borrow())) {
+            Ok(()) => dialog::message(300, 300, &tr_args("msg-expiry-saved", &[("path", &path)])),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-expiry-report", &[("error", &e.to_string())])),
+        }
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows the expiry date, how far off it is, the item and its quantity
+fn report_line(item: &InventoryItem, days: i64) -> String {
+    let when = match days {
+        days if days < 0 => tr_args("expiry-expired", &[("days", &(-days).to_string())]),
+        0 => tr("expiry-today"),
+        days => tr_args("expiry-days-left", &[("days", &days.to_string())]),
+    };
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        item.expiry_date.as_deref().unwrap_or_default(),
+        when,
+        item.name,
+        item.quantity,
+        item.location.as_deref().unwrap_or_default()
+    )
+}
@@ -2,7 +2,6 @@
 use fltk::{
     app,
     prelude::*,
-    window::Window,
     group::Tabs,
     enums::Align,
 };
@@ -10,26 +9,55 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use crate::config;
+use crate::i18n::{tr, tr_args};
 use crate::app::menu;
 use crate::app::events;
 use crate::inventory::InventoryUI;
 use crate::reader;
+use crate::ui::layout;
 
 pub fn run() {
     let app = app::App::default();
-    let mut wind = Window::new(100, 100, 800, 600, "Mifare Reader Utility");
+    
+    // A panic leaves a crash report, and the outputs and database are released on any exit
+    crate::shutdown::install_panic_hook();
+    crate::shutdown::install_signal_handlers();
+    
+    // Load configuration, the language is needed before any text is shown
+    let app_config = Rc::new(RefCell::new(config::load_config()));
+    crate::i18n::set_language(&app_config.borrow().language);
+    crate::ui::theme::apply_theme(app_config.borrow().theme);
+    
+    // Settings that couldn't be used were put back to their defaults
+    let config_problems = config::load_problems();
+    if !config_problems.is_empty() {
+        let message = tr_args("err-config-problems", &[("problems", &config_problems.join("\n"))]);
+        println!("{}", message);
+        fltk::dialog::alert(300, 300, &message);
+    }
+    let overridden = config::file::overridden_settings();
+    if !overridden.is_empty() {
+        println!("{}", tr_args("log-config-overrides", &[("settings", &overridden.join(", "))]));
+    }
+    
+    // Saved size and position, fitted to the screen
+    let mut wind = layout::create_main_window(&app_config.borrow());
+    let (width, height) = (wind.w(), wind.h());
     
     // Create menu and get the receiver for events
     let (receiver, menu_items) = menu::create_menu(&mut wind);
     
     // Create tabs - positioned just below the menu bar
-    let mut tabs = Tabs::new(0, 25, 800, 575, "");
+    let mut tabs = Tabs::new(
+        0,
+        layout::MENU_HEIGHT,
+        width,
+        height - layout::MENU_HEIGHT - layout::STATUS_HEIGHT,
+        "",
+    );
     // Make sure tabs are aligned to the top and visible
     tabs.set_tab_align(Align::Top);
     
-    // Load configuration
-    let app_config = Rc::new(RefCell::new(config::load_config()));
-    
     // Create shared state for keyboard layout selection
     let keyboard_layout = Rc::new(RefCell::new(app_config.borrow().default_keyboard_layout));
     
@@ -41,29 +69,60 @@ pub fn run() {
     crate::ui::create_conversion_tab(&mut tabs, keyboard_layout.clone());
     crate::ui::create_batch_tab(&mut tabs, keyboard_layout.clone());
     
+    // Scans are kept in the inventory database, apart from the inventory itself
+    if let Err(e) = crate::history::open_history(crate::inventory::DB_PATH) {
+        println!("{}", tr_args("err-history-open", &[("error", &e.to_string())]));
+    }
+    crate::history::create_history_tab(&mut tabs);
+    
     // Initialize inventory database
-    let inventory_ui = match initialize_inventory_database("inventory.db") {
+    let inventory_ui = match initialize_inventory_database(crate::inventory::DB_PATH) {
         Ok(ui) => ui,
         Err(_) => {
             // Error already handled in function
+            crate::shutdown::shut_down();
             return;
         }
     };
     
+    // A copy of the database once a day, imports take their own
+    crate::inventory::backup::schedule_daily_backups(inventory_ui.inventory_db.clone());
+    // Deleted items past their time in the trash are purged
+    crate::inventory::trash::schedule_trash_purge(inventory_ui.inventory_db.clone());
+    // Stock changes are pushed to the configured ERP integrations
+    crate::integrations::schedule_pushes(inventory_ui.inventory_db.clone());
+    
     // Setup import directories
-    setup_directories();
+    setup_directories(&app_config.borrow());
+    
+    // Files dropped into the import directory are imported as they arrive
+    #[cfg(feature = "sync")]
+    crate::sync::watch_import_directory(&app_config.borrow(), menu_items.sender);
     
     tabs.end();
     
+    // Reader health and session statistics along the bottom of the window
+    crate::ui::create_status_bar(
+        0,
+        height - layout::STATUS_HEIGHT,
+        width,
+        layout::STATUS_HEIGHT,
+        crate::inventory::DB_PATH,
+    );
+    
     // Force a redraw to ensure UI updates
     app::redraw();
     
     wind.end();
+    
+    // Only the tabs stretch with the window, the menu and status bar keep their height
+    wind.resizable(&tabs);
     wind.show();
     
-    println!("Main window shown");
+    println!("{}", tr("log-window-shown"));
     
     // Start the event loop
+    let layout_config = app_config.clone();
     events::run_event_loop(
         app,
         receiver,
@@ -73,38 +132,41 @@ pub fn run() {
         inventory_ui,
         menu_items
     );
+    
+    // Reopen at the same place next time
+    layout::save_window_geometry(&wind, &mut layout_config.borrow_mut());
+    crate::shutdown::shut_down();
 }
 
 fn initialize_inventory_database(db_path: &str) -> Result<Rc<InventoryUI>, ()> {
     match InventoryUI::new(db_path) {
         Ok(ui) => {
-            println!("Successfully initialized inventory database");
+            println!("{}", tr("log-inventory-ready"));
             let ui_rc = Rc::new(ui);
             // Set the global inventory reference so reader.rs can access it
             reader::set_inventory_ui(&ui_rc);
             Ok(ui_rc)
         },
         Err(e) => {
-            println!("Error initializing inventory database: {}", e);
-            fltk::dialog::alert(300, 300, &format!("Error initializing inventory database: {}", e));
+            let message = tr_args("err-inventory-init", &[("error", &e.to_string())]);
+            println!("{}", message);
+            fltk::dialog::alert(300, 300, &message);
             Err(())
         }
     }
 }
 
-fn setup_directories() {
+fn setup_directories(config: &config::AppConfig) {
     // Ensure import directories exist
-    let import_dir = "./import";
-    let processed_dir = "./processed";
-    let error_dir = "./error";
+    let dirs = config.sync_dirs();
     
     // Create directories if they don't exist
-    for dir in &[import_dir, processed_dir, error_dir] {
+    for dir in [dirs.import_dir.as_str(), dirs.processed_dir.as_str(), dirs.error_dir.as_str()] {
         if !std::path::Path::new(dir).exists() {
             if let Err(e) = std::fs::create_dir_all(dir) {
-                println!("Error creating directory {}: {}", dir, e);
+                println!("{}", tr_args("err-create-dir", &[("dir", dir), ("error", &e.to_string())]));
             } else {
-                println!("Created directory: {}", dir);
+                println!("{}", tr_args("log-created-dir", &[("dir", dir)]));
             }
         }
     }
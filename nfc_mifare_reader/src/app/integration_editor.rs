@@ -0,0 +1,225 @@
+// app/integration_editor.rs
+use fltk::{
+    button::{Button, CheckButton},
+    dialog,
+    enums::{Align, Font},
+    frame::Frame,
+    input::{Input, MultilineInput},
+    menu::Choice,
+    prelude::*,
+    text::{TextBuffer, TextDisplay, TextEditor},
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config;
+use crate::i18n::{tr, tr_args};
+use crate::integrations::{self, Integration, StockEvent, ALL_EVENT_KINDS, FIELDS, METHODS, PRESETS};
+
+// this edits the integration at `index`, or a new one when there is none, and calls
+// `on_saved` once it is saved
+pub fn show_integration_editor(
+    config: &Rc<RefCell<config::AppConfig>>,
+    index: Option<usize>,
+    on_saved: impl Fn() + 'static
+) {
+    let mut win = Window::new(220, 60, 640, 600, None).with_label(&tr("integration-title"));
+    win.make_modal(true);
+
+    let mut preset_choice = Choice::new(120, 15, 200, 25, None).with_label(&tr("integration-preset"));
+    for preset in &PRESETS {
+        preset_choice.add_choice(preset.name);
+    }
+    let enabled_check = CheckButton::new(340, 15, 200, 25, None).with_label(&tr("integration-enabled"));
+
+    let name_input = Input::new(120, 50, 500, 25, None).with_label(&tr("integration-name"));
+    let mut method_choice = Choice::new(120, 80, 90, 25, None);
+    for method in METHODS {
+        method_choice.add_choice(method);
+    }
+    let url_input = Input::new(260, 80, 360, 25, None).with_label(&tr("integration-url"));
+    let mut headers_input = MultilineInput::new(120, 110, 500, 50, None).with_label(&tr("integration-headers"));
+    headers_input.set_tooltip(&tr("integration-headers-tip"));
+
+    let mut events_label = Frame::new(20, 170, 95, 25, None).with_label(&tr("integration-events"));
+    events_label.set_align(Align::Right | Align::Inside);
+    events_label.set_tooltip(&tr("integration-events-tip"));
+    let mut event_checks = Vec::new();
+    for (position, kind) in ALL_EVENT_KINDS.iter().enumerate() {
+        let x = 120 + (position as i32 % 3) * 168;
+        let y = 170 + (position as i32 / 3) * 25;
+        let mut check = CheckButton::new(x, y, 165, 25, None).with_label(&kind.name());
+        check.set_tooltip(&tr("integration-events-tip"));
+        event_checks.push(check);
+    }
+
+    let body_buffer = TextBuffer::default();
+    let mut body_editor = TextEditor::new(120, 230, 500, 170, None).with_label(&tr("integration-body"));
+    body_editor.set_align(Align::Left | Align::Top);
+    body_editor.set_buffer(body_buffer.clone());
+    body_editor.set_text_font(Font::Courier);
+
+    let mut help = Frame::new(120, 402, 500, 35, None)
+        .with_label(&tr_args("integration-fields", &[("fields", &FIELDS.join(", "))]));
+    help.set_align(Align::Left | Align::Inside | Align::Wrap);
+    help.set_label_size(11);
+
+    let mut test_choice = Choice::new(120, 445, 180, 25, None).with_label(&tr("integration-test-with"));
+    for kind in ALL_EVENT_KINDS {
+        test_choice.add_choice(&kind.name());
+    }
+    test_choice.set_value(1);
+    let mut test_btn = Button::new(310, 445, 150, 25, None).with_label(&tr("integration-test"));
+    let mut send_btn = Button::new(470, 445, 150, 25, None).with_label(&tr("integration-send-test"));
+
+    let result_buffer = TextBuffer::default();
+    let mut result_display = TextDisplay::new(20, 478, 600, 75, None);
+    result_display.set_buffer(result_buffer.clone());
+    result_display.set_text_font(Font::Courier);
+    result_display.set_text_size(11);
+
+    let mut save_btn = Button::new(430, 562, 90, 30, None).with_label(&tr("btn-save"));
+    let mut cancel_btn = Button::new(530, 562, 90, 30, None).with_label(&tr("btn-cancel"));
+
+    win.end();
+    win.show();
+
+    // this puts an integration's settings into the fields
+    let show_integration = {
+        let name_input = name_input.clone();
+        let enabled_check = enabled_check.clone();
+        let method_choice = method_choice.clone();
+        let url_input = url_input.clone();
+        let headers_input = headers_input.clone();
+        let event_checks = event_checks.clone();
+        let body_buffer = body_buffer.clone();
+        move |integration: &Integration| {
+            name_input.clone().set_value(&integration.name);
+            enabled_check.clone().set_checked(integration.enabled);
+            let method = METHODS.iter().position(|method| *method == integration.method).unwrap_or(0);
+            method_choice.clone().set_value(method as i32);
+            url_input.clone().set_value(&integration.url);
+            headers_input.clone().set_value(&integration.headers.join("\n"));
+            for (check, kind) in event_checks.iter().zip(ALL_EVENT_KINDS) {
+                check.clone().set_checked(integration.events.contains(&kind));
+            }
+            body_buffer.clone().set_text(&integration.body);
+        }
+    };
+
+    let existing = index.and_then(|index| config.borrow().integrations.get(index).cloned());
+    show_integration(&existing.clone().unwrap_or_else(|| PRESETS[0].integration()));
+    preset_choice.set_value(if existing.is_some() { -1 } else { 0 });
+
+    let show_preset = show_integration.clone();
+    preset_choice.set_callback(move |choice| {
+        if let Some(preset) = usize::try_from(choice.value()).ok().and_then(|index| PRESETS.get(index)) {
+            show_preset(&preset.integration());
+        }
+    });
+
+    // this reads the integration back from the fields
+    let integration_fields = {
+        let name_input = name_input.clone();
+        let body_buffer = body_buffer.clone();
+        move || Integration {
+            name: name_input.value().trim().to_string(),
+            enabled: enabled_check.is_checked(),
+            events: event_checks.iter()
+                .zip(ALL_EVENT_KINDS)
+                .filter(|(check, _)| check.is_checked())
+                .map(|(_, kind)| kind)
+                .collect(),
+            method: METHODS.get(method_choice.value().max(0) as usize).unwrap_or(&METHODS[0]).to_string(),
+            url: url_input.value().trim().to_string(),
+            headers: headers_input.value()
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            body: body_buffer.text(),
+        }
+    };
+
+    // this fills in the templates for the event picked under "Test with"
+    let test_request = {
+        let integration_fields = integration_fields.clone();
+        let test_choice = test_choice.clone();
+        move || -> Result<(String, integrations::HttpRequest), String> {
+            let integration = integration_fields();
+            let kind = ALL_EVENT_KINDS[test_choice.value().max(0) as usize % ALL_EVENT_KINDS.len()];
+            let request = integration.request(&StockEvent::sample(kind))?;
+            let note = if integration.wants(kind) {
+                String::new()
+            } else {
+                tr_args("integration-test-skipped", &[("event", &kind.name())]) + "\n"
+            };
+            Ok((note, request))
+        }
+    };
+
+    let test_mapping = test_request.clone();
+    let mut result_test = result_buffer.clone();
+    test_btn.set_callback(move |_| {
+        match test_mapping() {
+            Ok((note, request)) => result_test.set_text(&(note + &request.describe())),
+            Err(e) => result_test.set_text(&e),
+        }
+    });
+
+    let mut result_send = result_buffer;
+    send_btn.set_callback(move |_| {
+        let request = match test_request() {
+            Ok((_, request)) => request,
+            Err(e) => {
+                result_send.set_text(&e);
+                return;
+            }
+        };
+        // a test waits for the answer, at most the request timeout
+        result_send.set_text(&match integrations::send(&request) {
+            Ok(()) => tr("integration-test-sent"),
+            Err(e) => e,
+        });
+    });
+
+    let config_save = config.clone();
+    let mut win_save = win.clone();
+    save_btn.set_callback(move |_| {
+        let integration = integration_fields();
+        if let Err(e) = integration.check() {
+            dialog::alert(300, 300, &e);
+            return;
+        }
+        let mut config = config_save.borrow_mut();
+        let taken = config.integrations.iter()
+            .enumerate()
+            .any(|(position, other)| Some(position) != index && other.name == integration.name);
+        if taken {
+            dialog::alert(300, 300, &tr_args("err-integration-name-taken", &[("name", &integration.name)]));
+            return;
+        }
+        match index.and_then(|index| config.integrations.get_mut(index)) {
+            Some(existing) => *existing = integration,
+            None => config.integrations.push(integration),
+        }
+
+        if let Err(e) = config::save_config(&config) {
+            dialog::alert(300, 300, &tr_args("err-save-config", &[("error", &e.to_string())]));
+            return;
+        }
+        // the pushes read integrations from the shared config
+        if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+            app_config.integrations = config.integrations.clone();
+        }
+        drop(config);
+        on_saved();
+        win_save.hide();
+    });
+
+    let mut win_cancel = win.clone();
+    cancel_btn.set_callback(move |_| {
+        win_cancel.hide();
+    });
+}
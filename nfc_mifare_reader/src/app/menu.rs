@@ -2,34 +2,35 @@
 use fltk::{
     app,
     prelude::*,
+    enums::Shortcut,
     menu::{MenuBar, MenuFlag},
 };
 use std::rc::Rc;
 use std::cell::RefCell;
 
+use crate::app::AppEvent;
+use crate::config;
+use crate::export::ExportFormat;
+use crate::i18n::{tr, LANGUAGES};
+
 pub struct MenuItems {
     pub keyboard_layout: Rc<RefCell<i32>>,
     pub config: Rc<RefCell<crate::config::AppConfig>>,
     pub card_buffer: Rc<RefCell<fltk::text::TextBuffer>>,
     pub inventory_ui: Rc<crate::inventory::InventoryUI>,
-    
+    // kept so the menu can be rebuilt when the language changes
+    pub menu: MenuBar,
+    pub sender: app::Sender<AppEvent>,
 }
 
-pub fn create_menu(wind: &mut fltk::window::Window) -> (app::Receiver<String>, MenuItems) {
+pub fn create_menu(wind: &mut fltk::window::Window) -> (app::Receiver<AppEvent>, MenuItems) {
     // Create menu
-    let mut menu = MenuBar::new(0, 0, 800, 25, "");
+    let mut menu = MenuBar::new(0, 0, wind.w(), crate::ui::layout::MENU_HEIGHT, "");
     
     // Create a channel for menu events
-    let (sender, receiver) = app::channel::<String>();
-    
-    // Add file menu
-    add_file_menu(&mut menu, &sender);
+    let (sender, receiver) = app::channel::<AppEvent>();
     
-    // Add edit menu
-    add_edit_menu(&mut menu, &sender);
-    
-    // Add help menu
-    add_help_menu(&mut menu, &sender);
+    add_menu_items(&mut menu, &sender);
     
     // Return the receiver and empty menu items (to be populated later)
     (receiver, MenuItems {
@@ -37,144 +38,150 @@ pub fn create_menu(wind: &mut fltk::window::Window) -> (app::Receiver<String>, M
         config: Rc::new(RefCell::new(crate::config::AppConfig::default())),
         card_buffer: Rc::new(RefCell::new(fltk::text::TextBuffer::default())),
         inventory_ui: Rc::new(crate::inventory::InventoryUI::new("").unwrap()), // This will be replaced
+        menu,
+        sender,
     })
 }
 
-fn add_file_menu(menu: &mut MenuBar, sender: &app::Sender<String>) {
-    // Clone sender for each menu item
-    let sender_csv = sender.clone();
-    let sender_json = sender.clone();
-    let sender_text = sender.clone();
-    let sender_log = sender.clone();
-    let sender_exit = sender.clone();
-    let sender_import = sender.clone();
-    let sender_view_db = sender.clone();
-    let sender_check_files = sender.clone();
-    let sender_gdrive_export = sender.clone();
-    let sender_gdrive_import = sender.clone();
-    
-    // Add menu items
-    menu.add(
-        "&File/&Export Data/as &CSV\t",
-        fltk::enums::Shortcut::Ctrl | 'e',
-        MenuFlag::Normal,
-        move |_| { sender_csv.send("export_csv".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Export Data/as &JSON\t",
-        fltk::enums::Shortcut::Ctrl | 'j',
-        MenuFlag::Normal,
-        move |_| { sender_json.send("export_json".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Export Data/as &Text\t",
-        fltk::enums::Shortcut::Ctrl | 't',
-        MenuFlag::Normal,
-        move |_| { sender_text.send("export_text".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Import Data\t",
-        fltk::enums::Shortcut::Ctrl | 'i',
-        MenuFlag::Normal,
-        move |_| { sender_import.send("import_data".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&View Database\t",
-        fltk::enums::Shortcut::Ctrl | 'd',
-        MenuFlag::Normal,
-        move |_| { sender_view_db.send("view_database".to_string()); }
-    );
+/// Fill the menu bar in the current language
+pub fn add_menu_items(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    // Add file menu
+    add_file_menu(menu, sender);
     
-    menu.add(
-        "&File/&Check Import Files\t",
-        fltk::enums::Shortcut::Ctrl | 'r',
-        MenuFlag::Normal,
-        move |_| { sender_check_files.send("check_files".to_string()); }
-    );
+    // Add edit menu
+    add_edit_menu(menu, sender);
     
-    menu.add(
-        "&File/&Export to Google Drive\t",
-        fltk::enums::Shortcut::Ctrl | 'g',
-        MenuFlag::Normal,
-        move |_| { sender_gdrive_export.send("gdrive_export".to_string()); }
-    );
+    // Add help menu
+    add_help_menu(menu, sender);
+}
 
-    menu.add(
-        "&File/&Import from Google Drive\t",
-        fltk::enums::Shortcut::Ctrl | 'h',  // Using 'h' to avoid conflict with 'g'
-        MenuFlag::Normal,
-        move |_| { sender_gdrive_import.send("gdrive_import".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Save Log\t",
-        fltk::enums::Shortcut::Ctrl | 's',
-        MenuFlag::Normal,
-        move |_| { sender_log.send("save_log".to_string()); }
-    );
-    
-    menu.add(
-        "&File/E&xit\t",
-        fltk::enums::Shortcut::Ctrl | 'q',
-        MenuFlag::Normal,
-        move |_| { sender_exit.send("exit".to_string()); }
-    );
+/// Rebuild the menu bar after the language changes
+pub fn relabel_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    menu.clear();
+    add_menu_items(menu, sender);
+    menu.redraw();
 }
 
-fn add_edit_menu(menu: &mut MenuBar, sender: &app::Sender<String>) {
-    let sender_pref = sender.clone();
-    let sender_kb_auto = sender.clone();
-    let sender_kb_win = sender.clone();
-    let sender_kb_mac = sender.clone();
-    let sender_kb_intl = sender.clone();
-    
-    menu.add(
-        "&Edit/&Preferences\t",
-        fltk::enums::Shortcut::Ctrl | 'p',
-        MenuFlag::Normal,
-        move |_| { sender_pref.send("preferences".to_string()); }
-    );
-    
-    menu.add(
-        "&Edit/&Keyboard Layout/&Auto-detect\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_auto.send("kb_auto".to_string()); }
-    );
-    
-    menu.add(
-        "&Edit/&Keyboard Layout/&Windows\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_win.send("kb_windows".to_string()); }
-    );
-    
-    menu.add(
-        "&Edit/&Keyboard Layout/&Mac US\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_mac.send("kb_mac_us".to_string()); }
-    );
-    
-    menu.add(
-        "&Edit/&Keyboard Layout/Mac &International\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_intl.send("kb_mac_intl".to_string()); }
-    );
+// a '/' inside a translation must not start a submenu
+fn menu_label(key: &str) -> String {
+    tr(key).replace('/', "\\/")
 }
 
-fn add_help_menu(menu: &mut MenuBar, sender: &app::Sender<String>) {
-    let sender_about = sender.clone();
-    
+// menu path from catalogue keys
+fn menu_path(keys: &[&str]) -> String {
+    let labels: Vec<String> = keys.iter().map(|key| menu_label(key)).collect();
+    format!("{}\t", labels.join("/"))
+}
+
+fn add_item(menu: &mut MenuBar, sender: &app::Sender<AppEvent>, keys: &[&str], shortcut: Shortcut, event: AppEvent) {
+    let sender = sender.clone();
     menu.add(
-        "&Help/&About\t",
-        fltk::enums::Shortcut::None,
+        &menu_path(keys),
+        shortcut,
         MenuFlag::Normal,
-        move |_| { sender_about.send("about".to_string()); }
+        move |_| { sender.send(event.clone()); }
     );
 }
+
+fn add_file_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    add_item(menu, sender, &["menu-file", "menu-export", "menu-export-csv"], Shortcut::Ctrl | 'e', AppEvent::ExportScans(ExportFormat::CSV));
+    add_item(menu, sender, &["menu-file", "menu-export", "menu-export-json"], Shortcut::Ctrl | 'j', AppEvent::ExportScans(ExportFormat::JSON));
+    add_item(menu, sender, &["menu-file", "menu-export", "menu-export-text"], Shortcut::Ctrl | 't', AppEvent::ExportScans(ExportFormat::Text));
+    add_item(menu, sender, &["menu-file", "menu-import"], Shortcut::Ctrl | 'i', AppEvent::ImportData);
+    add_item(menu, sender, &["menu-file", "menu-view-database"], Shortcut::Ctrl | 'd', AppEvent::ViewDatabase);
+    #[cfg(feature = "sync")]
+    add_item(menu, sender, &["menu-file", "menu-check-files"], Shortcut::Ctrl | 'r', AppEvent::CheckImportFiles);
+    add_item(menu, sender, &["menu-file", "menu-restore-backup"], Shortcut::None, AppEvent::RestoreBackup);
+    add_item(menu, sender, &["menu-file", "menu-expiry-report"], Shortcut::None, AppEvent::ExpiryReport);
+    #[cfg(feature = "sync")]
+    add_item(menu, sender, &["menu-file", "menu-gdrive", "menu-gdrive-export"], Shortcut::None, AppEvent::GDriveExport);
+    #[cfg(feature = "sync")]
+    add_item(menu, sender, &["menu-file", "menu-gdrive", "menu-gdrive-import"], Shortcut::None, AppEvent::GDriveImport);
+    #[cfg(feature = "sync")]
+    add_item(menu, sender, &["menu-file", "menu-gdrive", "menu-gdrive-sync"], Shortcut::None, AppEvent::StationSync);
+    add_signature_menu(menu, sender);
+    add_item(menu, sender, &["menu-file", "menu-save-log"], Shortcut::Ctrl | 's', AppEvent::SaveLog);
+    add_item(menu, sender, &["menu-file", "menu-exit"], Shortcut::Ctrl | 'q', AppEvent::Exit);
+}
+
+// the toggle is ticked while exports are signed
+fn add_signature_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    let signing = config::APP_CONFIG.lock().map(|config| config.sign_exports).unwrap_or(false);
+    let toggle_sender = sender.clone();
+    let index = menu.add(
+        &menu_path(&["menu-file", "menu-signatures", "menu-sign-exports"]),
+        Shortcut::None,
+        MenuFlag::Toggle,
+        move |_| { toggle_sender.send(AppEvent::ToggleSignExports); }
+    );
+    if signing {
+        if let Some(mut item) = menu.at(index) {
+            item.set();
+        }
+    }
+    
+    add_item(menu, sender, &["menu-file", "menu-signatures", "menu-verify-signature"], Shortcut::None, AppEvent::VerifySignature);
+    add_item(menu, sender, &["menu-file", "menu-signatures", "menu-signing-key"], Shortcut::None, AppEvent::ShowSigningKey);
+}
+
+fn add_edit_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    add_item(menu, sender, &["menu-edit", "menu-preferences"], Shortcut::Ctrl | 'p', AppEvent::Preferences);
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-auto"], Shortcut::None, AppEvent::SetKeyboardLayout(0));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-windows"], Shortcut::None, AppEvent::SetKeyboardLayout(1));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-mac-us"], Shortcut::None, AppEvent::SetKeyboardLayout(2));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-mac-intl"], Shortcut::None, AppEvent::SetKeyboardLayout(3));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-azerty"], Shortcut::None, AppEvent::SetKeyboardLayout(4));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-qwertz"], Shortcut::None, AppEvent::SetKeyboardLayout(5));
+    add_item(menu, sender, &["menu-edit", "menu-keyboard", "menu-kb-calibrate"], Shortcut::None, AppEvent::CalibrateKeyboard);
+    add_item(menu, sender, &["menu-edit", "menu-reader-config"], Shortcut::None, AppEvent::ReaderConfig);
+    add_profile_menu(menu, sender);
+    
+    // language names are shown in their own language, not translated
+    for (code, name) in LANGUAGES {
+        let sender = sender.clone();
+        menu.add(
+            &format!("{}/{}/{}\t", menu_label("menu-edit"), menu_label("menu-language"), name),
+            Shortcut::None,
+            MenuFlag::Normal,
+            move |_| { sender.send(AppEvent::SetLanguage(code.to_string())); }
+        );
+    }
+}
+
+// one radio item per profile with the active one ticked, then saving and deleting
+fn add_profile_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    let (profiles, active) = match config::APP_CONFIG.lock() {
+        Ok(config) => (
+            config.profiles.iter().map(|profile| profile.name.clone()).collect::<Vec<_>>(),
+            config.active_profile.clone(),
+        ),
+        Err(_) => (Vec::new(), String::new()),
+    };
+    
+    for (i, name) in profiles.iter().enumerate() {
+        // a line under the last profile, before the actions
+        let flag = if i + 1 == profiles.len() { MenuFlag::Radio | MenuFlag::MenuDivider } else { MenuFlag::Radio };
+        // profile names are the user's own, '&' and '/' are shown as typed
+        let label = name.replace('&', "&&").replace('/', "\\/");
+        let sender = sender.clone();
+        let event = AppEvent::SwitchProfile(name.clone());
+        let index = menu.add(
+            &format!("{}/{}/{}\t", menu_label("menu-edit"), menu_label("menu-profiles"), label),
+            Shortcut::None,
+            flag,
+            move |_| { sender.send(event.clone()); }
+        );
+        if *name == active {
+            if let Some(mut item) = menu.at(index) {
+                item.set();
+            }
+        }
+    }
+    
+    add_item(menu, sender, &["menu-edit", "menu-profiles", "menu-profile-save"], Shortcut::None, AppEvent::SaveProfile);
+    add_item(menu, sender, &["menu-edit", "menu-profiles", "menu-profile-delete"], Shortcut::None, AppEvent::DeleteProfile);
+}
+
+fn add_help_menu(menu: &mut MenuBar, sender: &app::Sender<AppEvent>) {
+    add_item(menu, sender, &["menu-help", "menu-about"], Shortcut::None, AppEvent::About);
+}
@@ -1,7 +1,15 @@
 // app/mod.rs
+pub mod app_event;
 pub mod init;
 pub mod menu;
 pub mod events;
+pub mod pattern_editor;
+pub mod calibration;
+pub mod reader_config;
+pub mod restore_backup;
+pub mod expiry_report;
+pub mod integration_editor;
 
 // Re-export the run function for convenience
-pub use init::run;
\ No newline at end of file
+pub use init::run;
+pub use app_event::AppEvent;
\ No newline at end of file
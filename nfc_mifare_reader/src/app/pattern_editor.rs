@@ -0,0 +1,181 @@
+// app/pattern_editor.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, CallbackTrigger, FrameType},
+    frame::Frame,
+    input::Input,
+    prelude::*,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::config::{self, FormatPattern};
+use crate::config::patterns::test_pattern;
+use crate::i18n::{tr, tr_args};
+
+// this shows the regex format pattern editor opened from Preferences
+pub fn show_pattern_editor(config: &Rc<RefCell<config::AppConfig>>) {
+    let mut win = Window::new(250, 80, 560, 470, None).with_label(&tr("pat-title"));
+    win.make_modal(true);
+
+    // the editor works on a copy so Cancel can throw the changes away
+    let patterns = Rc::new(RefCell::new(config.borrow().format_patterns.clone()));
+
+    let mut browser = HoldBrowser::new(20, 20, 520, 150, "");
+
+    let name_input = Input::new(120, 185, 420, 25, None).with_label(&tr("pat-name"));
+    let mut regex_input = Input::new(120, 215, 420, 25, None).with_label(&tr("pat-regex"));
+    let mut desc_input = Input::new(120, 245, 420, 25, None).with_label(&tr("pat-description"));
+
+    let mut help = Frame::new(20, 272, 520, 40, None).with_label(&tr("pat-help"));
+    help.set_align(Align::Left | Align::Inside | Align::Wrap);
+    help.set_label_size(11);
+
+    let mut test_input = Input::new(120, 320, 420, 25, None).with_label(&tr("pat-test-input"));
+    let mut test_result = Frame::new(120, 350, 420, 25, None).with_label(&tr("pat-enter-regex"));
+    test_result.set_frame(FrameType::DownBox);
+    test_result.set_align(Align::Left | Align::Inside);
+
+    let mut add_btn = Button::new(20, 390, 100, 30, None).with_label(&tr("btn-add"));
+    let mut update_btn = Button::new(130, 390, 100, 30, None).with_label(&tr("btn-update"));
+    let mut remove_btn = Button::new(240, 390, 100, 30, None).with_label(&tr("btn-remove"));
+    let mut save_btn = Button::new(350, 430, 90, 30, None).with_label(&tr("btn-save"));
+    let mut cancel_btn = Button::new(450, 430, 90, 30, None).with_label(&tr("btn-cancel"));
+
+    win.end();
+    win.show();
+
+    refresh_pattern_list(&mut browser, &patterns.borrow());
+
+    // live preview whenever the regex, description or test input changes
+    let update_preview = {
+        let regex_input = regex_input.clone();
+        let desc_input = desc_input.clone();
+        let test_input = test_input.clone();
+        let test_result = test_result.clone();
+        move || {
+            let mut test_result = test_result.clone();
+            test_result.set_label(&test_pattern(&regex_input.value(), &desc_input.value(), &test_input.value()));
+        }
+    };
+    for input in [&mut regex_input, &mut desc_input, &mut test_input] {
+        input.set_trigger(CallbackTrigger::Changed);
+        let update_preview = update_preview.clone();
+        input.set_callback(move |_| update_preview());
+    }
+
+    // selecting a pattern loads it into the inputs
+    let patterns_select = patterns.clone();
+    let mut name_select = name_input.clone();
+    let mut regex_select = regex_input.clone();
+    let mut desc_select = desc_input.clone();
+    let update_preview_select = update_preview.clone();
+    browser.set_callback(move |b| {
+        let line = b.value();
+        if line > 0 {
+            if let Some(pattern) = patterns_select.borrow().get(line as usize - 1) {
+                name_select.set_value(&pattern.name);
+                regex_select.set_value(&pattern.regex);
+                desc_select.set_value(&pattern.description);
+            }
+            update_preview_select();
+        }
+    });
+
+    let patterns_add = patterns.clone();
+    let mut browser_add = browser.clone();
+    let name_add = name_input.clone();
+    let regex_add = regex_input.clone();
+    let desc_add = desc_input.clone();
+    add_btn.set_callback(move |_| {
+        if let Some(pattern) = pattern_from_inputs(&name_add, &regex_add, &desc_add) {
+            patterns_add.borrow_mut().push(pattern);
+            refresh_pattern_list(&mut browser_add, &patterns_add.borrow());
+            let count = browser_add.size();
+            browser_add.select(count);
+        }
+    });
+
+    let patterns_update = patterns.clone();
+    let mut browser_update = browser.clone();
+    let name_update = name_input.clone();
+    let regex_update = regex_input.clone();
+    let desc_update = desc_input.clone();
+    update_btn.set_callback(move |_| {
+        let line = browser_update.value();
+        if line == 0 {
+            dialog::alert(300, 300, &tr("pat-err-select"));
+            return;
+        }
+        if let Some(pattern) = pattern_from_inputs(&name_update, &regex_update, &desc_update) {
+            patterns_update.borrow_mut()[line as usize - 1] = pattern;
+            refresh_pattern_list(&mut browser_update, &patterns_update.borrow());
+            browser_update.select(line);
+        }
+    });
+
+    let patterns_remove = patterns.clone();
+    let mut browser_remove = browser.clone();
+    remove_btn.set_callback(move |_| {
+        let line = browser_remove.value();
+        if line > 0 {
+            patterns_remove.borrow_mut().remove(line as usize - 1);
+            refresh_pattern_list(&mut browser_remove, &patterns_remove.borrow());
+        }
+    });
+
+    let config_save = config.clone();
+    let patterns_save = patterns.clone();
+    let mut win_save = win.clone();
+    save_btn.set_callback(move |_| {
+        let mut config = config_save.borrow_mut();
+        config.format_patterns = patterns_save.borrow().clone();
+
+        if let Err(e) = config::save_config(&config) {
+            dialog::alert(300, 300, &tr_args("pat-err-save", &[("error", &e.to_string())]));
+            return;
+        }
+
+        // the scan handlers read patterns from the shared config
+        if let Ok(mut app_config) = config::APP_CONFIG.lock() {
+            app_config.format_patterns = config.format_patterns.clone();
+        }
+
+        win_save.hide();
+    });
+
+    let mut win_cancel = win.clone();
+    cancel_btn.set_callback(move |_| {
+        win_cancel.hide();
+    });
+}
+
+// this rebuilds the browser lines from the working list of patterns
+fn refresh_pattern_list(browser: &mut HoldBrowser, patterns: &[FormatPattern]) {
+    browser.clear();
+    for pattern in patterns {
+        browser.add(&format!("{}  —  {}", pattern.name, pattern.regex));
+    }
+}
+
+// this validates the inputs and builds a pattern from them
+fn pattern_from_inputs(name: &Input, regex: &Input, description: &Input) -> Option<FormatPattern> {
+    if regex.value().is_empty() {
+        dialog::alert(300, 300, &tr("pat-err-empty"));
+        return None;
+    }
+
+    let pattern_name = if name.value().is_empty() { description.value() } else { name.value() };
+    let pattern = FormatPattern::new(&pattern_name, &regex.value(), &description.value());
+
+    match pattern.compile() {
+        Ok(_) => Some(pattern),
+        Err(e) => {
+            dialog::alert(300, 300, &tr_args("pat-err-invalid", &[("error", &e.to_string())]));
+            None
+        }
+    }
+}
@@ -0,0 +1,290 @@
+// app/reader_config.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, FrameType},
+    frame::Frame,
+    input::Input,
+    menu::Choice,
+    prelude::*,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::reader::wedge_config::{
+    self, HidDevice, ReaderProfile, ReportType, WedgeCommand, READER_PROFILES_PATH
+};
+
+// this shows the wedge reader configuration dialog opened from the Edit menu
+pub fn show_reader_config_dialog() {
+    let profiles = match wedge_config::load_profiles() {
+        Ok(profiles) => Rc::new(RefCell::new(profiles)),
+        Err(e) => {
+            dialog::alert(300, 300, &e);
+            return;
+        }
+    };
+    let devices = Rc::new(RefCell::new(wedge_config::list_hid_devices()));
+
+    let mut win = Window::new(250, 80, 560, 480, None).with_label(&tr("rc-title"));
+    win.make_modal(true);
+
+    let mut device_choice = Choice::new(90, 20, 360, 25, None).with_label(&tr("rc-reader"));
+    let mut rescan_btn = Button::new(460, 20, 80, 25, None).with_label(&tr("rc-rescan"));
+
+    let mut profile_frame = Frame::new(20, 55, 520, 25, "");
+    profile_frame.set_align(Align::Left | Align::Inside);
+
+    let mut command_browser = HoldBrowser::new(20, 85, 520, 140, "");
+    let mut send_btn = Button::new(420, 232, 120, 28, None).with_label(&tr("rc-send"));
+
+    // new commands are entered here, from the reader vendor's manual or configuration tool
+    let mut add_frame = Frame::new(20, 275, 520, 20, None).with_label(&tr("rc-add-heading"));
+    add_frame.set_align(Align::Left | Align::Inside);
+    let model_input = Input::new(140, 300, 400, 25, None).with_label(&tr("rc-model"));
+    let name_input = Input::new(140, 330, 400, 25, None).with_label(&tr("rc-command-name"));
+    let reports_input = Input::new(140, 360, 400, 25, None).with_label(&tr("rc-reports"));
+    let mut report_type_choice = Choice::new(140, 390, 120, 25, None).with_label(&tr("rc-report-type"));
+    report_type_choice.add_choice(&tr("rc-feature"));
+    report_type_choice.add_choice(&tr("rc-output"));
+    report_type_choice.set_value(0);
+    let mut help = Frame::new(270, 390, 270, 25, None).with_label(&tr("rc-reports-help"));
+    help.set_align(Align::Left | Align::Inside | Align::Wrap);
+    help.set_label_size(11);
+    help.set_frame(FrameType::NoBox);
+
+    let mut test_btn = Button::new(230, 435, 100, 30, None).with_label(&tr("rc-test"));
+    let mut save_btn = Button::new(340, 435, 100, 30, None).with_label(&tr("btn-save"));
+    let mut close_btn = Button::new(450, 435, 90, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    // refreshes the profile line and command list for the selected reader
+    let refresh_commands = {
+        let profiles = profiles.clone();
+        let devices = devices.clone();
+        let device_choice = device_choice.clone();
+        let profile_frame = profile_frame.clone();
+        let command_browser = command_browser.clone();
+        let model_input = model_input.clone();
+        let report_type_choice = report_type_choice.clone();
+        move || {
+            let mut profile_frame = profile_frame.clone();
+            let mut command_browser = command_browser.clone();
+            let mut model_input = model_input.clone();
+            let mut report_type_choice = report_type_choice.clone();
+            command_browser.clear();
+            let device = selected_device(&devices.borrow(), &device_choice);
+            let device = match device {
+                Some(device) => device,
+                None => {
+                    profile_frame.set_label(&tr("rc-no-readers"));
+                    return;
+                }
+            };
+
+            let profiles = profiles.borrow();
+            match wedge_config::find_profile(&profiles, &device.usb_id) {
+                Some(profile) => {
+                    profile_frame.set_label(&tr_args("rc-profile", &[
+                        ("model", &profile.model),
+                        ("count", &profile.commands.len().to_string()),
+                    ]));
+                    for command in &profile.commands {
+                        command_browser.add(&command.name);
+                    }
+                    model_input.set_value(&profile.model);
+                    report_type_choice.set_value(match profile.report_type {
+                        ReportType::Feature => 0,
+                        ReportType::Output => 1,
+                    });
+                },
+                None => {
+                    profile_frame.set_label(&tr_args("rc-no-profile", &[("usb_id", &device.usb_id)]));
+                    model_input.set_value(&device.name);
+                },
+            }
+        }
+    };
+
+    fill_device_choice(&mut device_choice, &devices.borrow());
+    refresh_commands();
+
+    let refresh_select = refresh_commands.clone();
+    device_choice.set_callback(move |_| refresh_select());
+
+    let devices_rescan = devices.clone();
+    let mut device_choice_rescan = device_choice.clone();
+    let refresh_rescan = refresh_commands.clone();
+    rescan_btn.set_callback(move |_| {
+        *devices_rescan.borrow_mut() = wedge_config::list_hid_devices();
+        fill_device_choice(&mut device_choice_rescan, &devices_rescan.borrow());
+        refresh_rescan();
+    });
+
+    // sends the selected command from the reader's profile
+    let profiles_send = profiles.clone();
+    let devices_send = devices.clone();
+    let device_choice_send = device_choice.clone();
+    let command_browser_send = command_browser.clone();
+    send_btn.set_callback(move |_| {
+        let device = match selected_device(&devices_send.borrow(), &device_choice_send) {
+            Some(device) => device,
+            None => return,
+        };
+        let profiles = profiles_send.borrow();
+        let profile = match wedge_config::find_profile(&profiles, &device.usb_id) {
+            Some(profile) => profile,
+            None => return,
+        };
+        let line = command_browser_send.value();
+        if line <= 0 {
+            dialog::alert(300, 300, &tr("rc-err-select"));
+            return;
+        }
+        if let Some(command) = profile.commands.get(line as usize - 1) {
+            send_and_report(&device, profile.report_type, command);
+        }
+    });
+
+    // sends the command in the inputs without saving it
+    let devices_test = devices.clone();
+    let device_choice_test = device_choice.clone();
+    let name_test = name_input.clone();
+    let reports_test = reports_input.clone();
+    let report_type_test = report_type_choice.clone();
+    test_btn.set_callback(move |_| {
+        let device = match selected_device(&devices_test.borrow(), &device_choice_test) {
+            Some(device) => device,
+            None => return,
+        };
+        match command_from_inputs(&name_test, &reports_test) {
+            Ok(command) => send_and_report(&device, report_type_from_choice(&report_type_test), &command),
+            Err(e) => dialog::alert(300, 300, &e),
+        }
+    });
+
+    // adds the command in the inputs to the reader's profile, creating the profile if needed
+    let profiles_save = profiles.clone();
+    let devices_save = devices.clone();
+    let device_choice_save = device_choice.clone();
+    let refresh_save = refresh_commands.clone();
+    let mut name_save = name_input.clone();
+    let mut reports_save = reports_input.clone();
+    save_btn.set_callback(move |_| {
+        let device = match selected_device(&devices_save.borrow(), &device_choice_save) {
+            Some(device) => device,
+            None => return,
+        };
+        let command = match command_from_inputs(&name_save, &reports_save) {
+            Ok(command) => command,
+            Err(e) => {
+                dialog::alert(300, 300, &e);
+                return;
+            }
+        };
+
+        {
+            let mut profiles = profiles_save.borrow_mut();
+            let model = model_input.value().trim().to_string();
+            let report_type = report_type_from_choice(&report_type_choice);
+            let index = match profiles.iter().position(|profile| profile.usb_id.eq_ignore_ascii_case(&device.usb_id)) {
+                Some(index) => index,
+                None => {
+                    profiles.push(ReaderProfile {
+                        model: model.clone(),
+                        usb_id: device.usb_id.clone(),
+                        report_type,
+                        commands: Vec::new(),
+                    });
+                    profiles.len() - 1
+                }
+            };
+            let profile = &mut profiles[index];
+            if !model.is_empty() {
+                profile.model = model;
+            }
+            profile.report_type = report_type;
+            // a command with the same name is replaced
+            profile.commands.retain(|existing| existing.name != command.name);
+            profile.commands.push(command);
+
+            if let Err(e) = wedge_config::save_profiles(&profiles) {
+                dialog::alert(300, 300, &tr_args("err-save-file", &[("path", READER_PROFILES_PATH), ("error", &e.to_string())]));
+                return;
+            }
+        }
+
+        name_save.set_value("");
+        reports_save.set_value("");
+        refresh_save();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+fn fill_device_choice(choice: &mut Choice, devices: &[HidDevice]) {
+    choice.clear();
+    for device in devices {
+        // '/' would make a submenu out of the device path
+        let label = format!("{}  {} ({})", device.path.trim_start_matches("/dev/"), device.name, device.usb_id);
+        choice.add_choice(&label.replace('/', "\\/"));
+    }
+    if !devices.is_empty() {
+        choice.set_value(0);
+    }
+}
+
+fn selected_device(devices: &[HidDevice], choice: &Choice) -> Option<HidDevice> {
+    let index = choice.value();
+    if index < 0 {
+        return None;
+    }
+    devices.get(index as usize).cloned()
+}
+
+fn report_type_from_choice(choice: &Choice) -> ReportType {
+    if choice.value() == 1 {
+        ReportType::Output
+    } else {
+        ReportType::Feature
+    }
+}
+
+fn command_from_inputs(name_input: &Input, reports_input: &Input) -> Result<WedgeCommand, String> {
+    let name = name_input.value().trim().to_string();
+    if name.is_empty() {
+        return Err(tr("rc-err-name"));
+    }
+    let reports: Vec<String> = reports_input.value()
+        .split(';')
+        .map(|report| report.trim().to_string())
+        .filter(|report| !report.is_empty())
+        .collect();
+    if reports.is_empty() {
+        return Err(tr("rc-err-reports"));
+    }
+
+    let command = WedgeCommand { name, reports };
+    // catch typos before anything reaches the reader or the profiles file
+    command.report_bytes()?;
+    Ok(command)
+}
+
+fn send_and_report(device: &HidDevice, report_type: ReportType, command: &WedgeCommand) {
+    let question = tr_args("rc-confirm-send", &[("command", &command.name), ("device", &device.name), ("path", &device.path)]);
+    if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("rc-send-button"), "") != Some(1) {
+        return;
+    }
+    match wedge_config::send_command(&device.path, report_type, command) {
+        Ok(()) => dialog::message(300, 300, &tr_args("rc-sent", &[("command", &command.name), ("path", &device.path)])),
+        Err(e) => dialog::alert(300, 300, &tr_args("rc-err-send", &[("error", &e)])),
+    }
+}
@@ -0,0 +1,81 @@
+// app/restore_backup.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, FrameType},
+    frame::Frame,
+    prelude::*,
+    window::Window,
+};
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::backup::{self, BackupSettings};
+use crate::inventory::InventoryUI;
+
+// this lists the backups of inventory.db, newest first, and restores the one chosen
+pub fn show_restore_dialog(inventory_ui: &Rc<InventoryUI>) {
+    let settings = BackupSettings::current();
+    let backups = backup::list_backups(&settings.directory);
+    if backups.is_empty() {
+        dialog::message(300, 300, &tr_args("msg-no-backups", &[("dir", &settings.directory.display().to_string())]));
+        return;
+    }
+
+    let mut win = Window::new(250, 120, 460, 360, None).with_label(&tr("restore-title"));
+    win.make_modal(true);
+
+    let mut help = Frame::new(20, 10, 420, 40, None).with_label(&tr("restore-help"));
+    help.set_align(Align::Left | Align::Inside | Align::Wrap);
+    help.set_frame(FrameType::NoBox);
+
+    let mut backup_browser = HoldBrowser::new(20, 55, 420, 245, "");
+    for backup in &backups {
+        backup_browser.add(&backup.label());
+    }
+    backup_browser.select(1);
+
+    let mut restore_btn = Button::new(240, 315, 100, 30, None).with_label(&tr("btn-restore"));
+    let mut close_btn = Button::new(350, 315, 90, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let inventory_ui = inventory_ui.clone();
+    let mut win_restore = win.clone();
+    restore_btn.set_callback(move |_| {
+        let line = backup_browser.value();
+        let chosen = match usize::try_from(line - 1).ok().and_then(|index| backups.get(index)) {
+            Some(chosen) => chosen,
+            None => {
+                dialog::alert(300, 300, &tr("err-backup-select"));
+                return;
+            }
+        };
+        let confirm = tr_args("restore-confirm", &[("backup", &chosen.label())]);
+        if dialog::choice2(300, 300, &confirm, &tr("btn-cancel"), &tr("btn-restore"), "") != Some(1) {
+            return;
+        }
+
+        let restored = backup::restore_backup(&mut inventory_ui.inventory_db.borrow_mut(), &chosen.path);
+        match restored {
+            Ok(undo) => {
+                inventory_ui.refresh();
+                let message = tr_args("msg-backup-restored", &[
+                    ("backup", &chosen.label()),
+                    ("undo", &undo.display().to_string()),
+                ]);
+                inventory_ui.log_event(&message);
+                win_restore.hide();
+                dialog::message(300, 300, &message);
+            },
+            Err(e) => dialog::alert(300, 300, &e),
+        }
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
@@ -0,0 +1,85 @@
+// batch/files.rs
+//
+// Scan logs the batch tab reads from disk: logs saved from the reader tab, CSV
+// exports, or plain lists with one UID per line. A directory is read file by file
+// in name order, including its subdirectories.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::i18n::tr_args;
+
+/// A UID found in a scan log, with the time it was captured when the log has it
+pub struct LoggedScan {
+    pub raw_uid: String,
+    pub timestamp: Option<String>,
+}
+
+/// The file itself, or every file under a directory sorted by path
+pub fn collect_scan_files(path: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if path.is_dir() {
+        collect_directory(path, &mut files)?;
+        files.sort();
+    } else if path.is_file() {
+        files.push(path.to_path_buf());
+    } else {
+        let message = tr_args("err-file-missing", &[("path", &path.display().to_string())]);
+        return Err(io::Error::new(io::ErrorKind::NotFound, message));
+    }
+    Ok(files)
+}
+
+fn collect_directory(directory: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        // hidden files are editor backups, sync state and the like
+        let hidden = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if hidden {
+            continue;
+        }
+        if path.is_dir() {
+            collect_directory(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The scans in a log, the decoded lines under each scan of a saved log are skipped
+pub fn parse_scan_log(text: &str) -> Vec<LoggedScan> {
+    text.lines().filter_map(parse_scan_line).collect()
+}
+
+fn parse_scan_line(line: &str) -> Option<LoggedScan> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('→') {
+        return None;
+    }
+
+    // Saved log: "[1700000000] (2023-11-14 22:13:20) Raw UID: ..."
+    if let Some(start) = line.find("Raw UID:") {
+        if let Some(rest) = line.strip_prefix('[') {
+            let timestamp = rest.split_once(']').map(|(timestamp, _)| timestamp.to_string());
+            let raw_uid = line[start + "Raw UID:".len()..].trim().to_string();
+            return Some(LoggedScan { raw_uid, timestamp });
+        }
+    }
+
+    // CSV export: "Timestamp,Raw UID,Hex UID,Decimal UID,Manufacturer,Format"
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() >= 6 {
+        if fields[0] == "Timestamp" {
+            return None;
+        }
+        return Some(LoggedScan {
+            raw_uid: fields[1].trim().to_string(),
+            timestamp: Some(fields[0].trim().to_string()),
+        });
+    }
+
+    Some(LoggedScan { raw_uid: line.to_string(), timestamp: None })
+}
@@ -1,5 +1,8 @@
 // batch/mod.rs
 pub mod ui;
+pub mod files;
+pub mod worker;
+pub mod stats;
 
 // Re-export primary functions for convenience
 pub use crate::batch::process_batch as batch_process;
@@ -7,12 +10,15 @@ pub use crate::batch::process_batch as batch_process;
 // Handle re-exporting batch.rs functionality for backward compatibility
 use std::cell::RefCell;
 use std::rc::Rc;
-use fltk::text::TextBuffer;
+use fltk::{frame::Frame, text::TextBuffer};
+
+use crate::batch::worker::{BatchJob, BatchOptions, BatchOutcome};
 
 pub fn process_batch(
-    text: &str,
-    kb_layout: i32,
-    result_buffer: Rc<RefCell<TextBuffer>>
-) {
-    crate::batch::ui::process_batch(text, kb_layout, result_buffer)
+    job: BatchJob,
+    options: BatchOptions,
+    result_buffer: Rc<RefCell<TextBuffer>>,
+    status: &mut Frame
+) -> Option<BatchOutcome> {
+    crate::batch::ui::process_batch(job, options, result_buffer, status)
 }
\ No newline at end of file
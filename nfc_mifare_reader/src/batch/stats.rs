@@ -0,0 +1,75 @@
+// batch/stats.rs
+//
+// Counts for the summary under the batch results. Scans are the same tag when their
+// hex UIDs match, whatever keyboard layout or log they were read with.
+use std::collections::{BTreeMap, HashSet};
+
+use crate::export::CardRecord;
+use crate::i18n::{tr, tr_args};
+
+/// Totals of a converted batch
+#[derive(Default)]
+pub struct BatchStats {
+    pub total: usize,
+    pub unique: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    // unique tags per manufacturer, by name
+    pub manufacturers: BTreeMap<String, usize>,
+}
+
+/// Hex UID of a record without spaces, None for a line that didn't decode
+pub fn tag_id(record: &CardRecord) -> Option<String> {
+    let tag_id = record.hex_uid.replace(' ', "");
+    if tag_id.is_empty() || !tag_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(tag_id.to_uppercase())
+}
+
+/// The first scan of each tag in batch order, lines that didn't decode are left out
+pub fn unique_records(records: &[CardRecord]) -> Vec<CardRecord> {
+    let mut seen = HashSet::new();
+    records
+        .iter()
+        .filter(|record| tag_id(record).is_some_and(|tag_id| seen.insert(tag_id)))
+        .cloned()
+        .collect()
+}
+
+impl BatchStats {
+    pub fn new(records: &[CardRecord]) -> Self {
+        let unique = unique_records(records);
+        let invalid = records.iter().filter(|record| tag_id(record).is_none()).count();
+
+        let mut manufacturers = BTreeMap::new();
+        for record in &unique {
+            *manufacturers.entry(record.manufacturer.clone()).or_insert(0) += 1;
+        }
+
+        BatchStats {
+            total: records.len(),
+            unique: unique.len(),
+            duplicates: records.len() - invalid - unique.len(),
+            invalid,
+            manufacturers,
+        }
+    }
+
+    /// Counts, then the unique tags of each manufacturer, for the end of the results
+    pub fn summary(&self) -> String {
+        let mut summary = tr_args("batch-stats", &[
+            ("total", &self.total.to_string()),
+            ("unique", &self.unique.to_string()),
+            ("duplicates", &self.duplicates.to_string()),
+            ("invalid", &self.invalid.to_string()),
+        ]);
+        if !self.manufacturers.is_empty() {
+            summary.push_str(&format!("\n{}", tr("batch-stats-manufacturers")));
+        }
+        for (manufacturer, count) in &self.manufacturers {
+            summary.push_str(&format!("\n   {}: {}", manufacturer, count));
+        }
+        summary
+    }
+}
@@ -1,36 +1,141 @@
 // /batch/ui.rs
 use std::cell::RefCell;
 use std::rc::Rc;
-use fltk::text::TextBuffer;
+use fltk::{
+    app,
+    dialog,
+    frame::Frame,
+    prelude::*,
+    text::TextBuffer,
+};
+use progress_sink::ProgressSink;
 
+use crate::batch::worker::{self, BatchJob, BatchMessage, BatchOptions, BatchOutcome};
+use crate::export::CardRecord;
+use crate::i18n::{tr, tr_args};
+use crate::ui::progress_dialog::ProgressDialog;
 use crate::utils;
 
+// Converted scans listed in the batch results, the rest are only counted
+const DISPLAYED_RECORDS: usize = 1000;
+
+/// Convert a batch on a worker thread behind a progress dialog with a cancel button.
+/// The results replace the contents of `result_buffer` and a summary goes in `status`,
+/// the outcome is returned unless the batch was cancelled or failed.
 pub fn process_batch(
-    text: &str,
-    kb_layout: i32,
-    result_buffer: Rc<RefCell<TextBuffer>>
-) {
-    let lines: Vec<&str> = text.split('\n').collect();
-    
-    let mut results = String::new();
-    
-    for (i, line) in lines.iter().enumerate() {
-        if line.trim().is_empty() {
-            continue;
+    job: BatchJob,
+    options: BatchOptions,
+    result_buffer: Rc<RefCell<TextBuffer>>,
+    status: &mut Frame
+) -> Option<BatchOutcome> {
+    let (sender, receiver) = app::channel::<BatchMessage>();
+    // closing the dialog does the same as Cancel, it goes away once the worker stops
+    let mut dialog = ProgressDialog::new(&tr("batch-progress-title"));
+    let worker = worker::start_batch(job, options, sender, dialog.cancel_flag());
+    let mut finished = None;
+
+    while dialog.shown() {
+        app::wait();
+        // checked first, so a worker that is done has sent its last message already
+        let worker_done = worker.is_finished();
+        while let Some(message) = receiver.recv() {
+            match message {
+                BatchMessage::Progress(progress) => dialog.report(&progress),
+                BatchMessage::Finished(outcome) => {
+                    result_buffer.borrow_mut().set_text(&render_outcome(&outcome));
+                    status.set_label(&summary(&outcome));
+                    finished = Some(outcome);
+                    dialog.hide();
+                },
+                BatchMessage::Cancelled => {
+                    status.set_label(&tr("batch-cancelled"));
+                    dialog.hide();
+                },
+                BatchMessage::Failed(error) => {
+                    status.set_label("");
+                    dialog.hide();
+                    dialog::alert(300, 300, &tr_args("err-batch", &[("error", &error)]));
+                },
+            }
+        }
+        // a worker that died without its last message must not leave the dialog open
+        if worker_done && dialog.shown() {
+            dialog.hide();
         }
-        
-        let (hex_uid, manufacturer) = utils::process_uid_for_display(line, kb_layout);
-        let format_desc = utils::interpret_format_code(line);
-        
-        // Calculate decimal value
-        let decimal_value = utils::hex_to_decimal(&hex_uid);
-        
-        results.push_str(&format!("UID #{}: {}\n", i + 1, line));
-        results.push_str(&format!("   → Hex: {}\n", hex_uid));
-        results.push_str(&format!("   → Decimal: {}\n", decimal_value));
-        results.push_str(&format!("   → Manufacturer: {}\n", manufacturer));
-        results.push_str(&format!("   → Format: {}\n\n", format_desc));
-    }
-    
-    result_buffer.borrow_mut().set_text(&results);
-}
\ No newline at end of file
+    }
+    finished
+}
+
+/// Decode one scanned UID into a record, `timestamp` is when it was captured if known
+pub fn convert_uid(raw_uid: &str, timestamp: String, kb_layout: i32) -> CardRecord {
+    let (hex_uid, manufacturer) = utils::process_uid_for_display(raw_uid, kb_layout);
+    let format = utils::interpret_format_code(raw_uid);
+
+    // Calculate decimal value
+    let decimal_uid = utils::hex_to_decimal(&hex_uid);
+
+    CardRecord {
+        timestamp,
+        raw_uid: raw_uid.to_string(),
+        hex_uid,
+        decimal_uid,
+        manufacturer,
+        format,
+        ..Default::default()
+    }
+}
+
+/// Text of a converted UID in the batch results, numbered from 1
+pub fn render_record(number: usize, record: &CardRecord) -> String {
+    format!(
+        "UID #{}: {}\n   → Hex: {}\n   → Decimal: {}\n   → Manufacturer: {}\n   → Format: {}\n\n",
+        number,
+        record.raw_uid,
+        record.hex_uid,
+        record.decimal_uid,
+        record.manufacturer,
+        record.format
+    )
+}
+
+// this lists the converted scans of a batch, the files that couldn't be read and the totals
+fn render_outcome(outcome: &BatchOutcome) -> String {
+    let mut text = String::new();
+    for (i, record) in outcome.records.iter().take(DISPLAYED_RECORDS).enumerate() {
+        text.push_str(&render_record(i + 1, record));
+    }
+    if outcome.records.len() > DISPLAYED_RECORDS {
+        let more = (outcome.records.len() - DISPLAYED_RECORDS).to_string();
+        text.push_str(&format!("{}\n\n", tr_args("batch-more", &[("count", &more)])));
+    }
+    for error in &outcome.errors {
+        text.push_str(&format!("{}\n", tr_args("err-batch-file", &[("error", error)])));
+    }
+    if !outcome.errors.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(&outcome.stats.summary());
+    text.push('\n');
+    if outcome.stats.duplicates > 0 {
+        text.push_str(&format!("\n{}\n", tr_args("batch-unique-hint", &[("unique", &outcome.stats.unique.to_string())])));
+    }
+    text
+}
+
+// this is the one line summary shown under the batch buttons once a batch is done
+fn summary(outcome: &BatchOutcome) -> String {
+    let mut summary = match outcome.files {
+        0 => tr_args("batch-done-text", &[("scans", &outcome.records.len().to_string())]),
+        files => tr_args("batch-done", &[
+            ("scans", &outcome.records.len().to_string()),
+            ("files", &files.to_string()),
+        ]),
+    };
+    if outcome.added > 0 {
+        summary.push_str(&format!(", {}", tr_args("batch-added", &[("count", &outcome.added.to_string())])));
+    }
+    if let Some(path) = &outcome.export_path {
+        summary.push_str(&format!(", {}", tr_args("batch-saved", &[("path", path)])));
+    }
+    summary
+}
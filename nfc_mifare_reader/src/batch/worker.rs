@@ -0,0 +1,167 @@
+// batch/worker.rs
+//
+// Batch conversions run on a worker thread so the window keeps responding. The
+// worker reports its progress over an FLTK channel, which wakes the event loop, and
+// in a status file for the REST API, checks a cancel flag between scans, and opens
+// its own connection to the inventory database to add new tags.
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use fltk::app;
+use progress_sink::{Progress, ProgressSink, StatusFile, Tracker};
+
+use crate::batch::files::{collect_scan_files, parse_scan_log};
+use crate::batch::stats::{tag_id, unique_records, BatchStats};
+use crate::batch::ui::convert_uid;
+use crate::export::{self, CardRecord, ExportFormat};
+use crate::i18n::tr_args;
+use crate::inventory::{self, create_inventory_item, InventoryDB};
+use crate::inventory::model::StockMode;
+use crate::ui::progress_dialog::DialogSink;
+
+// Pasted UIDs converted between two progress updates
+const PROGRESS_EVERY: usize = 100;
+
+/// What to convert, pasted text with a UID per line or a scan log file or directory
+pub enum BatchJob {
+    Text(String),
+    Path(PathBuf),
+}
+
+/// Where the results of a batch go besides the result display
+pub struct BatchOptions {
+    pub kb_layout: i32,
+    pub add_to_inventory: bool,
+    // file to write the converted scans to, its extension picks the format
+    pub export_path: Option<String>,
+}
+
+/// What a finished batch did
+#[derive(Default)]
+pub struct BatchOutcome {
+    pub records: Vec<CardRecord>,
+    pub stats: BatchStats,
+    pub files: usize,
+    pub added: usize,
+    pub export_path: Option<String>,
+    // files that could not be read, the others are still processed
+    pub errors: Vec<String>,
+}
+
+/// Sent by the worker, it ends with one of Finished, Cancelled or Failed
+pub enum BatchMessage {
+    // the line or file being converted
+    Progress(Progress),
+    Finished(BatchOutcome),
+    Cancelled,
+    Failed(String),
+}
+
+/// Convert a batch in the background, setting `cancel` stops it at the next scan
+pub fn start_batch(
+    job: BatchJob,
+    options: BatchOptions,
+    sender: app::Sender<BatchMessage>,
+    cancel: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let progress = (DialogSink::new(sender, BatchMessage::Progress), StatusFile::new("batch conversion"));
+        let message = match run_batch(job, &options, &progress, &cancel) {
+            Ok(Some(outcome)) => BatchMessage::Finished(outcome),
+            Ok(None) => BatchMessage::Cancelled,
+            Err(e) => BatchMessage::Failed(e.to_string()),
+        };
+        sender.send(message);
+    })
+}
+
+// None when the batch was cancelled, nothing is written in that case
+fn run_batch(
+    job: BatchJob,
+    options: &BatchOptions,
+    progress: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> io::Result<Option<BatchOutcome>> {
+    let mut outcome = BatchOutcome::default();
+    let mut tracker = Tracker::new();
+
+    match job {
+        BatchJob::Text(text) => {
+            let lines: Vec<&str> = text.lines().filter(|line| !line.trim().is_empty()).collect();
+            for (done, line) in lines.iter().enumerate() {
+                if cancel.load(Ordering::SeqCst) {
+                    return Ok(None);
+                }
+                if done % PROGRESS_EVERY == 0 {
+                    progress.report(&tracker.progress(done, lines.len(), position(done, lines.len())));
+                }
+                outcome.records.push(convert_uid(line, String::new(), options.kb_layout));
+            }
+        },
+        BatchJob::Path(path) => {
+            let files = collect_scan_files(&path)?;
+            outcome.files = files.len();
+
+            for (done, file) in files.iter().enumerate() {
+                let current = format!("{}: {}", position(done, files.len()), file.display());
+                progress.report(&tracker.progress(done, files.len(), current));
+
+                let text = match fs::read(file) {
+                    Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+                    Err(e) => {
+                        outcome.errors.push(format!("{}: {}", file.display(), e));
+                        continue;
+                    }
+                };
+                for scan in parse_scan_log(&text) {
+                    if cancel.load(Ordering::SeqCst) {
+                        return Ok(None);
+                    }
+                    let timestamp = scan.timestamp.unwrap_or_default();
+                    outcome.records.push(convert_uid(&scan.raw_uid, timestamp, options.kb_layout));
+                }
+            }
+        },
+    }
+
+    outcome.stats = BatchStats::new(&outcome.records);
+    if options.add_to_inventory {
+        outcome.added = add_new_tags(&outcome.records).map_err(io::Error::other)?;
+    }
+    if let Some(export_path) = &options.export_path {
+        let format = ExportFormat::from_path(export_path);
+        export::export_data(&outcome.records, format, export_path)?;
+        outcome.export_path = Some(export_path.clone());
+    }
+    Ok(Some(outcome))
+}
+
+// "3 of 12", counting from 1 as it is the one being converted
+fn position(done: usize, total: usize) -> String {
+    tr_args("batch-progress", &[("done", &(done + 1).to_string()), ("total", &total.to_string())])
+}
+
+// Tags not yet in the inventory are added with a quantity of 1, those already
+// there are left alone so re-reading a log doesn't count its stock twice
+fn add_new_tags(records: &[CardRecord]) -> rusqlite::Result<usize> {
+    let db = InventoryDB::new(inventory::DB_PATH)?;
+    let mut added = 0;
+
+    let tags = unique_records(records)
+        .into_iter()
+        .filter_map(|record| Some((tag_id(&record)?, record)));
+    for (tag_id, record) in tags {
+        if db.get_item_by_any_tag(&tag_id)?.is_some() {
+            continue;
+        }
+
+        let name = tr_args("batch-item-name", &[("tag_id", &tag_id)]);
+        let item = create_inventory_item(&tag_id, &name, Some(&record.manufacturer), 1, None, None);
+        db.save_item_with_mode(&item, Some(StockMode::Receive))?;
+        added += 1;
+    }
+    Ok(added)
+}
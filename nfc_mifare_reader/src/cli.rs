@@ -0,0 +1,119 @@
+// cli.rs
+//
+// What the app does without opening a window, for cron jobs and SSH sessions:
+//
+//   --verify FILE [--key PUBLIC_KEY]   check a signed export
+//   --import FILE                      add the items of a JSON export to the inventory
+//   --sync                             merge edits with the other stations once
+//
+// These are the whole program when it is built without the gui feature. `--sync`
+// needs the sync feature.
+use std::fs;
+use progress_sink::{StatusFile, TerminalBar};
+
+use crate::config;
+use crate::i18n::{self, tr, tr_args};
+use crate::inventory::{self, import, InventoryDB};
+use crate::signing;
+
+/// Whether the arguments ask for a command rather than the window
+pub fn is_command(args: &[String]) -> bool {
+    matches!(args.first().map(String::as_str), Some("--verify" | "--import" | "--sync"))
+}
+
+/// Run the command in `args`, returns the exit code
+pub fn run(args: &[String]) -> i32 {
+    let language = config::APP_CONFIG.lock().map(|config| config.language.clone()).unwrap_or_default();
+    i18n::set_language(&language);
+
+    match args.first().map(String::as_str) {
+        Some("--verify") => signing::verify_command(&args[1..]),
+        Some("--import") => import_command(&args[1..]),
+        #[cfg(feature = "sync")]
+        Some("--sync") => sync_command(),
+        #[cfg(not(feature = "sync"))]
+        Some("--sync") => {
+            eprintln!("{}", tr("err-sync-not-built"));
+            2
+        },
+        _ => {
+            eprintln!("{}", tr("usage-commands"));
+            2
+        }
+    }
+}
+
+fn open_inventory() -> Option<InventoryDB> {
+    match InventoryDB::new(inventory::DB_PATH) {
+        Ok(db) => Some(db),
+        Err(e) => {
+            println!("{}", tr_args("err-inventory-init", &[("error", &e.to_string())]));
+            None
+        }
+    }
+}
+
+fn import_command(args: &[String]) -> i32 {
+    let file = match args {
+        [file] => file,
+        _ => {
+            eprintln!("{}", tr("usage-import"));
+            return 2;
+        }
+    };
+
+    let content = match fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            println!("{}", tr_args("err-read-file", &[("error", &e.to_string())]));
+            return 1;
+        }
+    };
+    let db = match open_inventory() {
+        Some(db) => db,
+        None => return 1,
+    };
+
+    match import::import_json(&db, &content, &(TerminalBar::new(), StatusFile::new("import"))) {
+        Ok(count) => {
+            println!("{}", tr_args("msg-json-imported", &[("count", &count.to_string())]));
+            0
+        },
+        Err(e) => {
+            println!("{}", tr_args("err-json-import", &[("error", &e.to_string())]));
+            1
+        }
+    }
+}
+
+#[cfg(feature = "sync")]
+fn sync_command() -> i32 {
+    let (enabled, folder) = match config::APP_CONFIG.lock() {
+        Ok(config) => (config.gdrive_sync_enabled, config.gdrive_sync_folder.clone()),
+        Err(_) => (false, String::new()),
+    };
+    if !enabled {
+        println!("{}", tr("err-gdrive-disabled"));
+        return 1;
+    }
+    let db = match open_inventory() {
+        Some(db) => db,
+        None => return 1,
+    };
+
+    match crate::sync::StationSync::new(&folder).sync(&db) {
+        Ok(report) => {
+            println!("{}", tr_args("msg-stations-synced", &[
+                ("stations", &report.stations.to_string()),
+                ("shared", &report.local_operations.to_string()),
+                ("received", &report.received.to_string()),
+                ("changed", &report.changed_items.len().to_string()),
+            ]));
+            0
+        },
+        Err(e) => {
+            println!("{}", tr_args("err-station-sync", &[("error", &e)]));
+            1
+        }
+    }
+}
@@ -3,7 +3,19 @@ use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
 use serde::{Serialize, Deserialize};
+use super::file::{read_config, write_config};
+use super::patterns::FormatPattern;
+use super::profiles::ConfigProfile;
+use super::manufacturers::{lookup_manufacturer, reload_manufacturers};
+use crate::i18n::{self, tr_args};
+use crate::integrations::Integration;
+use crate::reader::command_cards::CommandCard;
+use crate::rules::ScanRule;
+use crate::ui::layout::WindowGeometry;
+use crate::ui::theme::Theme;
 
 // Define the SyncDirs structure
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -13,64 +25,215 @@ pub struct SyncDirs {
     pub error_dir: String,
 }
 
+/// Schema version of the settings file, raised when a setting changes meaning
+pub const CONFIG_VERSION: u32 = 1;
+
+// Number of keyboard layouts, Auto-detect included
+const KEYBOARD_LAYOUTS: i32 = 6;
+// Longest scan cooldown accepted, in seconds
+const MAX_SCAN_COOLDOWN_SECS: u64 = 3600;
+// Highest GPIO pin in BCM numbering
+const MAX_GPIO_PIN: u8 = 27;
+// Screen scales accepted from the file
+const MIN_UI_SCALE: f32 = 0.25;
+const MAX_UI_SCALE: f32 = 4.0;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct AppConfig {
+    // Version of the schema the file was written with, 0 for files from before versions
+    #[serde(default)]
+    pub config_version: u32,
     pub default_keyboard_layout: i32,
     pub manufacturer_database: HashMap<String, String>,
     pub save_logs: bool,
     pub log_directory: String,
     pub recent_files: Vec<String>,
+    // Legacy substring patterns, migrated into format_patterns on load
+    #[serde(default)]
     pub custom_format_patterns: HashMap<String, String>,
+    // Regex format patterns, checked in order against each scan
+    #[serde(default)]
+    pub format_patterns: Vec<FormatPattern>,
     #[serde(default)]
     pub import_directory: String,
     #[serde(default)]
     pub processed_directory: String,
     #[serde(default)]
     pub error_directory: String,
+    // Copies of inventory.db taken before imports and once a day, and how many of the
+    // newest daily and weekly copies are kept
+    #[serde(default = "default_backup_directory")]
+    pub backup_directory: String,
+    #[serde(default = "default_backup_keep_daily")]
+    pub backup_keep_daily: usize,
+    #[serde(default = "default_backup_keep_weekly")]
+    pub backup_keep_weekly: usize,
+    // Days a deleted item stays in the trash before it is purged, 0 keeps it until the
+    // trash is emptied
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    // Items whose expiry date is this many days away or less count as expiring
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u32,
+    // Import files dropped into the import directory as they arrive
+    #[serde(default = "default_watch_import_directory")]
+    pub watch_import_directory: bool,
+    // Exports get an Ed25519 signature beside them
+    #[serde(default)]
+    pub sign_exports: bool,
     // Google Drive sync settings
     #[serde(default)]
     pub gdrive_sync_enabled: bool,
     #[serde(default)]
     pub gdrive_sync_folder: String,
+    // 125 kHz RDM6300 reader settings
+    #[serde(default)]
+    pub rdm6300_enabled: bool,
+    #[serde(default = "default_rdm6300_port")]
+    pub rdm6300_port: String,
+    // Directory scanned for UID post-processing plugins (.so) at startup, an absolute path
+    #[serde(default = "default_plugin_directory")]
+    pub plugin_directory: String,
+    // Seconds a repeat scan of the same tag is ignored, 0 turns this off
+    #[serde(default = "default_scan_cooldown_secs")]
+    pub scan_cooldown_secs: u64,
+    // Buzzer and bi-color LED on GPIO pins (BCM numbering), an empty pin isn't driven
+    #[serde(default)]
+    pub feedback_enabled: bool,
+    #[serde(default = "default_feedback_buzzer_pin", with = "gpio_pin")]
+    pub feedback_buzzer_pin: Option<u8>,
+    #[serde(default = "default_feedback_led_red_pin", with = "gpio_pin")]
+    pub feedback_led_red_pin: Option<u8>,
+    #[serde(default = "default_feedback_led_green_pin", with = "gpio_pin")]
+    pub feedback_led_green_pin: Option<u8>,
+    // Language code for menus, dialogs and console output, "auto" follows LANG
+    #[serde(default = "default_language")]
+    pub language: String,
+    // Light, dark or high-contrast widget colors
+    #[serde(default)]
+    pub theme: Theme,
+    // Main window position and size when it was last closed
+    #[serde(default)]
+    pub window_geometry: Option<WindowGeometry>,
+    // Screen scale set with Ctrl +/-, None keeps the scale FLTK picks from the display DPI
+    #[serde(default)]
+    pub ui_scale: Option<f32>,
+    // Tags that run an action in the capture window instead of counting as a scan
+    #[serde(default)]
+    pub command_cards: Vec<CommandCard>,
+    // Scan rules, checked in order against each scan in inventory mode
+    #[serde(default)]
+    pub scan_rules: Vec<ScanRule>,
+    // HTTP calls that push stock changes to an ERP or other system
+    #[serde(default)]
+    pub integrations: Vec<Integration>,
+    // Named sets of site specific settings, and the one in use ("" for none)
+    #[serde(default)]
+    pub profiles: Vec<ConfigProfile>,
+    #[serde(default)]
+    pub active_profile: String,
+}
+
+fn default_rdm6300_port() -> String {
+    crate::reader::rdm6300::DEFAULT_PORT.to_string()
+}
+
+fn default_watch_import_directory() -> bool {
+    true
+}
+
+fn default_backup_directory() -> String {
+    "./backups".to_string()
+}
+
+fn default_backup_keep_daily() -> usize {
+    7
+}
+
+fn default_backup_keep_weekly() -> usize {
+    4
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_expiry_warning_days() -> u32 {
+    30
+}
+
+fn default_plugin_directory() -> String {
+    crate::plugins::default_plugin_directory().display().to_string()
+}
+
+fn default_scan_cooldown_secs() -> u64 {
+    crate::reader::cooldown::DEFAULT_COOLDOWN_SECS
+}
+
+fn default_feedback_buzzer_pin() -> Option<u8> {
+    Some(18)
+}
+
+fn default_feedback_led_red_pin() -> Option<u8> {
+    Some(23)
+}
+
+fn default_feedback_led_green_pin() -> Option<u8> {
+    Some(24)
+}
+
+fn default_language() -> String {
+    crate::i18n::AUTO_LANGUAGE.to_string()
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
-        let mut manufacturer_db = HashMap::new();
-        manufacturer_db.insert("04".to_string(), "NXP Semiconductors".to_string());
-        manufacturer_db.insert("05".to_string(), "Infineon Technologies".to_string());
-        manufacturer_db.insert("16".to_string(), "Texas Instruments".to_string());
-        manufacturer_db.insert("21".to_string(), "EM Microelectronic-Marin SA".to_string());
-        manufacturer_db.insert("28".to_string(), "LEGIC Identsystems AG".to_string());
-        manufacturer_db.insert("29".to_string(), "Gemplus".to_string());
-        manufacturer_db.insert("33".to_string(), "Atmel".to_string());
-        manufacturer_db.insert("47".to_string(), "Orga Kartensysteme GmbH".to_string());
-        manufacturer_db.insert("49".to_string(), "Inside Technology".to_string());
-        manufacturer_db.insert("55".to_string(), "Tönnjes C.A.R.D. International".to_string());
-        manufacturer_db.insert("57".to_string(), "Giesecke & Devrient".to_string());
-        manufacturer_db.insert("75".to_string(), "HID Global".to_string());
-        manufacturer_db.insert("87".to_string(), "Identive".to_string());
-        manufacturer_db.insert("95".to_string(), "NXP MIFARE Classic".to_string());
-        manufacturer_db.insert("96".to_string(), "NXP MIFARE Plus".to_string());
-        manufacturer_db.insert("98".to_string(), "NXP MIFARE DESFire".to_string());
         
-        let mut custom_patterns = HashMap::new();
-        custom_patterns.insert("*h-!)d-e".to_string(), "Card type 1 with QWERTY encoding".to_string());
-        custom_patterns.insert("@h-#d-$h-%d-e".to_string(), "Card type 2 with QWERTY encoding".to_string());
-        custom_patterns.insert("*h-e".to_string(), "Card type 3 with QWERTY encoding".to_string());
+        let format_patterns = vec![
+            FormatPattern::from_literal("*h-!)d-e", "Card type 1 with QWERTY encoding"),
+            FormatPattern::from_literal("@h-#d-$h-%d-e", "Card type 2 with QWERTY encoding"),
+            FormatPattern::from_literal("*h-e", "Card type 3 with QWERTY encoding"),
+        ];
         
         AppConfig {
+            config_version: CONFIG_VERSION,
             default_keyboard_layout: 0, // Auto-detect
-            manufacturer_database: manufacturer_db,
+            // only user added entries, the full list lives in manufacturers.csv
+            manufacturer_database: HashMap::new(),
             save_logs: false,
             log_directory: "./logs".to_string(),
             recent_files: Vec::new(),
-            custom_format_patterns: custom_patterns,
+            custom_format_patterns: HashMap::new(),
+            format_patterns,
             import_directory: "./import".to_string(),
             processed_directory: "./processed".to_string(),
             error_directory: "./error".to_string(),
+            backup_directory: default_backup_directory(),
+            backup_keep_daily: default_backup_keep_daily(),
+            backup_keep_weekly: default_backup_keep_weekly(),
+            trash_retention_days: default_trash_retention_days(),
+            expiry_warning_days: default_expiry_warning_days(),
+            watch_import_directory: default_watch_import_directory(),
+            sign_exports: false,
             gdrive_sync_enabled: false,
             gdrive_sync_folder: "./gdrive_sync".to_string(),
+            rdm6300_enabled: false,
+            rdm6300_port: default_rdm6300_port(),
+            plugin_directory: default_plugin_directory(),
+            scan_cooldown_secs: default_scan_cooldown_secs(),
+            feedback_enabled: false,
+            feedback_buzzer_pin: default_feedback_buzzer_pin(),
+            feedback_led_red_pin: default_feedback_led_red_pin(),
+            feedback_led_green_pin: default_feedback_led_green_pin(),
+            language: default_language(),
+            theme: Theme::default(),
+            window_geometry: None,
+            ui_scale: None,
+            command_cards: Vec::new(),
+            scan_rules: Vec::new(),
+            integrations: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: String::new(),
         }
     }
 }
@@ -81,39 +244,203 @@ pub fn new_config() -> AppConfig {
     AppConfig::default()
 }
 
-const CONFIG_PATH: &str = "mifare_reader_config.json";
+// Problems found while loading the settings, shown once the language is known
+static LOAD_PROBLEMS: Lazy<Mutex<Vec<ConfigProblem>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Something wrong with the settings file, kept as a translation key with its
+/// arguments because the settings are read before the language is set
+#[derive(Clone, Debug)]
+pub struct ConfigProblem {
+    key: &'static str,
+    args: Vec<(&'static str, String)>,
+}
 
+impl ConfigProblem {
+    pub fn new(key: &'static str, args: &[(&'static str, String)]) -> Self {
+        ConfigProblem { key, args: args.to_vec() }
+    }
+
+    pub fn message(&self) -> String {
+        let args: Vec<(&str, &str)> = self.args.iter().map(|(name, value)| (*name, value.as_str())).collect();
+        tr_args(self.key, &args)
+    }
+}
+
+/// Load the settings file, see config::file for where it is and how it can be
+/// overridden. Settings that are missing get their defaults and settings that can't
+/// work are put back to them, what was wrong is kept for `load_problems`.
 pub fn load_config() -> AppConfig {
-    if !Path::new(CONFIG_PATH).exists() {
-        let config = AppConfig::default();
-        save_config(&config).unwrap_or_else(|err| {
-            eprintln!("Error saving default config: {}", err);
-        });
-        return config;
+    let (mut config, mut problems) = read_config();
+    migrate_custom_patterns(&mut config);
+    migrate_manufacturer_database(&mut config);
+    problems.extend(config.validate());
+
+    if let Ok(mut load_problems) = LOAD_PROBLEMS.lock() {
+        *load_problems = problems;
     }
-    
-    match fs::read_to_string(CONFIG_PATH) {
-        Ok(data) => {
-            match serde_json::from_str(&data) {
-                Ok(config) => config,
-                Err(err) => {
-                    eprintln!("Error parsing config file, using defaults: {}", err);
-                    AppConfig::default()
+    config
+}
+
+/// What was wrong with the settings file when it was last loaded, translated
+pub fn load_problems() -> Vec<String> {
+    LOAD_PROBLEMS.lock()
+        .map(|problems| problems.iter().map(ConfigProblem::message).collect())
+        .unwrap_or_default()
+}
+
+pub fn save_config(config: &AppConfig) -> io::Result<()> {
+    write_config(config)
+}
+
+/// Put every setting back to its default and save, the window keeps its place and zoom
+pub fn reset_config(config: &mut AppConfig) -> io::Result<()> {
+    let window_geometry = config.window_geometry;
+    let ui_scale = config.ui_scale;
+    *config = AppConfig::default();
+    config.window_geometry = window_geometry;
+    config.ui_scale = ui_scale;
+    reload_manufacturers(config);
+    save_config(config)
+}
+
+impl AppConfig {
+    /// Directories the import check reads from and moves files to, an empty
+    /// setting from an older config uses the default directory
+    pub fn sync_dirs(&self) -> SyncDirs {
+        let defaults = AppConfig::default();
+        let pick = |dir: &str, default: String| if dir.trim().is_empty() { default } else { dir.to_string() };
+        SyncDirs {
+            import_dir: pick(&self.import_directory, defaults.import_directory),
+            processed_dir: pick(&self.processed_directory, defaults.processed_directory),
+            error_dir: pick(&self.error_directory, defaults.error_directory),
+        }
+    }
+
+    // Settings that can't work go back to their defaults, with a problem for each
+    fn validate(&mut self) -> Vec<ConfigProblem> {
+        let defaults = AppConfig::default();
+        let mut problems = Vec::new();
+        let mut invalid = |key: &'static str, setting: &str, value: String| {
+            problems.push(ConfigProblem::new(key, &[("setting", setting.to_string()), ("value", value)]));
+        };
+
+        if self.config_version > CONFIG_VERSION {
+            invalid("err-config-newer", "config_version", self.config_version.to_string());
+        }
+        self.config_version = CONFIG_VERSION;
+
+        if !(0..KEYBOARD_LAYOUTS).contains(&self.default_keyboard_layout) {
+            invalid("err-config-layout", "default_keyboard_layout", self.default_keyboard_layout.to_string());
+            self.default_keyboard_layout = defaults.default_keyboard_layout;
+        }
+        if self.language != i18n::AUTO_LANGUAGE && !i18n::LANGUAGES.iter().any(|(code, _)| *code == self.language) {
+            invalid("err-config-language", "language", self.language.clone());
+            self.language = defaults.language.clone();
+        }
+        if self.scan_cooldown_secs > MAX_SCAN_COOLDOWN_SECS {
+            invalid("err-config-cooldown", "scan_cooldown_secs", self.scan_cooldown_secs.to_string());
+            self.scan_cooldown_secs = defaults.scan_cooldown_secs;
+        }
+        // plugins are native code, a relative directory would load whatever is in the working directory
+        if !Path::new(&self.plugin_directory).is_absolute() {
+            invalid("err-config-plugin-dir", "plugin_directory", self.plugin_directory.clone());
+            self.plugin_directory = defaults.plugin_directory.clone();
+        }
+        if self.rdm6300_port.trim().is_empty() {
+            invalid("err-config-port", "rdm6300_port", String::new());
+            self.rdm6300_port = defaults.rdm6300_port.clone();
+        }
+        if let Some(scale) = self.ui_scale {
+            if !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&scale) {
+                invalid("err-config-scale", "ui_scale", scale.to_string());
+                self.ui_scale = None;
+            }
+        }
+
+        // an output on a pin that doesn't exist or is already taken is turned off
+        let mut pins_used = Vec::new();
+        for (setting, pin) in [
+            ("feedback_buzzer_pin", &mut self.feedback_buzzer_pin),
+            ("feedback_led_red_pin", &mut self.feedback_led_red_pin),
+            ("feedback_led_green_pin", &mut self.feedback_led_green_pin),
+        ] {
+            if let Some(number) = *pin {
+                if number > MAX_GPIO_PIN {
+                    invalid("err-config-pin", setting, number.to_string());
+                    *pin = None;
+                } else if pins_used.contains(&number) {
+                    invalid("err-config-pin-shared", setting, number.to_string());
+                    *pin = None;
+                } else {
+                    pins_used.push(number);
                 }
             }
-        },
-        Err(err) => {
-            eprintln!("Error reading config file, using defaults: {}", err);
-            AppConfig::default()
         }
+
+        self.format_patterns.retain(|pattern| {
+            let valid = pattern.compile().is_ok();
+            if !valid {
+                invalid("err-config-pattern", "format_patterns", pattern.regex.clone());
+            }
+            valid
+        });
+        if !self.active_profile.is_empty() && !self.profiles.iter().any(|profile| profile.name == self.active_profile) {
+            invalid("err-config-profile", "active_profile", self.active_profile.clone());
+            self.active_profile.clear();
+        }
+        self.command_cards.retain(|card| {
+            let valid = !card.tag_id.is_empty() && card.tag_id.chars().all(|c| c.is_ascii_hexdigit());
+            if !valid {
+                invalid("err-config-card", "command_cards", card.tag_id.clone());
+            }
+            valid
+        });
+        // each integration's push position is kept under its name
+        let mut integration_names = Vec::new();
+        self.integrations.retain(|integration| {
+            let valid = !integration_names.contains(&integration.name);
+            if !valid {
+                invalid("err-config-integration", "integrations", integration.name.clone());
+            }
+            integration_names.push(integration.name.clone());
+            valid
+        });
+
+        problems
     }
 }
 
-pub fn save_config(config: &AppConfig) -> io::Result<()> {
-    let data = serde_json::to_string_pretty(config)?;
-    let mut file = fs::File::create(CONFIG_PATH)?;
-    file.write_all(data.as_bytes())?;
-    Ok(())
+// Feedback pins are written as their number, or "off" for an output that isn't
+// driven since TOML has no empty value. Older JSON configs used null for that.
+pub(super) mod gpio_pin {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PinValue {
+        Number(u8),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(pin: &Option<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        match pin {
+            Some(number) => serializer.serialize_u8(*number),
+            None => serializer.serialize_str("off"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u8>, D::Error> {
+        match Option::<PinValue>::deserialize(deserializer)? {
+            None => Ok(None),
+            Some(PinValue::Number(number)) => Ok(Some(number)),
+            Some(PinValue::Text(text)) => match text.trim() {
+                "" | "off" => Ok(None),
+                number => number.parse().map(Some).map_err(|_| {
+                    serde::de::Error::custom(format!("expected a GPIO pin number or \"off\", found \"{}\"", number))
+                }),
+            },
+        }
+    }
 }
 
 pub fn get_manufacturer(code: &str, config: &AppConfig) -> String {
@@ -121,23 +448,68 @@ pub fn get_manufacturer(code: &str, config: &AppConfig) -> String {
         return "Unknown (UID too short)".to_string();
     }
     
+    // user entries first, then the shared manufacturer table
     let manuf_code = &code[0..2].to_lowercase();
     match config.manufacturer_database.get(manuf_code) {
         Some(name) => name.clone(),
-        None => "Unknown manufacturer".to_string(),
+        None => lookup_manufacturer(code),
     }
 }
 
 pub fn add_manufacturer(code: &str, name: &str, config: &mut AppConfig) -> io::Result<()> {
-    config.manufacturer_database.insert(code.to_lowercase(), name.to_string());
+    let code = code.trim();
+    if code.len() != 2 || !code.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Manufacturer code must be one hex byte"));
+    }
+    
+    config.manufacturer_database.insert(code.to_lowercase(), name.trim().to_string());
+    reload_manufacturers(config);
     save_config(config)
 }
 
 pub fn add_custom_pattern(pattern: &str, description: &str, config: &mut AppConfig) -> io::Result<()> {
-    config.custom_format_patterns.insert(pattern.to_string(), description.to_string());
+    let format_pattern = FormatPattern::new(description, pattern, description);
+    if let Err(e) = format_pattern.compile() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, e.to_string()));
+    }
+    
+    config.format_patterns.push(format_pattern);
     save_config(config)
 }
 
+// Older configs carried a copy of the hardcoded table, drop those entries
+// so the manufacturer data file isn't shadowed by them
+fn migrate_manufacturer_database(config: &mut AppConfig) {
+    const LEGACY_DEFAULTS: [(&str, &str); 16] = [
+        ("04", "NXP Semiconductors"), ("05", "Infineon Technologies"),
+        ("16", "Texas Instruments"), ("21", "EM Microelectronic-Marin SA"),
+        ("28", "LEGIC Identsystems AG"), ("29", "Gemplus"),
+        ("33", "Atmel"), ("47", "Orga Kartensysteme GmbH"),
+        ("49", "Inside Technology"), ("55", "Tönnjes C.A.R.D. International"),
+        ("57", "Giesecke & Devrient"), ("75", "HID Global"),
+        ("87", "Identive"), ("95", "NXP MIFARE Classic"),
+        ("96", "NXP MIFARE Plus"), ("98", "NXP MIFARE DESFire"),
+    ];
+    
+    for (code, name) in LEGACY_DEFAULTS {
+        if config.manufacturer_database.get(code).map(|n| n.as_str()) == Some(name) {
+            config.manufacturer_database.remove(code);
+        }
+    }
+}
+
+// Turn old substring patterns into escaped regex patterns
+fn migrate_custom_patterns(config: &mut AppConfig) {
+    let mut legacy: Vec<_> = config.custom_format_patterns.drain().collect();
+    legacy.sort();
+    
+    for (literal, description) in legacy {
+        if !config.format_patterns.iter().any(|p| p.regex == regex::escape(&literal)) {
+            config.format_patterns.push(FormatPattern::from_literal(&literal, &description));
+        }
+    }
+}
+
 // Save log data to a file
 pub fn save_log(log_data: &str, config: &AppConfig) -> io::Result<String> {
     if !config.save_logs {
@@ -0,0 +1,197 @@
+// config/file.rs
+//
+// The settings file is TOML, `mifare_reader_config.toml` in the working directory
+// unless MIFARE_READER_CONFIG names another file. A config saved by an older
+// release as JSON is converted the first time the app starts without a TOML file.
+//
+// Any top-level setting holding a single value can be overridden for one run with
+// an environment variable named after it, MIFARE_READER_SCAN_COOLDOWN_SECS=5 for
+// example. Overridden settings are saved with the value the file had, so changing
+// the preferences never makes an override permanent.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+use toml::{Table, Value};
+
+use super::app_config::{AppConfig, ConfigProblem};
+
+/// Settings file used when MIFARE_READER_CONFIG is not set
+pub const CONFIG_PATH: &str = "mifare_reader_config.toml";
+/// Environment variable naming another settings file
+pub const CONFIG_PATH_VAR: &str = "MIFARE_READER_CONFIG";
+/// Prefix of the environment variables overriding single settings
+pub const OVERRIDE_PREFIX: &str = "MIFARE_READER_";
+
+// Settings file of releases before the TOML one
+const LEGACY_CONFIG_PATH: &str = "mifare_reader_config.json";
+
+// A setting overridden from the environment, with its value in the file if it had one
+struct Override {
+    setting: String,
+    file_value: Option<Value>,
+}
+
+static OVERRIDDEN: Lazy<Mutex<Vec<Override>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// The settings file in use
+pub fn config_path() -> PathBuf {
+    match env::var(CONFIG_PATH_VAR) {
+        Ok(path) if !path.trim().is_empty() => PathBuf::from(path),
+        _ => PathBuf::from(CONFIG_PATH),
+    }
+}
+
+/// Read the settings file with the environment overrides applied. A file that can't
+/// be read gives the defaults and a problem saying why; a file that doesn't parse is
+/// first copied aside so saving the defaults doesn't lose it.
+pub fn read_config() -> (AppConfig, Vec<ConfigProblem>) {
+    let path = config_path();
+    let mut problems = Vec::new();
+
+    let mut table = if path.exists() {
+        match fs::read_to_string(&path) {
+            Ok(text) => match text.parse::<Table>() {
+                Ok(table) => table,
+                Err(e) => {
+                    problems.push(parse_problem(&path, &e.to_string()));
+                    return (AppConfig::default(), problems);
+                }
+            },
+            Err(e) => {
+                problems.push(ConfigProblem::new("err-config-read", &[
+                    ("path", path.display().to_string()),
+                    ("error", e.to_string()),
+                ]));
+                return (AppConfig::default(), problems);
+            }
+        }
+    } else {
+        let config = first_config(&path, &mut problems);
+        Table::try_from(&config).unwrap_or_default()
+    };
+
+    apply_overrides(&mut table, &mut problems);
+
+    match table.try_into::<AppConfig>() {
+        Ok(config) => (config, problems),
+        Err(e) => {
+            problems.push(parse_problem(&path, &e.to_string()));
+            (AppConfig::default(), problems)
+        }
+    }
+}
+
+/// Write the settings file, overridden settings keep the value the file had
+pub fn write_config(config: &AppConfig) -> io::Result<()> {
+    let mut table = Table::try_from(config)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    if let Ok(overridden) = OVERRIDDEN.lock() {
+        for setting in overridden.iter() {
+            match &setting.file_value {
+                Some(value) => table.insert(setting.setting.clone(), value.clone()),
+                None => table.remove(&setting.setting),
+            };
+        }
+    }
+
+    let text = toml::to_string_pretty(&table)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+    fs::write(config_path(), text)
+}
+
+/// Settings overridden from the environment this run, by setting name
+pub fn overridden_settings() -> Vec<String> {
+    OVERRIDDEN.lock()
+        .map(|overridden| overridden.iter().map(|setting| setting.setting.clone()).collect())
+        .unwrap_or_default()
+}
+
+// this converts the JSON settings of an older release, or writes the defaults when there are none
+fn first_config(path: &Path, problems: &mut Vec<ConfigProblem>) -> AppConfig {
+    let legacy = Path::new(LEGACY_CONFIG_PATH);
+    let config = match fs::read_to_string(legacy) {
+        Ok(data) if env::var(CONFIG_PATH_VAR).is_err() => match serde_json::from_str::<AppConfig>(&data) {
+            Ok(config) => {
+                println!("Converting {} to {}", legacy.display(), path.display());
+                config
+            },
+            Err(e) => {
+                problems.push(parse_problem(legacy, &e.to_string()));
+                AppConfig::default()
+            }
+        },
+        _ => AppConfig::default(),
+    };
+
+    if let Err(e) = write_config(&config) {
+        problems.push(ConfigProblem::new("err-config-write", &[
+            ("path", path.display().to_string()),
+            ("error", e.to_string()),
+        ]));
+    }
+    config
+}
+
+// this copies a file that didn't parse to "<name>.invalid" and says what was wrong with it
+fn parse_problem(path: &Path, error: &str) -> ConfigProblem {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".invalid");
+    let _ = fs::copy(path, &backup);
+
+    ConfigProblem::new("err-config-parse", &[
+        ("path", path.display().to_string()),
+        ("backup", PathBuf::from(backup).display().to_string()),
+        ("error", error.trim().to_string()),
+    ])
+}
+
+// this replaces settings holding a single value with the environment variables named after them
+fn apply_overrides(table: &mut Table, problems: &mut Vec<ConfigProblem>) {
+    let mut overridden = Vec::new();
+    let defaults = Table::try_from(AppConfig::default()).unwrap_or_default();
+
+    for (key, default) in &defaults {
+        let variable = format!("{}{}", OVERRIDE_PREFIX, key.to_uppercase());
+        let text = match env::var(&variable) {
+            Ok(text) => text,
+            Err(_) => continue,
+        };
+
+        // the file's value gives the type, or the default's when the file leaves it out
+        let current = table.get(key).unwrap_or(default);
+        match override_value(current, &text) {
+            Some(value) => {
+                let file_value = table.insert(key.clone(), value);
+                overridden.push(Override { setting: key.clone(), file_value });
+            },
+            None => problems.push(ConfigProblem::new("err-config-override", &[
+                ("variable", variable),
+                ("value", text),
+            ])),
+        }
+    }
+
+    if let Ok(mut current) = OVERRIDDEN.lock() {
+        *current = overridden;
+    }
+}
+
+// this parses an override as the same kind of value as the setting, None if it can't be
+fn override_value(current: &Value, text: &str) -> Option<Value> {
+    let text = text.trim();
+    match current {
+        Value::String(_) => Some(Value::String(text.to_string())),
+        Value::Integer(_) => text.parse().ok().map(Value::Integer),
+        Value::Float(_) => text.parse().ok().map(Value::Float),
+        Value::Boolean(_) => match text.to_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => Some(Value::Boolean(true)),
+            "0" | "false" | "no" | "off" => Some(Value::Boolean(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,78 @@
+// config/manufacturers.rs
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+
+use super::app_config::AppConfig;
+
+// Editable manufacturer list, one "code,name" entry per line ('#' starts a comment)
+pub const MANUFACTURERS_PATH: &str = "manufacturers.csv";
+
+// The merged table: built-in list, then the data file, then entries added in Preferences
+pub static MANUFACTURERS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| {
+    let table = match super::APP_CONFIG.lock() {
+        Ok(config) => build_manufacturer_table(&config),
+        Err(_) => uid_codec::manufacturer::builtin_table().clone(),
+    };
+    Mutex::new(table)
+});
+
+// Parse "code,name" lines, skipping comments and malformed codes
+pub fn parse_manufacturer_csv(data: &str) -> HashMap<String, String> {
+    uid_codec::manufacturer::parse_csv(data)
+}
+
+// Merge all sources into one table, later sources override earlier ones.
+// The built-in ISO/IEC 7816-6 list comes from uid_codec so lookups work without the data file.
+fn build_manufacturer_table(config: &AppConfig) -> HashMap<String, String> {
+    let mut table = uid_codec::manufacturer::builtin_table().clone();
+
+    if Path::new(MANUFACTURERS_PATH).exists() {
+        match fs::read_to_string(MANUFACTURERS_PATH) {
+            Ok(data) => table.extend(parse_manufacturer_csv(&data)),
+            Err(err) => eprintln!("Error reading {}: {}", MANUFACTURERS_PATH, err),
+        }
+    }
+
+    for (code, name) in &config.manufacturer_database {
+        table.insert(code.to_uppercase(), name.clone());
+    }
+
+    table
+}
+
+// Rebuild the shared table, e.g. after an entry was added in Preferences
+pub fn reload_manufacturers(config: &AppConfig) {
+    let table = build_manufacturer_table(config);
+    if let Ok(mut manufacturers) = MANUFACTURERS.lock() {
+        *manufacturers = table;
+    }
+}
+
+// Look up the manufacturer for the first byte of a hex UID
+pub fn lookup_manufacturer(hex_uid: &str) -> String {
+    if hex_uid.len() < 2 {
+        return "Unknown (UID too short)".to_string();
+    }
+
+    let code = hex_uid[0..2].to_uppercase();
+    match MANUFACTURERS.lock() {
+        Ok(manufacturers) => manufacturers
+            .get(&code)
+            .cloned()
+            .unwrap_or_else(|| "Unknown manufacturer".to_string()),
+        Err(_) => "Unknown manufacturer".to_string(),
+    }
+}
+
+// All known entries sorted by code, for the Preferences list
+pub fn all_manufacturers() -> Vec<(String, String)> {
+    let mut entries: Vec<(String, String)> = match MANUFACTURERS.lock() {
+        Ok(manufacturers) => manufacturers.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+        Err(_) => Vec::new(),
+    };
+    entries.sort();
+    entries
+}
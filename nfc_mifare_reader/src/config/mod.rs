@@ -1,5 +1,9 @@
 // config/mod.rs (correct version)
 pub mod app_config;
+pub mod file;
+pub mod patterns;
+pub mod manufacturers;
+pub mod profiles;
 
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -16,12 +20,16 @@ pub use app_config::{
     AppConfig,
     SyncDirs,
     load_config,
+    load_problems,
     save_config,
+    reset_config,
     save_log,
     get_manufacturer,
     add_manufacturer,
     add_custom_pattern
 };
+pub use patterns::{FormatPattern, match_format_patterns};
+pub use manufacturers::{lookup_manufacturer, all_manufacturers};
 
 // For backward compatibility
 pub use app_config::new_config;
\ No newline at end of file
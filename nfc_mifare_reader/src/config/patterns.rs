@@ -0,0 +1,82 @@
+// config/patterns.rs
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+// A user defined format pattern, matched against raw scan data.
+// Named capture groups can be referenced in the description as {group}.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FormatPattern {
+    pub name: String,
+    pub regex: String,
+    pub description: String,
+}
+
+impl FormatPattern {
+    pub fn new(name: &str, regex: &str, description: &str) -> Self {
+        FormatPattern {
+            name: name.to_string(),
+            regex: regex.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    // Old configs stored plain substrings, so those are escaped into a regex
+    pub fn from_literal(literal: &str, description: &str) -> Self {
+        FormatPattern::new(description, &regex::escape(literal), description)
+    }
+
+    pub fn compile(&self) -> Result<Regex, regex::Error> {
+        Regex::new(&self.regex)
+    }
+
+    // Returns the expanded description if the pattern matches the data
+    pub fn describe(&self, data: &str) -> Option<String> {
+        let re = self.compile().ok()?;
+        describe_with(&re, &self.description, data)
+    }
+}
+
+// Expand {group} placeholders with the captured values. Groups the description
+// doesn't mention are listed after it so nothing captured is hidden.
+pub fn describe_with(re: &Regex, description: &str, data: &str) -> Option<String> {
+    let caps = re.captures(data)?;
+    let mut result = description.to_string();
+    let mut unused = Vec::new();
+
+    for name in re.capture_names().flatten() {
+        let value = caps.name(name).map(|m| m.as_str()).unwrap_or("");
+        let placeholder = format!("{{{}}}", name);
+        if result.contains(&placeholder) {
+            result = result.replace(&placeholder, value);
+        } else {
+            unused.push(format!("{}={}", name, value));
+        }
+    }
+
+    if !unused.is_empty() {
+        result = format!("{} ({})", result, unused.join(", "));
+    }
+
+    Some(result)
+}
+
+// First matching pattern wins, in the order they are listed in the config
+pub fn match_format_patterns(data: &str, patterns: &[FormatPattern]) -> Option<String> {
+    patterns.iter().find_map(|pattern| pattern.describe(data))
+}
+
+// Check a pattern against sample data for the editor's live preview
+pub fn test_pattern(regex: &str, description: &str, sample: &str) -> String {
+    if regex.is_empty() {
+        return "Enter a regular expression".to_string();
+    }
+
+    match Regex::new(regex) {
+        Ok(re) => match describe_with(&re, description, sample) {
+            Some(result) => format!("Match: {}", result),
+            None => "No match".to_string(),
+        },
+        // regex errors span several lines, the last one says what is wrong
+        Err(e) => format!("Invalid regex: {}", e.to_string().lines().last().unwrap_or("")),
+    }
+}
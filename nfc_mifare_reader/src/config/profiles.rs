@@ -0,0 +1,127 @@
+// config/profiles.rs
+//
+// Named sets of the settings that change from one site or bench to another, such
+// as "Warehouse A", "Lab" or "Demo". A profile holds its own copy of the reader
+// settings, keyboard layout, sync directories and scan rules (where the webhooks
+// are); everything else is shared by all profiles. Switching keeps any changes made
+// under the active profile, then loads the other profile's copy over the settings.
+use std::io;
+use serde::{Serialize, Deserialize};
+
+use super::app_config::{gpio_pin, save_config, AppConfig};
+use crate::i18n::tr;
+use crate::rules::ScanRule;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ConfigProfile {
+    pub name: String,
+    pub default_keyboard_layout: i32,
+    // Reader settings
+    pub rdm6300_enabled: bool,
+    pub rdm6300_port: String,
+    pub scan_cooldown_secs: u64,
+    pub feedback_enabled: bool,
+    #[serde(with = "gpio_pin")]
+    pub feedback_buzzer_pin: Option<u8>,
+    #[serde(with = "gpio_pin")]
+    pub feedback_led_red_pin: Option<u8>,
+    #[serde(with = "gpio_pin")]
+    pub feedback_led_green_pin: Option<u8>,
+    // Sync directories
+    pub import_directory: String,
+    pub processed_directory: String,
+    pub error_directory: String,
+    pub gdrive_sync_enabled: bool,
+    pub gdrive_sync_folder: String,
+    // Scan rules, webhooks included
+    #[serde(default)]
+    pub scan_rules: Vec<ScanRule>,
+}
+
+impl ConfigProfile {
+    /// A profile holding the current values of its settings
+    pub fn from_config(name: &str, config: &AppConfig) -> Self {
+        ConfigProfile {
+            name: name.to_string(),
+            default_keyboard_layout: config.default_keyboard_layout,
+            rdm6300_enabled: config.rdm6300_enabled,
+            rdm6300_port: config.rdm6300_port.clone(),
+            scan_cooldown_secs: config.scan_cooldown_secs,
+            feedback_enabled: config.feedback_enabled,
+            feedback_buzzer_pin: config.feedback_buzzer_pin,
+            feedback_led_red_pin: config.feedback_led_red_pin,
+            feedback_led_green_pin: config.feedback_led_green_pin,
+            import_directory: config.import_directory.clone(),
+            processed_directory: config.processed_directory.clone(),
+            error_directory: config.error_directory.clone(),
+            gdrive_sync_enabled: config.gdrive_sync_enabled,
+            gdrive_sync_folder: config.gdrive_sync_folder.clone(),
+            scan_rules: config.scan_rules.clone(),
+        }
+    }
+
+    /// Put the profile's values over the settings
+    pub fn apply(&self, config: &mut AppConfig) {
+        config.default_keyboard_layout = self.default_keyboard_layout;
+        config.rdm6300_enabled = self.rdm6300_enabled;
+        config.rdm6300_port = self.rdm6300_port.clone();
+        config.scan_cooldown_secs = self.scan_cooldown_secs;
+        config.feedback_enabled = self.feedback_enabled;
+        config.feedback_buzzer_pin = self.feedback_buzzer_pin;
+        config.feedback_led_red_pin = self.feedback_led_red_pin;
+        config.feedback_led_green_pin = self.feedback_led_green_pin;
+        config.import_directory = self.import_directory.clone();
+        config.processed_directory = self.processed_directory.clone();
+        config.error_directory = self.error_directory.clone();
+        config.gdrive_sync_enabled = self.gdrive_sync_enabled;
+        config.gdrive_sync_folder = self.gdrive_sync_folder.clone();
+        config.scan_rules = self.scan_rules.clone();
+    }
+}
+
+/// Keep the current settings as a profile and make it the active one, a profile
+/// of the same name is replaced
+pub fn save_profile_as(name: &str, config: &mut AppConfig) -> io::Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, tr("err-profile-name")));
+    }
+
+    let profile = ConfigProfile::from_config(name, config);
+    match config.profiles.iter_mut().find(|existing| existing.name == name) {
+        Some(existing) => *existing = profile,
+        None => config.profiles.push(profile),
+    }
+    config.active_profile = name.to_string();
+    save_config(config)
+}
+
+/// Switch to another profile, the active one keeps the settings as they are now
+pub fn switch_profile(name: &str, config: &mut AppConfig) -> io::Result<()> {
+    let profile = match config.profiles.iter().find(|profile| profile.name == name) {
+        Some(profile) => profile.clone(),
+        None => return Err(io::Error::new(io::ErrorKind::NotFound, tr("err-profile-missing"))),
+    };
+
+    store_active_profile(config);
+    profile.apply(config);
+    config.active_profile = profile.name;
+    save_config(config)
+}
+
+/// Forget a profile, the settings stay as they are if it was the active one
+pub fn delete_profile(name: &str, config: &mut AppConfig) -> io::Result<()> {
+    config.profiles.retain(|profile| profile.name != name);
+    if config.active_profile == name {
+        config.active_profile.clear();
+    }
+    save_config(config)
+}
+
+// Changes made while a profile is active belong to that profile
+fn store_active_profile(config: &mut AppConfig) {
+    let updated = ConfigProfile::from_config(&config.active_profile, config);
+    if let Some(active) = config.profiles.iter_mut().find(|profile| profile.name == updated.name) {
+        *active = updated;
+    }
+}
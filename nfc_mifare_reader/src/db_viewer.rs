@@ -13,11 +13,13 @@ use fltk::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::{tr, tr_args};
+use crate::ui::theme;
 
 pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     // Create the main window
     let app = app::App::default();
-    let mut win = Window::new(100, 100, 960, 620, "Database Viewer");
+    let mut win = Window::new(100, 100, 960, 620, None).with_label(&tr("dbv-title"));
     win.make_modal(true);
     
     // Use a flex layout for better resizing behavior
@@ -26,7 +28,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     flex.set_margin(10);
     
     // Create a frame for the header
-    let mut header = Frame::new(0, 0, 940, 30, "Inventory Database");
+    let mut header = Frame::new(0, 0, 940, 30, None).with_label(&tr("dbv-heading"));
     header.set_label_size(18);
     header.set_align(fltk::enums::Align::Center);
     flex.fixed(&header, 30);
@@ -57,7 +59,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     let items = match inventory_ui.inventory_db.borrow().get_all_items() {
         Ok(items) => items,
         Err(e) => {
-            dialog::alert(300, 300, &format!("Error loading inventory: {}", e));
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
             vec![] // Return empty vector on error
         }
     };
@@ -74,38 +76,42 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
         match ctx {
             fltk::table::TableContext::StartPage => draw::set_font(fltk::enums::Font::Helvetica, 14),
             fltk::table::TableContext::ColHeader => {
-                draw::draw_rect_fill(x, y, w, h, fltk::enums::Color::from_rgb(220, 220, 220));
-                draw::set_draw_color(fltk::enums::Color::Black);
+                let palette = theme::palette();
+                draw::draw_rect_fill(x, y, w, h, palette.header_background);
+                draw::set_draw_color(palette.grid);
                 draw::draw_rect(x, y, w, h);
                 draw::set_font(fltk::enums::Font::HelveticaBold, 14);
+                draw::set_draw_color(palette.header_text);
                 let header = match col {
-                    0 => "Tag ID",
-                    1 => "Name",
-                    2 => "Quantity",
-                    3 => "Category",
-                    4 => "Location",
-                    5 => "Created",
-                    6 => "Updated",
-                    _ => "",
+                    0 => tr("inv-col-tag"),
+                    1 => tr("inv-col-name"),
+                    2 => tr("dbv-col-quantity"),
+                    3 => tr("inv-col-category"),
+                    4 => tr("dbv-col-location"),
+                    5 => tr("dbv-col-created"),
+                    6 => tr("dbv-col-updated"),
+                    _ => String::new(),
                 };
-                draw::draw_text2(header, x, y, w, h, fltk::enums::Align::Center);
+                draw::draw_text2(&header, x, y, w, h, fltk::enums::Align::Center);
             },
             fltk::table::TableContext::Cell => {
                 let items = items_clone.borrow();
                 
                 // Determine background color (alternate rows, highlight selected)
+                let palette = theme::palette();
                 let is_selected = *selected_row_clone.borrow() == row;
                 let bg_color = if is_selected {
-                    fltk::enums::Color::from_rgb(173, 216, 230) // Light blue for selected row
+                    palette.selection
                 } else if row % 2 == 0 {
-                    fltk::enums::Color::from_rgb(245, 245, 245) // Light gray for even rows
+                    palette.row_even
                 } else {
-                    fltk::enums::Color::White // White for odd rows
+                    palette.row_odd
                 };
                 
                 draw::draw_rect_fill(x, y, w, h, bg_color);
-                draw::set_draw_color(fltk::enums::Color::Black);
+                draw::set_draw_color(palette.grid);
                 draw::draw_rect(x, y, w, h);
+                draw::set_draw_color(palette.text);
                 
                 if row < items.len() as i32 {
                     let item = &items[row as usize];
@@ -142,8 +148,8 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     flex.fixed(&button_flex, 40); // Fixed height for button area
     
     // Add count display
-    let count_str = format!("{} items in database", items_data.borrow().len());
-    let mut count_label = Frame::new(0, 0, 200, 30, count_str.as_str());
+    let count_str = item_count(items_data.borrow().len());
+    let mut count_label = Frame::new(0, 0, 200, 30, None).with_label(&count_str);
     count_label.set_label_size(14);
     button_flex.fixed(&count_label, 200);
     
@@ -151,22 +157,22 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     let mut spacer = Frame::new(0, 0, 30, 30, "");
     
     // Create bright, visible buttons with contrasting colors
-    let mut delete_btn = Button::new(0, 0, 0, 30, "Delete");
+    let mut delete_btn = Button::new(0, 0, 0, 30, None).with_label(&tr("btn-delete"));
     delete_btn.set_color(fltk::enums::Color::from_rgb(255, 100, 100)); // Red for delete
     delete_btn.set_label_color(fltk::enums::Color::White);
     button_flex.fixed(&delete_btn, 130);
     
-    let mut export_btn = Button::new(0, 0, 0, 30, "Export CSV");
+    let mut export_btn = Button::new(0, 0, 0, 30, None).with_label(&tr("dbv-export"));
     export_btn.set_color(fltk::enums::Color::from_rgb(100, 200, 100)); // Green for export
     export_btn.set_label_color(fltk::enums::Color::Black);
     button_flex.fixed(&export_btn, 130);
     
-    let mut refresh_btn = Button::new(0, 0, 0, 30, "Refresh");
+    let mut refresh_btn = Button::new(0, 0, 0, 30, None).with_label(&tr("dbv-refresh"));
     refresh_btn.set_color(fltk::enums::Color::from_rgb(100, 100, 255)); // Blue for refresh
     refresh_btn.set_label_color(fltk::enums::Color::White);
     button_flex.fixed(&refresh_btn, 130);
     
-    let mut close_btn = Button::new(0, 0, 0, 30, "Close");
+    let mut close_btn = Button::new(0, 0, 0, 30, None).with_label(&tr("btn-close"));
     close_btn.set_color(fltk::enums::Color::from_rgb(200, 200, 200)); // Gray for close
     close_btn.set_label_color(fltk::enums::Color::Black);
     button_flex.fixed(&close_btn, 130);
@@ -196,14 +202,14 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                 let tag_id = items[selected_row_val as usize].tag_id.clone();
                 
                 // Ask for confirmation
-                if dialog::choice2(300, 300, &format!("Are you sure you want to delete the item with Tag ID '{}'?", tag_id), 
-                                "No", "Yes", "") == Some(1) {
+                if dialog::choice2(300, 300, &tr_args("dbv-trash-confirm", &[("tag_id", &tag_id)]), 
+                                &tr("btn-cancel"), &tr("btn-delete"), "") == Some(1) {
                     
                     // Delete the item
                     if let Err(e) = inventory_ui_clone.inventory_db.borrow().delete_item(&tag_id) {
-                        dialog::alert(300, 300, &format!("Error deleting item: {}", e));
+                        dialog::alert(300, 300, &tr_args("err-trash-delete", &[("error", &e.to_string())]));
                     } else {
-                        dialog::message(300, 300, "Item deleted successfully");
+                        dialog::message(300, 300, &tr("msg-inv-trashed"));
                         
                         // Refresh the table after deletion
                         if let Ok(updated_items) = inventory_ui_clone.inventory_db.borrow().get_all_items() {
@@ -212,7 +218,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                             table_clone.set_rows(items_data.borrow().len() as i32);
                             
                             // Update the count label
-                            let new_count = format!("{} items in database", items_data.borrow().len());
+                            let new_count = item_count(items_data.borrow().len());
                             count_label_clone.set_label(new_count.as_str());
                             
                             table_clone.redraw();
@@ -220,7 +226,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                     }
                 }
             } else {
-                dialog::alert(300, 300, "Please select an item to delete");
+                dialog::alert(300, 300, &tr("err-trash-select-delete"));
             }
         });
     }
@@ -228,7 +234,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     {
         let items_data = items_data.clone();
         export_btn.set_callback(move |_| {
-            if let Some(path) = dialog::file_chooser("Export as CSV", "*.csv", ".", false) {
+            if let Some(path) = dialog::file_chooser(&tr("title-export-csv"), "*.csv", ".", false) {
                 let items = items_data.borrow();
                 let mut csv = String::from("Tag ID,Name,Quantity,Category,Location,Created At,Last Updated\n");
                 
@@ -249,9 +255,10 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                 }
                 
                 if let Err(e) = std::fs::write(&path, csv) {
-                    dialog::alert(300, 300, &format!("Error writing file: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-write-file", &[("error", &e.to_string())]));
                 } else {
-                    dialog::message(300, 300, &format!("Data exported to {}", path));
+                    crate::signing::sign_export(std::path::Path::new(&path));
+                    dialog::message(300, 300, &tr_args("msg-exported", &[("path", &path)]));
                 }
             }
         });
@@ -269,7 +276,7 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
                 table_clone.set_rows(items_data.borrow().len() as i32);
                 
                 // Update the count label
-                let new_count = format!("{} items in database", items_data.borrow().len());
+                let new_count = item_count(items_data.borrow().len());
                 count_label_clone.set_label(new_count.as_str());
                 
                 table_clone.redraw();
@@ -295,4 +302,9 @@ pub fn show_database_viewer(inventory_ui: &Rc<crate::inventory::InventoryUI>) {
     while win.shown() {
         app::wait();
     }
-}
\ No newline at end of file
+}
+
+// Label under the table
+fn item_count(count: usize) -> String {
+    tr_args("dbv-count", &[("count", &count.to_string())])
+}
@@ -4,15 +4,41 @@ use std::io::{self, Write};
 use std::path::Path;
 use chrono::Local;
 
+/// Folder for exports written without asking for a file name
+pub const EXPORT_DIRECTORY: &str = "./exports";
+
 /// Export formats supported by the application
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ExportFormat {
     CSV,
     JSON,
     Text,
 }
 
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::CSV => "csv",
+            ExportFormat::JSON => "json",
+            ExportFormat::Text => "txt",
+        }
+    }
+
+    /// Format for a file name picked by the user, text unless it ends in .csv or .json
+    pub fn from_path(path: &str) -> ExportFormat {
+        let path = path.to_lowercase();
+        if path.ends_with(".csv") {
+            ExportFormat::CSV
+        } else if path.ends_with(".json") {
+            ExportFormat::JSON
+        } else {
+            ExportFormat::Text
+        }
+    }
+}
+
 /// Structure representing a card record
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CardRecord {
     pub timestamp: String,
     pub raw_uid: String,
@@ -20,6 +46,31 @@ pub struct CardRecord {
     pub decimal_uid: String,
     pub manufacturer: String,
     pub format: String,
+    // Local time of the scan, shown next to the Unix timestamp
+    pub captured_at: String,
+    // Lines shown under the record but not exported: badge, plugin fields and the like
+    pub notes: Vec<String>,
+}
+
+impl CardRecord {
+    /// The record as shown in the reader tab and written to saved logs
+    pub fn display_text(&self) -> String {
+        let mut text = format!(
+            "[{}] ({}) Raw UID: {}\n    → Hex: {}\n    → Decimal: {}\n    → Manufacturer: {}\n    → Format: {}\n",
+            self.timestamp,
+            self.captured_at,
+            self.raw_uid,
+            self.hex_uid,
+            self.decimal_uid,
+            self.manufacturer,
+            self.format
+        );
+        for note in &self.notes {
+            text.push_str(&format!("    → {}\n", note));
+        }
+        text.push('\n');
+        text
+    }
 }
 
 /// Export card data to a file
@@ -37,10 +88,23 @@ pub fn export_data(
     let path = Path::new(filename);
     let mut file = fs::File::create(path)?;
     file.write_all(content.as_bytes())?;
+    crate::signing::sign_export(path);
     
     Ok(format!("Data exported to {}", filename))
 }
 
+/// Path in the export folder named after its contents and the current time, the folder is created
+pub fn timestamped_export_path(name: &str, format: &ExportFormat) -> io::Result<String> {
+    fs::create_dir_all(EXPORT_DIRECTORY)?;
+    Ok(format!(
+        "{}/{}_{}.{}",
+        EXPORT_DIRECTORY,
+        name,
+        Local::now().format("%Y%m%d_%H%M%S"),
+        format.extension()
+    ))
+}
+
 /// Generate CSV content from card records
 fn generate_csv(records: &[CardRecord]) -> String {
     let mut csv = String::from("Timestamp,Raw UID,Hex UID,Decimal UID,Manufacturer,Format\n");
@@ -103,85 +167,3 @@ fn generate_text(records: &[CardRecord]) -> String {
     
     text
 }
-
-/// Parse data from text display and convert to card records
-pub fn parse_display_text(text: &str) -> Vec<CardRecord> {
-    let mut records = Vec::new();
-    let mut lines = text.lines().peekable();
-    
-    while let Some(line) = lines.next() {
-        // Look for lines that start with timestamps [numbers]
-        if line.starts_with('[') && line.contains("] (") && line.contains("Raw UID:") {
-            // Extract timestamp
-            let timestamp = if let Some(end) = line.find(']') {
-                line[1..end].to_string()
-            } else {
-                continue;
-            };
-            
-            // Extract raw UID
-            let raw_uid = if let Some(start) = line.find("Raw UID: ") {
-                let start = start + "Raw UID: ".len();
-                line[start..].to_string()
-            } else {
-                continue;
-            };
-            
-            // Extract other fields from subsequent lines
-            let mut hex_uid = String::new();
-            let mut decimal_uid = String::new();
-            let mut manufacturer = String::new();
-            let mut format = String::new();
-            
-            // Try to read the next lines for additional data
-            while let Some(next_line) = lines.peek() {
-                if next_line.trim().starts_with("→ Hex:") {
-                    hex_uid = next_line
-                        .trim()
-                        .trim_start_matches("→ Hex:")
-                        .trim()
-                        .to_string();
-                    lines.next();
-                } else if next_line.trim().starts_with("→ Decimal:") {
-                    decimal_uid = next_line
-                        .trim()
-                        .trim_start_matches("→ Decimal:")
-                        .trim()
-                        .to_string();
-                    lines.next();
-                } else if next_line.trim().starts_with("→ Manufacturer:") {
-                    manufacturer = next_line
-                        .trim()
-                        .trim_start_matches("→ Manufacturer:")
-                        .trim()
-                        .to_string();
-                    lines.next();
-                } else if next_line.trim().starts_with("→ Format:") {
-                    format = next_line
-                        .trim()
-                        .trim_start_matches("→ Format:")
-                        .trim()
-                        .to_string();
-                    lines.next();
-                } else {
-                    // Not a continuation line, break
-                    break;
-                }
-            }
-            
-            // Add the record if we have the minimum data
-            if !hex_uid.is_empty() {
-                records.push(CardRecord {
-                    timestamp,
-                    raw_uid,
-                    hex_uid,
-                    decimal_uid,
-                    manufacturer,
-                    format,
-                });
-            }
-        }
-    }
-    
-    records
-}
\ No newline at end of file
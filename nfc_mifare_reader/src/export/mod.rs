@@ -6,5 +6,5 @@ pub use formats::{
     ExportFormat,
     CardRecord,
     export_data,
-    parse_display_text
+    timestamped_export_path
 };
\ No newline at end of file
@@ -0,0 +1,128 @@
+// history/db.rs
+use rusqlite::{params, Connection, Result};
+
+use crate::export::CardRecord;
+
+/// One scan as kept in the scan history
+#[derive(Debug, Clone)]
+pub struct HistoryScan {
+    pub unix_time: i64,
+    // Local time, "YYYY-MM-DD HH:MM:SS" so it sorts and filters as text
+    pub scanned_at: String,
+    pub raw_uid: String,
+    pub hex_uid: String,
+    pub decimal_uid: String,
+    pub manufacturer: String,
+    pub format: String,
+    // Layout the raw UID was decoded with, None for readers that send the UID itself
+    pub keyboard_layout: Option<i32>,
+    pub reader: String,
+}
+
+impl HistoryScan {
+    pub fn from_record(record: &CardRecord, keyboard_layout: Option<i32>, reader: &str) -> Self {
+        HistoryScan {
+            unix_time: record.timestamp.parse().unwrap_or(0),
+            scanned_at: record.captured_at.clone(),
+            raw_uid: record.raw_uid.clone(),
+            hex_uid: record.hex_uid.clone(),
+            decimal_uid: record.decimal_uid.clone(),
+            manufacturer: record.manufacturer.clone(),
+            format: record.format.clone(),
+            keyboard_layout,
+            reader: reader.to_string(),
+        }
+    }
+
+    /// The scan as an export record
+    pub fn to_record(&self) -> CardRecord {
+        CardRecord {
+            timestamp: self.unix_time.to_string(),
+            raw_uid: self.raw_uid.clone(),
+            hex_uid: self.hex_uid.clone(),
+            decimal_uid: self.decimal_uid.clone(),
+            manufacturer: self.manufacturer.clone(),
+            format: self.format.clone(),
+            captured_at: self.scanned_at.clone(),
+            notes: Vec::new(),
+        }
+    }
+}
+
+// Every raw scan, kept in the inventory database file but apart from the inventory
+pub struct ScanHistory {
+    conn: Connection,
+}
+
+impl ScanHistory {
+    // Open the database, adding the history table on first use
+    pub fn new(db_path: &str) -> Result<Self> {
+        let conn = Connection::open(db_path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS scan_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                unix_time INTEGER NOT NULL,
+                scanned_at TEXT NOT NULL,
+                raw_uid TEXT NOT NULL,
+                hex_uid TEXT NOT NULL,
+                decimal_uid TEXT NOT NULL,
+                manufacturer TEXT NOT NULL,
+                format TEXT NOT NULL,
+                keyboard_layout INTEGER,
+                reader TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS scan_history_time ON scan_history (scanned_at);"
+        )?;
+        Ok(ScanHistory { conn })
+    }
+
+    // Store a scan
+    pub fn add_scan(&self, scan: &HistoryScan) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO scan_history
+                (unix_time, scanned_at, raw_uid, hex_uid, decimal_uid, manufacturer, format, keyboard_layout, reader)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                scan.unix_time,
+                scan.scanned_at,
+                scan.raw_uid,
+                scan.hex_uid,
+                scan.decimal_uid,
+                scan.manufacturer,
+                scan.format,
+                scan.keyboard_layout,
+                scan.reader,
+            ],
+        )?;
+        Ok(())
+    }
+
+    // Scans between two days, "YYYY-MM-DD" and both included, oldest first. A missing
+    // day leaves that end of the range open.
+    pub fn scans_between(&self, from: Option<&str>, to: Option<&str>) -> Result<Vec<HistoryScan>> {
+        // a day's last scan sorts before the next day
+        let from = from.map(|day| format!("{} 00:00:00", day)).unwrap_or_default();
+        let to = to.map(|day| format!("{} 23:59:59", day)).unwrap_or_else(|| "9999".to_string());
+
+        let mut stmt = self.conn.prepare(
+            "SELECT unix_time, scanned_at, raw_uid, hex_uid, decimal_uid, manufacturer, format, keyboard_layout, reader
+             FROM scan_history WHERE scanned_at >= ? AND scanned_at <= ? ORDER BY id"
+        )?;
+
+        let scan_iter = stmt.query_map(params![from, to], |row| {
+            Ok(HistoryScan {
+                unix_time: row.get(0)?,
+                scanned_at: row.get(1)?,
+                raw_uid: row.get(2)?,
+                hex_uid: row.get(3)?,
+                decimal_uid: row.get(4)?,
+                manufacturer: row.get(5)?,
+                format: row.get(6)?,
+                keyboard_layout: row.get(7)?,
+                reader: row.get(8)?,
+            })
+        })?;
+
+        scan_iter.collect()
+    }
+}
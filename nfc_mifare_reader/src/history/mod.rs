@@ -0,0 +1,61 @@
+// history/mod.rs
+//
+// Every scan the reader tab logs is also stored in the scan history, so a session's
+// scans are still there after the app is closed. The history lives in its own table
+// of the inventory database and is never changed by stock operations.
+pub mod db;
+#[cfg(feature = "gui")]
+pub mod ui;
+
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+
+use crate::export::CardRecord;
+use crate::i18n::tr_args;
+
+pub use db::{HistoryScan, ScanHistory};
+#[cfg(feature = "gui")]
+pub use ui::create_history_tab;
+
+static HISTORY: Lazy<Mutex<Option<ScanHistory>>> = Lazy::new(|| Mutex::new(None));
+
+/// Open the scan history in the database at `db_path`, scans aren't stored until it is
+pub fn open_history(db_path: &str) -> rusqlite::Result<()> {
+    let history = ScanHistory::new(db_path)?;
+    if let Ok(mut current) = HISTORY.lock() {
+        *current = Some(history);
+    }
+    Ok(())
+}
+
+/// Close the scan history, later scans aren't stored. Skipped if the history is in
+/// use (a panic while a scan was being stored).
+pub fn close_history() {
+    if let Ok(mut history) = HISTORY.try_lock() {
+        *history = None;
+    }
+}
+
+/// Store a scan, `reader` names where it came from and `keyboard_layout` is the layout
+/// its raw UID was decoded with. A failure is reported on the console, the scan is
+/// still shown and handled.
+pub fn record_scan(record: &CardRecord, keyboard_layout: Option<i32>, reader: &str) {
+    if let Ok(history) = HISTORY.lock() {
+        if let Some(history) = history.as_ref() {
+            if let Err(e) = history.add_scan(&HistoryScan::from_record(record, keyboard_layout, reader)) {
+                println!("{}", tr_args("err-history-save", &[("error", &e.to_string())]));
+            }
+        }
+    }
+}
+
+/// Stored scans between two days, "YYYY-MM-DD" and both included
+pub fn scans_between(from: Option<&str>, to: Option<&str>) -> rusqlite::Result<Vec<HistoryScan>> {
+    match HISTORY.lock() {
+        Ok(history) => match history.as_ref() {
+            Some(history) => history.scans_between(from, to),
+            None => Ok(Vec::new()),
+        },
+        Err(_) => Ok(Vec::new()),
+    }
+}
@@ -0,0 +1,158 @@
+// history/ui.rs
+use std::cell::RefCell;
+use std::rc::Rc;
+use chrono::{Local, NaiveDate};
+use fltk::{
+    button::Button,
+    dialog,
+    enums::{Align, Font},
+    frame::Frame,
+    group::Tabs,
+    input::Input,
+    prelude::*,
+    text::{TextBuffer, TextDisplay},
+};
+
+use crate::export::{self, CardRecord, ExportFormat};
+use crate::history::{self, HistoryScan};
+use crate::i18n::{tr, tr_args};
+use crate::ui::layout;
+
+// Stored scans listed in the tab, the rest of a range is only counted and exported
+const DISPLAYED_SCANS: usize = 1000;
+
+/// History tab, the stored scans of a date range with an export of that range
+pub fn create_history_tab(tabs: &mut Tabs) {
+    let mut history_tab = layout::tab_page(tabs, &tr("tab-history"));
+
+    let mut filter_row = layout::fixed_row(&mut history_tab, layout::ROW_HEIGHT);
+    let from_label = Frame::default().with_label(&tr("history-from"));
+    filter_row.fixed(&from_label, 50);
+    let mut from_input = Input::default();
+    from_input.set_tooltip(&tr("history-date-tooltip"));
+    filter_row.fixed(&from_input, 110);
+    let to_label = Frame::default().with_label(&tr("history-to"));
+    filter_row.fixed(&to_label, 30);
+    let mut to_input = Input::default();
+    to_input.set_tooltip(&tr("history-date-tooltip"));
+    filter_row.fixed(&to_input, 110);
+    let mut filter_btn = Button::default().with_label(&tr("history-filter"));
+    filter_row.fixed(&filter_btn, 80);
+    let mut today_btn = Button::default().with_label(&tr("history-today"));
+    filter_row.fixed(&today_btn, 80);
+    let mut all_btn = Button::default().with_label(&tr("history-all"));
+    filter_row.fixed(&all_btn, 80);
+    Frame::default();
+    filter_row.end();
+
+    let mut status_row = layout::fixed_row(&mut history_tab, layout::ROW_HEIGHT);
+    let mut count_label = Frame::default();
+    count_label.set_align(Align::Left | Align::Inside);
+    let mut export_btn = Button::default().with_label(&tr("history-export"));
+    status_row.fixed(&export_btn, 120);
+    status_row.end();
+
+    let history_buffer = TextBuffer::default();
+    let mut history_display = TextDisplay::default();
+    history_display.set_buffer(history_buffer.clone());
+    history_display.set_text_font(Font::Courier);
+
+    history_tab.end();
+    tabs.add(&history_tab);
+
+    // Scans of the range on display, for the export
+    let shown: Rc<RefCell<Vec<HistoryScan>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // this reloads the range in the date inputs, a bad date leaves the display as it was
+    let reload: Rc<dyn Fn()> = {
+        let from_input = from_input.clone();
+        let to_input = to_input.clone();
+        let shown = shown.clone();
+        Rc::new(move || {
+            let (from, to) = match (parse_day(&from_input.value()), parse_day(&to_input.value())) {
+                (Ok(from), Ok(to)) => (from, to),
+                (Err(day), _) | (_, Err(day)) => {
+                    dialog::alert(300, 300, &tr_args("err-history-date", &[("date", &day)]));
+                    return;
+                }
+            };
+            match history::scans_between(from.as_deref(), to.as_deref()) {
+                Ok(scans) => {
+                    history_buffer.clone().set_text(&render_scans(&scans));
+                    count_label.clone().set_label(&tr_args("history-count", &[("count", &scans.len().to_string())]));
+                    *shown.borrow_mut() = scans;
+                },
+                Err(e) => dialog::alert(300, 300, &tr_args("err-history-load", &[("error", &e.to_string())])),
+            }
+        })
+    };
+    reload();
+
+    let reload_filter = reload.clone();
+    filter_btn.set_callback(move |_| reload_filter());
+
+    let reload_today = reload.clone();
+    let mut from_today = from_input.clone();
+    let mut to_today = to_input.clone();
+    today_btn.set_callback(move |_| {
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        from_today.set_value(&today);
+        to_today.set_value(&today);
+        reload_today();
+    });
+
+    let reload_all = reload.clone();
+    all_btn.set_callback(move |_| {
+        from_input.set_value("");
+        to_input.set_value("");
+        reload_all();
+    });
+
+    export_btn.set_callback(move |_| {
+        let records: Vec<CardRecord> = shown.borrow().iter().map(HistoryScan::to_record).collect();
+        if records.is_empty() {
+            dialog::message(300, 300, &tr("history-export-empty"));
+            return;
+        }
+        if let Some(path) = dialog::file_chooser(&tr("history-export-title"), "*.{csv,json,txt}", ".", false) {
+            match export::export_data(&records, ExportFormat::from_path(&path), &path) {
+                Ok(message) => dialog::message(300, 300, &message),
+                Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())])),
+            }
+        }
+    });
+}
+
+// this takes a "YYYY-MM-DD" date input, blank for an open end of the range
+fn parse_day(input: &str) -> Result<Option<String>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d")
+        .map(|day| Some(day.format("%Y-%m-%d").to_string()))
+        .map_err(|_| input.to_string())
+}
+
+// this lays the scans out in columns, newest first
+fn render_scans(scans: &[HistoryScan]) -> String {
+    let mut text = format!(
+        "{:<19}  {:<20}  {:<14}  {:<20}  {}\n",
+        tr("history-col-time"),
+        tr("history-col-hex"),
+        tr("history-col-reader"),
+        tr("history-col-manufacturer"),
+        tr("history-col-raw")
+    );
+    for scan in scans.iter().rev().take(DISPLAYED_SCANS) {
+        text.push_str(&format!(
+            "{:<19}  {:<20}  {:<14}  {:<20}  {}\n",
+            scan.scanned_at, scan.hex_uid, scan.reader, scan.manufacturer, scan.raw_uid
+        ));
+    }
+    if scans.len() > DISPLAYED_SCANS {
+        let more = (scans.len() - DISPLAYED_SCANS).to_string();
+        text.push_str(&format!("\n{}\n", tr_args("history-more", &[("count", &more)])));
+    }
+    text
+}
@@ -0,0 +1,40 @@
+// i18n.rs
+//
+// Translated user-facing text. Each language has a catalogue in locales/<code>.ftl,
+// built into the binary and read by the text_catalogue crate, which also has the
+// block editor's and the attack toolkit's. See there for the syntax.
+//
+// Lookups fall back to English, then to the key itself, so a missing translation
+// shows up as English text rather than an empty label. The language can be changed
+// at runtime; menus are rebuilt and dialogs pick it up the next time they open.
+use text_catalogue::Catalogues;
+
+pub use text_catalogue::AUTO_LANGUAGE;
+
+/// Built-in languages, code and name in that language
+pub const LANGUAGES: [(&str, &str); 3] = [
+    ("en", "English"),
+    ("es", "Español"),
+    ("it", "Italiano"),
+];
+
+static CATALOGUES: Catalogues = Catalogues::new(&[
+    ("en", include_str!("../locales/en.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("it", include_str!("../locales/it.ftl")),
+]);
+
+/// Switch the language used by tr(), "auto" picks it from LANG (e.g. es_ES.UTF-8)
+pub fn set_language(setting: &str) {
+    CATALOGUES.set_language(setting);
+}
+
+/// Text for a message key in the current language
+pub fn tr(key: &str) -> String {
+    CATALOGUES.tr(key)
+}
+
+/// Text for a message key with its { $name } placeables filled in
+pub fn tr_args(key: &str, args: &[(&str, &str)]) -> String {
+    CATALOGUES.tr_args(key, args)
+}
@@ -0,0 +1,631 @@
+// integrations.rs
+//
+// Outbound integrations: stock changes pushed to an ERP (Odoo, ERPNext) or any HTTP
+// API. Each integration maps inventory events to one HTTP request with a templated
+// JSON body, sent with curl like the scan rule webhooks. They are edited in Preferences.
+//
+// This station's operation log (inventory_db/src/oplog.rs) is the retry queue. Each integration
+// keeps a cursor, the last local operation it has pushed, and a timer sends whatever came
+// after it in order. A request that fails stops that integration where it is and is tried
+// again later, waiting longer after each failure, so nothing is lost while the ERP is down
+// or the station is offline. A new integration starts with the changes made after it was
+// added; renaming one starts it over from there.
+//
+// Templates name event fields in double braces. In the body a field becomes a JSON value,
+// so text is quoted for you, in the URL and headers it is plain text:
+//   {"sku": {{tag_id}}, "name": {{name}}, "qty": {{delta}}, "ref": {{reference}}}
+// ${NAME} in the headers or body is read from the environment when the request is sent,
+// so API keys and passwords needn't be kept in the settings file.
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::io::Write;
+use std::process::{Command, Stdio};
+#[cfg(feature = "gui")]
+use std::rc::Rc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+#[cfg(feature = "gui")]
+use fltk::app;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config;
+use crate::i18n::{tr, tr_args};
+use crate::inventory::model::{create_inventory_item, generate_timestamp, InventoryItem, StockMode};
+use crate::inventory::oplog::{Change, Operation};
+use crate::inventory::InventoryDB;
+
+/// How often pending changes are pushed, in seconds
+const PUSH_CHECK: f64 = 15.0;
+/// Most operations one integration sends per push
+const BATCH_SIZE: usize = 50;
+/// Seconds curl waits for an answer
+const REQUEST_TIMEOUT: &str = "15";
+/// Wait after the first failure, doubled after each one up to MAX_RETRY_DELAY
+const RETRY_DELAY: Duration = Duration::from_secs(30);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30 * 60);
+
+/// HTTP methods an integration can use, in the order the editor lists them
+pub const METHODS: [&str; 3] = ["POST", "PUT", "PATCH"];
+
+/// Fields a template can name, in the order the editor lists them
+pub const FIELDS: [&str; 15] = [
+    "event", "tag_id", "name", "description", "quantity", "delta", "amount", "mode",
+    "location", "category", "expiry_date", "time", "station", "seq", "reference",
+];
+
+/// What happened to an item
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    // Added, or its name, description, location, category, expiry date or tags changed
+    ItemSaved,
+    StockReceived,
+    StockIssued,
+    StockCounted,
+    // A quantity change from before stock modes, or typed into the form
+    StockAdjusted,
+    ItemDeleted,
+}
+
+/// Events in the order the editor lists them
+pub const ALL_EVENT_KINDS: [EventKind; 6] = [
+    EventKind::ItemSaved,
+    EventKind::StockReceived,
+    EventKind::StockIssued,
+    EventKind::StockCounted,
+    EventKind::StockAdjusted,
+    EventKind::ItemDeleted,
+];
+
+impl EventKind {
+    /// The value of {{event}}
+    pub fn key(&self) -> &'static str {
+        match self {
+            EventKind::ItemSaved => "item_saved",
+            EventKind::StockReceived => "stock_received",
+            EventKind::StockIssued => "stock_issued",
+            EventKind::StockCounted => "stock_counted",
+            EventKind::StockAdjusted => "stock_adjusted",
+            EventKind::ItemDeleted => "item_deleted",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        tr(&format!("event-{}", self.key().replace('_', "-")))
+    }
+}
+
+/// An HTTP call made for inventory events
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Integration {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    // Events pushed, every event when empty
+    #[serde(default)]
+    pub events: Vec<EventKind>,
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub url: String,
+    // "Name: value" lines, e.g. "Authorization: Bearer ${ERP_TOKEN}"
+    #[serde(default)]
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_method() -> String {
+    METHODS[0].to_string()
+}
+
+/// A starting point for a new integration in the editor
+pub struct Preset {
+    pub name: &'static str,
+    pub method: &'static str,
+    pub url: &'static str,
+    pub headers: &'static [&'static str],
+    pub events: &'static [EventKind],
+    pub body: &'static str,
+}
+
+pub const PRESETS: [Preset; 3] = [
+    Preset {
+        name: "Custom API",
+        method: "POST",
+        url: "https://example.com/api/stock-events",
+        headers: &["Authorization: Bearer ${API_TOKEN}"],
+        events: &[],
+        body: r#"{
+  "event": {{event}},
+  "tag_id": {{tag_id}},
+  "name": {{name}},
+  "quantity": {{quantity}},
+  "delta": {{delta}},
+  "location": {{location}},
+  "time": {{time}},
+  "reference": {{reference}}
+}"#,
+    },
+    // a Material Receipt, a second integration with "Material Issue" and s_warehouse
+    // books issues
+    Preset {
+        name: "ERPNext Stock Entry",
+        method: "POST",
+        url: "https://erp.example.com/api/resource/Stock%20Entry",
+        headers: &["Authorization: token ${ERPNEXT_API_KEY}:${ERPNEXT_API_SECRET}"],
+        events: &[EventKind::StockReceived],
+        body: r#"{
+  "stock_entry_type": "Material Receipt",
+  "docstatus": 1,
+  "remarks": {{reference}},
+  "items": [
+    {"item_code": {{tag_id}}, "qty": {{amount}}, "t_warehouse": "Stores - EX"}
+  ]
+}"#,
+    },
+    // records each event in a model made in Odoo Studio, the uid is the API user's id
+    Preset {
+        name: "Odoo JSON-RPC",
+        method: "POST",
+        url: "https://odoo.example.com/jsonrpc",
+        headers: &[],
+        events: &[],
+        body: r#"{
+  "jsonrpc": "2.0",
+  "method": "call",
+  "params": {
+    "service": "object",
+    "method": "execute_kw",
+    "args": ["${ODOO_DB}", 2, "${ODOO_API_KEY}", "x_rfid_stock_event", "create", [{
+      "x_name": {{reference}},
+      "x_event": {{event}},
+      "x_tag_id": {{tag_id}},
+      "x_item": {{name}},
+      "x_quantity": {{quantity}},
+      "x_delta": {{delta}}
+    }]]
+  }
+}"#,
+    },
+];
+
+impl Preset {
+    pub fn integration(&self) -> Integration {
+        Integration {
+            name: self.name.to_string(),
+            enabled: true,
+            events: self.events.to_vec(),
+            method: self.method.to_string(),
+            url: self.url.to_string(),
+            headers: self.headers.iter().map(|header| header.to_string()).collect(),
+            body: self.body.to_string(),
+        }
+    }
+}
+
+/// One change to an item, as the templates see it
+#[derive(Clone, Debug)]
+pub struct StockEvent {
+    pub kind: EventKind,
+    pub tag_id: String,
+    // The item as it is when the event is pushed, None once it is deleted
+    pub item: Option<InventoryItem>,
+    pub delta: i32,
+    pub mode: Option<StockMode>,
+    pub time: String,
+    pub station: String,
+    pub seq: u64,
+}
+
+impl StockEvent {
+    /// The event a logged operation stands for. `item` is the item now; for a saved
+    /// item that has since been deleted the fields logged with the operation are used.
+    pub fn from_operation(op: &Operation, item: Option<InventoryItem>) -> StockEvent {
+        let (kind, delta, mode, item) = match &op.change {
+            Change::Upsert { item: fields } => (EventKind::ItemSaved, 0, None, item.or_else(|| Some(fields.clone()))),
+            Change::Adjust { delta, mode } => {
+                let kind = match mode {
+                    Some(StockMode::Receive) => EventKind::StockReceived,
+                    Some(StockMode::Issue) => EventKind::StockIssued,
+                    Some(StockMode::Count) => EventKind::StockCounted,
+                    None => EventKind::StockAdjusted,
+                };
+                (kind, *delta, *mode, item)
+            },
+            Change::Delete => (EventKind::ItemDeleted, 0, None, None),
+            Change::TagAdded { .. } | Change::TagDeactivated { .. } => (EventKind::ItemSaved, 0, None, item),
+        };
+        StockEvent {
+            kind,
+            tag_id: op.tag_id.clone(),
+            item,
+            delta,
+            mode,
+            time: op.time.clone(),
+            station: op.station.clone(),
+            seq: op.seq,
+        }
+    }
+
+    /// A made up event of `kind` for trying out a mapping
+    pub fn sample(kind: EventKind) -> StockEvent {
+        let item = create_inventory_item("04A1B2C3", "Sample item", None, 12, Some("Shelf A"), Some("Parts"));
+        let (delta, mode) = match kind {
+            EventKind::StockReceived => (2, Some(StockMode::Receive)),
+            EventKind::StockIssued => (-2, Some(StockMode::Issue)),
+            EventKind::StockCounted => (-1, Some(StockMode::Count)),
+            EventKind::StockAdjusted => (5, None),
+            EventKind::ItemSaved | EventKind::ItemDeleted => (0, None),
+        };
+        StockEvent {
+            kind,
+            tag_id: item.tag_id.clone(),
+            item: (kind != EventKind::ItemDeleted).then_some(item),
+            delta,
+            mode,
+            time: generate_timestamp(),
+            station: "test-station".to_string(),
+            seq: 1,
+        }
+    }
+
+    // this looks up a template field, None for a name that isn't one
+    fn field(&self, name: &str) -> Option<Value> {
+        let item = self.item.as_ref();
+        let text = |value: Option<&String>| value.map_or(Value::Null, |value| Value::from(value.as_str()));
+        let value = match name {
+            "event" => Value::from(self.kind.key()),
+            "tag_id" => Value::from(self.tag_id.as_str()),
+            "name" => text(item.map(|item| &item.name)),
+            "description" => text(item.and_then(|item| item.description.as_ref())),
+            "quantity" => item.map_or(Value::Null, |item| Value::from(item.quantity)),
+            "delta" => Value::from(self.delta),
+            "amount" => Value::from(self.delta.abs()),
+            "mode" => self.mode.map_or(Value::Null, |mode| serde_json::to_value(mode).unwrap_or(Value::Null)),
+            "location" => text(item.and_then(|item| item.location.as_ref())),
+            "category" => text(item.and_then(|item| item.category.as_ref())),
+            "expiry_date" => text(item.and_then(|item| item.expiry_date.as_ref())),
+            "time" => Value::from(self.time.as_str()),
+            "station" => Value::from(self.station.as_str()),
+            "seq" => Value::from(self.seq),
+            // the same on every retry, for ERPs that can skip a request they already have
+            "reference" => Value::from(format!("{}-{}", self.station, self.seq)),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// A request ready to send, ${NAME} still unexpanded
+#[derive(Clone, Debug, PartialEq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+impl HttpRequest {
+    /// The request as text, for the mapping test
+    pub fn describe(&self) -> String {
+        let mut text = format!("{} {}\n", self.method, self.url);
+        for header in &self.headers {
+            text.push_str(header);
+            text.push('\n');
+        }
+        text.push('\n');
+        text.push_str(&self.body);
+        text
+    }
+}
+
+impl Integration {
+    pub fn wants(&self, kind: EventKind) -> bool {
+        self.events.is_empty() || self.events.contains(&kind)
+    }
+
+    /// Fill in the templates for one event, checking that the body is JSON
+    pub fn request(&self, event: &StockEvent) -> Result<HttpRequest, String> {
+        let body = render(&self.body, event, |value| value.to_string())?;
+        serde_json::from_str::<Value>(&body)
+            .map_err(|e| tr_args("err-integration-json", &[("error", &e.to_string())]))?;
+        let url = render(&self.url, event, |value| encode_url_part(&plain_text(value)))?;
+        let headers = self.headers.iter()
+            .filter(|header| !header.trim().is_empty())
+            .map(|header| render(header, event, plain_text))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HttpRequest { method: self.method.clone(), url, headers, body })
+    }
+
+    /// Check the settings the editor can't, the templates against a sample event
+    pub fn check(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err(tr("err-integration-name"));
+        }
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(tr("err-integration-url"));
+        }
+        if let Some(header) = self.headers.iter().find(|header| !header.trim().is_empty() && !header.contains(':')) {
+            return Err(tr_args("err-integration-header", &[("header", header)]));
+        }
+        self.request(&StockEvent::sample(EventKind::StockReceived)).map(|_| ())
+    }
+
+    /// One line for the Preferences list: the name, method and URL, and how the pushes go
+    pub fn describe(&self, status: &PushStatus) -> String {
+        let state = if !self.enabled {
+            tr("integration-off")
+        } else if let Some(error) = &status.last_error {
+            tr_args("integration-failing", &[("pending", &status.pending.to_string()), ("error", error)])
+        } else {
+            tr_args("integration-pending", &[("pending", &status.pending.to_string())])
+        };
+        format!("{}  —  {} {}  —  {}", self.name, self.method, self.url, state)
+    }
+}
+
+// this replaces each {{field}} with `format(value)`
+fn render(template: &str, event: &StockEvent, format: impl Fn(Value) -> String) -> Result<String, String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find("}}")
+            .ok_or_else(|| tr("err-integration-braces"))?;
+        let name = after[..end].trim();
+        let value = event.field(name)
+            .ok_or_else(|| tr_args("err-integration-field", &[("field", name)]))?;
+        output.push_str(&format(value));
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+// this is a field as plain text, null as nothing
+fn plain_text(value: Value) -> String {
+    match value {
+        Value::String(text) => text,
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+// this percent-encodes everything but the characters that are safe in a URL path
+fn encode_url_part(text: &str) -> String {
+    text.bytes().map(|byte| match byte {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+        _ => format!("%{:02X}", byte),
+    }).collect()
+}
+
+// this replaces ${NAME} with the environment variable, an unset one with nothing
+fn expand_env(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        match rest[start + 2..].find('}') {
+            Some(end) => {
+                output.push_str(&env::var(&rest[start + 2..start + 2 + end]).unwrap_or_default());
+                rest = &rest[start + 3 + end..];
+            },
+            None => {
+                output.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Send a request and wait for the answer, any status from 400 up is a failure
+pub fn send(request: &HttpRequest) -> Result<(), String> {
+    let failed = |error: &str| tr_args("err-integration-send", &[("error", error)]);
+    let mut command = Command::new("curl");
+    command
+        .args(["-fsS", "-m", REQUEST_TIMEOUT, "-o", "/dev/null", "-X", &request.method])
+        .args(["-H", "Content-Type: application/json"]);
+    for header in &request.headers {
+        command.arg("-H").arg(expand_env(header));
+    }
+    // the body goes in on stdin, it can be longer than an argument may be
+    command
+        .args(["--data-binary", "@-"])
+        .arg(&request.url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+
+    let mut child = command.spawn().map_err(|e| failed(&e.to_string()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(expand_env(&request.body).as_bytes()).map_err(|e| failed(&e.to_string()))?;
+    }
+    let output = child.wait_with_output().map_err(|e| failed(&e.to_string()))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(failed(if error.is_empty() { "curl" } else { &error }))
+    }
+}
+
+/// How an integration's pushes are going
+#[derive(Clone, Debug, Default)]
+pub struct PushStatus {
+    // Local changes not pushed yet
+    pub pending: u64,
+    pub last_error: Option<String>,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+// requests for one integration, in log order; None for an event it doesn't want
+struct PushJob {
+    name: String,
+    steps: Vec<(u64, Option<HttpRequest>)>,
+}
+
+// how far a job got
+struct PushResult {
+    name: String,
+    pushed: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+struct Pushes {
+    in_flight: bool,
+    finished: Vec<PushResult>,
+    status: HashMap<String, PushStatus>,
+}
+
+static PUSHES: Lazy<Mutex<Pushes>> = Lazy::new(|| Mutex::new(Pushes::default()));
+
+/// Push pending changes every few seconds for as long as the app runs
+#[cfg(feature = "gui")]
+pub fn schedule_pushes(db: Rc<RefCell<InventoryDB>>) {
+    app::add_timeout3(PUSH_CHECK, move |handle| {
+        push_pending(&db);
+        app::repeat_timeout3(PUSH_CHECK, handle);
+    });
+}
+
+/// How the pushes of the integration called `name` are going
+pub fn status(name: &str) -> PushStatus {
+    PUSHES.lock().unwrap().status.get(name).cloned().unwrap_or_default()
+}
+
+/// Try integrations that are waiting after a failure at the next push
+pub fn retry_now() {
+    for status in PUSHES.lock().unwrap().status.values_mut() {
+        status.retry_at = None;
+    }
+}
+
+// this stores what the last push got through, then sends the next batch in the
+// background; a database that is busy is pushed next time
+fn push_pending(db: &RefCell<InventoryDB>) {
+    let db = match db.try_borrow() {
+        Ok(db) => db,
+        Err(_) => return,
+    };
+    let integrations: Vec<Integration> = match config::APP_CONFIG.lock() {
+        Ok(config) => config.integrations.clone(),
+        Err(_) => return,
+    };
+
+    let mut pushes = PUSHES.lock().unwrap();
+    for result in std::mem::take(&mut pushes.finished) {
+        if let Some(seq) = result.pushed {
+            if let Err(e) = db.set_integration_cursor(&result.name, seq) {
+                eprintln!("{}", tr_args("err-integration-failed", &[("name", &result.name), ("error", &e.to_string())]));
+            }
+        }
+        record_outcome(pushes.status.entry(result.name.clone()).or_default(), &result.name, result.error);
+    }
+    if pushes.in_flight {
+        return;
+    }
+
+    let last_seq = db.last_local_seq().unwrap_or(0);
+    let mut jobs = Vec::new();
+    for integration in integrations.iter().filter(|integration| integration.enabled) {
+        let status = pushes.status.entry(integration.name.clone()).or_default();
+        let cursor = db.integration_cursor(&integration.name).ok().flatten().unwrap_or(last_seq);
+        status.pending = last_seq.saturating_sub(cursor);
+        if status.retry_at.is_some_and(|at| at > Instant::now()) {
+            continue;
+        }
+        match pending_job(&db, integration) {
+            Ok(Some(job)) => jobs.push(job),
+            Ok(None) => {},
+            Err(e) => record_outcome(status, &integration.name, Some(e)),
+        }
+    }
+    if jobs.is_empty() {
+        return;
+    }
+
+    pushes.in_flight = true;
+    drop(pushes);
+    thread::spawn(move || {
+        let results: Vec<PushResult> = jobs.into_iter().map(run_job).collect();
+        let mut pushes = PUSHES.lock().unwrap();
+        pushes.finished.extend(results);
+        pushes.in_flight = false;
+    });
+}
+
+// this builds the requests for the operations after the integration's cursor, a new
+// integration starts after the last operation there is now
+fn pending_job(db: &InventoryDB, integration: &Integration) -> Result<Option<PushJob>, String> {
+    let cursor = match db.integration_cursor(&integration.name).map_err(|e| e.to_string())? {
+        Some(seq) => seq,
+        None => {
+            let seq = db.last_local_seq().map_err(|e| e.to_string())?;
+            db.set_integration_cursor(&integration.name, seq).map_err(|e| e.to_string())?;
+            return Ok(None);
+        }
+    };
+    let ops = db.local_operations_after(cursor, BATCH_SIZE).map_err(|e| e.to_string())?;
+    if ops.is_empty() {
+        return Ok(None);
+    }
+
+    let mut steps = Vec::with_capacity(ops.len());
+    for op in &ops {
+        let item = db.get_item(&op.tag_id).map_err(|e| e.to_string())?;
+        let event = StockEvent::from_operation(op, item);
+        let request = if integration.wants(event.kind) {
+            Some(integration.request(&event)?)
+        } else {
+            None
+        };
+        steps.push((op.seq, request));
+    }
+    Ok(Some(PushJob { name: integration.name.clone(), steps }))
+}
+
+// this sends a job's requests in order and stops at the first that fails
+fn run_job(job: PushJob) -> PushResult {
+    let mut pushed = None;
+    for (seq, request) in &job.steps {
+        if let Some(request) = request {
+            if let Err(e) = send(request) {
+                return PushResult { name: job.name, pushed, error: Some(e) };
+            }
+        }
+        pushed = Some(*seq);
+    }
+    PushResult { name: job.name, pushed, error: None }
+}
+
+// this clears a failure, or schedules the next try after one
+fn record_outcome(status: &mut PushStatus, name: &str, error: Option<String>) {
+    match error {
+        None => {
+            status.failures = 0;
+            status.retry_at = None;
+            status.last_error = None;
+        },
+        Some(error) => {
+            status.failures += 1;
+            let delay = RETRY_DELAY
+                .saturating_mul(2u32.saturating_pow(status.failures - 1))
+                .min(MAX_RETRY_DELAY);
+            status.retry_at = Some(Instant::now() + delay);
+            eprintln!("{}", tr_args("err-integration-failed", &[("name", name), ("error", &error)]));
+            status.last_error = Some(error);
+        }
+    }
+}
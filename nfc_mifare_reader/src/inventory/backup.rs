@@ -0,0 +1,241 @@
+// inventory/backup.rs
+//
+// Rotating copies of inventory.db. A copy is taken before every import and once a
+// day while the app runs, named after when and why it was taken, for example
+// inventory-20240315-142501-import.db. Every copy from today is kept so each import
+// of the day can be undone; older ones are thinned out to the newest copy of each of
+// the last `backup_keep_daily` days, then the newest of each of the `backup_keep_weekly`
+// weeks before those. Restoring a copy backs up the database as it is first, so a
+// restore can be undone the same way.
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "gui")]
+use std::rc::Rc;
+use chrono::{Datelike, IsoWeek, Local, NaiveDate, NaiveDateTime};
+#[cfg(feature = "gui")]
+use fltk::app;
+
+use crate::config::{self, AppConfig};
+use crate::i18n::{tr, tr_args};
+use crate::inventory::InventoryDB;
+
+/// How often the app checks whether today's backup has been taken, in seconds
+const DAILY_CHECK: f64 = 3600.0;
+const FILE_PREFIX: &str = "inventory-";
+const FILE_TIME: &str = "%Y%m%d-%H%M%S";
+
+/// Why a backup was taken, the last part of its file name
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackupReason {
+    Daily,
+    Import,
+    // The database as it was before a backup was restored over it
+    Restore,
+}
+
+impl BackupReason {
+    fn name(self) -> &'static str {
+        match self {
+            BackupReason::Daily => "daily",
+            BackupReason::Import => "import",
+            BackupReason::Restore => "restore",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [BackupReason::Daily, BackupReason::Import, BackupReason::Restore]
+            .into_iter()
+            .find(|reason| reason.name() == name)
+    }
+
+    /// Translated, for the list of backups
+    pub fn label(self) -> String {
+        tr(match self {
+            BackupReason::Daily => "backup-daily",
+            BackupReason::Import => "backup-import",
+            BackupReason::Restore => "backup-restore",
+        })
+    }
+}
+
+/// One backup file
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub taken: NaiveDateTime,
+    pub reason: BackupReason,
+}
+
+impl Backup {
+    // this reads when and why the copy was taken from its file name, None for other files
+    fn from_path(path: &Path) -> Option<Self> {
+        let stem = path.file_name()?
+            .to_str()?
+            .strip_prefix(FILE_PREFIX)?
+            .strip_suffix(".db")?;
+        let (time, reason) = stem.rsplit_once('-')?;
+        Some(Backup {
+            path: path.to_path_buf(),
+            taken: NaiveDateTime::parse_from_str(time, FILE_TIME).ok()?,
+            reason: BackupReason::from_name(reason)?,
+        })
+    }
+
+    /// One line for the list of backups
+    pub fn label(&self) -> String {
+        tr_args("backup-entry", &[
+            ("time", &self.taken.format("%Y-%m-%d %H:%M:%S").to_string()),
+            ("reason", &self.reason.label()),
+        ])
+    }
+}
+
+/// Where the backups go and how many are kept
+#[derive(Clone, Debug)]
+pub struct BackupSettings {
+    pub directory: PathBuf,
+    pub keep_daily: usize,
+    pub keep_weekly: usize,
+}
+
+impl BackupSettings {
+    /// The backup settings of `config`, an empty directory uses the default one
+    pub fn from_config(config: &AppConfig) -> Self {
+        let directory = if config.backup_directory.trim().is_empty() {
+            AppConfig::default().backup_directory
+        } else {
+            config.backup_directory.clone()
+        };
+        BackupSettings {
+            directory: PathBuf::from(directory),
+            keep_daily: config.backup_keep_daily,
+            keep_weekly: config.backup_keep_weekly,
+        }
+    }
+
+    /// The settings in use
+    pub fn current() -> Self {
+        match config::APP_CONFIG.lock() {
+            Ok(config) => BackupSettings::from_config(&config),
+            Err(_) => BackupSettings::from_config(&AppConfig::default()),
+        }
+    }
+}
+
+/// The backups in `directory`, newest first
+pub fn list_backups(directory: &Path) -> Vec<Backup> {
+    let mut backups: Vec<Backup> = fs::read_dir(directory)
+        .map(|entries| entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Backup::from_path(&entry.path()))
+            .collect())
+        .unwrap_or_default();
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.taken));
+    backups
+}
+
+/// Copy the database to a new backup and thin out the old ones, returns the new file
+pub fn take_backup(db: &InventoryDB, reason: BackupReason) -> Result<PathBuf, String> {
+    let settings = BackupSettings::current();
+    let path = copy_database(db, &settings, reason)?;
+    prune_backups(&settings);
+    Ok(path)
+}
+
+/// Put the backup at `backup` in place of the database. The database as it is now is
+/// backed up first, that copy is returned so the restore can be undone.
+pub fn restore_backup(db: &mut InventoryDB, backup: &Path) -> Result<PathBuf, String> {
+    let settings = BackupSettings::current();
+    let undo = copy_database(db, &settings, BackupReason::Restore)?;
+    db.restore_from(backup)
+        .map_err(|e| tr_args("err-backup-restore", &[("error", &e.to_string())]))?;
+    prune_backups(&settings);
+    Ok(undo)
+}
+
+/// Take today's backup if it hasn't been taken yet, then check again every hour for
+/// as long as the app runs
+#[cfg(feature = "gui")]
+pub fn schedule_daily_backups(db: Rc<RefCell<InventoryDB>>) {
+    take_daily_backup_if_due(&db);
+    app::add_timeout3(DAILY_CHECK, move |handle| {
+        take_daily_backup_if_due(&db);
+        app::repeat_timeout3(DAILY_CHECK, handle);
+    });
+}
+
+// this takes the daily backup unless one was taken today
+fn take_daily_backup_if_due(db: &RefCell<InventoryDB>) {
+    let settings = BackupSettings::current();
+    let today = Local::now().date_naive();
+    let taken_today = list_backups(&settings.directory)
+        .iter()
+        .any(|backup| backup.reason == BackupReason::Daily && backup.taken.date() == today);
+    if taken_today {
+        return;
+    }
+    // the database is in use, the next check takes the backup
+    let db = match db.try_borrow() {
+        Ok(db) => db,
+        Err(_) => return,
+    };
+    match take_backup(&db, BackupReason::Daily) {
+        Ok(path) => println!("{}", tr_args("log-backup-taken", &[("path", &path.display().to_string())])),
+        Err(e) => println!("{}", e),
+    }
+}
+
+// this writes the copy without removing any old one
+fn copy_database(db: &InventoryDB, settings: &BackupSettings, reason: BackupReason) -> Result<PathBuf, String> {
+    let failed = |e: &dyn std::fmt::Display| tr_args("err-backup", &[("error", &e.to_string())]);
+    fs::create_dir_all(&settings.directory).map_err(|e| failed(&e))?;
+    let name = format!("{}{}-{}.db", FILE_PREFIX, Local::now().format(FILE_TIME), reason.name());
+    let path = settings.directory.join(name);
+    db.backup_to(&path).map_err(|e| failed(&e))?;
+    Ok(path)
+}
+
+// this removes the backups the retention doesn't keep, one that can't be removed is
+// tried again after the next backup
+fn prune_backups(settings: &BackupSettings) {
+    let backups = list_backups(&settings.directory);
+    let keep = backups_to_keep(&backups, Local::now().date_naive(), settings.keep_daily, settings.keep_weekly);
+    for backup in backups.iter().filter(|backup| !keep.contains(&backup.path)) {
+        if let Err(e) = fs::remove_file(&backup.path) {
+            println!("{}", tr_args("err-backup-remove", &[
+                ("path", &backup.path.display().to_string()),
+                ("error", &e.to_string()),
+            ]));
+        }
+    }
+}
+
+// Every backup from `today`, the newest of each of the `keep_daily` days before it that
+// have one, and the newest of each of the `keep_weekly` weeks before those. `backups`
+// is newest first.
+fn backups_to_keep(backups: &[Backup], today: NaiveDate, keep_daily: usize, keep_weekly: usize) -> HashSet<PathBuf> {
+    let mut keep = HashSet::new();
+    let mut days: Vec<NaiveDate> = Vec::new();
+    let mut weeks: Vec<IsoWeek> = Vec::new();
+    for backup in backups {
+        let day = backup.taken.date();
+        if day >= today {
+            keep.insert(backup.path.clone());
+        } else if days.contains(&day) {
+            // an older copy from a day that already has one
+        } else if days.len() < keep_daily {
+            days.push(day);
+            keep.insert(backup.path.clone());
+        } else if weeks.len() < keep_weekly {
+            // weeks the daily copies reach into count as kept
+            let week = day.iso_week();
+            if !weeks.contains(&week) && !days.iter().any(|kept| kept.iso_week() == week) {
+                weeks.push(week);
+                keep.insert(backup.path.clone());
+            }
+        }
+    }
+    keep
+}
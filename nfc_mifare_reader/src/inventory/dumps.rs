@@ -0,0 +1,106 @@
+// inventory/dumps.rs
+//
+// Card dumps kept with inventory items, so what is on a badge lives with its stock
+// record. A dump is attached from a file saved by the block editor or another tool: a
+// raw dump of 16 bytes a block (.bin, .mfd, .dump) or a Flipper Zero .nfc file. The
+// database keeps the raw bytes, bytes a tool couldn't read are stored as 00.
+use std::path::Path;
+
+use mifare_rc522::CardLayout;
+
+use crate::i18n::tr_args;
+
+/// The largest dump kept, a Mifare Classic 4K
+pub const MAX_DUMP_BYTES: usize = 4096;
+
+/// What was read from a dump file
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedDump {
+    /// In hex, without spaces
+    pub uid: String,
+    pub data: Vec<u8>,
+}
+
+/// Read the dump in a file's contents, `name` is the file name and tells the format
+pub fn parse_dump(name: &str, contents: &[u8]) -> Result<ParsedDump, String> {
+    let flipper = Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("nfc"));
+    let dump = if flipper {
+        let text = std::str::from_utf8(contents).map_err(|_| tr_args("err-dump-not-flipper", &[("file", name)]))?;
+        parse_flipper(name, text)?
+    } else {
+        parse_raw(name, contents)?
+    };
+
+    if dump.data.len() > MAX_DUMP_BYTES {
+        return Err(tr_args("err-dump-too-big", &[("file", name), ("bytes", &dump.data.len().to_string())]));
+    }
+    Ok(dump)
+}
+
+// this takes a raw dump, whose UID is the first 4 bytes of block 0
+fn parse_raw(name: &str, contents: &[u8]) -> Result<ParsedDump, String> {
+    if contents.is_empty() || !contents.len().is_multiple_of(16) {
+        return Err(tr_args("err-dump-not-raw", &[("file", name), ("bytes", &contents.len().to_string())]));
+    }
+    Ok(ParsedDump { uid: to_hex(&contents[..4]), data: contents.to_vec() })
+}
+
+// this reads the "UID:" line and the "Block <n>:" lines of a Flipper file, "??" for a
+// byte the Flipper couldn't read
+fn parse_flipper(name: &str, text: &str) -> Result<ParsedDump, String> {
+    let mut uid = None;
+    let mut blocks: Vec<(usize, [u8; 16])> = Vec::new();
+    for line in text.lines() {
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => continue,
+        };
+        if key == "UID" {
+            uid = Some(parse_hex_bytes(value).map(|bytes| to_hex(&bytes)).ok_or_else(|| tr_args("err-dump-uid", &[("file", name)]))?);
+        } else if let Some(block) = key.strip_prefix("Block ").and_then(|block| block.trim().parse::<usize>().ok()) {
+            let bytes = parse_hex_bytes(value)
+                .and_then(|bytes| <[u8; 16]>::try_from(bytes).ok())
+                .ok_or_else(|| tr_args("err-dump-block", &[("file", name), ("block", &block.to_string())]))?;
+            blocks.push((block, bytes));
+        }
+    }
+
+    let count = match blocks.iter().map(|(block, _)| block + 1).max() {
+        Some(count) if count * 16 <= MAX_DUMP_BYTES => count,
+        Some(_) => return Err(tr_args("err-dump-too-many-blocks", &[("file", name)])),
+        None => return Err(tr_args("err-dump-no-blocks", &[("file", name)])),
+    };
+    let mut data = vec![0u8; count * 16];
+    for (block, bytes) in blocks {
+        data[block * 16..(block + 1) * 16].copy_from_slice(&bytes);
+    }
+    let uid = uid.unwrap_or_else(|| to_hex(&data[..4]));
+    Ok(ParsedDump { uid, data })
+}
+
+// this reads bytes written in hex with spaces between them, "??" reads as 00
+fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    text.split_whitespace()
+        .map(|byte| if byte == "??" { Some(0) } else { u8::from_str_radix(byte, 16).ok() })
+        .collect()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// One line a block, with its sector, the bytes in hex and the printable ones as text.
+/// Dumps are at most a 4K, see MAX_DUMP_BYTES.
+pub fn format_dump(data: &[u8]) -> String {
+    let mut text = String::new();
+    for (block, bytes) in CardLayout::Classic4K.blocks().zip(data.chunks(16)) {
+        let ascii: String = bytes.iter()
+            .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+            .collect();
+        let hex: Vec<String> = bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+        text.push_str(&format!("{:>2} {:>3}  {}  {}\n", block.sector(), block, hex.join(" "), ascii));
+    }
+    text
+}
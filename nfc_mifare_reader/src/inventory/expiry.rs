@@ -0,0 +1,96 @@
+// inventory/expiry.rs
+//
+// Expiry dates of items, kept as YYYY-MM-DD. An item is expiring once its date is
+// `expiry_warning_days` or fewer days away and expired from the day after it. The
+// inventory table colors those rows, the expiring items report lists them and the
+// "expires within" scan rule matches them.
+use chrono::{Local, NaiveDate};
+
+use crate::config;
+use crate::i18n::tr;
+use crate::inventory::model::InventoryItem;
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Where an item stands with its expiry date
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExpiryStatus {
+    /// The item has no expiry date
+    NoDate,
+    Fresh,
+    /// Expires in this many days, 0 is today
+    Expiring(i64),
+    /// Expired this many days ago, counting from the day after its date
+    Expired(i64),
+}
+
+/// The date in a YYYY-MM-DD text, None for anything else
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(text.trim(), DATE_FORMAT).ok()
+}
+
+/// An expiry date typed in the item form, checked and written as YYYY-MM-DD.
+/// Empty means no date.
+pub fn parse_input(text: &str) -> Result<Option<String>, String> {
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    parse_date(text)
+        .map(|date| Some(date.format(DATE_FORMAT).to_string()))
+        .ok_or_else(|| tr("err-form-expiry"))
+}
+
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// How many days ahead an item counts as expiring, from the settings
+pub fn warning_days() -> u32 {
+    config::APP_CONFIG.lock()
+        .map(|config| config.expiry_warning_days)
+        .unwrap_or_else(|_| config::AppConfig::default().expiry_warning_days)
+}
+
+/// Days from `today` to the item's expiry date, negative once it has passed.
+/// None when it has no date or the date can't be read.
+pub fn days_left(item: &InventoryItem, today: NaiveDate) -> Option<i64> {
+    let date = parse_date(item.expiry_date.as_deref()?)?;
+    Some((date - today).num_days())
+}
+
+pub fn status(item: &InventoryItem, today: NaiveDate, warning_days: u32) -> ExpiryStatus {
+    match days_left(item, today) {
+        None => ExpiryStatus::NoDate,
+        Some(days) if days < 0 => ExpiryStatus::Expired(-days),
+        Some(days) if days <= i64::from(warning_days) => ExpiryStatus::Expiring(days),
+        Some(_) => ExpiryStatus::Fresh,
+    }
+}
+
+/// Items that expire within `within_days` of `today`, those already expired
+/// included, soonest first, with their days left
+pub fn expiring_items(items: &[InventoryItem], today: NaiveDate, within_days: u32) -> Vec<(InventoryItem, i64)> {
+    let mut expiring: Vec<(InventoryItem, i64)> = items.iter()
+        .filter_map(|item| days_left(item, today).map(|days| (item.clone(), days)))
+        .filter(|(_, days)| *days <= i64::from(within_days))
+        .collect();
+    expiring.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.name.cmp(&b.0.name)));
+    expiring
+}
+
+/// The expiring items report as CSV
+pub fn report_csv(expiring: &[(InventoryItem, i64)]) -> String {
+    let mut csv = String::from("Tag ID,Name,Quantity,Location,Expiry Date,Days Left\n");
+    for (item, days) in expiring {
+        csv.push_str(&format!(
+            "{},\"{}\",{},\"{}\",{},{}\n",
+            item.tag_id,
+            item.name.replace('"', "\"\""),
+            item.quantity,
+            item.location.as_deref().unwrap_or_default().replace('"', "\"\""),
+            item.expiry_date.as_deref().unwrap_or_default(),
+            days
+        ));
+    }
+    csv
+}
@@ -0,0 +1,183 @@
+// inventory/import.rs
+//
+// Importing a JSON file of items is all or nothing. Every row is checked before
+// anything is saved and the rows are saved in one transaction, so a file with a
+// bad row leaves the inventory as it was. The problems are kept per row for the
+// report written beside a file that failed.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::Local;
+use inventory_db::ImportRowError;
+use progress_sink::{ProgressSink, Tracker};
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::backup::{self, BackupReason};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{CardDump, InventoryItem, ItemTag};
+
+// Rows of an import saved between two progress reports
+const IMPORT_PROGRESS_EVERY: usize = 100;
+
+/// A problem with one row of an import file, or with the whole file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    /// Row number counting from 1, None when the file itself is the problem
+    pub row: Option<usize>,
+    pub reason: String,
+}
+
+impl ImportError {
+    pub fn file(reason: impl ToString) -> Self {
+        ImportError { row: None, reason: reason.to_string() }
+    }
+
+    pub fn row(row: usize, reason: impl ToString) -> Self {
+        ImportError { row: Some(row), reason: reason.to_string() }
+    }
+}
+
+impl From<ImportRowError> for ImportError {
+    fn from(error: ImportRowError) -> Self {
+        ImportError { row: error.row, reason: error.error.to_string() }
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.row {
+            Some(row) => write!(f, "{}", tr_args("import-error-row", &[
+                ("row", &row.to_string()),
+                ("reason", &self.reason),
+            ])),
+            None => write!(f, "{}", self.reason),
+        }
+    }
+}
+
+/// Why a file was not imported, nothing from it was saved
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportFailure {
+    pub errors: Vec<ImportError>,
+}
+
+impl From<ImportError> for ImportFailure {
+    fn from(error: ImportError) -> Self {
+        ImportFailure { errors: vec![error] }
+    }
+}
+
+impl From<rusqlite::Error> for ImportFailure {
+    fn from(error: rusqlite::Error) -> Self {
+        ImportError::file(error).into()
+    }
+}
+
+impl fmt::Display for ImportFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.errors.as_slice() {
+            [] => Ok(()),
+            [only] => write!(f, "{}", only),
+            [first, ..] => write!(f, "{}", tr_args("import-errors", &[
+                ("count", &self.errors.len().to_string()),
+                ("first", &first.to_string()),
+            ])),
+        }
+    }
+}
+
+impl std::error::Error for ImportFailure {}
+
+/// The items of a JSON import file, or every row that can't be imported. The file is
+/// an export, with the items and the join table of their tags, or just the list of items.
+pub fn parse_items(json: &str) -> Result<Vec<InventoryItem>, ImportFailure> {
+    let rows: Vec<serde_json::Value> = match serde_json::from_str(json).map_err(ImportError::file)? {
+        serde_json::Value::Object(mut export) => serde_json::from_value(export.remove("items").unwrap_or_default())
+            .map_err(ImportError::file)?,
+        rows => serde_json::from_value(rows).map_err(ImportError::file)?,
+    };
+
+    let mut items = Vec::with_capacity(rows.len());
+    let mut errors = Vec::new();
+    for (index, row) in rows.into_iter().enumerate() {
+        match serde_json::from_value::<InventoryItem>(row) {
+            Ok(item) if item.tag_id.trim().is_empty() => {
+                errors.push(ImportError::row(index + 1, tr("import-error-no-tag")));
+            },
+            Ok(item) => items.push(item),
+            Err(e) => errors.push(ImportError::row(index + 1, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(items)
+    } else {
+        Err(ImportFailure { errors })
+    }
+}
+
+/// The tags of the items in an export, none for a plain list of items
+pub fn parse_item_tags(json: &str) -> Result<Vec<ItemTag>, ImportFailure> {
+    match serde_json::from_str(json).map_err(ImportError::file)? {
+        serde_json::Value::Object(mut export) => match export.remove("item_tags") {
+            Some(tags) => Ok(serde_json::from_value(tags).map_err(ImportError::file)?),
+            None => Ok(Vec::new()),
+        },
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// The card dumps of the items in an export, none for a plain list of items
+pub fn parse_card_dumps(json: &str) -> Result<Vec<CardDump>, ImportFailure> {
+    match serde_json::from_str(json).map_err(ImportError::file)? {
+        serde_json::Value::Object(mut export) => match export.remove("card_dumps") {
+            Some(dumps) => Ok(serde_json::from_value(dumps).map_err(ImportError::file)?),
+            None => Ok(Vec::new()),
+        },
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Import inventory from JSON in one transaction, nothing is saved unless every row is.
+/// The database is backed up first, so an import that was a mistake can be undone.
+/// The rows saved are reported to `progress`.
+pub fn import_json(db: &InventoryDB, json: &str, progress: &dyn ProgressSink) -> Result<usize, ImportFailure> {
+    let items = parse_items(json)?;
+    let item_tags = parse_item_tags(json)?;
+    let card_dumps = parse_card_dumps(json)?;
+    backup::take_backup(db, BackupReason::Import).map_err(ImportError::file)?;
+
+    let mut tracker = Tracker::new();
+    let row_of = |row: usize| tr_args("import-progress", &[("row", &row.to_string()), ("total", &items.len().to_string())]);
+    let mut report_every = |index: usize| {
+        if index.is_multiple_of(IMPORT_PROGRESS_EVERY) {
+            progress.report(&tracker.progress(index, items.len(), row_of(index + 1)));
+        }
+    };
+    let imported = db.import_rows(&items, &item_tags, &card_dumps, &mut report_every)
+        .map_err(|errors| ImportFailure { errors: errors.into_iter().map(ImportError::from).collect() })?;
+    progress.report(&tracker.progress(items.len(), items.len(), row_of(items.len())));
+
+    Ok(imported)
+}
+
+/// Write the problems of a failed import to "<file>.errors.txt" beside the file
+pub fn write_error_report(file: &Path, failure: &ImportFailure) -> io::Result<PathBuf> {
+    let mut report_path = file.as_os_str().to_owned();
+    report_path.push(".errors.txt");
+
+    let name = file.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let mut report = tr_args("import-report-title", &[
+        ("file", &name),
+        ("time", &Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+    ]);
+    report.push_str("\n\n");
+    for error in &failure.errors {
+        report.push_str(&error.to_string());
+        report.push('\n');
+    }
+
+    fs::write(&report_path, report)?;
+    Ok(PathBuf::from(report_path))
+}
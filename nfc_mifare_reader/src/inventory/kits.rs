@@ -0,0 +1,91 @@
+// inventory/kits.rs
+//
+// Kits, or bills of materials. A kit has a tag of its own and a list of inventory
+// items with how many of each one kit takes. Scanning the kit's tag in inventory mode
+// books every component at once: receive adds them, issue takes them out, in one
+// transaction. An issue that would take any component below zero books nothing and
+// shows the shortages instead; count mode only tells how many kits the stock makes.
+use crate::i18n::tr_args;
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{generate_timestamp, Kit, Named, Shortage, StockMode};
+
+// What booking a kit does to its components' stock is worked out by the database
+pub use inventory_db::kits::{buildable, component_delta, component_items, shortages};
+
+/// What a scan of a kit's tag did
+#[derive(Clone, Debug)]
+pub struct KitScan {
+    pub kit: Kit,
+    pub mode: StockMode,
+    /// Why nothing was booked, empty when the kit was booked
+    pub shortages: Vec<Shortage>,
+    /// How many kits the stock makes, for count mode
+    pub buildable: i32,
+}
+
+impl KitScan {
+    pub fn booked(&self) -> bool {
+        self.shortages.is_empty()
+    }
+
+    /// What the operator is told about the scan
+    pub fn message(&self) -> String {
+        if !self.booked() {
+            return shortage_report(&self.kit, &self.shortages);
+        }
+        match self.mode {
+            StockMode::Count => tr_args("kit-buildable", &[
+                ("kit", &self.kit.name),
+                ("count", &self.buildable.to_string()),
+            ]),
+            mode => tr_args("kit-booked", &[
+                ("kit", &self.kit.name),
+                ("mode", &mode.name()),
+                ("items", &self.kit.components.len().to_string()),
+            ]),
+        }
+    }
+}
+
+/// Book one kit for a scanned tag, Ok(None) when the tag isn't a kit
+pub fn scan_kit(db: &InventoryDB, tag_id: &str, mode: StockMode) -> rusqlite::Result<Option<KitScan>> {
+    let kit = match db.get_kit(tag_id)? {
+        Some(kit) => kit,
+        None => return Ok(None),
+    };
+
+    let items = component_items(db, &kit)?;
+    let buildable = buildable(&kit, &items);
+    let shortages = match mode {
+        StockMode::Count => Vec::new(),
+        _ => db.book_kit(&kit, mode, 1)?,
+    };
+    Ok(Some(KitScan { kit, mode, shortages, buildable }))
+}
+
+/// One line per short component, under a line naming the kit
+pub fn shortage_report(kit: &Kit, shortages: &[Shortage]) -> String {
+    let mut report = tr_args("kit-short", &[("kit", &kit.name), ("count", &shortages.len().to_string())]);
+    for shortage in shortages {
+        report.push('\n');
+        report.push_str(&match &shortage.name {
+            Some(name) => tr_args("kit-short-line", &[
+                ("item", name),
+                ("needed", &shortage.needed.to_string()),
+                ("available", &shortage.available.to_string()),
+            ]),
+            None => tr_args("kit-missing-line", &[("tag_id", &shortage.tag_id)]),
+        });
+    }
+    report
+}
+
+/// A new kit without components
+pub fn new_kit(tag_id: &str, name: &str) -> Kit {
+    Kit {
+        tag_id: tag_id.to_string(),
+        name: name.to_string(),
+        components: Vec::new(),
+        created_at: generate_timestamp(),
+    }
+}
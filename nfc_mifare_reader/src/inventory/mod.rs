@@ -1,10 +1,29 @@
 
-pub mod db;
+pub mod backup;
+pub mod dumps;
+pub mod expiry;
+pub mod import;
+pub mod kits;
 pub mod model;
+pub mod presence;
+pub mod purchasing;
+pub mod review;
+pub mod stocktake;
+pub mod trash;
+pub mod units;
+#[cfg(feature = "gui")]
 pub mod ui;
 
 
+// The database itself is the inventory_db crate, which the terminal UI and the REST
+// API open too
+pub use inventory_db::{db, oplog};
+
+/// Inventory database file, in the working directory
+pub const DB_PATH: &str = "inventory.db";
+
 pub use db::InventoryDB;
 pub use model::{InventoryItem, create_inventory_item};
 
+#[cfg(feature = "gui")]
 pub use ui::inventory_ui::InventoryUI;
\ No newline at end of file
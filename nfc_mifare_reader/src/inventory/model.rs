@@ -1,53 +1,41 @@
-use serde::{Deserialize, Serialize};
-use std::time::{SystemTime, UNIX_EPOCH};
+// inventory/model.rs
+//
+// The inventory types are the inventory_db crate's. The names the operator sees are
+// translated here, the crate only stores keys.
+pub use inventory_db::model::*;
 
-// Define item structure
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct InventoryItem {
-    pub tag_id: String,
-    pub name: String,
-    pub description: Option<String>,
-    pub quantity: i32,
-    pub location: Option<String>,
-    pub category: Option<String>,
-    pub last_updated: String,
-    pub created_at: String,
+use crate::i18n::tr;
+
+/// The name of a mode, status or presence change in the language of the UI
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+impl Named for PresenceChange {
+    fn name(&self) -> String {
+        match self {
+            PresenceChange::Removed => tr("presence-removed"),
+            PresenceChange::Returned => tr("presence-returned"),
+        }
+    }
 }
 
-// Helper to generate ISO timestamp
-pub fn generate_timestamp() -> String {
-    let now = SystemTime::now();
-    let since_epoch = now.duration_since(UNIX_EPOCH).unwrap();
-    let seconds = since_epoch.as_secs();
-    let millis = since_epoch.subsec_millis();
-    
-    #[allow(deprecated)]
-    let datetime = match chrono::DateTime::from_timestamp(seconds as i64, millis * 1_000_000) {
-        Some(dt) => dt.naive_local(),
-        None => chrono::NaiveDateTime::from_timestamp_millis(0).unwrap()
-    };
-    datetime.format("%Y-%m-%dT%H:%M:%S.%fZ").to_string()
+impl Named for StockMode {
+    fn name(&self) -> String {
+        match self {
+            StockMode::Receive => tr("mode-receive"),
+            StockMode::Issue => tr("mode-issue"),
+            StockMode::Count => tr("mode-count"),
+        }
+    }
 }
 
-// Create a new inventory item
-pub fn create_inventory_item(
-    tag_id: &str, 
-    name: &str, 
-    description: Option<&str>, 
-    quantity: i32, 
-    location: Option<&str>, 
-    category: Option<&str>
-) -> InventoryItem {
-    let now = generate_timestamp();
-    
-    InventoryItem {
-        tag_id: tag_id.to_string(),
-        name: name.to_string(),
-        description: description.map(ToString::to_string),
-        quantity,
-        location: location.map(ToString::to_string),
-        category: category.map(ToString::to_string),
-        last_updated: now.clone(),
-        created_at: now,
+impl Named for UnitStatus {
+    fn name(&self) -> String {
+        match self {
+            UnitStatus::InStock => tr("unit-in-stock"),
+            UnitStatus::Issued => tr("unit-issued"),
+            UnitStatus::Retired => tr("unit-retired"),
+        }
     }
-}
\ No newline at end of file
+}
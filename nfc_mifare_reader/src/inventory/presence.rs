@@ -0,0 +1,148 @@
+// inventory/presence.rs
+//
+// Presence monitoring, for tool-control cabinets with a reader inside. The reader
+// keeps reporting every tag left in its field, so while monitoring each report marks
+// the item as in place. An expected item not seen for the absence time is flagged as
+// removed, and as returned when one of its tags is read again. Nothing here touches
+// the database, the caller stores the changes polled from the monitor.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+
+use crate::inventory::model::{InventoryItem, PresenceChange};
+
+/// Seconds an item may go unseen before it counts as removed, unless set otherwise
+pub const DEFAULT_ABSENT_SECS: u64 = 10;
+
+/// An expected item and when one of its tags was last read
+#[derive(Clone, Debug)]
+pub struct Watched {
+    pub item_tag: String,
+    pub name: String,
+    pub last_seen: Instant,
+    pub present: bool,
+}
+
+/// A change the monitor noticed, not stored yet
+#[derive(Clone, Debug, PartialEq)]
+pub struct Transition {
+    pub item_tag: String,
+    pub name: String,
+    pub change: PresenceChange,
+}
+
+/// The items expected in the reader's field and whether they are there
+pub struct PresenceMonitor {
+    watched: BTreeMap<String, Watched>,
+    // other tags of the items, to the items' own tags
+    aliases: HashMap<String, String>,
+    absent_after: Duration,
+    returned: Vec<Transition>,
+}
+
+impl PresenceMonitor {
+    /// Watch `items`, which are taken to be in place when monitoring starts. `aliases`
+    /// maps the other tags of items to the items' own tags.
+    pub fn new(items: &[InventoryItem], aliases: &HashMap<String, String>, absent_after: Duration, now: Instant) -> Self {
+        let watched = items
+            .iter()
+            .map(|item| {
+                let item_tag = normalize_tag_id(&item.tag_id);
+                (item_tag.clone(), Watched { item_tag, name: item.name.clone(), last_seen: now, present: true })
+            })
+            .collect();
+        let aliases = aliases
+            .iter()
+            .map(|(tag_id, item_tag)| (normalize_tag_id(tag_id), normalize_tag_id(item_tag)))
+            .collect();
+        PresenceMonitor { watched, aliases, absent_after, returned: Vec::new() }
+    }
+
+    /// A tag was read. Tags of items that aren't watched are ignored.
+    pub fn seen(&mut self, tag_id: &str, now: Instant) {
+        let tag_id = normalize_tag_id(tag_id);
+        let item_tag = self.aliases.get(&tag_id).cloned().unwrap_or(tag_id);
+        if let Some(watched) = self.watched.get_mut(&item_tag) {
+            watched.last_seen = now;
+            if !watched.present {
+                watched.present = true;
+                self.returned.push(Transition {
+                    item_tag: watched.item_tag.clone(),
+                    name: watched.name.clone(),
+                    change: PresenceChange::Returned,
+                });
+            }
+        }
+    }
+
+    /// The returns since the last poll, then the items that have just gone unseen for
+    /// longer than the absence time
+    pub fn poll(&mut self, now: Instant) -> Vec<Transition> {
+        let mut changes: Vec<Transition> = self.returned.drain(..).collect();
+        for watched in self.watched.values_mut() {
+            if watched.present && now.duration_since(watched.last_seen) > self.absent_after {
+                watched.present = false;
+                changes.push(Transition {
+                    item_tag: watched.item_tag.clone(),
+                    name: watched.name.clone(),
+                    change: PresenceChange::Removed,
+                });
+            }
+        }
+        changes
+    }
+
+    /// The watched items, by tag
+    pub fn watched(&self) -> Vec<Watched> {
+        self.watched.values().cloned().collect()
+    }
+}
+
+// "04 a1 b2" and "04A1B2" are the same tag
+fn normalize_tag_id(tag_id: &str) -> String {
+    tag_id.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
+
+static MONITOR: Lazy<Mutex<Option<PresenceMonitor>>> = Lazy::new(|| Mutex::new(None));
+
+/// Start monitoring `items`, a monitor already running starts over
+pub fn start_monitoring(items: &[InventoryItem], aliases: &HashMap<String, String>, absent_after: Duration) {
+    if let Ok(mut monitor) = MONITOR.lock() {
+        *monitor = Some(PresenceMonitor::new(items, aliases, absent_after, Instant::now()));
+    }
+}
+
+/// Stop monitoring, changes not polled yet are dropped
+pub fn stop_monitoring() {
+    if let Ok(mut monitor) = MONITOR.lock() {
+        *monitor = None;
+    }
+}
+
+/// Whether presence is being monitored
+pub fn monitoring() -> bool {
+    MONITOR.lock().map(|monitor| monitor.is_some()).unwrap_or(false)
+}
+
+/// Mark a read tag as seen, repeated reads of a tag held in the field included
+pub fn tag_seen(tag_id: &str) {
+    if let Ok(mut monitor) = MONITOR.lock() {
+        if let Some(monitor) = monitor.as_mut() {
+            monitor.seen(tag_id, Instant::now());
+        }
+    }
+}
+
+/// The changes since the last poll, none when nothing is monitored
+pub fn poll_changes() -> Vec<Transition> {
+    match MONITOR.lock() {
+        Ok(mut monitor) => monitor.as_mut().map(|monitor| monitor.poll(Instant::now())).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// The watched items, none when nothing is monitored
+pub fn watched_items() -> Vec<Watched> {
+    MONITOR.lock().ok().and_then(|monitor| monitor.as_ref().map(PresenceMonitor::watched)).unwrap_or_default()
+}
@@ -0,0 +1,64 @@
+// inventory/purchasing.rs
+//
+// Suppliers and purchase orders. An item can be linked to the supplier it is bought
+// from, and an order lists how many of which items were ordered from one supplier.
+// While an order is picked for receiving, every quantity increase from a scan is also
+// booked against that order's line for the item; the order closes itself once every
+// line has come in. Nothing is booked against closed orders.
+use once_cell::sync::Lazy;
+use std::sync::Mutex;
+
+use crate::i18n::tr_args;
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{PoStatus, PurchaseOrder};
+
+// the order scans are received against, if any
+static RECEIVING_PO: Lazy<Mutex<Option<i64>>> = Lazy::new(|| Mutex::new(None));
+
+/// Receive scans against the order `po_id`, or against none
+pub fn set_receiving_po(po_id: Option<i64>) {
+    *RECEIVING_PO.lock().unwrap() = po_id;
+}
+
+/// The order scans are received against
+pub fn receiving_po() -> Option<i64> {
+    *RECEIVING_PO.lock().unwrap()
+}
+
+/// Book `quantity` of a scanned item against the order being received, with what the
+/// operator is told about it. Ok(None) when no open order is being received.
+pub fn record_receipt(db: &InventoryDB, tag_id: &str, quantity: i32) -> rusqlite::Result<Option<String>> {
+    let order = match receiving_po() {
+        Some(po_id) => db.get_purchase_order(po_id)?,
+        None => None,
+    };
+    let order = match order.filter(|order| order.status == PoStatus::Open) {
+        Some(order) => order,
+        None => return Ok(None),
+    };
+
+    if !db.receive_against_po(order.id, tag_id, quantity)? {
+        return Ok(Some(tr_args("po-not-on-order", &[("tag_id", tag_id), ("po", &order.number)])));
+    }
+
+    let order = db.get_purchase_order(order.id)?.unwrap_or(order);
+    let mut message = receipt_message(&order, tag_id, quantity);
+    if order.fully_received() {
+        db.set_po_status(order.id, PoStatus::Closed)?;
+        set_receiving_po(None);
+        message.push('\n');
+        message.push_str(&tr_args("po-complete", &[("po", &order.number)]));
+    }
+    Ok(Some(message))
+}
+
+// this says how much of the item's line has come in so far
+fn receipt_message(order: &PurchaseOrder, tag_id: &str, quantity: i32) -> String {
+    let line = order.lines.iter().find(|line| line.tag_id == tag_id);
+    tr_args("po-received", &[
+        ("po", &order.number),
+        ("quantity", &quantity.to_string()),
+        ("received", &line.map(|line| line.received).unwrap_or(quantity).to_string()),
+        ("ordered", &line.map(|line| line.ordered).unwrap_or_default().to_string()),
+    ])
+}
@@ -0,0 +1,167 @@
+// inventory/review.rs
+//
+// Queued scanning. Instead of booking every scan as it comes in, the capture window
+// can collect scans in a review list, one entry per tag and scan mode. The operator
+// adjusts the quantities and names the unknown tags, then commits the whole list in
+// one transaction or discards it. Nothing changes in the database before the commit.
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{Named, ReviewEntry, StockMode};
+
+/// A tag waiting in the review list
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueuedScan {
+    pub tag_id: String,
+    pub mode: StockMode,
+    /// Times the tag was scanned
+    pub scans: u32,
+    /// Amount received or issued, or the counted quantity in count mode
+    pub quantity: i32,
+    /// The inventory item's name, or the one given to an unknown tag
+    pub name: Option<String>,
+    /// Whether the tag was in the inventory when it was scanned
+    pub known: bool,
+}
+
+impl QueuedScan {
+    /// Why the entry can't be booked, None when it can
+    pub fn problem(&self) -> Option<String> {
+        if self.quantity < 0 {
+            return Some(tr_args("review-negative", &[("tag_id", &self.tag_id)]));
+        }
+        if self.known {
+            return None;
+        }
+        if self.mode == StockMode::Issue {
+            return Some(tr_args("err-issue-unknown", &[("tag_id", &self.tag_id)]));
+        }
+        match self.name.as_deref() {
+            Some(name) if !name.trim().is_empty() => None,
+            _ => Some(tr_args("review-unnamed", &[("tag_id", &self.tag_id)])),
+        }
+    }
+
+    /// One line of the review list
+    pub fn summary(&self) -> String {
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => tr("review-new-tag"),
+        };
+        tr_args("review-line", &[
+            ("mode", &self.mode.name()),
+            ("quantity", &self.quantity.to_string()),
+            ("name", &name),
+            ("tag_id", &self.tag_id),
+            ("scans", &self.scans.to_string()),
+        ])
+    }
+
+    fn entry(&self) -> ReviewEntry {
+        ReviewEntry {
+            tag_id: self.tag_id.clone(),
+            mode: self.mode,
+            quantity: self.quantity,
+            name: self.name.clone(),
+        }
+    }
+}
+
+static QUEUE: Lazy<Mutex<Vec<QueuedScan>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Add a scan to the review list. A tag scanned again in the same mode adds one to
+/// its entry, except in count mode where the counted quantity is entered at review.
+pub fn queue_scan(db: &InventoryDB, tag_id: &str, mode: StockMode) -> rusqlite::Result<QueuedScan> {
+    // every tag of an item counts towards the one entry, under the item's own tag
+    let item = db.get_item_by_any_tag(tag_id)?;
+    let tag_id = item.as_ref().map_or(tag_id, |item| item.tag_id.as_str());
+
+    let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = queue.iter_mut().find(|entry| entry.tag_id == tag_id && entry.mode == mode) {
+        entry.scans += 1;
+        if mode != StockMode::Count {
+            entry.quantity += 1;
+        }
+        return Ok(entry.clone());
+    }
+
+    let quantity = match (&item, mode) {
+        (Some(item), StockMode::Count) => item.quantity,
+        _ => 1,
+    };
+    let entry = QueuedScan {
+        tag_id: tag_id.to_string(),
+        mode,
+        scans: 1,
+        quantity,
+        known: item.is_some(),
+        name: item.as_ref().map(|item| item.name.clone()),
+    };
+    queue.push(entry.clone());
+    Ok(entry)
+}
+
+/// The review list in scan order
+pub fn queued_scans() -> Vec<QueuedScan> {
+    QUEUE.lock().map(|queue| queue.clone()).unwrap_or_default()
+}
+
+/// Number of entries waiting for review
+pub fn queue_len() -> usize {
+    QUEUE.lock().map(|queue| queue.len()).unwrap_or(0)
+}
+
+/// Change the quantity of the entry at `index`
+pub fn set_quantity(index: usize, quantity: i32) {
+    if let Some(entry) = QUEUE.lock().ok().as_mut().and_then(|queue| queue.get_mut(index)) {
+        entry.quantity = quantity;
+    }
+}
+
+/// Name the new item an unknown tag creates, known tags keep their item
+pub fn set_name(index: usize, name: &str) {
+    if let Some(entry) = QUEUE.lock().ok().as_mut().and_then(|queue| queue.get_mut(index)) {
+        if !entry.known {
+            entry.name = Some(name.trim().to_string()).filter(|name| !name.is_empty());
+        }
+    }
+}
+
+/// Take the entry at `index` off the list
+pub fn remove_scan(index: usize) {
+    if let Ok(mut queue) = QUEUE.lock() {
+        if index < queue.len() {
+            queue.remove(index);
+        }
+    }
+}
+
+/// Empty the review list without booking anything
+pub fn discard_queue() {
+    if let Ok(mut queue) = QUEUE.lock() {
+        queue.clear();
+    }
+}
+
+/// Book the review list in one transaction and empty it. Nothing is booked while
+/// any entry has a problem, the problems are returned instead.
+pub fn commit_queue(db: &InventoryDB) -> rusqlite::Result<Vec<String>> {
+    let mut queue = QUEUE.lock().unwrap_or_else(|e| e.into_inner());
+    let mut problems: Vec<String> = queue.iter().filter_map(QueuedScan::problem).collect();
+    // an item deleted since it was scanned has nothing left to issue
+    for entry in queue.iter().filter(|entry| entry.known && entry.mode == StockMode::Issue) {
+        if db.get_item(&entry.tag_id)?.is_none() {
+            problems.push(tr_args("err-issue-unknown", &[("tag_id", &entry.tag_id)]));
+        }
+    }
+    if !problems.is_empty() {
+        return Ok(problems);
+    }
+
+    let entries: Vec<ReviewEntry> = queue.iter().map(QueuedScan::entry).collect();
+    db.commit_review(&entries)?;
+    queue.clear();
+    Ok(Vec::new())
+}
@@ -0,0 +1,139 @@
+// inventory/stocktake.rs
+//
+// A stocktake counts the tags scanned between its start and finish and compares
+// them with the inventory. While one runs, scans are only counted, nothing in the
+// database changes.
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+
+use crate::i18n::tr_args;
+use crate::inventory::model::InventoryItem;
+use crate::utils;
+
+/// Tags counted so far
+pub struct Stocktake {
+    pub started: String,
+    counted: BTreeMap<String, u32>,
+}
+
+/// Outcome of a stocktake against the inventory
+pub struct StocktakeReport {
+    pub started: String,
+    pub found: Vec<InventoryItem>,
+    pub missing: Vec<InventoryItem>,
+    pub unknown: Vec<String>,
+    pub scans: u32,
+}
+
+// Missing items listed by name in the summary, the rest are only counted
+const LISTED_MISSING: usize = 10;
+
+impl Stocktake {
+    pub fn new(started: &str) -> Self {
+        Stocktake { started: started.to_string(), counted: BTreeMap::new() }
+    }
+
+    pub fn record(&mut self, tag_id: &str) {
+        *self.counted.entry(normalize_tag_id(tag_id)).or_insert(0) += 1;
+    }
+
+    /// Number of different tags scanned
+    pub fn tag_count(&self) -> usize {
+        self.counted.len()
+    }
+
+    /// Compare the count with the inventory. `aliases` maps the other tags of items to
+    /// the items' own tags, a scan of any of them counts for the item.
+    pub fn report(&self, items: &[InventoryItem], aliases: &HashMap<String, String>) -> StocktakeReport {
+        let aliases: HashMap<String, String> = aliases
+            .iter()
+            .map(|(tag_id, item_tag)| (normalize_tag_id(tag_id), normalize_tag_id(item_tag)))
+            .collect();
+        let item_tag = |tag_id: &String| aliases.get(tag_id).cloned().unwrap_or_else(|| tag_id.clone());
+        let counted: BTreeSet<String> = self.counted.keys().map(item_tag).collect();
+
+        let (found, missing): (Vec<_>, Vec<_>) = items
+            .iter()
+            .cloned()
+            .partition(|item| counted.contains(&normalize_tag_id(&item.tag_id)));
+        let unknown = self.counted
+            .keys()
+            .filter(|tag_id| !items.iter().any(|item| normalize_tag_id(&item.tag_id) == item_tag(tag_id)))
+            .cloned()
+            .collect();
+
+        StocktakeReport {
+            started: self.started.clone(),
+            found,
+            missing,
+            unknown,
+            scans: self.counted.values().sum(),
+        }
+    }
+}
+
+impl StocktakeReport {
+    /// Counts, then the missing items and unknown tags, for a message box
+    pub fn summary(&self) -> String {
+        let mut summary = tr_args("stocktake-summary", &[
+            ("started", &self.started),
+            ("scans", &self.scans.to_string()),
+            ("found", &self.found.len().to_string()),
+            ("missing", &self.missing.len().to_string()),
+            ("unknown", &self.unknown.len().to_string()),
+        ]);
+
+        for item in self.missing.iter().take(LISTED_MISSING) {
+            summary.push_str(&format!("\n  - {} ({})", item.name, item.tag_id));
+        }
+        if self.missing.len() > LISTED_MISSING {
+            let more = (self.missing.len() - LISTED_MISSING).to_string();
+            summary.push_str(&format!("\n  {}", tr_args("stocktake-more", &[("count", &more)])));
+        }
+        if !self.unknown.is_empty() {
+            summary.push_str(&format!("\n\n{}", tr_args("stocktake-unknown", &[("tags", &self.unknown.join(", "))])));
+        }
+        summary
+    }
+}
+
+// "04 a1 b2" and "04A1B2" are the same tag
+fn normalize_tag_id(tag_id: &str) -> String {
+    tag_id.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
+
+static STOCKTAKE: Lazy<Mutex<Option<Stocktake>>> = Lazy::new(|| Mutex::new(None));
+
+/// Start counting, a stocktake already running starts over
+pub fn start_stocktake() {
+    let (_, human_timestamp) = utils::get_timestamps();
+    if let Ok(mut stocktake) = STOCKTAKE.lock() {
+        *stocktake = Some(Stocktake::new(&human_timestamp));
+    }
+}
+
+/// Count a scan if a stocktake is running, false when there is none
+pub fn record_stocktake_scan(tag_id: &str) -> bool {
+    match STOCKTAKE.lock() {
+        Ok(mut stocktake) => match stocktake.as_mut() {
+            Some(stocktake) => {
+                stocktake.record(tag_id);
+                true
+            },
+            None => false,
+        },
+        Err(_) => false,
+    }
+}
+
+/// Different tags counted by the running stocktake, None when there is none
+pub fn stocktake_progress() -> Option<usize> {
+    STOCKTAKE.lock().ok().and_then(|stocktake| stocktake.as_ref().map(Stocktake::tag_count))
+}
+
+/// End the running stocktake and compare it with the inventory
+pub fn finish_stocktake(items: &[InventoryItem], aliases: &HashMap<String, String>) -> Option<StocktakeReport> {
+    let stocktake = STOCKTAKE.lock().ok()?.take()?;
+    Some(stocktake.report(items, aliases))
+}
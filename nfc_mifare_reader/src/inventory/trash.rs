@@ -0,0 +1,63 @@
+// inventory/trash.rs
+//
+// Deleted items go to the inventory_trash table instead of being lost (see
+// InventoryDB::delete_item). Items that have been in the trash longer than
+// `trash_retention_days` are purged at startup and then once an hour while the app runs.
+use std::cell::RefCell;
+#[cfg(feature = "gui")]
+use std::rc::Rc;
+use chrono::{Duration, Utc};
+#[cfg(feature = "gui")]
+use fltk::app;
+
+use crate::config;
+use crate::i18n::tr_args;
+use crate::inventory::InventoryDB;
+
+/// How often the trash is checked for items past their retention, in seconds
+const PURGE_CHECK: f64 = 3600.0;
+
+/// Timestamp before which trashed items are purged, None when they are kept until the
+/// trash is emptied
+pub fn purge_cutoff(retention_days: u32) -> Option<String> {
+    if retention_days == 0 {
+        return None;
+    }
+    let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+    // the format generate_timestamp writes deleted_at in
+    Some(cutoff.format("%Y-%m-%dT%H:%M:%S.%fZ").to_string())
+}
+
+/// Purge the items past their retention now, then check again every hour for as long
+/// as the app runs
+#[cfg(feature = "gui")]
+pub fn schedule_trash_purge(db: Rc<RefCell<InventoryDB>>) {
+    purge_expired(&db);
+    app::add_timeout3(PURGE_CHECK, move |handle| {
+        purge_expired(&db);
+        app::repeat_timeout3(PURGE_CHECK, handle);
+    });
+}
+
+// this purges with the retention in use, a database that is busy is purged next time
+fn purge_expired(db: &RefCell<InventoryDB>) {
+    let retention_days = config::APP_CONFIG.lock()
+        .map(|config| config.trash_retention_days)
+        .unwrap_or(0);
+    let cutoff = match purge_cutoff(retention_days) {
+        Some(cutoff) => cutoff,
+        None => return,
+    };
+    let db = match db.try_borrow() {
+        Ok(db) => db,
+        Err(_) => return,
+    };
+    match db.purge_trash(&cutoff) {
+        Ok(0) => {},
+        Ok(count) => println!("{}", tr_args("log-trash-purged", &[
+            ("count", &count.to_string()),
+            ("days", &retention_days.to_string()),
+        ])),
+        Err(e) => println!("{}", tr_args("err-trash-purge", &[("error", &e.to_string())])),
+    }
+}
@@ -16,6 +16,7 @@ use std::rc::Rc;
 use std::fs;
 use std::collections::HashSet;
 
+use crate::i18n::{tr, tr_args};
 use crate::inventory::ui::utils::ChoiceExt;
 use crate::inventory::db::InventoryDB;
 use crate::inventory::model::{InventoryItem, create_inventory_item};
@@ -77,7 +78,7 @@ impl InventoryUI {
                         
                         // Save to database
                         if let Err(e) = db_clone.borrow().save_item(&item) {
-                            dialog::alert(300, 300, &format!("Error saving item: {}", e));
+                            dialog::alert(300, 300, &tr_args("err-inv-save", &[("error", &e.to_string())]));
                             return;
                         }
                         
@@ -88,15 +89,15 @@ impl InventoryUI {
                             table_clone.borrow_mut().redraw();
                         }
                         
-                        log_buffer_clone.append(&format!("Saved item: {}\n", item.name));
-                        dialog::message(300, 300, "Item saved successfully");
+                        log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-saved", &[("item", &item.name)])));
+                        dialog::message(300, 300, &tr("msg-inv-saved"));
                     },
                     Err(e) => {
-                        dialog::alert(300, 300, &format!("Form validation error: {}", e));
+                        dialog::alert(300, 300, &tr_args("err-inv-form", &[("error", &e.to_string())]));
                     }
                 }
             } else {
-                dialog::alert(300, 300, "No item selected to save");
+                dialog::alert(300, 300, &tr("err-inv-select-save"));
             }
         });
     }
@@ -117,10 +118,10 @@ impl InventoryUI {
         delete_btn.set_callback(move |_| {
             if let Some(tag_id) = current_tag_clone.borrow().clone() {
                 // Ask for confirmation
-                if dialog::choice2(300, 300, "Are you sure you want to delete this item?", "No", "Yes", "") == Some(1) {
+                if dialog::choice2(300, 300, &tr("inv-delete-confirm"), &tr("btn-cancel"), &tr("inv-delete"), "") == Some(1) {
                     // Delete from database
                     if let Err(e) = db_clone.borrow().delete_item(&tag_id) {
-                        dialog::alert(300, 300, &format!("Error deleting item: {}", e));
+                        dialog::alert(300, 300, &tr_args("err-trash-delete", &[("error", &e.to_string())]));
                         return;
                     }
                     
@@ -137,11 +138,11 @@ impl InventoryUI {
                         table_clone.borrow_mut().redraw();
                     }
                     
-                    log_buffer_clone.append(&format!("Deleted item with tag: {}\n", tag_id));
-                    dialog::message(300, 300, "Item deleted successfully");
+                    log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-deleted", &[("tag_id", &tag_id)])));
+                    dialog::message(300, 300, &tr("msg-inv-deleted"));
                 }
             } else {
-                dialog::alert(300, 300, "No item selected to delete");
+                dialog::alert(300, 300, &tr("err-trash-select-delete"));
             }
         });
     }
@@ -159,7 +160,7 @@ impl InventoryUI {
         clear_btn.set_callback(move |_| {
             item_form_clone.clear();
             *current_tag_clone.borrow_mut() = None;
-            log_buffer_clone.append("Form cleared\n");
+            log_buffer_clone.append(&format!("{}\n", tr("log-inv-cleared")));
         });
     }
     
@@ -175,7 +176,7 @@ impl InventoryUI {
         
         add_btn.set_callback(move |_| {
             // Generate a new tag ID or prompt user for one
-            if let Some(tag_id) = dialog::input(300, 300, "Enter Tag ID for new item:", "") {
+            if let Some(tag_id) = dialog::input(300, 300, &tr("inv-new-tag"), "") {
                 if !tag_id.is_empty() {
                     // Clear form and set new tag ID
                     item_form_clone.clear();
@@ -184,9 +185,9 @@ impl InventoryUI {
                     let display_tag_id = tag_id.clone();
                     let log_tag_id = tag_id.clone();
                     
-                    item_form_clone.tag_id_display.set_label(&format!("Tag ID: {}", display_tag_id));
+                    item_form_clone.tag_id_display.set_label(&tr_args("form-tag", &[("tag_id", &display_tag_id)]));
                     *current_tag_clone.borrow_mut() = Some(tag_id);
-                    log_buffer_clone.append(&format!("Ready to add new item with tag: {}\n", log_tag_id));
+                    log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-new", &[("tag_id", &log_tag_id)])));
                 }
             }
         });
@@ -202,34 +203,36 @@ impl InventoryUI {
         
         export_btn.set_callback(move |_| {
             // Fixed the dialog::choice call to use dialog::choice2
-            match dialog::choice2(300, 300, "Select export format:", "JSON", "CSV", "Cancel") {
+            match dialog::choice2(300, 300, &tr("export-format"), "JSON", "CSV", &tr("btn-cancel")) {
                 Some(1) => { // JSON
-                    if let Some(path) = dialog::file_chooser("Save JSON Export", "*.json", "", false) {
+                    if let Some(path) = dialog::file_chooser(&tr("title-export-json"), "*.json", "", false) {
                         match db_clone.borrow().export_json() {
                             Ok(json) => {
                                 if let Err(e) = std::fs::write(&path, json) {
-                                    dialog::alert(300, 300, &format!("Error writing file: {}", e));
+                                    dialog::alert(300, 300, &tr_args("err-write-file", &[("error", &e.to_string())]));
                                 } else {
-                                    log_buffer_clone.append(&format!("Exported JSON to {}\n", path));
-                                    dialog::message(300, 300, &format!("Data exported to {}", path));
+                                    log_buffer_clone.append(&format!("{}\n", tr_args("log-exported", &[("path", &path)])));
+                                    crate::signing::sign_export(std::path::Path::new(&path));
+                                    dialog::message(300, 300, &tr_args("msg-exported", &[("path", &path)]));
                                 }
                             },
-                            Err(e) => dialog::alert(300, 300, &format!("Error exporting data: {}", e))
+                            Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())]))
                         }
                     }
                 },
                 Some(2) => { // CSV
-                    if let Some(path) = dialog::file_chooser("Save CSV Export", "*.csv", "", false) {
+                    if let Some(path) = dialog::file_chooser(&tr("title-export-csv"), "*.csv", "", false) {
                         match db_clone.borrow().export_csv() {
                             Ok(csv) => {
                                 if let Err(e) = std::fs::write(&path, csv) {
-                                    dialog::alert(300, 300, &format!("Error writing file: {}", e));
+                                    dialog::alert(300, 300, &tr_args("err-write-file", &[("error", &e.to_string())]));
                                 } else {
-                                    log_buffer_clone.append(&format!("Exported CSV to {}\n", path));
-                                    dialog::message(300, 300, &format!("Data exported to {}", path));
+                                    log_buffer_clone.append(&format!("{}\n", tr_args("log-exported", &[("path", &path)])));
+                                    crate::signing::sign_export(std::path::Path::new(&path));
+                                    dialog::message(300, 300, &tr_args("msg-exported", &[("path", &path)]));
                                 }
                             },
-                            Err(e) => dialog::alert(300, 300, &format!("Error exporting data: {}", e))
+                            Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())]))
                         }
                     }
                 },
@@ -259,7 +262,7 @@ impl InventoryUI {
                     let count = items_clone.borrow().len();
                     table_clone.borrow_mut().set_rows(count as i32);
                     table_clone.borrow_mut().redraw();
-                    log_buffer_clone.append("Showing all items\n");
+                    log_buffer_clone.append(&format!("{}\n", tr("log-search-all")));
                 }
             } else {
                 // Search for items
@@ -269,10 +272,10 @@ impl InventoryUI {
                         let count = items_clone.borrow().len();
                         table_clone.borrow_mut().set_rows(count as i32);
                         table_clone.borrow_mut().redraw();
-                        log_buffer_clone.append(&format!("Found {} items matching '{}'\n", count, query));
+                        log_buffer_clone.append(&format!("{}\n", tr_args("log-search-found", &[("count", &count.to_string()), ("query", &query)])));
                     },
                     Err(e) => {
-                        dialog::alert(300, 300, &format!("Error searching: {}", e));
+                        dialog::alert(300, 300, &tr_args("err-search", &[("error", &e.to_string())]));
                     }
                 }
             }
@@ -281,14 +284,14 @@ impl InventoryUI {
     
     // Create the inventory tab in the UI
     pub fn create_tab(&self, tabs: &mut Tabs) {
-        let inventory_tab = Group::new(0, 50, 800, 550, "Inventory");
+        let inventory_tab = Group::new(0, 50, 800, 550, None).with_label(&tr("tab-inventory"));
         
         // Create the left panel for the table
         let table_panel = Group::new(10, 60, 380, 530, "");
         
         // Search input
-        let search_input = Input::new(10, 60, 280, 30, "Search:");
-        let mut search_btn = Button::new(300, 60, 80, 30, "Search");
+        let search_input = Input::new(10, 60, 280, 30, None).with_label(&tr("inv-search-label"));
+        let mut search_btn = Button::new(300, 60, 80, 30, None).with_label(&tr("inv-search"));
         
         // Create a table to display inventory items
         let mut table = Table::new(10, 100, 380, 350, "");
@@ -297,11 +300,11 @@ impl InventoryUI {
         *self.item_table.borrow_mut() = table.clone();
         
         // Action buttons
-        let mut refresh_btn = Button::new(10, 460, 120, 30, "Refresh List");
-        let mut add_btn = Button::new(140, 460, 120, 30, "Add Item");
-        let mut export_btn = Button::new(270, 460, 120, 30, "Export");
+        let mut refresh_btn = Button::new(10, 460, 120, 30, None).with_label(&tr("inv-refresh"));
+        let mut add_btn = Button::new(140, 460, 120, 30, None).with_label(&tr("inv-add"));
+        let mut export_btn = Button::new(270, 460, 120, 30, None).with_label(&tr("inv-export"));
         
-        let mut stats_frame = Frame::new(10, 500, 380, 80, "Inventory Stats");
+        let mut stats_frame = Frame::new(10, 500, 380, 80, None);
         stats_frame.set_frame(FrameType::EngravedBox);
         stats_frame.set_label_type(LabelType::None);
         
@@ -313,7 +316,7 @@ impl InventoryUI {
         // Create the right panel for item details
         let detail_panel = Group::new(400, 60, 390, 530, "");
         
-        let mut detail_title = Frame::new(400, 60, 390, 30, "Item Details");
+        let mut detail_title = Frame::new(400, 60, 390, 30, None).with_label(&tr("inv-details"));
         detail_title.set_label_font(Font::HelveticaBold);
         detail_title.set_label_size(18);
         
@@ -321,12 +324,12 @@ impl InventoryUI {
         let mut item_form = ItemForm::new(400, 100, 390, 260);
         
         // Action buttons
-        let mut save_btn = Button::new(400, 370, 120, 30, "Save Changes");
-        let mut delete_btn = Button::new(530, 370, 120, 30, "Delete Item");
-        let mut clear_btn = Button::new(660, 370, 120, 30, "Clear Form");
+        let mut save_btn = Button::new(400, 370, 120, 30, None).with_label(&tr("inv-save"));
+        let mut delete_btn = Button::new(530, 370, 120, 30, None).with_label(&tr("inv-delete"));
+        let mut clear_btn = Button::new(660, 370, 120, 30, None).with_label(&tr("inv-clear"));
         
         // Event log
-        let _log_frame = Frame::new(400, 510, 390, 30, "Event Log");
+        let _log_frame = Frame::new(400, 510, 390, 30, None).with_label(&tr("inv-log"));
         let mut log_display = TextDisplay::new(400, 540, 390, 40, "");
         let log_buffer = TextBuffer::default();
         log_display.set_buffer(log_buffer.clone());
@@ -355,7 +358,7 @@ impl InventoryUI {
                     item_form_clone.display_item(&item);
                     
                     // Log
-                    log_buffer_clone.append(&format!("Loaded details for item: {}\n", item.name));
+                    log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-loaded", &[("item", &item.name)])));
                 }
             }
         });
@@ -454,12 +457,11 @@ impl InventoryUI {
                         .filter_map(|i| i.category.clone())
                         .collect();
                     
-                    stats_text_clone.set_label(&format!(
-                        "Total Items: {}\nTotal Quantity: {}\nCategories: {}",
-                        items.len(),
-                        total_quantity,
-                        categories.len()
-                    ));
+                    stats_text_clone.set_label(&tr_args("inv-stats", &[
+                        ("items", &items.len().to_string()),
+                        ("quantity", &total_quantity.to_string()),
+                        ("categories", &categories.len().to_string()),
+                    ]));
                     
                     // Populate category dropdown
                     let mut categories: Vec<String> = categories.into_iter().collect();
@@ -467,11 +469,12 @@ impl InventoryUI {
                     category_choice_clone.update_categories(&categories);
                     
                     // Add to log
-                    log_buffer_clone.append("Refreshed inventory list\n");
+                    log_buffer_clone.append(&format!("{}\n", tr("log-inv-refreshed")));
                 },
                 Err(e) => {
-                    dialog::alert(300, 300, &format!("Error loading inventory: {}", e));
-                    log_buffer_clone.append(&format!("Error: {}\n", e));
+                    let message = tr_args("err-load-items", &[("error", &e.to_string())]);
+                    dialog::alert(300, 300, &message);
+                    log_buffer_clone.append(&format!("{}\n", message));
                 }
             }
         });
@@ -485,48 +488,49 @@ impl InventoryUI {
                 // Item exists - increment quantity
                 let new_quantity = item.quantity + 1;
                 if let Err(e) = self.inventory_db.borrow().update_quantity(tag_id, new_quantity) {
-                    dialog::alert(300, 300, &format!("Error updating quantity: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-scan-quantity", &[("error", &e.to_string())]));
                     return;
                 }
                 
-                dialog::message(300, 300, &format!("Tag scanned: {}. Quantity updated to {}.", item.name, new_quantity));
+                dialog::message(300, 300, &tr_args("msg-scan-updated", &[("item", &item.name), ("quantity", &new_quantity.to_string())]));
             },
             Ok(None) => {
                 // Item doesn't exist - ask to create
                 if dialog::choice2(300, 300, 
-                    &format!("Tag ID {} not found in inventory. Would you like to add a new item?", tag_id),
-                    "No", "Yes", "") == Some(1) {
+                    &tr_args("scan-add-confirm", &[("tag_id", tag_id)]),
+                    &tr("btn-cancel"), &tr("inv-add"), "") == Some(1) {
                     
                     // Set current tag and prompt for details
                     *self.current_tag_id.borrow_mut() = Some(tag_id.to_string());
                     
                     // This would ideally open a form dialog, but for now we'll use a simple input
-                    if let Some(name) = dialog::input(300, 300, "Enter item name:", "") {
+                    if let Some(name) = dialog::input(300, 300, &tr("scan-item-name"), "") {
                         if !name.is_empty() {
                             // Create basic item
                             let item = create_inventory_item(tag_id, &name, None, 1, None, None);
                             
                             // Save to database
                             if let Err(e) = self.inventory_db.borrow().save_item(&item) {
-                                dialog::alert(300, 300, &format!("Error saving item: {}", e));
+                                dialog::alert(300, 300, &tr_args("err-inv-save", &[("error", &e.to_string())]));
                                 return;
                             }
                             
                             // Add Google Drive sync if enabled
                             // Update APP_CONFIG access depending on your final solution
                             // For Mutex-based approach:
+                            #[cfg(feature = "sync")]
                             if let Ok(config) = crate::config::APP_CONFIG.lock() {
                                 if config.gdrive_sync_enabled {
                                     use crate::sync::gdrive_sync::GDriveSync;
                                     let gdrive_sync = GDriveSync::new(&config.gdrive_sync_folder);
                                     match gdrive_sync.export_database(&self.inventory_db.borrow()) {
-                                        Ok(_) => println!("Automatically synced database to Google Drive"),
-                                        Err(e) => println!("Error auto-syncing to Google Drive: {}", e)
+                                        Ok(_) => println!("{}", tr("log-gdrive-auto-synced")),
+                                        Err(e) => println!("{}", tr_args("err-gdrive-auto-sync", &[("error", &e.to_string())]))
                                     }
                                 }
                             }
                             
-                            dialog::message(300, 300, &format!("New item '{}' added to inventory.", name));
+                            dialog::message(300, 300, &tr_args("msg-scan-added", &[("item", &name)]));
                             
                             // Refresh the table
                             if let Ok(all_items) = self.inventory_db.borrow().get_all_items() {
@@ -540,7 +544,7 @@ impl InventoryUI {
                 }
             },
             Err(e) => {
-                dialog::alert(300, 300, &format!("Error checking inventory: {}", e));
+                dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
             }
         }
     }
@@ -7,6 +7,8 @@ use fltk::{
 };
 use std::rc::Rc;
 use std::cell::RefCell;
+use crate::i18n::{tr, tr_args};
+use crate::inventory::expiry;
 use crate::inventory::model::InventoryItem;
 use crate::inventory::ui::utils::format_timestamp;
 
@@ -15,6 +17,7 @@ pub struct ItemForm {
     pub quantity_input: Input,
     pub category_choice: Choice,
     pub location_input: Input,
+    pub expiry_input: Input,
     pub description_input: MultilineInput,
     pub tag_id_display: Frame,
     pub created_display: Frame,
@@ -27,6 +30,7 @@ impl Clone for ItemForm {
             quantity_input: self.quantity_input.clone(),
             category_choice: self.category_choice.clone(),
             location_input: self.location_input.clone(),
+            expiry_input: self.expiry_input.clone(),
             description_input: self.description_input.clone(),
             tag_id_display: self.tag_id_display.clone(),
             created_display: self.created_display.clone(),
@@ -35,22 +39,28 @@ impl Clone for ItemForm {
     }
 }
 impl ItemForm {
+    /// Height taken by the fields and the tag/date lines below them
+    pub const HEIGHT: i32 = 400;
+    
     pub fn new(x: i32, y: i32, w: i32, _h: i32) -> Self {
-        let name_input = Input::new(x + 100, y, w - 100, 30, "Name:");
-        let quantity_input = Input::new(x + 100, y + 40, w - 100, 30, "Quantity:");
-        let category_choice = Choice::new(x + 100, y + 80, w - 100, 30, "Category:");
-        let location_input = Input::new(x + 100, y + 120, w - 100, 30, "Location:");
-        let description_input = MultilineInput::new(x + 100, y + 160, w - 100, 100, "Description:");
+        let name_input = Input::new(x + 100, y, w - 100, 30, None).with_label(&tr("form-name"));
+        let quantity_input = Input::new(x + 100, y + 40, w - 100, 30, None).with_label(&tr("triage-quantity"));
+        let category_choice = Choice::new(x + 100, y + 80, w - 100, 30, None).with_label(&tr("form-category"));
+        let location_input = Input::new(x + 100, y + 120, w - 100, 30, None).with_label(&tr("form-location"));
+        let mut expiry_input = Input::new(x + 100, y + 160, w - 100, 30, None).with_label(&tr("form-expires"));
+        expiry_input.set_tooltip(&tr("form-expires-tip"));
+        let description_input = MultilineInput::new(x + 100, y + 200, w - 100, 100, None).with_label(&tr("form-description"));
         
-        let tag_id_display = Frame::new(x, y + 270, w, 30, "Tag ID: None selected");
-        let created_display = Frame::new(x, y + 300, w, 30, "Created: -");
-        let updated_display = Frame::new(x, y + 330, w, 30, "Updated: -");
+        let tag_id_display = Frame::new(x, y + 310, w, 30, None).with_label(&tr("form-no-tag"));
+        let created_display = Frame::new(x, y + 340, w, 30, None).with_label(&tr_args("form-created", &[("time", "-")]));
+        let updated_display = Frame::new(x, y + 370, w, 30, None).with_label(&tr_args("form-updated", &[("time", "-")]));
         
         ItemForm {
             name_input,
             quantity_input,
             category_choice,
             location_input,
+            expiry_input,
             description_input,
             tag_id_display,
             created_display,
@@ -63,10 +73,11 @@ impl ItemForm {
         self.quantity_input.set_value("");
         self.category_choice.set_value(0);
         self.location_input.set_value("");
+        self.expiry_input.set_value("");
         self.description_input.set_value("");
-        self.tag_id_display.set_label("Tag ID: None selected");
-        self.created_display.set_label("Created: -");
-        self.updated_display.set_label("Updated: -");
+        self.tag_id_display.set_label(&tr("form-no-tag"));
+        self.created_display.set_label(&tr_args("form-created", &[("time", "-")]));
+        self.updated_display.set_label(&tr_args("form-updated", &[("time", "-")]));
     }
     
     pub fn display_item(&mut self, item: &InventoryItem) {
@@ -88,26 +99,27 @@ impl ItemForm {
         }
         
         self.location_input.set_value(&item.location.clone().unwrap_or_default());
+        self.expiry_input.set_value(&item.expiry_date.clone().unwrap_or_default());
         self.description_input.set_value(&item.description.clone().unwrap_or_default());
         
         // Update display fields
-        self.tag_id_display.set_label(&format!("Tag ID: {}", item.tag_id));
-        self.created_display.set_label(&format!("Created: {}", format_timestamp(&item.created_at)));
-        self.updated_display.set_label(&format!("Updated: {}", format_timestamp(&item.last_updated)));
+        self.tag_id_display.set_label(&tr_args("form-tag", &[("tag_id", &item.tag_id)]));
+        self.created_display.set_label(&tr_args("form-created", &[("time", &format_timestamp(&item.created_at))]));
+        self.updated_display.set_label(&tr_args("form-updated", &[("time", &format_timestamp(&item.last_updated))]));
     }
     
     pub fn get_form_data(&self, tag_id: &str) -> Result<InventoryItem, String> {
         // Validate form
         let name = self.name_input.value();
         if name.is_empty() {
-            return Err("Item name is required.".to_string());
+            return Err(tr("err-form-name"));
         }
         
         let quantity_str = self.quantity_input.value();
         let quantity = match quantity_str.parse::<i32>() {
             Ok(q) => q,
             Err(_) => {
-                return Err("Quantity must be a valid number.".to_string());
+                return Err(tr("err-form-quantity"));
             }
         };
         
@@ -132,8 +144,10 @@ impl ItemForm {
             Some(self.description_input.value())
         };
         
+        let expiry_date = expiry::parse_input(&self.expiry_input.value())?;
+        
         // Create a new item
-        let item = crate::inventory::model::create_inventory_item(
+        let mut item = crate::inventory::model::create_inventory_item(
             tag_id,
             &name,
             description.as_deref(),
@@ -141,13 +155,14 @@ impl ItemForm {
             location.as_deref(),
             category.as_deref()
         );
+        item.expiry_date = expiry_date;
         
         Ok(item)
     }
     
     pub fn update_categories(&mut self, categories: &[String]) {
         self.category_choice.clear();
-        self.category_choice.add_choice("Uncategorized");
+        self.category_choice.add_choice(&tr("form-uncategorized"));
         for cat in categories {
             self.category_choice.add_choice(cat);
         }
@@ -8,6 +8,7 @@ use std::collections::HashSet;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::tr_args;
 use crate::inventory::model::InventoryItem;
 
 pub struct StatsFrame {
@@ -40,12 +41,11 @@ impl StatsFrame {
             .collect();
         
         // Update the text display
-        self.text.set_label(&format!(
-            "Total Items: {}\nTotal Quantity: {}\nCategories: {}",
-            total_items,
-            total_quantity,
-            categories.len()
-        ));
+        self.text.set_label(&tr_args("inv-stats", &[
+            ("items", &total_items.to_string()),
+            ("quantity", &total_quantity.to_string()),
+            ("categories", &categories.len().to_string()),
+        ]));
     }
 }
 
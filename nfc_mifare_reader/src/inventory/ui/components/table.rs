@@ -3,7 +3,10 @@ use fltk::{prelude::*, table::Table, draw};
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::tr;
+use crate::inventory::expiry::{self, ExpiryStatus};
 use crate::inventory::model::InventoryItem;
+use crate::ui::theme;
 
 // Function to set up the inventory table
 pub fn setup_inventory_table(
@@ -15,33 +18,36 @@ pub fn setup_inventory_table(
     table.set_rows(0);
     table.set_row_header(true);
     table.set_row_resize(true);
-    table.set_cols(4);
+    table.set_cols(5);
     table.set_col_header(true);
     table.set_col_width(0, 100); // ID Column
     table.set_col_width(1, 150); // Name Column
     table.set_col_width(2, 50);  // Quantity Column
     table.set_col_width(3, 80);  // Category Column
+    table.set_col_width(4, 90);  // Expiry Date Column
     
     // Set up header drawing callback
     table.draw_cell(move |_t, ctx, row, col, x, y, w, h| {
         match ctx {
             fltk::table::TableContext::StartPage => draw::set_font(fltk::enums::Font::Helvetica, 14),
             fltk::table::TableContext::ColHeader => {
-                draw::draw_rect_fill(x, y, w, h, fltk::enums::Color::from_rgb(220, 220, 220));
-                draw::set_draw_color(fltk::enums::Color::Black);
+                let palette = theme::palette();
+                draw::draw_rect_fill(x, y, w, h, palette.header_background);
+                draw::set_draw_color(palette.grid);
                 draw::draw_rect(x, y, w, h);
                 draw::set_font(fltk::enums::Font::HelveticaBold, 12);
-                draw::set_draw_color(fltk::enums::Color::Black);
+                draw::set_draw_color(palette.header_text);
                 
                 let header = match col {
-                    0 => "Tag ID",
-                    1 => "Name",
-                    2 => "Qty",
-                    3 => "Category",
-                    _ => "",
+                    0 => tr("inv-col-tag"),
+                    1 => tr("inv-col-name"),
+                    2 => tr("inv-col-quantity"),
+                    3 => tr("inv-col-category"),
+                    4 => tr("inv-col-expires"),
+                    _ => String::new(),
                 };
                 
-                draw::draw_text2(header, x, y, w, h, fltk::enums::Align::Center);
+                draw::draw_text2(&header, x, y, w, h, fltk::enums::Align::Center);
             },
             fltk::table::TableContext::Cell => {
                 let items = items.borrow();
@@ -49,21 +55,25 @@ pub fn setup_inventory_table(
                 if row < items.len() as i32 {
                     let item = &items[row as usize];
                     
-                    // Alternate row colors
-                    if row % 2 == 0 {
-                        draw::draw_rect_fill(x, y, w, h, fltk::enums::Color::from_rgb(245, 245, 245));
-                    } else {
-                        draw::draw_rect_fill(x, y, w, h, fltk::enums::Color::from_rgb(255, 255, 255));
+                    // Alternate row colors, items close to or past their expiry date stand out
+                    let palette = theme::palette();
+                    match expiry::status(item, expiry::today(), expiry::warning_days()) {
+                        ExpiryStatus::Expired(_) => draw::draw_rect_fill(x, y, w, h, palette.row_expired),
+                        ExpiryStatus::Expiring(_) => draw::draw_rect_fill(x, y, w, h, palette.row_expiring),
+                        _ if row % 2 == 0 => draw::draw_rect_fill(x, y, w, h, palette.row_even),
+                        _ => draw::draw_rect_fill(x, y, w, h, palette.row_odd),
                     }
                     
-                    draw::set_draw_color(fltk::enums::Color::Black);
+                    draw::set_draw_color(palette.grid);
                     draw::draw_rect(x, y, w, h);
+                    draw::set_draw_color(palette.text);
                     
                     let text = match col {
                         0 => &item.tag_id,
                         1 => &item.name,
                         2 => return draw::draw_text2(&item.quantity.to_string(), x, y, w, h, fltk::enums::Align::Center),
                         3 => return draw::draw_text2(item.category.as_deref().unwrap_or(""), x, y, w, h, fltk::enums::Align::Center),
+                        4 => return draw::draw_text2(item.expiry_date.as_deref().unwrap_or(""), x, y, w, h, fltk::enums::Align::Center),
                         _ => "",
                     };
                     
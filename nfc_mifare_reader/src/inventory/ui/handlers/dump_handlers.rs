@@ -0,0 +1,218 @@
+// src/inventory/ui/handlers/dump_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::Font,
+    frame::Frame,
+    prelude::*,
+    text::{TextBuffer, TextDisplay},
+    window::Window,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::dumps::{format_dump, parse_dump};
+use crate::inventory::model::{CardDump, InventoryItem};
+use crate::inventory::ui::utils::format_timestamp;
+
+pub fn setup_dumps_button(
+    dumps_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>,
+    current_tag_id: Rc<RefCell<Option<String>>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    dumps_btn.set_callback(move |_| {
+        let tag_id = match current_tag_id.borrow().clone() {
+            Some(tag_id) => tag_id,
+            None => {
+                dialog::alert(300, 300, &tr("err-select-item-first"));
+                return;
+            }
+        };
+        // bind the lookup first so the database borrow ends before any dialog runs
+        let item = inventory_db.borrow().get_item(&tag_id);
+        match item {
+            Ok(Some(item)) => show_dumps_window(inventory_db.clone(), log_buffer_clone.clone(), item),
+            Ok(None) => dialog::alert(300, 300, &tr("err-item-gone")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-load-item", &[("error", &e.to_string())])),
+        }
+    });
+}
+
+// this lists the card dumps kept with the item (what is on a badge, say), to attach
+// one from a file, look at one, save one back to a file or delete one
+fn show_dumps_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer, item: InventoryItem) {
+    let mut win = Window::new(200, 100, 600, 400, None).with_label(&tr_args("dumps-title", &[("item", &item.name)]));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 560, 25, None).with_label(&tr("dumps-columns"));
+    let mut dump_browser = HoldBrowser::new(20, 40, 560, 300, "");
+    dump_browser.set_column_widths(&[150, 110, 60, 240]);
+    dump_browser.set_column_char('\t');
+
+    let mut attach_btn = Button::new(20, 355, 130, 30, None).with_label(&tr("dumps-attach"));
+    let mut view_btn = Button::new(160, 355, 80, 30, None).with_label(&tr("dumps-view"));
+    let mut save_btn = Button::new(250, 355, 100, 30, None).with_label(&tr("dumps-save"));
+    let mut delete_btn = Button::new(360, 355, 80, 30, None).with_label(&tr("btn-delete"));
+    let mut close_btn = Button::new(500, 355, 80, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let dumps: Rc<RefCell<Vec<CardDump>>> = Rc::new(RefCell::new(Vec::new()));
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let dumps = dumps.clone();
+        let dump_browser = dump_browser.clone();
+        let item_tag = item.tag_id.clone();
+        move || {
+            let mut dump_browser = dump_browser.clone();
+            dump_browser.clear();
+            match inventory_db.borrow().card_dumps(&item_tag) {
+                Ok(item_dumps) => *dumps.borrow_mut() = item_dumps,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-dumps-load", &[("error", &e.to_string())])),
+            }
+            for dump in dumps.borrow().iter() {
+                dump_browser.add(&dump_line(dump));
+            }
+        }
+    };
+    reload();
+
+    // the dump selected in the list
+    let selected = {
+        let dumps = dumps.clone();
+        let dump_browser = dump_browser.clone();
+        move || -> Option<CardDump> {
+            let line = dump_browser.value();
+            let dump = usize::try_from(line - 1).ok().and_then(|index| dumps.borrow().get(index).cloned());
+            if dump.is_none() {
+                dialog::alert(300, 300, &tr("err-dumps-select"));
+            }
+            dump
+        }
+    };
+
+    let db_attach = inventory_db.clone();
+    let reload_attach = reload.clone();
+    let mut log_attach = log_buffer.clone();
+    let item_attach = item.clone();
+    attach_btn.set_callback(move |_| {
+        let path = match dialog::file_chooser(&tr("title-dumps-attach"), "*.{bin,mfd,dump,nfc}", ".", false) {
+            Some(path) => path,
+            None => return,
+        };
+        let name = Path::new(&path).file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| path.clone());
+        let parsed = fs::read(&path)
+            .map_err(|e| tr_args("err-dumps-read", &[("path", &path), ("error", &e.to_string())]))
+            .and_then(|contents| parse_dump(&name, &contents));
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                dialog::alert(300, 300, &e);
+                return;
+            }
+        };
+
+        let result = db_attach.borrow().attach_card_dump(&item_attach.tag_id, &parsed.uid, Some(&name), &parsed.data);
+        match result {
+            Ok(dump) => log_attach.append(&format!("{}\n", tr_args("log-dumps-attached", &[
+                ("uid", &dump.uid),
+                ("blocks", &dump.blocks().to_string()),
+                ("item", &item_attach.name),
+            ]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-dumps-attach", &[("error", &e.to_string())])),
+        }
+        reload_attach();
+    });
+
+    let selected_view = selected.clone();
+    view_btn.set_callback(move |_| {
+        if let Some(dump) = selected_view() {
+            show_dump_contents(&dump);
+        }
+    });
+
+    let selected_save = selected.clone();
+    let mut log_save = log_buffer.clone();
+    save_btn.set_callback(move |_| {
+        let dump = match selected_save() {
+            Some(dump) => dump,
+            None => return,
+        };
+        let path = match dialog::file_chooser(&tr("title-dumps-save"), "*.bin", &format!("{}.bin", dump.uid), false) {
+            Some(path) => path,
+            None => return,
+        };
+        match fs::write(&path, &dump.data) {
+            Ok(()) => log_save.append(&format!("{}\n", tr_args("log-dumps-saved", &[("uid", &dump.uid), ("path", &path)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-dumps-save", &[("error", &e.to_string())])),
+        }
+    });
+
+    let db_delete = inventory_db;
+    let reload_delete = reload;
+    let mut log_delete = log_buffer;
+    delete_btn.set_callback(move |_| {
+        let dump = match selected() {
+            Some(dump) => dump,
+            None => return,
+        };
+        let question = tr_args("dumps-delete-confirm", &[("uid", &dump.uid), ("time", &format_timestamp(&dump.taken_at))]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-delete"), "") != Some(1) {
+            return;
+        }
+        let result = db_delete.borrow().delete_card_dump(dump.id);
+        match result {
+            Ok(_) => log_delete.append(&format!("{}\n", tr_args("log-dumps-deleted", &[("uid", &dump.uid), ("item", &item.name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-dumps-delete", &[("error", &e.to_string())])),
+        }
+        reload_delete();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows every block of a dump in hex, by sector
+fn show_dump_contents(dump: &CardDump) {
+    let mut win = Window::new(150, 80, 720, 500, None)
+        .with_label(&tr_args("dumps-view-title", &[("uid", &dump.uid), ("time", &format_timestamp(&dump.taken_at))]));
+    win.make_modal(true);
+
+    let mut buffer = TextBuffer::default();
+    buffer.set_text(&format!("{}\n{}", tr("dumps-view-columns"), format_dump(&dump.data)));
+    let mut display = TextDisplay::new(10, 10, 700, 440, "");
+    display.set_buffer(buffer);
+    display.set_text_font(Font::Courier);
+
+    let mut close_btn = Button::new(630, 460, 80, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows when the dump was attached, the card's UID, its size and the file it came from
+fn dump_line(dump: &CardDump) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        format_timestamp(&dump.taken_at),
+        dump.uid,
+        dump.blocks(),
+        dump.source.as_deref().unwrap_or("")
+    )
+}
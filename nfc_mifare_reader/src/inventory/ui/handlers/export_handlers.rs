@@ -6,9 +6,25 @@ use fltk::{
     text::TextBuffer,
 };
 use std::cell::RefCell;
+use std::path::Path;
 use std::rc::Rc;
+use progress_sink::StatusFile;
 
+use crate::i18n::{tr, tr_args};
 use crate::inventory::db::InventoryDB;
+use crate::inventory::import;
+
+// The join table of item tags, written to "<name>_tags.csv" beside the items' CSV.
+// Returns the path written.
+pub fn write_tags_csv(db: &InventoryDB, path: &str) -> Result<String, String> {
+    let csv = db.export_tags_csv().map_err(|e| e.to_string())?;
+    let items_path = Path::new(path);
+    let stem = items_path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let tags_path = items_path.with_file_name(format!("{}_tags.csv", stem));
+    std::fs::write(&tags_path, csv).map_err(|e| e.to_string())?;
+    crate::signing::sign_export(&tags_path);
+    Ok(tags_path.display().to_string())
+}
 
 pub fn setup_export_button(
     export_btn: &mut Button,
@@ -20,34 +36,41 @@ pub fn setup_export_button(
     
     export_btn.set_callback(move |_| {
         // Fixed the dialog::choice call to use dialog::choice2
-        match dialog::choice2(300, 300, "Select export format:", "JSON", "CSV", "Cancel") {
+        match dialog::choice2(300, 300, &tr("export-format"), "JSON", "CSV", &tr("btn-cancel")) {
             Some(1) => { // JSON
-                if let Some(path) = dialog::file_chooser("Save JSON Export", "*.json", "", false) {
+                if let Some(path) = dialog::file_chooser(&tr("title-export-json"), "*.json", "", false) {
                     match db_clone.borrow().export_json() {
                         Ok(json) => {
                             if let Err(e) = std::fs::write(&path, json) {
-                                dialog::alert(300, 300, &format!("Error writing file: {}", e));
+                                dialog::alert(300, 300, &tr_args("err-write-file", &[("error", &e.to_string())]));
                             } else {
-                                log_buffer_clone.append(&format!("Exported JSON to {}\n", path));
-                                dialog::message(300, 300, &format!("Data exported to {}", path));
+                                log_buffer_clone.append(&format!("{}\n", tr_args("log-exported", &[("path", &path)])));
+                                crate::signing::sign_export(std::path::Path::new(&path));
+                                dialog::message(300, 300, &tr_args("msg-exported", &[("path", &path)]));
                             }
                         },
-                        Err(e) => dialog::alert(300, 300, &format!("Error exporting data: {}", e))
+                        Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())]))
                     }
                 }
             },
             Some(2) => { // CSV
-                if let Some(path) = dialog::file_chooser("Save CSV Export", "*.csv", "", false) {
+                if let Some(path) = dialog::file_chooser(&tr("title-export-csv"), "*.csv", "", false) {
                     match db_clone.borrow().export_csv() {
                         Ok(csv) => {
                             if let Err(e) = std::fs::write(&path, csv) {
-                                dialog::alert(300, 300, &format!("Error writing file: {}", e));
+                                dialog::alert(300, 300, &tr_args("err-write-file", &[("error", &e.to_string())]));
                             } else {
-                                log_buffer_clone.append(&format!("Exported CSV to {}\n", path));
-                                dialog::message(300, 300, &format!("Data exported to {}", path));
+                                log_buffer_clone.append(&format!("{}\n", tr_args("log-exported", &[("path", &path)])));
+                                crate::signing::sign_export(std::path::Path::new(&path));
+                                // the tags of the items, one row per tag, go beside the items
+                                match write_tags_csv(&db_clone.borrow(), &path) {
+                                    Ok(tags_path) => log_buffer_clone.append(&format!("{}\n", tr_args("log-exported-tags", &[("path", &tags_path)]))),
+                                    Err(e) => dialog::alert(300, 300, &tr_args("err-export-tags", &[("error", &e.to_string())])),
+                                }
+                                dialog::message(300, 300, &tr_args("msg-exported", &[("path", &path)]));
                             }
                         },
-                        Err(e) => dialog::alert(300, 300, &format!("Error exporting data: {}", e))
+                        Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())]))
                     }
                 }
             },
@@ -66,21 +89,21 @@ pub fn setup_import_button(
     let mut log_buffer_clone = log_buffer.clone();
     
     import_btn.set_callback(move |_| {
-        match dialog::choice2(300, 300, "Select import format:", "JSON", "CSV", "Cancel") {
+        match dialog::choice2(300, 300, &tr("import-format"), "JSON", "CSV", &tr("btn-cancel")) {
             Some(1) => { // JSON
-                if let Some(path) = dialog::file_chooser("Open JSON Import", "*.json", "", true) {
+                if let Some(path) = dialog::file_chooser(&tr("title-import"), "*.json", "", true) {
                     match std::fs::read_to_string(&path) {
                         Ok(json) => {
-                            match db_clone.borrow().import_json(&json) {
+                            match import::import_json(&db_clone.borrow(), &json, &StatusFile::new("import")) {
                                 Ok(count) => {
-                                    log_buffer_clone.append(&format!("Imported {} items from {}\n", count, path));
-                                    dialog::message(300, 300, &format!("Successfully imported {} items", count));
+                                    log_buffer_clone.append(&format!("{}\n", tr_args("log-import-file", &[("count", &count.to_string()), ("file", &path)])));
+                                    dialog::message(300, 300, &tr_args("msg-json-imported", &[("count", &count.to_string())]));
                                     refresh_callback();
                                 },
-                                Err(e) => dialog::alert(300, 300, &format!("Error importing data: {}", e))
+                                Err(e) => dialog::alert(300, 300, &tr_args("err-json-import", &[("error", &e.to_string())]))
                             }
                         },
-                        Err(e) => dialog::alert(300, 300, &format!("Error reading file: {}", e))
+                        Err(e) => dialog::alert(300, 300, &tr_args("err-read-file", &[("error", &e.to_string())]))
                     }
                 }
             },
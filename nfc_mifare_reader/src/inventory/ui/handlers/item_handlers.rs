@@ -10,6 +10,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use std::collections::HashSet;
 
+use crate::i18n::{tr, tr_args};
 use crate::inventory::model::InventoryItem;
 use crate::inventory::db::InventoryDB;
 use crate::inventory::ui::components::form::ItemForm;
@@ -43,7 +44,7 @@ pub fn setup_save_button(
                     
                     // Save to database
                     if let Err(e) = db_clone.borrow().save_item(&item) {
-                        dialog::alert(300, 300, &format!("Error saving item: {}", e));
+                        dialog::alert(300, 300, &tr_args("err-inv-save", &[("error", &e.to_string())]));
                         return;
                     }
                     
@@ -54,15 +55,15 @@ pub fn setup_save_button(
                         table_clone.borrow_mut().redraw();
                     }
                     
-                    log_buffer_clone.append(&format!("Saved item: {}\n", item.name));
-                    dialog::message(300, 300, "Item saved successfully");
+                    log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-saved", &[("item", &item.name)])));
+                    dialog::message(300, 300, &tr("msg-inv-saved"));
                 },
                 Err(e) => {
-                    dialog::alert(300, 300, &format!("Form validation error: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-inv-form", &[("error", &e.to_string())]));
                 }
             }
         } else {
-            dialog::alert(300, 300, "No item selected to save");
+            dialog::alert(300, 300, &tr("err-inv-select-save"));
         }
     });
 }
@@ -86,10 +87,10 @@ pub fn setup_delete_button(
     delete_btn.set_callback(move |_| {
         if let Some(tag_id) = current_tag_clone.borrow().clone() {
             // Ask for confirmation
-            if dialog::choice2(300, 300, "Are you sure you want to delete this item?", "No", "Yes", "") == Some(1) {
+            if dialog::choice2(300, 300, &tr("inv-trash-confirm"), &tr("btn-cancel"), &tr("inv-delete"), "") == Some(1) {
                 // Delete from database
                 if let Err(e) = db_clone.borrow().delete_item(&tag_id) {
-                    dialog::alert(300, 300, &format!("Error deleting item: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-trash-delete", &[("error", &e.to_string())]));
                     return;
                 }
                 
@@ -106,11 +107,11 @@ pub fn setup_delete_button(
                     table_clone.borrow_mut().redraw();
                 }
                 
-                log_buffer_clone.append(&format!("Deleted item with tag: {}\n", tag_id));
-                dialog::message(300, 300, "Item deleted successfully");
+                log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-trashed", &[("tag_id", &tag_id)])));
+                dialog::message(300, 300, &tr("msg-inv-trashed"));
             }
         } else {
-            dialog::alert(300, 300, "No item selected to delete");
+            dialog::alert(300, 300, &tr("err-trash-select-delete"));
         }
     });
 }
@@ -128,7 +129,7 @@ pub fn setup_clear_button(
     clear_btn.set_callback(move |_| {
         item_form_clone.clear();
         *current_tag_clone.borrow_mut() = None;
-        log_buffer_clone.append("Form cleared\n");
+        log_buffer_clone.append(&format!("{}\n", tr("log-inv-cleared")));
     });
 }
 
@@ -144,7 +145,7 @@ pub fn setup_add_button(
     
     add_btn.set_callback(move |_| {
         // Generate a new tag ID or prompt user for one
-        if let Some(tag_id) = dialog::input(300, 300, "Enter Tag ID for new item:", "") {
+        if let Some(tag_id) = dialog::input(300, 300, &tr("inv-new-tag"), "") {
             if !tag_id.is_empty() {
                 // Clear form and set new tag ID
                 item_form_clone.clear();
@@ -153,9 +154,9 @@ pub fn setup_add_button(
                 let display_tag_id = tag_id.clone();
                 let log_tag_id = tag_id.clone();
                 
-                item_form_clone.tag_id_display.set_label(&format!("Tag ID: {}", display_tag_id));
+                item_form_clone.tag_id_display.set_label(&tr_args("form-tag", &[("tag_id", &display_tag_id)]));
                 *current_tag_clone.borrow_mut() = Some(tag_id);
-                log_buffer_clone.append(&format!("Ready to add new item with tag: {}\n", log_tag_id));
+                log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-new", &[("tag_id", &log_tag_id)])));
             }
         }
     });
@@ -195,12 +196,11 @@ pub fn setup_refresh_button(
                     .filter_map(|i| i.category.clone())
                     .collect();
                 
-                stats_text_clone.set_label(&format!(
-                    "Total Items: {}\nTotal Quantity: {}\nCategories: {}",
-                    items.len(),
-                    total_quantity,
-                    categories.len()
-                ));
+                stats_text_clone.set_label(&tr_args("inv-stats", &[
+                    ("items", &items.len().to_string()),
+                    ("quantity", &total_quantity.to_string()),
+                    ("categories", &categories.len().to_string()),
+                ]));
                 
                 // Populate category dropdown
                 let mut categories: Vec<String> = categories.into_iter().collect();
@@ -208,11 +208,12 @@ pub fn setup_refresh_button(
                 category_choice_clone.update_categories(&categories);
                 
                 // Add to log
-                log_buffer_clone.append("Refreshed inventory list\n");
+                log_buffer_clone.append(&format!("{}\n", tr("log-inv-refreshed")));
             },
             Err(e) => {
-                dialog::alert(300, 300, &format!("Error loading inventory: {}", e));
-                log_buffer_clone.append(&format!("Error: {}\n", e));
+                let message = tr_args("err-load-items", &[("error", &e.to_string())]);
+                dialog::alert(300, 300, &message);
+                log_buffer_clone.append(&format!("{}\n", message));
             }
         }
     });
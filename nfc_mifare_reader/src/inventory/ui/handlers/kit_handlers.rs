@@ -0,0 +1,294 @@
+// src/inventory/ui/handlers/kit_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    frame::Frame,
+    input::IntInput,
+    menu::Choice,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::kits;
+use crate::inventory::model::{InventoryItem, Kit, KitComponent, StockMode};
+use crate::inventory::ui::utils::tag_in_use;
+
+pub fn setup_kits_button(
+    kits_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    kits_btn.set_callback(move |_| {
+        show_kits_window(inventory_db.clone(), log_buffer_clone.clone());
+    });
+}
+
+// this lists the kits on the left and the bill of materials of the selected one on the
+// right, with the items to add to it above
+fn show_kits_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer) {
+    let mut win = Window::new(150, 100, 780, 460, None).with_label(&tr("kit-title"));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 300, 25, None).with_label(&tr("kit-title"));
+    let mut kit_browser = HoldBrowser::new(20, 40, 300, 340, "");
+    kit_browser.set_column_widths(&[170, 130]);
+    kit_browser.set_column_char('\t');
+
+    let mut item_choice = Choice::new(340, 10, 250, 25, None);
+    let mut per_kit_input = IntInput::new(600, 10, 60, 25, None);
+    per_kit_input.set_value("1");
+    per_kit_input.set_tooltip(&tr("kit-per-kit-tip"));
+    let mut add_item_btn = Button::new(670, 10, 90, 25, None).with_label(&tr("kit-add-item"));
+
+    Frame::new(340, 40, 420, 20, None).with_label(&tr("kit-component-columns"));
+    let mut component_browser = HoldBrowser::new(340, 60, 420, 320, "");
+    component_browser.set_column_widths(&[180, 110, 60, 60]);
+    component_browser.set_column_char('\t');
+
+    let mut new_kit_btn = Button::new(20, 390, 95, 30, None).with_label(&tr("kit-new"));
+    let mut delete_kit_btn = Button::new(122, 390, 95, 30, None).with_label(&tr("kit-delete"));
+
+    let mut remove_item_btn = Button::new(340, 390, 110, 30, None).with_label(&tr("kit-remove-item"));
+    let mut check_btn = Button::new(460, 390, 110, 30, None).with_label(&tr("kit-check-stock"));
+
+    let mut close_btn = Button::new(680, 425, 80, 28, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let all_kits: Rc<RefCell<Vec<Kit>>> = Rc::new(RefCell::new(Vec::new()));
+    let items: Rc<RefCell<Vec<InventoryItem>>> = Rc::new(RefCell::new(Vec::new()));
+
+    match inventory_db.borrow().get_all_items() {
+        Ok(all_items) => *items.borrow_mut() = all_items,
+        Err(e) => dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())])),
+    }
+    for item in items.borrow().iter() {
+        item_choice.add_choice(&format!("{} ({})", item.name, item.tag_id).replace('/', "\\/"));
+    }
+    if item_choice.size() > 0 {
+        item_choice.set_value(0);
+    }
+
+    // the kit selected on the left
+    let selected_kit = {
+        let all_kits = all_kits.clone();
+        let kit_browser = kit_browser.clone();
+        move || -> Option<Kit> {
+            let line = kit_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| all_kits.borrow().get(index).cloned())
+        }
+    };
+
+    let show_components = {
+        let items = items.clone();
+        let component_browser = component_browser.clone();
+        let selected_kit = selected_kit.clone();
+        move || {
+            let mut component_browser = component_browser.clone();
+            component_browser.clear();
+            if let Some(kit) = selected_kit() {
+                for component in &kit.components {
+                    let item = items.borrow().iter().find(|item| item.tag_id == component.tag_id).cloned();
+                    component_browser.add(&component_line(component, item.as_ref()));
+                }
+            }
+        }
+    };
+
+    // this keeps the kit with `select_tag` selected, or the one selected before
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let all_kits = all_kits.clone();
+        let kit_browser = kit_browser.clone();
+        let show_components = show_components.clone();
+        let selected_kit = selected_kit.clone();
+        move |select_tag: Option<String>| {
+            let mut kit_browser = kit_browser.clone();
+            let keep = select_tag.or_else(|| selected_kit().map(|kit| kit.tag_id));
+            kit_browser.clear();
+            match inventory_db.borrow().kits() {
+                Ok(kits) => *all_kits.borrow_mut() = kits,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-kit-load", &[("error", &e.to_string())])),
+            }
+            for kit in all_kits.borrow().iter() {
+                kit_browser.add(&format!("{}\t{}", kit.name, kit.tag_id));
+            }
+            let line = all_kits.borrow().iter()
+                .position(|kit| Some(&kit.tag_id) == keep.as_ref())
+                .map(|index| index as i32 + 1)
+                .unwrap_or(if kit_browser.size() > 0 { 1 } else { 0 });
+            if line > 0 {
+                kit_browser.select(line);
+            }
+            show_components();
+        }
+    };
+    reload(None);
+
+    let show_selected = show_components.clone();
+    kit_browser.set_callback(move |_| show_selected());
+
+    let db_new = inventory_db.clone();
+    let reload_new = reload.clone();
+    let mut log_new = log_buffer.clone();
+    new_kit_btn.set_callback(move |_| {
+        let tag_id = match dialog::input(300, 300, &tr("kit-tag-id"), "") {
+            Some(tag_id) if !tag_id.trim().is_empty() => tag_id.trim().to_uppercase(),
+            _ => return,
+        };
+        if let Some(message) = tag_in_use(&db_new.borrow(), &tag_id) {
+            dialog::alert(300, 300, &message);
+            return;
+        }
+        let name = match dialog::input(300, 300, &tr("kit-name"), "") {
+            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => return,
+        };
+        match db_new.borrow().save_kit(&kits::new_kit(&tag_id, &name)) {
+            Ok(()) => log_new.append(&format!("{}\n", tr_args("log-kit-added", &[("kit", &name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-kit-add", &[("error", &e.to_string())])),
+        }
+        reload_new(Some(tag_id));
+    });
+
+    let db_delete = inventory_db.clone();
+    let reload_delete = reload.clone();
+    let selected_delete = selected_kit.clone();
+    let mut log_delete = log_buffer.clone();
+    delete_kit_btn.set_callback(move |_| {
+        let kit = match selected_delete() {
+            Some(kit) => kit,
+            None => {
+                dialog::alert(300, 300, &tr("err-kit-select"));
+                return;
+            }
+        };
+        let question = tr_args("kit-delete-confirm", &[("kit", &kit.name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-delete"), "") != Some(1) {
+            return;
+        }
+        match db_delete.borrow().delete_kit(&kit.tag_id) {
+            Ok(_) => log_delete.append(&format!("{}\n", tr_args("log-kit-deleted", &[("kit", &kit.name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-kit-delete", &[("error", &e.to_string())])),
+        }
+        reload_delete(None);
+    });
+
+    let db_add = inventory_db.clone();
+    let reload_add = reload.clone();
+    let selected_add = selected_kit.clone();
+    let items_add = items.clone();
+    add_item_btn.set_callback(move |_| {
+        let mut kit = match selected_add() {
+            Some(kit) => kit,
+            None => {
+                dialog::alert(300, 300, &tr("err-kit-no-kit"));
+                return;
+            }
+        };
+        let item = match usize::try_from(item_choice.value()).ok().and_then(|index| items_add.borrow().get(index).cloned()) {
+            Some(item) => item,
+            None => {
+                dialog::alert(300, 300, &tr("err-no-item-selected"));
+                return;
+            }
+        };
+        let per_kit = match per_kit_input.value().trim().parse::<i32>() {
+            Ok(quantity) if quantity > 0 => quantity,
+            _ => {
+                dialog::alert(300, 300, &tr("err-kit-quantity"));
+                return;
+            }
+        };
+        // adding an item that is already in the kit changes how many it takes
+        kit.components.retain(|component| component.tag_id != item.tag_id);
+        kit.components.push(KitComponent { tag_id: item.tag_id.clone(), quantity: per_kit });
+        if let Err(e) = db_add.borrow().save_kit(&kit) {
+            dialog::alert(300, 300, &tr_args("err-kit-save", &[("error", &e.to_string())]));
+        }
+        reload_add(None);
+    });
+
+    let db_remove = inventory_db.clone();
+    let reload_remove = reload.clone();
+    let selected_remove = selected_kit.clone();
+    remove_item_btn.set_callback(move |_| {
+        let mut kit = match selected_remove() {
+            Some(kit) => kit,
+            None => return,
+        };
+        let line = component_browser.value();
+        match usize::try_from(line - 1).ok().filter(|index| *index < kit.components.len()) {
+            Some(index) => {
+                kit.components.remove(index);
+            },
+            None => {
+                dialog::alert(300, 300, &tr("err-no-item-selected"));
+                return;
+            }
+        }
+        if let Err(e) = db_remove.borrow().save_kit(&kit) {
+            dialog::alert(300, 300, &tr_args("err-kit-save", &[("error", &e.to_string())]));
+        }
+        reload_remove(None);
+    });
+
+    check_btn.set_callback(move |_| {
+        let kit = match selected_kit() {
+            Some(kit) => kit,
+            None => {
+                dialog::alert(300, 300, &tr("err-kit-select"));
+                return;
+            }
+        };
+        let count = match dialog::input(300, 300, &tr_args("kit-check-count", &[("kit", &kit.name)]), "1") {
+            Some(count) => match count.trim().parse::<i32>() {
+                Ok(count) if count > 0 => count,
+                _ => {
+                    dialog::alert(300, 300, &tr("err-kit-count"));
+                    return;
+                }
+            },
+            None => return,
+        };
+        let stock = match kits::component_items(&inventory_db.borrow(), &kit) {
+            Ok(stock) => stock,
+            Err(e) => {
+                dialog::alert(300, 300, &tr_args("err-kit-check", &[("error", &e.to_string())]));
+                return;
+            }
+        };
+        let shortages = kits::shortages(&kit, &stock, StockMode::Issue, count);
+        if shortages.is_empty() {
+            dialog::message(300, 300, &tr_args("kit-enough-stock", &[
+                ("count", &count.to_string()),
+                ("kit", &kit.name),
+                ("buildable", &kits::buildable(&kit, &stock).to_string()),
+            ]));
+        } else {
+            dialog::alert(300, 300, &kits::shortage_report(&kit, &shortages));
+        }
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows a component's item, tag, quantity per kit and quantity in stock
+fn component_line(component: &KitComponent, item: Option<&InventoryItem>) -> String {
+    match item {
+        Some(item) => format!("{}\t{}\t{}\t{}", item.name, component.tag_id, component.quantity, item.quantity),
+        None => format!("{}\t{}\t{}\t-", tr("item-not-in-inventory"), component.tag_id, component.quantity),
+    }
+}
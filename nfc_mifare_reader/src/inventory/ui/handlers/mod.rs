@@ -2,9 +2,25 @@ pub mod item_handlers;
 pub mod search_handlers;
 pub mod export_handlers;
 pub mod scan_handlers;
+pub mod trash_handlers;
+pub mod unit_handlers;
+pub mod kit_handlers;
+pub mod purchasing_handlers;
+pub mod triage_handlers;
+pub mod tag_handlers;
+pub mod dump_handlers;
+pub mod presence_handlers;
 
 // Re-export handler functions for convenience
 pub use item_handlers::*;
 pub use search_handlers::*;
 pub use export_handlers::*;
-pub use scan_handlers::*;
\ No newline at end of file
+pub use scan_handlers::*;
+pub use trash_handlers::*;
+pub use unit_handlers::*;
+pub use kit_handlers::*;
+pub use purchasing_handlers::*;
+pub use triage_handlers::*;
+pub use tag_handlers::*;
+pub use dump_handlers::*;
+pub use presence_handlers::*;
\ No newline at end of file
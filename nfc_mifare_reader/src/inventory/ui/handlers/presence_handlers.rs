@@ -0,0 +1,232 @@
+// src/inventory/ui/handlers/presence_handlers.rs
+use fltk::{
+    app,
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::Align,
+    frame::Frame,
+    input::IntInput,
+    menu::Choice,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{InventoryItem, Named, PresenceEvent};
+use crate::inventory::presence::{self, DEFAULT_ABSENT_SECS};
+use crate::inventory::ui::utils::format_timestamp;
+
+// How often the window looks for removed items, in seconds
+const CHECK_INTERVAL: f64 = 1.0;
+
+// Presence events listed in the window
+const LISTED_EVENTS: usize = 100;
+
+pub fn setup_presence_button(
+    presence_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    presence_btn.set_callback(move |_| {
+        show_presence_window(inventory_db.clone(), log_buffer_clone.clone());
+    });
+}
+
+// this watches the items at one location (a tool cabinet) while capture runs, and logs
+// an event when one is taken out of the reader's field or put back. The window isn't
+// modal so the capture window stays usable, and closing it stops monitoring.
+fn show_presence_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer) {
+    let mut win = Window::new(150, 80, 640, 520, None).with_label(&tr("presence-title"));
+
+    let mut location_choice = Choice::new(90, 10, 200, 25, None).with_label(&tr("form-location"));
+    let mut absent_input = IntInput::new(420, 10, 50, 25, None).with_label(&tr("presence-absent"));
+    absent_input.set_value(&DEFAULT_ABSENT_SECS.to_string());
+    let mut start_btn = Button::new(480, 10, 70, 25, None).with_label(&tr("presence-start"));
+    let mut stop_btn = Button::new(560, 10, 70, 25, None).with_label(&tr("presence-stop"));
+
+    let mut hint = Frame::new(10, 40, 620, 35, None);
+    hint.set_label(&tr("presence-help"));
+    hint.set_align(Align::Left | Align::Inside);
+    hint.set_label_size(11);
+
+    Frame::new(10, 80, 620, 20, None).with_label(&tr("presence-columns")).set_align(Align::Left | Align::Inside);
+    let mut item_browser = HoldBrowser::new(10, 100, 620, 200, "");
+    item_browser.set_column_widths(&[160, 260, 200]);
+    item_browser.set_column_char('\t');
+
+    Frame::new(10, 305, 620, 20, None).with_label(&tr("presence-events")).set_align(Align::Left | Align::Inside);
+    let mut event_browser = HoldBrowser::new(10, 325, 620, 150, "");
+    event_browser.set_column_widths(&[160, 100, 360]);
+    event_browser.set_column_char('\t');
+
+    let mut close_btn = Button::new(550, 485, 80, 28, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    // the locations the items are kept at
+    let locations: Vec<String> = match inventory_db.borrow().get_all_items() {
+        Ok(items) => items.iter()
+            .filter_map(|item| item.location.clone())
+            .filter(|location| !location.trim().is_empty())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect(),
+        Err(e) => {
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+            Vec::new()
+        }
+    };
+    // the first choice is every item wherever it is
+    location_choice.add_choice(&tr("presence-all-items"));
+    for location in &locations {
+        location_choice.add_choice(&location.replace('/', "\\/"));
+    }
+    location_choice.set_value(0);
+
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let item_browser = item_browser.clone();
+        let event_browser = event_browser.clone();
+        move || {
+            let mut item_browser = item_browser.clone();
+            let mut event_browser = event_browser.clone();
+            let item_line = item_browser.value();
+            item_browser.clear();
+            let now = Instant::now();
+            for watched in presence::watched_items() {
+                let status = if watched.present {
+                    tr("presence-in-place")
+                } else {
+                    tr_args("presence-gone", &[("secs", &now.duration_since(watched.last_seen).as_secs().to_string())])
+                };
+                item_browser.add(&format!("{}\t{}\t{}", watched.item_tag, watched.name, status));
+            }
+            if item_line > 0 && item_line <= item_browser.size() {
+                item_browser.select(item_line);
+            }
+
+            event_browser.clear();
+            match inventory_db.borrow().presence_events(LISTED_EVENTS) {
+                Ok(events) => {
+                    for event in &events {
+                        event_browser.add(&event_line(event));
+                    }
+                },
+                Err(e) => event_browser.add(&tr_args("err-presence-load", &[("error", &e.to_string())])),
+            }
+        }
+    };
+    reload();
+
+    // stores what the monitor noticed, while the window is open
+    let db_check = inventory_db.clone();
+    let reload_check = reload.clone();
+    let win_check = win.clone();
+    let mut log_check = log_buffer.clone();
+    app::add_timeout3(CHECK_INTERVAL, move |handle| {
+        if !win_check.shown() {
+            presence::stop_monitoring();
+            app::remove_timeout3(handle);
+            return;
+        }
+        let changes = presence::poll_changes();
+        for change in &changes {
+            let result = db_check.borrow().record_presence_event(&change.item_tag, &change.name, change.change);
+            match result {
+                Ok(event) => log_check.append(&format!("{}\n", tr_args("presence-event", &[
+                    ("name", &event.name),
+                    ("tag_id", &event.item_tag),
+                    ("change", &event.change.name()),
+                ]))),
+                Err(e) => log_check.append(&format!("{}\n", tr_args("err-presence-record", &[("error", &e.to_string())]))),
+            }
+        }
+        if presence::monitoring() {
+            reload_check();
+        }
+        app::repeat_timeout3(CHECK_INTERVAL, handle);
+    });
+
+    let db_start = inventory_db.clone();
+    let reload_start = reload.clone();
+    let mut log_start = log_buffer.clone();
+    start_btn.set_callback(move |_| {
+        let absent_secs = match absent_input.value().trim().parse::<u64>() {
+            Ok(secs) if secs > 0 => secs,
+            _ => {
+                dialog::alert(300, 300, &tr("err-presence-absent"));
+                return;
+            }
+        };
+        let location = usize::try_from(location_choice.value()).ok()
+            .and_then(|index| index.checked_sub(1))
+            .and_then(|index| locations.get(index).cloned());
+
+        let (items, aliases) = {
+            let db = db_start.borrow();
+            (db.get_all_items(), db.tag_aliases())
+        };
+        let items: Vec<InventoryItem> = match (items, aliases) {
+            (Ok(items), Ok(aliases)) => {
+                let items: Vec<InventoryItem> = items.into_iter()
+                    .filter(|item| location.is_none() || item.location == location)
+                    .collect();
+                presence::start_monitoring(&items, &aliases, Duration::from_secs(absent_secs));
+                items
+            },
+            (Err(e), _) | (_, Err(e)) => {
+                dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+                return;
+            }
+        };
+        let location = location.unwrap_or_else(|| tr("presence-every-location"));
+        log_start.append(&format!("{}\n", tr_args("log-presence-started", &[
+            ("count", &items.len().to_string()),
+            ("location", &location),
+        ])));
+        reload_start();
+    });
+
+    let reload_stop = reload.clone();
+    let mut log_stop = log_buffer;
+    stop_btn.set_callback(move |_| {
+        if presence::monitoring() {
+            presence::stop_monitoring();
+            log_stop.append(&format!("{}\n", tr("log-presence-stopped")));
+        }
+        reload_stop();
+    });
+
+    // closing the window stops monitoring, the timer notices it is hidden
+    win.set_callback(move |w| {
+        presence::stop_monitoring();
+        w.hide();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        presence::stop_monitoring();
+        win_close.hide();
+    });
+}
+
+// this shows when the change happened, which it was and the item
+fn event_line(event: &PresenceEvent) -> String {
+    format!(
+        "{}\t{}\t{} ({})",
+        format_timestamp(&event.at),
+        event.change.name(),
+        event.name,
+        event.item_tag
+    )
+}
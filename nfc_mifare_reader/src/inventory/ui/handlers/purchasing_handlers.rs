@@ -0,0 +1,440 @@
+// src/inventory/ui/handlers/purchasing_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    frame::Frame,
+    input::IntInput,
+    menu::Choice,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{InventoryItem, PoLine, PoStatus, PurchaseOrder, Supplier};
+use crate::inventory::purchasing;
+
+pub fn setup_purchasing_button(
+    purchasing_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    purchasing_btn.set_callback(move |_| {
+        show_purchasing_window(inventory_db.clone(), log_buffer_clone.clone());
+    });
+}
+
+// this lists the suppliers on the left, the orders placed with the selected one in the
+// middle and the lines of the selected order on the right
+fn show_purchasing_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer) {
+    let mut win = Window::new(100, 100, 960, 500, None).with_label(&tr("po-title"));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 260, 25, None).with_label(&tr("po-suppliers"));
+    let mut supplier_browser = HoldBrowser::new(20, 40, 260, 340, "");
+    supplier_browser.set_column_widths(&[130, 130]);
+    supplier_browser.set_column_char('\t');
+
+    Frame::new(300, 10, 220, 25, None).with_label(&tr("po-orders"));
+    let mut order_browser = HoldBrowser::new(300, 40, 220, 340, "");
+    order_browser.set_column_widths(&[110, 60, 50]);
+    order_browser.set_column_char('\t');
+
+    let mut item_choice = Choice::new(540, 10, 220, 25, None);
+    let mut ordered_input = IntInput::new(770, 10, 60, 25, None);
+    ordered_input.set_value("1");
+    ordered_input.set_tooltip(&tr("po-ordered-tip"));
+    let mut add_line_btn = Button::new(840, 10, 100, 25, None).with_label(&tr("po-add-line"));
+
+    Frame::new(540, 40, 400, 20, None).with_label(&tr("po-line-columns"));
+    let mut line_browser = HoldBrowser::new(540, 60, 400, 320, "");
+    line_browser.set_column_widths(&[170, 110, 60, 60]);
+    line_browser.set_column_char('\t');
+
+    let mut add_supplier_btn = Button::new(20, 390, 80, 30, None).with_label(&tr("btn-add"));
+    let mut link_item_btn = Button::new(105, 390, 90, 30, None).with_label(&tr("po-link-item"));
+    let mut delete_supplier_btn = Button::new(200, 390, 80, 30, None).with_label(&tr("btn-delete"));
+
+    let mut new_order_btn = Button::new(300, 390, 105, 30, None).with_label(&tr("po-new-order"));
+    let mut close_order_btn = Button::new(415, 390, 105, 30, None).with_label(&tr("po-close-reopen"));
+    let mut receive_btn = Button::new(300, 425, 105, 30, None).with_label(&tr("po-receive-into"));
+    let mut stop_receiving_btn = Button::new(415, 425, 105, 30, None).with_label(&tr("po-stop-receiving"));
+
+    let mut remove_line_btn = Button::new(540, 390, 110, 30, None).with_label(&tr("po-remove-line"));
+
+    let receiving_frame = Frame::new(540, 425, 300, 30, "");
+    let mut close_btn = Button::new(860, 460, 80, 28, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let suppliers: Rc<RefCell<Vec<Supplier>>> = Rc::new(RefCell::new(Vec::new()));
+    let orders: Rc<RefCell<Vec<PurchaseOrder>>> = Rc::new(RefCell::new(Vec::new()));
+    let items: Rc<RefCell<Vec<InventoryItem>>> = Rc::new(RefCell::new(Vec::new()));
+
+    match inventory_db.borrow().get_all_items() {
+        Ok(all_items) => *items.borrow_mut() = all_items,
+        Err(e) => dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())])),
+    }
+    for item in items.borrow().iter() {
+        item_choice.add_choice(&format!("{} ({})", item.name, item.tag_id).replace('/', "\\/"));
+    }
+    if item_choice.size() > 0 {
+        item_choice.set_value(0);
+    }
+
+    // the supplier selected on the left
+    let selected_supplier = {
+        let suppliers = suppliers.clone();
+        let supplier_browser = supplier_browser.clone();
+        move || -> Option<Supplier> {
+            let line = supplier_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| suppliers.borrow().get(index).cloned())
+        }
+    };
+
+    // the order selected in the middle
+    let selected_order = {
+        let orders = orders.clone();
+        let order_browser = order_browser.clone();
+        move || -> Option<PurchaseOrder> {
+            let line = order_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| orders.borrow().get(index).cloned())
+        }
+    };
+
+    let show_lines = {
+        let items = items.clone();
+        let line_browser = line_browser.clone();
+        let selected_order = selected_order.clone();
+        move || {
+            let mut line_browser = line_browser.clone();
+            line_browser.clear();
+            if let Some(order) = selected_order() {
+                for line in &order.lines {
+                    let item = items.borrow().iter().find(|item| item.tag_id == line.tag_id).cloned();
+                    line_browser.add(&po_line(line, item.as_ref()));
+                }
+            }
+        }
+    };
+
+    // this names the order scans are received against
+    let show_receiving = {
+        let inventory_db = inventory_db.clone();
+        move || {
+            let mut receiving_frame = receiving_frame.clone();
+            let order = purchasing::receiving_po()
+                .and_then(|po_id| inventory_db.borrow().get_purchase_order(po_id).ok().flatten());
+            receiving_frame.set_label(&match order {
+                Some(order) => tr_args("po-receiving", &[("po", &order.number)]),
+                None => tr("po-not-receiving"),
+            });
+        }
+    };
+    show_receiving();
+
+    // this keeps the order with `select_id` selected, or the one selected before
+    let reload_orders = {
+        let inventory_db = inventory_db.clone();
+        let orders = orders.clone();
+        let order_browser = order_browser.clone();
+        let selected_supplier = selected_supplier.clone();
+        let selected_order = selected_order.clone();
+        let show_lines = show_lines.clone();
+        move |select_id: Option<i64>| {
+            let mut order_browser = order_browser.clone();
+            let keep = select_id.or_else(|| selected_order().map(|order| order.id));
+            order_browser.clear();
+            orders.borrow_mut().clear();
+            if let Some(supplier) = selected_supplier() {
+                match inventory_db.borrow().purchase_orders() {
+                    Ok(all_orders) => *orders.borrow_mut() = all_orders.into_iter()
+                        .filter(|order| order.supplier_id == supplier.id)
+                        .collect(),
+                    Err(e) => dialog::alert(300, 300, &tr_args("err-po-load-orders", &[("error", &e.to_string())])),
+                }
+            }
+            for order in orders.borrow().iter() {
+                order_browser.add(&order_line(order));
+            }
+            let line = orders.borrow().iter()
+                .position(|order| Some(order.id) == keep)
+                .map(|index| index as i32 + 1)
+                .unwrap_or(if order_browser.size() > 0 { 1 } else { 0 });
+            if line > 0 {
+                order_browser.select(line);
+            }
+            show_lines();
+        }
+    };
+
+    // this keeps the supplier with `select_id` selected, or the one selected before
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let suppliers = suppliers.clone();
+        let supplier_browser = supplier_browser.clone();
+        let selected_supplier = selected_supplier.clone();
+        let reload_orders = reload_orders.clone();
+        move |select_id: Option<i64>| {
+            let mut supplier_browser = supplier_browser.clone();
+            let keep = select_id.or_else(|| selected_supplier().map(|supplier| supplier.id));
+            supplier_browser.clear();
+            match inventory_db.borrow().suppliers() {
+                Ok(all_suppliers) => *suppliers.borrow_mut() = all_suppliers,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-po-load-suppliers", &[("error", &e.to_string())])),
+            }
+            for supplier in suppliers.borrow().iter() {
+                supplier_browser.add(&format!(
+                    "{}\t{}",
+                    supplier.name,
+                    supplier.email.as_deref().or(supplier.phone.as_deref()).unwrap_or("")
+                ));
+            }
+            let line = suppliers.borrow().iter()
+                .position(|supplier| Some(supplier.id) == keep)
+                .map(|index| index as i32 + 1)
+                .unwrap_or(if supplier_browser.size() > 0 { 1 } else { 0 });
+            if line > 0 {
+                supplier_browser.select(line);
+            }
+            reload_orders(None);
+        }
+    };
+    reload(None);
+
+    let reload_select = reload_orders.clone();
+    supplier_browser.set_callback(move |_| reload_select(None));
+    let show_selected = show_lines.clone();
+    order_browser.set_callback(move |_| show_selected());
+
+    let db_add = inventory_db.clone();
+    let reload_add = reload.clone();
+    let mut log_add = log_buffer.clone();
+    add_supplier_btn.set_callback(move |_| {
+        let name = match dialog::input(300, 300, &tr("po-supplier-name"), "") {
+            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => return,
+        };
+        let optional = |key: &str| dialog::input(300, 300, &tr(key), "")
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        let contact = optional("po-contact");
+        let email = optional("po-email");
+        let phone = optional("po-phone");
+        match db_add.borrow().add_supplier(&name, contact.as_deref(), email.as_deref(), phone.as_deref()) {
+            Ok(id) => {
+                log_add.append(&format!("{}\n", tr_args("log-po-supplier-added", &[("supplier", &name)])));
+                reload_add(Some(id));
+            },
+            Err(e) => dialog::alert(300, 300, &tr_args("err-po-add-supplier", &[("error", &e.to_string())])),
+        }
+    });
+
+    let db_link = inventory_db.clone();
+    let selected_link = selected_supplier.clone();
+    let items_link = items.clone();
+    let item_choice_link = item_choice.clone();
+    let mut log_link = log_buffer.clone();
+    link_item_btn.set_callback(move |_| {
+        let supplier = match selected_link() {
+            Some(supplier) => supplier,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-no-supplier"));
+                return;
+            }
+        };
+        let item = match usize::try_from(item_choice_link.value()).ok().and_then(|index| items_link.borrow().get(index).cloned()) {
+            Some(item) => item,
+            None => {
+                dialog::alert(300, 300, &tr("err-no-item-selected"));
+                return;
+            }
+        };
+        match db_link.borrow().set_item_supplier(&item.tag_id, Some(supplier.id)) {
+            Ok(()) => log_link.append(&format!("{}\n", tr_args("log-po-item-linked", &[("item", &item.name), ("supplier", &supplier.name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-po-link-item", &[("error", &e.to_string())])),
+        }
+    });
+
+    let db_delete = inventory_db.clone();
+    let reload_delete = reload.clone();
+    let selected_delete = selected_supplier.clone();
+    let mut log_delete = log_buffer.clone();
+    delete_supplier_btn.set_callback(move |_| {
+        let supplier = match selected_delete() {
+            Some(supplier) => supplier,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-select-supplier"));
+                return;
+            }
+        };
+        let question = tr_args("po-delete-supplier-confirm", &[("supplier", &supplier.name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-delete"), "") != Some(1) {
+            return;
+        }
+        match db_delete.borrow().delete_supplier(supplier.id) {
+            Ok(true) => log_delete.append(&format!("{}\n", tr_args("log-po-supplier-deleted", &[("supplier", &supplier.name)]))),
+            Ok(false) => dialog::alert(300, 300, &tr("err-po-supplier-in-use")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-po-delete-supplier", &[("error", &e.to_string())])),
+        }
+        reload_delete(None);
+    });
+
+    let db_new = inventory_db.clone();
+    let reload_new = reload_orders.clone();
+    let selected_new = selected_supplier.clone();
+    let mut log_new = log_buffer.clone();
+    new_order_btn.set_callback(move |_| {
+        let supplier = match selected_new() {
+            Some(supplier) => supplier,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-no-supplier"));
+                return;
+            }
+        };
+        let number = match dialog::input(300, 300, &tr("po-order-number"), "") {
+            Some(number) if !number.trim().is_empty() => number.trim().to_string(),
+            _ => return,
+        };
+        match db_new.borrow().add_purchase_order(&number, supplier.id) {
+            Ok(id) => {
+                log_new.append(&format!("{}\n", tr_args("log-po-order-added", &[("po", &number), ("supplier", &supplier.name)])));
+                reload_new(Some(id));
+            },
+            Err(e) => dialog::alert(300, 300, &tr_args("err-po-add-order", &[("error", &e.to_string())])),
+        }
+    });
+
+    let db_close = inventory_db.clone();
+    let reload_close = reload_orders.clone();
+    let selected_close = selected_order.clone();
+    let show_receiving_close = show_receiving.clone();
+    close_order_btn.set_callback(move |_| {
+        let order = match selected_close() {
+            Some(order) => order,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-select-order"));
+                return;
+            }
+        };
+        let status = match order.status {
+            PoStatus::Open => PoStatus::Closed,
+            PoStatus::Closed => PoStatus::Open,
+        };
+        if let Err(e) = db_close.borrow().set_po_status(order.id, status) {
+            dialog::alert(300, 300, &tr_args("err-po-update-order", &[("error", &e.to_string())]));
+        }
+        if status == PoStatus::Closed && purchasing::receiving_po() == Some(order.id) {
+            purchasing::set_receiving_po(None);
+        }
+        show_receiving_close();
+        reload_close(None);
+    });
+
+    let selected_receive = selected_order.clone();
+    let show_receiving_receive = show_receiving.clone();
+    let mut log_receive = log_buffer.clone();
+    receive_btn.set_callback(move |_| {
+        let order = match selected_receive() {
+            Some(order) => order,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-select-order"));
+                return;
+            }
+        };
+        if order.status == PoStatus::Closed {
+            dialog::alert(300, 300, &tr("err-po-closed"));
+            return;
+        }
+        purchasing::set_receiving_po(Some(order.id));
+        log_receive.append(&format!("{}\n", tr_args("log-po-receiving", &[("po", &order.number)])));
+        show_receiving_receive();
+    });
+
+    let show_receiving_stop = show_receiving.clone();
+    stop_receiving_btn.set_callback(move |_| {
+        purchasing::set_receiving_po(None);
+        show_receiving_stop();
+    });
+
+    let db_line = inventory_db.clone();
+    let reload_line = reload_orders.clone();
+    let selected_line = selected_order.clone();
+    let items_line = items.clone();
+    add_line_btn.set_callback(move |_| {
+        let order = match selected_line() {
+            Some(order) => order,
+            None => {
+                dialog::alert(300, 300, &tr("err-po-no-order"));
+                return;
+            }
+        };
+        let item = match usize::try_from(item_choice.value()).ok().and_then(|index| items_line.borrow().get(index).cloned()) {
+            Some(item) => item,
+            None => {
+                dialog::alert(300, 300, &tr("err-no-item-selected"));
+                return;
+            }
+        };
+        let ordered = match ordered_input.value().trim().parse::<i32>() {
+            Ok(quantity) if quantity > 0 => quantity,
+            _ => {
+                dialog::alert(300, 300, &tr("err-po-quantity"));
+                return;
+            }
+        };
+        // adding an item that is already on the order changes how many were ordered
+        if let Err(e) = db_line.borrow().set_po_line(order.id, &item.tag_id, ordered) {
+            dialog::alert(300, 300, &tr_args("err-po-save-order", &[("error", &e.to_string())]));
+        }
+        reload_line(None);
+    });
+
+    let db_remove = inventory_db.clone();
+    remove_line_btn.set_callback(move |_| {
+        let order = match selected_order() {
+            Some(order) => order,
+            None => return,
+        };
+        let line = line_browser.value();
+        let tag_id = match usize::try_from(line - 1).ok().and_then(|index| order.lines.get(index)) {
+            Some(line) => line.tag_id.clone(),
+            None => {
+                dialog::alert(300, 300, &tr("err-po-select-line"));
+                return;
+            }
+        };
+        if let Err(e) = db_remove.borrow().remove_po_line(order.id, &tag_id) {
+            dialog::alert(300, 300, &tr_args("err-po-save-order", &[("error", &e.to_string())]));
+        }
+        reload_orders(None);
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows an order's number, status and how many of its lines have come in
+fn order_line(order: &PurchaseOrder) -> String {
+    let complete = order.lines.iter().filter(|line| line.received >= line.ordered).count();
+    let status = match order.status {
+        PoStatus::Open => tr("po-status-open"),
+        PoStatus::Closed => tr("po-status-closed"),
+    };
+    format!("{}\t{}\t{}/{}", order.number, status, complete, order.lines.len())
+}
+
+// this shows a line's item, tag and how many were received and ordered
+fn po_line(line: &PoLine, item: Option<&InventoryItem>) -> String {
+    let name = item.map(|item| item.name.clone()).unwrap_or_else(|| tr("item-not-in-inventory"));
+    format!("{}\t{}\t{}\t{}", name, line.tag_id, line.received, line.ordered)
+}
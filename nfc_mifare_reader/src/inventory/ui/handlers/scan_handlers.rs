@@ -7,6 +7,7 @@ use fltk::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::{tr, tr_args};
 use crate::inventory::db::InventoryDB;
 use crate::inventory::model::{InventoryItem, create_inventory_item};
 
@@ -23,37 +24,38 @@ pub fn process_scanned_tag(
             // Item exists - increment quantity
             let new_quantity = item.quantity + 1;
             if let Err(e) = inventory_db.borrow().update_quantity(tag_id, new_quantity) {
-                dialog::alert(300, 300, &format!("Error updating quantity: {}", e));
+                dialog::alert(300, 300, &tr_args("err-scan-quantity", &[("error", &e.to_string())]));
                 return;
             }
             
-            dialog::message(300, 300, &format!("Tag scanned: {}. Quantity updated to {}.", item.name, new_quantity));
+            dialog::message(300, 300, &tr_args("msg-scan-updated", &[("item", &item.name), ("quantity", &new_quantity.to_string())]));
         },
         Ok(None) => {
             // Item doesn't exist - ask to create
             if dialog::choice2(300, 300, 
-                &format!("Tag ID {} not found in inventory. Would you like to add a new item?", tag_id),
-                "No", "Yes", "") == Some(1) {
+                &tr_args("scan-add-confirm", &[("tag_id", tag_id)]),
+                &tr("btn-cancel"), &tr("inv-add"), "") == Some(1) {
                 
                 // Set current tag and prompt for details
                 *current_tag_id.borrow_mut() = Some(tag_id.to_string());
                 
                 // This would ideally open a form dialog, but for now we'll use a simple input
-                if let Some(name) = dialog::input(300, 300, "Enter item name:", "") {
+                if let Some(name) = dialog::input(300, 300, &tr("scan-item-name"), "") {
                     if !name.is_empty() {
                         // Create basic item
                         let item = create_inventory_item(tag_id, &name, None, 1, None, None);
                         
                         // Save to database
                         if let Err(e) = inventory_db.borrow().save_item(&item) {
-                            dialog::alert(300, 300, &format!("Error saving item: {}", e));
+                            dialog::alert(300, 300, &tr_args("err-inv-save", &[("error", &e.to_string())]));
                             return;
                         }
                         
                         // Add Google Drive sync if enabled
+                        #[cfg(feature = "sync")]
                         sync_to_gdrive(inventory_db);
                         
-                        dialog::message(300, 300, &format!("New item '{}' added to inventory.", name));
+                        dialog::message(300, 300, &tr_args("msg-scan-added", &[("item", &name)]));
                         
                         // Refresh the table
                         if let Ok(all_items) = inventory_db.borrow().get_all_items() {
@@ -67,11 +69,12 @@ pub fn process_scanned_tag(
             }
         },
         Err(e) => {
-            dialog::alert(300, 300, &format!("Error checking inventory: {}", e));
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
         }
     }
 }
 
+#[cfg(feature = "sync")]
 fn sync_to_gdrive(inventory_db: &Rc<RefCell<InventoryDB>>) {
     // Add Google Drive sync if enabled
     // Update APP_CONFIG access depending on your final solution
@@ -81,8 +84,8 @@ fn sync_to_gdrive(inventory_db: &Rc<RefCell<InventoryDB>>) {
             use crate::sync::gdrive_sync::GDriveSync;
             let gdrive_sync = GDriveSync::new(&config.gdrive_sync_folder);
             match gdrive_sync.export_database(&inventory_db.borrow()) {
-                Ok(_) => println!("Automatically synced database to Google Drive"),
-                Err(e) => println!("Error auto-syncing to Google Drive: {}", e)
+                Ok(_) => println!("{}", tr("log-gdrive-auto-synced")),
+                Err(e) => println!("{}", tr_args("err-gdrive-auto-sync", &[("error", &e.to_string())]))
             }
         }
     }
@@ -10,6 +10,7 @@ use fltk::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::{tr, tr_args};
 use crate::inventory::model::InventoryItem;
 use crate::inventory::db::InventoryDB;
 
@@ -36,7 +37,7 @@ pub fn setup_search_button(
                 let count = items_clone.borrow().len();
                 table_clone.borrow_mut().set_rows(count as i32);
                 table_clone.borrow_mut().redraw();
-                log_buffer_clone.append("Showing all items\n");
+                log_buffer_clone.append(&format!("{}\n", tr("log-search-all")));
             }
         } else {
             // Search for items
@@ -46,10 +47,10 @@ pub fn setup_search_button(
                     let count = items_clone.borrow().len();
                     table_clone.borrow_mut().set_rows(count as i32);
                     table_clone.borrow_mut().redraw();
-                    log_buffer_clone.append(&format!("Found {} items matching '{}'\n", count, query));
+                    log_buffer_clone.append(&format!("{}\n", tr_args("log-search-found", &[("count", &count.to_string()), ("query", &query)])));
                 },
                 Err(e) => {
-                    dialog::alert(300, 300, &format!("Error searching: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-search", &[("error", &e.to_string())]));
                 }
             }
         }
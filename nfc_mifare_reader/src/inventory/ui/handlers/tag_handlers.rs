@@ -0,0 +1,205 @@
+// src/inventory/ui/handlers/tag_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    frame::Frame,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{InventoryItem, ItemTag};
+use crate::inventory::ui::utils::{format_timestamp, tag_in_use};
+
+pub fn setup_tags_button(
+    tags_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>,
+    current_tag_id: Rc<RefCell<Option<String>>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    tags_btn.set_callback(move |_| {
+        let tag_id = match current_tag_id.borrow().clone() {
+            Some(tag_id) => tag_id,
+            None => {
+                dialog::alert(300, 300, &tr("err-select-item-first"));
+                return;
+            }
+        };
+        // bind the lookup first so the database borrow ends before any dialog runs
+        let item = inventory_db.borrow().get_item(&tag_id);
+        match item {
+            Ok(Some(item)) => show_tags_window(inventory_db.clone(), log_buffer_clone.clone(), item),
+            Ok(None) => dialog::alert(300, 300, &tr("err-item-gone")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-load-item", &[("error", &e.to_string())])),
+        }
+    });
+}
+
+// this lists every tag the item has had, to give it a new one (re-tagging after a tag
+// was lost or damaged) or deactivate one
+fn show_tags_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer, item: InventoryItem) {
+    let mut win = Window::new(200, 100, 600, 400, None).with_label(&tr_args("tags-title", &[("item", &item.name)]));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 560, 25, None).with_label(&tr("tags-columns"));
+    let mut tag_browser = HoldBrowser::new(20, 40, 560, 300, "");
+    tag_browser.set_column_widths(&[160, 150, 250]);
+    tag_browser.set_column_char('\t');
+
+    let mut associate_btn = Button::new(20, 355, 170, 30, None).with_label(&tr("tags-associate"));
+    let mut deactivate_btn = Button::new(200, 355, 130, 30, None).with_label(&tr("tags-deactivate"));
+    let mut close_btn = Button::new(500, 355, 80, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let tags: Rc<RefCell<Vec<ItemTag>>> = Rc::new(RefCell::new(Vec::new()));
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let tags = tags.clone();
+        let tag_browser = tag_browser.clone();
+        let item = item.clone();
+        move || {
+            let mut tag_browser = tag_browser.clone();
+            tag_browser.clear();
+            match inventory_db.borrow().tags_of_item(&item) {
+                Ok(item_tags) => *tags.borrow_mut() = item_tags,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-tags-load", &[("error", &e.to_string())])),
+            }
+            for tag in tags.borrow().iter() {
+                tag_browser.add(&tag_line(tag));
+            }
+        }
+    };
+    reload();
+
+    // the tag selected in the list
+    let selected = {
+        let tags = tags.clone();
+        let tag_browser = tag_browser.clone();
+        move || -> Option<ItemTag> {
+            let line = tag_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| tags.borrow().get(index).cloned())
+        }
+    };
+
+    let db_associate = inventory_db.clone();
+    let reload_associate = reload.clone();
+    let selected_associate = selected.clone();
+    let mut log_associate = log_buffer.clone();
+    let item_associate = item.clone();
+    associate_btn.set_callback(move |_| {
+        let new_tag = match ask_new_tag(&db_associate.borrow(), &item_associate) {
+            Some(new_tag) => new_tag,
+            None => return,
+        };
+
+        // a replacement takes the lost or damaged tag off the item
+        let active: Vec<ItemTag> = tags.borrow().iter().filter(|tag| tag.active()).cloned().collect();
+        let question = tr_args("tags-replace-confirm", &[("tag_id", &new_tag)]);
+        let replaced = if active.is_empty() || dialog::choice2(300, 300, &question, &tr("tags-add-it"), &tr("tags-replace-it"), "") != Some(1) {
+            None
+        } else {
+            match selected_associate().filter(ItemTag::active) {
+                Some(tag) => Some(tag),
+                None if active.len() == 1 => Some(active[0].clone()),
+                None => {
+                    dialog::alert(300, 300, &tr("err-tags-select-replaced"));
+                    return;
+                }
+            }
+        };
+        let note = match &replaced {
+            Some(old) => match dialog::input(300, 300, &tr_args("tags-why-removed", &[("tag_id", &old.tag_id)]), &tr("tags-lost")) {
+                Some(note) => Some(note.trim().to_string()).filter(|note| !note.is_empty()),
+                None => return,
+            },
+            None => None,
+        };
+
+        let deactivate: Vec<String> = replaced.iter().map(|tag| tag.tag_id.clone()).collect();
+        let result = db_associate.borrow().associate_tag(&item_associate.tag_id, &new_tag, &deactivate, note.as_deref());
+        match result {
+            Ok(true) => {
+                log_associate.append(&format!("{}\n", tr_args("log-tags-added", &[("tag_id", &new_tag), ("item", &item_associate.name)])));
+                if let Some(old) = &replaced {
+                    log_associate.append(&format!("{}\n", tr_args("log-tags-deactivated", &[("tag_id", &old.tag_id)])));
+                }
+            },
+            Ok(false) => dialog::alert(300, 300, &tr_args("err-tags-taken", &[("tag_id", &new_tag)])),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-tag-add", &[("error", &e.to_string())])),
+        }
+        reload_associate();
+    });
+
+    let db_deactivate = inventory_db.clone();
+    let reload_deactivate = reload.clone();
+    let mut log_deactivate = log_buffer;
+    let item_deactivate = item;
+    deactivate_btn.set_callback(move |_| {
+        let tag = match selected() {
+            Some(tag) if tag.active() => tag,
+            Some(_) => {
+                dialog::alert(300, 300, &tr("err-tags-already-deactivated"));
+                return;
+            }
+            None => {
+                dialog::alert(300, 300, &tr("err-tags-select"));
+                return;
+            }
+        };
+        let prompt = tr_args("tags-deactivate-confirm", &[("tag_id", &tag.tag_id), ("item", &item_deactivate.name)]);
+        let note = match dialog::input(300, 300, &prompt, "") {
+            Some(note) => Some(note.trim().to_string()).filter(|note| !note.is_empty()),
+            None => return,
+        };
+
+        let result = db_deactivate.borrow().deactivate_tag(&item_deactivate.tag_id, &tag.tag_id, note.as_deref());
+        match result {
+            Ok(true) => log_deactivate.append(&format!("{}\n", tr_args("log-tags-deactivated", &[("tag_id", &tag.tag_id)]))),
+            Ok(false) => dialog::alert(300, 300, &tr("err-tags-inactive")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-tags-deactivate", &[("error", &e.to_string())])),
+        }
+        reload_deactivate();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this asks for the new tag until it is one no item, unit or kit has, None if cancelled
+fn ask_new_tag(db: &InventoryDB, item: &InventoryItem) -> Option<String> {
+    let prompt = tr_args("tags-new-tag", &[("item", &item.name)]);
+    loop {
+        let input = dialog::input(300, 300, &prompt, "")?;
+        let tag_id: String = input.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase();
+        if tag_id.is_empty() || !tag_id.chars().all(|c| c.is_ascii_hexdigit()) {
+            dialog::alert(300, 300, &tr("err-tags-hex"));
+            continue;
+        }
+        match tag_in_use(db, &tag_id) {
+            Some(message) => dialog::alert(300, 300, &message),
+            None => return Some(tag_id),
+        }
+    }
+}
+
+// this shows the tag, when it was added, and whether it is active or since when it isn't
+fn tag_line(tag: &ItemTag) -> String {
+    let status = match (&tag.removed_at, &tag.note) {
+        (None, _) if tag.tag_id == tag.item_tag => tr("tags-active-own"),
+        (None, _) => tr("tags-active"),
+        (Some(removed_at), Some(note)) => tr_args("tags-deactivated-note", &[("time", &format_timestamp(removed_at)), ("note", note)]),
+        (Some(removed_at), None) => tr_args("tags-deactivated", &[("time", &format_timestamp(removed_at))]),
+    };
+    format!("{}\t{}\t{}", tag.tag_id, format_timestamp(&tag.added_at), status)
+}
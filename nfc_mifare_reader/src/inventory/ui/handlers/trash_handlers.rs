@@ -0,0 +1,173 @@
+// src/inventory/ui/handlers/trash_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, FrameType},
+    frame::Frame,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::TrashedItem;
+
+pub fn setup_trash_button(
+    trash_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>,
+    refresh_btn: Button
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    trash_btn.set_callback(move |_| {
+        show_trash_window(inventory_db.clone(), log_buffer_clone.clone(), refresh_btn.clone());
+    });
+}
+
+// this lists the deleted items, the most recent first, to restore them or delete them for good
+fn show_trash_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer, refresh_btn: Button) {
+    let mut win = Window::new(200, 100, 600, 420, None).with_label(&tr("trash-title"));
+    win.make_modal(true);
+
+    let retention = crate::config::APP_CONFIG.lock()
+        .map(|config| config.trash_retention_days)
+        .unwrap_or(0);
+    let help_text = if retention == 0 {
+        tr("trash-help")
+    } else {
+        tr_args("trash-help-days", &[("days", &retention.to_string())])
+    };
+    let mut help = Frame::new(20, 10, 560, 25, None).with_label(&help_text);
+    help.set_align(Align::Left | Align::Inside);
+    help.set_frame(FrameType::NoBox);
+
+    let mut trash_browser = HoldBrowser::new(20, 40, 560, 320, "");
+    trash_browser.set_column_widths(&[150, 130, 280]);
+    trash_browser.set_column_char('\t');
+
+    let mut restore_btn = Button::new(20, 375, 100, 30, None).with_label(&tr("btn-restore"));
+    let mut delete_btn = Button::new(130, 375, 130, 30, None).with_label(&tr("trash-delete"));
+    let mut empty_btn = Button::new(270, 375, 110, 30, None).with_label(&tr("trash-empty"));
+    let mut close_btn = Button::new(480, 375, 100, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let trashed = Rc::new(RefCell::new(Vec::new()));
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let trashed = trashed.clone();
+        let trash_browser = trash_browser.clone();
+        move || {
+            let mut trash_browser = trash_browser.clone();
+            trash_browser.clear();
+            match inventory_db.borrow().trashed_items() {
+                Ok(items) => *trashed.borrow_mut() = items,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-trash-load", &[("error", &e.to_string())])),
+            }
+            for entry in trashed.borrow().iter() {
+                trash_browser.add(&trash_line(entry));
+            }
+            if trash_browser.size() > 0 {
+                trash_browser.select(1);
+            }
+        }
+    };
+    reload();
+
+    // the trashed item selected in the list
+    let selected = {
+        let trashed = trashed.clone();
+        let trash_browser = trash_browser.clone();
+        move || -> Option<TrashedItem> {
+            let line = trash_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| trashed.borrow().get(index).cloned())
+        }
+    };
+
+    let db_restore = inventory_db.clone();
+    let reload_restore = reload.clone();
+    let selected_restore = selected.clone();
+    let mut log_restore = log_buffer.clone();
+    let mut refresh_restore = refresh_btn.clone();
+    restore_btn.set_callback(move |_| {
+        let entry = match selected_restore() {
+            Some(entry) => entry,
+            None => {
+                dialog::alert(300, 300, &tr("err-trash-select-restore"));
+                return;
+            }
+        };
+        if let Ok(Some(existing)) = db_restore.borrow().get_item(&entry.item.tag_id) {
+            dialog::alert(300, 300, &tr_args("err-trash-tag-taken", &[("tag_id", &entry.item.tag_id), ("item", &existing.name)]));
+            return;
+        }
+
+        match db_restore.borrow().restore_trashed(entry.id) {
+            Ok(true) => {
+                log_restore.append(&format!("{}\n", tr_args("log-trash-restored", &[("item", &entry.item.name)])));
+                refresh_restore.do_callback();
+            },
+            Ok(false) => dialog::alert(300, 300, &tr("err-trash-gone")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-trash-restore", &[("error", &e.to_string())])),
+        }
+        reload_restore();
+    });
+
+    let db_delete = inventory_db.clone();
+    let reload_delete = reload.clone();
+    let mut log_delete = log_buffer.clone();
+    delete_btn.set_callback(move |_| {
+        let entry = match selected() {
+            Some(entry) => entry,
+            None => {
+                dialog::alert(300, 300, &tr("err-trash-select-delete"));
+                return;
+            }
+        };
+        let question = tr_args("trash-delete-confirm", &[("item", &entry.item.name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("trash-delete"), "") != Some(1) {
+            return;
+        }
+
+        match db_delete.borrow().delete_trashed(entry.id) {
+            Ok(_) => log_delete.append(&format!("{}\n", tr_args("log-trash-deleted", &[("item", &entry.item.name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-trash-delete", &[("error", &e.to_string())])),
+        }
+        reload_delete();
+    });
+
+    let mut log_empty = log_buffer;
+    empty_btn.set_callback(move |_| {
+        let count = trashed.borrow().len();
+        if count == 0 {
+            return;
+        }
+        let question = tr_args("trash-empty-confirm", &[("count", &count.to_string())]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("trash-empty"), "") != Some(1) {
+            return;
+        }
+
+        match inventory_db.borrow().empty_trash() {
+            Ok(deleted) => log_empty.append(&format!("{}\n", tr_args("log-trash-emptied", &[("count", &deleted.to_string())]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-trash-empty", &[("error", &e.to_string())])),
+        }
+        reload();
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows when the item was deleted, its tag and its name with the quantity
+fn trash_line(entry: &TrashedItem) -> String {
+    let deleted_at = entry.deleted_at.get(..19).unwrap_or(&entry.deleted_at).replace('T', " ");
+    format!("{}\t{}\t{} ({})", deleted_at, entry.item.tag_id, entry.item.name, entry.item.quantity)
+}
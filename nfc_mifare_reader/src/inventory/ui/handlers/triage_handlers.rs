@@ -0,0 +1,236 @@
+// src/inventory/ui/handlers/triage_handlers.rs
+use fltk::{
+    browser::MultiBrowser,
+    button::{Button, CheckButton},
+    dialog,
+    frame::Frame,
+    input::{Input, IntInput},
+    menu::Choice,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{create_inventory_item, InventoryItem, UnknownTag};
+use crate::inventory::ui::utils::format_timestamp;
+
+pub fn setup_unknown_tags_button(
+    unknown_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>,
+    refresh_btn: Button
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    unknown_btn.set_callback(move |_| {
+        show_unknown_tags_window(inventory_db.clone(), log_buffer_clone.clone(), refresh_btn.clone());
+    });
+}
+
+// this lists the scanned tags that aren't in the inventory, to add them as items, give
+// them to an existing item or ignore them, several at a time
+fn show_unknown_tags_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer, refresh_btn: Button) {
+    let mut win = Window::new(150, 100, 700, 460, None).with_label(&tr("triage-title"));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 560, 25, None).with_label(&tr("triage-columns"));
+    let mut show_ignored = CheckButton::new(580, 10, 110, 25, None).with_label(&tr("triage-show-ignored"));
+
+    let mut tag_browser = MultiBrowser::new(20, 40, 660, 300, "");
+    tag_browser.set_column_widths(&[180, 60, 170, 170, 80]);
+    tag_browser.set_column_char('\t');
+
+    let mut name_input = Input::new(120, 350, 250, 25, None).with_label(&tr("triage-item-name"));
+    name_input.set_tooltip(&tr("triage-item-name-tip"));
+    let mut quantity_input = IntInput::new(450, 350, 60, 25, None).with_label(&tr("triage-quantity"));
+    quantity_input.set_value("1");
+    let mut create_btn = Button::new(520, 350, 160, 25, None).with_label(&tr("triage-create"));
+
+    let mut item_choice = Choice::new(120, 385, 390, 25, None).with_label(&tr("triage-existing-item"));
+    let mut assign_btn = Button::new(520, 385, 160, 25, None).with_label(&tr("triage-assign"));
+
+    let mut ignore_btn = Button::new(20, 425, 110, 28, None).with_label(&tr("triage-ignore"));
+    let mut unignore_btn = Button::new(140, 425, 110, 28, None).with_label(&tr("triage-unignore"));
+    let mut close_btn = Button::new(600, 425, 80, 28, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let items: Rc<RefCell<Vec<InventoryItem>>> = Rc::new(RefCell::new(Vec::new()));
+    let load_items = {
+        let inventory_db = inventory_db.clone();
+        let items = items.clone();
+        let item_choice = item_choice.clone();
+        move || {
+            let mut item_choice = item_choice.clone();
+            item_choice.clear();
+            match inventory_db.borrow().get_all_items() {
+                Ok(all_items) => *items.borrow_mut() = all_items,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())])),
+            }
+            for item in items.borrow().iter() {
+                item_choice.add_choice(&format!("{} ({})", item.name, item.tag_id).replace('/', "\\/"));
+            }
+            if item_choice.size() > 0 {
+                item_choice.set_value(0);
+            }
+        }
+    };
+    load_items();
+
+    let tags: Rc<RefCell<Vec<UnknownTag>>> = Rc::new(RefCell::new(Vec::new()));
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let tags = tags.clone();
+        let tag_browser = tag_browser.clone();
+        let show_ignored = show_ignored.clone();
+        move || {
+            let mut tag_browser = tag_browser.clone();
+            tag_browser.clear();
+            match inventory_db.borrow().unknown_tags(show_ignored.is_checked()) {
+                Ok(unknown) => *tags.borrow_mut() = unknown,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-triage-load", &[("error", &e.to_string())])),
+            }
+            for tag in tags.borrow().iter() {
+                tag_browser.add(&unknown_tag_line(tag));
+            }
+        }
+    };
+    reload();
+
+    let reload_ignored = reload.clone();
+    show_ignored.set_callback(move |_| reload_ignored());
+
+    // the tags selected in the list
+    let selected = {
+        let tags = tags.clone();
+        let tag_browser = tag_browser.clone();
+        move || -> Vec<UnknownTag> {
+            tags.borrow().iter().enumerate()
+                .filter(|(index, _)| tag_browser.selected(*index as i32 + 1))
+                .map(|(_, tag)| tag.clone())
+                .collect()
+        }
+    };
+
+    let db_create = inventory_db.clone();
+    let reload_create = reload.clone();
+    let load_items_create = load_items.clone();
+    let selected_create = selected.clone();
+    let mut log_create = log_buffer.clone();
+    let mut refresh_create = refresh_btn.clone();
+    create_btn.set_callback(move |_| {
+        let chosen = selected_create();
+        if chosen.is_empty() {
+            dialog::alert(300, 300, &tr("err-triage-select-create"));
+            return;
+        }
+        let quantity = match quantity_input.value().trim().parse::<i32>() {
+            Ok(quantity) if quantity >= 0 => quantity,
+            _ => {
+                dialog::alert(300, 300, &tr("err-triage-quantity"));
+                return;
+            }
+        };
+
+        let name = name_input.value().trim().to_string();
+        let new_items: Vec<InventoryItem> = chosen.iter()
+            .map(|tag| {
+                let item_name = if name.is_empty() {
+                    tr_args("batch-item-name", &[("tag_id", &tag.tag_id)])
+                } else {
+                    name.clone()
+                };
+                create_inventory_item(&tag.tag_id, &item_name, None, quantity, None, None)
+            })
+            .collect();
+
+        match db_create.borrow().add_triaged_items(&new_items) {
+            Ok(()) => {
+                log_create.append(&format!("{}\n", tr_args("log-triage-created", &[("count", &new_items.len().to_string())])));
+                refresh_create.do_callback();
+            },
+            Err(e) => dialog::alert(300, 300, &tr_args("err-triage-create", &[("error", &e.to_string())])),
+        }
+        reload_create();
+        load_items_create();
+    });
+
+    let db_assign = inventory_db.clone();
+    let reload_assign = reload.clone();
+    let load_items_assign = load_items.clone();
+    let selected_assign = selected.clone();
+    let mut log_assign = log_buffer.clone();
+    let mut refresh_assign = refresh_btn.clone();
+    assign_btn.set_callback(move |_| {
+        let tag = match selected_assign().as_slice() {
+            [tag] => tag.clone(),
+            _ => {
+                dialog::alert(300, 300, &tr("err-triage-select-assign"));
+                return;
+            }
+        };
+        let item = match usize::try_from(item_choice.value()).ok().and_then(|index| items.borrow().get(index).cloned()) {
+            Some(item) => item,
+            None => {
+                dialog::alert(300, 300, &tr("err-no-item-selected"));
+                return;
+            }
+        };
+        let question = tr_args("triage-assign-confirm", &[("tag_id", &tag.tag_id), ("item", &item.name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("triage-assign"), "") != Some(1) {
+            return;
+        }
+
+        match db_assign.borrow().associate_tag(&item.tag_id, &tag.tag_id, &[], None) {
+            Ok(true) => {
+                log_assign.append(&format!("{}\n", tr_args("log-triage-assigned", &[("tag_id", &tag.tag_id), ("item", &item.name)])));
+                refresh_assign.do_callback();
+            },
+            Ok(false) => dialog::alert(300, 300, &tr_args("err-triage-tag-taken", &[("tag_id", &tag.tag_id)])),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-tag-add", &[("error", &e.to_string())])),
+        }
+        reload_assign();
+        load_items_assign();
+    });
+
+    for (button, ignored) in [(&mut ignore_btn, true), (&mut unignore_btn, false)] {
+        let db_ignore = inventory_db.clone();
+        let reload_ignore = reload.clone();
+        let selected_ignore = selected.clone();
+        let mut log_ignore = log_buffer.clone();
+        button.set_callback(move |_| {
+            let tag_ids: Vec<String> = selected_ignore().into_iter().map(|tag| tag.tag_id).collect();
+            if tag_ids.is_empty() {
+                return;
+            }
+            match db_ignore.borrow().set_unknown_ignored(&tag_ids, ignored) {
+                Ok(count) if ignored => log_ignore.append(&format!("{}\n", tr_args("log-triage-ignored", &[("count", &count.to_string())]))),
+                Ok(count) => log_ignore.append(&format!("{}\n", tr_args("log-triage-unignored", &[("count", &count.to_string())]))),
+                Err(e) => dialog::alert(300, 300, &tr_args("err-triage-update", &[("error", &e.to_string())])),
+            }
+            reload_ignore();
+        });
+    }
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows the tag, how often it was scanned, when first and last, and whether it is ignored
+fn unknown_tag_line(tag: &UnknownTag) -> String {
+    format!(
+        "{}\t{}\t{}\t{}\t{}",
+        tag.tag_id,
+        tag.scans,
+        format_timestamp(&tag.first_seen),
+        format_timestamp(&tag.last_seen),
+        if tag.ignored { tr("triage-ignored") } else { String::new() }
+    )
+}
@@ -0,0 +1,310 @@
+// src/inventory/ui/handlers/unit_handlers.rs
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    frame::Frame,
+    prelude::*,
+    text::TextBuffer,
+    window::Window,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{Named, ProductStock, Unit, UnitStatus};
+use crate::inventory::units;
+use crate::inventory::ui::utils::tag_in_use;
+
+pub fn setup_units_button(
+    units_btn: &mut Button,
+    log_buffer: &TextBuffer,
+    inventory_db: Rc<RefCell<InventoryDB>>
+) {
+    let log_buffer_clone = log_buffer.clone();
+
+    units_btn.set_callback(move |_| {
+        show_units_window(inventory_db.clone(), log_buffer_clone.clone());
+    });
+}
+
+// this lists the products tracked per unit on the left and the units of the selected
+// one on the right, to register units and move them between statuses
+fn show_units_window(inventory_db: Rc<RefCell<InventoryDB>>, log_buffer: TextBuffer) {
+    let mut win = Window::new(150, 100, 780, 460, None).with_label(&tr("unit-title"));
+    win.make_modal(true);
+
+    Frame::new(20, 10, 300, 25, None).with_label(&tr("unit-products"));
+    let mut product_browser = HoldBrowser::new(20, 40, 300, 340, "");
+    product_browser.set_column_widths(&[180, 40, 40, 40]);
+    product_browser.set_column_char('\t');
+
+    Frame::new(340, 10, 420, 25, None).with_label(&tr("unit-units"));
+    let mut unit_browser = HoldBrowser::new(340, 40, 420, 340, "");
+    unit_browser.set_column_widths(&[120, 100, 80, 120]);
+    unit_browser.set_column_char('\t');
+
+    let mut add_product_btn = Button::new(20, 390, 95, 30, None).with_label(&tr("unit-add-product"));
+    let mut rename_product_btn = Button::new(122, 390, 95, 30, None).with_label(&tr("unit-rename"));
+    let mut delete_product_btn = Button::new(224, 390, 96, 30, None).with_label(&tr("btn-delete"));
+
+    let mut add_unit_btn = Button::new(340, 390, 80, 30, None).with_label(&tr("unit-add-unit"));
+    let mut receive_btn = Button::new(425, 390, 80, 30, None).with_label(&tr("unit-return"));
+    let mut issue_btn = Button::new(510, 390, 80, 30, None).with_label(&tr("unit-issue"));
+    let mut retire_btn = Button::new(595, 390, 80, 30, None).with_label(&tr("unit-retire"));
+    let mut remove_unit_btn = Button::new(680, 390, 80, 30, None).with_label(&tr("btn-remove"));
+
+    let mut close_btn = Button::new(680, 425, 80, 28, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    let products: Rc<RefCell<Vec<ProductStock>>> = Rc::new(RefCell::new(Vec::new()));
+    let product_units: Rc<RefCell<Vec<Unit>>> = Rc::new(RefCell::new(Vec::new()));
+
+    // the product selected on the left
+    let selected_product = {
+        let products = products.clone();
+        let product_browser = product_browser.clone();
+        move || -> Option<ProductStock> {
+            let line = product_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| products.borrow().get(index).cloned())
+        }
+    };
+
+    // the unit selected on the right
+    let selected_unit = {
+        let product_units = product_units.clone();
+        let unit_browser = unit_browser.clone();
+        move || -> Option<Unit> {
+            let line = unit_browser.value();
+            usize::try_from(line - 1).ok().and_then(|index| product_units.borrow().get(index).cloned())
+        }
+    };
+
+    let reload_units = {
+        let inventory_db = inventory_db.clone();
+        let product_units = product_units.clone();
+        let unit_browser = unit_browser.clone();
+        let selected_product = selected_product.clone();
+        move || {
+            let mut unit_browser = unit_browser.clone();
+            unit_browser.clear();
+            product_units.borrow_mut().clear();
+            let product = match selected_product() {
+                Some(product) => product,
+                None => return,
+            };
+            match inventory_db.borrow().product_units(product.product.id) {
+                Ok(units) => *product_units.borrow_mut() = units,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-unit-load-units", &[("error", &e.to_string())])),
+            }
+            for unit in product_units.borrow().iter() {
+                unit_browser.add(&unit_line(unit));
+            }
+        }
+    };
+
+    // this keeps the selected product selected, a new one is selected by its id
+    let reload = {
+        let inventory_db = inventory_db.clone();
+        let products = products.clone();
+        let product_browser = product_browser.clone();
+        let reload_units = reload_units.clone();
+        move |select_id: Option<i64>| {
+            let mut product_browser = product_browser.clone();
+            let keep = select_id.or_else(|| {
+                let line = product_browser.value();
+                usize::try_from(line - 1).ok()
+                    .and_then(|index| products.borrow().get(index).map(|stock| stock.product.id))
+            });
+            product_browser.clear();
+            match inventory_db.borrow().product_stock() {
+                Ok(stock) => *products.borrow_mut() = stock,
+                Err(e) => dialog::alert(300, 300, &tr_args("err-unit-load-products", &[("error", &e.to_string())])),
+            }
+            for stock in products.borrow().iter() {
+                product_browser.add(&product_line(stock));
+            }
+            let line = products.borrow().iter()
+                .position(|stock| Some(stock.product.id) == keep)
+                .map(|index| index as i32 + 1)
+                .unwrap_or(if product_browser.size() > 0 { 1 } else { 0 });
+            if line > 0 {
+                product_browser.select(line);
+            }
+            reload_units();
+        }
+    };
+    reload(None);
+
+    let reload_select = reload_units.clone();
+    product_browser.set_callback(move |_| reload_select());
+
+    let db_add = inventory_db.clone();
+    let reload_add = reload.clone();
+    let mut log_add = log_buffer.clone();
+    add_product_btn.set_callback(move |_| {
+        let name = match dialog::input(300, 300, &tr("unit-product-name"), "") {
+            Some(name) if !name.trim().is_empty() => name.trim().to_string(),
+            _ => return,
+        };
+        let category = dialog::input(300, 300, &tr("unit-category"), "")
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty());
+        match db_add.borrow().add_product(&name, None, category.as_deref()) {
+            Ok(id) => {
+                log_add.append(&format!("{}\n", tr_args("log-unit-product-added", &[("product", &name)])));
+                reload_add(Some(id));
+            },
+            Err(e) => dialog::alert(300, 300, &tr_args("err-unit-add-product", &[("error", &e.to_string())])),
+        }
+    });
+
+    let db_rename = inventory_db.clone();
+    let reload_rename = reload.clone();
+    let selected_rename = selected_product.clone();
+    rename_product_btn.set_callback(move |_| {
+        let mut product = match selected_rename() {
+            Some(stock) => stock.product,
+            None => {
+                dialog::alert(300, 300, &tr("err-unit-select-product"));
+                return;
+            }
+        };
+        match dialog::input(300, 300, &tr("unit-product-name"), &product.name) {
+            Some(name) if !name.trim().is_empty() => product.name = name.trim().to_string(),
+            _ => return,
+        }
+        if let Err(e) = db_rename.borrow().update_product(&product) {
+            dialog::alert(300, 300, &tr_args("err-unit-rename-product", &[("error", &e.to_string())]));
+        }
+        reload_rename(None);
+    });
+
+    let db_delete = inventory_db.clone();
+    let reload_delete = reload.clone();
+    let selected_delete = selected_product.clone();
+    let mut log_delete = log_buffer.clone();
+    delete_product_btn.set_callback(move |_| {
+        let stock = match selected_delete() {
+            Some(stock) => stock,
+            None => {
+                dialog::alert(300, 300, &tr("err-unit-select-product"));
+                return;
+            }
+        };
+        let question = tr_args("unit-delete-product-confirm", &[("product", &stock.product.name)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-delete"), "") != Some(1) {
+            return;
+        }
+        match db_delete.borrow().delete_product(stock.product.id) {
+            Ok(true) => log_delete.append(&format!("{}\n", tr_args("log-unit-product-deleted", &[("product", &stock.product.name)]))),
+            Ok(false) => dialog::alert(300, 300, &tr("err-unit-product-in-use")),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-unit-delete-product", &[("error", &e.to_string())])),
+        }
+        reload_delete(None);
+    });
+
+    let db_unit = inventory_db.clone();
+    let reload_unit = reload.clone();
+    let selected_unit_product = selected_product.clone();
+    let mut log_unit = log_buffer.clone();
+    add_unit_btn.set_callback(move |_| {
+        let product = match selected_unit_product() {
+            Some(stock) => stock.product,
+            None => {
+                dialog::alert(300, 300, &tr("err-unit-no-product"));
+                return;
+            }
+        };
+        let tag_id = match dialog::input(300, 300, &tr("unit-tag-id"), "") {
+            Some(tag_id) if !tag_id.trim().is_empty() => tag_id.trim().to_uppercase(),
+            _ => return,
+        };
+        if let Some(message) = tag_in_use(&db_unit.borrow(), &tag_id) {
+            dialog::alert(300, 300, &message);
+            return;
+        }
+        let serial = dialog::input(300, 300, &tr("unit-serial"), "")
+            .map(|serial| serial.trim().to_string())
+            .filter(|serial| !serial.is_empty());
+
+        let unit = units::new_unit(&tag_id, product.id, serial.as_deref(), None);
+        match db_unit.borrow().save_unit(&unit) {
+            Ok(()) => log_unit.append(&format!("{}\n", tr_args("log-unit-added", &[("tag_id", &tag_id), ("product", &product.name)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-unit-add", &[("error", &e.to_string())])),
+        }
+        reload_unit(None);
+    });
+
+    for (button, status) in [
+        (&mut receive_btn, UnitStatus::InStock),
+        (&mut issue_btn, UnitStatus::Issued),
+        (&mut retire_btn, UnitStatus::Retired),
+    ] {
+        let db_status = inventory_db.clone();
+        let reload_status = reload.clone();
+        let selected_status = selected_unit.clone();
+        let mut log_status = log_buffer.clone();
+        button.set_callback(move |_| {
+            let unit = match selected_status() {
+                Some(unit) => unit,
+                None => {
+                    dialog::alert(300, 300, &tr("err-unit-select"));
+                    return;
+                }
+            };
+            if unit.status == status {
+                return;
+            }
+            match db_status.borrow().set_unit_status(&unit.tag_id, status) {
+                Ok(_) => log_status.append(&format!("{}\n", tr_args("log-unit-status", &[("tag_id", &unit.tag_id), ("status", &status.name())]))),
+                Err(e) => dialog::alert(300, 300, &tr_args("err-unit-update", &[("error", &e.to_string())])),
+            }
+            reload_status(None);
+        });
+    }
+
+    let mut log_remove = log_buffer;
+    remove_unit_btn.set_callback(move |_| {
+        let unit = match selected_unit() {
+            Some(unit) => unit,
+            None => {
+                dialog::alert(300, 300, &tr("err-unit-select"));
+                return;
+            }
+        };
+        let question = tr_args("unit-remove-confirm", &[("tag_id", &unit.tag_id)]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("btn-remove"), "") != Some(1) {
+            return;
+        }
+        match inventory_db.borrow().delete_unit(&unit.tag_id) {
+            Ok(_) => log_remove.append(&format!("{}\n", tr_args("log-unit-removed", &[("tag_id", &unit.tag_id)]))),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-unit-remove", &[("error", &e.to_string())])),
+        }
+        reload(None);
+    });
+
+    let mut win_close = win.clone();
+    close_btn.set_callback(move |_| {
+        win_close.hide();
+    });
+}
+
+// this shows a product's name and its units in stock, issued and retired
+fn product_line(stock: &ProductStock) -> String {
+    format!("{}\t{}\t{}\t{}", stock.product.name, stock.in_stock, stock.issued, stock.retired)
+}
+
+// this shows a unit's serial number, tag, status and location
+fn unit_line(unit: &Unit) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        unit.serial.as_deref().unwrap_or("-"),
+        unit.tag_id,
+        unit.status.name(),
+        unit.location.as_deref().unwrap_or("")
+    )
+}
@@ -2,9 +2,9 @@
 use fltk::{
     app,
     button::Button,
-    enums::{Align, FrameType, Font, LabelType},
+    enums::{Align, FrameType, Font},
     frame::Frame,
-    group::{Group, Tabs},
+    group::{Flex, FlexType, Group, Tabs},
     input::Input,
     prelude::*,
     table::Table,
@@ -13,6 +13,8 @@ use fltk::{
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use crate::i18n::{tr, tr_args};
+use crate::ui::layout;
 use crate::inventory::db::InventoryDB;
 use crate::inventory::model::InventoryItem;
 use crate::inventory::ui::components::form::ItemForm;
@@ -24,6 +26,14 @@ use crate::inventory::ui::handlers::{
     },
     search_handlers::setup_search_button,
     export_handlers::setup_export_button,
+    trash_handlers::setup_trash_button,
+    unit_handlers::setup_units_button,
+    kit_handlers::setup_kits_button,
+    purchasing_handlers::setup_purchasing_button,
+    triage_handlers::setup_unknown_tags_button,
+    tag_handlers::setup_tags_button,
+    dump_handlers::setup_dumps_button,
+    presence_handlers::setup_presence_button,
     scan_handlers::process_scanned_tag
 };
 
@@ -33,6 +43,8 @@ pub struct InventoryUI {
     item_table: Rc<RefCell<Table>>,
     items: Rc<RefCell<Vec<InventoryItem>>>,
     current_tag_id: Rc<RefCell<Option<String>>>,
+    log_buffer: TextBuffer,
+    refresh_btn: Rc<RefCell<Option<Button>>>,
 }
 
 impl InventoryUI {
@@ -54,62 +66,92 @@ impl InventoryUI {
             item_table,
             items,
             current_tag_id,
+            log_buffer: TextBuffer::default(),
+            refresh_btn: Rc::new(RefCell::new(None)),
         })
     }
     
     // Create the inventory tab in the UI
     pub fn create_tab(&self, tabs: &mut Tabs) {
-        let inventory_tab = Group::new(0, 50, 800, 550, "Inventory");
+        let mut inventory_tab = layout::tab_page(tabs, &tr("tab-inventory"));
+        
+        // Table on the left, item details on the right, sharing the width
+        let mut panels = Flex::default();
+        panels.set_type(FlexType::Row);
+        panels.set_pad(layout::MARGIN);
         
         // Create the left panel for the table
-        let table_panel = Group::new(10, 60, 380, 530, "");
+        let mut table_panel = layout::column();
         
         // Search input
-        let search_input = Input::new(10, 60, 280, 30, "Search:");
-        let mut search_btn = Button::new(300, 60, 80, 30, "Search");
+        let mut search_row = layout::fixed_row(&mut table_panel, layout::ROW_HEIGHT);
+        let search_label = Frame::default().with_label(&tr("inv-search-label"));
+        search_row.fixed(&search_label, 60);
+        let search_input = Input::default();
+        let mut search_btn = Button::default().with_label(&tr("inv-search"));
+        search_row.fixed(&search_btn, 80);
+        search_row.end();
         
         // Create a table to display inventory items
-        let mut table = Table::new(10, 100, 380, 350, "");
+        let mut table = Table::default();
         
         // Store the table in our struct
         *self.item_table.borrow_mut() = table.clone();
         
         // Action buttons
-        let mut refresh_btn = Button::new(10, 460, 120, 30, "Refresh List");
-        let mut add_btn = Button::new(140, 460, 120, 30, "Add Item");
-        let mut export_btn = Button::new(270, 460, 120, 30, "Export");
-        
-        let mut stats_frame = Frame::new(10, 500, 380, 80, "Inventory Stats");
-        stats_frame.set_frame(FrameType::EngravedBox);
-        stats_frame.set_label_type(LabelType::None);
+        let mut table_buttons = layout::fixed_row(&mut table_panel, layout::ROW_HEIGHT);
+        let mut refresh_btn = Button::default().with_label(&tr("inv-refresh"));
+        let mut add_btn = Button::default().with_label(&tr("inv-add"));
+        let mut export_btn = Button::default().with_label(&tr("inv-export"));
+        let mut trash_btn = Button::default().with_label(&tr("trash-title"));
+        let mut units_btn = Button::default().with_label(&tr("unit-units"));
+        let mut kits_btn = Button::default().with_label(&tr("kit-title"));
+        let mut purchasing_btn = Button::default().with_label(&tr("inv-purchasing"));
+        let mut unknown_btn = Button::default().with_label(&tr("triage-title"));
+        let mut presence_btn = Button::default().with_label(&tr("inv-presence"));
+        table_buttons.end();
         
-        let mut stats_text = Frame::new(20, 510, 360, 60, "");
+        // Inventory stats, filled in on refresh
+        let mut stats_text = Frame::default();
+        stats_text.set_frame(FrameType::EngravedBox);
         stats_text.set_align(Align::TopLeft | Align::Inside);
+        table_panel.fixed(&stats_text, 80);
         
         table_panel.end();
         
         // Create the right panel for item details
-        let detail_panel = Group::new(400, 60, 390, 530, "");
+        let mut detail_panel = layout::column();
         
-        let mut detail_title = Frame::new(400, 60, 390, 30, "Item Details");
+        let mut detail_title = Frame::default().with_label(&tr("inv-details"));
         detail_title.set_label_font(Font::HelveticaBold);
         detail_title.set_label_size(18);
+        detail_panel.fixed(&detail_title, layout::ROW_HEIGHT);
         
-        // Create item form
-        let mut item_form = ItemForm::new(400, 100, 390, 260);
+        // Create item form, the panel resizes its group and the description stretches with it
+        let mut form_group = Group::new(0, 0, 390, ItemForm::HEIGHT, None);
+        let mut item_form = ItemForm::new(0, 0, 390, ItemForm::HEIGHT);
+        form_group.end();
+        form_group.resizable(&item_form.description_input);
         
         // Action buttons
-        let mut save_btn = Button::new(400, 370, 120, 30, "Save Changes");
-        let mut delete_btn = Button::new(530, 370, 120, 30, "Delete Item");
-        let mut clear_btn = Button::new(660, 370, 120, 30, "Clear Form");
+        let mut detail_buttons = layout::fixed_row(&mut detail_panel, layout::ROW_HEIGHT);
+        let mut save_btn = Button::default().with_label(&tr("inv-save"));
+        let mut delete_btn = Button::default().with_label(&tr("inv-delete"));
+        let mut clear_btn = Button::default().with_label(&tr("inv-clear"));
+        let mut tags_btn = Button::default().with_label(&tr("inv-tags"));
+        let mut dumps_btn = Button::default().with_label(&tr("inv-dumps"));
+        detail_buttons.end();
         
         // Event log
-        let _log_frame = Frame::new(400, 510, 390, 30, "Event Log");
-        let mut log_display = TextDisplay::new(400, 540, 390, 40, "");
-        let mut log_buffer = TextBuffer::default();
+        let log_frame = Frame::default().with_label(&tr("inv-log"));
+        detail_panel.fixed(&log_frame, layout::ROW_HEIGHT);
+        let mut log_display = TextDisplay::default();
+        detail_panel.fixed(&log_display, 60);
+        let mut log_buffer = self.log_buffer.clone();
         log_display.set_buffer(log_buffer.clone());
         
         detail_panel.end();
+        panels.end();
         
         // Setup table with a separate closure to avoid borrowing/moving issues
         let db_clone = self.inventory_db.clone();
@@ -128,7 +170,7 @@ impl InventoryUI {
                 item_form_clone.display_item(&item);
                 
                 // Log
-                log_buffer_clone.append(&format!("Loaded details for item: {}\n", item.name));
+                log_buffer_clone.append(&format!("{}\n", tr_args("log-inv-loaded", &[("item", &item.name)])));
             }
         });
         
@@ -177,12 +219,64 @@ impl InventoryUI {
             self.current_tag_id.clone()
         );
         
+        setup_tags_button(
+            &mut tags_btn,
+            &log_buffer,
+            self.inventory_db.clone(),
+            self.current_tag_id.clone()
+        );
+        
+        setup_dumps_button(
+            &mut dumps_btn,
+            &log_buffer,
+            self.inventory_db.clone(),
+            self.current_tag_id.clone()
+        );
+        
         setup_export_button(
             &mut export_btn,
             &log_buffer,
             self.inventory_db.clone()
         );
         
+        setup_trash_button(
+            &mut trash_btn,
+            &log_buffer,
+            self.inventory_db.clone(),
+            refresh_btn.clone()
+        );
+        
+        setup_units_button(
+            &mut units_btn,
+            &log_buffer,
+            self.inventory_db.clone()
+        );
+        
+        setup_kits_button(
+            &mut kits_btn,
+            &log_buffer,
+            self.inventory_db.clone()
+        );
+        
+        setup_purchasing_button(
+            &mut purchasing_btn,
+            &log_buffer,
+            self.inventory_db.clone()
+        );
+        
+        setup_unknown_tags_button(
+            &mut unknown_btn,
+            &log_buffer,
+            self.inventory_db.clone(),
+            refresh_btn.clone()
+        );
+        
+        setup_presence_button(
+            &mut presence_btn,
+            &log_buffer,
+            self.inventory_db.clone()
+        );
+        
         setup_search_button(
             &mut search_btn,
             &search_input,
@@ -192,6 +286,8 @@ impl InventoryUI {
             self.item_table.clone()
         );
         
+        *self.refresh_btn.borrow_mut() = Some(refresh_btn.clone());
+        
         inventory_tab.end();
         tabs.add(&inventory_tab);
         
@@ -204,6 +300,19 @@ impl InventoryUI {
         });
     }
     
+    /// Add a line to the inventory tab's event log
+    pub fn log_event(&self, message: &str) {
+        let mut log_buffer = self.log_buffer.clone();
+        log_buffer.append(&format!("{}\n", message));
+    }
+    
+    /// Reload the item list and stats, as the Refresh List button does
+    pub fn refresh(&self) {
+        if let Some(refresh_btn) = self.refresh_btn.borrow_mut().as_mut() {
+            refresh_btn.do_callback();
+        }
+    }
+    
     // Method to update inventory with a scanned tag
     pub fn process_scanned_tag(&self, tag_id: &str) {
         process_scanned_tag(
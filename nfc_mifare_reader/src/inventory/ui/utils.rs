@@ -3,6 +3,10 @@ use fltk::enums::{Align, Color, Font, FrameType};
 use fltk::menu::Choice;
 use fltk::prelude::MenuExt;
 
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::ui::theme;
+
 // Helper functions for table drawing
 pub fn draw_cell_bg(x: i32, y: i32, w: i32, h: i32, color: Color) {
     draw::push_clip(x, y, w, h);
@@ -15,7 +19,7 @@ pub trait ChoiceExt {
 impl ChoiceExt for Choice {
     fn update_categories(&mut self, categories: &[String]) {
         self.clear();
-        self.add_choice("Uncategorized");
+        self.add_choice(&tr("form-uncategorized"));
         for cat in categories {
             self.add_choice(cat);
         }
@@ -24,10 +28,11 @@ impl ChoiceExt for Choice {
 
 pub fn draw_cell_data(x: i32, y: i32, w: i32, h: i32, data: &str) {
     draw::push_clip(x, y, w, h);
-    draw::draw_box(FrameType::FlatBox, x, y, w, h, Color::White);
+    let palette = theme::palette();
+    draw::draw_box(FrameType::FlatBox, x, y, w, h, palette.background);
     
     // Text color
-    draw::set_draw_color(Color::Black);
+    draw::set_draw_color(palette.text);
     draw::set_font(Font::Helvetica, 14);
     draw::draw_text2(data, x + 5, y, w - 10, h, Align::Left);
     
@@ -41,4 +46,19 @@ pub fn format_timestamp(timestamp: &str) -> String {
     } else {
         timestamp.to_string()
     }
+}
+
+// Why a tag can't be given to a new unit, kit or item, None when no item, unit or kit has it
+pub fn tag_in_use(db: &InventoryDB, tag_id: &str) -> Option<String> {
+    match (db.get_item(tag_id), db.get_item_by_any_tag(tag_id), db.get_unit(tag_id), db.get_kit(tag_id)) {
+        (Ok(Some(item)), _, _, _) | (_, Ok(Some(item)), _, _) => {
+            Some(tr_args("err-tag-on-item", &[("tag_id", tag_id), ("item", &item.name)]))
+        },
+        (_, _, Ok(Some(_)), _) => Some(tr_args("err-tag-is-unit", &[("tag_id", tag_id)])),
+        (_, _, _, Ok(Some(kit))) => Some(tr_args("err-tag-on-kit", &[("tag_id", tag_id), ("kit", &kit.name)])),
+        (Err(e), _, _, _) | (_, Err(e), _, _) | (_, _, Err(e), _) | (_, _, _, Err(e)) => {
+            Some(tr_args("err-tag-check", &[("error", &e.to_string())]))
+        },
+        _ => None,
+    }
 }
\ No newline at end of file
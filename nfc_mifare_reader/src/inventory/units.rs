@@ -0,0 +1,73 @@
+// inventory/units.rs
+//
+// Serial-number tracking. Some stock is counted per unit rather than as a quantity on
+// one tag: each unit of a product has a tag of its own, a serial number and a status
+// (in stock, issued or retired). Scanning a unit's tag in inventory mode moves that
+// unit the way the stock mode says, the product's stock is the count of its units in
+// stock. A tag is either an item or a unit, never both.
+use crate::i18n::tr_args;
+use crate::inventory::db::InventoryDB;
+use crate::inventory::model::{generate_timestamp, Named, StockMode, Unit, UnitStatus};
+
+/// What a scan of a unit's tag did
+#[derive(Clone, Debug)]
+pub struct UnitScan {
+    pub unit: Unit,
+    pub product_name: String,
+    /// The status after the scan, None when the mode can't move the unit
+    pub new_status: Option<UnitStatus>,
+}
+
+impl UnitScan {
+    pub fn accepted(&self) -> bool {
+        self.new_status.is_some()
+    }
+
+    /// What the operator is told about the scan
+    pub fn message(&self) -> String {
+        let serial = self.unit.serial.clone().unwrap_or_else(|| self.unit.tag_id.clone());
+        match self.new_status {
+            Some(status) => tr_args("unit-scanned", &[
+                ("product", &self.product_name),
+                ("serial", &serial),
+                ("status", &status.name()),
+            ]),
+            None => tr_args("err-unit-status", &[
+                ("product", &self.product_name),
+                ("serial", &serial),
+                ("status", &self.unit.status.name()),
+            ]),
+        }
+    }
+}
+
+/// Book a scanned tag as a unit, Ok(None) when the tag isn't a unit
+pub fn scan_unit(db: &InventoryDB, tag_id: &str, mode: StockMode) -> rusqlite::Result<Option<UnitScan>> {
+    let unit = match db.get_unit(tag_id)? {
+        Some(unit) => unit,
+        None => return Ok(None),
+    };
+    let product_name = db.get_product(unit.product_id)?
+        .map(|product| product.name)
+        .unwrap_or_else(|| unit.product_id.to_string());
+
+    let new_status = unit.status.scanned(mode);
+    if let Some(status) = new_status.filter(|status| *status != unit.status) {
+        db.set_unit_status(tag_id, status)?;
+    }
+    Ok(Some(UnitScan { unit, product_name, new_status }))
+}
+
+/// A new unit of `product_id` on `tag_id`, in stock
+pub fn new_unit(tag_id: &str, product_id: i64, serial: Option<&str>, location: Option<&str>) -> Unit {
+    let now = generate_timestamp();
+    Unit {
+        tag_id: tag_id.to_string(),
+        product_id,
+        serial: serial.map(ToString::to_string),
+        status: UnitStatus::InStock,
+        location: location.map(ToString::to_string),
+        last_updated: now.clone(),
+        created_at: now,
+    }
+}
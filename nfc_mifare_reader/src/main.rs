@@ -1,175 +1,131 @@
 // main.rs
+//
+// Without the gui feature only the command line in cli.rs is built, so most of the
+// inventory and reader code, and what the modules re-export, has no caller there
+#![cfg_attr(not(feature = "gui"), allow(dead_code, unused_imports))]
+
+#[cfg(feature = "gui")]
 mod ui;
+#[cfg(not(feature = "gui"))]
+mod ui {
+    // The window settings are still part of the configuration file
+    pub mod layout;
+    pub mod theme;
+}
 mod reader;
 mod utils;
+#[cfg(feature = "gui")]
 mod batch;
 mod config;
 mod export;
 mod inventory;
+#[cfg(feature = "gui")]
 mod db_viewer;
+#[cfg(feature = "gui")]
 mod app;
+#[cfg(feature = "sync")]
 mod sync;
+mod wiegand;
+mod plugins;
+mod rules;
+mod integrations;
+mod i18n;
+mod history;
+mod signing;
+#[cfg(feature = "gui")]
+mod shutdown;
+mod cli;
 
+#[cfg(feature = "gui")]
 use fltk::{
     prelude::*,
-    window::Window,
     group::Tabs,
     enums::Align,
-    menu::{MenuBar, MenuFlag},
+    menu::MenuBar,
     dialog,
 };
+#[cfg(feature = "gui")]
 use std::cell::RefCell;
+#[cfg(feature = "gui")]
 use std::path::Path;
+#[cfg(feature = "gui")]
 use std::rc::Rc;
 
+#[cfg(feature = "gui")]
+use i18n::{tr, tr_args};
+
+/// Headless build: run the command given, there is no window to open
+#[cfg(not(feature = "gui"))]
 fn main() {
-    let app = fltk::app::App::default();
-    let mut wind = Window::new(100, 100, 800, 600, "Mifare Reader Utility");
-    
-    // Create menu
-    let mut menu = MenuBar::new(0, 0, 800, 25, "");
-    
-    // Create a channel for menu events
-    let (sender, receiver) = fltk::app::channel::<String>();
-    
-    // Create clones of the sender for each menu item before using move
-    let sender_csv = sender.clone();
-    let sender_json = sender.clone();
-    let sender_text = sender.clone();
-    let sender_log = sender.clone();
-    let sender_exit = sender.clone();
-    let sender_pref = sender.clone();
-    let sender_kb_auto = sender.clone();
-    let sender_kb_win = sender.clone();
-    let sender_kb_mac = sender.clone();
-    let sender_kb_intl = sender.clone();
-    let sender_about = sender.clone();
-    let sender_import = sender.clone();
-    let sender_view_db = sender.clone();
-    let sender_check_files = sender.clone();
-    let sender_gdrive_export = sender.clone();
-    let sender_gdrive_import = sender.clone();
-    
-    // Add menu items
-    menu.add(
-        "&File/&Export Data/as &CSV\t",
-        fltk::enums::Shortcut::Ctrl | 'e',
-        MenuFlag::Normal,
-        move |_| { sender_csv.send("export_csv".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Export Data/as &JSON\t",
-        fltk::enums::Shortcut::Ctrl | 'j',
-        MenuFlag::Normal,
-        move |_| { sender_json.send("export_json".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Export Data/as &Text\t",
-        fltk::enums::Shortcut::Ctrl | 't',
-        MenuFlag::Normal,
-        move |_| { sender_text.send("export_text".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Import Data\t",
-        fltk::enums::Shortcut::Ctrl | 'i',
-        MenuFlag::Normal,
-        move |_| { sender_import.send("import_data".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&View Database\t",
-        fltk::enums::Shortcut::Ctrl | 'd',
-        MenuFlag::Normal,
-        move |_| { sender_view_db.send("view_database".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Check Import Files\t",
-        fltk::enums::Shortcut::Ctrl | 'r',
-        MenuFlag::Normal,
-        move |_| { sender_check_files.send("check_files".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Google Drive/Export Database\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_gdrive_export.send("gdrive_export".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Google Drive/Import Database\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_gdrive_import.send("gdrive_import".to_string()); }
-    );
-    
-    menu.add(
-        "&File/&Save Log\t",
-        fltk::enums::Shortcut::Ctrl | 's',
-        MenuFlag::Normal,
-        move |_| { sender_log.send("save_log".to_string()); }
-    );
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(cli::run(&args));
+}
+
+#[cfg(feature = "gui")]
+fn main() {
+    // `--verify`, `--import` and `--sync` run without opening a window
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if cli::is_command(&args) {
+        std::process::exit(cli::run(&args));
+    }
     
-    menu.add(
-        "&File/E&xit\t",
-        fltk::enums::Shortcut::Ctrl | 'q',
-        MenuFlag::Normal,
-        move |_| { sender_exit.send("exit".to_string()); }
-    );
+    let app = fltk::app::App::default();
     
-    menu.add(
-        "&Edit/&Preferences\t",
-        fltk::enums::Shortcut::Ctrl | 'p',
-        MenuFlag::Normal,
-        move |_| { sender_pref.send("preferences".to_string()); }
-    );
+    // A panic leaves a crash report, and the outputs and database are released on any exit
+    shutdown::install_panic_hook();
+    shutdown::install_signal_handlers();
     
-    menu.add(
-        "&Edit/&Keyboard Layout/&Auto-detect\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_auto.send("kb_auto".to_string()); }
-    );
-    
-    menu.add(
-        "&Edit/&Keyboard Layout/&Windows\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_win.send("kb_windows".to_string()); }
-    );
+    // Load configuration, the language is needed before any text is shown
+    let app_config = Rc::new(RefCell::new(config::load_config()));
+    i18n::set_language(&app_config.borrow().language);
+    ui::theme::apply_theme(app_config.borrow().theme);
+    
+    // Settings that couldn't be used were put back to their defaults
+    let config_problems = config::load_problems();
+    if !config_problems.is_empty() {
+        let message = tr_args("err-config-problems", &[("problems", &config_problems.join("\n"))]);
+        println!("{}", message);
+        dialog::alert(300, 300, &message);
+    }
+    let overridden = config::file::overridden_settings();
+    if !overridden.is_empty() {
+        println!("{}", tr_args("log-config-overrides", &[("settings", &overridden.join(", "))]));
+    }
+    
+    // Saved size and position, fitted to the screen
+    let mut wind = ui::layout::create_main_window(&app_config.borrow());
+    let (width, height) = (wind.w(), wind.h());
     
-    menu.add(
-        "&Edit/&Keyboard Layout/&Mac US\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_mac.send("kb_mac_us".to_string()); }
-    );
+    // Create menu
+    let mut menu = MenuBar::new(0, 0, width, ui::layout::MENU_HEIGHT, "");
     
-    menu.add(
-        "&Edit/&Keyboard Layout/Mac &International\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_kb_intl.send("kb_mac_intl".to_string()); }
-    );
+    // Create a channel for menu events
+    let (sender, receiver) = fltk::app::channel::<app::AppEvent>();
     
-    menu.add(
-        "&Help/&About\t",
-        fltk::enums::Shortcut::None,
-        MenuFlag::Normal,
-        move |_| { sender_about.send("about".to_string()); }
-    );
+    // Menu labels come from the catalogue for the configured language
+    app::menu::add_menu_items(&mut menu, &sender);
     
     // Create tabs - positioned just below the menu bar
-    let mut tabs = Tabs::new(0, 25, 800, 575, "");
+    let mut tabs = Tabs::new(
+        0,
+        ui::layout::MENU_HEIGHT,
+        width,
+        height - ui::layout::MENU_HEIGHT - ui::layout::STATUS_HEIGHT,
+        "",
+    );
     // Make sure tabs are aligned to the top and visible
     tabs.set_tab_align(Align::Top);
     
-    // Load configuration
-    let app_config = Rc::new(RefCell::new(config::load_config()));
+    // Load UID post-processing plugins
+    let loaded_plugins = plugins::load_plugins(&app_config.borrow().plugin_directory);
+    if !loaded_plugins.is_empty() {
+        println!("{}", tr_args("log-plugins-loaded", &[("plugins", &loaded_plugins.join(", "))]));
+    }
+    
+    // Buzzer and LED scan feedback, when wired up and enabled
+    if let Err(e) = reader::feedback::configure(&app_config.borrow()) {
+        println!("{}", e);
+    }
     
     // Create shared state for keyboard layout selection
     let keyboard_layout = Rc::new(RefCell::new(app_config.borrow().default_keyboard_layout));
@@ -182,59 +138,92 @@ fn main() {
     ui::create_conversion_tab(&mut tabs, keyboard_layout.clone());
     ui::create_batch_tab(&mut tabs, keyboard_layout.clone());
     
+    // Scans are kept in the inventory database, apart from the inventory itself
+    if let Err(e) = history::open_history(inventory::DB_PATH) {
+        println!("{}", tr_args("err-history-open", &[("error", &e.to_string())]));
+    }
+    history::create_history_tab(&mut tabs);
+    
     // Try to initialize inventory tab with better error handling
-    let inventory_ui = match inventory::InventoryUI::new("inventory.db") {
+    let inventory_ui = match inventory::InventoryUI::new(inventory::DB_PATH) {
         Ok(ui) => {
-            println!("Successfully initialized inventory database");
+            println!("{}", tr("log-inventory-ready"));
             let ui_rc = Rc::new(ui);
             // Set the global inventory reference so reader.rs can access it
             reader::set_inventory_ui(&ui_rc);
             ui_rc
         },
         Err(e) => {
-            println!("Error initializing inventory database: {}", e);
-            dialog::alert(300, 300, &format!("Error initializing inventory database: {}", e));
+            let message = tr_args("err-inventory-init", &[("error", &e.to_string())]);
+            println!("{}", message);
+            dialog::alert(300, 300, &message);
             // Return early with the basic UI rather than failing completely
             tabs.end();
             // Just let FLTK handle tab selection
             fltk::app::redraw();
             wind.end();
+            wind.resizable(&tabs);
             wind.show();
             
             // Main event loop with no inventory functionality
             while app.wait() {
-                if let Some(msg) = receiver.recv() {
-                    if msg == "exit" {
+                if let Some(event) = receiver.recv() {
+                    if event == app::AppEvent::Exit {
                         wind.hide();
                         break;
                     }
                     // Handle other events...
                 }
             }
+            ui::layout::save_window_geometry(&wind, &mut app_config.borrow_mut());
+            shutdown::shut_down();
             return;
         }
     };
     
     // Create inventory tab - we reach here only if initialization succeeded
-    println!("Adding inventory tab");
+    println!("{}", tr("log-adding-inventory"));
     inventory_ui.create_tab(&mut tabs);
     
+    // A copy of the database once a day, imports take their own
+    inventory::backup::schedule_daily_backups(inventory_ui.inventory_db.clone());
+    // Deleted items past their time in the trash are purged
+    inventory::trash::schedule_trash_purge(inventory_ui.inventory_db.clone());
+    // Stock changes are pushed to the configured ERP integrations
+    integrations::schedule_pushes(inventory_ui.inventory_db.clone());
+    
+    // Files dropped into the import directory are imported as they arrive
+    #[cfg(feature = "sync")]
+    sync::watch_import_directory(&app_config.borrow(), sender);
+    
     tabs.end();
     
+    // Reader health and session statistics along the bottom of the window
+    ui::create_status_bar(
+        0,
+        height - ui::layout::STATUS_HEIGHT,
+        width,
+        ui::layout::STATUS_HEIGHT,
+        inventory::DB_PATH,
+    );
+    
     // Ensure the first tab is selected
-    println!("Setting active tab");
+    println!("{}", tr("log-setting-tab"));
     
     // Let FLTK handle default tab selection - this is more reliable
     // than trying to explicitly set it with set_value
     
     wind.end();
     
+    // Only the tabs stretch with the window, the menu and status bar keep their height
+    wind.resizable(&tabs);
+    
     // Force a redraw to ensure UI updates
     fltk::app::redraw();
     
     wind.show();
     
-    println!("Main window shown");
+    println!("{}", tr("log-window-shown"));
     
     // Create menu items for the event handler
     let menu_items = app::menu::MenuItems {
@@ -242,9 +231,12 @@ fn main() {
         config: app_config.clone(),
         card_buffer: card_data_buffer.clone(),
         inventory_ui: inventory_ui.clone(),
+        menu: menu.clone(),
+        sender,
     };
     
     // Run the event loop
+    let layout_config = app_config.clone();
     app::events::run_event_loop(
         app,
         receiver,
@@ -254,4 +246,8 @@ fn main() {
         inventory_ui,
         menu_items
     );
+    
+    // Reopen at the same place next time
+    ui::layout::save_window_geometry(&wind, &mut layout_config.borrow_mut());
+    shutdown::shut_down();
 }
\ No newline at end of file
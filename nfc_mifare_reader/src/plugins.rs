@@ -0,0 +1,277 @@
+// plugins.rs
+//
+// Dynamic library plugins for custom UID post-processing.
+//
+// Every shared library (.so) found in the plugin directory is loaded at startup.
+// The directory is ~/.config/mifare_reader/plugins (or under $XDG_CONFIG_HOME) unless
+// the settings name another absolute path. A plugin runs with the app's rights, so
+// neither the directory nor a library is loaded from when another user could have
+// put it there: it has to belong to the user running the app (or root) and be
+// writable by no one else.
+//
+// Plugins run on a thread of their own (PluginWorker), a slow plugin holds up the
+// scans behind it but never the window.
+// A plugin exports a C ABI that receives each scan as a JSON string and returns
+// the (possibly modified) scan as JSON, or NULL to leave it untouched:
+//
+//   const char *uid_plugin_name(void);                 // optional
+//   char *uid_plugin_process(const char *scan_json);   // required
+//   void uid_plugin_free(char *result);                // required, frees process() results
+//
+// The scan JSON looks like:
+//   {"raw": "...", "hex_uid": "04 A1 B2 C3", "manufacturer": "...", "format": "...", "extra": {}}
+// Entries added to "extra" are shown as additional lines under the scan.
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::os::raw::{c_char, c_void};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread;
+use once_cell::sync::Lazy;
+use serde::{Serialize, Deserialize};
+
+use crate::i18n::tr_args;
+
+/// The scan data handed to each plugin in turn
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PluginScan {
+    pub raw: String,
+    pub hex_uid: String,
+    pub manufacturer: String,
+    pub format: String,
+    #[serde(default)]
+    pub extra: BTreeMap<String, String>,
+}
+
+type NameFn = unsafe extern "C" fn() -> *const c_char;
+type ProcessFn = unsafe extern "C" fn(*const c_char) -> *mut c_char;
+type FreeFn = unsafe extern "C" fn(*mut c_char);
+
+/// A loaded plugin library
+pub struct Plugin {
+    pub name: String,
+    handle: *mut c_void,
+    process: ProcessFn,
+    free: FreeFn,
+}
+
+// The library handle is only used while holding the PLUGINS lock
+unsafe impl Send for Plugin {}
+
+impl Plugin {
+    /// Load a plugin from a shared library path
+    pub fn load(path: &Path) -> Result<Plugin, String> {
+        let c_path = CString::new(path.to_string_lossy().as_bytes())
+            .map_err(|_| "Invalid plugin path".to_string())?;
+
+        unsafe {
+            let handle = libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+            if handle.is_null() {
+                return Err(last_dl_error());
+            }
+
+            let process = libc::dlsym(handle, c"uid_plugin_process".as_ptr());
+            let free = libc::dlsym(handle, c"uid_plugin_free".as_ptr());
+            if process.is_null() || free.is_null() {
+                libc::dlclose(handle);
+                return Err("Missing uid_plugin_process or uid_plugin_free".to_string());
+            }
+
+            // Fall back to the file name when the plugin doesn't name itself
+            let name_sym = libc::dlsym(handle, c"uid_plugin_name".as_ptr());
+            let name = if name_sym.is_null() {
+                path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default()
+            } else {
+                let name_fn: NameFn = std::mem::transmute(name_sym);
+                let ptr = name_fn();
+                if ptr.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr(ptr).to_string_lossy().to_string()
+                }
+            };
+
+            Ok(Plugin {
+                name,
+                handle,
+                process: std::mem::transmute::<*mut c_void, ProcessFn>(process),
+                free: std::mem::transmute::<*mut c_void, FreeFn>(free),
+            })
+        }
+    }
+
+    /// Run the plugin on a scan, returns None if the plugin left it unchanged
+    pub fn process(&self, scan: &PluginScan) -> Result<Option<PluginScan>, String> {
+        let input = serde_json::to_string(scan).map_err(|e| e.to_string())?;
+        let c_input = CString::new(input).map_err(|e| e.to_string())?;
+
+        unsafe {
+            let result = (self.process)(c_input.as_ptr());
+            if result.is_null() {
+                return Ok(None);
+            }
+
+            let output = CStr::from_ptr(result).to_string_lossy().to_string();
+            (self.free)(result);
+
+            serde_json::from_str(&output)
+                .map(Some)
+                .map_err(|e| format!("Plugin {} returned invalid JSON: {}", self.name, e))
+        }
+    }
+}
+
+impl Drop for Plugin {
+    fn drop(&mut self) {
+        unsafe {
+            libc::dlclose(self.handle);
+        }
+    }
+}
+
+fn last_dl_error() -> String {
+    unsafe {
+        let err = libc::dlerror();
+        if err.is_null() {
+            "Unknown dlopen error".to_string()
+        } else {
+            CStr::from_ptr(err).to_string_lossy().to_string()
+        }
+    }
+}
+
+/// All plugins loaded at startup, applied in file name order
+pub static PLUGINS: Lazy<Mutex<Vec<Plugin>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// ~/.config/mifare_reader/plugins, or under $XDG_CONFIG_HOME when it is set
+pub fn default_plugin_directory() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("/etc"));
+    base.join("mifare_reader").join("plugins")
+}
+
+// Whether only the user running the app (or root) could have put `path` there: it
+// belongs to one of them and no one else can write to it. Symlinks are followed, the
+// file dlopen would load is the one checked.
+fn check_trusted(path: &Path) -> Result<(), String> {
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    let path_text = path.display().to_string();
+    let user = unsafe { libc::geteuid() };
+    if metadata.uid() != user && metadata.uid() != 0 {
+        return Err(tr_args("err-plugin-owner", &[("path", &path_text)]));
+    }
+    if metadata.mode() & 0o022 != 0 {
+        return Err(tr_args("err-plugin-writable", &[("path", &path_text)]));
+    }
+    Ok(())
+}
+
+/// Load every shared library in the plugin directory, returns the names loaded
+pub fn load_plugins(dir: &str) -> Vec<String> {
+    let mut loaded = Vec::new();
+    let dir = Path::new(dir);
+    if !dir.exists() {
+        return loaded; // No plugin directory, nothing to load
+    }
+    if !dir.is_absolute() {
+        eprintln!("{}", tr_args("err-plugin-relative", &[("path", &dir.display().to_string())]));
+        return loaded;
+    }
+    if let Err(e) = check_trusted(dir) {
+        eprintln!("{}", e);
+        return loaded;
+    }
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return loaded,
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "so"))
+        .collect();
+    paths.sort();
+
+    if let Ok(mut plugins) = PLUGINS.lock() {
+        for path in paths {
+            if let Err(e) = check_trusted(&path) {
+                eprintln!("{}", e);
+                continue;
+            }
+            match Plugin::load(&path) {
+                Ok(plugin) => {
+                    loaded.push(plugin.name.clone());
+                    plugins.push(plugin);
+                },
+                Err(e) => eprintln!("{}", tr_args("err-plugin-load", &[("path", &path.display().to_string()), ("error", &e)])),
+            }
+        }
+    }
+
+    loaded
+}
+
+/// Pass a scan through every loaded plugin, in order
+pub fn apply_plugins(scan: PluginScan) -> PluginScan {
+    let plugins = match PLUGINS.lock() {
+        Ok(plugins) => plugins,
+        Err(_) => return scan,
+    };
+
+    let mut current = scan;
+    for plugin in plugins.iter() {
+        match plugin.process(&current) {
+            Ok(Some(updated)) => current = updated,
+            Ok(None) => {},
+            Err(e) => eprintln!("{}", tr_args("err-plugin-failed", &[("plugin", &plugin.name), ("error", &e)])),
+        }
+    }
+
+    current
+}
+
+/// Runs the plugins on a thread of their own. Scans come back in the order they were
+/// submitted, each with the context sent along with it.
+pub struct PluginWorker<T> {
+    jobs: Sender<(PluginScan, T)>,
+    results: Receiver<(PluginScan, T)>,
+}
+
+impl<T: Send + 'static> PluginWorker<T> {
+    /// Start the thread, it ends once the worker is dropped and the scan in hand is done
+    pub fn start() -> Self {
+        let (jobs, pending) = mpsc::channel::<(PluginScan, T)>();
+        let (done, results) = mpsc::channel();
+        thread::spawn(move || {
+            for (scan, context) in pending {
+                if done.send((apply_plugins(scan), context)).is_err() {
+                    break;
+                }
+            }
+        });
+        PluginWorker { jobs, results }
+    }
+
+    /// Queue a scan for the plugins
+    pub fn submit(&self, scan: PluginScan, context: T) {
+        let _ = self.jobs.send((scan, context));
+    }
+
+    /// The next scan the plugins are done with, without blocking
+    pub fn try_next(&self) -> Option<(PluginScan, T)> {
+        self.results.try_recv().ok()
+    }
+}
+
+/// Plugin supplied extra fields as notes for a scan record, one per field
+pub fn extra_notes(scan: &PluginScan) -> Vec<String> {
+    scan.extra
+        .iter()
+        .map(|(key, value)| format!("{}: {}", key, value))
+        .collect()
+}
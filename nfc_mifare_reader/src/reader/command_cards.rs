@@ -0,0 +1,126 @@
+// reader/command_cards.rs
+//
+// Command cards are tags set aside in Preferences that run an action when scanned,
+// so the capture window can be driven from the reader alone. Each action also has
+// a function key for when a keyboard is at hand.
+#[cfg(feature = "gui")]
+use std::io;
+#[cfg(feature = "gui")]
+use fltk::enums::Key;
+use serde::{Serialize, Deserialize};
+
+use crate::config::APP_CONFIG;
+#[cfg(feature = "gui")]
+use crate::export::{self, ExportFormat};
+use crate::i18n::tr;
+use crate::inventory::model::StockMode;
+#[cfg(feature = "gui")]
+use super::scan_log::session_scans;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandAction {
+    ToggleInventoryMode,
+    ToggleItemForm,
+    StartStocktake,
+    FinishStocktake,
+    ExportCsv,
+    ExportJson,
+    ReceiveMode,
+    IssueMode,
+    CountMode,
+}
+
+/// Actions in the order the Preferences choice lists them
+pub const ALL_ACTIONS: [CommandAction; 9] = [
+    CommandAction::ToggleInventoryMode,
+    CommandAction::ToggleItemForm,
+    CommandAction::StartStocktake,
+    CommandAction::FinishStocktake,
+    CommandAction::ExportCsv,
+    CommandAction::ExportJson,
+    CommandAction::ReceiveMode,
+    CommandAction::IssueMode,
+    CommandAction::CountMode,
+];
+
+/// A tag that runs an action instead of being treated as a scan
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CommandCard {
+    pub tag_id: String,
+    pub action: CommandAction,
+}
+
+impl CommandAction {
+    pub fn name(&self) -> String {
+        match self {
+            CommandAction::ToggleInventoryMode => tr("cc-toggle-inventory"),
+            CommandAction::ToggleItemForm => tr("cc-toggle-form"),
+            CommandAction::StartStocktake => tr("cc-start-stocktake"),
+            CommandAction::FinishStocktake => tr("cc-finish-stocktake"),
+            CommandAction::ExportCsv => tr("cc-export-csv"),
+            CommandAction::ExportJson => tr("cc-export-json"),
+            CommandAction::ReceiveMode => tr("cc-receive-mode"),
+            CommandAction::IssueMode => tr("cc-issue-mode"),
+            CommandAction::CountMode => tr("cc-count-mode"),
+        }
+    }
+
+    pub fn index(&self) -> i32 {
+        ALL_ACTIONS.iter().position(|action| action == self).unwrap_or(0) as i32
+    }
+
+    pub fn from_index(index: i32) -> Option<CommandAction> {
+        ALL_ACTIONS.get(usize::try_from(index).ok()?).copied()
+    }
+
+    /// Function key running the action in the capture window, F2 upwards
+    #[cfg(feature = "gui")]
+    pub fn hotkey(&self) -> Key {
+        Key::from_i32(Key::F2.bits() + self.index())
+    }
+
+    /// Name of the hotkey as shown next to the action
+    pub fn hotkey_label(&self) -> String {
+        format!("F{}", 2 + self.index())
+    }
+
+    /// Scan mode the action switches the capture window to, None for the other actions
+    pub fn stock_mode(&self) -> Option<StockMode> {
+        match self {
+            CommandAction::ReceiveMode => Some(StockMode::Receive),
+            CommandAction::IssueMode => Some(StockMode::Issue),
+            CommandAction::CountMode => Some(StockMode::Count),
+            _ => None,
+        }
+    }
+}
+
+/// Action of a command card, None for an ordinary tag
+pub fn find_command(tag_id: &str) -> Option<CommandAction> {
+    let tag_id = normalize_tag_id(tag_id);
+    let config = APP_CONFIG.lock().ok()?;
+    config.command_cards
+        .iter()
+        .find(|card| normalize_tag_id(&card.tag_id) == tag_id)
+        .map(|card| card.action)
+}
+
+/// Action bound to a function key
+#[cfg(feature = "gui")]
+pub fn action_for_key(key: Key) -> Option<CommandAction> {
+    ALL_ACTIONS.iter().copied().find(|action| action.hotkey() == key)
+}
+
+/// Write the scans of this session to a timestamped file in the export folder, returns its path
+#[cfg(feature = "gui")]
+pub fn export_scans(format: ExportFormat) -> io::Result<String> {
+    let path = export::timestamped_export_path("scans", &format)?;
+    export::export_data(&session_scans(), format, &path)?;
+    Ok(path)
+}
+
+// "04 a1 b2" and "04A1B2" are the same tag
+fn normalize_tag_id(tag_id: &str) -> String {
+    tag_id.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
@@ -0,0 +1,71 @@
+// reader/cooldown.rs
+//
+// Duplicate scan suppression. A card held against the reader arrives as a stream of
+// identical scans; within the cooldown window only the first one reaches the inventory.
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use once_cell::sync::Lazy;
+
+use crate::config::APP_CONFIG;
+
+/// Seconds the same tag is ignored after a scan, unless configured otherwise
+pub const DEFAULT_COOLDOWN_SECS: u64 = 3;
+
+/// When each tag was last scanned
+pub struct ScanCooldown {
+    last_seen: HashMap<String, Instant>,
+}
+
+impl ScanCooldown {
+    pub fn new() -> Self {
+        ScanCooldown { last_seen: HashMap::new() }
+    }
+
+    /// Whether a scan of `tag_id` should be processed. Every scan restarts the tag's
+    /// window, ignored ones included, so a card stays suppressed for as long as it is held.
+    pub fn accept(&mut self, tag_id: &str, now: Instant, cooldown: Duration) -> bool {
+        self.last_seen.retain(|_, seen| now.duration_since(*seen) < cooldown);
+        self.last_seen.insert(normalize_tag_id(tag_id), now).is_none()
+    }
+
+    /// Forget every tag, the next scan of any card is processed
+    pub fn clear(&mut self) {
+        self.last_seen.clear();
+    }
+}
+
+impl Default for ScanCooldown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// "04 a1 b2" and "04A1B2" are the same tag
+fn normalize_tag_id(tag_id: &str) -> String {
+    tag_id.chars().filter(|c| !c.is_whitespace()).collect::<String>().to_uppercase()
+}
+
+static SCAN_COOLDOWN: Lazy<Mutex<ScanCooldown>> = Lazy::new(|| Mutex::new(ScanCooldown::new()));
+
+/// Check a scan against the configured cooldown, 0 seconds turns suppression off
+pub fn accept_scan(tag_id: &str) -> bool {
+    let seconds = APP_CONFIG.lock()
+        .map(|config| config.scan_cooldown_secs)
+        .unwrap_or(DEFAULT_COOLDOWN_SECS);
+    if seconds == 0 {
+        return true;
+    }
+
+    match SCAN_COOLDOWN.lock() {
+        Ok(mut cooldown) => cooldown.accept(tag_id, Instant::now(), Duration::from_secs(seconds)),
+        Err(_) => true,
+    }
+}
+
+/// Start a fresh window for every tag, used when capture starts
+pub fn reset_cooldown() {
+    if let Ok(mut cooldown) = SCAN_COOLDOWN.lock() {
+        cooldown.clear();
+    }
+}
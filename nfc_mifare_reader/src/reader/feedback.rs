@@ -0,0 +1,170 @@
+// reader/feedback.rs
+//
+// Buzzer and bi-color LED on GPIO pins, so operators know how a scan went without
+// watching the screen. Each outcome has its own pattern:
+//
+//   Success      one short beep, green
+//   Unknown tag  two short beeps, amber (red + green)
+//   Error        one long beep, red
+//
+// Patterns play on a background thread so the UI never waits on them. A new scan
+// while a pattern is playing replaces whatever was still queued.
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use rppal::gpio::{Gpio, OutputPin};
+
+use crate::config::AppConfig;
+
+/// How a scan went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanFeedback {
+    /// Tag read and, with inventory mode on, found in the inventory
+    Success,
+    /// Tag read but not in the inventory
+    UnknownTag,
+    /// Unreadable scan or a database error
+    Error,
+}
+
+/// One step of a pattern: which outputs are on, and for how long
+struct Step {
+    buzzer: bool,
+    red: bool,
+    green: bool,
+    millis: u64,
+}
+
+const fn step(buzzer: bool, red: bool, green: bool, millis: u64) -> Step {
+    Step { buzzer, red, green, millis }
+}
+
+const SUCCESS_PATTERN: [Step; 2] = [
+    step(true, false, true, 80),
+    step(false, false, true, 500),
+];
+const UNKNOWN_TAG_PATTERN: [Step; 4] = [
+    step(true, true, true, 80),
+    step(false, true, true, 80),
+    step(true, true, true, 80),
+    step(false, true, true, 500),
+];
+const ERROR_PATTERN: [Step; 2] = [
+    step(true, true, false, 600),
+    step(false, true, false, 400),
+];
+
+impl ScanFeedback {
+    fn pattern(&self) -> &'static [Step] {
+        match self {
+            ScanFeedback::Success => &SUCCESS_PATTERN,
+            ScanFeedback::UnknownTag => &UNKNOWN_TAG_PATTERN,
+            ScanFeedback::Error => &ERROR_PATTERN,
+        }
+    }
+}
+
+/// Output pins, any of them can be left unconnected
+struct Outputs {
+    buzzer: Option<OutputPin>,
+    red: Option<OutputPin>,
+    green: Option<OutputPin>,
+}
+
+fn set(pin: &mut Option<OutputPin>, on: bool) {
+    if let Some(pin) = pin {
+        if on {
+            pin.set_high();
+        } else {
+            pin.set_low();
+        }
+    }
+}
+
+impl Outputs {
+    fn open(config: &AppConfig) -> Result<Self, rppal::gpio::Error> {
+        let gpio = Gpio::new()?;
+        let open_pin = |pin: Option<u8>| -> Result<Option<OutputPin>, rppal::gpio::Error> {
+            match pin {
+                Some(pin) => Ok(Some(gpio.get(pin)?.into_output())),
+                None => Ok(None),
+            }
+        };
+        Ok(Outputs {
+            buzzer: open_pin(config.feedback_buzzer_pin)?,
+            red: open_pin(config.feedback_led_red_pin)?,
+            green: open_pin(config.feedback_led_green_pin)?,
+        })
+    }
+
+    fn show(&mut self, step: &Step) {
+        set(&mut self.buzzer, step.buzzer);
+        set(&mut self.red, step.red);
+        set(&mut self.green, step.green);
+    }
+
+    fn off(&mut self) {
+        self.show(&step(false, false, false, 0));
+    }
+}
+
+/// Channel to the thread playing patterns and the thread itself, None while feedback is off
+static FEEDBACK: Lazy<Mutex<Option<(Sender<ScanFeedback>, JoinHandle<()>)>>> = Lazy::new(|| Mutex::new(None));
+
+/// Open the configured pins and start the pattern thread, or stop it when feedback is
+/// turned off. Called at startup and whenever the preferences are saved.
+pub fn configure(config: &AppConfig) -> Result<(), String> {
+    // Dropping the old sender ends the old thread, which releases its pins
+    let mut feedback = FEEDBACK.lock().map_err(|_| "Feedback state unavailable".to_string())?;
+    *feedback = None;
+
+    if !config.feedback_enabled {
+        return Ok(());
+    }
+
+    let mut outputs = Outputs::open(config).map_err(|e| format!("Error opening feedback GPIO pins: {}", e))?;
+    outputs.off();
+
+    let (sender, receiver) = mpsc::channel::<ScanFeedback>();
+    let handle = thread::spawn(move || {
+        while let Ok(first) = receiver.recv() {
+            // Only the latest outcome matters if scans came in while a pattern played
+            let latest = receiver.try_iter().last().unwrap_or(first);
+            for step in latest.pattern() {
+                outputs.show(step);
+                thread::sleep(Duration::from_millis(step.millis));
+            }
+            outputs.off();
+        }
+        outputs.off();
+    });
+
+    *feedback = Some((sender, handle));
+    Ok(())
+}
+
+/// Play the pattern for a scan outcome, does nothing while feedback is off
+pub fn signal(outcome: ScanFeedback) {
+    if let Ok(feedback) = FEEDBACK.lock() {
+        if let Some((sender, _)) = feedback.as_ref() {
+            let _ = sender.send(outcome);
+        }
+    }
+}
+
+/// Stop the pattern thread and wait for it to turn the buzzer and LED off, so the
+/// app never exits with an output left on. Skipped if the state is in use (a panic
+/// while a scan was being signalled).
+pub fn shut_down() {
+    let stopped = match FEEDBACK.try_lock() {
+        Ok(mut feedback) => feedback.take(),
+        Err(_) => None,
+    };
+    // Dropping the sender ends the pattern after the one playing
+    if let Some((sender, handle)) = stopped {
+        drop(sender);
+        let _ = handle.join();
+    }
+}
@@ -1,5 +1,22 @@
 // reader/mod.rs
+#[cfg(feature = "gui")]
 pub mod ui;
+pub mod rdm6300;
+pub mod status;
+pub mod cooldown;
+#[cfg(feature = "gui")]
+pub mod feedback;
+pub mod wedge_config;
+pub mod command_cards;
+#[cfg(feature = "gui")]
+pub mod scan_log;
+#[cfg(feature = "gui")]
+pub mod review_window;
 
 // Re-export the main reader functions for backwards compatibility
-pub use ui::{start_capture, set_inventory_ui};
\ No newline at end of file
+#[cfg(feature = "gui")]
+pub use ui::{start_capture, set_inventory_ui};
+pub use rdm6300::{Em4100Tag, Rdm6300Reader};
+pub use cooldown::{accept_scan, ScanCooldown};
+#[cfg(feature = "gui")]
+pub use feedback::ScanFeedback;
@@ -0,0 +1,205 @@
+// reader/rdm6300.rs
+//
+// 125 kHz backend for RDM6300 style UART modules (EM4100 / EM4102 fobs).
+// The module streams a 14 byte frame every time a tag is in the field:
+//
+//   0x02 | 10 ASCII hex chars (version + 4 data bytes) | 2 ASCII hex checksum | 0x03
+//
+// The checksum is the XOR of the 5 decoded bytes.
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default serial device of the Raspberry Pi primary UART
+pub const DEFAULT_PORT: &str = "/dev/serial0";
+
+/// RDM6300 modules always talk 9600 baud, 8N1
+const BAUD_RATE: libc::speed_t = libc::B9600;
+
+const FRAME_START: u8 = 0x02;
+const FRAME_END: u8 = 0x03;
+const FRAME_LEN: usize = 14;
+
+/// The module repeats the same frame while a tag stays in the field, a tag
+/// held there is reported again once per window (presence monitoring needs it)
+const REPEAT_WINDOW: Duration = Duration::from_millis(1500);
+
+/// A decoded EM4100 tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Em4100Tag {
+    /// Version / customer ID byte
+    pub version: u8,
+    /// The 32-bit card data
+    pub data: [u8; 4],
+}
+
+impl Em4100Tag {
+    /// Full 10 digit hex ID as printed by most readers
+    pub fn hex_id(&self) -> String {
+        format!(
+            "{:02X}{:02X}{:02X}{:02X}{:02X}",
+            self.version, self.data[0], self.data[1], self.data[2], self.data[3]
+        )
+    }
+
+    /// The 32-bit card data as a decimal number (the 10 digit number printed on most fobs)
+    pub fn card_data_decimal(&self) -> u32 {
+        u32::from_be_bytes(self.data)
+    }
+
+    /// Facility code in the common "FFF,CCCCC" fob marking (third byte of the ID)
+    pub fn facility_code(&self) -> u8 {
+        self.data[1]
+    }
+
+    /// Card number in the common "FFF,CCCCC" fob marking (last two bytes of the ID)
+    pub fn card_number(&self) -> u16 {
+        u16::from_be_bytes([self.data[2], self.data[3]])
+    }
+
+    /// EM4100 tags do not carry an IC manufacturer code, only a customer ID
+    pub fn manufacturer(&self) -> String {
+        format!("EM Microelectronic EM4100 (customer ID {:02X})", self.version)
+    }
+}
+
+/// Parse a complete RDM6300 frame, returns None on framing or checksum errors
+pub fn parse_frame(frame: &[u8]) -> Option<Em4100Tag> {
+    if frame.len() != FRAME_LEN || frame[0] != FRAME_START || frame[FRAME_LEN - 1] != FRAME_END {
+        return None;
+    }
+
+    let text = std::str::from_utf8(&frame[1..FRAME_LEN - 1]).ok()?;
+    let mut bytes = [0u8; 6];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    // XOR of the five payload bytes must match the checksum byte
+    let checksum = bytes[..5].iter().fold(0u8, |acc, b| acc ^ b);
+    if checksum != bytes[5] {
+        return None;
+    }
+
+    Some(Em4100Tag {
+        version: bytes[0],
+        data: [bytes[1], bytes[2], bytes[3], bytes[4]],
+    })
+}
+
+/// Open the serial port in raw 9600 8N1 mode with a short read timeout
+fn open_serial(port: &str) -> io::Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOCTTY)
+        .open(port)?;
+
+    let fd = file.as_raw_fd();
+    unsafe {
+        let mut tty: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(fd, &mut tty) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        libc::cfmakeraw(&mut tty);
+        libc::cfsetispeed(&mut tty, BAUD_RATE);
+        libc::cfsetospeed(&mut tty, BAUD_RATE);
+        tty.c_cflag |= libc::CLOCAL | libc::CREAD;
+        tty.c_cflag &= !(libc::PARENB | libc::CSTOPB);
+
+        // Return after 0.5s without data so the thread can notice a stop request
+        tty.c_cc[libc::VMIN] = 0;
+        tty.c_cc[libc::VTIME] = 5;
+
+        if libc::tcsetattr(fd, libc::TCSANOW, &tty) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(file)
+}
+
+/// A running RDM6300 reader thread
+pub struct Rdm6300Reader {
+    receiver: Receiver<Em4100Tag>,
+    running: Arc<AtomicBool>,
+}
+
+impl Rdm6300Reader {
+    /// Open the serial port and start reading tags in the background
+    pub fn start(port: &str) -> io::Result<Self> {
+        let mut serial = open_serial(port)?;
+        let (sender, receiver) = mpsc::channel();
+        let running = Arc::new(AtomicBool::new(true));
+        let running_thread = running.clone();
+
+        thread::spawn(move || {
+            let mut frame: Vec<u8> = Vec::with_capacity(FRAME_LEN);
+            let mut last_tag: Option<(Em4100Tag, Instant)> = None;
+            let mut buf = [0u8; 32];
+
+            while running_thread.load(Ordering::SeqCst) {
+                let count = match serial.read(&mut buf) {
+                    Ok(count) => count,
+                    Err(e) => {
+                        eprintln!("RDM6300 read error: {}", e);
+                        break;
+                    }
+                };
+
+                for &byte in &buf[..count] {
+                    // Resynchronise on every start byte
+                    if byte == FRAME_START {
+                        frame.clear();
+                    }
+                    if frame.is_empty() && byte != FRAME_START {
+                        continue;
+                    }
+
+                    frame.push(byte);
+                    if frame.len() < FRAME_LEN {
+                        continue;
+                    }
+
+                    if let Some(tag) = parse_frame(&frame) {
+                        let repeated = matches!(last_tag, Some((last, at))
+                            if last == tag && at.elapsed() < REPEAT_WINDOW);
+
+                        if !repeated {
+                            if sender.send(tag).is_err() {
+                                // The capture window went away
+                                return;
+                            }
+                            last_tag = Some((tag, Instant::now()));
+                        }
+                    }
+                    frame.clear();
+                }
+            }
+        });
+
+        Ok(Rdm6300Reader { receiver, running })
+    }
+
+    /// Get the next tag read since the last call, without blocking
+    pub fn try_next(&self) -> Option<Em4100Tag> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Ask the background thread to stop
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Drop for Rdm6300Reader {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,186 @@
+// reader/review_window.rs
+//
+// The review list of queued scans, opened from the capture window. Entries can be
+// changed or removed one at a time, the list is booked or thrown away as a whole.
+use fltk::{
+    browser::HoldBrowser,
+    button::Button,
+    dialog,
+    enums::{Align, Color},
+    frame::Frame,
+    prelude::*,
+    window::Window,
+};
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::InventoryUI;
+use crate::inventory::model::{Named, StockMode};
+use crate::inventory::purchasing;
+use crate::inventory::review::{self, QueuedScan};
+use crate::ui::theme;
+
+// this shows the review list of the scans queued in the capture window
+pub fn show_review_window(inventory_ui: &'static InventoryUI) {
+    let mut win = Window::new(250, 120, 560, 420, None).with_label(&tr("review-title"));
+    win.set_color(theme::palette().background);
+    win.make_modal(true);
+
+    let mut browser = HoldBrowser::new(20, 20, 520, 250, "");
+
+    let mut problems = Frame::new(20, 275, 520, 40, None);
+    problems.set_align(Align::Left | Align::Inside | Align::Wrap);
+    problems.set_label_size(11);
+    problems.set_label_color(Color::Red);
+
+    let mut quantity_btn = Button::new(20, 325, 120, 30, None).with_label(&tr("review-quantity"));
+    let mut name_btn = Button::new(150, 325, 120, 30, None).with_label(&tr("review-name"));
+    let mut remove_btn = Button::new(280, 325, 100, 30, None).with_label(&tr("btn-remove"));
+    let mut commit_btn = Button::new(240, 370, 100, 30, None).with_label(&tr("review-commit"));
+    let mut discard_btn = Button::new(350, 370, 90, 30, None).with_label(&tr("review-discard"));
+    let mut close_btn = Button::new(450, 370, 90, 30, None).with_label(&tr("btn-close"));
+
+    win.end();
+    win.show();
+
+    refresh_review_list(&mut browser, &mut problems);
+
+    let mut browser_quantity = browser.clone();
+    let mut problems_quantity = problems.clone();
+    quantity_btn.set_callback(move |_| {
+        let entry = match selected_entry(&browser_quantity) {
+            Some(entry) => entry,
+            None => return,
+        };
+        let prompt = match entry.1.mode {
+            StockMode::Count => tr_args("mode-count-prompt", &[("item", &entry_name(&entry.1))]),
+            mode => tr_args("review-quantity-prompt", &[("mode", &mode.name()), ("tag_id", &entry.1.tag_id)]),
+        };
+        if let Some(input) = dialog::input(300, 300, &prompt, &entry.1.quantity.to_string()) {
+            match input.trim().parse::<i32>() {
+                Ok(quantity) if quantity >= 0 => {
+                    review::set_quantity(entry.0, quantity);
+                    refresh_review_list(&mut browser_quantity, &mut problems_quantity);
+                    browser_quantity.select(entry.0 as i32 + 1);
+                },
+                _ => dialog::alert(300, 300, &tr("err-count-quantity")),
+            }
+        }
+    });
+
+    let mut browser_name = browser.clone();
+    let mut problems_name = problems.clone();
+    name_btn.set_callback(move |_| {
+        let entry = match selected_entry(&browser_name) {
+            Some(entry) => entry,
+            None => return,
+        };
+        if entry.1.known {
+            dialog::alert(300, 300, &tr_args("review-known", &[("name", &entry_name(&entry.1))]));
+            return;
+        }
+        let current = entry.1.name.clone().unwrap_or_default();
+        if let Some(name) = dialog::input(300, 300, &tr_args("review-name-prompt", &[("tag_id", &entry.1.tag_id)]), &current) {
+            review::set_name(entry.0, &name);
+            refresh_review_list(&mut browser_name, &mut problems_name);
+            browser_name.select(entry.0 as i32 + 1);
+        }
+    });
+
+    let mut browser_remove = browser.clone();
+    let mut problems_remove = problems.clone();
+    remove_btn.set_callback(move |_| {
+        if let Some((index, _)) = selected_entry(&browser_remove) {
+            review::remove_scan(index);
+            refresh_review_list(&mut browser_remove, &mut problems_remove);
+        }
+    });
+
+    let mut browser_commit = browser.clone();
+    let mut problems_commit = problems.clone();
+    let mut win_commit = win.clone();
+    commit_btn.set_callback(move |_| {
+        let entries = review::queued_scans();
+        if entries.is_empty() {
+            win_commit.hide();
+            return;
+        }
+        // bind the result first so the database borrow ends before any dialog runs
+        let result = review::commit_queue(&inventory_ui.inventory_db.borrow());
+        match result {
+            Ok(problems) if problems.is_empty() => {
+                record_receipts(inventory_ui, &entries);
+                inventory_ui.log_event(&tr_args("review-committed", &[("count", &entries.len().to_string())]));
+                inventory_ui.refresh();
+                win_commit.hide();
+            },
+            Ok(_) => {
+                refresh_review_list(&mut browser_commit, &mut problems_commit);
+                dialog::alert(300, 300, &tr("review-has-problems"));
+            },
+            Err(e) => dialog::alert(300, 300, &tr_args("err-review-commit", &[("error", &e.to_string())])),
+        }
+    });
+
+    let mut win_discard = win.clone();
+    discard_btn.set_callback(move |_| {
+        let count = review::queue_len();
+        if count == 0 {
+            win_discard.hide();
+            return;
+        }
+        let question = tr_args("review-discard-confirm", &[("count", &count.to_string())]);
+        if dialog::choice2(300, 300, &question, &tr("btn-cancel"), &tr("review-discard"), "") == Some(1) {
+            review::discard_queue();
+            win_discard.hide();
+        }
+    });
+
+    close_btn.set_callback(move |_| {
+        win.hide();
+    });
+}
+
+// this rebuilds the list and the problems line from the queue
+fn refresh_review_list(browser: &mut HoldBrowser, problems: &mut Frame) {
+    let entries = review::queued_scans();
+    browser.clear();
+    for entry in &entries {
+        let marker = if entry.problem().is_some() { "! " } else { "" };
+        browser.add(&format!("{}{}", marker, entry.summary()));
+    }
+
+    let first_problem = entries.iter().find_map(QueuedScan::problem);
+    let count = entries.iter().filter(|entry| entry.problem().is_some()).count();
+    problems.set_label(&match first_problem {
+        Some(problem) if count > 1 => tr_args("review-problems", &[("problem", &problem), ("count", &(count - 1).to_string())]),
+        Some(problem) => problem,
+        None => String::new(),
+    });
+}
+
+// this returns the selected entry and its index in the queue
+fn selected_entry(browser: &HoldBrowser) -> Option<(usize, QueuedScan)> {
+    let line = browser.value();
+    if line == 0 {
+        dialog::alert(300, 300, &tr("review-select"));
+        return None;
+    }
+    let index = line as usize - 1;
+    review::queued_scans().get(index).cloned().map(|entry| (index, entry))
+}
+
+fn entry_name(entry: &QueuedScan) -> String {
+    entry.name.clone().unwrap_or_else(|| entry.tag_id.clone())
+}
+
+// a committed receipt also counts against the purchase order being received
+fn record_receipts(inventory_ui: &InventoryUI, entries: &[QueuedScan]) {
+    for entry in entries.iter().filter(|entry| entry.known && entry.mode == StockMode::Receive && entry.quantity > 0) {
+        let receipt = purchasing::record_receipt(&inventory_ui.inventory_db.borrow(), &entry.tag_id, entry.quantity);
+        match receipt {
+            Ok(Some(message)) => inventory_ui.log_event(&message),
+            Ok(None) => {},
+            Err(e) => dialog::alert(300, 300, &tr_args("err-capture-po", &[("error", &e.to_string())])),
+        }
+    }
+}
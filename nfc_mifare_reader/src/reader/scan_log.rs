@@ -0,0 +1,49 @@
+// reader/scan_log.rs
+//
+// The scans captured this session, kept as records from the moment they are read.
+// The reader tab shows them as text; exports and command cards take the records
+// themselves, so changing how a scan is displayed can't break them.
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::Mutex;
+use fltk::{prelude::*, text::TextBuffer};
+use once_cell::sync::Lazy;
+
+use crate::export::CardRecord;
+use crate::history;
+
+static SCANS: Lazy<Mutex<Vec<CardRecord>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Keep a scan, store it in the scan history and add its text to the reader tab
+/// display. `reader` names the scan source and `keyboard_layout` is the layout the
+/// raw UID was decoded with, None for readers that send the UID itself.
+pub fn log_scan(
+    record: CardRecord,
+    reader: &str,
+    keyboard_layout: Option<i32>,
+    card_buffer: &Rc<RefCell<TextBuffer>>
+) {
+    card_buffer.borrow_mut().append(&record.display_text());
+    history::record_scan(&record, keyboard_layout, reader);
+    if let Ok(mut scans) = SCANS.lock() {
+        scans.push(record);
+    }
+}
+
+/// The scans of this session, oldest first
+pub fn session_scans() -> Vec<CardRecord> {
+    SCANS.lock().map(|scans| scans.clone()).unwrap_or_default()
+}
+
+/// The scans of this session, None while they are being changed
+pub fn try_session_scans() -> Option<Vec<CardRecord>> {
+    SCANS.try_lock().ok().map(|scans| scans.clone())
+}
+
+/// Forget the scans of this session, along with the reader tab display
+pub fn clear_scans(card_buffer: &Rc<RefCell<TextBuffer>>) {
+    card_buffer.borrow_mut().set_text("");
+    if let Ok(mut scans) = SCANS.lock() {
+        scans.clear();
+    }
+}
@@ -0,0 +1,71 @@
+// reader/status.rs
+//
+// Session-wide reader health shared between the capture window and the status bar.
+use std::sync::Mutex;
+use once_cell::sync::Lazy;
+
+/// Connection state of the scan sources
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReaderConnection {
+    /// Capture isn't running
+    Idle,
+    /// Listening on the scan FIFO, optionally with the 125 kHz reader on a serial port
+    Listening { lf_port: Option<String> },
+    /// A scan source failed to open
+    Error(String),
+}
+
+/// Health information for the status bar
+#[derive(Debug, Clone)]
+pub struct ReaderHealth {
+    pub connection: ReaderConnection,
+    /// Reported by the scan feeder with a "firmware,<version>" FIFO line
+    pub firmware: Option<String>,
+    pub scans_this_session: u32,
+    pub last_scan: Option<String>,
+}
+
+pub static READER_HEALTH: Lazy<Mutex<ReaderHealth>> = Lazy::new(|| Mutex::new(ReaderHealth {
+    connection: ReaderConnection::Idle,
+    firmware: None,
+    scans_this_session: 0,
+    last_scan: None,
+}));
+
+/// Update the connection state
+pub fn set_connection(connection: ReaderConnection) {
+    if let Ok(mut health) = READER_HEALTH.lock() {
+        health.connection = connection;
+    }
+}
+
+/// Remember the firmware version reported by the reader
+pub fn set_firmware(version: &str) {
+    if let Ok(mut health) = READER_HEALTH.lock() {
+        health.firmware = Some(version.to_string());
+    }
+}
+
+/// Count a scan and remember when it happened
+pub fn record_scan(human_timestamp: &str) {
+    if let Ok(mut health) = READER_HEALTH.lock() {
+        health.scans_this_session += 1;
+        health.last_scan = Some(human_timestamp.to_string());
+    }
+}
+
+/// Snapshot of the current health
+pub fn reader_health() -> Option<ReaderHealth> {
+    READER_HEALTH.lock().ok().map(|health| health.clone())
+}
+
+impl std::fmt::Display for ReaderConnection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReaderConnection::Idle => write!(f, "Idle"),
+            ReaderConnection::Listening { lf_port: None } => write!(f, "Listening"),
+            ReaderConnection::Listening { lf_port: Some(port) } => write!(f, "Listening (+125 kHz on {})", port),
+            ReaderConnection::Error(e) => write!(f, "Error: {}", e),
+        }
+    }
+}
@@ -1,8 +1,8 @@
 // reader/ui.rs
 use fltk::{
     app,
-    button::Button,
-    enums::{Color, Font},
+    button::{Button, CheckButton},
+    enums::{Event, Font},
     frame::Frame,
     input::{Input, MultilineInput},
     prelude::*,
@@ -21,8 +21,55 @@ use std::os::unix::fs::OpenOptionsExt;
 use libc;
 
 use crate::utils;
+use crate::wiegand;
+use crate::plugins::{self, PluginScan, PluginWorker};
+use crate::config::APP_CONFIG;
 use crate::inventory::InventoryUI;
-use crate::inventory::model::{create_inventory_item, generate_timestamp, InventoryItem};
+use crate::ui::theme;
+use super::rdm6300::Rdm6300Reader;
+use super::status::{self, ReaderConnection};
+use super::cooldown::{accept_scan, reset_cooldown};
+use super::feedback::{self, ScanFeedback};
+use super::scan_log::log_scan;
+use super::review_window::show_review_window;
+use super::command_cards::{action_for_key, export_scans, find_command, CommandAction};
+use crate::export::{CardRecord, ExportFormat};
+use crate::i18n::{tr, tr_args};
+use crate::rules::run_scan_rules;
+use crate::inventory::{kits, presence, purchasing, review, units};
+use crate::inventory::stocktake::{finish_stocktake, record_stocktake_scan, start_stocktake, stocktake_progress};
+use crate::inventory::model::{create_inventory_item, generate_timestamp, InventoryItem, Named, StockMode, ALL_STOCK_MODES};
+
+// The capture window widgets a command card can change
+#[derive(Clone)]
+struct CaptureControls {
+    inventory_mode: CheckButton,
+    show_form: CheckButton,
+    command_status: Frame,
+    stock_mode: Choice,
+    queue: CheckButton,
+}
+
+impl CaptureControls {
+    fn stock_mode(&self) -> StockMode {
+        StockMode::from_index(self.stock_mode.value())
+    }
+}
+
+// Scan sources as stored in the scan history, the 125 kHz reader is named by its port
+const MANUAL_READER: &str = "manual";
+const FIFO_READER: &str = "fifo";
+
+// A decoded scan on the plugin thread, with what logging it takes afterwards
+struct PendingScan {
+    reader: String,
+    // Layout a keyboard wedge scan was decoded with, None for the 125 kHz reader
+    kb_layout: Option<i32>,
+    timestamp: String,
+    captured_at: String,
+    // Card number the 125 kHz reader decoded, wedge scans get theirs from the hex UID
+    decimal_uid: Option<String>,
+}
 
 // Instead of a static variable, we'll use a more direct approach
 // through function parameters
@@ -38,38 +85,68 @@ pub fn set_inventory_ui(inventory_ui: &Rc<InventoryUI>) {
 }
 
 pub fn start_capture(btn: &mut Button, card_buffer: Rc<RefCell<TextBuffer>>, kb_layout: Rc<RefCell<i32>>) {
-    if btn.label() == "Start Capture" {
-        btn.set_label("Stop Capture");
+    if btn.label() == tr("capture-start") {
+        btn.set_label(&tr("capture-stop"));
         
         // Create a capture window - increased height to accommodate manual input
-        let mut capture_wind = Window::new(300, 300, 500, 250, "Card Capture");
-        capture_wind.set_color(Color::White);
+        let mut capture_wind = Window::new(300, 300, 500, 285, None).with_label(&tr("capture-title"));
+        capture_wind.set_color(theme::palette().background);
         
-        Frame::new(20, 20, 460, 40, "Present cards to the reader\nCard data will appear here:").set_label_size(14);
+        Frame::new(20, 20, 460, 40, None).with_label(&tr("capture-present")).set_label_size(14);
         
         // Input display that shows what's being captured
-        let mut input_display = Frame::new(20, 80, 460, 30, "Waiting for card...");
+        let mut input_display = Frame::new(20, 80, 460, 30, None).with_label(&tr("capture-waiting"));
         input_display.set_frame(fltk::enums::FrameType::DownBox);
-        input_display.set_color(Color::White);
+        input_display.set_color(theme::palette().background);
         input_display.set_align(fltk::enums::Align::Left | fltk::enums::Align::Inside);
         
+        // Result of the last command card, or the running stocktake
+        let mut command_status = Frame::new(20, 115, 460, 30, "");
+        command_status.set_align(fltk::enums::Align::Left | fltk::enums::Align::Inside);
+        command_status.set_label_size(12);
+        
         // Add a text input field for manual card entry
-        let mut manual_input = Input::new(100, 160, 270, 30, "Manual Entry:");
-        let mut submit_btn = Button::new(380, 160, 100, 30, "Submit");
+        let mut manual_input = Input::new(100, 160, 270, 30, None).with_label(&tr("capture-manual"));
+        let mut submit_btn = Button::new(380, 160, 100, 30, None).with_label(&tr("capture-submit"));
         
         // Create checkboxes as before
         let inventory_mode = fltk::button::CheckButton::default()
             .with_pos(20, 200)
             .with_size(200, 30)
-            .with_label("Update Inventory");
+            .with_label(&tr("capture-inventory"));
         inventory_mode.set_checked(true);
 
         let show_form = fltk::button::CheckButton::default()
             .with_pos(220, 200)
             .with_size(260, 30)
-            .with_label("Show Item Form When Scanning");
+            .with_label(&tr("capture-show-form"));
         show_form.set_checked(true);
         
+        // Whether a scan books stock in, books it out or sets the counted quantity
+        let mut stock_mode = Choice::new(100, 240, 150, 30, None).with_label(&tr("capture-mode"));
+        for mode in ALL_STOCK_MODES {
+            stock_mode.add_choice(&mode.name());
+        }
+        stock_mode.set_value(StockMode::Receive.index());
+        
+        // Queued scans wait in a review list until the operator commits them
+        let queue = CheckButton::new(260, 240, 120, 30, None).with_label(&tr("review-queue"));
+        let mut review_btn = Button::new(380, 240, 100, 30, None).with_label(&review_button_label(0));
+        review_btn.set_callback(move |_| {
+            if let Ok(inventory_ui) = get_inventory_ui() {
+                show_review_window(inventory_ui);
+            }
+        });
+        
+        // Widgets the command cards and their function keys act on
+        let controls = CaptureControls {
+            inventory_mode: inventory_mode.clone(),
+            show_form: show_form.clone(),
+            command_status: command_status.clone(),
+            stock_mode: stock_mode.clone(),
+            queue: queue.clone(),
+        };
+        
         // FIFO-based card reading approach
         let fifo_path = "/tmp/rfid_scans.fifo";
         
@@ -83,100 +160,34 @@ pub fn start_capture(btn: &mut Button, card_buffer: Rc<RefCell<TextBuffer>>, kb_
                 .output() {
                 Ok(_) => {},
                 Err(e) => {
-                    dialog::alert(300, 300, &format!("Error creating FIFO: {}", e));
+                    dialog::alert(300, 300, &tr_args("err-capture-fifo", &[("error", &e.to_string())]));
                 }
             }
         }
         
+        // Scans from an earlier capture session don't hold back the first scan of this one
+        reset_cooldown();
+        
         // Track if we're currently processing a card
         let processing_card = Rc::new(RefCell::new(false));
         
+        // Plugins run off the UI thread, the timer picks up the scans they are done with
+        let plugin_worker = Rc::new(PluginWorker::<PendingScan>::start());
+        
         // Set up the callback for the submit button
-        let card_buffer_clone2 = card_buffer.clone();
         let kb_layout_clone2 = kb_layout.clone();
-        let show_form_clone2 = show_form.clone();
-        let inventory_mode_clone2 = inventory_mode.clone();
         let mut input_display_clone2 = input_display.clone();
         let mut manual_input_clone = manual_input.clone();
+        let plugin_worker_submit = plugin_worker.clone();
 
         submit_btn.set_callback(move |_| {
             let card_data = manual_input_clone.value();
             if !card_data.is_empty() {
                 // Process the card data manually
-                input_display_clone2.set_label(&format!("Processing: {}", card_data));
+                input_display_clone2.set_label(&tr_args("capture-processing", &[("card", &card_data)]));
                 
-                // Process as before
-                let (unix_timestamp, human_timestamp) = utils::get_timestamps();
-                let kb_layout_value = *kb_layout_clone2.borrow();
-                let (hex_uid, manufacturer) = utils::process_uid_for_display(&card_data, kb_layout_value);
-                let decimal_value = utils::hex_to_decimal(&hex_uid);
-                let format_desc = utils::interpret_format_code(&card_data);
-                
-                let record = format!(
-                    "[{}] ({}) Raw UID: {}\n    → Hex: {}\n    → Decimal: {}\n    → Manufacturer: {}\n    → Format: {}\n\n", 
-                    unix_timestamp,
-                    human_timestamp, 
-                    card_data, 
-                    hex_uid,
-                    decimal_value, 
-                    manufacturer,
-                    format_desc
-                );
-                
-                let mut buffer = card_buffer_clone2.borrow_mut();
-                let current = buffer.text();
-                buffer.set_text(&format!("{}{}", current, record));
-                
-                // Handle inventory functionality
-                let clean_tag_id = hex_uid.replace(" ", "");
-                
-                if inventory_mode_clone2.is_checked() {
-                    if let Ok(inventory_ui) = get_inventory_ui() {
-                        match inventory_ui.inventory_db.borrow().get_item(&clean_tag_id) {
-                            Ok(Some(item)) => {
-                                if show_form_clone2.is_checked() {
-                                    show_item_update_dialog(inventory_ui, item.clone());
-                                } else {
-                                    if let Err(e) = inventory_ui.inventory_db.borrow().update_quantity(&clean_tag_id, item.quantity + 1) {
-                                        dialog::alert(300, 300, &format!("Error updating quantity: {}", e));
-                                    } else {
-                                        dialog::message(300, 300, &format!("Updated quantity of '{}' to {}", item.name, item.quantity + 1));
-                                    }
-                                }
-                            },
-                            Ok(None) => {
-                                if show_form_clone2.is_checked() {
-                                    show_new_item_dialog(inventory_ui, clean_tag_id.clone(), manufacturer.clone());
-                                } else {
-                                    // Simple item creation
-                                    if dialog::choice2(300, 300, &format!("Tag ID {} not found in inventory. Create a new item?", clean_tag_id), "No", "Yes", "") == Some(1) {
-                                        if let Some(name) = dialog::input(300, 300, "Enter item name:", "") {
-                                            if !name.is_empty() {
-                                                let new_item = create_inventory_item(
-                                                    &clean_tag_id,
-                                                    &name,
-                                                    None,
-                                                    1,
-                                                    None,
-                                                    None
-                                                );
-                                                
-                                                if let Err(e) = inventory_ui.inventory_db.borrow().save_item(&new_item) {
-                                                    dialog::alert(300, 300, &format!("Error saving item: {}", e));
-                                                } else {
-                                                    dialog::message(300, 300, &format!("New item '{}' added to inventory", name));
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            },
-                            Err(e) => {
-                                dialog::alert(300, 300, &format!("Error checking inventory: {}", e));
-                            }
-                        }
-                    }
-                }
+                // Decode the scan and hand it to the plugins
+                submit_wedge_scan(&plugin_worker_submit, &card_data, *kb_layout_clone2.borrow(), MANUAL_READER);
                 
                 // Clear the input field after processing
                 manual_input_clone.set_value("");
@@ -192,7 +203,55 @@ pub fn start_capture(btn: &mut Button, card_buffer: Rc<RefCell<TextBuffer>>, kb_
         let processing_card_clone = processing_card.clone();
         let fifo_path_clone = fifo_path.to_string();
         
-        let timer_handle = app::add_timeout(0.05, move || {
+        // Start the 125 kHz RDM6300 reader as a second scan source if enabled
+        let lf_reader: Rc<RefCell<Option<Rdm6300Reader>>> = Rc::new(RefCell::new(None));
+        let (rdm6300_enabled, rdm6300_port) = match APP_CONFIG.lock() {
+            Ok(config) => (config.rdm6300_enabled, config.rdm6300_port.clone()),
+            Err(_) => (false, String::new()),
+        };
+        if rdm6300_enabled {
+            match Rdm6300Reader::start(&rdm6300_port) {
+                Ok(reader) => {
+                    *lf_reader.borrow_mut() = Some(reader);
+                    status::set_connection(ReaderConnection::Listening { lf_port: Some(rdm6300_port.clone()) });
+                },
+                Err(e) => {
+                    let problem = [("port", rdm6300_port.as_str()), ("error", &e.to_string())];
+                    status::set_connection(ReaderConnection::Error(tr_args("capture-lf-failed", &problem)));
+                    dialog::alert(300, 300, &tr_args("err-capture-lf", &problem));
+                },
+            }
+        } else {
+            status::set_connection(ReaderConnection::Listening { lf_port: None });
+        }
+        let lf_reader_clone = lf_reader.clone();
+        let lf_reader_id = format!("rdm6300:{}", rdm6300_port);
+        let mut controls_timer = controls.clone();
+        let plugin_worker_timer = plugin_worker.clone();
+        
+        let timer_handle = app::add_timeout3(0.05, move |handle| {
+            // Tags from the 125 kHz reader don't need keyboard decoding
+            let lf_tag = lf_reader_clone.borrow().as_ref().and_then(|reader| reader.try_next());
+            if let Some(tag) = lf_tag {
+                input_display_clone.set_label(&tr_args("capture-processing", &[("card", &tag.hex_id())]));
+                
+                let (timestamp, captured_at) = utils::get_timestamps();
+                status::record_scan(&captured_at);
+                plugin_worker_timer.submit(PluginScan {
+                    raw: tag.hex_id(),
+                    hex_uid: utils::format_hex_uid(&tag.hex_id()),
+                    manufacturer: tag.manufacturer(),
+                    format: format!("EM4100 125 kHz (Facility {}, Card {})", tag.facility_code(), tag.card_number()),
+                    extra: Default::default(),
+                }, PendingScan {
+                    reader: lf_reader_id.clone(),
+                    kb_layout: None,
+                    timestamp,
+                    captured_at,
+                    decimal_uid: Some(tag.card_data_decimal().to_string()),
+                });
+            }
+            
             // Only process if we're not already processing a card
             if !*processing_card_clone.borrow() {
                 // Open the FIFO in non-blocking mode
@@ -207,91 +266,24 @@ pub fn start_capture(btn: &mut Button, card_buffer: Rc<RefCell<TextBuffer>>, kb_
                         // Set processing flag
                         *processing_card_clone.borrow_mut() = true;
                         
-                        // Process each line
+                        // Process each line, a feeder reading stacked tags writes one per card
                         for line_result in reader.lines() {
                             if let Ok(line) = line_result {
+                                // The scan feeder may report the reader firmware as "firmware,<version>"
+                                if let Some(version) = line.strip_prefix("firmware,") {
+                                    status::set_firmware(version.trim());
+                                    continue;
+                                }
+                                
                                 // Parse the line (format: timestamp,card_data)
                                 if let Some(idx) = line.find(',') {
                                     let card_data = line[idx+1..].trim().to_string();
                                     
                                     // Process the card data
-                                    input_display_clone.set_label(&format!("Processing: {}", card_data));
-                                    
-                                    // Process as before
-                                    let (unix_timestamp, human_timestamp) = utils::get_timestamps();
-                                    let kb_layout_value = *kb_layout_clone.borrow();
-                                    let (hex_uid, manufacturer) = utils::process_uid_for_display(&card_data, kb_layout_value);
-                                    let decimal_value = utils::hex_to_decimal(&hex_uid);
-                                    let format_desc = utils::interpret_format_code(&card_data);
-                                    
-                                    let record = format!(
-                                        "[{}] ({}) Raw UID: {}\n    → Hex: {}\n    → Decimal: {}\n    → Manufacturer: {}\n    → Format: {}\n\n", 
-                                        unix_timestamp,
-                                        human_timestamp, 
-                                        card_data, 
-                                        hex_uid,
-                                        decimal_value, 
-                                        manufacturer,
-                                        format_desc
-                                    );
-                                    
-                                    let mut buffer = card_buffer_clone.borrow_mut();
-                                    let current = buffer.text();
-                                    buffer.set_text(&format!("{}{}", current, record));
+                                    input_display_clone.set_label(&tr_args("capture-processing", &[("card", &card_data)]));
                                     
-                                    // Handle inventory functionality
-                                    let clean_tag_id = hex_uid.replace(" ", "");
-                                    
-                                    if inventory_mode_clone.is_checked() {
-                                        if let Ok(inventory_ui) = get_inventory_ui() {
-                                            match inventory_ui.inventory_db.borrow().get_item(&clean_tag_id) {
-                                                Ok(Some(item)) => {
-                                                    if show_form_clone.is_checked() {
-                                                        show_item_update_dialog(inventory_ui, item.clone());
-                                                    } else {
-                                                        if let Err(e) = inventory_ui.inventory_db.borrow().update_quantity(&clean_tag_id, item.quantity + 1) {
-                                                            dialog::alert(300, 300, &format!("Error updating quantity: {}", e));
-                                                        } else {
-                                                            dialog::message(300, 300, &format!("Updated quantity of '{}' to {}", item.name, item.quantity + 1));
-                                                        }
-                                                    }
-                                                },
-                                                Ok(None) => {
-                                                    if show_form_clone.is_checked() {
-                                                        show_new_item_dialog(inventory_ui, clean_tag_id.clone(), manufacturer.clone());
-                                                    } else {
-                                                        // Simple item creation
-                                                        if dialog::choice2(300, 300, &format!("Tag ID {} not found in inventory. Create a new item?", clean_tag_id), "No", "Yes", "") == Some(1) {
-                                                            if let Some(name) = dialog::input(300, 300, "Enter item name:", "") {
-                                                                if !name.is_empty() {
-                                                                    let new_item = create_inventory_item(
-                                                                        &clean_tag_id,
-                                                                        &name,
-                                                                        None,
-                                                                        1,
-                                                                        None,
-                                                                        None
-                                                                    );
-                                                                    
-                                                                    if let Err(e) = inventory_ui.inventory_db.borrow().save_item(&new_item) {
-                                                                        dialog::alert(300, 300, &format!("Error saving item: {}", e));
-                                                                    } else {
-                                                                        dialog::message(300, 300, &format!("New item '{}' added to inventory", name));
-                                                                    }
-                                                                }
-                                                            }
-                                                        }
-                                                    }
-                                                },
-                                                Err(e) => {
-                                                    dialog::alert(300, 300, &format!("Error checking inventory: {}", e));
-                                                }
-                                            }
-                                        }
-                                    }
-                                    
-                                    // Only process one card at a time
-                                    break;
+                                    // Decode the scan and hand it to the plugins
+                                    submit_wedge_scan(&plugin_worker_timer, &card_data, *kb_layout_clone.borrow(), FIFO_READER);
                                 }
                             }
                         }
@@ -302,31 +294,84 @@ pub fn start_capture(btn: &mut Button, card_buffer: Rc<RefCell<TextBuffer>>, kb_
                 }
             }
             
+            // Scans the plugins are done with are logged and booked in the order they came in
+            while let Some((scan, pending)) = plugin_worker_timer.try_next() {
+                let record = build_scan_record(&scan, &pending);
+                let clean_tag_id = scan.hex_uid.replace(" ", "");
+                // A card held on the reader keeps sending its UID, which is how presence
+                // monitoring knows it is still there. Typed in scans are never repeats.
+                let manual = pending.reader == MANUAL_READER;
+                if !manual {
+                    presence::tag_seen(&clean_tag_id);
+                }
+                let repeat = !manual && !accept_scan(&clean_tag_id);
+                
+                if !handle_command_card(&clean_tag_id, repeat, &mut controls_timer) {
+                    log_scan(mark_repeat(record, repeat), &pending.reader, pending.kb_layout, &card_buffer_clone);
+                    
+                    // Handle inventory functionality
+                    if !repeat {
+                        handle_scan_result(&clean_tag_id, &scan.manufacturer, inventory_mode_clone.is_checked(), show_form_clone.is_checked(), controls_timer.stock_mode(), controls_timer.queue.is_checked());
+                    }
+                }
+            }
+            
             // Reset the status display if not processing
             if !*processing_card_clone.borrow() {
-                input_display_clone.set_label("Waiting for card...");
+                input_display_clone.set_label(&tr("capture-waiting"));
+            }
+            
+            // Keep the stocktake count current while one runs
+            if let Some(count) = stocktake_progress() {
+                let label = tr_args("cc-stocktake-running", &[("count", &count.to_string())]);
+                if controls_timer.command_status.label() != label {
+                    controls_timer.command_status.set_label(&label);
+                }
+            }
+            
+            // Keep the review button's count current
+            let label = review_button_label(review::queue_len());
+            if review_btn.label() != label {
+                review_btn.set_label(&label);
             }
             
             // Continue checking - more frequently (50ms)
-            app::repeat_timeout(0.05, move || {
-                // This will be handled by the next invocation of the timer callback
-            });
+            app::repeat_timeout3(0.05, handle);
         });
         
         capture_wind.end();
+        
+        // Function keys run the same actions as the command cards
+        let mut controls_keys = controls.clone();
+        capture_wind.handle(move |_, event| {
+            if event != Event::KeyDown {
+                return false;
+            }
+            match action_for_key(app::event_key()) {
+                Some(action) => {
+                    run_command_feedback(action, &mut controls_keys);
+                    true
+                },
+                None => false,
+            }
+        });
+        
         capture_wind.show();
         
         let mut btn_clone = btn.clone();
-        let fifo_path_str = fifo_path.to_string();
         capture_wind.set_callback(move |w| {
-            // Clean up the timer when the window is closed
-            app::remove_timeout(|| {});
+            // Clean up the timer and the 125 kHz reader when the window is closed
+            app::remove_timeout3(timer_handle);
+            if let Some(reader) = lf_reader.borrow_mut().take() {
+                reader.stop();
+            }
+            status::set_connection(ReaderConnection::Idle);
             w.hide();
-            btn_clone.set_label("Start Capture");
+            btn_clone.set_label(&tr("capture-start"));
         });
         
     } else {
-        btn.set_label("Start Capture");
+        btn.set_label(&tr("capture-start"));
     }
 }
 
@@ -338,7 +383,350 @@ fn get_inventory_ui() -> Result<&'static InventoryUI, String> {
             // and ensure it lives for the duration of the program
             Ok(&*ptr)
         } else {
-            Err("Inventory system not initialized".to_string())
+            Err(tr("err-inventory-missing"))
+        }
+    }
+}
+
+// Decode keyboard wedge card data and queue it for the plugins
+fn submit_wedge_scan(worker: &PluginWorker<PendingScan>, card_data: &str, kb_layout: i32, reader: &str) {
+    let (timestamp, captured_at) = utils::get_timestamps();
+    status::record_scan(&captured_at);
+    let (hex_uid, manufacturer) = utils::process_uid_for_display(card_data, kb_layout);
+    
+    worker.submit(PluginScan {
+        raw: card_data.to_string(),
+        format: utils::interpret_format_code(card_data),
+        hex_uid,
+        manufacturer,
+        extra: Default::default(),
+    }, PendingScan {
+        reader: reader.to_string(),
+        kb_layout: Some(kb_layout),
+        timestamp,
+        captured_at,
+        decimal_uid: None,
+    });
+}
+
+// The record of a scan the plugins are done with
+fn build_scan_record(scan: &PluginScan, pending: &PendingScan) -> CardRecord {
+    // Wiegand badge fields are only read from wedge scans
+    let mut notes = Vec::new();
+    if pending.kb_layout.is_some() {
+        notes.push(tr_args("scan-badge", &[("badge", &wiegand::describe_badge(&scan.hex_uid))]));
+    }
+    notes.extend(plugins::extra_notes(scan));
+    
+    CardRecord {
+        timestamp: pending.timestamp.clone(),
+        captured_at: pending.captured_at.clone(),
+        raw_uid: scan.raw.clone(),
+        hex_uid: scan.hex_uid.clone(),
+        decimal_uid: pending.decimal_uid.clone().unwrap_or_else(|| utils::hex_to_decimal(&scan.hex_uid)),
+        manufacturer: scan.manufacturer.clone(),
+        format: scan.format.clone(),
+        notes,
+    }
+}
+
+// Note a scan dropped by the duplicate scan cooldown in its record
+fn mark_repeat(mut record: CardRecord, repeat: bool) -> CardRecord {
+    if repeat {
+        record.notes.push(tr("scan-repeat"));
+    }
+    record
+}
+
+// Run the action of a command card, returns whether the tag was one. A command card
+// held on the reader acts once per cooldown, like any other tag.
+fn handle_command_card(clean_tag_id: &str, repeat: bool, controls: &mut CaptureControls) -> bool {
+    let action = match find_command(clean_tag_id) {
+        Some(action) => action,
+        None => return false,
+    };
+    if !repeat {
+        run_command_feedback(action, controls);
+    }
+    true
+}
+
+// Run a command and report how it went on the buzzer/LED and in the capture window
+fn run_command_feedback(action: CommandAction, controls: &mut CaptureControls) {
+    let message = match run_command(action, controls) {
+        Ok(message) => {
+            feedback::signal(ScanFeedback::Success);
+            message
+        },
+        Err(message) => {
+            feedback::signal(ScanFeedback::Error);
+            message
+        },
+    };
+    controls.command_status.set_label(&format!("{}: {}", action.name(), message));
+}
+
+fn run_command(action: CommandAction, controls: &mut CaptureControls) -> Result<String, String> {
+    match action {
+        CommandAction::ToggleInventoryMode => {
+            let on = !controls.inventory_mode.is_checked();
+            controls.inventory_mode.set_checked(on);
+            Ok(tr(if on { "cc-on" } else { "cc-off" }))
+        },
+        CommandAction::ToggleItemForm => {
+            let on = !controls.show_form.is_checked();
+            controls.show_form.set_checked(on);
+            Ok(tr(if on { "cc-on" } else { "cc-off" }))
+        },
+        CommandAction::StartStocktake => {
+            start_stocktake();
+            Ok(tr("cc-stocktake-started"))
+        },
+        CommandAction::FinishStocktake => {
+            let inventory_ui = get_inventory_ui()?;
+            let items = inventory_ui.inventory_db.borrow().get_all_items().map_err(|e| e.to_string())?;
+            let aliases = inventory_ui.inventory_db.borrow().tag_aliases().map_err(|e| e.to_string())?;
+            let report = finish_stocktake(&items, &aliases).ok_or_else(|| tr("cc-no-stocktake"))?;
+            dialog::message(300, 300, &report.summary());
+            Ok(tr_args("cc-stocktake-finished", &[("missing", &report.missing.len().to_string())]))
+        },
+        CommandAction::ExportCsv | CommandAction::ExportJson => {
+            let format = if action == CommandAction::ExportCsv { ExportFormat::CSV } else { ExportFormat::JSON };
+            export_scans(format)
+                .map(|path| tr_args("cc-exported", &[("path", &path)]))
+                .map_err(|e| e.to_string())
+        },
+        CommandAction::ReceiveMode | CommandAction::IssueMode | CommandAction::CountMode => {
+            let mode = action.stock_mode().unwrap_or_default();
+            controls.stock_mode.set_value(mode.index());
+            Ok(mode.name())
+        },
+    }
+}
+
+// Give buzzer/LED feedback for a decoded scan and update the inventory if asked to
+fn handle_scan_result(clean_tag_id: &str, manufacturer: &str, inventory_mode: bool, show_form: bool, mode: StockMode, queue: bool) {
+    // An undecodable scan comes back as "Invalid format"
+    if clean_tag_id.is_empty() || !clean_tag_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        feedback::signal(ScanFeedback::Error);
+        return;
+    }
+    
+    // A running stocktake only counts the scan
+    if record_stocktake_scan(clean_tag_id) {
+        feedback::signal(ScanFeedback::Success);
+        return;
+    }
+    
+    if inventory_mode {
+        if queue && queue_for_review(clean_tag_id, mode) {
+            return;
+        }
+        // Matching scan rules take the place of the usual update
+        if !apply_scan_rules(clean_tag_id, manufacturer, mode) {
+            update_inventory_for_tag(clean_tag_id, show_form, mode);
+        }
+    } else {
+        feedback::signal(ScanFeedback::Success);
+    }
+}
+
+// Label of the capture window's review button
+fn review_button_label(count: usize) -> String {
+    tr_args("review-open", &[("count", &count.to_string())])
+}
+
+// Add a scan to the review list, returns whether it was queued. Kit and unit tags
+// aren't, they are booked as they are scanned.
+fn queue_for_review(clean_tag_id: &str, mode: StockMode) -> bool {
+    let inventory_ui = match get_inventory_ui() {
+        Ok(inventory_ui) => inventory_ui,
+        Err(_) => return false,
+    };
+    let db = inventory_ui.inventory_db.borrow();
+    let booked_directly = db.get_kit(clean_tag_id).map(|kit| kit.is_some()).unwrap_or(false)
+        || db.get_unit(clean_tag_id).map(|unit| unit.is_some()).unwrap_or(false);
+    if booked_directly {
+        return false;
+    }
+    // the database borrow ends before the dialog below runs
+    let queued = review::queue_scan(&db, clean_tag_id, mode);
+    drop(db);
+    
+    match queued {
+        Ok(entry) => {
+            feedback::signal(if entry.known { ScanFeedback::Success } else { ScanFeedback::UnknownTag });
+            true
+        },
+        Err(e) => {
+            feedback::signal(ScanFeedback::Error);
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+            true
+        }
+    }
+}
+
+// Run the scan rules for a tag, returns whether any matched
+fn apply_scan_rules(clean_tag_id: &str, manufacturer: &str, mode: StockMode) -> bool {
+    let rules = match APP_CONFIG.lock() {
+        Ok(config) => config.scan_rules.clone(),
+        Err(_) => return false,
+    };
+    if rules.is_empty() {
+        return false;
+    }
+    let inventory_ui = match get_inventory_ui() {
+        Ok(inventory_ui) => inventory_ui,
+        Err(_) => return false,
+    };
+    
+    let outcome = run_scan_rules(&rules, clean_tag_id, manufacturer, &inventory_ui.inventory_db.borrow());
+    if outcome.matched == 0 {
+        return false;
+    }
+    
+    feedback::signal(if outcome.errors.is_empty() { ScanFeedback::Success } else { ScanFeedback::Error });
+    if !outcome.errors.is_empty() {
+        dialog::alert(300, 300, &outcome.errors.join("\n"));
+    }
+    if outcome.open_form {
+        // Bind the lookup result first so the database borrow ends before the dialog runs
+        let existing = inventory_ui.inventory_db.borrow().get_item_by_any_tag(clean_tag_id);
+        match existing {
+            Ok(Some(item)) => show_item_update_dialog(inventory_ui, item, mode),
+            Ok(None) => show_new_item_dialog(inventory_ui, clean_tag_id.to_string(), manufacturer.to_string()),
+            Err(e) => dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())])),
+        }
+    }
+    true
+}
+
+// Look up a scanned tag in the inventory and book it in, out or count it as the mode says
+fn update_inventory_for_tag(clean_tag_id: &str, show_form: bool, mode: StockMode) {
+    let inventory_ui = match get_inventory_ui() {
+        Ok(inventory_ui) => inventory_ui,
+        Err(_) => {
+            feedback::signal(ScanFeedback::Error);
+            return;
+        }
+    };
+    
+    // A unit's tag moves that unit, there is no quantity to change
+    let unit_scan = units::scan_unit(&inventory_ui.inventory_db.borrow(), clean_tag_id, mode);
+    match unit_scan {
+        Ok(Some(scan)) if scan.accepted() => {
+            feedback::signal(ScanFeedback::Success);
+            inventory_ui.log_event(&scan.message());
+            return;
+        },
+        Ok(Some(scan)) => {
+            feedback::signal(ScanFeedback::Error);
+            dialog::alert(300, 300, &scan.message());
+            return;
+        },
+        Ok(None) => {},
+        Err(e) => {
+            feedback::signal(ScanFeedback::Error);
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+            return;
+        }
+    }
+    
+    // A kit's tag books all of its components at once
+    let kit_scan = kits::scan_kit(&inventory_ui.inventory_db.borrow(), clean_tag_id, mode);
+    match kit_scan {
+        Ok(Some(scan)) if scan.booked() => {
+            feedback::signal(ScanFeedback::Success);
+            inventory_ui.log_event(&scan.message());
+            if mode == StockMode::Count {
+                dialog::message(300, 300, &scan.message());
+            } else {
+                inventory_ui.refresh();
+            }
+            return;
+        },
+        Ok(Some(scan)) => {
+            feedback::signal(ScanFeedback::Error);
+            dialog::alert(300, 300, &scan.message());
+            return;
+        },
+        Ok(None) => {},
+        Err(e) => {
+            feedback::signal(ScanFeedback::Error);
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+            return;
+        }
+    }
+    
+    // Bind the lookup result first so the database borrow ends before any dialog runs.
+    // Any active tag of an item finds it, the item is updated by its own tag.
+    let existing = inventory_ui.inventory_db.borrow().get_item_by_any_tag(clean_tag_id);
+    // Feedback first, the dialogs below wait for the operator
+    feedback::signal(match &existing {
+        Ok(Some(_)) => ScanFeedback::Success,
+        Ok(None) => ScanFeedback::UnknownTag,
+        Err(_) => ScanFeedback::Error,
+    });
+    match existing {
+        Ok(Some(item)) => {
+            if show_form {
+                show_item_update_dialog(inventory_ui, item.clone(), mode);
+            } else {
+                let new_quantity = match mode {
+                    StockMode::Count => match ask_counted_quantity(&item) {
+                        Some(quantity) => quantity,
+                        None => return,
+                    },
+                    _ => mode.scanned_quantity(item.quantity),
+                };
+                if let Err(e) = inventory_ui.inventory_db.borrow().update_quantity_with_mode(&item.tag_id, new_quantity, Some(mode)) {
+                    feedback::signal(ScanFeedback::Error);
+                    dialog::alert(300, 300, &tr_args("err-scan-quantity", &[("error", &e.to_string())]));
+                } else {
+                    // a receipt also counts against the purchase order being received
+                    if new_quantity > item.quantity && mode == StockMode::Receive {
+                        let receipt = purchasing::record_receipt(
+                            &inventory_ui.inventory_db.borrow(), &item.tag_id, new_quantity - item.quantity
+                        );
+                        match receipt {
+                            Ok(Some(message)) => inventory_ui.log_event(&message),
+                            Ok(None) => {},
+                            Err(e) => dialog::alert(300, 300, &tr_args("err-capture-po", &[("error", &e.to_string())])),
+                        }
+                    }
+                    dialog::message(300, 300, &tr_args("msg-capture-quantity", &[("item", &item.name), ("quantity", &new_quantity.to_string())]));
+                }
+            }
+        },
+        // unknown tags wait on the triage list instead of interrupting the operator
+        Ok(None) => record_unknown_tag(inventory_ui, clean_tag_id),
+        Err(e) => {
+            dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())]));
+        }
+    }
+}
+
+// Count a scan of a tag that isn't in the inventory on the triage list, ignored tags quietly
+fn record_unknown_tag(inventory_ui: &InventoryUI, clean_tag_id: &str) {
+    let recorded = inventory_ui.inventory_db.borrow().record_unknown_scan(clean_tag_id);
+    match recorded {
+        Ok(tag) if tag.ignored => {},
+        Ok(tag) => inventory_ui.log_event(&tr_args("triage-recorded", &[
+            ("tag_id", &tag.tag_id),
+            ("scans", &tag.scans.to_string()),
+        ])),
+        Err(e) => dialog::alert(300, 300, &tr_args("err-load-items", &[("error", &e.to_string())])),
+    }
+}
+
+// Ask for the counted quantity of an item in count mode, None if cancelled or not a number
+fn ask_counted_quantity(item: &InventoryItem) -> Option<i32> {
+    let input = dialog::input(300, 300, &tr_args("mode-count-prompt", &[("item", &item.name)]), &item.quantity.to_string())?;
+    match input.trim().parse::<i32>() {
+        Ok(quantity) if quantity >= 0 => Some(quantity),
+        _ => {
+            dialog::alert(300, 300, &tr("err-count-quantity"));
+            None
         }
     }
 }
@@ -346,43 +734,43 @@ fn get_inventory_ui() -> Result<&'static InventoryUI, String> {
 // New function to show item creation dialog - Note: takes ownership of tag_id and manufacturer
 fn show_new_item_dialog(inventory_ui: &'static InventoryUI, tag_id: String, manufacturer: String) {
     // Create modal window
-    let mut win = Window::new(300, 200, 450, 450, "New Item");
+    let mut win = Window::new(300, 200, 450, 450, None).with_label(&tr("capture-new-title"));
     win.make_modal(true);
     
     // Add title
-    let mut title = Frame::new(0, 10, 450, 30, "Add New Inventory Item");
+    let mut title = Frame::new(0, 10, 450, 30, None).with_label(&tr("capture-new-heading"));
     title.set_label_font(Font::HelveticaBold);
     title.set_label_size(18);
     
     // Tag ID display
-    let tag_label = format!("Tag ID: {}", tag_id);
+    let tag_label = tr_args("form-tag", &[("tag_id", &tag_id)]);
     let mut tag_frame = Frame::new(20, 50, 410, 30, tag_label.as_str());
     tag_frame.set_label_font(Font::HelveticaBold);
     
     // Manufacturer display
-    let manuf_label = format!("Manufacturer: {}", manufacturer);
+    let manuf_label = tr_args("capture-manufacturer", &[("manufacturer", &manufacturer)]);
     Frame::new(20, 80, 410, 30, manuf_label.as_str());
     
     // Add form elements with labels
-    let mut name_input = Input::new(150, 120, 270, 30, "Name:");
-    let mut desc_input = MultilineInput::new(150, 160, 270, 70, "Description:");
-    let mut qty_input = Input::new(150, 240, 270, 30, "Quantity:");
+    let mut name_input = Input::new(150, 120, 270, 30, None).with_label(&tr("form-name"));
+    let mut desc_input = MultilineInput::new(150, 160, 270, 70, None).with_label(&tr("form-description"));
+    let mut qty_input = Input::new(150, 240, 270, 30, None).with_label(&tr("capture-quantity"));
     qty_input.set_value("1"); // Default quantity
     
-    let mut location_input = Input::new(150, 280, 270, 30, "Location:");
+    let mut location_input = Input::new(150, 280, 270, 30, None).with_label(&tr("form-location"));
     
-    let mut category_choice = Choice::new(150, 320, 270, 30, "Category:");
+    let mut category_choice = Choice::new(150, 320, 270, 30, None).with_label(&tr("form-category"));
     // Get categories from database and populate the dropdown
     if let Ok(categories_with_count) = inventory_ui.inventory_db.borrow().get_categories() {
-        category_choice.add_choice("Uncategorized");
+        category_choice.add_choice(&tr("form-uncategorized"));
         for (category, _) in categories_with_count {
             category_choice.add_choice(&category);
         }
     }
     
     // Add save and cancel buttons
-    let mut save_btn = Button::new(120, 380, 100, 40, "Save");
-    let mut cancel_btn = Button::new(230, 380, 100, 40, "Cancel");
+    let mut save_btn = Button::new(120, 380, 100, 40, None).with_label(&tr("btn-save"));
+    let mut cancel_btn = Button::new(230, 380, 100, 40, None).with_label(&tr("btn-cancel"));
     
     win.end();
     win.show();
@@ -399,7 +787,7 @@ fn show_new_item_dialog(inventory_ui: &'static InventoryUI, tag_id: String, manu
     save_btn.set_callback(move |_| {
         // Validate inputs
         if name_input_clone.value().is_empty() {
-            dialog::alert(300, 300, "Item name is required");
+            dialog::alert(300, 300, &tr("err-form-name"));
             return;
         }
         
@@ -435,9 +823,9 @@ fn show_new_item_dialog(inventory_ui: &'static InventoryUI, tag_id: String, manu
         
         // Save to database
         if let Err(e) = inventory_ui.inventory_db.borrow().save_item(&new_item) {
-            dialog::alert(300, 300, &format!("Error saving item: {}", e));
+            dialog::alert(300, 300, &tr_args("err-inv-save", &[("error", &e.to_string())]));
         } else {
-            dialog::message(300, 300, &format!("New item '{}' added to inventory", name_input_clone.value()));
+            dialog::message(300, 300, &tr_args("msg-scan-added", &[("item", &name_input_clone.value())]));
             win_copy.hide();
         }
     });
@@ -449,22 +837,18 @@ fn show_new_item_dialog(inventory_ui: &'static InventoryUI, tag_id: String, manu
 }
 
 // New function to show item update dialog - Note: takes ownership of the item
-fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryItem) {
+fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryItem, mode: StockMode) {
     // Create modal window
-    let mut win = Window::new(300, 200, 450, 500, "Update Item");
+    let mut win = Window::new(300, 200, 450, 500, None).with_label(&tr("capture-update-title"));
     win.make_modal(true);
     
     // Add title
-    let mut title = Frame::new(0, 10, 450, 30, "Update Inventory Item");
+    let mut title = Frame::new(0, 10, 450, 30, None).with_label(&tr("capture-update-heading"));
     title.set_label_font(Font::HelveticaBold);
     title.set_label_size(18);
     
     // Item information display
-    let info_text = format!(
-        "Item: {}\nTag ID: {}", 
-        item.name, 
-        item.tag_id
-    );
+    let info_text = tr_args("capture-item-info", &[("item", &item.name), ("tag_id", &item.tag_id)]);
     let mut info_frame = Frame::new(0, 40, 450, 60, info_text.as_str());
     info_frame.set_label_font(Font::HelveticaBold);
     
@@ -472,27 +856,28 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
     let form_group = Group::new(20, 110, 410, 300, "");
     
     // Current quantity display
-    let qty_text = format!("Current Quantity: {}", item.quantity);
+    let qty_text = tr_args("capture-current-quantity", &[("quantity", &item.quantity.to_string())]);
     Frame::new(20, 110, 410, 30, qty_text.as_str());
     
     // Quick quantity update controls
     let mut decrement_btn = Button::new(120, 150, 40, 40, "-");
     let mut increment_btn = Button::new(290, 150, 40, 40, "+");
     let mut new_qty_input = Input::new(170, 155, 110, 30, "");
-    new_qty_input.set_value(&item.quantity.to_string());
+    // Proposed from the capture mode, a count starts from the quantity on record
+    new_qty_input.set_value(&mode.scanned_quantity(item.quantity).to_string());
     
     // Location update
-    Frame::new(20, 200, 100, 30, "Location:");
+    Frame::new(20, 200, 100, 30, None).with_label(&tr("form-location"));
     let mut location_input = Input::new(120, 200, 310, 30, "");
     location_input.set_value(&item.location.clone().unwrap_or_default());
     
     // Category update
-    Frame::new(20, 240, 100, 30, "Category:");
+    Frame::new(20, 240, 100, 30, None).with_label(&tr("form-category"));
     let mut category_choice = Choice::new(120, 240, 310, 30, "");
     
     // Populate categories dropdown
     if let Ok(categories_with_count) = inventory_ui.inventory_db.borrow().get_categories() {
-        category_choice.add_choice("Uncategorized");
+        category_choice.add_choice(&tr("form-uncategorized"));
         let mut selected_index = 0;
         
         for (i, (category, _)) in categories_with_count.iter().enumerate() {
@@ -508,16 +893,16 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
     }
     
     // Description update
-    Frame::new(20, 280, 410, 20, "Description:");
+    Frame::new(20, 280, 410, 20, None).with_label(&tr("form-description"));
     let mut desc_input = MultilineInput::new(20, 300, 410, 80, "");
     desc_input.set_value(&item.description.clone().unwrap_or_default());
     
     form_group.end();
     
     // Add save, delete, and cancel buttons
-    let mut save_btn = Button::new(90, 400, 90, 40, "Save");
-    let mut delete_btn = Button::new(190, 400, 90, 40, "Delete");
-    let mut cancel_btn = Button::new(290, 400, 90, 40, "Cancel");
+    let mut save_btn = Button::new(90, 400, 90, 40, None).with_label(&tr("btn-save"));
+    let mut delete_btn = Button::new(190, 400, 90, 40, None).with_label(&tr("btn-delete"));
+    let mut cancel_btn = Button::new(290, 400, 90, 40, None).with_label(&tr("btn-cancel"));
     
     win.end();
     win.show();
@@ -543,6 +928,7 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
     let tag_id_for_save = item.tag_id.clone();
     let name = item.name.clone();
     let created_at = item.created_at.clone();
+    let expiry_date = item.expiry_date.clone();
     
     let new_qty_input_save = new_qty_input.clone();
     let location_input_save = location_input.clone();
@@ -563,6 +949,7 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
             category: None,
             last_updated: generate_timestamp(),
             created_at: created_at.clone(),
+            expiry_date: expiry_date.clone(),
         };
         
         // Set optional fields
@@ -587,10 +974,10 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
         };
         
         // Save to database
-        if let Err(e) = inventory_ui.inventory_db.borrow().save_item(&updated_item) {
-            dialog::alert(300, 300, &format!("Error updating item: {}", e));
+        if let Err(e) = inventory_ui.inventory_db.borrow().save_item_with_mode(&updated_item, Some(mode)) {
+            dialog::alert(300, 300, &tr_args("err-capture-update", &[("error", &e.to_string())]));
         } else {
-            dialog::message(300, 300, &format!("Item '{}' updated", name));
+            dialog::message(300, 300, &tr_args("msg-capture-updated", &[("item", &name)]));
             win_copy.hide();
         }
     });
@@ -599,12 +986,12 @@ fn show_item_update_dialog(inventory_ui: &'static InventoryUI, item: InventoryIt
     let mut win_delete = win.clone();
     let delete_tag_id = item.tag_id.clone();
     delete_btn.set_callback(move |_| {
-        if dialog::choice2(300, 300, "Are you sure you want to delete this item?", "No", "Yes", "") == Some(1) {
+        if dialog::choice2(300, 300, &tr("inv-trash-confirm"), &tr("btn-cancel"), &tr("inv-delete"), "") == Some(1) {
             // Delete from database
             if let Err(e) = inventory_ui.inventory_db.borrow().delete_item(&delete_tag_id) {
-                dialog::alert(300, 300, &format!("Error deleting item: {}", e));
+                dialog::alert(300, 300, &tr_args("err-trash-delete", &[("error", &e.to_string())]));
             } else {
-                dialog::message(300, 300, "Item deleted successfully");
+                dialog::message(300, 300, &tr("msg-inv-trashed"));
                 win_delete.hide();
             }
         }
@@ -0,0 +1,189 @@
+// reader/wedge_config.rs
+//
+// Configuration commands for USB keyboard wedge readers. Besides typing UIDs, most
+// wedge readers take vendor commands as HID reports on their hidraw device: buzzer
+// on/off, output format, LED behaviour. The command bytes differ per model and come
+// from the vendor's configuration tool or manual, so they live in an editable
+// profiles file, one profile per reader model:
+//
+//   [
+//     {
+//       "model": "Reader model name",
+//       "usb_id": "ffff:0035",
+//       "report_type": "feature",
+//       "commands": [
+//         { "name": "Buzzer off", "reports": ["01 02 03 ..."] }
+//       ]
+//     }
+//   ]
+//
+// A command is a sequence of reports sent in order. Profiles are matched to connected
+// readers by USB vendor:product ID.
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+
+use uid_codec::parse_hex;
+
+use crate::i18n::tr_args;
+
+/// Editable reader profiles
+pub const READER_PROFILES_PATH: &str = "reader_profiles.json";
+
+/// Pause between the reports of one command, readers often drop reports sent back to back
+const REPORT_DELAY: Duration = Duration::from_millis(50);
+
+/// How a reader takes its commands
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportType {
+    /// HID feature report (HIDIOCSFEATURE), what most configuration tools use
+    Feature,
+    /// Output report written to the hidraw device
+    Output,
+}
+
+fn default_report_type() -> ReportType {
+    ReportType::Feature
+}
+
+/// A named command, one or more reports in hex (first byte is the report ID)
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WedgeCommand {
+    pub name: String,
+    pub reports: Vec<String>,
+}
+
+impl WedgeCommand {
+    /// The reports as bytes, checked before anything is sent
+    pub fn report_bytes(&self) -> Result<Vec<Vec<u8>>, String> {
+        self.reports.iter()
+            .map(|report| parse_hex(report).map_err(|e| tr_args("rc-err-hex", &[("error", &e.to_string()), ("command", &self.name)])))
+            .collect()
+    }
+}
+
+/// Commands known for one reader model
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReaderProfile {
+    pub model: String,
+    /// USB vendor and product ID as "vvvv:pppp"
+    pub usb_id: String,
+    #[serde(default = "default_report_type")]
+    pub report_type: ReportType,
+    #[serde(default)]
+    pub commands: Vec<WedgeCommand>,
+}
+
+/// A HID device found under /sys/class/hidraw
+#[derive(Clone, Debug)]
+pub struct HidDevice {
+    /// e.g. /dev/hidraw0
+    pub path: String,
+    /// USB vendor and product ID as "vvvv:pppp"
+    pub usb_id: String,
+    pub name: String,
+}
+
+/// Load the profiles file, no file means no profiles yet
+pub fn load_profiles() -> Result<Vec<ReaderProfile>, String> {
+    if !Path::new(READER_PROFILES_PATH).exists() {
+        return Ok(Vec::new());
+    }
+    let data = fs::read_to_string(READER_PROFILES_PATH)
+        .map_err(|e| tr_args("err-read-file-path", &[("path", READER_PROFILES_PATH), ("error", &e.to_string())]))?;
+    serde_json::from_str(&data)
+        .map_err(|e| tr_args("rc-err-parse", &[("path", READER_PROFILES_PATH), ("error", &e.to_string())]))
+}
+
+pub fn save_profiles(profiles: &[ReaderProfile]) -> io::Result<()> {
+    let data = serde_json::to_string_pretty(profiles)?;
+    fs::write(READER_PROFILES_PATH, data)
+}
+
+/// Profile for a USB ID, if there is one
+pub fn find_profile<'a>(profiles: &'a [ReaderProfile], usb_id: &str) -> Option<&'a ReaderProfile> {
+    profiles.iter().find(|profile| profile.usb_id.eq_ignore_ascii_case(usb_id))
+}
+
+// HID_ID=0003:0000FFFF:00000035 (bus:vendor:product) to "ffff:0035"
+fn usb_id_from_hid_id(hid_id: &str) -> Option<String> {
+    let mut parts = hid_id.split(':');
+    let _bus = parts.next()?;
+    let vendor = u32::from_str_radix(parts.next()?, 16).ok()?;
+    let product = u32::from_str_radix(parts.next()?, 16).ok()?;
+    Some(format!("{:04x}:{:04x}", vendor & 0xFFFF, product & 0xFFFF))
+}
+
+/// Connected HID devices, sorted by device path
+pub fn list_hid_devices() -> Vec<HidDevice> {
+    let entries = match fs::read_dir("/sys/class/hidraw") {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut devices: Vec<HidDevice> = entries.flatten()
+        .filter_map(|entry| {
+            let node = entry.file_name().to_string_lossy().to_string();
+            let uevent = fs::read_to_string(entry.path().join("device/uevent")).ok()?;
+            let mut usb_id = None;
+            let mut name = String::new();
+            for line in uevent.lines() {
+                if let Some(hid_id) = line.strip_prefix("HID_ID=") {
+                    usb_id = usb_id_from_hid_id(hid_id);
+                } else if let Some(hid_name) = line.strip_prefix("HID_NAME=") {
+                    name = hid_name.to_string();
+                }
+            }
+            Some(HidDevice { path: format!("/dev/{}", node), usb_id: usb_id?, name })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.path.cmp(&b.path));
+    devices
+}
+
+// HIDIOCSFEATURE(len) from linux/hidraw.h: _IOC(_IOC_WRITE | _IOC_READ, 'H', 0x06, len)
+fn hidiocsfeature(len: usize) -> libc::c_ulong {
+    (3 << 30) | ((len as libc::c_ulong) << 16) | ((b'H' as libc::c_ulong) << 8) | 0x06
+}
+
+/// Send a command's reports to a reader
+pub fn send_command(device_path: &str, report_type: ReportType, command: &WedgeCommand) -> Result<(), String> {
+    let reports = command.report_bytes()?;
+    let mut device = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| tr_args("rc-err-open", &[("path", device_path), ("error", &e.to_string())]))?;
+
+    for (index, report) in reports.iter().enumerate() {
+        if index > 0 {
+            thread::sleep(REPORT_DELAY);
+        }
+        match report_type {
+            ReportType::Output => {
+                device.write_all(report)
+                    .map_err(|e| tr_args("rc-err-write", &[("report", &(index + 1).to_string()), ("path", device_path), ("error", &e.to_string())]))?;
+            },
+            ReportType::Feature => {
+                let mut buffer = report.clone();
+                // the buffer is valid for the length encoded in the request
+                let result = unsafe {
+                    libc::ioctl(device.as_raw_fd(), hidiocsfeature(buffer.len()) as _, buffer.as_mut_ptr())
+                };
+                if result < 0 {
+                    return Err(tr_args("rc-err-feature", &[
+                        ("report", &(index + 1).to_string()),
+                        ("path", device_path),
+                        ("error", &io::Error::last_os_error().to_string()),
+                    ]));
+                }
+            },
+        }
+    }
+    Ok(())
+}
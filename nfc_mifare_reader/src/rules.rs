@@ -0,0 +1,338 @@
+// rules.rs
+//
+// Scan rules: "when a scanned tag matches a condition, do an action". Rules are
+// edited in Preferences and checked in order against every scan in inventory mode.
+// Every matching rule runs; when none match the scan is handled as before.
+//
+// Webhooks are POSTed with curl and shell commands run through sh, both in the
+// background. The scan is handed to them as JSON in the request body, and as the
+// TAG_ID, MANUFACTURER and ITEM_NAME environment variables for commands:
+//   {"tag_id": "04A1B2C3", "manufacturer": "...", "item": "...", "quantity": 3, "timestamp": "..."}
+use std::process::{Command, Stdio};
+use std::thread;
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+
+use crate::i18n::{tr, tr_args};
+use crate::inventory::db::InventoryDB;
+use crate::inventory::expiry;
+use crate::inventory::model::{generate_timestamp, InventoryItem, StockMode};
+
+/// A condition on a scan and what to do when it holds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ScanRule {
+    pub name: String,
+    pub condition: RuleCondition,
+    pub action: RuleAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RuleCondition {
+    AnyTag,
+    // Regex matched against the hex tag ID without spaces
+    TagMatches(String),
+    // Case-insensitive part of the manufacturer name
+    Manufacturer(String),
+    Category(String),
+    Location(String),
+    KnownTag,
+    UnknownTag,
+    // The item expires in this many days or fewer, or has expired
+    ExpiresWithin(u32),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum RuleAction {
+    Increment(i32),
+    Decrement(i32),
+    SetLocation(String),
+    OpenForm,
+    Webhook(String),
+    ShellCommand(String),
+}
+
+/// What a scan is matched on
+pub struct ScanContext<'a> {
+    pub tag_id: &'a str,
+    pub manufacturer: &'a str,
+    pub item: Option<&'a InventoryItem>,
+}
+
+/// What running the rules for one scan did
+#[derive(Default, Debug)]
+pub struct RulesOutcome {
+    pub matched: usize,
+    pub open_form: bool,
+    pub errors: Vec<String>,
+}
+
+// Translation keys of the condition and action kinds, in Preferences order
+const CONDITION_KEYS: [&str; 8] = [
+    "rule-any-tag", "rule-tag-matches", "rule-manufacturer", "rule-category",
+    "rule-location", "rule-known-tag", "rule-unknown-tag", "rule-expires-within",
+];
+const ACTION_KEYS: [&str; 6] = [
+    "rule-increment", "rule-decrement", "rule-set-location",
+    "rule-open-form", "rule-webhook", "rule-shell",
+];
+
+impl RuleCondition {
+    /// Names of the condition kinds for the Preferences choice
+    pub fn kind_names() -> Vec<String> {
+        CONDITION_KEYS.iter().map(|key| tr(key)).collect()
+    }
+
+    pub fn kind_index(&self) -> i32 {
+        match self {
+            RuleCondition::AnyTag => 0,
+            RuleCondition::TagMatches(_) => 1,
+            RuleCondition::Manufacturer(_) => 2,
+            RuleCondition::Category(_) => 3,
+            RuleCondition::Location(_) => 4,
+            RuleCondition::KnownTag => 5,
+            RuleCondition::UnknownTag => 6,
+            RuleCondition::ExpiresWithin(_) => 7,
+        }
+    }
+
+    /// The text a condition compares with, empty for those without one
+    pub fn value(&self) -> String {
+        match self {
+            RuleCondition::TagMatches(value)
+            | RuleCondition::Manufacturer(value)
+            | RuleCondition::Category(value)
+            | RuleCondition::Location(value) => value.clone(),
+            RuleCondition::ExpiresWithin(days) => days.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    /// Build a condition from the Preferences fields, checking the regex and the days
+    pub fn from_parts(kind: i32, value: &str) -> Result<RuleCondition, String> {
+        let value = value.trim().to_string();
+        if value.is_empty() && matches!(kind, 1..=4 | 7) {
+            return Err(tr("err-rule-value"));
+        }
+        let condition = match kind {
+            0 => RuleCondition::AnyTag,
+            1 => {
+                Regex::new(&value).map_err(|e| tr_args("pat-err-invalid", &[("error", &e.to_string())]))?;
+                RuleCondition::TagMatches(value)
+            },
+            2 => RuleCondition::Manufacturer(value),
+            3 => RuleCondition::Category(value),
+            4 => RuleCondition::Location(value),
+            5 => RuleCondition::KnownTag,
+            7 => RuleCondition::ExpiresWithin(value.parse().map_err(|_| tr("err-rule-days"))?),
+            _ => RuleCondition::UnknownTag,
+        };
+        Ok(condition)
+    }
+
+    pub fn matches(&self, scan: &ScanContext) -> bool {
+        match self {
+            RuleCondition::AnyTag => true,
+            RuleCondition::TagMatches(pattern) => Regex::new(pattern)
+                .map(|re| re.is_match(scan.tag_id))
+                .unwrap_or(false),
+            RuleCondition::Manufacturer(name) => scan.manufacturer
+                .to_lowercase()
+                .contains(&name.to_lowercase()),
+            RuleCondition::Category(category) => scan.item
+                .and_then(|item| item.category.as_deref())
+                .is_some_and(|c| c.eq_ignore_ascii_case(category)),
+            RuleCondition::Location(location) => scan.item
+                .and_then(|item| item.location.as_deref())
+                .is_some_and(|l| l.eq_ignore_ascii_case(location)),
+            RuleCondition::KnownTag => scan.item.is_some(),
+            RuleCondition::UnknownTag => scan.item.is_none(),
+            RuleCondition::ExpiresWithin(days) => scan.item
+                .and_then(|item| expiry::days_left(item, expiry::today()))
+                .is_some_and(|left| left <= i64::from(*days)),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        if let RuleCondition::ExpiresWithin(days) = self {
+            return tr_args("rule-expires-within-days", &[("days", &days.to_string())]);
+        }
+        let name = tr(CONDITION_KEYS[self.kind_index() as usize]);
+        match self.value() {
+            value if value.is_empty() => name,
+            value => format!("{} '{}'", name, value),
+        }
+    }
+}
+
+impl RuleAction {
+    /// Names of the action kinds for the Preferences choice
+    pub fn kind_names() -> Vec<String> {
+        ACTION_KEYS.iter().map(|key| tr(key)).collect()
+    }
+
+    pub fn kind_index(&self) -> i32 {
+        match self {
+            RuleAction::Increment(_) => 0,
+            RuleAction::Decrement(_) => 1,
+            RuleAction::SetLocation(_) => 2,
+            RuleAction::OpenForm => 3,
+            RuleAction::Webhook(_) => 4,
+            RuleAction::ShellCommand(_) => 5,
+        }
+    }
+
+    /// The amount, location, URL or command of an action
+    pub fn value(&self) -> String {
+        match self {
+            RuleAction::Increment(amount) | RuleAction::Decrement(amount) => amount.to_string(),
+            RuleAction::SetLocation(value)
+            | RuleAction::Webhook(value)
+            | RuleAction::ShellCommand(value) => value.clone(),
+            RuleAction::OpenForm => String::new(),
+        }
+    }
+
+    /// Build an action from the Preferences fields, an empty amount counts as 1
+    pub fn from_parts(kind: i32, value: &str) -> Result<RuleAction, String> {
+        let value = value.trim().to_string();
+        let amount = || match value.as_str() {
+            "" => Ok(1),
+            text => text.parse::<i32>()
+                .ok()
+                .filter(|amount| *amount > 0)
+                .ok_or_else(|| tr("err-rule-amount")),
+        };
+        let action = match kind {
+            0 => RuleAction::Increment(amount()?),
+            1 => RuleAction::Decrement(amount()?),
+            2 => RuleAction::SetLocation(value),
+            3 => RuleAction::OpenForm,
+            4 => RuleAction::Webhook(value),
+            _ => RuleAction::ShellCommand(value),
+        };
+        if action.value().is_empty() && matches!(kind, 2 | 4 | 5) {
+            return Err(tr("err-rule-value"));
+        }
+        Ok(action)
+    }
+
+    pub fn describe(&self) -> String {
+        let name = tr(ACTION_KEYS[self.kind_index() as usize]);
+        match self {
+            RuleAction::Increment(amount) | RuleAction::Decrement(amount) => format!("{} {}", name, amount),
+            RuleAction::OpenForm => name,
+            _ => format!("{} '{}'", name, self.value()),
+        }
+    }
+}
+
+impl ScanRule {
+    /// One line for the Preferences list
+    pub fn describe(&self) -> String {
+        tr_args("rule-line", &[
+            ("name", &self.name),
+            ("condition", &self.condition.describe()),
+            ("action", &self.action.describe()),
+        ])
+    }
+}
+
+/// Run every rule whose condition holds for the scan, in order
+pub fn run_scan_rules(rules: &[ScanRule], tag_id: &str, manufacturer: &str, db: &InventoryDB) -> RulesOutcome {
+    let mut outcome = RulesOutcome::default();
+    // conditions see the item as it was scanned, before any rule changed it. Any
+    // active tag of an item finds it.
+    let item = match db.get_item_by_any_tag(tag_id) {
+        Ok(item) => item,
+        Err(e) => {
+            outcome.errors.push(e.to_string());
+            return outcome;
+        }
+    };
+    let scan = ScanContext { tag_id, manufacturer, item: item.as_ref() };
+
+    for rule in rules.iter().filter(|rule| rule.condition.matches(&scan)) {
+        outcome.matched += 1;
+        if let Err(e) = run_action(&rule.action, &scan, db, &mut outcome) {
+            outcome.errors.push(format!("{}: {}", rule.name, e));
+        }
+    }
+    outcome
+}
+
+fn run_action(action: &RuleAction, scan: &ScanContext, db: &InventoryDB, outcome: &mut RulesOutcome) -> Result<(), String> {
+    match action {
+        RuleAction::Increment(amount) | RuleAction::Decrement(amount) => {
+            // re-read, an earlier rule may have changed the quantity
+            let item = db.get_item_by_any_tag(scan.tag_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| tr("err-rule-unknown-tag"))?;
+            let quantity = match action {
+                RuleAction::Increment(_) => item.quantity.saturating_add(*amount),
+                _ => (item.quantity - amount).max(0),
+            };
+            let mode = match action {
+                RuleAction::Increment(_) => StockMode::Receive,
+                _ => StockMode::Issue,
+            };
+            db.update_quantity_with_mode(&item.tag_id, quantity, Some(mode)).map_err(|e| e.to_string())?;
+        },
+        RuleAction::SetLocation(location) => {
+            let mut item = db.get_item_by_any_tag(scan.tag_id)
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| tr("err-rule-unknown-tag"))?;
+            item.location = Some(location.clone());
+            item.last_updated = generate_timestamp();
+            db.save_item(&item).map_err(|e| e.to_string())?;
+        },
+        RuleAction::OpenForm => outcome.open_form = true,
+        RuleAction::Webhook(url) => {
+            let mut command = Command::new("curl");
+            command
+                .args(["-fsS", "-m", "10", "-X", "POST", "-H", "Content-Type: application/json", "-d"])
+                .arg(scan_json(scan))
+                .arg(url);
+            spawn_background(command)?;
+        },
+        RuleAction::ShellCommand(shell_command) => {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                .arg(shell_command)
+                .env("TAG_ID", scan.tag_id)
+                .env("MANUFACTURER", scan.manufacturer)
+                .env("ITEM_NAME", scan.item.map(|item| item.name.as_str()).unwrap_or(""));
+            spawn_background(command)?;
+        },
+    }
+    Ok(())
+}
+
+fn scan_json(scan: &ScanContext) -> String {
+    serde_json::json!({
+        "tag_id": scan.tag_id,
+        "manufacturer": scan.manufacturer,
+        "item": scan.item.map(|item| item.name.clone()),
+        "quantity": scan.item.map(|item| item.quantity),
+        "timestamp": generate_timestamp(),
+    }).to_string()
+}
+
+// Start a webhook or command without holding up the scan, a thread reaps it and
+// reports a failure on the console since the scan has moved on by then
+fn spawn_background(mut command: Command) -> Result<(), String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+    thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => {
+            eprintln!("{}", tr_args("err-rule-exit", &[("status", &status.to_string())]));
+        },
+        Err(e) => eprintln!("{}", tr_args("err-rule-exit", &[("status", &e.to_string())])),
+        _ => {},
+    });
+    Ok(())
+}
@@ -0,0 +1,128 @@
+// shutdown.rs
+//
+// Leaving the app the same way however it ends. Closing the window, the Exit menu
+// item, SIGTERM (a session logging out, systemd stopping a kiosk) and SIGINT all end
+// the event loop, after which shut_down() turns the buzzer and LED off, closes the
+// scan history and checkpoints inventory.db so no change is left only in its
+// write-ahead log. A panic writes a crash report next to the settings file, with the
+// scans of the session that the history may not have stored, then does the same.
+use std::backtrace::Backtrace;
+use std::fs;
+use std::io::Write;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use fltk::app;
+
+use crate::config;
+use crate::history;
+use crate::i18n::{tr, tr_args};
+use crate::inventory;
+use crate::reader::{feedback, scan_log};
+
+/// How often the event loop looks for a termination signal, in seconds
+const SIGNAL_POLL: f64 = 0.25;
+
+static TERMINATION_REQUESTED: AtomicBool = AtomicBool::new(false);
+static RESOURCES_RELEASED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn request_termination(_signal: libc::c_int) {
+    // Only an atomic store is safe in a signal handler, the event loop does the rest
+    TERMINATION_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// End the event loop on SIGTERM, SIGINT and SIGHUP instead of dying on the spot
+pub fn install_signal_handlers() {
+    let handler = request_termination as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    for signal in [libc::SIGTERM, libc::SIGINT, libc::SIGHUP] {
+        // SAFETY: the handler only stores to an atomic
+        unsafe {
+            libc::signal(signal, handler);
+        }
+    }
+
+    app::add_timeout3(SIGNAL_POLL, |handle| {
+        if TERMINATION_REQUESTED.load(Ordering::SeqCst) {
+            app::quit();
+        } else {
+            app::repeat_timeout3(SIGNAL_POLL, handle);
+        }
+    });
+}
+
+/// Write a crash report on any panic, and release the hardware and database when the
+/// main thread panics
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        // Plain English, the catalogue may be what panicked
+        match write_crash_report(info) {
+            Ok(path) => eprintln!("Crash report written to {}", path.display()),
+            Err(e) => eprintln!("Error writing the crash report: {}", e),
+        }
+        // A panic in a background thread leaves the app running with what it holds
+        if std::thread::current().name() == Some("main") {
+            if let Err(e) = release_resources() {
+                eprintln!("Error flushing {}: {}", inventory::DB_PATH, e);
+            }
+        }
+    }));
+}
+
+/// Release everything the app holds, once the event loop has ended
+pub fn shut_down() {
+    println!("{}", tr("log-shutting-down"));
+    if let Err(e) = release_resources() {
+        println!("{}", tr_args("err-db-flush", &[("path", inventory::DB_PATH), ("error", &e.to_string())]));
+    }
+}
+
+// Turn the outputs off, close the history and checkpoint the database. Only the first
+// call does anything, a panic during shutdown doesn't run it again.
+fn release_resources() -> rusqlite::Result<()> {
+    if RESOURCES_RELEASED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    feedback::shut_down();
+    history::close_history();
+    inventory::db::checkpoint(inventory::DB_PATH)
+}
+
+/// Folder the crash reports go to, next to the settings file
+pub fn crash_report_dir() -> PathBuf {
+    config::file::config_path()
+        .parent()
+        .map(|folder| folder.join("crash-reports"))
+        .unwrap_or_else(|| PathBuf::from("crash-reports"))
+}
+
+// this writes what panicked, where, the backtrace and the session's scans
+fn write_crash_report(info: &PanicHookInfo) -> std::io::Result<PathBuf> {
+    let now = chrono::Local::now();
+    let folder = crash_report_dir();
+    fs::create_dir_all(&folder)?;
+    let path = folder.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+
+    let message = info.payload().downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".to_string());
+    let location = info.location()
+        .map(|location| format!("{}:{}", location.file(), location.line()))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    let mut report = fs::File::create(&path)?;
+    writeln!(report, "{} {} crashed at {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), now.format("%Y-%m-%d %H:%M:%S"))?;
+    writeln!(report, "Thread: {}", std::thread::current().name().unwrap_or("unnamed"))?;
+    writeln!(report, "Panic: {}", message)?;
+    writeln!(report, "Location: {}\n", location)?;
+    writeln!(report, "Backtrace:\n{}\n", Backtrace::force_capture())?;
+
+    // A panic while the scans are being changed leaves them locked, they are skipped then
+    let scans = scan_log::try_session_scans().unwrap_or_default();
+    writeln!(report, "Scans this session: {}", scans.len())?;
+    for scan in scans {
+        writeln!(report, "{}\t{}\t{}", scan.timestamp, scan.hex_uid, scan.raw_uid)?;
+    }
+    Ok(path)
+}
@@ -0,0 +1,128 @@
+// signing.rs
+//
+// Ed25519 signatures for exported files, so whoever receives an export can check
+// nothing in it changed on the way. The app keeps one key pair, made the first time
+// a file is signed. A signature goes in "<file>.sig" beside the file, with the public
+// key that made it; a recipient checks that key against the one the station gave
+// them (Show Signing Key), otherwise anyone could sign a changed file with their own.
+// The files are the ones the file_signing crate reads and writes for the block editor
+// too; this module adds the settings, the translated messages and --verify.
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+use file_signing::SigningError;
+
+use crate::config;
+use crate::i18n::{tr, tr_args};
+
+/// The station's secret key, readable by its owner only
+pub const SIGNING_KEY_PATH: &str = "mifare_reader_signing.key";
+
+/// A signature checked against the file it signs
+pub struct Verification {
+    /// Public key that made the signature, as hex
+    pub public_key: String,
+    /// Whether that is this station's own key
+    pub own_key: bool,
+}
+
+/// This station's public key as hex, the key pair is made when there is none yet
+pub fn public_key() -> io::Result<String> {
+    Ok(file_signing::public_key_hex(&signing_key()?))
+}
+
+/// Sign a file, writing its signature beside it
+pub fn sign_file(path: &Path) -> io::Result<PathBuf> {
+    file_signing::sign_file(path, &signing_key()?)
+}
+
+/// Sign an export when the settings ask for it, a failure is only logged so the
+/// export itself still counts
+pub fn sign_export(path: &Path) -> Option<PathBuf> {
+    let enabled = config::APP_CONFIG.lock().map(|config| config.sign_exports).unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+
+    match sign_file(path) {
+        Ok(sig_path) => {
+            println!("{}", tr_args("log-export-signed", &[("path", &sig_path.display().to_string())]));
+            Some(sig_path)
+        },
+        Err(e) => {
+            println!("{}", tr_args("err-sign-export", &[
+                ("path", &path.display().to_string()),
+                ("error", &e.to_string()),
+            ]));
+            None
+        }
+    }
+}
+
+/// Check a file against the signature beside it
+pub fn verify_file(path: &Path) -> Result<Verification, String> {
+    let public_key = file_signing::verify_file(path).map_err(|e| message(&e))?;
+    let own_key = file_signing::load_key(Path::new(SIGNING_KEY_PATH))
+        .map(|key| file_signing::public_key_hex(&key) == public_key)
+        .unwrap_or(false);
+    Ok(Verification { public_key, own_key })
+}
+
+/// `--verify FILE [--key PUBLIC_KEY]` from the command line, gives the exit code.
+/// Without --key the signer's key is printed to compare by hand.
+pub fn verify_command(args: &[String]) -> i32 {
+    let (file, expected) = match args {
+        [file] => (file, None),
+        [file, flag, key] if flag == "--key" => (file, Some(key.replace([' ', ':'], "").to_uppercase())),
+        _ => {
+            eprintln!("{}", tr("usage-verify"));
+            return 2;
+        }
+    };
+
+    match verify_file(Path::new(file)) {
+        Ok(verification) => match expected {
+            Some(expected) if expected != verification.public_key => {
+                println!("{}", tr_args("err-signature-other-key", &[("key", &verification.public_key)]));
+                1
+            },
+            _ => {
+                println!("{}", tr_args("msg-signature-valid", &[("key", &verification.public_key)]));
+                0
+            }
+        },
+        Err(e) => {
+            println!("{}", e);
+            1
+        }
+    }
+}
+
+// this loads the station's key, or makes and saves one the first time
+fn signing_key() -> io::Result<SigningKey> {
+    match file_signing::load_or_create_key(Path::new(SIGNING_KEY_PATH)) {
+        Ok((key, created)) => {
+            if created {
+                println!("{}", tr_args("log-signing-key-created", &[("path", SIGNING_KEY_PATH)]));
+            }
+            Ok(key)
+        },
+        Err(SigningError::Io(e)) => Err(e),
+        Err(e) => Err(io::Error::new(io::ErrorKind::InvalidData, message(&e))),
+    }
+}
+
+// A signing error in the language of the app
+fn message(error: &SigningError) -> String {
+    match error {
+        SigningError::Io(e) => e.to_string(),
+        SigningError::InvalidKey(path) => tr_args("err-signing-key", &[("path", &path.display().to_string())]),
+        SigningError::SignatureUnreadable(path, e) => tr_args("err-signature-read", &[
+            ("path", &path.display().to_string()),
+            ("error", &e.to_string()),
+        ]),
+        SigningError::InvalidSignature(path) => tr_args("err-signature-format", &[("path", &path.display().to_string())]),
+        SigningError::Mismatch => tr("err-signature-mismatch"),
+    }
+}
@@ -1,11 +1,10 @@
 // file_sync.rs
-use notify::{Watcher, RecursiveMode, DebouncedEvent, watcher};
-use std::sync::mpsc::channel;
-use std::time::Duration;
 use std::path::{Path, PathBuf};
 use std::fs;
 use chrono::Local;
-use std::thread;
+use progress_sink::StatusFile;
+use crate::i18n::tr_args;
+use crate::inventory::import::{import_json, write_error_report, ImportError, ImportFailure};
 use crate::inventory::InventoryUI;
 
 
@@ -13,7 +12,6 @@ pub struct FileSync {
     import_path: String,
     processed_path: String,
     error_path: String,
-    is_running: bool,
 }
 
 impl FileSync {
@@ -33,90 +31,11 @@ impl FileSync {
             import_path: import_path.to_string(),
             processed_path: processed_path.to_string(),
             error_path: error_path.to_string(),
-            is_running: false,
         }
     }
     
-    pub fn start(&mut self) -> Result<(), String> {
-        if self.is_running {
-            println!("File sync already running");
-            return Ok(());
-        }
-        
-        self.is_running = true;
-        
-        // Process any existing files in the import directory
-        if let Ok(entries) = fs::read_dir(&self.import_path) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    if self.should_process_file(&entry.path()) {
-                        println!("Found existing file to process: {:?}", entry.path());
-                        // Instead of processing directly here, we'll move it to a "pending" directory
-                        // and let the main thread handle it on next iteration
-                    }
-                }
-            }
-        }
-        
-        // Set up file watcher that just sends notifications, doesn't process
-        let import_path = self.import_path.clone();
-        let processed_path = self.processed_path.clone();
-        let error_path = self.error_path.clone();
-        
-        // Create a channel for sending file notifications back to the main thread
-        let (tx, _rx) = channel();
-        let watcher_tx = tx.clone();
-        
-        // Create the watcher in its own thread
-        thread::spawn(move || {
-            let (watch_tx, watch_rx) = channel();
-            let mut watcher = match watcher(watch_tx, Duration::from_secs(2)) {
-                Ok(w) => w,
-                Err(e) => {
-                    println!("Error creating watcher: {}", e);
-                    return;
-                }
-            };
-            
-            if let Err(e) = watcher.watch(&import_path, RecursiveMode::Recursive) {
-                println!("Error watching directory: {}", e);
-                return;
-            }
-            
-            println!("Watching for new files in: {}", import_path);
-            
-            loop {
-                match watch_rx.recv() {
-                    Ok(event) => {
-                        match event {
-                            DebouncedEvent::Create(path) | DebouncedEvent::Write(path) => {
-                                if path.extension().map_or(false, |ext| ext == "json") {
-                                    // Allow a small delay to ensure file is fully written
-                                    thread::sleep(Duration::from_millis(500));
-                                    
-                                    println!("New file detected: {:?}", path);
-                                    
-                                    // Just notify the main thread about the file
-                                    let _ = watcher_tx.send(path);
-                                }
-                            },
-                            _ => {}
-                        }
-                    },
-                    Err(e) => {
-                        println!("Watch error: {:?}", e);
-                        break;
-                    }
-                }
-            }
-        });
-        
-        // Return the receiver for the main thread to poll
-        Ok(())
-    }
-    
     fn should_process_file(&self, path: &Path) -> bool {
-        path.extension().map_or(false, |ext| ext == "json")
+        is_import_file(path)
     }
     
     // New method to get list of files to process
@@ -137,8 +56,8 @@ impl FileSync {
         files
     }
     
-    // Process a single file and move it to appropriate directory
-    pub fn process_file(&self, path: &Path, success: bool) -> Result<(), String> {
+    // Process a single file and move it to appropriate directory, giving where it went
+    pub fn process_file(&self, path: &Path, success: bool) -> Result<PathBuf, String> {
         let file_name = match path.file_name() {
             Some(name) => name.to_str().unwrap_or("unknown.json"),
             None => return Err("Invalid file path".to_string())
@@ -158,54 +77,90 @@ impl FileSync {
         }
         
         println!("File moved to: {:?}", dest_path);
-        Ok(())
+        Ok(dest_path)
+    }
+}
+
+/// Whether a file in the import directory is one the inventory can import
+pub fn is_import_file(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "json")
+}
+
+/// What became of one file from the import directory
+pub struct ImportedFile {
+    pub name: String,
+    /// Items imported, or why none were
+    pub result: Result<usize, ImportFailure>,
+    /// Problems of a failed import, written beside the file in the error directory
+    pub report: Option<PathBuf>,
+}
+
+impl ImportedFile {
+    /// One line saying how the import went
+    pub fn message(&self) -> String {
+        match (&self.result, &self.report) {
+            (Ok(count), _) => tr_args("log-import-file", &[
+                ("file", &self.name),
+                ("count", &count.to_string()),
+            ]),
+            (Err(e), Some(report)) => tr_args("err-import-file-report", &[
+                ("file", &self.name),
+                ("error", &e.to_string()),
+                ("report", &report.display().to_string()),
+            ]),
+            (Err(e), None) => tr_args("err-import-file", &[
+                ("file", &self.name),
+                ("error", &e.to_string()),
+            ]),
+        }
     }
 }
 
+/// Import one file from the import directory in a single transaction and move it
+/// to the processed or error directory, a file that fails gets an error report
+pub fn import_file(
+    file_sync: &FileSync,
+    file_path: &Path,
+    inventory_ui: &InventoryUI
+) -> ImportedFile {
+    let name = file_path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let result = fs::read_to_string(file_path)
+        .map_err(|e| ImportFailure::from(ImportError::file(e)))
+        .and_then(|contents| import_json(&inventory_ui.inventory_db.borrow(), &contents, &StatusFile::new("import")));
+
+    // Move file to processed or error directory
+    let moved_to = match file_sync.process_file(file_path, result.is_ok()) {
+        Ok(dest_path) => dest_path,
+        Err(e) => {
+            eprintln!("Error moving imported file: {}", e);
+            file_path.to_path_buf()
+        }
+    };
+
+    let mut report = None;
+    if let Err(failure) = &result {
+        match write_error_report(&moved_to, failure) {
+            Ok(report_path) => report = Some(report_path),
+            Err(e) => eprintln!("Error writing import report: {}", e),
+        }
+    }
+
+    ImportedFile { name, result, report }
+}
+
+/// Import every file waiting in the import directory, each in its own transaction
 pub fn check_for_import_files(
     import_dir: &str,
     processed_dir: &str, 
     error_dir: &str,
     inventory_ui: &std::rc::Rc<crate::inventory::InventoryUI>
-) -> Result<usize, String> {
+) -> Result<Vec<ImportedFile>, String> {
     let file_sync = FileSync::new(import_dir, processed_dir, error_dir);
     let pending_files = file_sync.get_pending_files();
     
-    let mut processed_count = 0;
-    
-    for file_path in pending_files {
-        // Process each file
-        match std::fs::read_to_string(&file_path) {
-            Ok(contents) => {
-                // Check if it's JSON (we'll only handle JSON for now)
-                if file_path.extension().map_or(false, |ext| ext == "json") {
-                    match inventory_ui.inventory_db.borrow().import_json(&contents) {
-                        Ok(items_imported) => {
-                            // Move file to processed directory
-                            if let Err(e) = file_sync.process_file(&file_path, true) {
-                                eprintln!("Error moving processed file: {}", e);
-                            }
-                            processed_count += items_imported;
-                        },
-                        Err(e) => {
-                            eprintln!("Error importing file: {}", e);
-                            // Move file to error directory
-                            if let Err(e) = file_sync.process_file(&file_path, false) {
-                                eprintln!("Error moving error file: {}", e);
-                            }
-                        }
-                    }
-                }
-            },
-            Err(e) => {
-                eprintln!("Error reading file: {}", e);
-                // Move file to error directory
-                if let Err(e) = file_sync.process_file(&file_path, false) {
-                    eprintln!("Error moving error file: {}", e);
-                }
-            }
-        }
-    }
-    
-    Ok(processed_count)
-}
\ No newline at end of file
+    Ok(pending_files.iter()
+        .map(|file_path| import_file(&file_sync, file_path, inventory_ui))
+        .collect())
+}
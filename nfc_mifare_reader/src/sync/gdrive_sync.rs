@@ -3,7 +3,8 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use std::io;
 use chrono::Local;
-use crate::inventory::InventoryDB;
+use progress_sink::StatusFile;
+use crate::inventory::{import, InventoryDB};
 
 pub struct GDriveSync {
     sync_folder: String,
@@ -42,6 +43,7 @@ impl GDriveSync {
         match fs::write(&file_path, json_data) {
             Ok(_) => {
                 println!("Database exported to Google Drive sync folder: {:?}", file_path);
+                crate::signing::sign_export(&file_path);
                 Ok(file_path.to_string_lossy().to_string())
             },
             Err(e) => Err(format!("Failed to write to Google Drive sync folder: {}", e))
@@ -54,7 +56,7 @@ impl GDriveSync {
             Some(file_path) => {
                 match fs::read_to_string(&file_path) {
                     Ok(content) => {
-                        match db.import_json(&content) {
+                        match import::import_json(db, &content, &StatusFile::new("import")) {
                             Ok(count) => {
                                 println!("Imported {} items from Google Drive sync file: {:?}", count, file_path);
                                 Ok(count)
@@ -0,0 +1,128 @@
+// sync/import_watcher.rs
+//
+// Keeps an eye on the import directory so files dropped into it are imported
+// without going through Sync > Check Import Files. The watcher thread only finds
+// the files; each one is sent to the main thread as AppEvent::ImportFile, which
+// imports it into the inventory and moves it to the processed or error directory.
+use notify::{watcher, DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use fltk::app;
+
+use crate::app::AppEvent;
+use crate::config::AppConfig;
+use crate::i18n::tr_args;
+use super::file_sync::{is_import_file, FileSync};
+
+// A file has to be left alone this long before it is taken, so one still being
+// copied in isn't imported half written
+const SETTLE_SECS: u64 = 2;
+// How often the forwarding thread checks whether it has been stopped
+const STOP_POLL: Duration = Duration::from_millis(500);
+
+// The watcher in use and the flag keeping its forwarding thread going
+struct ImportWatcher {
+    _watcher: RecommendedWatcher,
+    running: Arc<AtomicBool>,
+}
+
+impl Drop for ImportWatcher {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+static WATCHER: Lazy<Mutex<Option<ImportWatcher>>> = Lazy::new(|| Mutex::new(None));
+
+/// Watch the import directory of the settings, or stop watching when the setting
+/// is off. Called again after the directories change, the old watch is replaced.
+pub fn watch_import_directory(config: &AppConfig, sender: app::Sender<AppEvent>) {
+    stop_import_watcher();
+    if !config.watch_import_directory {
+        return;
+    }
+
+    let dirs = config.sync_dirs();
+    match start_import_watcher(&dirs.import_dir, &dirs.processed_dir, &dirs.error_dir, sender) {
+        Ok(()) => println!("{}", tr_args("log-import-watching", &[("dir", &dirs.import_dir)])),
+        Err(e) => println!("{}", tr_args("err-import-watch", &[
+            ("dir", &dirs.import_dir),
+            ("error", &e),
+        ])),
+    }
+}
+
+/// Start watching an import directory, files already waiting in it are sent first
+pub fn start_import_watcher(
+    import_dir: &str,
+    processed_dir: &str,
+    error_dir: &str,
+    sender: app::Sender<AppEvent>
+) -> Result<(), String> {
+    // Creates the directories when they are missing
+    let file_sync = FileSync::new(import_dir, processed_dir, error_dir);
+
+    let (watch_tx, watch_rx) = mpsc::channel();
+    let mut import_watcher = watcher(watch_tx, Duration::from_secs(SETTLE_SECS))
+        .map_err(|e| e.to_string())?;
+    import_watcher.watch(import_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| e.to_string())?;
+
+    for path in file_sync.get_pending_files() {
+        sender.send(AppEvent::ImportFile(path));
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    let running_thread = running.clone();
+
+    thread::spawn(move || {
+        while running_thread.load(Ordering::SeqCst) {
+            let event = match watch_rx.recv_timeout(STOP_POLL) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+
+            if let Some(path) = arrived_file(event) {
+                sender.send(AppEvent::ImportFile(path));
+            }
+        }
+    });
+
+    if let Ok(mut current) = WATCHER.lock() {
+        *current = Some(ImportWatcher { _watcher: import_watcher, running });
+    }
+    Ok(())
+}
+
+/// Stop watching the import directory
+pub fn stop_import_watcher() {
+    if let Ok(mut current) = WATCHER.lock() {
+        *current = None;
+    }
+}
+
+// this gives the file an event says has arrived or changed, if it is one to import
+fn arrived_file(event: DebouncedEvent) -> Option<PathBuf> {
+    let path = match event {
+        DebouncedEvent::Create(path) | DebouncedEvent::Write(path) => path,
+        // Moved in from elsewhere, or renamed once the copy finished
+        DebouncedEvent::Rename(_, path) => path,
+        DebouncedEvent::Error(e, _) => {
+            eprintln!("Import watch error: {}", e);
+            return None;
+        },
+        _ => return None,
+    };
+
+    if is_import_file(&path) && path.is_file() {
+        Some(path)
+    } else {
+        None
+    }
+}
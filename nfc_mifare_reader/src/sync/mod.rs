@@ -1,18 +1,27 @@
 // sync/mod.rs
+#[cfg(feature = "gui")]
 pub mod file_sync;
 pub mod gdrive_sync;
+#[cfg(feature = "gui")]
+pub mod import_watcher;
+pub mod station_sync;
 
 // Re-export the core types for convenience
+#[cfg(feature = "gui")]
 pub use file_sync::FileSync;
 pub use gdrive_sync::GDriveSync;
+#[cfg(feature = "gui")]
+pub use import_watcher::watch_import_directory;
+pub use station_sync::StationSync;
 
 // Function to check for import files (moved from main.rs)
+#[cfg(feature = "gui")]
 pub fn check_for_import_files(
     import_dir: &str, 
     processed_dir: &str, 
     error_dir: &str, 
     inventory_ui: &std::rc::Rc<crate::inventory::InventoryUI>
-) -> Result<usize, String> {
+) -> Result<Vec<file_sync::ImportedFile>, String> {
     // Implementation moved from main.rs
     // This would process import files using the inventory UI instance
     file_sync::check_for_import_files(import_dir, processed_dir, error_dir, inventory_ui)
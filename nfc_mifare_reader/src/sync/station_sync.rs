@@ -0,0 +1,128 @@
+// station_sync.rs - Merges inventory edits between stations through a shared folder
+//
+// Each station writes its own operation log to <folder>/oplog/<station id>.jsonl and only
+// reads the others, so no file ever has two writers and a Drive or Syncthing folder can't
+// produce conflicting copies. Replaying the logs is deterministic (see inventory_db/src/oplog.rs),
+// so stations converge however late or out of order the files arrive.
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::inventory::oplog::Operation;
+use crate::inventory::InventoryDB;
+
+pub struct SyncReport {
+    // size of this station's log, it is always shared whole
+    pub local_operations: usize,
+    pub received: usize,
+    pub stations: usize,
+    pub changed_items: Vec<String>,
+}
+
+pub struct StationSync {
+    log_folder: PathBuf,
+}
+
+impl StationSync {
+    pub fn new(sync_folder: &str) -> Self {
+        StationSync {
+            log_folder: Path::new(sync_folder).join("oplog"),
+        }
+    }
+
+    // Publish this station's log, then merge in everything new from the other stations
+    pub fn sync(&self, db: &InventoryDB) -> Result<SyncReport, String> {
+        fs::create_dir_all(&self.log_folder)
+            .map_err(|e| format!("Failed to create sync folder {:?}: {}", self.log_folder, e))?;
+
+        let station = db.station_id().map_err(|e| format!("Failed to read station id: {}", e))?;
+        db.seed_operation_log().map_err(|e| format!("Failed to log existing items: {}", e))?;
+        let local_operations = self.write_own_log(db, &station)?;
+
+        let known = db.version_vector().map_err(|e| format!("Failed to read operation log: {}", e))?;
+        let mut incoming = Vec::new();
+        let mut stations = 0;
+        for path in self.other_logs(&station)? {
+            stations += 1;
+            for op in read_log(&path)? {
+                if op.seq > known.get(&op.station).copied().unwrap_or(0) {
+                    incoming.push(op);
+                }
+            }
+        }
+
+        let changed_items = db.apply_operations(&incoming)
+            .map_err(|e| format!("Failed to apply operations: {}", e))?;
+        println!(
+            "Station sync: shared {} local operations, received {} from {} stations, {} items changed",
+            local_operations, incoming.len(), stations, changed_items.len()
+        );
+
+        Ok(SyncReport {
+            local_operations,
+            received: incoming.len(),
+            stations,
+            changed_items,
+        })
+    }
+
+    // The whole log is rewritten through a temporary file, so the sync client
+    // never uploads a half-written one
+    fn write_own_log(&self, db: &InventoryDB, station: &str) -> Result<usize, String> {
+        let ops = db.station_operations(station)
+            .map_err(|e| format!("Failed to read operation log: {}", e))?;
+
+        let path = self.log_folder.join(format!("{}.jsonl", station));
+        let temp_path = self.log_folder.join(format!(".{}.jsonl.tmp", station));
+        let write = || -> std::io::Result<()> {
+            let mut file = fs::File::create(&temp_path)?;
+            for op in &ops {
+                let line = serde_json::to_string(op)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                writeln!(file, "{}", line)?;
+            }
+            file.sync_all()?;
+            fs::rename(&temp_path, &path)
+        };
+        write().map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+        Ok(ops.len())
+    }
+
+    fn other_logs(&self, station: &str) -> Result<Vec<PathBuf>, String> {
+        let own_name = format!("{}.jsonl", station);
+        let entries = fs::read_dir(&self.log_folder)
+            .map_err(|e| format!("Failed to read sync folder {:?}: {}", self.log_folder, e))?;
+
+        let mut logs: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+            .filter(|path| path.file_name().is_some_and(|name| name.to_string_lossy() != own_name))
+            .collect();
+        logs.sort();
+        Ok(logs)
+    }
+}
+
+// Operations in a station's log. A line that doesn't parse is usually a file the sync client
+// is still downloading, so reading stops there and the rest is picked up on the next sync
+// (taking later lines would move the version vector past the missing ones).
+fn read_log(path: &Path) -> Result<Vec<Operation>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+
+    let mut ops = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Operation>(line) {
+            Ok(op) => ops.push(op),
+            Err(e) => {
+                println!("Stopping at line {} of {:?}: {}", number + 1, path, e);
+                break;
+            }
+        }
+    }
+    Ok(ops)
+}
@@ -1,53 +1,62 @@
 // ui/common.rs
 use fltk::{
-    button::Button,
-    enums::FrameType,
+    button::{Button, CheckButton},
+    dialog,
+    enums::{Align, FrameType},
     frame::Frame,
-    group::{Group, Tabs},
+    group::{Flex, Tabs},
     input::Input,
     menu::Choice,
     prelude::*,
-    text::{TextBuffer, TextDisplay, TextEditor},
+    text::{TextBuffer, TextDisplay, TextEditor, WrapMode},
 };
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::reader;
 use crate::ui::converter;
 use crate::batch;
+use crate::batch::worker::{BatchJob, BatchOptions};
+use crate::export::{self, CardRecord, ExportFormat};
+use crate::i18n::{tr, tr_args};
+use crate::ui::layout;
 
 pub fn create_reader_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>, card_data_buffer: Rc<RefCell<TextBuffer>>) {
-    // Changed from y=50 to y=25 to align with tab bar
-    let reader_tab = Group::new(0, 25, 800, 575, "Reader Mode");
+    let mut reader_tab = layout::tab_page(tabs, &tr("tab-reader"));
     
     // Shared buffers
     let instructions_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     
-    // Instructions section - adjusted y coordinates
-    let mut instructions_frame = Frame::new(10, 35, 780, 100, "Instructions");
-    instructions_frame.set_frame(FrameType::EngravedBox);
-    
-    let mut instructions_display = TextDisplay::new(20, 55, 760, 70, "");
+    // Instructions section
+    let mut instructions_section = section(&tr("reader-instructions"));
+    reader_tab.fixed(&instructions_section, 100);
+    let mut instructions_display = TextDisplay::default();
+    instructions_display.wrap_mode(WrapMode::AtBounds, 0);
     {
         let mut buffer = instructions_buffer.borrow_mut();
-        buffer.set_text("Welcome to the Mifare Reader Utility!\n\n\
-                       Present Mifare cards to the reader to capture their UIDs. UIDs will be automatically converted to human-readable format.");
+        buffer.set_text(&tr("reader-welcome"));
         instructions_display.set_buffer(buffer.clone());
     }
-    
-    // Capture controls - adjusted y coordinates
-    let mut capture_btn = Button::new(20, 145, 120, 30, "Start Capture");
-    let mut clear_btn = Button::new(150, 145, 120, 30, "Clear Data");
-    
-    // Card data display - adjusted y coordinates
-    let mut data_frame = Frame::new(10, 185, 780, 380, "Card Data");
-    data_frame.set_frame(FrameType::EngravedBox);
-    
-    let mut card_data_display = TextDisplay::new(20, 205, 760, 350, "");
+    instructions_section.end();
+    
+    // Capture controls
+    let mut controls = layout::fixed_row(&mut reader_tab, layout::ROW_HEIGHT);
+    let mut capture_btn = Button::default().with_label(&tr("capture-start"));
+    controls.fixed(&capture_btn, 120);
+    let mut clear_btn = Button::default().with_label(&tr("reader-clear"));
+    controls.fixed(&clear_btn, 120);
+    Frame::default();
+    controls.end();
+    
+    // Card data display, takes the rest of the page
+    let mut data_section = section(&tr("reader-card-data"));
+    let mut card_data_display = TextDisplay::default();
     {
         let buffer = card_data_buffer.borrow();
         card_data_display.set_buffer(buffer.clone());
     }
+    data_section.end();
     
     let card_data_buffer_1 = card_data_buffer.clone();
     let kb_layout_for_capture = keyboard_layout.clone();
@@ -57,8 +66,8 @@ pub fn create_reader_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>, car
     
     let card_data_buffer_2 = card_data_buffer.clone();
     clear_btn.set_callback(move |_| {
-        if fltk::dialog::choice2(300, 300, "Are you sure you want to clear all captured data?", "Cancel", "Clear", "") == Some(1) {
-            card_data_buffer_2.borrow_mut().set_text("");
+        if fltk::dialog::choice2(300, 300, &tr("reader-clear-confirm"), &tr("btn-cancel"), &tr("btn-clear"), "") == Some(1) {
+            reader::scan_log::clear_scans(&card_data_buffer_2);
         }
     });
     
@@ -67,65 +76,54 @@ pub fn create_reader_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>, car
 }
 
 pub fn create_conversion_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>) {
-    // Changed from y=50 to y=25 to align with tab bar
-    let conversion_tab = Group::new(0, 25, 800, 575, "UID Conversion");
+    let mut conversion_tab = layout::tab_page(tabs, &tr("tab-conversion"));
     
-    // Adjusted all y coordinates by subtracting 25
-    Frame::new(20, 45, 100, 30, "Enter Card UID:");
-    let uid_input = Input::new(130, 45, 300, 30, "");
-    let mut convert_btn = Button::new(450, 45, 100, 30, "Convert");
+    let mut input_row = layout::fixed_row(&mut conversion_tab, layout::ROW_HEIGHT);
+    let uid_label = Frame::default().with_label(&tr("conv-uid"));
+    input_row.fixed(&uid_label, 110);
+    let uid_input = Input::default();
+    let mut convert_btn = Button::default().with_label(&tr("conv-convert"));
+    input_row.fixed(&convert_btn, 100);
+    input_row.end();
     
-    Frame::new(20, 95, 740, 30, "Conversion Results:");
+    let results_label = Frame::default().with_label(&tr("conv-results"));
+    conversion_tab.fixed(&results_label, layout::ROW_HEIGHT);
     
     // Result displays for conversion
     let hex_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     let dec_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     let mfg_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     let format_buffer = Rc::new(RefCell::new(TextBuffer::default()));
+    let badge_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     
-    Frame::new(20, 135, 200, 30, "Hexadecimal:");
-    let mut hex_display = TextDisplay::new(230, 135, 530, 30, "");
-    {
-        let buffer = hex_buffer.borrow();
-        hex_display.set_buffer(buffer.clone());
-    }
-    
-    Frame::new(20, 175, 200, 30, "Decimal:");
-    let mut dec_display = TextDisplay::new(230, 175, 530, 30, "");
-    {
-        let buffer = dec_buffer.borrow();
-        dec_display.set_buffer(buffer.clone());
-    }
-    
-    Frame::new(20, 215, 200, 30, "Manufacturer:");
-    let mut mfg_display = TextDisplay::new(230, 215, 530, 30, "");
-    {
-        let buffer = mfg_buffer.borrow();
-        mfg_display.set_buffer(buffer.clone());
-    }
-    
-    Frame::new(20, 255, 200, 30, "Format Description:");
-    let mut format_display = TextDisplay::new(230, 255, 530, 30, "");
-    {
-        let buffer = format_buffer.borrow();
-        format_display.set_buffer(buffer.clone());
-    }
+    result_row(&mut conversion_tab, &tr("conv-hex"), &hex_buffer);
+    result_row(&mut conversion_tab, &tr("conv-decimal"), &dec_buffer);
+    result_row(&mut conversion_tab, &tr("conv-manufacturer"), &mfg_buffer);
+    result_row(&mut conversion_tab, &tr("conv-format"), &format_buffer);
+    result_row(&mut conversion_tab, &tr("conv-badge"), &badge_buffer);
     
     // Add instructions for keyboard encoding issues
-    let mut kb_frame = Frame::new(20, 295, 740, 120, "");
-    kb_frame.set_label(
-        "Note about keyboard encoding: If you see special characters instead of numbers,\n\
-        this utility will automatically convert them to the correct format based on selected keyboard layout.\n\n\
-        Format codes explanation:\n\
-        'e' = QWERTY keyboard, 'f' = AZERTY keyboard, 'h' = QUERTY keyboard, 'r' = reader specific format."
-    );
+    let mut kb_frame = Frame::default();
+    kb_frame.set_label(&tr("conv-keyboard-note"));
+    kb_frame.set_align(Align::Left | Align::Inside | Align::Wrap);
+    conversion_tab.fixed(&kb_frame, 90);
     
     // Add keyboard layout selector
-    Frame::new(20, 425, 180, 30, "Keyboard Layout:");
-    
-    let mut keyboard_choice = Choice::new(210, 425, 150, 30, "");
-    keyboard_choice.add_choice("Auto-detect|Windows|Mac US|Mac International");
+    let mut layout_row = layout::fixed_row(&mut conversion_tab, layout::ROW_HEIGHT);
+    let layout_label = Frame::default().with_label(&tr("prefs-keyboard-layout"));
+    layout_row.fixed(&layout_label, 180);
+    let mut keyboard_choice = Choice::default();
+    for key in ["menu-kb-auto", "menu-kb-windows", "menu-kb-mac-us", "menu-kb-mac-intl", "menu-kb-azerty", "menu-kb-qwertz"] {
+        // menu labels carry '&' shortcut markers, a Choice would show them
+        keyboard_choice.add_choice(&tr(key).replace('&', ""));
+    }
     keyboard_choice.set_value(0); // Default to Auto-detect
+    layout_row.fixed(&keyboard_choice, 150);
+    Frame::default();
+    layout_row.end();
+    
+    // Spare height stays below the form
+    Frame::default();
     
     let keyboard_layout_for_selector = keyboard_layout.clone();
     keyboard_choice.set_callback(move |c| {
@@ -137,6 +135,7 @@ pub fn create_conversion_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>)
     let dec_buffer_clone = dec_buffer.clone();
     let mfg_buffer_clone = mfg_buffer.clone();
     let format_buffer_clone = format_buffer.clone();
+    let badge_buffer_clone = badge_buffer.clone();
     let uid_input_clone = uid_input.clone();
     let keyboard_layout_for_convert = keyboard_layout.clone();
     
@@ -147,7 +146,8 @@ pub fn create_conversion_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>)
             hex_buffer_clone.clone(),
             dec_buffer_clone.clone(),
             mfg_buffer_clone.clone(),
-            format_buffer_clone.clone()
+            format_buffer_clone.clone(),
+            badge_buffer_clone.clone()
         );
     });
     
@@ -156,55 +156,162 @@ pub fn create_conversion_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>)
 }
 
 pub fn create_batch_tab(tabs: &mut Tabs, keyboard_layout: Rc<RefCell<i32>>) {
-    // Changed from y=50 to y=25 to align with tab bar
-    let batch_tab = Group::new(0, 25, 800, 575, "Batch Conversion");
+    let mut batch_tab = layout::tab_page(tabs, &tr("tab-batch"));
     
-    // Adjusted all y coordinates by subtracting 25
-    let mut batch_instructions = Frame::new(20, 45, 740, 50, "");
-    batch_instructions.set_label("Paste multiple UIDs below, one per line. The application will convert all of them at once.");
+    let mut batch_instructions = Frame::default();
+    batch_instructions.set_label(&tr("batch-instructions"));
+    batch_instructions.set_align(Align::Wrap);
+    batch_tab.fixed(&batch_instructions, 50);
     
     let batch_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     let batch_result_buffer = Rc::new(RefCell::new(TextBuffer::default()));
     
     // Use TextEditor instead of TextDisplay for editable input
-    let mut batch_input = TextEditor::new(20, 105, 740, 150, "");
+    let mut batch_input = TextEditor::default();
     batch_input.set_buffer(batch_buffer.borrow_mut().clone());
     batch_input.set_frame(FrameType::DownBox);
     batch_input.set_text_font(fltk::enums::Font::Courier);
     
+    let mut buttons = layout::fixed_row(&mut batch_tab, layout::ROW_HEIGHT);
+    
     // Add clear input button for batch input
-    let mut batch_clear_input_btn = Button::new(20, 265, 120, 30, "Clear Input");
+    let mut batch_clear_input_btn = Button::default().with_label(&tr("batch-clear-input"));
+    buttons.fixed(&batch_clear_input_btn, 120);
     let batch_buffer_for_clear = batch_buffer.clone();
     batch_clear_input_btn.set_callback(move |_| {
-        if fltk::dialog::choice2(300, 300, "Clear input data?", "Cancel", "Clear", "") == Some(1) {
+        if fltk::dialog::choice2(300, 300, &tr("batch-clear-input-confirm"), &tr("btn-cancel"), &tr("btn-clear"), "") == Some(1) {
             batch_buffer_for_clear.borrow_mut().set_text("");
         }
     });
     
-    let mut batch_convert_btn = Button::new(350, 265, 120, 30, "Convert All");
+    Frame::default();
+    let mut batch_convert_btn = Button::default().with_label(&tr("batch-convert"));
+    buttons.fixed(&batch_convert_btn, 120);
     
     // Add clear results button
-    let mut batch_clear_results_btn = Button::new(480, 265, 120, 30, "Clear Results");
+    let mut batch_clear_results_btn = Button::default().with_label(&tr("batch-clear-results"));
+    buttons.fixed(&batch_clear_results_btn, 120);
+    
+    // Saves the first scan of each tag from the last batch
+    let mut export_unique_btn = Button::default().with_label(&tr("batch-export-unique"));
+    buttons.fixed(&export_unique_btn, 120);
+    export_unique_btn.deactivate();
+    buttons.end();
+    
+    // Converted scans of the last batch, for the unique export
+    let last_records: Rc<RefCell<Vec<CardRecord>>> = Rc::new(RefCell::new(Vec::new()));
+    
     let batch_result_buffer_for_clear = batch_result_buffer.clone();
+    let records_for_clear = last_records.clone();
+    let mut export_unique_for_clear = export_unique_btn.clone();
     batch_clear_results_btn.set_callback(move |_| {
         batch_result_buffer_for_clear.borrow_mut().set_text("");
+        records_for_clear.borrow_mut().clear();
+        export_unique_for_clear.deactivate();
     });
     
-    let mut batch_results = TextDisplay::new(20, 305, 740, 210, "");
+    let records_for_export = last_records.clone();
+    export_unique_btn.set_callback(move |_| {
+        let unique = batch::stats::unique_records(&records_for_export.borrow());
+        if let Some(path) = dialog::file_chooser(&tr("batch-export-unique-title"), "*.{csv,json,txt}", ".", false) {
+            match export::export_data(&unique, ExportFormat::from_path(&path), &path) {
+                Ok(message) => dialog::message(300, 300, &message),
+                Err(e) => dialog::alert(300, 300, &tr_args("err-export", &[("error", &e.to_string())])),
+            }
+        }
+    });
+    
+    // Scan logs on disk, and where the results of every batch go
+    let mut file_row = layout::fixed_row(&mut batch_tab, layout::ROW_HEIGHT);
+    let mut file_btn = Button::default().with_label(&tr("batch-file"));
+    file_row.fixed(&file_btn, 120);
+    let mut folder_btn = Button::default().with_label(&tr("batch-folder"));
+    file_row.fixed(&folder_btn, 120);
+    let add_to_inventory = CheckButton::default().with_label(&tr("batch-add-inventory"));
+    let save_results = CheckButton::default().with_label(&tr("batch-save-file"));
+    file_row.end();
+    
+    let mut batch_status = Frame::default();
+    batch_status.set_align(Align::Left | Align::Inside);
+    batch_tab.fixed(&batch_status, 20);
+    
+    let mut batch_results = TextDisplay::default();
     batch_results.set_buffer(batch_result_buffer.borrow().clone());
     batch_results.set_text_font(fltk::enums::Font::Courier);
     
+    // this asks where to save the results when the box is ticked, then runs the batch
+    let run_batch: Rc<dyn Fn(BatchJob)> = {
+        let keyboard_layout = keyboard_layout.clone();
+        let result_buffer = batch_result_buffer.clone();
+        let status = batch_status.clone();
+        let last_records = last_records.clone();
+        let export_unique_btn = export_unique_btn.clone();
+        Rc::new(move |job| {
+            let export_path = if save_results.is_checked() {
+                match dialog::file_chooser(&tr("batch-save-title"), "*.{csv,json,txt}", ".", false) {
+                    Some(path) => Some(path),
+                    None => return,
+                }
+            } else {
+                None
+            };
+            let options = BatchOptions {
+                kb_layout: *keyboard_layout.borrow(),
+                add_to_inventory: add_to_inventory.is_checked(),
+                export_path,
+            };
+            if let Some(outcome) = batch::process_batch(job, options, result_buffer.clone(), &mut status.clone()) {
+                let mut export_unique_btn = export_unique_btn.clone();
+                if outcome.stats.unique > 0 {
+                    export_unique_btn.activate();
+                } else {
+                    export_unique_btn.deactivate();
+                }
+                *last_records.borrow_mut() = outcome.records;
+            }
+        })
+    };
+    
+    let run_file_batch = run_batch.clone();
+    file_btn.set_callback(move |_| {
+        if let Some(path) = dialog::file_chooser(&tr("batch-file-title"), "*", ".", false) {
+            run_file_batch(BatchJob::Path(PathBuf::from(path)));
+        }
+    });
+    
+    let run_folder_batch = run_batch.clone();
+    folder_btn.set_callback(move |_| {
+        if let Some(path) = dialog::dir_chooser(&tr("batch-folder-title"), ".", false) {
+            run_folder_batch(BatchJob::Path(PathBuf::from(path)));
+        }
+    });
+    
     let batch_buffer_clone = batch_buffer.clone();
-    let batch_result_buffer_clone = batch_result_buffer.clone();
-    let kb_layout_for_batch = keyboard_layout.clone();
     batch_convert_btn.set_callback(move |_| {
-        batch::process_batch(
-            &batch_buffer_clone.borrow().text(),
-            *kb_layout_for_batch.borrow(),
-            batch_result_buffer_clone.clone()
-        );
+        run_batch(BatchJob::Text(batch_buffer_clone.borrow().text()));
     });
     
     batch_tab.end();
     tabs.add(&batch_tab);
-}
\ No newline at end of file
+}
+
+// this adds an engraved box with a heading to the current page, end it once its contents are added
+fn section(heading: &str) -> Flex {
+    let mut section = layout::column();
+    section.set_frame(FrameType::EngravedBox);
+    section.set_margin(layout::MARGIN);
+    let mut heading_frame = Frame::default().with_label(heading);
+    heading_frame.set_align(Align::Left | Align::Inside);
+    section.fixed(&heading_frame, 20);
+    section
+}
+
+// this adds a labelled one line result display to the conversion tab
+fn result_row(page: &mut Flex, label: &str, buffer: &Rc<RefCell<TextBuffer>>) {
+    let mut row = layout::fixed_row(page, layout::ROW_HEIGHT);
+    let label_frame = Frame::default().with_label(label);
+    row.fixed(&label_frame, 200);
+    let mut display = TextDisplay::default();
+    display.set_buffer(buffer.borrow().clone());
+    row.end();
+}
@@ -5,6 +5,7 @@ use fltk::text::TextBuffer;
 
 // Import utility functions from the utils module
 use crate::utils;
+use crate::wiegand;
 
 pub fn convert_uid(
     uid: &str,
@@ -12,7 +13,8 @@ pub fn convert_uid(
     hex_buffer: Rc<RefCell<TextBuffer>>,
     dec_buffer: Rc<RefCell<TextBuffer>>,
     mfg_buffer: Rc<RefCell<TextBuffer>>,
-    format_buffer: Rc<RefCell<TextBuffer>>
+    format_buffer: Rc<RefCell<TextBuffer>>,
+    badge_buffer: Rc<RefCell<TextBuffer>>
 ) {
     if uid.is_empty() {
         // Clear all buffers if input is empty
@@ -20,6 +22,7 @@ pub fn convert_uid(
         dec_buffer.borrow_mut().set_text("");
         mfg_buffer.borrow_mut().set_text("");
         format_buffer.borrow_mut().set_text("");
+        badge_buffer.borrow_mut().set_text("");
         return;
     }
     
@@ -32,9 +35,13 @@ pub fn convert_uid(
     // Determine format
     let format_desc = utils::interpret_format_code(uid);
     
+    // Facility code / card number under common badge formats
+    let badge_desc = wiegand::describe_badge(&hex_uid);
+    
     // Update display buffers
     hex_buffer.borrow_mut().set_text(&hex_uid);
     dec_buffer.borrow_mut().set_text(&decimal_value);
     mfg_buffer.borrow_mut().set_text(&manufacturer);
     format_buffer.borrow_mut().set_text(&format_desc);
+    badge_buffer.borrow_mut().set_text(&badge_desc);
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+// ui/layout.rs
+//
+// Main window geometry and the Flex helpers the tabs are laid out with. The
+// window reopens where it was closed, clamped to the work area of its screen so
+// it always fits a small Pi touchscreen, and a Ctrl +/- zoom is kept between runs.
+#[cfg(feature = "gui")]
+use std::sync::Mutex;
+#[cfg(feature = "gui")]
+use fltk::{
+    app,
+    enums::Event,
+    group::{Flex, FlexType, Tabs},
+    prelude::*,
+    window::Window,
+};
+use serde::{Serialize, Deserialize};
+
+#[cfg(feature = "gui")]
+use crate::config::{self, AppConfig};
+#[cfg(feature = "gui")]
+use crate::i18n::{tr, tr_args};
+
+pub const DEFAULT_WIDTH: i32 = 800;
+pub const DEFAULT_HEIGHT: i32 = 625;
+// Smallest usable size, a 480x320 touchscreen
+pub const MIN_WIDTH: i32 = 480;
+pub const MIN_HEIGHT: i32 = 320;
+pub const MENU_HEIGHT: i32 = 25;
+pub const STATUS_HEIGHT: i32 = 25;
+pub const TAB_BAR_HEIGHT: i32 = 25;
+// Spacing used inside the tab pages
+pub const MARGIN: i32 = 10;
+pub const ROW_HEIGHT: i32 = 30;
+
+/// Position and size of the main window when it was last closed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+// Screen scale once the configured one was applied, to tell a zoom from FLTK's own DPI pick
+#[cfg(feature = "gui")]
+static STARTUP_SCALE: Mutex<Option<f32>> = Mutex::new(None);
+
+/// Apply the saved screen scale, then open the main window at its saved geometry
+#[cfg(feature = "gui")]
+pub fn create_main_window(config: &AppConfig) -> Window {
+    if let Some(scale) = config.ui_scale {
+        for screen in 0..app::screen_count() {
+            app::set_screen_scale(screen, scale);
+        }
+    }
+
+    let geometry = fit_to_screen(config.window_geometry);
+    if let Ok(mut startup_scale) = STARTUP_SCALE.lock() {
+        *startup_scale = Some(app::screen_scale(app::screen_num(geometry.x, geometry.y)));
+    }
+
+    let mut wind = Window::new(geometry.x, geometry.y, geometry.width, geometry.height, None)
+        .with_label(&tr("app-title"));
+    wind.size_range(MIN_WIDTH, MIN_HEIGHT, 0, 0);
+    // Closing the main window ends the app even with dialogs open, so it shuts down
+    // properly instead of leaving the event loop running for them
+    wind.set_callback(|_| {
+        if app::event() == Event::Close {
+            app::quit();
+        }
+    });
+    wind
+}
+
+// this centers a first run window and pulls a saved one back onto the screen it was on
+#[cfg(feature = "gui")]
+fn fit_to_screen(saved: Option<WindowGeometry>) -> WindowGeometry {
+    let screen = match saved {
+        Some(geometry) => app::screen_num(geometry.x, geometry.y),
+        None => 0,
+    };
+    let (area_x, area_y, area_w, area_h) = app::screen_work_area(screen);
+
+    let width = saved.map_or(DEFAULT_WIDTH, |g| g.width).clamp(MIN_WIDTH, area_w.max(MIN_WIDTH));
+    let height = saved.map_or(DEFAULT_HEIGHT, |g| g.height).clamp(MIN_HEIGHT, area_h.max(MIN_HEIGHT));
+    let (x, y) = match saved {
+        Some(geometry) => (
+            geometry.x.clamp(area_x, (area_x + area_w - width).max(area_x)),
+            geometry.y.clamp(area_y, (area_y + area_h - height).max(area_y)),
+        ),
+        None => (
+            area_x + ((area_w - width) / 2).max(0),
+            area_y + ((area_h - height) / 2).max(0),
+        ),
+    };
+
+    WindowGeometry { x, y, width, height }
+}
+
+/// Remember where the main window was and any zoom, called once the event loop ends
+#[cfg(feature = "gui")]
+pub fn save_window_geometry(wind: &Window, config: &mut AppConfig) {
+    config.window_geometry = Some(WindowGeometry {
+        x: wind.x(),
+        y: wind.y(),
+        width: wind.w(),
+        height: wind.h(),
+    });
+
+    let scale = app::screen_scale(app::screen_num(wind.x(), wind.y()));
+    let startup_scale = STARTUP_SCALE.lock().ok().and_then(|s| *s);
+    if config.ui_scale.is_some() || startup_scale.is_some_and(|s| (s - scale).abs() > 0.01) {
+        config.ui_scale = Some(scale);
+    }
+
+    if let Err(e) = config::save_config(config) {
+        println!("{}", tr_args("err-save-config", &[("error", &e.to_string())]));
+    }
+}
+
+/// Column filling a tab page below the tab labels, it becomes one tab of `tabs`
+#[cfg(feature = "gui")]
+pub fn tab_page(tabs: &Tabs, label: &str) -> Flex {
+    let mut page = Flex::new(
+        tabs.x(),
+        tabs.y() + TAB_BAR_HEIGHT,
+        tabs.w(),
+        tabs.h() - TAB_BAR_HEIGHT,
+        None,
+    )
+    .with_label(label);
+    page.set_type(FlexType::Column);
+    page.set_margin(MARGIN);
+    page.set_pad(MARGIN);
+    page
+}
+
+/// Row of fixed height inside a column, end it once its widgets are added
+#[cfg(feature = "gui")]
+pub fn fixed_row(parent: &mut Flex, height: i32) -> Flex {
+    let mut row = Flex::default();
+    row.set_type(FlexType::Row);
+    row.set_pad(MARGIN);
+    parent.fixed(&row, height);
+    row
+}
+
+/// Column that shares the free space of its parent, end it once its widgets are added
+#[cfg(feature = "gui")]
+pub fn column() -> Flex {
+    let mut column = Flex::default();
+    column.set_type(FlexType::Column);
+    column.set_pad(MARGIN);
+    column
+}
@@ -1,6 +1,10 @@
 // ui/mod.rs
 pub mod converter;
 pub mod common;
+pub mod status_bar;
+pub mod layout;
+pub mod theme;
+pub mod progress_dialog;
 
 // Re-export the primary UI functions
 pub use common::{
@@ -8,6 +12,7 @@ pub use common::{
     create_conversion_tab,
     create_batch_tab
 };
+pub use status_bar::create_status_bar;
 
 // Additional UI helpers
 pub fn init_ui() {
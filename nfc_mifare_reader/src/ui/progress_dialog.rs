@@ -0,0 +1,113 @@
+// ui/progress_dialog.rs
+//
+// The dialog of a long operation running on a worker thread: what it is doing, a bar
+// with the time left, and a button to cancel it. The worker reports through a
+// DialogSink, which hands each report to the event loop over an FLTK channel, and the
+// loop passes it on to the dialog.
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use fltk::{
+    app,
+    button::Button,
+    enums::Align,
+    frame::Frame,
+    misc::Progress as ProgressBar,
+    prelude::*,
+    window::Window,
+};
+use progress_sink::{format_eta, Progress, ProgressSink};
+
+use crate::i18n::{tr, tr_args};
+
+pub struct ProgressDialog {
+    win: Window,
+    current: Frame,
+    bar: ProgressBar,
+    cancel: Arc<AtomicBool>,
+}
+
+impl ProgressDialog {
+    /// A modal dialog titled `title`, shown until hide() is called. Cancel and closing
+    /// the dialog both set the cancel flag, for the worker to see at its next step.
+    pub fn new(title: &str) -> Self {
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let mut win = Window::new(300, 300, 400, 130, None).with_label(title);
+        win.make_modal(true);
+
+        let mut current = Frame::new(20, 15, 360, 25, None).with_label(&tr("batch-starting"));
+        current.set_align(Align::Left | Align::Inside | Align::Clip);
+        let mut bar = ProgressBar::new(20, 45, 360, 25, None);
+        bar.set_minimum(0.0);
+        bar.set_maximum(1.0);
+        let mut cancel_btn = Button::new(290, 85, 90, 30, None).with_label(&tr("btn-cancel"));
+        win.end();
+
+        {
+            let cancel = cancel.clone();
+            let mut current = current.clone();
+            cancel_btn.set_callback(move |_| request_cancel(&cancel, &mut current));
+        }
+        {
+            let cancel = cancel.clone();
+            let mut current = current.clone();
+            win.set_callback(move |_| request_cancel(&cancel, &mut current));
+        }
+
+        win.show();
+        ProgressDialog { win, current, bar, cancel }
+    }
+
+    /// Set by Cancel, for the worker
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.cancel.clone()
+    }
+
+    pub fn shown(&self) -> bool {
+        self.win.shown()
+    }
+
+    pub fn hide(&mut self) {
+        self.win.hide();
+    }
+}
+
+// On the UI thread, with the reports the event loop took off the channel
+impl ProgressSink for ProgressDialog {
+    fn report(&self, progress: &Progress) {
+        let mut bar = self.bar.clone();
+        bar.set_value(progress.fraction());
+        bar.set_label(&format!("{:.0}%", progress.percent));
+
+        let mut label = progress.message.clone();
+        if let Some(eta) = progress.eta {
+            label.push_str(&format!(", {}", tr_args("progress-eta", &[("eta", &format_eta(eta))])));
+        }
+        self.current.clone().set_label(&label);
+    }
+}
+
+/// The worker's end of a dialog, each report goes to the event loop as the message
+/// `wrap` makes of it
+pub struct DialogSink<T> {
+    sender: app::Sender<T>,
+    wrap: fn(Progress) -> T,
+}
+
+impl<T: 'static + Send + Sync> DialogSink<T> {
+    pub fn new(sender: app::Sender<T>, wrap: fn(Progress) -> T) -> Self {
+        DialogSink { sender, wrap }
+    }
+}
+
+impl<T: 'static + Send + Sync> ProgressSink for DialogSink<T> {
+    fn report(&self, progress: &Progress) {
+        self.sender.send((self.wrap)(progress.clone()));
+    }
+}
+
+// this asks the worker to stop at its next step
+fn request_cancel(cancel: &AtomicBool, current: &mut Frame) {
+    cancel.store(true, Ordering::SeqCst);
+    current.set_label(&tr("batch-cancelling"));
+}
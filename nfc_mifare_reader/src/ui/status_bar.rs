@@ -0,0 +1,76 @@
+// ui/status_bar.rs
+use fltk::{
+    app,
+    enums::{Align, Color, FrameType},
+    frame::Frame,
+    prelude::*,
+};
+use std::fs;
+
+use crate::reader::status::{reader_health, ReaderConnection};
+
+// How often the status bar refreshes, in seconds
+const REFRESH_INTERVAL: f64 = 1.0;
+
+// this creates the status bar along the bottom of the main window and keeps it updated
+pub fn create_status_bar(x: i32, y: i32, w: i32, h: i32, db_path: &str) -> Frame {
+    let mut status_bar = Frame::new(x, y, w, h, "");
+    status_bar.set_frame(FrameType::ThinDownBox);
+    status_bar.set_align(Align::Left | Align::Inside);
+    status_bar.set_label_size(12);
+
+    refresh_status_bar(&mut status_bar, db_path);
+
+    let mut status_bar_timer = status_bar.clone();
+    let db_path = db_path.to_string();
+    app::add_timeout3(REFRESH_INTERVAL, move |handle| {
+        refresh_status_bar(&mut status_bar_timer, &db_path);
+        app::repeat_timeout3(REFRESH_INTERVAL, handle);
+    });
+
+    status_bar
+}
+
+// this rebuilds the status text from the shared reader health and the database file
+fn refresh_status_bar(status_bar: &mut Frame, db_path: &str) {
+    let health = match reader_health() {
+        Some(health) => health,
+        None => return,
+    };
+
+    let db_size = match fs::metadata(db_path) {
+        Ok(meta) => format_size(meta.len()),
+        Err(_) => "n/a".to_string(),
+    };
+
+    let label = format!(
+        " Reader: {}  |  Firmware: {}  |  Scans this session: {}  |  Last scan: {}  |  DB: {}",
+        health.connection,
+        health.firmware.as_deref().unwrap_or("n/a"),
+        health.scans_this_session,
+        health.last_scan.as_deref().unwrap_or("never"),
+        db_size
+    );
+
+    // only redraw when something changed, the timer runs every second
+    if status_bar.label() != label {
+        status_bar.set_label(&label);
+        status_bar.set_label_color(match health.connection {
+            ReaderConnection::Error(_) => Color::Red,
+            ReaderConnection::Listening { .. } => Color::DarkGreen,
+            ReaderConnection::Idle => Color::Foreground,
+        });
+        status_bar.redraw();
+    }
+}
+
+// this turns a byte count into a short human readable size
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
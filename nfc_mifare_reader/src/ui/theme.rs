@@ -0,0 +1,148 @@
+// ui/theme.rs
+//
+// Light, dark and high-contrast themes. fltk-theme restyles the standard widgets;
+// the tables and displays that draw their own cells take their colors from the
+// palette of the current theme, read at draw time so a theme change only needs
+// a redraw.
+#[cfg(feature = "gui")]
+use std::sync::RwLock;
+#[cfg(feature = "gui")]
+use fltk::enums::Color;
+#[cfg(feature = "gui")]
+use fltk_theme::{ThemeType, WidgetTheme};
+#[cfg(feature = "gui")]
+use once_cell::sync::Lazy;
+use serde::{Serialize, Deserialize};
+
+use crate::i18n::tr;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    #[default]
+    Light,
+    Dark,
+    HighContrast,
+}
+
+/// Themes in the order the Preferences choice lists them
+pub const ALL_THEMES: [Theme; 3] = [Theme::Light, Theme::Dark, Theme::HighContrast];
+
+/// Colors for custom drawn tables and displays
+#[cfg(feature = "gui")]
+#[derive(Clone, Copy, Debug)]
+pub struct Palette {
+    pub background: Color,
+    pub text: Color,
+    pub header_background: Color,
+    pub header_text: Color,
+    pub row_even: Color,
+    pub row_odd: Color,
+    pub selection: Color,
+    pub grid: Color,
+    // Rows of items close to or past their expiry date
+    pub row_expiring: Color,
+    pub row_expired: Color,
+}
+
+#[cfg(feature = "gui")]
+const LIGHT_PALETTE: Palette = Palette {
+    background: Color::White,
+    text: Color::Black,
+    header_background: Color::from_rgb(220, 220, 220),
+    header_text: Color::Black,
+    row_even: Color::from_rgb(245, 245, 245),
+    row_odd: Color::White,
+    selection: Color::from_rgb(173, 216, 230),
+    grid: Color::Black,
+    row_expiring: Color::from_rgb(255, 236, 179),
+    row_expired: Color::from_rgb(255, 205, 210),
+};
+
+#[cfg(feature = "gui")]
+const DARK_PALETTE: Palette = Palette {
+    background: Color::from_rgb(43, 43, 43),
+    text: Color::from_rgb(220, 220, 220),
+    header_background: Color::from_rgb(60, 63, 65),
+    header_text: Color::from_rgb(230, 230, 230),
+    row_even: Color::from_rgb(50, 50, 50),
+    row_odd: Color::from_rgb(43, 43, 43),
+    selection: Color::from_rgb(38, 79, 120),
+    grid: Color::from_rgb(85, 85, 85),
+    row_expiring: Color::from_rgb(92, 75, 20),
+    row_expired: Color::from_rgb(110, 35, 35),
+};
+
+#[cfg(feature = "gui")]
+const HIGH_CONTRAST_PALETTE: Palette = Palette {
+    background: Color::Black,
+    text: Color::White,
+    header_background: Color::Black,
+    header_text: Color::from_rgb(255, 255, 0),
+    row_even: Color::Black,
+    row_odd: Color::Black,
+    selection: Color::from_rgb(0, 0, 160),
+    grid: Color::White,
+    row_expiring: Color::from_rgb(128, 96, 0),
+    row_expired: Color::from_rgb(160, 0, 0),
+};
+
+impl Theme {
+    pub fn name(&self) -> String {
+        match self {
+            Theme::Light => tr("theme-light"),
+            Theme::Dark => tr("theme-dark"),
+            Theme::HighContrast => tr("theme-high-contrast"),
+        }
+    }
+
+    pub fn index(&self) -> i32 {
+        ALL_THEMES.iter().position(|theme| theme == self).unwrap_or(0) as i32
+    }
+
+    pub fn from_index(index: i32) -> Theme {
+        ALL_THEMES.get(index.max(0) as usize).copied().unwrap_or_default()
+    }
+
+    #[cfg(feature = "gui")]
+    pub fn palette(&self) -> Palette {
+        match self {
+            Theme::Light => LIGHT_PALETTE,
+            Theme::Dark => DARK_PALETTE,
+            Theme::HighContrast => HIGH_CONTRAST_PALETTE,
+        }
+    }
+
+    #[cfg(feature = "gui")]
+    fn widget_theme(&self) -> ThemeType {
+        match self {
+            Theme::Light => ThemeType::Greybird,
+            Theme::Dark => ThemeType::Dark,
+            Theme::HighContrast => ThemeType::HighContrast,
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+static CURRENT_THEME: Lazy<RwLock<Theme>> = Lazy::new(|| RwLock::new(Theme::Light));
+
+/// Restyle the widgets and switch the palette, then redraw every window
+#[cfg(feature = "gui")]
+pub fn apply_theme(theme: Theme) {
+    WidgetTheme::new(theme.widget_theme()).apply();
+    if let Ok(mut current) = CURRENT_THEME.write() {
+        *current = theme;
+    }
+    fltk::app::redraw();
+}
+
+#[cfg(feature = "gui")]
+pub fn current_theme() -> Theme {
+    CURRENT_THEME.read().map(|theme| *theme).unwrap_or_default()
+}
+
+/// Palette of the current theme
+#[cfg(feature = "gui")]
+pub fn palette() -> Palette {
+    current_theme().palette()
+}
@@ -2,6 +2,10 @@
 use std::time::{SystemTime, UNIX_EPOCH};
 use chrono::{DateTime, TimeZone, Local};
 
+use uid_codec::{decode_uid, KeyboardLayout};
+
+use crate::config::{APP_CONFIG, lookup_manufacturer, match_format_patterns};
+
 /// Get current timestamps in both Unix and human-readable formats
 pub fn get_timestamps() -> (String, String) {
     // Get current time
@@ -19,30 +23,8 @@ pub fn get_timestamps() -> (String, String) {
 
 /// Process a UID into human-readable format
 pub fn process_uid_for_display(uid: &str, keyboard_layout: i32) -> (String, String) {
-    // First, handle keyboard encoding formats and normalize
-    let decoded = match keyboard_layout {
-        1 => decode_windows_format(uid),   // Windows
-        2 => decode_mac_us_format(uid),    // Mac US
-        3 => decode_mac_intl_format(uid),  // Mac International
-        _ => {
-            // Auto-detect: try to guess based on content
-            if uid.contains('@') || uid.contains('!') || uid.contains('^') {
-                // Likely Windows/standard encoding
-                decode_windows_format(uid)
-            } else if uid.contains('§') || uid.contains('±') {
-                // Likely Mac with international chars
-                decode_mac_intl_format(uid)
-            } else {
-                // Default to Mac US layout
-                decode_mac_us_format(uid)
-            }
-        }
-    };
-    
-    // Extract just the hex digits
-    let clean_uid: String = decoded.chars()
-        .filter(|c| c.is_ascii_hexdigit())
-        .collect();
+    // Handle keyboard encoding formats and extract just the hex digits
+    let clean_uid = decode_uid(uid, KeyboardLayout::from_index(keyboard_layout));
     
     if clean_uid.is_empty() {
         return ("Invalid format".to_string(), "Unknown".to_string());
@@ -59,17 +41,7 @@ pub fn process_uid_for_display(uid: &str, keyboard_layout: i32) -> (String, Stri
 
 /// Format hex UID with spaces for better readability
 pub fn format_hex_uid(hex_uid: &str) -> String {
-    let chars: Vec<char> = hex_uid.chars().collect();
-    let mut formatted = String::new();
-    
-    for (i, c) in chars.iter().enumerate() {
-        formatted.push(*c);
-        if (i + 1) % 2 == 0 && i < chars.len() - 1 {
-            formatted.push(' ');
-        }
-    }
-    
-    formatted.to_uppercase()
+    uid_codec::format_hex_uid(hex_uid)
 }
 
 /// Convert hexadecimal to decimal
@@ -78,212 +50,30 @@ pub fn hex_to_decimal(hex: &str) -> String {
         return "N/A".to_string();
     }
     
-    let clean_hex = hex.replace(" ", "");
-    match u64::from_str_radix(&clean_hex, 16) {
-        Ok(decimal) => decimal.to_string(),
-        Err(_) => "Invalid hex value".to_string()
+    match uid_codec::hex_to_decimal(hex) {
+        Some(decimal) => decimal.to_string(),
+        None => "Invalid hex value".to_string()
     }
 }
 
-/// Handle standard/Windows keyboard mapping
-pub fn decode_windows_format(encoded_str: &str) -> String {
-    if encoded_str.is_empty() {
-        return String::new();
-    }
-    
-    let mut decoded = String::new();
-    
-    for c in encoded_str.chars() {
-        match c {
-            '!' => decoded.push('1'),
-            '@' => decoded.push('2'),
-            '#' => decoded.push('3'),
-            '$' => decoded.push('4'),
-            '%' => decoded.push('5'),
-            '^' => decoded.push('6'),
-            '&' => decoded.push('7'),
-            '*' => decoded.push('8'),
-            '(' => decoded.push('9'),
-            ')' => decoded.push('0'),
-            'h' => decoded.push('h'),
-            'd' => decoded.push('d'),
-            'e' => decoded.push('e'),
-            'r' => decoded.push('r'),
-            '-' => decoded.push('-'),
-            ' ' => decoded.push(' '),
-            c if c.is_ascii_hexdigit() => decoded.push(c),
-            _ => {}  // Skip other characters
-        }
-    }
-    
-    decoded
-}
-
-/// Handle Mac US keyboard mapping
-pub fn decode_mac_us_format(encoded_str: &str) -> String {
-    if encoded_str.is_empty() {
-        return String::new();
-    }
-    
-    let mut decoded = String::new();
-    
-    for c in encoded_str.chars() {
-        match c {
-            '!' => decoded.push('1'),
-            '@' => decoded.push('2'),
-            '#' => decoded.push('3'),
-            '$' => decoded.push('4'),
-            '%' => decoded.push('5'),
-            '^' => decoded.push('6'),
-            '&' => decoded.push('7'),
-            '*' => decoded.push('8'),
-            '(' => decoded.push('9'),
-            ')' => decoded.push('0'),
-            // Mac-specific mappings
-            '¡' => decoded.push('1'),
-            '™' => decoded.push('2'),
-            '£' => decoded.push('3'),
-            '¢' => decoded.push('4'),
-            '∞' => decoded.push('5'),
-            '§' => decoded.push('6'),
-            '¶' => decoded.push('7'),
-            '•' => decoded.push('8'),
-            'ª' => decoded.push('9'),
-            'º' => decoded.push('0'),
-            // Format indicators
-            'h' => decoded.push('h'),
-            'd' => decoded.push('d'),
-            'e' => decoded.push('e'),
-            'r' => decoded.push('r'),
-            '-' => decoded.push('-'),
-            ' ' => decoded.push(' '),
-            c if c.is_ascii_hexdigit() => decoded.push(c),
-            _ => {}  // Skip other characters
-        }
-    }
-    
-    decoded
-}
-
-/// Handle Mac International keyboard mapping
-pub fn decode_mac_intl_format(encoded_str: &str) -> String {
-    if encoded_str.is_empty() {
-        return String::new();
-    }
-    
-    let mut decoded = String::new();
-    
-    for c in encoded_str.chars() {
-        match c {
-            // Standard shift+number mappings
-            '!' => decoded.push('1'),
-            '@' => decoded.push('2'),
-            '#' => decoded.push('3'),
-            '$' => decoded.push('4'),
-            '%' => decoded.push('5'),
-            '^' => decoded.push('6'),
-            '&' => decoded.push('7'),
-            '*' => decoded.push('8'),
-            '(' => decoded.push('9'),
-            ')' => decoded.push('0'),
-            // Mac International specific mappings
-            '¡' => decoded.push('1'),
-            '™' => decoded.push('2'),
-            '£' => decoded.push('3'),
-            '¢' => decoded.push('4'),
-            '∞' => decoded.push('5'),
-            '§' => decoded.push('6'),
-            '¶' => decoded.push('7'),
-            '•' => decoded.push('8'),
-            'ª' => decoded.push('9'),
-            'º' => decoded.push('0'),
-            '±' => decoded.push('='),
-            '≠' => decoded.push('='),
-            '€' => decoded.push('e'),
-            // Additional international characters
-            'ä' => decoded.push('a'),
-            'á' => decoded.push('a'),
-            'à' => decoded.push('a'),
-            'é' => decoded.push('e'),
-            'è' => decoded.push('e'),
-            'í' => decoded.push('i'),
-            'ì' => decoded.push('i'),
-            'ó' => decoded.push('o'),
-            'ò' => decoded.push('o'),
-            'ú' => decoded.push('u'),
-            'ù' => decoded.push('u'),
-            // Format indicators
-            'h' => decoded.push('h'),
-            'd' => decoded.push('d'),
-            'e' => decoded.push('e'),
-            'r' => decoded.push('r'),
-            '-' => decoded.push('-'),
-            ' ' => decoded.push(' '),
-            c if c.is_ascii_hexdigit() => decoded.push(c),
-            _ => {}  // Skip other characters
-        }
-    }
-    
-    decoded
-}
-
 /// Identify manufacturer based on first byte of UID
 pub fn identify_manufacturer(hex_uid: &str) -> String {
-    if hex_uid.len() >= 2 {
-        let manuf_code = &hex_uid[0..2].to_lowercase();
-        match manuf_code.as_str() {
-            "04" => "NXP Semiconductors".to_string(),
-            "05" => "Infineon Technologies".to_string(),
-            "16" => "Texas Instruments".to_string(),
-            "21" => "EM Microelectronic-Marin SA".to_string(),
-            "28" => "LEGIC Identsystems AG".to_string(),
-            "29" => "Gemplus".to_string(),
-            "33" => "Atmel".to_string(),
-            "47" => "Orga Kartensysteme GmbH".to_string(),
-            "49" => "Inside Technology".to_string(),
-            "55" => "Tönnjes C.A.R.D. International".to_string(),
-            "57" => "Giesecke & Devrient".to_string(),
-            "75" => "HID Global".to_string(),
-            "87" => "Identive".to_string(),
-            "95" => "NXP MIFARE Classic".to_string(),
-            "96" => "NXP MIFARE Plus".to_string(),
-            "98" => "NXP MIFARE DESFire".to_string(),
-            _ => "Unknown manufacturer".to_string(),
-        }
-    } else {
-        "Unknown (UID too short)".to_string()
-    }
+    lookup_manufacturer(hex_uid)
 }
 
 /// Interpret format codes from the captured data
 pub fn interpret_format_code(data: &str) -> String {
-    // Look for format indicators
-    if data.contains(" e") || data.contains("-e") {
-        return "QWERTY keyboard layout".to_string();
-    } else if data.contains(" f") || data.contains("-f") {
-        return "AZERTY keyboard layout".to_string();
-    } else if data.contains(" h") || data.contains("-h") {
-        return "QUERTY keyboard layout".to_string();
-    } else if data.contains(" r") || data.contains("-r") {
-        return "Reader-specific format".to_string();
-    } else if data.contains("format description") {
-        return "Format description command".to_string();
-    } else if data.contains("data format") {
-        return "Data format specification".to_string();
-    } else if data.contains("disable buzzer") {
-        return "Reader configuration command".to_string();
-    }
-    
-    // Check for patterns in your log data
-    if data.contains("*h-!)d-e") {
-        return "Card type 1 with QWERTY encoding".to_string();
-    } else if data.contains("@h-#d-$h-%d-e") {
-        return "Card type 2 with QWERTY encoding".to_string();
-    } else if data.contains("*h-e") {
-        return "Card type 3 with QWERTY encoding".to_string();
+    // User defined patterns take priority over the built-in format codes
+    if let Ok(config) = APP_CONFIG.lock() {
+        if let Some(description) = match_format_patterns(data, &config.format_patterns) {
+            return description;
+        }
     }
     
-    "Standard format".to_string()
+    // Fall back to the built-in format indicators
+    uid_codec::interpret_format_code(data)
+        .unwrap_or("Standard format")
+        .to_string()
 }
 
 /// Generate a report about a specific UID
@@ -313,13 +103,7 @@ pub fn generate_uid_report(uid: &str, keyboard_layout: i32) -> String {
         decimal,
         manufacturer,
         format,
-        match keyboard_layout {
-            0 => "Auto-detect",
-            1 => "Windows",
-            2 => "Mac US",
-            3 => "Mac International",
-            _ => "Unknown"
-        }
+        KeyboardLayout::from_index(keyboard_layout).name()
     )
 }
 
@@ -336,7 +120,7 @@ pub fn contains_uid_data(text: &str) -> bool {
     }
     
     // Check for keyboard-encoded characters
-    let special_chars = ['!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '¡', '™', '£', '¢', '∞', '§'];
+    let special_chars = ['!', '@', '#', '$', '%', '^', '&', '*', '(', ')', '¡', '™', '£', '¢', '∞', '§', 'é', 'è', 'ç', 'à'];
     for c in special_chars {
         if text.contains(c) {
             return true;
@@ -357,19 +141,5 @@ pub fn contains_uid_data(text: &str) -> bool {
 
 /// Extended mapping of card types based on UID characteristics
 pub fn identify_card_type(hex_uid: &str) -> String {
-    if hex_uid.is_empty() || hex_uid.contains("Invalid") {
-        return "Unknown card type".to_string();
-    }
-    
-    let len = hex_uid.replace(" ", "").len();
-    
-    match len {
-        8 => "MIFARE Classic (4 byte UID)".to_string(),
-        14 => "MIFARE Classic (7 byte UID)".to_string(),
-        16 => "MIFARE DESFire (8 byte UID)".to_string(),
-        20 => "MIFARE Plus (10 byte UID)".to_string(),
-        4 => "Partial read/Single block ID".to_string(),
-        _ if len < 8 => "Partial/Incomplete UID".to_string(),
-        _ => format!("Non-standard card ({} byte UID)", len / 2)
-    }
-}
\ No newline at end of file
+    uid_codec::identify_card_type(hex_uid)
+}
@@ -0,0 +1,137 @@
+// wiegand.rs
+//
+// Facility code / card number decoding for common corporate badge formats.
+// Readers in Wiegand mode send the raw credential bits, usually printed as
+// hex or decimal by keyboard wedge readers, so the value is checked against
+// each known layout and its parity bits before it is reported.
+
+/// Known Wiegand badge layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeFormat {
+    /// HID H10301 26-bit: 8-bit facility code, 16-bit card number
+    H10301,
+    /// HID Corporate 1000 35-bit: 12-bit company ID, 20-bit card number
+    Corporate1000,
+    /// HID H10304 37-bit: 16-bit facility code, 19-bit card number
+    H10304,
+}
+
+impl BadgeFormat {
+    pub const ALL: [BadgeFormat; 3] = [BadgeFormat::H10301, BadgeFormat::Corporate1000, BadgeFormat::H10304];
+
+    /// Total number of bits including parity
+    pub fn bit_len(&self) -> u32 {
+        match self {
+            BadgeFormat::H10301 => 26,
+            BadgeFormat::Corporate1000 => 35,
+            BadgeFormat::H10304 => 37,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BadgeFormat::H10301 => "H10301 26-bit",
+            BadgeFormat::Corporate1000 => "Corporate 1000 35-bit",
+            BadgeFormat::H10304 => "H10304 37-bit",
+        }
+    }
+}
+
+/// A decoded badge credential
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadgeCredential {
+    pub format: BadgeFormat,
+    pub facility_code: u32,
+    pub card_number: u32,
+}
+
+impl std::fmt::Display for BadgeCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (Facility {}, Card {})", self.format.name(), self.facility_code, self.card_number)
+    }
+}
+
+/// Get bit `pos` counted from the first (leftmost) transmitted bit
+fn bit_at(value: u64, bit_len: u32, pos: u32) -> u64 {
+    (value >> (bit_len - 1 - pos)) & 1
+}
+
+/// Extract `width` bits starting at `start` counted from the first transmitted bit
+fn field(value: u64, bit_len: u32, start: u32, width: u32) -> u32 {
+    ((value >> (bit_len - start - width)) & ((1u64 << width) - 1)) as u32
+}
+
+/// Count set bits at the given positions
+fn ones_at(value: u64, bit_len: u32, positions: impl Iterator<Item = u32>) -> u32 {
+    positions.map(|pos| bit_at(value, bit_len, pos) as u32).sum()
+}
+
+/// Decode a raw Wiegand value under a specific format, checking parity
+pub fn decode(value: u64, format: BadgeFormat) -> Option<BadgeCredential> {
+    let len = format.bit_len();
+    if value >> len != 0 {
+        return None;
+    }
+
+    let (parity_ok, facility_code, card_number) = match format {
+        BadgeFormat::H10301 => {
+            // Leading bit: even parity over bits 0-12, trailing bit: odd parity over bits 13-25
+            let even = ones_at(value, len, 0..13) % 2 == 0;
+            let odd = ones_at(value, len, 13..26) % 2 == 1;
+            (even && odd, field(value, len, 1, 8), field(value, len, 9, 16))
+        },
+        BadgeFormat::H10304 => {
+            // Leading bit: even parity over bits 0-18, trailing bit: odd parity over bits 18-36
+            let even = ones_at(value, len, 0..19) % 2 == 0;
+            let odd = ones_at(value, len, 18..37) % 2 == 1;
+            (even && odd, field(value, len, 1, 16), field(value, len, 17, 19))
+        },
+        BadgeFormat::Corporate1000 => {
+            // Bit 1: even parity over two of every three bits from bit 2,
+            // bit 34: odd parity over two of every three bits from bit 1,
+            // bit 0: odd parity over the whole credential
+            let even = ones_at(value, len, std::iter::once(1).chain((2..34).filter(|pos| pos % 3 != 1))) % 2 == 0;
+            let odd = ones_at(value, len, (1..35).filter(|pos| pos % 3 != 0)) % 2 == 1;
+            let overall = ones_at(value, len, 0..35) % 2 == 1;
+            (even && odd && overall, field(value, len, 2, 12), field(value, len, 14, 20))
+        },
+    };
+
+    if parity_ok {
+        Some(BadgeCredential { format, facility_code, card_number })
+    } else {
+        None
+    }
+}
+
+/// Try every known format against a hex value, returning the first with valid parity.
+/// Only formats whose zero padded hex width matches the input are tried, so a
+/// 4 byte MIFARE UID (8 digits) is never mistaken for a 35 or 37-bit credential.
+pub fn decode_hex(hex: &str) -> Option<BadgeCredential> {
+    let clean_hex: String = hex.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if clean_hex.is_empty() || clean_hex.len() > 10 {
+        return None;
+    }
+
+    let value = u64::from_str_radix(&clean_hex, 16).ok()?;
+    BadgeFormat::ALL.iter()
+        .filter(|format| (format.bit_len() as usize).div_ceil(4) == clean_hex.len())
+        .find_map(|format| decode(value, *format))
+}
+
+/// Describe a scanned UID in badge terms for the reader display
+pub fn describe_badge(hex_uid: &str) -> String {
+    if let Some(credential) = decode_hex(hex_uid) {
+        return credential.to_string();
+    }
+
+    // Wiegand 26 readers without parity report a 4 byte UID as its low 24 bits
+    let clean_hex: String = hex_uid.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if clean_hex.len() == 8 {
+        if let Ok(value) = u32::from_str_radix(&clean_hex, 16) {
+            return format!("Wiegand 26 view (Facility {}, Card {})", (value >> 16) & 0xFF, value & 0xFFFF);
+        }
+    }
+
+    "No badge format detected".to_string()
+}
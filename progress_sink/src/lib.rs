@@ -0,0 +1,172 @@
+//! Progress reports of long operations: dumps, clones, attacks, batch conversions and
+//! imports.
+//!
+//! An operation reports to a [`ProgressSink`] without knowing where the report ends
+//! up: the bar of a command line tool, the progress dialog of the FLTK app, or a status
+//! file the REST API serves. A [`Tracker`] turns the steps done into a percentage and
+//! an estimate of the time left.
+
+pub mod status;
+pub mod terminal;
+
+pub use status::{read_statuses, OperationStatus, StatusFile, STATUS_DIR};
+pub use terminal::TerminalBar;
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Where an operation stands
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    /// 0 to 100
+    pub percent: f64,
+    /// What the operation is doing, e.g. "sector 3 of 16"
+    pub message: String,
+    /// Time left, None until there is a pace to go by
+    pub eta: Option<Duration>,
+}
+
+impl Progress {
+    /// `done` of `total` steps, without an estimate
+    pub fn steps(done: usize, total: usize, message: impl Into<String>) -> Self {
+        let percent = if total == 0 { 100.0 } else { done.min(total) as f64 / total as f64 * 100.0 };
+        Progress { percent, message: message.into(), eta: None }
+    }
+
+    /// 0 to 1, for progress bars
+    pub fn fraction(&self) -> f64 {
+        self.percent / 100.0
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.percent >= 100.0
+    }
+}
+
+impl fmt::Display for Progress {
+    // e.g. "42% sector 7 of 16, about 1m 05s left"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0}%", self.percent)?;
+        if !self.message.is_empty() {
+            write!(f, " {}", self.message)?;
+        }
+        if let Some(eta) = self.eta {
+            write!(f, ", about {} left", format_eta(eta))?;
+        }
+        Ok(())
+    }
+}
+
+/// A time left as "45s", "3m 05s" or "1h 02m"
+pub fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// Something that shows or keeps the progress of an operation. Reports come from the
+/// thread running the operation, a sink for a window hands them to the UI thread.
+pub trait ProgressSink {
+    fn report(&self, progress: &Progress);
+}
+
+/// Nowhere, for callers that don't show progress
+impl ProgressSink for () {
+    fn report(&self, _progress: &Progress) {}
+}
+
+impl<F: Fn(&Progress)> ProgressSink for F {
+    fn report(&self, progress: &Progress) {
+        self(progress);
+    }
+}
+
+/// Both sinks, e.g. the terminal bar and the status file
+impl<A: ProgressSink, B: ProgressSink> ProgressSink for (A, B) {
+    fn report(&self, progress: &Progress) {
+        self.0.report(progress);
+        self.1.report(progress);
+    }
+}
+
+/// Turns the steps done into a [`Progress`], with the time left estimated from the
+/// pace of the steps since the first report. Steps already done at the first report,
+/// e.g. kept from an earlier run, don't count towards the pace.
+pub struct Tracker {
+    started: Instant,
+    first: Option<usize>,
+}
+
+impl Tracker {
+    pub fn new() -> Self {
+        Tracker { started: Instant::now(), first: None }
+    }
+
+    pub fn progress(&mut self, done: usize, total: usize, message: impl Into<String>) -> Progress {
+        let first = match self.first {
+            Some(first) => first,
+            None => {
+                self.started = Instant::now();
+                self.first = Some(done);
+                done
+            }
+        };
+        let mut progress = Progress::steps(done, total, message);
+        progress.eta = estimate(done.saturating_sub(first), total.saturating_sub(done), self.started.elapsed());
+        progress
+    }
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the time `left` steps take at the pace of `paced` steps in `elapsed`
+fn estimate(paced: usize, left: usize, elapsed: Duration) -> Option<Duration> {
+    if paced == 0 || left == 0 {
+        return None;
+    }
+    Some(elapsed.mul_f64(left as f64 / paced as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_steps() {
+        let progress = Progress::steps(3, 12, "sector 3 of 12");
+        assert_eq!(progress.percent, 25.0);
+        assert_eq!(progress.fraction(), 0.25);
+        assert!(!progress.is_done());
+        assert!(Progress::steps(0, 0, "").is_done());
+        assert_eq!(Progress::steps(15, 12, "").percent, 100.0);
+    }
+
+    #[test]
+    fn estimates_from_the_pace() {
+        assert_eq!(estimate(4, 12, Duration::from_secs(2)), Some(Duration::from_secs(6)));
+        assert_eq!(estimate(0, 12, Duration::from_secs(2)), None);
+        assert_eq!(estimate(4, 0, Duration::from_secs(2)), None);
+
+        // steps done before the first report don't make the pace look faster
+        let mut tracker = Tracker::new();
+        assert_eq!(tracker.progress(12, 20, "").eta, None);
+        assert_eq!(tracker.first, Some(12));
+    }
+
+    #[test]
+    fn displays_the_time_left() {
+        let mut progress = Progress::steps(7, 16, "sector 7 of 16");
+        assert_eq!(progress.to_string(), "44% sector 7 of 16");
+        progress.eta = Some(Duration::from_secs(65));
+        assert_eq!(progress.to_string(), "44% sector 7 of 16, about 1m 05s left");
+        assert_eq!(format_eta(Duration::from_secs(45)), "45s");
+        assert_eq!(format_eta(Duration::from_secs(3720)), "1h 02m");
+    }
+}
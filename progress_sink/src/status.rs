@@ -0,0 +1,150 @@
+//! Status files, how the operations running on the Pi tell the REST API where they
+//! stand. Each operation writes its own file in [`STATUS_DIR`] and removes it when it
+//! is done. The file of a program that died is skipped by its process id.
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use crate::{Progress, ProgressSink};
+
+/// Where the status files are, next to the scan FIFO
+pub const STATUS_DIR: &str = "/tmp/rfid_progress";
+
+// numbers the status files of one process
+static NEXT_FILE: AtomicUsize = AtomicUsize::new(1);
+
+/// The progress of one operation in a status file, removed when it is dropped
+pub struct StatusFile {
+    path: PathBuf,
+    operation: String,
+}
+
+impl StatusFile {
+    /// A status file in STATUS_DIR for `operation`, e.g. "nested attack"
+    pub fn new(operation: &str) -> Self {
+        Self::in_dir(STATUS_DIR, operation)
+    }
+
+    pub fn in_dir(dir: impl AsRef<Path>, operation: &str) -> Self {
+        let number = NEXT_FILE.fetch_add(1, Ordering::Relaxed);
+        let path = dir.as_ref().join(format!("{}-{}.status", process::id(), number));
+        StatusFile { path, operation: operation.to_string() }
+    }
+}
+
+impl ProgressSink for StatusFile {
+    // a report that can't be written is dropped, the operation goes on without it
+    fn report(&self, progress: &Progress) {
+        let _ = write_status(&self.path, &self.operation, progress);
+    }
+}
+
+impl Drop for StatusFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+// Three lines: the operation, the percentage and the seconds left ("-" when not known)
+// separated by a tab, and the message. It is written beside the status file and
+// renamed over it, so a reader never sees half of one.
+fn write_status(path: &Path, operation: &str, progress: &Progress) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let eta = progress.eta.map_or_else(|| "-".to_string(), |eta| eta.as_secs().to_string());
+    let text = format!("{}\n{:.1}\t{}\n{}\n", operation, progress.percent, eta, progress.message.replace('\n', " "));
+    let partial = path.with_extension("partial");
+    fs::write(&partial, text)?;
+    fs::rename(&partial, path)
+}
+
+/// An operation running in some program, as read from its status file
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationStatus {
+    pub pid: u32,
+    pub operation: String,
+    pub progress: Progress,
+}
+
+/// The operations with a status file in `dir`, by process
+pub fn read_statuses(dir: impl AsRef<Path>) -> Vec<OperationStatus> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut statuses: Vec<OperationStatus> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "status" {
+                return None;
+            }
+            let pid = path.file_stem()?.to_str()?.split('-').next()?.parse().ok()?;
+            if !process_running(pid) {
+                return None;
+            }
+            parse_status(pid, &fs::read_to_string(&path).ok()?)
+        })
+        .collect();
+    statuses.sort_by(|a, b| (a.pid, &a.operation).cmp(&(b.pid, &b.operation)));
+    statuses
+}
+
+// Without /proc there is no telling, the file is taken as it is
+fn process_running(pid: u32) -> bool {
+    let proc = Path::new("/proc");
+    !proc.exists() || proc.join(pid.to_string()).exists()
+}
+
+fn parse_status(pid: u32, text: &str) -> Option<OperationStatus> {
+    let mut lines = text.lines();
+    let operation = lines.next()?.to_string();
+    let (percent, eta) = lines.next()?.split_once('\t')?;
+    let message = lines.next().unwrap_or_default().to_string();
+    let progress = Progress {
+        percent: percent.parse().ok()?,
+        message,
+        eta: eta.parse().ok().map(Duration::from_secs),
+    };
+    Some(OperationStatus { pid, operation, progress })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_files_come_and_go_with_the_operation() {
+        let dir = std::env::temp_dir().join(format!("progress-sink-{}", process::id()));
+        let status = StatusFile::in_dir(&dir, "nested attack");
+        assert!(read_statuses(&dir).is_empty());
+
+        let mut progress = Progress::steps(5, 15, "keys 5/15");
+        progress.eta = Some(Duration::from_secs(40));
+        status.report(&progress);
+        let statuses = read_statuses(&dir);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].pid, process::id());
+        assert_eq!(statuses[0].operation, "nested attack");
+        assert_eq!(statuses[0].progress.message, "keys 5/15");
+        assert_eq!(statuses[0].progress.eta, Some(Duration::from_secs(40)));
+        assert!((statuses[0].progress.percent - 33.3).abs() < 0.1);
+
+        drop(status);
+        assert!(read_statuses(&dir).is_empty());
+        let _ = fs::remove_dir(&dir);
+    }
+
+    #[test]
+    fn reads_a_status_without_an_estimate() {
+        let status = parse_status(7, "import\n100.0\t-\n\n").unwrap();
+        assert!(status.progress.is_done());
+        assert_eq!(status.progress.eta, None);
+        assert_eq!(status.progress.message, "");
+        assert!(parse_status(7, "import\n").is_none());
+    }
+}
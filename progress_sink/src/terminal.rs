@@ -0,0 +1,60 @@
+//! The progress bar of the command line tools.
+use std::io::{self, Write};
+
+use crate::{Progress, ProgressSink};
+
+/// Redraws one line of the terminal, `[=======>      ] 42% sector 7 of 16`, and ends
+/// the line once the operation is done
+pub struct TerminalBar {
+    width: usize,
+}
+
+impl TerminalBar {
+    pub fn new() -> Self {
+        Self::with_width(30)
+    }
+
+    /// A bar `width` characters long between the brackets
+    pub fn with_width(width: usize) -> Self {
+        TerminalBar { width }
+    }
+}
+
+impl Default for TerminalBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressSink for TerminalBar {
+    fn report(&self, progress: &Progress) {
+        let mut stdout = io::stdout().lock();
+        // the padding clears what is left of a longer line before
+        let _ = write!(stdout, "\r[{}] {:<60}", render_bar(progress.fraction(), self.width), progress.to_string());
+        if progress.is_done() {
+            let _ = writeln!(stdout);
+        }
+        let _ = stdout.flush();
+    }
+}
+
+/// The inside of the bar, `width` characters filled up to `fraction`
+pub fn render_bar(fraction: f64, width: usize) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * width as f64) as usize;
+    (0..width)
+        .map(|i| if i < filled { '=' } else if i == filled { '>' } else { ' ' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_bar() {
+        assert_eq!(render_bar(0.0, 5), ">    ");
+        assert_eq!(render_bar(0.5, 6), "===>  ");
+        assert_eq!(render_bar(1.0, 4), "====");
+        assert_eq!(render_bar(2.0, 4), "====");
+    }
+}
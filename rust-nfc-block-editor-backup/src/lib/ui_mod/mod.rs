@@ -1,8 +0,0 @@
-// Export UI modules
-pub mod attacks;
-pub mod block_ops;
-pub mod card_ops;
-pub mod common; // Make common public
-pub mod main_menu;
-pub mod magic_ops;
-pub mod sector_ops;
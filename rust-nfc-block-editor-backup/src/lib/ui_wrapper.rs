@@ -1,2 +0,0 @@
-
-pub use crate::lib::ui_mod::*;
@@ -0,0 +1,530 @@
+# English, also the fallback for keys missing from the other catalogues
+
+# Startup and the command line tools
+is-intact-but-was-signed = { $file } is intact but was signed by another key: { $public_key }
+valid-signature-by-key = { $file }: valid signature by the key { $public_key }
+compare-key-with-one-sender = Compare the key with the one the sender gave you.
+err-cant-be-trusted = { $file } can't be trusted: { $error }
+nfc-rfid-block-editor = NFC/RFID Block Editor
+initializing = Initializing...
+spi-interface-initialized-successfully = SPI interface initialized successfully.
+err-initialize-spi = Failed to initialize SPI: { $error }
+make-sure-spi-is-enabled = Make sure SPI is enabled on your Raspberry Pi.
+run-sudo-raspi-config-go = Run 'sudo raspi-config', go to 'Interface Options' > 'SPI' and enable it.
+mfrc522-rfid-reader-initialized-successfully = MFRC522 RFID reader initialized successfully.
+err-initialize-mfrc522-rfid-reader = Failed to initialize MFRC522 RFID reader: { $error }
+check-connections-and-ensure-reader = Check connections and ensure reader is properly connected.
+err-in-main-menu = Error in main menu: { $error }
+exiting-program-goodbye = Exiting program. Goodbye!
+created-signing-key-keep-copy = Created the signing key { $path }, keep a copy of it
+fastest-error-free-speed = Fastest error-free speed: { $speed }
+no-speed-ran-without-errors = No speed ran without errors. Check the card is on the reader and uses the default key.
+benchmarking-spi-speeds-per-test = Benchmarking { $count } SPI speeds, { $duration }s per test.
+leave-mifare-classic-1k-card = Leave a Mifare Classic 1K card with the default key on the reader for the whole run.
+press-enter-to-start = Press Enter to start...
+bench-testing = Testing { $speed }...
+timing-trials-done = { $trial }/{ $trials } trials
+timing-transactions-on-block-with = Timing { $trials } transactions on block { $block } with key { $key } at { $speed } Hz.
+leave-card-on-reader-for = Leave the card on the reader for the whole run.
+timings-written-to = { $count } timings written to { $output }
+
+# Menus
+prepare-your-card-you-have = Prepare your card. You have { $seconds } seconds to place it on the reader...
+countdown-seconds = { $number }/{ $seconds } seconds
+reading-card-now = Reading card now...
+err-load = Failed to load { $path }: { $error }
+press-enter-to-continue = Press Enter to continue...
+err-lower-spi-speed = Failed to lower the SPI speed: { $error }
+title-block-editor = NFC/RFID BLOCK EDITOR
+reader-spi = Reader: { $status } (SPI { $speed })
+reader-check-wiring = The reader is not answering, check the wiring and power.
+spi-speed-lowered = Too many communication errors, SPI speed lowered to { $speed }.
+read-only-mode-cards-cant = READ-ONLY MODE ({ $reason }): cards can't be changed
+main-menu = MAIN MENU:
+read-card-uid = Read Card UID
+read-block = Read Block
+write-block = Write Block
+dump-card = Dump Card
+format-card = Format Card
+change-keys = Change Keys
+modify-access-bits = Modify Access Bits
+block-editor-interactive = Block Editor (Interactive)
+test-keys = Test Keys
+data-encryption = Data Encryption ({ $status })
+restore-from-backup = Restore From Backup
+reader-diagnostics = Reader Diagnostics
+key-store-keys = Key Store ({ $count } keys)
+card-profiles = Card Profiles
+credit-test-magic-card = Credit Test (magic card)
+menu-exit = Exit
+enter-your-choice = Enter your choice:
+menu-exiting = Exiting...
+err-invalid-choice-press-enter-to = Invalid choice. Press Enter to continue...
+err-error = Error: { $error }
+title-reader-diagnostics = READER DIAGNOSTICS
+spi-clock = SPI clock: { $speed }
+problem-transactions-are-logged-to = Problem transactions are logged to { $path }
+enter-r-to-reset-counters = Enter 'r' to reset the counters, or press Enter to go back:
+counters-reset = Counters reset.
+title-read-card-uid = READ CARD UID
+read-one-card = Read one card
+scan-continuously-each-card-reported = Scan continuously (each card reported once while it stays on the reader)
+card-uid = Card UID: { $uid }
+uid-as-decimal = UID as decimal: { $decimal }
+no-card-detected-or-error = No card detected or error reading card.
+title-modify-access-bits = MODIFY ACCESS BITS
+enter-sector-number-0-15 = Enter sector number (0-15, 0-39 on a 4K):
+err-invalid-sector-number-must-be = Invalid sector number. Must be between 0 and 39.
+select-access-configuration = Select access configuration:
+transport-all-open-default = Transport (all open, default)
+secure-read-with-key-write = Secure (read with Key A, write with Key B)
+read-only-no-writes-allowed = Read-only (no writes allowed)
+custom-advanced-not-implemented = Custom (advanced, not implemented)
+enter-choice-1-3 = Enter choice (1-3):
+custom-access-bits-not-implemented = Custom access bits not implemented yet. Operation cancelled.
+err-invalid-choice-operation-cancelled = Invalid choice. Operation cancelled.
+you-need-current-key-to = You need the current Key A to modify access bits.
+enter-key-12-hex-chars = Enter Key A (12 hex chars, default FFFFFFFFFFFF):
+err-invalid-key-format-using-default = Invalid key format. Using default key.
+new-access-conditions = New access conditions:
+confirm-access-bits-change-y = Confirm access bits change? (y/n):
+operation-cancelled = Operation cancelled.
+access-bits-modified-successfully = Access bits modified successfully!
+err-modify-access-bits-check-authentication = Failed to modify access bits. Check authentication key and access rights.
+scanning-press-enter-to-stop = Scanning. Press Enter to stop.
+card-decimal = Card { $scans }: { $uid } (decimal { $decimal })
+scan-card-removed = removed: { $uid }
+cards-scanned = { $scans } cards scanned.
+signal-summary = Signal: { $summary }
+title-read-block = READ BLOCK
+enter-block-number-0-63 = Enter block number (0-63, 0-255 on a 4K):
+err-invalid-block-number-must-be = Invalid block number. Must be between 0 and 255.
+warning-block-is-sector-trailer = Warning: Block { $block } is a sector trailer containing access bits and keys.
+select-authentication-method = Select authentication method:
+key-default-ffffffffffff = Key A (default: FFFFFFFFFFFF)
+key-b-default-ffffffffffff = Key B (default: FFFFFFFFFFFF)
+enter-choice-1-2 = Enter choice (1-2):
+err-invalid-choice-using-key-by = Invalid choice. Using Key A by default.
+use-custom-key-y-n = Use custom key? (y/n):
+enter-key-12-hex-chars-2 = Enter key (12 hex chars):
+err-could-not-detect-card = Error: Could not detect card.
+err-could-not-read-card-uid = Error: Could not read card UID.
+card-detected-uid = Card detected. UID: { $uid }
+err-could-not-select-card = Error: Could not select card.
+authentication-failed-try-different-key = Authentication failed. Try a different key.
+block-data = Block { $block } data:
+block-hex = HEX: { $hex }
+key-a-hex = Key A: { $hex }
+access-bits-hex = Access Bits: { $hex }
+key-b-hex = Key B: { $hex }
+access-conditions = Access Conditions:
+block-decrypted = Decrypted: { $hex }
+block-ascii = ASCII: { $ascii }
+err-reading-block-data = Error reading block data.
+title-write-block = WRITE BLOCK
+warning-block-0-contains-manufacturer = WARNING: Block 0 contains manufacturer data and card UID.
+writing-to-this-block-may = Writing to this block may brick your card permanently!
+are-you-absolutely-sure-type = Are you ABSOLUTELY sure? (type YES in uppercase):
+warning-block-is-sector-trailer-2 = WARNING: Block { $block } is a sector trailer containing access bits and keys.
+incorrect-values-may-lock-sector = Incorrect values may lock the sector or the entire card permanently!
+are-you-sure-you-want = Are you sure you want to continue? (y/n):
+choose-data-format = Choose data format:
+text-will-be-padded-to = Text (will be padded to 16 bytes)
+hexadecimal-must-be-exactly-32 = Hexadecimal (must be exactly 32 hex chars)
+enter-text-max-16-chars = Enter text (max 16 chars):
+enter-hex-data-32-chars = Enter hex data (32 chars):
+err-invalid-hex-data-must-be = Invalid hex data. Must be exactly 16 bytes (32 hex chars).
+err-invalid-choice = Invalid choice.
+data-to-be-written = Data to be written:
+confirm-write-y-n = Confirm write? (y/n):
+block-encrypted = Encrypted: { $hex }
+block-written-successfully = Block written successfully!
+err-writing-block-check-access-rights = Error writing block. Check access rights.
+write-sector-trailer = WRITE SECTOR TRAILER
+block-sector = Block: { $block } (Sector { $sector })
+select-access-bits-configuration = Select access bits configuration:
+custom-advanced = Custom (advanced)
+enter-choice-1-4 = Enter choice (1-4):
+custom-access-bits-not-implemented-2 = Custom access bits not implemented yet. Using transport configuration.
+err-invalid-choice-using-transport-configuration = Invalid choice. Using transport configuration.
+enter-key-b-12-hex = Enter Key B (12 hex chars, default FFFFFFFFFFFF):
+authentication-needed-for-current-sector = Authentication needed for current sector trailer.
+use-key-default-ffffffffffff = Use Key A (default: FFFFFFFFFFFF)
+use-key-b-default-ffffffffffff = Use Key B (default: FFFFFFFFFFFF)
+trailer-data-to-be-written = Trailer data to be written:
+access-conditions-2 = Access conditions:
+put-card-back-and-press = Put the card back and press Enter to check it (q to stop):
+title-dump-card = DUMP CARD
+dump-card-optionally-save-to = Dump card (optionally save to a file)
+write-dump-file-to-card = Write a dump file to a card
+files-flipper-zero-nfc-or = Files: Flipper Zero .nfc, or raw .bin/.mfd/.dump (mfoc, mfcuk, nfc-mfclassic)
+of-blocks-read = { $known } of { $total } blocks read.
+decrypt-data-blocks-with-data = Decrypt the data blocks with the data key? (y/n):
+save-to-file-nfc-for = Save to file (.nfc for Flipper Zero, .mfd/.bin for libnfc tools, Enter to skip):
+dump-saved-to = Dump saved to { $path }
+err-save-dump = Failed to save dump: { $error }
+err-dumping-card = Error dumping card.
+sign-dump-y-n = Sign the dump? (y/n):
+signature-saved-to = Signature saved to { $path }
+public-key-to-give-recipient = Public key to give the recipient: { $public_key }
+err-sign-dump = Failed to sign the dump: { $error }
+dump-file-to-write = Dump file to write:
+signature-valid-signed-by-key = Signature valid, signed by the key { $public_key }
+signature-of-doesnt-hold = The signature of { $path } doesn't hold: { $error }
+write-it-anyway-y-n = Write it anyway? (y/n):
+dump-of-card-of-blocks = Dump of card { $uid }: { $known } of { $total } blocks known
+dump-target-card-needs-to = { $layout } dump, the target card needs to be one as well
+keys-for-target-card-hex = Keys for the target card: a hex key, a key file (key list, Proxmark or MCT export, .mfd dump),
+or-enter-for-default-keys = or Enter for the default keys:
+keys-loaded-from = { $count } keys loaded from { $source }
+err-load-keys = Failed to load keys: { $error }
+err-invalid-key-it-must-be = Invalid key, it must be 6 bytes.
+block-0-holds-uid-and = Block 0 holds the UID and can only be written on magic cards.
+write-block-0-y-n = Write block 0? (y/n):
+sector-trailers-set-keys-and = Sector trailers set the keys and access bits. A wrong trailer can lock a sector for good.
+write-sector-trailers-y-n = Write sector trailers? (y/n):
+encrypt-data-blocks-with-data = Encrypt the data blocks with the data key? (y/n):
+no-card-found-to-encrypt = No card found to encrypt the data for.
+blocks-written = { $count } blocks written.
+err-failed-blocks = Failed blocks: { $blocks }
+block-editor = BLOCK EDITOR
+title-test-keys = TEST KEYS
+this-will-test-multiple-keys = This will test multiple keys against the sectors of your card.
+this-process-may-take-some = This process may take some time.
+key-testing-results = Key Testing Results:
+no-working-keys-found-for = No working keys found for any sector.
+sector-key = Sector { $sector }: Key { $hex }
+save-keys-to-key-file = Save keys to a key file for mfoc -f (Enter to skip):
+keys-saved-to = { $count } keys saved to { $path }
+err-save-keys = Failed to save keys: { $error }
+err-testing-keys = Error testing keys: { $error }
+key-store = KEY STORE
+imported-keys-are-saved-to = Imported keys are saved to { $path }
+import-key-file-key-list = Import a key file (key list, MCT .keys, Proxmark chk output, key.bin or JSON, CSV, dump)
+export-keys-as-key-list = Export the keys as a key list
+key-origin-default = built-in default
+key-origin-this-run = found this run
+menu-back = Back
+key-file-to-import = Key file to import:
+err-import = Failed to import { $file }: { $error }
+keys-read-from-new-already = { $count } keys read from { $file } ({ $format }): { $added } new, { $known } already in the store.
+key-store-saved = Key store saved.
+err-save-key-store = Failed to save the key store: { $error }
+save-key-list-to = Save the key list to:
+keys-saved-to-2 = { $count } keys saved to { $file }
+title-card-profiles = CARD PROFILES
+profiles-describe-what-common-cards = Profiles describe what common cards hold and which keys open them.
+add-your-own-as-profile = Add your own as .profile files in { $path }
+skipped-profile = Skipped profile { $problem }
+profile-card-kind = { $kind } card, { $source }
+profile-layout = Layout:
+profile-sector-label = Sector { $sector }: { $label }
+profile-block-label = Block { $block }: { $label }
+profile-keys = Keys:
+profile-key = { $sectors } Key { $key_type }: { $spec }
+journal-commit-record-in-block = Journal: commit record in block { $record }, data in blocks { $blocks }
+no-card-found = No card found.
+decoded = Decoded ({ $profile }):
+profile-workflow-block = { $title }: block { $block } ({ $label })
+profile-label-data = data
+err-hex-data-16-bytes = The hex data must be exactly 16 bytes
+block-couldnt-be-read-none = Block { $block } couldn't be read, none of the keys opens its sector.
+new-content-text-up-to = New content: text (up to 16 chars), or hex:<32 hex chars>, Enter to cancel:
+block-written = Block { $block } written.
+block-was-not-written-no = Block { $block } was not written: no key with write access worked.
+err-invalid-amount = Invalid amount '{ $input }'.
+cards-journal-holds-unfinished-update = The card's journal holds an unfinished update of blocks { $blocks }, read-only mode leaves it.
+finished-update-of-blocks-card = Finished the update of blocks { $blocks } the card was taken away during.
+credit-test = CREDIT TEST
+for-testing-your-own-closed = For testing your own closed-loop system (charging, vending, canteen):
+magic-card-gets-profiles-balance = a magic card gets a profile's balance and keys, is used at the system,
+then-is-read-back-to = then is read back to check the system changed the balance as it should.
+has-no-value-blocks-to = { $profile } has no value blocks to test.
+uid-to-give-card-8 = UID to give the card (8 hex digits, Enter to keep its own):
+err-invalid-uid-it-must-be = Invalid UID, it must be 4 bytes.
+put-profiles-keys-in-sector = Put the profile's keys in the sector trailers? (y/n):
+this-overwrites-card-use-magic = This overwrites the card. Use a magic card, not one of the system's own cards.
+continue-y-n = Continue? (y/n):
+test-card-ready = Test card ready.
+card-was-only-partly-set = The card was only partly set up:
+test-it-anyway-y-n = Test it anyway? (y/n):
+use-card-at-system-under = Use the card at the system under test, then put it back on the reader.
+press-enter-when-it-is = Press Enter when it is back, or q to stop:
+no-card-found-put-it = No card found, put it on the reader and try again.
+credit-use-round = Use { $round }:
+expected-change = { $result } { $check }: { $amount } -> { $amount2 } (expected change: { $expected })
+save-report-as-csv-enter = Save the report as CSV (Enter to skip):
+report-saved-to = Report saved to { $path }
+err-save-report = Failed to save the report: { $error }
+title-format-card = FORMAT CARD
+skipped-template = Skipped template { $problem }
+format-with = Format with:
+template-file = A template file
+new-template = A new template
+add-your-own-as-template = Add your own as .template files in { $path }
+enter-your-choice-enter-to = Enter your choice (Enter to cancel):
+template-file-2 = Template file:
+sector-key-access-key-b = Sector  Key A         Access    Key B         Data
+warning-all-data-in-of = WARNING: All data in { $selection } of these sectors will be lost. Block 0 (manufacturer block) will not be modified.
+type-format-to-format-card = Type FORMAT to format the card, SAVE to save the template to a file:
+template-saved-to = Template saved to { $path }
+card-formatted-successfully = Card formatted successfully.
+err-formatting-card = Error formatting card.
+reading-written-blocks-back = Reading { $count } written blocks back...
+blocks-in-them-e-g = Blocks in them (e.g. 4-6, Enter for every block):
+no-sector-of-card-is = No sector of the card is selected.
+template-name = Template name:
+description-optional = Description (optional):
+sectors-to-format-enter-for = Sectors to format (Enter for 1-15):
+key-12-hex-digits-enter = Key A (12 hex digits, Enter for FFFFFFFFFFFF):
+key-b-12-hex-digits = Key B (12 hex digits, Enter for FFFFFFFFFFFF):
+access-bytes-8-hex-digits = Access bytes (8 hex digits, Enter for transport FF078069):
+make-sectors-ndef-container-y = Make the sectors an NDEF container? (y/n):
+title-restore-from-backup = RESTORE FROM BACKUP
+block-0-and-sector-trailers = Block 0 and the sector trailers are backed up before they are written.
+no-backups-yet = No backups yet.
+backups-newest-first = Backups, newest first:
+backup-to-restore-enter-to = Backup to restore (Enter to go back):
+backup-of-card = Backup of card { $uid }:
+block-hex-line = Block { $block }: { $hex }
+block-key-isnt-known-it = Block { $block }: a key isn't known, it can't be restored
+gen1a-magic-card-takes-blocks = A gen1a magic card takes the blocks through its backdoor. Any other card is
+written-with-key-stores-keys = written with the key store's keys, block 0 only where the card allows it.
+this-overwrites-block-0-and = This overwrites block 0 and the sector trailers on the card. Continue? (y/n):
+title-change-keys = CHANGE KEYS
+you-need-current-key-to-2 = You need the current key to change keys.
+authenticate-with-key = Authenticate with Key A
+authenticate-with-key-b = Authenticate with Key B
+enter-current-key-12-hex = Enter current key (12 hex chars, default FFFFFFFFFFFF):
+which-keys-do-you-want = Which keys do you want to change?
+key-only = Key A only
+key-b-only = Key B only
+both-keys = Both keys
+no-keys-selected-for-change = No keys selected for change. Operation cancelled.
+enter-new-key-12-hex = Enter new Key A (12 hex chars):
+enter-new-key-b-12 = Enter new Key B (12 hex chars):
+changing-keys-for-sector = Changing keys for sector { $sector }:
+new-key = New Key A: { $hex }
+key-unchanged = Key A: (unchanged)
+new-key-b = New Key B: { $hex }
+key-b-unchanged = Key B: (unchanged)
+confirm-key-change-y-n = Confirm key change? (y/n):
+keys-changed-successfully = Keys changed successfully!
+err-change-keys-check-authentication-key = Failed to change keys. Check authentication key and access rights.
+title-data-encryption = DATA ENCRYPTION
+data-blocks-are-encrypted-with = Data blocks are encrypted with AES-128 when written and decrypted when read.
+block-0-and-sector-trailers-2 = Block 0 and the sector trailers are never encrypted.
+encryption-status = Status: { $status }
+use-one-key-for-every = Use one key for every card
+use-master-key-each-cards = Use a master key, each card's key derived from its UID
+data-encryption-is = Data encryption is { $current }.
+data-written-with-current-key = Data written with the current key can only be read with that key.
+replace-current-key-y-n = Replace the current key? (y/n):
+key-32-hex-chars-or = Key (32 hex chars), or Enter to generate one:
+err-invalid-key-it-must-be-2 = Invalid key, it must be 16 bytes.
+data-key-hex = Key: { $hex }
+saved-to-keep-copy-encrypted = Saved to { $path }. Keep a copy, encrypted data can't be read without it.
+
+# Card operations
+formatting-sector = Formatting sector { $sector }...
+block-reset-to-zeros = Block { $block } reset to zeros
+err-reset-block = Failed to reset block { $block }
+sector-trailer-reset-to-factory = Sector trailer reset to factory defaults
+err-reset-sector-trailer = Failed to reset sector trailer
+err-could-not-authenticate-sector-with = Could not authenticate sector { $sector } with any key
+format-complete-successfully-reset-15 = Format complete. Successfully reset { $count }/15 sectors.
+block-written-2 = Block { $block } written: { $hex }
+err-write-block = Failed to write block { $block }
+format-complete-successfully-formatted-sectors = Format complete. Successfully formatted { $count }/{ $total } sectors.
+gen1a-backdoor-open-writing-every = Gen1a backdoor open, writing every known block.
+no-gen1a-backdoor-writing-with = No gen1a backdoor, writing with the card's keys.
+block-write-failed = Block { $block }: write failed
+backed-up-to = Backed up to { $path }
+editor-block-data = Block { $block } data:
+this-is-sector-trailer-block = This is a sector trailer block (Key A, Access Bits, Key B)
+block-trailer-access = Block { $block } (Trailer): { $access }
+err-read-block-data = Failed to read block data.
+are-you-absolutely-sure-type-2 = Are you ABSOLUTELY sure? (type YES in uppercase to confirm):
+warning-block-is-sector-trailer-3 = WARNING: Block { $block } is a sector trailer containing keys and access conditions.
+writing-incorrect-data-may-lock = Writing incorrect data may lock your card or sector permanently!
+block-written-successfully-2 = Block { $block } written successfully!
+data-written = Data written: { $hex }
+err-write-to-block = Failed to write to block { $block }.
+block-editor-menu = BLOCK EDITOR MENU
+editor-data-encryption = Data encryption: { $data_key }
+editor-read-block = Read block
+write-block-text = Write block (text)
+write-block-hex = Write block (hex)
+create-sector-trailer = Create sector trailer
+exit-to-main-menu = Exit to main menu
+enter-choice = Enter choice:
+block-read-successful = Block read successful.
+enter-text-to-write-max = Enter text to write (max 16 chars):
+block-write-successful = Block write successful.
+enter-hex-data-32-hex = Enter hex data (32 hex chars without spaces):
+err-invalid-hex-data-must-be-2 = Invalid hex data. Must be exactly 32 hex characters (16 bytes).
+creating-sector-trailer-for-sector = Creating sector trailer for sector { $sector } (block { $block })
+enter-new-key-12-hex-2 = Enter new Key A (12 hex chars, default FFFFFFFFFFFF):
+enter-new-key-b-12-2 = Enter new Key B (12 hex chars, default FFFFFFFFFFFF):
+sector-trailer-created = Sector trailer created:
+access-conditions-3 = Access Conditions:
+    { $access_bits }
+write-this-trailer-to-block = Write this trailer to block { $block }? (y/n):
+sector-trailer-written-successfully = Sector trailer written successfully!
+err-writing-sector-trailer = Error writing sector trailer: { $error }
+err-creating-sector-trailer = Error creating sector trailer: { $error }
+returning-to-main-menu = Returning to main menu...
+err-invalid-choice-please-try-again = Invalid choice. Please try again.
+err-authentication-check-key = Authentication failed. Check your key.
+err-data-16-bytes = Data must be exactly 16 bytes
+operation-cancelled-by-user = Operation cancelled by user
+enter-key-12-hex-chars-3 = Enter key (12 hex chars, default FFFFFFFFFFFF):
+block-skipped-not-in-dump = Block { $block }: skipped (not in dump)
+card-selected-uid-size = Card selected. UID: { $uid }  Size: { $sak } ({ $layout })
+dump-profile = Profile: { $profile }
+dumping-card-data = Dumping card data ({ $selection })...
+dump-sector = Sector { $sector }
+block-authentication-or-read-failed = Block { $block }: (Authentication or read failed)
+dump-block-label = Block { $block }: { $hex }  [{ $label }]
+block-trailer-key = Block { $block } (Trailer): Key A: { $access }
+no-card-detected = No card detected
+err-get-card-uid = Failed to get card UID
+err-select-card = Failed to select card
+card-selected-uid = Card selected. UID: { $uid }
+dumping-sector = Dumping sector { $sector }:
+block-authentication-failed = Block { $block }: (Authentication failed)
+block-read-failed = Block { $block }: (Read failed)
+card-selected-uid-size-2 = Card selected. UID: { $uid }  Size: { $size } ({ $layout })
+testing-keys = Testing keys...
+dump-sector-heading = Sector { $sector }:
+found-working-key = Found working Key { $key_type }: { $hex }
+waiting-for-card-to-be = Waiting for card to be removed...
+card-removed = Card removed
+err-authenticate-sector-try-with-custom = Failed to authenticate sector { $sector }. Try with custom keys.
+verify-of-blocks-read-back = Verify: { $count } of { $checked } blocks read back as written. { $result }
+block-couldnt-be-read-back = Block { $block }: couldn't be read back
+verify-block = Block { $block }:
+verify-written = written: { $hex }
+on-card = on card: { $actual }
+
+# Menu prompts
+dump-this-may-take-while = Dump { $selection }? This may take a while. Continue? (y/n):
+this-overwrites-data-in-of = This overwrites the data in { $selection } of the card. Continue? (y/n):
+test-y-n = Test { $selection }? (y/n):
+number-or-enter-for-none = { $prompt } (number, or Enter for none):
+write-to-block-y-n = Write { $hex } to block { $block }? (y/n):
+block-is-new-amount-enter = { $name } (block { $block }) is { $now }, new amount{ $unit } (Enter to keep):
+write-blocks-keep-card-on = Write blocks { $blocks }? Keep the card on the reader. (y/n):
+block-to-start-with = { $name } (block { $block }) to start with{ $unit }:
+expected-change-of-e-g = Expected change of { $name }{ $unit } (e.g. -1.80, Enter for any):
+save-to-enter-for = Save to (Enter for { $path }):
+sectors-e-g-1-3 = Sectors (e.g. 1-3,7, Enter for { $default }):
+profile-prompt = Profile
+profile-prompt-annotate = Card profile to annotate the dump with
+profile-prompt-system = Profile of the system's cards
+template-cleared = cleared
+block-now = Now: { $hex }
+
+# Access conditions
+access-bits-invalid = Access bits don't match their inverted copy, the sector is locked
+access-never = Never
+access-read-and-write = R: { $read }, W: { $write }
+access-read-write = R/W: { $keys }
+access-trailer =
+    Key A: { $key_a }
+    Access Bits: { $access_bits }
+    Key B: { $key_b }
+access-block = Block { $block }: { $conditions }
+access-blocks = Blocks { $first }-{ $last }: { $conditions }
+access-trailer-block = Block { $block } (Trailer):
+access-invalid-block-index = Invalid block index
+access-invalid-block-type = Invalid block type
+
+# Data encryption, selections, trailer writes and the journal
+encryption-off = off
+encryption-on-fixed = on, one key for every card
+encryption-on-per-uid = on, key derived from each card's UID
+encryption-not-set-up = not set up
+turn-encryption-on = Turn encryption on
+turn-encryption-off = Turn encryption off
+selection-all = all
+selection-all-sectors = all sectors
+selection-sector = sector { $sector }
+selection-sectors = sectors { $sectors }
+selection-blocks = blocks { $blocks }
+selection-sectors-blocks = { $sectors }, blocks { $blocks }
+journal-done = Update written and read back.
+journal-rolled-back = The update couldn't be made, the card still holds the old data in every block.
+trailer-written = Sector { $sector } trailer written and read back.
+trailer-written-attempt = Sector { $sector } trailer written and read back on attempt { $attempt }.
+trailer-refused =
+    The card refused the sector { $sector } trailer { $attempts } times, it doesn't hold the new one.
+    Check that the access bits let the key used write the trailer.
+trailer-card-gone =
+    The card left the reader while the sector { $sector } trailer was written. It may hold
+    the old trailer, the new one or a torn one: put it back on the reader, without
+    moving it, to have the sector checked.
+trailer-torn =
+    Neither the old key nor the new keys open sector { $sector }: the trailer write was torn.
+    The trailer as it was before is in the backup (Restore From Backup). A magic
+    card takes it back through its backdoor, any other card keeps the sector locked.
+journal-pending =
+    The card left the reader during the update, its journal has what is needed to
+    finish it. Put the card back on the reader, before it is used anywhere else, and
+    read it with the profile.
+
+# Profile values and records
+decode-value = { $name }: { $amount } (block { $block })
+decode-value-not-read = { $name }: not read (block { $block })
+decode-value-invalid = { $name }: block { $block } is not a valid value block
+decode-record = { $name } (block { $block })
+decode-record-numbered = { $name } { $number } (block { $block })
+decode-record-not-read = { $name }: not read
+decode-record-empty = { $name }: empty
+decode-outside-block = (outside the block)
+
+# Credit test
+problem-values = Values: { $guidance }
+problem-value-not-written = Block { $block }: the value couldn't be written
+problem-trailer-not-read = Sector { $sector }: the trailer couldn't be read, its keys were left
+problem-trailer-not-written = Sector { $sector }: the trailer couldn't be written
+problem-uid-not-written = Block 0: the UID couldn't be written, the card may not be a magic card
+problem-block0-not-read = Block 0 couldn't be read, the UID was left
+check-block = Block { $block }
+result-pass = PASS
+result-fail = FAIL
+expected-change-any = any
+credit-value = Value
+value-unreadable = unreadable
+amount-unit = in { $unit }
+
+# Benchmark and timing tables
+bench-spi-clock = SPI clock
+bench-uid-polls = UID polls/s
+bench-auth-reads = auth+read/s
+bench-full-dump = full dump
+bench-errors = { $count } err
+bench-no-reader = no reader
+bench-blocks = { $count }/64 blk
+bench-failed = failed
+timing-command = command
+timing-ok-tried = ok/tried
+timing-min = min us
+timing-median = median
+timing-max = max us
+
+# Command line options
+opt-speeds = --speeds needs a list, e.g. 500k,1M,4M
+opt-seconds = --seconds needs a positive number
+opt-trials = --trials needs a positive number
+opt-block = --block needs a block number from 0 to 255
+opt-key = --key needs 6 bytes of hex, e.g. FFFFFFFFFFFF
+opt-spi-speed = --spi-speed needs a speed, e.g. 1M or 500k
+opt-output = --output needs a file name
+opt-invalid-speed = Invalid SPI speed '{ $speed }'
+opt-unknown-benchmark = Unknown benchmark option '{ $option }'
+opt-unknown-timing = Unknown timing option '{ $option }'
+opt-verify-usage = Usage: verify <file> [--key <public key>]
+selection-flag-needs-list = { $flag } needs a list, e.g. 1-3,7
+selection-not-a-range = '{ $part }' is not a number or range
+selection-beyond-last = { $number } is beyond the last one, { $last }
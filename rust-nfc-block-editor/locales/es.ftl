@@ -0,0 +1,530 @@
+# Español
+
+# Arranque y herramientas de línea de comandos
+is-intact-but-was-signed = { $file } está intacto pero fue firmado por otra clave: { $public_key }
+valid-signature-by-key = { $file }: firma válida de la clave { $public_key }
+compare-key-with-one-sender = Compare la clave con la que le dio el remitente.
+err-cant-be-trusted = No se puede confiar en { $file }: { $error }
+nfc-rfid-block-editor = Editor de bloques NFC/RFID
+initializing = Inicializando...
+spi-interface-initialized-successfully = Interfaz SPI inicializada correctamente.
+err-initialize-spi = No se pudo inicializar SPI: { $error }
+make-sure-spi-is-enabled = Asegúrese de que SPI esté activado en su Raspberry Pi.
+run-sudo-raspi-config-go = Ejecute 'sudo raspi-config', vaya a 'Interface Options' > 'SPI' y actívelo.
+mfrc522-rfid-reader-initialized-successfully = Lector RFID MFRC522 inicializado correctamente.
+err-initialize-mfrc522-rfid-reader = No se pudo inicializar el lector RFID MFRC522: { $error }
+check-connections-and-ensure-reader = Revise las conexiones y que el lector esté bien conectado.
+err-in-main-menu = Error en el menú principal: { $error }
+exiting-program-goodbye = Saliendo del programa. ¡Adiós!
+created-signing-key-keep-copy = Se creó la clave de firma { $path }, guarde una copia
+fastest-error-free-speed = Velocidad más rápida sin errores: { $speed }
+no-speed-ran-without-errors = Ninguna velocidad funcionó sin errores. Compruebe que la tarjeta esté en el lector y use la clave por defecto.
+benchmarking-spi-speeds-per-test = Midiendo { $count } velocidades SPI, { $duration }s por prueba.
+leave-mifare-classic-1k-card = Deje una tarjeta Mifare Classic 1K con la clave por defecto en el lector durante toda la prueba.
+press-enter-to-start = Pulse Enter para empezar...
+bench-testing = Probando { $speed }...
+timing-trials-done = { $trial }/{ $trials } pruebas
+timing-transactions-on-block-with = Midiendo { $trials } transacciones en el bloque { $block } con la clave { $key } a { $speed } Hz.
+leave-card-on-reader-for = Deje la tarjeta en el lector durante toda la prueba.
+timings-written-to = { $count } tiempos escritos en { $output }
+
+# Menús
+prepare-your-card-you-have = Prepare su tarjeta. Tiene { $seconds } segundos para ponerla en el lector...
+countdown-seconds = { $number }/{ $seconds } segundos
+reading-card-now = Leyendo la tarjeta...
+err-load = No se pudo cargar { $path }: { $error }
+press-enter-to-continue = Pulse Enter para continuar...
+err-lower-spi-speed = No se pudo bajar la velocidad SPI: { $error }
+title-block-editor = EDITOR DE BLOQUES NFC/RFID
+reader-spi = Lector: { $status } (SPI { $speed })
+reader-check-wiring = El lector no responde, revisa el cableado y la alimentación.
+spi-speed-lowered = Demasiados errores de comunicación, velocidad SPI reducida a { $speed }.
+read-only-mode-cards-cant = MODO SOLO LECTURA ({ $reason }): las tarjetas no se pueden modificar
+main-menu = MENÚ PRINCIPAL:
+read-card-uid = Leer UID de la tarjeta
+read-block = Leer bloque
+write-block = Escribir bloque
+dump-card = Volcar tarjeta
+format-card = Formatear tarjeta
+change-keys = Cambiar claves
+modify-access-bits = Modificar bits de acceso
+block-editor-interactive = Editor de bloques (interactivo)
+test-keys = Probar claves
+data-encryption = Cifrado de datos ({ $status })
+restore-from-backup = Restaurar desde copia de seguridad
+reader-diagnostics = Diagnóstico del lector
+key-store-keys = Almacén de claves ({ $count } claves)
+card-profiles = Perfiles de tarjeta
+credit-test-magic-card = Prueba de crédito (tarjeta mágica)
+menu-exit = Salir
+enter-your-choice = Introduzca su opción:
+menu-exiting = Saliendo...
+err-invalid-choice-press-enter-to = Opción no válida. Pulse Enter para continuar...
+err-error = Error: { $error }
+title-reader-diagnostics = DIAGNÓSTICO DEL LECTOR
+spi-clock = Reloj SPI: { $speed }
+problem-transactions-are-logged-to = Las transacciones con problemas se registran en { $path }
+enter-r-to-reset-counters = Introduzca 'r' para poner a cero los contadores, o pulse Enter para volver:
+counters-reset = Contadores a cero.
+title-read-card-uid = LEER UID DE LA TARJETA
+read-one-card = Leer una tarjeta
+scan-continuously-each-card-reported = Escanear continuamente (cada tarjeta se informa una vez mientras siga en el lector)
+card-uid = UID de la tarjeta: { $uid }
+uid-as-decimal = UID en decimal: { $decimal }
+no-card-detected-or-error = No se detectó ninguna tarjeta o hubo un error al leerla.
+title-modify-access-bits = MODIFICAR BITS DE ACCESO
+enter-sector-number-0-15 = Introduzca el número de sector (0-15, 0-39 en una 4K):
+err-invalid-sector-number-must-be = Número de sector no válido. Debe estar entre 0 y 39.
+select-access-configuration = Seleccione la configuración de acceso:
+transport-all-open-default = Transporte (todo abierto, por defecto)
+secure-read-with-key-write = Segura (lectura con la clave A, escritura con la clave B)
+read-only-no-writes-allowed = Solo lectura (no se permite escribir)
+custom-advanced-not-implemented = Personalizada (avanzado, no implementado)
+enter-choice-1-3 = Introduzca una opción (1-3):
+custom-access-bits-not-implemented = Los bits de acceso personalizados aún no están implementados. Operación cancelada.
+err-invalid-choice-operation-cancelled = Opción no válida. Operación cancelada.
+you-need-current-key-to = Necesita la clave A actual para modificar los bits de acceso.
+enter-key-12-hex-chars = Introduzca la clave A (12 caracteres hex, por defecto FFFFFFFFFFFF):
+err-invalid-key-format-using-default = Formato de clave no válido. Se usa la clave por defecto.
+new-access-conditions = Nuevas condiciones de acceso:
+confirm-access-bits-change-y = ¿Confirmar el cambio de los bits de acceso? (y/n):
+operation-cancelled = Operación cancelada.
+access-bits-modified-successfully = ¡Bits de acceso modificados correctamente!
+err-modify-access-bits-check-authentication = No se pudieron modificar los bits de acceso. Revise la clave de autenticación y los permisos de acceso.
+scanning-press-enter-to-stop = Escaneando. Pulse Enter para parar.
+card-decimal = Tarjeta { $scans }: { $uid } (decimal { $decimal })
+scan-card-removed = retirada: { $uid }
+cards-scanned = { $scans } tarjetas escaneadas.
+signal-summary = Señal: { $summary }
+title-read-block = LEER BLOQUE
+enter-block-number-0-63 = Introduzca el número de bloque (0-63, 0-255 en una 4K):
+err-invalid-block-number-must-be = Número de bloque no válido. Debe estar entre 0 y 255.
+warning-block-is-sector-trailer = Aviso: el bloque { $block } es un trailer de sector con los bits de acceso y las claves.
+select-authentication-method = Seleccione el método de autenticación:
+key-default-ffffffffffff = Clave A (por defecto: FFFFFFFFFFFF)
+key-b-default-ffffffffffff = Clave B (por defecto: FFFFFFFFFFFF)
+enter-choice-1-2 = Introduzca una opción (1-2):
+err-invalid-choice-using-key-by = Opción no válida. Se usa la clave A por defecto.
+use-custom-key-y-n = ¿Usar una clave personalizada? (y/n):
+enter-key-12-hex-chars-2 = Introduzca la clave (12 caracteres hex):
+err-could-not-detect-card = Error: no se pudo detectar la tarjeta.
+err-could-not-read-card-uid = Error: no se pudo leer el UID de la tarjeta.
+card-detected-uid = Tarjeta detectada. UID: { $uid }
+err-could-not-select-card = Error: no se pudo seleccionar la tarjeta.
+authentication-failed-try-different-key = La autenticación falló. Pruebe otra clave.
+block-data = Datos del bloque { $block }:
+block-hex = HEX: { $hex }
+key-a-hex = Clave A: { $hex }
+access-bits-hex = Bits de acceso: { $hex }
+key-b-hex = Clave B: { $hex }
+access-conditions = Condiciones de acceso:
+block-decrypted = Descifrado: { $hex }
+block-ascii = ASCII: { $ascii }
+err-reading-block-data = Error al leer los datos del bloque.
+title-write-block = ESCRIBIR BLOQUE
+warning-block-0-contains-manufacturer = AVISO: el bloque 0 contiene los datos del fabricante y el UID de la tarjeta.
+writing-to-this-block-may = ¡Escribir en este bloque puede inutilizar la tarjeta para siempre!
+are-you-absolutely-sure-type = ¿Está COMPLETAMENTE seguro? (escriba YES en mayúsculas):
+warning-block-is-sector-trailer-2 = AVISO: el bloque { $block } es un trailer de sector con los bits de acceso y las claves.
+incorrect-values-may-lock-sector = ¡Valores incorrectos pueden bloquear el sector o toda la tarjeta para siempre!
+are-you-sure-you-want = ¿Seguro que quiere continuar? (y/n):
+choose-data-format = Elija el formato de los datos:
+text-will-be-padded-to = Texto (se rellena hasta 16 bytes)
+hexadecimal-must-be-exactly-32 = Hexadecimal (exactamente 32 caracteres hex)
+enter-text-max-16-chars = Introduzca el texto (máx. 16 caracteres):
+enter-hex-data-32-chars = Introduzca los datos hex (32 caracteres):
+err-invalid-hex-data-must-be = Datos hex no válidos. Deben ser exactamente 16 bytes (32 caracteres hex).
+err-invalid-choice = Opción no válida.
+data-to-be-written = Datos a escribir:
+confirm-write-y-n = ¿Confirmar la escritura? (y/n):
+block-encrypted = Cifrado: { $hex }
+block-written-successfully = ¡Bloque escrito correctamente!
+err-writing-block-check-access-rights = Error al escribir el bloque. Revise los permisos de acceso.
+write-sector-trailer = ESCRIBIR TRAILER DE SECTOR
+block-sector = Bloque: { $block } (sector { $sector })
+select-access-bits-configuration = Seleccione la configuración de los bits de acceso:
+custom-advanced = Personalizada (avanzado)
+enter-choice-1-4 = Introduzca una opción (1-4):
+custom-access-bits-not-implemented-2 = Los bits de acceso personalizados aún no están implementados. Se usa la configuración de transporte.
+err-invalid-choice-using-transport-configuration = Opción no válida. Se usa la configuración de transporte.
+enter-key-b-12-hex = Introduzca la clave B (12 caracteres hex, por defecto FFFFFFFFFFFF):
+authentication-needed-for-current-sector = Hace falta autenticarse con el trailer de sector actual.
+use-key-default-ffffffffffff = Usar la clave A (por defecto: FFFFFFFFFFFF)
+use-key-b-default-ffffffffffff = Usar la clave B (por defecto: FFFFFFFFFFFF)
+trailer-data-to-be-written = Datos del trailer a escribir:
+access-conditions-2 = Condiciones de acceso:
+put-card-back-and-press = Vuelva a poner la tarjeta y pulse Enter para comprobarla (q para parar):
+title-dump-card = VOLCAR TARJETA
+dump-card-optionally-save-to = Volcar la tarjeta (y opcionalmente guardarla en un archivo)
+write-dump-file-to-card = Escribir un archivo de volcado en una tarjeta
+files-flipper-zero-nfc-or = Archivos: .nfc de Flipper Zero, o .bin/.mfd/.dump sin formato (mfoc, mfcuk, nfc-mfclassic)
+of-blocks-read = { $known } de { $total } bloques leídos.
+decrypt-data-blocks-with-data = ¿Descifrar los bloques de datos con la clave de datos? (y/n):
+save-to-file-nfc-for = Guardar en un archivo (.nfc para Flipper Zero, .mfd/.bin para las herramientas de libnfc, Enter para omitir):
+dump-saved-to = Volcado guardado en { $path }
+err-save-dump = No se pudo guardar el volcado: { $error }
+err-dumping-card = Error al volcar la tarjeta.
+sign-dump-y-n = ¿Firmar el volcado? (y/n):
+signature-saved-to = Firma guardada en { $path }
+public-key-to-give-recipient = Clave pública para el destinatario: { $public_key }
+err-sign-dump = No se pudo firmar el volcado: { $error }
+dump-file-to-write = Archivo de volcado a escribir:
+signature-valid-signed-by-key = Firma válida, firmada por la clave { $public_key }
+signature-of-doesnt-hold = La firma de { $path } no es válida: { $error }
+write-it-anyway-y-n = ¿Escribirlo de todos modos? (y/n):
+dump-of-card-of-blocks = Volcado de la tarjeta { $uid }: { $known } de { $total } bloques conocidos
+dump-target-card-needs-to = Volcado de { $layout }, la tarjeta de destino también debe serlo
+keys-for-target-card-hex = Claves para la tarjeta de destino: una clave hex, un archivo de claves (lista de claves, exportación de Proxmark o MCT, volcado .mfd),
+or-enter-for-default-keys = o Enter para las claves por defecto:
+keys-loaded-from = { $count } claves cargadas de { $source }
+err-load-keys = No se pudieron cargar las claves: { $error }
+err-invalid-key-it-must-be = Clave no válida, debe tener 6 bytes.
+block-0-holds-uid-and = El bloque 0 contiene el UID y solo se puede escribir en tarjetas mágicas.
+write-block-0-y-n = ¿Escribir el bloque 0? (y/n):
+sector-trailers-set-keys-and = Los trailers de sector fijan las claves y los bits de acceso. Un trailer erróneo puede bloquear un sector para siempre.
+write-sector-trailers-y-n = ¿Escribir los trailers de sector? (y/n):
+encrypt-data-blocks-with-data = ¿Cifrar los bloques de datos con la clave de datos? (y/n):
+no-card-found-to-encrypt = No se encontró ninguna tarjeta para la que cifrar los datos.
+blocks-written = { $count } bloques escritos.
+err-failed-blocks = Bloques fallidos: { $blocks }
+block-editor = EDITOR DE BLOQUES
+title-test-keys = PROBAR CLAVES
+this-will-test-multiple-keys = Se probarán varias claves contra los sectores de su tarjeta.
+this-process-may-take-some = Este proceso puede tardar un poco.
+key-testing-results = Resultados de la prueba de claves:
+no-working-keys-found-for = No se encontraron claves válidas para ningún sector.
+sector-key = Sector { $sector }: clave { $hex }
+save-keys-to-key-file = Guardar las claves en un archivo de claves para mfoc -f (Enter para omitir):
+keys-saved-to = { $count } claves guardadas en { $path }
+err-save-keys = No se pudieron guardar las claves: { $error }
+err-testing-keys = Error al probar las claves: { $error }
+key-store = ALMACÉN DE CLAVES
+imported-keys-are-saved-to = Las claves importadas se guardan en { $path }
+import-key-file-key-list = Importar un archivo de claves (lista de claves, .keys de MCT, salida de chk de Proxmark, key.bin o JSON, CSV, volcado)
+export-keys-as-key-list = Exportar las claves como lista de claves
+key-origin-default = predeterminada
+key-origin-this-run = encontrada en esta sesión
+menu-back = Volver
+key-file-to-import = Archivo de claves a importar:
+err-import = No se pudo importar { $file }: { $error }
+keys-read-from-new-already = { $count } claves leídas de { $file } ({ $format }): { $added } nuevas, { $known } ya estaban en el almacén.
+key-store-saved = Almacén de claves guardado.
+err-save-key-store = No se pudo guardar el almacén de claves: { $error }
+save-key-list-to = Guardar la lista de claves en:
+keys-saved-to-2 = { $count } claves guardadas en { $file }
+title-card-profiles = PERFILES DE TARJETA
+profiles-describe-what-common-cards = Los perfiles describen lo que contienen las tarjetas habituales y qué claves las abren.
+add-your-own-as-profile = Añada los suyos como archivos .profile en { $path }
+skipped-profile = Perfil omitido { $problem }
+profile-card-kind = Tarjeta { $kind }, { $source }
+profile-layout = Estructura:
+profile-sector-label = Sector { $sector }: { $label }
+profile-block-label = Bloque { $block }: { $label }
+profile-keys = Claves:
+profile-key = { $sectors } clave { $key_type }: { $spec }
+journal-commit-record-in-block = Diario: registro de confirmación en el bloque { $record }, datos en los bloques { $blocks }
+no-card-found = No se encontró ninguna tarjeta.
+decoded = Decodificado ({ $profile }):
+profile-workflow-block = { $title }: bloque { $block } ({ $label })
+profile-label-data = datos
+err-hex-data-16-bytes = Los datos hex deben ser exactamente 16 bytes
+block-couldnt-be-read-none = No se pudo leer el bloque { $block }, ninguna clave abre su sector.
+new-content-text-up-to = Nuevo contenido: texto (hasta 16 caracteres), o hex:<32 caracteres hex>, Enter para cancelar:
+block-written = Bloque { $block } escrito.
+block-was-not-written-no = El bloque { $block } no se escribió: ninguna clave con permiso de escritura funcionó.
+err-invalid-amount = Importe no válido '{ $input }'.
+cards-journal-holds-unfinished-update = El diario de la tarjeta contiene una actualización sin terminar de los bloques { $blocks }, el modo solo lectura la deja como está.
+finished-update-of-blocks-card = Se terminó la actualización de los bloques { $blocks } durante la que se retiró la tarjeta.
+credit-test = PRUEBA DE CRÉDITO
+for-testing-your-own-closed = Para probar su propio sistema de circuito cerrado (carga, venta automática, comedor):
+magic-card-gets-profiles-balance = una tarjeta mágica recibe el saldo y las claves de un perfil, se usa en el sistema
+then-is-read-back-to = y después se vuelve a leer para comprobar que el sistema cambió el saldo como debía.
+has-no-value-blocks-to = { $profile } no tiene bloques de valor que probar.
+uid-to-give-card-8 = UID para la tarjeta (8 dígitos hex, Enter para conservar el suyo):
+err-invalid-uid-it-must-be = UID no válido, debe tener 4 bytes.
+put-profiles-keys-in-sector = ¿Poner las claves del perfil en los trailers de sector? (y/n):
+this-overwrites-card-use-magic = Esto sobrescribe la tarjeta. Use una tarjeta mágica, no una de las tarjetas del propio sistema.
+continue-y-n = ¿Continuar? (y/n):
+test-card-ready = Tarjeta de prueba lista.
+card-was-only-partly-set = La tarjeta solo se preparó en parte:
+test-it-anyway-y-n = ¿Probarla de todos modos? (y/n):
+use-card-at-system-under = Use la tarjeta en el sistema bajo prueba y vuelva a ponerla en el lector.
+press-enter-when-it-is = Pulse Enter cuando esté de vuelta, o q para parar:
+no-card-found-put-it = No se encontró ninguna tarjeta, póngala en el lector y vuelva a intentarlo.
+credit-use-round = Uso { $round }:
+expected-change = { $result } { $check }: { $amount } -> { $amount2 } (cambio esperado: { $expected })
+save-report-as-csv-enter = Guardar el informe como CSV (Enter para omitir):
+report-saved-to = Informe guardado en { $path }
+err-save-report = No se pudo guardar el informe: { $error }
+title-format-card = FORMATEAR TARJETA
+skipped-template = Plantilla omitida { $problem }
+format-with = Formatear con:
+template-file = Un archivo de plantilla
+new-template = Una plantilla nueva
+add-your-own-as-template = Añada las suyas como archivos .template en { $path }
+enter-your-choice-enter-to = Introduzca su opción (Enter para cancelar):
+template-file-2 = Archivo de plantilla:
+sector-key-access-key-b = Sector  Clave A       Acceso    Clave B       Datos
+warning-all-data-in-of = AVISO: se perderán todos los datos en { $selection } de estos sectores. El bloque 0 (bloque del fabricante) no se modificará.
+type-format-to-format-card = Escriba FORMAT para formatear la tarjeta, SAVE para guardar la plantilla en un archivo:
+template-saved-to = Plantilla guardada en { $path }
+card-formatted-successfully = Tarjeta formateada correctamente.
+err-formatting-card = Error al formatear la tarjeta.
+reading-written-blocks-back = Releyendo { $count } bloques escritos...
+blocks-in-them-e-g = Bloques en ellos (p. ej. 4-6, Enter para todos los bloques):
+no-sector-of-card-is = No hay ningún sector de la tarjeta seleccionado.
+template-name = Nombre de la plantilla:
+description-optional = Descripción (opcional):
+sectors-to-format-enter-for = Sectores a formatear (Enter para 1-15):
+key-12-hex-digits-enter = Clave A (12 dígitos hex, Enter para FFFFFFFFFFFF):
+key-b-12-hex-digits = Clave B (12 dígitos hex, Enter para FFFFFFFFFFFF):
+access-bytes-8-hex-digits = Bytes de acceso (8 dígitos hex, Enter para el de transporte FF078069):
+make-sectors-ndef-container-y = ¿Convertir los sectores en un contenedor NDEF? (y/n):
+title-restore-from-backup = RESTAURAR DESDE COPIA DE SEGURIDAD
+block-0-and-sector-trailers = Se hace una copia del bloque 0 y de los trailers de sector antes de escribirlos.
+no-backups-yet = Aún no hay copias de seguridad.
+backups-newest-first = Copias de seguridad, la más reciente primero:
+backup-to-restore-enter-to = Copia a restaurar (Enter para volver):
+backup-of-card = Copia de la tarjeta { $uid }:
+block-hex-line = Bloque { $block }: { $hex }
+block-key-isnt-known-it = Bloque { $block }: no se conoce una clave, no se puede restaurar
+gen1a-magic-card-takes-blocks = Una tarjeta mágica gen1a recibe los bloques por su puerta trasera. Cualquier otra tarjeta
+written-with-key-stores-keys = se escribe con las claves del almacén, el bloque 0 solo si la tarjeta lo permite.
+this-overwrites-block-0-and = Esto sobrescribe el bloque 0 y los trailers de sector de la tarjeta. ¿Continuar? (y/n):
+title-change-keys = CAMBIAR CLAVES
+you-need-current-key-to-2 = Necesita la clave actual para cambiar las claves.
+authenticate-with-key = Autenticarse con la clave A
+authenticate-with-key-b = Autenticarse con la clave B
+enter-current-key-12-hex = Introduzca la clave actual (12 caracteres hex, por defecto FFFFFFFFFFFF):
+which-keys-do-you-want = ¿Qué claves quiere cambiar?
+key-only = Solo la clave A
+key-b-only = Solo la clave B
+both-keys = Las dos claves
+no-keys-selected-for-change = No se eligió ninguna clave para cambiar. Operación cancelada.
+enter-new-key-12-hex = Introduzca la nueva clave A (12 caracteres hex):
+enter-new-key-b-12 = Introduzca la nueva clave B (12 caracteres hex):
+changing-keys-for-sector = Cambiando las claves del sector { $sector }:
+new-key = Nueva clave A: { $hex }
+key-unchanged = Clave A: (sin cambios)
+new-key-b = Nueva clave B: { $hex }
+key-b-unchanged = Clave B: (sin cambios)
+confirm-key-change-y-n = ¿Confirmar el cambio de claves? (y/n):
+keys-changed-successfully = ¡Claves cambiadas correctamente!
+err-change-keys-check-authentication-key = No se pudieron cambiar las claves. Revise la clave de autenticación y los permisos de acceso.
+title-data-encryption = CIFRADO DE DATOS
+data-blocks-are-encrypted-with = Los bloques de datos se cifran con AES-128 al escribirlos y se descifran al leerlos.
+block-0-and-sector-trailers-2 = El bloque 0 y los trailers de sector nunca se cifran.
+encryption-status = Estado: { $status }
+use-one-key-for-every = Usar una clave para todas las tarjetas
+use-master-key-each-cards = Usar una clave maestra, la clave de cada tarjeta se deriva de su UID
+data-encryption-is = El cifrado de datos está { $current }.
+data-written-with-current-key = Los datos escritos con la clave actual solo se pueden leer con esa clave.
+replace-current-key-y-n = ¿Reemplazar la clave actual? (y/n):
+key-32-hex-chars-or = Clave (32 caracteres hex), o Enter para generar una:
+err-invalid-key-it-must-be-2 = Clave no válida, debe tener 16 bytes.
+data-key-hex = Clave: { $hex }
+saved-to-keep-copy-encrypted = Guardada en { $path }. Guarde una copia, los datos cifrados no se pueden leer sin ella.
+
+# Operaciones con la tarjeta
+formatting-sector = Formateando el sector { $sector }...
+block-reset-to-zeros = Bloque { $block } puesto a ceros
+err-reset-block = No se pudo restablecer el bloque { $block }
+sector-trailer-reset-to-factory = Trailer de sector restablecido a los valores de fábrica
+err-reset-sector-trailer = No se pudo restablecer el trailer de sector
+err-could-not-authenticate-sector-with = No se pudo autenticar el sector { $sector } con ninguna clave
+format-complete-successfully-reset-15 = Formateo terminado. Se restablecieron { $count }/15 sectores.
+block-written-2 = Bloque { $block } escrito: { $hex }
+err-write-block = No se pudo escribir el bloque { $block }
+format-complete-successfully-formatted-sectors = Formateo terminado. Se formatearon { $count }/{ $total } sectores.
+gen1a-backdoor-open-writing-every = Puerta trasera gen1a abierta, escribiendo todos los bloques conocidos.
+no-gen1a-backdoor-writing-with = Sin puerta trasera gen1a, escribiendo con las claves de la tarjeta.
+block-write-failed = Bloque { $block }: la escritura falló
+backed-up-to = Copia guardada en { $path }
+editor-block-data = Datos del bloque { $block }:
+this-is-sector-trailer-block = Este es un bloque trailer de sector (clave A, bits de acceso, clave B)
+block-trailer-access = Bloque { $block } (trailer): { $access }
+err-read-block-data = No se pudieron leer los datos del bloque.
+are-you-absolutely-sure-type-2 = ¿Está COMPLETAMENTE seguro? (escriba YES en mayúsculas para confirmar):
+warning-block-is-sector-trailer-3 = AVISO: el bloque { $block } es un trailer de sector con las claves y las condiciones de acceso.
+writing-incorrect-data-may-lock = ¡Escribir datos incorrectos puede bloquear la tarjeta o el sector para siempre!
+block-written-successfully-2 = ¡Bloque { $block } escrito correctamente!
+data-written = Datos escritos: { $hex }
+err-write-to-block = No se pudo escribir en el bloque { $block }.
+block-editor-menu = MENÚ DEL EDITOR DE BLOQUES
+editor-data-encryption = Cifrado de datos: { $data_key }
+editor-read-block = Leer bloque
+write-block-text = Escribir bloque (texto)
+write-block-hex = Escribir bloque (hex)
+create-sector-trailer = Crear trailer de sector
+exit-to-main-menu = Salir al menú principal
+enter-choice = Introduzca una opción:
+block-read-successful = Bloque leído correctamente.
+enter-text-to-write-max = Introduzca el texto a escribir (máx. 16 caracteres):
+block-write-successful = Bloque escrito correctamente.
+enter-hex-data-32-hex = Introduzca los datos hex (32 caracteres hex sin espacios):
+err-invalid-hex-data-must-be-2 = Datos hex no válidos. Deben ser exactamente 32 caracteres hex (16 bytes).
+creating-sector-trailer-for-sector = Creando el trailer del sector { $sector } (bloque { $block })
+enter-new-key-12-hex-2 = Introduzca la nueva clave A (12 caracteres hex, por defecto FFFFFFFFFFFF):
+enter-new-key-b-12-2 = Introduzca la nueva clave B (12 caracteres hex, por defecto FFFFFFFFFFFF):
+sector-trailer-created = Trailer de sector creado:
+access-conditions-3 = Condiciones de acceso:
+    { $access_bits }
+write-this-trailer-to-block = ¿Escribir este trailer en el bloque { $block }? (y/n):
+sector-trailer-written-successfully = ¡Trailer de sector escrito correctamente!
+err-writing-sector-trailer = Error al escribir el trailer de sector: { $error }
+err-creating-sector-trailer = Error al crear el trailer de sector: { $error }
+returning-to-main-menu = Volviendo al menú principal...
+err-invalid-choice-please-try-again = Opción no válida. Inténtelo de nuevo.
+err-authentication-check-key = La autenticación falló. Revise su clave.
+err-data-16-bytes = Los datos deben ser exactamente 16 bytes
+operation-cancelled-by-user = Operación cancelada por el usuario
+enter-key-12-hex-chars-3 = Introduzca la clave (12 caracteres hex, por defecto FFFFFFFFFFFF):
+block-skipped-not-in-dump = Bloque { $block }: omitido (no está en el volcado)
+card-selected-uid-size = Tarjeta seleccionada. UID: { $uid }  Tamaño: { $sak } ({ $layout })
+dump-profile = Perfil: { $profile }
+dumping-card-data = Volcando los datos de la tarjeta ({ $selection })...
+dump-sector = Sector { $sector }
+block-authentication-or-read-failed = Bloque { $block }: (falló la autenticación o la lectura)
+dump-block-label = Bloque { $block }: { $hex }  [{ $label }]
+block-trailer-key = Bloque { $block } (trailer): clave A: { $access }
+no-card-detected = No se detectó ninguna tarjeta
+err-get-card-uid = No se pudo obtener el UID de la tarjeta
+err-select-card = No se pudo seleccionar la tarjeta
+card-selected-uid = Tarjeta seleccionada. UID: { $uid }
+dumping-sector = Volcando el sector { $sector }:
+block-authentication-failed = Bloque { $block }: (falló la autenticación)
+block-read-failed = Bloque { $block }: (falló la lectura)
+card-selected-uid-size-2 = Tarjeta seleccionada. UID: { $uid }  Tamaño: { $size } ({ $layout })
+testing-keys = Probando claves...
+dump-sector-heading = Sector { $sector }:
+found-working-key = Se encontró una clave { $key_type } válida: { $hex }
+waiting-for-card-to-be = Esperando a que se retire la tarjeta...
+card-removed = Tarjeta retirada
+err-authenticate-sector-try-with-custom = No se pudo autenticar el sector { $sector }. Pruebe con claves personalizadas.
+verify-of-blocks-read-back = Verificación: { $count } de { $checked } bloques se releyeron tal como se escribieron. { $result }
+block-couldnt-be-read-back = Bloque { $block }: no se pudo releer
+verify-block = Bloque { $block }:
+verify-written = escrito: { $hex }
+on-card = en la tarjeta: { $actual }
+
+# Preguntas de los menús
+dump-this-may-take-while = ¿Volcar { $selection }? Puede tardar un rato. ¿Continuar? (y/n):
+this-overwrites-data-in-of = Esto sobrescribe los datos en { $selection } de la tarjeta. ¿Continuar? (y/n):
+test-y-n = ¿Probar { $selection }? (y/n):
+number-or-enter-for-none = { $prompt } (número, o Enter para ninguno):
+write-to-block-y-n = ¿Escribir { $hex } en el bloque { $block }? (y/n):
+block-is-new-amount-enter = { $name } (bloque { $block }) vale { $now }, nuevo importe{ $unit } (Enter para conservarlo):
+write-blocks-keep-card-on = ¿Escribir los bloques { $blocks }? Mantenga la tarjeta en el lector. (y/n):
+block-to-start-with = { $name } (bloque { $block }) inicial{ $unit }:
+expected-change-of-e-g = Cambio esperado de { $name }{ $unit } (p. ej. -1.80, Enter para cualquiera):
+save-to-enter-for = Guardar en (Enter para { $path }):
+sectors-e-g-1-3 = Sectores (p. ej. 1-3,7, Enter para { $default }):
+profile-prompt = Perfil
+profile-prompt-annotate = Perfil de tarjeta para anotar el volcado
+profile-prompt-system = Perfil de las tarjetas del sistema
+template-cleared = borrado
+block-now = Ahora: { $hex }
+
+# Condiciones de acceso
+access-bits-invalid = Los bits de acceso no coinciden con su copia invertida, el sector está bloqueado
+access-never = Nunca
+access-read-and-write = L: { $read }, E: { $write }
+access-read-write = L/E: { $keys }
+access-trailer =
+    Clave A: { $key_a }
+    Bits de acceso: { $access_bits }
+    Clave B: { $key_b }
+access-block = Bloque { $block }: { $conditions }
+access-blocks = Bloques { $first }-{ $last }: { $conditions }
+access-trailer-block = Bloque { $block } (trailer):
+access-invalid-block-index = Índice de bloque no válido
+access-invalid-block-type = Tipo de bloque no válido
+
+# Cifrado de datos, selecciones, escritura de trailers y el diario
+encryption-off = desactivado
+encryption-on-fixed = activado, una clave para todas las tarjetas
+encryption-on-per-uid = activado, clave derivada del UID de cada tarjeta
+encryption-not-set-up = sin configurar
+turn-encryption-on = Activar el cifrado
+turn-encryption-off = Desactivar el cifrado
+selection-all = cada uno
+selection-all-sectors = todos los sectores
+selection-sector = el sector { $sector }
+selection-sectors = los sectores { $sectors }
+selection-blocks = los bloques { $blocks }
+selection-sectors-blocks = { $sectors }, bloques { $blocks }
+journal-done = Actualización escrita y releída.
+journal-rolled-back = No se pudo hacer la actualización, la tarjeta conserva los datos anteriores en todos los bloques.
+trailer-written = Trailer del sector { $sector } escrito y releído.
+trailer-written-attempt = Trailer del sector { $sector } escrito y releído en el intento { $attempt }.
+trailer-refused =
+    La tarjeta rechazó el trailer del sector { $sector } { $attempts } veces, no tiene el nuevo.
+    Compruebe que los bits de acceso permiten a la clave usada escribir el trailer.
+trailer-card-gone =
+    La tarjeta salió del lector mientras se escribía el trailer del sector { $sector }. Puede tener
+    el trailer anterior, el nuevo o uno a medias: vuelva a ponerla en el lector, sin
+    moverla, para que se compruebe el sector.
+trailer-torn =
+    Ni la clave anterior ni las nuevas abren el sector { $sector }: la escritura del trailer quedó a medias.
+    El trailer tal como era está en la copia de seguridad (Restaurar desde copia de seguridad). Una tarjeta
+    mágica lo recupera por su puerta trasera, cualquier otra tarjeta deja el sector bloqueado.
+journal-pending =
+    La tarjeta salió del lector durante la actualización, su diario tiene lo necesario para
+    terminarla. Vuelva a poner la tarjeta en el lector, antes de usarla en otro sitio, y
+    léala con el perfil.
+
+# Valores y registros de los perfiles
+decode-value = { $name }: { $amount } (bloque { $block })
+decode-value-not-read = { $name }: no leído (bloque { $block })
+decode-value-invalid = { $name }: el bloque { $block } no es un bloque de valor válido
+decode-record = { $name } (bloque { $block })
+decode-record-numbered = { $name } { $number } (bloque { $block })
+decode-record-not-read = { $name }: no leído
+decode-record-empty = { $name }: vacío
+decode-outside-block = (fuera del bloque)
+
+# Prueba de crédito
+problem-values = Valores: { $guidance }
+problem-value-not-written = Bloque { $block }: no se pudo escribir el valor
+problem-trailer-not-read = Sector { $sector }: no se pudo leer el trailer, sus claves se dejaron como estaban
+problem-trailer-not-written = Sector { $sector }: no se pudo escribir el trailer
+problem-uid-not-written = Bloque 0: no se pudo escribir el UID, puede que la tarjeta no sea mágica
+problem-block0-not-read = No se pudo leer el bloque 0, el UID se dejó como estaba
+check-block = Bloque { $block }
+result-pass = OK
+result-fail = FALLO
+expected-change-any = cualquiera
+credit-value = Valor
+value-unreadable = ilegible
+amount-unit = en { $unit }
+
+# Tablas de rendimiento y de tiempos
+bench-spi-clock = Reloj SPI
+bench-uid-polls = lecturas UID/s
+bench-auth-reads = auth+lectura/s
+bench-full-dump = volcado completo
+bench-errors = { $count } err
+bench-no-reader = sin lector
+bench-blocks = { $count }/64 blq
+bench-failed = falló
+timing-command = comando
+timing-ok-tried = ok/intentos
+timing-min = mín us
+timing-median = mediana
+timing-max = máx us
+
+# Opciones de línea de comandos
+opt-speeds = --speeds necesita una lista, p. ej. 500k,1M,4M
+opt-seconds = --seconds necesita un número positivo
+opt-trials = --trials necesita un número positivo
+opt-block = --block necesita un número de bloque de 0 a 255
+opt-key = --key necesita 6 bytes en hex, p. ej. FFFFFFFFFFFF
+opt-spi-speed = --spi-speed necesita una velocidad, p. ej. 1M o 500k
+opt-output = --output necesita un nombre de archivo
+opt-invalid-speed = Velocidad SPI no válida '{ $speed }'
+opt-unknown-benchmark = Opción de benchmark desconocida '{ $option }'
+opt-unknown-timing = Opción de timing desconocida '{ $option }'
+opt-verify-usage = Uso: verify <archivo> [--key <clave pública>]
+selection-flag-needs-list = { $flag } necesita una lista, p. ej. 1-3,7
+selection-not-a-range = '{ $part }' no es un número ni un rango
+selection-beyond-last = { $number } está más allá del último, { $last }
@@ -0,0 +1,530 @@
+# Italiano
+
+# Avvio e strumenti da riga di comando
+is-intact-but-was-signed = { $file } è integro ma è stato firmato da un'altra chiave: { $public_key }
+valid-signature-by-key = { $file }: firma valida della chiave { $public_key }
+compare-key-with-one-sender = Confronti la chiave con quella ricevuta dal mittente.
+err-cant-be-trusted = { $file } non è affidabile: { $error }
+nfc-rfid-block-editor = Editor di blocchi NFC/RFID
+initializing = Inizializzazione...
+spi-interface-initialized-successfully = Interfaccia SPI inizializzata correttamente.
+err-initialize-spi = Impossibile inizializzare SPI: { $error }
+make-sure-spi-is-enabled = Verifichi che SPI sia abilitato sul Raspberry Pi.
+run-sudo-raspi-config-go = Esegua 'sudo raspi-config', vada in 'Interface Options' > 'SPI' e lo abiliti.
+mfrc522-rfid-reader-initialized-successfully = Lettore RFID MFRC522 inizializzato correttamente.
+err-initialize-mfrc522-rfid-reader = Impossibile inizializzare il lettore RFID MFRC522: { $error }
+check-connections-and-ensure-reader = Controlli i collegamenti e che il lettore sia collegato bene.
+err-in-main-menu = Errore nel menu principale: { $error }
+exiting-program-goodbye = Uscita dal programma. Arrivederci!
+created-signing-key-keep-copy = Creata la chiave di firma { $path }, ne conservi una copia
+fastest-error-free-speed = Velocità più alta senza errori: { $speed }
+no-speed-ran-without-errors = Nessuna velocità ha funzionato senza errori. Controlli che la tessera sia sul lettore e usi la chiave predefinita.
+benchmarking-spi-speeds-per-test = Misura di { $count } velocità SPI, { $duration }s per prova.
+leave-mifare-classic-1k-card = Lasci una tessera Mifare Classic 1K con la chiave predefinita sul lettore per tutta la prova.
+press-enter-to-start = Premere Invio per iniziare...
+bench-testing = Prova di { $speed }...
+timing-trials-done = { $trial }/{ $trials } prove
+timing-transactions-on-block-with = Misura di { $trials } transazioni sul blocco { $block } con la chiave { $key } a { $speed } Hz.
+leave-card-on-reader-for = Lasci la tessera sul lettore per tutta la prova.
+timings-written-to = { $count } tempi scritti in { $output }
+
+# Menu
+prepare-your-card-you-have = Prepari la tessera. Ha { $seconds } secondi per metterla sul lettore...
+countdown-seconds = { $number }/{ $seconds } secondi
+reading-card-now = Lettura della tessera...
+err-load = Impossibile caricare { $path }: { $error }
+press-enter-to-continue = Premere Invio per continuare...
+err-lower-spi-speed = Impossibile abbassare la velocità SPI: { $error }
+title-block-editor = EDITOR DI BLOCCHI NFC/RFID
+reader-spi = Lettore: { $status } (SPI { $speed })
+reader-check-wiring = Il lettore non risponde, controlla i collegamenti e l'alimentazione.
+spi-speed-lowered = Troppi errori di comunicazione, velocità SPI ridotta a { $speed }.
+read-only-mode-cards-cant = MODALITÀ SOLA LETTURA ({ $reason }): le tessere non possono essere modificate
+main-menu = MENU PRINCIPALE:
+read-card-uid = Leggi UID della tessera
+read-block = Leggi blocco
+write-block = Scrivi blocco
+dump-card = Dump della tessera
+format-card = Formatta tessera
+change-keys = Cambia chiavi
+modify-access-bits = Modifica bit di accesso
+block-editor-interactive = Editor di blocchi (interattivo)
+test-keys = Prova chiavi
+data-encryption = Cifratura dei dati ({ $status })
+restore-from-backup = Ripristina da backup
+reader-diagnostics = Diagnostica del lettore
+key-store-keys = Archivio chiavi ({ $count } chiavi)
+card-profiles = Profili delle tessere
+credit-test-magic-card = Test del credito (tessera magic)
+menu-exit = Esci
+enter-your-choice = Inserisca la sua scelta:
+menu-exiting = Uscita...
+err-invalid-choice-press-enter-to = Scelta non valida. Premere Invio per continuare...
+err-error = Errore: { $error }
+title-reader-diagnostics = DIAGNOSTICA DEL LETTORE
+spi-clock = Clock SPI: { $speed }
+problem-transactions-are-logged-to = Le transazioni con problemi sono registrate in { $path }
+enter-r-to-reset-counters = Inserisca 'r' per azzerare i contatori, o prema Invio per tornare indietro:
+counters-reset = Contatori azzerati.
+title-read-card-uid = LEGGI UID DELLA TESSERA
+read-one-card = Leggi una tessera
+scan-continuously-each-card-reported = Scansione continua (ogni tessera viene segnalata una volta finché resta sul lettore)
+card-uid = UID della tessera: { $uid }
+uid-as-decimal = UID in decimale: { $decimal }
+no-card-detected-or-error = Nessuna tessera rilevata o errore nella lettura.
+title-modify-access-bits = MODIFICA BIT DI ACCESSO
+enter-sector-number-0-15 = Inserisca il numero del settore (0-15, 0-39 su una 4K):
+err-invalid-sector-number-must-be = Numero di settore non valido. Deve essere tra 0 e 39.
+select-access-configuration = Scelga la configurazione di accesso:
+transport-all-open-default = Trasporto (tutto aperto, predefinita)
+secure-read-with-key-write = Sicura (lettura con la chiave A, scrittura con la chiave B)
+read-only-no-writes-allowed = Sola lettura (nessuna scrittura consentita)
+custom-advanced-not-implemented = Personalizzata (avanzata, non implementata)
+enter-choice-1-3 = Inserisca una scelta (1-3):
+custom-access-bits-not-implemented = I bit di accesso personalizzati non sono ancora implementati. Operazione annullata.
+err-invalid-choice-operation-cancelled = Scelta non valida. Operazione annullata.
+you-need-current-key-to = Serve la chiave A attuale per modificare i bit di accesso.
+enter-key-12-hex-chars = Inserisca la chiave A (12 caratteri hex, predefinita FFFFFFFFFFFF):
+err-invalid-key-format-using-default = Formato della chiave non valido. Si usa la chiave predefinita.
+new-access-conditions = Nuove condizioni di accesso:
+confirm-access-bits-change-y = Confermare la modifica dei bit di accesso? (y/n):
+operation-cancelled = Operazione annullata.
+access-bits-modified-successfully = Bit di accesso modificati correttamente!
+err-modify-access-bits-check-authentication = Impossibile modificare i bit di accesso. Controlli la chiave di autenticazione e i permessi di accesso.
+scanning-press-enter-to-stop = Scansione in corso. Premere Invio per fermare.
+card-decimal = Tessera { $scans }: { $uid } (decimale { $decimal })
+scan-card-removed = rimossa: { $uid }
+cards-scanned = { $scans } tessere lette.
+signal-summary = Segnale: { $summary }
+title-read-block = LEGGI BLOCCO
+enter-block-number-0-63 = Inserisca il numero del blocco (0-63, 0-255 su una 4K):
+err-invalid-block-number-must-be = Numero di blocco non valido. Deve essere tra 0 e 255.
+warning-block-is-sector-trailer = Attenzione: il blocco { $block } è un trailer di settore con i bit di accesso e le chiavi.
+select-authentication-method = Scelga il metodo di autenticazione:
+key-default-ffffffffffff = Chiave A (predefinita: FFFFFFFFFFFF)
+key-b-default-ffffffffffff = Chiave B (predefinita: FFFFFFFFFFFF)
+enter-choice-1-2 = Inserisca una scelta (1-2):
+err-invalid-choice-using-key-by = Scelta non valida. Si usa la chiave A.
+use-custom-key-y-n = Usare una chiave personalizzata? (y/n):
+enter-key-12-hex-chars-2 = Inserisca la chiave (12 caratteri hex):
+err-could-not-detect-card = Errore: impossibile rilevare la tessera.
+err-could-not-read-card-uid = Errore: impossibile leggere l'UID della tessera.
+card-detected-uid = Tessera rilevata. UID: { $uid }
+err-could-not-select-card = Errore: impossibile selezionare la tessera.
+authentication-failed-try-different-key = Autenticazione non riuscita. Provi un'altra chiave.
+block-data = Dati del blocco { $block }:
+block-hex = HEX: { $hex }
+key-a-hex = Chiave A: { $hex }
+access-bits-hex = Bit di accesso: { $hex }
+key-b-hex = Chiave B: { $hex }
+access-conditions = Condizioni di accesso:
+block-decrypted = Decifrato: { $hex }
+block-ascii = ASCII: { $ascii }
+err-reading-block-data = Errore nella lettura dei dati del blocco.
+title-write-block = SCRIVI BLOCCO
+warning-block-0-contains-manufacturer = ATTENZIONE: il blocco 0 contiene i dati del produttore e l'UID della tessera.
+writing-to-this-block-may = Scrivere in questo blocco può rendere la tessera inutilizzabile per sempre!
+are-you-absolutely-sure-type = È ASSOLUTAMENTE sicuro? (scriva YES in maiuscolo):
+warning-block-is-sector-trailer-2 = ATTENZIONE: il blocco { $block } è un trailer di settore con i bit di accesso e le chiavi.
+incorrect-values-may-lock-sector = Valori errati possono bloccare il settore o l'intera tessera per sempre!
+are-you-sure-you-want = Vuole davvero continuare? (y/n):
+choose-data-format = Scelga il formato dei dati:
+text-will-be-padded-to = Testo (completato fino a 16 byte)
+hexadecimal-must-be-exactly-32 = Esadecimale (esattamente 32 caratteri hex)
+enter-text-max-16-chars = Inserisca il testo (max 16 caratteri):
+enter-hex-data-32-chars = Inserisca i dati hex (32 caratteri):
+err-invalid-hex-data-must-be = Dati hex non validi. Devono essere esattamente 16 byte (32 caratteri hex).
+err-invalid-choice = Scelta non valida.
+data-to-be-written = Dati da scrivere:
+confirm-write-y-n = Confermare la scrittura? (y/n):
+block-encrypted = Cifrato: { $hex }
+block-written-successfully = Blocco scritto correttamente!
+err-writing-block-check-access-rights = Errore nella scrittura del blocco. Controlli i permessi di accesso.
+write-sector-trailer = SCRIVI TRAILER DI SETTORE
+block-sector = Blocco: { $block } (settore { $sector })
+select-access-bits-configuration = Scelga la configurazione dei bit di accesso:
+custom-advanced = Personalizzata (avanzata)
+enter-choice-1-4 = Inserisca una scelta (1-4):
+custom-access-bits-not-implemented-2 = I bit di accesso personalizzati non sono ancora implementati. Si usa la configurazione di trasporto.
+err-invalid-choice-using-transport-configuration = Scelta non valida. Si usa la configurazione di trasporto.
+enter-key-b-12-hex = Inserisca la chiave B (12 caratteri hex, predefinita FFFFFFFFFFFF):
+authentication-needed-for-current-sector = Serve l'autenticazione con il trailer di settore attuale.
+use-key-default-ffffffffffff = Usa la chiave A (predefinita: FFFFFFFFFFFF)
+use-key-b-default-ffffffffffff = Usa la chiave B (predefinita: FFFFFFFFFFFF)
+trailer-data-to-be-written = Dati del trailer da scrivere:
+access-conditions-2 = Condizioni di accesso:
+put-card-back-and-press = Rimetta la tessera e prema Invio per controllarla (q per fermare):
+title-dump-card = DUMP DELLA TESSERA
+dump-card-optionally-save-to = Dump della tessera (con salvataggio facoltativo in un file)
+write-dump-file-to-card = Scrivi un file di dump su una tessera
+files-flipper-zero-nfc-or = File: .nfc di Flipper Zero, o .bin/.mfd/.dump grezzi (mfoc, mfcuk, nfc-mfclassic)
+of-blocks-read = { $known } di { $total } blocchi letti.
+decrypt-data-blocks-with-data = Decifrare i blocchi dati con la chiave dei dati? (y/n):
+save-to-file-nfc-for = Salva in un file (.nfc per Flipper Zero, .mfd/.bin per gli strumenti libnfc, Invio per saltare):
+dump-saved-to = Dump salvato in { $path }
+err-save-dump = Impossibile salvare il dump: { $error }
+err-dumping-card = Errore nel dump della tessera.
+sign-dump-y-n = Firmare il dump? (y/n):
+signature-saved-to = Firma salvata in { $path }
+public-key-to-give-recipient = Chiave pubblica da dare al destinatario: { $public_key }
+err-sign-dump = Impossibile firmare il dump: { $error }
+dump-file-to-write = File di dump da scrivere:
+signature-valid-signed-by-key = Firma valida, firmata dalla chiave { $public_key }
+signature-of-doesnt-hold = La firma di { $path } non è valida: { $error }
+write-it-anyway-y-n = Scriverlo comunque? (y/n):
+dump-of-card-of-blocks = Dump della tessera { $uid }: { $known } di { $total } blocchi noti
+dump-target-card-needs-to = Dump di una { $layout }, anche la tessera di destinazione deve esserlo
+keys-for-target-card-hex = Chiavi per la tessera di destinazione: una chiave hex, un file di chiavi (elenco di chiavi, esportazione Proxmark o MCT, dump .mfd),
+or-enter-for-default-keys = o Invio per le chiavi predefinite:
+keys-loaded-from = { $count } chiavi caricate da { $source }
+err-load-keys = Impossibile caricare le chiavi: { $error }
+err-invalid-key-it-must-be = Chiave non valida, deve essere di 6 byte.
+block-0-holds-uid-and = Il blocco 0 contiene l'UID e si può scrivere solo sulle tessere magic.
+write-block-0-y-n = Scrivere il blocco 0? (y/n):
+sector-trailers-set-keys-and = I trailer di settore impostano le chiavi e i bit di accesso. Un trailer errato può bloccare un settore per sempre.
+write-sector-trailers-y-n = Scrivere i trailer di settore? (y/n):
+encrypt-data-blocks-with-data = Cifrare i blocchi dati con la chiave dei dati? (y/n):
+no-card-found-to-encrypt = Nessuna tessera trovata per cui cifrare i dati.
+blocks-written = { $count } blocchi scritti.
+err-failed-blocks = Blocchi non riusciti: { $blocks }
+block-editor = EDITOR DI BLOCCHI
+title-test-keys = PROVA CHIAVI
+this-will-test-multiple-keys = Verranno provate diverse chiavi sui settori della tessera.
+this-process-may-take-some = L'operazione può richiedere un po' di tempo.
+key-testing-results = Risultati della prova delle chiavi:
+no-working-keys-found-for = Nessuna chiave valida trovata per alcun settore.
+sector-key = Settore { $sector }: chiave { $hex }
+save-keys-to-key-file = Salva le chiavi in un file di chiavi per mfoc -f (Invio per saltare):
+keys-saved-to = { $count } chiavi salvate in { $path }
+err-save-keys = Impossibile salvare le chiavi: { $error }
+err-testing-keys = Errore nella prova delle chiavi: { $error }
+key-store = ARCHIVIO CHIAVI
+imported-keys-are-saved-to = Le chiavi importate sono salvate in { $path }
+import-key-file-key-list = Importa un file di chiavi (elenco di chiavi, .keys di MCT, output chk di Proxmark, key.bin o JSON, CSV, dump)
+export-keys-as-key-list = Esporta le chiavi come elenco di chiavi
+key-origin-default = predefinita
+key-origin-this-run = trovata in questa sessione
+menu-back = Indietro
+key-file-to-import = File di chiavi da importare:
+err-import = Impossibile importare { $file }: { $error }
+keys-read-from-new-already = { $count } chiavi lette da { $file } ({ $format }): { $added } nuove, { $known } già nell'archivio.
+key-store-saved = Archivio chiavi salvato.
+err-save-key-store = Impossibile salvare l'archivio chiavi: { $error }
+save-key-list-to = Salva l'elenco di chiavi in:
+keys-saved-to-2 = { $count } chiavi salvate in { $file }
+title-card-profiles = PROFILI DELLE TESSERE
+profiles-describe-what-common-cards = I profili descrivono cosa contengono le tessere comuni e quali chiavi le aprono.
+add-your-own-as-profile = Aggiunga i suoi come file .profile in { $path }
+skipped-profile = Profilo saltato { $problem }
+profile-card-kind = Tessera { $kind }, { $source }
+profile-layout = Struttura:
+profile-sector-label = Settore { $sector }: { $label }
+profile-block-label = Blocco { $block }: { $label }
+profile-keys = Chiavi:
+profile-key = { $sectors } chiave { $key_type }: { $spec }
+journal-commit-record-in-block = Registro: record di commit nel blocco { $record }, dati nei blocchi { $blocks }
+no-card-found = Nessuna tessera trovata.
+decoded = Decodificato ({ $profile }):
+profile-workflow-block = { $title }: blocco { $block } ({ $label })
+profile-label-data = dati
+err-hex-data-16-bytes = I dati hex devono essere esattamente 16 byte
+block-couldnt-be-read-none = Impossibile leggere il blocco { $block }, nessuna chiave apre il suo settore.
+new-content-text-up-to = Nuovo contenuto: testo (fino a 16 caratteri), o hex:<32 caratteri hex>, Invio per annullare:
+block-written = Blocco { $block } scritto.
+block-was-not-written-no = Il blocco { $block } non è stato scritto: nessuna chiave con permesso di scrittura ha funzionato.
+err-invalid-amount = Importo non valido '{ $input }'.
+cards-journal-holds-unfinished-update = Il registro della tessera contiene un aggiornamento non finito dei blocchi { $blocks }, la modalità sola lettura lo lascia com'è.
+finished-update-of-blocks-card = Completato l'aggiornamento dei blocchi { $blocks } durante il quale la tessera è stata tolta.
+credit-test = TEST DEL CREDITO
+for-testing-your-own-closed = Per provare il proprio sistema a circuito chiuso (ricarica, distributori, mensa):
+magic-card-gets-profiles-balance = una tessera magic riceve il saldo e le chiavi di un profilo, viene usata nel sistema,
+then-is-read-back-to = poi viene riletta per controllare che il sistema abbia cambiato il saldo come previsto.
+has-no-value-blocks-to = { $profile } non ha blocchi valore da provare.
+uid-to-give-card-8 = UID da dare alla tessera (8 cifre hex, Invio per tenere il suo):
+err-invalid-uid-it-must-be = UID non valido, deve essere di 4 byte.
+put-profiles-keys-in-sector = Mettere le chiavi del profilo nei trailer di settore? (y/n):
+this-overwrites-card-use-magic = Questo sovrascrive la tessera. Usi una tessera magic, non una delle tessere del sistema.
+continue-y-n = Continuare? (y/n):
+test-card-ready = Tessera di prova pronta.
+card-was-only-partly-set = La tessera è stata preparata solo in parte:
+test-it-anyway-y-n = Provarla comunque? (y/n):
+use-card-at-system-under = Usi la tessera nel sistema in prova, poi la rimetta sul lettore.
+press-enter-when-it-is = Prema Invio quando è di nuovo sul lettore, o q per fermare:
+no-card-found-put-it = Nessuna tessera trovata, la metta sul lettore e riprovi.
+credit-use-round = Uso { $round }:
+expected-change = { $result } { $check }: { $amount } -> { $amount2 } (variazione attesa: { $expected })
+save-report-as-csv-enter = Salva il rapporto come CSV (Invio per saltare):
+report-saved-to = Rapporto salvato in { $path }
+err-save-report = Impossibile salvare il rapporto: { $error }
+title-format-card = FORMATTA TESSERA
+skipped-template = Modello saltato { $problem }
+format-with = Formatta con:
+template-file = Un file di modello
+new-template = Un nuovo modello
+add-your-own-as-template = Aggiunga i suoi come file .template in { $path }
+enter-your-choice-enter-to = Inserisca la sua scelta (Invio per annullare):
+template-file-2 = File di modello:
+sector-key-access-key-b = Settore Chiave A      Accesso   Chiave B      Dati
+warning-all-data-in-of = ATTENZIONE: tutti i dati in { $selection } di questi settori andranno persi. Il blocco 0 (blocco del produttore) non verrà modificato.
+type-format-to-format-card = Scriva FORMAT per formattare la tessera, SAVE per salvare il modello in un file:
+template-saved-to = Modello salvato in { $path }
+card-formatted-successfully = Tessera formattata correttamente.
+err-formatting-card = Errore nella formattazione della tessera.
+reading-written-blocks-back = Rilettura di { $count } blocchi scritti...
+blocks-in-them-e-g = Blocchi al loro interno (es. 4-6, Invio per tutti i blocchi):
+no-sector-of-card-is = Nessun settore della tessera è selezionato.
+template-name = Nome del modello:
+description-optional = Descrizione (facoltativa):
+sectors-to-format-enter-for = Settori da formattare (Invio per 1-15):
+key-12-hex-digits-enter = Chiave A (12 cifre hex, Invio per FFFFFFFFFFFF):
+key-b-12-hex-digits = Chiave B (12 cifre hex, Invio per FFFFFFFFFFFF):
+access-bytes-8-hex-digits = Byte di accesso (8 cifre hex, Invio per quelli di trasporto FF078069):
+make-sectors-ndef-container-y = Rendere i settori un contenitore NDEF? (y/n):
+title-restore-from-backup = RIPRISTINA DA BACKUP
+block-0-and-sector-trailers = Il blocco 0 e i trailer di settore vengono salvati in un backup prima di essere scritti.
+no-backups-yet = Ancora nessun backup.
+backups-newest-first = Backup, dal più recente:
+backup-to-restore-enter-to = Backup da ripristinare (Invio per tornare indietro):
+backup-of-card = Backup della tessera { $uid }:
+block-hex-line = Blocco { $block }: { $hex }
+block-key-isnt-known-it = Blocco { $block }: una chiave non è nota, non si può ripristinare
+gen1a-magic-card-takes-blocks = Una tessera magic gen1a riceve i blocchi tramite la sua backdoor. Qualsiasi altra tessera
+written-with-key-stores-keys = viene scritta con le chiavi dell'archivio, il blocco 0 solo se la tessera lo consente.
+this-overwrites-block-0-and = Questo sovrascrive il blocco 0 e i trailer di settore della tessera. Continuare? (y/n):
+title-change-keys = CAMBIA CHIAVI
+you-need-current-key-to-2 = Serve la chiave attuale per cambiare le chiavi.
+authenticate-with-key = Autenticazione con la chiave A
+authenticate-with-key-b = Autenticazione con la chiave B
+enter-current-key-12-hex = Inserisca la chiave attuale (12 caratteri hex, predefinita FFFFFFFFFFFF):
+which-keys-do-you-want = Quali chiavi vuole cambiare?
+key-only = Solo la chiave A
+key-b-only = Solo la chiave B
+both-keys = Entrambe le chiavi
+no-keys-selected-for-change = Nessuna chiave scelta da cambiare. Operazione annullata.
+enter-new-key-12-hex = Inserisca la nuova chiave A (12 caratteri hex):
+enter-new-key-b-12 = Inserisca la nuova chiave B (12 caratteri hex):
+changing-keys-for-sector = Modifica delle chiavi del settore { $sector }:
+new-key = Nuova chiave A: { $hex }
+key-unchanged = Chiave A: (invariata)
+new-key-b = Nuova chiave B: { $hex }
+key-b-unchanged = Chiave B: (invariata)
+confirm-key-change-y-n = Confermare la modifica delle chiavi? (y/n):
+keys-changed-successfully = Chiavi cambiate correttamente!
+err-change-keys-check-authentication-key = Impossibile cambiare le chiavi. Controlli la chiave di autenticazione e i permessi di accesso.
+title-data-encryption = CIFRATURA DEI DATI
+data-blocks-are-encrypted-with = I blocchi dati vengono cifrati con AES-128 in scrittura e decifrati in lettura.
+block-0-and-sector-trailers-2 = Il blocco 0 e i trailer di settore non vengono mai cifrati.
+encryption-status = Stato: { $status }
+use-one-key-for-every = Usa una chiave per tutte le tessere
+use-master-key-each-cards = Usa una chiave master, la chiave di ogni tessera è derivata dal suo UID
+data-encryption-is = La cifratura dei dati è { $current }.
+data-written-with-current-key = I dati scritti con la chiave attuale si possono leggere solo con quella chiave.
+replace-current-key-y-n = Sostituire la chiave attuale? (y/n):
+key-32-hex-chars-or = Chiave (32 caratteri hex), o Invio per generarne una:
+err-invalid-key-it-must-be-2 = Chiave non valida, deve essere di 16 byte.
+data-key-hex = Chiave: { $hex }
+saved-to-keep-copy-encrypted = Salvata in { $path }. Ne conservi una copia, i dati cifrati non si possono leggere senza.
+
+# Operazioni sulla tessera
+formatting-sector = Formattazione del settore { $sector }...
+block-reset-to-zeros = Blocco { $block } azzerato
+err-reset-block = Impossibile azzerare il blocco { $block }
+sector-trailer-reset-to-factory = Trailer di settore riportato ai valori di fabbrica
+err-reset-sector-trailer = Impossibile ripristinare il trailer di settore
+err-could-not-authenticate-sector-with = Impossibile autenticare il settore { $sector } con alcuna chiave
+format-complete-successfully-reset-15 = Formattazione completata. Ripristinati { $count }/15 settori.
+block-written-2 = Blocco { $block } scritto: { $hex }
+err-write-block = Impossibile scrivere il blocco { $block }
+format-complete-successfully-formatted-sectors = Formattazione completata. Formattati { $count }/{ $total } settori.
+gen1a-backdoor-open-writing-every = Backdoor gen1a aperta, scrittura di tutti i blocchi noti.
+no-gen1a-backdoor-writing-with = Nessuna backdoor gen1a, scrittura con le chiavi della tessera.
+block-write-failed = Blocco { $block }: scrittura non riuscita
+backed-up-to = Backup salvato in { $path }
+editor-block-data = Dati del blocco { $block }:
+this-is-sector-trailer-block = Questo è un blocco trailer di settore (chiave A, bit di accesso, chiave B)
+block-trailer-access = Blocco { $block } (trailer): { $access }
+err-read-block-data = Impossibile leggere i dati del blocco.
+are-you-absolutely-sure-type-2 = È ASSOLUTAMENTE sicuro? (scriva YES in maiuscolo per confermare):
+warning-block-is-sector-trailer-3 = ATTENZIONE: il blocco { $block } è un trailer di settore con le chiavi e le condizioni di accesso.
+writing-incorrect-data-may-lock = Scrivere dati errati può bloccare la tessera o il settore per sempre!
+block-written-successfully-2 = Blocco { $block } scritto correttamente!
+data-written = Dati scritti: { $hex }
+err-write-to-block = Impossibile scrivere nel blocco { $block }.
+block-editor-menu = MENU DELL'EDITOR DI BLOCCHI
+editor-data-encryption = Cifratura dei dati: { $data_key }
+editor-read-block = Leggi blocco
+write-block-text = Scrivi blocco (testo)
+write-block-hex = Scrivi blocco (hex)
+create-sector-trailer = Crea trailer di settore
+exit-to-main-menu = Torna al menu principale
+enter-choice = Inserisca una scelta:
+block-read-successful = Blocco letto correttamente.
+enter-text-to-write-max = Inserisca il testo da scrivere (max 16 caratteri):
+block-write-successful = Blocco scritto correttamente.
+enter-hex-data-32-hex = Inserisca i dati hex (32 caratteri hex senza spazi):
+err-invalid-hex-data-must-be-2 = Dati hex non validi. Devono essere esattamente 32 caratteri hex (16 byte).
+creating-sector-trailer-for-sector = Creazione del trailer del settore { $sector } (blocco { $block })
+enter-new-key-12-hex-2 = Inserisca la nuova chiave A (12 caratteri hex, predefinita FFFFFFFFFFFF):
+enter-new-key-b-12-2 = Inserisca la nuova chiave B (12 caratteri hex, predefinita FFFFFFFFFFFF):
+sector-trailer-created = Trailer di settore creato:
+access-conditions-3 = Condizioni di accesso:
+    { $access_bits }
+write-this-trailer-to-block = Scrivere questo trailer nel blocco { $block }? (y/n):
+sector-trailer-written-successfully = Trailer di settore scritto correttamente!
+err-writing-sector-trailer = Errore nella scrittura del trailer di settore: { $error }
+err-creating-sector-trailer = Errore nella creazione del trailer di settore: { $error }
+returning-to-main-menu = Ritorno al menu principale...
+err-invalid-choice-please-try-again = Scelta non valida. Riprovi.
+err-authentication-check-key = Autenticazione non riuscita. Controlli la chiave.
+err-data-16-bytes = I dati devono essere esattamente 16 byte
+operation-cancelled-by-user = Operazione annullata dall'utente
+enter-key-12-hex-chars-3 = Inserisca la chiave (12 caratteri hex, predefinita FFFFFFFFFFFF):
+block-skipped-not-in-dump = Blocco { $block }: saltato (non è nel dump)
+card-selected-uid-size = Tessera selezionata. UID: { $uid }  Dimensione: { $sak } ({ $layout })
+dump-profile = Profilo: { $profile }
+dumping-card-data = Dump dei dati della tessera ({ $selection })...
+dump-sector = Settore { $sector }
+block-authentication-or-read-failed = Blocco { $block }: (autenticazione o lettura non riuscita)
+dump-block-label = Blocco { $block }: { $hex }  [{ $label }]
+block-trailer-key = Blocco { $block } (trailer): chiave A: { $access }
+no-card-detected = Nessuna tessera rilevata
+err-get-card-uid = Impossibile ottenere l'UID della tessera
+err-select-card = Impossibile selezionare la tessera
+card-selected-uid = Tessera selezionata. UID: { $uid }
+dumping-sector = Dump del settore { $sector }:
+block-authentication-failed = Blocco { $block }: (autenticazione non riuscita)
+block-read-failed = Blocco { $block }: (lettura non riuscita)
+card-selected-uid-size-2 = Tessera selezionata. UID: { $uid }  Dimensione: { $size } ({ $layout })
+testing-keys = Prova delle chiavi...
+dump-sector-heading = Settore { $sector }:
+found-working-key = Trovata una chiave { $key_type } valida: { $hex }
+waiting-for-card-to-be = In attesa che la tessera venga tolta...
+card-removed = Tessera tolta
+err-authenticate-sector-try-with-custom = Impossibile autenticare il settore { $sector }. Provi con chiavi personalizzate.
+verify-of-blocks-read-back = Verifica: { $count } di { $checked } blocchi riletti come scritti. { $result }
+block-couldnt-be-read-back = Blocco { $block }: impossibile rileggerlo
+verify-block = Blocco { $block }:
+verify-written = scritto: { $hex }
+on-card = sulla tessera: { $actual }
+
+# Domande dei menu
+dump-this-may-take-while = Dump di { $selection }? Può richiedere un po' di tempo. Continuare? (y/n):
+this-overwrites-data-in-of = Questo sovrascrive i dati in { $selection } della tessera. Continuare? (y/n):
+test-y-n = Provare { $selection }? (y/n):
+number-or-enter-for-none = { $prompt } (numero, o Invio per nessuno):
+write-to-block-y-n = Scrivere { $hex } nel blocco { $block }? (y/n):
+block-is-new-amount-enter = { $name } (blocco { $block }) vale { $now }, nuovo importo{ $unit } (Invio per mantenerlo):
+write-blocks-keep-card-on = Scrivere i blocchi { $blocks }? Tenga la tessera sul lettore. (y/n):
+block-to-start-with = { $name } (blocco { $block }) iniziale{ $unit }:
+expected-change-of-e-g = Variazione attesa di { $name }{ $unit } (es. -1.80, Invio per qualsiasi):
+save-to-enter-for = Salva in (Invio per { $path }):
+sectors-e-g-1-3 = Settori (es. 1-3,7, Invio per { $default }):
+profile-prompt = Profilo
+profile-prompt-annotate = Profilo della tessera con cui annotare il dump
+profile-prompt-system = Profilo delle tessere del sistema
+template-cleared = azzerati
+block-now = Ora: { $hex }
+
+# Condizioni di accesso
+access-bits-invalid = I bit di accesso non corrispondono alla loro copia invertita, il settore è bloccato
+access-never = Mai
+access-read-and-write = L: { $read }, S: { $write }
+access-read-write = L/S: { $keys }
+access-trailer =
+    Chiave A: { $key_a }
+    Bit di accesso: { $access_bits }
+    Chiave B: { $key_b }
+access-block = Blocco { $block }: { $conditions }
+access-blocks = Blocchi { $first }-{ $last }: { $conditions }
+access-trailer-block = Blocco { $block } (trailer):
+access-invalid-block-index = Indice di blocco non valido
+access-invalid-block-type = Tipo di blocco non valido
+
+# Cifratura dei dati, selezioni, scrittura dei trailer e il registro
+encryption-off = disattivata
+encryption-on-fixed = attiva, una chiave per tutte le tessere
+encryption-on-per-uid = attiva, chiave derivata dall'UID di ogni tessera
+encryption-not-set-up = non configurata
+turn-encryption-on = Attiva la cifratura
+turn-encryption-off = Disattiva la cifratura
+selection-all = ciascuno
+selection-all-sectors = tutti i settori
+selection-sector = settore { $sector }
+selection-sectors = settori { $sectors }
+selection-blocks = blocchi { $blocks }
+selection-sectors-blocks = { $sectors }, blocchi { $blocks }
+journal-done = Aggiornamento scritto e riletto.
+journal-rolled-back = Impossibile fare l'aggiornamento, la tessera ha ancora i dati precedenti in ogni blocco.
+trailer-written = Trailer del settore { $sector } scritto e riletto.
+trailer-written-attempt = Trailer del settore { $sector } scritto e riletto al tentativo { $attempt }.
+trailer-refused =
+    La tessera ha rifiutato il trailer del settore { $sector } { $attempts } volte, non ha quello nuovo.
+    Controlli che i bit di accesso permettano alla chiave usata di scrivere il trailer.
+trailer-card-gone =
+    La tessera ha lasciato il lettore mentre veniva scritto il trailer del settore { $sector }. Può avere
+    il trailer precedente, quello nuovo o uno scritto a metà: la rimetta sul lettore, senza
+    muoverla, per far controllare il settore.
+trailer-torn =
+    Né la chiave precedente né quelle nuove aprono il settore { $sector }: la scrittura del trailer è rimasta a metà.
+    Il trailer com'era prima è nel backup (Ripristina da backup). Una tessera magic
+    lo riprende tramite la sua backdoor, qualsiasi altra tessera lascia il settore bloccato.
+journal-pending =
+    La tessera ha lasciato il lettore durante l'aggiornamento, il suo registro ha quanto serve per
+    completarlo. Rimetta la tessera sul lettore, prima di usarla altrove, e
+    la legga con il profilo.
+
+# Valori e record dei profili
+decode-value = { $name }: { $amount } (blocco { $block })
+decode-value-not-read = { $name }: non letto (blocco { $block })
+decode-value-invalid = { $name }: il blocco { $block } non è un blocco valore valido
+decode-record = { $name } (blocco { $block })
+decode-record-numbered = { $name } { $number } (blocco { $block })
+decode-record-not-read = { $name }: non letto
+decode-record-empty = { $name }: vuoto
+decode-outside-block = (fuori dal blocco)
+
+# Test del credito
+problem-values = Valori: { $guidance }
+problem-value-not-written = Blocco { $block }: impossibile scrivere il valore
+problem-trailer-not-read = Settore { $sector }: impossibile leggere il trailer, le sue chiavi sono rimaste com'erano
+problem-trailer-not-written = Settore { $sector }: impossibile scrivere il trailer
+problem-uid-not-written = Blocco 0: impossibile scrivere l'UID, la tessera potrebbe non essere una magic
+problem-block0-not-read = Impossibile leggere il blocco 0, l'UID è rimasto com'era
+check-block = Blocco { $block }
+result-pass = OK
+result-fail = KO
+expected-change-any = qualsiasi
+credit-value = Valore
+value-unreadable = illeggibile
+amount-unit = in { $unit }
+
+# Tabelle di benchmark e dei tempi
+bench-spi-clock = Clock SPI
+bench-uid-polls = letture UID/s
+bench-auth-reads = auth+lettura/s
+bench-full-dump = dump completo
+bench-errors = { $count } err
+bench-no-reader = nessun lettore
+bench-blocks = { $count }/64 blc
+bench-failed = fallito
+timing-command = comando
+timing-ok-tried = ok/provati
+timing-min = min us
+timing-median = mediana
+timing-max = max us
+
+# Opzioni da riga di comando
+opt-speeds = --speeds richiede un elenco, es. 500k,1M,4M
+opt-seconds = --seconds richiede un numero positivo
+opt-trials = --trials richiede un numero positivo
+opt-block = --block richiede un numero di blocco da 0 a 255
+opt-key = --key richiede 6 byte in hex, es. FFFFFFFFFFFF
+opt-spi-speed = --spi-speed richiede una velocità, es. 1M o 500k
+opt-output = --output richiede un nome di file
+opt-invalid-speed = Velocità SPI non valida '{ $speed }'
+opt-unknown-benchmark = Opzione di benchmark sconosciuta '{ $option }'
+opt-unknown-timing = Opzione di timing sconosciuta '{ $option }'
+opt-verify-usage = Uso: verify <file> [--key <chiave pubblica>]
+selection-flag-needs-list = { $flag } richiede un elenco, es. 1-3,7
+selection-not-a-range = '{ $part }' non è un numero né un intervallo
+selection-beyond-last = { $number } è oltre l'ultimo, { $last }
@@ -0,0 +1,20 @@
+# Building access credential: the badge number in one application sector.
+# Copy this file to ~/.config/nfc-block-editor/profiles to adapt it to a system.
+name Access badge
+kind access
+description Door access credential. Readers check the facility code and badge
+description number in sector 1, the other sectors are left blank.
+
+sector 0 Manufacturer block
+sector 1 Credential
+block 4 Facility code and badge number
+block 5 Badge holder
+block 6 Valid from and until
+
+key 0 A FFFFFFFFFFFF
+key 1 A uid-xor 414343455353
+key 1 B uid-xor 4241444745FF
+
+read 1 Read the credential
+write 5 Change the badge holder
+write 6 Change the validity dates
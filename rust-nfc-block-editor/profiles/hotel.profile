@@ -0,0 +1,33 @@
+# Hotel room key: a typical layout of the cards a front desk encodes for each stay.
+# Lock systems differ, copy this file to ~/.config/nfc-block-editor/profiles and
+# change the blocks and keys to match yours.
+name Hotel key card
+kind hotel
+description Room keys encoded at check-in. Sector 0 holds the MAD, the stay is in
+description sector 1 and the key's history in sector 2.
+
+sector 0 Manufacturer block and MAD
+sector 1 Stay data
+sector 2 Key history
+block 1 MAD
+block 2 MAD
+block 4 Room number
+block 5 Check-in and check-out dates
+block 6 Card number and guest
+block 8 Last door opened
+block 9 Issue count
+
+key 0 A A0A1A2A3A4A5
+key 1-2 A FFFFFFFFFFFF
+key 1-2 B uid-xor 484F54454C21
+
+# Decoders: the stay dates as BCD (YYMMDDhhmm) and the issue counter
+record 5 Stay
+field 0 5 bcd Check-in
+field 5 5 bcd Check-out
+value 9 Issue count
+
+read 1 Read the stay
+read 2 Read the key history
+write 4 Change the room number
+write 5 Change the stay dates
@@ -0,0 +1,48 @@
+# Closed-loop transit or cafeteria card with the balance in a value block.
+# Copy this file to ~/.config/nfc-block-editor/profiles to adapt it to a system.
+name Transit card
+kind transit
+description Stored value card. The balance is a value block with a backup copy,
+description the last trips are in sector 3.
+
+sector 0 Manufacturer block and MAD
+sector 1 Card holder and issue data
+sector 2 Balance
+sector 3 Trip history
+sector 4 Update journal
+block 4 Card holder
+block 5 Issue date and card type
+block 8 Balance (value block)
+block 9 Balance backup (value block)
+block 10 Last top-up
+block 12 Trip 1
+block 13 Trip 2
+block 14 Trip 3
+block 16 Journal commit record
+
+key 0 A A0A1A2A3A4A5
+key 1-4 A FFFFFFFFFFFF
+key 1-4 B uid-xor 545249503031
+
+# Decoders: the balance in cents, the last top-up and the last three trips
+currency 0.01 EUR
+value 8 Balance
+value 9 Balance backup
+record 10 Last top-up
+field 0 4 unix-time Time
+field 4 2 amount Amount
+field 6 2 uint Machine
+record 12-14 Trip
+field 0 4 unix-time Time
+field 4 2 amount Fare
+field 6 2 uint Station
+field 8 1 uint Line
+
+# Balance changes go through a journal in sector 4, so a card taken away halfway
+# never ends up with the balance and its backup disagreeing
+journal 16-18
+
+read 2 Read the balance
+read 3 Read the trip history
+balance Change the balance
+write 4 Change the card holder
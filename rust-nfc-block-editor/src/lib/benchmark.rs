@@ -0,0 +1,211 @@
+// Reader throughput at different SPI clock speeds, to pick the speed setting
+//
+// For each speed the reader is reopened and re-initialized, then three workloads run
+// against the card on the reader:
+//   - UID polls: request + anticollision, as fast as possible
+//   - auth + read: request, anticollision, select, authenticate block 4 with the default
+//     key, read it
+//   - full dump: read_card, i.e. what menu option 4 does
+// Errors are cycles that didn't complete. A speed the wiring can't carry shows up as
+// errors (or the reader not answering) rather than as a faster time.
+use std::error::Error;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crate::lib::mfrc522::{
+    mfrc522_init, open_spi, reader_status, ReaderStatus,
+    mfrc522_request, mfrc522_anticoll, mfrc522_select_tag,
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
+    PICC_REQIDL, PICC_AUTHENT1A, MI_OK, SpiReader
+};
+use crate::lib::mifare::{read_card, BlockAddr, Key, KeyStore};
+use crate::lib::i18n::{tr, tr_args};
+
+// Speeds tried when none are given, from well below the usual 1 MHz up to the
+// MFRC522's 10 MHz limit
+pub const DEFAULT_SPEEDS: [u32; 6] = [100_000, 500_000, 1_000_000, 2_000_000, 4_000_000, 8_000_000];
+
+// Block read in the auth + read test, the first data block outside the manufacturer sector
+const BENCH_BLOCK: BlockAddr = BlockAddr(4);
+const DEFAULT_KEY: Key = Key([0xFF; 6]);
+// Full dumps timed per speed, the best one is reported
+const DUMP_RUNS: usize = 3;
+
+// Results for one speed
+pub struct SpeedResult {
+    pub speed_hz: u32,
+    // None when the reader didn't answer at this speed
+    pub polls_per_sec: Option<f64>,
+    pub poll_errors: usize,
+    pub reads_per_sec: Option<f64>,
+    pub read_errors: usize,
+    pub dump_time: Option<Duration>,
+    pub dump_blocks: usize,
+}
+
+// Parse "1M", "500k", "250000" or "2.5MHz" into Hz
+pub fn parse_speed(text: &str) -> Option<u32> {
+    let lower = text.trim().to_lowercase();
+    let lower = lower.trim_end_matches("hz");
+    let (number, scale) = if let Some(number) = lower.strip_suffix('m') {
+        (number, 1_000_000.0)
+    } else if let Some(number) = lower.strip_suffix('k') {
+        (number, 1_000.0)
+    } else {
+        (lower, 1.0)
+    };
+    let hz = number.trim().parse::<f64>().ok()? * scale;
+    if hz >= 1.0 && hz <= u32::MAX as f64 {
+        Some(hz as u32)
+    } else {
+        None
+    }
+}
+
+pub fn format_speed(speed_hz: u32) -> String {
+    if speed_hz >= 1_000_000 {
+        format!("{} MHz", speed_hz as f64 / 1_000_000.0)
+    } else if speed_hz >= 1_000 {
+        format!("{} kHz", speed_hz as f64 / 1_000.0)
+    } else {
+        format!("{} Hz", speed_hz)
+    }
+}
+
+// Repeat `cycle` for `duration`, returns (completed cycles per second, failed cycles)
+fn measure<F>(spi: &mut SpiReader, duration: Duration, mut cycle: F) -> Result<(f64, usize), Box<dyn Error>>
+where
+    F: FnMut(&mut SpiReader) -> Result<bool, Box<dyn Error>>,
+{
+    let start = Instant::now();
+    let mut completed = 0usize;
+    let mut failed = 0usize;
+    while start.elapsed() < duration {
+        if cycle(spi)? {
+            completed += 1;
+        } else {
+            failed += 1;
+        }
+    }
+    Ok((completed as f64 / start.elapsed().as_secs_f64(), failed))
+}
+
+fn poll_uid(spi: &mut SpiReader) -> Result<bool, Box<dyn Error>> {
+    let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
+    if status != MI_OK {
+        return Ok(false);
+    }
+    let (status, _) = mfrc522_anticoll(spi)?;
+    Ok(status == MI_OK)
+}
+
+fn auth_and_read(spi: &mut SpiReader) -> Result<bool, Box<dyn Error>> {
+    let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
+    if status != MI_OK {
+        return Ok(false);
+    }
+    let (status, uid) = mfrc522_anticoll(spi)?;
+    if status != MI_OK || mfrc522_select_tag(spi, &uid)? == 0 {
+        return Ok(false);
+    }
+    let ok = mfrc522_auth(spi, PICC_AUTHENT1A, BENCH_BLOCK.get(), DEFAULT_KEY.as_bytes(), &uid)? == MI_OK
+        && mfrc522_read(spi, BENCH_BLOCK.get())?.is_some();
+    mfrc522_stop_crypto1(spi)?;
+    Ok(ok)
+}
+
+// Run all three workloads at one speed
+pub fn benchmark_speed(speed_hz: u32, duration: Duration) -> Result<SpeedResult, Box<dyn Error>> {
+    let mut result = SpeedResult {
+        speed_hz,
+        polls_per_sec: None,
+        poll_errors: 0,
+        reads_per_sec: None,
+        read_errors: 0,
+        dump_time: None,
+        dump_blocks: 0,
+    };
+
+    let mut spi = open_spi(speed_hz)?;
+    if mfrc522_init(&mut spi).is_err() || reader_status(&mut spi) == ReaderStatus::Disconnected {
+        return Ok(result);
+    }
+
+    let (rate, errors) = measure(&mut spi, duration, poll_uid)?;
+    result.polls_per_sec = Some(rate);
+    result.poll_errors = errors;
+
+    let (rate, errors) = measure(&mut spi, duration, auth_and_read)?;
+    result.reads_per_sec = Some(rate);
+    result.read_errors = errors;
+
+    // Shared by the runs, so after the first one every sector opens on the first key
+    let mut key_store = KeyStore::new(vec![DEFAULT_KEY]);
+    for _ in 0..DUMP_RUNS {
+        let start = Instant::now();
+        if let Some(dump) = read_card(&mut spi, &mut key_store)? {
+            let elapsed = start.elapsed();
+            // a faster run that read less isn't a better one
+            if dump.known_blocks() > result.dump_blocks
+                || (dump.known_blocks() == result.dump_blocks && result.dump_time.map_or(true, |best| elapsed < best))
+            {
+                result.dump_blocks = dump.known_blocks();
+                result.dump_time = Some(elapsed);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+pub fn print_results(results: &[SpeedResult]) {
+    let rate = |value: Option<f64>, errors: usize| match value {
+        Some(value) => format!("{:>8.1} ({})", value, tr_args("bench-errors", &[("count", &format!("{:>4}", errors))])),
+        None => format!("{:>19}", tr("bench-no-reader")),
+    };
+
+    println!("\n{:>10} | {:>19} | {:>19} | {:>16}", tr("bench-spi-clock"), tr("bench-uid-polls"), tr("bench-auth-reads"), tr("bench-full-dump"));
+    println!("{:-<10}-+-{:-<19}-+-{:-<19}-+-{:-<16}", "", "", "", "");
+    for result in results {
+        let dump = match result.dump_time {
+            Some(time) => format!("{:>6.2}s {}", time.as_secs_f64(), tr_args("bench-blocks", &[("count", &format!("{:>2}", result.dump_blocks))])),
+            None if result.polls_per_sec.is_some() => tr("bench-failed"),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:>10} | {} | {} | {:>16}",
+            format_speed(result.speed_hz),
+            rate(result.polls_per_sec, result.poll_errors),
+            rate(result.reads_per_sec, result.read_errors),
+            dump
+        );
+    }
+
+    // Fastest speed that read as much of the card as any other, with no failed cycles
+    let most_blocks = results.iter().map(|result| result.dump_blocks).max().unwrap_or(0);
+    let best = results.iter()
+        .filter(|result| result.poll_errors == 0 && result.read_errors == 0 && result.dump_blocks == most_blocks && most_blocks > 0)
+        .min_by(|a, b| a.dump_time.cmp(&b.dump_time));
+    match best {
+        Some(best) => println!("\n{}", tr_args("fastest-error-free-speed", &[("speed", &format_speed(best.speed_hz))])),
+        None => println!("\n{}", tr("no-speed-ran-without-errors")),
+    }
+}
+
+// `benchmark` subcommand
+pub fn run_benchmark(speeds: &[u32], duration: Duration) -> Result<(), Box<dyn Error>> {
+    println!("{}", tr_args("benchmarking-spi-speeds-per-test", &[("count", &speeds.len().to_string()), ("duration", &duration.as_secs_f64().to_string())]));
+    println!("{}", tr("leave-mifare-classic-1k-card"));
+    print!("{}", tr("press-enter-to-start"));
+    io::stdout().flush()?;
+    io::stdin().read_line(&mut String::new())?;
+
+    let mut results = Vec::new();
+    for &speed in speeds {
+        println!("{}", tr_args("bench-testing", &[("speed", &format_speed(speed))]));
+        results.push(benchmark_speed(speed, duration)?);
+    }
+
+    print_results(&results);
+    Ok(())
+}
@@ -0,0 +1,160 @@
+// Testing a closed-loop payment or vending system with a card of known credit
+//
+// Meant for QA teams testing their own systems (EV chargers, vending, canteens): a magic
+// card is given a profile's values (balance, counters), optionally the profile's keys
+// and the UID of a card the system knows. After the card has been used at the system
+// it is read again and each value is compared with the change the use should have made.
+// A value block left torn, or a change other than the expected one, fails the test.
+use std::error::Error;
+use std::fs;
+use mifare_rc522::BlockAddr;
+
+use crate::lib::mfrc522::{PICC_AUTHENT1A, SpiReader};
+
+use crate::lib::i18n::{tr, tr_args};
+use crate::lib::mifare::decode::{value_block, value_from_block};
+use crate::lib::mifare::dump::{read_card, write_block_with_store, CardDump};
+use crate::lib::mifare::journal::{journaled_write, JournalWrite};
+use crate::lib::mifare::keystore::KeyStore;
+use crate::lib::mifare::profile::CardProfile;
+
+// What to put on the test card
+pub struct CardSetup {
+    // Value block and the value it starts with, in units of the profile's currency
+    pub values: Vec<(BlockAddr, i32)>,
+    // UID for block 0, on cards that let block 0 be written
+    pub uid: Option<[u8; 4]>,
+    // Put the profile's keys into the sector trailers
+    pub write_keys: bool,
+}
+
+// One value after a use of the card
+pub struct ValueCheck {
+    pub name: String,
+    pub block: BlockAddr,
+    pub before: i32,
+    // None when the block couldn't be read or isn't a valid value block
+    pub after: Option<i32>,
+    // None when any change is fine
+    pub expected_change: Option<i32>,
+}
+
+impl ValueCheck {
+    pub fn passed(&self) -> bool {
+        match (self.after, self.expected_change) {
+            (Some(after), Some(change)) => after as i64 - self.before as i64 == change as i64,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+}
+
+// An amount typed in the profile's currency ("12.50") in the units stored on the card
+pub fn parse_amount(profile: &CardProfile, text: &str) -> Option<i32> {
+    let amount = text.trim().parse::<f64>().ok()?;
+    let units = match &profile.currency {
+        Some(currency) => amount / currency.scale,
+        None if amount.fract() == 0.0 => amount,
+        None => return None,
+    };
+    let units = units.round();
+    (units >= i32::MIN as f64 && units <= i32::MAX as f64).then_some(units as i32)
+}
+
+// Write the setup to the card on the reader. Returns what couldn't be written, empty
+// when everything was.
+pub fn program_card(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile, setup: &CardSetup)
+    -> Result<Vec<String>, Box<dyn Error>> {
+    let dump = read_card(spi, key_store)?.ok_or("No card on the reader")?;
+    let mut problems = Vec::new();
+
+    // Values set together go through the profile's journal when it has one
+    let values: Vec<(BlockAddr, [u8; 16])> = setup.values.iter().map(|(block, value)| (*block, value_block(*value, block.get()))).collect();
+    if values.len() > 1 && profile.journal.len() > values.len() {
+        let outcome = journaled_write(spi, key_store, &profile.journal, &values)?;
+        if outcome != JournalWrite::Done {
+            problems.push(tr_args("problem-values", &[("guidance", &outcome.guidance())]));
+        }
+    } else {
+        for (block, data) in &values {
+            if !write_block_with_store(spi, key_store, *block, data)? {
+                problems.push(tr_args("problem-value-not-written", &[("block", &block.to_string())]));
+            }
+        }
+    }
+
+    if setup.write_keys {
+        let uid = setup.uid.map_or_else(|| dump.uid.clone(), |uid| uid.to_vec());
+        for sector in dump.layout().sectors() {
+            let keys = profile.keys_for(sector, &uid);
+            if keys.is_empty() {
+                continue;
+            }
+            let trailer_addr = sector.trailer();
+            let mut trailer = match dump.block(trailer_addr) {
+                Some(trailer) => trailer,
+                None => {
+                    problems.push(tr_args("problem-trailer-not-read", &[("sector", &sector.to_string())]));
+                    continue;
+                },
+            };
+            for sector_key in &keys {
+                let key_bytes = if sector_key.auth_type == PICC_AUTHENT1A { 0..6 } else { 10..16 };
+                trailer[key_bytes].copy_from_slice(sector_key.key.as_bytes());
+            }
+            if write_block_with_store(spi, key_store, trailer_addr, &trailer)? {
+                key_store.expect(sector, &keys);
+            } else {
+                problems.push(tr_args("problem-trailer-not-written", &[("sector", &sector.to_string())]));
+            }
+        }
+    }
+
+    // Last, the card answers with the new UID from here on
+    if let Some(uid) = setup.uid {
+        match dump.block(BlockAddr(0)) {
+            Some(mut block0) => {
+                block0[0..4].copy_from_slice(&uid);
+                block0[4] = uid.iter().fold(0, |bcc, byte| bcc ^ byte);
+                if !write_block_with_store(spi, key_store, BlockAddr(0), &block0)? {
+                    problems.push(tr("problem-uid-not-written"));
+                }
+            },
+            None => problems.push(tr("problem-block0-not-read")),
+        }
+    }
+
+    Ok(problems)
+}
+
+// Compare the profile's values in a dump read after a use with the values before it
+pub fn check_values(profile: &CardProfile, before: &[(BlockAddr, i32)], expected: &[(BlockAddr, Option<i32>)], dump: &CardDump)
+    -> Vec<ValueCheck> {
+    before.iter()
+        .map(|(block, value)| ValueCheck {
+            name: profile.values.iter()
+                .find(|(known, _)| known == block)
+                .map_or_else(|| tr_args("check-block", &[("block", &block.to_string())]), |(_, name)| name.clone()),
+            block: *block,
+            before: *value,
+            after: dump.block(*block).and_then(|data| value_from_block(&data)).map(|(value, _)| value),
+            expected_change: expected.iter().find(|(known, _)| known == block).and_then(|(_, change)| *change),
+        })
+        .collect()
+}
+
+// The checks as CSV, one row per value
+pub fn save_report(path: &str, checks: &[ValueCheck]) -> Result<(), Box<dyn Error>> {
+    let mut csv = String::from("value,block,before,after,expected_change,result\n");
+    for check in checks {
+        let optional = |value: Option<i32>| value.map_or_else(String::new, |value| value.to_string());
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            check.name.replace(',', " "), check.block, check.before,
+            optional(check.after), optional(check.expected_change),
+            if check.passed() { "pass" } else { "fail" }
+        ));
+    }
+    fs::write(path, csv)?;
+    Ok(())
+}
@@ -2,15 +2,43 @@
 pub mod access;
 pub mod operations;
 pub mod admin;
+pub mod backup;
+pub mod card;
+pub mod crypt;
+pub mod decode;
 pub mod dump;
+pub mod flipper;
+pub mod journal;
+pub mod keyfile;
+pub mod keystore;
+pub mod presence;
+pub mod profile;
+pub mod selection;
+pub mod template;
+pub mod trailer;
+pub mod verify;
 pub mod block_editor;
 
 
 // Re-export common items for convenience
-pub use access::AccessBits;
+pub use access::{AccessBits, invalid_access_bits};
 pub use operations::{read_card_uid, wait_for_card_removal, read_sector_data, 
                     write_block_data, write_block_raw, DEFAULT_KEYS};
-pub use admin::{modify_sector_access, change_sector_keys, format_card};
-pub use dump::{dump_card, dump_sector};
+pub use backup::{guarded_write, list_backups, load_backup, restore_backup};
+pub use card::{Block, Card, KeyType, Sector, SectorAccess, Trailer};
+pub use admin::{modify_sector_access, change_sector_keys, format_card, format_with_template};
+pub use crypt::{DataKey, DataCipher, KeyMode, encrypt_dump, decrypt_dump};
+pub use decode::{decode_card, value_block, value_from_block};
+pub use dump::{dump_card, dump_sector, print_sector, read_card, read_sectors, CardDump, load_dump_file, save_dump_file, write_block_with_store, write_dump};
+pub use journal::{journaled_write, pending_update, recover_journal, JournalWrite};
+pub use keyfile::{import_key_file, load_key_file, save_key_file, KeyImport};
+pub use keystore::{KeyStore, SectorKey};
+pub use mifare_rc522::{BlockAddr, CardLayout, Key, SectorIndex};
+pub use presence::{PresenceTracker, PresenceEvent, wait_for_removal};
+pub use profile::{load_profiles, CardProfile, Workflow};
+pub use selection::Selection;
+pub use template::{load_templates, FormatTemplate};
+pub use trailer::{resume_trailer, write_trailer, TrailerWrite};
+pub use verify::{read_back, verify_blocks, VerifyReport};
 pub use block_editor::{read_block, write_block, create_sector_trailer, 
                      format_text_block, interactive_edit};
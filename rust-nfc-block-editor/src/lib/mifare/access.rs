@@ -1,105 +1,112 @@
 use std::fmt;
 
-// Access bit configurations
+use mifare_rc522::{BlockAddr, SectorIndex};
+
+use crate::lib::i18n::{tr, tr_args};
+
+// Access bit configurations, index 0-2 for the data blocks (or block groups of a 4K
+// sector) and 3 for the trailer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AccessBits {
-    pub c1: [bool; 4],  // Access conditions for C1 (least significant bit)
+    pub c1: [bool; 4],  // Access conditions for C1 (most significant bit)
     pub c2: [bool; 4],  // Access conditions for C2
-    pub c3: [bool; 4],  // Access conditions for C3 (most significant bit)
+    pub c3: [bool; 4],  // Access conditions for C3 (least significant bit)
+}
+
+// Trailer bytes 6-8 hold each bit twice, once inverted (bit n of a nibble is block n):
+// Byte 6 - high nibble !C2, low nibble !C1
+// Byte 7 - high nibble  C1, low nibble !C3
+// Byte 8 - high nibble  C3, low nibble  C2
+// Byte 9 is the general purpose byte, free for the user
+const GENERAL_PURPOSE_BYTE: u8 = 0x69;
+
+// The trailer's access bits are stored twice, once inverted. A trailer where the copies
+// don't match locks the sector for good.
+pub fn access_bits_valid(access: &[u8; 4]) -> bool {
+    let inverted_c1 = access[0] & 0x0F;
+    let inverted_c2 = access[0] >> 4;
+    let inverted_c3 = access[1] & 0x0F;
+    let c1 = access[1] >> 4;
+    let c2 = access[2] & 0x0F;
+    let c3 = access[2] >> 4;
+    c1 == !inverted_c1 & 0x0F && c2 == !inverted_c2 & 0x0F && c3 == !inverted_c3 & 0x0F
+}
+
+// Shown in place of the access conditions of a trailer that fails access_bits_valid
+pub fn invalid_access_bits() -> String {
+    tr("access-bits-invalid")
+}
+
+// Keys that can read or write, or none
+const NEVER: &str = "Never";
+
+fn keys(who: &str) -> String {
+    if who == NEVER { tr("access-never") } else { who.to_string() }
+}
+
+// "R: Key A|B, W: Never"
+fn read_and_write((read, write): (&str, &str)) -> String {
+    tr_args("access-read-and-write", &[("read", &keys(read)), ("write", &keys(write))])
+}
+
+// The data blocks' conditions are shortened to "R/W: Key A|B" when the same keys do both
+fn read_write(read: &str, write: &str) -> String {
+    if read == write && read != NEVER {
+        tr_args("access-read-write", &[("keys", &keys(read))])
+    } else {
+        read_and_write((read, write))
+    }
+}
+
+fn bits(nibble: u8) -> [bool; 4] {
+    [0, 1, 2, 3].map(|block| nibble >> block & 1 != 0)
+}
+
+fn nibble(bits: &[bool; 4]) -> u8 {
+    bits.iter().enumerate().fold(0, |nibble, (block, bit)| nibble | (*bit as u8) << block)
 }
 
 impl AccessBits {
-    // Create access bits from raw bytes
-    pub fn from_bytes(access_bytes: &[u8; 4]) -> Self {
-        let mut c1 = [false; 4];
-        let mut c2 = [false; 4];
-        let mut c3 = [false; 4];
-        
-        // The access bits are in a weird order in the sector trailer
-        // Byte 6 - b7 = C3b, b6 = C3a, b5 = C2b, b4 = C2a, b3 = C1b, b2 = C1a, b1 = C0b, b0 = C0a
-        // Byte 7 - b7 = C1c, b6 = C1f, b5 = C1e, b4 = C1d, b3 = C0c, b2 = C0f, b1 = C0e, b0 = C0d
-        // Byte 8 - b7 = C3c, b6 = C3f, b5 = C3e, b4 = C3d, b3 = C2c, b2 = C2f, b1 = C2e, b0 = C2d
-        
-        // Extract C1 bits
-        c1[0] = (access_bytes[0] & 0b00000100) != 0; // C1a from byte 6 bit 2
-        c1[1] = (access_bytes[0] & 0b00001000) != 0; // C1b from byte 6 bit 3
-        c1[2] = (access_bytes[1] & 0b10000000) != 0; // C1c from byte 7 bit 7
-        c1[3] = (access_bytes[1] & 0b01000000) != 0; // C1f from byte 7 bit 6
-        
-        // Extract C2 bits
-        c2[0] = (access_bytes[0] & 0b00010000) != 0; // C2a from byte 6 bit 4
-        c2[1] = (access_bytes[0] & 0b00100000) != 0; // C2b from byte 6 bit 5
-        c2[2] = (access_bytes[2] & 0b00001000) != 0; // C2c from byte 8 bit 3
-        c2[3] = (access_bytes[2] & 0b00000100) != 0; // C2f from byte 8 bit 2
-        
-        // Extract C3 bits
-        c3[0] = (access_bytes[0] & 0b01000000) != 0; // C3a from byte 6 bit 6
-        c3[1] = (access_bytes[0] & 0b10000000) != 0; // C3b from byte 6 bit 7
-        c3[2] = (access_bytes[2] & 0b10000000) != 0; // C3c from byte 8 bit 7
-        c3[3] = (access_bytes[2] & 0b01000000) != 0; // C3f from byte 8 bit 6
-        
-        Self { c1, c2, c3 }
+    // Access bits from trailer bytes 6-9, None when the inverted copies don't match
+    pub fn from_bytes(access_bytes: &[u8; 4]) -> Option<Self> {
+        if !access_bits_valid(access_bytes) {
+            return None;
+        }
+        Some(Self {
+            c1: bits(access_bytes[1] >> 4),
+            c2: bits(access_bytes[2] & 0x0F),
+            c3: bits(access_bytes[2] >> 4),
+        })
     }
     
-    // Convert access bits to raw bytes for writing to card
+    // Trailer bytes 6-9 for writing to the card, with the inverted copies
     pub fn to_bytes(&self) -> [u8; 4] {
-        let mut access_bytes = [0u8; 4];
-        
-        // Byte 6
-        if self.c1[0] { access_bytes[0] |= 0b00000100; } // C1a -> bit 2
-        if self.c1[1] { access_bytes[0] |= 0b00001000; } // C1b -> bit 3
-        if self.c2[0] { access_bytes[0] |= 0b00010000; } // C2a -> bit 4
-        if self.c2[1] { access_bytes[0] |= 0b00100000; } // C2b -> bit 5
-        if self.c3[0] { access_bytes[0] |= 0b01000000; } // C3a -> bit 6
-        if self.c3[1] { access_bytes[0] |= 0b10000000; } // C3b -> bit 7
-        
-        // Byte 7
-        if self.c1[2] { access_bytes[1] |= 0b10000000; } // C1c -> bit 7
-        if self.c1[3] { access_bytes[1] |= 0b01000000; } // C1f -> bit 6
-        
-        // Byte 8
-        if self.c2[2] { access_bytes[2] |= 0b00001000; } // C2c -> bit 3
-        if self.c2[3] { access_bytes[2] |= 0b00000100; } // C2f -> bit 2
-        if self.c3[2] { access_bytes[2] |= 0b10000000; } // C3c -> bit 7
-        if self.c3[3] { access_bytes[2] |= 0b01000000; } // C3f -> bit 6
-        
-        // Byte 9 (usually 0x69 or some combination for user data byte)
-        access_bytes[3] = 0x69;
-        
-        access_bytes
+        let (c1, c2, c3) = (nibble(&self.c1), nibble(&self.c2), nibble(&self.c3));
+        [
+            (!c2 & 0x0F) << 4 | (!c1 & 0x0F),
+            c1 << 4 | (!c3 & 0x0F),
+            c3 << 4 | c2,
+            GENERAL_PURPOSE_BYTE,
+        ]
     }
     
     // Get a predefined access configuration
     pub fn get_predefined_config(config_type: &str) -> Self {
         match config_type {
-            "transport" => {
-                // Transport configuration - Everything accessible with Key A
-                let c1 = [false, false, false, false];
-                let c2 = [false, false, false, false];
-                let c3 = [false, false, false, false];
-                Self { c1, c2, c3 }
-            },
             "secure" => {
-                // Secure configuration - Data blocks read with Key A, write with Key B
-                // Key A can only authenticate, Key B can read/write/auth
-                let c1 = [false, false, true, false];
-                let c2 = [false, true, false, false];
-                // In the "secure" configuration:
-                let c3 = [true, true, false, true];
-                Self { c1, c2, c3 }
+                // Data blocks read with Key A or B, written with Key B (100). Only Key B
+                // changes the keys and access bits, and it can't be read (trailer 011).
+                Self { c1: [true, true, true, false], c2: [false, false, false, true], c3: [false, false, false, true] }
             },
             "readonly" => {
-                // Read-only configuration - No writes allowed
-                let c1 = [false, true, true, false];
-                let c2 = [false, false, false, true];
-                let c3 = [true, true, false, true];
-                Self { c1, c2, c3 }
+                // Data blocks read with Key A or B and never written (010). Key B can
+                // still change the keys and access bits (trailer 011).
+                Self { c1: [false; 4], c2: [true; 4], c3: [false, false, false, true] }
             },
             _ => {
-                // Default to transport configuration
-                let c1 = [false, false, false, false];
-                let c2 = [false, false, false, false];
-                let c3 = [false, false, false, false];
-                Self { c1, c2, c3 }
+                // Transport configuration (FF 07 80): everything with Key A (data 000,
+                // trailer 001), also the default
+                Self { c1: [false; 4], c2: [false; 4], c3: [false, false, false, true] }
             }
         }
     }
@@ -108,11 +115,11 @@ impl AccessBits {
     pub fn interpret_access(&self, block_type: &str, block_index: usize) -> String {
         let index = match block_type {
             "data" => {
-                if block_index >= 3 { return "Invalid block index".to_string(); }
+                if block_index >= 3 { return tr("access-invalid-block-index"); }
                 block_index
             },
             "trailer" => 3,
-            _ => return "Invalid block type".to_string()
+            _ => return tr("access-invalid-block-type")
         };
         
         let c1 = self.c1[index];
@@ -121,51 +128,119 @@ impl AccessBits {
         
         match block_type {
             "data" => {
-                match (c1, c2, c3) {
-                    (false, false, false) => "R/W: Key A|B".to_string(),
-                    (false, false, true) => "R: Key A|B, W: Never".to_string(),
-                    (true, false, false) => "R: Key A|B, W: Key B".to_string(),
-                    (true, false, true) => "R: Key B, W: Key B".to_string(),
-                    (false, true, false) => "R: Key A|B, W: Never".to_string(),
-                    (false, true, true) => "R: Key B, W: Never".to_string(),
-                    (true, true, false) => "R: Key A|B, W: Key B".to_string(),
-                    (true, true, true) => "R: Never, W: Never".to_string(),
-                }
+                let (read, write) = match (c1, c2, c3) {
+                    (false, false, false) => ("Key A|B", "Key A|B"),
+                    (false, false, true) => ("Key A|B", NEVER),
+                    (true, false, false) => ("Key A|B", "Key B"),
+                    (true, false, true) => ("Key B", NEVER),
+                    (false, true, false) => ("Key A|B", NEVER),
+                    (false, true, true) => ("Key B", "Key B"),
+                    (true, true, false) => ("Key A|B", "Key B"),
+                    (true, true, true) => (NEVER, NEVER),
+                };
+                read_write(read, write)
             },
             "trailer" => {
-                let key_a_access = match (c1, c2) {
-                    (false, false) => "R: Never, W: Key A",
-                    (false, true) => "R: Never, W: Never",
-                    (true, false) => "R: Never, W: Key B",
-                    (true, true) => "R: Never, W: Never",
-                };
-                
-                let access_bits_access = match (c1, c3) {
-                    (false, false) => "R: Key A|B, W: Key A",
-                    (true, false) => "R: Key A|B, W: Never",
-                    (false, true) => "R: Key A|B, W: Key B",
-                    (true, true) => "R: Key A|B, W: Never",
-                };
-                
-                let key_b_access = match (c2, c3) {
-                    (false, false) => "R: Key A|B, W: Key A",
-                    (true, false) => "R: Key A|B, W: Key B",
-                    (false, true) => "R: Never, W: Key A",
-                    (true, true) => "R: Never, W: Never",
+                // (read, write) of Key A, the access bits and Key B
+                let (key_a_access, access_bits_access, key_b_access) = match (c1, c2, c3) {
+                    (false, false, false) => ((NEVER, "Key A"), ("Key A", NEVER), ("Key A", "Key A")),
+                    (false, true, false) => ((NEVER, NEVER), ("Key A", NEVER), ("Key A", NEVER)),
+                    (true, false, false) => ((NEVER, "Key B"), ("Key A|B", NEVER), (NEVER, "Key B")),
+                    (true, true, false) => ((NEVER, NEVER), ("Key A|B", NEVER), (NEVER, NEVER)),
+                    (false, false, true) => ((NEVER, "Key A"), ("Key A", "Key A"), ("Key A", "Key A")),
+                    (false, true, true) => ((NEVER, "Key B"), ("Key A|B", "Key B"), (NEVER, "Key B")),
+                    (true, false, true) => ((NEVER, NEVER), ("Key A|B", "Key B"), (NEVER, NEVER)),
+                    (true, true, true) => ((NEVER, NEVER), ("Key A|B", NEVER), (NEVER, NEVER)),
                 };
                 
-                format!("Key A: {}\nAccess Bits: {}\nKey B: {}", key_a_access, access_bits_access, key_b_access)
+                tr_args("access-trailer", &[
+                    ("key_a", &read_and_write(key_a_access)),
+                    ("access_bits", &read_and_write(access_bits_access)),
+                    ("key_b", &read_and_write(key_b_access)),
+                ])
             },
-            _ => "Invalid block type".to_string()
+            _ => tr("access-invalid-block-type")
         }
     }
+
+    // Access conditions of the sector's data blocks, "Block 4: ..." per block, or
+    // "Blocks 128-132: ..." per group of 5 blocks in the 16 block sectors of a 4K
+    pub fn data_access_lines(&self, sector: SectorIndex) -> Vec<String> {
+        let mut groups: Vec<(BlockAddr, BlockAddr)> = Vec::new();
+        for block in sector.data_blocks() {
+            match groups.get_mut(block.access_group()) {
+                Some((_, last)) => *last = block,
+                None => groups.push((block, block)),
+            }
+        }
+        groups.iter()
+            .enumerate()
+            .map(|(group, (first, last))| {
+                let conditions = self.interpret_access("data", group);
+                if first == last {
+                    tr_args("access-block", &[("block", &first.to_string()), ("conditions", &conditions)])
+                } else {
+                    tr_args("access-blocks", &[("first", &first.to_string()), ("last", &last.to_string()), ("conditions", &conditions)])
+                }
+            })
+            .collect()
+    }
+}
+
+// Whether the card lets Key B be read under a trailer with these bytes (6-8 hold the
+// access bits). It can with the trailer conditions (C1 C2 C3) 000, 010 and 001.
+pub fn key_b_readable(trailer: &[u8]) -> bool {
+    let (c1, c2, c3) = (trailer[7] & 0x80 != 0, trailer[8] & 0x08 != 0, trailer[8] & 0x80 != 0);
+    !(c1 || c2 && c3)
 }
 
 impl fmt::Display for AccessBits {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Block 0: {}\n", self.interpret_access("data", 0))?;
-        write!(f, "Block 1: {}\n", self.interpret_access("data", 1))?;
-        write!(f, "Block 2: {}\n", self.interpret_access("data", 2))?;
-        write!(f, "Block 3 (Trailer): \n{}", self.interpret_access("trailer", 0))
+        write!(f, "{}\n", tr_args("access-block", &[("block", "0"), ("conditions", &self.interpret_access("data", 0))]))?;
+        write!(f, "{}\n", tr_args("access-block", &[("block", "1"), ("conditions", &self.interpret_access("data", 1))]))?;
+        write!(f, "{}\n", tr_args("access-block", &[("block", "2"), ("conditions", &self.interpret_access("data", 2))]))?;
+        write!(f, "{}\n{}", tr_args("access-trailer-block", &[("block", "3")]), self.interpret_access("trailer", 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use super::*;
+
+    fn conditions() -> impl Strategy<Value = AccessBits> {
+        any::<[[bool; 4]; 3]>().prop_map(|[c1, c2, c3]| AccessBits { c1, c2, c3 })
+    }
+
+    proptest! {
+        #[test]
+        fn every_combination_round_trips(access in conditions(), general_purpose in any::<u8>()) {
+            let mut bytes = access.to_bytes();
+            prop_assert!(access_bits_valid(&bytes));
+            bytes[3] = general_purpose;
+            prop_assert_eq!(AccessBits::from_bytes(&bytes), Some(access));
+        }
+
+        #[test]
+        fn any_flipped_bit_is_rejected(access in conditions(), byte in 0..3usize, bit in 0..8u8) {
+            let mut bytes = access.to_bytes();
+            bytes[byte] ^= 1 << bit;
+            prop_assert_eq!(AccessBits::from_bytes(&bytes), None);
+        }
+
+        #[test]
+        fn only_matching_copies_decode(bytes in any::<[u8; 4]>()) {
+            match AccessBits::from_bytes(&bytes) {
+                Some(access) => prop_assert_eq!(&access.to_bytes()[..3], &bytes[..3]),
+                None => prop_assert!(!access_bits_valid(&bytes)),
+            }
+        }
+    }
+
+    #[test]
+    fn predefined_configurations_match_the_datasheet() {
+        assert_eq!(AccessBits::get_predefined_config("transport").to_bytes(), [0xFF, 0x07, 0x80, 0x69]);
+        assert_eq!(AccessBits::get_predefined_config("secure").to_bytes(), [0x78, 0x77, 0x88, 0x69]);
+        assert!(key_b_readable(&[[0; 6].as_slice(), &AccessBits::get_predefined_config("transport").to_bytes()].concat()));
     }
 }
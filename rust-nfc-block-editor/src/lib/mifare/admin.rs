@@ -1,22 +1,25 @@
 use std::error::Error;
-use rppal::spi::Spi;
+use mifare_rc522::{CardLayout, Key, SectorIndex};
 
 use crate::lib::mfrc522::{
     mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, 
-    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read, mfrc522_write,
-    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
+    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK, SpiReader
 };
 
 use crate::lib::utils::{bytes_to_hex, bytes_to_ascii, uid_to_string};
 use crate::lib::mifare::access::AccessBits;
+use crate::lib::mifare::backup::guarded_write;
+use crate::lib::mifare::dump::{authenticate_sector, select_card};
+use crate::lib::mifare::keystore::{KeyStore, SectorKey};
 use crate::lib::mifare::operations::DEFAULT_KEYS;
+use crate::lib::mifare::selection::Selection;
+use crate::lib::mifare::template::FormatTemplate;
+use crate::lib::mifare::trailer::write_trailer;
+use crate::lib::i18n::{tr, tr_args};
 
 // Modify access conditions for a sector
-pub fn modify_sector_access(spi: &mut Spi, sector: u8, access_bits: &AccessBits) -> Result<bool, Box<dyn Error>> {
-    if sector >= 16 {
-        return Err("Invalid sector number".into());
-    }
-    
+pub fn modify_sector_access(spi: &mut SpiReader, sector: SectorIndex, access_bits: &AccessBits) -> Result<bool, Box<dyn Error>> {
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
@@ -40,8 +43,7 @@ pub fn modify_sector_access(spi: &mut Spi, sector: u8, access_bits: &AccessBits)
     let mut auth_key = &DEFAULT_KEYS[0];
     
     for key in &DEFAULT_KEYS {
-        let trailer_block = sector * 4 + 3;
-        let status = mfrc522_auth(spi, PICC_AUTHENT1A, trailer_block, key, &uid)?;
+        let status = mfrc522_auth(spi, PICC_AUTHENT1A, sector.trailer().get(), key.as_bytes(), &uid)?;
         if status == MI_OK {
             authenticated = true;
             auth_key = key;
@@ -55,8 +57,8 @@ pub fn modify_sector_access(spi: &mut Spi, sector: u8, access_bits: &AccessBits)
     }
     
     // Read the current trailer to preserve the keys
-    let trailer_block = sector * 4 + 3;
-    let trailer_data_opt = mfrc522_read(spi, trailer_block)?;
+    let trailer_block = sector.trailer();
+    let trailer_data_opt = mfrc522_read(spi, trailer_block.get())?;
     
     if trailer_data_opt.is_none() {
         mfrc522_stop_crypto1(spi)?;
@@ -68,8 +70,8 @@ pub fn modify_sector_access(spi: &mut Spi, sector: u8, access_bits: &AccessBits)
     // Create new trailer data with updated access bits
     let mut new_trailer = [0u8; 16];
     
-    // Copy Key A (first 6 bytes)
-    new_trailer[0..6].copy_from_slice(&trailer_data[0..6]);
+    // Key A (first 6 bytes) reads as zeros, it is the key that opened the sector
+    new_trailer[0..6].copy_from_slice(auth_key.as_bytes());
     
     // Set the new access bits (bytes 6-9)
     let access_bytes = access_bits.to_bytes();
@@ -78,28 +80,18 @@ pub fn modify_sector_access(spi: &mut Spi, sector: u8, access_bits: &AccessBits)
     // Copy Key B (last 6 bytes)
     new_trailer[10..16].copy_from_slice(&trailer_data[10..16]);
     
-    // Write the updated trailer
-    if mfrc522_write(spi, trailer_block, &new_trailer)? != MI_OK {
-        mfrc522_stop_crypto1(spi)?;
-        return Ok(false);
-    }
+    // Write the updated trailer, read back and retried when it didn't go through
+    let outcome = write_trailer(spi, &uid, trailer_block, &new_trailer, PICC_AUTHENT1A, auth_key)?;
+    println!("{}", outcome.guidance(sector));
     
     mfrc522_stop_crypto1(spi)?;
-    return Ok(true);
+    return Ok(outcome.is_written());
 }
 
 // Change keys for a sector
-pub fn change_sector_keys(spi: &mut Spi, sector: u8, current_key: &[u8], 
-                     change_key_a: bool, new_key_a: &[u8],
-                     change_key_b: bool, new_key_b: &[u8]) -> Result<bool, Box<dyn Error>> {
-    if sector >= 16 {
-        return Err("Invalid sector number".into());
-    }
-    
-    if current_key.len() != 6 || (change_key_a && new_key_a.len() != 6) || (change_key_b && new_key_b.len() != 6) {
-        return Err("Invalid key length (must be 6 bytes)".into());
-    }
-    
+pub fn change_sector_keys(spi: &mut SpiReader, sector: SectorIndex, current_key: &Key, 
+                     change_key_a: bool, new_key_a: &Key,
+                     change_key_b: bool, new_key_b: &Key) -> Result<bool, Box<dyn Error>> {
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
@@ -119,8 +111,8 @@ pub fn change_sector_keys(spi: &mut Spi, sector: u8, current_key: &[u8],
     }
     
     // Authenticate with current key
-    let trailer_block = sector * 4 + 3;
-    let status = mfrc522_auth(spi, PICC_AUTHENT1A, trailer_block, current_key, &uid)?;
+    let trailer_block = sector.trailer();
+    let status = mfrc522_auth(spi, PICC_AUTHENT1A, trailer_block.get(), current_key.as_bytes(), &uid)?;
     
     if status != MI_OK {
         mfrc522_stop_crypto1(spi)?;
@@ -128,7 +120,7 @@ pub fn change_sector_keys(spi: &mut Spi, sector: u8, current_key: &[u8],
     }
     
     // Read current trailer
-    let trailer_data_opt = mfrc522_read(spi, trailer_block)?;
+    let trailer_data_opt = mfrc522_read(spi, trailer_block.get())?;
     
     if trailer_data_opt.is_none() {
         mfrc522_stop_crypto1(spi)?;
@@ -140,30 +132,29 @@ pub fn change_sector_keys(spi: &mut Spi, sector: u8, current_key: &[u8],
     // Create new trailer data
     let mut new_trailer = [0u8; 16];
     
-    // Copy current trailer
+    // Copy current trailer, Key A reads as zeros so it is the current key
     new_trailer.copy_from_slice(&trailer_data);
+    new_trailer[0..6].copy_from_slice(current_key.as_bytes());
     
     // Update keys as needed
     if change_key_a {
-        new_trailer[0..6].copy_from_slice(new_key_a);
+        new_trailer[0..6].copy_from_slice(new_key_a.as_bytes());
     }
     
     if change_key_b {
-        new_trailer[10..16].copy_from_slice(new_key_b);
+        new_trailer[10..16].copy_from_slice(new_key_b.as_bytes());
     }
     
-    // Write the updated trailer
-    if mfrc522_write(spi, trailer_block, &new_trailer)? != MI_OK {
-        mfrc522_stop_crypto1(spi)?;
-        return Ok(false);
-    }
+    // Write the updated trailer, read back and retried when it didn't go through
+    let outcome = write_trailer(spi, &uid, trailer_block, &new_trailer, PICC_AUTHENT1A, current_key)?;
+    println!("{}", outcome.guidance(sector));
     
     mfrc522_stop_crypto1(spi)?;
-    return Ok(true);
+    return Ok(outcome.is_written());
 }
 
 // Format a card to factory defaults (all sectors to transport configuration)
-pub fn format_card(spi: &mut Spi) -> Result<bool, Box<dyn Error>> {
+pub fn format_card(spi: &mut SpiReader) -> Result<bool, Box<dyn Error>> {
     // Default trailer data (all 0xFF for Key A, default transport access bits, all 0xFF for Key B)
     let default_trailer = [
         0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, // Key A
@@ -195,35 +186,34 @@ pub fn format_card(spi: &mut Spi) -> Result<bool, Box<dyn Error>> {
     let mut success_count = 0;
     
     // Format each sector
-    for sector in 1..16 {  // Skip sector 0 to avoid damaging manufacturer data
-        println!("Formatting sector {}...", sector);
+    for sector in CardLayout::Classic1K.sectors().skip(1) {  // Skip sector 0 to avoid damaging manufacturer data
+        println!("{}", tr_args("formatting-sector", &[("sector", &sector.to_string())]));
         
         // Try to authenticate with different keys
         let mut authenticated = false;
         
         for &auth_type in &[PICC_AUTHENT1A, PICC_AUTHENT1B] {
-            for &key in &DEFAULT_KEYS {
-                let trailer_block = sector * 4 + 3;
-                let status = mfrc522_auth(spi, auth_type, trailer_block, &key, &uid)?;
+            for key in &DEFAULT_KEYS {
+                let trailer_block = sector.trailer();
+                let status = mfrc522_auth(spi, auth_type, trailer_block.get(), key.as_bytes(), &uid)?;
                 if status == MI_OK {
                     authenticated = true;
                     
                     // Write default data to all data blocks
-                    for block_offset in 0..3 {
-                        let block_addr = sector * 4 + block_offset;
-                        if mfrc522_write(spi, block_addr, &default_data)? == MI_OK {
-                            println!("  Block {} reset to zeros", block_addr);
+                    for block_addr in sector.data_blocks() {
+                        if guarded_write(spi, block_addr, &default_data)? == MI_OK {
+                            println!("  {}", tr_args("block-reset-to-zeros", &[("block", &block_addr.to_string())]));
                         } else {
-                            println!("  Failed to reset block {}", block_addr);
+                            println!("  {}", tr_args("err-reset-block", &[("block", &block_addr.to_string())]));
                         }
                     }
                     
                     // Write default trailer to trailer block
-                    if mfrc522_write(spi, trailer_block, &default_trailer)? == MI_OK {
-                        println!("  Sector trailer reset to factory defaults");
+                    if guarded_write(spi, trailer_block, &default_trailer)? == MI_OK {
+                        println!("  {}", tr("sector-trailer-reset-to-factory"));
                         success_count += 1;
                     } else {
-                        println!("  Failed to reset sector trailer");
+                        println!("  {}", tr("err-reset-sector-trailer"));
                     }
                     
                     // Stop after successful formatting of this sector
@@ -237,13 +227,71 @@ pub fn format_card(spi: &mut Spi) -> Result<bool, Box<dyn Error>> {
         }
         
         if !authenticated {
-            println!("  Could not authenticate sector {} with any key", sector);
+            println!("  {}", tr_args("err-could-not-authenticate-sector-with", &[("sector", &sector.to_string())]));
         }
         
         // Always stop crypto before trying next sector
         mfrc522_stop_crypto1(spi)?;
     }
     
-    println!("Format complete. Successfully reset {}/15 sectors.", success_count);
+    println!("{}", tr_args("format-complete-successfully-reset-15", &[("count", &success_count.to_string())]));
     return Ok(success_count > 0);
 }
+
+// Format the template's sectors that are selected with its keys, access bits and data.
+// Each sector is opened with the keys from the key store, and the keys it is given are
+// remembered for it. Returns the sectors formatted.
+pub fn format_with_template(spi: &mut SpiReader, key_store: &mut KeyStore, template: &FormatTemplate, selection: &Selection)
+    -> Result<Vec<SectorIndex>, Box<dyn Error>> {
+    let (uid, _) = match select_card(spi, PICC_REQIDL)? {
+        Some(selected) => selected,
+        None => return Ok(Vec::new()),
+    };
+    
+    let sectors: Vec<SectorIndex> = template.sectors.iter().copied().filter(|sector| selection.has_sector(*sector)).collect();
+    let mut formatted = Vec::new();
+    
+    for &sector in &sectors {
+        println!("{}", tr_args("formatting-sector", &[("sector", &sector.to_string())]));
+        
+        let sector_key = match authenticate_sector(spi, sector, &uid, key_store)? {
+            Some(sector_key) => sector_key,
+            None => {
+                println!("  {}", tr_args("err-could-not-authenticate-sector-with", &[("sector", &sector.to_string())]));
+                continue;
+            }
+        };
+        key_store.remember(sector, sector_key);
+        
+        // Data blocks first, the trailer last: after it the old keys no longer work
+        let mut written = true;
+        for (block_addr, data) in template.blocks(sector) {
+            if block_addr.is_trailer() {
+                let outcome = write_trailer(spi, &uid, block_addr, &data, sector_key.auth_type, &sector_key.key)?;
+                println!("  {}", outcome.guidance(sector).replace('\n', "\n  "));
+                written = outcome.is_written();
+            } else if guarded_write(spi, block_addr, &data)? == MI_OK {
+                println!("  {}", tr_args("block-written-2", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))]));
+            } else {
+                println!("  {}", tr_args("err-write-block", &[("block", &block_addr.to_string())]));
+                written = false;
+                break;
+            }
+        }
+        
+        if written {
+            let trailer = template.trailer(sector);
+            let key_a = Key(trailer[0..6].try_into()?);
+            let key_b = Key(trailer[10..16].try_into()?);
+            key_store.remember(sector, SectorKey { auth_type: PICC_AUTHENT1A, key: key_a });
+            key_store.add_keys(&[key_b]);
+            formatted.push(sector);
+        }
+    }
+    
+    // Only stop crypto once at the end
+    mfrc522_stop_crypto1(spi)?;
+    
+    println!("{}", tr_args("format-complete-successfully-formatted-sectors", &[("count", &formatted.len().to_string()), ("total", &sectors.len().to_string())]));
+    Ok(formatted)
+}
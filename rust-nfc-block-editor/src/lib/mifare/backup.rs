@@ -0,0 +1,222 @@
+// Backups of block 0 and the sector trailers, taken before they are written
+//
+// A wrong block 0 or sector trailer can brick a card for good, so before one is
+// written the block as it is on the card goes into a snapshot of that card under
+// ~/.config/nfc-block-editor/backups. One snapshot (a Flipper .nfc file) is kept per
+// card per run, and a block only goes in the first time, so the snapshot holds the
+// card as it was before this run changed it. The card never sends its keys back: a
+// trailer is completed with the key that authenticated the write, and the other key
+// is left unknown unless the access bits let it be read.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use mifare_rc522::{BlockAddr, CardLayout};
+
+use crate::lib::mfrc522::{
+    mfrc522_read, mfrc522_write, mfrc522_open_backdoor,
+    last_authentication, PICC_REQALL, PICC_AUTHENT1A, MI_OK, SpiReader
+};
+use crate::lib::utils::{bytes_to_hex, uid_to_string};
+use crate::lib::mifare::access::key_b_readable;
+use crate::lib::mifare::dump::{select_card, CardDump, DumpBlock, load_dump_file, save_dump_file};
+use crate::lib::mifare::keystore::KeyStore;
+use crate::lib::mifare::operations::write_block_raw;
+use crate::lib::i18n::{tr, tr_args};
+
+// Blocks in the snapshot of a Classic 1K, it grows when a 4K is changed
+const SNAPSHOT_BLOCKS: usize = 64;
+
+// The card being changed in this run: its UID, its snapshot file and what is in it
+static SNAPSHOT: Mutex<Option<(Vec<u8>, PathBuf, CardDump)>> = Mutex::new(None);
+
+// ~/.config/nfc-block-editor/backups (or $XDG_CONFIG_HOME)
+pub fn backup_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("backups")
+}
+
+// Whether writing the block can brick the card
+pub fn needs_backup(block_addr: BlockAddr) -> bool {
+    block_addr.is_manufacturer() || block_addr.is_trailer()
+}
+
+// Write a block under the authentication in force, like mfrc522_write. Block 0 and
+// sector trailers are backed up first, and not written when the backup fails.
+pub fn guarded_write(spi: &mut SpiReader, block_addr: BlockAddr, data: &[u8]) -> Result<u8, Box<dyn Error>> {
+    if needs_backup(block_addr) {
+        back_up_block(spi, block_addr)
+            .map_err(|e| format!("Block {} not written, it couldn't be backed up first: {}", block_addr, e))?;
+    }
+    mfrc522_write(spi, block_addr.get(), data)
+}
+
+// Backups saved so far, newest first
+pub fn list_backups() -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let dir = backup_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|ext| ext == "nfc") {
+            let modified = entry.metadata()?.modified()?;
+            backups.push((modified, path));
+        }
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.0));
+    Ok(backups.into_iter().map(|(_, path)| path).collect())
+}
+
+// Load a backup for restoring
+pub fn load_backup(path: &Path) -> Result<CardDump, Box<dyn Error>> {
+    load_dump_file(&path.to_string_lossy())
+}
+
+// Write a backup to the card on the reader. A gen1a magic card takes every known block
+// through its backdoor; any other card (gen2/CUID magic cards, or the original card)
+// is written block by block with the keys from the key store and the backup's own
+// trailers, so block 0 only changes where the card allows it.
+// Returns the blocks written and the blocks that failed.
+pub fn restore_backup(spi: &mut SpiReader, backup: &CardDump, key_store: &mut KeyStore)
+    -> Result<(Vec<BlockAddr>, Vec<BlockAddr>), Box<dyn Error>> {
+    if select_card(spi, PICC_REQALL)?.is_none() {
+        return Err("No card found".into());
+    }
+
+    let backdoor = mfrc522_open_backdoor(spi)?;
+    if backdoor {
+        // Through the backdoor nothing is authenticated, so nothing is backed up
+        // either: the target is a magic card being made into a copy
+        println!("{}", tr("gen1a-backdoor-open-writing-every"));
+    } else {
+        println!("{}", tr("no-gen1a-backdoor-writing-with"));
+        key_store.add_keys(&backup.trailer_keys());
+    }
+
+    let mut written = Vec::new();
+    let mut failed = Vec::new();
+    for block_addr in backup.layout().blocks() {
+        let data = match backup.block(block_addr) {
+            Some(data) => data,
+            None => continue,
+        };
+
+        let ok = if backdoor {
+            mfrc522_write(spi, block_addr.get(), &data)? == MI_OK
+        } else {
+            let sector = block_addr.sector();
+            let mut working_key = None;
+            for key in key_store.candidate_keys(sector) {
+                if write_block_raw(spi, block_addr, &key, &data)? {
+                    working_key = Some(key);
+                    break;
+                }
+            }
+            if let Some(key) = working_key {
+                key_store.remember_key(sector, key);
+            }
+            working_key.is_some()
+        };
+
+        if ok {
+            println!("  {}", tr_args("block-hex-line", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))]));
+            written.push(block_addr);
+        } else {
+            println!("  {}", tr_args("block-write-failed", &[("block", &block_addr.to_string())]));
+            failed.push(block_addr);
+        }
+    }
+    Ok((written, failed))
+}
+
+// this reads the block, completes a trailer with the key in use and adds it to the
+// card's snapshot
+fn back_up_block(spi: &mut SpiReader, block_addr: BlockAddr) -> Result<(), Box<dyn Error>> {
+    let auth = last_authentication(spi).ok_or("the card isn't authenticated")?;
+    let auth_sector = BlockAddr(auth.block_addr).sector();
+    if auth_sector != block_addr.sector() {
+        return Err(format!("the authentication is for sector {}", auth_sector).into());
+    }
+
+    let mut snapshot = SNAPSHOT.lock().map_err(|_| "Backup state poisoned")?;
+    if snapshot.as_ref().is_none_or(|(uid, _, _)| *uid != auth.uid) {
+        *snapshot = Some((auth.uid.clone(), new_backup_path(&auth.uid)?, CardDump::new(auth.uid.clone(), SNAPSHOT_BLOCKS)));
+    }
+    let (_, path, dump) = snapshot.as_mut().ok_or("Backup state missing")?;
+    // A block past the 1K is on a 4K, the snapshot grows to hold it
+    if block_addr.index() >= dump.blocks.len() {
+        dump.blocks.resize(CardLayout::Classic4K.block_count(), [None; 16]);
+    }
+
+    // Block 0 goes in along with the sector 0 trailer, it is readable under the same
+    // authentication
+    let mut blocks = vec![block_addr];
+    if block_addr.is_trailer() && block_addr.sector().first_block().is_manufacturer() {
+        blocks.insert(0, BlockAddr(0));
+    }
+
+    let mut changed = false;
+    for block in blocks {
+        if dump.blocks[block.index()].iter().any(Option::is_some) {
+            continue;
+        }
+        let data = mfrc522_read(spi, block.get())?.ok_or_else(|| format!("block {} can't be read", block))?;
+        if data.len() != 16 {
+            return Err(format!("block {} read {} bytes", block, data.len()).into());
+        }
+        dump.blocks[block.index()] = if block.is_trailer() {
+            complete_trailer(&data, auth.auth_mode, &auth.key)
+        } else {
+            known(&data)
+        };
+        changed = true;
+    }
+
+    if changed {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        save_dump_file(&path.to_string_lossy(), dump)?;
+        println!("{}", tr_args("backed-up-to", &[("path", &path.display().to_string())]));
+    }
+    Ok(())
+}
+
+// this names a new snapshot after the card and the time
+fn new_backup_path(uid: &[u8]) -> Result<PathBuf, Box<dyn Error>> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let name = uid_to_string(uid).replace([':', ' '], "");
+    Ok(backup_dir().join(format!("{}-{}.nfc", name, now)))
+}
+
+// A trailer reads back with Key A as zeros and Key B as zeros unless the access bits
+// make it readable. The key that authenticated fills its own place.
+fn complete_trailer(data: &[u8], auth_mode: u8, key: &[u8]) -> DumpBlock {
+    let mut block = known(data);
+
+    if auth_mode == PICC_AUTHENT1A {
+        block[0..6].iter_mut().zip(key).for_each(|(byte, k)| *byte = Some(*k));
+        if !key_b_readable(data) {
+            block[10..16].iter_mut().for_each(|byte| *byte = None);
+        }
+    } else {
+        block[0..6].iter_mut().for_each(|byte| *byte = None);
+        block[10..16].iter_mut().zip(key).for_each(|(byte, k)| *byte = Some(*k));
+    }
+    block
+}
+
+fn known(data: &[u8]) -> DumpBlock {
+    let mut block = [None; 16];
+    block.iter_mut().zip(data).for_each(|(byte, value)| *byte = Some(*value));
+    block
+}
+
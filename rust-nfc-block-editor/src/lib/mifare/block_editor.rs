@@ -1,183 +1,199 @@
 use std::error::Error;
-use rppal::spi::Spi;
 use std::io::{self, Write};
+use mifare_rc522::{BlockAddr, Key, SectorIndex};
 
 use crate::lib::mfrc522::{
     mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, 
-    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read, mfrc522_write,
-    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
+    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK, SpiReader
 };
 
 use crate::lib::utils::{bytes_to_hex, bytes_to_ascii, hex_string_to_bytes, uid_to_string};
-use crate::lib::mifare::access::AccessBits;
+use crate::lib::mifare::access::{AccessBits, invalid_access_bits};
+use crate::lib::mifare::backup::guarded_write;
+use crate::lib::mifare::trailer::write_trailer;
+use crate::lib::mifare::crypt::{is_data_block, DataKey};
+use crate::lib::i18n::{tr, tr_args};
 
-/// Read a specific block's data and display it in both hex and ASCII formats
-pub fn read_block(spi: &mut Spi, block_addr: u8, auth_mode: u8, key: &[u8]) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
-    // Validate input parameters
-    if block_addr > 63 {
-        return Err("Invalid block address (must be 0-63)".into());
-    }
-    
-    if key.len() != 6 {
-        return Err("Invalid key length (must be 6 bytes)".into());
-    }
+// The transport key, used when no key is given
+const DEFAULT_KEY: Key = Key([0xFF; 6]);
 
-    // Check if it's a sector trailer
-    let is_trailer = block_addr % 4 == 3;
+/// Read a specific block's data and display it in both hex and ASCII formats,
+/// a data block is decrypted when data encryption is on
+pub fn read_block(spi: &mut SpiReader, block_addr: BlockAddr, auth_mode: u8, key: &Key, data_key: Option<&DataKey>) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    // Check if it's a sector trailer (every block number is on a Classic 4K)
+    let is_trailer = block_addr.is_trailer();
     
     // Connect to the card
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        return Err("No card detected".into());
+        return Err(tr("no-card-detected").into());
     }
     
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        return Err("Failed to get card UID".into());
+        return Err(tr("err-get-card-uid").into());
     }
     
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        return Err("Failed to select card".into());
+        return Err(tr("err-select-card").into());
     }
     
-    println!("Card detected. UID: {}", uid_to_string(&uid));
+    println!("{}", tr_args("card-detected-uid", &[("uid", &uid_to_string(&uid))]));
     
     // Try to authenticate
-    let status = mfrc522_auth(spi, auth_mode, block_addr, key, &uid)?;
+    let status = mfrc522_auth(spi, auth_mode, block_addr.get(), key.as_bytes(), &uid)?;
     if status != MI_OK {
         mfrc522_stop_crypto1(spi)?;
-        return Err("Authentication failed. Check your key.".into());
+        return Err(tr("err-authentication-check-key").into());
     }
     
     // Read the block data
-    let data_opt = mfrc522_read(spi, block_addr)?;
+    let data_opt = mfrc522_read(spi, block_addr.get())?;
     mfrc522_stop_crypto1(spi)?;
     
-    if let Some(data) = data_opt {
-        println!("Block {} data:", block_addr);
-        println!("HEX: {}", bytes_to_hex(&data));
+    if let Some(mut data) = data_opt {
+        println!("{}", tr_args("editor-block-data", &[("block", &block_addr.to_string())]));
+        println!("{}", tr_args("block-hex", &[("hex", &bytes_to_hex(&data))]));
         
         if is_trailer {
             // This is a sector trailer - show detailed information
-            println!("This is a sector trailer block (Key A, Access Bits, Key B)");
-            println!("Key A: {}", bytes_to_hex(&data[0..6]));
-            println!("Access Bits: {}", bytes_to_hex(&data[6..10]));
-            println!("Key B: {}", bytes_to_hex(&data[10..16]));
+            println!("{}", tr("this-is-sector-trailer-block"));
+            println!("{}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(&data[0..6]))]));
+            println!("{}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&data[6..10]))]));
+            println!("{}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(&data[10..16]))]));
             
             // Show interpreted access conditions
             let access_bytes = [data[6], data[7], data[8], data[9]];
-            let access_bits = AccessBits::from_bytes(&access_bytes);
-            println!("\nAccess Conditions:");
-            let sector = block_addr / 4;
-            let first_block = sector * 4;
-            println!("Block {}: {}", first_block, access_bits.interpret_access("data", 0));
-            println!("Block {}: {}", first_block + 1, access_bits.interpret_access("data", 1));
-            println!("Block {}: {}", first_block + 2, access_bits.interpret_access("data", 2));
-            println!("Block {} (Trailer): {}", block_addr, 
-                    access_bits.interpret_access("trailer", 0).replace("\n", "\n  "));
+            match AccessBits::from_bytes(&access_bytes) {
+                Some(access_bits) => {
+                    println!("\n{}", tr("access-conditions"));
+                    for line in access_bits.data_access_lines(block_addr.sector()) {
+                        println!("{}", line);
+                    }
+                    println!("{}", tr_args("block-trailer-access", &[("block", &block_addr.to_string()), ("access", &access_bits.interpret_access("trailer", 0).replace("\n", "\n  "))]));
+                },
+                None => println!("\n{}", invalid_access_bits()),
+            }
         } else {
-            // Regular data block
-            println!("ASCII: {}", bytes_to_ascii(&data));
+            // Regular data block, decrypted when it was written encrypted
+            if let Some(cipher) = data_key.and_then(|data_key| data_key.cipher_for(&uid)) {
+                if is_data_block(block_addr) {
+                    if let Ok(block) = <[u8; 16]>::try_from(&data[..16]) {
+                        data = cipher.decrypt_block(block_addr, &block).to_vec();
+                        println!("{}", tr_args("block-decrypted", &[("hex", &bytes_to_hex(&data))]));
+                    }
+                }
+            }
+            println!("{}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&data))]));
         }
         
         return Ok(Some(data));
     } else {
-        println!("Failed to read block data.");
+        println!("{}", tr("err-read-block-data"));
         return Ok(None);
     }
 }
 
-/// Write data to a specific block
-pub fn write_block(spi: &mut Spi, block_addr: u8, auth_mode: u8, key: &[u8], data: &[u8]) -> Result<bool, Box<dyn Error>> {
-    // Validate input parameters
-    if block_addr > 63 {
-        return Err("Invalid block address (must be 0-63)".into());
-    }
-    
-    if key.len() != 6 {
-        return Err("Invalid key length (must be 6 bytes)".into());
-    }
-    
+/// Write data to a specific block, a data block is encrypted when data encryption is on
+pub fn write_block(spi: &mut SpiReader, block_addr: BlockAddr, auth_mode: u8, key: &Key, data: &[u8], data_key: Option<&DataKey>) -> Result<bool, Box<dyn Error>> {
+    // Validate input parameters (every block number is on a Classic 4K)
     if data.len() != 16 {
-        return Err("Data must be exactly 16 bytes".into());
+        return Err(tr("err-data-16-bytes").into());
     }
     
     // Check for special blocks that need warnings
-    if block_addr == 0 {
-        println!("WARNING: Block 0 contains manufacturer data and card UID.");
-        println!("Writing to this block may brick your card permanently!");
+    if block_addr.is_manufacturer() {
+        println!("{}", tr("warning-block-0-contains-manufacturer"));
+        println!("{}", tr("writing-to-this-block-may"));
         
         let mut input = String::new();
-        print!("Are you ABSOLUTELY sure? (type YES in uppercase to confirm): ");
+        print!("{} ", tr("are-you-absolutely-sure-type-2"));
         io::stdout().flush()?;
         io::stdin().read_line(&mut input)?;
         if input.trim() != "YES" {
-            return Err("Operation cancelled by user".into());
+            return Err(tr("operation-cancelled-by-user").into());
         }
-    } else if block_addr % 4 == 3 {
-        println!("WARNING: Block {} is a sector trailer containing keys and access conditions.", block_addr);
-        println!("Writing incorrect data may lock your card or sector permanently!");
+    } else if block_addr.is_trailer() {
+        println!("{}", tr_args("warning-block-is-sector-trailer-3", &[("block", &block_addr.to_string())]));
+        println!("{}", tr("writing-incorrect-data-may-lock"));
         
         let mut input = String::new();
-        print!("Are you sure you want to continue? (y/n): ");
+        print!("{} ", tr("are-you-sure-you-want"));
         io::stdout().flush()?;
         io::stdin().read_line(&mut input)?;
         if input.trim().to_lowercase() != "y" {
-            return Err("Operation cancelled by user".into());
+            return Err(tr("operation-cancelled-by-user").into());
         }
     }
     
     // Connect to the card
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        return Err("No card detected".into());
+        return Err(tr("no-card-detected").into());
     }
     
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        return Err("Failed to get card UID".into());
+        return Err(tr("err-get-card-uid").into());
     }
     
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        return Err("Failed to select card".into());
+        return Err(tr("err-select-card").into());
     }
     
-    println!("Card detected. UID: {}", uid_to_string(&uid));
+    println!("{}", tr_args("card-detected-uid", &[("uid", &uid_to_string(&uid))]));
     
     // Try to authenticate
-    let status = mfrc522_auth(spi, auth_mode, block_addr, key, &uid)?;
+    let status = mfrc522_auth(spi, auth_mode, block_addr.get(), key.as_bytes(), &uid)?;
     if status != MI_OK {
         mfrc522_stop_crypto1(spi)?;
-        return Err("Authentication failed. Check your key.".into());
+        return Err(tr("err-authentication-check-key").into());
+    }
+    
+    // Encrypt data blocks for this card when data encryption is on
+    let mut stored = data.to_vec();
+    if let Some(cipher) = data_key.and_then(|data_key| data_key.cipher_for(&uid)) {
+        if is_data_block(block_addr) {
+            if let Ok(block) = <[u8; 16]>::try_from(data) {
+                stored = cipher.encrypt_block(block_addr, &block).to_vec();
+                println!("{}", tr_args("block-encrypted", &[("hex", &bytes_to_hex(&stored))]));
+            }
+        }
+    }
+    
+    // A trailer is read back and written again when it didn't go through
+    if block_addr.is_trailer() {
+        let trailer: [u8; 16] = stored.as_slice().try_into()?;
+        let outcome = write_trailer(spi, &uid, block_addr, &trailer, auth_mode, key)?;
+        mfrc522_stop_crypto1(spi)?;
+        println!("{}", outcome.guidance(block_addr.sector()));
+        return Ok(outcome.is_written());
     }
     
     // Write the data
-    let status = mfrc522_write(spi, block_addr, data)?;
+    let status = guarded_write(spi, block_addr, &stored)?;
     mfrc522_stop_crypto1(spi)?;
     
     if status == MI_OK {
-        println!("Block {} written successfully!", block_addr);
-        println!("Data written: {}", bytes_to_hex(data));
-        println!("ASCII: {}", bytes_to_ascii(data));
+        println!("{}", tr_args("block-written-successfully-2", &[("block", &block_addr.to_string())]));
+        println!("{}", tr_args("data-written", &[("hex", &bytes_to_hex(data))]));
+        println!("{}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(data))]));
         return Ok(true);
     } else {
-        println!("Failed to write to block {}.", block_addr);
+        println!("{}", tr_args("err-write-to-block", &[("block", &block_addr.to_string())]));
         return Ok(false);
     }
 }
 
 /// Prepare a sector trailer with custom keys and access bits
-pub fn create_sector_trailer(key_a: &[u8], key_b: &[u8], access_config: &str) -> Result<[u8; 16], Box<dyn Error>> {
-    if key_a.len() != 6 || key_b.len() != 6 {
-        return Err("Keys must be exactly 6 bytes each".into());
-    }
-    
+pub fn create_sector_trailer(key_a: &Key, key_b: &Key, access_config: &str) -> Result<[u8; 16], Box<dyn Error>> {
     let mut trailer = [0u8; 16];
     
     // Set Key A
-    trailer[0..6].copy_from_slice(key_a);
+    trailer[0..6].copy_from_slice(key_a.as_bytes());
     
     // Set access bits based on configuration
     let access_bits = AccessBits::get_predefined_config(access_config);
@@ -185,7 +201,7 @@ pub fn create_sector_trailer(key_a: &[u8], key_b: &[u8], access_config: &str) ->
     trailer[6..10].copy_from_slice(&access_bytes);
     
     // Set Key B
-    trailer[10..16].copy_from_slice(key_b);
+    trailer[10..16].copy_from_slice(key_b.as_bytes());
     
     Ok(trailer)
 }
@@ -203,19 +219,22 @@ pub fn format_text_block(text: &str) -> [u8; 16] {
     block
 }
 
-/// Interactive block editor menu
-pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+/// Interactive block editor menu, data blocks go through data encryption when it is on
+pub fn interactive_edit(spi: &mut SpiReader, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
     loop {
-        println!("\nBLOCK EDITOR MENU");
+        println!("\n{}", tr("block-editor-menu"));
         println!("=================");
-        println!("1. Read block");
-        println!("2. Write block (text)");
-        println!("3. Write block (hex)");
-        println!("4. Create sector trailer");
-        println!("0. Exit to main menu");
+        if let Some(data_key) = data_key {
+            println!("{}", tr_args("editor-data-encryption", &[("data_key", &data_key.describe().to_string())]));
+        }
+        println!("1. {}", tr("editor-read-block"));
+        println!("2. {}", tr("write-block-text"));
+        println!("3. {}", tr("write-block-hex"));
+        println!("4. {}", tr("create-sector-trailer"));
+        println!("0. {}", tr("exit-to-main-menu"));
         
         let mut choice = String::new();
-        print!("Enter choice: ");
+        print!("{} ", tr("enter-choice"));
         io::stdout().flush()?;
         io::stdin().read_line(&mut choice)?;
         
@@ -225,9 +244,9 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                 let block_addr = get_block_number()?;
                 let (auth_mode, key) = get_authentication_info()?;
                 
-                match read_block(spi, block_addr, auth_mode, &key) {
-                    Ok(_) => println!("Block read successful."),
-                    Err(e) => println!("Error: {}", e),
+                match read_block(spi, block_addr, auth_mode, &key, data_key) {
+                    Ok(_) => println!("{}", tr("block-read-successful")),
+                    Err(e) => println!("{}", tr_args("err-error", &[("error", &e.to_string())])),
                 }
             },
             "2" => {
@@ -235,16 +254,17 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                 let block_addr = get_block_number()?;
                 let (auth_mode, key) = get_authentication_info()?;
                 
-                print!("Enter text to write (max 16 chars): ");
+                print!("{} ", tr("enter-text-to-write-max"));
                 io::stdout().flush()?;
                 let mut text = String::new();
                 io::stdin().read_line(&mut text)?;
                 
                 let block_data = format_text_block(text.trim());
                 
-                match write_block(spi, block_addr, auth_mode, &key, &block_data) {
-                    Ok(_) => println!("Block write successful."),
-                    Err(e) => println!("Error: {}", e),
+                match write_block(spi, block_addr, auth_mode, &key, &block_data, data_key) {
+                    Ok(true) => println!("{}", tr("block-write-successful")),
+                    Ok(false) => {},
+                    Err(e) => println!("{}", tr_args("err-error", &[("error", &e.to_string())])),
                 }
             },
             "3" => {
@@ -252,74 +272,75 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                 let block_addr = get_block_number()?;
                 let (auth_mode, key) = get_authentication_info()?;
                 
-                print!("Enter hex data (32 hex chars without spaces): ");
+                print!("{} ", tr("enter-hex-data-32-hex"));
                 io::stdout().flush()?;
                 let mut hex_str = String::new();
                 io::stdin().read_line(&mut hex_str)?;
                 
                 match hex_string_to_bytes(hex_str.trim()) {
                     Some(data) if data.len() == 16 => {
-                        match write_block(spi, block_addr, auth_mode, &key, &data) {
-                            Ok(_) => println!("Block write successful."),
-                            Err(e) => println!("Error: {}", e),
+                        match write_block(spi, block_addr, auth_mode, &key, &data, data_key) {
+                            Ok(true) => println!("{}", tr("block-write-successful")),
+                            Ok(false) => {},
+                            Err(e) => println!("{}", tr_args("err-error", &[("error", &e.to_string())])),
                         }
                     },
-                    _ => println!("Invalid hex data. Must be exactly 32 hex characters (16 bytes)."),
+                    _ => println!("{}", tr("err-invalid-hex-data-must-be-2")),
                 }
             },
             "4" => {
                 // Create and write sector trailer
                 let sector = get_sector_number()?;
-                let block_addr = sector * 4 + 3; // Sector trailer block
+                let block_addr = sector.trailer();
                 
-                println!("\nCreating sector trailer for sector {} (block {})", sector, block_addr);
+                println!("\n{}", tr_args("creating-sector-trailer-for-sector", &[("sector", &sector.to_string()), ("block", &block_addr.to_string())]));
                 
                 // Get current authentication info
                 let (auth_mode, current_key) = get_authentication_info()?;
                 
                 // Get new keys
-                print!("Enter new Key A (12 hex chars, default FFFFFFFFFFFF): ");
+                print!("{} ", tr("enter-new-key-12-hex-2"));
                 io::stdout().flush()?;
                 let mut key_a_str = String::new();
                 io::stdin().read_line(&mut key_a_str)?;
                 
                 let key_a = if key_a_str.trim().is_empty() {
-                    [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+                    DEFAULT_KEY
                 } else {
-                    match hex_string_to_bytes(key_a_str.trim()) {
-                        Some(bytes) if bytes.len() == 6 => bytes,
-                        _ => {
-                            println!("Invalid key format. Using default key.");
-                            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+                    match Key::from_hex(key_a_str.trim()) {
+                        Some(key) => key,
+                        None => {
+                            println!("{}", tr("err-invalid-key-format-using-default"));
+                            DEFAULT_KEY
                         }
                     }
                 };
                 
-                print!("Enter new Key B (12 hex chars, default FFFFFFFFFFFF): ");
+                print!("{} ", tr("enter-new-key-b-12-2"));
                 io::stdout().flush()?;
                 let mut key_b_str = String::new();
                 io::stdin().read_line(&mut key_b_str)?;
                 
                 let key_b = if key_b_str.trim().is_empty() {
-                    [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+                    DEFAULT_KEY
                 } else {
-                    match hex_string_to_bytes(key_b_str.trim()) {
-                        Some(bytes) if bytes.len() == 6 => bytes,
-                        _ => {
-                            println!("Invalid key format. Using default key.");
-                            [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+                    match Key::from_hex(key_b_str.trim()) {
+                        Some(key) => key,
+                        None => {
+                            println!("{}", tr("err-invalid-key-format-using-default"));
+                            DEFAULT_KEY
                         }
                     }
                 };
                 
                 // Choose access configuration
-                println!("\nSelect access configuration:");
-                println!("1. Transport (all open, default)");
-                println!("2. Secure (read with Key A, write with Key B)");
-                println!("3. Read-only (no writes allowed)");
+                println!("\n{}", tr("select-access-configuration"));
+                println!("1. {}", tr("transport-all-open-default"));
+                println!("2. {}", tr("secure-read-with-key-write"));
+                println!("3. {}", tr("read-only-no-writes-allowed"));
                 
                 let mut config_choice = String::new();
-                print!("Enter choice (1-3): ");
+                print!("{} ", tr("enter-choice-1-3"));
                 io::stdout().flush()?;
                 io::stdin().read_line(&mut config_choice)?;
                 
@@ -328,7 +349,7 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                     "2" => "secure",
                     "3" => "readonly",
                     _ => {
-                        println!("Invalid choice. Using transport configuration.");
+                        println!("{}", tr("err-invalid-choice-using-transport-configuration"));
                         "transport"
                     }
                 };
@@ -336,38 +357,40 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                 // Create the trailer
                 match create_sector_trailer(&key_a, &key_b, access_config) {
                     Ok(trailer) => {
-                        println!("\nSector trailer created:");
-                        println!("Key A: {}", bytes_to_hex(&trailer[0..6]));
-                        println!("Access Bits: {}", bytes_to_hex(&trailer[6..10]));
-                        println!("Key B: {}", bytes_to_hex(&trailer[10..16]));
+                        println!("\n{}", tr("sector-trailer-created"));
+                        println!("{}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(&trailer[0..6]))]));
+                        println!("{}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&trailer[6..10]))]));
+                        println!("{}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(&trailer[10..16]))]));
                         
                         let access_bytes = [trailer[6], trailer[7], trailer[8], trailer[9]];
-                        let access_bits = AccessBits::from_bytes(&access_bytes);
-                        println!("\nAccess Conditions:");
-                        println!("{}", access_bits);
+                        match AccessBits::from_bytes(&access_bytes) {
+                            Some(access_bits) => println!("\n{}", tr_args("access-conditions-3", &[("access_bits", &access_bits.to_string())])),
+                            None => println!("\n{}", invalid_access_bits()),
+                        }
                         
                         let mut confirm = String::new();
-                        print!("\nWrite this trailer to block {}? (y/n): ", block_addr);
+                        print!("\n{} ", tr_args("write-this-trailer-to-block", &[("block", &block_addr.to_string())]));
                         io::stdout().flush()?;
                         io::stdin().read_line(&mut confirm)?;
                         
                         if confirm.trim().to_lowercase() == "y" {
-                            match write_block(spi, block_addr, auth_mode, &current_key, &trailer) {
-                                Ok(_) => println!("Sector trailer written successfully!"),
-                                Err(e) => println!("Error writing sector trailer: {}", e),
+                            match write_block(spi, block_addr, auth_mode, &current_key, &trailer, None) {
+                                Ok(true) => println!("{}", tr("sector-trailer-written-successfully")),
+                                Ok(false) => {},
+                                Err(e) => println!("{}", tr_args("err-writing-sector-trailer", &[("error", &e.to_string())])),
                             }
                         } else {
-                            println!("Operation cancelled.");
+                            println!("{}", tr("operation-cancelled"));
                         }
                     },
-                    Err(e) => println!("Error creating sector trailer: {}", e),
+                    Err(e) => println!("{}", tr_args("err-creating-sector-trailer", &[("error", &e.to_string())])),
                 }
             },
             "0" => {
-                println!("Returning to main menu...");
+                println!("{}", tr("returning-to-main-menu"));
                 break;
             },
-            _ => println!("Invalid choice. Please try again."),
+            _ => println!("{}", tr("err-invalid-choice-please-try-again")),
         }
     }
     
@@ -375,39 +398,39 @@ pub fn interactive_edit(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
 }
 
 // Helper function to get a block number from user input
-fn get_block_number() -> Result<u8, Box<dyn Error>> {
+fn get_block_number() -> Result<BlockAddr, Box<dyn Error>> {
     let mut input = String::new();
-    print!("Enter block number (0-63): ");
+    print!("{} ", tr("enter-block-number-0-63"));
     io::stdout().flush()?;
     io::stdin().read_line(&mut input)?;
     
     match input.trim().parse::<u8>() {
-        Ok(num) if num <= 63 => Ok(num),
-        _ => Err("Invalid block number. Must be between 0 and 63.".into()),
+        Ok(num) => Ok(BlockAddr(num)),
+        _ => Err(tr("err-invalid-block-number-must-be").into()),
     }
 }
 
 // Helper function to get a sector number from user input
-fn get_sector_number() -> Result<u8, Box<dyn Error>> {
+fn get_sector_number() -> Result<SectorIndex, Box<dyn Error>> {
     let mut input = String::new();
-    print!("Enter sector number (0-15): ");
+    print!("{} ", tr("enter-sector-number-0-15"));
     io::stdout().flush()?;
     io::stdin().read_line(&mut input)?;
     
-    match input.trim().parse::<u8>() {
-        Ok(num) if num <= 15 => Ok(num),
-        _ => Err("Invalid sector number. Must be between 0 and 15.".into()),
+    match input.trim().parse::<u8>().ok().and_then(SectorIndex::new) {
+        Some(sector) => Ok(sector),
+        None => Err(tr("err-invalid-sector-number-must-be").into()),
     }
 }
 
 // Helper function to get authentication info (mode and key)
-fn get_authentication_info() -> Result<(u8, Vec<u8>), Box<dyn Error>> {
-    println!("\nSelect authentication method:");
-    println!("1. Key A (default: FFFFFFFFFFFF)");
-    println!("2. Key B (default: FFFFFFFFFFFF)");
+fn get_authentication_info() -> Result<(u8, Key), Box<dyn Error>> {
+    println!("\n{}", tr("select-authentication-method"));
+    println!("1. {}", tr("key-default-ffffffffffff"));
+    println!("2. {}", tr("key-b-default-ffffffffffff"));
     
     let mut choice = String::new();
-    print!("Enter choice (1-2): ");
+    print!("{} ", tr("enter-choice-1-2"));
     io::stdout().flush()?;
     io::stdin().read_line(&mut choice)?;
     
@@ -415,24 +438,24 @@ fn get_authentication_info() -> Result<(u8, Vec<u8>), Box<dyn Error>> {
         "1" => PICC_AUTHENT1A,
         "2" => PICC_AUTHENT1B,
         _ => {
-            println!("Invalid choice. Using Key A by default.");
+            println!("{}", tr("err-invalid-choice-using-key-by"));
             PICC_AUTHENT1A
         }
     };
     
-    print!("Enter key (12 hex chars, default FFFFFFFFFFFF): ");
+    print!("{} ", tr("enter-key-12-hex-chars-3"));
     io::stdout().flush()?;
     let mut key_str = String::new();
     io::stdin().read_line(&mut key_str)?;
     
     let key = if key_str.trim().is_empty() {
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        DEFAULT_KEY
     } else {
-        match hex_string_to_bytes(key_str.trim()) {
-            Some(bytes) if bytes.len() == 6 => bytes,
-            _ => {
-                println!("Invalid key format. Using default key.");
-                [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        match Key::from_hex(key_str.trim()) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
+                DEFAULT_KEY
             }
         }
     };
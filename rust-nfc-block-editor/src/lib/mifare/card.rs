@@ -0,0 +1,262 @@
+// The card on the reader, for code that uses the editor's library
+//
+// Reading a sector by hand takes a request, anticollision, select and authentication
+// in the right order, and a failed authentication leaves the card halted until it is
+// selected again. Card does that sequencing:
+//
+//   let mut card = Card::connect(&mut spi)?;
+//   let sector = card.sector(3).with_key(KeyType::KeyA, Key([0xFF; 6])).read_all()?;
+//   for block in &sector.blocks { println!("{}: {}", block.addr, bytes_to_hex(&block.data)); }
+//
+// Without with_key the sector is opened with the card's key store, by default the
+// editor's DEFAULT_KEYS, and the key that works is remembered for the next sector
+// operation. Writes are refused in read-only mode, go through the same backups as the
+// menus, and are read back before they count as done.
+use std::error::Error;
+use mifare_rc522::{BlockAddr, CardLayout, Key, SectorIndex};
+
+use crate::lib::mfrc522::{
+    mfrc522_auth, mfrc522_halt, mfrc522_read, mfrc522_stop_crypto1,
+    PICC_AUTHENT1A, PICC_AUTHENT1B, PICC_REQALL, PICC_REQIDL, MI_OK, SpiReader
+};
+use crate::lib::mifare::access::{key_b_readable, AccessBits};
+use crate::lib::mifare::backup::guarded_write;
+use crate::lib::mifare::dump::{authenticate_sector, read_sectors, select_card, CardDump};
+use crate::lib::mifare::keystore::{KeyStore, SectorKey};
+use crate::lib::mifare::operations::DEFAULT_KEYS;
+use crate::lib::mifare::selection::Selection;
+use crate::lib::mifare::trailer::{write_trailer, TrailerWrite};
+use crate::lib::mifare::verify::read_back;
+
+// Which of the sector's keys a key is
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeyType {
+    KeyA,
+    KeyB,
+}
+
+impl KeyType {
+    // The MFRC522 authentication command for the key
+    pub fn auth_type(self) -> u8 {
+        match self {
+            KeyType::KeyA => PICC_AUTHENT1A,
+            KeyType::KeyB => PICC_AUTHENT1B,
+        }
+    }
+}
+
+// A selected card
+pub struct Card<'r> {
+    spi: &'r mut SpiReader,
+    // The anticollision answer, the UID and its check byte
+    uid: Vec<u8>,
+    sak: u8,
+    key_store: KeyStore,
+}
+
+// One sector of the card, opened with a key given or from the card's key store
+pub struct SectorAccess<'c, 'r> {
+    card: &'c mut Card<'r>,
+    sector: u8,
+    key: Option<SectorKey>,
+}
+
+// A block and what it holds
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Block {
+    pub addr: BlockAddr,
+    pub data: [u8; 16],
+}
+
+// A sector trailer as far as the card shows it. Key A always reads as zeros, Key B
+// only when the access bits let it be read.
+pub struct Trailer {
+    pub addr: BlockAddr,
+    pub access_bytes: [u8; 4],
+    // None when the access bits don't match their inverted copy
+    pub access: Option<AccessBits>,
+    pub key_b: Option<Key>,
+}
+
+// Everything read from a sector
+pub struct Sector {
+    pub index: SectorIndex,
+    // The key that opened the sector
+    pub key: SectorKey,
+    // Data blocks, block 0 included for sector 0
+    pub blocks: Vec<Block>,
+    pub trailer: Trailer,
+}
+
+impl<'r> Card<'r> {
+    // Select the card on the reader, an error when there is none
+    pub fn connect(spi: &'r mut SpiReader) -> Result<Self, Box<dyn Error>> {
+        let (uid, sak) = select_card(spi, PICC_REQIDL)?.ok_or("No card on the reader")?;
+        Ok(Card { spi, uid, sak, key_store: KeyStore::new(DEFAULT_KEYS.to_vec()) })
+    }
+
+    // Open sectors with these keys instead of the default ones
+    pub fn with_key_store(mut self, key_store: KeyStore) -> Self {
+        self.key_store = key_store;
+        self
+    }
+
+    pub fn uid(&self) -> &[u8] {
+        &self.uid
+    }
+
+    pub fn sak(&self) -> u8 {
+        self.sak
+    }
+
+    pub fn layout(&self) -> CardLayout {
+        CardLayout::from_sak_or_1k(self.sak)
+    }
+
+    // The key store with the keys found so far
+    pub fn key_store(&self) -> &KeyStore {
+        &self.key_store
+    }
+
+    pub fn sector(&mut self, sector: u8) -> SectorAccess<'_, 'r> {
+        SectorAccess { card: self, sector, key: None }
+    }
+
+    // Every block the key store's keys open, the others left unknown
+    pub fn read_all(&mut self) -> Result<CardDump, Box<dyn Error>> {
+        mfrc522_stop_crypto1(self.spi)?;
+        let dump = read_sectors(self.spi, &mut self.key_store, &Selection::all())?
+            .ok_or("The card left the reader")?;
+        self.reselect()?;
+        Ok(dump)
+    }
+
+    // Put the card to sleep, it answers the reader again once it is taken away and back
+    pub fn halt(self) -> Result<(), Box<dyn Error>> {
+        mfrc522_stop_crypto1(self.spi)?;
+        mfrc522_halt(self.spi)
+    }
+
+    // Select the card again after a failed authentication halted it
+    fn reselect(&mut self) -> Result<(), Box<dyn Error>> {
+        mfrc522_stop_crypto1(self.spi)?;
+        match select_card(self.spi, PICC_REQALL)? {
+            Some((uid, _)) if uid == self.uid => Ok(()),
+            _ => Err("The card left the reader".into()),
+        }
+    }
+}
+
+impl<'c, 'r> SectorAccess<'c, 'r> {
+    // Open the sector with this key only
+    pub fn with_key(mut self, key_type: KeyType, key: impl Into<Key>) -> Self {
+        self.key = Some(SectorKey { auth_type: key_type.auth_type(), key: key.into() });
+        self
+    }
+
+    // The sector's data blocks and trailer
+    pub fn read_all(mut self) -> Result<Sector, Box<dyn Error>> {
+        let (sector, key) = self.open()?;
+        let mut blocks = Vec::new();
+        for addr in sector.data_blocks() {
+            blocks.push(Block { addr, data: self.read(addr)? });
+        }
+        let trailer_data = self.read(sector.trailer())?;
+        let access_bytes: [u8; 4] = trailer_data[6..10].try_into()?;
+        let trailer = Trailer {
+            addr: sector.trailer(),
+            access_bytes,
+            access: AccessBits::from_bytes(&access_bytes),
+            key_b: key_b_readable(&trailer_data).then(|| Key(trailer_data[10..16].try_into().unwrap())),
+        };
+        Ok(Sector { index: sector, key, blocks, trailer })
+    }
+
+    // One block of the sector
+    pub fn read_block(mut self, block: impl Into<BlockAddr>) -> Result<Block, Box<dyn Error>> {
+        let addr = self.block_in_sector(block.into())?;
+        self.open()?;
+        Ok(Block { addr, data: self.read(addr)? })
+    }
+
+    // Write a data block of the sector and read it back. Trailers go through
+    // write_trailer and block 0 through the Write Block menu.
+    pub fn write_block(mut self, block: impl Into<BlockAddr>, data: &[u8; 16]) -> Result<(), Box<dyn Error>> {
+        refuse_in_read_only()?;
+        let addr = self.block_in_sector(block.into())?;
+        if addr.is_manufacturer() || addr.is_trailer() {
+            return Err(format!("Block {} holds the UID or keys, write_block only writes data blocks", addr).into());
+        }
+        self.open()?;
+        if guarded_write(self.card.spi, addr, data)? != MI_OK {
+            return Err(format!("Block {} was not written, the key may not have write access", addr).into());
+        }
+        let report = read_back(self.card.spi, addr, data)?;
+        if !report.passed() {
+            return Err(format!("Block {} didn't read back as written", addr).into());
+        }
+        Ok(())
+    }
+
+    // Write the sector's trailer, read back and retried like the menus do. The keys of
+    // the new trailer open the sector from here on.
+    pub fn write_trailer(mut self, trailer: &[u8; 16]) -> Result<TrailerWrite, Box<dyn Error>> {
+        refuse_in_read_only()?;
+        let (sector, key) = self.open()?;
+        let outcome = write_trailer(self.card.spi, &self.card.uid, sector.trailer(), trailer, key.auth_type, &key.key)?;
+        if outcome.is_written() {
+            let key_a = Key(trailer[0..6].try_into()?);
+            self.card.key_store.remember(sector, SectorKey { auth_type: PICC_AUTHENT1A, key: key_a });
+            self.card.key_store.add_keys(&[Key(trailer[10..16].try_into()?)]);
+        }
+        Ok(outcome)
+    }
+
+    // Authenticate the sector, with the key given or the first key from the store that works
+    fn open(&mut self) -> Result<(SectorIndex, SectorKey), Box<dyn Error>> {
+        let layout = self.card.layout();
+        let sector = layout.sector(self.sector)
+            .ok_or_else(|| format!("A {} has no sector {}", layout.name(), self.sector))?;
+        let card = &mut *self.card;
+
+        let opened = match self.key {
+            Some(key) => {
+                let status = mfrc522_auth(card.spi, key.auth_type, sector.trailer().get(), key.key.as_bytes(), &card.uid)?;
+                (status == MI_OK).then_some(key)
+            },
+            None => authenticate_sector(card.spi, sector, &card.uid, &card.key_store)?,
+        };
+        match opened {
+            Some(key) => {
+                card.key_store.remember(sector, key);
+                Ok((sector, key))
+            },
+            None => {
+                // Leave the card selected for whatever comes next
+                card.reselect()?;
+                Err(format!("Sector {} didn't open with {}", sector,
+                    if self.key.is_some() { "the key given" } else { "any key in the key store" }).into())
+            },
+        }
+    }
+
+    fn read(&mut self, addr: BlockAddr) -> Result<[u8; 16], Box<dyn Error>> {
+        let data = mfrc522_read(self.card.spi, addr.get())?
+            .ok_or_else(|| format!("Block {} couldn't be read, the access bits may not allow it", addr))?;
+        Ok(data.get(..16).and_then(|data| data.try_into().ok()).ok_or("Short read")?)
+    }
+
+    fn block_in_sector(&self, block: BlockAddr) -> Result<BlockAddr, Box<dyn Error>> {
+        if block.sector().get() != self.sector {
+            return Err(format!("Block {} is in sector {}, not sector {}", block, block.sector(), self.sector).into());
+        }
+        Ok(block)
+    }
+}
+
+fn refuse_in_read_only() -> Result<(), Box<dyn Error>> {
+    match safe_mode::read_only_reason() {
+        Some(reason) => Err(format!("Read-only mode ({}): the card isn't written", reason).into()),
+        None => Ok(()),
+    }
+}
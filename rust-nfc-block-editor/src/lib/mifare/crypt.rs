@@ -0,0 +1,223 @@
+// AES-128 encryption of the data stored in the card's data blocks
+//
+// Each 16 byte data block is one AES block, so encrypted data takes no more room
+// than plain data. The block address is mixed in before encrypting (the block is
+// XORed with the encrypted block address), so the same text in two blocks doesn't
+// give the same bytes. Block 0 and the sector trailers are never encrypted.
+//
+// The key is either used as it is for every card, or is a master key from which
+// each card's key is derived with its UID, so one card's key opens no other card.
+use std::error::Error;
+use std::fs;
+use std::io::{Read, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+use aes::Aes128;
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit, generic_array::GenericArray};
+use mifare_rc522::BlockAddr;
+
+use crate::lib::mifare::dump::CardDump;
+use crate::lib::i18n::tr;
+use crate::lib::utils::{bytes_to_hex, hex_string_to_bytes};
+
+// How the card's key comes from the configured key
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeyMode {
+    // The configured key encrypts every card
+    Fixed,
+    // The configured key is a master key, each card's key is derived from its UID
+    PerUid,
+}
+
+impl KeyMode {
+    fn name(self) -> &'static str {
+        match self {
+            KeyMode::Fixed => "fixed",
+            KeyMode::PerUid => "per-uid",
+        }
+    }
+}
+
+// Data encryption settings, saved beside the SPI speeds
+#[derive(Clone, PartialEq, Debug)]
+pub struct DataKey {
+    pub enabled: bool,
+    pub mode: KeyMode,
+    pub key: [u8; 16],
+}
+
+impl DataKey {
+    // ~/.config/nfc-block-editor/data_key.conf (or $XDG_CONFIG_HOME)
+    pub fn default_path() -> PathBuf {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join("nfc-block-editor").join("data_key.conf")
+    }
+
+    // Settings in the file, None when there is no file yet
+    pub fn load(path: &Path) -> Result<Option<Self>, Box<dyn Error>> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut data_key = DataKey { enabled: true, mode: KeyMode::Fixed, key: [0; 16] };
+        let mut has_key = false;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let (name, value) = line.split_once(' ').ok_or_else(|| format!("Invalid line '{}'", line))?;
+            match (name, value.trim()) {
+                ("enabled", "yes") => data_key.enabled = true,
+                ("enabled", "no") => data_key.enabled = false,
+                ("mode", "fixed") => data_key.mode = KeyMode::Fixed,
+                ("mode", "per-uid") => data_key.mode = KeyMode::PerUid,
+                ("key", hex) => {
+                    data_key.key = hex_string_to_bytes(hex)
+                        .and_then(|key| <[u8; 16]>::try_from(key.as_slice()).ok())
+                        .ok_or("The data key must be 16 bytes (32 hex chars)")?;
+                    has_key = true;
+                },
+                _ => return Err(format!("Invalid line '{}'", line).into()),
+            }
+        }
+
+        if !has_key {
+            return Err(format!("No key in {}", path.display()).into());
+        }
+        Ok(Some(data_key))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let text = format!(
+            "# AES-128 key for the data blocks, keep a copy: data encrypted with a lost key can't be read\n\
+             enabled {}\nmode {}\nkey {}\n",
+            if self.enabled { "yes" } else { "no" },
+            self.mode.name(),
+            bytes_to_hex(&self.key).replace(' ', ""),
+        );
+        // readable by its owner only, like the signing key; an older copy saved with the
+        // default permissions is tightened too
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    // Cipher for one card, None while encryption is turned off
+    pub fn cipher_for(&self, uid: &[u8]) -> Option<DataCipher> {
+        if !self.enabled {
+            return None;
+        }
+        let key = match self.mode {
+            KeyMode::Fixed => self.key,
+            KeyMode::PerUid => derive_card_key(&self.key, uid),
+        };
+        Some(DataCipher::new(&key))
+    }
+
+    // One line for the menus
+    pub fn describe(&self) -> String {
+        match (self.enabled, self.mode) {
+            (false, _) => tr("encryption-off"),
+            (true, KeyMode::Fixed) => tr("encryption-on-fixed"),
+            (true, KeyMode::PerUid) => tr("encryption-on-per-uid"),
+        }
+    }
+}
+
+// A new random key from the system's random source
+pub fn generate_key() -> Result<[u8; 16], Box<dyn Error>> {
+    let mut key = [0u8; 16];
+    fs::File::open("/dev/urandom")?.read_exact(&mut key)?;
+    Ok(key)
+}
+
+// Whether a block holds data that is encrypted, block 0 and trailers never are
+pub fn is_data_block(block_addr: BlockAddr) -> bool {
+    !block_addr.is_manufacturer() && !block_addr.is_trailer()
+}
+
+// The cipher for one card's data blocks
+pub struct DataCipher {
+    aes: Aes128,
+}
+
+impl DataCipher {
+    pub fn new(key: &[u8; 16]) -> Self {
+        DataCipher { aes: Aes128::new(GenericArray::from_slice(key)) }
+    }
+
+    pub fn encrypt_block(&self, block_addr: BlockAddr, data: &[u8; 16]) -> [u8; 16] {
+        let mut block = xor(data, &self.tweak(block_addr));
+        self.aes.encrypt_block(GenericArray::from_mut_slice(&mut block));
+        block
+    }
+
+    pub fn decrypt_block(&self, block_addr: BlockAddr, data: &[u8; 16]) -> [u8; 16] {
+        let mut block = *data;
+        self.aes.decrypt_block(GenericArray::from_mut_slice(&mut block));
+        xor(&block, &self.tweak(block_addr))
+    }
+
+    // this gives the value a block is XORed with, the encrypted block address
+    fn tweak(&self, block_addr: BlockAddr) -> [u8; 16] {
+        let mut tweak = [0u8; 16];
+        tweak[0] = block_addr.get();
+        self.aes.encrypt_block(GenericArray::from_mut_slice(&mut tweak));
+        tweak
+    }
+}
+
+// Encrypt the data blocks of a dump, blocks only partly read are left as they are
+pub fn encrypt_dump(dump: &mut CardDump, cipher: &DataCipher) {
+    map_data_blocks(dump, |block_addr, data| cipher.encrypt_block(block_addr, data));
+}
+
+// Decrypt the data blocks of a dump
+pub fn decrypt_dump(dump: &mut CardDump, cipher: &DataCipher) {
+    map_data_blocks(dump, |block_addr, data| cipher.decrypt_block(block_addr, data));
+}
+
+fn map_data_blocks(dump: &mut CardDump, convert: impl Fn(BlockAddr, &[u8; 16]) -> [u8; 16]) {
+    for block_addr in dump.layout().blocks() {
+        if !is_data_block(block_addr) {
+            continue;
+        }
+        if let Some(data) = dump.block(block_addr) {
+            dump.set_block(block_addr, &convert(block_addr, &data));
+        }
+    }
+}
+
+// A card's key: the master key encrypting 0x01, the UID and ISO/IEC 9797-1 padding.
+// Anticollision returns the UID with its check byte, which is left out.
+fn derive_card_key(master: &[u8; 16], uid: &[u8]) -> [u8; 16] {
+    let uid = match uid {
+        [a, b, c, d, bcc] if a ^ b ^ c ^ d == *bcc => &uid[..4],
+        _ => uid,
+    };
+    let mut input = [0u8; 16];
+    input[0] = 0x01;
+    let len = uid.len().min(14);
+    input[1..1 + len].copy_from_slice(&uid[..len]);
+    input[1 + len] = 0x80;
+
+    let mut key = input;
+    Aes128::new(GenericArray::from_slice(master)).encrypt_block(GenericArray::from_mut_slice(&mut key));
+    key
+}
+
+fn xor(a: &[u8; 16], b: &[u8; 16]) -> [u8; 16] {
+    std::array::from_fn(|i| a[i] ^ b[i])
+}
@@ -0,0 +1,187 @@
+// Decoders for what a card profile says its blocks hold
+//
+// Value blocks are the Classic's own format for counters and balances: the value as a
+// 32 bit little endian number, then inverted, then again, and the block address four
+// times (plain, inverted, plain, inverted) so a torn write can be told from a good one.
+//
+// Records are blocks with a known layout, like the last transaction or a history of
+// trips. A profile lists their fields (see profile.rs):
+//   currency 0.01 EUR               what one unit of an amount is worth
+//   value 8 Balance                 a value block holding an amount
+//   record 12-14 Trip               one record per block
+//   field 0 4 unix-time Time        offset, length, type and name of a field
+// Field types: uint and uint-be (little / big endian), bcd, hex, text, unix-time
+// (seconds since 1970, little endian) and amount (uint times the currency).
+use mifare_rc522::{BlockAddr, SectorIndex};
+
+use crate::lib::i18n::{tr, tr_args};
+use crate::lib::mifare::dump::CardDump;
+use crate::lib::mifare::profile::CardProfile;
+use crate::lib::utils::bytes_to_hex;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum FieldType {
+    Uint,
+    UintBe,
+    Bcd,
+    Hex,
+    Text,
+    UnixTime,
+    Amount,
+}
+
+const ALL_FIELD_TYPES: [FieldType; 7] = [
+    FieldType::Uint, FieldType::UintBe, FieldType::Bcd, FieldType::Hex,
+    FieldType::Text, FieldType::UnixTime, FieldType::Amount,
+];
+
+impl FieldType {
+    pub fn name(self) -> &'static str {
+        match self {
+            FieldType::Uint => "uint",
+            FieldType::UintBe => "uint-be",
+            FieldType::Bcd => "bcd",
+            FieldType::Hex => "hex",
+            FieldType::Text => "text",
+            FieldType::UnixTime => "unix-time",
+            FieldType::Amount => "amount",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ALL_FIELD_TYPES.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Field {
+    pub offset: usize,
+    pub length: usize,
+    pub kind: FieldType,
+    pub name: String,
+}
+
+// Blocks that each hold one record laid out the same way
+#[derive(Clone, PartialEq, Debug)]
+pub struct RecordLayout {
+    pub blocks: Vec<BlockAddr>,
+    pub name: String,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Currency {
+    pub scale: f64,
+    pub unit: String,
+}
+
+impl Currency {
+    pub fn format(&self, amount: i64) -> String {
+        // Enough decimals to show the scale, 0.01 gives 2
+        let decimals = format!("{}", self.scale).split('.').nth(1).map_or(0, str::len);
+        format!("{:.*} {}", decimals, amount as f64 * self.scale, self.unit).trim_end().to_string()
+    }
+}
+
+// Value and address of a value block, None when the block isn't one (or is torn)
+pub fn value_from_block(data: &[u8; 16]) -> Option<(i32, u8)> {
+    let value = i32::from_le_bytes(data[0..4].try_into().ok()?);
+    let inverted = i32::from_le_bytes(data[4..8].try_into().ok()?);
+    let copy = i32::from_le_bytes(data[8..12].try_into().ok()?);
+    let addr = data[12];
+    (value == copy && value == !inverted && data[13] == !addr && data[14] == addr && data[15] == !addr)
+        .then_some((value, addr))
+}
+
+// A value block holding `value`, with the block address it is written to
+pub fn value_block(value: i32, addr: u8) -> [u8; 16] {
+    let mut data = [0u8; 16];
+    data[0..4].copy_from_slice(&value.to_le_bytes());
+    data[4..8].copy_from_slice(&(!value).to_le_bytes());
+    data[8..12].copy_from_slice(&value.to_le_bytes());
+    data[12..16].copy_from_slice(&[addr, !addr, addr, !addr]);
+    data
+}
+
+// One line per value block and record of the profile, as far as the dump has them
+pub fn decode_card(profile: &CardProfile, dump: &CardDump, sectors: Option<&[SectorIndex]>) -> Vec<String> {
+    let wanted = |block: BlockAddr| sectors.is_none_or(|sectors| sectors.contains(&block.sector()));
+    let mut lines = Vec::new();
+
+    for (block, name) in profile.values.iter().filter(|(block, _)| wanted(*block)) {
+        let line = match dump.block(*block) {
+            None => tr_args("decode-value-not-read", &[("name", name), ("block", &block.to_string())]),
+            Some(data) => match value_from_block(&data) {
+                Some((value, _)) => tr_args("decode-value", &[("name", name), ("amount", &format_amount(profile, value as i64)), ("block", &block.to_string())]),
+                None => tr_args("decode-value-invalid", &[("name", name), ("block", &block.to_string())]),
+            },
+        };
+        lines.push(line);
+    }
+
+    for record in &profile.records {
+        let blocks: Vec<BlockAddr> = record.blocks.iter().copied().filter(|block| wanted(*block)).collect();
+        for (number, block) in blocks.iter().enumerate() {
+            let name = if record.blocks.len() > 1 {
+                tr_args("decode-record-numbered", &[("name", &record.name), ("number", &(number + 1).to_string()), ("block", &block.to_string())])
+            } else {
+                tr_args("decode-record", &[("name", &record.name), ("block", &block.to_string())])
+            };
+            let line = match dump.block(*block) {
+                None => tr_args("decode-record-not-read", &[("name", &name)]),
+                Some(data) if data.iter().all(|byte| *byte == 0) => tr_args("decode-record-empty", &[("name", &name)]),
+                Some(data) => {
+                    let fields: Vec<String> = record.fields.iter()
+                        .map(|field| format!("{} {}", field.name, decode_field(profile, field, &data)))
+                        .collect();
+                    format!("{}: {}", name, fields.join(", "))
+                },
+            };
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+// An amount in the profile's currency, a plain number when it has none
+pub fn format_amount(profile: &CardProfile, amount: i64) -> String {
+    match &profile.currency {
+        Some(currency) => currency.format(amount),
+        None => amount.to_string(),
+    }
+}
+
+fn decode_field(profile: &CardProfile, field: &Field, data: &[u8; 16]) -> String {
+    let bytes = match data.get(field.offset..field.offset + field.length) {
+        Some(bytes) => bytes,
+        None => return tr("decode-outside-block"),
+    };
+    let little_endian = || bytes.iter().rev().fold(0u64, |value, byte| value << 8 | *byte as u64);
+
+    match field.kind {
+        FieldType::Uint => little_endian().to_string(),
+        FieldType::UintBe => bytes.iter().fold(0u64, |value, byte| value << 8 | *byte as u64).to_string(),
+        FieldType::Bcd => bytes.iter().map(|byte| format!("{:02X}", byte)).collect(),
+        FieldType::Hex => bytes_to_hex(bytes),
+        FieldType::Text => String::from_utf8_lossy(bytes).trim_end_matches('\0').trim().to_string(),
+        FieldType::UnixTime => format_unix_time(little_endian()),
+        FieldType::Amount => format_amount(profile, little_endian() as i64),
+    }
+}
+
+// "2024-05-01 10:22:03 UTC"
+fn format_unix_time(seconds: u64) -> String {
+    let days = (seconds / 86400) as i64;
+    let time = seconds % 86400;
+    // Days since 1970-01-01 to a civil date, after Howard Hinnant's civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC", year, month, day, time / 3600, time / 60 % 60, time % 60)
+}
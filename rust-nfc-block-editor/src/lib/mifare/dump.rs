@@ -1,81 +1,280 @@
 use std::error::Error;
-use rppal::spi::Spi;
+use std::fs;
+use std::path::Path;
+use mifare_rc522::{BlockAddr, CardLayout, Key, SectorIndex};
 
 use crate::lib::mfrc522::{
     mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, 
     mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
-    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK
+    PICC_REQIDL, PICC_REQALL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK, SpiReader
 };
 
 use crate::lib::utils::{bytes_to_hex, bytes_to_ascii, uid_to_string};
-use crate::lib::mifare::access::AccessBits;
+use crate::lib::mifare::access::{AccessBits, invalid_access_bits};
+use crate::lib::mifare::keystore::{KeyStore, SectorKey};
+use crate::lib::mifare::flipper::{parse_flipper_nfc, format_flipper_nfc};
+use crate::lib::mifare::operations::write_block_raw;
+use crate::lib::mifare::profile::CardProfile;
+use crate::lib::mifare::selection::Selection;
+use crate::lib::i18n::{tr, tr_args};
 
-// Dump all card data (Classic 1K) using the method from the working code
-pub fn dump_card(spi: &mut Spi) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
-    // Key to use for authentication
-    let key = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
-    
-    // Request tag
-    let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
+// One block of a dump, None for bytes that couldn't be read
+pub type DumpBlock = [Option<u8>; 16];
+
+// Everything read from a card, in memory so it can be saved and written back
+pub struct CardDump {
+    pub uid: Vec<u8>,
+    pub atqa: Option<[u8; 2]>,
+    pub sak: Option<u8>,
+    pub blocks: Vec<DumpBlock>,
+}
+
+impl CardDump {
+    // Dump with every block unknown
+    pub fn new(uid: Vec<u8>, block_count: usize) -> Self {
+        CardDump {
+            uid,
+            atqa: None,
+            sak: None,
+            blocks: vec![[None; 16]; block_count],
+        }
+    }
+
+    // Block data if every byte of it is known
+    pub fn block(&self, block_addr: BlockAddr) -> Option<[u8; 16]> {
+        let block = self.blocks.get(block_addr.index())?;
+        let mut data = [0u8; 16];
+        for (byte, known) in data.iter_mut().zip(block.iter()) {
+            *byte = (*known)?;
+        }
+        Some(data)
+    }
+
+    pub fn set_block(&mut self, block_addr: BlockAddr, data: &[u8]) {
+        if let Some(block) = self.blocks.get_mut(block_addr.index()) {
+            for (byte, value) in block.iter_mut().zip(data.iter()) {
+                *byte = Some(*value);
+            }
+        }
+    }
+
+    // The layout of the card the dump is of, from its size
+    pub fn layout(&self) -> CardLayout {
+        CardLayout::from_block_count(self.blocks.len())
+    }
+
+    // Key A and Key B of every sector trailer, where the dump has all 6 bytes
+    pub fn trailer_keys(&self) -> Vec<Key> {
+        let mut keys = Vec::new();
+        let trailers = self.layout().sectors().map(SectorIndex::trailer);
+        for block in trailers.filter_map(|trailer| self.blocks.get(trailer.index())) {
+            for key in [&block[0..6], &block[10..16]] {
+                let key: Option<Vec<u8>> = key.iter().copied().collect();
+                if let Some(key) = key.and_then(|key| <[u8; 6]>::try_from(key.as_slice()).ok()).map(Key) {
+                    if !keys.contains(&key) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+        keys
+    }
+
+    pub fn known_blocks(&self) -> usize {
+        self.blocks.iter().filter(|block| block.iter().all(Option::is_some)).count()
+    }
+}
+
+// Load a dump saved by this tool, a Flipper Zero (.nfc) or any raw 16 bytes per block
+// dump (.bin, .mfd, .dump)
+pub fn load_dump_file(path: &str) -> Result<CardDump, Box<dyn Error>> {
+    if is_flipper_file(path) {
+        let text = fs::read_to_string(path)?;
+        return parse_flipper_nfc(&text);
+    }
+
+    let data = fs::read(path)?;
+    if data.is_empty() || data.len() % 16 != 0 {
+        return Err(format!("{} is not a raw dump ({} bytes, expected a multiple of 16)", path, data.len()).into());
+    }
+
+    // Raw dumps carry no UID field, it is the first 4 bytes of block 0
+    let mut dump = CardDump::new(data[0..4].to_vec(), data.len() / 16);
+    dump.sak = Some(data[5]);
+    for (block, data) in dump.blocks.iter_mut().zip(data.chunks(16)) {
+        block.iter_mut().zip(data).for_each(|(byte, value)| *byte = Some(*value));
+    }
+    Ok(dump)
+}
+
+// Save a dump as a Flipper .nfc file or, for any other extension, a raw binary dump
+// (unknown bytes are written as 00)
+pub fn save_dump_file(path: &str, dump: &CardDump) -> Result<(), Box<dyn Error>> {
+    if is_flipper_file(path) {
+        fs::write(path, format_flipper_nfc(dump))?;
+    } else {
+        let data: Vec<u8> = dump.blocks.iter()
+            .flat_map(|block| block.iter().map(|byte| byte.unwrap_or(0)))
+            .collect();
+        fs::write(path, data)?;
+    }
+    Ok(())
+}
+
+fn is_flipper_file(path: &str) -> bool {
+    Path::new(path).extension().map_or(false, |ext| ext.eq_ignore_ascii_case("nfc"))
+}
+
+// Write a dump to the card on the reader. Each block is authenticated with the first
+// key from the key store that works, starting with the one that last worked in its sector.
+// Block 0 and the sector trailers are only written when asked for: block 0 can only
+// be changed on magic cards, and a trailer with the wrong keys locks its sector.
+// Only the selected blocks are written. Returns the blocks written and the blocks that failed.
+pub fn write_dump(spi: &mut SpiReader, dump: &CardDump, key_store: &mut KeyStore, include_block0: bool, include_trailers: bool,
+    selection: &Selection) -> Result<(Vec<BlockAddr>, Vec<BlockAddr>), Box<dyn Error>> {
+    let mut written = Vec::new();
+    let mut failed = Vec::new();
+
+    for block_addr in dump.layout().blocks() {
+        let is_trailer = block_addr.is_trailer();
+        if (block_addr.is_manufacturer() && !include_block0) || (is_trailer && !include_trailers) || !selection.has_block(block_addr) {
+            continue;
+        }
+
+        let data = match dump.block(block_addr) {
+            Some(data) => data,
+            None => {
+                println!("  {}", tr_args("block-skipped-not-in-dump", &[("block", &block_addr.to_string())]));
+                continue;
+            }
+        };
+
+        let sector = block_addr.sector();
+        let mut working_key = None;
+        for key in key_store.candidate_keys(sector) {
+            if write_block_raw(spi, block_addr, &key, &data)? {
+                working_key = Some(key);
+                break;
+            }
+        }
+
+        match working_key {
+            Some(key) => {
+                println!("  {}", tr_args("block-hex-line", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))]));
+                key_store.remember_key(sector, key);
+                written.push(block_addr);
+            },
+            None => {
+                println!("  {}", tr_args("block-write-failed", &[("block", &block_addr.to_string())]));
+                failed.push(block_addr);
+            }
+        }
+    }
+
+    Ok((written, failed))
+}
+
+// Write a block with the first key from the key store that opens its sector
+pub fn write_block_with_store(spi: &mut SpiReader, key_store: &mut KeyStore, block: BlockAddr, data: &[u8]) -> Result<bool, Box<dyn Error>> {
+    let sector = block.sector();
+    for key in key_store.candidate_keys(sector) {
+        if write_block_raw(spi, block, &key, data)? {
+            key_store.remember_key(sector, key);
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Request, anticollision and select. Returns the anticollision answer (UID and check
+// byte) and the SAK.
+pub fn select_card(spi: &mut SpiReader, req_mode: u8) -> Result<Option<(Vec<u8>, u8)>, Box<dyn Error>> {
+    let (status, _) = mfrc522_request(spi, req_mode)?;
     if status != MI_OK {
         return Ok(None);
     }
     
-    // Anti-collision
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
         return Ok(None);
     }
     
-    // Select the tag
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
         return Ok(None);
     }
     
-    println!("Card selected. UID: {}  Size: {}", uid_to_string(&uid), size);
-    println!("\nDumping card data...");
+    Ok(Some((uid, size)))
+}
+
+// Authenticate a sector with the keys from the key store, in the store's order for
+// that sector. A failed authentication drops the card out of the selected state, so
+// the card is selected again before the next key.
+pub fn authenticate_sector(spi: &mut SpiReader, sector: SectorIndex, uid: &[u8], key_store: &KeyStore)
+    -> Result<Option<SectorKey>, Box<dyn Error>> {
+    let trailer_block = sector.trailer();
     
-    // Classic 1K has 16 sectors with 4 blocks each
-    for sector in 0..16 {
-        println!("\nSector {}", sector);
-        println!("------------------");
+    for candidate in key_store.candidates(sector) {
+        if mfrc522_auth(spi, candidate.auth_type, trailer_block.get(), candidate.key.as_bytes(), uid)? == MI_OK {
+            return Ok(Some(candidate));
+        }
         
-        for block in 0..4 {
-            let block_addr = sector * 4 + block;
-            
-            // Authenticate for the block
-            let status = mfrc522_auth(spi, PICC_AUTHENT1A, block_addr, &key, &uid)?;
-            
-            if status == MI_OK {
-                if let Some(data) = mfrc522_read(spi, block_addr)? {
-                    println!("  Block {}: {}", block_addr, bytes_to_hex(&data));
-                    
-                    // For non-sector trailer blocks, also show ASCII
-                    if block != 3 {
-                        println!("          ASCII: {}", bytes_to_ascii(&data));
-                    } else {
-                        // Sector trailer - display keys and access bits
-                        println!("          Key A: {}", bytes_to_hex(&data[0..6]));
-                        println!("          Access Bits: {}", bytes_to_hex(&data[6..10]));
-                        println!("          Key B: {}", bytes_to_hex(&data[10..16]));
-                        
-                        // Show interpreted access conditions
-                        let access_bytes = [data[6], data[7], data[8], data[9]];
-                        let access_bits = AccessBits::from_bytes(&access_bytes);
-                        println!("\n          Access Conditions:");
-                        println!("          Block {}: {}", block_addr-3, access_bits.interpret_access("data", 0));
-                        println!("          Block {}: {}", block_addr-2, access_bits.interpret_access("data", 1));
-                        println!("          Block {}: {}", block_addr-1, access_bits.interpret_access("data", 2));
-                        println!("          Block {} (Trailer): Key A: {}", block_addr, 
-                                access_bits.interpret_access("trailer", 0).split('\n').next().unwrap_or(""));
-                    }
-                } else {
-                    println!("  Block {}: (Read failed)", block_addr);
+        mfrc522_stop_crypto1(spi)?;
+        // REQALL also wakes a card that went to HALT
+        match select_card(spi, PICC_REQALL)? {
+            Some((new_uid, _)) if new_uid == uid => {},
+            // Card removed or replaced
+            _ => return Ok(None),
+        }
+    }
+    
+    Ok(None)
+}
+
+// Read every block of the card on the reader (Classic Mini, 1K or 4K, from the SAK) without
+// printing anything. Each sector is authenticated once and its blocks read under that one
+// authentication, with keys tried in the key store's order for the sector. The key that
+// works is remembered for the next dump. Blocks no key opens are left unknown.
+pub fn read_card(spi: &mut SpiReader, key_store: &mut KeyStore) -> Result<Option<CardDump>, Box<dyn Error>> {
+    read_sectors(spi, key_store, &Selection::all())
+}
+
+// Like read_card, for the selected blocks only. The others are left unknown.
+pub fn read_sectors(spi: &mut SpiReader, key_store: &mut KeyStore, selection: &Selection) -> Result<Option<CardDump>, Box<dyn Error>> {
+    let (uid, size) = match select_card(spi, PICC_REQIDL)? {
+        Some(selected) => selected,
+        None => return Ok(None),
+    };
+    
+    // Anticollision returns the UID followed by its check byte
+    let layout = CardLayout::from_sak_or_1k(size);
+    let mut dump = CardDump::new(uid[..4].to_vec(), layout.block_count());
+    dump.sak = Some(size);
+    
+    for sector in selection.sectors(layout) {
+        let sector_key = match authenticate_sector(spi, sector, &uid, key_store)? {
+            Some(sector_key) => sector_key,
+            None => continue,
+        };
+        key_store.remember(sector, sector_key);
+        
+        for block_addr in sector.blocks() {
+            if !selection.has_block(block_addr) {
+                continue;
+            }
+            if let Some(mut data) = mfrc522_read(spi, block_addr.get())? {
+                // The card never returns Key A, and Key B only where the access bits
+                // allow it, but the key that just worked is known
+                let is_trailer = block_addr.is_trailer();
+                let key_a_known = !is_trailer || sector_key.auth_type == PICC_AUTHENT1A;
+                if is_trailer {
+                    let key_bytes = if key_a_known { 0..6 } else { 10..16 };
+                    data[key_bytes].copy_from_slice(sector_key.key.as_bytes());
+                }
+                dump.set_block(block_addr, &data);
+                if !key_a_known {
+                    dump.blocks[block_addr.index()][0..6].fill(None);
                 }
-            } else {
-                println!("  Authentication failed for Block {}", block_addr);
-                break; // Can't read more blocks in this sector
             }
         }
     }
@@ -83,76 +282,142 @@ pub fn dump_card(spi: &mut Spi) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
     // Only stop crypto once at the end
     mfrc522_stop_crypto1(spi)?;
     
-    Ok(Some(uid))
+    Ok(Some(dump))
 }
 
-// Simple dump of a specific card sector
-pub fn dump_sector(spi: &mut Spi, sector: u8) -> Result<bool, Box<dyn Error>> {
-    if sector >= 16 {
-        return Err("Invalid sector number (must be 0-15)".into());
+// Dump the selected sectors (Classic 1K or 4K) and print them sector by sector, with what the
+// profile says the sectors and blocks hold
+pub fn dump_card(spi: &mut SpiReader, key_store: &mut KeyStore, profile: Option<&CardProfile>, selection: &Selection)
+    -> Result<Option<CardDump>, Box<dyn Error>> {
+    let dump = match read_sectors(spi, key_store, selection)? {
+        Some(dump) => dump,
+        None => return Ok(None),
+    };
+    
+    println!("{}", tr_args("card-selected-uid-size", &[("uid", &uid_to_string(&dump.uid)), ("sak", &dump.sak.unwrap_or(0).to_string()), ("layout", dump.layout().name())]));
+    if let Some(profile) = profile {
+        println!("{}", tr_args("dump-profile", &[("profile", &profile.name.to_string())]));
     }
+    println!("\n{}", tr_args("dumping-card-data", &[("selection", &selection.describe().to_string())]));
     
+    for sector in selection.sectors(dump.layout()) {
+        print_sector(&dump, sector, profile);
+    }
+    
+    Ok(Some(dump))
+}
+
+// Print one sector of a dump, the trailer with its keys and access conditions
+pub fn print_sector(dump: &CardDump, sector: SectorIndex, profile: Option<&CardProfile>) {
+    match profile.and_then(|profile| profile.sector_label(sector)) {
+        Some(label) => println!("\n{}", tr_args("profile-sector-label", &[("sector", &sector.to_string()), ("label", label)])),
+        None => println!("\n{}", tr_args("dump-sector", &[("sector", &sector.to_string())])),
+    }
+    println!("------------------");
+    
+    for block_addr in sector.blocks() {
+        let data = match dump.block(block_addr) {
+            Some(data) => data,
+            None => {
+                println!("  {}", tr_args("block-authentication-or-read-failed", &[("block", &block_addr.to_string())]));
+                continue;
+            }
+        };
+        
+        match profile.and_then(|profile| profile.block_label(block_addr)) {
+            Some(label) => println!("  {}", tr_args("dump-block-label", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data)), ("label", label)])),
+            None => println!("  {}", tr_args("block-hex-line", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))])),
+        }
+        
+        // For non-sector trailer blocks, also show ASCII
+        if !block_addr.is_trailer() {
+            println!("          {}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&data))]));
+        } else {
+            // Sector trailer - display keys and access bits
+            println!("          {}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(&data[0..6]))]));
+            println!("          {}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&data[6..10]))]));
+            println!("          {}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(&data[10..16]))]));
+            
+            // Show interpreted access conditions
+            let access_bytes = [data[6], data[7], data[8], data[9]];
+            match AccessBits::from_bytes(&access_bytes) {
+                Some(access_bits) => {
+                    println!("\n          {}", tr("access-conditions"));
+                    for line in access_bits.data_access_lines(sector) {
+                        println!("          {}", line);
+                    }
+                    println!("          {}", tr_args("block-trailer-key", &[("block", &block_addr.to_string()), ("access", (access_bits.interpret_access("trailer", 0).split('\n').next().unwrap_or("")))]));
+                },
+                None => println!("\n          {}", invalid_access_bits()),
+            }
+        }
+    }
+}
+
+// Simple dump of a specific card sector
+pub fn dump_sector(spi: &mut SpiReader, sector: SectorIndex) -> Result<bool, Box<dyn Error>> {
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        println!("No card detected");
+        println!("{}", tr("no-card-detected"));
         return Ok(false);
     }
     
     // Anti-collision
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        println!("Failed to get card UID");
+        println!("{}", tr("err-get-card-uid"));
         return Ok(false);
     }
     
     // Select the tag
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        println!("Failed to select card");
+        println!("{}", tr("err-select-card"));
         return Ok(false);
     }
     
-    println!("Card selected. UID: {}", uid_to_string(&uid));
-    println!("\nDumping sector {}:", sector);
+    println!("{}", tr_args("card-selected-uid", &[("uid", &uid_to_string(&uid))]));
+    println!("\n{}", tr_args("dumping-sector", &[("sector", &sector.to_string())]));
     println!("------------------");
     
     // Just use the default key
     let key = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
     
-    for block_offset in 0..4 {
-        let block_addr = sector * 4 + block_offset;
-        
+    for block_addr in sector.blocks() {
         // Authenticate directly for each block
-        let status = mfrc522_auth(spi, PICC_AUTHENT1A, block_addr, &key, &uid)?;
+        let status = mfrc522_auth(spi, PICC_AUTHENT1A, block_addr.get(), &key, &uid)?;
         if status != MI_OK {
-            println!("  Block {}: (Authentication failed)", block_addr);
+            println!("  {}", tr_args("block-authentication-failed", &[("block", &block_addr.to_string())]));
             break; // Stop at first authentication failure
         }
         
-        if let Some(data) = mfrc522_read(spi, block_addr)? {
-            println!("  Block {}: {}", block_addr, bytes_to_hex(&data));
+        if let Some(data) = mfrc522_read(spi, block_addr.get())? {
+            println!("  {}", tr_args("block-hex-line", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))]));
             
-            if block_offset == 3 {
+            if block_addr.is_trailer() {
                 // Sector trailer - display keys and access bits
-                println!("    Key A: {}", bytes_to_hex(&data[0..6]));
-                println!("    Access Bits: {}", bytes_to_hex(&data[6..10]));
-                println!("    Key B: {}", bytes_to_hex(&data[10..16]));
+                println!("    {}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(&data[0..6]))]));
+                println!("    {}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&data[6..10]))]));
+                println!("    {}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(&data[10..16]))]));
                 
                 // Show interpreted access conditions
                 let access_bytes = [data[6], data[7], data[8], data[9]];
-                let access_bits = AccessBits::from_bytes(&access_bytes);
-                println!("\n    Access Conditions:");
-                println!("    Block {}: {}", block_addr-3, access_bits.interpret_access("data", 0));
-                println!("    Block {}: {}", block_addr-2, access_bits.interpret_access("data", 1));
-                println!("    Block {}: {}", block_addr-1, access_bits.interpret_access("data", 2));
-                println!("    Block {} (Trailer): {}", block_addr, 
-                         access_bits.interpret_access("trailer", 0).replace("\n", "\n    "));
+                match AccessBits::from_bytes(&access_bytes) {
+                    Some(access_bits) => {
+                        println!("\n    {}", tr("access-conditions"));
+                        for line in access_bits.data_access_lines(sector) {
+                            println!("    {}", line);
+                        }
+                        println!("    {}", tr_args("block-trailer-access", &[("block", &block_addr.to_string()), ("access", &access_bits.interpret_access("trailer", 0).replace("\n", "\n    "))]));
+                    },
+                    None => println!("\n    {}", invalid_access_bits()),
+                }
             } else {
-                println!("    ASCII: {}", bytes_to_ascii(&data));
+                println!("    {}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&data))]));
             }
         } else {
-            println!("  Block {}: (Read failed)", block_addr);
+            println!("  {}", tr_args("block-read-failed", &[("block", &block_addr.to_string())]));
         }
     }
     
@@ -162,14 +427,14 @@ pub fn dump_sector(spi: &mut Spi, sector: u8) -> Result<bool, Box<dyn Error>> {
     Ok(true)
 }
 
-// Function to test various keys against a card
-pub fn test_keys(spi: &mut Spi) -> Result<Vec<(u8, [u8; 6])>, Box<dyn Error>> {
+// Function to test various keys against the selected sectors of a card
+pub fn test_keys(spi: &mut SpiReader, selection: &Selection) -> Result<Vec<(SectorIndex, Key)>, Box<dyn Error>> {
     let keys = [
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],  // Default key
-        [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5],  // Common key
-        [0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7],  // Common key
-        [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],  // All zeroes
-        [0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5],  // Another common key
+        Key([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]),  // Default key
+        Key([0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]),  // Common key
+        Key([0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7]),  // Common key
+        Key([0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),  // All zeroes
+        Key([0xB0, 0xB1, 0xB2, 0xB3, 0xB4, 0xB5]),  // Another common key
     ];
     
     // Request tag
@@ -190,16 +455,17 @@ pub fn test_keys(spi: &mut Spi) -> Result<Vec<(u8, [u8; 6])>, Box<dyn Error>> {
         return Err("Failed to select card".into());
     }
     
-    println!("Card selected. UID: {}  Size: {}", uid_to_string(&uid), size);
-    println!("\nTesting keys...");
+    let layout = CardLayout::from_sak_or_1k(size);
+    println!("{}", tr_args("card-selected-uid-size-2", &[("uid", &uid_to_string(&uid)), ("size", &size.to_string()), ("layout", layout.name())]));
+    println!("\n{}", tr("testing-keys"));
     
     let mut results = Vec::new();
     
     // Test keys for each sector
-    for sector in 0..16 {
-        println!("Sector {}: ", sector);
+    for sector in selection.sectors(layout) {
+        println!("{} ", tr_args("dump-sector-heading", &[("sector", &sector.to_string())]));
         
-        let first_block = sector * 4;
+        let first_block = sector.first_block();
         
         for auth_type in &[PICC_AUTHENT1A, PICC_AUTHENT1B] {
             for key in &keys {
@@ -220,15 +486,13 @@ pub fn test_keys(spi: &mut Spi) -> Result<Vec<(u8, [u8; 6])>, Box<dyn Error>> {
                 mfrc522_select_tag(spi, &new_uid)?;
                 
                 // Try authentication with this key
-                let status = mfrc522_auth(spi, *auth_type, first_block, key, &new_uid)?;
+                let status = mfrc522_auth(spi, *auth_type, first_block.get(), key.as_bytes(), &new_uid)?;
                 if status == MI_OK {
                     // This key works!
                     let key_type = if *auth_type == PICC_AUTHENT1A { "A" } else { "B" };
-                    println!("  Found working Key {}: {}", key_type, bytes_to_hex(key));
+                    println!("  {}", tr_args("found-working-key", &[("key_type", key_type), ("hex", &bytes_to_hex(key.as_bytes()))]));
                     
-                    let mut key_copy = [0u8; 6];
-                    key_copy.copy_from_slice(key);
-                    results.push((sector, key_copy));
+                    results.push((sector, *key));
                     
                     // Clean up
                     mfrc522_stop_crypto1(spi)?;
@@ -0,0 +1,170 @@
+// Flipper Zero .nfc files (Mifare Classic)
+//
+// The Flipper saves cards as a text file of "Key: value" lines, one line per block,
+// with "??" for bytes it couldn't read:
+//
+//   Filetype: Flipper NFC device
+//   Version: 4
+//   Device type: Mifare Classic
+//   UID: 04 A1 B2 C3
+//   ATQA: 00 04
+//   SAK: 08
+//   Mifare Classic type: 1K
+//   Data format version: 2
+//   Block 0: 04 A1 B2 C3 D4 08 04 00 62 63 64 65 66 67 68 69
+//   ...
+//
+// Older firmware wrote Version 2 or 3 files with the same fields, they read the same way.
+use std::error::Error;
+
+use crate::lib::mifare::dump::{CardDump, DumpBlock};
+
+const FILETYPE: &str = "Flipper NFC device";
+const FORMAT_VERSION: u32 = 4;
+const DEVICE_TYPE: &str = "Mifare Classic";
+
+// Blocks on each Mifare Classic type the Flipper knows
+fn blocks_for_type(card_type: &str) -> Option<usize> {
+    match card_type.to_uppercase().as_str() {
+        "MINI" => Some(20),
+        "1K" => Some(64),
+        "4K" => Some(256),
+        _ => None,
+    }
+}
+
+fn type_for_blocks(blocks: usize) -> &'static str {
+    match blocks {
+        0..=20 => "MINI",
+        21..=64 => "1K",
+        _ => "4K",
+    }
+}
+
+// Space separated hex bytes, "??" allowed where `allow_unknown` is set
+fn parse_bytes(value: &str, allow_unknown: bool) -> Result<Vec<Option<u8>>, String> {
+    value.split_whitespace()
+        .map(|byte| {
+            if allow_unknown && byte == "??" {
+                Ok(None)
+            } else if byte.len() == 2 {
+                u8::from_str_radix(byte, 16).map(Some).map_err(|_| format!("Invalid byte '{}'", byte))
+            } else {
+                Err(format!("Invalid byte '{}'", byte))
+            }
+        })
+        .collect()
+}
+
+fn parse_known_bytes(value: &str) -> Result<Vec<u8>, String> {
+    Ok(parse_bytes(value, false)?.into_iter().flatten().collect())
+}
+
+fn format_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")
+}
+
+// Parse the contents of a Flipper .nfc file
+pub fn parse_flipper_nfc(text: &str) -> Result<CardDump, Box<dyn Error>> {
+    let mut filetype = None;
+    let mut device_type = None;
+    let mut card_type = None;
+    let mut uid = Vec::new();
+    let mut atqa = None;
+    let mut sak = None;
+    let mut blocks: Vec<(usize, DumpBlock)> = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => return Err(format!("Line {}: expected 'Key: value'", number + 1).into()),
+        };
+        let at_line = |e: String| format!("Line {}: {}", number + 1, e);
+
+        match key {
+            "Filetype" => filetype = Some(value.to_string()),
+            "Device type" => device_type = Some(value.to_string()),
+            "Mifare Classic type" => card_type = Some(value.to_string()),
+            "UID" => uid = parse_known_bytes(value).map_err(at_line)?,
+            "ATQA" => {
+                let bytes = parse_known_bytes(value).map_err(at_line)?;
+                atqa = <[u8; 2]>::try_from(bytes.as_slice()).ok();
+            },
+            "SAK" => sak = parse_known_bytes(value).map_err(at_line)?.first().copied(),
+            _ => {
+                if let Some(index) = key.strip_prefix("Block ") {
+                    let index = index.trim().parse::<usize>()
+                        .map_err(|_| at_line(format!("Invalid block number '{}'", index)))?;
+                    let bytes = parse_bytes(value, true).map_err(at_line)?;
+                    let block: DumpBlock = bytes.try_into()
+                        .map_err(|_| at_line(format!("Block {} must have 16 bytes", index)))?;
+                    blocks.push((index, block));
+                }
+                // Other keys (Version, Data format version, Ultralight/NTAG fields...) aren't needed
+            }
+        }
+    }
+
+    if filetype.as_deref() != Some(FILETYPE) {
+        return Err("Not a Flipper NFC file".into());
+    }
+    match device_type.as_deref() {
+        Some(DEVICE_TYPE) => {},
+        Some(other) => return Err(format!("Unsupported Flipper device type '{}', only Mifare Classic dumps can be used", other).into()),
+        None => return Err("Flipper file has no device type".into()),
+    }
+    if uid.is_empty() {
+        return Err("Flipper file has no UID".into());
+    }
+
+    let highest_block = blocks.iter().map(|(index, _)| index + 1).max().unwrap_or(0);
+    let block_count = match card_type.as_deref() {
+        Some(card_type) => blocks_for_type(card_type)
+            .ok_or_else(|| format!("Unknown Mifare Classic type '{}'", card_type))?,
+        None => highest_block,
+    };
+    if highest_block > block_count {
+        return Err(format!("Block {} is past the end of a {} block card", highest_block - 1, block_count).into());
+    }
+
+    let mut dump = CardDump::new(uid, block_count);
+    dump.atqa = atqa;
+    dump.sak = sak;
+    for (index, block) in blocks {
+        dump.blocks[index] = block;
+    }
+    Ok(dump)
+}
+
+// Format a dump as a Flipper .nfc file (current format version)
+pub fn format_flipper_nfc(dump: &CardDump) -> String {
+    let card_type = type_for_blocks(dump.blocks.len());
+    // Fall back to what a genuine card of this size reports
+    let (default_atqa, default_sak) = match card_type {
+        "MINI" => ([0x00, 0x04], 0x09),
+        "1K" => ([0x00, 0x04], 0x08),
+        _ => ([0x00, 0x02], 0x18),
+    };
+
+    let mut text = String::new();
+    text.push_str(&format!("Filetype: {}\n", FILETYPE));
+    text.push_str(&format!("Version: {}\n", FORMAT_VERSION));
+    text.push_str(&format!("Device type: {}\n", DEVICE_TYPE));
+    text.push_str(&format!("UID: {}\n", format_bytes(&dump.uid)));
+    text.push_str(&format!("ATQA: {}\n", format_bytes(&dump.atqa.unwrap_or(default_atqa))));
+    text.push_str(&format!("SAK: {:02X}\n", dump.sak.unwrap_or(default_sak)));
+    text.push_str(&format!("Mifare Classic type: {}\n", card_type));
+    text.push_str("Data format version: 2\n");
+    text.push_str("# Mifare Classic blocks, '??' means unknown data\n");
+    for (index, block) in dump.blocks.iter().enumerate() {
+        let bytes: Vec<String> = block.iter()
+            .map(|byte| byte.map_or("??".to_string(), |b| format!("{:02X}", b)))
+            .collect();
+        text.push_str(&format!("Block {}: {}\n", index, bytes.join(" ")));
+    }
+    text
+}
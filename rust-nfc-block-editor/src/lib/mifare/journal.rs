@@ -0,0 +1,193 @@
+// Updates of several blocks that a card leaving the reader can't leave half done
+//
+// A balance and its backup copy, or a balance and the record of the top-up, have to
+// change together: a card taken away between the two writes holds one new and one old
+// block. A profile can give blocks for a journal (the journal setting), and updates of
+// several blocks then go through it:
+//   1. the new data is written to the journal's data blocks and read back
+//   2. the journal's first block, the commit record, is written with the blocks the
+//      data is meant for and read back. From here on the update counts as made.
+//   3. the data is written to those blocks
+//   4. the commit record is cleared
+// Before step 2 the blocks all still hold their old data. After it, a committed
+// record on the card means the update has to be finished, which recover_journal does
+// from the data in the journal the next time the card is on the reader.
+//
+// The commit record: byte 0 'J', byte 1 the state, byte 2 the number of blocks, the
+// block addresses from byte 3, and byte 15 the XOR of bytes 0-14, so a torn record
+// doesn't count as committed.
+use std::error::Error;
+use mifare_rc522::BlockAddr;
+
+use crate::lib::i18n::tr;
+use crate::lib::mfrc522::{mfrc522_stop_crypto1, SpiReader};
+use crate::lib::mifare::dump::{read_sectors, write_block_with_store, CardDump};
+use crate::lib::mifare::keystore::KeyStore;
+use crate::lib::mifare::selection::Selection;
+use crate::lib::mifare::verify::block_matches;
+
+const RECORD_MAGIC: u8 = b'J';
+const STATE_IDLE: u8 = 0x00;
+const STATE_COMMITTED: u8 = 0xC0;
+// Block addresses fit in bytes 3-14 of the record
+const MAX_BLOCKS: usize = 12;
+
+// Where a journaled update left the card
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum JournalWrite {
+    // Every block holds the new data and the journal is clear
+    Done,
+    // Stopped before the commit, every block still holds its old data
+    RolledBack,
+    // Committed, or maybe committed, but not finished: recover_journal finishes it
+    Pending,
+}
+
+impl JournalWrite {
+    pub fn guidance(self) -> String {
+        match self {
+            JournalWrite::Done => tr("journal-done"),
+            JournalWrite::RolledBack => tr("journal-rolled-back"),
+            JournalWrite::Pending => tr("journal-pending"),
+        }
+    }
+}
+
+// Write the blocks through the journal, which is the profile's journal blocks: the
+// commit record first, then one data block for each block updated
+pub fn journaled_write(spi: &mut SpiReader, key_store: &mut KeyStore, journal: &[BlockAddr], updates: &[(BlockAddr, [u8; 16])])
+    -> Result<JournalWrite, Box<dyn Error>> {
+    let (record_block, slots) = journal.split_first().ok_or("The profile has no journal")?;
+    if updates.len() > slots.len().min(MAX_BLOCKS) {
+        return Err(format!("The journal holds {} blocks, the update has {}", slots.len().min(MAX_BLOCKS), updates.len()).into());
+    }
+    if let Some((block, _)) = updates.iter().find(|(block, _)| block.is_manufacturer() || block.is_trailer() || journal.contains(block)) {
+        return Err(format!("Block {} can't be updated through the journal", block).into());
+    }
+    // An unfinished update would be lost when its journal is written over
+    recover_journal(spi, key_store, journal)?;
+
+    // 1. The new data into the journal
+    let staged: Vec<(BlockAddr, [u8; 16])> = slots.iter().zip(updates).map(|(slot, (_, data))| (*slot, *data)).collect();
+    for (slot, data) in &staged {
+        if !write_block_with_store(spi, key_store, *slot, data)? {
+            return Ok(JournalWrite::RolledBack);
+        }
+    }
+    if !reads_back(spi, key_store, &staged)? {
+        return Ok(JournalWrite::RolledBack);
+    }
+
+    // 2. The commit
+    let targets: Vec<BlockAddr> = updates.iter().map(|(block, _)| *block).collect();
+    let record = commit_record(&targets);
+    write_block_with_store(spi, key_store, *record_block, &record)?;
+    match read_blocks(spi, key_store, &[*record_block])?.map(|dump| dump.block(*record_block)) {
+        Some(Some(on_card)) if on_card == record => {},
+        Some(_) => return Ok(JournalWrite::RolledBack),
+        // Whether the commit got to the card is only known once it is back
+        None => return Ok(JournalWrite::Pending),
+    }
+
+    // 3. and 4.
+    if finish(spi, key_store, *record_block, updates)? {
+        Ok(JournalWrite::Done)
+    } else {
+        Ok(JournalWrite::Pending)
+    }
+}
+
+// The blocks of an update the card's journal holds committed but not finished, None
+// when there isn't one (or no card)
+pub fn pending_update(spi: &mut SpiReader, key_store: &mut KeyStore, journal: &[BlockAddr])
+    -> Result<Option<Vec<(BlockAddr, [u8; 16])>>, Box<dyn Error>> {
+    let (record_block, slots) = journal.split_first().ok_or("The profile has no journal")?;
+    let dump = match read_blocks(spi, key_store, journal)? {
+        Some(dump) => dump,
+        None => return Ok(None),
+    };
+    let record = dump.block(*record_block)
+        .ok_or_else(|| format!("The journal (block {}) couldn't be read, none of the keys opens it", record_block))?;
+    let targets = match committed_blocks(&record) {
+        Some(targets) if targets.len() <= slots.len() => targets,
+        _ => return Ok(None),
+    };
+
+    let mut updates = Vec::new();
+    for (target, slot) in targets.iter().zip(slots) {
+        let data = dump.block(*slot)
+            .ok_or_else(|| format!("Journal block {} couldn't be read", slot))?;
+        updates.push((*target, data));
+    }
+    Ok(Some(updates))
+}
+
+// Finish an update the card left the reader during. Returns the blocks written, None
+// when the journal had nothing to finish.
+pub fn recover_journal(spi: &mut SpiReader, key_store: &mut KeyStore, journal: &[BlockAddr])
+    -> Result<Option<Vec<BlockAddr>>, Box<dyn Error>> {
+    let updates = match pending_update(spi, key_store, journal)? {
+        Some(updates) => updates,
+        None => return Ok(None),
+    };
+    if !finish(spi, key_store, journal[0], &updates)? {
+        return Err("An unfinished update is in the card's journal and couldn't be finished".into());
+    }
+    Ok(Some(updates.iter().map(|(block, _)| *block).collect()))
+}
+
+// Write the blocks and, once they read back, clear the commit record. False when the
+// update is still committed.
+fn finish(spi: &mut SpiReader, key_store: &mut KeyStore, record_block: BlockAddr, updates: &[(BlockAddr, [u8; 16])]) -> Result<bool, Box<dyn Error>> {
+    for (block, data) in updates {
+        if !write_block_with_store(spi, key_store, *block, data)? {
+            return Ok(false);
+        }
+    }
+    if !reads_back(spi, key_store, updates)? {
+        return Ok(false);
+    }
+    let idle = record(STATE_IDLE, &[]);
+    Ok(write_block_with_store(spi, key_store, record_block, &idle)? && reads_back(spi, key_store, &[(record_block, idle)])?)
+}
+
+// Whether the blocks read back as written, false when the card left the reader
+fn reads_back(spi: &mut SpiReader, key_store: &mut KeyStore, written: &[(BlockAddr, [u8; 16])]) -> Result<bool, Box<dyn Error>> {
+    let blocks: Vec<BlockAddr> = written.iter().map(|(block, _)| *block).collect();
+    Ok(read_blocks(spi, key_store, &blocks)?.is_some_and(|dump| {
+        written.iter().all(|(block, data)| dump.blocks.get(block.index()).is_some_and(|actual| block_matches(*block, data, actual)))
+    }))
+}
+
+// None when the card left the reader
+fn read_blocks(spi: &mut SpiReader, key_store: &mut KeyStore, blocks: &[BlockAddr]) -> Result<Option<CardDump>, Box<dyn Error>> {
+    // The sector of the last write is still authenticated
+    mfrc522_stop_crypto1(spi)?;
+    read_sectors(spi, key_store, &Selection::of_blocks(blocks))
+}
+
+fn commit_record(targets: &[BlockAddr]) -> [u8; 16] {
+    record(STATE_COMMITTED, targets)
+}
+
+fn record(state: u8, targets: &[BlockAddr]) -> [u8; 16] {
+    let mut record = [0u8; 16];
+    record[0] = RECORD_MAGIC;
+    record[1] = state;
+    record[2] = targets.len() as u8;
+    record[3..3 + targets.len()].iter_mut().zip(targets).for_each(|(byte, block)| *byte = block.get());
+    record[15] = record[..15].iter().fold(0, |check, byte| check ^ byte);
+    record
+}
+
+// The blocks a committed record is for
+fn committed_blocks(record: &[u8; 16]) -> Option<Vec<BlockAddr>> {
+    let count = record[2] as usize;
+    let valid = record[0] == RECORD_MAGIC
+        && record[1] == STATE_COMMITTED
+        && (1..=MAX_BLOCKS).contains(&count)
+        && record[..15].iter().fold(0, |check, byte| check ^ byte) == record[15];
+    let targets: Vec<BlockAddr> = record[3..3 + count.min(MAX_BLOCKS)].iter().map(|block| BlockAddr(*block)).collect();
+    // A committed update never writes block 0 or a trailer
+    (valid && targets.iter().all(|block| !block.is_manufacturer() && !block.is_trailer())).then_some(targets)
+}
@@ -0,0 +1,280 @@
+// Key files shared with the libnfc tools, the Proxmark3 client and MIFARE Classic Tool
+//
+// Several kinds of file carry keys:
+//   - key lists: text, one 12 hex digit key per line, '#' starts a comment. This is what
+//     mfoc -f reads, what the Proxmark dictionaries hold and what MCT exports as .keys
+//   - saved output of the Proxmark `hf mf chk` / `hf mf fchk` commands: the table of the
+//     keys found per sector
+//   - hf-mf-<UID>-key.bin from `hf mf chk --dump` or `hf mf autopwn`: every sector's
+//     Key A, then every sector's Key B, 6 bytes each
+//   - hf-mf-<UID>-dump.json from `hf mf dump`, or a JSON list of hex keys
+//   - CSV: every 12 hex digit cell is a key, a "sector" column says which sector
+//   - .mfd dumps from mfoc, mfcuk or nfc-mfclassic: the keys are in the sector trailers,
+//     which is how nfc-mfclassic takes its keys, so an old dump unlocks the card it came from
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use mifare_rc522::Key;
+
+use crate::lib::mifare::dump::load_dump_file;
+
+// Extensions of the dump files load_dump_file reads, their trailers hold the keys
+const DUMP_EXTENSIONS: [&str; 4] = ["mfd", "bin", "dump", "nfc"];
+
+// Sizes of the Proxmark key.bin of a Mini, 1K, 2K and 4K card (12 bytes per sector)
+const KEY_BIN_SIZES: [usize; 4] = [60, 192, 384, 480];
+
+// Places kept per key and file, a default key can be in every sector of a card
+const MAX_PLACES: usize = 4;
+
+// The kinds of key file import_key_file reads
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeyFileFormat {
+    KeyList,
+    ProxmarkCheck,
+    ProxmarkKeyBin,
+    Json,
+    Csv,
+    Dump,
+}
+
+impl KeyFileFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyFileFormat::KeyList => "key list",
+            KeyFileFormat::ProxmarkCheck => "Proxmark hf mf chk",
+            KeyFileFormat::ProxmarkKeyBin => "Proxmark key.bin",
+            KeyFileFormat::Json => "JSON",
+            KeyFileFormat::Csv => "CSV",
+            KeyFileFormat::Dump => "dump",
+        }
+    }
+}
+
+// A key in a key file and where in the file it was found ("line 4", "sector 2 key B")
+pub struct ImportedKey {
+    pub key: Key,
+    pub places: Vec<String>,
+}
+
+// Keys of one key file, duplicates merged, in file order
+pub struct KeyImport {
+    pub file: String,
+    pub format: KeyFileFormat,
+    pub keys: Vec<ImportedKey>,
+}
+
+impl KeyImport {
+    // Where the key came from, as recorded in the key store:
+    // "std.keys (key list): line 3" or "chk.log (Proxmark hf mf chk): sector 0 key A, ..."
+    pub fn source(&self, key: &ImportedKey) -> String {
+        let mut source = format!("{} ({})", self.file, self.format.name());
+        if !key.places.is_empty() {
+            source.push_str(": ");
+            source.push_str(&key.places.join(", "));
+        }
+        source
+    }
+
+    // Add a key, or another place for a key already in the file
+    fn add(&mut self, key: Key, place: String) {
+        match self.keys.iter_mut().find(|imported| imported.key == key) {
+            Some(imported) if imported.places.len() < MAX_PLACES => imported.places.push(place),
+            Some(imported) if imported.places.len() == MAX_PLACES => imported.places.push("...".to_string()),
+            Some(_) => {},
+            None => self.keys.push(ImportedKey { key, places: vec![place] }),
+        }
+    }
+}
+
+// Keys in any of the key files above, with where each was found
+pub fn import_key_file(path: &str) -> Result<KeyImport, Box<dyn Error>> {
+    let file = Path::new(path).file_name()
+        .map_or_else(|| path.to_string(), |name| name.to_string_lossy().into_owned());
+    let extension = Path::new(path).extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let data = fs::read(path)?;
+
+    let mut import = KeyImport { file, format: KeyFileFormat::KeyList, keys: Vec::new() };
+    if extension == "bin" && KEY_BIN_SIZES.contains(&data.len()) {
+        import.format = KeyFileFormat::ProxmarkKeyBin;
+        let sectors = data.len() / 12;
+        for (index, key) in data.chunks(6).enumerate() {
+            let key_type = if index < sectors { "A" } else { "B" };
+            import.add(Key(key.try_into()?), format!("sector {} key {}", index % sectors, key_type));
+        }
+    } else if DUMP_EXTENSIONS.contains(&extension.as_str()) {
+        import.format = KeyFileFormat::Dump;
+        let dump = load_dump_file(path)?;
+        for sector in dump.layout().sectors() {
+            let trailer = match dump.blocks.get(sector.trailer().index()) {
+                Some(trailer) => trailer,
+                None => continue,
+            };
+            for (key_type, key) in [("A", &trailer[0..6]), ("B", &trailer[10..16])] {
+                let key: Option<Vec<u8>> = key.iter().copied().collect();
+                if let Some(key) = key.and_then(|key| <[u8; 6]>::try_from(key.as_slice()).ok()) {
+                    import.add(Key(key), format!("sector {} key {}", sector, key_type));
+                }
+            }
+        }
+    } else {
+        let text = String::from_utf8(data).map_err(|_| format!("{} is neither a key file nor a dump", path))?;
+        // JSON is also recognised without the extension, Proxmark output lines start with "[+]" and aren't JSON
+        let json = serde_json::from_str::<Value>(&text).ok().filter(|json| json.is_object() || json.is_array());
+        let keys = if extension == "json" || json.is_some() {
+            import.format = KeyFileFormat::Json;
+            match json {
+                Some(json) => json_keys(&json),
+                None => json_keys(&serde_json::from_str(&text)?),
+            }
+        } else if extension == "csv" {
+            import.format = KeyFileFormat::Csv;
+            parse_csv_keys(&text)
+        } else {
+            match parse_check_output(&text) {
+                keys if !keys.is_empty() => {
+                    import.format = KeyFileFormat::ProxmarkCheck;
+                    keys
+                },
+                _ => parse_key_list(&text)?.into_iter()
+                    .map(|(number, key)| (key, format!("line {}", number)))
+                    .collect(),
+            }
+        };
+        for (key, place) in keys {
+            import.add(key, place);
+        }
+    }
+
+    if import.keys.is_empty() {
+        return Err(format!("No keys found in {}", path).into());
+    }
+    Ok(import)
+}
+
+// Keys in a key file or dump, duplicates removed, in file order
+pub fn load_key_file(path: &str) -> Result<Vec<Key>, Box<dyn Error>> {
+    Ok(import_key_file(path)?.keys.into_iter().map(|imported| imported.key).collect())
+}
+
+// Text key list, the key is the first 12 hex digits on each line, with its line number
+pub fn parse_key_list(text: &str) -> Result<Vec<(usize, Key)>, String> {
+    let mut keys = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let word = match line.split_whitespace().next() {
+            Some(word) => word,
+            None => continue,
+        };
+        let key = Key::from_hex(word)
+            .ok_or_else(|| format!("Line {}: '{}' is not a 12 digit hex key", number + 1, word))?;
+        keys.push((number + 1, key));
+    }
+    Ok(keys)
+}
+
+// Keys found by `hf mf chk` / `hf mf fchk`, from the table the client prints:
+//   [+]  000 | 003 | FFFFFFFFFFFF | 1 | A0A1A2A3A4A5 | 1
+// or, from older clients:
+//   |000|  ffffffffffff  | 1 |  ------------  | 0 |
+// The cell after each key says whether it was found, 0 (or a dashed key) means not.
+fn parse_check_output(text: &str) -> Vec<(Key, String)> {
+    let mut keys = Vec::new();
+    for line in text.lines().filter(|line| line.contains('|')) {
+        let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+        let sector = match cells.iter().find_map(|cell| cell.split_whitespace().last()?.parse::<u16>().ok()) {
+            Some(sector) => sector,
+            None => continue,
+        };
+        let key_columns = cells.iter()
+            .enumerate()
+            .filter(|(_, cell)| Key::from_hex(cell).is_some() || (cell.len() == 12 && cell.chars().all(|c| c == '-')));
+        for ((column, cell), key_type) in key_columns.zip(["A", "B"]) {
+            let found = cells.get(column + 1).is_some_and(|result| !result.is_empty() && *result != "0");
+            if let Some(key) = Key::from_hex(cell).filter(|_| found) {
+                keys.push((key, format!("sector {} key {}", sector, key_type)));
+            }
+        }
+    }
+    keys
+}
+
+// Keys of a Proxmark JSON dump ("SectorKeys"), or of a list of hex keys, on its own
+// or under "keys"
+fn json_keys(json: &Value) -> Vec<(Key, String)> {
+    let mut keys = Vec::new();
+
+    if let Some(sector_keys) = json.get("SectorKeys").and_then(Value::as_object) {
+        let mut sectors: Vec<(u32, &Value)> = sector_keys.iter()
+            .filter_map(|(sector, keys)| Some((sector.parse().ok()?, keys)))
+            .collect();
+        sectors.sort_by_key(|(sector, _)| *sector);
+        for (sector, sector_keys) in sectors {
+            for (field, key_type) in [("KeyA", "A"), ("KeyB", "B")] {
+                if let Some(key) = sector_keys.get(field).and_then(Value::as_str).and_then(Key::from_hex) {
+                    keys.push((key, format!("sector {} key {}", sector, key_type)));
+                }
+            }
+        }
+    }
+
+    let list = json.as_array().or_else(|| json.get("keys").and_then(Value::as_array));
+    for (index, entry) in list.into_iter().flatten().enumerate() {
+        if let Some(key) = entry.as_str().and_then(Key::from_hex) {
+            keys.push((key, format!("entry {}", index + 1)));
+        }
+    }
+    keys
+}
+
+// Every 12 hex digit cell of a CSV file is a key. A first row without keys names the
+// columns: a "sector" column gives the key's sector, the other names say which key it is.
+fn parse_csv_keys(text: &str) -> Vec<(Key, String)> {
+    let mut keys = Vec::new();
+    let mut header: Vec<String> = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let cells: Vec<&str> = line.split([',', ';', '\t'])
+            .map(|cell| cell.trim().trim_matches('"').trim())
+            .collect();
+        if !cells.iter().any(|cell| Key::from_hex(cell).is_some()) {
+            if header.is_empty() && number == 0 {
+                header = cells.iter().map(|cell| cell.to_lowercase()).collect();
+            }
+            continue;
+        }
+
+        let sector = header.iter()
+            .position(|name| name == "sector")
+            .and_then(|column| cells.get(column))
+            .filter(|sector| !sector.is_empty());
+        for (column, cell) in cells.iter().enumerate() {
+            if let Some(key) = Key::from_hex(cell) {
+                let mut place = match sector {
+                    Some(sector) => format!("sector {}", sector),
+                    None => format!("row {}", number + 1),
+                };
+                if let Some(name) = header.get(column).filter(|name| !name.is_empty() && *name != "key") {
+                    place.push(' ');
+                    place.push_str(name);
+                }
+                keys.push((key, place));
+            }
+        }
+    }
+    keys
+}
+
+// Save keys as a text key list that mfoc -f and the Proxmark3 client read
+pub fn save_key_file(path: &str, keys: &[Key]) -> Result<(), Box<dyn Error>> {
+    let mut text = String::from("# Mifare Classic keys, one per line\n");
+    for key in keys {
+        text.push_str(&key.to_string());
+        text.push('\n');
+    }
+    fs::write(path, text)?;
+    Ok(())
+}
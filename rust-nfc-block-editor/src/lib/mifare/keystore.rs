@@ -0,0 +1,190 @@
+// Keys to try on a card, and which key opened each sector last time
+//
+// Cards almost always use the same key for a sector from one operation to the next,
+// so trying the key that worked last first means most sectors authenticate on the
+// first attempt. Every failed attempt costs a re-select of the card.
+//
+// Keys imported from key files are saved with the files they came from, so the store
+// keeps them between runs and can say where each key was found.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use mifare_rc522::{Key, SectorIndex};
+
+use crate::lib::mfrc522::{PICC_AUTHENT1A, PICC_AUTHENT1B};
+use crate::lib::mifare::keyfile::KeyImport;
+use crate::lib::mifare::operations::DEFAULT_KEYS;
+
+// A key and the key type (PICC_AUTHENT1A or PICC_AUTHENT1B) it authenticates as
+#[derive(Clone, Copy, PartialEq)]
+pub struct SectorKey {
+    pub auth_type: u8,
+    pub key: Key,
+}
+
+pub struct KeyStore {
+    keys: Vec<Key>,
+    // Indexed by sector, grows as sectors are opened
+    sector_keys: Vec<Option<SectorKey>>,
+    // Imported keys and the sources they were found in, in import order
+    sources: Vec<(Key, Vec<String>)>,
+}
+
+impl Default for KeyStore {
+    fn default() -> Self {
+        KeyStore::new(DEFAULT_KEYS.to_vec())
+    }
+}
+
+impl KeyStore {
+    pub fn new(keys: Vec<Key>) -> Self {
+        let mut store = KeyStore { keys: Vec::new(), sector_keys: Vec::new(), sources: Vec::new() };
+        store.add_keys(&keys);
+        store
+    }
+
+    // ~/.config/nfc-block-editor/keys.txt (or $XDG_CONFIG_HOME)
+    pub fn default_path() -> PathBuf {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .unwrap_or_else(|| PathBuf::from("."));
+        base.join("nfc-block-editor").join("keys.txt")
+    }
+
+    // The default keys and the keys imported before, kept in the file
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let mut store = KeyStore::default();
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(store),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+            let (key, sources) = line.split_once('#').unwrap_or((line, ""));
+            let key = Key::from_hex(key.trim()).ok_or_else(|| format!("Invalid line '{}'", line))?;
+            for source in sources.split("; ").map(str::trim).filter(|source| !source.is_empty()) {
+                store.add_source(key, source.to_string());
+            }
+        }
+        Ok(store)
+    }
+
+    // Imported keys with their sources as comments, a key list mfoc -f can read too
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+        let mut text = String::from("# Keys imported into the key store, each followed by the files it was found in\n");
+        for (key, sources) in &self.sources {
+            text.push_str(&format!("{} # {}\n", key, sources.join("; ")));
+        }
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    // Add the keys of a key file with where they were found. Returns how many keys were
+    // new and how many the store had already; those get the file added to their sources.
+    pub fn import(&mut self, import: &KeyImport) -> (usize, usize) {
+        let mut added = 0;
+        for imported in &import.keys {
+            if !self.keys.contains(&imported.key) {
+                added += 1;
+            }
+            self.add_source(imported.key, import.source(imported));
+        }
+        (added, import.keys.len() - added)
+    }
+
+    fn add_source(&mut self, key: Key, source: String) {
+        self.add_keys(&[key]);
+        match self.sources.iter_mut().find(|(known, _)| *known == key) {
+            Some((_, sources)) if !sources.contains(&source) => sources.push(source),
+            Some(_) => {},
+            None => self.sources.push((key, vec![source])),
+        }
+    }
+
+    // Every key in the order they are tried
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+
+    // Files the key was imported from, empty for the default keys and keys found this run
+    pub fn sources(&self, key: &Key) -> &[String] {
+        self.sources.iter()
+            .find(|(known, _)| known == key)
+            .map_or(&[], |(_, sources)| sources.as_slice())
+    }
+
+    // Add keys to try, duplicates are skipped
+    pub fn add_keys(&mut self, keys: &[Key]) {
+        for key in keys {
+            if !self.keys.contains(key) {
+                self.keys.push(*key);
+            }
+        }
+    }
+
+    // Key that last opened the sector
+    pub fn sector_key(&self, sector: SectorIndex) -> Option<SectorKey> {
+        self.sector_keys.get(sector.index()).copied().flatten()
+    }
+
+    pub fn remember(&mut self, sector: SectorIndex, sector_key: SectorKey) {
+        let index = sector.index();
+        if self.sector_keys.len() <= index {
+            self.sector_keys.resize(index + 1, None);
+        }
+        self.sector_keys[index] = Some(sector_key);
+        self.add_keys(&[sector_key.key]);
+    }
+
+    // Keys a card profile expects on the sector. The first is tried first unless another
+    // key already opened the sector.
+    pub fn expect(&mut self, sector: SectorIndex, expected: &[SectorKey]) {
+        for sector_key in expected {
+            self.add_keys(&[sector_key.key]);
+        }
+        if let (None, Some(first)) = (self.sector_key(sector), expected.first()) {
+            self.remember(sector, *first);
+        }
+    }
+
+    // Record a key that opened the sector where the key type isn't known. A key that
+    // already opened the sector keeps its type, anything else is taken to be Key A,
+    // the type write_block_raw tries first.
+    pub fn remember_key(&mut self, sector: SectorIndex, key: Key) {
+        if self.sector_key(sector).map(|known| known.key) != Some(key) {
+            self.remember(sector, SectorKey { auth_type: PICC_AUTHENT1A, key });
+        }
+    }
+
+    // Keys to try on a sector in order: the one that last worked, then every key as
+    // Key A, then every key as Key B
+    pub fn candidates(&self, sector: SectorIndex) -> Vec<SectorKey> {
+        let last = self.sector_key(sector);
+        let mut candidates: Vec<SectorKey> = last.into_iter().collect();
+        for auth_type in [PICC_AUTHENT1A, PICC_AUTHENT1B] {
+            for key in &self.keys {
+                let candidate = SectorKey { auth_type, key: *key };
+                if Some(candidate) != last {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }
+
+    // Same order without the key type, for operations that try both types themselves
+    pub fn candidate_keys(&self, sector: SectorIndex) -> Vec<Key> {
+        let mut keys: Vec<Key> = Vec::new();
+        for candidate in self.candidates(sector) {
+            if !keys.contains(&candidate.key) {
+                keys.push(candidate.key);
+            }
+        }
+        keys
+    }
+}
@@ -1,25 +1,28 @@
 use std::error::Error;
-use rppal::spi::Spi;
+use mifare_rc522::{BlockAddr, Key, SectorIndex};
 
 use crate::lib::mfrc522::{
     mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, 
-    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read, mfrc522_write,
-    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
+    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK, SpiReader
 };
 
 use crate::lib::utils::{bytes_to_hex, bytes_to_ascii, uid_to_string};
 use crate::lib::mifare::access::AccessBits;
+use crate::lib::mifare::backup::guarded_write;
+use crate::lib::mifare::presence::{wait_for_removal, PresenceTracker};
+use crate::lib::i18n::{tr, tr_args};
 
 // Common authentication keys to try
-pub const DEFAULT_KEYS: [[u8; 6]; 4] = [
-    [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],  // Default key
-    [0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5],  // Common key
-    [0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7],  // Common key
-    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],  // All zeroes
+pub const DEFAULT_KEYS: [Key; 4] = [
+    Key([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]),  // Default key
+    Key([0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]),  // Common key
+    Key([0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7]),  // Common key
+    Key([0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),  // All zeroes
 ];
 
 // Read card UID
-pub fn read_card_uid(spi: &mut Spi) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+pub fn read_card_uid(spi: &mut SpiReader) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
@@ -36,23 +39,18 @@ pub fn read_card_uid(spi: &mut Spi) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
 }
 
 // Wait for a card to be removed
-pub fn wait_for_card_removal(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
-    println!("Waiting for card to be removed...");
+pub fn wait_for_card_removal(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
+    println!("{}", tr("waiting-for-card-to-be"));
     
-    while read_card_uid(spi)?.is_some() {
-        std::thread::sleep(std::time::Duration::from_millis(100));
-    }
+    // Debounced, so a single missed poll doesn't count as a removal
+    wait_for_removal(spi, &mut PresenceTracker::default())?;
     
-    println!("Card removed");
+    println!("{}", tr("card-removed"));
     Ok(())
 }
 
-// Read all blocks in a sector
-pub fn read_sector_data(spi: &mut Spi, sector: u8) -> Result<Option<(Vec<u8>, [Option<Vec<u8>>; 4])>, Box<dyn Error>> {
-    if sector >= 16 {
-        return Err("Invalid sector number".into());
-    }
-    
+// Read all blocks in a sector (4 blocks, or 16 in the upper sectors of a 4K)
+pub fn read_sector_data(spi: &mut SpiReader, sector: SectorIndex) -> Result<Option<(Vec<u8>, Vec<Option<Vec<u8>>>)>, Box<dyn Error>> {
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
@@ -78,8 +76,8 @@ pub fn read_sector_data(spi: &mut Spi, sector: u8) -> Result<Option<(Vec<u8>, [O
     
     // First try with Key A
     for key in &DEFAULT_KEYS {
-        let trailer_block = sector * 4 + 3;
-        let status = mfrc522_auth(spi, PICC_AUTHENT1A, trailer_block, key, &uid)?;
+        let trailer_block = sector.trailer();
+        let status = mfrc522_auth(spi, PICC_AUTHENT1A, trailer_block.get(), key.as_bytes(), &uid)?;
         if status == MI_OK {
             authenticated = true;
             auth_key_type = PICC_AUTHENT1A;
@@ -91,8 +89,8 @@ pub fn read_sector_data(spi: &mut Spi, sector: u8) -> Result<Option<(Vec<u8>, [O
     // If Key A fails, try Key B
     if !authenticated {
         for key in &DEFAULT_KEYS {
-            let trailer_block = sector * 4 + 3;
-            let status = mfrc522_auth(spi, PICC_AUTHENT1B, trailer_block, key, &uid)?;
+            let trailer_block = sector.trailer();
+            let status = mfrc522_auth(spi, PICC_AUTHENT1B, trailer_block.get(), key.as_bytes(), &uid)?;
             if status == MI_OK {
                 authenticated = true;
                 auth_key_type = PICC_AUTHENT1B;
@@ -103,27 +101,27 @@ pub fn read_sector_data(spi: &mut Spi, sector: u8) -> Result<Option<(Vec<u8>, [O
     }
     
     if !authenticated {
-        println!("Failed to authenticate sector {}. Try with custom keys.", sector);
+        println!("{}", tr_args("err-authenticate-sector-try-with-custom", &[("sector", &sector.to_string())]));
         mfrc522_stop_crypto1(spi)?;
         return Ok(None);
     }
     
     // Read data from blocks
-    let mut blocks: [Option<Vec<u8>>; 4] = [None, None, None, None];
+    let mut blocks: Vec<Option<Vec<u8>>> = Vec::new();
     
-    for block_offset in 0..4 {
-        let block_addr = sector * 4 + block_offset;
+    for block_addr in sector.blocks() {
+        blocks.push(None);
         
         // Re-authenticate for each block if needed
-        if block_offset > 0 {
-            let status = mfrc522_auth(spi, auth_key_type, block_addr, auth_key, &uid)?;
+        if block_addr > sector.first_block() {
+            let status = mfrc522_auth(spi, auth_key_type, block_addr.get(), auth_key.as_bytes(), &uid)?;
             if status != MI_OK {
                 continue;  // Skip this block if authentication fails
             }
         }
         
-        if let Some(block_data) = mfrc522_read(spi, block_addr)? {
-            blocks[block_offset as usize] = Some(block_data);
+        if let Some(block_data) = mfrc522_read(spi, block_addr.get())? {
+            blocks[block_addr.offset() as usize] = Some(block_data);
         }
     }
     
@@ -133,9 +131,9 @@ pub fn read_sector_data(spi: &mut Spi, sector: u8) -> Result<Option<(Vec<u8>, [O
 }
 
 // Write data to a specific block
-pub fn write_block_data(spi: &mut Spi, block_addr: u8, text: &str) -> Result<Option<(Vec<u8>, String)>, Box<dyn Error>> {
-    let sector = block_addr / 4;
-    let is_trailer = block_addr % 4 == 3;
+pub fn write_block_data(spi: &mut SpiReader, block_addr: BlockAddr, text: &str) -> Result<Option<(Vec<u8>, String)>, Box<dyn Error>> {
+    let sector = block_addr.sector();
+    let is_trailer = block_addr.is_trailer();
     
     if is_trailer {
         return Err("Cannot write to sector trailer using this function".into());
@@ -165,7 +163,7 @@ pub fn write_block_data(spi: &mut Spi, block_addr: u8, text: &str) -> Result<Opt
     // For write access, always try Key B first, then Key A
     for &auth_type in &[PICC_AUTHENT1B, PICC_AUTHENT1A] {
         for &key in &DEFAULT_KEYS {
-            let status = mfrc522_auth(spi, auth_type, sector * 4 + 3, &key, &uid)?;
+            let status = mfrc522_auth(spi, auth_type, sector.trailer().get(), key.as_bytes(), &uid)?;
             if status == MI_OK {
                 authenticated = true;
                 break;
@@ -186,7 +184,7 @@ pub fn write_block_data(spi: &mut Spi, block_addr: u8, text: &str) -> Result<Opt
     data.resize(16, 0); // Pad with zeros
     
     // Write data to the block
-    if mfrc522_write(spi, block_addr, &data)? != MI_OK {
+    if guarded_write(spi, block_addr, &data)? != MI_OK {
         mfrc522_stop_crypto1(spi)?;
         return Ok(None);
     }
@@ -200,13 +198,12 @@ pub fn write_block_data(spi: &mut Spi, block_addr: u8, text: &str) -> Result<Opt
 }
 
 // Write data to a specific block with a provided key
-pub fn write_block_raw(spi: &mut Spi, block_addr: u8, key: &[u8], data: &[u8]) -> Result<bool, Box<dyn Error>> {
-    if key.len() != 6 || data.len() != 16 {
-        return Err("Invalid key or data length".into());
+pub fn write_block_raw(spi: &mut SpiReader, block_addr: BlockAddr, key: &Key, data: &[u8]) -> Result<bool, Box<dyn Error>> {
+    if data.len() != 16 {
+        return Err("Invalid data length".into());
     }
     
-    let sector = block_addr / 4;
-    let trailer_block = sector * 4 + 3;
+    let trailer_block = block_addr.sector().trailer();
     
     // Request tag
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
@@ -230,7 +227,7 @@ pub fn write_block_raw(spi: &mut Spi, block_addr: u8, key: &[u8], data: &[u8]) -
     let mut authenticated = false;
     
     for &auth_type in &[PICC_AUTHENT1A, PICC_AUTHENT1B] {
-        let status = mfrc522_auth(spi, auth_type, trailer_block, key, &uid)?;
+        let status = mfrc522_auth(spi, auth_type, trailer_block.get(), key.as_bytes(), &uid)?;
         if status == MI_OK {
             authenticated = true;
             break;
@@ -243,7 +240,7 @@ pub fn write_block_raw(spi: &mut Spi, block_addr: u8, key: &[u8], data: &[u8]) -
     }
     
     // Write data to the block
-    let result = mfrc522_write(spi, block_addr, data)? == MI_OK;
+    let result = guarded_write(spi, block_addr, data)? == MI_OK;
     
     mfrc522_stop_crypto1(spi)?;
     
@@ -0,0 +1,132 @@
+// Card presence tracking for continuous scanning
+//
+// A card held on the reader answers every poll, so a plain polling loop reports it
+// over and over, and a single poll can miss a card that is still there (the edge of
+// the antenna field, a bad anticollision frame). The tracker turns polls into events:
+//   - NewCard: a card arrived, reported once
+//   - StillPresent: the reported card is still on the reader
+//   - Removed: the reported card was missing for `removal_misses` polls in a row
+//   - NoCard: nothing on the reader and nothing reported
+//
+// Reported cards are put in HALT, so they stop answering REQIDL and only the WUPA
+// (PICC_REQALL) presence check sees them. A card that leaves the field and comes back
+// within the removal window is still the same card, not a new scan.
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+use crate::lib::mfrc522::{
+    mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, mfrc522_halt,
+    PICC_REQIDL, PICC_REQALL, MI_OK, SpiReader
+};
+
+// Missed polls in a row before a card counts as removed
+pub const REMOVAL_MISSES: u32 = 3;
+// Time between polls in scan loops
+pub const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PresenceEvent {
+    NewCard(Vec<u8>),
+    StillPresent,
+    Removed(Vec<u8>),
+    NoCard,
+}
+
+pub struct PresenceTracker {
+    current: Option<Vec<u8>>,
+    misses: u32,
+    removal_misses: u32,
+}
+
+impl Default for PresenceTracker {
+    fn default() -> Self {
+        PresenceTracker::new(REMOVAL_MISSES)
+    }
+}
+
+impl PresenceTracker {
+    pub fn new(removal_misses: u32) -> Self {
+        PresenceTracker {
+            current: None,
+            misses: 0,
+            removal_misses: removal_misses.max(1),
+        }
+    }
+
+    // UID of the card on the reader, once reported
+    pub fn current(&self) -> Option<&[u8]> {
+        self.current.as_deref()
+    }
+
+    // Poll the reader once and update the state
+    pub fn poll(&mut self, spi: &mut SpiReader) -> Result<PresenceEvent, Box<dyn Error>> {
+        // A card that isn't halted answers REQIDL: a new card, or the current one back
+        // in the field. The halted current card only answers a wake-up.
+        let mut seen = detect_card(spi, PICC_REQIDL)?;
+        if seen.is_none() && self.current.is_some() {
+            seen = detect_card(spi, PICC_REQALL)?;
+        }
+        Ok(self.update(seen))
+    }
+
+    // State machine step for the card seen by one poll, if any. A different card
+    // replacing the current one is reported as NewCard without a Removed first.
+    pub fn update(&mut self, seen: Option<Vec<u8>>) -> PresenceEvent {
+        match seen {
+            Some(uid) if self.current.as_ref() == Some(&uid) => {
+                self.misses = 0;
+                PresenceEvent::StillPresent
+            },
+            Some(uid) => {
+                self.misses = 0;
+                self.current = Some(uid.clone());
+                PresenceEvent::NewCard(uid)
+            },
+            None => match self.current.take() {
+                Some(uid) => {
+                    self.misses += 1;
+                    if self.misses >= self.removal_misses {
+                        self.misses = 0;
+                        PresenceEvent::Removed(uid)
+                    } else {
+                        self.current = Some(uid);
+                        PresenceEvent::StillPresent
+                    }
+                },
+                None => PresenceEvent::NoCard,
+            },
+        }
+    }
+}
+
+// Request, anticollision and select with `req_mode`, then halt the card. Returns
+// the 4 byte UID.
+fn detect_card(spi: &mut SpiReader, req_mode: u8) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let (status, _) = mfrc522_request(spi, req_mode)?;
+    if status != MI_OK {
+        return Ok(None);
+    }
+
+    let (status, uid) = mfrc522_anticoll(spi)?;
+    if status != MI_OK {
+        return Ok(None);
+    }
+
+    // HALT is only accepted from a selected card
+    if mfrc522_select_tag(spi, &uid)? != 0 {
+        mfrc522_halt(spi)?;
+    }
+
+    Ok(Some(uid[..4].to_vec()))
+}
+
+// Poll until the tracked card has been gone for the removal window
+pub fn wait_for_removal(spi: &mut SpiReader, tracker: &mut PresenceTracker) -> Result<(), Box<dyn Error>> {
+    loop {
+        match tracker.poll(spi)? {
+            PresenceEvent::Removed(_) | PresenceEvent::NoCard => return Ok(()),
+            PresenceEvent::NewCard(_) | PresenceEvent::StillPresent => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
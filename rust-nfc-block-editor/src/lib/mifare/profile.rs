@@ -0,0 +1,351 @@
+// Known card layouts: which sectors hold what and which keys open them
+//
+// A profile is a text file with one setting per line, '#' starts a comment:
+//   name Hotel key card
+//   kind hotel                        hotel, transit, access or other
+//   description Room keys written by the front desk
+//   sector 1 Stay data                what a sector holds
+//   block 4 Room number               what a block holds
+//   key 1 A A0A1A2A3A4A5              a key for sectors: one sector, a range (2-5) or *
+//   key 2-3 B uid-xor 5A5A5A5A5A5A    a key derived from the UID: the UID repeated to
+//                                     6 bytes and XORed with the constant
+//   read 1-2 Read the stay            a guided read of some sectors
+//   write 4 Change the room number    a guided write of one data block
+//   journal 16-21                     blocks for the journal multi-block updates go
+//                                     through (see journal.rs), trailers left out
+//   balance Top up                    a guided change of the value blocks, made
+//                                     through the journal
+// Balances and transaction records are decoded with the currency, value, record and
+// field settings described in decode.rs.
+//
+// The hotel, transit and access profiles come with the editor. Profiles saved in
+// ~/.config/nfc-block-editor/profiles (*.profile) are added to them, and replace a
+// built-in profile with the same name.
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use mifare_rc522::{BlockAddr, CardLayout, Key, SectorIndex};
+
+use crate::lib::mfrc522::{PICC_AUTHENT1A, PICC_AUTHENT1B};
+use crate::lib::mifare::decode::{Currency, Field, FieldType, RecordLayout};
+use crate::lib::mifare::keystore::{KeyStore, SectorKey};
+
+// The profiles built into the editor
+const BUILT_IN: [(&str, &str); 3] = [
+    ("hotel.profile", include_str!("../../../profiles/hotel.profile")),
+    ("transit.profile", include_str!("../../../profiles/transit.profile")),
+    ("access.profile", include_str!("../../../profiles/access.profile")),
+];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CardKind {
+    Hotel,
+    Transit,
+    Access,
+    Other,
+}
+
+impl CardKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            CardKind::Hotel => "hotel",
+            CardKind::Transit => "transit",
+            CardKind::Access => "access",
+            CardKind::Other => "other",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [CardKind::Hotel, CardKind::Transit, CardKind::Access, CardKind::Other]
+            .into_iter()
+            .find(|kind| kind.name() == name)
+    }
+}
+
+// How a profile key is made
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum KeySpec {
+    Fixed(Key),
+    // The UID repeated to 6 bytes, XORed with the constant
+    UidXor(Key),
+}
+
+impl KeySpec {
+    pub fn key_for(self, uid: &[u8]) -> Option<Key> {
+        match self {
+            KeySpec::Fixed(key) => Some(key),
+            KeySpec::UidXor(_) if uid.is_empty() => None,
+            KeySpec::UidXor(Key(mut key)) => {
+                for (byte, uid_byte) in key.iter_mut().zip(uid.iter().cycle()) {
+                    *byte ^= uid_byte;
+                }
+                Some(Key(key))
+            },
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            KeySpec::Fixed(key) => key.to_string(),
+            KeySpec::UidXor(constant) => format!("UID XOR {}", constant),
+        }
+    }
+}
+
+// A key the profile expects on some sectors, as Key A (PICC_AUTHENT1A) or Key B
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProfileKey {
+    pub sectors: Vec<SectorIndex>,
+    pub auth_type: u8,
+    pub spec: KeySpec,
+}
+
+// A guided step offered for the profile
+#[derive(Clone, PartialEq, Debug)]
+pub enum Workflow {
+    Read { sectors: Vec<SectorIndex>, title: String },
+    Write { block: BlockAddr, title: String },
+    // The value blocks together, through the journal
+    Balance { title: String },
+}
+
+impl Workflow {
+    pub fn title(&self) -> &str {
+        match self {
+            Workflow::Read { title, .. } | Workflow::Write { title, .. } | Workflow::Balance { title } => title,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct CardProfile {
+    pub name: String,
+    pub kind: CardKind,
+    pub description: String,
+    pub sectors: Vec<(SectorIndex, String)>,
+    pub blocks: Vec<(BlockAddr, String)>,
+    pub keys: Vec<ProfileKey>,
+    pub workflows: Vec<Workflow>,
+    pub currency: Option<Currency>,
+    // Value blocks holding amounts
+    pub values: Vec<(BlockAddr, String)>,
+    pub records: Vec<RecordLayout>,
+    // Commit record then data blocks, empty without a journal
+    pub journal: Vec<BlockAddr>,
+    // "built-in" or the file it was loaded from
+    pub source: String,
+}
+
+impl CardProfile {
+    pub fn parse(text: &str, source: &str) -> Result<Self, Box<dyn Error>> {
+        let mut profile = CardProfile {
+            name: String::new(),
+            kind: CardKind::Other,
+            description: String::new(),
+            sectors: Vec::new(),
+            blocks: Vec::new(),
+            keys: Vec::new(),
+            workflows: Vec::new(),
+            currency: None,
+            values: Vec::new(),
+            records: Vec::new(),
+            journal: Vec::new(),
+            source: source.to_string(),
+        };
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || format!("Line {}: invalid setting '{}'", number + 1, line);
+            let (setting, value) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            let value = value.trim();
+            // Settings about sectors or blocks start with the numbers, the rest is the text
+            let (numbers, text) = value.split_once(char::is_whitespace)
+                .map_or((value, ""), |(numbers, text)| (numbers, text.trim()));
+
+            match setting {
+                "name" => profile.name = value.to_string(),
+                "kind" => profile.kind = CardKind::from_name(value).ok_or_else(invalid)?,
+                "description" => {
+                    if !profile.description.is_empty() {
+                        profile.description.push(' ');
+                    }
+                    profile.description.push_str(value);
+                },
+                "sector" => {
+                    for sector in parse_sectors(numbers).ok_or_else(invalid)? {
+                        profile.sectors.push((sector, text.to_string()));
+                    }
+                },
+                "block" => {
+                    let block = numbers.parse::<u8>().map(BlockAddr).map_err(|_| invalid())?;
+                    profile.blocks.push((block, text.to_string()));
+                },
+                "key" => profile.keys.push(parse_key(numbers, text).ok_or_else(invalid)?),
+                "read" => {
+                    let sectors = parse_sectors(numbers).ok_or_else(invalid)?;
+                    profile.workflows.push(Workflow::Read { sectors, title: text.to_string() });
+                },
+                "write" => {
+                    let block = numbers.parse::<u8>().map(BlockAddr).map_err(|_| invalid())?;
+                    // Guided writes only change data, block 0 and trailers need the Write Block menu
+                    if block.is_manufacturer() || block.is_trailer() {
+                        return Err(format!("Line {}: block {} can't be written by a profile", number + 1, block).into());
+                    }
+                    profile.workflows.push(Workflow::Write { block, title: text.to_string() });
+                },
+                "balance" => profile.workflows.push(Workflow::Balance { title: value.to_string() }),
+                "journal" => {
+                    let blocks = parse_data_blocks(numbers).ok_or_else(invalid)?;
+                    // The commit record and at least one data block
+                    if blocks.first().is_none_or(|first| first.is_manufacturer()) || blocks.len() < 2 {
+                        return Err(format!("Line {}: a journal needs 2 blocks besides block 0 and trailers", number + 1).into());
+                    }
+                    profile.journal = blocks;
+                },
+                "currency" => {
+                    let scale = numbers.parse::<f64>().ok().filter(|scale| *scale > 0.0).ok_or_else(invalid)?;
+                    profile.currency = Some(Currency { scale, unit: text.to_string() });
+                },
+                "value" => {
+                    let block = numbers.parse::<u8>().map(BlockAddr).map_err(|_| invalid())?;
+                    profile.values.push((block, text.to_string()));
+                },
+                "record" => {
+                    let blocks = parse_data_blocks(numbers).ok_or_else(invalid)?;
+                    profile.records.push(RecordLayout { blocks, name: text.to_string(), fields: Vec::new() });
+                },
+                // A field belongs to the record above it
+                "field" => {
+                    let words: Vec<&str> = value.split_whitespace().collect();
+                    let field = match words[..] {
+                        [offset, length, kind, ref name @ ..] if !name.is_empty() => Field {
+                            offset: offset.parse().ok().filter(|offset| *offset < 16).ok_or_else(invalid)?,
+                            length: length.parse().ok().filter(|length| (1..=8).contains(length)).ok_or_else(invalid)?,
+                            kind: FieldType::from_name(kind).ok_or_else(invalid)?,
+                            name: name.join(" "),
+                        },
+                        _ => return Err(invalid().into()),
+                    };
+                    let record = profile.records.last_mut()
+                        .ok_or_else(|| format!("Line {}: a field needs a record above it", number + 1))?;
+                    record.fields.push(field);
+                },
+                _ => return Err(invalid().into()),
+            }
+        }
+
+        if profile.name.is_empty() {
+            return Err("The profile has no name".into());
+        }
+        let balance = profile.workflows.iter().any(|workflow| matches!(workflow, Workflow::Balance { .. }));
+        if balance && (profile.journal.len() <= profile.values.len() || profile.values.is_empty()) {
+            return Err("A balance change needs value blocks and a journal with a block for each".into());
+        }
+        if let Some((block, _)) = profile.values.iter().find(|(block, _)| profile.journal.contains(block)) {
+            return Err(format!("Block {} is both a value block and part of the journal", block).into());
+        }
+        Ok(profile)
+    }
+
+    pub fn sector_label(&self, sector: SectorIndex) -> Option<&str> {
+        self.sectors.iter().find(|(known, _)| *known == sector).map(|(_, label)| label.as_str())
+    }
+
+    pub fn block_label(&self, block: BlockAddr) -> Option<&str> {
+        self.blocks.iter().find(|(known, _)| *known == block).map(|(_, label)| label.as_str())
+    }
+
+    // Keys the profile expects on the sector of the card with this UID, in file order
+    pub fn keys_for(&self, sector: SectorIndex, uid: &[u8]) -> Vec<SectorKey> {
+        self.keys.iter()
+            .filter(|profile_key| profile_key.sectors.contains(&sector))
+            .filter_map(|profile_key| Some(SectorKey { auth_type: profile_key.auth_type, key: profile_key.spec.key_for(uid)? }))
+            .collect()
+    }
+
+    // Put the keys the profile expects on the card with this UID into the key store
+    pub fn expect_keys(&self, key_store: &mut KeyStore, uid: &[u8]) {
+        for sector in CardLayout::Classic4K.sectors() {
+            key_store.expect(sector, &self.keys_for(sector, uid));
+        }
+    }
+}
+
+// "3", "2-5" or "*" (every sector of a Classic 4K)
+fn parse_sectors(text: &str) -> Option<Vec<SectorIndex>> {
+    if text == "*" {
+        return Some(CardLayout::Classic4K.sectors().collect());
+    }
+    let (first, last) = text.split_once('-').unwrap_or((text, text));
+    let (first, last) = (first.parse::<u8>().ok()?, last.parse::<u8>().ok()?);
+    (first <= last).then(|| (first..=last).map(SectorIndex::new).collect()).flatten()
+}
+
+// "16" or "16-18", the trailers in the range left out
+fn parse_data_blocks(text: &str) -> Option<Vec<BlockAddr>> {
+    let (first, last) = text.split_once('-').unwrap_or((text, text));
+    let (first, last) = (first.parse::<u8>().ok()?, last.parse::<u8>().ok()?);
+    Some((first..=last).map(BlockAddr).filter(|block| !block.is_trailer()).collect())
+}
+
+// "<sectors> A|B <hex>" or "<sectors> A|B uid-xor <hex>", the sectors already split off
+fn parse_key(sectors: &str, rest: &str) -> Option<ProfileKey> {
+    let words: Vec<&str> = rest.split_whitespace().collect();
+    let auth_type = match words.first()?.to_uppercase().as_str() {
+        "A" => PICC_AUTHENT1A,
+        "B" => PICC_AUTHENT1B,
+        _ => return None,
+    };
+    let spec = match words[1..] {
+        [key] => KeySpec::Fixed(Key::from_hex(key)?),
+        ["uid-xor", constant] => KeySpec::UidXor(Key::from_hex(constant)?),
+        _ => return None,
+    };
+    Some(ProfileKey { sectors: parse_sectors(sectors)?, auth_type, spec })
+}
+
+// ~/.config/nfc-block-editor/profiles (or $XDG_CONFIG_HOME)
+pub fn profile_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("profiles")
+}
+
+// The built-in profiles and the ones in profile_dir, by name. A profile file that
+// can't be read is left out and reported in the second list.
+pub fn load_profiles() -> (Vec<CardProfile>, Vec<String>) {
+    let mut profiles = Vec::new();
+    let mut problems = Vec::new();
+    for (file, text) in BUILT_IN {
+        match CardProfile::parse(text, "built-in") {
+            Ok(profile) => profiles.push(profile),
+            Err(e) => problems.push(format!("{}: {}", file, e)),
+        }
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(profile_dir())
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "profile"));
+    paths.sort();
+    for path in paths {
+        let loaded = fs::read_to_string(&path)
+            .map_err(|e| e.into())
+            .and_then(|text| CardProfile::parse(&text, &path.display().to_string()));
+        match loaded {
+            Ok(profile) => {
+                profiles.retain(|known: &CardProfile| !known.name.eq_ignore_ascii_case(&profile.name));
+                profiles.push(profile);
+            },
+            Err(e) => problems.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    (profiles, problems)
+}
@@ -0,0 +1,136 @@
+// The part of a card an operation works on
+//
+// Dumps, formats, dump writes and key tests go through every sector unless they are
+// given a selection, which on a 4K card or for a single application sector is most of
+// the time spent. Sectors and blocks are given as lists of numbers and ranges:
+//   1-3,7        sectors 1, 2, 3 and 7
+//   * or empty   every sector (or every block of the selected sectors)
+// Blocks narrow the selected sectors down further, e.g. blocks 4-6 for the data of
+// sector 1 without its trailer.
+//
+// --sectors and --blocks on the command line set the selection the menus start with.
+use std::sync::OnceLock;
+
+use mifare_rc522::{BlockAddr, CardLayout, SectorIndex};
+
+use crate::lib::i18n::{tr, tr_args};
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Selection {
+    // None for every sector
+    sectors: Option<Vec<u8>>,
+    // None for every block of the selected sectors
+    blocks: Option<Vec<u8>>,
+}
+
+static FROM_ARGS: OnceLock<Result<Selection, String>> = OnceLock::new();
+
+impl Selection {
+    // The whole card
+    pub fn all() -> Self {
+        Selection::default()
+    }
+
+    // Just these blocks, in whichever sectors they are
+    pub fn of_blocks(blocks: &[BlockAddr]) -> Self {
+        let mut blocks: Vec<u8> = blocks.iter().map(|block| block.get()).collect();
+        blocks.sort();
+        blocks.dedup();
+        Selection { sectors: None, blocks: Some(blocks) }
+    }
+
+    pub fn parse(sectors: &str, blocks: &str) -> Result<Self, String> {
+        let sectors = parse_numbers(sectors, CardLayout::Classic4K.sector_count() as u16)
+            .map_err(|e| format!("Invalid sectors: {}", e))?;
+        let blocks = parse_numbers(blocks, CardLayout::Classic4K.block_count() as u16)
+            .map_err(|e| format!("Invalid blocks: {}", e))?;
+        Ok(Selection { sectors, blocks })
+    }
+
+    // The selection given with --sectors and --blocks, the whole card without them
+    pub fn from_args() -> Result<Self, String> {
+        FROM_ARGS.get_or_init(|| {
+            let args: Vec<String> = std::env::args().collect();
+            let value = |flag: &str| -> Result<String, String> {
+                match args.iter().position(|arg| arg == flag) {
+                    Some(index) => args.get(index + 1)
+                        .cloned()
+                        .ok_or_else(|| tr_args("selection-flag-needs-list", &[("flag", flag)])),
+                    None => Ok(String::new()),
+                }
+            };
+            Selection::parse(&value("--sectors")?, &value("--blocks")?)
+        }).clone()
+    }
+
+    pub fn is_all(&self) -> bool {
+        self.sectors.is_none() && self.blocks.is_none()
+    }
+
+    // The selected sectors of a card with this layout, in order. A block selection only
+    // keeps the sectors its blocks are in.
+    pub fn sectors(&self, layout: CardLayout) -> Vec<SectorIndex> {
+        layout.sectors().filter(|sector| self.has_sector(*sector)).collect()
+    }
+
+    pub fn has_sector(&self, sector: SectorIndex) -> bool {
+        self.sectors.as_ref().is_none_or(|sectors| sectors.contains(&sector.get()))
+            && self.blocks.as_ref().is_none_or(|blocks| blocks.iter().any(|block| BlockAddr(*block).sector() == sector))
+    }
+
+    pub fn has_block(&self, block: BlockAddr) -> bool {
+        self.has_sector(block.sector()) && self.blocks.as_ref().is_none_or(|blocks| blocks.contains(&block.get()))
+    }
+
+    // "all sectors", "sectors 1-3, 7", "sector 2, blocks 8-9"
+    pub fn describe(&self) -> String {
+        let sectors = match &self.sectors {
+            None => tr("selection-all-sectors"),
+            Some(sectors) if sectors.len() == 1 => tr_args("selection-sector", &[("sector", &sectors[0].to_string())]),
+            Some(sectors) => tr_args("selection-sectors", &[("sectors", &describe_numbers(sectors))]),
+        };
+        match &self.blocks {
+            None => sectors,
+            Some(blocks) if self.sectors.is_none() => tr_args("selection-blocks", &[("blocks", &describe_numbers(blocks))]),
+            Some(blocks) => tr_args("selection-sectors-blocks", &[("sectors", &sectors), ("blocks", &describe_numbers(blocks))]),
+        }
+    }
+}
+
+// "1-3,7" as the numbers 1, 2, 3 and 7, each below `limit`. None for "*" or nothing.
+pub fn parse_numbers(text: &str, limit: u16) -> Result<Option<Vec<u8>>, String> {
+    let text = text.trim();
+    if text.is_empty() || text == "*" {
+        return Ok(None);
+    }
+    let mut numbers = Vec::new();
+    for part in text.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (first, last) = part.split_once('-').unwrap_or((part, part));
+        let (first, last) = match (first.trim().parse::<u16>(), last.trim().parse::<u16>()) {
+            (Ok(first), Ok(last)) if first <= last => (first, last),
+            _ => return Err(tr_args("selection-not-a-range", &[("part", part)])),
+        };
+        if last >= limit {
+            return Err(tr_args("selection-beyond-last", &[("number", &last.to_string()), ("last", &(limit - 1).to_string())]));
+        }
+        numbers.extend((first..=last).map(|number| number as u8));
+    }
+    numbers.sort();
+    numbers.dedup();
+    Ok(Some(numbers))
+}
+
+// The numbers back in the "1-3, 7" form
+fn describe_numbers(numbers: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+    for &number in numbers {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == number => *last = number,
+            _ => ranges.push((number, number)),
+        }
+    }
+    ranges.iter()
+        .map(|(first, last)| if first == last { first.to_string() } else { format!("{}-{}", first, last) })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
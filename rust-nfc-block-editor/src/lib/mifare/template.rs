@@ -0,0 +1,316 @@
+// Format templates: what a card is formatted to, sector by sector
+//
+// A template is a text file with one setting per line, '#' starts a comment:
+//   name Office badge
+//   description Keys of the badge system, data blocks left blank
+//   sectors 1-15                      the sectors that are formatted, by default 1-15
+//                                     and the sectors given data. NDEF sectors and
+//                                     sector 0 are always formatted with them.
+//   key * A A0B1C2D3E4F5              keys for sectors: one sector, a range (2-5) or *
+//   key * B FFFFFFFFFFFF
+//   access 1-15 7F078800              access bytes 6-9 of the trailer, as 8 hex digits
+//   data 4 0102030405060708090A0B0C0D0E0F10
+//   ndef 1-15                         an NDEF container: MAD in sector 0 and the NFC
+//                                     Forum keys and access bits on the listed sectors
+// A later line overrides an earlier one for the same sector. Sectors without a key are
+// left at FFFFFFFFFFFF, without access bytes at transport (FF078069), and data blocks
+// without data are cleared. Block 0 is never written.
+//
+// The transport and NDEF templates come with the editor. Templates saved in
+// ~/.config/nfc-block-editor/templates (*.template) are added to them, and replace a
+// built-in template with the same name, so a template file can be shared as it is.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use mifare_rc522::{BlockAddr, CardLayout, Key, SectorIndex};
+
+use crate::lib::mifare::access::access_bits_valid;
+
+// The templates built into the editor
+const BUILT_IN: [(&str, &str); 2] = [
+    ("transport.template", include_str!("../../../templates/transport.template")),
+    ("ndef.template", include_str!("../../../templates/ndef.template")),
+];
+
+// Templates format a Classic 1K
+const LAYOUT: CardLayout = CardLayout::Classic1K;
+
+pub const TRANSPORT_KEY: Key = Key([0xFF; 6]);
+pub const TRANSPORT_ACCESS: [u8; 4] = [0xFF, 0x07, 0x80, 0x69];
+
+// Keys and access bits NFC Forum type MIFARE Classic tags use: the MAD sector is
+// readable with the MAD key, the NDEF sectors with the NDEF key. The last byte of the
+// access bytes is the general purpose byte (MAD version / NDEF mapping version).
+const MAD_KEY: Key = Key([0xA0, 0xA1, 0xA2, 0xA3, 0xA4, 0xA5]);
+const MAD_ACCESS: [u8; 4] = [0x78, 0x77, 0x88, 0xC1];
+const NDEF_KEY: Key = Key([0xD3, 0xF7, 0xD3, 0xF7, 0xD3, 0xF7]);
+const NDEF_ACCESS: [u8; 4] = [0x7F, 0x07, 0x88, 0x40];
+// The MAD is in sector 0
+const MAD_SECTOR: SectorIndex = SectorIndex::FIRST;
+// Application ID of an NDEF sector in the MAD
+const NDEF_AID: [u8; 2] = [0x03, 0xE1];
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FormatTemplate {
+    pub name: String,
+    pub description: String,
+    pub sectors: Vec<SectorIndex>,
+    // Per key setting: its sectors, 'A' or 'B' and the key
+    pub keys: Vec<(Vec<SectorIndex>, char, Key)>,
+    pub access: Vec<(Vec<SectorIndex>, [u8; 4])>,
+    pub data: Vec<(BlockAddr, [u8; 16])>,
+    // "built-in" or the file it was loaded from
+    pub source: String,
+    // The template as written, which is what gets saved
+    pub text: String,
+}
+
+impl FormatTemplate {
+    pub fn parse(text: &str, source: &str) -> Result<Self, Box<dyn Error>> {
+        let mut template = FormatTemplate {
+            name: String::new(),
+            description: String::new(),
+            sectors: LAYOUT.sectors().skip(1).collect(),
+            keys: Vec::new(),
+            access: Vec::new(),
+            data: Vec::new(),
+            source: source.to_string(),
+            text: text.to_string(),
+        };
+        // Sectors given data are added to the default set, a sectors setting replaces
+        // it. The sectors of an NDEF container are always formatted.
+        let mut sectors_given = false;
+        let mut named = Vec::new();
+        let mut ndef_sectors = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || format!("Line {}: invalid setting '{}'", number + 1, line);
+            let (setting, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let value = value.trim();
+            let (numbers, rest) = value.split_once(char::is_whitespace)
+                .map_or((value, ""), |(numbers, rest)| (numbers, rest.trim()));
+
+            match setting {
+                "name" => template.name = value.to_string(),
+                "description" => {
+                    if !template.description.is_empty() {
+                        template.description.push(' ');
+                    }
+                    template.description.push_str(value);
+                },
+                "sectors" => {
+                    template.sectors = parse_sectors(value).ok_or_else(invalid)?;
+                    sectors_given = true;
+                },
+                "key" => {
+                    let sectors = parse_sectors(numbers).ok_or_else(invalid)?;
+                    let (key_type, key) = match rest.split_whitespace().collect::<Vec<_>>()[..] {
+                        [key_type, key] => (key_type.to_uppercase(), Key::from_hex(key).ok_or_else(invalid)?),
+                        _ => return Err(invalid().into()),
+                    };
+                    let key_type = match key_type.as_str() {
+                        "A" => 'A',
+                        "B" => 'B',
+                        _ => return Err(invalid().into()),
+                    };
+                    template.keys.push((sectors, key_type, key));
+                },
+                "access" => {
+                    let sectors = parse_sectors(numbers).ok_or_else(invalid)?;
+                    let access = parse_hex(rest).and_then(|bytes| <[u8; 4]>::try_from(bytes).ok()).ok_or_else(invalid)?;
+                    if !access_bits_valid(&access) {
+                        return Err(format!("Line {}: access bytes {} would make the sector unusable", number + 1, rest).into());
+                    }
+                    template.access.push((sectors, access));
+                },
+                "data" => {
+                    let block = numbers.parse::<u8>().ok().map(BlockAddr).filter(|block| LAYOUT.has_block(*block)).ok_or_else(invalid)?;
+                    if block.is_manufacturer() || block.is_trailer() {
+                        return Err(format!("Line {}: block {} can't be set by data, use key and access", number + 1, block).into());
+                    }
+                    let data = parse_hex(rest).and_then(|bytes| <[u8; 16]>::try_from(bytes).ok()).ok_or_else(invalid)?;
+                    named.push(block.sector());
+                    template.data.push((block, data));
+                },
+                "ndef" => {
+                    let sectors = parse_sectors(value).filter(|sectors| !sectors.contains(&MAD_SECTOR)).ok_or_else(invalid)?;
+                    ndef_sectors.push(MAD_SECTOR);
+                    ndef_sectors.extend(&sectors);
+                    template.add_ndef(&sectors);
+                },
+                _ => return Err(invalid().into()),
+            }
+        }
+
+        if template.name.is_empty() {
+            return Err("The template has no name".into());
+        }
+        if !sectors_given {
+            template.sectors.extend(named);
+        }
+        template.sectors.extend(ndef_sectors);
+        template.sectors.sort();
+        template.sectors.dedup();
+        Ok(template)
+    }
+
+    // The MAD in sector 0 listing the sectors as NDEF, and an empty NDEF message in the
+    // first of them
+    fn add_ndef(&mut self, sectors: &[SectorIndex]) {
+        let mut mad = [0u8; 32];
+        // Info byte: the card publisher sector, taken to be the first NDEF sector
+        mad[1] = sectors.first().map_or(0, |sector| sector.get());
+        for sector in sectors {
+            let aid = sector.index() * 2;
+            mad[aid..aid + 2].copy_from_slice(&NDEF_AID);
+        }
+        mad[0] = mad_crc(&mad[1..]);
+
+        self.keys.push((vec![MAD_SECTOR], 'A', MAD_KEY));
+        self.access.push((vec![MAD_SECTOR], MAD_ACCESS));
+        self.data.push((BlockAddr(1), mad[0..16].try_into().unwrap()));
+        self.data.push((BlockAddr(2), mad[16..32].try_into().unwrap()));
+
+        self.keys.push((sectors.to_vec(), 'A', NDEF_KEY));
+        self.access.push((sectors.to_vec(), NDEF_ACCESS));
+        if let Some(first) = sectors.first() {
+            // NDEF message TLV of length 0, then the terminator TLV
+            let mut message = [0u8; 16];
+            message[0..3].copy_from_slice(&[0x03, 0x00, 0xFE]);
+            self.data.push((first.first_block(), message));
+        }
+    }
+
+    // The trailer the sector is formatted with
+    pub fn trailer(&self, sector: SectorIndex) -> [u8; 16] {
+        let key = |key_type: char| self.keys.iter()
+            .rev()
+            .find(|(sectors, known, _)| *known == key_type && sectors.contains(&sector))
+            .map_or(TRANSPORT_KEY, |(_, _, key)| *key);
+        let access = self.access.iter()
+            .rev()
+            .find(|(sectors, _)| sectors.contains(&sector))
+            .map_or(TRANSPORT_ACCESS, |(_, access)| *access);
+
+        let mut trailer = [0u8; 16];
+        trailer[0..6].copy_from_slice(key('A').as_bytes());
+        trailer[6..10].copy_from_slice(&access);
+        trailer[10..16].copy_from_slice(key('B').as_bytes());
+        trailer
+    }
+
+    // What a data block is formatted with
+    pub fn block(&self, block: BlockAddr) -> [u8; 16] {
+        self.data.iter()
+            .rev()
+            .find(|(known, _)| *known == block)
+            .map_or([0u8; 16], |(_, data)| *data)
+    }
+
+    // Blocks the template writes in the sector, data blocks first and the trailer last
+    pub fn blocks(&self, sector: SectorIndex) -> Vec<(BlockAddr, [u8; 16])> {
+        let mut blocks: Vec<(BlockAddr, [u8; 16])> = sector.data_blocks()
+            .filter(|block| !block.is_manufacturer())
+            .map(|block| (block, self.block(block)))
+            .collect();
+        blocks.push((sector.trailer(), self.trailer(sector)));
+        blocks
+    }
+
+    // A file name for the template, from its name
+    pub fn file_name(&self) -> String {
+        let stem: String = self.name.to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        format!("{}.template", stem.trim_matches('-'))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, &self.text)?;
+        Ok(())
+    }
+}
+
+// CRC-8 of the MAD: polynomial 0x1D, preset 0xC7, over the info byte and the AIDs
+fn mad_crc(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0xC7;
+    for byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x1D } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+// "3", "2-5" or "*", within a Classic 1K
+fn parse_sectors(text: &str) -> Option<Vec<SectorIndex>> {
+    if text == "*" {
+        return Some(LAYOUT.sectors().collect());
+    }
+    let (first, last) = text.split_once('-').unwrap_or((text, text));
+    let (first, last) = (first.parse::<u8>().ok()?, last.parse::<u8>().ok()?);
+    (first <= last).then(|| (first..=last).map(|sector| LAYOUT.sector(sector)).collect()).flatten()
+}
+
+// Hex digits, spaces allowed between bytes
+fn parse_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..digits.len()).step_by(2).map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok()).collect()
+}
+
+// ~/.config/nfc-block-editor/templates (or $XDG_CONFIG_HOME)
+pub fn template_dir() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("templates")
+}
+
+// A template file from anywhere, e.g. one someone shared
+pub fn load_template(path: &Path) -> Result<FormatTemplate, Box<dyn Error>> {
+    FormatTemplate::parse(&fs::read_to_string(path)?, &path.display().to_string())
+}
+
+// The built-in templates and the ones in template_dir, by name. A template file that
+// can't be read is left out and reported in the second list.
+pub fn load_templates() -> (Vec<FormatTemplate>, Vec<String>) {
+    let mut templates = Vec::new();
+    let mut problems = Vec::new();
+    for (file, text) in BUILT_IN {
+        match FormatTemplate::parse(text, "built-in") {
+            Ok(template) => templates.push(template),
+            Err(e) => problems.push(format!("{}: {}", file, e)),
+        }
+    }
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(template_dir())
+        .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+        .unwrap_or_default();
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "template"));
+    paths.sort();
+    for path in paths {
+        match load_template(&path) {
+            Ok(template) => {
+                templates.retain(|known: &FormatTemplate| !known.name.eq_ignore_ascii_case(&template.name));
+                templates.push(template);
+            },
+            Err(e) => problems.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    templates.sort_by_key(|template| template.name.to_lowercase());
+    (templates, problems)
+}
@@ -0,0 +1,131 @@
+// Writing sector trailers so a failed write doesn't go unnoticed
+//
+// A trailer holds the keys of its sector: a write that only half reaches the card can
+// leave the sector with keys nobody knows. So a trailer is read back after it is
+// written, and written again (up to TRAILER_ATTEMPTS times) when it doesn't read back
+// as sent. Before a retry the sector is opened again, with the old key first and then
+// with the new trailer's keys, which tells an ACK that got lost (the new keys open it
+// and the trailer reads back right) from a write the card didn't take (the old key
+// still opens it) and from a torn write (neither does).
+use std::error::Error;
+use mifare_rc522::{BlockAddr, Key, SectorIndex};
+
+use crate::lib::mfrc522::{mfrc522_auth, mfrc522_stop_crypto1, PICC_AUTHENT1A, PICC_AUTHENT1B, PICC_REQALL, MI_OK, SpiReader};
+use crate::lib::i18n::tr_args;
+use crate::lib::mifare::access::access_bits_valid;
+use crate::lib::mifare::backup::guarded_write;
+use crate::lib::mifare::dump::select_card;
+use crate::lib::mifare::verify::read_back;
+
+// Writes of one trailer before giving up
+pub const TRAILER_ATTEMPTS: usize = 3;
+
+// Where a trailer write left the sector
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TrailerWrite {
+    // Read back as written, on this attempt
+    Written(usize),
+    // Every attempt was refused, the old key still opens the sector
+    Refused,
+    // The card left the reader, what its trailer holds isn't known
+    CardGone,
+    // Neither the old key nor the new ones open the sector
+    Torn,
+}
+
+impl TrailerWrite {
+    pub fn is_written(self) -> bool {
+        matches!(self, TrailerWrite::Written(_))
+    }
+
+    // What happened to the trailer of the sector and what to do about it
+    pub fn guidance(self, sector: SectorIndex) -> String {
+        match self {
+            TrailerWrite::Written(1) => tr_args("trailer-written", &[("sector", &sector.to_string())]),
+            TrailerWrite::Written(attempt) => tr_args("trailer-written-attempt", &[("sector", &sector.to_string()), ("attempt", &attempt.to_string())]),
+            TrailerWrite::Refused => tr_args("trailer-refused", &[("sector", &sector.to_string()), ("attempts", &TRAILER_ATTEMPTS.to_string())]),
+            TrailerWrite::CardGone => tr_args("trailer-card-gone", &[("sector", &sector.to_string())]),
+            TrailerWrite::Torn => tr_args("trailer-torn", &[("sector", &sector.to_string())]),
+        }
+    }
+}
+
+// Write a trailer under the authentication in force, made with `auth_mode` and `key`,
+// then read it back. Retries open the sector again first.
+pub fn write_trailer(spi: &mut SpiReader, uid: &[u8], block: BlockAddr, trailer: &[u8; 16], auth_mode: u8, key: &Key)
+    -> Result<TrailerWrite, Box<dyn Error>> {
+    if !block.is_trailer() {
+        return Err(format!("Block {} is not a sector trailer", block).into());
+    }
+    if !access_bits_valid(&[trailer[6], trailer[7], trailer[8], trailer[9]]) {
+        return Err(format!("Access bits {:02X} {:02X} {:02X} don't match their inverted copy, they would lock sector {}",
+            trailer[6], trailer[7], trailer[8], block.sector()).into());
+    }
+
+    for attempt in 1..=TRAILER_ATTEMPTS {
+        if attempt > 1 {
+            match check_trailer(spi, uid, block, trailer, auth_mode, key)? {
+                // The previous write went through, only its answer got lost
+                TrailerWrite::Written(_) => return Ok(TrailerWrite::Written(attempt - 1)),
+                TrailerWrite::Refused => {},
+                outcome => return Ok(outcome),
+            }
+        }
+        if guarded_write(spi, block, trailer)? == MI_OK && read_back(spi, block, trailer)?.passed() {
+            return Ok(TrailerWrite::Written(attempt));
+        }
+    }
+
+    match check_trailer(spi, uid, block, trailer, auth_mode, key)? {
+        TrailerWrite::Written(_) => Ok(TrailerWrite::Written(TRAILER_ATTEMPTS)),
+        outcome => Ok(outcome),
+    }
+}
+
+// Carry on with a trailer write once the card that left the reader is back: find out
+// which trailer it has, and write the new one again where the old one is still there
+pub fn resume_trailer(spi: &mut SpiReader, block: BlockAddr, trailer: &[u8; 16], auth_mode: u8, key: &Key)
+    -> Result<TrailerWrite, Box<dyn Error>> {
+    let (uid, _) = match select_card(spi, PICC_REQALL)? {
+        Some(selected) => selected,
+        None => return Ok(TrailerWrite::CardGone),
+    };
+    match check_trailer(spi, &uid, block, trailer, auth_mode, key)? {
+        TrailerWrite::Refused => write_trailer(spi, &uid, block, trailer, auth_mode, key),
+        outcome => Ok(outcome),
+    }
+}
+
+// Which trailer the card has: Written when it reads back as `trailer`, Refused while a
+// key opens the sector to another trailer. The sector is left open for a write.
+fn check_trailer(spi: &mut SpiReader, uid: &[u8], block: BlockAddr, trailer: &[u8; 16], auth_mode: u8, key: &Key)
+    -> Result<TrailerWrite, Box<dyn Error>> {
+    let key = key.as_bytes();
+    let new_key_a = &trailer[0..6];
+    let new_key_b = &trailer[10..16];
+    // A key that stayed the same opens the sector whichever trailer it has
+    let old_key_changed = key != if auth_mode == PICC_AUTHENT1A { new_key_a } else { new_key_b };
+
+    let candidates = [(auth_mode, key, old_key_changed), (PICC_AUTHENT1A, new_key_a, false), (PICC_AUTHENT1B, new_key_b, false)];
+    for (candidate_mode, candidate_key, is_old) in candidates {
+        match open_sector(spi, uid, block, candidate_mode, candidate_key)? {
+            None => return Ok(TrailerWrite::CardGone),
+            Some(false) => continue,
+            Some(true) if is_old => return Ok(TrailerWrite::Refused),
+            Some(true) if read_back(spi, block, trailer)?.passed() => return Ok(TrailerWrite::Written(1)),
+            Some(true) => return Ok(TrailerWrite::Refused),
+        }
+    }
+    Ok(TrailerWrite::Torn)
+}
+
+// Select the card again and authenticate the trailer. None when the card is gone.
+fn open_sector(spi: &mut SpiReader, uid: &[u8], block: BlockAddr, auth_mode: u8, key: &[u8]) -> Result<Option<bool>, Box<dyn Error>> {
+    mfrc522_stop_crypto1(spi)?;
+    // REQALL also wakes a card a failed authentication sent to HALT
+    match select_card(spi, PICC_REQALL)? {
+        Some((selected, _)) if selected == uid => {},
+        _ => return Ok(None),
+    }
+    Ok(Some(mfrc522_auth(spi, auth_mode, block.get(), key, uid)? == MI_OK))
+}
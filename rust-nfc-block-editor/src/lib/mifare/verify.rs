@@ -0,0 +1,111 @@
+// Reading back what a write left on the card
+//
+// A card acknowledging a write doesn't mean the data is on it: the card can leave the
+// field halfway, and some clone cards answer every write with an ACK and keep nothing.
+// After dump writes, formats, backup restores and block writes the blocks written are
+// read again and compared with what was meant to go on the card.
+//
+// The card never sends Key A back and Key B only where the access bits allow it, so a
+// trailer is compared as far as it can be read: the access bytes, the key that opened
+// the sector, and Key B when it is readable.
+use std::error::Error;
+use mifare_rc522::BlockAddr;
+
+use crate::lib::mfrc522::{mfrc522_read, mfrc522_stop_crypto1, SpiReader};
+use crate::lib::utils::bytes_to_hex;
+use crate::lib::mifare::access::key_b_readable;
+use crate::lib::mifare::dump::{read_sectors, DumpBlock};
+use crate::lib::mifare::keystore::KeyStore;
+use crate::lib::mifare::selection::Selection;
+use crate::lib::i18n::{tr, tr_args};
+
+// A block that didn't read back as written
+pub struct Mismatch {
+    pub block: BlockAddr,
+    pub expected: [u8; 16],
+    // What the card returned, None for bytes that couldn't be read
+    pub actual: DumpBlock,
+}
+
+pub struct VerifyReport {
+    pub checked: usize,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl VerifyReport {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    pub fn print(&self) {
+        println!("\n{}", tr_args("verify-of-blocks-read-back", &[("count", &(self.checked - self.mismatches.len()).to_string()), ("checked", &self.checked.to_string()), ("result", &tr(if self.passed() { "result-pass" } else { "result-fail" }))]));
+        for mismatch in &self.mismatches {
+            if mismatch.actual.iter().all(Option::is_none) {
+                println!("  {}", tr_args("block-couldnt-be-read-back", &[("block", &mismatch.block.to_string())]));
+                continue;
+            }
+            let actual: Vec<String> = mismatch.actual.iter()
+                .map(|byte| byte.map_or("??".to_string(), |byte| format!("{:02X}", byte)))
+                .collect();
+            println!("  {}", tr_args("verify-block", &[("block", &mismatch.block.to_string())]));
+            println!("    {}", tr_args("verify-written", &[("hex", &bytes_to_hex(&mismatch.expected))]));
+            println!("    {}", tr_args("on-card", &[("actual", &actual.join(" "))]));
+        }
+    }
+}
+
+// Read the blocks back from the card on the reader, with the keys from the key store,
+// and compare them with the data they were written with
+pub fn verify_blocks(spi: &mut SpiReader, key_store: &mut KeyStore, written: &[(BlockAddr, [u8; 16])])
+    -> Result<VerifyReport, Box<dyn Error>> {
+    let blocks: Vec<BlockAddr> = written.iter().map(|(block, _)| *block).collect();
+    mfrc522_stop_crypto1(spi)?;
+    let dump = read_sectors(spi, key_store, &Selection::of_blocks(&blocks))?
+        .ok_or("The card left the reader before it could be verified")?;
+
+    let mismatches = written.iter()
+        .filter_map(|(block, expected)| {
+            let actual = dump.blocks.get(block.index()).copied().unwrap_or([None; 16]);
+            (!block_matches(*block, expected, &actual)).then_some(Mismatch { block: *block, expected: *expected, actual })
+        })
+        .collect();
+    Ok(VerifyReport { checked: written.len(), mismatches })
+}
+
+// Read one block back under the authentication it was just written with
+pub fn read_back(spi: &mut SpiReader, block: BlockAddr, expected: &[u8; 16]) -> Result<VerifyReport, Box<dyn Error>> {
+    let mut actual: DumpBlock = [None; 16];
+    if let Some(data) = mfrc522_read(spi, block.get())? {
+        actual.iter_mut().zip(&data).for_each(|(byte, value)| *byte = Some(*value));
+        // Key A always reads as zeros
+        if block.is_trailer() {
+            actual[0..6].fill(None);
+        }
+    }
+    let mismatches = if block_matches(block, expected, &actual) {
+        Vec::new()
+    } else {
+        vec![Mismatch { block, expected: *expected, actual }]
+    };
+    Ok(VerifyReport { checked: 1, mismatches })
+}
+
+// Whether a block read back holds what was written. Data blocks have to be read in
+// full, trailers are compared where the card shows them.
+pub fn block_matches(block: BlockAddr, expected: &[u8; 16], actual: &DumpBlock) -> bool {
+    if !block.is_trailer() {
+        return actual.iter().zip(expected).all(|(actual, expected)| *actual == Some(*expected));
+    }
+
+    if actual[6..10].iter().zip(&expected[6..10]).any(|(actual, expected)| *actual != Some(*expected)) {
+        return false;
+    }
+    // The access bytes are the ones written, so they tell whether Key B can be read
+    let key_b_shown = key_b_readable(expected);
+
+    actual.iter().zip(expected).enumerate().all(|(index, (actual, expected))| match index {
+        0..=5 => actual.is_none_or(|byte| byte == *expected),
+        10..=15 if !key_b_shown => true,
+        _ => *actual == Some(*expected),
+    })
+}
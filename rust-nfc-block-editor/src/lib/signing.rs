@@ -0,0 +1,41 @@
+// Ed25519 signatures of saved dumps
+//
+// A signature is written to "<dump>.sig" beside the dump by the file_signing crate,
+// which the reader app signs its exports with too. The editor's key pair is made the
+// first time a dump is signed and kept with the other settings. A valid signature
+// only says which key signed the file: compare that key with the one the sender gave
+// you (`verify <file> --key <public key>` does it for you).
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::SigningKey;
+use crate::lib::i18n::tr_args;
+pub use file_signing::{signature_path, verify_file};
+
+// ~/.config/nfc-block-editor/signing.key (or $XDG_CONFIG_HOME), readable by its owner only
+pub fn default_key_path() -> PathBuf {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(|| PathBuf::from("."));
+    base.join("nfc-block-editor").join("signing.key")
+}
+
+// This editor's public key as hex, the key pair is made when there is none yet
+pub fn public_key(key_path: &Path) -> Result<String, Box<dyn Error>> {
+    Ok(file_signing::public_key_hex(&signing_key(key_path)?))
+}
+
+// Sign a file, writing its signature beside it
+pub fn sign_file(path: &Path, key_path: &Path) -> Result<PathBuf, Box<dyn Error>> {
+    Ok(file_signing::sign_file(path, &signing_key(key_path)?)?)
+}
+
+// this loads the key, or makes and saves one the first time
+fn signing_key(key_path: &Path) -> Result<SigningKey, Box<dyn Error>> {
+    let (key, created) = file_signing::load_or_create_key(key_path)?;
+    if created {
+        println!("{}", tr_args("created-signing-key-keep-copy", &[("path", &key_path.display().to_string())]));
+    }
+    Ok(key)
+}
@@ -0,0 +1,187 @@
+// Response timings of the card commands, for research into clone chips and for tuning
+// the reader's timeouts and retries
+//
+// Each trial takes a card through a whole transaction: request, anticollision, select,
+// authenticate and read one block. Every command is timed on its own, from the moment
+// it is handed to the reader to the moment its answer is back, and the trial stops at
+// the first command that fails. All timings go to a CSV file with one row per command,
+// so they can be plotted or compared between cards; a summary per command is printed.
+// Clone chips tend to answer authentication at a different speed than NXP's, and the
+// slowest successful answers show how much room the timeouts need.
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::time::{Duration, Instant};
+
+use crate::lib::mfrc522::{
+    mfrc522_request, mfrc522_anticoll, mfrc522_select_tag,
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read, current_speed,
+    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK, SpiReader
+};
+use crate::lib::i18n::{tr, tr_args};
+
+pub const DEFAULT_TRIALS: usize = 200;
+pub const DEFAULT_BLOCK: u8 = 4;
+pub const DEFAULT_KEY: [u8; 6] = [0xFF; 6];
+// Pause between trials, so the card sees every trial the same way
+const TRIAL_PAUSE: Duration = Duration::from_millis(5);
+
+// One timed command
+pub struct Timing {
+    pub trial: usize,
+    pub command: &'static str,
+    pub ok: bool,
+    pub elapsed: Duration,
+}
+
+// What to time
+pub struct TimingConfig {
+    pub trials: usize,
+    pub block: u8,
+    pub key: [u8; 6],
+    pub key_b: bool,
+}
+
+impl Default for TimingConfig {
+    fn default() -> Self {
+        TimingConfig {
+            trials: DEFAULT_TRIALS,
+            block: DEFAULT_BLOCK,
+            key: DEFAULT_KEY,
+            key_b: false,
+        }
+    }
+}
+
+// Run `command` and record how long it took, returns whether it worked
+fn timed<F>(timings: &mut Vec<Timing>, trial: usize, command: &'static str, run: F) -> Result<bool, Box<dyn Error>>
+where
+    F: FnOnce() -> Result<bool, Box<dyn Error>>,
+{
+    let start = Instant::now();
+    let ok = run()?;
+    timings.push(Timing { trial, command, ok, elapsed: start.elapsed() });
+    Ok(ok)
+}
+
+// this runs one trial, stopping at the first command that fails
+fn run_trial(spi: &mut SpiReader, config: &TimingConfig, trial: usize, timings: &mut Vec<Timing>) -> Result<(), Box<dyn Error>> {
+    if !timed(timings, trial, "request", || Ok(mfrc522_request(spi, PICC_REQIDL)?.0 == MI_OK))? {
+        return Ok(());
+    }
+
+    let mut uid = Vec::new();
+    if !timed(timings, trial, "anticoll", || {
+        let (status, answer) = mfrc522_anticoll(spi)?;
+        uid = answer;
+        Ok(status == MI_OK)
+    })? {
+        return Ok(());
+    }
+
+    if !timed(timings, trial, "select", || Ok(mfrc522_select_tag(spi, &uid)? != 0))? {
+        return Ok(());
+    }
+
+    let auth_mode = if config.key_b { PICC_AUTHENT1B } else { PICC_AUTHENT1A };
+    let authenticated = timed(timings, trial, "auth", || {
+        Ok(mfrc522_auth(spi, auth_mode, config.block, &config.key, &uid)? == MI_OK)
+    })?;
+    if authenticated {
+        timed(timings, trial, "read", || Ok(mfrc522_read(spi, config.block)?.is_some()))?;
+    }
+    mfrc522_stop_crypto1(spi)?;
+    Ok(())
+}
+
+// Time `config.trials` transactions with the card on the reader
+pub fn collect_timings(spi: &mut SpiReader, config: &TimingConfig) -> Result<Vec<Timing>, Box<dyn Error>> {
+    let mut timings = Vec::new();
+    for trial in 1..=config.trials {
+        run_trial(spi, config, trial, &mut timings)?;
+        if trial % 50 == 0 {
+            println!("  {}", tr_args("timing-trials-done", &[("trial", &trial.to_string()), ("trials", &config.trials.to_string())]));
+        }
+        std::thread::sleep(TRIAL_PAUSE);
+    }
+    Ok(timings)
+}
+
+// Write the timings as CSV: trial, command, block, status, microseconds, SPI clock
+pub fn write_csv(path: &str, timings: &[Timing], config: &TimingConfig) -> Result<(), Box<dyn Error>> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "trial,command,block,status,micros,spi_hz")?;
+    let speed = current_speed();
+    for timing in timings {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            timing.trial,
+            timing.command,
+            config.block,
+            if timing.ok { "ok" } else { "fail" },
+            timing.elapsed.as_micros(),
+            speed
+        )?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+// this gives the value below which `share` of the sorted values lie
+fn percentile(sorted: &[u128], share: f64) -> u128 {
+    let index = ((sorted.len() - 1) as f64 * share).round() as usize;
+    sorted[index]
+}
+
+// Per command: how often it worked and how long the successful answers took
+pub fn print_summary(timings: &[Timing]) {
+    println!("\n{:<9} | {:>11} | {:>8} | {:>8} | {:>8} | {:>8}",
+        tr("timing-command"), tr("timing-ok-tried"), tr("timing-min"), tr("timing-median"), "p95", tr("timing-max"));
+    println!("{:-<9}-+-{:-<11}-+-{:-<8}-+-{:-<8}-+-{:-<8}-+-{:-<8}", "", "", "", "", "", "");
+    for command in ["request", "anticoll", "select", "auth", "read"] {
+        let tried: Vec<&Timing> = timings.iter().filter(|timing| timing.command == command).collect();
+        if tried.is_empty() {
+            continue;
+        }
+        let mut micros: Vec<u128> = tried.iter()
+            .filter(|timing| timing.ok)
+            .map(|timing| timing.elapsed.as_micros())
+            .collect();
+        micros.sort_unstable();
+
+        let counts = format!("{}/{}", micros.len(), tried.len());
+        if micros.is_empty() {
+            println!("{:<9} | {:>11} | {:>8} | {:>8} | {:>8} | {:>8}", command, counts, "-", "-", "-", "-");
+        } else {
+            println!(
+                "{:<9} | {:>11} | {:>8} | {:>8} | {:>8} | {:>8}",
+                command,
+                counts,
+                micros[0],
+                percentile(&micros, 0.5),
+                percentile(&micros, 0.95),
+                micros[micros.len() - 1]
+            );
+        }
+    }
+}
+
+// `timing` subcommand
+pub fn run_timing(spi: &mut SpiReader, config: &TimingConfig, output: &str) -> Result<(), Box<dyn Error>> {
+    println!("{}", tr_args("timing-transactions-on-block-with", &[("trials", &config.trials.to_string()), ("block", &config.block.to_string()), ("key", if config.key_b { "B" } else { "A" }), ("speed", &current_speed().to_string())]));
+    println!("{}", tr("leave-card-on-reader-for"));
+    print!("{}", tr("press-enter-to-start"));
+    io::stdout().flush()?;
+    io::stdin().read_line(&mut String::new())?;
+
+    let timings = collect_timings(spi, config)?;
+    if timings.is_empty() {
+        return Err("No commands were timed".into());
+    }
+
+    print_summary(&timings);
+    write_csv(output, &timings, config)?;
+    println!("\n{}", tr_args("timings-written-to", &[("count", &timings.len().to_string()), ("output", output)]));
+    Ok(())
+}
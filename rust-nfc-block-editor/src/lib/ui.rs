@@ -1,28 +1,46 @@
 use std::error::Error;
 use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 use std::time::Duration;
-use rppal::spi::Spi;
 
 use crate::lib::mfrc522::{
     mfrc522_request, mfrc522_anticoll, mfrc522_select_tag, 
-    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read, mfrc522_write,
-    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK
+    mfrc522_auth, mfrc522_stop_crypto1, mfrc522_read,
+    PICC_REQIDL, PICC_AUTHENT1A, PICC_AUTHENT1B, MI_OK,
+    ensure_connected, current_speed, ReaderStatus, SpeedSetting,
+    signal_metrics, reset_signal_metrics, signal_log_path, SpiReader
 };
+use crate::lib::benchmark::format_speed;
+use crate::lib::credit_test::{self, CardSetup};
+use crate::lib::mifare::decode::{format_amount, value_block, value_from_block};
 
 use crate::lib::mifare::{
     read_card_uid, read_sector_data, write_block_data, write_block_raw,
-    modify_sector_access, change_sector_keys, format_card, dump_card,
-    AccessBits
+    modify_sector_access, change_sector_keys, format_with_template, dump_card,
+    load_dump_file, save_dump_file, write_dump, import_key_file, load_key_file, save_key_file,
+    AccessBits, invalid_access_bits, BlockAddr, Key, KeyStore, SectorIndex, PresenceTracker, PresenceEvent, DEFAULT_KEYS,
+    CardDump, CardLayout, CardProfile, FormatTemplate, Selection, Workflow, decode_card, load_profiles, print_sector, read_card, format_text_block,
+    DataKey, KeyMode, encrypt_dump, decrypt_dump,
+    guarded_write, list_backups, load_backup, restore_backup, read_back, verify_blocks,
+    resume_trailer, write_trailer, TrailerWrite, journaled_write, pending_update, recover_journal
 };
+use crate::lib::mifare::crypt::{generate_key, is_data_block};
+use crate::lib::mifare::profile::profile_dir;
+use crate::lib::mifare::template::{load_template, load_templates, template_dir};
+use crate::lib::mifare::presence::POLL_INTERVAL;
+use crate::lib::signing::{self, sign_file, verify_file};
 
 use crate::lib::utils::{
     uid_to_string, bytes_to_hex, bytes_to_ascii, hex_string_to_bytes
 };
+use crate::lib::i18n::{tr, tr_args};
 
 // Helper function for countdown timer when placing card
 pub fn countdown_for_card_placement(seconds: u64) -> Result<(), Box<dyn Error>> {
-    println!("\nPrepare your card. You have {} seconds to place it on the reader...", seconds);
+    println!("\n{}", tr_args("prepare-your-card-you-have", &[("seconds", &seconds.to_string())]));
     
     // Progress bar width
     let width = 30;
@@ -40,13 +58,13 @@ pub fn countdown_for_card_placement(seconds: u64) -> Result<(), Box<dyn Error>>
                 print!(" ");
             }
         }
-        print!("] {:2}/{} seconds", seconds - i + 1, seconds);
+        print!("] {}", tr_args("countdown-seconds", &[("number", &format!("{:2}", seconds - i + 1)), ("seconds", &seconds.to_string())]));
         io::stdout().flush()?;
         
         thread::sleep(Duration::from_secs(1));
     }
     
-    println!("\n\nReading card now...");
+    println!("\n\n{}", tr("reading-card-now"));
     Ok(())
 }
 
@@ -68,117 +86,223 @@ pub fn clear_screen() {
     print!("{}[1;1H", 27 as char);
 }
 
+// Reconnection attempts before the menu is shown with the reader disconnected
+const RECONNECT_ATTEMPTS: u32 = 5;
+
 // UI Main Menu
-pub fn main_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+pub fn main_menu(spi: &mut SpiReader, speed_setting: &mut Option<SpeedSetting>) -> Result<(), Box<dyn Error>> {
+    // Keys loaded and the key that opened each sector, kept between dumps and writes
+    let key_store_path = KeyStore::default_path();
+    let mut key_store = match KeyStore::load(&key_store_path) {
+        Ok(key_store) => key_store,
+        Err(e) => {
+            println!("{}", tr_args("err-load", &[("path", &key_store_path.display().to_string()), ("error", &e.to_string())]));
+            wait_for_input(&tr("press-enter-to-continue"))?;
+            KeyStore::default()
+        }
+    };
+    
+    // Data block encryption, set up from the Data Encryption menu
+    let data_key_path = DataKey::default_path();
+    let mut data_key = match DataKey::load(&data_key_path) {
+        Ok(data_key) => data_key,
+        Err(e) => {
+            println!("{}", tr_args("err-load", &[("path", &data_key_path.display().to_string()), ("error", &e.to_string())]));
+            wait_for_input(&tr("press-enter-to-continue"))?;
+            None
+        }
+    };
+    
     loop {
+        // Reconnect first if the reader went away since the last operation
+        let status = ensure_connected(spi, RECONNECT_ATTEMPTS);
+        
+        // Slow the SPI clock down if the last operations had communication errors
+        let mut lowered_speed = None;
+        if let Some(setting) = speed_setting.as_mut() {
+            match setting.fall_back_if_unreliable(spi) {
+                Ok(speed_hz) => lowered_speed = speed_hz,
+                Err(e) => println!("{}", tr_args("err-lower-spi-speed", &[("error", &e.to_string())])),
+            }
+        }
+        
         clear_screen();
         println!("==========================");
-        println!("  NFC/RFID BLOCK EDITOR  ");
+        println!("  {}  ", tr("title-block-editor"));
         println!("==========================");
+        println!("{}", tr_args("reader-spi", &[("status", &status.to_string()), ("speed", &format_speed(current_speed()))]));
+        if status == ReaderStatus::Disconnected {
+            println!("{}", tr("reader-check-wiring"));
+        }
+        if let Some(speed_hz) = lowered_speed {
+            println!("{}", tr_args("spi-speed-lowered", &[("speed", &format_speed(speed_hz))]));
+        }
+        if let Some(reason) = safe_mode::read_only_reason() {
+            println!("{}", tr_args("read-only-mode-cards-cant", &[("reason", reason)]));
+        }
         
-        println!("\nMAIN MENU:");
-        println!("1. Read Card UID");
-        println!("2. Read Block");
-        println!("3. Write Block");
-        println!("4. Dump Card");
-        println!("5. Format Card");
-        println!("6. Change Keys");
-        println!("7. Modify Access Bits");
-        println!("8. Block Editor (Interactive)");  // Added this option
-        println!("9. Test Keys");                   // Added this option
-        println!("0. Exit");
+        // Items that change the card are left out in read-only mode, the numbers stay
+        let read_only = safe_mode::is_read_only();
+        println!("\n{}", tr("main-menu"));
+        println!("1. {}", tr("read-card-uid"));
+        println!("2. {}", tr("read-block"));
+        if !read_only {
+            println!("3. {}", tr("write-block"));
+        }
+        println!("4. {}", tr("dump-card"));
+        if !read_only {
+            println!("5. {}", tr("format-card"));
+            println!("6. {}", tr("change-keys"));
+            println!("7. {}", tr("modify-access-bits"));
+            println!("8. {}", tr("block-editor-interactive"));  // Added this option
+        }
+        println!("9. {}", tr("test-keys"));                   // Added this option
+        println!("10. {}", tr_args("data-encryption", &[("status", &data_key.as_ref().map_or_else(|| tr("encryption-not-set-up"), DataKey::describe))]));
+        if !read_only {
+            println!("11. {}", tr("restore-from-backup"));
+        }
+        println!("12. {}", tr("reader-diagnostics"));
+        println!("13. {}", tr_args("key-store-keys", &[("count", &key_store.keys().len().to_string())]));
+        println!("14. {}", tr("card-profiles"));
+        if !read_only {
+            println!("15. {}", tr("credit-test-magic-card"));
+        }
+        println!("0. {}", tr("menu-exit"));
         
-        let choice = wait_for_input("\nEnter your choice: ")?;
+        let choice = wait_for_input(&format!("\n{} ", tr("enter-your-choice")))?;
         
-        match choice.as_str() {
-            "1" => read_uid_menu(spi)?,
-            "2" => read_block_menu(spi)?,
-            "3" => write_block_menu(spi)?,
-            "4" => dump_card_menu(spi)?,
-            "5" => format_card_menu(spi)?,
-            "6" => change_keys_menu(spi)?,
-            "7" => access_bits_menu(spi)?,
-            "8" => block_editor_menu(spi)?,  // New menu function
-            "9" => test_keys_menu(spi)?,     // New menu function
+        let result = match choice.as_str() {
+            "3" | "5" | "6" | "7" | "8" | "11" | "15" if read_only => {
+                safe_mode::refuse();
+                wait_for_input(&tr("press-enter-to-continue"))?;
+                Ok(())
+            },
+            "1" => read_uid_menu(spi),
+            "2" => read_block_menu(spi, data_key.as_ref()),
+            "3" => write_block_menu(spi, data_key.as_ref()),
+            "4" => dump_card_menu(spi, &mut key_store, data_key.as_ref()),
+            "5" => format_card_menu(spi, &mut key_store),
+            "6" => change_keys_menu(spi),
+            "7" => access_bits_menu(spi),
+            "8" => block_editor_menu(spi, data_key.as_ref()),  // New menu function
+            "9" => test_keys_menu(spi),     // New menu function
+            "10" => data_key_menu(&mut data_key, &data_key_path),
+            "11" => restore_backup_menu(spi, &mut key_store),
+            "12" => diagnostics_menu(),
+            "13" => key_store_menu(&mut key_store, &key_store_path),
+            "14" => profiles_menu(spi, &mut key_store),
+            "15" => credit_test_menu(spi, &mut key_store),
             "0" => {
-                println!("Exiting...");
+                println!("{}", tr("menu-exiting"));
                 break;
             },
             _ => {
-                println!("Invalid choice. Press Enter to continue...");
+                println!("{}", tr("err-invalid-choice-press-enter-to"));
                 wait_for_input("")?;
+                Ok(())
             }
+        };
+        
+        // A failed operation is often a lost reader, so stay in the menu
+        // and let the next iteration try to reconnect
+        if let Err(e) = result {
+            println!("\n{}", tr_args("err-error", &[("error", &e.to_string())]));
+            wait_for_input(&tr("press-enter-to-continue"))?;
         }
     }
     
     Ok(())
 }
+// Signal quality since the start (or the last reset), to tell RF problems from key problems
+fn diagnostics_menu() -> Result<(), Box<dyn Error>> {
+    clear_screen();
+    println!("{}", tr("title-reader-diagnostics"));
+    println!("==================");
+    println!("{}\n", tr_args("spi-clock", &[("speed", &format_speed(current_speed()))]));
+    println!("{}", signal_metrics());
+    println!("\n{}", tr_args("problem-transactions-are-logged-to", &[("path", &signal_log_path().display().to_string())]));
+    
+    let choice = wait_for_input(&format!("\n{} ", tr("enter-r-to-reset-counters")))?;
+    if choice.eq_ignore_ascii_case("r") {
+        reset_signal_metrics();
+        println!("{}", tr("counters-reset"));
+        wait_for_input(&tr("press-enter-to-continue"))?;
+    }
+    Ok(())
+}
+
 // Read Card UID Menu
-fn read_uid_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn read_uid_menu(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("READ CARD UID");
+    println!("{}", tr("title-read-card-uid"));
     println!("=============");
     
+    println!("\n1. {}", tr("read-one-card"));
+    println!("2. {}", tr("scan-continuously-each-card-reported"));
+    if wait_for_input(&format!("\n{} ", tr("enter-your-choice")))? == "2" {
+        return scan_uids_menu(spi);
+    }
+    
     countdown_for_card_placement(5)?;
     
     match read_card_uid(spi)? {
         Some(uid) => {
-            println!("\nCard UID: {}", uid_to_string(&uid));
-            println!("UID as decimal: {}", crate::lib::utils::uid_to_num(&uid));
+            println!("\n{}", tr_args("card-uid", &[("uid", &uid_to_string(&uid))]));
+            println!("{}", tr_args("uid-as-decimal", &[("decimal", &crate::lib::utils::uid_to_num(&uid).to_string())]));
         },
         None => {
-            println!("\nNo card detected or error reading card.");
+            println!("\n{}", tr("no-card-detected-or-error"));
         }
     }
     
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Access Bits Menu
-fn access_bits_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn access_bits_menu(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("MODIFY ACCESS BITS");
+    println!("{}", tr("title-modify-access-bits"));
     println!("=================");
     
-    let sector_str = wait_for_input("\nEnter sector number (0-15): ")?;
-    let sector = match sector_str.parse::<u8>() {
-        Ok(num) if num <= 15 => num,
-        _ => {
-            println!("Invalid sector number. Must be between 0 and 15.");
-            wait_for_input("\nPress Enter to continue...")?;
+    let sector_str = wait_for_input(&format!("\n{} ", tr("enter-sector-number-0-15")))?;
+    let sector = match sector_str.parse::<u8>().ok().and_then(SectorIndex::new) {
+        Some(sector) => sector,
+        None => {
+            println!("{}", tr("err-invalid-sector-number-must-be"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     };
     
     // Choose access condition
-    println!("\nSelect access configuration:");
-    println!("1. Transport (all open, default)");
-    println!("2. Secure (read with Key A, write with Key B)");
-    println!("3. Read-only (no writes allowed)");
-    println!("4. Custom (advanced, not implemented)");
+    println!("\n{}", tr("select-access-configuration"));
+    println!("1. {}", tr("transport-all-open-default"));
+    println!("2. {}", tr("secure-read-with-key-write"));
+    println!("3. {}", tr("read-only-no-writes-allowed"));
+    println!("4. {}", tr("custom-advanced-not-implemented"));
     
-    let access_choice = wait_for_input("\nEnter choice (1-3): ")?;
+    let access_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-3")))?;
     
     let access_bits = match access_choice.as_str() {
         "1" => AccessBits::get_predefined_config("transport"),
         "2" => AccessBits::get_predefined_config("secure"),
         "3" => AccessBits::get_predefined_config("readonly"),
         "4" => {
-            println!("Custom access bits not implemented yet. Operation cancelled.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("custom-access-bits-not-implemented"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         },
         _ => {
-            println!("Invalid choice. Operation cancelled.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("err-invalid-choice-operation-cancelled"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     };
     
     // Get authentication key
-    println!("\nYou need the current Key A to modify access bits.");
-    let key_str = wait_for_input("Enter Key A (12 hex chars, default FFFFFFFFFFFF): ")?;
+    println!("\n{}", tr("you-need-current-key-to"));
+    let key_str = wait_for_input(&format!("{} ", tr("enter-key-12-hex-chars")))?;
     
     let key = if key_str.is_empty() {
         [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
@@ -186,20 +310,20 @@ fn access_bits_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
         match hex_string_to_bytes(&key_str) {
             Some(bytes) if bytes.len() == 6 => bytes,
             _ => {
-                println!("Invalid key format. Using default key.");
+                println!("{}", tr("err-invalid-key-format-using-default"));
                 [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
             }
         }
     };
     
     // Show the new access conditions
-    println!("\nNew access conditions:");
+    println!("\n{}", tr("new-access-conditions"));
     println!("{}", access_bits);
     
-    let confirm = wait_for_input("\nConfirm access bits change? (y/n): ")?.to_lowercase();
+    let confirm = wait_for_input(&format!("\n{} ", tr("confirm-access-bits-change-y")))?.to_lowercase();
     if confirm != "y" {
-        println!("Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("{}", tr("operation-cancelled"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
@@ -209,61 +333,95 @@ fn access_bits_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
     let result = modify_sector_access(spi, sector, &access_bits)?;
     
     if result {
-        println!("\nAccess bits modified successfully!");
+        println!("\n{}", tr("access-bits-modified-successfully"));
     } else {
-        println!("\nFailed to modify access bits. Check authentication key and access rights.");
+        println!("\n{}", tr("err-modify-access-bits-check-authentication"));
     }
     
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+    Ok(())
+}
+
+// Report cards as they arrive and leave until Enter is pressed
+fn scan_uids_menu(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
+    println!("\n{}\n", tr("scanning-press-enter-to-stop"));
+    
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = Arc::clone(&stop);
+    thread::spawn(move || {
+        let _ = io::stdin().read_line(&mut String::new());
+        stop_flag.store(true, Ordering::Relaxed);
+    });
+    
+    let before = signal_metrics();
+    let mut tracker = PresenceTracker::default();
+    let mut scans = 0;
+    while !stop.load(Ordering::Relaxed) {
+        match tracker.poll(spi)? {
+            PresenceEvent::NewCard(uid) => {
+                scans += 1;
+                println!("{}", tr_args("card-decimal", &[("scans", &scans.to_string()), ("uid", &uid_to_string(&uid)), ("decimal", &crate::lib::utils::uid_to_num(&uid).to_string())]));
+            },
+            PresenceEvent::Removed(uid) => println!("  {}", tr_args("scan-card-removed", &[("uid", &uid_to_string(&uid))])),
+            PresenceEvent::StillPresent | PresenceEvent::NoCard => {},
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    
+    println!("\n{}", tr_args("cards-scanned", &[("scans", &scans.to_string())]));
+    let during = signal_metrics().since(&before);
+    println!("{}", tr_args("signal-summary", &[("summary", &during.summary())]));
+    println!("{}", during.diagnosis());
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Read Block Menu
-fn read_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn read_block_menu(spi: &mut SpiReader, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("READ BLOCK");
+    println!("{}", tr("title-read-block"));
     println!("==========");
     
-    let block_str = wait_for_input("\nEnter block number (0-63): ")?;
+    let block_str = wait_for_input(&format!("\n{} ", tr("enter-block-number-0-63")))?;
     let block_number = match block_str.parse::<u8>() {
-        Ok(num) if num <= 63 => num,
+        Ok(num) => BlockAddr(num),
         _ => {
-            println!("Invalid block number. Must be between 0 and 63.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("err-invalid-block-number-must-be"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     };
     
-    // Show a warning for sector trailers (block numbers 3, 7, 11, etc.)
-    if block_number % 4 == 3 {
-        println!("\nWarning: Block {} is a sector trailer containing access bits and keys.", block_number);
+    // Show a warning for sector trailers (block numbers 3, 7, 11, etc., and every 16th from 143 on a 4K)
+    if block_number.is_trailer() {
+        println!("\n{}", tr_args("warning-block-is-sector-trailer", &[("block", &block_number.to_string())]));
     }
     
     // Get authentication key
-    println!("\nSelect authentication method:");
-    println!("1. Key A (default: FFFFFFFFFFFF)");
-    println!("2. Key B (default: FFFFFFFFFFFF)");
+    println!("\n{}", tr("select-authentication-method"));
+    println!("1. {}", tr("key-default-ffffffffffff"));
+    println!("2. {}", tr("key-b-default-ffffffffffff"));
     
-    let key_choice = wait_for_input("\nEnter choice (1-2): ")?;
+    let key_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-2")))?;
     
     let auth_mode = match key_choice.as_str() {
         "1" => PICC_AUTHENT1A,
         "2" => PICC_AUTHENT1B,
         _ => {
-            println!("Invalid choice. Using Key A by default.");
+            println!("{}", tr("err-invalid-choice-using-key-by"));
             PICC_AUTHENT1A
         }
     };
     
     // Ask for custom key if needed
-    let use_custom_key = wait_for_input("\nUse custom key? (y/n): ")?.to_lowercase();
+    let use_custom_key = wait_for_input(&format!("\n{} ", tr("use-custom-key-y-n")))?.to_lowercase();
     
     let key = if use_custom_key == "y" {
-        let key_str = wait_for_input("Enter key (12 hex chars): ")?;
+        let key_str = wait_for_input(&format!("{} ", tr("enter-key-12-hex-chars-2")))?;
         match hex_string_to_bytes(&key_str) {
             Some(bytes) if bytes.len() == 6 => bytes,
             _ => {
-                println!("Invalid key format. Using default key.");
+                println!("{}", tr("err-invalid-key-format-using-default"));
                 [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
             }
         }
@@ -276,104 +434,120 @@ fn read_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
     // Request tag and get UID
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        println!("\nError: Could not detect card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-detect-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Anti-collision and get UID
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        println!("\nError: Could not read card UID.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-read-card-uid"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
-    println!("\nCard detected. UID: {}", uid_to_string(&uid));
+    println!("\n{}", tr_args("card-detected-uid", &[("uid", &uid_to_string(&uid))]));
     
     // Select card
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        println!("\nError: Could not select card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-select-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Authenticate
-    let status = mfrc522_auth(spi, auth_mode, block_number, &key, &uid)?;
+    let status = mfrc522_auth(spi, auth_mode, block_number.get(), &key, &uid)?;
     if status != MI_OK {
-        println!("\nAuthentication failed. Try a different key.");
+        println!("\n{}", tr("authentication-failed-try-different-key"));
         mfrc522_stop_crypto1(spi)?;
-        wait_for_input("\nPress Enter to continue...")?;
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Read the block
-    match mfrc522_read(spi, block_number)? {
+    match mfrc522_read(spi, block_number.get())? {
         Some(data) => {
-            println!("\nBlock {} data:", block_number);
-            println!("HEX: {}", bytes_to_hex(&data));
+            println!("\n{}", tr_args("block-data", &[("block", &block_number.to_string())]));
+            println!("{}", tr_args("block-hex", &[("hex", &bytes_to_hex(&data))]));
             
             // If this is a sector trailer, display keys and access bits
-            if block_number % 4 == 3 {
-                println!("  Key A: {}", bytes_to_hex(&data[0..6]));
-                println!("  Access Bits: {}", bytes_to_hex(&data[6..10]));
-                println!("  Key B: {}", bytes_to_hex(&data[10..16]));
+            if block_number.is_trailer() {
+                println!("  {}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(&data[0..6]))]));
+                println!("  {}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&data[6..10]))]));
+                println!("  {}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(&data[10..16]))]));
                 
                 // Parse and display access conditions
                 let access_bytes = [data[6], data[7], data[8], data[9]];
-                let access_bits = AccessBits::from_bytes(&access_bytes);
-                println!("\nAccess Conditions:");
-                println!("{}", access_bits);
+                match AccessBits::from_bytes(&access_bytes) {
+                    Some(access_bits) => {
+                        println!("\n{}", tr("access-conditions"));
+                        for line in access_bits.data_access_lines(block_number.sector()) {
+                            println!("{}", line);
+                        }
+                        println!("{}\n{}", tr_args("access-trailer-block", &[("block", &block_number.to_string())]), access_bits.interpret_access("trailer", 0));
+                    },
+                    None => println!("\n{}", invalid_access_bits()),
+                }
             } else {
-                println!("ASCII: {}", bytes_to_ascii(&data));
+                match data_key.and_then(|data_key| data_key.cipher_for(&uid)) {
+                    Some(cipher) if is_data_block(block_number) && data.len() >= 16 => {
+                        let mut block = [0u8; 16];
+                        block.copy_from_slice(&data[..16]);
+                        let plain = cipher.decrypt_block(block_number, &block);
+                        println!("{}", tr_args("block-decrypted", &[("hex", &bytes_to_hex(&plain))]));
+                        println!("{}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&plain))]));
+                    },
+                    _ => println!("{}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&data))])),
+                }
             }
         },
         None => {
-            println!("\nError reading block data.");
+            println!("\n{}", tr("err-reading-block-data"));
         }
     }
     
     mfrc522_stop_crypto1(spi)?;
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Write Block Menu
-fn write_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn write_block_menu(spi: &mut SpiReader, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("WRITE BLOCK");
+    println!("{}", tr("title-write-block"));
     println!("===========");
     
-    let block_str = wait_for_input("\nEnter block number (0-63): ")?;
+    let block_str = wait_for_input(&format!("\n{} ", tr("enter-block-number-0-63")))?;
     let block_number = match block_str.parse::<u8>() {
-        Ok(num) if num <= 63 => num,
+        Ok(num) => BlockAddr(num),
         _ => {
-            println!("Invalid block number. Must be between 0 and 63.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("err-invalid-block-number-must-be"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     };
     
     // Show warnings for special blocks
-    if block_number == 0 {
-        println!("\nWARNING: Block 0 contains manufacturer data and card UID.");
-        println!("Writing to this block may brick your card permanently!");
+    if block_number.is_manufacturer() {
+        println!("\n{}", tr("warning-block-0-contains-manufacturer"));
+        println!("{}", tr("writing-to-this-block-may"));
         
-        let confirm = wait_for_input("\nAre you ABSOLUTELY sure? (type YES in uppercase): ")?;
+        let confirm = wait_for_input(&format!("\n{} ", tr("are-you-absolutely-sure-type")))?;
         if confirm != "YES" {
-            println!("Operation cancelled.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("operation-cancelled"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
-    } else if block_number % 4 == 3 {
-        println!("\nWARNING: Block {} is a sector trailer containing access bits and keys.", block_number);
-        println!("Incorrect values may lock the sector or the entire card permanently!");
+    } else if block_number.is_trailer() {
+        println!("\n{}", tr_args("warning-block-is-sector-trailer-2", &[("block", &block_number.to_string())]));
+        println!("{}", tr("incorrect-values-may-lock-sector"));
         
-        let confirm = wait_for_input("\nAre you sure you want to continue? (y/n): ")?.to_lowercase();
+        let confirm = wait_for_input(&format!("\n{} ", tr("are-you-sure-you-want")))?.to_lowercase();
         if confirm != "y" {
-            println!("Operation cancelled.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("operation-cancelled"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
         
@@ -382,17 +556,17 @@ fn write_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
     }
     
     // Get data entry method
-    println!("\nChoose data format:");
-    println!("1. Text (will be padded to 16 bytes)");
-    println!("2. Hexadecimal (must be exactly 32 hex chars)");
+    println!("\n{}", tr("choose-data-format"));
+    println!("1. {}", tr("text-will-be-padded-to"));
+    println!("2. {}", tr("hexadecimal-must-be-exactly-32"));
     
-    let format_choice = wait_for_input("\nEnter choice (1-2): ")?;
+    let format_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-2")))?;
     
     let mut data = Vec::new();
     
     match format_choice.as_str() {
         "1" => {
-            let text = wait_for_input("\nEnter text (max 16 chars): ")?;
+            let text = wait_for_input(&format!("\n{} ", tr("enter-text-max-16-chars")))?;
             data = text.as_bytes()[0..std::cmp::min(text.len(), 16)].to_vec();
             
             // Pad with zeros to 16 bytes
@@ -401,50 +575,50 @@ fn write_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
             }
         },
         "2" => {
-            let hex = wait_for_input("\nEnter hex data (32 chars): ")?;
+            let hex = wait_for_input(&format!("\n{} ", tr("enter-hex-data-32-chars")))?;
             match hex_string_to_bytes(&hex) {
                 Some(bytes) if bytes.len() == 16 => {
                     data = bytes;
                 },
                 _ => {
-                    println!("Invalid hex data. Must be exactly 16 bytes (32 hex chars).");
-                    wait_for_input("\nPress Enter to continue...")?;
+                    println!("{}", tr("err-invalid-hex-data-must-be"));
+                    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
                     return Ok(());
                 }
             }
         },
         _ => {
-            println!("Invalid choice.");
-            wait_for_input("\nPress Enter to continue...")?;
+            println!("{}", tr("err-invalid-choice"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     }
     
     // Get authentication key
-    println!("\nSelect authentication method:");
-    println!("1. Key A (default: FFFFFFFFFFFF)");
-    println!("2. Key B (default: FFFFFFFFFFFF)");
+    println!("\n{}", tr("select-authentication-method"));
+    println!("1. {}", tr("key-default-ffffffffffff"));
+    println!("2. {}", tr("key-b-default-ffffffffffff"));
     
-    let key_choice = wait_for_input("\nEnter choice (1-2): ")?;
+    let key_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-2")))?;
     
     let auth_mode = match key_choice.as_str() {
         "1" => PICC_AUTHENT1A,
         "2" => PICC_AUTHENT1B,
         _ => {
-            println!("Invalid choice. Using Key A by default.");
+            println!("{}", tr("err-invalid-choice-using-key-by"));
             PICC_AUTHENT1A
         }
     };
     
     // Ask for custom key if needed
-    let use_custom_key = wait_for_input("\nUse custom key? (y/n): ")?.to_lowercase();
+    let use_custom_key = wait_for_input(&format!("\n{} ", tr("use-custom-key-y-n")))?.to_lowercase();
     
     let key = if use_custom_key == "y" {
-        let key_str = wait_for_input("Enter key (12 hex chars): ")?;
+        let key_str = wait_for_input(&format!("{} ", tr("enter-key-12-hex-chars-2")))?;
         match hex_string_to_bytes(&key_str) {
             Some(bytes) if bytes.len() == 6 => bytes,
             _ => {
-                println!("Invalid key format. Using default key.");
+                println!("{}", tr("err-invalid-key-format-using-default"));
                 [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
             }
         }
@@ -453,14 +627,14 @@ fn write_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
     };
     
     // Show data to be written
-    println!("\nData to be written:");
-    println!("HEX: {}", bytes_to_hex(&data));
-    println!("ASCII: {}", bytes_to_ascii(&data));
+    println!("\n{}", tr("data-to-be-written"));
+    println!("{}", tr_args("block-hex", &[("hex", &bytes_to_hex(&data))]));
+    println!("{}", tr_args("block-ascii", &[("ascii", &bytes_to_ascii(&data))]));
     
-    let confirm = wait_for_input("\nConfirm write? (y/n): ")?.to_lowercase();
+    let confirm = wait_for_input(&format!("\n{} ", tr("confirm-write-y-n")))?.to_lowercase();
     if confirm != "y" {
-        println!("Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("{}", tr("operation-cancelled"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
@@ -469,80 +643,91 @@ fn write_block_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
     // Request tag and get UID
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        println!("\nError: Could not detect card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-detect-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Anti-collision and get UID
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        println!("\nError: Could not read card UID.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-read-card-uid"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
-    println!("\nCard detected. UID: {}", uid_to_string(&uid));
+    println!("\n{}", tr_args("card-detected-uid", &[("uid", &uid_to_string(&uid))]));
     
     // Select card
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        println!("\nError: Could not select card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-select-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Authenticate
-    let status = mfrc522_auth(spi, auth_mode, block_number, &key, &uid)?;
+    let status = mfrc522_auth(spi, auth_mode, block_number.get(), &key, &uid)?;
     if status != MI_OK {
-        println!("\nAuthentication failed. Try a different key.");
+        println!("\n{}", tr("authentication-failed-try-different-key"));
         mfrc522_stop_crypto1(spi)?;
-        wait_for_input("\nPress Enter to continue...")?;
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
+    // Data blocks are stored encrypted for this card when data encryption is on
+    if let Some(cipher) = data_key.and_then(|data_key| data_key.cipher_for(&uid)) {
+        if is_data_block(block_number) {
+            let mut block = [0u8; 16];
+            block.copy_from_slice(&data);
+            data = cipher.encrypt_block(block_number, &block).to_vec();
+            println!("{}", tr_args("block-encrypted", &[("hex", &bytes_to_hex(&data))]));
+        }
+    }
+    
     // Write the block
-    let write_status = mfrc522_write(spi, block_number, &data)?;
+    let write_status = guarded_write(spi, block_number, &data)?;
     if write_status == MI_OK {
-        println!("\nBlock written successfully!");
+        println!("\n{}", tr("block-written-successfully"));
+        read_back(spi, block_number, data.as_slice().try_into()?)?.print();
     } else {
-        println!("\nError writing block. Check access rights.");
+        println!("\n{}", tr("err-writing-block-check-access-rights"));
     }
     
     mfrc522_stop_crypto1(spi)?;
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Write Sector Trailer Menu (special handling for block 3, 7, 11, etc.)
-fn write_sector_trailer_menu(spi: &mut Spi, block_number: u8) -> Result<(), Box<dyn Error>> {
+fn write_sector_trailer_menu(spi: &mut SpiReader, block_number: BlockAddr) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("WRITE SECTOR TRAILER");
+    println!("{}", tr("write-sector-trailer"));
     println!("===================");
-    println!("\nBlock: {} (Sector {})", block_number, block_number / 4);
+    println!("\n{}", tr_args("block-sector", &[("block", &block_number.to_string()), ("sector", &block_number.sector().to_string())]));
     
     // Get Key A
-    let key_a_str = wait_for_input("\nEnter Key A (12 hex chars, default FFFFFFFFFFFF): ")?;
+    let key_a_str = wait_for_input(&format!("\n{} ", tr("enter-key-12-hex-chars")))?;
     let key_a = if key_a_str.is_empty() {
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        Key([0xFF; 6])
     } else {
-        match hex_string_to_bytes(&key_a_str) {
-            Some(bytes) if bytes.len() == 6 => bytes,
-            _ => {
-                println!("Invalid key format. Using default key.");
-                [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        match Key::from_hex(&key_a_str) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
+                Key([0xFF; 6])
             }
         }
     };
     
     // Choose access bits configuration
-    println!("\nSelect access bits configuration:");
-    println!("1. Transport (all open, default)");
-    println!("2. Secure (read with Key A, write with Key B)");
-    println!("3. Read-only (no writes allowed)");
-    println!("4. Custom (advanced)");
+    println!("\n{}", tr("select-access-bits-configuration"));
+    println!("1. {}", tr("transport-all-open-default"));
+    println!("2. {}", tr("secure-read-with-key-write"));
+    println!("3. {}", tr("read-only-no-writes-allowed"));
+    println!("4. {}", tr("custom-advanced"));
     
-    let access_choice = wait_for_input("\nEnter choice (1-4): ")?;
+    let access_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-4")))?;
     
     let access_bits = match access_choice.as_str() {
         "1" => AccessBits::get_predefined_config("transport"),
@@ -550,81 +735,81 @@ fn write_sector_trailer_menu(spi: &mut Spi, block_number: u8) -> Result<(), Box<
         "3" => AccessBits::get_predefined_config("readonly"),
         "4" => {
             // TODO: Implement custom access bits configuration
-            println!("Custom access bits not implemented yet. Using transport configuration.");
+            println!("{}", tr("custom-access-bits-not-implemented-2"));
             AccessBits::get_predefined_config("transport")
         },
         _ => {
-            println!("Invalid choice. Using transport configuration.");
+            println!("{}", tr("err-invalid-choice-using-transport-configuration"));
             AccessBits::get_predefined_config("transport")
         }
     };
     
     // Get Key B
-    let key_b_str = wait_for_input("\nEnter Key B (12 hex chars, default FFFFFFFFFFFF): ")?;
+    let key_b_str = wait_for_input(&format!("\n{} ", tr("enter-key-b-12-hex")))?;
     let key_b = if key_b_str.is_empty() {
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        Key([0xFF; 6])
     } else {
-        match hex_string_to_bytes(&key_b_str) {
-            Some(bytes) if bytes.len() == 6 => bytes,
-            _ => {
-                println!("Invalid key format. Using default key.");
-                [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        match Key::from_hex(&key_b_str) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
+                Key([0xFF; 6])
             }
         }
     };
     
     // Get authentication key for current sector trailer
-    println!("\nAuthentication needed for current sector trailer.");
-    println!("1. Use Key A (default: FFFFFFFFFFFF)");
-    println!("2. Use Key B (default: FFFFFFFFFFFF)");
+    println!("\n{}", tr("authentication-needed-for-current-sector"));
+    println!("1. {}", tr("use-key-default-ffffffffffff"));
+    println!("2. {}", tr("use-key-b-default-ffffffffffff"));
     
-    let key_choice = wait_for_input("\nEnter choice (1-2): ")?;
+    let key_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-2")))?;
     
     let auth_mode = match key_choice.as_str() {
         "1" => PICC_AUTHENT1A,
         "2" => PICC_AUTHENT1B,
         _ => {
-            println!("Invalid choice. Using Key A by default.");
+            println!("{}", tr("err-invalid-choice-using-key-by"));
             PICC_AUTHENT1A
         }
     };
     
     // Ask for custom key if needed
-    let use_custom_key = wait_for_input("\nUse custom key? (y/n): ")?.to_lowercase();
+    let use_custom_key = wait_for_input(&format!("\n{} ", tr("use-custom-key-y-n")))?.to_lowercase();
     
     let auth_key = if use_custom_key == "y" {
-        let key_str = wait_for_input("Enter key (12 hex chars): ")?;
-        match hex_string_to_bytes(&key_str) {
-            Some(bytes) if bytes.len() == 6 => bytes,
-            _ => {
-                println!("Invalid key format. Using default key.");
-                [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        let key_str = wait_for_input(&format!("{} ", tr("enter-key-12-hex-chars-2")))?;
+        match Key::from_hex(&key_str) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
+                Key([0xFF; 6])
             }
         }
     } else {
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        Key([0xFF; 6])
     };
     
     // Construct the trailer data
     let access_bytes = access_bits.to_bytes();
     
     let mut trailer_data = [0u8; 16];
-    trailer_data[0..6].copy_from_slice(&key_a);
+    trailer_data[0..6].copy_from_slice(key_a.as_bytes());
     trailer_data[6..10].copy_from_slice(&access_bytes);
-    trailer_data[10..16].copy_from_slice(&key_b);
+    trailer_data[10..16].copy_from_slice(key_b.as_bytes());
     
     // Show trailer data to be written
-    println!("\nTrailer data to be written:");
-    println!("Key A: {}", bytes_to_hex(&key_a));
-    println!("Access Bits: {}", bytes_to_hex(&access_bytes));
-    println!("Key B: {}", bytes_to_hex(&key_b));
-    println!("\nAccess conditions:");
+    println!("\n{}", tr("trailer-data-to-be-written"));
+    println!("{}", tr_args("key-a-hex", &[("hex", &bytes_to_hex(key_a.as_bytes()))]));
+    println!("{}", tr_args("access-bits-hex", &[("hex", &bytes_to_hex(&access_bytes))]));
+    println!("{}", tr_args("key-b-hex", &[("hex", &bytes_to_hex(key_b.as_bytes()))]));
+    println!("\n{}", tr("access-conditions-2"));
     println!("{}", access_bits);
     
-    let confirm = wait_for_input("\nConfirm write? (y/n): ")?.to_lowercase();
+    let confirm = wait_for_input(&format!("\n{} ", tr("confirm-write-y-n")))?.to_lowercase();
     if confirm != "y" {
-        println!("Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("{}", tr("operation-cancelled"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
@@ -633,266 +818,1100 @@ fn write_sector_trailer_menu(spi: &mut Spi, block_number: u8) -> Result<(), Box<
     // Request tag and get UID
     let (status, _) = mfrc522_request(spi, PICC_REQIDL)?;
     if status != MI_OK {
-        println!("\nError: Could not detect card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-detect-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Anti-collision and get UID
     let (status, uid) = mfrc522_anticoll(spi)?;
     if status != MI_OK {
-        println!("\nError: Could not read card UID.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-read-card-uid"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
-    println!("\nCard detected. UID: {}", uid_to_string(&uid));
+    println!("\n{}", tr_args("card-detected-uid", &[("uid", &uid_to_string(&uid))]));
     
     // Select card
     let size = mfrc522_select_tag(spi, &uid)?;
     if size == 0 {
-        println!("\nError: Could not select card.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("\n{}", tr("err-could-not-select-card"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Authenticate
-    let status = mfrc522_auth(spi, auth_mode, block_number, &auth_key, &uid)?;
+    let status = mfrc522_auth(spi, auth_mode, block_number.get(), auth_key.as_bytes(), &uid)?;
     if status != MI_OK {
-        println!("\nAuthentication failed. Try a different key.");
+        println!("\n{}", tr("authentication-failed-try-different-key"));
         mfrc522_stop_crypto1(spi)?;
-        wait_for_input("\nPress Enter to continue...")?;
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
-    // Write the trailer
-    let write_status = mfrc522_write(spi, block_number, &trailer_data)?;
-    if write_status == MI_OK {
-        println!("\nSector trailer written successfully!");
-    } else {
-        println!("\nError writing sector trailer. Check access rights.");
-    }
+    // Write the trailer, read it back and retry when it didn't go through
+    let outcome = write_trailer(spi, &uid, block_number, &trailer_data, auth_mode, &auth_key)?;
+    report_trailer_write(spi, outcome, block_number, &trailer_data, auth_mode, &auth_key)?;
     
     mfrc522_stop_crypto1(spi)?;
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
+// Tell what a trailer write left on the card. When the card left the reader during the
+// write, it is checked again once it is put back. Returns whether the trailer is written.
+fn report_trailer_write(spi: &mut SpiReader, mut outcome: TrailerWrite, block: BlockAddr, trailer: &[u8; 16], auth_mode: u8, key: &Key)
+    -> Result<bool, Box<dyn Error>> {
+    let sector = block.sector();
+    loop {
+        println!("\n{}", outcome.guidance(sector));
+        if outcome != TrailerWrite::CardGone {
+            return Ok(outcome.is_written());
+        }
+        if wait_for_input(&format!("\n{} ", tr("put-card-back-and-press")))?.to_lowercase() == "q" {
+            return Ok(false);
+        }
+        outcome = resume_trailer(spi, block, trailer, auth_mode, key)?;
+    }
+}
+
 // Dump Card Menu
-fn dump_card_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn dump_card_menu(spi: &mut SpiReader, key_store: &mut KeyStore, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("DUMP CARD");
+    println!("{}", tr("title-dump-card"));
     println!("=========");
     
-    let confirm = wait_for_input("\nDump entire card? This may take a while. Continue? (y/n): ")?.to_lowercase();
+    println!("\n1. {}", tr("dump-card-optionally-save-to"));
+    if !safe_mode::is_read_only() {
+        println!("2. {}", tr("write-dump-file-to-card"));
+        println!("   {}", tr("files-flipper-zero-nfc-or"));
+    }
+    
+    match wait_for_input(&format!("\n{} ", tr("enter-your-choice")))?.as_str() {
+        "1" => dump_to_file_menu(spi, key_store, data_key),
+        "2" if safe_mode::is_read_only() => {
+            safe_mode::refuse();
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            Ok(())
+        },
+        "2" => write_dump_menu(spi, key_store, data_key),
+        _ => Ok(()),
+    }
+}
+
+fn dump_to_file_menu(spi: &mut SpiReader, key_store: &mut KeyStore, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
+    let selection = ask_selection(true)?;
+    let confirm = wait_for_input(&format!("\n{} ", tr_args("dump-this-may-take-while", &[("selection", &selection.describe().to_string())])))?.to_lowercase();
     if confirm != "y" {
         return Ok(());
     }
     
+    let profile = choose_profile(&tr("profile-prompt-annotate"))?;
+    
     countdown_for_card_placement(5)?;
     
-    match dump_card(spi)? {
-        Some(_) => {
-            // Card dump was successful, output is already printed by the dump_card function
+    if let Some(profile) = &profile {
+        expect_profile_keys(spi, key_store, profile)?;
+    }
+    match dump_card(spi, key_store, profile.as_ref(), &selection)? {
+        Some(mut dump) => {
+            // The blocks are already printed by dump_card
+            if let Some(profile) = &profile {
+                print_decoded(profile, &dump, Some(&selection.sectors(dump.layout())));
+            }
+            println!("\n{}", tr_args("of-blocks-read", &[("known", &dump.known_blocks().to_string()), ("total", &dump.blocks.len().to_string())]));
+            
+            // The dump keeps the blocks as stored unless asked for the plain data
+            if let Some(cipher) = data_key.and_then(|data_key| data_key.cipher_for(&dump.uid)) {
+                if wait_for_input(&format!("\n{} ", tr("decrypt-data-blocks-with-data")))?.to_lowercase() == "y" {
+                    decrypt_dump(&mut dump, &cipher);
+                }
+            }
+            
+            let path = wait_for_input(&format!("\n{} ", tr("save-to-file-nfc-for")))?;
+            if !path.is_empty() {
+                match save_dump_file(&path, &dump) {
+                    Ok(()) => {
+                        println!("{}", tr_args("dump-saved-to", &[("path", &path.to_string())]));
+                        sign_dump_prompt(&path)?;
+                    },
+                    Err(e) => println!("{}", tr_args("err-save-dump", &[("error", &e.to_string())])),
+                }
+            }
         },
         None => {
-            println!("\nError dumping card.");
+            println!("\n{}", tr("err-dumping-card"));
         }
     }
     
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+    Ok(())
+}
+
+// Offer to sign a saved dump so whoever receives it can check it wasn't changed
+fn sign_dump_prompt(path: &str) -> Result<(), Box<dyn Error>> {
+    if wait_for_input(&format!("{} ", tr("sign-dump-y-n")))?.to_lowercase() != "y" {
+        return Ok(());
+    }
+    
+    let key_path = signing::default_key_path();
+    match sign_file(Path::new(path), &key_path) {
+        Ok(sig_path) => {
+            println!("{}", tr_args("signature-saved-to", &[("path", &sig_path.display().to_string())]));
+            if let Ok(public_key) = signing::public_key(&key_path) {
+                println!("{}", tr_args("public-key-to-give-recipient", &[("public_key", &public_key.to_string())]));
+            }
+        },
+        Err(e) => println!("{}", tr_args("err-sign-dump", &[("error", &e.to_string())])),
+    }
+    Ok(())
+}
+
+fn write_dump_menu(spi: &mut SpiReader, key_store: &mut KeyStore, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
+    let path = wait_for_input(&format!("\n{} ", tr("dump-file-to-write")))?;
+    if path.is_empty() {
+        return Ok(());
+    }
+    
+    let mut dump = match load_dump_file(&path) {
+        Ok(dump) => dump,
+        Err(e) => {
+            println!("{}", tr_args("err-load", &[("path", &path.to_string()), ("error", &e.to_string())]));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        }
+    };
+    
+    // A signed dump is checked before it goes on a card
+    if signing::signature_path(Path::new(&path)).exists() {
+        match verify_file(Path::new(&path)) {
+            Ok(public_key) => println!("{}", tr_args("signature-valid-signed-by-key", &[("public_key", &public_key.to_string())])),
+            Err(e) => {
+                println!("{}", tr_args("signature-of-doesnt-hold", &[("path", &path.to_string()), ("error", &e.to_string())]));
+                if wait_for_input(&format!("{} ", tr("write-it-anyway-y-n")))?.to_lowercase() != "y" {
+                    return Ok(());
+                }
+            }
+        }
+    }
+    
+    println!("\n{}", tr_args("dump-of-card-of-blocks", &[("uid", &uid_to_string(&dump.uid)), ("known", &dump.known_blocks().to_string()), ("total", &dump.blocks.len().to_string())]));
+    println!("{}", tr_args("dump-target-card-needs-to", &[("layout", dump.layout().name())]));
+    
+    println!("\n{}", tr("keys-for-target-card-hex"));
+    let key_input = wait_for_input(&format!("{} ", tr("or-enter-for-default-keys")))?;
+    let keys = if key_input.is_empty() {
+        DEFAULT_KEYS.to_vec()
+    } else if Path::new(&key_input).is_file() {
+        match load_key_file(&key_input) {
+            Ok(keys) => {
+                println!("{}", tr_args("keys-loaded-from", &[("count", &keys.len().to_string()), ("source", &key_input.to_string())]));
+                keys
+            },
+            Err(e) => {
+                println!("{}", tr_args("err-load-keys", &[("error", &e.to_string())]));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    } else {
+        match Key::from_hex(&key_input) {
+            Some(key) => vec![key],
+            None => {
+                println!("{}", tr("err-invalid-key-it-must-be"));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    };
+    // A card being restored from its own dump still has the dump's keys
+    key_store.add_keys(&keys);
+    key_store.add_keys(&dump.trailer_keys());
+    
+    let selection = ask_selection(true)?;
+    println!("\n{}", tr("block-0-holds-uid-and"));
+    let include_block0 = selection.has_block(BlockAddr(0)) && wait_for_input(&format!("{} ", tr("write-block-0-y-n")))?.to_lowercase() == "y";
+    println!("{}", tr("sector-trailers-set-keys-and"));
+    let include_trailers = wait_for_input(&format!("{} ", tr("write-sector-trailers-y-n")))?.to_lowercase() == "y";
+    // A plain dump used as a template is encrypted for each card it is written to
+    let encrypt = match data_key {
+        Some(data_key) if data_key.enabled => {
+            wait_for_input(&format!("{} ", tr("encrypt-data-blocks-with-data")))?.to_lowercase() == "y"
+        },
+        _ => false,
+    };
+    
+    let confirm = wait_for_input(&format!("\n{} ", tr_args("this-overwrites-data-in-of", &[("selection", &selection.describe().to_string())])))?.to_lowercase();
+    if confirm != "y" {
+        return Ok(());
+    }
+    
+    countdown_for_card_placement(5)?;
+    
+    if encrypt {
+        let cipher = match read_card_uid(spi)? {
+            Some(uid) => data_key.and_then(|data_key| data_key.cipher_for(&uid)),
+            None => None,
+        };
+        match cipher {
+            Some(cipher) => encrypt_dump(&mut dump, &cipher),
+            None => {
+                println!("\n{}", tr("no-card-found-to-encrypt"));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    }
+    
+    let (written, failed) = write_dump(spi, &dump, key_store, include_block0, include_trailers, &selection)?;
+    println!("\n{}", tr_args("blocks-written", &[("count", &written.len().to_string())]));
+    if !failed.is_empty() {
+        let blocks: Vec<String> = failed.iter().map(|b| b.to_string()).collect();
+        println!("{}", tr_args("err-failed-blocks", &[("blocks", &blocks.join(", "))]));
+    }
+    let expected: Vec<(BlockAddr, [u8; 16])> = written.iter()
+        .filter_map(|block| dump.block(*block).map(|data| (*block, data)))
+        .collect();
+    verify_written(spi, key_store, &expected)?;
+    
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Block Editor Menu
-fn block_editor_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn block_editor_menu(spi: &mut SpiReader, data_key: Option<&DataKey>) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("BLOCK EDITOR");
+    println!("{}", tr("block-editor"));
     println!("============");
     
     // Launch interactive block editor
-    crate::lib::mifare::block_editor::interactive_edit(spi)?;
+    crate::lib::mifare::block_editor::interactive_edit(spi, data_key)?;
     
     Ok(())
 }
 
 // Test Keys Menu
-fn test_keys_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn test_keys_menu(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("TEST KEYS");
+    println!("{}", tr("title-test-keys"));
     println!("=========");
     
-    println!("This will test multiple keys against all sectors of your card.");
-    println!("This process may take some time.");
+    println!("{}", tr("this-will-test-multiple-keys"));
+    println!("{}", tr("this-process-may-take-some"));
     
-    let confirm = wait_for_input("\nProceed? (y/n): ")?.to_lowercase();
+    let selection = ask_selection(false)?;
+    let confirm = wait_for_input(&format!("\n{} ", tr_args("test-y-n", &[("selection", &selection.describe().to_string())])))?.to_lowercase();
     if confirm != "y" {
         return Ok(());
     }
     
     countdown_for_card_placement(5)?;
     
-    match crate::lib::mifare::dump::test_keys(spi) {
+    match crate::lib::mifare::dump::test_keys(spi, &selection) {
         Ok(results) => {
-            println!("\nKey Testing Results:");
+            println!("\n{}", tr("key-testing-results"));
             println!("====================");
             
             if results.is_empty() {
-                println!("No working keys found for any sector.");
+                println!("{}", tr("no-working-keys-found-for"));
             } else {
-                for (sector, key) in results {
-                    println!("Sector {}: Key {}", sector, crate::lib::utils::bytes_to_hex(&key));
+                for (sector, key) in &results {
+                    println!("{}", tr_args("sector-key", &[("sector", &sector.to_string()), ("hex", &crate::lib::utils::bytes_to_hex(key.as_bytes()).to_string())]));
+                }
+                
+                let path = wait_for_input(&format!("\n{} ", tr("save-keys-to-key-file")))?;
+                if !path.is_empty() {
+                    let mut keys: Vec<Key> = Vec::new();
+                    for (_, key) in &results {
+                        if !keys.contains(key) {
+                            keys.push(*key);
+                        }
+                    }
+                    match save_key_file(&path, &keys) {
+                        Ok(()) => println!("{}", tr_args("keys-saved-to", &[("count", &keys.len().to_string()), ("path", &path.to_string())])),
+                        Err(e) => println!("{}", tr_args("err-save-keys", &[("error", &e.to_string())])),
+                    }
                 }
             }
         },
         Err(e) => {
-            println!("Error testing keys: {}", e);
+            println!("{}", tr_args("err-testing-keys", &[("error", &e.to_string())]));
         }
     }
     
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
-// Format Card Menu
-fn format_card_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+// Keys tried on every card, with the key files they were imported from
+fn key_store_menu(key_store: &mut KeyStore, path: &Path) -> Result<(), Box<dyn Error>> {
+    loop {
+        clear_screen();
+        println!("{}", tr("key-store"));
+        println!("=========");
+        println!("{}\n", tr_args("imported-keys-are-saved-to", &[("path", &path.display().to_string())]));
+        
+        for (number, key) in key_store.keys().iter().enumerate() {
+            let sources = key_store.sources(key);
+            let origin = if !sources.is_empty() {
+                sources.join("\n                   ")
+            } else if DEFAULT_KEYS.contains(key) {
+                tr("key-origin-default")
+            } else {
+                tr("key-origin-this-run")
+            };
+            println!("{:3}. {}  {}", number + 1, key, origin);
+        }
+        
+        println!("\n1. {}", tr("import-key-file-key-list"));
+        println!("2. {}", tr("export-keys-as-key-list"));
+        println!("0. {}", tr("menu-back"));
+        
+        match wait_for_input(&format!("\n{} ", tr("enter-your-choice")))?.as_str() {
+            "1" => {
+                let file = wait_for_input(&format!("\n{} ", tr("key-file-to-import")))?;
+                if file.is_empty() {
+                    continue;
+                }
+                let import = match import_key_file(&file) {
+                    Ok(import) => import,
+                    Err(e) => {
+                        println!("{}", tr_args("err-import", &[("file", &file.to_string()), ("error", &e.to_string())]));
+                        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                        continue;
+                    }
+                };
+                let (added, known) = key_store.import(&import);
+                println!("\n{}", tr_args("keys-read-from-new-already", &[("count", &import.keys.len().to_string()), ("file", &import.file.to_string()), ("format", import.format.name()), ("added", &added.to_string()), ("known", &known.to_string())]));
+                match key_store.save(path) {
+                    Ok(()) => println!("{}", tr("key-store-saved")),
+                    Err(e) => println!("{}", tr_args("err-save-key-store", &[("error", &e.to_string())])),
+                }
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            },
+            "2" => {
+                let file = wait_for_input(&format!("\n{} ", tr("save-key-list-to")))?;
+                if !file.is_empty() {
+                    match save_key_file(&file, key_store.keys()) {
+                        Ok(()) => println!("{}", tr_args("keys-saved-to-2", &[("count", &key_store.keys().len().to_string()), ("file", &file.to_string())])),
+                        Err(e) => println!("{}", tr_args("err-save-keys", &[("error", &e.to_string())])),
+                    }
+                    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                }
+            },
+            _ => return Ok(()),
+        }
+    }
+}
+
+// Built-in and saved card profiles, with their layouts and guided reads and writes
+fn profiles_menu(spi: &mut SpiReader, key_store: &mut KeyStore) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("FORMAT CARD");
+    println!("{}", tr("title-card-profiles"));
+    println!("=============");
+    println!("{}", tr("profiles-describe-what-common-cards"));
+    println!("{}", tr_args("add-your-own-as-profile", &[("path", &profile_dir().display().to_string())]));
+    
+    match choose_profile(&tr("profile-prompt"))? {
+        Some(profile) => profile_menu(spi, key_store, &profile),
+        None => Ok(()),
+    }
+}
+
+// Ask for one of the profiles, None when there are none or none is picked
+fn choose_profile(prompt: &str) -> Result<Option<CardProfile>, Box<dyn Error>> {
+    let (mut profiles, problems) = load_profiles();
+    for problem in &problems {
+        println!("{}", tr_args("skipped-profile", &[("problem", &problem.to_string())]));
+    }
+    if profiles.is_empty() {
+        return Ok(None);
+    }
+    
+    println!();
+    for (number, profile) in profiles.iter().enumerate() {
+        println!("{}. {} ({})", number + 1, profile.name, profile.kind.name());
+    }
+    let choice = wait_for_input(&format!("\n{} ", tr_args("number-or-enter-for-none", &[("prompt", prompt)])))?;
+    match choice.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= profiles.len() => Ok(Some(profiles.swap_remove(number - 1))),
+        _ => Ok(None),
+    }
+}
+
+// The profile's keys for the card on the reader, derived ones need its UID
+fn expect_profile_keys(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile) -> Result<(), Box<dyn Error>> {
+    // Anticollision returns the UID followed by its check byte
+    let uid = read_card_uid(spi)?.map(|uid| uid[..uid.len().min(4)].to_vec()).unwrap_or_default();
+    profile.expect_keys(key_store, &uid);
+    Ok(())
+}
+
+fn profile_menu(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile) -> Result<(), Box<dyn Error>> {
+    // Writes are left out in read-only mode
+    let workflows: Vec<&Workflow> = profile.workflows.iter()
+        .filter(|workflow| !safe_mode::is_read_only() || matches!(workflow, Workflow::Read { .. }))
+        .collect();
+    
+    loop {
+        clear_screen();
+        println!("{}", profile.name.to_uppercase());
+        println!("{}", "=".repeat(profile.name.len()));
+        println!("{}", tr_args("profile-card-kind", &[("kind", profile.kind.name()), ("source", &profile.source.to_string())]));
+        if !profile.description.is_empty() {
+            println!("\n{}", profile.description);
+        }
+        
+        println!("\n{}", tr("profile-layout"));
+        for (sector, label) in &profile.sectors {
+            println!("  {}", tr_args("profile-sector-label", &[("sector", &sector.to_string()), ("label", &label.to_string())]));
+            for (block, label) in profile.blocks.iter().filter(|(block, _)| block.sector() == *sector) {
+                println!("    {}", tr_args("profile-block-label", &[("block", &block.to_string()), ("label", &label.to_string())]));
+            }
+        }
+        println!("\n{}", tr("profile-keys"));
+        for profile_key in &profile.keys {
+            let key_type = if profile_key.auth_type == PICC_AUTHENT1A { "A" } else { "B" };
+            let sectors = match (profile_key.sectors.first(), profile_key.sectors.last()) {
+                (Some(first), Some(last)) if first != last => tr_args("selection-sectors", &[("sectors", &format!("{}-{}", first, last))]),
+                (Some(first), _) => tr_args("selection-sector", &[("sector", &first.to_string())]),
+                _ => continue,
+            };
+            println!("  {}", tr_args("profile-key", &[("sectors", &sectors.to_string()), ("key_type", key_type), ("spec", &profile_key.spec.describe().to_string())]));
+        }
+        if let Some((record, data)) = profile.journal.split_first() {
+            println!("\n{}", tr_args("journal-commit-record-in-block", &[("record", &record.to_string()), ("blocks", &block_list(data).to_string())]));
+        }
+        
+        println!();
+        for (number, workflow) in workflows.iter().enumerate() {
+            println!("{}. {}", number + 1, workflow.title());
+        }
+        println!("0. {}", tr("menu-back"));
+        
+        let choice = wait_for_input(&format!("\n{} ", tr("enter-your-choice")))?;
+        let workflow = match choice.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= workflows.len() => workflows[number - 1],
+            _ => return Ok(()),
+        };
+        
+        let result = match workflow {
+            Workflow::Read { sectors, title } => profile_read(spi, key_store, profile, sectors, title),
+            Workflow::Write { block, title } => profile_write(spi, key_store, profile, *block, title),
+            Workflow::Balance { title } => profile_balance(spi, key_store, profile, title),
+        };
+        if let Err(e) = result {
+            println!("\n{}", tr_args("err-error", &[("error", &e.to_string())]));
+        }
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+    }
+}
+
+// Guided read: the profile's sectors, annotated
+fn profile_read(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile, sectors: &[SectorIndex], title: &str)
+    -> Result<(), Box<dyn Error>> {
+    println!("\n{}", title);
+    countdown_for_card_placement(3)?;
+    
+    expect_profile_keys(spi, key_store, profile)?;
+    finish_pending_update(spi, key_store, profile)?;
+    let dump = match read_card(spi, key_store)? {
+        Some(dump) => dump,
+        None => {
+            println!("{}", tr("no-card-found"));
+            return Ok(());
+        }
+    };
+    
+    println!("{}", tr_args("card-uid", &[("uid", &uid_to_string(&dump.uid))]));
+    for sector in sectors.iter().filter(|sector| dump.layout().has_sector(**sector)) {
+        print_sector(&dump, *sector, Some(profile));
+    }
+    print_decoded(profile, &dump, Some(sectors));
+    Ok(())
+}
+
+// Balances and records the profile knows how to decode
+fn print_decoded(profile: &CardProfile, dump: &CardDump, sectors: Option<&[SectorIndex]>) {
+    let lines = decode_card(profile, dump, sectors);
+    if !lines.is_empty() {
+        println!("\n{}", tr_args("decoded", &[("profile", &profile.name.to_string())]));
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+}
+
+// Guided write of one data block: show what the block holds now, then write the new data
+fn profile_write(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile, block: BlockAddr, title: &str)
+    -> Result<(), Box<dyn Error>> {
+    let label = profile.block_label(block).map_or_else(|| tr("profile-label-data"), str::to_string);
+    println!("\n{}", tr_args("profile-workflow-block", &[("title", title), ("block", &block.to_string()), ("label", &label)]));
+    countdown_for_card_placement(3)?;
+    
+    expect_profile_keys(spi, key_store, profile)?;
+    finish_pending_update(spi, key_store, profile)?;
+    let dump = match read_card(spi, key_store)? {
+        Some(dump) => dump,
+        None => {
+            println!("{}", tr("no-card-found"));
+            return Ok(());
+        }
+    };
+    match dump.block(block) {
+        Some(data) => println!("\n{}\n     {}", tr_args("block-now", &[("hex", &bytes_to_hex(&data))]), bytes_to_ascii(&data)),
+        None => {
+            println!("\n{}", tr_args("block-couldnt-be-read-none", &[("block", &block.to_string())]));
+            return Ok(());
+        }
+    }
+    
+    let input = wait_for_input(&format!("\n{} ", tr("new-content-text-up-to")))?;
+    if input.is_empty() {
+        return Ok(());
+    }
+    let data = match input.strip_prefix("hex:") {
+        Some(hex) => match hex_string_to_bytes(hex).filter(|data| data.len() == 16) {
+            Some(data) => data,
+            None => return Err(tr("err-hex-data-16-bytes").into()),
+        },
+        None => format_text_block(&input).to_vec(),
+    };
+    if wait_for_input(&format!("{} ", tr_args("write-to-block-y-n", &[("hex", &bytes_to_hex(&data)), ("block", &block.to_string())])))?.to_lowercase() != "y" {
+        return Ok(());
+    }
+    
+    let sector = block.sector();
+    for key in key_store.candidate_keys(sector) {
+        if write_block_raw(spi, block, &key, &data)? {
+            key_store.remember_key(sector, key);
+            println!("{}", tr_args("block-written", &[("block", &block.to_string())]));
+            return verify_written(spi, key_store, &[(block, data.as_slice().try_into()?)]);
+        }
+    }
+    println!("{}", tr_args("block-was-not-written-no", &[("block", &block.to_string())]));
+    Ok(())
+}
+
+// Guided balance change: the value blocks get their new amounts through the journal,
+// so they all change or none of them does
+fn profile_balance(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile, title: &str) -> Result<(), Box<dyn Error>> {
+    println!("\n{}", title);
+    countdown_for_card_placement(3)?;
+    
+    expect_profile_keys(spi, key_store, profile)?;
+    finish_pending_update(spi, key_store, profile)?;
+    let dump = match read_card(spi, key_store)? {
+        Some(dump) => dump,
+        None => {
+            println!("{}", tr("no-card-found"));
+            return Ok(());
+        }
+    };
+    
+    let unit = profile.currency.as_ref().map_or(String::new(), |currency| format!(" {}", tr_args("amount-unit", &[("unit", &currency.unit)])));
+    let mut updates = Vec::new();
+    for (block, name) in &profile.values {
+        let now = dump.block(*block)
+            .and_then(|data| value_from_block(&data))
+            .map_or_else(|| tr("value-unreadable"), |(value, _)| format_amount(profile, value as i64));
+        let input = wait_for_input(&format!("{} ", tr_args("block-is-new-amount-enter", &[("name", name), ("block", &block.to_string()), ("now", &now), ("unit", &unit)])))?;
+        if input.is_empty() {
+            continue;
+        }
+        match credit_test::parse_amount(profile, &input) {
+            Some(value) => updates.push((*block, value_block(value, block.get()))),
+            None => {
+                println!("{}", tr_args("err-invalid-amount", &[("input", &input.to_string())]));
+                return Ok(());
+            }
+        }
+    }
+    if updates.is_empty() {
+        return Ok(());
+    }
+    
+    let blocks: Vec<BlockAddr> = updates.iter().map(|(block, _)| *block).collect();
+    if wait_for_input(&format!("{} ", tr_args("write-blocks-keep-card-on", &[("blocks", &block_list(&blocks))])))?.to_lowercase() != "y" {
+        return Ok(());
+    }
+    let outcome = journaled_write(spi, key_store, &profile.journal, &updates)?;
+    println!("\n{}", outcome.guidance());
+    Ok(())
+}
+
+// An update the card was taken away during is finished before the card is read or
+// written with the profile. Read-only mode only reports it.
+fn finish_pending_update(spi: &mut SpiReader, key_store: &mut KeyStore, profile: &CardProfile) -> Result<(), Box<dyn Error>> {
+    if profile.journal.is_empty() {
+        return Ok(());
+    }
+    if safe_mode::is_read_only() {
+        if let Some(updates) = pending_update(spi, key_store, &profile.journal)? {
+            let blocks: Vec<BlockAddr> = updates.iter().map(|(block, _)| *block).collect();
+            println!("{}", tr_args("cards-journal-holds-unfinished-update", &[("blocks", &block_list(&blocks).to_string())]));
+        }
+        return Ok(());
+    }
+    if let Some(blocks) = recover_journal(spi, key_store, &profile.journal)? {
+        println!("{}", tr_args("finished-update-of-blocks-card", &[("blocks", &block_list(&blocks).to_string())]));
+    }
+    Ok(())
+}
+
+// "8, 9, 10"
+fn block_list(blocks: &[BlockAddr]) -> String {
+    blocks.iter().map(BlockAddr::to_string).collect::<Vec<String>>().join(", ")
+}
+
+// Program a magic card with a profile's values, then check what the system under test
+// changed each time the card is used there
+fn credit_test_menu(spi: &mut SpiReader, key_store: &mut KeyStore) -> Result<(), Box<dyn Error>> {
+    clear_screen();
+    println!("{}", tr("credit-test"));
     println!("===========");
+    println!("{}", tr("for-testing-your-own-closed"));
+    println!("{}", tr("magic-card-gets-profiles-balance"));
+    println!("{}", tr("then-is-read-back-to"));
+    
+    let profile = match choose_profile(&tr("profile-prompt-system"))? {
+        Some(profile) if !profile.values.is_empty() => profile,
+        Some(profile) => {
+            println!("\n{}", tr_args("has-no-value-blocks-to", &[("profile", &profile.name.to_string())]));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        },
+        None => return Ok(()),
+    };
+    let unit = profile.currency.as_ref().map_or(String::new(), |currency| format!(" {}", tr_args("amount-unit", &[("unit", &currency.unit)])));
+    
+    let mut values = Vec::new();
+    for (block, name) in &profile.values {
+        let input = wait_for_input(&format!("{} ", tr_args("block-to-start-with", &[("name", name), ("block", &block.to_string()), ("unit", &unit)])))?;
+        match credit_test::parse_amount(&profile, &input) {
+            Some(value) => values.push((*block, value)),
+            None => {
+                println!("{}", tr_args("err-invalid-amount", &[("input", &input.to_string())]));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    }
     
-    println!("\nWARNING: This will reset all sectors to default transport configuration.");
-    println!("All data will be lost. Sector 0 (manufacturer block) will not be modified.");
+    let uid_input = wait_for_input(&format!("{} ", tr("uid-to-give-card-8")))?;
+    let uid = if uid_input.is_empty() {
+        None
+    } else {
+        match hex_string_to_bytes(&uid_input).and_then(|uid| <[u8; 4]>::try_from(uid.as_slice()).ok()) {
+            Some(uid) => Some(uid),
+            None => {
+                println!("{}", tr("err-invalid-uid-it-must-be"));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    };
+    let write_keys = wait_for_input(&format!("{} ", tr("put-profiles-keys-in-sector")))?.to_lowercase() == "y";
+    let setup = CardSetup { values, uid, write_keys };
     
-    let confirm = wait_for_input("\nAre you sure you want to format the card? (type FORMAT to confirm): ")?;
-    if confirm != "FORMAT" {
-        println!("Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+    println!("\n{}", tr("this-overwrites-card-use-magic"));
+    if wait_for_input(&format!("{} ", tr("continue-y-n")))?.to_lowercase() != "y" {
         return Ok(());
     }
+    countdown_for_card_placement(5)?;
+    
+    let problems = credit_test::program_card(spi, key_store, &profile, &setup)?;
+    if problems.is_empty() {
+        println!("\n{}", tr("test-card-ready"));
+    } else {
+        println!("\n{}", tr("card-was-only-partly-set"));
+        for problem in &problems {
+            println!("  {}", problem);
+        }
+        if wait_for_input(&format!("{} ", tr("test-it-anyway-y-n")))?.to_lowercase() != "y" {
+            return Ok(());
+        }
+    }
+    
+    let mut before = setup.values;
+    for round in 1.. {
+        println!("\n{}", tr("use-card-at-system-under"));
+        if wait_for_input(&format!("{} ", tr("press-enter-when-it-is")))?.eq_ignore_ascii_case("q") {
+            break;
+        }
+        
+        let mut expected = Vec::new();
+        for (block, _) in &before {
+            let name = profile.values.iter().find(|(known, _)| known == block).map_or_else(|| tr("credit-value"), |(_, name)| name.clone());
+            let input = wait_for_input(&format!("{} ", tr_args("expected-change-of-e-g", &[("name", &name), ("unit", &unit)])))?;
+            expected.push((*block, credit_test::parse_amount(&profile, &input)));
+        }
+        
+        expect_profile_keys(spi, key_store, &profile)?;
+        let dump = match read_card(spi, key_store)? {
+            Some(dump) => dump,
+            None => {
+                println!("{}", tr("no-card-found-put-it"));
+                continue;
+            }
+        };
+        
+        let checks = credit_test::check_values(&profile, &before, &expected, &dump);
+        println!("\n{}", tr_args("credit-use-round", &[("round", &round.to_string())]));
+        for check in &checks {
+            let amount = |value: Option<i32>| value.map_or_else(|| tr("value-unreadable"), |value| format_amount(&profile, value as i64));
+            println!("  {}", tr_args("expected-change", &[("result", &format!("{:4}", tr(if check.passed() { "result-pass" } else { "result-fail" }))), ("check", &check.name.to_string()), ("amount", &amount(Some(check.before)).to_string()), ("amount2", &amount(check.after).to_string()), ("expected", &check.expected_change.map_or_else(|| tr("expected-change-any"), |change| format_amount(&profile, change as i64)))]));
+        }
+        print_decoded(&profile, &dump, None);
+        
+        let path = wait_for_input(&format!("\n{} ", tr("save-report-as-csv-enter")))?;
+        if !path.is_empty() {
+            match credit_test::save_report(&path, &checks) {
+                Ok(()) => println!("{}", tr_args("report-saved-to", &[("path", &path.to_string())])),
+                Err(e) => println!("{}", tr_args("err-save-report", &[("error", &e.to_string())])),
+            }
+        }
+        
+        // The next use starts from what the card holds now
+        for (value, check) in before.iter_mut().zip(&checks) {
+            if let Some(after) = check.after {
+                value.1 = after;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Format Card Menu
+fn format_card_menu(spi: &mut SpiReader, key_store: &mut KeyStore) -> Result<(), Box<dyn Error>> {
+    clear_screen();
+    println!("{}", tr("title-format-card"));
+    println!("===========");
+    
+    let (templates, problems) = load_templates();
+    for problem in &problems {
+        println!("{}", tr_args("skipped-template", &[("problem", &problem.to_string())]));
+    }
+    println!("\n{}", tr("format-with"));
+    for (number, template) in templates.iter().enumerate() {
+        println!("{}. {} ({})", number + 1, template.name, template.source);
+    }
+    println!("F. {}", tr("template-file"));
+    println!("N. {}", tr("new-template"));
+    println!("{}", tr_args("add-your-own-as-template", &[("path", &template_dir().display().to_string())]));
+    
+    let choice = wait_for_input(&format!("\n{} ", tr("enter-your-choice-enter-to")))?;
+    let template = match choice.to_uppercase().as_str() {
+        "F" => {
+            let file = wait_for_input(&format!("\n{} ", tr("template-file-2")))?;
+            load_template(Path::new(&file))?
+        },
+        "N" => new_template()?,
+        choice => match choice.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= templates.len() => templates[number - 1].clone(),
+            _ => return Ok(()),
+        },
+    };
+    
+    println!("\n{}", template.name);
+    if !template.description.is_empty() {
+        println!("{}", template.description);
+    }
+    println!("\n{}", tr("sector-key-access-key-b"));
+    for &sector in &template.sectors {
+        let trailer = template.trailer(sector);
+        let data_blocks = template.blocks(sector).iter()
+            .filter(|(block, data)| !block.is_trailer() && data.iter().any(|byte| *byte != 0))
+            .map(|(block, _)| block.to_string())
+            .collect::<Vec<String>>();
+        println!("{:>6}  {}  {}  {}  {}",
+            sector,
+            bytes_to_hex(&trailer[0..6]).replace(' ', ""),
+            bytes_to_hex(&trailer[6..10]).replace(' ', ""),
+            bytes_to_hex(&trailer[10..16]).replace(' ', ""),
+            if data_blocks.is_empty() { tr("template-cleared") } else { tr_args("selection-blocks", &[("blocks", &data_blocks.join(", "))]) });
+    }
+    
+    let selection = ask_selection(false)?;
+    println!("\n{}", tr_args("warning-all-data-in-of", &[("selection", &if selection.is_all() { tr("selection-all") } else { selection.describe() })]));
+    let confirm = wait_for_input(&format!("\n{} ", tr("type-format-to-format-card")))?;
+    match confirm.as_str() {
+        "FORMAT" => {},
+        "SAVE" => {
+            let default_path = template_dir().join(template.file_name());
+            let file = wait_for_input(&format!("{} ", tr_args("save-to-enter-for", &[("path", &default_path.display().to_string())])))?;
+            let path = if file.is_empty() { default_path } else { file.into() };
+            template.save(&path)?;
+            println!("{}", tr_args("template-saved-to", &[("path", &path.display().to_string())]));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        },
+        _ => {
+            println!("{}", tr("operation-cancelled"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        }
+    }
     
     countdown_for_card_placement(5)?;
     
-    if format_card(spi)? {
-        println!("\nCard formatted successfully.");
+    let formatted = format_with_template(spi, key_store, &template, &selection)?;
+    if !formatted.is_empty() {
+        println!("\n{}", tr("card-formatted-successfully"));
+        let expected: Vec<(BlockAddr, [u8; 16])> = formatted.iter().flat_map(|sector| template.blocks(*sector)).collect();
+        verify_written(spi, key_store, &expected)?;
     } else {
-        println!("\nError formatting card.");
+        println!("\n{}", tr("err-formatting-card"));
+    }
+    
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+    Ok(())
+}
+
+// Read the blocks just written back from the card and report the ones that differ
+fn verify_written(spi: &mut SpiReader, key_store: &mut KeyStore, written: &[(BlockAddr, [u8; 16])]) -> Result<(), Box<dyn Error>> {
+    if written.is_empty() {
+        return Ok(());
+    }
+    println!("\n{}", tr_args("reading-written-blocks-back", &[("count", &written.len().to_string())]));
+    verify_blocks(spi, key_store, written)?.print();
+    Ok(())
+}
+
+// Ask which sectors, and with `with_blocks` which blocks in them, an operation works
+// on. Enter keeps the selection given with --sectors / --blocks, or the whole card.
+fn ask_selection(with_blocks: bool) -> Result<Selection, Box<dyn Error>> {
+    let default = Selection::from_args().unwrap_or_default();
+    loop {
+        let sectors = wait_for_input(&format!("\n{} ", tr_args("sectors-e-g-1-3", &[("default", &default.describe().to_string())])))?;
+        let blocks = if with_blocks {
+            wait_for_input(&format!("{} ", tr("blocks-in-them-e-g")))?
+        } else {
+            String::new()
+        };
+        if sectors.is_empty() && blocks.is_empty() {
+            return Ok(default);
+        }
+        match Selection::parse(&sectors, &blocks) {
+            Ok(selection) if selection.sectors(CardLayout::Classic4K).is_empty() => println!("{}", tr("no-sector-of-card-is")),
+            Ok(selection) => return Ok(selection),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+// Ask for the settings of a template and save it with the others
+fn new_template() -> Result<FormatTemplate, Box<dyn Error>> {
+    let name = wait_for_input(&format!("\n{} ", tr("template-name")))?;
+    let description = wait_for_input(&format!("{} ", tr("description-optional")))?;
+    let sectors = wait_for_input(&format!("{} ", tr("sectors-to-format-enter-for")))?;
+    let key_a = wait_for_input(&format!("{} ", tr("key-12-hex-digits-enter")))?;
+    let key_b = wait_for_input(&format!("{} ", tr("key-b-12-hex-digits")))?;
+    let access = wait_for_input(&format!("{} ", tr("access-bytes-8-hex-digits")))?;
+    let ndef = wait_for_input(&format!("{} ", tr("make-sectors-ndef-container-y")))?.to_lowercase() == "y";
+    
+    let or_default = |value: String, default: &str| if value.is_empty() { default.to_string() } else { value };
+    let sectors = or_default(sectors, "1-15");
+    let mut text = format!("name {}\n", name);
+    if !description.is_empty() {
+        text.push_str(&format!("description {}\n", description));
+    }
+    text.push_str(&format!("sectors {}\n", sectors));
+    text.push_str(&format!("key * A {}\n", or_default(key_a, "FFFFFFFFFFFF")));
+    text.push_str(&format!("key * B {}\n", or_default(key_b, "FFFFFFFFFFFF")));
+    text.push_str(&format!("access * {}\n", or_default(access, "FF078069")));
+    if ndef {
+        text.push_str(&format!("ndef {}\n", sectors));
+    }
+    
+    let mut template = FormatTemplate::parse(&text, "new")?;
+    let path = template_dir().join(template.file_name());
+    template.source = path.display().to_string();
+    template.save(&path)?;
+    println!("{}", tr_args("template-saved-to", &[("path", &path.display().to_string())]));
+    Ok(template)
+}
+
+// Restore From Backup Menu
+fn restore_backup_menu(spi: &mut SpiReader, key_store: &mut KeyStore) -> Result<(), Box<dyn Error>> {
+    clear_screen();
+    println!("{}", tr("title-restore-from-backup"));
+    println!("===================");
+    
+    println!("\n{}", tr("block-0-and-sector-trailers"));
+    let backups = list_backups()?;
+    if backups.is_empty() {
+        println!("{}", tr("no-backups-yet"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+        return Ok(());
+    }
+    
+    println!("\n{}", tr("backups-newest-first"));
+    for (index, path) in backups.iter().enumerate() {
+        let name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        println!("{}. {}", index + 1, name);
     }
     
-    wait_for_input("\nPress Enter to continue...")?;
+    let choice = wait_for_input(&format!("\n{} ", tr("backup-to-restore-enter-to")))?;
+    let path = match choice.parse::<usize>().ok().and_then(|index| index.checked_sub(1)).and_then(|index| backups.get(index)) {
+        Some(path) => path,
+        None => return Ok(()),
+    };
+    
+    let backup = match load_backup(path) {
+        Ok(backup) => backup,
+        Err(e) => {
+            println!("{}", tr_args("err-load", &[("path", &path.display().to_string()), ("error", &e.to_string())]));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        }
+    };
+    
+    println!("\n{}", tr_args("backup-of-card", &[("uid", &uid_to_string(&backup.uid))]));
+    for block_addr in backup.layout().blocks() {
+        if let Some(data) = backup.block(block_addr) {
+            println!("  {}", tr_args("block-hex-line", &[("block", &block_addr.to_string()), ("hex", &bytes_to_hex(&data))]));
+        } else if backup.blocks[block_addr.index()].iter().any(Option::is_some) {
+            println!("  {}", tr_args("block-key-isnt-known-it", &[("block", &block_addr.to_string())]));
+        }
+    }
+    
+    println!("\n{}", tr("gen1a-magic-card-takes-blocks"));
+    println!("{}", tr("written-with-key-stores-keys"));
+    let confirm = wait_for_input(&format!("{} ", tr("this-overwrites-block-0-and")))?.to_lowercase();
+    if confirm != "y" {
+        return Ok(());
+    }
+    
+    countdown_for_card_placement(5)?;
+    
+    let (written, failed) = restore_backup(spi, &backup, key_store)?;
+    println!("\n{}", tr_args("blocks-written", &[("count", &written.len().to_string())]));
+    if !failed.is_empty() {
+        let blocks: Vec<String> = failed.iter().map(|b| b.to_string()).collect();
+        println!("{}", tr_args("err-failed-blocks", &[("blocks", &blocks.join(", "))]));
+    }
+    let expected: Vec<(BlockAddr, [u8; 16])> = written.iter()
+        .filter_map(|block| backup.block(*block).map(|data| (*block, data)))
+        .collect();
+    verify_written(spi, key_store, &expected)?;
+    
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
 
 // Change Keys Menu
-fn change_keys_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
+fn change_keys_menu(spi: &mut SpiReader) -> Result<(), Box<dyn Error>> {
     clear_screen();
-    println!("CHANGE KEYS");
+    println!("{}", tr("title-change-keys"));
     println!("===========");
     
-    let sector_str = wait_for_input("\nEnter sector number (0-15): ")?;
-    let sector = match sector_str.parse::<u8>() {
-        Ok(num) if num <= 15 => num,
-        _ => {
-            println!("Invalid sector number. Must be between 0 and 15.");
-            wait_for_input("\nPress Enter to continue...")?;
+    let sector_str = wait_for_input(&format!("\n{} ", tr("enter-sector-number-0-15")))?;
+    let sector = match sector_str.parse::<u8>().ok().and_then(SectorIndex::new) {
+        Some(sector) => sector,
+        None => {
+            println!("{}", tr("err-invalid-sector-number-must-be"));
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
             return Ok(());
         }
     };
     
     // Get current key
-    println!("\nYou need the current key to change keys.");
+    println!("\n{}", tr("you-need-current-key-to-2"));
     
     // Choose key type for authentication
-    println!("1. Authenticate with Key A");
-    println!("2. Authenticate with Key B");
+    println!("1. {}", tr("authenticate-with-key"));
+    println!("2. {}", tr("authenticate-with-key-b"));
     
-    let key_type_choice = wait_for_input("\nEnter choice (1-2): ")?;
+    let key_type_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-2")))?;
     
     let auth_type = match key_type_choice.as_str() {
         "1" => PICC_AUTHENT1A,
         "2" => PICC_AUTHENT1B,
         _ => {
-            println!("Invalid choice. Using Key A by default.");
+            println!("{}", tr("err-invalid-choice-using-key-by"));
             PICC_AUTHENT1A
         }
     };
     
     // Get current key
-    let current_key_str = wait_for_input("\nEnter current key (12 hex chars, default FFFFFFFFFFFF): ")?;
+    let current_key_str = wait_for_input(&format!("\n{} ", tr("enter-current-key-12-hex")))?;
     let current_key = if current_key_str.is_empty() {
-        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        Key([0xFF; 6])
     } else {
-        match hex_string_to_bytes(&current_key_str) {
-            Some(bytes) if bytes.len() == 6 => bytes,
-            _ => {
-                println!("Invalid key format. Using default key.");
-                [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec()
+        match Key::from_hex(&current_key_str) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
+                Key([0xFF; 6])
             }
         }
     };
     
     // Ask which keys to change
-    println!("\nWhich keys do you want to change?");
-    println!("1. Key A only");
-    println!("2. Key B only");
-    println!("3. Both keys");
+    println!("\n{}", tr("which-keys-do-you-want"));
+    println!("1. {}", tr("key-only"));
+    println!("2. {}", tr("key-b-only"));
+    println!("3. {}", tr("both-keys"));
     
-    let change_choice = wait_for_input("\nEnter choice (1-3): ")?;
+    let change_choice = wait_for_input(&format!("\n{} ", tr("enter-choice-1-3")))?;
     
     let change_key_a = change_choice == "1" || change_choice == "3";
     let change_key_b = change_choice == "2" || change_choice == "3";
     
     if !change_key_a && !change_key_b {
-        println!("No keys selected for change. Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("{}", tr("no-keys-selected-for-change"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
     // Get new keys
-    let mut new_key_a = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec();
-    let mut new_key_b = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF].to_vec();
+    let mut new_key_a = Key([0xFF; 6]);
+    let mut new_key_b = Key([0xFF; 6]);
     
     if change_key_a {
-        let key_a_str = wait_for_input("\nEnter new Key A (12 hex chars): ")?;
-        match hex_string_to_bytes(&key_a_str) {
-            Some(bytes) if bytes.len() == 6 => {
-                new_key_a = bytes;
+        let key_a_str = wait_for_input(&format!("\n{} ", tr("enter-new-key-12-hex")))?;
+        match Key::from_hex(&key_a_str) {
+            Some(key) => {
+                new_key_a = key;
             },
-            _ => {
-                println!("Invalid key format. Using default key.");
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
             }
         }
     }
     
     if change_key_b {
-        let key_b_str = wait_for_input("\nEnter new Key B (12 hex chars): ")?;
-        match hex_string_to_bytes(&key_b_str) {
-            Some(bytes) if bytes.len() == 6 => {
-                new_key_b = bytes;
+        let key_b_str = wait_for_input(&format!("\n{} ", tr("enter-new-key-b-12")))?;
+        match Key::from_hex(&key_b_str) {
+            Some(key) => {
+                new_key_b = key;
             },
-            _ => {
-                println!("Invalid key format. Using default key.");
+            None => {
+                println!("{}", tr("err-invalid-key-format-using-default"));
             }
         }
     }
     
     // Confirmation
-    println!("\nChanging keys for sector {}:", sector);
+    println!("\n{}", tr_args("changing-keys-for-sector", &[("sector", &sector.to_string())]));
     if change_key_a {
-        println!("New Key A: {}", bytes_to_hex(&new_key_a));
+        println!("{}", tr_args("new-key", &[("hex", &bytes_to_hex(new_key_a.as_bytes()))]));
     } else {
-        println!("Key A: (unchanged)");
+        println!("{}", tr("key-unchanged"));
     }
     
     if change_key_b {
-        println!("New Key B: {}", bytes_to_hex(&new_key_b));
+        println!("{}", tr_args("new-key-b", &[("hex", &bytes_to_hex(new_key_b.as_bytes()))]));
     } else {
-        println!("Key B: (unchanged)");
+        println!("{}", tr("key-b-unchanged"));
     }
     
-    let confirm = wait_for_input("\nConfirm key change? (y/n): ")?.to_lowercase();
+    let confirm = wait_for_input(&format!("\n{} ", tr("confirm-key-change-y-n")))?.to_lowercase();
     if confirm != "y" {
-        println!("Operation cancelled.");
-        wait_for_input("\nPress Enter to continue...")?;
+        println!("{}", tr("operation-cancelled"));
+        wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
         return Ok(());
     }
     
@@ -904,11 +1923,73 @@ fn change_keys_menu(spi: &mut Spi) -> Result<(), Box<dyn Error>> {
                                   change_key_b, &new_key_b)?;
     
     if result {
-        println!("\nKeys changed successfully!");
+        println!("\n{}", tr("keys-changed-successfully"));
     } else {
-        println!("\nFailed to change keys. Check authentication key and access rights.");
+        println!("\n{}", tr("err-change-keys-check-authentication-key"));
+    }
+    
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+    Ok(())
+}
+
+// Data Encryption Menu
+fn data_key_menu(data_key: &mut Option<DataKey>, path: &Path) -> Result<(), Box<dyn Error>> {
+    clear_screen();
+    println!("{}", tr("title-data-encryption"));
+    println!("===============");
+    println!("\n{}", tr("data-blocks-are-encrypted-with"));
+    println!("{}", tr("block-0-and-sector-trailers-2"));
+    println!("\n{}", tr_args("encryption-status", &[("status", &data_key.as_ref().map_or_else(|| tr("encryption-not-set-up"), DataKey::describe))]));
+    
+    println!("\n1. {}", tr("use-one-key-for-every"));
+    println!("2. {}", tr("use-master-key-each-cards"));
+    if let Some(current) = data_key.as_ref() {
+        println!("3. {}", tr(if current.enabled { "turn-encryption-off" } else { "turn-encryption-on" }));
     }
+    println!("0. {}", tr("menu-back"));
+    
+    let mode = match wait_for_input(&format!("\n{} ", tr("enter-your-choice")))?.as_str() {
+        "1" => KeyMode::Fixed,
+        "2" => KeyMode::PerUid,
+        "3" if data_key.is_some() => {
+            if let Some(current) = data_key.as_mut() {
+                current.enabled = !current.enabled;
+                current.save(path)?;
+                println!("\n{}", tr_args("data-encryption-is", &[("current", &current.describe().to_string())]));
+            }
+            wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+            return Ok(());
+        },
+        _ => return Ok(()),
+    };
+    
+    if data_key.is_some() {
+        println!("\n{}", tr("data-written-with-current-key"));
+        if wait_for_input(&format!("{} ", tr("replace-current-key-y-n")))?.to_lowercase() != "y" {
+            return Ok(());
+        }
+    }
+    
+    let key_input = wait_for_input(&format!("\n{} ", tr("key-32-hex-chars-or")))?;
+    let key = if key_input.is_empty() {
+        generate_key()?
+    } else {
+        match hex_string_to_bytes(&key_input).and_then(|key| <[u8; 16]>::try_from(key.as_slice()).ok()) {
+            Some(key) => key,
+            None => {
+                println!("{}", tr("err-invalid-key-it-must-be-2"));
+                wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
+                return Ok(());
+            }
+        }
+    };
+    
+    let new_key = DataKey { enabled: true, mode, key };
+    new_key.save(path)?;
+    println!("\n{}", tr_args("data-key-hex", &[("hex", &bytes_to_hex(&key))]));
+    println!("{}", tr_args("saved-to-keep-copy-encrypted", &[("path", &path.display().to_string())]));
+    *data_key = Some(new_key);
     
-    wait_for_input("\nPress Enter to continue...")?;
+    wait_for_input(&format!("\n{}", tr("press-enter-to-continue")))?;
     Ok(())
 }
@@ -1,51 +1,184 @@
 pub mod lib {
+    pub mod benchmark;
+    pub mod credit_test;
+    pub mod i18n;
     pub mod mfrc522;
     pub mod mifare;
+    pub mod signing;
+    pub mod timing;
     pub mod ui;
     pub mod utils;
 }
 
-use rppal::spi::{Bus, Mode, SlaveSelect, Spi};
 use std::error::Error;
+use std::path::Path;
 use std::process;
+use std::time::Duration;
+
+use crate::lib::benchmark::{parse_speed, run_benchmark, DEFAULT_SPEEDS};
+use crate::lib::mfrc522::{mfrc522_init, negotiate_speed, open_spi, SpeedConfig, SPI_SPEED_HZ};
+use crate::lib::mifare::Selection;
+use crate::lib::signing::verify_file;
+use crate::lib::timing::{run_timing, TimingConfig};
+use crate::lib::utils::hex_string_to_bytes;
+use crate::lib::i18n::{set_language, tr, tr_args, AUTO_LANGUAGE};
+
+// `benchmark [--speeds 500k,1M,4M] [--seconds N]`
+fn benchmark_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut speeds = DEFAULT_SPEEDS.to_vec();
+    let mut seconds = 3.0;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--speeds" => {
+                let list = args.next().ok_or_else(|| tr("opt-speeds"))?;
+                speeds = list.split(',')
+                    .map(|speed| parse_speed(speed).ok_or_else(|| tr_args("opt-invalid-speed", &[("speed", speed)])))
+                    .collect::<Result<_, _>>()?;
+            },
+            "--seconds" => {
+                seconds = args.next()
+                    .and_then(|value| value.parse::<f64>().ok())
+                    .filter(|value| *value > 0.0)
+                    .ok_or_else(|| tr("opt-seconds"))?;
+            },
+            other => return Err(tr_args("opt-unknown-benchmark", &[("option", other)]).into()),
+        }
+    }
+    run_benchmark(&speeds, Duration::from_secs_f64(seconds))
+}
+
+// `timing [--trials N] [--block B] [--key FFFFFFFFFFFF] [--key-b] [--spi-speed 1M] [--output timings.csv]`
+fn timing_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let mut config = TimingConfig::default();
+    let mut speed_hz = SPI_SPEED_HZ;
+    let mut output = "timings.csv".to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trials" => {
+                config.trials = args.next()
+                    .and_then(|value| value.parse::<usize>().ok())
+                    .filter(|value| *value > 0)
+                    .ok_or_else(|| tr("opt-trials"))?;
+            },
+            "--block" => {
+                config.block = args.next()
+                    .and_then(|value| value.parse::<u8>().ok())
+                    .ok_or_else(|| tr("opt-block"))?;
+            },
+            "--key" => {
+                config.key = args.next()
+                    .and_then(|value| hex_string_to_bytes(value))
+                    .and_then(|key| key.try_into().ok())
+                    .ok_or_else(|| tr("opt-key"))?;
+            },
+            "--key-b" => config.key_b = true,
+            "--spi-speed" => {
+                speed_hz = args.next()
+                    .and_then(|speed| parse_speed(speed))
+                    .ok_or_else(|| tr("opt-spi-speed"))?;
+            },
+            "--output" => output = args.next().ok_or_else(|| tr("opt-output"))?.clone(),
+            other => return Err(tr_args("opt-unknown-timing", &[("option", other)]).into()),
+        }
+    }
+
+    let mut spi = open_spi(speed_hz)?;
+    mfrc522_init(&mut spi)?;
+    run_timing(&mut spi, &config, &output)
+}
+
+// `verify <file> [--key <public key>]`, exits with 1 when the signature doesn't hold
+fn verify_command(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let (file, expected) = match args {
+        [file] => (file, None),
+        [file, flag, key] if flag == "--key" => (file, Some(key.replace([' ', ':'], "").to_uppercase())),
+        _ => return Err(tr("opt-verify-usage").into()),
+    };
+    
+    match verify_file(Path::new(file)) {
+        Ok(public_key) if expected.as_ref().is_some_and(|expected| *expected != public_key) => {
+            println!("{}", tr_args("is-intact-but-was-signed", &[("file", &file.to_string()), ("public_key", &public_key.to_string())]));
+            process::exit(1);
+        },
+        Ok(public_key) => {
+            println!("{}", tr_args("valid-signature-by-key", &[("file", &file.to_string()), ("public_key", &public_key.to_string())]));
+            if expected.is_none() {
+                println!("{}", tr("compare-key-with-one-sender"));
+            }
+            Ok(())
+        },
+        Err(e) => {
+            println!("{}", tr_args("err-cant-be-trusted", &[("file", &file.to_string()), ("error", &e.to_string())]));
+            process::exit(1);
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("NFC/RFID Block Editor");
+    set_language(AUTO_LANGUAGE);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("benchmark") => return benchmark_command(&args[1..]),
+        Some("timing") => return timing_command(&args[1..]),
+        Some("verify") => return verify_command(&args[1..]),
+        _ => {},
+    }
+    
+    // `--spi-speed 1M` fixes the speed, otherwise the fastest reliable one is negotiated
+    // and lowered again if communication errors pile up
+    let forced_speed = match args.iter().position(|arg| arg == "--spi-speed") {
+        Some(index) => Some(args.get(index + 1)
+            .and_then(|speed| parse_speed(speed))
+            .ok_or_else(|| tr("opt-spi-speed"))?),
+        None => None,
+    };
+    
+    // `--sectors 1-3,7` / `--blocks 4-6` preselect part of the card in the menus
+    Selection::from_args()?;
+    
+    println!("{}", tr("nfc-rfid-block-editor"));
     println!("=====================");
-    println!("Initializing...");
+    println!("{}", tr("initializing"));
     
     // Initialize SPI
-    let mut spi = match Spi::new(Bus::Spi0, SlaveSelect::Ss0, 1_000_000, Mode::Mode0) {
-        Ok(spi) => {
-            println!("SPI interface initialized successfully.");
-            spi
+    let opened = match forced_speed {
+        Some(speed_hz) => open_spi(speed_hz).map(|spi| (spi, None)),
+        None => negotiate_speed(SpeedConfig::load(SpeedConfig::default_path()))
+            .map(|(spi, setting)| (spi, Some(setting))),
+    };
+    let (mut spi, mut speed_setting) = match opened {
+        Ok(opened) => {
+            println!("{}", tr("spi-interface-initialized-successfully"));
+            opened
         },
         Err(e) => {
-            eprintln!("Failed to initialize SPI: {}", e);
-            eprintln!("Make sure SPI is enabled on your Raspberry Pi.");
-            eprintln!("Run 'sudo raspi-config', go to 'Interface Options' > 'SPI' and enable it.");
-            return Err(e.into());
+            eprintln!("{}", tr_args("err-initialize-spi", &[("error", &e.to_string())]));
+            eprintln!("{}", tr("make-sure-spi-is-enabled"));
+            eprintln!("{}", tr("run-sudo-raspi-config-go"));
+            return Err(e);
         }
     };
     
     // Initialize MFRC522
     match crate::lib::mfrc522::mfrc522_init(&mut spi) {
         Ok(_) => {
-            println!("MFRC522 RFID reader initialized successfully.");
+            println!("{}", tr("mfrc522-rfid-reader-initialized-successfully"));
         },
         Err(e) => {
-            eprintln!("Failed to initialize MFRC522 RFID reader: {}", e);
-            eprintln!("Check connections and ensure reader is properly connected.");
+            eprintln!("{}", tr_args("err-initialize-mfrc522-rfid-reader", &[("error", &e.to_string())]));
+            eprintln!("{}", tr("check-connections-and-ensure-reader"));
             return Err(e);
         }
     }
     
     // Start the main menu
-    if let Err(e) = crate::lib::ui::main_menu(&mut spi) {
-        eprintln!("Error in main menu: {}", e);
+    if let Err(e) = crate::lib::ui::main_menu(&mut spi, &mut speed_setting) {
+        eprintln!("{}", tr_args("err-in-main-menu", &[("error", &e.to_string())]));
         process::exit(1);
     }
     
-    println!("Exiting program. Goodbye!");
+    println!("{}", tr("exiting-program-goodbye"));
     Ok(())
 }
@@ -0,0 +1,55 @@
+/*
+ * Read a card through the C interface.
+ *
+ *     cargo build --release --lib --features ffi
+ *     cc examples/c/read_card.c -Iinclude -Ltarget/release -lrust_rfid_nfc_toolkit -o read_card
+ *     LD_LIBRARY_PATH=target/release ./read_card
+ */
+#include <stdio.h>
+#include "rfid_toolkit.h"
+
+static void print_block(uint8_t block, const uint8_t *data, void *user_data)
+{
+    (void)user_data;
+    printf("%2u: ", block);
+    if (data == NULL) {
+        printf("(no access)\n");
+        return;
+    }
+    for (int i = 0; i < RFID_BLOCK_SIZE; i++) {
+        printf("%02X ", data[i]);
+    }
+    printf("\n");
+}
+
+int main(void)
+{
+    RfidReader *reader = rfid_reader_new(0, 0, 25);
+    if (reader == NULL) {
+        fprintf(stderr, "Failed to open reader: %s\n", rfid_last_error());
+        return 1;
+    }
+
+    uint8_t uid[10];
+    size_t uid_len = 0;
+    printf("Hold a card near the reader...\n");
+    int status = rfid_poll(reader, 10000, uid, sizeof(uid), &uid_len);
+    if (status == RFID_OK) {
+        printf("UID:");
+        for (size_t i = 0; i < uid_len; i++) {
+            printf(" %02X", uid[i]);
+        }
+        printf("\n");
+
+        status = rfid_dump(reader, NULL, RFID_KEY_A, 5000, print_block, NULL);
+    }
+
+    if (status == RFID_NO_CARD) {
+        printf("No card detected\n");
+    } else if (status != RFID_OK) {
+        fprintf(stderr, "Error: %s\n", rfid_last_error());
+    }
+
+    rfid_reader_free(reader);
+    return status == RFID_OK ? 0 : 1;
+}
@@ -0,0 +1,66 @@
+/*
+ * C interface to the rust-rfid-nfc-toolkit MFRC522 driver.
+ *
+ * Build the shared library with:
+ *     cargo build --release --lib --features ffi
+ * and link against ../target/release/librust_rfid_nfc_toolkit.so.
+ *
+ * Every call returns one of the RFID_* codes. After RFID_ERROR or
+ * RFID_INVALID_ARGUMENT, rfid_last_error() describes the problem.
+ * A reader handle must only be used from one thread at a time.
+ */
+#ifndef RFID_TOOLKIT_H
+#define RFID_TOOLKIT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RFID_OK                 0
+#define RFID_NO_CARD            1   /* no card was presented before the timeout */
+#define RFID_ERROR             -1
+#define RFID_INVALID_ARGUMENT  -2
+
+#define RFID_KEY_A 0
+#define RFID_KEY_B 1
+
+#define RFID_BLOCK_SIZE 16
+
+typedef struct RfidReader RfidReader;
+
+/* Called once per block by rfid_dump, data is NULL for blocks the key could not open. */
+typedef void (*rfid_block_callback)(uint8_t block, const uint8_t *data, void *user_data);
+
+/* Open and initialize the reader (e.g. bus 0, device 0, reset GPIO 25), NULL on failure. */
+RfidReader *rfid_reader_new(uint8_t spi_bus, uint8_t spi_device, uint8_t reset_pin);
+
+/* Turn the antenna off and release the reader. NULL is ignored. */
+void rfid_reader_free(RfidReader *reader);
+
+/* Wait up to timeout_ms for a card and copy its UID into uid_out. */
+int rfid_poll(RfidReader *reader, uint32_t timeout_ms,
+              uint8_t *uid_out, size_t uid_capacity, size_t *uid_len);
+
+/* Read one block into data_out (16 bytes). key is 6 bytes, NULL for the factory default key. */
+int rfid_read_block(RfidReader *reader, uint8_t block, const uint8_t *key, int key_type,
+                    uint32_t timeout_ms, uint8_t *data_out);
+
+/* Write 16 bytes to a data block. Block 0 and sector trailers are refused. */
+int rfid_write_block(RfidReader *reader, uint8_t block, const uint8_t *data,
+                     const uint8_t *key, int key_type, uint32_t timeout_ms);
+
+/* Read all 64 blocks of a 1K card, calling callback for each. */
+int rfid_dump(RfidReader *reader, const uint8_t *key, int key_type, uint32_t timeout_ms,
+              rfid_block_callback callback, void *user_data);
+
+/* Message for the last error on the calling thread, valid until the next call. */
+const char *rfid_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RFID_TOOLKIT_H */
@@ -0,0 +1,39 @@
+#!/usr/bin/env python3
+"""
+Example using the native Rust driver through the PyO3 extension module
+instead of shelling out to rfid_wrapper.py.
+
+Build it first from the toolkit directory:
+    pip install maturin
+    maturin develop --release
+"""
+
+import rust_rfid_nfc_toolkit as rfid
+
+
+def main():
+    reader = rfid.Reader()  # SPI0 CE0, reset on GPIO 25
+    try:
+        uid = reader.read_uid(timeout=10)
+        if uid is None:
+            print("No card detected")
+            return
+        print("UID:", uid.hex(" ").upper())
+
+        block = reader.read_block(8)
+        if block is not None:
+            print("Block 8:", block.hex(" ").upper())
+
+        reader.write_block(8, b"hello from rust")
+
+        dump = reader.dump(timeout=10)
+        if dump is not None:
+            _, blocks = dump
+            for number, data in enumerate(blocks):
+                print(f"{number:2}: {data.hex(' ').upper() if data else '(no access)'}")
+    finally:
+        reader.close()
+
+
+if __name__ == "__main__":
+    main()
@@ -3,6 +3,11 @@
 RFID Wrapper for integration with Rust application.
 This script provides a bridge to use Python's SimpleMFRC522 library
 which has better support for clone cards and the FM17522 chip variant.
+
+Run as "rfid_wrapper.py serve" it stays up and answers one JSON request per
+line on stdin, e.g. {"id": 1, "command": "read"}, with one JSON response per
+line on stdout carrying the same id. "ping" answers straight away so the Rust
+side can check the process is healthy.
 """
 
 import sys
@@ -49,7 +54,7 @@ def read_card():
     finally:
         cleanup()
     
-    print(json.dumps(result))
+    return result
 
 def write_card(text):
     """Write data to an RFID card."""
@@ -83,7 +88,7 @@ def write_card(text):
     finally:
         cleanup()
     
-    print(json.dumps(result))
+    return result
 
 def test_keys():
     """Test default keys on the RFID card."""
@@ -123,23 +128,62 @@ def test_keys():
     finally:
         cleanup()
     
-    print(json.dumps(result))
+    return result
+
+def handle_request(request):
+    """Run one command from the serve loop."""
+    command = request.get("command")
+
+    if command == "ping":
+        return {"success": True, "error": None}
+    if command == "read":
+        return read_card()
+    if command == "write":
+        if "text" not in request:
+            return {"success": False, "uid": None, "error": "Text to write is required"}
+        return write_card(request["text"])
+    if command == "test_keys":
+        return test_keys()
+    return {"success": False, "error": f"Unknown command: {command}"}
+
+def serve():
+    """Answer line-delimited JSON requests until stdin is closed."""
+    for line in sys.stdin:
+        line = line.strip()
+        if not line:
+            continue
+
+        try:
+            request = json.loads(line)
+        except ValueError as e:
+            print(json.dumps({"id": None, "success": False, "error": f"Invalid request: {e}"}), flush=True)
+            continue
+
+        try:
+            result = handle_request(request)
+        except Exception as e:
+            result = {"success": False, "error": str(e)}
+
+        result["id"] = request.get("id")
+        print(json.dumps(result), flush=True)
 
 if __name__ == "__main__":
     if len(sys.argv) < 2:
-        print(json.dumps({"success": False, "error": "Command required: read, write, or test_keys"}))
+        print(json.dumps({"success": False, "error": "Command required: read, write, test_keys or serve"}))
         sys.exit(1)
     
     command = sys.argv[1]
     
-    if command == "read":
-        read_card()
+    if command == "serve":
+        serve()
+    elif command == "read":
+        print(json.dumps(read_card()))
     elif command == "write":
         if len(sys.argv) < 3:
             print(json.dumps({"success": False, "error": "Text to write is required"}))
             sys.exit(1)
-        write_card(sys.argv[2])
+        print(json.dumps(write_card(sys.argv[2])))
     elif command == "test_keys":
-        test_keys()
+        print(json.dumps(test_keys()))
     else:
         print(json.dumps({"success": False, "error": f"Unknown command: {command}"}))
@@ -4,6 +4,7 @@ use std::error::Error;
 use std::io::{self, Write};
 use std::fmt;
 
+
 // MFRC522 Commands
 const PCD_IDLE: u8 = 0x00;
 const PCD_AUTHENT: u8 = 0x0E;
@@ -293,17 +294,26 @@ fn main() -> Result<(), Box<dyn Error>> {
     
     println!("MFRC522 initialized successfully");
     
-    // Menu system
+    if let Some(reason) = safe_mode::read_only_reason() {
+        println!("READ-ONLY MODE ({}): cards can't be changed", reason);
+    }
+    
+    // Menu system, the items that change the card are left out in read-only mode
+    let read_only = safe_mode::is_read_only();
     loop {
         println!("\nSelect an option:");
         println!("1. Read card UID");
         println!("2. Read card data");
-        println!("3. Write data to card");
+        if !read_only {
+            println!("3. Write data to card");
+        }
         println!("4. Dump card contents");
-        println!("5. Modify sector access");
-        println!("6. Change keys");
-        println!("7. Format card (reset to defaults)");
-        println!("8. Write to specific block");
+        if !read_only {
+            println!("5. Modify sector access");
+            println!("6. Change keys");
+            println!("7. Format card (reset to defaults)");
+            println!("8. Write to specific block");
+        }
         println!("9. Exit");
         print!("Enter choice: ");
         io::stdout().flush()?;
@@ -313,6 +323,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let choice = input.trim();
         
         match choice {
+            "3" | "5" | "6" | "7" | "8" if read_only => safe_mode::refuse(),
             "1" => {
                 println!("\n=== Reading Card UID ===");
                 
@@ -3,6 +3,7 @@ use std::{thread, time::Duration};
 use std::error::Error;
 use std::io::{self, Write};
 
+
 // MFRC522 Commands
 const PCD_IDLE: u8 = 0x00;
 const PCD_AUTHENT: u8 = 0x0E;
@@ -93,12 +94,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     
     println!("MFRC522 initialized successfully");
     
-    // Menu system
+    if let Some(reason) = safe_mode::read_only_reason() {
+        println!("READ-ONLY MODE ({}): cards can't be changed", reason);
+    }
+    
+    // Menu system, writing is left out in read-only mode
+    let read_only = safe_mode::is_read_only();
     loop {
         println!("\nSelect an option:");
         println!("1. Read card UID");
         println!("2. Read card data");
-        println!("3. Write data to card");
+        if !read_only {
+            println!("3. Write data to card");
+        }
         println!("4. Dump card contents");
         println!("5. Exit");
         print!("Enter choice: ");
@@ -109,6 +117,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let choice = input.trim();
         
         match choice {
+            "3" if read_only => safe_mode::refuse(),
             "1" => {
                 println!("\n=== Reading Card UID ===");
                 
@@ -855,40 +864,20 @@ fn clear_bit_mask(spi: &mut Spi, reg: u8, mask: u8) -> Result<(), Box<dyn Error>
 
 // Format UID as a hex string
 fn uid_to_string(uid: &[u8]) -> String {
-    uid.iter()
-        .map(|byte| format!("{:02X}", byte))
-        .collect::<Vec<String>>()
-        .join(":")
+    uid_codec::format_uid(uid)
 }
 
 // Convert UID to a single decimal number
 fn uid_to_num(uid: &[u8]) -> u64 {
-    let mut num: u64 = 0;
-    
-    for &byte in uid {
-        num = num * 256 + (byte as u64);
-    }
-    
-    num
+    uid_codec::uid_to_num(uid)
 }
 
 // Format bytes as a hex string
 fn bytes_to_hex(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|byte| format!("{:02X}", byte))
-        .collect::<Vec<String>>()
-        .join(" ")
+    uid_codec::bytes_to_hex(bytes)
 }
 
 // Convert bytes to ASCII string (replacing non-printable chars with dots)
 fn bytes_to_ascii(bytes: &[u8]) -> String {
-    bytes.iter()
-        .map(|&byte| {
-            if byte >= 32 && byte <= 126 {
-                byte as char
-            } else {
-                '.'
-            }
-        })
-        .collect()
+    uid_codec::bytes_to_ascii(bytes)
 }
@@ -5,11 +5,11 @@ use std::sync::{Arc, Mutex};
 use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
 use std::thread;
 use std::time::Duration;
-use fltk::{app, prelude::*, text::TextBuffer};
+use fltk::{app, enums::Color, prelude::*, text::TextBuffer};
 use fltk_theme::{WidgetTheme, ThemeType};
 use ctrlc;
 
-use rust_rfid_nfc_toolkit::rfid::{SPI_BUS, SPI_DEVICE, RESET_PIN, SimpleMifareRW, MFRC522Wrapper};
+use rust_rfid_nfc_toolkit::rfid::{SPI_BUS, SPI_DEVICE, RESET_PIN, SimpleMifareRW, MFRC522Wrapper, ReaderStatus, ReaderSupervisor};
 use rust_rfid_nfc_toolkit::ui::{WriterCommand, create_ui};
 use rust_rfid_nfc_toolkit::utils::init_logging;
 
@@ -19,6 +19,7 @@ const PYTHON_SCRIPT_PATH: &str = "python/rfid_wrapper.py";
 enum UiMessage {
     SetUID(String),
     SetText(String),
+    SetStatus(ReaderStatus),
 }
 
 struct WriterWorker {
@@ -116,6 +117,13 @@ impl WriterWorker {
     }
     
     fn handle_write(&mut self, text: &str) {
+        // The button is disabled, this keeps a write from getting through another way
+        if let Some(reason) = safe_mode::read_only_reason() {
+            warn!("Write refused in read-only mode ({})", reason);
+            self.ui_sender.send(UiMessage::SetText(format!("Writing is disabled in read-only mode ({})", reason)));
+            return;
+        }
+        
         let mut simple_mifare = self.mifare_rw.clone();
         
         self.ui_sender.send(UiMessage::SetText("Writing to card... Please wait".to_string()));
@@ -197,7 +205,7 @@ pub fn main() -> Result<()> {
     
     let (worker_sender, worker_receiver) = channel();
     
-    let (mut window, input, mut uid_label, mut data_display, mut buffer) = create_ui(worker_sender.clone())?;
+    let (mut window, input, mut uid_label, mut data_display, mut buffer, mut status_label) = create_ui(worker_sender.clone())?;
     window.show();
     
     info!("Initializing MFRC522 hardware...");
@@ -221,6 +229,12 @@ pub fn main() -> Result<()> {
         std::process::exit(0);
     }).expect("Error setting Ctrl-C handler");
     
+    // watch the reader and bring it back if it drops off the SPI bus
+    let status_sender = ui_sender.clone();
+    let mut supervisor = ReaderSupervisor::start(mfrc522_wrapper.clone(), move |status| {
+        status_sender.send(UiMessage::SetStatus(status));
+    });
+    
     // a new worker is created and we start the thread
     let mut worker = WriterWorker::new(mifare_rw, ui_sender, should_exit.clone());
     
@@ -242,6 +256,14 @@ pub fn main() -> Result<()> {
                 UiMessage::SetText(text) => {
                     buffer.set_text(&text);
                 },
+                UiMessage::SetStatus(status) => {
+                    status_label.set_label(&format!("Reader: {}", status));
+                    let color = match status {
+                        ReaderStatus::Connected => 0x98c379,    // Green
+                        ReaderStatus::Disconnected => 0xe06c75, // Red
+                    };
+                    status_label.set_label_color(Color::from_u32(color));
+                },
             }
             app.redraw(); 
         }
@@ -259,6 +281,7 @@ pub fn main() -> Result<()> {
     }
     
     // Cleanup before exit
+    supervisor.stop();
     let _ = mfrc522_wrapper.cleanup();
     
     info!("Application exited cleanly");
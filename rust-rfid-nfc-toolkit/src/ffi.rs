@@ -0,0 +1,234 @@
+//! C ABI for the reader core, built into the cdylib with `--features ffi`.
+//! The matching declarations are in include/rfid_toolkit.h.
+//!
+//! Every function returns one of the `RFID_*` codes. After `RFID_ERROR`,
+//! `rfid_last_error()` describes what went wrong on the calling thread.
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, c_void, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::time::Duration;
+
+use crate::rfid::card::{KeyType, BLOCK_SIZE, DEFAULT_KEY};
+use crate::rfid::mfrc522::MFRC522Wrapper;
+
+pub const RFID_OK: c_int = 0;
+/// no card was presented before the timeout
+pub const RFID_NO_CARD: c_int = 1;
+pub const RFID_ERROR: c_int = -1;
+pub const RFID_INVALID_ARGUMENT: c_int = -2;
+
+pub const RFID_KEY_A: c_int = 0;
+pub const RFID_KEY_B: c_int = 1;
+
+/// called once per block by `rfid_dump`, `data` is NULL for blocks the key could not open
+pub type RfidBlockCallback = Option<extern "C" fn(block: u8, data: *const u8, user_data: *mut c_void)>;
+
+/// opaque handle handed to C
+pub struct RfidReader {
+    inner: MFRC522Wrapper,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<CString> = RefCell::new(CString::default());
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// run `f`, turning errors and panics into return codes so nothing unwinds into C
+fn guarded(f: impl FnOnce() -> anyhow::Result<c_int>) -> c_int {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(code)) => code,
+        Ok(Err(e)) => {
+            set_last_error(e.to_string());
+            RFID_ERROR
+        },
+        Err(_) => {
+            set_last_error("Panic in reader code".to_string());
+            RFID_ERROR
+        },
+    }
+}
+
+fn invalid(message: &str) -> c_int {
+    set_last_error(message.to_string());
+    RFID_INVALID_ARGUMENT
+}
+
+/// key from a 6 byte buffer, NULL means the factory default key
+unsafe fn key_arg(key: *const u8) -> [u8; 6] {
+    if key.is_null() {
+        return DEFAULT_KEY;
+    }
+    let mut buffer = [0u8; 6];
+    buffer.copy_from_slice(std::slice::from_raw_parts(key, 6));
+    buffer
+}
+
+fn key_type_arg(key_type: c_int) -> Option<KeyType> {
+    match key_type {
+        RFID_KEY_A => Some(KeyType::A),
+        RFID_KEY_B => Some(KeyType::B),
+        _ => None,
+    }
+}
+
+fn timeout_arg(timeout_ms: u32) -> Duration {
+    Duration::from_millis(timeout_ms as u64)
+}
+
+/// Open and initialize the reader, returns NULL on failure (see `rfid_last_error`).
+#[no_mangle]
+pub extern "C" fn rfid_reader_new(spi_bus: u8, spi_device: u8, reset_pin: u8) -> *mut RfidReader {
+    let result = panic::catch_unwind(|| MFRC522Wrapper::new(spi_bus, spi_device, reset_pin));
+    match result {
+        Ok(Ok(inner)) => Box::into_raw(Box::new(RfidReader { inner })),
+        Ok(Err(e)) => {
+            set_last_error(e.to_string());
+            ptr::null_mut()
+        },
+        Err(_) => {
+            set_last_error("Panic while opening the reader".to_string());
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Turn the antenna off and release the reader.
+///
+/// # Safety
+/// `reader` must come from `rfid_reader_new` and not be used afterwards. NULL is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn rfid_reader_free(reader: *mut RfidReader) {
+    if reader.is_null() {
+        return;
+    }
+    let reader = Box::from_raw(reader);
+    let _ = reader.inner.cleanup();
+}
+
+/// Wait up to `timeout_ms` for a card and copy its UID into `uid_out`.
+///
+/// # Safety
+/// `reader` must be a live handle, `uid_out` must hold `uid_capacity` bytes and
+/// `uid_len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn rfid_poll(reader: *mut RfidReader, timeout_ms: u32, uid_out: *mut u8, uid_capacity: usize, uid_len: *mut usize) -> c_int {
+    let reader = match reader.as_ref() {
+        Some(reader) => reader,
+        None => return invalid("reader is NULL"),
+    };
+    if uid_out.is_null() || uid_len.is_null() {
+        return invalid("uid_out and uid_len are required");
+    }
+
+    guarded(|| match reader.inner.read_uid(timeout_arg(timeout_ms))? {
+        None => Ok(RFID_NO_CARD),
+        Some(uid) if uid.len() > uid_capacity => {
+            Ok(invalid(&format!("UID is {} bytes, buffer holds {}", uid.len(), uid_capacity)))
+        },
+        Some(uid) => {
+            ptr::copy_nonoverlapping(uid.as_ptr(), uid_out, uid.len());
+            *uid_len = uid.len();
+            Ok(RFID_OK)
+        },
+    })
+}
+
+/// Wait for a card and read `block` into the 16 byte buffer `data_out`.
+///
+/// # Safety
+/// `reader` must be a live handle, `key` NULL or 6 readable bytes, `data_out` 16 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rfid_read_block(reader: *mut RfidReader, block: u8, key: *const u8, key_type: c_int, timeout_ms: u32, data_out: *mut u8) -> c_int {
+    let reader = match reader.as_ref() {
+        Some(reader) => reader,
+        None => return invalid("reader is NULL"),
+    };
+    let key_type = match key_type_arg(key_type) {
+        Some(key_type) => key_type,
+        None => return invalid("key_type must be RFID_KEY_A or RFID_KEY_B"),
+    };
+    if data_out.is_null() {
+        return invalid("data_out is required");
+    }
+    let key = key_arg(key);
+
+    guarded(|| match reader.inner.read_block(block, &key, key_type, timeout_arg(timeout_ms))? {
+        None => Ok(RFID_NO_CARD),
+        Some((_, data)) => {
+            ptr::copy_nonoverlapping(data.as_ptr(), data_out, BLOCK_SIZE);
+            Ok(RFID_OK)
+        },
+    })
+}
+
+/// Wait for a card and write the 16 bytes at `data` to `block`. Sector trailers are refused.
+///
+/// # Safety
+/// `reader` must be a live handle, `key` NULL or 6 readable bytes, `data` 16 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn rfid_write_block(reader: *mut RfidReader, block: u8, data: *const u8, key: *const u8, key_type: c_int, timeout_ms: u32) -> c_int {
+    let reader = match reader.as_ref() {
+        Some(reader) => reader,
+        None => return invalid("reader is NULL"),
+    };
+    let key_type = match key_type_arg(key_type) {
+        Some(key_type) => key_type,
+        None => return invalid("key_type must be RFID_KEY_A or RFID_KEY_B"),
+    };
+    if data.is_null() {
+        return invalid("data is required");
+    }
+    let key = key_arg(key);
+    let mut block_data = [0u8; BLOCK_SIZE];
+    block_data.copy_from_slice(std::slice::from_raw_parts(data, BLOCK_SIZE));
+
+    guarded(|| match reader.inner.write_block(block, &block_data, &key, key_type, timeout_arg(timeout_ms))? {
+        None => Ok(RFID_NO_CARD),
+        Some(_) => Ok(RFID_OK),
+    })
+}
+
+/// Wait for a card and read all 64 blocks of a 1K card, calling `callback` for each one.
+///
+/// # Safety
+/// `reader` must be a live handle and `key` NULL or 6 readable bytes. The `data` pointer
+/// passed to `callback` is only valid during the call.
+#[no_mangle]
+pub unsafe extern "C" fn rfid_dump(reader: *mut RfidReader, key: *const u8, key_type: c_int, timeout_ms: u32, callback: RfidBlockCallback, user_data: *mut c_void) -> c_int {
+    let reader = match reader.as_ref() {
+        Some(reader) => reader,
+        None => return invalid("reader is NULL"),
+    };
+    let key_type = match key_type_arg(key_type) {
+        Some(key_type) => key_type,
+        None => return invalid("key_type must be RFID_KEY_A or RFID_KEY_B"),
+    };
+    let callback = match callback {
+        Some(callback) => callback,
+        None => return invalid("callback is required"),
+    };
+    let key = key_arg(key);
+
+    guarded(|| match reader.inner.dump(&key, key_type, timeout_arg(timeout_ms))? {
+        None => Ok(RFID_NO_CARD),
+        Some((_, blocks)) => {
+            for (block, data) in blocks.iter().enumerate() {
+                let data = data.as_ref().map_or(ptr::null(), |data| data.as_ptr());
+                callback(block as u8, data, user_data);
+            }
+            Ok(RFID_OK)
+        },
+    })
+}
+
+/// Message for the last error on this thread, empty if there was none.
+/// The string stays valid until the next call on the same thread.
+#[no_mangle]
+pub extern "C" fn rfid_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ptr())
+}
@@ -3,6 +3,14 @@ pub mod rfid;
 pub mod ui;
 pub mod utils;
 
+// Python bindings for the reader core
+#[cfg(feature = "python")]
+pub mod python;
+
+// C ABI for the reader core
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 // Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const AUTHORS: &str = env!("CARGO_PKG_AUTHORS");
@@ -0,0 +1,102 @@
+//! Python extension module exposing the native MFRC522 driver, so Python scripts
+//! can use the Rust reader directly instead of going through the python_bridge.
+//!
+//! Build and install with `maturin develop --release` (see pyproject.toml), then:
+//!
+//! ```python
+//! import rust_rfid_nfc_toolkit as rfid
+//! reader = rfid.Reader()
+//! uid = reader.read_uid(timeout=10)
+//! ```
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::time::Duration;
+
+use crate::rfid::card::{KeyType, BLOCK_SIZE, DEFAULT_KEY};
+use crate::rfid::constants::{RESET_PIN, SPI_BUS, SPI_DEVICE};
+use crate::rfid::mfrc522::MFRC522Wrapper;
+
+/// the MFRC522 reader, every call waits up to `timeout` seconds for a card
+/// and returns None if none was presented
+#[pyclass(name = "Reader")]
+pub struct PyReader {
+    inner: MFRC522Wrapper,
+}
+
+#[pymethods]
+impl PyReader {
+    #[new]
+    #[pyo3(signature = (spi_bus = SPI_BUS, spi_device = SPI_DEVICE, reset_pin = RESET_PIN))]
+    fn new(spi_bus: u8, spi_device: u8, reset_pin: u8) -> PyResult<Self> {
+        Ok(PyReader {
+            inner: MFRC522Wrapper::new(spi_bus, spi_device, reset_pin)?,
+        })
+    }
+
+    /// UID of the next card as bytes
+    #[pyo3(signature = (timeout = 5.0))]
+    fn read_uid(&self, py: Python<'_>, timeout: f64) -> PyResult<Option<Vec<u8>>> {
+        let timeout = timeout_arg(timeout);
+        Ok(py.detach(|| self.inner.read_uid(timeout))?)
+    }
+
+    /// the 16 bytes of `block`
+    #[pyo3(signature = (block, key = None, key_type = "A", timeout = 5.0))]
+    fn read_block(&self, py: Python<'_>, block: u8, key: Option<Vec<u8>>, key_type: &str, timeout: f64) -> PyResult<Option<Vec<u8>>> {
+        let (key, key_type, timeout) = (key_arg(key)?, key_type_arg(key_type)?, timeout_arg(timeout));
+        let result = py.detach(|| self.inner.read_block(block, &key, key_type, timeout))?;
+        Ok(result.map(|(_, data)| data.to_vec()))
+    }
+
+    /// write up to 16 bytes (zero padded) to a data block, returns the card's UID
+    #[pyo3(signature = (block, data, key = None, key_type = "A", timeout = 5.0))]
+    fn write_block(&self, py: Python<'_>, block: u8, data: Vec<u8>, key: Option<Vec<u8>>, key_type: &str, timeout: f64) -> PyResult<Option<Vec<u8>>> {
+        if data.len() > BLOCK_SIZE {
+            return Err(PyValueError::new_err(format!("A block holds {} bytes, got {}", BLOCK_SIZE, data.len())));
+        }
+        let mut block_data = [0u8; BLOCK_SIZE];
+        block_data[..data.len()].copy_from_slice(&data);
+
+        let (key, key_type, timeout) = (key_arg(key)?, key_type_arg(key_type)?, timeout_arg(timeout));
+        Ok(py.detach(|| self.inner.write_block(block, &block_data, &key, key_type, timeout))?)
+    }
+
+    /// (uid, blocks) for a 1K card, blocks of sectors that refused the key are None
+    #[pyo3(signature = (key = None, key_type = "A", timeout = 5.0))]
+    fn dump(&self, py: Python<'_>, key: Option<Vec<u8>>, key_type: &str, timeout: f64) -> PyResult<Option<(Vec<u8>, Vec<Option<Vec<u8>>>)>> {
+        let (key, key_type, timeout) = (key_arg(key)?, key_type_arg(key_type)?, timeout_arg(timeout));
+        let result = py.detach(|| self.inner.dump(&key, key_type, timeout))?;
+        Ok(result.map(|(uid, blocks)| (uid, blocks.into_iter().map(|b| b.map(|data| data.to_vec())).collect())))
+    }
+
+    /// turn the antenna off and leave the chip idle
+    fn close(&self) -> PyResult<()> {
+        Ok(self.inner.cleanup()?)
+    }
+}
+
+fn key_arg(key: Option<Vec<u8>>) -> PyResult<[u8; 6]> {
+    match key {
+        None => Ok(DEFAULT_KEY),
+        Some(key) => key.try_into().map_err(|_| PyValueError::new_err("Keys are 6 bytes")),
+    }
+}
+
+fn key_type_arg(key_type: &str) -> PyResult<KeyType> {
+    match key_type {
+        "A" | "a" => Ok(KeyType::A),
+        "B" | "b" => Ok(KeyType::B),
+        _ => Err(PyValueError::new_err("key_type must be \"A\" or \"B\"")),
+    }
+}
+
+fn timeout_arg(seconds: f64) -> Duration {
+    Duration::from_secs_f64(seconds.max(0.0))
+}
+
+#[pymodule]
+fn rust_rfid_nfc_toolkit(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyReader>()?;
+    m.add("VERSION", crate::VERSION)?;
+    Ok(())
+}
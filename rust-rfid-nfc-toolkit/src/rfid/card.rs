@@ -0,0 +1,326 @@
+use anyhow::Result;
+use log::debug;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::rfid::constants::*;
+use crate::rfid::mfrc522::{MFRC522, MFRC522Wrapper};
+
+/// bytes in a MIFARE Classic block
+pub const BLOCK_SIZE: usize = 16;
+/// blocks on a MIFARE Classic 1K card (16 sectors of 4 blocks)
+pub const BLOCKS_1K: u8 = 64;
+/// factory default key for both key A and key B
+pub const DEFAULT_KEY: [u8; 6] = [0xFF; 6];
+
+/// how long to wait between polls while waiting for a card
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// most bytes we read back from the FIFO for one command
+const MAX_RESPONSE_LEN: usize = 16;
+
+/// which sector key to authenticate with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    A,
+    B,
+}
+
+impl KeyType {
+    fn auth_command(self) -> u8 {
+        match self {
+            KeyType::A => PICC_AUTHENT1A,
+            KeyType::B => PICC_AUTHENT1B,
+        }
+    }
+}
+
+/// result of a transceive/authenticate command sent to the card
+struct CardResponse {
+    data: Vec<u8>,
+    bits: usize,
+}
+
+/// sector trailer of the sector holding `block`
+pub fn sector_trailer(block: u8) -> u8 {
+    block - block % 4 + 3
+}
+
+impl MFRC522 {
+    /// send `data` to the card and collect its answer, this is the one place
+    /// that talks to the card, everything below is built on it
+    fn to_card(&mut self, command: u8, data: &[u8]) -> Result<Option<CardResponse>> {
+        let (irq_en, wait_irq) = match command {
+            COMMAND_MF_AUTHENT => (0x12, 0x10),
+            COMMAND_TRANSCEIVE => (0x77, 0x30),
+            _ => (0x00, 0x00),
+        };
+
+        self.write_register(REG_COM_I_EN, irq_en | 0x80)?;
+        self.clear_bit_mask(REG_COM_IRQ, 0x80)?;
+        self.set_bit_mask(REG_FIFO_LEVEL, 0x80)?; // flush the FIFO
+        self.write_register(REG_COMMAND, COMMAND_IDLE)?;
+
+        for &byte in data {
+            self.write_register(REG_FIFO_DATA, byte)?;
+        }
+
+        self.write_register(REG_COMMAND, command)?;
+        if command == COMMAND_TRANSCEIVE {
+            self.set_bit_mask(REG_BIT_FRAMING, 0x80)?; // StartSend
+        }
+
+        // wait for the command to finish, the chip's timer fires if the card never answers
+        let mut irq = 0;
+        let mut finished = false;
+        for _ in 0..2000 {
+            irq = self.read_register(REG_COM_IRQ)?;
+            if irq & 0x01 != 0 || irq & wait_irq != 0 {
+                finished = true;
+                break;
+            }
+        }
+        self.clear_bit_mask(REG_BIT_FRAMING, 0x80)?;
+
+        if !finished || self.read_register(REG_ERROR)? & 0x1B != 0 {
+            return Ok(None);
+        }
+        // timer interrupt, no card answered
+        if irq & irq_en & 0x01 != 0 {
+            return Ok(None);
+        }
+
+        let mut response = CardResponse { data: Vec::new(), bits: 0 };
+        if command == COMMAND_TRANSCEIVE {
+            let fifo_len = self.read_register(REG_FIFO_LEVEL)? as usize;
+            let last_bits = (self.read_register(REG_CONTROL)? & 0x07) as usize;
+            response.bits = match (fifo_len, last_bits) {
+                (0, _) => 0,
+                (len, 0) => len * 8,
+                (len, bits) => (len - 1) * 8 + bits,
+            };
+
+            for _ in 0..fifo_len.clamp(1, MAX_RESPONSE_LEN) {
+                response.data.push(self.read_register(REG_FIFO_DATA)?);
+            }
+        }
+
+        Ok(Some(response))
+    }
+
+    /// let the chip's coprocessor compute the ISO 14443A CRC of `data`
+    fn calculate_crc(&mut self, data: &[u8]) -> Result<[u8; 2]> {
+        self.clear_bit_mask(REG_DIV_IRQ, 0x04)?;
+        self.set_bit_mask(REG_FIFO_LEVEL, 0x80)?;
+
+        for &byte in data {
+            self.write_register(REG_FIFO_DATA, byte)?;
+        }
+        self.write_register(REG_COMMAND, COMMAND_CALC_CRC)?;
+
+        for _ in 0..0xFF {
+            if self.read_register(REG_DIV_IRQ)? & 0x04 != 0 {
+                break;
+            }
+        }
+
+        Ok([self.read_register(REG_CRC_RESULT_L)?, self.read_register(REG_CRC_RESULT_H)?])
+    }
+
+    /// send `data` followed by its CRC
+    fn to_card_with_crc(&mut self, data: &[u8]) -> Result<Option<CardResponse>> {
+        let crc = self.calculate_crc(data)?;
+        let mut frame = data.to_vec();
+        frame.extend_from_slice(&crc);
+        self.to_card(COMMAND_TRANSCEIVE, &frame)
+    }
+
+    /// is there a card in the field
+    pub fn request(&mut self, mode: u8) -> Result<bool> {
+        self.write_register(REG_BIT_FRAMING, 0x07)?;
+        let response = self.to_card(COMMAND_TRANSCEIVE, &[mode])?;
+        Ok(matches!(response, Some(r) if r.bits == 0x10))
+    }
+
+    /// get the UID of the card in the field, checked against its BCC byte
+    pub fn anticoll(&mut self) -> Result<Option<Vec<u8>>> {
+        self.write_register(REG_BIT_FRAMING, 0x00)?;
+        let response = match self.to_card(COMMAND_TRANSCEIVE, &[PICC_ANTICOLL, 0x20])? {
+            Some(response) if response.data.len() == 5 => response,
+            _ => return Ok(None),
+        };
+
+        let bcc = response.data[..4].iter().fold(0, |acc, b| acc ^ b);
+        if bcc != response.data[4] {
+            debug!("UID checksum mismatch");
+            return Ok(None);
+        }
+
+        Ok(Some(response.data[..4].to_vec()))
+    }
+
+    /// select the card so it accepts authentication, returns its SAK
+    pub fn select_tag(&mut self, uid: &[u8]) -> Result<Option<u8>> {
+        let bcc = uid.iter().fold(0, |acc, b| acc ^ b);
+        let mut frame = vec![PICC_SELECTTAG, 0x70];
+        frame.extend_from_slice(uid);
+        frame.push(bcc);
+
+        match self.to_card_with_crc(&frame)? {
+            Some(response) if response.bits == 0x18 => Ok(Some(response.data[0])),
+            _ => Ok(None),
+        }
+    }
+
+    /// request, anticollision and select in one go, returns the UID of the selected card
+    pub fn select_card(&mut self) -> Result<Option<Vec<u8>>> {
+        if !self.request(PICC_REQIDL)? {
+            return Ok(None);
+        }
+        let uid = match self.anticoll()? {
+            Some(uid) => uid,
+            None => return Ok(None),
+        };
+        Ok(self.select_tag(&uid)?.map(|_| uid))
+    }
+
+    /// authenticate the sector holding `block`
+    pub fn authenticate(&mut self, key_type: KeyType, block: u8, key: &[u8; 6], uid: &[u8]) -> Result<()> {
+        let mut frame = vec![key_type.auth_command(), block];
+        frame.extend_from_slice(key);
+        frame.extend_from_slice(&uid[..uid.len().min(4)]);
+
+        self.to_card(COMMAND_MF_AUTHENT, &frame)?;
+
+        // MFCrypto1On is only set when the card accepted the key
+        if self.read_register(REG_STATUS2)? & 0x08 == 0 {
+            return Err(anyhow::anyhow!("Authentication failed for block {} with key {:?}", block, key_type));
+        }
+        Ok(())
+    }
+
+    /// leave the authenticated state so the next card can be selected
+    pub fn stop_crypto(&mut self) -> Result<()> {
+        self.clear_bit_mask(REG_STATUS2, 0x08)
+    }
+
+    /// read one block, the sector must be authenticated
+    pub fn read_block(&mut self, block: u8) -> Result<[u8; BLOCK_SIZE]> {
+        match self.to_card_with_crc(&[PICC_READ, block])? {
+            Some(response) if response.data.len() == BLOCK_SIZE => {
+                let mut data = [0u8; BLOCK_SIZE];
+                data.copy_from_slice(&response.data);
+                Ok(data)
+            },
+            _ => Err(anyhow::anyhow!("Failed to read block {}", block)),
+        }
+    }
+
+    /// write one block, the sector must be authenticated
+    pub fn write_block(&mut self, block: u8, data: &[u8; BLOCK_SIZE]) -> Result<()> {
+        // the card acknowledges both the command and the data with a 4 bit ACK (0xA)
+        let acked = |response: Option<CardResponse>| {
+            matches!(response, Some(r) if r.bits == 4 && r.data[0] & 0x0F == 0x0A)
+        };
+
+        if !acked(self.to_card_with_crc(&[PICC_WRITE, block])?) {
+            return Err(anyhow::anyhow!("Card refused write to block {}", block));
+        }
+        if !acked(self.to_card_with_crc(data)?) {
+            return Err(anyhow::anyhow!("Failed to write block {}", block));
+        }
+        Ok(())
+    }
+
+    /// poll for a card until one is selected or `timeout` passes
+    pub fn wait_for_card(&mut self, timeout: Duration) -> Result<Option<Vec<u8>>> {
+        let start = Instant::now();
+        loop {
+            if let Some(uid) = self.select_card()? {
+                return Ok(Some(uid));
+            }
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// wait for a card, authenticate and read one block
+    pub fn read_card_block(&mut self, block: u8, key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<(Vec<u8>, [u8; BLOCK_SIZE])>> {
+        let uid = match self.wait_for_card(timeout)? {
+            Some(uid) => uid,
+            None => return Ok(None),
+        };
+
+        let result = self.authenticate(key_type, block, key, &uid).and_then(|_| self.read_block(block));
+        self.stop_crypto()?;
+        result.map(|data| Some((uid, data)))
+    }
+
+    /// wait for a card, authenticate and write one block.
+    /// Sector trailers are refused, a bad trailer locks the sector for good.
+    pub fn write_card_block(&mut self, block: u8, data: &[u8; BLOCK_SIZE], key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<Vec<u8>>> {
+        if block == 0 || block == sector_trailer(block) {
+            return Err(anyhow::anyhow!("Block {} is the manufacturer block or a sector trailer", block));
+        }
+
+        let uid = match self.wait_for_card(timeout)? {
+            Some(uid) => uid,
+            None => return Ok(None),
+        };
+
+        let result = self.authenticate(key_type, block, key, &uid).and_then(|_| self.write_block(block, data));
+        self.stop_crypto()?;
+        result.map(|_| Some(uid))
+    }
+
+    /// wait for a card and read every block of a 1K card with one key,
+    /// blocks of sectors that refuse the key come back as None
+    pub fn dump(&mut self, key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<(Vec<u8>, Vec<Option<[u8; BLOCK_SIZE]>>)>> {
+        let uid = match self.wait_for_card(timeout)? {
+            Some(uid) => uid,
+            None => return Ok(None),
+        };
+
+        let mut blocks = Vec::with_capacity(BLOCKS_1K as usize);
+        for sector_start in (0..BLOCKS_1K).step_by(4) {
+            if self.authenticate(key_type, sector_start, key, &uid).is_err() {
+                debug!("Sector {} refused the key", sector_start / 4);
+                blocks.extend([None; 4]);
+                // a failed authentication halts the card, wake it up for the next sector
+                self.stop_crypto()?;
+                self.select_card()?;
+                continue;
+            }
+
+            for block in sector_start..sector_start + 4 {
+                blocks.push(self.read_block(block).ok());
+            }
+        }
+        self.stop_crypto()?;
+
+        Ok(Some((uid, blocks)))
+    }
+}
+
+impl MFRC522Wrapper {
+    /// wait for a card and return its UID
+    pub fn read_uid(&self, timeout: Duration) -> Result<Option<Vec<u8>>> {
+        self.with_reader(|mfrc522| mfrc522.wait_for_card(timeout))
+    }
+
+    /// wait for a card and read one block
+    pub fn read_block(&self, block: u8, key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<(Vec<u8>, [u8; BLOCK_SIZE])>> {
+        self.with_reader(|mfrc522| mfrc522.read_card_block(block, key, key_type, timeout))
+    }
+
+    /// wait for a card and write one data block
+    pub fn write_block(&self, block: u8, data: &[u8; BLOCK_SIZE], key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<Vec<u8>>> {
+        self.with_reader(|mfrc522| mfrc522.write_card_block(block, data, key, key_type, timeout))
+    }
+
+    /// wait for a card and read all of it
+    pub fn dump(&self, key: &[u8; 6], key_type: KeyType, timeout: Duration) -> Result<Option<(Vec<u8>, Vec<Option<[u8; BLOCK_SIZE]>>)>> {
+        self.with_reader(|mfrc522| mfrc522.dump(key, key_type, timeout))
+    }
+}
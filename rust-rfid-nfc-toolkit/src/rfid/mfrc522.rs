@@ -11,26 +11,34 @@ use crate::rfid::constants::*;
 pub struct MFRC522 {
     spi: Spi,
     reset_pin: OutputPin,
+    spi_bus: u8,
+    spi_device: u8,
+}
+
+/// open the SPI device the reader is wired to
+fn open_spi(spi_bus: u8, spi_device: u8) -> Result<Spi> {
+    // bring down the speed when we iInitialize SPI for better reliability
+    let spi = Spi::new(
+        match spi_bus {
+            0 => Bus::Spi0,
+            1 => Bus::Spi1,
+            _ => return Err(anyhow::anyhow!("Invalid SPI bus")),
+        },
+        match spi_device {
+            0 => SlaveSelect::Ss0,
+            1 => SlaveSelect::Ss1,
+            _ => return Err(anyhow::anyhow!("Invalid SPI device")),
+        },
+        SPI_FREQUENCY_HZ,
+        Mode::Mode0
+    )?;
+    Ok(spi)
 }
 
 impl MFRC522 {
     /// here we create a new MFRC522 instance
     pub fn new(spi_bus: u8, spi_device: u8, reset_pin: u8) -> Result<Self> {
-        // bring down the speed when we iInitialize SPI for better reliability
-        let spi = Spi::new(
-            match spi_bus {
-                0 => Bus::Spi0,
-                1 => Bus::Spi1,
-                _ => return Err(anyhow::anyhow!("Invalid SPI bus")),
-            },
-            match spi_device {
-                0 => SlaveSelect::Ss0,
-                1 => SlaveSelect::Ss1,
-                _ => return Err(anyhow::anyhow!("Invalid SPI device")),
-            },
-            SPI_FREQUENCY_HZ,
-            Mode::Mode0
-        )?;
+        let spi = open_spi(spi_bus, spi_device)?;
         
         // and voila we initialize GPIO reset pin
         let gpio = Gpio::new()?;
@@ -40,6 +48,8 @@ impl MFRC522 {
         let mut mfrc522 = MFRC522 {
             spi,
             reset_pin,
+            spi_bus,
+            spi_device,
         };
         
         // Initialize the MFRC522
@@ -95,6 +105,24 @@ impl MFRC522 {
         Ok(())
     }
     
+    /// check the chip still answers, a missing or unpowered reader leaves MISO
+    /// floating so the version register reads back as 0x00 or 0xFF
+    pub fn is_responding(&mut self) -> bool {
+        matches!(self.read_register(REG_VERSION), Ok(version) if version != 0x00 && version != 0xFF)
+    }
+    
+    /// reopen the SPI device and run the init sequence again after the reader was lost
+    pub fn reconnect(&mut self) -> Result<()> {
+        self.spi = open_spi(self.spi_bus, self.spi_device)?;
+        self.init()?;
+        
+        if !self.is_responding() {
+            return Err(anyhow::anyhow!("Reader not responding"));
+        }
+        
+        Ok(())
+    }
+    
     /// we need to be good boys and clean up MFRC522 resources
     pub fn cleanup(&mut self) -> Result<()> {
         // here we turn off the antenna
@@ -189,6 +217,30 @@ impl MFRC522Wrapper {
         }
     }
     
+    /// check whether the reader still answers on the SPI bus
+    pub fn is_responding(&self) -> bool {
+        match self.inner.lock() {
+            Ok(mut mfrc522) => mfrc522.is_responding(),
+            Err(_) => false,
+        }
+    }
+    
+    /// try to bring a lost reader back
+    pub fn reconnect(&self) -> Result<()> {
+        match self.inner.lock() {
+            Ok(mut mfrc522) => mfrc522.reconnect(),
+            Err(_) => Err(anyhow::anyhow!("MFRC522 lock poisoned")),
+        }
+    }
+    
+    /// run `f` with the reader locked
+    pub(crate) fn with_reader<T>(&self, f: impl FnOnce(&mut MFRC522) -> Result<T>) -> Result<T> {
+        match self.inner.lock() {
+            Ok(mut mfrc522) => f(&mut mfrc522),
+            Err(_) => Err(anyhow::anyhow!("MFRC522 lock poisoned")),
+        }
+    }
+    
     /// clean up MFRC522 resources
     pub fn cleanup(&self) -> Result<()> {
         if let Ok(mut mfrc522) = self.inner.lock() {
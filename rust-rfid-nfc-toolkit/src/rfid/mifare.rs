@@ -42,7 +42,7 @@ impl SimpleMifareRW {
 pub fn clone(&self) -> Self {
     SimpleMifareRW {
         mfrc522: self.mfrc522.clone(),
-        python_rfid: PythonRFID::new(&self.python_rfid.python_script_path),
+        python_rfid: self.python_rfid.clone(),
         use_python: self.use_python,
     }
 }    
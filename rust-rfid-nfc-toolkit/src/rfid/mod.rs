@@ -1,10 +1,14 @@
+pub mod card;
 pub mod constants;
 pub mod mfrc522;
 pub mod mifare;
 pub mod python_bridge;
+pub mod supervisor;
 
 // Re-export commonly used types
+pub use card::KeyType;
 pub use constants::*;
 pub use mfrc522::{MFRC522, MFRC522Wrapper};
 pub use mifare::SimpleMifareRW;
 pub use python_bridge::PythonRFID;
+pub use supervisor::{ReaderStatus, ReaderSupervisor};
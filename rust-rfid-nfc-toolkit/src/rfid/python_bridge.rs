@@ -1,9 +1,20 @@
 use anyhow::{Context, Result};
 use log::{debug, info, warn};
-use serde::{Deserialize, Serialize};
-use std::process::Command;
-use std::time::Duration;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::rfid::constants::{CARD_DETECTION_TIMEOUT_SECS, KEY_TESTING_TIMEOUT_SECS};
+
+/// how long the worker gets to answer a ping
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
 
 // define response structs for deserializing Python JSON output
 #[derive(Debug, Deserialize)]
@@ -29,26 +40,84 @@ struct KeySector {
     pub key_type: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct PingResponse {
+    pub success: bool,
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TestKeysResponse {
     pub success: bool,
     pub uid: Option<String>,
-    #[serde(rename = "sectors")]
+    #[serde(rename = "sectors", default)]
     pub sectors: Vec<KeySector>,
     pub error: Option<String>,
 }
 
 /// convert UID string to vec of bytes
 pub fn uid_string_to_bytes(uid_str: &str) -> Vec<u8> {
-    uid_str
-        .split_whitespace()
-        .filter_map(|s| u8::from_str_radix(s, 16).ok())
-        .collect()
+    uid_codec::parse_hex_lenient(uid_str).unwrap_or_default()
+}
+
+/// the long-lived `rfid_wrapper.py serve` process and the lines it has printed
+struct Worker {
+    child: Child,
+    stdin: ChildStdin,
+    responses: Receiver<String>,
+}
+
+impl Worker {
+    fn spawn(script_path: &str) -> Result<Self> {
+        let mut child = Command::new("python3")
+            .arg(script_path)
+            .arg("serve")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .context("Failed to start Python worker")?;
+
+        let stdin = child.stdin.take().context("Python worker has no stdin")?;
+        let stdout = child.stdout.take().context("Python worker has no stdout")?;
+
+        // a reader thread so waiting for an answer can time out,
+        // the channel closes when the process exits
+        let (tx, responses) = channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        info!("Started Python worker (pid {})", child.id());
+        Ok(Worker { child, stdin, responses })
+    }
+
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    fn kill(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        self.kill();
+    }
 }
 
-/// create a struct that encapsulates RFID operations using Python's SimpleMFRC522 library
+/// create a struct that encapsulates RFID operations using Python's SimpleMFRC522 library.
+/// Commands go to one supervised `rfid_wrapper.py serve` process as line-delimited JSON,
+/// it is started on first use, restarted if it crashed and killed if it stops answering.
 pub struct PythonRFID {
-   pub  python_script_path: String,
+    pub python_script_path: String,
+    worker: Arc<Mutex<Option<Worker>>>,
+    next_id: Arc<AtomicU64>,
 }
 
 impl PythonRFID {
@@ -56,32 +125,102 @@ impl PythonRFID {
     pub fn new(script_path: &str) -> Self {
         PythonRFID {
             python_script_path: script_path.to_owned(),
+            worker: Arc::new(Mutex::new(None)),
+            next_id: Arc::new(AtomicU64::new(1)),
         }
     }
     
-    /// read a card using Python
-    pub fn read_card(&self) -> Result<(Vec<u8>, String)> {
-        info!("Reading card using the bridge...");
+    /// create a clone that talks to the same worker process
+    pub fn clone(&self) -> Self {
+        PythonRFID {
+            python_script_path: self.python_script_path.clone(),
+            worker: self.worker.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+    
+    /// send one command to the worker and wait up to `timeout` for its answer
+    fn request<T: DeserializeOwned>(&self, command: &str, args: Value, timeout: Duration) -> Result<T> {
+        let mut guard = self.worker.lock().map_err(|_| anyhow::anyhow!("Python worker lock poisoned"))?;
         
-        // execute Python script with "read" command
-        let output = Command::new("python3")
-            .arg(&self.python_script_path)
-            .arg("read")
-            .output()
-            .context("Failed to execute Python script")?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("Python script error: {}", stderr);
-            return Err(anyhow::anyhow!("Python script error: {}", stderr));
+        // (re)start the worker if this is the first command or it died since the last one
+        if !guard.as_mut().is_some_and(|worker| worker.is_running()) {
+            if guard.is_some() {
+                warn!("Python worker exited, restarting it");
+            }
+            *guard = Some(Worker::spawn(&self.python_script_path)?);
+        }
+        let worker = guard.as_mut().expect("worker was just started");
+        
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut request = json!({ "id": id, "command": command });
+        if let (Value::Object(request), Value::Object(args)) = (&mut request, args) {
+            request.extend(args);
         }
+        debug!("Python request: {}", request);
         
-        // parse JSON response
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        debug!("Python response: {}", stdout);
+        if let Err(e) = writeln!(worker.stdin, "{}", request).and_then(|_| worker.stdin.flush()) {
+            worker.kill();
+            *guard = None;
+            return Err(anyhow::anyhow!("Failed to send command to Python worker: {}", e));
+        }
+        
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match worker.responses.recv_timeout(remaining) {
+                Ok(line) => {
+                    debug!("Python response: {}", line);
+                    let response: Value = match serde_json::from_str(&line) {
+                        Ok(response) => response,
+                        // not one of ours, e.g. a library printing to stdout
+                        Err(_) => continue,
+                    };
+                    // skip answers to earlier requests
+                    if response.get("id").and_then(Value::as_u64) != Some(id) {
+                        continue;
+                    }
+                    return serde_json::from_value(response).context("Failed to parse Python response");
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    warn!("Python worker did not answer '{}' within {:?}, restarting it", command, timeout);
+                    worker.kill();
+                    *guard = None;
+                    return Err(anyhow::anyhow!("Python command '{}' timed out after {:?}", command, timeout));
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    let status = worker.child.wait().map(|s| s.to_string()).unwrap_or_else(|e| e.to_string());
+                    *guard = None;
+                    return Err(anyhow::anyhow!("Python worker exited while running '{}' ({})", command, status));
+                },
+            }
+        }
+    }
+    
+    /// check the worker is up and answering, starting it if needed
+    pub fn health_check(&self) -> Result<()> {
+        let response: PingResponse = self.request("ping", json!({}), HEALTH_CHECK_TIMEOUT)?;
+        if !response.success {
+            return Err(anyhow::anyhow!("Python worker unhealthy: {}",
+                response.error.unwrap_or_else(|| "Unknown error".to_string())));
+        }
+        Ok(())
+    }
+    
+    /// stop the worker, the next command starts a new one
+    pub fn shutdown(&self) {
+        if let Ok(mut guard) = self.worker.lock() {
+            if let Some(mut worker) = guard.take() {
+                worker.kill();
+            }
+        }
+    }
+    
+    /// read a card using Python
+    pub fn read_card(&self) -> Result<(Vec<u8>, String)> {
+        info!("Reading card using the bridge...");
         
-        let response: ReadCardResponse = serde_json::from_str(&stdout)
-            .context("Failed to parse Python response")?;
+        let response: ReadCardResponse = self.request("read", json!({}), Duration::from_secs(CARD_DETECTION_TIMEOUT_SECS))?;
             
         if !response.success {
             return Err(anyhow::anyhow!("Python read failed: {}", 
@@ -103,26 +242,9 @@ impl PythonRFID {
     pub fn write_card(&self, text: &str) -> Result<Vec<u8>> {
         info!("Writing card using Python bridge...");
         
-        // execute Python script with "write" command
-        let output = Command::new("python3")
-            .arg(&self.python_script_path)
-            .arg("write")
-            .arg(text)
-            .output()
-            .context("Failed to execute Python script")?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("Python script error: {}", stderr);
-            return Err(anyhow::anyhow!("Python script error: {}", stderr));
-        }
-        
-        // parse JSON response
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        debug!("Python response: {}", stdout);
-        
-        let response: WriteCardResponse = serde_json::from_str(&stdout)
-            .context("Failed to parse Python response")?;
+        // writing waits for the card once to write and once to read back the UID
+        let timeout = Duration::from_secs(CARD_DETECTION_TIMEOUT_SECS * 2);
+        let response: WriteCardResponse = self.request("write", json!({ "text": text }), timeout)?;
             
         if !response.success {
             return Err(anyhow::anyhow!("Python write failed: {}", 
@@ -143,25 +265,7 @@ impl PythonRFID {
     pub fn test_keys(&self) -> Result<Vec<(u8, Vec<u8>)>> {
         info!("Testing keys using the bridge...");
         
-        // execute Python script with "test_keys" command
-        let output = Command::new("python3")
-            .arg(&self.python_script_path)
-            .arg("test_keys")
-            .output()
-            .context("Failed to execute Python script")?;
-            
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            warn!("Python script error: {}", stderr);
-            return Err(anyhow::anyhow!("Python script error: {}", stderr));
-        }
-        
-        // parse JSON response
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        debug!("Python response: {}", stdout);
-        
-        let response: TestKeysResponse = serde_json::from_str(&stdout)
-            .context("Failed to parse Python response")?;
+        let response: TestKeysResponse = self.request("test_keys", json!({}), Duration::from_secs(KEY_TESTING_TIMEOUT_SECS))?;
             
         if !response.success {
             return Err(anyhow::anyhow!("Python key test failed: {}", 
@@ -0,0 +1,113 @@
+use log::{info, warn};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::rfid::mfrc522::MFRC522Wrapper;
+
+/// how often a connected reader is checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// first delay between reconnection attempts, doubled after each failure
+const RECONNECT_INITIAL_DELAY: Duration = Duration::from_millis(500);
+/// longest delay between reconnection attempts
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(30);
+/// granularity of the sleeps so stop() doesn't wait a whole backoff period
+const SLEEP_STEP: Duration = Duration::from_millis(100);
+
+/// whether the reader is answering on the SPI bus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderStatus {
+    Connected,
+    Disconnected,
+}
+
+impl fmt::Display for ReaderStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReaderStatus::Connected => write!(f, "Connected"),
+            ReaderStatus::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+/// background thread that watches the reader and re-initializes it when it
+/// disappears (loose wiring, reader power cycle) instead of erroring until restart
+pub struct ReaderSupervisor {
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ReaderSupervisor {
+    /// start watching the reader, `on_change` is called whenever the status changes
+    pub fn start<F>(reader: MFRC522Wrapper, on_change: F) -> Self
+    where
+        F: Fn(ReaderStatus) + Send + 'static,
+    {
+        let running = Arc::new(AtomicBool::new(true));
+        let running_thread = running.clone();
+
+        let handle = thread::spawn(move || {
+            let mut status = ReaderStatus::Connected;
+            let mut delay = RECONNECT_INITIAL_DELAY;
+            on_change(status);
+
+            while running_thread.load(Ordering::SeqCst) {
+                match status {
+                    ReaderStatus::Connected => {
+                        if !reader.is_responding() {
+                            warn!("Reader stopped responding");
+                            status = ReaderStatus::Disconnected;
+                            delay = RECONNECT_INITIAL_DELAY;
+                            on_change(status);
+                            continue;
+                        }
+                        sleep_while_running(&running_thread, CHECK_INTERVAL);
+                    },
+                    ReaderStatus::Disconnected => {
+                        match reader.reconnect() {
+                            Ok(_) => {
+                                info!("Reader reconnected");
+                                status = ReaderStatus::Connected;
+                                on_change(status);
+                            },
+                            Err(e) => {
+                                warn!("Reconnect failed, retrying in {:?}: {}", delay, e);
+                                sleep_while_running(&running_thread, delay);
+                                delay = (delay * 2).min(RECONNECT_MAX_DELAY);
+                            }
+                        }
+                    },
+                }
+            }
+        });
+
+        ReaderSupervisor {
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    /// stop watching and wait for the thread to finish
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ReaderSupervisor {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn sleep_while_running(running: &AtomicBool, duration: Duration) {
+    let mut slept = Duration::ZERO;
+    while slept < duration && running.load(Ordering::SeqCst) {
+        thread::sleep(SLEEP_STEP);
+        slept += SLEEP_STEP;
+    }
+}
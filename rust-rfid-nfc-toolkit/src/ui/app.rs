@@ -12,6 +12,7 @@ use fltk::{
 };
 use log::{info, warn};
 
+
 /// Represents a command to be processed by the worker thread
 #[derive(Debug, Clone)]
 pub enum WriterCommand {
@@ -38,7 +39,7 @@ fn create_styled_button(label: &str) -> Button {
 }
 
 /// Create a modern style UI for the RFID toolkit
-pub fn create_ui(sender: std::sync::mpsc::Sender<WriterCommand>) -> Result<(Window, Input, Frame, TextDisplay, TextBuffer)> {
+pub fn create_ui(sender: std::sync::mpsc::Sender<WriterCommand>) -> Result<(Window, Input, Frame, TextDisplay, TextBuffer, Frame)> {
     // Create main window
     let mut window = Window::default()
         .with_size(550, 450)
@@ -83,6 +84,13 @@ pub fn create_ui(sender: std::sync::mpsc::Sender<WriterCommand>) -> Result<(Wind
     button_flex.end();
     flex.fixed(&button_flex, 45);
     
+    // Operators in read-only mode can read and test keys but not write
+    if let Some(reason) = safe_mode::read_only_reason() {
+        write_btn.deactivate();
+        input.deactivate();
+        window.set_label(&format!("RFID/NFC Writer - read-only ({})", reason));
+    }
+    
     // Info section
     let mut uid_label = Frame::default().with_label("UID:");
     uid_label.set_label_size(16);
@@ -110,8 +118,11 @@ pub fn create_ui(sender: std::sync::mpsc::Sender<WriterCommand>) -> Result<(Wind
     let mut bottom_flex = Flex::default().row();
     bottom_flex.set_spacing(10);
     
-    // Add spacer to push exit button to right
-    let _spacer = Frame::default();
+    // Reader status on the left, it also pushes the exit button to the right
+    let mut status_label = Frame::default().with_label("Reader: Connecting...");
+    status_label.set_label_size(14);
+    status_label.set_align(Align::Left | Align::Inside);
+    status_label.set_label_color(Color::from_u32(0xdcdfe4)); // Light text
     
     let mut exit_btn = create_styled_button("Exit");
     exit_btn.set_color(Color::from_u32(0xe06c75)); // Red color
@@ -167,5 +178,5 @@ pub fn create_ui(sender: std::sync::mpsc::Sender<WriterCommand>) -> Result<(Wind
         }
     });
     
-    Ok((window, input, uid_label, data_display, buffer))
+    Ok((window, input, uid_label, data_display, buffer, status_label))
 }
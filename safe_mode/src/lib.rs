@@ -0,0 +1,96 @@
+//! Read-only ("safe") mode, for operators who may read cards but must never attack or
+//! change one.
+//!
+//! An administrator turns it on for the whole machine by creating
+//! `/etc/nfc-tools/read-only`, or for some logins by putting them in the
+//! `nfc-operators` group; neither can be undone without root. `--read-only` or
+//! `NFC_READ_ONLY=1` turn it on for one run. Nothing turns it off.
+//!
+//! Each tool decides what read-only mode takes away: the block editor its writes,
+//! formats and key changes, the attack toolkit its key searches, attacks, UID writes
+//! and cloning, the RFID toolkit its writer and the master tool's write items. Those
+//! are hidden from the menus and refused if asked for anyway, see [`refuse`].
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Created by an administrator to make every tool on the machine read-only
+pub const READ_ONLY_MARKER: &str = "/etc/nfc-tools/read-only";
+/// Logins in this group only get the read-only tools
+pub const OPERATOR_GROUP: &str = "nfc-operators";
+
+static READ_ONLY: OnceLock<Option<&'static str>> = OnceLock::new();
+
+/// Whether attacks and writes are off for this run
+pub fn is_read_only() -> bool {
+    read_only_reason().is_some()
+}
+
+/// Why read-only mode is on, None when everything is allowed
+pub fn read_only_reason() -> Option<&'static str> {
+    *READ_ONLY.get_or_init(|| {
+        if Path::new(READ_ONLY_MARKER).exists() {
+            Some(READ_ONLY_MARKER)
+        } else if in_operator_group() {
+            Some("nfc-operators group")
+        } else if std::env::args().any(|arg| arg == "--read-only") {
+            Some("--read-only")
+        } else if std::env::var("NFC_READ_ONLY").is_ok_and(|value| matches!(value.as_str(), "1" | "yes" | "true")) {
+            Some("NFC_READ_ONLY")
+        } else {
+            None
+        }
+    })
+}
+
+/// Printed instead of running an operation read-only mode doesn't allow
+pub fn refuse() {
+    println!("Not available in read-only mode ({}).", read_only_reason().unwrap_or("read-only"));
+}
+
+// The process's groups as the kernel has them, so the login can't be faked with
+// environment variables
+fn in_operator_group() -> bool {
+    let gid = match fs::read_to_string("/etc/group").ok().and_then(|groups| group_id(&groups, OPERATOR_GROUP)) {
+        Some(gid) => gid,
+        None => return false,
+    };
+
+    fs::read_to_string("/proc/self/status")
+        .map(|status| has_group(&status, &gid))
+        .unwrap_or(false)
+}
+
+// The ID of a group in the text of /etc/group
+fn group_id(groups: &str, name: &str) -> Option<String> {
+    groups.lines()
+        .map(|line| line.split(':').collect::<Vec<_>>())
+        .find(|fields| fields.len() >= 3 && fields[0] == name)
+        .map(|fields| fields[2].to_string())
+}
+
+// Whether the Gid: or Groups: lines of /proc/<pid>/status list `gid`
+fn has_group(status: &str, gid: &str) -> bool {
+    status.lines()
+        .filter(|line| line.starts_with("Gid:") || line.starts_with("Groups:"))
+        .any(|line| line.split_whitespace().skip(1).any(|id| id == gid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_operator_group() {
+        let groups = "root:x:0:\nspi:x:999:pi\nnfc-operators:x:1005:alice,bob\n";
+        assert_eq!(group_id(groups, OPERATOR_GROUP).as_deref(), Some("1005"));
+        assert_eq!(group_id(groups, "gpio"), None);
+
+        let status = "Name:\tnfc\nUid:\t1000\t1000\t1000\t1000\nGid:\t1000\t1000\t1000\t1000\nGroups:\t999 1005 \n";
+        assert!(has_group(status, "1005"));
+        assert!(has_group(status, "1000"));
+        assert!(!has_group(status, "1001"));
+        assert!(!has_group("Uid:\t1005\t1005\t1005\t1005\n", "1005"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Translated messages: the reader app's windows and the console output of the block
+//! editor and the attack toolkit.
+//!
+//! Each program has a catalogue per language in its own `locales/<code>.ftl`, built
+//! into the binary, using the simple part of the Fluent syntax:
+//!
+//! ```text
+//! # comment
+//! menu-file = &File
+//! msg-imported = Successfully imported { $count } items.
+//! about-text = First line
+//!     indented lines continue the message on a new line
+//!
+//!     blank lines between them are kept
+//! ```
+//!
+//! Lookups fall back to English, then to the key itself, so a missing translation
+//! shows up as English text rather than an empty one. The language can be changed
+//! at runtime.
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+/// Setting that follows the system locale (LANG)
+pub const AUTO_LANGUAGE: &str = "auto";
+/// Language of the messages a catalogue doesn't have
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// The catalogues of one program, kept in a static. The sources are normally
+/// `include_str!("../locales/<code>.ftl")`.
+///
+/// ```
+/// use text_catalogue::Catalogues;
+///
+/// static CATALOGUES: Catalogues = Catalogues::new(&[
+///     ("en", "msg-read = Read { $count } blocks\n"),
+///     ("es", "msg-read = { $count } bloques leídos\n"),
+/// ]);
+///
+/// CATALOGUES.set_language("es");
+/// assert_eq!(CATALOGUES.tr_args("msg-read", &[("count", "4")]), "4 bloques leídos");
+/// ```
+pub struct Catalogues {
+    sources: &'static [(&'static str, &'static str)],
+    // parsed the first time a message is looked up
+    messages: OnceLock<HashMap<&'static str, HashMap<String, String>>>,
+    language: RwLock<&'static str>,
+}
+
+impl Catalogues {
+    /// Catalogues from (language code, .ftl source) pairs, English among them
+    pub const fn new(sources: &'static [(&'static str, &'static str)]) -> Self {
+        Catalogues {
+            sources,
+            messages: OnceLock::new(),
+            language: RwLock::new(FALLBACK_LANGUAGE),
+        }
+    }
+
+    /// Language code for a setting, "auto" or an empty one picks it from LANG
+    /// (e.g. es_ES.UTF-8). Languages without a catalogue get English.
+    pub fn resolve_language(&self, setting: &str) -> &'static str {
+        let code = if setting.is_empty() || setting == AUTO_LANGUAGE {
+            std::env::var("LANG").unwrap_or_default()
+        } else {
+            setting.to_string()
+        };
+        let code = code.split(['_', '.', '-']).next().unwrap_or("").to_lowercase();
+
+        self.sources.iter()
+            .map(|(known, _)| *known)
+            .find(|known| *known == code)
+            .unwrap_or(FALLBACK_LANGUAGE)
+    }
+
+    /// Switch the language of the messages
+    pub fn set_language(&self, setting: &str) {
+        let language = self.resolve_language(setting);
+        if let Ok(mut current) = self.language.write() {
+            *current = language;
+        }
+    }
+
+    pub fn current_language(&self) -> &'static str {
+        self.language.read().map(|current| *current).unwrap_or(FALLBACK_LANGUAGE)
+    }
+
+    /// Text for a message key in the current language
+    pub fn tr(&self, key: &str) -> String {
+        let messages = self.messages.get_or_init(|| {
+            self.sources.iter().map(|(code, source)| (*code, parse_catalogue(source))).collect()
+        });
+        messages.get(self.current_language())
+            .and_then(|catalogue| catalogue.get(key))
+            .or_else(|| messages.get(FALLBACK_LANGUAGE).and_then(|catalogue| catalogue.get(key)))
+            .cloned()
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    /// Text for a message key with its { $name } placeables filled in
+    pub fn tr_args(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let mut text = self.tr(key);
+        for (name, value) in args {
+            text = text.replace(&format!("{{ ${} }}", name), value);
+        }
+        text
+    }
+}
+
+/// The messages of one .ftl catalogue by key
+pub fn parse_catalogue(source: &str) -> HashMap<String, String> {
+    let mut messages: HashMap<String, String> = HashMap::new();
+    let mut current_key: Option<String> = None;
+    // blank lines only belong to a message when an indented line follows them
+    let mut blank_lines = 0;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        // an indented line continues the previous message
+        if line.starts_with(char::is_whitespace) {
+            if let Some(key) = &current_key {
+                if let Some(value) = messages.get_mut(key) {
+                    // a message can also start on the line after its key
+                    if !value.is_empty() {
+                        value.push_str(&"\n".repeat(blank_lines + 1));
+                    }
+                    value.push_str(trimmed);
+                }
+            }
+            blank_lines = 0;
+            continue;
+        }
+
+        blank_lines = 0;
+        if trimmed.starts_with('#') {
+            current_key = None;
+            continue;
+        }
+
+        if let Some((key, value)) = trimmed.split_once('=') {
+            let key = key.trim().to_string();
+            messages.insert(key.clone(), value.trim().to_string());
+            current_key = Some(key);
+        }
+    }
+
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static TEST_CATALOGUES: Catalogues = Catalogues::new(&[
+        ("en", "# English\ngreeting = Hello { $name }\nfarewell = Bye\nhelp =\n    First line\n\n    after a blank line\n"),
+        ("es", "greeting = Hola { $name }\n"),
+    ]);
+
+    #[test]
+    fn parses_continued_messages() {
+        let messages = parse_catalogue("about = One\n    two\n\n    three\n\n# next\nkey = value = with equals\n");
+        assert_eq!(messages["about"], "One\ntwo\n\nthree");
+        assert_eq!(messages["key"], "value = with equals");
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn falls_back_to_english_then_the_key() {
+        assert_eq!(TEST_CATALOGUES.resolve_language("es_ES.UTF-8"), "es");
+        assert_eq!(TEST_CATALOGUES.resolve_language("de"), FALLBACK_LANGUAGE);
+
+        TEST_CATALOGUES.set_language("es");
+        assert_eq!(TEST_CATALOGUES.tr_args("greeting", &[("name", "Ana")]), "Hola Ana");
+        assert_eq!(TEST_CATALOGUES.tr("farewell"), "Bye");
+        assert_eq!(TEST_CATALOGUES.tr("help"), "First line\n\nafter a blank line");
+        assert_eq!(TEST_CATALOGUES.tr("missing-key"), "missing-key");
+        TEST_CATALOGUES.set_language("en");
+        assert_eq!(TEST_CATALOGUES.current_language(), "en");
+    }
+}
@@ -0,0 +1,63 @@
+//! Interpretation of reader format codes and UID lengths.
+
+/// Interpret the built-in format indicators a wedge reader appends to its output
+pub fn interpret_format_code(data: &str) -> Option<&'static str> {
+    if data.contains(" e") || data.contains("-e") {
+        Some("QWERTY keyboard layout")
+    } else if data.contains(" f") || data.contains("-f") {
+        Some("AZERTY keyboard layout")
+    } else if data.contains(" h") || data.contains("-h") {
+        Some("QUERTY keyboard layout")
+    } else if data.contains(" r") || data.contains("-r") {
+        Some("Reader-specific format")
+    } else if data.contains("format description") {
+        Some("Format description command")
+    } else if data.contains("data format") {
+        Some("Data format specification")
+    } else if data.contains("disable buzzer") {
+        Some("Reader configuration command")
+    } else {
+        None
+    }
+}
+
+/// Guess the card family from the length of a hex UID
+pub fn identify_card_type(hex_uid: &str) -> String {
+    if hex_uid.is_empty() || hex_uid.contains("Invalid") {
+        return "Unknown card type".to_string();
+    }
+
+    let len = hex_uid.replace(' ', "").len();
+
+    match len {
+        8 => "MIFARE Classic (4 byte UID)".to_string(),
+        14 => "MIFARE Classic (7 byte UID)".to_string(),
+        16 => "MIFARE DESFire (8 byte UID)".to_string(),
+        20 => "MIFARE Plus (10 byte UID)".to_string(),
+        4 => "Partial read/Single block ID".to_string(),
+        _ if len < 8 => "Partial/Incomplete UID".to_string(),
+        _ => format!("Non-standard card ({} byte UID)", len / 2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_format_codes() {
+        assert_eq!(interpret_format_code("04a1-e"), Some("QWERTY keyboard layout"));
+        assert_eq!(interpret_format_code("04a1 f"), Some("AZERTY keyboard layout"));
+        assert_eq!(interpret_format_code("disable buzzer"), Some("Reader configuration command"));
+        assert_eq!(interpret_format_code("04a1b2c3"), None);
+    }
+
+    #[test]
+    fn card_type_from_length() {
+        assert_eq!(identify_card_type("04 A1 B2 C3"), "MIFARE Classic (4 byte UID)");
+        assert_eq!(identify_card_type("04A1B2C3D4E5F6"), "MIFARE Classic (7 byte UID)");
+        assert_eq!(identify_card_type("04A1"), "Partial read/Single block ID");
+        assert_eq!(identify_card_type(""), "Unknown card type");
+        assert_eq!(identify_card_type("04A1B2C3D4E5F60708090A0B"), "Non-standard card (12 byte UID)");
+    }
+}
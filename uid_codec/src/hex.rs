@@ -0,0 +1,115 @@
+//! Hex and decimal formatting for UIDs and block data.
+
+/// Format bytes as space separated hex, e.g. `04 A1 B2 C3`
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Format a card UID as colon separated hex, e.g. `04:A1:B2:C3`
+pub fn format_uid(uid: &[u8]) -> String {
+    uid.iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
+/// Format bytes as ASCII, replacing non-printable characters with dots
+pub fn bytes_to_ascii(bytes: &[u8]) -> String {
+    bytes.iter()
+        .map(|&byte| if (32..=126).contains(&byte) { byte as char } else { '.' })
+        .collect()
+}
+
+/// Convert a UID to a single number, first byte most significant
+pub fn uid_to_num(uid: &[u8]) -> u64 {
+    uid.iter().fold(0u64, |num, &byte| num.wrapping_mul(256).wrapping_add(byte as u64))
+}
+
+/// Group a string of hex digits into uppercase byte pairs, e.g. `04a1b2` -> `04 A1 B2`
+pub fn format_hex_uid(hex_uid: &str) -> String {
+    let mut formatted = String::with_capacity(hex_uid.len() * 3 / 2);
+
+    for (i, c) in hex_uid.chars().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            formatted.push(' ');
+        }
+        formatted.push(c);
+    }
+
+    formatted.to_uppercase()
+}
+
+/// Convert a hex UID (spaces allowed) to its decimal value
+pub fn hex_to_decimal(hex: &str) -> Option<u64> {
+    let clean_hex = hex.replace(' ', "");
+    u64::from_str_radix(&clean_hex, 16).ok()
+}
+
+/// Parse a hex string strictly, allowing only whitespace and `:` between digits
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let hex: String = hex.chars().filter(|c| !c.is_whitespace() && *c != ':').collect();
+    if !hex.len().is_multiple_of(2) {
+        return Err("Invalid hex string length".to_string());
+    }
+
+    let mut result = Vec::with_capacity(hex.len() / 2);
+    for i in (0..hex.len()).step_by(2) {
+        let byte = hex.get(i..i + 2)
+            .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+            .ok_or_else(|| "Invalid hex character".to_string())?;
+        result.push(byte);
+    }
+
+    Ok(result)
+}
+
+/// Parse a hex string leniently, ignoring every non-hex character
+pub fn parse_hex_lenient(hex: &str) -> Option<Vec<u8>> {
+    let cleaned: String = hex.chars().filter(|c| c.is_ascii_hexdigit()).collect();
+    if !cleaned.len().is_multiple_of(2) {
+        return None;
+    }
+
+    parse_hex(&cleaned).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_bytes() {
+        let uid = [0x04, 0xA1, 0xB2, 0xC3];
+        assert_eq!(bytes_to_hex(&uid), "04 A1 B2 C3");
+        assert_eq!(format_uid(&uid), "04:A1:B2:C3");
+        assert_eq!(bytes_to_ascii(b"Hi\x00\x7F~"), "Hi..~");
+        assert_eq!(uid_to_num(&uid), 0x04A1B2C3);
+    }
+
+    #[test]
+    fn groups_hex_digits() {
+        assert_eq!(format_hex_uid("04a1b2c3"), "04 A1 B2 C3");
+        assert_eq!(format_hex_uid("abc"), "AB C");
+        assert_eq!(format_hex_uid(""), "");
+    }
+
+    #[test]
+    fn converts_to_decimal() {
+        assert_eq!(hex_to_decimal("04 A1"), Some(0x04A1));
+        assert_eq!(hex_to_decimal("zz"), None);
+        assert_eq!(hex_to_decimal("FF FF FF FF FF FF FF FF FF"), None);
+    }
+
+    #[test]
+    fn parses_hex() {
+        assert_eq!(parse_hex("04:a1 B2"), Ok(vec![0x04, 0xA1, 0xB2]));
+        assert!(parse_hex("04A").is_err());
+        assert!(parse_hex("0G").is_err());
+        assert!(parse_hex("é1").is_err());
+        assert_eq!(parse_hex_lenient("04-A1-B2"), Some(vec![0x04, 0xA1, 0xB2]));
+        assert_eq!(parse_hex_lenient("04A"), None);
+    }
+}
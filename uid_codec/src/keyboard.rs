@@ -0,0 +1,309 @@
+//! Decoding of UIDs typed by keyboard wedge readers under different host layouts.
+
+/// Host keyboard layout the reader's keystrokes were interpreted with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardLayout {
+    /// Guess the layout from the characters received
+    Auto,
+    Windows,
+    MacUs,
+    MacInternational,
+    /// French AZERTY host receiving a US scancode reader
+    Azerty,
+    /// German QWERTZ host receiving a US scancode reader
+    Qwertz,
+}
+
+impl KeyboardLayout {
+    /// Map the index used by the layout menus and config (0 = auto-detect)
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => KeyboardLayout::Windows,
+            2 => KeyboardLayout::MacUs,
+            3 => KeyboardLayout::MacInternational,
+            4 => KeyboardLayout::Azerty,
+            5 => KeyboardLayout::Qwertz,
+            _ => KeyboardLayout::Auto,
+        }
+    }
+
+    pub fn index(&self) -> i32 {
+        match self {
+            KeyboardLayout::Auto => 0,
+            KeyboardLayout::Windows => 1,
+            KeyboardLayout::MacUs => 2,
+            KeyboardLayout::MacInternational => 3,
+            KeyboardLayout::Azerty => 4,
+            KeyboardLayout::Qwertz => 5,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyboardLayout::Auto => "Auto-detect",
+            KeyboardLayout::Windows => "Windows",
+            KeyboardLayout::MacUs => "Mac US",
+            KeyboardLayout::MacInternational => "Mac International",
+            KeyboardLayout::Azerty => "French AZERTY",
+            KeyboardLayout::Qwertz => "German QWERTZ",
+        }
+    }
+
+    /// Guess the layout from the special characters in the data.
+    /// Characters only one layout produces are checked first: the accented
+    /// AZERTY number row, then the QWERTZ shifted symbols, then US Windows.
+    pub fn detect(data: &str) -> Self {
+        if data.contains(|c| AZERTY_MARKERS.contains(&c)) {
+            KeyboardLayout::Azerty
+        } else if data.contains(|c| QWERTZ_MARKERS.contains(&c)) {
+            KeyboardLayout::Qwertz
+        } else if data.contains('@') || data.contains('!') || data.contains('^') {
+            KeyboardLayout::Windows
+        } else if data.contains('§') || data.contains('±') {
+            KeyboardLayout::MacInternational
+        } else {
+            KeyboardLayout::MacUs
+        }
+    }
+
+    /// Undo the layout's character mapping, keeping format indicators
+    pub fn decode(&self, encoded: &str) -> String {
+        match self {
+            KeyboardLayout::Auto => KeyboardLayout::detect(encoded).decode(encoded),
+            KeyboardLayout::Windows => decode_windows_format(encoded),
+            KeyboardLayout::MacUs => decode_mac_us_format(encoded),
+            KeyboardLayout::MacInternational => decode_mac_intl_format(encoded),
+            KeyboardLayout::Azerty => decode_azerty_format(encoded),
+            KeyboardLayout::Qwertz => decode_qwertz_format(encoded),
+        }
+    }
+}
+
+/// Characters that only show up when an AZERTY host types the number row
+const AZERTY_MARKERS: [char; 6] = ['é', 'è', 'ç', 'à', '_', '\''];
+
+/// Shifted number row symbols a US keyboard can't produce
+const QWERTZ_MARKERS: [char; 3] = ['"', '/', '='];
+
+/// Layouts offered in the menus, in index order
+pub const ALL_LAYOUTS: [KeyboardLayout; 6] = [
+    KeyboardLayout::Auto,
+    KeyboardLayout::Windows,
+    KeyboardLayout::MacUs,
+    KeyboardLayout::MacInternational,
+    KeyboardLayout::Azerty,
+    KeyboardLayout::Qwertz,
+];
+
+/// Decode raw wedge input to just its hex digits
+pub fn decode_uid(raw: &str, layout: KeyboardLayout) -> String {
+    layout.decode(raw).chars().filter(|c| c.is_ascii_hexdigit()).collect()
+}
+
+/// Layouts that turn a scan of a reference card back into its known UID.
+/// Used by calibration so the decoder is chosen from a real scan instead
+/// of guessed from whichever special characters happen to appear.
+pub fn calibrate(raw: &str, expected_hex: &str) -> Vec<KeyboardLayout> {
+    let expected: String = expected_hex.chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_uppercase();
+    if expected.is_empty() {
+        return Vec::new();
+    }
+
+    ALL_LAYOUTS.iter()
+        .filter(|layout| **layout != KeyboardLayout::Auto)
+        .filter(|layout| decode_uid(raw, **layout).to_uppercase() == expected)
+        .copied()
+        .collect()
+}
+
+/// Shift+digit symbols shared by every layout
+fn shifted_digit(c: char) -> Option<char> {
+    match c {
+        '!' => Some('1'),
+        '@' => Some('2'),
+        '#' => Some('3'),
+        '$' => Some('4'),
+        '%' => Some('5'),
+        '^' => Some('6'),
+        '&' => Some('7'),
+        '*' => Some('8'),
+        '(' => Some('9'),
+        ')' => Some('0'),
+        _ => None,
+    }
+}
+
+/// Option+digit symbols produced by Mac layouts
+fn mac_option_digit(c: char) -> Option<char> {
+    match c {
+        '¡' => Some('1'),
+        '™' => Some('2'),
+        '£' => Some('3'),
+        '¢' => Some('4'),
+        '∞' => Some('5'),
+        '§' => Some('6'),
+        '¶' => Some('7'),
+        '•' => Some('8'),
+        'ª' => Some('9'),
+        'º' => Some('0'),
+        _ => None,
+    }
+}
+
+/// Accented and symbol characters produced by the Mac International layout
+fn mac_intl_symbol(c: char) -> Option<char> {
+    match c {
+        '±' | '≠' => Some('='),
+        '€' => Some('e'),
+        'ä' | 'á' | 'à' => Some('a'),
+        'é' | 'è' => Some('e'),
+        'í' | 'ì' => Some('i'),
+        'ó' | 'ò' => Some('o'),
+        'ú' | 'ù' => Some('u'),
+        _ => None,
+    }
+}
+
+/// Characters kept as-is: hex digits plus the format indicator letters
+fn passthrough(c: char) -> Option<char> {
+    match c {
+        'h' | 'd' | 'e' | 'r' | '-' | ' ' => Some(c),
+        c if c.is_ascii_hexdigit() => Some(c),
+        _ => None,
+    }
+}
+
+/// Handle standard/Windows keyboard mapping
+pub fn decode_windows_format(encoded_str: &str) -> String {
+    encoded_str.chars()
+        .filter_map(|c| shifted_digit(c).or_else(|| passthrough(c)))
+        .collect()
+}
+
+/// Handle Mac US keyboard mapping
+pub fn decode_mac_us_format(encoded_str: &str) -> String {
+    encoded_str.chars()
+        .filter_map(|c| shifted_digit(c).or_else(|| mac_option_digit(c)).or_else(|| passthrough(c)))
+        .collect()
+}
+
+/// Handle Mac International keyboard mapping
+pub fn decode_mac_intl_format(encoded_str: &str) -> String {
+    encoded_str.chars()
+        .filter_map(|c| {
+            shifted_digit(c)
+                .or_else(|| mac_option_digit(c))
+                .or_else(|| mac_intl_symbol(c))
+                .or_else(|| passthrough(c))
+        })
+        .collect()
+}
+
+/// Handle French AZERTY mapping. The number row types symbols unless
+/// shifted, and the US `A` key position is `Q`.
+pub fn decode_azerty_format(encoded_str: &str) -> String {
+    encoded_str.chars()
+        .filter_map(|c| match c {
+            '&' => Some('1'),
+            'é' => Some('2'),
+            '"' => Some('3'),
+            '\'' => Some('4'),
+            '(' => Some('5'),
+            '-' => Some('6'),
+            'è' => Some('7'),
+            '_' => Some('8'),
+            'ç' => Some('9'),
+            'à' => Some('0'),
+            'q' => Some('a'),
+            'Q' => Some('A'),
+            _ => passthrough(c),
+        })
+        .collect()
+}
+
+/// Handle German QWERTZ mapping. Digits come through as-is, the shifted
+/// number row differs from US from `"` onwards.
+pub fn decode_qwertz_format(encoded_str: &str) -> String {
+    encoded_str.chars()
+        .filter_map(|c| match c {
+            '!' => Some('1'),
+            '"' => Some('2'),
+            '§' => Some('3'),
+            '$' => Some('4'),
+            '%' => Some('5'),
+            '&' => Some('6'),
+            '/' => Some('7'),
+            '(' => Some('8'),
+            ')' => Some('9'),
+            '=' => Some('0'),
+            _ => passthrough(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_indices_round_trip() {
+        for index in 0..6 {
+            assert_eq!(KeyboardLayout::from_index(index).index(), index);
+        }
+        assert_eq!(KeyboardLayout::from_index(42), KeyboardLayout::Auto);
+    }
+
+    #[test]
+    fn decodes_windows_symbols() {
+        assert_eq!(decode_windows_format(")$A!B@C#"), "04A1B2C3");
+        assert_eq!(decode_uid(")$A!B@C#", KeyboardLayout::Windows), "04A1B2C3");
+    }
+
+    #[test]
+    fn decodes_mac_symbols() {
+        assert_eq!(decode_uid("º¢A¡B™C£", KeyboardLayout::MacUs), "04A1B2C3");
+        assert_eq!(decode_mac_intl_format("€±"), "e=");
+    }
+
+    #[test]
+    fn auto_detect_picks_layout() {
+        assert_eq!(KeyboardLayout::detect("!@"), KeyboardLayout::Windows);
+        assert_eq!(KeyboardLayout::detect("§1"), KeyboardLayout::MacInternational);
+        assert_eq!(KeyboardLayout::detect("04a1"), KeyboardLayout::MacUs);
+        assert_eq!(decode_uid(")$a!", KeyboardLayout::Auto), "04a1");
+    }
+
+    #[test]
+    fn decodes_azerty_number_row() {
+        assert_eq!(decode_azerty_format("àé'q&bçè"), "024a1b97");
+        assert_eq!(decode_uid("à'Q&B\"C_", KeyboardLayout::Azerty), "04A1B3C8");
+        assert_eq!(KeyboardLayout::detect("à'q&"), KeyboardLayout::Azerty);
+    }
+
+    #[test]
+    fn decodes_qwertz_symbols() {
+        assert_eq!(decode_uid("=$A!B\"C§", KeyboardLayout::Qwertz), "04A1B2C3");
+        assert_eq!(decode_qwertz_format("/()&"), "7896");
+        assert_eq!(KeyboardLayout::detect("=$A!"), KeyboardLayout::Qwertz);
+        assert_eq!(KeyboardLayout::detect(")$A!B@"), KeyboardLayout::Windows);
+    }
+
+    #[test]
+    fn calibration_finds_matching_layouts() {
+        assert_eq!(calibrate("à'q&bçè", "04 A1 B9 7"), vec![KeyboardLayout::Azerty]);
+        assert_eq!(calibrate("=$A!B\"C§", "04:a1:b2:c3"), vec![KeyboardLayout::Qwertz]);
+        assert_eq!(calibrate(")$A!B@C#", "04A1B2C3"), vec![KeyboardLayout::Windows, KeyboardLayout::MacUs, KeyboardLayout::MacInternational]);
+        assert_eq!(calibrate("04A1B2C3", "04A1B2C3").len(), 5);
+        assert!(calibrate(")$A!", "FFFF").is_empty());
+        assert!(calibrate(")$A!", "").is_empty());
+    }
+
+    #[test]
+    fn keeps_format_indicators() {
+        assert_eq!(decode_windows_format("*h-e"), "8h-e");
+        assert_eq!(decode_windows_format("xyz"), "");
+    }
+}
@@ -0,0 +1,16 @@
+//! Shared UID decoding used by the reader, block editor, attack and RFID toolkit apps.
+//!
+//! Keyboard wedge readers type the UID as if it were keyboard input, so depending on
+//! the host layout the hex digits may arrive as shifted symbols. This crate turns those
+//! strings (or raw UID bytes) back into hex, decimal and manufacturer information.
+
+pub mod format;
+pub mod hex;
+pub mod keyboard;
+pub mod manufacturer;
+
+pub use format::{identify_card_type, interpret_format_code};
+pub use hex::{bytes_to_ascii, bytes_to_hex, format_hex_uid, format_uid, hex_to_decimal,
+              parse_hex, parse_hex_lenient, uid_to_num};
+pub use keyboard::{calibrate, decode_uid, KeyboardLayout, ALL_LAYOUTS};
+pub use manufacturer::lookup_manufacturer;
@@ -0,0 +1,61 @@
+//! IC manufacturer lookup from the first UID byte (ISO/IEC 7816-6 codes).
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Built-in manufacturer list in `code,name` CSV form
+pub const BUILTIN_CSV: &str = include_str!("../data/manufacturers.csv");
+
+/// Parse `code,name` lines, skipping `#` comments and malformed codes.
+/// Codes are normalised to uppercase.
+pub fn parse_csv(data: &str) -> HashMap<String, String> {
+    let mut table = HashMap::new();
+
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((code, name)) = line.split_once(',') {
+            let code = code.trim().to_uppercase();
+            if code.len() == 2 && code.chars().all(|c| c.is_ascii_hexdigit()) {
+                table.insert(code, name.trim().to_string());
+            }
+        }
+    }
+
+    table
+}
+
+/// The built-in table, parsed once
+pub fn builtin_table() -> &'static HashMap<String, String> {
+    static TABLE: OnceLock<HashMap<String, String>> = OnceLock::new();
+    TABLE.get_or_init(|| parse_csv(BUILTIN_CSV))
+}
+
+/// Look up the manufacturer for the first byte of a hex UID string
+pub fn lookup_manufacturer(hex_uid: &str) -> Option<&'static str> {
+    let code = hex_uid.get(0..2)?.to_uppercase();
+    builtin_table().get(&code).map(|name| name.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_list_has_common_vendors() {
+        assert_eq!(lookup_manufacturer("04A1B2C3"), Some("NXP Semiconductors Germany"));
+        assert_eq!(lookup_manufacturer("05"), Some("Infineon Technologies AG Germany"));
+        assert_eq!(lookup_manufacturer("ff00"), None);
+        assert_eq!(lookup_manufacturer("0"), None);
+    }
+
+    #[test]
+    fn parses_csv_lines() {
+        let table = parse_csv("# comment\n0a, Vendor A \nzz,Bad\n123,Bad\n\nno comma\n");
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get("0A").map(|s| s.as_str()), Some("Vendor A"));
+    }
+}